@@ -1,10 +1,13 @@
 #![no_std]
 #![allow(clippy::too_many_arguments)]
 
+use boards_shared::{
+    char_floor, copy_string_prefix, escape_attr, is_hex_color, is_valid_address, sanitize_color,
+};
 use soroban_render_sdk::prelude::*;
 use soroban_sdk::{
-    contract, contractimpl, contracttype, Address, Bytes, BytesN, Env, IntoVal, String, Symbol,
-    Val, Vec,
+    contract, contractimpl, contracttype, symbol_short, Address, Bytes, BytesN, Env, IntoVal, Map,
+    String, Symbol, Val, Vec,
 };
 
 // Declare render capabilities
@@ -24,6 +27,130 @@ pub enum AdminKey {
     Theme,
     /// Config contract address (for site-wide settings)
     Config,
+    /// Number of moderation log entries for a board
+    ModLogCount(u64),
+    /// Chunk of moderation log entries (board_id, chunk_index)
+    ModLogChunk(u64, u64),
+    /// Next moderator note id for a user (board_id, user)
+    UserNoteCount(u64, Address),
+    /// Chunk of moderator notes about a user (board_id, user, chunk_index)
+    UserNoteChunk(u64, Address, u32),
+    /// Position the last "Hide All Posts" run for a user reached (board_id, user) -> u32
+    HideAllCursor(u64, Address),
+}
+
+/// Number of moderation log entries stored per chunk
+const MOD_LOG_CHUNK_SIZE: u64 = 50;
+
+/// Longest reason (in bytes) carried in a moderation event
+const MOD_EVENT_REASON_LEN: usize = 64;
+
+/// Maximum number of items a bulk moderation call may touch
+const MAX_BULK_ITEMS: u32 = 25;
+
+/// Number of moderator notes stored per chunk
+const USER_NOTE_CHUNK_SIZE: u32 = 20;
+
+/// Maximum length of a single moderator note
+const MAX_USER_NOTE_LEN: u32 = 500;
+
+/// Number of content bytes shown in a flag queue preview
+const FLAG_PREVIEW_LEN: u32 = 200;
+
+/// Maximum number of flag reasons listed per item in the flag queue
+const MAX_FLAG_REASONS_SHOWN: u32 = 10;
+
+/// Number of members listed per page on the members page
+const MEMBERS_PER_PAGE: u32 = 50;
+
+/// Number of global bans listed on the registry bans page
+const MAX_GLOBAL_BANS_SHOWN: u32 = 100;
+
+/// Reactions the voting contract accepts in a board's allowlist
+const REACTION_NAMES: [&str; 8] = [
+    "thumbsup", "tada", "heart", "eyes", "laugh", "rocket", "thinking", "sad",
+];
+
+/// Longest reaction list accepted from the voting page form
+const MAX_REACTION_LIST_LEN: usize = 128;
+
+/// Longest image host list accepted from the settings form
+const MAX_IMAGE_HOST_LIST_LEN: usize = 1344;
+
+/// Locales the board contract accepts, with the names shown on the settings page
+const BOARD_LOCALES: [(&str, &str); 16] = [
+    ("en", "English"),
+    ("cs", "Czech"),
+    ("de", "German"),
+    ("es", "Spanish"),
+    ("fi", "Finnish"),
+    ("fr", "French"),
+    ("it", "Italian"),
+    ("ja", "Japanese"),
+    ("ko", "Korean"),
+    ("nb", "Norwegian"),
+    ("nl", "Dutch"),
+    ("pl", "Polish"),
+    ("pt", "Portuguese"),
+    ("ru", "Russian"),
+    ("tr", "Turkish"),
+    ("zh", "Chinese"),
+];
+
+/// Longest stored string echoed back into an HTML attribute
+const MAX_ATTR_LEN: usize = 128;
+
+/// Markup between breadcrumb segments (matches the board contract)
+const BREADCRUMB_SEPARATOR: &str = "<span class=\"crumb-sep\">›</span>";
+
+/// Registry aliases that can be upgraded from the upgrades page. The admin
+/// contract is left out: it cannot be re-entered to upgrade itself.
+const UPGRADEABLE_ALIASES: [&str; 10] = [
+    "main",
+    "theme",
+    "perms",
+    "content",
+    "community",
+    "config",
+    "pages",
+    "board",
+    "voting",
+    "profile",
+];
+
+/// Moderator note attached to a user, visible only to the mod team
+#[contracttype]
+#[derive(Clone)]
+pub struct UserNote {
+    pub id: u32,
+    pub author: Address,
+    pub text: String,
+    pub timestamp: u64,
+}
+
+/// Moderation log entry recorded by the admin contract
+#[contracttype]
+#[derive(Clone)]
+pub struct ModAction {
+    pub actor: Address,
+    pub board_id: u64,
+    pub action: Symbol,
+    pub target_thread: Option<u64>,
+    pub target_reply: Option<u64>,
+    pub target_user: Option<Address>,
+    pub timestamp: u64,
+    pub note: Option<String>,
+}
+
+/// What a moderation event acted on
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum ModTarget {
+    Board,
+    Thread(u64),
+    /// (thread_id, reply_id)
+    Reply(u64, u64),
+    User(Address),
 }
 
 // ============================================================================
@@ -42,6 +169,41 @@ pub struct BoardConfig {
     pub reply_chunk_size: u32,
 }
 
+/// Thread metadata from board contract
+#[contracttype]
+#[derive(Clone)]
+pub struct ThreadMeta {
+    pub id: u64,
+    pub board_id: u64,
+    pub title: String,
+    pub creator: Address,
+    pub created_at: u64,
+    pub updated_at: u64,
+    pub reply_count: u32,
+    pub is_locked: bool,
+    pub is_pinned: bool,
+    pub is_hidden: bool,
+    pub is_deleted: bool,
+    pub flair_id: Option<u32>,
+}
+
+/// Reply metadata from content contract
+#[contracttype]
+#[derive(Clone)]
+pub struct ReplyMeta {
+    pub id: u64,
+    pub board_id: u64,
+    pub thread_id: u64,
+    pub parent_id: u64,
+    pub depth: u32,
+    pub creator: Address,
+    pub created_at: u64,
+    pub updated_at: u64,
+    pub is_hidden: bool,
+    pub is_deleted: bool,
+    pub flag_count: u32,
+}
+
 /// Ban record from permissions contract
 #[contracttype]
 #[derive(Clone)]
@@ -66,6 +228,18 @@ pub enum Role {
     Owner = 4,
 }
 
+/// Thread list ordering from board contract
+#[contracttype]
+#[derive(Clone, Copy, PartialEq, Eq)]
+#[repr(u32)]
+pub enum ThreadSort {
+    Hot = 0,
+    New = 1,
+    Top = 2,
+    Active = 3,
+    Controversial = 4,
+}
+
 /// Type of flagged content
 #[contracttype]
 #[derive(Clone, Copy, PartialEq, Eq)]
@@ -87,6 +261,39 @@ pub struct FlaggedItem {
     pub first_flagged_at: u64,
 }
 
+/// Crosspost reference from content contract
+#[contracttype]
+#[derive(Clone)]
+pub struct CrosspostRef {
+    pub original_board_id: u64,
+    pub original_thread_id: u64,
+    pub original_title: String,
+    pub original_author: Address,
+    pub crossposted_by: Address,
+    pub crossposted_at: u64,
+}
+
+/// Flag reason from content contract
+#[contracttype]
+#[derive(Clone, Copy, PartialEq, Eq)]
+#[repr(u32)]
+pub enum FlagReason {
+    Spam = 0,
+    Abuse = 1,
+    OffTopic = 2,
+    Other = 3,
+}
+
+/// Open flag summary from content contract
+#[contracttype]
+#[derive(Clone)]
+pub struct FlagRecord {
+    pub flagger: Address,
+    pub reason: FlagReason,
+    pub details: String,
+    pub created_at: u64,
+}
+
 /// Permission set from permissions contract
 #[contracttype]
 #[derive(Clone)]
@@ -121,6 +328,23 @@ pub struct FlairDef {
     pub enabled: bool,
 }
 
+/// Thread template from board contract
+#[contracttype]
+#[derive(Clone)]
+pub struct ThreadTemplate {
+    pub id: u32,
+    pub name: String,
+    pub body: String,
+}
+
+/// Inline image rules from board contract (matches boards-board ImagePolicy)
+#[contracttype]
+#[derive(Clone)]
+pub struct ImagePolicy {
+    pub allow_images: bool,
+    pub allowed_hosts: Vec<String>,
+}
+
 /// Community info from board contract
 #[contracttype]
 #[derive(Clone)]
@@ -130,6 +354,41 @@ pub struct CommunityInfo {
     pub display_name: String,
 }
 
+/// Board category from the registry (matches boards-registry Category)
+#[contracttype]
+#[derive(Clone)]
+pub struct Category {
+    pub id: u32,
+    pub name: String,
+}
+
+/// Board metadata (matches boards-board BoardMeta)
+#[contracttype]
+#[derive(Clone)]
+pub struct BoardMeta {
+    pub id: u64,
+    pub slug: String,
+    pub name: String,
+    pub description: String,
+    pub creator: Address,
+    pub created_at: u64,
+    pub thread_count: u64,
+    pub is_readonly: bool,
+    pub is_private: bool,
+    pub is_listed: bool,
+}
+
+/// Network-wide ban from the registry (matches boards-registry GlobalBan)
+#[contracttype]
+#[derive(Clone)]
+pub struct GlobalBan {
+    pub user: Address,
+    pub issuer: Address,
+    pub reason: String,
+    pub created_at: u64,
+    pub expires_at: Option<u64>,
+}
+
 /// Voting configuration from voting contract
 #[contracttype]
 #[derive(Clone)]
@@ -227,6 +486,30 @@ impl BoardsAdmin {
         env.storage().instance().set(&AdminKey::Config, &config);
     }
 
+    /// Re-point sibling contract addresses after a redeploy (registry only).
+    /// Authorization comes from the currently stored registry.
+    pub fn set_contracts(
+        env: Env,
+        registry: Address,
+        permissions: Address,
+        content: Address,
+        theme: Address,
+    ) {
+        let current: Address = env
+            .storage()
+            .instance()
+            .get(&AdminKey::Registry)
+            .expect("Not initialized");
+        current.require_auth();
+
+        env.storage().instance().set(&AdminKey::Registry, &registry);
+        env.storage()
+            .instance()
+            .set(&AdminKey::Permissions, &permissions);
+        env.storage().instance().set(&AdminKey::Content, &content);
+        env.storage().instance().set(&AdminKey::Theme, &theme);
+    }
+
     /// Get registry address
     pub fn get_registry(env: Env) -> Address {
         env.storage()
@@ -280,6 +563,21 @@ impl BoardsAdmin {
         board_contract.expect("Board contract not registered")
     }
 
+    /// Get voting contract address from registry (None if not registered)
+    fn get_voting_contract_address(env: &Env) -> Option<Address> {
+        let registry: Address = env
+            .storage()
+            .instance()
+            .get(&AdminKey::Registry)
+            .expect("Not initialized");
+        let alias_args: Vec<Val> = Vec::from_array(env, [Symbol::new(env, "voting").into_val(env)]);
+        env.invoke_contract(
+            &registry,
+            &Symbol::new(env, "get_contract_by_alias"),
+            alias_args,
+        )
+    }
+
     /// Set config address (registry admin only)
     pub fn set_config(env: Env, config: Address, caller: Address) {
         caller.require_auth();
@@ -305,7 +603,12 @@ impl BoardsAdmin {
             // Board admin routes (without /admin prefix - for direct access)
             .handle(b"/b/{id}/members", |req| {
                 let board_id = req.get_var_u32(b"id").unwrap_or(0) as u64;
-                Self::render_members(&env, board_id, &viewer)
+                Self::render_members(&env, board_id, 1, &viewer)
+            })
+            .or_handle(b"/b/{id}/members/{page}", |req| {
+                let board_id = req.get_var_u32(b"id").unwrap_or(0) as u64;
+                let page = req.get_var_u32(b"page").unwrap_or(1);
+                Self::render_members(&env, board_id, page, &viewer)
             })
             .or_handle(b"/b/{id}/banned", |req| {
                 let board_id = req.get_var_u32(b"id").unwrap_or(0) as u64;
@@ -331,6 +634,10 @@ impl BoardsAdmin {
                 let board_id = req.get_var_u32(b"id").unwrap_or(0) as u64;
                 Self::render_flairs(&env, board_id, &viewer)
             })
+            .or_handle(b"/b/{id}/templates", |req| {
+                let board_id = req.get_var_u32(b"id").unwrap_or(0) as u64;
+                Self::render_templates(&env, board_id, &viewer)
+            })
             .or_handle(b"/b/{id}/rules", |req| {
                 let board_id = req.get_var_u32(b"id").unwrap_or(0) as u64;
                 Self::render_rules(&env, board_id, &viewer)
@@ -339,10 +646,24 @@ impl BoardsAdmin {
                 let board_id = req.get_var_u32(b"id").unwrap_or(0) as u64;
                 Self::render_voting(&env, board_id, &viewer)
             })
+            .or_handle(b"/b/{id}/log", |req| {
+                let board_id = req.get_var_u32(b"id").unwrap_or(0) as u64;
+                Self::render_mod_log(&env, board_id, &viewer)
+            })
+            .or_handle(b"/b/{id}/user/{address}", |req| {
+                let board_id = req.get_var_u32(b"id").unwrap_or(0) as u64;
+                let address = req.get_var(b"address").unwrap_or(Bytes::new(&env));
+                Self::render_user(&env, board_id, &address, &viewer)
+            })
             // Board admin routes (with /admin prefix - canonical URLs)
             .or_handle(b"/admin/b/{id}/members", |req| {
                 let board_id = req.get_var_u32(b"id").unwrap_or(0) as u64;
-                Self::render_members(&env, board_id, &viewer)
+                Self::render_members(&env, board_id, 1, &viewer)
+            })
+            .or_handle(b"/admin/b/{id}/members/{page}", |req| {
+                let board_id = req.get_var_u32(b"id").unwrap_or(0) as u64;
+                let page = req.get_var_u32(b"page").unwrap_or(1);
+                Self::render_members(&env, board_id, page, &viewer)
             })
             .or_handle(b"/admin/b/{id}/banned", |req| {
                 let board_id = req.get_var_u32(b"id").unwrap_or(0) as u64;
@@ -368,6 +689,10 @@ impl BoardsAdmin {
                 let board_id = req.get_var_u32(b"id").unwrap_or(0) as u64;
                 Self::render_flairs(&env, board_id, &viewer)
             })
+            .or_handle(b"/admin/b/{id}/templates", |req| {
+                let board_id = req.get_var_u32(b"id").unwrap_or(0) as u64;
+                Self::render_templates(&env, board_id, &viewer)
+            })
             .or_handle(b"/b/{id}/flairs/{flair_id}/edit", |req| {
                 let board_id = req.get_var_u32(b"id").unwrap_or(0) as u64;
                 let flair_id = req.get_var_u32(b"flair_id").unwrap_or(0);
@@ -386,11 +711,32 @@ impl BoardsAdmin {
                 let board_id = req.get_var_u32(b"id").unwrap_or(0) as u64;
                 Self::render_voting(&env, board_id, &viewer)
             })
+            .or_handle(b"/admin/b/{id}/log", |req| {
+                let board_id = req.get_var_u32(b"id").unwrap_or(0) as u64;
+                Self::render_mod_log(&env, board_id, &viewer)
+            })
+            .or_handle(b"/admin/b/{id}/user/{address}", |req| {
+                let board_id = req.get_var_u32(b"id").unwrap_or(0) as u64;
+                let address = req.get_var(b"address").unwrap_or(Bytes::new(&env));
+                Self::render_user(&env, board_id, &address, &viewer)
+            })
             // Registry admin routes
             .or_handle(b"/registry", |_| Self::render_registry_admin(&env, &viewer))
             .or_handle(b"/admin/registry", |_| {
                 Self::render_registry_admin(&env, &viewer)
             })
+            .or_handle(b"/registry/upgrades", |_| {
+                Self::render_upgrades(&env, &viewer)
+            })
+            .or_handle(b"/admin/registry/upgrades", |_| {
+                Self::render_upgrades(&env, &viewer)
+            })
+            .or_handle(b"/registry/bans", |_| {
+                Self::render_global_bans(&env, &viewer)
+            })
+            .or_handle(b"/admin/registry/bans", |_| {
+                Self::render_global_bans(&env, &viewer)
+            })
             // Site settings routes (global admin)
             .or_handle(b"/settings", |_| Self::render_site_settings(&env, &viewer))
             .or_handle(b"/admin/settings", |_| {
@@ -437,25 +783,80 @@ impl BoardsAdmin {
             .hr()
     }
 
-    /// Render the navigation bar for sub-pages (includes Back to Settings link)
-    fn render_nav_subpage(env: &Env, board_id: u64) -> MarkdownBuilder<'_> {
+    /// Render the navigation bar for sub-pages, then breadcrumbs and a heading for `title`
+    fn render_nav_subpage<'a>(env: &'a Env, board_id: u64, title: &str) -> MarkdownBuilder<'a> {
         let aliases = Self::fetch_aliases(env);
         let site_name_include = Self::config_include(env, b"site_name");
-        MarkdownBuilder::new(env)
+        let md = MarkdownBuilder::new(env)
             .raw(aliases) // Emit aliases for include resolution
             .raw_str("<a href=\"render:/\">")
             .raw(site_name_include)
             .raw_str("</a>")
-            .text(" | ")
-            .raw_str("[Back to Board](render:/b/")
+            .newline()
+            .hr();
+        Self::render_breadcrumbs(env, md, board_id, title).h1(title)
+    }
+
+    /// Render breadcrumbs: Home › Community › Board › Settings › current page.
+    /// Every segment links except the current page, which is plain text.
+    fn render_breadcrumbs<'a>(
+        env: &'a Env,
+        mut md: MarkdownBuilder<'a>,
+        board_id: u64,
+        current: &str,
+    ) -> MarkdownBuilder<'a> {
+        let board_contract = Self::get_board_contract_address(env);
+        md = md
+            .div_start("breadcrumbs")
+            .raw_str("<a href=\"render:/\">Home</a>");
+
+        let community: Option<CommunityInfo> = env
+            .try_invoke_contract::<Option<CommunityInfo>, soroban_sdk::Error>(
+                &board_contract,
+                &Symbol::new(env, "get_board_community_info"),
+                Vec::from_array(env, [board_id.into_val(env)]),
+            )
+            .ok()
+            .and_then(|r| r.ok())
+            .flatten();
+        if let Some(community) = community {
+            md = md
+                .raw_str(BREADCRUMB_SEPARATOR)
+                .raw_str("<a href=\"render:/c/")
+                .raw(Self::escape_attr(env, &community.name))
+                .raw_str("\">")
+                .raw(Self::escape_attr(env, &community.display_name))
+                .raw_str("</a>");
+        }
+
+        let config: Option<BoardConfig> = env
+            .try_invoke_contract::<BoardConfig, soroban_sdk::Error>(
+                &board_contract,
+                &Symbol::new(env, "get_config"),
+                Vec::from_array(env, [board_id.into_val(env)]),
+            )
+            .ok()
+            .and_then(|r| r.ok());
+        md = md
+            .raw_str(BREADCRUMB_SEPARATOR)
+            .raw_str("<a href=\"render:/b/")
             .number(board_id as u32)
-            .raw_str(")")
-            .text(" | ")
-            .raw_str("[Back to Settings](render:/admin/b/")
+            .raw_str("\">");
+        md = match config {
+            Some(config) => md.raw(Self::escape_attr(env, &config.name)),
+            None => md.raw_str("Board"),
+        };
+
+        md.raw_str("</a>")
+            .raw_str(BREADCRUMB_SEPARATOR)
+            .raw_str("<a href=\"render:/admin/b/")
             .number(board_id as u32)
-            .raw_str("/settings)")
-            .newline()
-            .hr()
+            .raw_str("/settings\">Settings</a>")
+            .raw_str(BREADCRUMB_SEPARATOR)
+            .raw_str("<span class=\"crumb-current\">")
+            .raw_str(current)
+            .raw_str("</span>")
+            .div_end()
     }
 
     /// Convert an Address to its contract ID string as Bytes
@@ -544,15 +945,147 @@ impl BoardsAdmin {
         addr.to_string()
     }
 
+    /// Format a Unix timestamp as a human-readable date string.
+    fn format_timestamp(env: &Env, timestamp: u64) -> Bytes {
+        // Handle legacy ledger sequence numbers (small values)
+        if timestamp < 1_000_000_000 {
+            let mut result = Bytes::from_slice(env, b"Ledger ");
+            result.append(&u64_to_bytes(env, timestamp));
+            return result;
+        }
+
+        let total_seconds = timestamp;
+        let total_minutes = total_seconds / 60;
+        let total_hours = total_minutes / 60;
+        let total_days = total_hours / 24;
+
+        let minutes = (total_minutes % 60) as u8;
+        let hours = (total_hours % 24) as u8;
+
+        let (year, month, day) = Self::days_to_date(total_days as i64);
+
+        // Format: "YYYY-MM-DD HH:MM UTC"
+        let mut buffer = [0u8; 20];
+
+        buffer[0] = b'0' + ((year / 1000) % 10) as u8;
+        buffer[1] = b'0' + ((year / 100) % 10) as u8;
+        buffer[2] = b'0' + ((year / 10) % 10) as u8;
+        buffer[3] = b'0' + (year % 10) as u8;
+        buffer[4] = b'-';
+        buffer[5] = b'0' + ((month / 10) % 10);
+        buffer[6] = b'0' + (month % 10);
+        buffer[7] = b'-';
+        buffer[8] = b'0' + ((day / 10) % 10);
+        buffer[9] = b'0' + (day % 10);
+        buffer[10] = b' ';
+        buffer[11] = b'0' + ((hours / 10) % 10);
+        buffer[12] = b'0' + (hours % 10);
+        buffer[13] = b':';
+        buffer[14] = b'0' + ((minutes / 10) % 10);
+        buffer[15] = b'0' + (minutes % 10);
+        buffer[16] = b' ';
+        buffer[17] = b'U';
+        buffer[18] = b'T';
+        buffer[19] = b'C';
+
+        Bytes::from_slice(env, &buffer)
+    }
+
+    /// Convert days since Unix epoch to (year, month, day).
+    fn days_to_date(days: i64) -> (i32, u8, u8) {
+        let z = days + 719468;
+        let era = if z >= 0 { z } else { z - 146096 } / 146097;
+        let doe = (z - era * 146097) as u32;
+        let yoe = (doe - doe / 1460 + doe / 36524 - doe / 146096) / 365;
+        let y = yoe as i64 + era * 400;
+        let doy = doe - (365 * yoe + yoe / 4 - yoe / 100);
+        let mp = (5 * doy + 2) / 153;
+        let d = doy - (153 * mp + 2) / 5 + 1;
+        let m = if mp < 10 { mp + 3 } else { mp - 9 };
+        let year = if m <= 2 { y + 1 } else { y };
+
+        (year as i32, m as u8, d as u8)
+    }
+
+    /// Build a single-line preview of post content for the flag queue
+    /// (also used for invite request messages).
+    /// Cuts at FLAG_PREVIEW_LEN bytes without splitting a UTF-8 character,
+    /// folds line breaks into spaces and escapes `<` so the preview stays inside its quote.
+    fn content_preview(env: &Env, body: &Bytes) -> Bytes {
+        let end = char_floor(body, FLAG_PREVIEW_LEN);
+
+        let mut preview = Bytes::new(env);
+        for i in 0..end {
+            match body.get(i).unwrap() {
+                b'\n' | b'\r' => preview.push_back(b' '),
+                b'<' => preview.append(&Bytes::from_slice(env, b"&lt;")),
+                byte => preview.push_back(byte),
+            }
+        }
+        if end < body.len() {
+            preview.append(&Bytes::from_slice(env, b"..."));
+        }
+        preview
+    }
+
+    /// Clamp a 1-based page number to the available pages.
+    /// Returns (page, start index, total pages); an empty list still has one page.
+    fn page_bounds(total: u32, page: u32, per_page: u32) -> (u32, u32, u32) {
+        let total_pages = total.div_ceil(per_page).max(1);
+        let page = page.clamp(1, total_pages);
+        (page, (page - 1) * per_page, total_pages)
+    }
+
+    /// Attribute escaping with the admin pages' shorter length cap
+    fn escape_attr(env: &Env, value: &String) -> Bytes {
+        escape_attr(env, value, MAX_ATTR_LEN)
+    }
+
+    /// Display label for a flag reason
+    fn flag_reason_label(reason: FlagReason) -> &'static str {
+        match reason {
+            FlagReason::Spam => "Spam",
+            FlagReason::Abuse => "Abuse",
+            FlagReason::OffTopic => "Off-topic",
+            FlagReason::Other => "Other",
+        }
+    }
+
+    /// Format a count with thousands separators (e.g. 1,204)
+    fn format_count(env: &Env, n: u32) -> Bytes {
+        let mut digits = [0u8; 10];
+        let mut len = 0;
+        let mut rest = n;
+        loop {
+            digits[len] = b'0' + (rest % 10) as u8;
+            len += 1;
+            rest /= 10;
+            if rest == 0 {
+                break;
+            }
+        }
+
+        let mut result = Bytes::new(env);
+        for i in (0..len).rev() {
+            result.push_back(digits[i]);
+            if i > 0 && i % 3 == 0 {
+                result.push_back(b',');
+            }
+        }
+        result
+    }
+
     /// Render members list page
-    fn render_members(env: &Env, board_id: u64, viewer: &Option<Address>) -> Bytes {
+    /// Render the members page. Owner, admins and moderators are listed in full;
+    /// plain members are paginated (`page` is 1-based).
+    fn render_members(env: &Env, board_id: u64, page: u32, viewer: &Option<Address>) -> Bytes {
         let permissions: Address = env
             .storage()
             .instance()
             .get(&AdminKey::Permissions)
             .expect("Not initialized");
 
-        let mut md = Self::render_nav_subpage(env, board_id).h1("Board Members");
+        let mut md = Self::render_nav_subpage(env, board_id, "Board Members");
 
         // Check if viewer has permission to view members (moderator+)
         let can_view = if let Some(user) = viewer {
@@ -586,12 +1119,17 @@ impl BoardsAdmin {
         }
 
         // Fetch admins
-        md = md.h2("Admins");
         let admins: Vec<Address> = env.invoke_contract(
             &permissions,
             &Symbol::new(env, "list_admins"),
             Vec::from_array(env, [board_id.into_val(env)]),
         );
+        md = md
+            .h2("")
+            .text("Admins (")
+            .raw(Self::format_count(env, admins.len()))
+            .text(")")
+            .newline();
 
         if admins.is_empty() {
             md = md.paragraph("*No admins*");
@@ -600,21 +1138,31 @@ impl BoardsAdmin {
                 let addr = admins.get(i).unwrap();
                 let addr_str = Self::format_address(env, &addr);
                 md = md.text("- `").text_string(&addr_str).text("`");
+                md = Self::render_user_link(md, board_id, &addr_str);
                 // Demote button (for owner only)
-                md = md
-                    .text(" ")
-                    .tx_link_to("[Demote]", "admin", "remove_admin", "");
+                md = Self::render_member_actions(
+                    env,
+                    md,
+                    board_id,
+                    &addr,
+                    &[("[Demote]", "remove_admin")],
+                );
                 md = md.newline();
             }
         }
 
         // Fetch moderators
-        md = md.h2("Moderators");
         let mods: Vec<Address> = env.invoke_contract(
             &permissions,
             &Symbol::new(env, "list_moderators"),
             Vec::from_array(env, [board_id.into_val(env)]),
         );
+        md = md
+            .h2("")
+            .text("Moderators (")
+            .raw(Self::format_count(env, mods.len()))
+            .text(")")
+            .newline();
 
         if mods.is_empty() {
             md = md.paragraph("*No moderators*");
@@ -623,21 +1171,44 @@ impl BoardsAdmin {
                 let addr = mods.get(i).unwrap();
                 let addr_str = Self::format_address(env, &addr);
                 md = md.text("- `").text_string(&addr_str).text("`");
+                md = Self::render_user_link(md, board_id, &addr_str);
                 // Demote button (for admin+)
-                md = md
-                    .text(" ")
-                    .tx_link_to("[Demote]", "admin", "remove_moderator", "");
+                md = Self::render_member_actions(
+                    env,
+                    md,
+                    board_id,
+                    &addr,
+                    &[("[Demote]", "remove_moderator")],
+                );
                 md = md.newline();
             }
         }
 
-        // Fetch members
-        md = md.h2("Members");
+        // Fetch one page of members
+        let member_count: u32 = env.invoke_contract(
+            &permissions,
+            &Symbol::new(env, "role_count"),
+            Vec::from_array(env, [board_id.into_val(env), Role::Member.into_val(env)]),
+        );
+        let (page, start, total_pages) = Self::page_bounds(member_count, page, MEMBERS_PER_PAGE);
         let members: Vec<Address> = env.invoke_contract(
             &permissions,
-            &Symbol::new(env, "list_members"),
-            Vec::from_array(env, [board_id.into_val(env)]),
+            &Symbol::new(env, "list_members_page"),
+            Vec::from_array(
+                env,
+                [
+                    board_id.into_val(env),
+                    start.into_val(env),
+                    MEMBERS_PER_PAGE.into_val(env),
+                ],
+            ),
         );
+        md = md
+            .h2("")
+            .text("Members (")
+            .raw(Self::format_count(env, member_count))
+            .text(")")
+            .newline();
 
         if members.is_empty() {
             md = md.paragraph("*No members*");
@@ -646,21 +1217,53 @@ impl BoardsAdmin {
                 let addr = members.get(i).unwrap();
                 let addr_str = Self::format_address(env, &addr);
                 md = md.text("- `").text_string(&addr_str).text("`");
+                md = Self::render_user_link(md, board_id, &addr_str);
                 // Promote/Remove buttons
+                md = Self::render_member_actions(
+                    env,
+                    md,
+                    board_id,
+                    &addr,
+                    &[
+                        ("[Promote to Mod]", "add_moderator"),
+                        ("[Remove]", "remove_member"),
+                    ],
+                );
+                md = md.newline();
+            }
+        }
+
+        if total_pages > 1 {
+            md = md.newline();
+            if page > 1 {
                 md = md
-                    .text(" ")
-                    .tx_link_to("[Promote to Mod]", "admin", "set_moderator", "");
+                    .raw_str("[< Prev](render:/admin/b/")
+                    .number(board_id as u32)
+                    .raw_str("/members/")
+                    .number(page - 1)
+                    .raw_str(") ");
+            }
+            md = md
+                .text("Page ")
+                .number(page)
+                .text(" of ")
+                .number(total_pages);
+            if page < total_pages {
                 md = md
-                    .text(" ")
-                    .tx_link_to("[Remove]", "admin", "remove_member", "");
-                md = md.newline();
+                    .raw_str(" [Next >](render:/admin/b/")
+                    .number(board_id as u32)
+                    .raw_str("/members/")
+                    .number(page + 1)
+                    .raw_str(")");
             }
+            md = md.newline();
         }
 
-        // Add member form
+        // Add member form - wrapped in data-form to isolate from per-row actions
         md = md
             .hr()
             .h3("Add Member")
+            .raw_str("<div data-form>\n")
             .raw_str("<input type=\"hidden\" name=\"board_id\" value=\"")
             .number(board_id as u32)
             .raw_str("\" />\n")
@@ -670,11 +1273,39 @@ impl BoardsAdmin {
             .text(" ")
             .form_link_to("Add as Moderator", "admin", "add_moderator")
             .text(" ")
-            .form_link_to("Add as Admin", "admin", "add_admin");
+            .form_link_to("Add as Admin", "admin", "add_admin")
+            .raw_str("\n</div>\n");
 
         Self::render_footer_into(env, md).build()
     }
 
+    /// Render a member row's actions as one form carrying that row's target address,
+    /// so actions never pick up fields from other rows
+    fn render_member_actions<'a>(
+        env: &Env,
+        md: MarkdownBuilder<'a>,
+        board_id: u64,
+        user: &Address,
+        actions: &[(&str, &str)],
+    ) -> MarkdownBuilder<'a> {
+        let mut md = md
+            .text(" ")
+            .raw_str("<span data-form>")
+            .raw_str("<input type=\"hidden\" name=\"board_id\" value=\"")
+            .number(board_id as u32)
+            .raw_str("\" />")
+            .raw_str("<input type=\"hidden\" name=\"user_address\" value=\"")
+            .text_string(&Self::format_address(env, user))
+            .raw_str("\" />");
+        for (i, (label, func)) in actions.iter().enumerate() {
+            if i > 0 {
+                md = md.text(" ");
+            }
+            md = md.form_link_to(label, "admin", func);
+        }
+        md.raw_str("</span>")
+    }
+
     /// Render banned users page
     fn render_banned(env: &Env, board_id: u64, viewer: &Option<Address>) -> Bytes {
         let permissions: Address = env
@@ -683,7 +1314,7 @@ impl BoardsAdmin {
             .get(&AdminKey::Permissions)
             .expect("Not initialized");
 
-        let mut md = Self::render_nav_subpage(env, board_id).h1("Banned Users");
+        let mut md = Self::render_nav_subpage(env, board_id, "Banned Users");
 
         // Check if viewer has permission (moderator+)
         let can_view = if let Some(user) = viewer {
@@ -707,11 +1338,22 @@ impl BoardsAdmin {
             Vec::from_array(env, [board_id.into_val(env)]),
         );
 
-        if bans.is_empty() {
+        // Expired bans no longer apply; list them separately until purged
+        let now = env.ledger().timestamp();
+        let mut active: Vec<Ban> = Vec::new(env);
+        let mut expired: Vec<Ban> = Vec::new(env);
+        for ban in bans.iter() {
+            match ban.expires_at {
+                Some(expires) if expires <= now => expired.push_back(ban),
+                _ => active.push_back(ban),
+            }
+        }
+
+        if active.is_empty() {
             md = md.tip("No banned users. Good work!");
         } else {
-            for i in 0..bans.len() {
-                let ban = bans.get(i).unwrap();
+            for i in 0..active.len() {
+                let ban = active.get(i).unwrap();
                 let user_str = Self::format_address(env, &ban.user);
                 let issuer_str = Self::format_address(env, &ban.issuer);
 
@@ -733,21 +1375,61 @@ impl BoardsAdmin {
                 if let Some(expires) = ban.expires_at {
                     md = md
                         .text("**Expires:** ")
-                        .number(expires as u32)
-                        .text(" (timestamp)")
+                        .raw(Self::format_timestamp(env, expires))
                         .newline();
                 } else {
                     md = md.text("**Expires:** *Permanent*").newline();
                 }
 
-                // Unban link with hidden fields
+                // Each action gets its own form so fields from other bans aren't submitted
                 md = md
+                    .raw_str("<div data-form>\n")
+                    .raw_str("<input type=\"hidden\" name=\"board_id\" value=\"")
+                    .number(board_id as u32)
+                    .raw_str("\" />\n")
+                    .raw_str("<input type=\"hidden\" name=\"user\" value=\"")
+                    .text_string(&user_str)
+                    .raw_str("\" />\n")
+                    .form_link_to("Unban User", "admin", "unban_user")
+                    .raw_str("\n</div>\n")
+                    .raw_str("<div data-form>\n")
                     .raw_str("<input type=\"hidden\" name=\"board_id\" value=\"")
                     .number(board_id as u32)
                     .raw_str("\" />\n")
-                    .tx_link_to("Unban User", "admin", "unban_user", "")
+                    .raw_str("<input type=\"hidden\" name=\"user\" value=\"")
+                    .text_string(&user_str)
+                    .raw_str("\" />\n")
+                    .input(
+                        "duration_hours",
+                        "New duration from now (hours, 0 = permanent)",
+                    )
+                    .newline()
+                    .form_link_to("Extend Ban", "admin", "extend_ban")
+                    .raw_str("\n</div>\n");
+                md = Self::render_hide_all_form(env, md, board_id, &ban.user, &user_str);
+            }
+        }
+
+        if !expired.is_empty() {
+            md = md.hr().h3("Expired Bans");
+            for ban in expired.iter() {
+                let user_str = Self::format_address(env, &ban.user);
+                md = md
+                    .text("`")
+                    .text_string(&user_str)
+                    .text("` ")
+                    .raw_str("<span class=\"badge badge-expired\">expired</span> ")
+                    .raw(Self::format_timestamp(env, ban.expires_at.unwrap_or(0)))
                     .newline();
             }
+            md = md
+                .newline()
+                .raw_str("<div data-form>\n")
+                .raw_str("<input type=\"hidden\" name=\"board_id\" value=\"")
+                .number(board_id as u32)
+                .raw_str("\" />\n")
+                .form_link_to("Purge Expired Bans", "admin", "purge_expired_bans")
+                .raw_str("\n</div>\n");
         }
 
         // Ban user form
@@ -768,7 +1450,402 @@ impl BoardsAdmin {
         Self::render_footer_into(env, md).build()
     }
 
-    /// Render flag queue page
+    /// Render the moderation log page (most recent actions first)
+    fn render_mod_log(env: &Env, board_id: u64, viewer: &Option<Address>) -> Bytes {
+        let permissions: Address = env
+            .storage()
+            .instance()
+            .get(&AdminKey::Permissions)
+            .expect("Not initialized");
+
+        let mut md = Self::render_nav_subpage(env, board_id, "Moderation Log");
+
+        // Check if viewer has permission (moderator+)
+        let can_view = if let Some(user) = viewer {
+            let args: Vec<Val> = Vec::from_array(env, [board_id.into_val(env), user.into_val(env)]);
+            let perms: PermissionSet =
+                env.invoke_contract(&permissions, &Symbol::new(env, "get_permissions"), args);
+            perms.can_moderate
+        } else {
+            false
+        };
+
+        if !can_view {
+            md = md.warning("You must be a moderator to view this page.");
+            return Self::render_footer_into(env, md).build();
+        }
+
+        let actions = Self::list_mod_actions(env.clone(), board_id, 0, MOD_LOG_CHUNK_SIZE);
+
+        if actions.is_empty() {
+            md = md.tip("No moderation actions have been recorded yet.");
+        } else {
+            for action in actions.iter() {
+                let actor_str = Self::format_address(env, &action.actor);
+
+                md = md
+                    .hr()
+                    .text("**")
+                    .text(Self::mod_action_label(env, &action.action))
+                    .text("** by `")
+                    .text_string(&actor_str)
+                    .text("`")
+                    .newline();
+
+                if let Some(thread_id) = action.target_thread {
+                    md = md
+                        .text("**Thread:** ")
+                        .raw_str("[#")
+                        .number(thread_id as u32)
+                        .raw_str("](render:/b/")
+                        .number(board_id as u32)
+                        .raw_str("/t/")
+                        .number(thread_id as u32)
+                        .raw_str(")")
+                        .newline();
+                }
+                if let Some(reply_id) = action.target_reply {
+                    md = md.text("**Reply:** #").number(reply_id as u32).newline();
+                }
+                if let Some(user) = &action.target_user {
+                    let user_str = Self::format_address(env, user);
+                    md = md
+                        .text("**User:** `")
+                        .text_string(&user_str)
+                        .text("`")
+                        .newline();
+                }
+                if let Some(note) = &action.note {
+                    md = md.text("**Note:** ").text_string(note).newline();
+                }
+
+                md = md
+                    .text("**When:** ")
+                    .raw(Self::format_timestamp(env, action.timestamp))
+                    .newline();
+            }
+        }
+
+        Self::render_footer_into(env, md).build()
+    }
+
+    /// Human-readable label for a moderation log action
+    fn mod_action_label(env: &Env, action: &Symbol) -> &'static str {
+        let labels: [(&str, &'static str); 23] = [
+            ("hide_thread", "Hid thread"),
+            ("unhide_thread", "Unhid thread"),
+            ("hide_reply", "Hid reply"),
+            ("unhide_reply", "Unhid reply"),
+            ("lock_thread", "Locked thread"),
+            ("unlock_thread", "Unlocked thread"),
+            ("schedule_thread", "Scheduled thread"),
+            ("pin_thread", "Pinned thread"),
+            ("unpin_thread", "Unpinned thread"),
+            ("delete_thread", "Deleted thread"),
+            ("delete_reply", "Deleted reply"),
+            ("remove_crosspost", "Removed crosspost"),
+            ("clear_flags", "Cleared flags"),
+            ("ban_user", "Banned user"),
+            ("unban_user", "Unbanned user"),
+            ("extend_ban", "Extended ban"),
+            ("set_role", "Changed role"),
+            ("remove_role", "Removed role"),
+            ("transfer_owner", "Accepted ownership"),
+            ("quarantine", "Quarantined user"),
+            ("unquarantine", "Released user from quarantine"),
+            ("archive_board", "Archived board"),
+            ("unarchive_board", "Unarchived board"),
+        ];
+        for (name, label) in labels.iter() {
+            if *action == Symbol::new(env, name) {
+                return label;
+            }
+        }
+        "Moderation action"
+    }
+
+    /// Render the moderator view of a single user: role, ban status,
+    /// recently flagged content, and the mod team's notes
+    fn render_user(env: &Env, board_id: u64, address: &Bytes, viewer: &Option<Address>) -> Bytes {
+        let permissions: Address = env
+            .storage()
+            .instance()
+            .get(&AdminKey::Permissions)
+            .expect("Not initialized");
+        let content: Address = env
+            .storage()
+            .instance()
+            .get(&AdminKey::Content)
+            .expect("Not initialized");
+
+        let mut md = Self::render_nav_subpage(env, board_id, "User");
+
+        // Check if viewer has permission (moderator+)
+        let viewer_perms: Option<PermissionSet> = viewer.as_ref().map(|user| {
+            let args: Vec<Val> = Vec::from_array(env, [board_id.into_val(env), user.into_val(env)]);
+            env.invoke_contract(&permissions, &Symbol::new(env, "get_permissions"), args)
+        });
+        let can_view = viewer_perms
+            .as_ref()
+            .map(|p| p.can_moderate)
+            .unwrap_or(false);
+        let can_admin = viewer_perms.as_ref().map(|p| p.can_admin).unwrap_or(false);
+
+        if !can_view {
+            md = md.warning("You must be a moderator to view this page.");
+            return Self::render_footer_into(env, md).build();
+        }
+
+        // Malformed addresses would trap in from_string_bytes
+        if !is_valid_address(address) {
+            md = md.warning("Invalid address.");
+            return Self::render_footer_into(env, md).build();
+        }
+        let user = Address::from_string_bytes(address);
+        let user_str = Self::format_address(env, &user);
+
+        md = md
+            .text("`")
+            .text_string(&user_str)
+            .text("`")
+            .newline()
+            .newline();
+
+        // Role and ban status
+        let role: Role = env.invoke_contract(
+            &permissions,
+            &Symbol::new(env, "get_role"),
+            Vec::from_array(env, [board_id.into_val(env), user.into_val(env)]),
+        );
+        md = md.text("**Role:** ").text(Self::role_label(role)).newline();
+
+        let is_banned: bool = env.invoke_contract(
+            &permissions,
+            &Symbol::new(env, "is_banned"),
+            Vec::from_array(env, [board_id.into_val(env), user.into_val(env)]),
+        );
+        if is_banned {
+            md = md.text("**Status:** Banned").newline();
+            let ban: Option<Ban> = env.invoke_contract(
+                &permissions,
+                &Symbol::new(env, "get_ban"),
+                Vec::from_array(env, [board_id.into_val(env), user.into_val(env)]),
+            );
+            if let Some(ban) = ban {
+                md = md
+                    .text("**Reason:** ")
+                    .text_string(&ban.reason)
+                    .newline()
+                    .text("**Expires:** ");
+                md = match ban.expires_at {
+                    Some(expires) => md.raw(Self::format_timestamp(env, expires)),
+                    None => md.text("*Permanent*"),
+                };
+                md = md.newline();
+            }
+        } else {
+            md = md.text("**Status:** Not banned").newline();
+        }
+
+        // Quarantine toggle: new posts from a quarantined user start hidden
+        let quarantined: bool = env.invoke_contract(
+            &permissions,
+            &Symbol::new(env, "is_quarantined"),
+            Vec::from_array(env, [board_id.into_val(env), user.into_val(env)]),
+        );
+        md = md
+            .text("**Quarantine:** ")
+            .text(if quarantined {
+                "On (new posts start hidden)"
+            } else {
+                "Off"
+            })
+            .text(" ")
+            .raw_str("<span data-form>")
+            .raw_str("<input type=\"hidden\" name=\"board_id\" value=\"")
+            .number(board_id as u32)
+            .raw_str("\" />")
+            .raw_str("<input type=\"hidden\" name=\"user\" value=\"")
+            .text_string(&user_str)
+            .raw_str("\" />")
+            .raw_str("<input type=\"hidden\" name=\"on\" value=\"")
+            .raw_str(if quarantined { "false" } else { "true" })
+            .raw_str("\" />")
+            .form_link_to(
+                if quarantined {
+                    "[Release]"
+                } else {
+                    "[Quarantine]"
+                },
+                "admin",
+                "set_quarantined",
+            )
+            .raw_str("</span>")
+            .newline();
+
+        // Clean up after a spammer: hides up to MAX_BULK_ITEMS posts per click
+        md = Self::render_hide_all_form(env, md, board_id, &user, &user_str);
+
+        // Recently flagged content authored by this user (newest flags first)
+        md = md.h2("Recent Flagged Content");
+        let flagged: Vec<FlaggedItem> = env.invoke_contract(
+            &content,
+            &Symbol::new(env, "list_flagged_content"),
+            Vec::from_array(env, [board_id.into_val(env)]),
+        );
+        let board_contract = Self::get_board_contract_address(env);
+        let mut shown = 0;
+        let mut scanned = 0;
+        let mut i = flagged.len();
+        while i > 0 && scanned < 50 && shown < 10 {
+            i -= 1;
+            scanned += 1;
+            let item = flagged.get(i).unwrap();
+
+            let author: Option<Address> = if item.item_type == FlaggedType::Thread {
+                env.try_invoke_contract::<Option<ThreadMeta>, soroban_sdk::Error>(
+                    &board_contract,
+                    &Symbol::new(env, "get_thread"),
+                    Vec::from_array(env, [board_id.into_val(env), item.thread_id.into_val(env)]),
+                )
+                .ok()
+                .and_then(|r| r.ok())
+                .flatten()
+                .map(|t| t.creator)
+            } else {
+                env.try_invoke_contract::<Option<ReplyMeta>, soroban_sdk::Error>(
+                    &content,
+                    &Symbol::new(env, "get_reply"),
+                    Vec::from_array(
+                        env,
+                        [
+                            board_id.into_val(env),
+                            item.thread_id.into_val(env),
+                            item.reply_id.into_val(env),
+                        ],
+                    ),
+                )
+                .ok()
+                .and_then(|r| r.ok())
+                .flatten()
+                .map(|r| r.creator)
+            };
+
+            if author.as_ref() != Some(&user) {
+                continue;
+            }
+            shown += 1;
+
+            md = md.raw_str("- [Thread #").number(item.thread_id as u32);
+            if item.item_type == FlaggedType::Reply {
+                md = md.raw_str(" / Reply #").number(item.reply_id as u32);
+            }
+            md = md
+                .raw_str("](render:/b/")
+                .number(board_id as u32)
+                .raw_str("/t/")
+                .number(item.thread_id as u32);
+            if item.item_type == FlaggedType::Reply {
+                md = md.raw_str("/r/").number(item.reply_id as u32);
+            }
+            md = md
+                .raw_str(")")
+                .text(" - ")
+                .number(item.flag_count)
+                .text(" flags")
+                .newline();
+        }
+        if shown == 0 {
+            md = md.paragraph("*No flagged content*");
+        }
+
+        // Moderator notes (newest first)
+        md = md.h2("Moderator Notes");
+        let notes = Self::list_user_notes(env.clone(), board_id, user.clone());
+        if notes.is_empty() {
+            md = md.paragraph("*No notes yet*");
+        } else {
+            let mut n = notes.len();
+            while n > 0 {
+                n -= 1;
+                let note = notes.get(n).unwrap();
+                let author_str = Self::format_address(env, &note.author);
+                md = md
+                    .hr()
+                    .text_string(&note.text)
+                    .newline()
+                    .newline()
+                    .text("*by `")
+                    .text_string(&author_str)
+                    .text("` on ")
+                    .raw(Self::format_timestamp(env, note.timestamp))
+                    .text("*");
+
+                // Delete link (author or admin)
+                if can_admin || viewer.as_ref() == Some(&note.author) {
+                    md = md
+                        .text(" ")
+                        .raw_str("<span data-form>")
+                        .raw_str("<input type=\"hidden\" name=\"board_id\" value=\"")
+                        .number(board_id as u32)
+                        .raw_str("\" />")
+                        .raw_str("<input type=\"hidden\" name=\"user\" value=\"")
+                        .text_string(&user_str)
+                        .raw_str("\" />")
+                        .raw_str("<input type=\"hidden\" name=\"note_id\" value=\"")
+                        .number(note.id)
+                        .raw_str("\" />")
+                        .form_link_to("[Delete]", "admin", "delete_user_note")
+                        .raw_str("</span>");
+                }
+                md = md.newline();
+            }
+        }
+
+        // Add note form
+        md = md
+            .hr()
+            .h3("Add Note")
+            .raw_str("<div data-form>\n")
+            .raw_str("<input type=\"hidden\" name=\"board_id\" value=\"")
+            .number(board_id as u32)
+            .raw_str("\" />\n")
+            .raw_str("<input type=\"hidden\" name=\"user\" value=\"")
+            .text_string(&user_str)
+            .raw_str("\" />\n")
+            .textarea("note", 3, "Note for the mod team (max 500 characters)")
+            .newline()
+            .form_link_to("Add Note", "admin", "add_user_note")
+            .raw_str("\n</div>\n");
+
+        Self::render_footer_into(env, md).build()
+    }
+
+    /// Append a link to a user's moderator page after their address
+    fn render_user_link<'a>(
+        md: MarkdownBuilder<'a>,
+        board_id: u64,
+        addr_str: &String,
+    ) -> MarkdownBuilder<'a> {
+        md.raw_str(" [Notes](render:/admin/b/")
+            .number(board_id as u32)
+            .raw_str("/user/")
+            .text_string(addr_str)
+            .raw_str(")")
+    }
+
+    /// Display name for a role
+    fn role_label(role: Role) -> &'static str {
+        match role {
+            Role::Guest => "Guest",
+            Role::Member => "Member",
+            Role::Moderator => "Moderator",
+            Role::Admin => "Admin",
+            Role::Owner => "Owner",
+        }
+    }
+
+    /// Render flag queue page
     fn render_flag_queue(env: &Env, board_id: u64, viewer: &Option<Address>) -> Bytes {
         let permissions: Address = env
             .storage()
@@ -781,7 +1858,7 @@ impl BoardsAdmin {
             .get(&AdminKey::Content)
             .expect("Not initialized");
 
-        let mut md = Self::render_nav_subpage(env, board_id).h1("Flag Queue");
+        let mut md = Self::render_nav_subpage(env, board_id, "Flag Queue");
 
         // Check if viewer has permission (moderator+)
         let can_view = if let Some(user) = viewer {
@@ -810,34 +1887,179 @@ impl BoardsAdmin {
         } else {
             md = md.paragraph("Review flagged content and take appropriate action.");
 
+            let board_contract = Self::get_board_contract_address(env);
             for i in 0..flagged.len() {
                 let item = flagged.get(i).unwrap();
+                let is_thread = item.item_type == FlaggedType::Thread;
+
+                // Current state of the item; anything we can no longer load counts as deleted
+                let (is_hidden, is_deleted) = if is_thread {
+                    env.try_invoke_contract::<Option<ThreadMeta>, soroban_sdk::Error>(
+                        &board_contract,
+                        &Symbol::new(env, "get_thread"),
+                        Vec::from_array(
+                            env,
+                            [board_id.into_val(env), item.thread_id.into_val(env)],
+                        ),
+                    )
+                    .ok()
+                    .and_then(|r| r.ok())
+                    .flatten()
+                    .map(|t| (t.is_hidden, t.is_deleted))
+                    .unwrap_or((false, true))
+                } else {
+                    env.try_invoke_contract::<Option<ReplyMeta>, soroban_sdk::Error>(
+                        &content,
+                        &Symbol::new(env, "get_reply"),
+                        Vec::from_array(
+                            env,
+                            [
+                                board_id.into_val(env),
+                                item.thread_id.into_val(env),
+                                item.reply_id.into_val(env),
+                            ],
+                        ),
+                    )
+                    .ok()
+                    .and_then(|r| r.ok())
+                    .flatten()
+                    .map(|r| (r.is_hidden, r.is_deleted))
+                    .unwrap_or((false, true))
+                };
 
                 md = md.hr();
 
-                let type_str = if item.item_type == FlaggedType::Thread {
-                    "Thread"
-                } else {
-                    "Reply"
-                };
+                let type_str = if is_thread { "Thread" } else { "Reply" };
                 md = md
                     .h3("")
                     .text(type_str)
                     .text(" #")
                     .number(item.thread_id as u32);
 
-                if item.item_type == FlaggedType::Reply {
+                if !is_thread {
                     md = md.text(" / Reply #").number(item.reply_id as u32);
                 }
 
+                if is_deleted {
+                    md = md.raw_str(" <span class=\"badge badge-deleted\">deleted</span>");
+                } else if is_hidden {
+                    // Distinguish threads hidden by the flag threshold from mod-hidden ones
+                    let auto_hidden = is_thread
+                        && env
+                            .try_invoke_contract::<bool, soroban_sdk::Error>(
+                                &board_contract,
+                                &Symbol::new(env, "is_thread_auto_hidden"),
+                                Vec::from_array(
+                                    env,
+                                    [board_id.into_val(env), item.thread_id.into_val(env)],
+                                ),
+                            )
+                            .ok()
+                            .and_then(|r| r.ok())
+                            .unwrap_or(false);
+                    md = if auto_hidden {
+                        md.raw_str(" <span class=\"badge badge-hidden\">auto-hidden</span>")
+                    } else {
+                        md.raw_str(" <span class=\"badge badge-hidden\">hidden</span>")
+                    };
+                }
+                md = md.newline();
+
+                // Quoted excerpt from the first chunk of the body
+                if !is_deleted {
+                    let body: Option<Bytes> = if is_thread {
+                        env.try_invoke_contract::<Option<Bytes>, soroban_sdk::Error>(
+                            &content,
+                            &Symbol::new(env, "get_thread_body_chunk"),
+                            Vec::from_array(
+                                env,
+                                [
+                                    board_id.into_val(env),
+                                    item.thread_id.into_val(env),
+                                    0u32.into_val(env),
+                                ],
+                            ),
+                        )
+                    } else {
+                        env.try_invoke_contract::<Option<Bytes>, soroban_sdk::Error>(
+                            &content,
+                            &Symbol::new(env, "get_reply_content_chunk"),
+                            Vec::from_array(
+                                env,
+                                [
+                                    board_id.into_val(env),
+                                    item.thread_id.into_val(env),
+                                    item.reply_id.into_val(env),
+                                    0u32.into_val(env),
+                                ],
+                            ),
+                        )
+                    }
+                    .ok()
+                    .and_then(|r| r.ok())
+                    .flatten();
+
+                    if let Some(body) = body {
+                        if !body.is_empty() {
+                            md = md
+                                .raw_str("> ")
+                                .raw(Self::content_preview(env, &body))
+                                .newline()
+                                .newline();
+                        }
+                    }
+                }
+
                 md = md
-                    .newline()
                     .text("**Flags:** ")
                     .number(item.flag_count)
+                    .text(" | **First flagged:** ")
+                    .raw(Self::format_timestamp(env, item.first_flagged_at))
+                    .newline()
                     .newline();
 
+                // Reasons given by each flagger
+                let reply_id: Option<u64> = if is_thread { None } else { Some(item.reply_id) };
+                let flags: Vec<FlagRecord> = env.invoke_contract(
+                    &content,
+                    &Symbol::new(env, "list_flags"),
+                    Vec::from_array(
+                        env,
+                        [
+                            board_id.into_val(env),
+                            item.thread_id.into_val(env),
+                            reply_id.into_val(env),
+                        ],
+                    ),
+                );
+                let shown = flags.len().min(MAX_FLAG_REASONS_SHOWN);
+                for j in 0..shown {
+                    let flag = flags.get(j).unwrap();
+                    md = md
+                        .raw_str("- **")
+                        .text(Self::flag_reason_label(flag.reason))
+                        .raw_str("** by `")
+                        .text_string(&Self::format_address(env, &flag.flagger))
+                        .raw_str("` on ")
+                        .raw(Self::format_timestamp(env, flag.created_at));
+                    if !flag.details.is_empty() {
+                        md = md.raw_str(": \"").text_string(&flag.details).raw_str("\"");
+                    }
+                    md = md.newline();
+                }
+                if flags.len() > shown {
+                    md = md
+                        .raw_str("- *...and ")
+                        .number(flags.len() - shown)
+                        .raw_str(" more*")
+                        .newline();
+                }
+                if shown > 0 {
+                    md = md.newline();
+                }
+
                 // View link
-                if item.item_type == FlaggedType::Thread {
+                if is_thread {
                     md = md
                         .raw_str("[View Thread](render:/b/")
                         .number(board_id as u32)
@@ -864,34 +2086,104 @@ impl BoardsAdmin {
                     .number(item.thread_id as u32)
                     .raw_str("\" />\n");
 
-                if item.item_type == FlaggedType::Reply {
+                if !is_thread {
                     md = md
                         .raw_str("<input type=\"hidden\" name=\"reply_id\" value=\"")
                         .number(item.reply_id as u32)
                         .raw_str("\" />\n");
                 }
 
-                // Actions
-                if item.item_type == FlaggedType::Thread {
-                    md = md
-                        .text(" | ")
-                        .tx_link_to("Hide Thread", "admin", "hide_thread", "");
-                    md = md
-                        .text(" | ")
-                        .tx_link_to("Delete Thread", "admin", "delete_thread", "");
-                } else {
-                    md = md
-                        .text(" | ")
-                        .tx_link_to("Hide Reply", "admin", "hide_reply", "");
-                    md = md
-                        .text(" | ")
-                        .tx_link_to("Delete Reply", "admin", "delete_reply", "");
+                // Actions (deleted items can only have their flags cleared)
+                if !is_deleted {
+                    md = match (is_thread, is_hidden) {
+                        (true, false) => {
+                            md.text(" | ")
+                                .tx_link_to("Hide Thread", "admin", "hide_thread", "")
+                        }
+                        (true, true) => {
+                            md.text(" | ")
+                                .tx_link_to("Unhide Thread", "admin", "unhide_thread", "")
+                        }
+                        (false, false) => {
+                            md.text(" | ")
+                                .tx_link_to("Hide Reply", "admin", "hide_reply", "")
+                        }
+                        (false, true) => {
+                            md.text(" | ")
+                                .tx_link_to("Unhide Reply", "admin", "unhide_reply", "")
+                        }
+                    };
+                    md = if is_thread {
+                        md.text(" | ")
+                            .tx_link_to("Delete Thread", "admin", "delete_thread", "")
+                    } else {
+                        md.text(" | ")
+                            .tx_link_to("Delete Reply", "admin", "delete_reply", "")
+                    };
+
+                    // Crossposts can also be retracted, which updates the original thread
+                    let is_crosspost = is_thread
+                        && env
+                            .try_invoke_contract::<Option<CrosspostRef>, soroban_sdk::Error>(
+                                &content,
+                                &Symbol::new(env, "get_crosspost_ref"),
+                                Vec::from_array(
+                                    env,
+                                    [board_id.into_val(env), item.thread_id.into_val(env)],
+                                ),
+                            )
+                            .ok()
+                            .and_then(|r| r.ok())
+                            .flatten()
+                            .is_some();
+                    if is_crosspost {
+                        md = md.text(" | ").tx_link_to(
+                            "Remove Crosspost",
+                            "admin",
+                            "remove_crosspost",
+                            "",
+                        );
+                    }
                 }
                 md = md
                     .text(" | ")
                     .tx_link_to("Clear Flags", "admin", "clear_flags", "");
                 md = md.newline();
             }
+
+            // Bulk actions: checked items are submitted as "thread" or "thread:reply" ids
+            md = md
+                .hr()
+                .h2("Bulk Actions")
+                .note("Select up to 25 items. Items that no longer exist are skipped.")
+                .raw_str("<div data-form>\n")
+                .raw_str("<input type=\"hidden\" name=\"board_id\" value=\"")
+                .number(board_id as u32)
+                .raw_str("\" />\n");
+
+            for i in 0..flagged.len() {
+                let item = flagged.get(i).unwrap();
+                md = md
+                    .raw_str("<label><input type=\"checkbox\" name=\"selected\" value=\"")
+                    .number(item.thread_id as u32);
+                if item.item_type == FlaggedType::Reply {
+                    md = md.raw_str(":").number(item.reply_id as u32);
+                }
+                md = md
+                    .raw_str("\" /> ")
+                    .text("Thread #")
+                    .number(item.thread_id as u32);
+                if item.item_type == FlaggedType::Reply {
+                    md = md.text(" / Reply #").number(item.reply_id as u32);
+                }
+                md = md.raw_str("</label>\n");
+            }
+
+            md = md
+                .form_link_to("Hide selected", "admin", "hide_selected")
+                .text(" | ")
+                .form_link_to("Clear selected", "admin", "clear_selected")
+                .raw_str("\n</div>\n");
         }
 
         Self::render_footer_into(env, md).build()
@@ -905,16 +2197,16 @@ impl BoardsAdmin {
             .get(&AdminKey::Permissions)
             .expect("Not initialized");
 
-        let mut md = Self::render_nav_subpage(env, board_id).h1("Invite Requests");
+        let mut md = Self::render_nav_subpage(env, board_id, "Invite Requests");
 
         // Check if viewer has permission (moderator+)
-        let can_view = if let Some(user) = viewer {
+        let (can_view, can_admin) = if let Some(user) = viewer {
             let args: Vec<Val> = Vec::from_array(env, [board_id.into_val(env), user.into_val(env)]);
             let perms: PermissionSet =
                 env.invoke_contract(&permissions, &Symbol::new(env, "get_permissions"), args);
-            perms.can_moderate
+            (perms.can_moderate, perms.can_admin)
         } else {
-            false
+            (false, false)
         };
 
         if !can_view {
@@ -929,13 +2221,46 @@ impl BoardsAdmin {
             Vec::from_array(env, [board_id.into_val(env)]),
         );
 
+        // Expired requests are already filtered out by the permissions contract
+        let ttl: u64 = env.invoke_contract(
+            &permissions,
+            &Symbol::new(env, "get_invite_request_ttl"),
+            Vec::from_array(env, [board_id.into_val(env)]),
+        );
+        if ttl > 0 {
+            md = md
+                .text("Requests expire after ")
+                .number(ttl as u32)
+                .text(" seconds.")
+                .newline()
+                .raw_str("<span data-form>")
+                .raw_str("<input type=\"hidden\" name=\"board_id\" value=\"")
+                .number(board_id as u32)
+                .raw_str("\" />")
+                .form_link_to("Purge Expired Requests", "admin", "purge_expired_requests")
+                .raw_str("</span>\n")
+                .newline();
+        }
+
         if requests.is_empty() {
             md = md.tip("No pending invite requests.");
         } else {
             md = md.paragraph("Users requesting to join this board:");
 
+            // Messages are stored apart from the requests; fetch them in one call
+            let mut users: Vec<Address> = Vec::new(env);
+            for request in requests.iter() {
+                users.push_back(request.user);
+            }
+            let messages: Vec<String> = env.invoke_contract(
+                &permissions,
+                &Symbol::new(env, "get_invite_messages"),
+                Vec::from_array(env, [board_id.into_val(env), users.into_val(env)]),
+            );
+
             for i in 0..requests.len() {
                 let request = requests.get(i).unwrap();
+                let message = messages.get(i).unwrap_or(String::from_str(env, ""));
                 let user_str = Self::format_address(env, &request.user);
 
                 md = md
@@ -946,21 +2271,39 @@ impl BoardsAdmin {
                     .text("`")
                     .newline()
                     .text("**Requested:** ")
-                    .number(request.created_at as u32)
-                    .text(" (timestamp)")
-                    .newline()
-                    // Hidden fields for actions
+                    .raw(Self::format_timestamp(env, request.created_at))
+                    .newline();
+                if !message.is_empty() {
+                    let mut buf = [0u8; 500];
+                    let len = message.len() as usize;
+                    message.copy_into_slice(&mut buf[..len]);
+                    let text = Bytes::from_slice(env, &buf[..len]);
+                    md = md
+                        .raw_str("> ")
+                        .raw(Self::content_preview(env, &text))
+                        .newline();
+                }
+                md = md
+                    // Each request is its own form so actions target this user
+                    .raw_str("<div data-form>\n")
                     .raw_str("<input type=\"hidden\" name=\"board_id\" value=\"")
                     .number(board_id as u32)
                     .raw_str("\" />\n")
                     .raw_str("<input type=\"hidden\" name=\"user\" value=\"")
                     .text_string(&user_str)
                     .raw_str("\" />\n")
-                    // Action buttons
-                    .tx_link_to("Accept", "admin", "accept_invite", "")
+                    .form_link_to("Accept as Member", "admin", "accept_invite");
+                if can_admin {
+                    md = md.text(" | ").form_link_to(
+                        "Accept as Moderator",
+                        "admin",
+                        "accept_invite_moderator",
+                    );
+                }
+                md = md
                     .text(" | ")
-                    .tx_link_to("Reject", "admin", "revoke_invite", "")
-                    .newline();
+                    .form_link_to("Reject", "admin", "revoke_invite")
+                    .raw_str("\n</div>\n");
             }
         }
 
@@ -990,9 +2333,92 @@ impl BoardsAdmin {
         Self::render_footer_into(env, md).build()
     }
 
-    /// Render board settings page
-    fn render_settings(env: &Env, board_id: u64, viewer: &Option<Address>) -> Bytes {
-        let permissions: Address = env
+    /// Render a numeric settings input prefilled with its current value
+    fn render_setting_input<'a>(
+        env: &Env,
+        md: MarkdownBuilder<'a>,
+        name: &str,
+        value: u64,
+    ) -> MarkdownBuilder<'a> {
+        md.raw_str("<input type=\"text\" name=\"")
+            .raw_str(name)
+            .raw_str("\" value=\"")
+            .raw(u64_to_bytes(env, value))
+            .raw_str("\" />\n")
+            .newline()
+    }
+
+    /// Render a "Hide All Posts" form for a user, continuing from where the last
+    /// run stopped. Renders nothing once every post has been checked.
+    fn render_hide_all_form<'a>(
+        env: &Env,
+        md: MarkdownBuilder<'a>,
+        board_id: u64,
+        user: &Address,
+        user_str: &String,
+    ) -> MarkdownBuilder<'a> {
+        let total = Self::user_post_count(env.clone(), board_id, user.clone());
+        let start: u32 = env
+            .storage()
+            .persistent()
+            .get(&AdminKey::HideAllCursor(board_id, user.clone()))
+            .unwrap_or(0);
+        if start >= total {
+            return md;
+        }
+        let left = total - start;
+        md.raw_str("<div data-form>\n")
+            .raw_str("<input type=\"hidden\" name=\"board_id\" value=\"")
+            .number(board_id as u32)
+            .raw_str("\" />\n")
+            .raw_str("<input type=\"hidden\" name=\"user\" value=\"")
+            .text_string(user_str)
+            .raw_str("\" />\n")
+            .raw_str("<input type=\"hidden\" name=\"start\" value=\"")
+            .number(start)
+            .raw_str("\" />\n")
+            .raw_str("<input type=\"hidden\" name=\"limit\" value=\"")
+            .number(MAX_BULK_ITEMS)
+            .raw_str("\" />\n")
+            .number(left)
+            .text(if left == 1 {
+                " post left to check "
+            } else {
+                " posts left to check "
+            })
+            .form_link_to("Hide All Posts", "admin", "hide_all_by_user")
+            .raw_str("\n</div>\n")
+    }
+
+    /// Render a Danger Zone form: hidden ids, a confirmation input and the action
+    fn render_danger_action<'a>(
+        md: MarkdownBuilder<'a>,
+        board_id: u64,
+        community_id: Option<u64>,
+        label: &str,
+        method: &str,
+    ) -> MarkdownBuilder<'a> {
+        let mut md = md
+            .raw_str("<div data-form>\n")
+            .raw_str("<input type=\"hidden\" name=\"board_id\" value=\"")
+            .number(board_id as u32)
+            .raw_str("\" />\n");
+        if let Some(id) = community_id {
+            md = md
+                .raw_str("<input type=\"hidden\" name=\"community_id\" value=\"")
+                .number(id as u32)
+                .raw_str("\" />\n");
+        }
+        md.input("confirm", "Board name or CONFIRM")
+            .newline()
+            .form_link_to(label, "admin", method)
+            .raw_str("\n</div>\n")
+            .newline()
+    }
+
+    /// Render board settings page
+    fn render_settings(env: &Env, board_id: u64, viewer: &Option<Address>) -> Bytes {
+        let permissions: Address = env
             .storage()
             .instance()
             .get(&AdminKey::Permissions)
@@ -1045,6 +2471,34 @@ impl BoardsAdmin {
             false
         };
 
+        // Ownership transfer state (owner and proposed owner both need it)
+        let owner_opt: Option<Address> = env.invoke_contract(
+            &permissions,
+            &Symbol::new(env, "get_board_owner"),
+            Vec::from_array(env, [board_id.into_val(env)]),
+        );
+        let pending_owner: Option<Address> = env.invoke_contract(
+            &permissions,
+            &Symbol::new(env, "get_pending_board_owner"),
+            Vec::from_array(env, [board_id.into_val(env)]),
+        );
+
+        // The proposed new owner may not be an admin yet, so offer acceptance first
+        if viewer.is_some() && pending_owner == *viewer {
+            md = md
+                .h2("Ownership Transfer")
+                .tip("You have been offered ownership of this board.")
+                .raw_str("<div data-form>\n")
+                .raw_str("<input type=\"hidden\" name=\"board_id\" value=\"")
+                .number(board_id as u32)
+                .raw_str("\" />\n")
+                .form_link_to("Accept Ownership", "admin", "accept_ownership_transfer")
+                .text(" ")
+                .form_link_to("Decline", "admin", "cancel_ownership_transfer")
+                .raw_str("\n</div>\n")
+                .newline();
+        }
+
         if !can_admin {
             md = md.warning("You must be an admin to view this page.");
             return Self::render_footer_into(env, md).build();
@@ -1058,12 +2512,18 @@ impl BoardsAdmin {
                 .newline()
                 .newline()
                 .note("Board name can be changed by updating the board configuration.")
+                // Registry name rules, one error code each
+                .raw_str("{{errors {\"3\": \"Board names must be 3-50 characters long.\", ")
+                .raw_str("\"4\": \"That name is reserved for the site.\", ")
+                .raw_str("\"5\": \"Another board already uses that name.\", ")
+                .raw_str("\"6\": \"That name used to belong to another board and still points to it.\", ")
+                .raw_str("\"7\": \"Board names can't start or end with a dash.\"}}}\n")
                 .raw_str("<input type=\"hidden\" name=\"board_id\" value=\"")
                 .number(board_id as u32)
                 .raw_str("\" />\n")
                 .input(
                     "new_name",
-                    "New board name (3-50 chars, letters/numbers/-/_)",
+                    "New board name (3-50 chars, no leading or trailing dash)",
                 )
                 .newline()
                 .form_link_to("Rename Board", "admin", "rename_board")
@@ -1071,746 +2531,1140 @@ impl BoardsAdmin {
                 .newline();
         }
 
-        // Get flag threshold
+        // Current values, used to prefill the grouped settings form
         let threshold: u32 = env.invoke_contract(
             &permissions,
             &Symbol::new(env, "get_flag_threshold"),
             Vec::from_array(env, [board_id.into_val(env)]),
         );
-
-        md = md
-            .h2("Moderation Settings")
-            .text("**Flag threshold for auto-hide:** ")
-            .number(threshold)
-            .newline()
-            .raw_str("<input type=\"hidden\" name=\"board_id\" value=\"")
-            .number(board_id as u32)
-            .raw_str("\" />\n")
-            .input("threshold", "New threshold (number)")
-            .newline()
-            .form_link_to("Update Threshold", "admin", "set_flag_threshold")
-            .newline()
-            .newline();
-
-        // Get chunk size and other settings (board_contract already defined above)
         let chunk_size: u32 = env.invoke_contract(
             &board_contract,
             &Symbol::new(env, "get_chunk_size"),
             Vec::from_array(env, [board_id.into_val(env)]),
         );
-
-        md = md.h2("Display Settings")
-            .text("**Reply chunk size:** ").number(chunk_size).text(" replies per batch").newline()
-            .newline()
-            .note("Controls how many replies load at once in waterfall loading. Lower values load faster but require more scrolling.")
-            .raw_str("<input type=\"hidden\" name=\"board_id\" value=\"")
-            .number(board_id as u32)
-            .raw_str("\" />\n")
-            .input("chunk_size", "New chunk size (1-20)")
-            .newline()
-            .form_link_to("Update Chunk Size", "admin", "set_chunk_size")
-            .newline()
-            .newline();
-
-        // Max reply depth setting
         let max_depth: u32 = env.invoke_contract(
             &board_contract,
             &Symbol::new(env, "get_max_reply_depth"),
             Vec::from_array(env, [board_id.into_val(env)]),
         );
-
-        md = md.h2("Reply Threading")
-            .text("**Maximum reply depth:** ").number(max_depth).text(" levels").newline()
-            .newline()
-            .note("Controls how deeply nested replies can be. Replies at the maximum depth cannot have children. Setting this lower helps keep discussions focused.")
-            .raw_str("<input type=\"hidden\" name=\"board_id\" value=\"")
-            .number(board_id as u32)
-            .raw_str("\" />\n")
-            .input("max_depth", "New max depth (1-20)")
-            .newline()
-            .form_link_to("Update Max Depth", "admin", "set_max_reply_depth")
-            .newline()
-            .newline();
-
-        // Edit window setting
         let edit_window: u64 = env.invoke_contract(
             &board_contract,
             &Symbol::new(env, "get_edit_window"),
             Vec::from_array(env, [board_id.into_val(env)]),
         );
+        // Convert seconds to hours for display (0 = no limit)
+        let edit_hours = edit_window / 3600;
 
-        // Convert seconds to hours for display
-        let edit_hours = if edit_window == 0 {
-            0
-        } else {
-            edit_window / 3600
-        };
-
-        md = md.h2("Content Editing").text("**Edit window:** ");
-
-        if edit_window == 0 {
-            md = md
-                .text("No limit (users can always edit their content)")
-                .newline();
-        } else {
-            md = md.number(edit_hours as u32).text(" hours").newline();
-        }
-
-        md = md.newline()
-            .note("Controls how long users can edit their posts after creation. Moderators can always edit.")
+        md = md
+            .h2("Board Settings")
+            .note("Change any of the values below and save. Unchanged fields are left as they are.")
+            .raw_str("<div data-form>\n")
             .raw_str("<input type=\"hidden\" name=\"board_id\" value=\"")
             .number(board_id as u32)
             .raw_str("\" />\n")
-            .input("edit_hours", "Edit window in hours (0 = no limit)")
-            .newline()
-            .form_link_to("Update Edit Window", "admin", "set_edit_window")
-            .newline()
+            .h3("Moderation")
+            .text("Number of flags before a post is auto-hidden.")
+            .newline();
+        md = Self::render_setting_input(env, md, "flag_threshold", threshold as u64);
+        md = md
+            .h3("Display")
+            .text("Replies per batch in waterfall loading (1-20). Lower values load faster.")
+            .newline();
+        md = Self::render_setting_input(env, md, "chunk_size", chunk_size as u64);
+        md = md
+            .h3("Reply Threading")
+            .text("Maximum reply depth (1-20). Replies at the maximum depth cannot have children.")
+            .newline();
+        md = Self::render_setting_input(env, md, "max_depth", max_depth as u64);
+        md = md
+            .h3("Content Editing")
+            .text("Edit window in hours (0 = no limit). Moderators can always edit.")
+            .newline();
+        md = Self::render_setting_input(env, md, "edit_hours", edit_hours);
+        md = md
+            .form_link_to("Save Settings", "admin", "update_board_settings")
+            .raw_str("\n</div>\n")
             .newline();
 
-        // Board visibility setting - query board contract
-        let is_listed: bool = env
-            .try_invoke_contract::<bool, soroban_sdk::Error>(
+        // Default thread ordering for the board view
+        let default_sort: ThreadSort = env
+            .try_invoke_contract::<ThreadSort, soroban_sdk::Error>(
                 &board_contract,
-                &Symbol::new(env, "get_board_listed"),
+                &Symbol::new(env, "get_default_sort"),
                 Vec::from_array(env, [board_id.into_val(env)]),
             )
-            .unwrap_or(Ok(false))
-            .unwrap_or(false);
-
-        md = md.h2("Board Visibility").text("**Listed publicly:** ");
+            .ok()
+            .and_then(|r| r.ok())
+            .unwrap_or(ThreadSort::New);
 
-        if is_listed {
-            md = md.text("Yes (appears on home page)").newline();
-        } else {
+        md = md
+            .h2("Default Sort")
+            .text("How threads are ordered when a visitor hasn't picked a sort.")
+            .newline()
+            .raw_str("<div data-form>\n")
+            .raw_str("<input type=\"hidden\" name=\"board_id\" value=\"")
+            .number(board_id as u32)
+            .raw_str("\" />\n")
+            .raw_str("<select name=\"sort\">\n");
+        let sort_options: [(&str, &str, ThreadSort); 5] = [
+            ("hot", "Hot", ThreadSort::Hot),
+            ("new", "New", ThreadSort::New),
+            ("top", "Top", ThreadSort::Top),
+            ("active", "Active", ThreadSort::Active),
+            ("controversial", "Controversial", ThreadSort::Controversial),
+        ];
+        for (value, label, option) in sort_options.iter() {
             md = md
-                .text("No (hidden from home page, accessible via direct link)")
-                .newline();
+                .raw_str("<option value=\"")
+                .raw_str(value)
+                .raw_str(if *option == default_sort {
+                    "\" selected>"
+                } else {
+                    "\">"
+                })
+                .raw_str(label)
+                .raw_str("</option>\n");
         }
+        md = md
+            .raw_str("</select>\n")
+            .form_link_to("Update Default Sort", "admin", "set_default_sort")
+            .raw_str("\n</div>\n")
+            .newline();
 
-        md = md.newline()
-            .note("Controls whether this board appears in the public board list on the home page. Unlisted boards are still accessible via direct link.")
+        // Boards without the getter use the board contract's default of 5
+        let controversial_min_votes = env
+            .try_invoke_contract::<u32, soroban_sdk::Error>(
+                &board_contract,
+                &Symbol::new(env, "get_controversial_min_votes"),
+                Vec::from_array(env, [board_id.into_val(env)]),
+            )
+            .ok()
+            .and_then(|r| r.ok())
+            .unwrap_or(5);
+        md = md
+            .h3("Controversial minimum votes")
+            .text("Threads with fewer total votes are left out of the Controversial sort.")
+            .newline()
+            .raw_str("<div data-form>\n")
             .raw_str("<input type=\"hidden\" name=\"board_id\" value=\"")
             .number(board_id as u32)
             .raw_str("\" />\n");
+        md = Self::render_setting_input(env, md, "min_votes", controversial_min_votes as u64);
+        md = md
+            .form_link_to("Update Minimum", "admin", "set_controversial_min_votes")
+            .raw_str("\n</div>\n")
+            .newline();
 
-        if is_listed {
-            md = md.form_link_to("Hide from Public List", "admin", "unlist_board");
-        } else {
-            md = md.form_link_to("Show on Public List", "admin", "list_board");
-        }
-
-        md = md.newline().newline();
-
-        // Board access control (public/private) - get from config
-        let is_private = config_opt.as_ref().map(|c| c.is_private).unwrap_or(false);
-
-        md = md.h2("Access Control").text("**Board type:** ");
+        // Post size limits (boards without the getters fall back to the defaults)
+        let size_limit = |getter: &str, default: u32| -> u32 {
+            env.try_invoke_contract::<u32, soroban_sdk::Error>(
+                &board_contract,
+                &Symbol::new(env, getter),
+                Vec::from_array(env, [board_id.into_val(env)]),
+            )
+            .ok()
+            .and_then(|r| r.ok())
+            .unwrap_or(default)
+        };
+        let max_thread_body_bytes = size_limit("get_max_thread_body_bytes", 16_384);
+        let max_reply_bytes = size_limit("get_max_reply_bytes", 8_192);
 
-        if is_private {
-            md = md.text("Private (members only)").newline();
-        } else {
-            md = md.text("Public (anyone can view and post)").newline();
-        }
+        md = md
+            .h2("Post Size Limits")
+            .text("Maximum thread body and reply sizes in bytes (1-16384). Edits are checked too.")
+            .newline()
+            .raw_str("<div data-form>\n")
+            .raw_str("<input type=\"hidden\" name=\"board_id\" value=\"")
+            .number(board_id as u32)
+            .raw_str("\" />\n")
+            .h3("Thread body");
+        md = Self::render_setting_input(
+            env,
+            md,
+            "max_thread_body_bytes",
+            max_thread_body_bytes as u64,
+        );
+        md = md.h3("Reply");
+        md = Self::render_setting_input(env, md, "max_reply_bytes", max_reply_bytes as u64);
+        md = md
+            .form_link_to("Update Size Limits", "admin", "set_post_size_limits")
+            .raw_str("\n</div>\n")
+            .newline();
 
-        md = md.newline()
-            .note("Controls who can access this board. Private boards require membership to view or post content.")
+        // Thread render budget (boards without the getter use the default of 12288)
+        let render_budget = size_limit("get_render_budget", 12_288);
+        md = md
+            .h3("Thread render budget")
+            .text("Approximate bytes shown on a thread page (4096-65536). ")
+            .text("Longer posts get a \"Show full post\" link.")
+            .newline()
+            .raw_str("<div data-form>\n")
             .raw_str("<input type=\"hidden\" name=\"board_id\" value=\"")
             .number(board_id as u32)
             .raw_str("\" />\n");
+        md = Self::render_setting_input(env, md, "budget", render_budget as u64);
+        md = md
+            .form_link_to("Update Budget", "admin", "set_render_budget")
+            .raw_str("\n</div>\n")
+            .newline();
 
-        if is_private {
-            md = md.form_link_to("Make Public", "admin", "make_public");
-        } else {
-            md = md.form_link_to("Make Private", "admin", "make_private");
-        }
+        // Inline reply nesting (boards without the getter use the default of 4)
+        let collapse_depth = size_limit("get_collapse_depth", 4);
+        md = md
+            .h3("Reply nesting")
+            .text("Reply depth shown inline on a thread page (at least 1). ")
+            .text("Deeper replies get a \"Continue this thread\" link.")
+            .newline()
+            .raw_str("<div data-form>\n")
+            .raw_str("<input type=\"hidden\" name=\"board_id\" value=\"")
+            .number(board_id as u32)
+            .raw_str("\" />\n");
+        md = Self::render_setting_input(env, md, "depth", collapse_depth as u64);
+        md = md
+            .form_link_to("Update Nesting", "admin", "set_collapse_depth")
+            .raw_str("\n</div>\n")
+            .newline();
 
-        md = md.newline().newline();
+        // Reply expanders (boards without the getter always embed children)
+        let collapse_children_over = size_limit("get_collapse_children_over", 0);
+        md = md
+            .h3("Reply expanders")
+            .text("Replies with more children than this show a \"Show N replies\" link ")
+            .text("instead of loading them with the page (0 = always load).")
+            .newline()
+            .raw_str("<div data-form>\n")
+            .raw_str("<input type=\"hidden\" name=\"board_id\" value=\"")
+            .number(board_id as u32)
+            .raw_str("\" />\n");
+        md = Self::render_setting_input(env, md, "count", collapse_children_over as u64);
+        md = md
+            .form_link_to("Update Expanders", "admin", "set_collapse_children_over")
+            .raw_str("\n</div>\n")
+            .newline();
 
-        // Board read-only status
-        let is_readonly = env
-            .try_invoke_contract::<bool, soroban_sdk::Error>(
+        // Low-score collapse (boards without the getter use the default of -3)
+        let collapse_score = env
+            .try_invoke_contract::<i32, soroban_sdk::Error>(
                 &board_contract,
-                &Symbol::new(env, "is_readonly"),
+                &Symbol::new(env, "get_collapse_score"),
                 Vec::from_array(env, [board_id.into_val(env)]),
             )
-            .unwrap_or(Ok(false))
-            .unwrap_or(false);
-
-        md = md.h2("Posting Status").text("**Current status:** ");
-
-        if is_readonly {
-            md = md.text("Read-only (no new posts allowed)").newline();
-        } else {
-            md = md.text("Open (members can post)").newline();
-        }
-
-        md = md.newline()
-            .note("Controls whether new threads and replies can be created. Use read-only mode to archive a board.")
+            .ok()
+            .and_then(|r| r.ok())
+            .unwrap_or(-3);
+        md = md
+            .h3("Low-score replies")
+            .text("Replies scoring below this start collapsed (moderators always see them).")
+            .newline()
+            .raw_str("<div data-form>\n")
             .raw_str("<input type=\"hidden\" name=\"board_id\" value=\"")
             .number(board_id as u32)
-            .raw_str("\" />\n");
+            .raw_str("\" />\n")
+            .raw_str("<input type=\"text\" name=\"score\" value=\"")
+            .raw_str(if collapse_score < 0 { "-" } else { "" })
+            .number(collapse_score.unsigned_abs())
+            .raw_str("\" />\n")
+            .newline()
+            .form_link_to("Update Threshold", "admin", "set_collapse_score")
+            .raw_str("\n</div>\n")
+            .newline();
 
-        if is_readonly {
-            md = md.form_link_to("Enable Posting", "admin", "enable_posting");
-        } else {
-            md = md.form_link_to("Make Read-Only", "admin", "make_readonly");
+        // Wiki editors (boards without the getter let members edit)
+        let wiki_edit_role = env
+            .try_invoke_contract::<Role, soroban_sdk::Error>(
+                &board_contract,
+                &Symbol::new(env, "get_wiki_edit_role"),
+                Vec::from_array(env, [board_id.into_val(env)]),
+            )
+            .ok()
+            .and_then(|r| r.ok())
+            .unwrap_or(Role::Member);
+        md = md
+            .h3("Wiki editors")
+            .text("Lowest role that can create and edit the board's wiki pages.")
+            .newline()
+            .raw_str("<div data-form>\n")
+            .raw_str("<input type=\"hidden\" name=\"board_id\" value=\"")
+            .number(board_id as u32)
+            .raw_str("\" />\n")
+            .raw_str("<select name=\"role\">\n");
+        let role_options: [(&str, &str, Role); 2] = [
+            ("member", "Members", Role::Member),
+            ("moderator", "Moderators", Role::Moderator),
+        ];
+        for (value, label, option) in role_options.iter() {
+            md = md
+                .raw_str("<option value=\"")
+                .raw_str(value)
+                .raw_str(if *option == wiki_edit_role {
+                    "\" selected>"
+                } else {
+                    "\">"
+                })
+                .raw_str(label)
+                .raw_str("</option>\n");
         }
+        md = md
+            .raw_str("</select>\n")
+            .form_link_to("Update Wiki Editors", "admin", "set_wiki_edit_role")
+            .raw_str("\n</div>\n")
+            .newline();
 
-        md = md.newline().newline();
-
-        // Community Management Section
-        md = md.h2("Community");
-
-        // Get community contract address via alias lookup
-        let registry: Address = env
-            .storage()
-            .instance()
-            .get(&AdminKey::Registry)
-            .expect("Not initialized");
-        let community_contract_opt: Option<Address> = env.invoke_contract(
-            &registry,
-            &Symbol::new(env, "get_contract_by_alias"),
-            Vec::from_array(env, [Symbol::new(env, "community").into_val(env)]),
-        );
-
-        if let Some(ref community_contract) = community_contract_opt {
-            // Get current community association from community contract
-            let community_id_opt: Option<u64> = env.invoke_contract(
-                community_contract,
-                &Symbol::new(env, "get_board_community"),
+        // Locale (boards without the getter have none)
+        let locale = env
+            .try_invoke_contract::<Option<Symbol>, soroban_sdk::Error>(
+                &board_contract,
+                &Symbol::new(env, "get_locale"),
                 Vec::from_array(env, [board_id.into_val(env)]),
-            );
-
-            if let Some(community_id) = community_id_opt {
-                // Board is in a community - get community info
-                let comm_args: Vec<Val> = Vec::from_array(env, [community_id.into_val(env)]);
-                let comm_opt: Option<CommunityMeta> = env.invoke_contract(
-                    community_contract,
-                    &Symbol::new(env, "get_community"),
-                    comm_args,
-                );
-
-                if let Some(community) = comm_opt {
-                    md = md
-                        .text("**Current Community:** ")
-                        .raw_str("<a href=\"render:/c/")
-                        .text_string(&community.name)
-                        .raw_str("\">")
-                        .text_string(&community.display_name)
-                        .raw_str("</a>")
-                        .newline()
-                        .newline()
-                        .note("Leaving a community makes this a standalone board.")
-                        .raw_str("<div data-form>\n")
-                        .raw_str("<input type=\"hidden\" name=\"community_id\" value=\"")
-                        .number(community_id as u32)
-                        .raw_str("\" />\n")
-                        .raw_str("<input type=\"hidden\" name=\"board_id\" value=\"")
-                        .number(board_id as u32)
-                        .raw_str("\" />\n")
-                        .raw_str("<input type=\"hidden\" name=\"caller\" value=\"")
-                        .text_string(&viewer.as_ref().unwrap().to_string())
-                        .raw_str("\" />\n")
-                        .raw_str("<input type=\"hidden\" name=\"_redirect\" value=\"/b/")
-                        .number(board_id as u32)
-                        .raw_str("/settings\" />\n")
-                        .form_link_to("Leave Community", "community", "remove_board")
-                        .raw_str("\n</div>\n")
-                        .newline();
+            )
+            .ok()
+            .and_then(|r| r.ok());
+        if let Some(locale) = locale {
+            md = md
+                .h3("Locale")
+                .text("Language of the board. Sets how dates are written and lets ")
+                .text("board lists group boards by language.")
+                .newline()
+                .raw_str("<div data-form>\n")
+                .raw_str("<input type=\"hidden\" name=\"board_id\" value=\"")
+                .number(board_id as u32)
+                .raw_str("\" />\n")
+                .raw_str("<select name=\"locale\">\n")
+                .raw_str(if locale.is_none() {
+                    "<option value=\"\" selected>Not set</option>\n"
                 } else {
-                    md = md
-                        .text("**Status:** In community (ID: ")
-                        .number(community_id as u32)
-                        .text(")")
-                        .newline();
-                }
-            } else {
-                // Board is standalone - show option to move to a community
+                    "<option value=\"\">Not set</option>\n"
+                });
+            for (code, name) in BOARD_LOCALES.iter() {
                 md = md
-                    .text("**Status:** Standalone board (not in a community)")
-                    .newline()
-                    .newline();
-
-                // Get communities the user owns
-                let count: u64 = env.invoke_contract(
-                    community_contract,
-                    &Symbol::new(env, "community_count"),
-                    Vec::new(env),
-                );
+                    .raw_str("<option value=\"")
+                    .raw_str(code)
+                    .raw_str(if locale == Some(Symbol::new(env, code)) {
+                        "\" selected>"
+                    } else {
+                        "\">"
+                    })
+                    .raw_str(name)
+                    .raw_str("</option>\n");
+            }
+            md = md
+                .raw_str("</select>\n")
+                .form_link_to("Update Locale", "admin", "set_locale")
+                .raw_str("\n</div>\n")
+                .newline();
+        }
 
-                let mut user_communities: Vec<CommunityMeta> = Vec::new(env);
-                for i in 0..count {
-                    let comm_args: Vec<Val> = Vec::from_array(env, [i.into_val(env)]);
-                    let comm_opt: Option<CommunityMeta> = env.invoke_contract(
-                        community_contract,
-                        &Symbol::new(env, "get_community"),
-                        comm_args,
-                    );
-                    if let Some(community) = comm_opt {
-                        if community.owner == *viewer.as_ref().unwrap() {
-                            user_communities.push_back(community);
-                        }
-                    }
+        // Inline images (boards without the getter store posts as written)
+        let image_policy = env
+            .try_invoke_contract::<ImagePolicy, soroban_sdk::Error>(
+                &board_contract,
+                &Symbol::new(env, "get_image_policy"),
+                Vec::from_array(env, [board_id.into_val(env)]),
+            )
+            .ok()
+            .and_then(|r| r.ok());
+        if let Some(policy) = image_policy {
+            md = md
+                .h3("Inline images")
+                .text("Images from other hosts are turned into plain links when posts are ")
+                .text("written or edited. Leave the hosts empty to allow any host.")
+                .newline()
+                .raw_str("<div data-form>\n")
+                .raw_str("<input type=\"hidden\" name=\"board_id\" value=\"")
+                .number(board_id as u32)
+                .raw_str("\" />\n")
+                .raw_str("<input type=\"hidden\" name=\"allow_images\" value=\"false\" />\n")
+                .raw_str("<label><input type=\"checkbox\" name=\"allow_images\" value=\"true\"")
+                .raw_str(if policy.allow_images { " checked" } else { "" })
+                .raw_str(" /> Allow inline images</label>\n")
+                .raw_str("<input type=\"text\" name=\"allowed_hosts\" value=\"");
+            for (i, host) in policy.allowed_hosts.iter().enumerate() {
+                if i > 0 {
+                    md = md.raw_str(", ");
                 }
+                md = md.raw(Self::escape_attr(env, &host));
+            }
+            md = md
+                .raw_str("\" />\n")
+                .newline()
+                .form_link_to("Update Images", "admin", "set_image_policy")
+                .raw_str("\n</div>\n")
+                .newline();
+        }
 
-                if user_communities.is_empty() {
-                    md = md
-                        .tip("Create a community to organize your boards together.")
-                        .raw_str("<a href=\"render:/new\">Create Community</a>\n");
-                } else {
-                    // Note: Form inputs ordered to match function signature:
-                    // add_board(community_id, board_id, caller)
-                    // Wrap in data-form div to create form boundary (isolates from other forms on page)
-                    md = md
-                        .note("Move this board to one of your communities.")
-                        .raw_str("<div data-form>\n")
-                        .raw_str("<label>Select Community:</label>\n")
-                        .raw_str("<select name=\"community_id\">\n");
-
-                    for i in 0..user_communities.len() {
-                        let comm = user_communities.get(i).unwrap();
-                        md = md
-                            .raw_str("<option value=\"")
-                            .number(comm.id as u32)
-                            .raw_str("\">")
-                            .text_string(&comm.display_name)
-                            .raw_str("</option>\n");
-                    }
-
+        // Blocked terms (boards without the getter have no blocklist)
+        let blocked_terms = env
+            .try_invoke_contract::<Vec<String>, soroban_sdk::Error>(
+                &board_contract,
+                &Symbol::new(env, "list_blocked_terms"),
+                Vec::from_array(env, [board_id.into_val(env)]),
+            )
+            .ok()
+            .and_then(|r| r.ok());
+        if let Some(terms) = blocked_terms {
+            md = md
+                .h3("Blocked terms")
+                .text("Threads, replies and titles containing any of these are rejected. ")
+                .text("Matching ignores case.")
+                .newline();
+            if terms.is_empty() {
+                md = md.paragraph("No blocked terms.");
+            } else {
+                md = md.raw_str("<ul class=\"blocked-terms\">\n");
+                for term in terms.iter() {
                     md = md
-                        .raw_str("</select>\n")
-                        .raw_str("<input type=\"hidden\" name=\"board_id\" value=\"")
-                        .number(board_id as u32)
-                        .raw_str("\" />\n")
-                        .raw_str("<input type=\"hidden\" name=\"caller\" value=\"")
-                        .text_string(&viewer.as_ref().unwrap().to_string())
-                        .raw_str("\" />\n")
-                        .raw_str("<input type=\"hidden\" name=\"_redirect\" value=\"/b/")
-                        .number(board_id as u32)
-                        .raw_str("/settings\" />\n")
-                        .newline()
-                        .form_link_to("Move to Community", "community", "add_board")
-                        .raw_str("\n</div>\n")
-                        .newline();
+                        .raw_str("<li><code>")
+                        .raw(Self::escape_attr(env, &term))
+                        .raw_str("</code></li>\n");
                 }
+                md = md.raw_str("</ul>\n");
             }
-        } else {
-            md = md.text("Community features not available.").newline();
+            md = md
+                .raw_str("<div data-form>\n")
+                .raw_str("<input type=\"hidden\" name=\"board_id\" value=\"")
+                .number(board_id as u32)
+                .raw_str("\" />\n")
+                .input("term", "Word or URL fragment")
+                .newline()
+                .form_link_to("Block Term", "admin", "add_blocked_term")
+                .text(" ")
+                .form_link_to("Unblock Term", "admin", "remove_blocked_term")
+                .raw_str("\n</div>\n")
+                .newline();
         }
 
-        md = md.newline();
+        // Reply cooldown (skipped for boards without one)
+        let reply_cooldown = env
+            .try_invoke_contract::<u64, soroban_sdk::Error>(
+                &board_contract,
+                &Symbol::new(env, "get_reply_cooldown"),
+                Vec::from_array(env, [board_id.into_val(env)]),
+            )
+            .ok()
+            .and_then(|r| r.ok());
+        if let Some(reply_cooldown) = reply_cooldown {
+            md = md
+                .h3("Reply cooldown")
+                .text("Seconds a user must wait between replies in the same thread ")
+                .text("(0 = off, at most 3600). Moderators are exempt.")
+                .newline()
+                .raw_str("<div data-form>\n")
+                .raw_str("<input type=\"hidden\" name=\"board_id\" value=\"")
+                .number(board_id as u32)
+                .raw_str("\" />\n");
+            md = Self::render_setting_input(env, md, "seconds", reply_cooldown);
+            md = md
+                .form_link_to("Update Cooldown", "admin", "set_reply_cooldown")
+                .raw_str("\n</div>\n")
+                .newline();
+        }
+
+        // Public moderation log (skipped for boards without one)
+        let modlog_public = env
+            .try_invoke_contract::<bool, soroban_sdk::Error>(
+                &board_contract,
+                &Symbol::new(env, "is_modlog_public"),
+                Vec::from_array(env, [board_id.into_val(env)]),
+            )
+            .ok()
+            .and_then(|r| r.ok());
+        if let Some(modlog_public) = modlog_public {
+            md = md
+                .h3("Public moderation log")
+                .text("Let anyone who can view the board see recent moderation actions. ")
+                .text("Who acted, which user was affected and the reasons stay moderator-only.")
+                .newline()
+                .raw_str("<div data-form>\n")
+                .raw_str("<input type=\"hidden\" name=\"board_id\" value=\"")
+                .number(board_id as u32)
+                .raw_str("\" />\n")
+                .raw_str("<input type=\"hidden\" name=\"public_modlog\" value=\"false\" />\n")
+                .raw_str("<label><input type=\"checkbox\" name=\"public_modlog\" value=\"true\"")
+                .raw_str(if modlog_public { " checked" } else { "" })
+                .raw_str(" /> Public moderation log</label>\n")
+                .newline()
+                .form_link_to("Update Log Visibility", "admin", "set_public_modlog")
+                .raw_str("\n</div>\n")
+                .newline();
+        }
+
+        // Karma thresholds (boards without the getters have none)
+        let min_karma = |getter: &str| -> i64 {
+            env.try_invoke_contract::<i64, soroban_sdk::Error>(
+                &board_contract,
+                &Symbol::new(env, getter),
+                Vec::from_array(env, [board_id.into_val(env)]),
+            )
+            .ok()
+            .and_then(|r| r.ok())
+            .unwrap_or(0)
+        };
+        let min_karma_to_post = min_karma("get_min_karma_to_post");
+        let min_karma_to_reply = min_karma("get_min_karma_to_reply");
 
         md = md
-            .h2("Quick Links")
-            .raw_str("[View Members](render:/admin/b/")
-            .number(board_id as u32)
-            .raw_str("/members)")
-            .text(" | ")
-            .raw_str("[View Invites](render:/admin/b/")
-            .number(board_id as u32)
-            .raw_str("/invites)")
-            .text(" | ")
-            .raw_str("[View Banned](render:/admin/b/")
-            .number(board_id as u32)
-            .raw_str("/banned)")
-            .text(" | ")
-            .raw_str("[View Flag Queue](render:/admin/b/")
-            .number(board_id as u32)
-            .raw_str("/flags)")
+            .h2("Karma Requirements")
+            .text("Minimum total karma needed to start threads or reply (0 = no requirement). ")
+            .text("Moderators and exempted users are not checked.")
             .newline()
-            .raw_str("[Manage Flairs](render:/admin/b/")
-            .number(board_id as u32)
-            .raw_str("/flairs)")
-            .text(" | ")
-            .raw_str("[Edit Rules](render:/admin/b/")
+            .raw_str("<div data-form>\n")
+            .raw_str("<input type=\"hidden\" name=\"board_id\" value=\"")
             .number(board_id as u32)
-            .raw_str("/rules)")
-            .text(" | ")
-            .raw_str("[Voting Config](render:/admin/b/")
+            .raw_str("\" />\n")
+            .h3("To post");
+        md = Self::render_setting_input(env, md, "min_karma_to_post", min_karma_to_post as u64);
+        md = md.h3("To reply");
+        md = Self::render_setting_input(env, md, "min_karma_to_reply", min_karma_to_reply as u64);
+        md = md
+            .form_link_to("Update Karma Requirements", "admin", "set_karma_thresholds")
+            .raw_str("\n</div>\n")
+            .newline()
+            .raw_str("<div data-form>\n")
+            .raw_str("<input type=\"hidden\" name=\"board_id\" value=\"")
             .number(board_id as u32)
-            .raw_str("/voting)")
+            .raw_str("\" />\n")
+            .h3("Exemptions")
+            .input("user", "Wallet address (G...)")
             .newline()
+            .form_link_to("Exempt User", "admin", "exempt_from_karma")
+            .text(" ")
+            .form_link_to("Remove Exemption", "admin", "remove_karma_exemption")
+            .raw_str("\n</div>\n")
             .newline();
 
-        // Danger Zone - only show to board owner
-        if let (Some(v), Some(ref creator)) = (viewer, &creator_opt) {
-            if v == creator {
-                md = md
-                    .h2("Danger Zone")
-                    .warning("These actions are irreversible.")
-                    .raw_str("[Delete Board](render:/admin/b/")
-                    .number(board_id as u32)
-                    .raw_str("/delete)")
-                    .newline();
+        // Theme picker: the registry's catalog plus the registry default
+        let registry: Address = env
+            .storage()
+            .instance()
+            .get(&AdminKey::Registry)
+            .expect("Not initialized");
+        let themes: Map<String, Address> = env
+            .try_invoke_contract::<Map<String, Address>, soroban_sdk::Error>(
+                &registry,
+                &Symbol::new(env, "list_themes"),
+                Vec::new(env),
+            )
+            .ok()
+            .and_then(|r| r.ok())
+            .unwrap_or(Map::new(env));
+        let current_theme: Option<String> = env
+            .try_invoke_contract::<Option<String>, soroban_sdk::Error>(
+                &board_contract,
+                &Symbol::new(env, "get_theme_name"),
+                Vec::from_array(env, [board_id.into_val(env)]),
+            )
+            .ok()
+            .and_then(|r| r.ok())
+            .flatten()
+            // A theme unregistered since it was picked no longer applies
+            .filter(|name| themes.contains_key(name.clone()));
+
+        md = md.h2("Theme");
+        if current_theme.is_none() {
+            md = md.raw_str("- **Default** (current)\n");
+        } else {
+            md = md.raw_str("- Default\n");
+        }
+        for (name, _) in themes.iter() {
+            md = md.raw_str("- ");
+            if current_theme.as_ref() == Some(&name) {
+                md = md.raw_str("**").text_string(&name).raw_str("** (current)");
+            } else {
+                md = md.text_string(&name);
             }
+            md = md
+                .raw_str(" · [Preview](render:/b/")
+                .number(board_id as u32)
+                .raw_str("/preview/theme/")
+                .text_string(&name)
+                .raw_str(")\n");
         }
-
-        Self::render_footer_into(env, md).build()
-    }
-
-    /// Render delete board confirmation page
-    fn render_delete(env: &Env, board_id: u64, viewer: &Option<Address>) -> Bytes {
-        // Get board contract (single contract for all boards)
-        let board_contract = Self::get_board_contract_address(env);
-
-        let mut md = MarkdownBuilder::new(env);
-
-        // Back navigation
         md = md
-            .div_start("back-nav")
-            .raw_str("<a href=\"render:/admin/b/")
+            .newline()
+            .raw_str("<div data-form>\n")
+            .raw_str("<input type=\"hidden\" name=\"board_id\" value=\"")
             .number(board_id as u32)
-            .raw_str("/settings\" class=\"back-link\">← Back to Settings</a>")
-            .div_end()
+            .raw_str("\" />\n")
+            .input("name", "Theme name")
+            .newline()
+            .form_link_to("Use Theme", "admin", "set_board_theme")
+            .text(" ")
+            .form_link_to("Use Default", "admin", "reset_board_theme")
+            .raw_str("\n</div>\n")
             .newline();
 
-        // Get board config and metadata from board contract (now requires board_id)
-        let config: BoardConfig = env.invoke_contract(
-            &board_contract,
-            &Symbol::new(env, "get_config"),
-            Vec::from_array(env, [board_id.into_val(env)]),
-        );
-
-        let creator_opt: Option<Address> = env.invoke_contract(
-            &board_contract,
-            &Symbol::new(env, "get_creator"),
-            Vec::from_array(env, [board_id.into_val(env)]),
-        );
-
-        let thread_count: u64 = env.invoke_contract(
-            &board_contract,
-            &Symbol::new(env, "thread_count"),
-            Vec::from_array(env, [board_id.into_val(env)]),
-        );
-
-        md = md.h1("Delete Board");
+        // Home page category, from the registry's list
+        let categories: Vec<Category> = env
+            .try_invoke_contract::<Vec<Category>, soroban_sdk::Error>(
+                &registry,
+                &Symbol::new(env, "list_categories"),
+                Vec::new(env),
+            )
+            .ok()
+            .and_then(|r| r.ok())
+            .unwrap_or(Vec::new(env));
+        if let Some(user) = viewer.as_ref().filter(|_| !categories.is_empty()) {
+            let current_category: u32 = env
+                .try_invoke_contract::<u32, soroban_sdk::Error>(
+                    &registry,
+                    &Symbol::new(env, "get_board_category"),
+                    Vec::from_array(env, [board_id.into_val(env)]),
+                )
+                .ok()
+                .and_then(|r| r.ok())
+                .unwrap_or(0);
 
-        // Check if viewer is logged in
-        if viewer.is_none() {
-            return md
-                .warning("Please connect your wallet to access this page.")
-                .build();
+            md = md
+                .h2("Category")
+                .text("Groups this board on the home page.")
+                .newline()
+                .raw_str("<div data-form>\n")
+                .raw_str("<input type=\"hidden\" name=\"board_id\" value=\"")
+                .number(board_id as u32)
+                .raw_str("\" />\n")
+                .raw_str("<select name=\"category_id\">\n")
+                .raw_str(if current_category == 0 {
+                    "<option value=\"0\" selected>Uncategorized</option>\n"
+                } else {
+                    "<option value=\"0\">Uncategorized</option>\n"
+                });
+            for category in categories.iter() {
+                md = md
+                    .raw_str("<option value=\"")
+                    .number(category.id)
+                    .raw_str(if category.id == current_category {
+                        "\" selected>"
+                    } else {
+                        "\">"
+                    })
+                    .text_string(&category.name)
+                    .raw_str("</option>\n");
+            }
+            md = md
+                .raw_str("</select>\n")
+                .raw_str("<input type=\"hidden\" name=\"caller\" value=\"")
+                .text_string(&user.to_string())
+                .raw_str("\" />\n")
+                .raw_str("<input type=\"hidden\" name=\"_redirect\" value=\"/b/")
+                .number(board_id as u32)
+                .raw_str("/settings\" />\n")
+                .form_link_to("Update Category", "registry", "set_board_category")
+                .raw_str("\n</div>\n")
+                .newline();
         }
 
-        let viewer_addr = viewer.as_ref().unwrap();
-
-        // Check if viewer is the board owner
-        let is_owner = creator_opt
-            .as_ref()
-            .map(|c| c == viewer_addr)
-            .unwrap_or(false);
-        if !is_owner {
-            return md
-                .warning("Only the board owner can delete this board.")
-                .build();
-        }
+        // Board CSS overrides (boards without the getter have none)
+        let custom_css: String = env
+            .try_invoke_contract::<Option<String>, soroban_sdk::Error>(
+                &board_contract,
+                &Symbol::new(env, "get_custom_css"),
+                Vec::from_array(env, [board_id.into_val(env)]),
+            )
+            .ok()
+            .and_then(|r| r.ok())
+            .flatten()
+            .unwrap_or(String::from_str(env, ""));
 
-        // Show warning and confirmation
         md = md
-            .warning("This action is permanent and cannot be undone!")
+            .h2("Custom CSS")
+            .text("Appended after the theme stylesheet (max 8 KB). ")
+            .text("@import, url(, < and backslashes are not allowed.")
             .newline()
-            .paragraph("You are about to delete:")
-            .raw_str("<div class=\"board-card\" style=\"pointer-events: none;\"><span class=\"board-card-title\">")
-            .text_string(&config.name)
-            .raw_str("</span><span class=\"board-card-desc\">")
-            .text_string(&config.description)
-            .raw_str("</span><span class=\"board-card-meta\">")
-            .number(thread_count as u32)
-            .text(" threads")
-            .raw_str("</span></div>\n")
+            .raw_str("<div data-form>\n")
+            .raw_str("<input type=\"hidden\" name=\"board_id\" value=\"")
+            .number(board_id as u32)
+            .raw_str("\" />\n")
+            .textarea_markdown_with_value_noparse_string(
+                "css",
+                8,
+                ".board-header { border-color: #7857e1; }",
+                &custom_css,
+            )
+            .newline()
+            .form_link_to("Update CSS", "admin", "set_custom_css")
+            .text(" ")
+            .form_link_to("Reset to default", "admin", "reset_custom_css")
+            .raw_str("\n</div>\n")
             .newline();
 
-        if thread_count > 0 {
-            md = md.note(
-                "This board contains threads. All threads and replies will be permanently deleted.",
-            );
-        }
+        let invite_ttl: u64 = env.invoke_contract(
+            &permissions,
+            &Symbol::new(env, "get_invite_request_ttl"),
+            Vec::from_array(env, [board_id.into_val(env)]),
+        );
 
         md = md
+            .h2("Invite Requests")
+            .text("Seconds before a pending invite request expires (0 = never expire).")
             .newline()
-            .paragraph("To confirm deletion, click the button below:")
-            .newline()
+            .raw_str("<div data-form>\n")
             .raw_str("<input type=\"hidden\" name=\"board_id\" value=\"")
             .number(board_id as u32)
-            .raw_str("\" />\n")
-            .raw_str("<input type=\"hidden\" name=\"caller\" value=\"")
-            .text_string(&viewer_addr.to_string())
-            .raw_str("\" />\n")
-            .raw_str("<input type=\"hidden\" name=\"_redirect\" value=\"/\" />\n")
-            .newline()
-            .form_link_to("Delete Board Permanently", "main", "delete_board")
-            .newline()
-            .newline()
-            .raw_str("<a href=\"render:/admin/b/")
+            .raw_str("\" />\n");
+        md = Self::render_setting_input(env, md, "ttl_seconds", invite_ttl);
+        md = md
+            .form_link_to("Update Invite TTL", "admin", "set_invite_request_ttl")
+            .raw_str("\n</div>\n")
+            .newline();
+
+        // Board visibility setting - query board contract
+        let is_listed: bool = env
+            .try_invoke_contract::<bool, soroban_sdk::Error>(
+                &board_contract,
+                &Symbol::new(env, "get_board_listed"),
+                Vec::from_array(env, [board_id.into_val(env)]),
+            )
+            .unwrap_or(Ok(false))
+            .unwrap_or(false);
+
+        md = md.h2("Board Visibility").text("**Listed publicly:** ");
+
+        if is_listed {
+            md = md.text("Yes (appears on home page)").newline();
+        } else {
+            md = md
+                .text("No (hidden from home page, accessible via direct link)")
+                .newline();
+        }
+
+        md = md.newline()
+            .note("Controls whether this board appears in the public board list on the home page. Unlisted boards are still accessible via direct link.")
+            .raw_str("<input type=\"hidden\" name=\"board_id\" value=\"")
             .number(board_id as u32)
-            .raw_str("/settings\">Cancel</a>\n");
+            .raw_str("\" />\n");
 
-        Self::render_footer_into(env, md).build()
-    }
+        if is_listed {
+            md = md.form_link_to("Hide from Public List", "admin", "unlist_board");
+        } else {
+            md = md.form_link_to("Show on Public List", "admin", "list_board");
+        }
 
-    /// Render flair management page
-    fn render_flairs(env: &Env, board_id: u64, viewer: &Option<Address>) -> Bytes {
-        let permissions: Address = env
-            .storage()
-            .instance()
-            .get(&AdminKey::Permissions)
-            .expect("Not initialized");
+        md = md.newline().newline();
+
+        // Board access control (public/private) - get from config
+        let is_private = config_opt.as_ref().map(|c| c.is_private).unwrap_or(false);
 
-        let mut md = Self::render_nav_subpage(env, board_id).h1("Flair Management");
+        md = md.h2("Access Control").text("**Board type:** ");
 
-        // Check if viewer has admin permission
-        let can_admin = if let Some(user) = viewer {
-            let args: Vec<Val> = Vec::from_array(env, [board_id.into_val(env), user.into_val(env)]);
-            let perms: PermissionSet =
-                env.invoke_contract(&permissions, &Symbol::new(env, "get_permissions"), args);
-            perms.can_admin
+        if is_private {
+            md = md.text("Private (members only)").newline();
         } else {
-            false
-        };
+            md = md.text("Public (anyone can view and post)").newline();
+        }
 
-        if !can_admin {
-            md = md.warning("You must be an admin to manage flairs.");
-            return Self::render_footer_into(env, md).build();
+        md = md.newline()
+            .note("Controls who can access this board. Private boards require membership to view or post content.");
+
+        if is_private {
+            md = md
+                .raw_str("<input type=\"hidden\" name=\"board_id\" value=\"")
+                .number(board_id as u32)
+                .raw_str("\" />\n")
+                .form_link_to("Make Public", "admin", "make_public");
+        } else {
+            md = md.text("Making the board private is in the Danger Zone below.");
         }
 
-        // Get board contract (single contract for all boards)
-        let board_contract = Self::get_board_contract_address(env);
+        md = md.newline().newline();
 
-        // Get existing flairs (now requires board_id)
-        let flairs: Vec<FlairDef> = env.invoke_contract(
-            &board_contract,
-            &Symbol::new(env, "list_flairs"),
-            Vec::from_array(env, [board_id.into_val(env)]),
-        );
+        // Board read-only status
+        let is_readonly = env
+            .try_invoke_contract::<bool, soroban_sdk::Error>(
+                &board_contract,
+                &Symbol::new(env, "is_readonly"),
+                Vec::from_array(env, [board_id.into_val(env)]),
+            )
+            .unwrap_or(Ok(false))
+            .unwrap_or(false);
 
-        md = md.h2("Current Flairs");
+        md = md.h2("Posting Status").text("**Current status:** ");
 
-        if flairs.is_empty() {
-            md = md.paragraph("No flairs defined yet. Create one below.");
+        if is_readonly {
+            md = md.text("Read-only (no new posts allowed)").newline();
         } else {
-            for i in 0..flairs.len() {
-                let flair = flairs.get(i).unwrap();
-                md = md.hr()
-                    .raw_str("<div class=\"flair-preview\" style=\"display: inline-block; padding: 2px 8px; border-radius: 3px; background: ")
-                    .text_string(&flair.bg_color)
-                    .raw_str("; color: ")
-                    .text_string(&flair.color)
-                    .raw_str(";\">")
-                    .text_string(&flair.name)
-                    .raw_str("</div>\n\n")
-                    .raw_str("- **ID:** ").number(flair.id).newline()
-                    .raw_str("- **Enabled:** ").text(if flair.enabled { "Yes" } else { "No" }).newline()
-                    .raw_str("- **Required:** ").text(if flair.required { "Yes" } else { "No" }).newline()
-                    .raw_str("- **Mod Only:** ").text(if flair.mod_only { "Yes" } else { "No" }).newline()
-                    .newline()
-                    // Edit button - links to separate edit page
-                    .raw_str("[Edit](render:/admin/b/")
-                    .number(board_id as u32)
-                    .raw_str("/flairs/")
-                    .number(flair.id)
-                    .raw_str("/edit)");
+            md = md.text("Open (members can post)").newline();
+        }
 
-                // Disable button (only for enabled flairs) - wrapped in data-form to isolate inputs
-                if flair.enabled {
-                    md = md
-                        .text(" | ")
-                        .raw_str("<div data-form>\n")
-                        .raw_str("<input type=\"hidden\" name=\"board_id\" value=\"")
-                        .number(board_id as u32)
-                        .raw_str("\" />\n")
-                        .raw_str("<input type=\"hidden\" name=\"flair_id\" value=\"")
-                        .number(flair.id)
-                        .raw_str("\" />\n")
-                        .form_link_to("Disable", "admin", "disable_flair")
-                        .raw_str("</div>\n");
-                }
+        md = md.newline()
+            .note("Controls whether new threads and replies can be created. Use read-only mode to archive a board.");
 
-                md = md.newline();
-            }
+        if is_readonly {
+            md = md
+                .raw_str("<input type=\"hidden\" name=\"board_id\" value=\"")
+                .number(board_id as u32)
+                .raw_str("\" />\n")
+                .form_link_to("Enable Posting", "admin", "enable_posting");
+        } else {
+            md = md.text("Making the board read-only is in the Danger Zone below.");
         }
 
-        // Create new flair form - wrapped in data-form to isolate from disable buttons
-        // Pre-fill color fields with defaults to ensure they're never empty
-        md = md.hr()
-            .h2("Create New Flair")
-            .raw_str("<div data-form>\n")
-            .raw_str("<input type=\"hidden\" name=\"board_id\" value=\"")
-            .number(board_id as u32)
-            .raw_str("\" />\n")
-            .input("name", "Flair name (max 32 chars)")
-            .newline()
-            .input_with_value("color", "Text color", "#ffffff")
-            .newline()
-            .input_with_value("bg_color", "Background color", "#808080")
-            .newline()
-            // Hidden inputs provide default "false" when checkbox is unchecked
-            .raw_str("<input type=\"hidden\" name=\"required\" value=\"false\" />\n")
-            .raw_str("<label><input type=\"checkbox\" name=\"required\" value=\"true\" /> Required for new posts</label>\n")
-            .raw_str("<input type=\"hidden\" name=\"mod_only\" value=\"false\" />\n")
-            .raw_str("<label><input type=\"checkbox\" name=\"mod_only\" value=\"true\" /> Moderator only</label>\n")
-            .form_link_to("Create Flair", "admin", "create_flair")
-            .raw_str("</div>\n");
+        md = md.newline().newline();
 
-        Self::render_footer_into(env, md).build()
-    }
+        // Community Management Section
+        md = md.h2("Community");
+        let mut leave_community_id: Option<u64> = None;
 
-    /// Render flair edit page
-    fn render_flair_edit(
-        env: &Env,
-        board_id: u64,
-        flair_id: u32,
-        viewer: &Option<Address>,
-    ) -> Bytes {
-        let permissions: Address = env
+        // Get community contract address via alias lookup
+        let registry: Address = env
             .storage()
             .instance()
-            .get(&AdminKey::Permissions)
+            .get(&AdminKey::Registry)
             .expect("Not initialized");
-
-        let mut md = Self::render_nav_subpage(env, board_id).h1("Edit Flair");
-
-        // Check if viewer has admin permission
-        let can_admin = if let Some(user) = viewer {
-            let args: Vec<Val> = Vec::from_array(env, [board_id.into_val(env), user.into_val(env)]);
-            let perms: PermissionSet =
-                env.invoke_contract(&permissions, &Symbol::new(env, "get_permissions"), args);
-            perms.can_admin
-        } else {
-            false
-        };
-
-        if !can_admin {
-            md = md.warning("You must be an admin to edit flairs.");
-            return Self::render_footer_into(env, md).build();
-        }
-
-        // Get board contract (single contract for all boards)
-        let board_contract = Self::get_board_contract_address(env);
-
-        // Get the flair (now requires board_id)
-        let flair_opt: Option<FlairDef> = env.invoke_contract(
-            &board_contract,
-            &Symbol::new(env, "get_flair"),
-            Vec::from_array(env, [board_id.into_val(env), flair_id.into_val(env)]),
+        let community_contract_opt: Option<Address> = env.invoke_contract(
+            &registry,
+            &Symbol::new(env, "get_contract_by_alias"),
+            Vec::from_array(env, [Symbol::new(env, "community").into_val(env)]),
         );
 
-        if let Some(flair) = flair_opt {
-            // Show current flair preview
-            md = md
-                .raw_str("<div class=\"flair-preview\" style=\"display: inline-block; padding: 2px 8px; border-radius: 3px; margin-bottom: 16px; background: ")
-                .text_string(&flair.bg_color)
-                .raw_str("; color: ")
-                .text_string(&flair.color)
-                .raw_str(";\">")
-                .text_string(&flair.name)
-                .raw_str("</div>\n\n")
-                .text("Editing flair ID: ").number(flair_id).newline()
-                .newline()
-                // Hidden fields
-                .raw_str("<input type=\"hidden\" name=\"board_id\" value=\"")
-                .number(board_id as u32)
-                .raw_str("\" />\n")
-                .raw_str("<input type=\"hidden\" name=\"flair_id\" value=\"")
-                .number(flair_id)
-                .raw_str("\" />\n")
-                .raw_str("<input type=\"hidden\" name=\"_redirect\" value=\"/admin/b/")
-                .number(board_id as u32)
-                .raw_str("/flairs\" />\n");
+        if let Some(ref community_contract) = community_contract_opt {
+            // Get current community association from community contract
+            let community_id_opt: Option<u64> = env.invoke_contract(
+                community_contract,
+                &Symbol::new(env, "get_board_community"),
+                Vec::from_array(env, [board_id.into_val(env)]),
+            );
 
-            // Pre-fill form with current values
-            // Name field - we need to use raw HTML to set the value
-            let name_len = flair.name.len() as usize;
-            let mut name_buf = [0u8; 64];
-            let name_copy_len = if name_len > 64 { 64 } else { name_len };
-            flair.name.copy_into_slice(&mut name_buf[..name_copy_len]);
+            if let Some(community_id) = community_id_opt {
+                // Board is in a community - get community info
+                let comm_args: Vec<Val> = Vec::from_array(env, [community_id.into_val(env)]);
+                let comm_opt: Option<CommunityMeta> = env.invoke_contract(
+                    community_contract,
+                    &Symbol::new(env, "get_community"),
+                    comm_args,
+                );
 
-            md = md
-                .raw_str("<div class=\"form-group\">\n")
-                .raw_str("<label for=\"name\">Name</label>\n")
-                .raw_str("<input type=\"text\" name=\"name\" id=\"name\" value=\"");
-            for j in 0..name_copy_len {
-                md = md.raw_str(core::str::from_utf8(&name_buf[j..j + 1]).unwrap_or(""));
-            }
-            md = md.raw_str("\" />\n").raw_str("</div>\n\n");
+                if let Some(community) = comm_opt {
+                    md = md
+                        .text("**Current Community:** ")
+                        .raw_str("<a href=\"render:/c/")
+                        .raw(Self::escape_attr(env, &community.name))
+                        .raw_str("\">")
+                        .text_string(&community.display_name)
+                        .raw_str("</a>")
+                        .newline()
+                        .newline()
+                        .text("Leaving the community is in the Danger Zone below.")
+                        .newline();
+                    leave_community_id = Some(community_id);
+                } else {
+                    md = md
+                        .text("**Status:** In community (ID: ")
+                        .number(community_id as u32)
+                        .text(")")
+                        .newline();
+                }
+            } else {
+                // Board is standalone - show option to move to a community
+                md = md
+                    .text("**Status:** Standalone board (not in a community)")
+                    .newline()
+                    .newline();
 
-            // Color field
-            let color_len = flair.color.len() as usize;
-            let mut color_buf = [0u8; 32];
-            let color_copy_len = if color_len > 32 { 32 } else { color_len };
-            flair
-                .color
-                .copy_into_slice(&mut color_buf[..color_copy_len]);
+                // Get communities the user owns from the community contract's owner
+                // index, rather than scanning every community
+                let owned_args: Vec<Val> =
+                    Vec::from_array(env, [viewer.as_ref().unwrap().into_val(env)]);
+                let user_communities: Vec<CommunityMeta> = env
+                    .try_invoke_contract::<Vec<CommunityMeta>, soroban_sdk::Error>(
+                        community_contract,
+                        &Symbol::new(env, "get_owned_communities"),
+                        owned_args,
+                    )
+                    .ok()
+                    .and_then(|r| r.ok())
+                    .unwrap_or_else(|| Vec::new(env));
 
-            md = md
-                .raw_str("<div class=\"form-group\">\n")
-                .raw_str("<label for=\"color\">Text color</label>\n")
-                .raw_str("<input type=\"text\" name=\"color\" id=\"color\" value=\"");
-            for j in 0..color_copy_len {
-                md = md.raw_str(core::str::from_utf8(&color_buf[j..j + 1]).unwrap_or(""));
-            }
-            md = md.raw_str("\" />\n").raw_str("</div>\n\n");
+                if user_communities.is_empty() {
+                    md = md
+                        .tip("Create a community to organize your boards together.")
+                        .raw_str("<a href=\"render:/new\">Create Community</a>\n");
+                } else {
+                    // Note: Form inputs ordered to match function signature:
+                    // add_board(community_id, board_id, caller)
+                    // Wrap in data-form div to create form boundary (isolates from other forms on page)
+                    md = md
+                        .note("Move this board to one of your communities.")
+                        .raw_str("<div data-form>\n")
+                        .raw_str("<label>Select Community:</label>\n")
+                        .raw_str("<select name=\"community_id\">\n");
 
-            // Background color field
-            let bg_len = flair.bg_color.len() as usize;
-            let mut bg_buf = [0u8; 32];
-            let bg_copy_len = if bg_len > 32 { 32 } else { bg_len };
-            flair.bg_color.copy_into_slice(&mut bg_buf[..bg_copy_len]);
+                    for i in 0..user_communities.len() {
+                        let comm = user_communities.get(i).unwrap();
+                        md = md
+                            .raw_str("<option value=\"")
+                            .number(comm.id as u32)
+                            .raw_str("\">")
+                            .text_string(&comm.display_name)
+                            .raw_str("</option>\n");
+                    }
 
-            md = md
-                .raw_str("<div class=\"form-group\">\n")
-                .raw_str("<label for=\"bg_color\">Background color</label>\n")
-                .raw_str("<input type=\"text\" name=\"bg_color\" id=\"bg_color\" value=\"");
-            for j in 0..bg_copy_len {
-                md = md.raw_str(core::str::from_utf8(&bg_buf[j..j + 1]).unwrap_or(""));
+                    md = md
+                        .raw_str("</select>\n")
+                        .raw_str("<input type=\"hidden\" name=\"board_id\" value=\"")
+                        .number(board_id as u32)
+                        .raw_str("\" />\n")
+                        .raw_str("<input type=\"hidden\" name=\"caller\" value=\"")
+                        .text_string(&viewer.as_ref().unwrap().to_string())
+                        .raw_str("\" />\n")
+                        .raw_str("<input type=\"hidden\" name=\"_redirect\" value=\"/b/")
+                        .number(board_id as u32)
+                        .raw_str("/settings\" />\n")
+                        .newline()
+                        .form_link_to("Move to Community", "community", "add_board")
+                        .raw_str("\n</div>\n")
+                        .newline();
+                }
             }
-            md = md.raw_str("\" />\n").raw_str("</div>\n\n");
+        } else {
+            md = md.text("Community features not available.").newline();
+        }
 
-            // Checkboxes with current values
-            md = md.raw_str("<input type=\"hidden\" name=\"required\" value=\"false\" />\n");
-            if flair.required {
-                md = md.raw_str("<label><input type=\"checkbox\" name=\"required\" value=\"true\" checked /> Required for new posts</label>\n");
-            } else {
-                md = md.raw_str("<label><input type=\"checkbox\" name=\"required\" value=\"true\" /> Required for new posts</label>\n");
-            }
+        md = md.newline();
 
-            md = md.raw_str("<input type=\"hidden\" name=\"mod_only\" value=\"false\" />\n");
-            if flair.mod_only {
-                md = md.raw_str("<label><input type=\"checkbox\" name=\"mod_only\" value=\"true\" checked /> Moderator only</label>\n");
-            } else {
-                md = md.raw_str("<label><input type=\"checkbox\" name=\"mod_only\" value=\"true\" /> Moderator only</label>\n");
-            }
+        md = md
+            .h2("Quick Links")
+            .raw_str("[View Members](render:/admin/b/")
+            .number(board_id as u32)
+            .raw_str("/members)")
+            .text(" | ")
+            .raw_str("[View Invites](render:/admin/b/")
+            .number(board_id as u32)
+            .raw_str("/invites)")
+            .text(" | ")
+            .raw_str("[View Banned](render:/admin/b/")
+            .number(board_id as u32)
+            .raw_str("/banned)")
+            .text(" | ")
+            .raw_str("[View Flag Queue](render:/admin/b/")
+            .number(board_id as u32)
+            .raw_str("/flags)")
+            .text(" | ")
+            .raw_str("[Mod Log](render:/admin/b/")
+            .number(board_id as u32)
+            .raw_str("/log)")
+            .newline()
+            .raw_str("[Manage Flairs](render:/admin/b/")
+            .number(board_id as u32)
+            .raw_str("/flairs)")
+            .text(" | ")
+            .raw_str("[Thread Templates](render:/admin/b/")
+            .number(board_id as u32)
+            .raw_str("/templates)")
+            .text(" | ")
+            .raw_str("[Edit Rules](render:/admin/b/")
+            .number(board_id as u32)
+            .raw_str("/rules)")
+            .text(" | ")
+            .raw_str("[Voting Config](render:/admin/b/")
+            .number(board_id as u32)
+            .raw_str("/voting)")
+            .newline()
+            .newline();
 
-            md = md.raw_str("<input type=\"hidden\" name=\"enabled\" value=\"false\" />\n");
-            if flair.enabled {
-                md = md.raw_str("<label><input type=\"checkbox\" name=\"enabled\" value=\"true\" checked /> Enabled</label>\n");
+        // Ownership transfer - only show to the current owner
+        if viewer.is_some() && owner_opt == *viewer {
+            md = md.h2("Transfer Ownership");
+            if let Some(ref pending) = pending_owner {
+                let pending_str = Self::format_address(env, pending);
+                md = md
+                    .text("**Pending transfer to:** `")
+                    .text_string(&pending_str)
+                    .text("`")
+                    .newline()
+                    .newline()
+                    .note("The transfer completes when the new owner accepts it. You will become an admin.")
+                    .raw_str("<div data-form>\n")
+                    .raw_str("<input type=\"hidden\" name=\"board_id\" value=\"")
+                    .number(board_id as u32)
+                    .raw_str("\" />\n")
+                    .form_link_to("Cancel Transfer", "admin", "cancel_ownership_transfer")
+                    .raw_str("\n</div>\n")
+                    .newline();
             } else {
-                md = md.raw_str("<label><input type=\"checkbox\" name=\"enabled\" value=\"true\" /> Enabled</label>\n");
+                md = md
+                    .note("Ownership moves only after the new owner accepts. You will become an admin.")
+                    .raw_str("<div data-form>\n")
+                    .raw_str("<input type=\"hidden\" name=\"board_id\" value=\"")
+                    .number(board_id as u32)
+                    .raw_str("\" />\n")
+                    .input("new_owner", "New owner wallet address (G...)")
+                    .newline()
+                    .form_link_to("Transfer Ownership", "admin", "initiate_ownership_transfer")
+                    .raw_str("\n</div>\n")
+                    .newline();
             }
+            md = md.newline();
+        }
+
+        let is_owner = viewer.is_some() && owner_opt == *viewer;
+        let is_archived: bool = env
+            .try_invoke_contract::<bool, soroban_sdk::Error>(
+                &board_contract,
+                &Symbol::new(env, "is_archived"),
+                Vec::from_array(env, [board_id.into_val(env)]),
+            )
+            .ok()
+            .and_then(|r| r.ok())
+            .unwrap_or(false);
 
+        // Unarchiving only restores settings, so it needs no confirmation
+        if is_owner && is_archived {
             md = md
-                .newline()
-                .form_link_to("Update Flair", "admin", "update_flair")
-                .newline()
-                .newline()
-                .raw_str("[Cancel](render:/admin/b/")
+                .h2("Archive")
+                .note("This board is archived. Unarchiving restores its previous settings.")
+                .raw_str("<div data-form>\n")
+                .raw_str("<input type=\"hidden\" name=\"board_id\" value=\"")
                 .number(board_id as u32)
-                .raw_str("/flairs)");
-        } else {
-            md = md.warning("Flair not found.");
+                .raw_str("\" />\n")
+                .form_link_to("Unarchive Board", "admin", "unarchive_board")
+                .raw_str("\n</div>\n")
+                .newline();
+        }
+
+        // Danger Zone - actions that lock readers out or take the board out of view.
+        // Each one requires typing the board name (or CONFIRM) to go through.
+        md = md
+            .raw_str("<div class=\"danger-zone\">\n")
+            .h2("Danger Zone")
+            .warning("Type the board name or CONFIRM next to an action to carry it out.");
+        if !is_private {
+            md = md.paragraph("Make the board private. Guests immediately lose access.");
+            md = Self::render_danger_action(md, board_id, None, "Make Private", "make_private");
+        }
+        if !is_readonly {
+            md = md.paragraph("Make the board read-only. No new threads or replies can be posted.");
+            md = Self::render_danger_action(md, board_id, None, "Make Read-Only", "make_readonly");
+        }
+        if let Some(community_id) = leave_community_id {
+            md = md.paragraph("Leave the community. This becomes a standalone board.");
+            md = Self::render_danger_action(
+                md,
+                board_id,
+                Some(community_id),
+                "Leave Community",
+                "leave_community",
+            );
+        }
+        if is_owner && !is_archived {
+            md = md.paragraph("Archive the board. It becomes read-only and unlisted.");
+            md = Self::render_danger_action(md, board_id, None, "Archive Board", "archive_board");
+        }
+        if let (Some(v), Some(ref creator)) = (viewer, &creator_opt) {
+            if v == creator {
+                md = md
+                    .paragraph("Deleting a board is irreversible.")
+                    .raw_str("[Delete Board](render:/admin/b/")
+                    .number(board_id as u32)
+                    .raw_str("/delete)")
+                    .newline();
+            }
         }
+        md = md.raw_str("</div>\n");
 
         Self::render_footer_into(env, md).build()
     }
 
-    /// Render rules editor page
-    fn render_rules(env: &Env, board_id: u64, viewer: &Option<Address>) -> Bytes {
+    /// Render delete board confirmation page
+    fn render_delete(env: &Env, board_id: u64, viewer: &Option<Address>) -> Bytes {
+        // Get board contract (single contract for all boards)
+        let board_contract = Self::get_board_contract_address(env);
+
+        let md = MarkdownBuilder::new(env);
+        let mut md = Self::render_breadcrumbs(env, md, board_id, "Delete Board").newline();
+
+        // Get board config and metadata from board contract (now requires board_id)
+        let config: BoardConfig = env.invoke_contract(
+            &board_contract,
+            &Symbol::new(env, "get_config"),
+            Vec::from_array(env, [board_id.into_val(env)]),
+        );
+
+        let creator_opt: Option<Address> = env.invoke_contract(
+            &board_contract,
+            &Symbol::new(env, "get_creator"),
+            Vec::from_array(env, [board_id.into_val(env)]),
+        );
+
+        let thread_count: u64 = env.invoke_contract(
+            &board_contract,
+            &Symbol::new(env, "thread_count"),
+            Vec::from_array(env, [board_id.into_val(env)]),
+        );
+
+        md = md.h1("Delete Board");
+
+        // Check if viewer is logged in
+        if viewer.is_none() {
+            return md
+                .warning("Please connect your wallet to access this page.")
+                .build();
+        }
+
+        let viewer_addr = viewer.as_ref().unwrap();
+
+        // Check if viewer is the board owner
+        let is_owner = creator_opt
+            .as_ref()
+            .map(|c| c == viewer_addr)
+            .unwrap_or(false);
+        if !is_owner {
+            return md
+                .warning("Only the board owner can delete this board.")
+                .build();
+        }
+
+        // Show warning and confirmation
+        md = md
+            .warning("This action is permanent and cannot be undone!")
+            .newline()
+            .paragraph("You are about to delete:")
+            .raw_str("<div class=\"board-card\" style=\"pointer-events: none;\"><span class=\"board-card-title\">")
+            .text_string(&config.name)
+            .raw_str("</span><span class=\"board-card-desc\">")
+            .text_string(&config.description)
+            .raw_str("</span><span class=\"board-card-meta\">")
+            .number(thread_count as u32)
+            .text(" threads")
+            .raw_str("</span></div>\n")
+            .newline();
+
+        if thread_count > 0 {
+            md = md.note(
+                "This board contains threads. All threads and replies will be permanently deleted.",
+            );
+        }
+
+        md = md
+            .newline()
+            .paragraph("To confirm deletion, click the button below:")
+            .newline()
+            .raw_str("<input type=\"hidden\" name=\"board_id\" value=\"")
+            .number(board_id as u32)
+            .raw_str("\" />\n")
+            .raw_str("<input type=\"hidden\" name=\"caller\" value=\"")
+            .text_string(&viewer_addr.to_string())
+            .raw_str("\" />\n")
+            .raw_str("<input type=\"hidden\" name=\"_redirect\" value=\"/\" />\n")
+            .newline()
+            .form_link_to("Delete Board Permanently", "main", "delete_board")
+            .newline()
+            .newline()
+            .raw_str("<a href=\"render:/admin/b/")
+            .number(board_id as u32)
+            .raw_str("/settings\">Cancel</a>\n");
+
+        Self::render_footer_into(env, md).build()
+    }
+
+    /// Render flair management page
+    fn render_flairs(env: &Env, board_id: u64, viewer: &Option<Address>) -> Bytes {
         let permissions: Address = env
             .storage()
             .instance()
             .get(&AdminKey::Permissions)
             .expect("Not initialized");
 
-        let mut md = Self::render_nav_subpage(env, board_id).h1("Board Rules Editor");
+        let mut md = Self::render_nav_subpage(env, board_id, "Flair Management");
 
         // Check if viewer has admin permission
         let can_admin = if let Some(user) = viewer {
@@ -1823,84 +3677,105 @@ impl BoardsAdmin {
         };
 
         if !can_admin {
-            md = md.warning("You must be an admin to edit board rules.");
+            md = md.warning("You must be an admin to manage flairs.");
             return Self::render_footer_into(env, md).build();
         }
 
         // Get board contract (single contract for all boards)
         let board_contract = Self::get_board_contract_address(env);
 
-        // Get current rules (now requires board_id)
-        let current_rules: Option<String> = env.invoke_contract(
+        // Get existing flairs (now requires board_id)
+        let flairs: Vec<FlairDef> = env.invoke_contract(
             &board_contract,
-            &Symbol::new(env, "get_rules"),
+            &Symbol::new(env, "list_flairs"),
             Vec::from_array(env, [board_id.into_val(env)]),
         );
 
-        md = md.h2("Current Rules");
+        md = md.h2("Current Flairs");
 
-        if let Some(ref rules) = current_rules {
-            if !rules.is_empty() {
-                md = md
-                    .div_start("rules-content")
-                    .text_string(rules)
-                    .div_end()
+        if flairs.is_empty() {
+            md = md.paragraph("No flairs defined yet. Create one below.");
+        } else {
+            for i in 0..flairs.len() {
+                let flair = flairs.get(i).unwrap();
+                md = md.hr()
+                    .raw_str("<div class=\"flair-preview\" style=\"display: inline-block; padding: 2px 8px; border-radius: 3px; background: ")
+                    .raw(sanitize_color(env, &flair.bg_color))
+                    .raw_str("; color: ")
+                    .raw(sanitize_color(env, &flair.color))
+                    .raw_str(";\">")
+                    .raw(Self::escape_attr(env, &flair.name))
+                    .raw_str("</div>\n\n")
+                    .raw_str("- **ID:** ").number(flair.id).newline()
+                    .raw_str("- **Enabled:** ").text(if flair.enabled { "Yes" } else { "No" }).newline()
+                    .raw_str("- **Required:** ").text(if flair.required { "Yes" } else { "No" }).newline()
+                    .raw_str("- **Mod Only:** ").text(if flair.mod_only { "Yes" } else { "No" }).newline()
                     .newline()
-                    .raw_str("<input type=\"hidden\" name=\"board_id\" value=\"")
+                    // Edit button - links to separate edit page
+                    .raw_str("[Edit](render:/admin/b/")
                     .number(board_id as u32)
-                    .raw_str("\" />\n")
-                    .form_link_to("Clear Rules", "admin", "clear_rules")
-                    .newline();
-            } else {
-                md = md.paragraph("No rules set.");
+                    .raw_str("/flairs/")
+                    .number(flair.id)
+                    .raw_str("/edit)");
+
+                // Disable button (only for enabled flairs) - wrapped in data-form to isolate inputs
+                if flair.enabled {
+                    md = md
+                        .text(" | ")
+                        .raw_str("<div data-form>\n")
+                        .raw_str("<input type=\"hidden\" name=\"board_id\" value=\"")
+                        .number(board_id as u32)
+                        .raw_str("\" />\n")
+                        .raw_str("<input type=\"hidden\" name=\"flair_id\" value=\"")
+                        .number(flair.id)
+                        .raw_str("\" />\n")
+                        .form_link_to("Disable", "admin", "disable_flair")
+                        .raw_str("</div>\n");
+                }
+
+                md = md.newline();
             }
-        } else {
-            md = md.paragraph("No rules set.");
         }
 
-        // Edit rules form
+        // Create new flair form - wrapped in data-form to isolate from disable buttons
+        // Pre-fill color fields with defaults to ensure they're never empty
         md = md.hr()
-            .h2("Edit Rules")
-            .note("Rules are displayed on the board page and when creating new threads. Use plain text or Markdown.")
+            .h2("Create New Flair")
+            .raw_str("<div data-form>\n")
             .raw_str("<input type=\"hidden\" name=\"board_id\" value=\"")
             .number(board_id as u32)
             .raw_str("\" />\n")
-            .raw_str("<input type=\"hidden\" name=\"_redirect\" value=\"/admin/b/")
-            .number(board_id as u32)
-            .raw_str("/rules\" />\n")
-            .textarea_markdown("rules", 10, "Enter board rules here...");
-
-        // Pre-fill with current rules if available
-        if let Some(rules) = current_rules {
-            if !rules.is_empty() {
-                // Note: Can't pre-fill textarea in this setup, user needs to re-enter
-                md = md.newline().note(
-                    "Copy existing rules above and paste into the text area if you want to edit.",
-                );
-            }
-        }
-
-        md = md
+            .input("name", "Flair name (max 32 chars)")
             .newline()
-            .form_link_to("Save Rules", "admin", "set_rules");
+            .input_with_value("color", "Text color", "#ffffff")
+            .newline()
+            .input_with_value("bg_color", "Background color", "#808080")
+            .newline()
+            // Hidden inputs provide default "false" when checkbox is unchecked
+            .raw_str("<input type=\"hidden\" name=\"required\" value=\"false\" />\n")
+            .raw_str("<label><input type=\"checkbox\" name=\"required\" value=\"true\" /> Required for new posts</label>\n")
+            .raw_str("<input type=\"hidden\" name=\"mod_only\" value=\"false\" />\n")
+            .raw_str("<label><input type=\"checkbox\" name=\"mod_only\" value=\"true\" /> Moderator only</label>\n")
+            .form_link_to("Create Flair", "admin", "create_flair")
+            .raw_str("</div>\n");
 
         Self::render_footer_into(env, md).build()
     }
 
-    /// Render voting configuration page
-    fn render_voting(env: &Env, board_id: u64, viewer: &Option<Address>) -> Bytes {
+    /// Render flair edit page
+    fn render_flair_edit(
+        env: &Env,
+        board_id: u64,
+        flair_id: u32,
+        viewer: &Option<Address>,
+    ) -> Bytes {
         let permissions: Address = env
             .storage()
             .instance()
             .get(&AdminKey::Permissions)
             .expect("Not initialized");
-        let registry: Address = env
-            .storage()
-            .instance()
-            .get(&AdminKey::Registry)
-            .expect("Not initialized");
 
-        let mut md = Self::render_nav_subpage(env, board_id).h1("Voting Configuration");
+        let mut md = Self::render_nav_subpage(env, board_id, "Edit Flair");
 
         // Check if viewer has admin permission
         let can_admin = if let Some(user) = viewer {
@@ -1913,661 +3788,1166 @@ impl BoardsAdmin {
         };
 
         if !can_admin {
-            md = md.warning("You must be an admin to configure voting.");
+            md = md.warning("You must be an admin to edit flairs.");
             return Self::render_footer_into(env, md).build();
         }
 
-        // Get voting contract from registry
-        let voting_contract: Option<Address> = env.invoke_contract(
-            &registry,
-            &Symbol::new(env, "get_voting_contract"),
-            Vec::new(env),
-        );
-
-        if let Some(voting_addr) = voting_contract {
-            // Get current config
-            let config: Option<VotingConfig> = env
-                .try_invoke_contract::<VotingConfig, soroban_sdk::Error>(
-                    &voting_addr,
-                    &Symbol::new(env, "get_voting_config"),
-                    Vec::from_array(env, [board_id.into_val(env)]),
-                )
-                .ok()
-                .and_then(|r| r.ok());
+        // Get board contract (single contract for all boards)
+        let board_contract = Self::get_board_contract_address(env);
 
-            md = md.h2("Current Configuration");
+        // Get the flair (now requires board_id)
+        let flair_opt: Option<FlairDef> = env.invoke_contract(
+            &board_contract,
+            &Symbol::new(env, "get_flair"),
+            Vec::from_array(env, [board_id.into_val(env), flair_id.into_val(env)]),
+        );
 
-            if let Some(cfg) = config {
-                md = md
-                    .raw_str("- **Voting enabled:** ")
-                    .text(if cfg.enabled { "Yes" } else { "No" })
-                    .newline()
-                    .raw_str("- **Allow downvotes:** ")
-                    .text(if cfg.allow_downvotes { "Yes" } else { "No" })
-                    .newline()
-                    .raw_str("- **Karma tracking:** ")
-                    .text(if cfg.karma_enabled { "Yes" } else { "No" })
-                    .newline()
-                    .raw_str("- **Karma multiplier:** ")
-                    .number(cfg.karma_multiplier)
-                    .newline();
-            } else {
-                md = md
-                    .paragraph("Using default configuration (voting enabled, downvotes allowed).");
-            }
-
-            // Update form
-            md = md.hr()
-                .h2("Update Configuration")
+        if let Some(flair) = flair_opt {
+            // Show current flair preview
+            md = md
+                .raw_str("<div class=\"flair-preview\" style=\"display: inline-block; padding: 2px 8px; border-radius: 3px; margin-bottom: 16px; background: ")
+                .raw(sanitize_color(env, &flair.bg_color))
+                .raw_str("; color: ")
+                .raw(sanitize_color(env, &flair.color))
+                .raw_str(";\">")
+                .raw(Self::escape_attr(env, &flair.name))
+                .raw_str("</div>\n\n")
+                .text("Editing flair ID: ").number(flair_id).newline()
+                .newline()
+                // Hidden fields
                 .raw_str("<input type=\"hidden\" name=\"board_id\" value=\"")
                 .number(board_id as u32)
                 .raw_str("\" />\n")
+                .raw_str("<input type=\"hidden\" name=\"flair_id\" value=\"")
+                .number(flair_id)
+                .raw_str("\" />\n")
                 .raw_str("<input type=\"hidden\" name=\"_redirect\" value=\"/admin/b/")
                 .number(board_id as u32)
-                .raw_str("/voting\" />\n")
-                .raw_str("<label><input type=\"checkbox\" name=\"enabled\" value=\"true\" checked /> Enable voting</label>\n")
-                .raw_str("<label><input type=\"checkbox\" name=\"allow_downvotes\" value=\"true\" checked /> Allow downvotes</label>\n")
-                .raw_str("<label><input type=\"checkbox\" name=\"karma_enabled\" value=\"true\" /> Enable karma tracking</label>\n")
-                .input("karma_multiplier", "Karma multiplier (default: 1)")
+                .raw_str("/flairs\" />\n");
+
+            // Pre-fill form with current values (escaped for the value attribute)
+            md = md
+                .raw_str("<div class=\"form-group\">\n")
+                .raw_str("<label for=\"name\">Name</label>\n")
+                .raw_str("<input type=\"text\" name=\"name\" id=\"name\" value=\"")
+                .raw(Self::escape_attr(env, &flair.name))
+                .raw_str("\" />\n")
+                .raw_str("</div>\n\n")
+                .raw_str("<div class=\"form-group\">\n")
+                .raw_str("<label for=\"color\">Text color</label>\n")
+                .raw_str("<input type=\"text\" name=\"color\" id=\"color\" value=\"")
+                .raw(Self::escape_attr(env, &flair.color))
+                .raw_str("\" />\n")
+                .raw_str("</div>\n\n")
+                .raw_str("<div class=\"form-group\">\n")
+                .raw_str("<label for=\"bg_color\">Background color</label>\n")
+                .raw_str("<input type=\"text\" name=\"bg_color\" id=\"bg_color\" value=\"")
+                .raw(Self::escape_attr(env, &flair.bg_color))
+                .raw_str("\" />\n")
+                .raw_str("</div>\n\n");
+
+            // Checkboxes with current values
+            md = md.raw_str("<input type=\"hidden\" name=\"required\" value=\"false\" />\n");
+            if flair.required {
+                md = md.raw_str("<label><input type=\"checkbox\" name=\"required\" value=\"true\" checked /> Required for new posts</label>\n");
+            } else {
+                md = md.raw_str("<label><input type=\"checkbox\" name=\"required\" value=\"true\" /> Required for new posts</label>\n");
+            }
+
+            md = md.raw_str("<input type=\"hidden\" name=\"mod_only\" value=\"false\" />\n");
+            if flair.mod_only {
+                md = md.raw_str("<label><input type=\"checkbox\" name=\"mod_only\" value=\"true\" checked /> Moderator only</label>\n");
+            } else {
+                md = md.raw_str("<label><input type=\"checkbox\" name=\"mod_only\" value=\"true\" /> Moderator only</label>\n");
+            }
+
+            md = md.raw_str("<input type=\"hidden\" name=\"enabled\" value=\"false\" />\n");
+            if flair.enabled {
+                md = md.raw_str("<label><input type=\"checkbox\" name=\"enabled\" value=\"true\" checked /> Enabled</label>\n");
+            } else {
+                md = md.raw_str("<label><input type=\"checkbox\" name=\"enabled\" value=\"true\" /> Enabled</label>\n");
+            }
+
+            md = md
                 .newline()
-                .form_link_to("Update Configuration", "admin", "set_voting_config");
+                .form_link_to("Update Flair", "admin", "update_flair")
+                .newline()
+                .newline()
+                .raw_str("[Cancel](render:/admin/b/")
+                .number(board_id as u32)
+                .raw_str("/flairs)");
         } else {
-            md = md.note(
-                "Voting contract is not configured for this board. Voting features are disabled.",
-            );
+            md = md.warning("Flair not found.");
         }
 
         Self::render_footer_into(env, md).build()
     }
 
-    /// Render registry admin page
-    fn render_registry_admin(env: &Env, viewer: &Option<Address>) -> Bytes {
-        let registry: Address = env
+    /// Render thread template management page
+    fn render_templates(env: &Env, board_id: u64, viewer: &Option<Address>) -> Bytes {
+        let permissions: Address = env
             .storage()
             .instance()
-            .get(&AdminKey::Registry)
+            .get(&AdminKey::Permissions)
             .expect("Not initialized");
 
-        let mut md = MarkdownBuilder::new(env)
-            .render_link("Soroban Boards", "/")
-            .newline()
-            .hr()
-            .h1("Registry Administration");
+        let mut md = Self::render_nav_subpage(env, board_id, "Thread Templates");
 
-        if viewer.is_none() {
-            md = md.warning("Please connect your wallet to access registry admin.");
+        // Check if viewer has admin permission
+        let can_admin = if let Some(user) = viewer {
+            let args: Vec<Val> = Vec::from_array(env, [board_id.into_val(env), user.into_val(env)]);
+            let perms: PermissionSet =
+                env.invoke_contract(&permissions, &Symbol::new(env, "get_permissions"), args);
+            perms.can_admin
+        } else {
+            false
+        };
+
+        if !can_admin {
+            md = md.warning("You must be an admin to manage templates.");
             return Self::render_footer_into(env, md).build();
         }
 
-        let viewer_addr = viewer.as_ref().unwrap();
-
-        // Get all admins
-        let admins: Vec<Address> =
-            env.invoke_contract(&registry, &Symbol::new(env, "get_admins"), Vec::new(env));
-
-        // Check if viewer is an admin
-        let viewer_is_admin: bool = env.invoke_contract(
-            &registry,
-            &Symbol::new(env, "is_admin"),
-            Vec::from_array(env, [viewer_addr.into_val(env)]),
+        let board_contract = Self::get_board_contract_address(env);
+        let templates: Vec<ThreadTemplate> = env.invoke_contract(
+            &board_contract,
+            &Symbol::new(env, "list_templates"),
+            Vec::from_array(env, [board_id.into_val(env)]),
         );
 
-        md = md.h2("Current Admins").raw_str("<ul>\n");
+        md = md
+            .h2("Current Templates")
+            .paragraph("Templates are offered on the new thread form. Deleting one does not change threads started from it.");
 
-        for i in 0..admins.len() {
-            let admin = admins.get(i).unwrap();
-            md = md
-                .raw_str("<li><code>")
-                .text_string(&admin.to_string())
-                .raw_str("</code></li>\n");
+        if templates.is_empty() {
+            md = md.paragraph("No templates defined yet. Create one below.");
         }
-
-        md = md.raw_str("</ul>\n").newline();
-
-        // If viewer is admin, show add/remove admin forms
-        if viewer_is_admin {
+        for template in templates.iter() {
+            // Delete button wrapped in data-form to isolate its inputs
             md = md
-                .h2("Add Admin")
-                .raw_str("<form>\n")
-                .raw_str("<input type=\"text\" name=\"new_admin\" placeholder=\"Address (G...)\" style=\"width:100%;max-width:400px;\" />\n")
-                .raw_str("<p><a class=\"soroban-action\" href=\"form:@registry:add_admin\">Add Admin</a></p>\n")
-                .raw_str("</form>\n")
+                .hr()
+                .raw_str("**")
+                .raw(Self::escape_attr(env, &template.name))
+                .raw_str("**\n\n")
+                .raw_str("<div data-form>\n")
+                .raw_str("<input type=\"hidden\" name=\"board_id\" value=\"")
+                .number(board_id as u32)
+                .raw_str("\" />\n")
+                .raw_str("<input type=\"hidden\" name=\"template_id\" value=\"")
+                .number(template.id)
+                .raw_str("\" />\n")
+                .form_link_to("Delete", "admin", "delete_template")
+                .raw_str("</div>\n")
                 .newline();
-
-            if admins.len() > 1 {
-                md = md
-                    .h2("Remove Admin")
-                    .raw_str("<form>\n")
-                    .raw_str("<input type=\"text\" name=\"admin_to_remove\" placeholder=\"Address (G...)\" style=\"width:100%;max-width:400px;\" />\n")
-                    .raw_str("<p><a class=\"soroban-action\" href=\"form:@registry:remove_admin\">Remove Admin</a></p>\n")
-                    .raw_str("</form>\n")
-                    .newline();
-            }
         }
 
-        // Show viewer's address for reference
         md = md
+            .hr()
+            .h2("Create New Template")
+            .raw_str("<div data-form>\n")
+            .raw_str("<input type=\"hidden\" name=\"board_id\" value=\"")
+            .number(board_id as u32)
+            .raw_str("\" />\n")
+            .input("name", "Template name (max 32 chars)")
             .newline()
-            .h2("Your Address")
-            .raw_str("<p><code>")
-            .text_string(&viewer_addr.to_string())
-            .raw_str("</code>");
-
-        if viewer_is_admin {
-            md = md.raw_str(" <span style=\"color:green;\">(Admin)</span>");
-        }
-
-        md = md.raw_str("</p>");
-
-        // Add link back to home
-        md = md.newline().render_link("← Back to Home", "/");
+            .textarea_markdown(
+                "body",
+                8,
+                "Steps to reproduce / Expected / Actual (max 4 KB)",
+            )
+            .newline()
+            .form_link_to("Create Template", "admin", "create_template")
+            .raw_str("</div>\n");
 
         Self::render_footer_into(env, md).build()
     }
 
-    // ========================================================================
-    // Site Settings Render Functions
-    // ========================================================================
+    /// Render rules editor page
+    fn render_rules(env: &Env, board_id: u64, viewer: &Option<Address>) -> Bytes {
+        let permissions: Address = env
+            .storage()
+            .instance()
+            .get(&AdminKey::Permissions)
+            .expect("Not initialized");
 
-    /// Navigation for site settings pages
-    fn render_settings_nav(env: &Env) -> MarkdownBuilder<'_> {
-        let aliases = Self::fetch_aliases(env);
-        MarkdownBuilder::new(env)
-            .raw(aliases) // Emit aliases for include resolution
-            .render_link("Soroban Boards", "/")
-            .text(" | ")
-            .render_link("Admin", "/admin/registry")
-            .text(" | ")
-            .render_link("Settings", "/admin/settings")
-            .newline()
-            .hr()
-    }
+        let mut md = Self::render_nav_subpage(env, board_id, "Board Rules Editor");
 
-    /// Check if viewer is a registry admin
-    fn is_registry_admin(env: &Env, viewer: &Option<Address>) -> bool {
-        if let Some(user) = viewer {
-            let registry: Address = env
-                .storage()
-                .instance()
-                .get(&AdminKey::Registry)
-                .expect("Not initialized");
-            env.invoke_contract(
-                &registry,
-                &Symbol::new(env, "is_admin"),
-                Vec::from_array(env, [user.into_val(env)]),
-            )
+        // Check if viewer has admin permission
+        let can_admin = if let Some(user) = viewer {
+            let args: Vec<Val> = Vec::from_array(env, [board_id.into_val(env), user.into_val(env)]);
+            let perms: PermissionSet =
+                env.invoke_contract(&permissions, &Symbol::new(env, "get_permissions"), args);
+            perms.can_admin
         } else {
             false
-        }
-    }
-
-    /// Render main site settings page
-    fn render_site_settings(env: &Env, viewer: &Option<Address>) -> Bytes {
-        let mut md = Self::render_settings_nav(env).h1("Site Settings");
+        };
 
-        if !Self::is_registry_admin(env, viewer) {
-            md = md.warning("You must be a registry admin to access site settings.");
+        if !can_admin {
+            md = md.warning("You must be an admin to edit board rules.");
             return Self::render_footer_into(env, md).build();
         }
 
-        md = md
-            .paragraph("Configure global settings for this Soroban Boards instance.")
-            .newline()
-            // Branding card
-            .raw_str("<div class=\"card\">\n")
-            .raw_str("<h3><a href=\"render:/admin/settings/branding\">Branding</a></h3>\n")
-            .raw_str("<p>Site name, tagline, logo, footer text, and primary color.</p>\n")
-            .raw_str("</div>\n")
-            .newline()
-            // Thresholds card
-            .raw_str("<div class=\"card\">\n")
-            .raw_str("<h3><a href=\"render:/admin/settings/thresholds\">Creation Thresholds</a></h3>\n")
-            .raw_str("<p>Requirements for creating boards and communities: karma, account age, post count, and per-user limits.</p>\n")
-            .raw_str("</div>\n")
-            .newline()
-            // Operational card
-            .raw_str("<div class=\"card\">\n")
-            .raw_str("<h3><a href=\"render:/admin/settings/operational\">Operational Settings</a></h3>\n")
-            .raw_str("<p>Reply depth, chunk sizes, edit windows, and name length limits.</p>\n")
-            .raw_str("</div>\n")
-            .newline()
-            // Pages card
-            .raw_str("<div class=\"card\">\n")
-            .raw_str("<h3><a href=\"render:/p/admin\">Manage Pages</a></h3>\n")
-            .raw_str("<p>Create and edit static content pages like Help, About, and custom pages.</p>\n")
-            .raw_str("</div>\n")
-            .newline()
-            .render_link("← Back to Registry Admin", "/admin/registry");
+        // Get board contract (single contract for all boards)
+        let board_contract = Self::get_board_contract_address(env);
 
-        Self::render_footer_into(env, md).build()
-    }
+        // Get current rules (now requires board_id)
+        let current_rules: Option<String> = env.invoke_contract(
+            &board_contract,
+            &Symbol::new(env, "get_rules"),
+            Vec::from_array(env, [board_id.into_val(env)]),
+        );
 
-    /// Render branding settings page
-    fn render_branding_settings(env: &Env, viewer: &Option<Address>) -> Bytes {
-        let mut md = Self::render_settings_nav(env).h1("Branding Settings");
+        md = md.h2("Current Rules");
 
-        if !Self::is_registry_admin(env, viewer) {
-            md = md.warning("You must be a registry admin to access site settings.");
-            return Self::render_footer_into(env, md).build();
+        if let Some(ref rules) = current_rules {
+            if !rules.is_empty() {
+                md = md
+                    .div_start("rules-content")
+                    .text_string(rules)
+                    .div_end()
+                    .newline()
+                    .raw_str("<input type=\"hidden\" name=\"board_id\" value=\"")
+                    .number(board_id as u32)
+                    .raw_str("\" />\n")
+                    .form_link_to("Clear Rules", "admin", "clear_rules")
+                    .newline();
+            } else {
+                md = md.paragraph("No rules set.");
+            }
+        } else {
+            md = md.paragraph("No rules set.");
         }
 
-        // Get current branding from config
-        let config_opt: Option<Address> = env.storage().instance().get(&AdminKey::Config);
-
-        if config_opt.is_none() {
-            md = md.warning("Config contract not set. Please contact the administrator.");
-            return Self::render_footer_into(env, md).build();
-        }
+        // Edit rules form
+        md = md.hr()
+            .h2("Edit Rules")
+            .note("Rules are displayed on the board page and when creating new threads. Use plain text or Markdown.")
+            .raw_str("<input type=\"hidden\" name=\"board_id\" value=\"")
+            .number(board_id as u32)
+            .raw_str("\" />\n")
+            .raw_str("<input type=\"hidden\" name=\"_redirect\" value=\"/admin/b/")
+            .number(board_id as u32)
+            .raw_str("/rules\" />\n");
 
-        let config = config_opt.unwrap();
-        let branding: Branding =
-            env.invoke_contract(&config, &Symbol::new(env, "get_branding"), Vec::new(env));
+        // Pre-fill with current rules if available
+        let empty = String::from_str(env, "");
+        md = md.textarea_markdown_with_value_string(
+            "rules",
+            10,
+            "Enter board rules here...",
+            current_rules.as_ref().unwrap_or(&empty),
+        );
 
         md = md
-            .h2("Current Branding")
-            .newline()
-            .text("- **Site Name:** ")
-            .text_string(&branding.site_name)
-            .newline()
-            .text("- **Home Content:** ")
-            .text_string(&branding.tagline)
             .newline()
-            // Color swatch: inline span with background color and contrasting text
-            .raw_str("- **Primary Color:** <span style=\"background-color:")
-            .text_string(&branding.primary_color)
-            .raw_str(";color:#fff;padding:2px 8px;border-radius:4px;border:1px solid #333;\">")
-            .text_string(&branding.primary_color)
-            .raw_str("</span>\n");
+            .form_link_to("Save Rules", "admin", "set_rules");
 
-        if !branding.logo_url.is_empty() {
-            md = md
-                .text("- **Logo URL:** ")
-                .text_string(&branding.logo_url)
-                .newline();
+        Self::render_footer_into(env, md).build()
+    }
+
+    /// Render voting configuration page
+    fn render_voting(env: &Env, board_id: u64, viewer: &Option<Address>) -> Bytes {
+        let permissions: Address = env
+            .storage()
+            .instance()
+            .get(&AdminKey::Permissions)
+            .expect("Not initialized");
+
+        let mut md = Self::render_nav_subpage(env, board_id, "Voting Configuration");
+
+        // Check if viewer has admin permission
+        let can_admin = if let Some(user) = viewer {
+            let args: Vec<Val> = Vec::from_array(env, [board_id.into_val(env), user.into_val(env)]);
+            let perms: PermissionSet =
+                env.invoke_contract(&permissions, &Symbol::new(env, "get_permissions"), args);
+            perms.can_admin
         } else {
-            md = md.text("- **Logo URL:** *(not set)*").newline();
+            false
+        };
+
+        if !can_admin {
+            md = md.warning("You must be an admin to configure voting.");
+            return Self::render_footer_into(env, md).build();
         }
 
-        if !branding.favicon_url.is_empty() {
+        // Get voting contract from registry
+        let voting_contract = Self::get_voting_contract_address(env);
+
+        if let Some(voting_addr) = voting_contract {
+            // Get current config
+            let config: Option<VotingConfig> = env
+                .try_invoke_contract::<VotingConfig, soroban_sdk::Error>(
+                    &voting_addr,
+                    &Symbol::new(env, "get_voting_config"),
+                    Vec::from_array(env, [board_id.into_val(env)]),
+                )
+                .ok()
+                .and_then(|r| r.ok());
+
+            let (member_weight, moderator_weight): (u32, u32) = env
+                .try_invoke_contract::<(u32, u32), soroban_sdk::Error>(
+                    &voting_addr,
+                    &Symbol::new(env, "get_vote_weights"),
+                    Vec::from_array(env, [board_id.into_val(env)]),
+                )
+                .ok()
+                .and_then(|r| r.ok())
+                .unwrap_or((1, 1));
+
+            md = md.h2("Current Configuration");
+
+            if let Some(ref cfg) = config {
+                md = md
+                    .raw_str("- **Voting enabled:** ")
+                    .text(if cfg.enabled { "Yes" } else { "No" })
+                    .newline()
+                    .raw_str("- **Allow downvotes:** ")
+                    .text(if cfg.allow_downvotes { "Yes" } else { "No" })
+                    .newline()
+                    .raw_str("- **Karma tracking:** ")
+                    .text(if cfg.karma_enabled { "Yes" } else { "No" })
+                    .newline()
+                    .raw_str("- **Karma multiplier:** ")
+                    .number(cfg.karma_multiplier)
+                    .newline();
+            } else {
+                md = md
+                    .paragraph("Using default configuration (voting enabled, downvotes allowed).");
+            }
             md = md
-                .text("- **Favicon URL:** ")
-                .text_string(&branding.favicon_url)
+                .raw_str("- **Member vote weight:** ")
+                .number(member_weight)
+                .newline()
+                .raw_str("- **Moderator vote weight:** ")
+                .number(moderator_weight)
                 .newline();
-        } else {
-            md = md.text("- **Favicon URL:** *(not set)*").newline();
-        }
 
-        if !branding.footer_text.is_empty() {
+            // Update form - fields reflect the current config, since blank numbers mean 1
+            // Hidden inputs provide default "false" when checkbox is unchecked
+            let (enabled, allow_downvotes, karma_enabled, karma_multiplier) = config
+                .as_ref()
+                .map(|c| {
+                    (
+                        c.enabled,
+                        c.allow_downvotes,
+                        c.karma_enabled,
+                        c.karma_multiplier,
+                    )
+                })
+                .unwrap_or((true, true, true, 1));
             md = md
-                .text("- **Footer Text:** ")
-                .text_string(&branding.footer_text)
-                .newline();
+                .hr()
+                .h2("Update Configuration")
+                .raw_str("<input type=\"hidden\" name=\"board_id\" value=\"")
+                .number(board_id as u32)
+                .raw_str("\" />\n")
+                .raw_str("<input type=\"hidden\" name=\"_redirect\" value=\"/admin/b/")
+                .number(board_id as u32)
+                .raw_str("/voting\" />\n")
+                .raw_str("<input type=\"hidden\" name=\"enabled\" value=\"false\" />\n")
+                .raw_str("<label><input type=\"checkbox\" name=\"enabled\" value=\"true\"")
+                .raw_str(if enabled { " checked" } else { "" })
+                .raw_str(" /> Enable voting</label>\n")
+                .raw_str("<input type=\"hidden\" name=\"allow_downvotes\" value=\"false\" />\n")
+                .raw_str("<label><input type=\"checkbox\" name=\"allow_downvotes\" value=\"true\"")
+                .raw_str(if allow_downvotes { " checked" } else { "" })
+                .raw_str(" /> Allow downvotes</label>\n")
+                .raw_str("<input type=\"hidden\" name=\"karma_enabled\" value=\"false\" />\n")
+                .raw_str("<label><input type=\"checkbox\" name=\"karma_enabled\" value=\"true\"")
+                .raw_str(if karma_enabled { " checked" } else { "" })
+                .raw_str(" /> Enable karma tracking</label>\n")
+                .input_with_value_number(
+                    "karma_multiplier",
+                    "Karma multiplier (1-10, default: 1)",
+                    karma_multiplier,
+                )
+                .newline()
+                .paragraph("Trusted roles' votes can count more; guests always count 1.")
+                .input_with_value_number(
+                    "vote_weight_member",
+                    "Member vote weight (1-10, default: 1)",
+                    member_weight,
+                )
+                .newline()
+                .input_with_value_number(
+                    "vote_weight_moderator",
+                    "Moderator vote weight (1-10, default: 1)",
+                    moderator_weight,
+                )
+                .newline()
+                .form_link_to("Update Configuration", "admin", "set_voting_config");
+
+            // Reaction allowlist (reactions don't affect score or karma)
+            let allowlist: Option<Vec<Symbol>> = env
+                .try_invoke_contract::<Vec<Symbol>, soroban_sdk::Error>(
+                    &voting_addr,
+                    &Symbol::new(env, "get_reaction_allowlist"),
+                    Vec::from_array(env, [board_id.into_val(env)]),
+                )
+                .ok()
+                .and_then(|r| r.ok());
+            if let Some(allowlist) = allowlist {
+                md = md
+                    .hr()
+                    .h2("Reactions")
+                    .paragraph("Reactions shown under posts. Leave empty to turn them off.")
+                    .raw_str("<p>Available: ");
+                for (i, name) in REACTION_NAMES.iter().enumerate() {
+                    if i > 0 {
+                        md = md.raw_str(", ");
+                    }
+                    md = md.raw_str("<code>").raw_str(name).raw_str("</code>");
+                }
+                md = md
+                    .raw_str("</p>\n<div data-form>\n")
+                    .raw_str("<input type=\"hidden\" name=\"board_id\" value=\"")
+                    .number(board_id as u32)
+                    .raw_str("\" />\n")
+                    .raw_str("<input type=\"text\" name=\"reactions\" value=\"");
+                let mut first = true;
+                for emoji in allowlist.iter() {
+                    let Some(name) = REACTION_NAMES
+                        .iter()
+                        .find(|name| Symbol::new(env, name) == emoji)
+                    else {
+                        continue;
+                    };
+                    if !first {
+                        md = md.raw_str(", ");
+                    }
+                    md = md.raw_str(name);
+                    first = false;
+                }
+                md = md
+                    .raw_str("\" />\n")
+                    .form_link_to("Update Reactions", "admin", "set_reaction_allowlist")
+                    .raw_str("\n</div>\n");
+            }
         } else {
-            md = md.text("- **Footer Text:** *(using default)*").newline();
+            md = md.note(
+                "Voting contract is not configured for this board. Voting features are disabled.",
+            );
         }
 
-        // Update forms - one per field, pre-populated with current values
-        // Each field is wrapped in a data-form div for proper form boundary detection
-        md = md
-            .newline()
-            .h2("Update Branding")
-            .newline()
-            // Site Name form
-            .raw_str("<div data-form>\n\n")
-            .h3("Site Name")
-            .input_with_value_string("site_name", "Site name", &branding.site_name)
-            .newline()
-            .form_link_to("Update Site Name", "admin", "set_site_name")
-            .raw_str("\n</div>\n\n")
-            // Home Content form
-            .raw_str("<div data-form>\n\n")
-            .h3("Home Content")
-            .textarea_markdown_with_value_string(
-                "tagline",
-                3,
-                "Home content (markdown)",
-                &branding.tagline,
-            )
-            .newline()
-            .form_link_to("Update Home Content", "admin", "set_tagline")
-            .raw_str("\n</div>\n\n")
-            // Primary Color form
-            .raw_str("<div data-form>\n\n")
-            .h3("Primary Color")
-            .input_with_value_string(
-                "primary_color",
-                "CSS color (e.g., #7857e1)",
-                &branding.primary_color,
-            )
-            .newline()
-            .form_link_to("Update Primary Color", "admin", "set_primary_color")
-            .raw_str("\n</div>\n\n")
-            // Logo URL form
-            .raw_str("<div data-form>\n\n")
-            .h3("Logo URL")
-            .input_with_value_string("logo_url", "URL to logo image", &branding.logo_url)
-            .newline()
-            .form_link_to("Update Logo URL", "admin", "set_logo_url")
-            .raw_str("\n</div>\n\n")
-            // Favicon URL form
-            .raw_str("<div data-form>\n\n")
-            .h3("Favicon URL")
-            .input_with_value_string("favicon_url", "URL to favicon", &branding.favicon_url)
-            .newline()
-            .form_link_to("Update Favicon URL", "admin", "set_favicon_url")
-            .raw_str("\n</div>\n\n")
-            // Footer Text form
-            .raw_str("<div data-form>\n\n")
-            .h3("Footer Text")
-            .textarea_markdown_with_value_noparse_string(
-                "footer_text",
-                3,
-                "Custom footer text",
-                &branding.footer_text,
-            )
-            .newline()
-            .form_link_to("Update Footer Text", "admin", "set_footer_text")
-            .raw_str("\n</div>\n\n")
-            .render_link("← Back to Settings", "/admin/settings");
-
         Self::render_footer_into(env, md).build()
     }
 
-    /// Render threshold settings page
-    fn render_threshold_settings(env: &Env, viewer: &Option<Address>) -> Bytes {
-        let mut md = Self::render_settings_nav(env).h1("Creation Thresholds");
+    /// Render registry admin page
+    fn render_registry_admin(env: &Env, viewer: &Option<Address>) -> Bytes {
+        let registry: Address = env
+            .storage()
+            .instance()
+            .get(&AdminKey::Registry)
+            .expect("Not initialized");
 
-        if !Self::is_registry_admin(env, viewer) {
-            md = md.warning("You must be a registry admin to access site settings.");
+        let mut md = MarkdownBuilder::new(env)
+            .render_link("Soroban Boards", "/")
+            .newline()
+            .hr()
+            .h1("Registry Administration");
+
+        if viewer.is_none() {
+            md = md.warning("Please connect your wallet to access registry admin.");
             return Self::render_footer_into(env, md).build();
         }
 
-        let config_opt: Option<Address> = env.storage().instance().get(&AdminKey::Config);
+        let viewer_addr = viewer.as_ref().unwrap();
 
-        if config_opt.is_none() {
-            md = md.warning("Config contract not set. Please contact the administrator.");
-            return Self::render_footer_into(env, md).build();
+        // Get all admins
+        let admins: Vec<Address> =
+            env.invoke_contract(&registry, &Symbol::new(env, "get_admins"), Vec::new(env));
+
+        // Check if viewer is an admin
+        let viewer_is_admin: bool = env.invoke_contract(
+            &registry,
+            &Symbol::new(env, "is_admin"),
+            Vec::from_array(env, [viewer_addr.into_val(env)]),
+        );
+
+        md = md.h2("Current Admins").raw_str("<ul>\n");
+
+        for i in 0..admins.len() {
+            let admin = admins.get(i).unwrap();
+            md = md
+                .raw_str("<li><code>")
+                .text_string(&admin.to_string())
+                .raw_str("</code></li>\n");
         }
 
-        let config = config_opt.unwrap();
+        md = md.raw_str("</ul>\n").newline();
 
-        // Board thresholds
-        let board_thresholds: CreationThresholds = env.invoke_contract(
-            &config,
-            &Symbol::new(env, "get_board_thresholds"),
-            Vec::new(env),
-        );
+        // If viewer is admin, show add/remove admin forms
+        if viewer_is_admin {
+            md = md
+                .h2("Add Admin")
+                .raw_str("<form>\n")
+                .raw_str("<input type=\"text\" name=\"new_admin\" placeholder=\"Address (G...)\" style=\"width:100%;max-width:400px;\" />\n")
+                .raw_str("<p><a class=\"soroban-action\" href=\"form:@admin:add_registry_admin\">Add Admin</a></p>\n")
+                .raw_str("</form>\n")
+                .newline();
 
-        md = md
-            .h2("Board Creation Thresholds")
-            .text("- **Minimum Karma:** ")
-            .number(board_thresholds.min_karma as u32)
-            .text("\n- **Minimum Account Age (seconds):** ")
-            .number(board_thresholds.min_account_age_secs as u32)
-            .text("\n- **Minimum Post Count:** ")
-            .number(board_thresholds.min_post_count)
-            .text("\n- **Require Profile:** ")
-            .text(if board_thresholds.require_profile {
-                "Yes"
-            } else {
-                "No"
-            })
-            .text("\n- **Per-User Limit:** ")
-            .number(board_thresholds.per_user_limit)
-            .text(" (0 = unlimited)")
-            .text("\n- **XLM Lock (stroops):** ")
-            .number(board_thresholds.xlm_lock_stroops as u32)
-            .newline()
-            .newline()
-            .raw_str("<div data-form>\n\n")
-            .h3("Update Board Thresholds")
-            .raw_str("<input type=\"hidden\" name=\"threshold_type\" value=\"board\" />\n\n")
-            .text("**Min Karma** (0 = none):")
-            .newline()
-            .input_with_value_number("min_karma", "Min Karma", board_thresholds.min_karma as u32)
-            .newline()
-            .text("**Min Account Age** (seconds, 0 = none):")
-            .newline()
-            .input_with_value_number(
-                "min_account_age_secs",
-                "Min Account Age",
-                board_thresholds.min_account_age_secs as u32,
-            )
-            .newline()
-            .text("**Min Post Count** (0 = none):")
-            .newline()
-            .input_with_value_number(
-                "min_post_count",
-                "Min Post Count",
-                board_thresholds.min_post_count,
-            )
-            .newline()
-            .text("**Require Profile:**")
-            .newline()
-            .select_bool("require_profile", board_thresholds.require_profile)
-            .newline()
-            .text("**Per-User Limit** (0 = unlimited):")
-            .newline()
-            .input_with_value_number(
-                "per_user_limit",
-                "Per-User Limit",
-                board_thresholds.per_user_limit,
-            )
-            .newline()
-            .text("**XLM Lock** (stroops, 0 = none):")
-            .newline()
-            .input_with_value_number(
-                "xlm_lock_stroops",
-                "XLM Lock",
-                board_thresholds.xlm_lock_stroops as u32,
-            )
-            .newline()
-            .form_link_to("Update Board Thresholds", "admin", "update_thresholds")
-            .raw_str("\n</div>\n\n");
+            md = md
+                .render_link("Contract Upgrades", "/admin/registry/upgrades")
+                .text(" | ")
+                .render_link("Global Bans", "/admin/registry/bans")
+                .newline();
 
-        // Community thresholds
-        let community_thresholds: CreationThresholds = env.invoke_contract(
-            &config,
-            &Symbol::new(env, "get_community_thresholds"),
-            Vec::new(env),
-        );
+            let (cap, cooldown): (u32, u64) = env
+                .try_invoke_contract::<(u32, u64), soroban_sdk::Error>(
+                    &registry,
+                    &Symbol::new(env, "get_board_creation_limits"),
+                    Vec::new(env),
+                )
+                .ok()
+                .and_then(|r| r.ok())
+                .unwrap_or((0, 0));
+
+            md = md
+                .h2("Board Creation Limits")
+                .text("Per account; registry admins are exempt. 0 disables a limit.")
+                .newline()
+                .text("**Boards per account:** ")
+                .number(cap)
+                .newline()
+                .text("**Cooldown between boards:** ")
+                .number(cooldown as u32)
+                .text(" seconds")
+                .newline()
+                .raw_str("<div data-form>\n")
+                .input("boards_per_account", "Boards per account (0 = unlimited)")
+                .newline()
+                .input("cooldown_secs", "Cooldown in seconds (0 = none)")
+                .newline()
+                .form_link_to("Update Limits", "admin", "set_board_creation_limits")
+                .raw_str("\n</div>\n")
+                .newline();
+
+            md = Self::render_featured_admin(env, md);
+
+            if admins.len() > 1 {
+                md = md
+                    .h2("Remove Admin")
+                    .raw_str("<form>\n")
+                    .raw_str("<input type=\"text\" name=\"admin_to_remove\" placeholder=\"Address (G...)\" style=\"width:100%;max-width:400px;\" />\n")
+                    .raw_str("<p><a class=\"soroban-action\" href=\"form:@admin:remove_registry_admin\">Remove Admin</a></p>\n")
+                    .raw_str("</form>\n")
+                    .newline();
+            }
+        }
 
+        // Show viewer's address for reference
         md = md
             .newline()
-            .h2("Community Creation Thresholds")
-            .text("- **Minimum Karma:** ")
-            .number(community_thresholds.min_karma as u32)
-            .text("\n- **Minimum Account Age (seconds):** ")
-            .number(community_thresholds.min_account_age_secs as u32)
-            .text("\n- **Minimum Post Count:** ")
-            .number(community_thresholds.min_post_count)
-            .text("\n- **Require Profile:** ")
-            .text(if community_thresholds.require_profile {
-                "Yes"
-            } else {
-                "No"
-            })
-            .text("\n- **Per-User Limit:** ")
-            .number(community_thresholds.per_user_limit)
-            .text(" (0 = unlimited)")
-            .text("\n- **XLM Lock (stroops):** ")
-            .number(community_thresholds.xlm_lock_stroops as u32)
-            .newline()
-            .newline()
-            .raw_str("<div data-form>\n\n")
-            .h3("Update Community Thresholds")
-            .raw_str("<input type=\"hidden\" name=\"threshold_type\" value=\"community\" />\n\n")
-            .text("**Min Karma** (0 = none):")
-            .newline()
-            .input_with_value_number(
-                "min_karma",
-                "Min Karma",
-                community_thresholds.min_karma as u32,
-            )
-            .newline()
-            .text("**Min Account Age** (seconds, 0 = none):")
-            .newline()
-            .input_with_value_number(
-                "min_account_age_secs",
-                "Min Account Age",
-                community_thresholds.min_account_age_secs as u32,
-            )
-            .newline()
-            .text("**Min Post Count** (0 = none):")
-            .newline()
-            .input_with_value_number(
-                "min_post_count",
-                "Min Post Count",
-                community_thresholds.min_post_count,
-            )
-            .newline()
-            .text("**Require Profile:**")
-            .newline()
-            .select_bool("require_profile", community_thresholds.require_profile)
-            .newline()
-            .text("**Per-User Limit** (0 = unlimited):")
-            .newline()
-            .input_with_value_number(
-                "per_user_limit",
-                "Per-User Limit",
-                community_thresholds.per_user_limit,
-            )
-            .newline()
-            .text("**XLM Lock** (stroops, 0 = none):")
-            .newline()
-            .input_with_value_number(
-                "xlm_lock_stroops",
-                "XLM Lock",
-                community_thresholds.xlm_lock_stroops as u32,
+            .h2("Your Address")
+            .raw_str("<p><code>")
+            .text_string(&viewer_addr.to_string())
+            .raw_str("</code>");
+
+        if viewer_is_admin {
+            md = md.raw_str(" <span style=\"color:green;\">(Admin)</span>");
+        }
+
+        md = md.raw_str("</p>");
+
+        // Add link back to home
+        md = md.newline().render_link("← Back to Home", "/");
+
+        Self::render_footer_into(env, md).build()
+    }
+
+    /// Featured boards section of the registry admin page: the current list with
+    /// remove/move-up forms, and a form to feature another board
+    fn render_featured_admin<'a>(env: &'a Env, md: MarkdownBuilder<'a>) -> MarkdownBuilder<'a> {
+        let board_contract = Self::get_board_contract_address(env);
+        let featured: Vec<BoardMeta> = env
+            .try_invoke_contract::<Vec<BoardMeta>, soroban_sdk::Error>(
+                &board_contract,
+                &Symbol::new(env, "list_featured"),
+                Vec::new(env),
             )
+            .ok()
+            .and_then(|r| r.ok())
+            .unwrap_or(Vec::new(env));
+
+        let mut md = md
+            .h2("Featured Boards")
+            .text("Shown at the top of the home page, in this order. ")
+            .text("Unlisting or archiving a board removes it.")
+            .newline();
+        if featured.is_empty() {
+            md = md.paragraph("No featured boards.");
+        }
+
+        for (i, board) in featured.iter().enumerate() {
+            md = md
+                .raw_str("<div class=\"featured-board\">")
+                .number(i as u32 + 1)
+                .text(". ")
+                .text_string(&board.name)
+                .text(" (#")
+                .number(board.id as u32)
+                .text(")")
+                .raw_str("\n<div data-form>\n")
+                .raw_str("<input type=\"hidden\" name=\"board_id\" value=\"")
+                .number(board.id as u32)
+                .raw_str("\" />\n")
+                .form_link_to("Remove", "admin", "unfeature_board")
+                .raw_str("\n</div>\n");
+            if i > 0 {
+                md = md
+                    .raw_str("<div data-form>\n")
+                    .raw_str("<input type=\"hidden\" name=\"board_id\" value=\"")
+                    .number(board.id as u32)
+                    .raw_str("\" />\n")
+                    .raw_str("<input type=\"hidden\" name=\"position\" value=\"")
+                    .number(i as u32 - 1)
+                    .raw_str("\" />\n")
+                    .form_link_to("Move Up", "admin", "move_featured_board")
+                    .raw_str("\n</div>\n");
+            }
+            md = md.raw_str("</div>\n");
+        }
+
+        md.raw_str("<div data-form>\n")
+            .input("board_id", "Board ID")
             .newline()
-            .form_link_to("Update Community Thresholds", "admin", "update_thresholds")
+            .form_link_to("Feature Board", "admin", "feature_board")
             .raw_str("\n</div>\n")
             .newline()
-            .newline()
-            .render_link("← Back to Settings", "/admin/settings");
-
-        Self::render_footer_into(env, md).build()
     }
 
-    /// Render operational settings page
-    fn render_operational_settings(env: &Env, viewer: &Option<Address>) -> Bytes {
-        let mut md = Self::render_settings_nav(env).h1("Operational Settings");
+    /// Render the contract upgrades page (registry admins only)
+    fn render_upgrades(env: &Env, viewer: &Option<Address>) -> Bytes {
+        let mut md = Self::render_settings_nav(env).h1("Contract Upgrades");
 
         if !Self::is_registry_admin(env, viewer) {
-            md = md.warning("You must be a registry admin to access site settings.");
+            md = md.warning("You must be a registry admin to manage upgrades.");
             return Self::render_footer_into(env, md).build();
         }
 
-        let config_opt: Option<Address> = env.storage().instance().get(&AdminKey::Config);
-
-        if config_opt.is_none() {
-            md = md.warning("Config contract not set. Please contact the administrator.");
-            return Self::render_footer_into(env, md).build();
-        }
+        let registry: Address = env
+            .storage()
+            .instance()
+            .get(&AdminKey::Registry)
+            .expect("Not initialized");
 
-        let config = config_opt.unwrap();
+        md = md
+            .paragraph("Upload the new WASM first, then enter its hash (64 hex characters).")
+            .note("One board contract serves every board, so upgrading it upgrades all boards.");
 
-        // Get current values
-        let max_reply_depth: u32 = env.invoke_contract(
-            &config,
-            &Symbol::new(env, "get_max_reply_depth"),
-            Vec::new(env),
-        );
-        let reply_chunk_size: u32 = env.invoke_contract(
-            &config,
-            &Symbol::new(env, "get_reply_chunk_size"),
-            Vec::new(env),
-        );
-        let default_edit_window: u64 = env.invoke_contract(
-            &config,
-            &Symbol::new(env, "get_default_edit_window"),
-            Vec::new(env),
-        );
-        let thread_body_max_bytes: u32 = env.invoke_contract(
-            &config,
-            &Symbol::new(env, "get_thread_body_max_bytes"),
-            Vec::new(env),
-        );
-        let board_name_limits: NameLimits = env.invoke_contract(
-            &config,
-            &Symbol::new(env, "get_board_name_limits"),
-            Vec::new(env),
-        );
-        let community_name_limits: NameLimits = env.invoke_contract(
-            &config,
-            &Symbol::new(env, "get_community_name_limits"),
-            Vec::new(env),
-        );
+        for alias in UPGRADEABLE_ALIASES.iter() {
+            let contract: Option<Address> = env.invoke_contract(
+                &registry,
+                &Symbol::new(env, "get_contract_by_alias"),
+                Vec::from_array(env, [Symbol::new(env, alias).into_val(env)]),
+            );
+            let Some(contract) = contract else {
+                continue;
+            };
+
+            let wasm_hash: Option<BytesN<32>> = env
+                .try_invoke_contract::<Option<BytesN<32>>, soroban_sdk::Error>(
+                    &registry,
+                    &Symbol::new(env, "get_contract_wasm_hash"),
+                    Vec::from_array(env, [contract.into_val(env)]),
+                )
+                .ok()
+                .and_then(|r| r.ok())
+                .flatten();
+
+            md = md
+                .hr()
+                .h3(alias)
+                .text("**Contract:** `")
+                .text_string(&contract.to_string())
+                .text("`")
+                .newline()
+                .text("**WASM hash:** ");
+            md = match wasm_hash {
+                Some(hash) => md
+                    .text("`")
+                    .raw(Self::wasm_hash_to_hex(env, &hash))
+                    .text("`"),
+                None => md.text("*Not recorded (never upgraded through the registry)*"),
+            };
+            md = md
+                .newline()
+                .raw_str("<div data-form>\n")
+                .raw_str("<input type=\"hidden\" name=\"alias\" value=\"")
+                .raw_str(alias)
+                .raw_str("\" />\n")
+                .input("wasm_hash", "New WASM hash (hex)")
+                .newline()
+                .form_link_to("Upgrade", "admin", "upgrade_contract_by_alias")
+                .raw_str("\n</div>\n");
+        }
+
+        Self::render_footer_into(env, md).build()
+    }
+
+    /// Render the network-wide ban list (registry admins only)
+    fn render_global_bans(env: &Env, viewer: &Option<Address>) -> Bytes {
+        let mut md = Self::render_settings_nav(env).h1("Global Bans");
+
+        if !Self::is_registry_admin(env, viewer) {
+            md = md.warning("You must be a registry admin to manage global bans.");
+            return Self::render_footer_into(env, md).build();
+        }
+
+        let registry: Address = env
+            .storage()
+            .instance()
+            .get(&AdminKey::Registry)
+            .expect("Not initialized");
+
+        md = md.paragraph("Globally banned users cannot view or post on any board.");
+
+        let bans: Vec<GlobalBan> = env
+            .try_invoke_contract::<Vec<GlobalBan>, soroban_sdk::Error>(
+                &registry,
+                &Symbol::new(env, "list_global_bans"),
+                Vec::from_array(
+                    env,
+                    [0u32.into_val(env), MAX_GLOBAL_BANS_SHOWN.into_val(env)],
+                ),
+            )
+            .ok()
+            .and_then(|r| r.ok())
+            .unwrap_or(Vec::new(env));
+
+        if bans.is_empty() {
+            md = md.paragraph("No global bans.");
+        }
+
+        let now = env.ledger().timestamp();
+        for ban in bans.iter() {
+            let user_str = Self::format_address(env, &ban.user);
+            md = md.hr().text("`").text_string(&user_str).text("`");
+            if ban.expires_at.is_some_and(|expires| now >= expires) {
+                md = md.raw_str(" <span class=\"badge badge-expired\">expired</span>");
+            }
+            md = md
+                .newline()
+                .text("**Reason:** ")
+                .text_string(&ban.reason)
+                .newline()
+                .text("**Issued by:** `")
+                .text_string(&Self::format_address(env, &ban.issuer))
+                .text("` on ")
+                .raw(Self::format_timestamp(env, ban.created_at))
+                .newline();
+            md = match ban.expires_at {
+                Some(expires) => md
+                    .text("**Expires:** ")
+                    .raw(Self::format_timestamp(env, expires))
+                    .newline(),
+                None => md.text("**Expires:** *Permanent*").newline(),
+            };
+            md = md
+                .raw_str("<div data-form>\n")
+                .raw_str("<input type=\"hidden\" name=\"user\" value=\"")
+                .text_string(&user_str)
+                .raw_str("\" />\n")
+                .form_link_to("Lift Ban", "admin", "global_unban_user")
+                .raw_str("\n</div>\n");
+        }
 
         md = md
-            .h2("Reply Settings")
-            .text("**Max Reply Depth:** ")
-            .number(max_reply_depth)
-            .newline()
-            .text("**Reply Chunk Size:** ")
-            .number(reply_chunk_size)
+            .hr()
+            .h3("Ban a User Everywhere")
+            .raw_str("<div data-form>\n")
+            .input("user", "Wallet address (G...)")
             .newline()
+            .input("reason", "Reason for ban")
             .newline()
-            .input("max_reply_depth", "Max Reply Depth")
+            .input("duration_hours", "Duration (hours, 0 = permanent)")
             .newline()
-            .form_link_to("Update", "admin", "config_set_max_reply_depth")
+            .form_link_to("Ban Globally", "admin", "global_ban_user")
+            .raw_str("\n</div>\n")
             .newline()
-            .input("reply_chunk_size", "Reply Chunk Size")
+            .render_link("← Back to Registry Admin", "/admin/registry");
+
+        Self::render_footer_into(env, md).build()
+    }
+
+    // ========================================================================
+    // Site Settings Render Functions
+    // ========================================================================
+
+    /// Navigation for site settings pages
+    fn render_settings_nav(env: &Env) -> MarkdownBuilder<'_> {
+        let aliases = Self::fetch_aliases(env);
+        MarkdownBuilder::new(env)
+            .raw(aliases) // Emit aliases for include resolution
+            .render_link("Soroban Boards", "/")
+            .text(" | ")
+            .render_link("Admin", "/admin/registry")
+            .text(" | ")
+            .render_link("Settings", "/admin/settings")
             .newline()
-            .form_link_to("Update", "admin", "config_set_reply_chunk_size")
+            .hr()
+    }
+
+    /// Check if viewer is a registry admin
+    fn is_registry_admin(env: &Env, viewer: &Option<Address>) -> bool {
+        if let Some(user) = viewer {
+            let registry: Address = env
+                .storage()
+                .instance()
+                .get(&AdminKey::Registry)
+                .expect("Not initialized");
+            env.invoke_contract(
+                &registry,
+                &Symbol::new(env, "is_admin"),
+                Vec::from_array(env, [user.into_val(env)]),
+            )
+        } else {
+            false
+        }
+    }
+
+    /// Render main site settings page
+    fn render_site_settings(env: &Env, viewer: &Option<Address>) -> Bytes {
+        let mut md = Self::render_settings_nav(env).h1("Site Settings");
+
+        if !Self::is_registry_admin(env, viewer) {
+            md = md.warning("You must be a registry admin to access site settings.");
+            return Self::render_footer_into(env, md).build();
+        }
+
+        md = md
+            .paragraph("Configure global settings for this Soroban Boards instance.")
             .newline()
+            // Branding card
+            .raw_str("<div class=\"card\">\n")
+            .raw_str("<h3><a href=\"render:/admin/settings/branding\">Branding</a></h3>\n")
+            .raw_str("<p>Site name, tagline, logo, footer text, and primary color.</p>\n")
+            .raw_str("</div>\n")
             .newline()
-            .h2("Content Settings")
-            .text("**Default Edit Window:** ")
-            .number(default_edit_window as u32)
-            .text(" seconds (")
-            .number((default_edit_window / 3600) as u32)
-            .text(" hours)")
+            // Thresholds card
+            .raw_str("<div class=\"card\">\n")
+            .raw_str("<h3><a href=\"render:/admin/settings/thresholds\">Creation Thresholds</a></h3>\n")
+            .raw_str("<p>Requirements for creating boards and communities: karma, account age, post count, and per-user limits.</p>\n")
+            .raw_str("</div>\n")
             .newline()
-            .text("**Thread Body Max Bytes:** ")
-            .number(thread_body_max_bytes)
+            // Operational card
+            .raw_str("<div class=\"card\">\n")
+            .raw_str("<h3><a href=\"render:/admin/settings/operational\">Operational Settings</a></h3>\n")
+            .raw_str("<p>Reply depth, chunk sizes, edit windows, and name length limits.</p>\n")
+            .raw_str("</div>\n")
             .newline()
+            // Pages card
+            .raw_str("<div class=\"card\">\n")
+            .raw_str("<h3><a href=\"render:/p/admin\">Manage Pages</a></h3>\n")
+            .raw_str("<p>Create and edit static content pages like Help, About, and custom pages.</p>\n")
+            .raw_str("</div>\n")
             .newline()
-            .input("default_edit_window", "Edit Window (seconds)")
+            .render_link("← Back to Registry Admin", "/admin/registry");
+
+        Self::render_footer_into(env, md).build()
+    }
+
+    /// Render branding settings page
+    fn render_branding_settings(env: &Env, viewer: &Option<Address>) -> Bytes {
+        let mut md = Self::render_settings_nav(env).h1("Branding Settings");
+
+        if !Self::is_registry_admin(env, viewer) {
+            md = md.warning("You must be a registry admin to access site settings.");
+            return Self::render_footer_into(env, md).build();
+        }
+
+        // Get current branding from config
+        let config_opt: Option<Address> = env.storage().instance().get(&AdminKey::Config);
+
+        if config_opt.is_none() {
+            md = md.warning("Config contract not set. Please contact the administrator.");
+            return Self::render_footer_into(env, md).build();
+        }
+
+        let config = config_opt.unwrap();
+        let branding: Branding =
+            env.invoke_contract(&config, &Symbol::new(env, "get_branding"), Vec::new(env));
+
+        md = md
+            .h2("Current Branding")
             .newline()
-            .form_link_to("Update", "admin", "config_set_default_edit_window")
+            .text("- **Site Name:** ")
+            .text_string(&branding.site_name)
             .newline()
-            .input("thread_body_max_bytes", "Thread Body Max Bytes")
+            .text("- **Home Content:** ")
+            .text_string(&branding.tagline)
             .newline()
-            .form_link_to("Update", "admin", "config_set_thread_body_max_bytes")
+            // Color swatch: inline span with background color and contrasting text
+            .raw_str("- **Primary Color:** <span style=\"background-color:")
+            .raw(sanitize_color(env, &branding.primary_color))
+            .raw_str(";color:#fff;padding:2px 8px;border-radius:4px;border:1px solid #333;\">")
+            .raw(Self::escape_attr(env, &branding.primary_color))
+            .raw_str("</span>\n");
+
+        if !branding.logo_url.is_empty() {
+            md = md
+                .text("- **Logo URL:** ")
+                .text_string(&branding.logo_url)
+                .newline();
+        } else {
+            md = md.text("- **Logo URL:** *(not set)*").newline();
+        }
+
+        if !branding.favicon_url.is_empty() {
+            md = md
+                .text("- **Favicon URL:** ")
+                .text_string(&branding.favicon_url)
+                .newline();
+        } else {
+            md = md.text("- **Favicon URL:** *(not set)*").newline();
+        }
+
+        if !branding.footer_text.is_empty() {
+            md = md
+                .text("- **Footer Text:** ")
+                .text_string(&branding.footer_text)
+                .newline();
+        } else {
+            md = md.text("- **Footer Text:** *(using default)*").newline();
+        }
+
+        // Update forms - one per field, pre-populated with current values
+        // Each field is wrapped in a data-form div for proper form boundary detection
+        md = md
             .newline()
+            .h2("Update Branding")
             .newline()
-            .h2("Name Length Limits")
-            .text("**Board Name:** ")
-            .number(board_name_limits.min_length)
-            .text("-")
-            .number(board_name_limits.max_length)
-            .text(" characters")
+            // Site Name form
+            .raw_str("<div data-form>\n\n")
+            .h3("Site Name")
+            .input_with_value_string("site_name", "Site name", &branding.site_name)
             .newline()
-            .text("**Community Name:** ")
-            .number(community_name_limits.min_length)
-            .text("-")
-            .number(community_name_limits.max_length)
-            .text(" characters")
+            .form_link_to("Update Site Name", "admin", "set_site_name")
+            .raw_str("\n</div>\n\n")
+            // Home Content form
+            .raw_str("<div data-form>\n\n")
+            .h3("Home Content")
+            .textarea_markdown_with_value_string(
+                "tagline",
+                3,
+                "Home content (markdown)",
+                &branding.tagline,
+            )
             .newline()
+            .form_link_to("Update Home Content", "admin", "set_tagline")
+            .raw_str("\n</div>\n\n")
+            // Primary Color form
+            .raw_str("<div data-form>\n\n")
+            .h3("Primary Color")
+            .input_with_value_string(
+                "primary_color",
+                "CSS color (e.g., #7857e1)",
+                &branding.primary_color,
+            )
             .newline()
-            .h3("Update Board Name Limits")
-            .raw_str("<input type=\"hidden\" name=\"limit_type\" value=\"board\" />\n")
-            .input("min_length", "Min Length")
+            .form_link_to("Update Primary Color", "admin", "set_primary_color")
+            .raw_str("\n</div>\n\n")
+            // Logo URL form
+            .raw_str("<div data-form>\n\n")
+            .h3("Logo URL")
+            .input_with_value_string("logo_url", "URL to logo image", &branding.logo_url)
             .newline()
-            .input("max_length", "Max Length")
+            .form_link_to("Update Logo URL", "admin", "set_logo_url")
+            .raw_str("\n</div>\n\n")
+            // Favicon URL form
+            .raw_str("<div data-form>\n\n")
+            .h3("Favicon URL")
+            .input_with_value_string("favicon_url", "URL to favicon", &branding.favicon_url)
             .newline()
-            .form_link_to("Update", "admin", "config_set_name_limits")
+            .form_link_to("Update Favicon URL", "admin", "set_favicon_url")
+            .raw_str("\n</div>\n\n")
+            // Footer Text form
+            .raw_str("<div data-form>\n\n")
+            .h3("Footer Text")
+            .textarea_markdown_with_value_noparse_string(
+                "footer_text",
+                3,
+                "Custom footer text",
+                &branding.footer_text,
+            )
+            .newline()
+            .form_link_to("Update Footer Text", "admin", "set_footer_text")
+            .raw_str("\n</div>\n\n")
+            .render_link("← Back to Settings", "/admin/settings");
+
+        Self::render_footer_into(env, md).build()
+    }
+
+    /// Render threshold settings page
+    fn render_threshold_settings(env: &Env, viewer: &Option<Address>) -> Bytes {
+        let mut md = Self::render_settings_nav(env).h1("Creation Thresholds");
+
+        if !Self::is_registry_admin(env, viewer) {
+            md = md.warning("You must be a registry admin to access site settings.");
+            return Self::render_footer_into(env, md).build();
+        }
+
+        let config_opt: Option<Address> = env.storage().instance().get(&AdminKey::Config);
+
+        if config_opt.is_none() {
+            md = md.warning("Config contract not set. Please contact the administrator.");
+            return Self::render_footer_into(env, md).build();
+        }
+
+        let config = config_opt.unwrap();
+
+        // Board thresholds
+        let board_thresholds: CreationThresholds = env.invoke_contract(
+            &config,
+            &Symbol::new(env, "get_board_thresholds"),
+            Vec::new(env),
+        );
+
+        md = md
+            .h2("Board Creation Thresholds")
+            .text("- **Minimum Karma:** ")
+            .number(board_thresholds.min_karma as u32)
+            .text("\n- **Minimum Account Age (seconds):** ")
+            .number(board_thresholds.min_account_age_secs as u32)
+            .text("\n- **Minimum Post Count:** ")
+            .number(board_thresholds.min_post_count)
+            .text("\n- **Require Profile:** ")
+            .text(if board_thresholds.require_profile {
+                "Yes"
+            } else {
+                "No"
+            })
+            .text("\n- **Per-User Limit:** ")
+            .number(board_thresholds.per_user_limit)
+            .text(" (0 = unlimited)")
+            .text("\n- **XLM Lock (stroops):** ")
+            .number(board_thresholds.xlm_lock_stroops as u32)
             .newline()
             .newline()
-            .h3("Update Community Name Limits")
-            .raw_str("<input type=\"hidden\" name=\"limit_type\" value=\"community\" />\n")
-            .input("min_length", "Min Length")
+            .raw_str("<div data-form>\n\n")
+            .h3("Update Board Thresholds")
+            .raw_str("<input type=\"hidden\" name=\"threshold_type\" value=\"board\" />\n\n")
+            .text("**Min Karma** (0 = none):")
             .newline()
-            .input("max_length", "Max Length")
+            .input_with_value_number("min_karma", "Min Karma", board_thresholds.min_karma as u32)
             .newline()
-            .form_link_to("Update", "admin", "config_set_name_limits")
+            .text("**Min Account Age** (seconds, 0 = none):")
+            .newline()
+            .input_with_value_number(
+                "min_account_age_secs",
+                "Min Account Age",
+                board_thresholds.min_account_age_secs as u32,
+            )
+            .newline()
+            .text("**Min Post Count** (0 = none):")
+            .newline()
+            .input_with_value_number(
+                "min_post_count",
+                "Min Post Count",
+                board_thresholds.min_post_count,
+            )
+            .newline()
+            .text("**Require Profile:**")
+            .newline()
+            .select_bool("require_profile", board_thresholds.require_profile)
+            .newline()
+            .text("**Per-User Limit** (0 = unlimited):")
+            .newline()
+            .input_with_value_number(
+                "per_user_limit",
+                "Per-User Limit",
+                board_thresholds.per_user_limit,
+            )
+            .newline()
+            .text("**XLM Lock** (stroops, 0 = none):")
+            .newline()
+            .input_with_value_number(
+                "xlm_lock_stroops",
+                "XLM Lock",
+                board_thresholds.xlm_lock_stroops as u32,
+            )
+            .newline()
+            .form_link_to("Update Board Thresholds", "admin", "update_thresholds")
+            .raw_str("\n</div>\n\n");
+
+        // Community thresholds
+        let community_thresholds: CreationThresholds = env.invoke_contract(
+            &config,
+            &Symbol::new(env, "get_community_thresholds"),
+            Vec::new(env),
+        );
+
+        md = md
+            .newline()
+            .h2("Community Creation Thresholds")
+            .text("- **Minimum Karma:** ")
+            .number(community_thresholds.min_karma as u32)
+            .text("\n- **Minimum Account Age (seconds):** ")
+            .number(community_thresholds.min_account_age_secs as u32)
+            .text("\n- **Minimum Post Count:** ")
+            .number(community_thresholds.min_post_count)
+            .text("\n- **Require Profile:** ")
+            .text(if community_thresholds.require_profile {
+                "Yes"
+            } else {
+                "No"
+            })
+            .text("\n- **Per-User Limit:** ")
+            .number(community_thresholds.per_user_limit)
+            .text(" (0 = unlimited)")
+            .text("\n- **XLM Lock (stroops):** ")
+            .number(community_thresholds.xlm_lock_stroops as u32)
+            .newline()
+            .newline()
+            .raw_str("<div data-form>\n\n")
+            .h3("Update Community Thresholds")
+            .raw_str("<input type=\"hidden\" name=\"threshold_type\" value=\"community\" />\n\n")
+            .text("**Min Karma** (0 = none):")
+            .newline()
+            .input_with_value_number(
+                "min_karma",
+                "Min Karma",
+                community_thresholds.min_karma as u32,
+            )
+            .newline()
+            .text("**Min Account Age** (seconds, 0 = none):")
+            .newline()
+            .input_with_value_number(
+                "min_account_age_secs",
+                "Min Account Age",
+                community_thresholds.min_account_age_secs as u32,
+            )
+            .newline()
+            .text("**Min Post Count** (0 = none):")
+            .newline()
+            .input_with_value_number(
+                "min_post_count",
+                "Min Post Count",
+                community_thresholds.min_post_count,
+            )
+            .newline()
+            .text("**Require Profile:**")
+            .newline()
+            .select_bool("require_profile", community_thresholds.require_profile)
+            .newline()
+            .text("**Per-User Limit** (0 = unlimited):")
+            .newline()
+            .input_with_value_number(
+                "per_user_limit",
+                "Per-User Limit",
+                community_thresholds.per_user_limit,
+            )
+            .newline()
+            .text("**XLM Lock** (stroops, 0 = none):")
+            .newline()
+            .input_with_value_number(
+                "xlm_lock_stroops",
+                "XLM Lock",
+                community_thresholds.xlm_lock_stroops as u32,
+            )
+            .newline()
+            .form_link_to("Update Community Thresholds", "admin", "update_thresholds")
+            .raw_str("\n</div>\n")
             .newline()
             .newline()
             .render_link("← Back to Settings", "/admin/settings");
@@ -2575,1742 +4955,7580 @@ impl BoardsAdmin {
         Self::render_footer_into(env, md).build()
     }
 
-    // ========================================================================
-    // Admin Operations (handlers for forms)
-    // ========================================================================
+    /// Render operational settings page
+    fn render_operational_settings(env: &Env, viewer: &Option<Address>) -> Bytes {
+        let mut md = Self::render_settings_nav(env).h1("Operational Settings");
+
+        if !Self::is_registry_admin(env, viewer) {
+            md = md.warning("You must be a registry admin to access site settings.");
+            return Self::render_footer_into(env, md).build();
+        }
+
+        let config_opt: Option<Address> = env.storage().instance().get(&AdminKey::Config);
+
+        if config_opt.is_none() {
+            md = md.warning("Config contract not set. Please contact the administrator.");
+            return Self::render_footer_into(env, md).build();
+        }
+
+        let config = config_opt.unwrap();
+
+        // Get current values
+        let max_reply_depth: u32 = env.invoke_contract(
+            &config,
+            &Symbol::new(env, "get_max_reply_depth"),
+            Vec::new(env),
+        );
+        let reply_chunk_size: u32 = env.invoke_contract(
+            &config,
+            &Symbol::new(env, "get_reply_chunk_size"),
+            Vec::new(env),
+        );
+        let default_edit_window: u64 = env.invoke_contract(
+            &config,
+            &Symbol::new(env, "get_default_edit_window"),
+            Vec::new(env),
+        );
+        let thread_body_max_bytes: u32 = env.invoke_contract(
+            &config,
+            &Symbol::new(env, "get_thread_body_max_bytes"),
+            Vec::new(env),
+        );
+        let board_name_limits: NameLimits = env.invoke_contract(
+            &config,
+            &Symbol::new(env, "get_board_name_limits"),
+            Vec::new(env),
+        );
+        let community_name_limits: NameLimits = env.invoke_contract(
+            &config,
+            &Symbol::new(env, "get_community_name_limits"),
+            Vec::new(env),
+        );
+
+        md = md
+            .h2("Reply Settings")
+            .text("**Max Reply Depth:** ")
+            .number(max_reply_depth)
+            .newline()
+            .text("**Reply Chunk Size:** ")
+            .number(reply_chunk_size)
+            .newline()
+            .newline()
+            .input("max_reply_depth", "Max Reply Depth")
+            .newline()
+            .form_link_to("Update", "admin", "config_set_max_reply_depth")
+            .newline()
+            .input("reply_chunk_size", "Reply Chunk Size")
+            .newline()
+            .form_link_to("Update", "admin", "config_set_reply_chunk_size")
+            .newline()
+            .newline()
+            .h2("Content Settings")
+            .text("**Default Edit Window:** ")
+            .number(default_edit_window as u32)
+            .text(" seconds (")
+            .number((default_edit_window / 3600) as u32)
+            .text(" hours)")
+            .newline()
+            .text("**Thread Body Max Bytes:** ")
+            .number(thread_body_max_bytes)
+            .newline()
+            .newline()
+            .input("default_edit_window", "Edit Window (seconds)")
+            .newline()
+            .form_link_to("Update", "admin", "config_set_default_edit_window")
+            .newline()
+            .input("thread_body_max_bytes", "Thread Body Max Bytes")
+            .newline()
+            .form_link_to("Update", "admin", "config_set_thread_body_max_bytes")
+            .newline()
+            .newline()
+            .h2("Name Length Limits")
+            .text("**Board Name:** ")
+            .number(board_name_limits.min_length)
+            .text("-")
+            .number(board_name_limits.max_length)
+            .text(" characters")
+            .newline()
+            .text("**Community Name:** ")
+            .number(community_name_limits.min_length)
+            .text("-")
+            .number(community_name_limits.max_length)
+            .text(" characters")
+            .newline()
+            .newline()
+            .h3("Update Board Name Limits")
+            .raw_str("<input type=\"hidden\" name=\"limit_type\" value=\"board\" />\n")
+            .input("min_length", "Min Length")
+            .newline()
+            .input("max_length", "Max Length")
+            .newline()
+            .form_link_to("Update", "admin", "config_set_name_limits")
+            .newline()
+            .newline()
+            .h3("Update Community Name Limits")
+            .raw_str("<input type=\"hidden\" name=\"limit_type\" value=\"community\" />\n")
+            .input("min_length", "Min Length")
+            .newline()
+            .input("max_length", "Max Length")
+            .newline()
+            .form_link_to("Update", "admin", "config_set_name_limits")
+            .newline()
+            .newline()
+            .render_link("← Back to Settings", "/admin/settings");
+
+        Self::render_footer_into(env, md).build()
+    }
+
+    // ========================================================================
+    // Admin Operations (handlers for forms)
+    // ========================================================================
+
+    /// Set a user's role on a board (admin+)
+    pub fn set_role(env: Env, board_id: u64, user: Address, role: Role, caller: Address) {
+        caller.require_auth();
+
+        let permissions: Address = env
+            .storage()
+            .instance()
+            .get(&AdminKey::Permissions)
+            .expect("Not initialized");
+
+        // Verify caller has admin permissions
+        let caller_perms: PermissionSet = env.invoke_contract(
+            &permissions,
+            &Symbol::new(&env, "get_permissions"),
+            Vec::from_array(&env, [board_id.into_val(&env), caller.into_val(&env)]),
+        );
+
+        if !caller_perms.can_admin {
+            panic!("Caller must be admin or owner");
+        }
+
+        // Set the role
+        let args: Vec<Val> = Vec::from_array(
+            &env,
+            [
+                board_id.into_val(&env),
+                user.into_val(&env),
+                role.into_val(&env),
+                caller.into_val(&env),
+            ],
+        );
+        env.invoke_contract::<()>(&permissions, &Symbol::new(&env, "set_role"), args);
+
+        Self::record_mod_action(
+            &env,
+            board_id,
+            &caller,
+            "set_role",
+            None,
+            None,
+            Some(user),
+            None,
+        );
+    }
+
+    /// Add a user as Member (convenience function for forms)
+    pub fn add_member(env: Env, board_id: u64, user_address: Address, caller: Address) {
+        Self::set_role(env, board_id, user_address, Role::Member, caller);
+    }
+
+    /// Add a user as Moderator (convenience function for forms)
+    pub fn add_moderator(env: Env, board_id: u64, user_address: Address, caller: Address) {
+        Self::set_role(env, board_id, user_address, Role::Moderator, caller);
+    }
+
+    /// Add a user as Admin (convenience function for forms)
+    pub fn add_admin(env: Env, board_id: u64, user_address: Address, caller: Address) {
+        Self::set_role(env, board_id, user_address, Role::Admin, caller);
+    }
+
+    /// Demote an Admin to Member (owner only)
+    pub fn remove_admin(env: Env, board_id: u64, user_address: Address, caller: Address) {
+        Self::remove_role(
+            env,
+            board_id,
+            user_address,
+            Role::Admin,
+            Role::Owner,
+            caller,
+        );
+    }
+
+    /// Demote a Moderator to Member (admin+)
+    pub fn remove_moderator(env: Env, board_id: u64, user_address: Address, caller: Address) {
+        Self::remove_role(
+            env,
+            board_id,
+            user_address,
+            Role::Moderator,
+            Role::Admin,
+            caller,
+        );
+    }
+
+    /// Remove a Member from the board (moderator+)
+    pub fn remove_member(env: Env, board_id: u64, user_address: Address, caller: Address) {
+        Self::remove_role(
+            env,
+            board_id,
+            user_address,
+            Role::Member,
+            Role::Moderator,
+            caller,
+        );
+    }
+
+    /// Helper to strip a role from a user.
+    /// The target must currently hold `current` (so a lower-ranked caller can't use
+    /// a removal handler to demote someone above them) and the caller must be at
+    /// least `min_caller`. Admins and moderators drop to Member; members drop to Guest.
+    fn remove_role(
+        env: Env,
+        board_id: u64,
+        user: Address,
+        current: Role,
+        min_caller: Role,
+        caller: Address,
+    ) {
+        caller.require_auth();
+
+        let permissions: Address = env
+            .storage()
+            .instance()
+            .get(&AdminKey::Permissions)
+            .expect("Not initialized");
+
+        let caller_perms: PermissionSet = env.invoke_contract(
+            &permissions,
+            &Symbol::new(&env, "get_permissions"),
+            Vec::from_array(&env, [board_id.into_val(&env), caller.into_val(&env)]),
+        );
+
+        if (caller_perms.role as u32) < (min_caller as u32) {
+            match min_caller {
+                Role::Owner => panic!("Only owner can remove admins"),
+                Role::Admin => panic!("Caller must be admin or owner"),
+                _ => panic!("Caller must be moderator or higher"),
+            }
+        }
+
+        let user_role: Role = env.invoke_contract(
+            &permissions,
+            &Symbol::new(&env, "get_role"),
+            Vec::from_array(&env, [board_id.into_val(&env), user.into_val(&env)]),
+        );
+
+        if user_role != current {
+            panic!("User does not have the role being removed");
+        }
+
+        let new_role = if current == Role::Member {
+            Role::Guest
+        } else {
+            Role::Member
+        };
+
+        let args: Vec<Val> = Vec::from_array(
+            &env,
+            [
+                board_id.into_val(&env),
+                user.into_val(&env),
+                new_role.into_val(&env),
+                caller.into_val(&env),
+            ],
+        );
+        env.invoke_contract::<()>(&permissions, &Symbol::new(&env, "set_role"), args);
+
+        Self::record_mod_action(
+            &env,
+            board_id,
+            &caller,
+            "remove_role",
+            None,
+            None,
+            Some(user),
+            None,
+        );
+    }
+
+    // ========================================================================
+    // User Notes
+    // ========================================================================
+
+    /// Attach a note about a user, visible to the mod team (moderator+).
+    /// Returns the new note's id.
+    pub fn add_user_note(
+        env: Env,
+        board_id: u64,
+        user: Address,
+        note: String,
+        caller: Address,
+    ) -> u32 {
+        caller.require_auth();
+
+        let permissions: Address = env
+            .storage()
+            .instance()
+            .get(&AdminKey::Permissions)
+            .expect("Not initialized");
+
+        // Verify caller has moderator permissions
+        let caller_perms: PermissionSet = env.invoke_contract(
+            &permissions,
+            &Symbol::new(&env, "get_permissions"),
+            Vec::from_array(&env, [board_id.into_val(&env), caller.into_val(&env)]),
+        );
+
+        if !caller_perms.can_moderate {
+            panic!("Caller must be moderator or higher");
+        }
+
+        if note.is_empty() || note.len() > MAX_USER_NOTE_LEN {
+            panic!("Note must be 1-500 characters");
+        }
+
+        let count_key = AdminKey::UserNoteCount(board_id, user.clone());
+        let id: u32 = env.storage().persistent().get(&count_key).unwrap_or(0);
+        let chunk_key = AdminKey::UserNoteChunk(board_id, user, id / USER_NOTE_CHUNK_SIZE);
+        let mut chunk: Vec<UserNote> = env
+            .storage()
+            .persistent()
+            .get(&chunk_key)
+            .unwrap_or(Vec::new(&env));
+
+        chunk.push_back(UserNote {
+            id,
+            author: caller,
+            text: note,
+            timestamp: env.ledger().timestamp(),
+        });
+
+        env.storage().persistent().set(&chunk_key, &chunk);
+        env.storage().persistent().set(&count_key, &(id + 1));
+
+        id
+    }
+
+    /// List notes about a user, oldest first
+    pub fn list_user_notes(env: Env, board_id: u64, user: Address) -> Vec<UserNote> {
+        let count: u32 = env
+            .storage()
+            .persistent()
+            .get(&AdminKey::UserNoteCount(board_id, user.clone()))
+            .unwrap_or(0);
+
+        let mut notes = Vec::new(&env);
+        let chunks = count.div_ceil(USER_NOTE_CHUNK_SIZE);
+        for chunk_index in 0..chunks {
+            let chunk: Vec<UserNote> = env
+                .storage()
+                .persistent()
+                .get(&AdminKey::UserNoteChunk(
+                    board_id,
+                    user.clone(),
+                    chunk_index,
+                ))
+                .unwrap_or(Vec::new(&env));
+            notes.append(&chunk);
+        }
+        notes
+    }
+
+    /// Delete a note about a user (note author or admin+)
+    pub fn delete_user_note(env: Env, board_id: u64, user: Address, note_id: u32, caller: Address) {
+        caller.require_auth();
+
+        let permissions: Address = env
+            .storage()
+            .instance()
+            .get(&AdminKey::Permissions)
+            .expect("Not initialized");
+
+        let chunk_key = AdminKey::UserNoteChunk(board_id, user, note_id / USER_NOTE_CHUNK_SIZE);
+        let chunk: Vec<UserNote> = env
+            .storage()
+            .persistent()
+            .get(&chunk_key)
+            .unwrap_or(Vec::new(&env));
+
+        let mut index: Option<u32> = None;
+        for i in 0..chunk.len() {
+            if chunk.get(i).unwrap().id == note_id {
+                index = Some(i);
+                break;
+            }
+        }
+        let index = index.expect("Note not found");
+
+        if chunk.get(index).unwrap().author != caller {
+            let caller_perms: PermissionSet = env.invoke_contract(
+                &permissions,
+                &Symbol::new(&env, "get_permissions"),
+                Vec::from_array(&env, [board_id.into_val(&env), caller.into_val(&env)]),
+            );
+            if !caller_perms.can_admin {
+                panic!("Only the note author or an admin can delete it");
+            }
+        }
+
+        let mut chunk = chunk;
+        chunk.remove(index);
+        env.storage().persistent().set(&chunk_key, &chunk);
+    }
+
+    // ========================================================================
+    // Ownership Transfer
+    // ========================================================================
+
+    /// Start transferring a board to a new owner (owner only).
+    /// The transfer stays pending until the new owner accepts it.
+    pub fn initiate_ownership_transfer(
+        env: Env,
+        board_id: u64,
+        new_owner: Address,
+        caller: Address,
+    ) {
+        caller.require_auth();
+
+        let permissions: Address = env
+            .storage()
+            .instance()
+            .get(&AdminKey::Permissions)
+            .expect("Not initialized");
+
+        let args: Vec<Val> = Vec::from_array(
+            &env,
+            [
+                board_id.into_val(&env),
+                new_owner.into_val(&env),
+                caller.into_val(&env),
+            ],
+        );
+        env.invoke_contract::<()>(
+            &permissions,
+            &Symbol::new(&env, "propose_board_owner"),
+            args,
+        );
+    }
+
+    /// Cancel a pending ownership transfer (owner, or the proposed owner to decline)
+    pub fn cancel_ownership_transfer(env: Env, board_id: u64, caller: Address) {
+        caller.require_auth();
+
+        let permissions: Address = env
+            .storage()
+            .instance()
+            .get(&AdminKey::Permissions)
+            .expect("Not initialized");
+
+        let args: Vec<Val> =
+            Vec::from_array(&env, [board_id.into_val(&env), caller.into_val(&env)]);
+        env.invoke_contract::<()>(
+            &permissions,
+            &Symbol::new(&env, "cancel_board_owner_transfer"),
+            args,
+        );
+    }
+
+    /// Accept a pending ownership transfer (proposed owner only).
+    /// The previous owner is demoted to Admin.
+    pub fn accept_ownership_transfer(env: Env, board_id: u64, caller: Address) {
+        caller.require_auth();
+
+        let permissions: Address = env
+            .storage()
+            .instance()
+            .get(&AdminKey::Permissions)
+            .expect("Not initialized");
+
+        let previous: Option<Address> = env.invoke_contract(
+            &permissions,
+            &Symbol::new(&env, "get_board_owner"),
+            Vec::from_array(&env, [board_id.into_val(&env)]),
+        );
+
+        let args: Vec<Val> =
+            Vec::from_array(&env, [board_id.into_val(&env), caller.into_val(&env)]);
+        env.invoke_contract::<()>(
+            &permissions,
+            &Symbol::new(&env, "accept_board_ownership"),
+            args,
+        );
+
+        Self::record_mod_action(
+            &env,
+            board_id,
+            &caller,
+            "transfer_owner",
+            None,
+            None,
+            previous,
+            None,
+        );
+    }
+
+    /// Get the proposed new owner for a board, if a transfer is pending
+    pub fn get_pending_ownership_transfer(env: Env, board_id: u64) -> Option<Address> {
+        let permissions: Address = env
+            .storage()
+            .instance()
+            .get(&AdminKey::Permissions)
+            .expect("Not initialized");
+
+        env.invoke_contract(
+            &permissions,
+            &Symbol::new(&env, "get_pending_board_owner"),
+            Vec::from_array(&env, [board_id.into_val(&env)]),
+        )
+    }
+
+    // ========================================================================
+    // Board Archival
+    // ========================================================================
+
+    /// Archive a board (owner only). The board becomes read-only and unlisted
+    /// until it is unarchived. Requires the board name or "CONFIRM" as confirmation.
+    pub fn archive_board(env: Env, board_id: u64, confirm: String, caller: Address) {
+        caller.require_auth();
+        Self::require_confirmation(&env, board_id, &confirm);
+        Self::set_board_archived(&env, board_id, true, &caller);
+    }
+
+    /// Unarchive a board (owner only), restoring its previous settings
+    pub fn unarchive_board(env: Env, board_id: u64, caller: Address) {
+        caller.require_auth();
+        Self::set_board_archived(&env, board_id, false, &caller);
+    }
+
+    /// Helper to archive or unarchive a board after checking ownership
+    fn set_board_archived(env: &Env, board_id: u64, archived: bool, caller: &Address) {
+        let permissions: Address = env
+            .storage()
+            .instance()
+            .get(&AdminKey::Permissions)
+            .expect("Not initialized");
+
+        let caller_perms: PermissionSet = env.invoke_contract(
+            &permissions,
+            &Symbol::new(env, "get_permissions"),
+            Vec::from_array(env, [board_id.into_val(env), caller.into_val(env)]),
+        );
+        if caller_perms.role != Role::Owner {
+            panic!("Only the board owner can archive or unarchive it");
+        }
+
+        let board_contract = Self::get_board_contract_address(env);
+        let func = if archived {
+            "archive_board"
+        } else {
+            "unarchive_board"
+        };
+        env.invoke_contract::<()>(
+            &board_contract,
+            &Symbol::new(env, func),
+            Vec::from_array(env, [board_id.into_val(env), caller.into_val(env)]),
+        );
+
+        Self::record_mod_action(env, board_id, caller, func, None, None, None, None);
+    }
+
+    // ========================================================================
+    // Registry Admin Operations
+    // ========================================================================
+
+    /// Add a site-wide admin on the registry (registry admin only)
+    pub fn add_registry_admin(env: Env, new_admin: Address, caller: Address) {
+        Self::require_registry_admin(&env, &caller);
+        let registry: Address = env
+            .storage()
+            .instance()
+            .get(&AdminKey::Registry)
+            .expect("Not initialized");
+
+        let args: Vec<Val> =
+            Vec::from_array(&env, [new_admin.into_val(&env), caller.into_val(&env)]);
+        env.invoke_contract::<()>(&registry, &Symbol::new(&env, "add_admin"), args);
+    }
+
+    /// Ban a user from every board via the registry (registry admin only).
+    /// Accepts duration_hours as String since HTML forms submit strings; 0 = permanent.
+    pub fn global_ban_user(
+        env: Env,
+        user: Address,
+        reason: String,
+        duration_hours: String,
+        caller: Address,
+    ) {
+        Self::require_registry_admin(&env, &caller);
+        let registry: Address = env
+            .storage()
+            .instance()
+            .get(&AdminKey::Registry)
+            .expect("Not initialized");
+
+        let hours = Self::parse_string_to_u64(&duration_hours);
+        let expires_at: Option<u64> = if hours > 0 {
+            let expires_at = hours
+                .checked_mul(3600)
+                .and_then(|secs| env.ledger().timestamp().checked_add(secs))
+                .expect("Ban duration is too long");
+            Some(expires_at)
+        } else {
+            None
+        };
+
+        let args: Vec<Val> = Vec::from_array(
+            &env,
+            [
+                user.into_val(&env),
+                reason.into_val(&env),
+                expires_at.into_val(&env),
+                caller.into_val(&env),
+            ],
+        );
+        env.invoke_contract::<()>(&registry, &Symbol::new(&env, "global_ban"), args);
+    }
+
+    /// Update the registry's per-account board cap and cooldown (registry admin only).
+    /// Accepts both as Strings since HTML forms submit strings; 0 disables a limit.
+    pub fn set_board_creation_limits(
+        env: Env,
+        boards_per_account: String,
+        cooldown_secs: String,
+        caller: Address,
+    ) {
+        Self::require_registry_admin(&env, &caller);
+        let registry: Address = env
+            .storage()
+            .instance()
+            .get(&AdminKey::Registry)
+            .expect("Not initialized");
+
+        let cap = Self::parse_string_to_u64(&boards_per_account) as u32;
+        let cooldown = Self::parse_string_to_u64(&cooldown_secs);
+        let args: Vec<Val> = Vec::from_array(
+            &env,
+            [
+                cap.into_val(&env),
+                cooldown.into_val(&env),
+                caller.into_val(&env),
+            ],
+        );
+        env.invoke_contract::<()>(
+            &registry,
+            &Symbol::new(&env, "set_board_creation_limits"),
+            args,
+        );
+    }
+
+    /// Feature a board on the home page (registry admin only)
+    pub fn feature_board(env: Env, board_id: u64, caller: Address) {
+        Self::update_featured(&env, board_id, true, caller);
+    }
+
+    /// Take a board off the home page's featured list (registry admin only)
+    pub fn unfeature_board(env: Env, board_id: u64, caller: Address) {
+        Self::update_featured(&env, board_id, false, caller);
+    }
+
+    fn update_featured(env: &Env, board_id: u64, featured: bool, caller: Address) {
+        Self::require_registry_admin(env, &caller);
+
+        let board_contract = Self::get_board_contract_address(env);
+        let args: Vec<Val> = Vec::from_array(
+            env,
+            [
+                board_id.into_val(env),
+                featured.into_val(env),
+                caller.into_val(env),
+            ],
+        );
+        env.invoke_contract::<()>(&board_contract, &Symbol::new(env, "set_featured"), args);
+    }
+
+    /// Move a featured board to a new position (registry admin only)
+    pub fn move_featured_board(env: Env, board_id: u64, position: u32, caller: Address) {
+        Self::require_registry_admin(&env, &caller);
+
+        let board_contract = Self::get_board_contract_address(&env);
+        let args: Vec<Val> = Vec::from_array(
+            &env,
+            [
+                board_id.into_val(&env),
+                position.into_val(&env),
+                caller.into_val(&env),
+            ],
+        );
+        env.invoke_contract::<()>(&board_contract, &Symbol::new(&env, "move_featured"), args);
+    }
+
+    /// Lift a user's global ban via the registry (registry admin only)
+    pub fn global_unban_user(env: Env, user: Address, caller: Address) {
+        Self::require_registry_admin(&env, &caller);
+        let registry: Address = env
+            .storage()
+            .instance()
+            .get(&AdminKey::Registry)
+            .expect("Not initialized");
+
+        let args: Vec<Val> = Vec::from_array(&env, [user.into_val(&env), caller.into_val(&env)]);
+        env.invoke_contract::<()>(&registry, &Symbol::new(&env, "global_unban"), args);
+    }
+
+    /// Remove a site-wide admin from the registry (registry admin only).
+    /// The last remaining admin cannot be removed.
+    pub fn remove_registry_admin(env: Env, admin_to_remove: Address, caller: Address) {
+        Self::require_registry_admin(&env, &caller);
+        let registry: Address = env
+            .storage()
+            .instance()
+            .get(&AdminKey::Registry)
+            .expect("Not initialized");
+
+        let admins: Vec<Address> =
+            env.invoke_contract(&registry, &Symbol::new(&env, "get_admins"), Vec::new(&env));
+        if admins.len() <= 1 {
+            panic!("Cannot remove the last admin");
+        }
+
+        let args: Vec<Val> = Vec::from_array(
+            &env,
+            [admin_to_remove.into_val(&env), caller.into_val(&env)],
+        );
+        env.invoke_contract::<()>(&registry, &Symbol::new(&env, "remove_admin"), args);
+    }
+
+    // ========================================================================
+    // Contract Upgrades
+    // ========================================================================
+
+    /// Upgrade the board contract (registry admin only). A single board
+    /// contract serves every board, so this upgrades all boards at once.
+    pub fn upgrade_board(env: Env, new_wasm_hash: BytesN<32>, caller: Address) {
+        Self::upgrade_alias(&env, Symbol::new(&env, "board"), new_wasm_hash, &caller);
+    }
+
+    /// Upgrade a registered contract from the upgrades page (registry admin only).
+    /// Accepts the alias and hex-encoded hash as Strings since HTML forms submit strings.
+    pub fn upgrade_contract_by_alias(env: Env, alias: String, wasm_hash: String, caller: Address) {
+        let name = UPGRADEABLE_ALIASES
+            .iter()
+            .find(|a| String::from_str(&env, a) == alias)
+            .expect("Unknown contract alias");
+        let new_wasm_hash = Self::parse_wasm_hash(&env, &wasm_hash);
+        Self::upgrade_alias(&env, Symbol::new(&env, name), new_wasm_hash, &caller);
+    }
+
+    /// Helper: upgrade the contract registered under `alias` via the registry,
+    /// which the target contract trusts to authorize upgrades
+    fn upgrade_alias(env: &Env, alias: Symbol, new_wasm_hash: BytesN<32>, caller: &Address) {
+        Self::require_registry_admin(env, caller);
+        let registry: Address = env
+            .storage()
+            .instance()
+            .get(&AdminKey::Registry)
+            .expect("Not initialized");
+
+        let contract: Address = env
+            .invoke_contract::<Option<Address>>(
+                &registry,
+                &Symbol::new(env, "get_contract_by_alias"),
+                Vec::from_array(env, [alias.into_val(env)]),
+            )
+            .expect("Contract not registered");
+
+        let args: Vec<Val> = Vec::from_array(
+            env,
+            [
+                contract.into_val(env),
+                new_wasm_hash.into_val(env),
+                caller.into_val(env),
+            ],
+        );
+        env.invoke_contract::<()>(&registry, &Symbol::new(env, "upgrade_contract"), args);
+    }
+
+    // ========================================================================
+    // Moderation Log
+    // ========================================================================
+
+    /// Get the number of moderation log entries for a board
+    pub fn get_mod_action_count(env: Env, board_id: u64) -> u64 {
+        env.storage()
+            .persistent()
+            .get(&AdminKey::ModLogCount(board_id))
+            .unwrap_or(0)
+    }
+
+    /// List moderation log entries for a board, newest first.
+    /// `start` is an offset from the most recent entry.
+    pub fn list_mod_actions(env: Env, board_id: u64, start: u64, limit: u64) -> Vec<ModAction> {
+        let count = Self::get_mod_action_count(env.clone(), board_id);
+        let mut actions = Vec::new(&env);
+        let end = core::cmp::min(start.saturating_add(limit), count);
+
+        let mut loaded_index: Option<u64> = None;
+        let mut chunk: Vec<ModAction> = Vec::new(&env);
+        for i in start..end {
+            let index = count - 1 - i;
+            let chunk_index = index / MOD_LOG_CHUNK_SIZE;
+            if loaded_index != Some(chunk_index) {
+                chunk = env
+                    .storage()
+                    .persistent()
+                    .get(&AdminKey::ModLogChunk(board_id, chunk_index))
+                    .unwrap_or(Vec::new(&env));
+                loaded_index = Some(chunk_index);
+            }
+            if let Some(action) = chunk.get((index % MOD_LOG_CHUNK_SIZE) as u32) {
+                actions.push_back(action);
+            }
+        }
+
+        actions
+    }
+
+    /// Append an entry to a board's moderation log.
+    /// Entries are stored in fixed-size chunks so a single write never has to
+    /// rewrite the whole history.
+    fn record_mod_action(
+        env: &Env,
+        board_id: u64,
+        actor: &Address,
+        action: &str,
+        target_thread: Option<u64>,
+        target_reply: Option<u64>,
+        target_user: Option<Address>,
+        note: Option<String>,
+    ) {
+        let count: u64 = env
+            .storage()
+            .persistent()
+            .get(&AdminKey::ModLogCount(board_id))
+            .unwrap_or(0);
+        let chunk_key = AdminKey::ModLogChunk(board_id, count / MOD_LOG_CHUNK_SIZE);
+        let mut chunk: Vec<ModAction> = env
+            .storage()
+            .persistent()
+            .get(&chunk_key)
+            .unwrap_or(Vec::new(env));
+
+        let target = match (target_thread, target_reply, &target_user) {
+            (Some(thread_id), Some(reply_id), _) => ModTarget::Reply(thread_id, reply_id),
+            (Some(thread_id), None, _) => ModTarget::Thread(thread_id),
+            (None, _, Some(user)) => ModTarget::User(user.clone()),
+            _ => ModTarget::Board,
+        };
+        Self::emit_mod_event(env, board_id, action, target, actor, note.as_ref());
+
+        chunk.push_back(ModAction {
+            actor: actor.clone(),
+            board_id,
+            action: Symbol::new(env, action),
+            target_thread,
+            target_reply,
+            target_user,
+            timestamp: env.ledger().timestamp(),
+            note,
+        });
+
+        env.storage().persistent().set(&chunk_key, &chunk);
+        env.storage()
+            .persistent()
+            .set(&AdminKey::ModLogCount(board_id), &(count + 1));
+    }
+
+    /// Publish a moderation event for off-chain alerting.
+    /// Topics are `("mod", board_id)`; data is `(action, target, caller, reason)`
+    /// with the reason cut to its first MOD_EVENT_REASON_LEN bytes.
+    fn emit_mod_event(
+        env: &Env,
+        board_id: u64,
+        action: &str,
+        target: ModTarget,
+        caller: &Address,
+        reason: Option<&String>,
+    ) {
+        let reason = reason.map(|reason| {
+            let mut buf = [0u8; MOD_EVENT_REASON_LEN + 1];
+            let copied = copy_string_prefix(reason, &mut buf);
+            let mut len = copied.min(MOD_EVENT_REASON_LEN);
+            if copied > MOD_EVENT_REASON_LEN {
+                // Back off while the cut lands on a UTF-8 continuation byte
+                while len > 0 && buf[len] & 0xC0 == 0x80 {
+                    len -= 1;
+                }
+            }
+            String::from_bytes(env, &buf[..len])
+        });
+        env.events().publish(
+            (symbol_short!("mod"), board_id),
+            (Symbol::new(env, action), target, caller.clone(), reason),
+        );
+    }
+
+    /// Tell a user about a moderator action on their content. Best effort:
+    /// content contracts without an inbox are skipped.
+    fn notify_user(
+        env: &Env,
+        recipient: &Address,
+        kind: &str,
+        board_id: u64,
+        thread_id: u64,
+        reply_id: Option<u64>,
+        actor: &Address,
+    ) {
+        let Some(content) = env
+            .storage()
+            .instance()
+            .get::<_, Address>(&AdminKey::Content)
+        else {
+            return;
+        };
+        let args: Vec<Val> = Vec::from_array(
+            env,
+            [
+                recipient.into_val(env),
+                Symbol::new(env, kind).into_val(env),
+                board_id.into_val(env),
+                thread_id.into_val(env),
+                reply_id.into_val(env),
+                actor.into_val(env),
+            ],
+        );
+        let _ = env.try_invoke_contract::<(), soroban_sdk::Error>(
+            &content,
+            &Symbol::new(env, "push_notification"),
+            args,
+        );
+    }
+
+    /// Notify the author of a thread (reply_id None) or reply that it was hidden
+    fn notify_hidden(
+        env: &Env,
+        board_id: u64,
+        thread_id: u64,
+        reply_id: Option<u64>,
+        actor: &Address,
+    ) {
+        if let Some(author) = Self::item_author(env, board_id, thread_id, reply_id) {
+            Self::notify_user(env, &author, "hidden", board_id, thread_id, reply_id, actor);
+        }
+    }
+
+    /// Author of a thread (reply_id None) or reply, or None if it doesn't exist
+    fn item_author(
+        env: &Env,
+        board_id: u64,
+        thread_id: u64,
+        reply_id: Option<u64>,
+    ) -> Option<Address> {
+        match reply_id {
+            None => env
+                .try_invoke_contract::<Option<(String, Address)>, soroban_sdk::Error>(
+                    &Self::get_board_contract_address(env),
+                    &Symbol::new(env, "get_thread_title_and_author"),
+                    Vec::from_array(env, [board_id.into_val(env), thread_id.into_val(env)]),
+                )
+                .ok()
+                .and_then(|r| r.ok())
+                .flatten()
+                .map(|(_, author)| author),
+            Some(reply_id) => {
+                let content: Address = env
+                    .storage()
+                    .instance()
+                    .get(&AdminKey::Content)
+                    .expect("Not initialized");
+                env.try_invoke_contract::<Option<Address>, soroban_sdk::Error>(
+                    &content,
+                    &Symbol::new(env, "get_reply_author"),
+                    Vec::from_array(
+                        env,
+                        [
+                            board_id.into_val(env),
+                            thread_id.into_val(env),
+                            reply_id.into_val(env),
+                        ],
+                    ),
+                )
+                .ok()
+                .and_then(|r| r.ok())
+                .flatten()
+            }
+        }
+    }
+
+    // ========================================================================
+    // Invite Operations
+    // ========================================================================
+
+    /// Accept a pending invite request as Member (moderator+)
+    pub fn accept_invite(env: Env, board_id: u64, user: Address, caller: Address) {
+        Self::accept_invite_as(env, board_id, user, Role::Member, caller);
+    }
+
+    /// Accept a pending invite request as Moderator (admin+)
+    pub fn accept_invite_moderator(env: Env, board_id: u64, user: Address, caller: Address) {
+        Self::accept_invite_as(env, board_id, user, Role::Moderator, caller);
+    }
+
+    /// Accept a pending invite request, granting the chosen role.
+    /// Moderators may only grant Member; Moderator and above needs admin+.
+    pub fn accept_invite_as(env: Env, board_id: u64, user: Address, role: Role, caller: Address) {
+        caller.require_auth();
+
+        let permissions: Address = env
+            .storage()
+            .instance()
+            .get(&AdminKey::Permissions)
+            .expect("Not initialized");
+
+        // Verify caller has moderator permissions
+        let caller_perms: PermissionSet = env.invoke_contract(
+            &permissions,
+            &Symbol::new(&env, "get_permissions"),
+            Vec::from_array(&env, [board_id.into_val(&env), caller.into_val(&env)]),
+        );
+
+        if !caller_perms.can_moderate {
+            panic!("Caller must be moderator or higher");
+        }
+
+        match role {
+            Role::Member => {}
+            Role::Moderator | Role::Admin => {
+                if !caller_perms.can_admin {
+                    panic!("Caller must be admin or owner to grant Moderator or above");
+                }
+            }
+            _ => panic!("Invalid role for invite"),
+        }
+
+        // Accept the invite; the permissions contract enforces owner-only Admin grants
+        let args: Vec<Val> = Vec::from_array(
+            &env,
+            [
+                board_id.into_val(&env),
+                user.into_val(&env),
+                role.into_val(&env),
+                caller.into_val(&env),
+            ],
+        );
+        env.invoke_contract::<()>(
+            &permissions,
+            &Symbol::new(&env, "accept_invite_with_role"),
+            args,
+        );
+
+        Self::emit_mod_event(
+            &env,
+            board_id,
+            "accept_invite",
+            ModTarget::User(user),
+            &caller,
+            None,
+        );
+    }
+
+    /// Revoke/reject a pending invite request (moderator+)
+    pub fn revoke_invite(env: Env, board_id: u64, user: Address, caller: Address) {
+        caller.require_auth();
+
+        let permissions: Address = env
+            .storage()
+            .instance()
+            .get(&AdminKey::Permissions)
+            .expect("Not initialized");
+
+        // Verify caller has moderator permissions
+        let caller_perms: PermissionSet = env.invoke_contract(
+            &permissions,
+            &Symbol::new(&env, "get_permissions"),
+            Vec::from_array(&env, [board_id.into_val(&env), caller.into_val(&env)]),
+        );
+
+        if !caller_perms.can_moderate {
+            panic!("Caller must be moderator or higher");
+        }
+
+        // Revoke the invite
+        let args: Vec<Val> = Vec::from_array(
+            &env,
+            [
+                board_id.into_val(&env),
+                user.into_val(&env),
+                caller.into_val(&env),
+            ],
+        );
+        env.invoke_contract::<()>(&permissions, &Symbol::new(&env, "revoke_invite"), args);
+    }
+
+    /// Remove expired invite requests for a board (moderator+)
+    /// Returns the number of requests removed
+    pub fn purge_expired_requests(env: Env, board_id: u64, caller: Address) -> u32 {
+        caller.require_auth();
+
+        let permissions: Address = env
+            .storage()
+            .instance()
+            .get(&AdminKey::Permissions)
+            .expect("Not initialized");
+
+        // Verify caller has moderator permissions
+        let caller_perms: PermissionSet = env.invoke_contract(
+            &permissions,
+            &Symbol::new(&env, "get_permissions"),
+            Vec::from_array(&env, [board_id.into_val(&env), caller.into_val(&env)]),
+        );
+
+        if !caller_perms.can_moderate {
+            panic!("Caller must be moderator or higher");
+        }
+
+        let args: Vec<Val> =
+            Vec::from_array(&env, [board_id.into_val(&env), caller.into_val(&env)]);
+        env.invoke_contract::<u32>(
+            &permissions,
+            &Symbol::new(&env, "purge_expired_requests"),
+            args,
+        )
+    }
+
+    /// Leave a board, giving up the caller's own role (members and staff).
+    /// Owners must transfer ownership first. Requires the board name or "CONFIRM".
+    pub fn leave_board(env: Env, board_id: u64, confirm: String, caller: Address) {
+        caller.require_auth();
+        Self::require_confirmation(&env, board_id, &confirm);
+
+        let permissions: Address = env
+            .storage()
+            .instance()
+            .get(&AdminKey::Permissions)
+            .expect("Not initialized");
+
+        let caller_perms: PermissionSet = env.invoke_contract(
+            &permissions,
+            &Symbol::new(&env, "get_permissions"),
+            Vec::from_array(
+                &env,
+                [board_id.into_val(&env), caller.clone().into_val(&env)],
+            ),
+        );
+        if caller_perms.role == Role::Owner {
+            panic!("Owner must transfer ownership before leaving the board");
+        }
+
+        let args: Vec<Val> =
+            Vec::from_array(&env, [board_id.into_val(&env), caller.into_val(&env)]);
+        env.invoke_contract::<()>(&permissions, &Symbol::new(&env, "leave_board"), args);
+    }
+
+    /// Directly invite a user as Member (moderator+)
+    pub fn invite_member(env: Env, board_id: u64, user: Address, caller: Address) {
+        Self::invite_with_role(env, board_id, user, Role::Member, caller);
+    }
+
+    /// Directly invite a user as Moderator (admin+)
+    pub fn invite_moderator(env: Env, board_id: u64, user: Address, caller: Address) {
+        Self::invite_with_role(env, board_id, user, Role::Moderator, caller);
+    }
+
+    /// Directly invite a user as Admin (owner only)
+    pub fn invite_admin(env: Env, board_id: u64, user: Address, caller: Address) {
+        Self::invite_with_role(env, board_id, user, Role::Admin, caller);
+    }
+
+    /// Helper function to invite with a specific role
+    fn invite_with_role(env: Env, board_id: u64, user: Address, role: Role, caller: Address) {
+        caller.require_auth();
+
+        let permissions: Address = env
+            .storage()
+            .instance()
+            .get(&AdminKey::Permissions)
+            .expect("Not initialized");
+
+        // The permissions contract handles authorization checks
+        let args: Vec<Val> = Vec::from_array(
+            &env,
+            [
+                board_id.into_val(&env),
+                user.into_val(&env),
+                role.into_val(&env),
+                caller.into_val(&env),
+            ],
+        );
+        env.invoke_contract::<()>(&permissions, &Symbol::new(&env, "invite_member"), args);
+    }
+
+    /// Ban a user from a board (moderator+)
+    pub fn ban_user(
+        env: Env,
+        board_id: u64,
+        user: Address,
+        reason: String,
+        duration_hours: u64,
+        caller: Address,
+    ) {
+        caller.require_auth();
+
+        let permissions: Address = env
+            .storage()
+            .instance()
+            .get(&AdminKey::Permissions)
+            .expect("Not initialized");
+
+        // Verify caller has moderator permissions
+        let caller_perms: PermissionSet = env.invoke_contract(
+            &permissions,
+            &Symbol::new(&env, "get_permissions"),
+            Vec::from_array(&env, [board_id.into_val(&env), caller.into_val(&env)]),
+        );
+
+        if !caller_perms.can_moderate {
+            panic!("Caller must be moderator or higher");
+        }
+
+        // 0 = permanent; permissions computes the expiry from the duration
+        let duration: Option<u64> = if duration_hours > 0 {
+            Some(duration_hours)
+        } else {
+            None
+        };
+
+        // Ban the user
+        // Permissions expects: (board_id, user, reason, duration_hours, caller)
+        let args: Vec<Val> = Vec::from_array(
+            &env,
+            [
+                board_id.into_val(&env),
+                user.into_val(&env),
+                reason.into_val(&env),
+                duration.into_val(&env),
+                caller.into_val(&env),
+            ],
+        );
+        env.invoke_contract::<()>(&permissions, &Symbol::new(&env, "ban_user"), args);
+
+        Self::notify_user(&env, &user, "banned", board_id, 0, None, &caller);
+        Self::record_mod_action(
+            &env,
+            board_id,
+            &caller,
+            "ban_user",
+            None,
+            None,
+            Some(user),
+            Some(reason),
+        );
+    }
+
+    /// Unban a user from a board (moderator+)
+    pub fn unban_user(env: Env, board_id: u64, user: Address, caller: Address) {
+        caller.require_auth();
+
+        let permissions: Address = env
+            .storage()
+            .instance()
+            .get(&AdminKey::Permissions)
+            .expect("Not initialized");
+
+        // Verify caller has moderator permissions
+        let caller_perms: PermissionSet = env.invoke_contract(
+            &permissions,
+            &Symbol::new(&env, "get_permissions"),
+            Vec::from_array(&env, [board_id.into_val(&env), caller.into_val(&env)]),
+        );
+
+        if !caller_perms.can_moderate {
+            panic!("Caller must be moderator or higher");
+        }
+
+        // Unban the user
+        let args: Vec<Val> = Vec::from_array(
+            &env,
+            [
+                board_id.into_val(&env),
+                user.into_val(&env),
+                caller.into_val(&env),
+            ],
+        );
+        env.invoke_contract::<()>(&permissions, &Symbol::new(&env, "unban_user"), args);
+
+        Self::record_mod_action(
+            &env,
+            board_id,
+            &caller,
+            "unban_user",
+            None,
+            None,
+            Some(user),
+            None,
+        );
+    }
+
+    /// Lengthen a user's ban (moderator+).
+    /// Accepts duration_hours as String since HTML forms submit strings; 0 = permanent.
+    pub fn extend_ban(
+        env: Env,
+        board_id: u64,
+        user: Address,
+        duration_hours: String,
+        caller: Address,
+    ) {
+        caller.require_auth();
+
+        let hours = Self::parse_string_to_u64(&duration_hours);
+
+        let permissions: Address = env
+            .storage()
+            .instance()
+            .get(&AdminKey::Permissions)
+            .expect("Not initialized");
+
+        // Verify caller has moderator permissions
+        let caller_perms: PermissionSet = env.invoke_contract(
+            &permissions,
+            &Symbol::new(&env, "get_permissions"),
+            Vec::from_array(&env, [board_id.into_val(&env), caller.into_val(&env)]),
+        );
+
+        if !caller_perms.can_moderate {
+            panic!("Caller must be moderator or higher");
+        }
+
+        let duration: Option<u64> = if hours > 0 { Some(hours) } else { None };
+
+        let args: Vec<Val> = Vec::from_array(
+            &env,
+            [
+                board_id.into_val(&env),
+                user.into_val(&env),
+                duration.into_val(&env),
+                caller.into_val(&env),
+            ],
+        );
+        env.invoke_contract::<()>(&permissions, &Symbol::new(&env, "extend_ban"), args);
+
+        Self::record_mod_action(
+            &env,
+            board_id,
+            &caller,
+            "extend_ban",
+            None,
+            None,
+            Some(user),
+            None,
+        );
+    }
+
+    /// Remove expired bans from a board (moderator+).
+    /// Returns the number of bans removed.
+    pub fn purge_expired_bans(env: Env, board_id: u64, caller: Address) -> u32 {
+        caller.require_auth();
+
+        let permissions: Address = env
+            .storage()
+            .instance()
+            .get(&AdminKey::Permissions)
+            .expect("Not initialized");
+
+        // Verify caller has moderator permissions
+        let caller_perms: PermissionSet = env.invoke_contract(
+            &permissions,
+            &Symbol::new(&env, "get_permissions"),
+            Vec::from_array(&env, [board_id.into_val(&env), caller.into_val(&env)]),
+        );
+
+        if !caller_perms.can_moderate {
+            panic!("Caller must be moderator or higher");
+        }
+
+        let args: Vec<Val> =
+            Vec::from_array(&env, [board_id.into_val(&env), caller.into_val(&env)]);
+        env.invoke_contract(&permissions, &Symbol::new(&env, "purge_expired_bans"), args)
+    }
+
+    /// Quarantine or release a user (moderator+).
+    /// A quarantined user's new threads and replies start hidden until approved.
+    /// Accepts the flag as a String since HTML forms submit strings
+    pub fn set_quarantined(env: Env, board_id: u64, user: Address, on: String, caller: Address) {
+        caller.require_auth();
+
+        let on = Self::parse_checkbox(&Some(on), false);
+
+        let permissions: Address = env
+            .storage()
+            .instance()
+            .get(&AdminKey::Permissions)
+            .expect("Not initialized");
+
+        // Verify caller has moderator permissions
+        let caller_perms: PermissionSet = env.invoke_contract(
+            &permissions,
+            &Symbol::new(&env, "get_permissions"),
+            Vec::from_array(&env, [board_id.into_val(&env), caller.into_val(&env)]),
+        );
+
+        if !caller_perms.can_moderate {
+            panic!("Caller must be moderator or higher");
+        }
+
+        let args: Vec<Val> = Vec::from_array(
+            &env,
+            [
+                board_id.into_val(&env),
+                user.into_val(&env),
+                on.into_val(&env),
+                caller.into_val(&env),
+            ],
+        );
+        env.invoke_contract::<()>(&permissions, &Symbol::new(&env, "set_quarantined"), args);
+
+        let action = if on { "quarantine" } else { "unquarantine" };
+        Self::record_mod_action(
+            &env,
+            board_id,
+            &caller,
+            action,
+            None,
+            None,
+            Some(user),
+            None,
+        );
+    }
+
+    /// Hide a thread (moderator+)
+    pub fn hide_thread(env: Env, board_id: u64, thread_id: u64, caller: Address) {
+        caller.require_auth();
+
+        let permissions: Address = env
+            .storage()
+            .instance()
+            .get(&AdminKey::Permissions)
+            .expect("Not initialized");
+
+        // Verify caller has moderator permissions
+        let caller_perms: PermissionSet = env.invoke_contract(
+            &permissions,
+            &Symbol::new(&env, "get_permissions"),
+            Vec::from_array(&env, [board_id.into_val(&env), caller.into_val(&env)]),
+        );
+
+        if !caller_perms.can_moderate {
+            panic!("Caller must be moderator or higher");
+        }
+
+        // Get board contract (single contract for all boards)
+        let board_contract = Self::get_board_contract_address(&env);
+
+        // Hide the thread (now requires board_id)
+        let args: Vec<Val> = Vec::from_array(
+            &env,
+            [
+                board_id.into_val(&env),
+                thread_id.into_val(&env),
+                true.into_val(&env),
+            ],
+        );
+        env.invoke_contract::<()>(
+            &board_contract,
+            &Symbol::new(&env, "set_thread_hidden"),
+            args,
+        );
+
+        Self::notify_hidden(&env, board_id, thread_id, None, &caller);
+        Self::record_mod_action(
+            &env,
+            board_id,
+            &caller,
+            "hide_thread",
+            Some(thread_id),
+            None,
+            None,
+            None,
+        );
+    }
+
+    /// Unhide a thread (moderator+)
+    pub fn unhide_thread(env: Env, board_id: u64, thread_id: u64, caller: Address) {
+        caller.require_auth();
+
+        let permissions: Address = env
+            .storage()
+            .instance()
+            .get(&AdminKey::Permissions)
+            .expect("Not initialized");
+
+        // Verify caller has moderator permissions
+        let caller_perms: PermissionSet = env.invoke_contract(
+            &permissions,
+            &Symbol::new(&env, "get_permissions"),
+            Vec::from_array(&env, [board_id.into_val(&env), caller.into_val(&env)]),
+        );
+
+        if !caller_perms.can_moderate {
+            panic!("Caller must be moderator or higher");
+        }
+
+        // Get board contract (single contract for all boards)
+        let board_contract = Self::get_board_contract_address(&env);
+
+        // Unhide the thread (now requires board_id)
+        let args: Vec<Val> = Vec::from_array(
+            &env,
+            [
+                board_id.into_val(&env),
+                thread_id.into_val(&env),
+                false.into_val(&env),
+            ],
+        );
+        env.invoke_contract::<()>(
+            &board_contract,
+            &Symbol::new(&env, "set_thread_hidden"),
+            args,
+        );
+
+        Self::record_mod_action(
+            &env,
+            board_id,
+            &caller,
+            "unhide_thread",
+            Some(thread_id),
+            None,
+            None,
+            None,
+        );
+    }
+
+    /// Hide a reply (moderator+)
+    pub fn hide_reply(env: Env, board_id: u64, thread_id: u64, reply_id: u64, caller: Address) {
+        caller.require_auth();
+
+        let permissions: Address = env
+            .storage()
+            .instance()
+            .get(&AdminKey::Permissions)
+            .expect("Not initialized");
+        let content: Address = env
+            .storage()
+            .instance()
+            .get(&AdminKey::Content)
+            .expect("Not initialized");
+
+        // Verify caller has moderator permissions
+        let caller_perms: PermissionSet = env.invoke_contract(
+            &permissions,
+            &Symbol::new(&env, "get_permissions"),
+            Vec::from_array(&env, [board_id.into_val(&env), caller.into_val(&env)]),
+        );
+
+        if !caller_perms.can_moderate {
+            panic!("Caller must be moderator or higher");
+        }
+
+        // Hide the reply
+        let args: Vec<Val> = Vec::from_array(
+            &env,
+            [
+                board_id.into_val(&env),
+                thread_id.into_val(&env),
+                reply_id.into_val(&env),
+                true.into_val(&env),
+            ],
+        );
+        env.invoke_contract::<()>(&content, &Symbol::new(&env, "set_reply_hidden"), args);
+
+        Self::notify_hidden(&env, board_id, thread_id, Some(reply_id), &caller);
+        Self::record_mod_action(
+            &env,
+            board_id,
+            &caller,
+            "hide_reply",
+            Some(thread_id),
+            Some(reply_id),
+            None,
+            None,
+        );
+    }
+
+    /// Unhide a reply (moderator+)
+    pub fn unhide_reply(env: Env, board_id: u64, thread_id: u64, reply_id: u64, caller: Address) {
+        caller.require_auth();
+
+        let permissions: Address = env
+            .storage()
+            .instance()
+            .get(&AdminKey::Permissions)
+            .expect("Not initialized");
+        let content: Address = env
+            .storage()
+            .instance()
+            .get(&AdminKey::Content)
+            .expect("Not initialized");
+
+        // Verify caller has moderator permissions
+        let caller_perms: PermissionSet = env.invoke_contract(
+            &permissions,
+            &Symbol::new(&env, "get_permissions"),
+            Vec::from_array(&env, [board_id.into_val(&env), caller.into_val(&env)]),
+        );
+
+        if !caller_perms.can_moderate {
+            panic!("Caller must be moderator or higher");
+        }
+
+        // Unhide the reply
+        let args: Vec<Val> = Vec::from_array(
+            &env,
+            [
+                board_id.into_val(&env),
+                thread_id.into_val(&env),
+                reply_id.into_val(&env),
+                false.into_val(&env),
+            ],
+        );
+        env.invoke_contract::<()>(&content, &Symbol::new(&env, "set_reply_hidden"), args);
+
+        Self::record_mod_action(
+            &env,
+            board_id,
+            &caller,
+            "unhide_reply",
+            Some(thread_id),
+            Some(reply_id),
+            None,
+            None,
+        );
+    }
+
+    /// Clear flags on content (moderator+)
+    pub fn clear_flags(
+        env: Env,
+        board_id: u64,
+        thread_id: u64,
+        reply_id: Option<u64>,
+        caller: Address,
+    ) {
+        caller.require_auth();
+
+        let permissions: Address = env
+            .storage()
+            .instance()
+            .get(&AdminKey::Permissions)
+            .expect("Not initialized");
+        let content: Address = env
+            .storage()
+            .instance()
+            .get(&AdminKey::Content)
+            .expect("Not initialized");
+
+        // Verify caller has moderator permissions
+        let caller_perms: PermissionSet = env.invoke_contract(
+            &permissions,
+            &Symbol::new(&env, "get_permissions"),
+            Vec::from_array(&env, [board_id.into_val(&env), caller.into_val(&env)]),
+        );
+
+        if !caller_perms.can_moderate {
+            panic!("Caller must be moderator or higher");
+        }
+
+        // Clear flags
+        let args: Vec<Val> = Vec::from_array(
+            &env,
+            [
+                board_id.into_val(&env),
+                thread_id.into_val(&env),
+                reply_id.into_val(&env),
+            ],
+        );
+        env.invoke_contract::<()>(&content, &Symbol::new(&env, "clear_flags"), args);
+
+        Self::record_mod_action(
+            &env,
+            board_id,
+            &caller,
+            "clear_flags",
+            Some(thread_id),
+            reply_id,
+            None,
+            None,
+        );
+    }
+
+    /// Update flag threshold for a board (admin+)
+    /// Accepts threshold as String since HTML forms submit strings
+    pub fn set_flag_threshold(env: Env, board_id: u64, threshold: String, caller: Address) {
+        caller.require_auth();
+
+        // Parse string to u32
+        let threshold_u32 = Self::parse_string_to_u32(&threshold);
+
+        let permissions: Address = env
+            .storage()
+            .instance()
+            .get(&AdminKey::Permissions)
+            .expect("Not initialized");
+
+        // Verify caller has admin permissions
+        let caller_perms: PermissionSet = env.invoke_contract(
+            &permissions,
+            &Symbol::new(&env, "get_permissions"),
+            Vec::from_array(&env, [board_id.into_val(&env), caller.into_val(&env)]),
+        );
+
+        if !caller_perms.can_admin {
+            panic!("Caller must be admin or owner");
+        }
+
+        // Set the threshold
+        let args: Vec<Val> = Vec::from_array(
+            &env,
+            [
+                board_id.into_val(&env),
+                threshold_u32.into_val(&env),
+                caller.into_val(&env),
+            ],
+        );
+        env.invoke_contract::<()>(&permissions, &Symbol::new(&env, "set_flag_threshold"), args);
+    }
+
+    /// Update how long invite requests stay pending (admin+)
+    /// Accepts ttl_seconds as String since HTML forms submit strings; 0 disables expiry
+    pub fn set_invite_request_ttl(env: Env, board_id: u64, ttl_seconds: String, caller: Address) {
+        caller.require_auth();
+
+        let ttl = Self::parse_string_to_u64(&ttl_seconds);
+
+        let permissions: Address = env
+            .storage()
+            .instance()
+            .get(&AdminKey::Permissions)
+            .expect("Not initialized");
+
+        // Verify caller has admin permissions
+        let caller_perms: PermissionSet = env.invoke_contract(
+            &permissions,
+            &Symbol::new(&env, "get_permissions"),
+            Vec::from_array(&env, [board_id.into_val(&env), caller.into_val(&env)]),
+        );
+
+        if !caller_perms.can_admin {
+            panic!("Caller must be admin or owner");
+        }
+
+        let args: Vec<Val> = Vec::from_array(
+            &env,
+            [
+                board_id.into_val(&env),
+                ttl.into_val(&env),
+                caller.into_val(&env),
+            ],
+        );
+        env.invoke_contract::<()>(
+            &permissions,
+            &Symbol::new(&env, "set_invite_request_ttl"),
+            args,
+        );
+    }
+
+    /// Update several board settings in one form submission (admin+)
+    /// Accepts all fields as Strings since HTML forms submit strings. Empty
+    /// fields and fields matching the current value are skipped.
+    /// Returns the number of settings that changed.
+    pub fn update_board_settings(
+        env: Env,
+        board_id: u64,
+        chunk_size: String,
+        max_depth: String,
+        edit_hours: String,
+        flag_threshold: String,
+        caller: Address,
+    ) -> u32 {
+        caller.require_auth();
+
+        let permissions: Address = env
+            .storage()
+            .instance()
+            .get(&AdminKey::Permissions)
+            .expect("Not initialized");
+
+        // Verify caller has admin permissions
+        let caller_perms: PermissionSet = env.invoke_contract(
+            &permissions,
+            &Symbol::new(&env, "get_permissions"),
+            Vec::from_array(&env, [board_id.into_val(&env), caller.into_val(&env)]),
+        );
+
+        if !caller_perms.can_admin {
+            panic!("Caller must be admin or owner");
+        }
+
+        // Validate every field before applying any of them
+        let chunk_size = Self::parse_setting_field(&chunk_size)
+            .expect("chunk_size must be a number")
+            .map(|v| {
+                if !(1..=20).contains(&v) {
+                    panic!("chunk_size must be between 1 and 20");
+                }
+                v as u32
+            });
+        let max_depth = Self::parse_setting_field(&max_depth)
+            .expect("max_depth must be a number")
+            .map(|v| {
+                if !(1..=20).contains(&v) {
+                    panic!("max_depth must be between 1 and 20");
+                }
+                v as u32
+            });
+        let edit_hours = Self::parse_setting_field(&edit_hours)
+            .expect("edit_hours must be a number")
+            .inspect(|&v| {
+                if v > u32::MAX as u64 {
+                    panic!("edit_hours is too large");
+                }
+            });
+        let flag_threshold = Self::parse_setting_field(&flag_threshold)
+            .expect("flag_threshold must be a number")
+            .map(|v| u32::try_from(v).expect("flag_threshold is too large"));
+
+        let board_contract = Self::get_board_contract_address(&env);
+        let board_arg = || Vec::from_array(&env, [board_id.into_val(&env)]);
+        let mut changed = 0;
+
+        if let Some(value) = chunk_size {
+            let current: u32 = env.invoke_contract(
+                &board_contract,
+                &Symbol::new(&env, "get_chunk_size"),
+                board_arg(),
+            );
+            if value != current {
+                let args: Vec<Val> = Vec::from_array(
+                    &env,
+                    [
+                        board_id.into_val(&env),
+                        value.into_val(&env),
+                        caller.into_val(&env),
+                    ],
+                );
+                env.invoke_contract::<()>(
+                    &board_contract,
+                    &Symbol::new(&env, "set_chunk_size"),
+                    args,
+                );
+                changed += 1;
+            }
+        }
+
+        if let Some(value) = max_depth {
+            let current: u32 = env.invoke_contract(
+                &board_contract,
+                &Symbol::new(&env, "get_max_reply_depth"),
+                board_arg(),
+            );
+            if value != current {
+                let args: Vec<Val> = Vec::from_array(
+                    &env,
+                    [
+                        board_id.into_val(&env),
+                        value.into_val(&env),
+                        caller.into_val(&env),
+                    ],
+                );
+                env.invoke_contract::<()>(
+                    &board_contract,
+                    &Symbol::new(&env, "set_max_reply_depth"),
+                    args,
+                );
+                changed += 1;
+            }
+        }
+
+        if let Some(hours) = edit_hours {
+            let current: u64 = env.invoke_contract(
+                &board_contract,
+                &Symbol::new(&env, "get_edit_window"),
+                board_arg(),
+            );
+            // The form shows whole hours, so a window that isn't one (say 90 minutes)
+            // is only replaced when the hours are actually changed
+            if hours != current / 3600 {
+                let args: Vec<Val> = Vec::from_array(
+                    &env,
+                    [
+                        board_id.into_val(&env),
+                        (hours * 3600).into_val(&env),
+                        caller.into_val(&env),
+                    ],
+                );
+                env.invoke_contract::<()>(
+                    &board_contract,
+                    &Symbol::new(&env, "set_edit_window"),
+                    args,
+                );
+                changed += 1;
+            }
+        }
+
+        if let Some(value) = flag_threshold {
+            let current: u32 = env.invoke_contract(
+                &permissions,
+                &Symbol::new(&env, "get_flag_threshold"),
+                board_arg(),
+            );
+            if value != current {
+                let args: Vec<Val> = Vec::from_array(
+                    &env,
+                    [
+                        board_id.into_val(&env),
+                        value.into_val(&env),
+                        caller.into_val(&env),
+                    ],
+                );
+                env.invoke_contract::<()>(
+                    &permissions,
+                    &Symbol::new(&env, "set_flag_threshold"),
+                    args,
+                );
+                changed += 1;
+            }
+        }
+
+        changed
+    }
+
+    /// Update the default thread ordering for a board (admin+)
+    /// Accepts sort as String ("hot", "new", "top" or "active") since HTML forms submit strings
+    pub fn set_default_sort(env: Env, board_id: u64, sort: String, caller: Address) {
+        caller.require_auth();
+
+        let sort = Self::parse_thread_sort(&sort);
+
+        let permissions: Address = env
+            .storage()
+            .instance()
+            .get(&AdminKey::Permissions)
+            .expect("Not initialized");
+
+        // Verify caller has admin permissions
+        let caller_perms: PermissionSet = env.invoke_contract(
+            &permissions,
+            &Symbol::new(&env, "get_permissions"),
+            Vec::from_array(
+                &env,
+                [board_id.into_val(&env), caller.clone().into_val(&env)],
+            ),
+        );
+
+        if !caller_perms.can_admin {
+            panic!("Caller must be admin or owner");
+        }
+
+        let board_contract = Self::get_board_contract_address(&env);
+        let args: Vec<Val> = Vec::from_array(
+            &env,
+            [
+                board_id.into_val(&env),
+                sort.into_val(&env),
+                caller.into_val(&env),
+            ],
+        );
+        env.invoke_contract::<()>(
+            &board_contract,
+            &Symbol::new(&env, "set_default_sort"),
+            args,
+        );
+    }
+
+    /// Update the minimum total votes for the Controversial sort (admin+)
+    /// Accepts min_votes as a String since HTML forms submit strings
+    pub fn set_controversial_min_votes(
+        env: Env,
+        board_id: u64,
+        min_votes: String,
+        caller: Address,
+    ) {
+        caller.require_auth();
+
+        let min_votes = Self::parse_string_to_u32(&min_votes);
+
+        let permissions: Address = env
+            .storage()
+            .instance()
+            .get(&AdminKey::Permissions)
+            .expect("Not initialized");
+
+        // Verify caller has admin permissions
+        let caller_perms: PermissionSet = env.invoke_contract(
+            &permissions,
+            &Symbol::new(&env, "get_permissions"),
+            Vec::from_array(
+                &env,
+                [board_id.into_val(&env), caller.clone().into_val(&env)],
+            ),
+        );
+
+        if !caller_perms.can_admin {
+            panic!("Caller must be admin or owner");
+        }
+
+        let board_contract = Self::get_board_contract_address(&env);
+        let args: Vec<Val> = Vec::from_array(
+            &env,
+            [
+                board_id.into_val(&env),
+                min_votes.into_val(&env),
+                caller.into_val(&env),
+            ],
+        );
+        env.invoke_contract::<()>(
+            &board_contract,
+            &Symbol::new(&env, "set_controversial_min_votes"),
+            args,
+        );
+    }
+
+    /// Update the maximum thread body and reply sizes in bytes (admin+)
+    /// Accepts both limits as Strings since HTML forms submit strings
+    pub fn set_post_size_limits(
+        env: Env,
+        board_id: u64,
+        max_thread_body_bytes: String,
+        max_reply_bytes: String,
+        caller: Address,
+    ) {
+        caller.require_auth();
+
+        let max_thread_body_bytes = Self::parse_string_to_u32(&max_thread_body_bytes);
+        let max_reply_bytes = Self::parse_string_to_u32(&max_reply_bytes);
+
+        let permissions: Address = env
+            .storage()
+            .instance()
+            .get(&AdminKey::Permissions)
+            .expect("Not initialized");
+
+        // Verify caller has admin permissions
+        let caller_perms: PermissionSet = env.invoke_contract(
+            &permissions,
+            &Symbol::new(&env, "get_permissions"),
+            Vec::from_array(
+                &env,
+                [board_id.into_val(&env), caller.clone().into_val(&env)],
+            ),
+        );
+
+        if !caller_perms.can_admin {
+            panic!("Caller must be admin or owner");
+        }
+
+        // The content contract stores at most 16 KB per post
+        let valid = 1..=16_384;
+        if !valid.contains(&max_thread_body_bytes) || !valid.contains(&max_reply_bytes) {
+            panic!("Post size limits must be between 1 and 16384 bytes");
+        }
+
+        let board_contract = Self::get_board_contract_address(&env);
+        let args: Vec<Val> = Vec::from_array(
+            &env,
+            [
+                board_id.into_val(&env),
+                max_thread_body_bytes.into_val(&env),
+                max_reply_bytes.into_val(&env),
+                caller.into_val(&env),
+            ],
+        );
+        env.invoke_contract::<()>(
+            &board_contract,
+            &Symbol::new(&env, "set_post_size_limits"),
+            args,
+        );
+    }
+
+    /// Update the approximate byte budget for thread pages (admin+)
+    /// Accepts the budget as a String since HTML forms submit strings
+    pub fn set_render_budget(env: Env, board_id: u64, budget: String, caller: Address) {
+        caller.require_auth();
+
+        let budget = Self::parse_string_to_u32(&budget);
+
+        let permissions: Address = env
+            .storage()
+            .instance()
+            .get(&AdminKey::Permissions)
+            .expect("Not initialized");
+
+        // Verify caller has admin permissions
+        let caller_perms: PermissionSet = env.invoke_contract(
+            &permissions,
+            &Symbol::new(&env, "get_permissions"),
+            Vec::from_array(
+                &env,
+                [board_id.into_val(&env), caller.clone().into_val(&env)],
+            ),
+        );
+
+        if !caller_perms.can_admin {
+            panic!("Caller must be admin or owner");
+        }
+
+        let board_contract = Self::get_board_contract_address(&env);
+        let args: Vec<Val> = Vec::from_array(
+            &env,
+            [
+                board_id.into_val(&env),
+                budget.into_val(&env),
+                caller.into_val(&env),
+            ],
+        );
+        env.invoke_contract::<()>(
+            &board_contract,
+            &Symbol::new(&env, "set_render_budget"),
+            args,
+        );
+    }
+
+    /// Update the score below which replies start collapsed (admin+)
+    /// Accepts the score as a String since HTML forms submit strings
+    pub fn set_collapse_score(env: Env, board_id: u64, score: String, caller: Address) {
+        caller.require_auth();
+
+        let score = Self::parse_string_to_i32(&score);
+
+        let permissions: Address = env
+            .storage()
+            .instance()
+            .get(&AdminKey::Permissions)
+            .expect("Not initialized");
+
+        // Verify caller has admin permissions
+        let caller_perms: PermissionSet = env.invoke_contract(
+            &permissions,
+            &Symbol::new(&env, "get_permissions"),
+            Vec::from_array(
+                &env,
+                [board_id.into_val(&env), caller.clone().into_val(&env)],
+            ),
+        );
+
+        if !caller_perms.can_admin {
+            panic!("Caller must be admin or owner");
+        }
+
+        let board_contract = Self::get_board_contract_address(&env);
+        let args: Vec<Val> = Vec::from_array(
+            &env,
+            [
+                board_id.into_val(&env),
+                score.into_val(&env),
+                caller.into_val(&env),
+            ],
+        );
+        env.invoke_contract::<()>(
+            &board_contract,
+            &Symbol::new(&env, "set_collapse_score"),
+            args,
+        );
+    }
+
+    /// Update how deep replies nest inline on thread pages (admin+)
+    /// Accepts the depth as a String since HTML forms submit strings
+    pub fn set_collapse_depth(env: Env, board_id: u64, depth: String, caller: Address) {
+        caller.require_auth();
+
+        let depth = Self::parse_string_to_u32(&depth);
+
+        let permissions: Address = env
+            .storage()
+            .instance()
+            .get(&AdminKey::Permissions)
+            .expect("Not initialized");
+
+        // Verify caller has admin permissions
+        let caller_perms: PermissionSet = env.invoke_contract(
+            &permissions,
+            &Symbol::new(&env, "get_permissions"),
+            Vec::from_array(
+                &env,
+                [board_id.into_val(&env), caller.clone().into_val(&env)],
+            ),
+        );
+
+        if !caller_perms.can_admin {
+            panic!("Caller must be admin or owner");
+        }
+
+        let board_contract = Self::get_board_contract_address(&env);
+        let args: Vec<Val> = Vec::from_array(
+            &env,
+            [
+                board_id.into_val(&env),
+                depth.into_val(&env),
+                caller.into_val(&env),
+            ],
+        );
+        env.invoke_contract::<()>(
+            &board_contract,
+            &Symbol::new(&env, "set_collapse_depth"),
+            args,
+        );
+    }
+
+    /// Update the children count above which replies show a "Show N replies" link (admin+)
+    /// Accepts the count as a String since HTML forms submit strings
+    pub fn set_collapse_children_over(env: Env, board_id: u64, count: String, caller: Address) {
+        caller.require_auth();
+
+        let count = Self::parse_string_to_u32(&count);
+
+        let permissions: Address = env
+            .storage()
+            .instance()
+            .get(&AdminKey::Permissions)
+            .expect("Not initialized");
+
+        // Verify caller has admin permissions
+        let caller_perms: PermissionSet = env.invoke_contract(
+            &permissions,
+            &Symbol::new(&env, "get_permissions"),
+            Vec::from_array(
+                &env,
+                [board_id.into_val(&env), caller.clone().into_val(&env)],
+            ),
+        );
+
+        if !caller_perms.can_admin {
+            panic!("Caller must be admin or owner");
+        }
+
+        let board_contract = Self::get_board_contract_address(&env);
+        let args: Vec<Val> = Vec::from_array(
+            &env,
+            [
+                board_id.into_val(&env),
+                count.into_val(&env),
+                caller.into_val(&env),
+            ],
+        );
+        env.invoke_contract::<()>(
+            &board_contract,
+            &Symbol::new(&env, "set_collapse_children_over"),
+            args,
+        );
+    }
+
+    /// Update the lowest role that can edit a board's wiki (admin+)
+    /// Accepts role as String ("member" or "moderator") since HTML forms submit strings
+    pub fn set_wiki_edit_role(env: Env, board_id: u64, role: String, caller: Address) {
+        caller.require_auth();
+
+        let role = Self::parse_wiki_edit_role(&role);
+
+        let permissions: Address = env
+            .storage()
+            .instance()
+            .get(&AdminKey::Permissions)
+            .expect("Not initialized");
+
+        // Verify caller has admin permissions
+        let caller_perms: PermissionSet = env.invoke_contract(
+            &permissions,
+            &Symbol::new(&env, "get_permissions"),
+            Vec::from_array(
+                &env,
+                [board_id.into_val(&env), caller.clone().into_val(&env)],
+            ),
+        );
+
+        if !caller_perms.can_admin {
+            panic!("Caller must be admin or owner");
+        }
+
+        let board_contract = Self::get_board_contract_address(&env);
+        let args: Vec<Val> = Vec::from_array(
+            &env,
+            [
+                board_id.into_val(&env),
+                role.into_val(&env),
+                caller.into_val(&env),
+            ],
+        );
+        env.invoke_contract::<()>(
+            &board_contract,
+            &Symbol::new(&env, "set_wiki_edit_role"),
+            args,
+        );
+    }
+
+    /// Update a board's locale (admin+)
+    /// Accepts a locale code as String since HTML forms submit strings; empty clears it
+    pub fn set_locale(env: Env, board_id: u64, locale: String, caller: Address) {
+        caller.require_auth();
+
+        let locale = Self::parse_locale(&env, &locale);
+
+        let permissions: Address = env
+            .storage()
+            .instance()
+            .get(&AdminKey::Permissions)
+            .expect("Not initialized");
+
+        // Verify caller has admin permissions
+        let caller_perms: PermissionSet = env.invoke_contract(
+            &permissions,
+            &Symbol::new(&env, "get_permissions"),
+            Vec::from_array(
+                &env,
+                [board_id.into_val(&env), caller.clone().into_val(&env)],
+            ),
+        );
+
+        if !caller_perms.can_admin {
+            panic!("Caller must be admin or owner");
+        }
+
+        let board_contract = Self::get_board_contract_address(&env);
+        let args: Vec<Val> = Vec::from_array(
+            &env,
+            [
+                board_id.into_val(&env),
+                locale.into_val(&env),
+                caller.into_val(&env),
+            ],
+        );
+        env.invoke_contract::<()>(&board_contract, &Symbol::new(&env, "set_locale"), args);
+    }
+
+    /// Update a board's inline image rules (admin+)
+    /// Accepts the checkbox and comma-separated hosts as Strings since HTML forms submit strings
+    pub fn set_image_policy(
+        env: Env,
+        board_id: u64,
+        allow_images: String,
+        allowed_hosts: String,
+        caller: Address,
+    ) {
+        caller.require_auth();
+
+        let allow_images = Self::parse_checkbox(&Some(allow_images), false);
+        let allowed_hosts = Self::parse_image_hosts(&env, &allowed_hosts);
+
+        let permissions: Address = env
+            .storage()
+            .instance()
+            .get(&AdminKey::Permissions)
+            .expect("Not initialized");
+
+        // Verify caller has admin permissions
+        let caller_perms: PermissionSet = env.invoke_contract(
+            &permissions,
+            &Symbol::new(&env, "get_permissions"),
+            Vec::from_array(
+                &env,
+                [board_id.into_val(&env), caller.clone().into_val(&env)],
+            ),
+        );
+
+        if !caller_perms.can_admin {
+            panic!("Caller must be admin or owner");
+        }
+
+        let board_contract = Self::get_board_contract_address(&env);
+        let args: Vec<Val> = Vec::from_array(
+            &env,
+            [
+                board_id.into_val(&env),
+                allow_images.into_val(&env),
+                allowed_hosts.into_val(&env),
+                caller.into_val(&env),
+            ],
+        );
+        env.invoke_contract::<()>(
+            &board_contract,
+            &Symbol::new(&env, "set_image_policy"),
+            args,
+        );
+    }
+
+    /// Add a term to a board's blocklist (admin+)
+    pub fn add_blocked_term(env: Env, board_id: u64, term: String, caller: Address) {
+        Self::update_blocked_term(&env, board_id, term, "add_blocked_term", caller);
+    }
+
+    /// Remove a term from a board's blocklist (admin+)
+    pub fn remove_blocked_term(env: Env, board_id: u64, term: String, caller: Address) {
+        Self::update_blocked_term(&env, board_id, term, "remove_blocked_term", caller);
+    }
+
+    fn update_blocked_term(
+        env: &Env,
+        board_id: u64,
+        term: String,
+        board_fn: &str,
+        caller: Address,
+    ) {
+        caller.require_auth();
+
+        let permissions: Address = env
+            .storage()
+            .instance()
+            .get(&AdminKey::Permissions)
+            .expect("Not initialized");
+
+        // Verify caller has admin permissions
+        let caller_perms: PermissionSet = env.invoke_contract(
+            &permissions,
+            &Symbol::new(env, "get_permissions"),
+            Vec::from_array(env, [board_id.into_val(env), caller.clone().into_val(env)]),
+        );
+
+        if !caller_perms.can_admin {
+            panic!("Caller must be admin or owner");
+        }
+
+        let board_contract = Self::get_board_contract_address(env);
+        let args: Vec<Val> = Vec::from_array(
+            env,
+            [
+                board_id.into_val(env),
+                term.into_val(env),
+                caller.into_val(env),
+            ],
+        );
+        env.invoke_contract::<()>(&board_contract, &Symbol::new(env, board_fn), args);
+    }
+
+    /// Update the seconds a user must wait between replies in one thread (admin+, 0 = off)
+    /// Accepts the cooldown as a String since HTML forms submit strings
+    pub fn set_reply_cooldown(env: Env, board_id: u64, seconds: String, caller: Address) {
+        caller.require_auth();
+
+        let seconds = Self::parse_string_to_u64(&seconds);
+
+        let permissions: Address = env
+            .storage()
+            .instance()
+            .get(&AdminKey::Permissions)
+            .expect("Not initialized");
+
+        // Verify caller has admin permissions
+        let caller_perms: PermissionSet = env.invoke_contract(
+            &permissions,
+            &Symbol::new(&env, "get_permissions"),
+            Vec::from_array(
+                &env,
+                [board_id.into_val(&env), caller.clone().into_val(&env)],
+            ),
+        );
+
+        if !caller_perms.can_admin {
+            panic!("Caller must be admin or owner");
+        }
+
+        let board_contract = Self::get_board_contract_address(&env);
+        let args: Vec<Val> = Vec::from_array(
+            &env,
+            [
+                board_id.into_val(&env),
+                seconds.into_val(&env),
+                caller.into_val(&env),
+            ],
+        );
+        env.invoke_contract::<()>(
+            &board_contract,
+            &Symbol::new(&env, "set_reply_cooldown"),
+            args,
+        );
+    }
+
+    /// Open the board's moderation log to non-moderators, or close it again (admin+)
+    /// Accepts the checkbox as a String since HTML forms submit strings
+    pub fn set_public_modlog(env: Env, board_id: u64, public_modlog: String, caller: Address) {
+        caller.require_auth();
+
+        let public_modlog = Self::parse_checkbox(&Some(public_modlog), false);
+
+        let permissions: Address = env
+            .storage()
+            .instance()
+            .get(&AdminKey::Permissions)
+            .expect("Not initialized");
+
+        // Verify caller has admin permissions
+        let caller_perms: PermissionSet = env.invoke_contract(
+            &permissions,
+            &Symbol::new(&env, "get_permissions"),
+            Vec::from_array(
+                &env,
+                [board_id.into_val(&env), caller.clone().into_val(&env)],
+            ),
+        );
+
+        if !caller_perms.can_admin {
+            panic!("Caller must be admin or owner");
+        }
+
+        let board_contract = Self::get_board_contract_address(&env);
+        let args: Vec<Val> = Vec::from_array(
+            &env,
+            [
+                board_id.into_val(&env),
+                public_modlog.into_val(&env),
+                caller.into_val(&env),
+            ],
+        );
+        env.invoke_contract::<()>(
+            &board_contract,
+            &Symbol::new(&env, "set_public_modlog"),
+            args,
+        );
+    }
+
+    /// Update the minimum karma to post and reply (admin+, 0 = no requirement)
+    /// Accepts both thresholds as Strings since HTML forms submit strings
+    pub fn set_karma_thresholds(
+        env: Env,
+        board_id: u64,
+        min_karma_to_post: String,
+        min_karma_to_reply: String,
+        caller: Address,
+    ) {
+        caller.require_auth();
+
+        let min_karma_to_post = Self::parse_string_to_u32(&min_karma_to_post) as i64;
+        let min_karma_to_reply = Self::parse_string_to_u32(&min_karma_to_reply) as i64;
+
+        let permissions: Address = env
+            .storage()
+            .instance()
+            .get(&AdminKey::Permissions)
+            .expect("Not initialized");
+
+        // Verify caller has admin permissions
+        let caller_perms: PermissionSet = env.invoke_contract(
+            &permissions,
+            &Symbol::new(&env, "get_permissions"),
+            Vec::from_array(
+                &env,
+                [board_id.into_val(&env), caller.clone().into_val(&env)],
+            ),
+        );
+
+        if !caller_perms.can_admin {
+            panic!("Caller must be admin or owner");
+        }
+
+        let board_contract = Self::get_board_contract_address(&env);
+        let args: Vec<Val> = Vec::from_array(
+            &env,
+            [
+                board_id.into_val(&env),
+                min_karma_to_post.into_val(&env),
+                min_karma_to_reply.into_val(&env),
+                caller.into_val(&env),
+            ],
+        );
+        env.invoke_contract::<()>(
+            &board_contract,
+            &Symbol::new(&env, "set_karma_thresholds"),
+            args,
+        );
+    }
+
+    /// Exempt a user from the board's karma requirements (moderator+)
+    pub fn exempt_from_karma(env: Env, board_id: u64, user: Address, caller: Address) {
+        Self::set_karma_exempt(&env, board_id, user, true, caller);
+    }
+
+    /// Remove a user's exemption from the board's karma requirements (moderator+)
+    pub fn remove_karma_exemption(env: Env, board_id: u64, user: Address, caller: Address) {
+        Self::set_karma_exempt(&env, board_id, user, false, caller);
+    }
+
+    fn set_karma_exempt(env: &Env, board_id: u64, user: Address, exempt: bool, caller: Address) {
+        caller.require_auth();
+
+        let permissions: Address = env
+            .storage()
+            .instance()
+            .get(&AdminKey::Permissions)
+            .expect("Not initialized");
+
+        let caller_perms: PermissionSet = env.invoke_contract(
+            &permissions,
+            &Symbol::new(env, "get_permissions"),
+            Vec::from_array(env, [board_id.into_val(env), caller.clone().into_val(env)]),
+        );
+
+        if !caller_perms.can_moderate {
+            panic!("Caller must be moderator or higher");
+        }
+
+        let board_contract = Self::get_board_contract_address(env);
+        let args: Vec<Val> = Vec::from_array(
+            env,
+            [
+                board_id.into_val(env),
+                user.into_val(env),
+                exempt.into_val(env),
+                caller.into_val(env),
+            ],
+        );
+        env.invoke_contract::<()>(&board_contract, &Symbol::new(env, "set_karma_exempt"), args);
+    }
+
+    /// Set CSS overrides appended after the theme stylesheet for a board (admin+)
+    pub fn set_custom_css(env: Env, board_id: u64, css: String, caller: Address) {
+        Self::update_custom_css(&env, board_id, css, caller);
+    }
+
+    /// Drop a board's CSS overrides so it uses the theme as-is (admin+)
+    pub fn reset_custom_css(env: Env, board_id: u64, caller: Address) {
+        Self::update_custom_css(&env, board_id, String::from_str(&env, ""), caller);
+    }
+
+    /// Pick a theme for a board from the registry's catalog (admin+)
+    pub fn set_board_theme(env: Env, board_id: u64, name: String, caller: Address) {
+        Self::update_board_theme(&env, board_id, name, caller);
+    }
+
+    /// Return a board to the registry's default theme (admin+)
+    pub fn reset_board_theme(env: Env, board_id: u64, caller: Address) {
+        Self::update_board_theme(&env, board_id, String::from_str(&env, ""), caller);
+    }
+
+    fn update_board_theme(env: &Env, board_id: u64, name: String, caller: Address) {
+        caller.require_auth();
+
+        let permissions: Address = env
+            .storage()
+            .instance()
+            .get(&AdminKey::Permissions)
+            .expect("Not initialized");
+
+        let caller_perms: PermissionSet = env.invoke_contract(
+            &permissions,
+            &Symbol::new(env, "get_permissions"),
+            Vec::from_array(env, [board_id.into_val(env), caller.clone().into_val(env)]),
+        );
+
+        if !caller_perms.can_admin {
+            panic!("Caller must be admin or owner");
+        }
+
+        let board_contract = Self::get_board_contract_address(env);
+        let args: Vec<Val> = Vec::from_array(
+            env,
+            [
+                board_id.into_val(env),
+                name.into_val(env),
+                caller.into_val(env),
+            ],
+        );
+        env.invoke_contract::<()>(
+            &board_contract,
+            &Symbol::new(env, "set_theme_by_name"),
+            args,
+        );
+    }
+
+    fn update_custom_css(env: &Env, board_id: u64, css: String, caller: Address) {
+        caller.require_auth();
+
+        let permissions: Address = env
+            .storage()
+            .instance()
+            .get(&AdminKey::Permissions)
+            .expect("Not initialized");
+
+        let caller_perms: PermissionSet = env.invoke_contract(
+            &permissions,
+            &Symbol::new(env, "get_permissions"),
+            Vec::from_array(env, [board_id.into_val(env), caller.clone().into_val(env)]),
+        );
+
+        if !caller_perms.can_admin {
+            panic!("Caller must be admin or owner");
+        }
+
+        let board_contract = Self::get_board_contract_address(env);
+        let args: Vec<Val> = Vec::from_array(
+            env,
+            [
+                board_id.into_val(env),
+                css.into_val(env),
+                caller.into_val(env),
+            ],
+        );
+        env.invoke_contract::<()>(&board_contract, &Symbol::new(env, "set_custom_css"), args);
+    }
+
+    /// Update reply chunk size for waterfall loading (admin+)
+    /// Accepts chunk_size as String since HTML forms submit strings
+    pub fn set_chunk_size(env: Env, board_id: u64, chunk_size: String, caller: Address) {
+        caller.require_auth();
+
+        // Parse string to u32
+        let chunk_size_u32 = Self::parse_string_to_u32(&chunk_size);
+
+        let permissions: Address = env
+            .storage()
+            .instance()
+            .get(&AdminKey::Permissions)
+            .expect("Not initialized");
+
+        // Verify caller has admin permissions
+        let caller_perms: PermissionSet = env.invoke_contract(
+            &permissions,
+            &Symbol::new(&env, "get_permissions"),
+            Vec::from_array(&env, [board_id.into_val(&env), caller.into_val(&env)]),
+        );
+
+        if !caller_perms.can_admin {
+            panic!("Caller must be admin or owner");
+        }
+
+        // Validate chunk size (1-20 is reasonable range)
+        if !(1..=20).contains(&chunk_size_u32) {
+            panic!("Chunk size must be between 1 and 20");
+        }
+
+        // Get board contract (single contract for all boards)
+        let board_contract = Self::get_board_contract_address(&env);
+
+        // Set the chunk size (now requires board_id)
+        let args: Vec<Val> = Vec::from_array(
+            &env,
+            [
+                board_id.into_val(&env),
+                chunk_size_u32.into_val(&env),
+                caller.into_val(&env),
+            ],
+        );
+        env.invoke_contract::<()>(&board_contract, &Symbol::new(&env, "set_chunk_size"), args);
+    }
+
+    /// Update maximum reply depth for a board (admin+)
+    /// Accepts max_depth as String since HTML forms submit strings
+    pub fn set_max_reply_depth(env: Env, board_id: u64, max_depth: String, caller: Address) {
+        caller.require_auth();
+
+        // Parse string to u32
+        let max_depth_u32 = Self::parse_string_to_u32(&max_depth);
+
+        let permissions: Address = env
+            .storage()
+            .instance()
+            .get(&AdminKey::Permissions)
+            .expect("Not initialized");
+
+        // Verify caller has admin permissions
+        let caller_perms: PermissionSet = env.invoke_contract(
+            &permissions,
+            &Symbol::new(&env, "get_permissions"),
+            Vec::from_array(
+                &env,
+                [board_id.into_val(&env), caller.clone().into_val(&env)],
+            ),
+        );
+
+        if !caller_perms.can_admin {
+            panic!("Caller must be admin or owner");
+        }
+
+        // Validate max depth (1-20 is reasonable range)
+        if !(1..=20).contains(&max_depth_u32) {
+            panic!("Max reply depth must be between 1 and 20");
+        }
+
+        // Get board contract (single contract for all boards)
+        let board_contract = Self::get_board_contract_address(&env);
+
+        // Set the max reply depth (now requires board_id)
+        let args: Vec<Val> = Vec::from_array(
+            &env,
+            [
+                board_id.into_val(&env),
+                max_depth_u32.into_val(&env),
+                caller.into_val(&env),
+            ],
+        );
+        env.invoke_contract::<()>(
+            &board_contract,
+            &Symbol::new(&env, "set_max_reply_depth"),
+            args,
+        );
+    }
+
+    /// Update edit window for a board (admin+)
+    /// Accepts edit_hours as String since HTML forms submit strings
+    /// Input is in hours, stored as seconds
+    pub fn set_edit_window(env: Env, board_id: u64, edit_hours: String, caller: Address) {
+        caller.require_auth();
+
+        // Parse string to u32 (hours)
+        let hours_u32 = Self::parse_string_to_u32(&edit_hours);
+
+        let permissions: Address = env
+            .storage()
+            .instance()
+            .get(&AdminKey::Permissions)
+            .expect("Not initialized");
+
+        // Verify caller has admin permissions
+        let caller_perms: PermissionSet = env.invoke_contract(
+            &permissions,
+            &Symbol::new(&env, "get_permissions"),
+            Vec::from_array(
+                &env,
+                [board_id.into_val(&env), caller.clone().into_val(&env)],
+            ),
+        );
+
+        if !caller_perms.can_admin {
+            panic!("Caller must be admin or owner");
+        }
+
+        // Convert hours to seconds (0 stays 0 for "no limit")
+        let seconds: u64 = (hours_u32 as u64) * 3600;
+
+        // Get board contract (single contract for all boards)
+        let board_contract = Self::get_board_contract_address(&env);
+
+        // Set the edit window (now requires board_id)
+        let args: Vec<Val> = Vec::from_array(
+            &env,
+            [
+                board_id.into_val(&env),
+                seconds.into_val(&env),
+                caller.into_val(&env),
+            ],
+        );
+        env.invoke_contract::<()>(&board_contract, &Symbol::new(&env, "set_edit_window"), args);
+    }
+
+    /// Rename a board (admin+)
+    /// The old name becomes an alias that continues to resolve
+    pub fn rename_board(env: Env, board_id: u64, new_name: String, caller: Address) {
+        caller.require_auth();
+
+        let permissions: Address = env
+            .storage()
+            .instance()
+            .get(&AdminKey::Permissions)
+            .expect("Not initialized");
+
+        // Verify caller has admin permissions
+        let caller_perms: PermissionSet = env.invoke_contract(
+            &permissions,
+            &Symbol::new(&env, "get_permissions"),
+            Vec::from_array(
+                &env,
+                [board_id.into_val(&env), caller.clone().into_val(&env)],
+            ),
+        );
+
+        if !caller_perms.can_admin {
+            panic!("Caller must be admin or owner");
+        }
+
+        // Call registry's rename_board function
+        let registry: Address = env
+            .storage()
+            .instance()
+            .get(&AdminKey::Registry)
+            .expect("Not initialized");
+
+        let args: Vec<Val> = Vec::from_array(
+            &env,
+            [
+                board_id.into_val(&env),
+                new_name.into_val(&env),
+                caller.into_val(&env),
+            ],
+        );
+        env.invoke_contract::<()>(&registry, &Symbol::new(&env, "rename_board"), args);
+
+        Self::emit_mod_event(
+            &env,
+            board_id,
+            "rename_board",
+            ModTarget::Board,
+            &caller,
+            None,
+        );
+    }
+
+    /// List a board publicly (admin+)
+    /// Makes the board appear on the home page
+    pub fn list_board(env: Env, board_id: u64, caller: Address) {
+        caller.require_auth();
+        Self::set_board_listed(env, board_id, true, caller);
+    }
+
+    /// Unlist a board (admin+)
+    /// Hides the board from the home page but keeps it accessible via direct link
+    pub fn unlist_board(env: Env, board_id: u64, caller: Address) {
+        caller.require_auth();
+        Self::set_board_listed(env, board_id, false, caller);
+    }
+
+    /// Helper to set board listed status
+    fn set_board_listed(env: Env, board_id: u64, is_listed: bool, caller: Address) {
+        // Get board contract (single contract for all boards)
+        let board_contract = Self::get_board_contract_address(&env);
+
+        // Call board contract's set_board_listed function (now requires board_id)
+        // (The board contract handles permission checking internally)
+        let args: Vec<Val> = Vec::from_array(
+            &env,
+            [
+                board_id.into_val(&env),
+                is_listed.into_val(&env),
+                caller.into_val(&env),
+            ],
+        );
+        env.invoke_contract::<()>(
+            &board_contract,
+            &Symbol::new(&env, "set_board_listed"),
+            args,
+        );
+
+        let action = if is_listed {
+            "list_board"
+        } else {
+            "unlist_board"
+        };
+        Self::emit_mod_event(&env, board_id, action, ModTarget::Board, &caller, None);
+    }
+
+    /// Make a board public (admin+)
+    pub fn make_public(env: Env, board_id: u64, caller: Address) {
+        caller.require_auth();
+        Self::set_board_private(env, board_id, false, caller);
+    }
+
+    /// Make a board private (admin+)
+    /// Requires the board name or "CONFIRM" since guests lose access immediately
+    pub fn make_private(env: Env, board_id: u64, confirm: String, caller: Address) {
+        caller.require_auth();
+        Self::require_confirmation(&env, board_id, &confirm);
+        Self::set_board_private(env, board_id, true, caller);
+    }
+
+    /// Helper to set board private status
+    fn set_board_private(env: Env, board_id: u64, is_private: bool, caller: Address) {
+        // Get board contract (single contract for all boards)
+        let board_contract = Self::get_board_contract_address(&env);
+
+        // Call board contract's set_private function (now requires board_id)
+        // (The board contract handles permission checking internally)
+        let args: Vec<Val> = Vec::from_array(
+            &env,
+            [
+                board_id.into_val(&env),
+                is_private.into_val(&env),
+                caller.into_val(&env),
+            ],
+        );
+        env.invoke_contract::<()>(&board_contract, &Symbol::new(&env, "set_private"), args);
+
+        let action = if is_private {
+            "make_private"
+        } else {
+            "make_public"
+        };
+        Self::emit_mod_event(&env, board_id, action, ModTarget::Board, &caller, None);
+    }
+
+    /// Enable posting on a board (admin+)
+    pub fn enable_posting(env: Env, board_id: u64, caller: Address) {
+        caller.require_auth();
+        Self::set_board_readonly(env, board_id, false, caller);
+    }
+
+    /// Make a board read-only (admin+)
+    /// Requires the board name or "CONFIRM" as confirmation
+    pub fn make_readonly(env: Env, board_id: u64, confirm: String, caller: Address) {
+        caller.require_auth();
+        Self::require_confirmation(&env, board_id, &confirm);
+        Self::set_board_readonly(env, board_id, true, caller);
+    }
+
+    /// Remove a board from its community (community owner or admin)
+    /// Requires the board name or "CONFIRM" as confirmation
+    pub fn leave_community(
+        env: Env,
+        board_id: u64,
+        community_id: u64,
+        confirm: String,
+        caller: Address,
+    ) {
+        caller.require_auth();
+        Self::require_confirmation(&env, board_id, &confirm);
+
+        let registry: Address = env
+            .storage()
+            .instance()
+            .get(&AdminKey::Registry)
+            .expect("Not initialized");
+        let community_contract: Address = env
+            .invoke_contract::<Option<Address>>(
+                &registry,
+                &Symbol::new(&env, "get_contract_by_alias"),
+                Vec::from_array(&env, [Symbol::new(&env, "community").into_val(&env)]),
+            )
+            .expect("Community contract not registered");
+
+        // The community contract checks that the caller may remove boards
+        let args: Vec<Val> = Vec::from_array(
+            &env,
+            [
+                community_id.into_val(&env),
+                board_id.into_val(&env),
+                caller.into_val(&env),
+            ],
+        );
+        env.invoke_contract::<()>(
+            &community_contract,
+            &Symbol::new(&env, "remove_board"),
+            args,
+        );
+    }
+
+    /// Check the confirmation typed for a destructive action.
+    /// Accepts the board's name or the literal "CONFIRM", ignoring surrounding whitespace.
+    fn require_confirmation(env: &Env, board_id: u64, confirm: &String) {
+        if confirm.len() > 64 {
+            panic!("Confirmation does not match: type the board name or CONFIRM");
+        }
+        let mut buf = [0u8; 64];
+        let (start, end) = Self::trimmed_form_value(confirm, &mut buf);
+        let typed = &buf[start..end];
+        if typed.is_empty() {
+            panic!("Confirmation required: type the board name or CONFIRM");
+        }
+        if typed == b"CONFIRM" {
+            return;
+        }
+
+        let board_contract = Self::get_board_contract_address(env);
+        let config: Option<BoardConfig> = env
+            .try_invoke_contract::<BoardConfig, soroban_sdk::Error>(
+                &board_contract,
+                &Symbol::new(env, "get_config"),
+                Vec::from_array(env, [board_id.into_val(env)]),
+            )
+            .ok()
+            .and_then(|r| r.ok());
+        if let Some(config) = config {
+            let len = config.name.len() as usize;
+            if len == typed.len() {
+                let mut name = [0u8; 64];
+                config.name.copy_into_slice(&mut name[..len]);
+                if &name[..len] == typed {
+                    return;
+                }
+            }
+        }
+        panic!("Confirmation does not match: type the board name or CONFIRM");
+    }
+
+    /// Helper to set board readonly status
+    fn set_board_readonly(env: Env, board_id: u64, is_readonly: bool, caller: Address) {
+        let permissions: Address = env
+            .storage()
+            .instance()
+            .get(&AdminKey::Permissions)
+            .expect("Not initialized");
+
+        // Verify caller has admin permissions
+        let caller_perms: PermissionSet = env.invoke_contract(
+            &permissions,
+            &Symbol::new(&env, "get_permissions"),
+            Vec::from_array(
+                &env,
+                [board_id.into_val(&env), caller.clone().into_val(&env)],
+            ),
+        );
+
+        if !caller_perms.can_admin {
+            panic!("Caller must be admin or owner");
+        }
+
+        // Get board contract (single contract for all boards)
+        let board_contract = Self::get_board_contract_address(&env);
+
+        // Call board contract's set_readonly function (now requires board_id)
+        let args: Vec<Val> = Vec::from_array(
+            &env,
+            [
+                board_id.into_val(&env),
+                is_readonly.into_val(&env),
+                caller.into_val(&env),
+            ],
+        );
+        env.invoke_contract::<()>(&board_contract, &Symbol::new(&env, "set_readonly"), args);
+
+        let action = if is_readonly {
+            "make_readonly"
+        } else {
+            "enable_posting"
+        };
+        Self::emit_mod_event(&env, board_id, action, ModTarget::Board, &caller, None);
+    }
+
+    /// Pin a thread (moderator+)
+    pub fn pin_thread(env: Env, board_id: u64, thread_id: u64, caller: Address) {
+        caller.require_auth();
+
+        let permissions: Address = env
+            .storage()
+            .instance()
+            .get(&AdminKey::Permissions)
+            .expect("Not initialized");
+
+        // Verify caller has moderator permissions
+        let caller_perms: PermissionSet = env.invoke_contract(
+            &permissions,
+            &Symbol::new(&env, "get_permissions"),
+            Vec::from_array(&env, [board_id.into_val(&env), caller.into_val(&env)]),
+        );
+
+        if !caller_perms.can_moderate {
+            panic!("Caller must be moderator or higher");
+        }
+
+        // Get board contract (single contract for all boards)
+        let board_contract = Self::get_board_contract_address(&env);
+
+        // Pin the thread (now requires board_id)
+        let args: Vec<Val> = Vec::from_array(
+            &env,
+            [
+                board_id.into_val(&env),
+                thread_id.into_val(&env),
+                true.into_val(&env),
+            ],
+        );
+        env.invoke_contract::<()>(
+            &board_contract,
+            &Symbol::new(&env, "set_thread_pinned"),
+            args,
+        );
+
+        Self::record_mod_action(
+            &env,
+            board_id,
+            &caller,
+            "pin_thread",
+            Some(thread_id),
+            None,
+            None,
+            None,
+        );
+    }
+
+    /// Unpin a thread (moderator+)
+    pub fn unpin_thread(env: Env, board_id: u64, thread_id: u64, caller: Address) {
+        caller.require_auth();
+
+        let permissions: Address = env
+            .storage()
+            .instance()
+            .get(&AdminKey::Permissions)
+            .expect("Not initialized");
+
+        // Verify caller has moderator permissions
+        let caller_perms: PermissionSet = env.invoke_contract(
+            &permissions,
+            &Symbol::new(&env, "get_permissions"),
+            Vec::from_array(&env, [board_id.into_val(&env), caller.into_val(&env)]),
+        );
+
+        if !caller_perms.can_moderate {
+            panic!("Caller must be moderator or higher");
+        }
+
+        // Get board contract (single contract for all boards)
+        let board_contract = Self::get_board_contract_address(&env);
+
+        // Unpin the thread (now requires board_id)
+        let args: Vec<Val> = Vec::from_array(
+            &env,
+            [
+                board_id.into_val(&env),
+                thread_id.into_val(&env),
+                false.into_val(&env),
+            ],
+        );
+        env.invoke_contract::<()>(
+            &board_contract,
+            &Symbol::new(&env, "set_thread_pinned"),
+            args,
+        );
+
+        Self::record_mod_action(
+            &env,
+            board_id,
+            &caller,
+            "unpin_thread",
+            Some(thread_id),
+            None,
+            None,
+            None,
+        );
+    }
+
+    /// Lock a thread (moderator+)
+    pub fn lock_thread(env: Env, board_id: u64, thread_id: u64, caller: Address) {
+        caller.require_auth();
+
+        let permissions: Address = env
+            .storage()
+            .instance()
+            .get(&AdminKey::Permissions)
+            .expect("Not initialized");
+
+        // Verify caller has moderator permissions
+        let caller_perms: PermissionSet = env.invoke_contract(
+            &permissions,
+            &Symbol::new(&env, "get_permissions"),
+            Vec::from_array(&env, [board_id.into_val(&env), caller.into_val(&env)]),
+        );
+
+        if !caller_perms.can_moderate {
+            panic!("Caller must be moderator or higher");
+        }
+
+        // Get board contract (single contract for all boards)
+        let board_contract = Self::get_board_contract_address(&env);
+
+        // Lock the thread (now requires board_id)
+        let args: Vec<Val> = Vec::from_array(
+            &env,
+            [
+                board_id.into_val(&env),
+                thread_id.into_val(&env),
+                true.into_val(&env),
+            ],
+        );
+        env.invoke_contract::<()>(
+            &board_contract,
+            &Symbol::new(&env, "set_thread_locked"),
+            args,
+        );
+
+        Self::record_mod_action(
+            &env,
+            board_id,
+            &caller,
+            "lock_thread",
+            Some(thread_id),
+            None,
+            None,
+            None,
+        );
+    }
+
+    /// Unlock a thread (moderator+)
+    pub fn unlock_thread(env: Env, board_id: u64, thread_id: u64, caller: Address) {
+        caller.require_auth();
+
+        let permissions: Address = env
+            .storage()
+            .instance()
+            .get(&AdminKey::Permissions)
+            .expect("Not initialized");
+
+        // Verify caller has moderator permissions
+        let caller_perms: PermissionSet = env.invoke_contract(
+            &permissions,
+            &Symbol::new(&env, "get_permissions"),
+            Vec::from_array(&env, [board_id.into_val(&env), caller.into_val(&env)]),
+        );
+
+        if !caller_perms.can_moderate {
+            panic!("Caller must be moderator or higher");
+        }
+
+        // Get board contract (single contract for all boards)
+        let board_contract = Self::get_board_contract_address(&env);
+
+        // Unlock the thread (now requires board_id)
+        let args: Vec<Val> = Vec::from_array(
+            &env,
+            [
+                board_id.into_val(&env),
+                thread_id.into_val(&env),
+                false.into_val(&env),
+            ],
+        );
+        env.invoke_contract::<()>(
+            &board_contract,
+            &Symbol::new(&env, "set_thread_locked"),
+            args,
+        );
+
+        Self::record_mod_action(
+            &env,
+            board_id,
+            &caller,
+            "unlock_thread",
+            Some(thread_id),
+            None,
+            None,
+            None,
+        );
+    }
+
+    /// Set when a thread opens for and stops taking replies (moderator+).
+    /// Both times are hours from now; a blank field leaves that side unbounded.
+    pub fn set_thread_schedule(
+        env: Env,
+        board_id: u64,
+        thread_id: u64,
+        open_in_hours: String,
+        lock_in_hours: String,
+        caller: Address,
+    ) {
+        caller.require_auth();
+
+        let permissions: Address = env
+            .storage()
+            .instance()
+            .get(&AdminKey::Permissions)
+            .expect("Not initialized");
+
+        // Verify caller has moderator permissions
+        let caller_perms: PermissionSet = env.invoke_contract(
+            &permissions,
+            &Symbol::new(&env, "get_permissions"),
+            Vec::from_array(
+                &env,
+                [board_id.into_val(&env), caller.clone().into_val(&env)],
+            ),
+        );
+
+        if !caller_perms.can_moderate {
+            panic!("Caller must be moderator or higher");
+        }
+
+        let now = env.ledger().timestamp();
+        let to_timestamp = |hours: u64| {
+            hours
+                .checked_mul(3600)
+                .and_then(|secs| now.checked_add(secs))
+                .expect("Schedule too far ahead")
+        };
+        let open_at = Self::parse_setting_field(&open_in_hours)
+            .expect("Open time must be a number of hours")
+            .map(to_timestamp);
+        let lock_at = Self::parse_setting_field(&lock_in_hours)
+            .expect("Lock time must be a number of hours")
+            .map(to_timestamp);
+
+        let board_contract = Self::get_board_contract_address(&env);
+        let args: Vec<Val> = Vec::from_array(
+            &env,
+            [
+                board_id.into_val(&env),
+                thread_id.into_val(&env),
+                open_at.into_val(&env),
+                lock_at.into_val(&env),
+                caller.clone().into_val(&env),
+            ],
+        );
+        env.invoke_contract::<()>(
+            &board_contract,
+            &Symbol::new(&env, "set_thread_schedule"),
+            args,
+        );
+
+        Self::record_mod_action(
+            &env,
+            board_id,
+            &caller,
+            "schedule_thread",
+            Some(thread_id),
+            None,
+            None,
+            None,
+        );
+    }
+
+    /// Delete a thread (moderator+)
+    pub fn delete_thread(env: Env, board_id: u64, thread_id: u64, caller: Address) {
+        caller.require_auth();
+
+        let permissions: Address = env
+            .storage()
+            .instance()
+            .get(&AdminKey::Permissions)
+            .expect("Not initialized");
+
+        // Verify caller has moderator permissions
+        let caller_perms: PermissionSet = env.invoke_contract(
+            &permissions,
+            &Symbol::new(&env, "get_permissions"),
+            Vec::from_array(
+                &env,
+                [board_id.into_val(&env), caller.clone().into_val(&env)],
+            ),
+        );
+
+        if !caller_perms.can_moderate {
+            panic!("Caller must be moderator or higher");
+        }
+
+        // Get board contract (single contract for all boards)
+        let board_contract = Self::get_board_contract_address(&env);
+
+        // Delete the thread (soft delete) - now requires board_id
+        let args: Vec<Val> = Vec::from_array(
+            &env,
+            [
+                board_id.into_val(&env),
+                thread_id.into_val(&env),
+                caller.into_val(&env),
+            ],
+        );
+        env.invoke_contract::<()>(&board_contract, &Symbol::new(&env, "delete_thread"), args);
+
+        Self::record_mod_action(
+            &env,
+            board_id,
+            &caller,
+            "delete_thread",
+            Some(thread_id),
+            None,
+            None,
+            None,
+        );
+    }
+
+    /// Remove a crosspost from this board (moderator+). The content contract
+    /// soft-deletes the crosspost thread and drops it from the original's crosspost list.
+    pub fn remove_crosspost(env: Env, board_id: u64, thread_id: u64, caller: Address) {
+        caller.require_auth();
+
+        let permissions: Address = env
+            .storage()
+            .instance()
+            .get(&AdminKey::Permissions)
+            .expect("Not initialized");
+        let content: Address = env
+            .storage()
+            .instance()
+            .get(&AdminKey::Content)
+            .expect("Not initialized");
+
+        let caller_perms: PermissionSet = env.invoke_contract(
+            &permissions,
+            &Symbol::new(&env, "get_permissions"),
+            Vec::from_array(
+                &env,
+                [board_id.into_val(&env), caller.clone().into_val(&env)],
+            ),
+        );
+        if !caller_perms.can_moderate {
+            panic!("Caller must be moderator or higher");
+        }
+
+        let args: Vec<Val> = Vec::from_array(
+            &env,
+            [
+                board_id.into_val(&env),
+                thread_id.into_val(&env),
+                caller.clone().into_val(&env),
+            ],
+        );
+        env.invoke_contract::<()>(&content, &Symbol::new(&env, "remove_crosspost"), args);
+
+        Self::record_mod_action(
+            &env,
+            board_id,
+            &caller,
+            "remove_crosspost",
+            Some(thread_id),
+            None,
+            None,
+            None,
+        );
+    }
+
+    /// Delete a reply (moderator+)
+    pub fn delete_reply(env: Env, board_id: u64, thread_id: u64, reply_id: u64, caller: Address) {
+        caller.require_auth();
+
+        let permissions: Address = env
+            .storage()
+            .instance()
+            .get(&AdminKey::Permissions)
+            .expect("Not initialized");
+        let content: Address = env
+            .storage()
+            .instance()
+            .get(&AdminKey::Content)
+            .expect("Not initialized");
+
+        // Verify caller has moderator permissions
+        let caller_perms: PermissionSet = env.invoke_contract(
+            &permissions,
+            &Symbol::new(&env, "get_permissions"),
+            Vec::from_array(
+                &env,
+                [board_id.into_val(&env), caller.clone().into_val(&env)],
+            ),
+        );
+
+        if !caller_perms.can_moderate {
+            panic!("Caller must be moderator or higher");
+        }
+
+        // Delete the reply (soft delete via content contract)
+        let args: Vec<Val> = Vec::from_array(
+            &env,
+            [
+                board_id.into_val(&env),
+                thread_id.into_val(&env),
+                reply_id.into_val(&env),
+                caller.into_val(&env),
+            ],
+        );
+        env.invoke_contract::<()>(&content, &Symbol::new(&env, "delete_reply"), args);
+
+        Self::record_mod_action(
+            &env,
+            board_id,
+            &caller,
+            "delete_reply",
+            Some(thread_id),
+            Some(reply_id),
+            None,
+            None,
+        );
+    }
+
+    // ========================================================================
+    // Bulk Moderation
+    // ========================================================================
+
+    /// Hide several replies in a thread (moderator+).
+    /// Missing replies are skipped; returns the number actually hidden.
+    pub fn hide_replies_bulk(
+        env: Env,
+        board_id: u64,
+        thread_id: u64,
+        reply_ids: Vec<u64>,
+        caller: Address,
+    ) -> u32 {
+        caller.require_auth();
+        Self::check_bulk_request(&env, board_id, reply_ids.len(), &caller);
+
+        let mut done = 0;
+        for reply_id in reply_ids.iter() {
+            if Self::try_hide_item(&env, board_id, thread_id, Some(reply_id), &caller) {
+                done += 1;
+            }
+        }
+        done
+    }
+
+    /// Hide several threads (moderator+).
+    /// Missing threads are skipped; returns the number actually hidden.
+    pub fn hide_threads_bulk(
+        env: Env,
+        board_id: u64,
+        thread_ids: Vec<u64>,
+        caller: Address,
+    ) -> u32 {
+        caller.require_auth();
+        Self::check_bulk_request(&env, board_id, thread_ids.len(), &caller);
+
+        let mut done = 0;
+        for thread_id in thread_ids.iter() {
+            if Self::try_hide_item(&env, board_id, thread_id, None, &caller) {
+                done += 1;
+            }
+        }
+        done
+    }
+
+    /// Hide a user's posts on a board (moderator+).
+    /// Pages through every thread the user started and then every reply they
+    /// posted, taking the window [start, start + limit) of `user_post_count` posts
+    /// so a long cleanup can resume across transactions. Posts that are already
+    /// hidden are skipped. Returns the number of posts hidden.
+    pub fn hide_all_by_user(
+        env: Env,
+        board_id: u64,
+        user: Address,
+        start: u32,
+        limit: u32,
+        caller: Address,
+    ) -> u32 {
+        caller.require_auth();
+        Self::check_bulk_request(&env, board_id, limit, &caller);
+
+        let thread_total = Self::user_log_len(&env, board_id, &user, false);
+        let end = start.saturating_add(limit);
+        let mut hidden = 0;
+        if start < thread_total {
+            let threads: Vec<ThreadMeta> = env
+                .try_invoke_contract::<Vec<ThreadMeta>, soroban_sdk::Error>(
+                    &Self::get_board_contract_address(&env),
+                    &Symbol::new(&env, "list_user_thread_log"),
+                    Self::user_log_args(&env, board_id, &user, start, end.min(thread_total)),
+                )
+                .ok()
+                .and_then(|r| r.ok())
+                .unwrap_or(Vec::new(&env));
+            for thread in threads.iter() {
+                if !thread.is_hidden
+                    && !thread.is_deleted
+                    && Self::try_hide_item(&env, board_id, thread.id, None, &caller)
+                {
+                    hidden += 1;
+                }
+            }
+        }
+        if end > thread_total {
+            let reply_start = start.max(thread_total) - thread_total;
+            let replies: Vec<ReplyMeta> = env
+                .try_invoke_contract::<Vec<ReplyMeta>, soroban_sdk::Error>(
+                    &Self::get_content(env.clone()),
+                    &Symbol::new(&env, "list_user_reply_log"),
+                    Self::user_log_args(&env, board_id, &user, reply_start, end - thread_total),
+                )
+                .ok()
+                .and_then(|r| r.ok())
+                .unwrap_or(Vec::new(&env));
+            for reply in replies.iter() {
+                if !reply.is_hidden
+                    && !reply.is_deleted
+                    && Self::try_hide_item(&env, board_id, reply.thread_id, Some(reply.id), &caller)
+                {
+                    hidden += 1;
+                }
+            }
+        }
+
+        // Remember how far the cleanup got for the moderation pages
+        let total = thread_total + Self::user_log_len(&env, board_id, &user, true);
+        let cursor_key = AdminKey::HideAllCursor(board_id, user);
+        let cursor: u32 = env.storage().persistent().get(&cursor_key).unwrap_or(0);
+        if start <= cursor && end > cursor {
+            env.storage().persistent().set(&cursor_key, &end.min(total));
+        }
+        hidden
+    }
+
+    /// Number of posts `hide_all_by_user` pages through: the user's threads, then replies
+    pub fn user_post_count(env: Env, board_id: u64, user: Address) -> u32 {
+        Self::user_log_len(&env, board_id, &user, false)
+            + Self::user_log_len(&env, board_id, &user, true)
+    }
+
+    /// Clear flags on several threads/replies (moderator+).
+    /// Each item is (thread_id, reply_id); missing items are skipped.
+    /// Returns the number of items actually cleared.
+    pub fn clear_flags_bulk(
+        env: Env,
+        board_id: u64,
+        items: Vec<(u64, Option<u64>)>,
+        caller: Address,
+    ) -> u32 {
+        caller.require_auth();
+        Self::check_bulk_request(&env, board_id, items.len(), &caller);
+
+        let mut done = 0;
+        for (thread_id, reply_id) in items.iter() {
+            if Self::try_clear_item(&env, board_id, thread_id, reply_id, &caller) {
+                done += 1;
+            }
+        }
+        done
+    }
+
+    /// Form handler for "Hide selected" on the flag queue.
+    /// `selected` is a comma-separated list of `thread` or `thread:reply` ids.
+    pub fn hide_selected(env: Env, board_id: u64, selected: String, caller: Address) -> u32 {
+        caller.require_auth();
+        let items = Self::parse_selected_items(&env, &selected);
+        Self::check_bulk_request(&env, board_id, items.len(), &caller);
+
+        let mut done = 0;
+        for (thread_id, reply_id) in items.iter() {
+            if Self::try_hide_item(&env, board_id, thread_id, reply_id, &caller) {
+                done += 1;
+            }
+        }
+        done
+    }
+
+    /// Form handler for "Clear selected" on the flag queue.
+    /// `selected` is a comma-separated list of `thread` or `thread:reply` ids.
+    pub fn clear_selected(env: Env, board_id: u64, selected: String, caller: Address) -> u32 {
+        caller.require_auth();
+        let items = Self::parse_selected_items(&env, &selected);
+        Self::check_bulk_request(&env, board_id, items.len(), &caller);
+
+        let mut done = 0;
+        for (thread_id, reply_id) in items.iter() {
+            if Self::try_clear_item(&env, board_id, thread_id, reply_id, &caller) {
+                done += 1;
+            }
+        }
+        done
+    }
+
+    /// Length of a user's full thread log (board contract) or reply log (content
+    /// contract). Zero if the contract doesn't keep one.
+    fn user_log_len(env: &Env, board_id: u64, user: &Address, replies: bool) -> u32 {
+        let (contract, func) = if replies {
+            (Self::get_content(env.clone()), "user_reply_log_len")
+        } else {
+            (Self::get_board_contract_address(env), "user_thread_log_len")
+        };
+        env.try_invoke_contract::<u64, soroban_sdk::Error>(
+            &contract,
+            &Symbol::new(env, func),
+            Vec::from_array(env, [board_id.into_val(env), user.into_val(env)]),
+        )
+        .ok()
+        .and_then(|r| r.ok())
+        .unwrap_or(0) as u32
+    }
+
+    /// Arguments for a user log listing covering positions [start, end)
+    fn user_log_args(env: &Env, board_id: u64, user: &Address, start: u32, end: u32) -> Vec<Val> {
+        Vec::from_array(
+            env,
+            [
+                board_id.into_val(env),
+                user.into_val(env),
+                (start as u64).into_val(env),
+                (end.saturating_sub(start) as u64).into_val(env),
+            ],
+        )
+    }
+
+    /// Single permission check shared by the bulk handlers, plus the batch size cap
+    fn check_bulk_request(env: &Env, board_id: u64, count: u32, caller: &Address) {
+        if count > MAX_BULK_ITEMS {
+            panic!("Too many items in batch (max 25)");
+        }
+
+        let permissions: Address = env
+            .storage()
+            .instance()
+            .get(&AdminKey::Permissions)
+            .expect("Not initialized");
+
+        let caller_perms: PermissionSet = env.invoke_contract(
+            &permissions,
+            &Symbol::new(env, "get_permissions"),
+            Vec::from_array(env, [board_id.into_val(env), caller.into_val(env)]),
+        );
+
+        if !caller_perms.can_moderate {
+            panic!("Caller must be moderator or higher");
+        }
+    }
+
+    /// Hide one thread (reply_id None) or reply, returning false if it doesn't exist
+    /// or the call failed
+    fn try_hide_item(
+        env: &Env,
+        board_id: u64,
+        thread_id: u64,
+        reply_id: Option<u64>,
+        caller: &Address,
+    ) -> bool {
+        // The setters quietly ignore missing items, so check first
+        let Some(author) = Self::item_author(env, board_id, thread_id, reply_id) else {
+            return false;
+        };
+        let ok = match reply_id {
+            None => {
+                let board_contract = Self::get_board_contract_address(env);
+                let args: Vec<Val> = Vec::from_array(
+                    env,
+                    [
+                        board_id.into_val(env),
+                        thread_id.into_val(env),
+                        true.into_val(env),
+                    ],
+                );
+                env.try_invoke_contract::<(), soroban_sdk::Error>(
+                    &board_contract,
+                    &Symbol::new(env, "set_thread_hidden"),
+                    args,
+                )
+                .is_ok()
+            }
+            Some(reply_id) => {
+                let content: Address = env
+                    .storage()
+                    .instance()
+                    .get(&AdminKey::Content)
+                    .expect("Not initialized");
+                let args: Vec<Val> = Vec::from_array(
+                    env,
+                    [
+                        board_id.into_val(env),
+                        thread_id.into_val(env),
+                        reply_id.into_val(env),
+                        true.into_val(env),
+                    ],
+                );
+                env.try_invoke_contract::<(), soroban_sdk::Error>(
+                    &content,
+                    &Symbol::new(env, "set_reply_hidden"),
+                    args,
+                )
+                .is_ok()
+            }
+        };
+
+        if ok {
+            Self::notify_user(
+                env, &author, "hidden", board_id, thread_id, reply_id, caller,
+            );
+            let action = if reply_id.is_some() {
+                "hide_reply"
+            } else {
+                "hide_thread"
+            };
+            Self::record_mod_action(
+                env,
+                board_id,
+                caller,
+                action,
+                Some(thread_id),
+                reply_id,
+                None,
+                None,
+            );
+        }
+        ok
+    }
+
+    /// Clear flags on one thread or reply, returning false if it doesn't exist
+    /// or the call failed
+    fn try_clear_item(
+        env: &Env,
+        board_id: u64,
+        thread_id: u64,
+        reply_id: Option<u64>,
+        caller: &Address,
+    ) -> bool {
+        if Self::item_author(env, board_id, thread_id, reply_id).is_none() {
+            return false;
+        }
+        let content: Address = env
+            .storage()
+            .instance()
+            .get(&AdminKey::Content)
+            .expect("Not initialized");
+        let args: Vec<Val> = Vec::from_array(
+            env,
+            [
+                board_id.into_val(env),
+                thread_id.into_val(env),
+                reply_id.into_val(env),
+            ],
+        );
+        let ok = env
+            .try_invoke_contract::<(), soroban_sdk::Error>(
+                &content,
+                &Symbol::new(env, "clear_flags"),
+                args,
+            )
+            .is_ok();
+
+        if ok {
+            Self::record_mod_action(
+                env,
+                board_id,
+                caller,
+                "clear_flags",
+                Some(thread_id),
+                reply_id,
+                None,
+                None,
+            );
+        }
+        ok
+    }
+
+    /// Parse a flag queue selection like "3,5:2,7" into (thread_id, reply_id) items
+    fn parse_selected_items(env: &Env, selected: &String) -> Vec<(u64, Option<u64>)> {
+        let mut items = Vec::new(env);
+        let len = selected.len() as usize;
+        if len == 0 {
+            return items;
+        }
+        if len > 512 {
+            panic!("Too many items in batch (max 25)");
+        }
+
+        let mut buf = [0u8; 512];
+        selected.copy_into_slice(&mut buf[..len]);
+
+        let mut thread: Option<u64> = None;
+        let mut current: Option<u64> = None;
+        for &b in buf[..len].iter().chain([b','].iter()) {
+            match b {
+                b'0'..=b'9' => {
+                    let digit = (b - b'0') as u64;
+                    current = Some(
+                        current
+                            .unwrap_or(0)
+                            .checked_mul(10)
+                            .and_then(|v| v.checked_add(digit))
+                            .expect("Invalid selection"),
+                    );
+                }
+                b':' => {
+                    if thread.is_some() {
+                        panic!("Invalid selection");
+                    }
+                    thread = Some(current.take().expect("Invalid selection"));
+                }
+                b',' => match (thread.take(), current.take()) {
+                    (Some(t), Some(r)) => items.push_back((t, Some(r))),
+                    (None, Some(t)) => items.push_back((t, None)),
+                    (None, None) => {}
+                    _ => panic!("Invalid selection"),
+                },
+                b' ' => {}
+                _ => panic!("Invalid selection"),
+            }
+        }
+        items
+    }
+
+    // ========================================================================
+    // Flair Operations
+    // ========================================================================
+
+    /// Create a new flair (admin+)
+    /// Parameters are optional to handle form submissions where fields may be empty
+    pub fn create_flair(
+        env: Env,
+        board_id: u64,
+        name: String,
+        color: Option<String>,
+        bg_color: Option<String>,
+        required: Option<String>,
+        mod_only: Option<String>,
+        caller: Address,
+    ) {
+        caller.require_auth();
+
+        let permissions: Address = env
+            .storage()
+            .instance()
+            .get(&AdminKey::Permissions)
+            .expect("Not initialized");
+
+        // Verify caller has admin permissions
+        let caller_perms: PermissionSet = env.invoke_contract(
+            &permissions,
+            &Symbol::new(&env, "get_permissions"),
+            Vec::from_array(
+                &env,
+                [board_id.into_val(&env), caller.clone().into_val(&env)],
+            ),
+        );
+
+        if !caller_perms.can_admin {
+            panic!("Caller must be admin or owner");
+        }
+
+        // Get board contract (single contract for all boards)
+        let board_contract = Self::get_board_contract_address(&env);
+
+        // Provide defaults for optional color fields
+        let final_color = color.unwrap_or_else(|| String::from_str(&env, "#ffffff"));
+        let final_bg_color = bg_color.unwrap_or_else(|| String::from_str(&env, "#808080"));
+
+        Self::validate_flair_fields(&name, &final_color, &final_bg_color);
+
+        // Parse checkbox values
+        let is_required = Self::parse_checkbox(&required, false);
+        let is_mod_only = Self::parse_checkbox(&mod_only, false);
+
+        // Create the flair (now requires board_id)
+        let args: Vec<Val> = Vec::from_array(
+            &env,
+            [
+                board_id.into_val(&env),
+                name.into_val(&env),
+                final_color.into_val(&env),
+                final_bg_color.into_val(&env),
+                is_required.into_val(&env),
+                is_mod_only.into_val(&env),
+                caller.into_val(&env),
+            ],
+        );
+        env.invoke_contract::<u32>(&board_contract, &Symbol::new(&env, "create_flair"), args);
+    }
+
+    /// Disable a flair (admin+)
+    pub fn disable_flair(env: Env, board_id: u64, flair_id: String, caller: Address) {
+        caller.require_auth();
+
+        let flair_id_u32 = Self::parse_string_to_u32(&flair_id);
+
+        let permissions: Address = env
+            .storage()
+            .instance()
+            .get(&AdminKey::Permissions)
+            .expect("Not initialized");
+
+        // Verify caller has admin permissions
+        let caller_perms: PermissionSet = env.invoke_contract(
+            &permissions,
+            &Symbol::new(&env, "get_permissions"),
+            Vec::from_array(
+                &env,
+                [board_id.into_val(&env), caller.clone().into_val(&env)],
+            ),
+        );
+
+        if !caller_perms.can_admin {
+            panic!("Caller must be admin or owner");
+        }
+
+        // Get board contract (single contract for all boards)
+        let board_contract = Self::get_board_contract_address(&env);
+
+        // Disable the flair (now requires board_id)
+        let args: Vec<Val> = Vec::from_array(
+            &env,
+            [
+                board_id.into_val(&env),
+                flair_id_u32.into_val(&env),
+                caller.into_val(&env),
+            ],
+        );
+        env.invoke_contract::<()>(&board_contract, &Symbol::new(&env, "disable_flair"), args);
+    }
+
+    /// Update an existing flair (admin+)
+    pub fn update_flair(
+        env: Env,
+        board_id: u64,
+        flair_id: String,
+        name: String,
+        color: Option<String>,
+        bg_color: Option<String>,
+        required: Option<String>,
+        mod_only: Option<String>,
+        enabled: Option<String>,
+        caller: Address,
+    ) {
+        caller.require_auth();
+
+        let flair_id_u32 = Self::parse_string_to_u32(&flair_id);
+
+        let permissions: Address = env
+            .storage()
+            .instance()
+            .get(&AdminKey::Permissions)
+            .expect("Not initialized");
+
+        // Verify caller has admin permissions
+        let caller_perms: PermissionSet = env.invoke_contract(
+            &permissions,
+            &Symbol::new(&env, "get_permissions"),
+            Vec::from_array(
+                &env,
+                [board_id.into_val(&env), caller.clone().into_val(&env)],
+            ),
+        );
+
+        if !caller_perms.can_admin {
+            panic!("Caller must be admin or owner");
+        }
+
+        // Get board contract (single contract for all boards)
+        let board_contract = Self::get_board_contract_address(&env);
+
+        // Provide defaults for optional color fields
+        let final_color = color.unwrap_or_else(|| String::from_str(&env, "#ffffff"));
+        let final_bg_color = bg_color.unwrap_or_else(|| String::from_str(&env, "#808080"));
+        Self::validate_flair_fields(&name, &final_color, &final_bg_color);
+
+        // Parse checkbox values
+        let is_required = Self::parse_checkbox(&required, false);
+        let is_mod_only = Self::parse_checkbox(&mod_only, false);
+        let is_enabled = Self::parse_checkbox(&enabled, true); // Default to enabled
+
+        // Build FlairDef
+        let flair = FlairDef {
+            id: flair_id_u32,
+            name,
+            color: final_color,
+            bg_color: final_bg_color,
+            required: is_required,
+            mod_only: is_mod_only,
+            enabled: is_enabled,
+        };
+
+        // Update the flair (board_id is required by boards-board.update_flair)
+        let args: Vec<Val> = Vec::from_array(
+            &env,
+            [
+                board_id.into_val(&env),
+                flair_id_u32.into_val(&env),
+                flair.into_val(&env),
+                caller.into_val(&env),
+            ],
+        );
+        env.invoke_contract::<()>(&board_contract, &Symbol::new(&env, "update_flair"), args);
+    }
+
+    /// Create a thread template (admin+)
+    pub fn create_template(env: Env, board_id: u64, name: String, body: String, caller: Address) {
+        caller.require_auth();
+
+        let permissions: Address = env
+            .storage()
+            .instance()
+            .get(&AdminKey::Permissions)
+            .expect("Not initialized");
+
+        // Verify caller has admin permissions
+        let caller_perms: PermissionSet = env.invoke_contract(
+            &permissions,
+            &Symbol::new(&env, "get_permissions"),
+            Vec::from_array(
+                &env,
+                [board_id.into_val(&env), caller.clone().into_val(&env)],
+            ),
+        );
+
+        if !caller_perms.can_admin {
+            panic!("Caller must be admin or owner");
+        }
+
+        let board_contract = Self::get_board_contract_address(&env);
+        let args: Vec<Val> = Vec::from_array(
+            &env,
+            [
+                board_id.into_val(&env),
+                name.into_val(&env),
+                body.into_val(&env),
+                caller.into_val(&env),
+            ],
+        );
+        env.invoke_contract::<u32>(&board_contract, &Symbol::new(&env, "create_template"), args);
+    }
+
+    /// Delete a thread template (admin+)
+    pub fn delete_template(env: Env, board_id: u64, template_id: String, caller: Address) {
+        caller.require_auth();
+
+        let template_id_u32 = Self::parse_string_to_u32(&template_id);
+
+        let permissions: Address = env
+            .storage()
+            .instance()
+            .get(&AdminKey::Permissions)
+            .expect("Not initialized");
+
+        // Verify caller has admin permissions
+        let caller_perms: PermissionSet = env.invoke_contract(
+            &permissions,
+            &Symbol::new(&env, "get_permissions"),
+            Vec::from_array(
+                &env,
+                [board_id.into_val(&env), caller.clone().into_val(&env)],
+            ),
+        );
+
+        if !caller_perms.can_admin {
+            panic!("Caller must be admin or owner");
+        }
+
+        let board_contract = Self::get_board_contract_address(&env);
+        let args: Vec<Val> = Vec::from_array(
+            &env,
+            [
+                board_id.into_val(&env),
+                template_id_u32.into_val(&env),
+                caller.into_val(&env),
+            ],
+        );
+        env.invoke_contract::<()>(&board_contract, &Symbol::new(&env, "delete_template"), args);
+    }
+
+    /// Parse a checkbox form value ("true"/"false"); missing or empty uses the default
+    fn parse_checkbox(value: &Option<String>, default: bool) -> bool {
+        match value {
+            Some(s) if !s.is_empty() => Self::parse_string_to_bool(s),
+            _ => default,
+        }
+    }
+
+    /// Copy a form value into a buffer and return the bounds with ASCII whitespace trimmed
+    fn trimmed_form_value(value: &String, buf: &mut [u8; 64]) -> (usize, usize) {
+        let len = value.len() as usize;
+        if len > buf.len() {
+            panic!("Value too long");
+        }
+        value.copy_into_slice(&mut buf[..len]);
+
+        let mut start = 0;
+        let mut end = len;
+        while start < end && buf[start].is_ascii_whitespace() {
+            start += 1;
+        }
+        while end > start && buf[end - 1].is_ascii_whitespace() {
+            end -= 1;
+        }
+        (start, end)
+    }
+
+    /// Parse a form number, ignoring surrounding whitespace
+    fn parse_string_to_u64(value: &String) -> u64 {
+        let mut buf = [0u8; 64];
+        let (start, end) = Self::trimmed_form_value(value, &mut buf);
+        if start == end {
+            panic!("Number is required");
+        }
+
+        let mut result: u64 = 0;
+        for &b in buf[start..end].iter() {
+            if !b.is_ascii_digit() {
+                panic!("Invalid number");
+            }
+            result = result
+                .checked_mul(10)
+                .and_then(|r| r.checked_add((b - b'0') as u64))
+                .expect("Number too large");
+        }
+        result
+    }
+
+    /// Parse a form number that must fit in a u32, ignoring surrounding whitespace
+    fn parse_string_to_u32(value: &String) -> u32 {
+        u32::try_from(Self::parse_string_to_u64(value)).expect("Number too large")
+    }
+
+    /// Parse a form number that may be negative, ignoring surrounding whitespace
+    fn parse_string_to_i32(value: &String) -> i32 {
+        let mut buf = [0u8; 64];
+        let (start, end) = Self::trimmed_form_value(value, &mut buf);
+        let negative = start < end && buf[start] == b'-';
+        let digits = &buf[start + negative as usize..end];
+        if digits.is_empty() {
+            panic!("Number is required");
+        }
+
+        let mut result: i64 = 0;
+        for &b in digits.iter() {
+            if !b.is_ascii_digit() {
+                panic!("Invalid number");
+            }
+            result = result
+                .checked_mul(10)
+                .and_then(|r| r.checked_add((b - b'0') as i64))
+                .expect("Number too large");
+        }
+        i32::try_from(if negative { -result } else { result }).expect("Number too large")
+    }
+
+    /// Parse an optional numeric settings field.
+    /// Returns Some(None) for a blank field and None if the value is not a number.
+    fn parse_setting_field(value: &String) -> Option<Option<u64>> {
+        let mut buf = [0u8; 64];
+        let (start, end) = Self::trimmed_form_value(value, &mut buf);
+        if start == end {
+            return Some(None);
+        }
+
+        let mut result: u64 = 0;
+        for &b in buf[start..end].iter() {
+            if !b.is_ascii_digit() {
+                return None;
+            }
+            result = result.checked_mul(10)?.checked_add((b - b'0') as u64)?;
+        }
+        Some(Some(result))
+    }
+
+    /// Parse a thread sort name from a form value
+    fn parse_thread_sort(value: &String) -> ThreadSort {
+        let mut buf = [0u8; 64];
+        let (start, end) = Self::trimmed_form_value(value, &mut buf);
+        match &buf[start..end] {
+            b"hot" => ThreadSort::Hot,
+            b"new" => ThreadSort::New,
+            b"top" => ThreadSort::Top,
+            b"active" => ThreadSort::Active,
+            b"controversial" => ThreadSort::Controversial,
+            _ => panic!("Unknown sort: use hot, new, top, active or controversial"),
+        }
+    }
+
+    /// Parse a wiki edit role from a form value ("member" or "moderator")
+    fn parse_wiki_edit_role(value: &String) -> Role {
+        let mut buf = [0u8; 64];
+        let (start, end) = Self::trimmed_form_value(value, &mut buf);
+        match &buf[start..end] {
+            b"member" => Role::Member,
+            b"moderator" => Role::Moderator,
+            _ => panic!("Unknown role: use member or moderator"),
+        }
+    }
+
+    /// Parse a locale code from a form value (one of BOARD_LOCALES, empty for none)
+    fn parse_locale(env: &Env, value: &String) -> Option<Symbol> {
+        let mut buf = [0u8; 64];
+        let (start, end) = Self::trimmed_form_value(value, &mut buf);
+        if start == end {
+            return None;
+        }
+        match BOARD_LOCALES
+            .iter()
+            .find(|(code, _)| code.as_bytes() == &buf[start..end])
+        {
+            Some((code, _)) => Some(Symbol::new(env, code)),
+            None => panic!("Unknown locale"),
+        }
+    }
+
+    /// Parse a hex-encoded 32-byte WASM hash
+    fn parse_wasm_hash(env: &Env, value: &String) -> BytesN<32> {
+        if value.len() != 64 {
+            panic!("Invalid WASM hash");
+        }
+        let mut hex = [0u8; 64];
+        value.copy_into_slice(&mut hex);
+
+        let nibble = |c: u8| -> u8 {
+            match c {
+                b'0'..=b'9' => c - b'0',
+                b'a'..=b'f' => c - b'a' + 10,
+                b'A'..=b'F' => c - b'A' + 10,
+                _ => panic!("Invalid WASM hash"),
+            }
+        };
+        let mut hash = [0u8; 32];
+        for (i, byte) in hash.iter_mut().enumerate() {
+            *byte = (nibble(hex[i * 2]) << 4) | nibble(hex[i * 2 + 1]);
+        }
+        BytesN::from_array(env, &hash)
+    }
+
+    /// Hex-encode a 32-byte WASM hash for display
+    fn wasm_hash_to_hex(env: &Env, hash: &BytesN<32>) -> Bytes {
+        const DIGITS: &[u8; 16] = b"0123456789abcdef";
+        let mut hex = [0u8; 64];
+        for (i, byte) in hash.to_array().iter().enumerate() {
+            hex[i * 2] = DIGITS[(byte >> 4) as usize];
+            hex[i * 2 + 1] = DIGITS[(byte & 0x0f) as usize];
+        }
+        Bytes::from_slice(env, &hex)
+    }
+
+    /// Parse a form boolean: "true"/"false"/"1"/"0", ignoring surrounding whitespace
+    fn parse_string_to_bool(value: &String) -> bool {
+        let mut buf = [0u8; 64];
+        let (start, end) = Self::trimmed_form_value(value, &mut buf);
+        match &buf[start..end] {
+            b"true" | b"1" => true,
+            b"false" | b"0" => false,
+            _ => panic!("Invalid boolean"),
+        }
+    }
+
+    /// Validate flair form fields (name 1-32 chars, colors `#rgb` or `#rrggbb`)
+    fn validate_flair_fields(name: &String, color: &String, bg_color: &String) {
+        if name.is_empty() || name.len() > 32 {
+            panic!("Flair name must be 1-32 characters");
+        }
+        if !is_hex_color(color) || !is_hex_color(bg_color) {
+            panic!("Flair colors must be hex (#rgb or #rrggbb)");
+        }
+    }
+
+    // ========================================================================
+    // Rules Operations
+    // ========================================================================
+
+    /// Set board rules (admin+)
+    pub fn set_rules(env: Env, board_id: u64, rules: String, caller: Address) {
+        caller.require_auth();
+
+        let permissions: Address = env
+            .storage()
+            .instance()
+            .get(&AdminKey::Permissions)
+            .expect("Not initialized");
+
+        // Verify caller has admin permissions
+        let caller_perms: PermissionSet = env.invoke_contract(
+            &permissions,
+            &Symbol::new(&env, "get_permissions"),
+            Vec::from_array(
+                &env,
+                [board_id.into_val(&env), caller.clone().into_val(&env)],
+            ),
+        );
+
+        if !caller_perms.can_admin {
+            panic!("Caller must be admin or owner");
+        }
+
+        // Get board contract (single contract for all boards)
+        let board_contract = Self::get_board_contract_address(&env);
+
+        // Set the rules (now requires board_id)
+        let args: Vec<Val> = Vec::from_array(
+            &env,
+            [
+                board_id.into_val(&env),
+                rules.into_val(&env),
+                caller.into_val(&env),
+            ],
+        );
+        env.invoke_contract::<()>(&board_contract, &Symbol::new(&env, "set_rules"), args);
+    }
+
+    /// Clear board rules (admin+)
+    pub fn clear_rules(env: Env, board_id: u64, caller: Address) {
+        caller.require_auth();
+
+        let permissions: Address = env
+            .storage()
+            .instance()
+            .get(&AdminKey::Permissions)
+            .expect("Not initialized");
+
+        // Verify caller has admin permissions
+        let caller_perms: PermissionSet = env.invoke_contract(
+            &permissions,
+            &Symbol::new(&env, "get_permissions"),
+            Vec::from_array(
+                &env,
+                [board_id.into_val(&env), caller.clone().into_val(&env)],
+            ),
+        );
+
+        if !caller_perms.can_admin {
+            panic!("Caller must be admin or owner");
+        }
+
+        // Get board contract (single contract for all boards)
+        let board_contract = Self::get_board_contract_address(&env);
+
+        // Clear the rules (now requires board_id)
+        let args: Vec<Val> =
+            Vec::from_array(&env, [board_id.into_val(&env), caller.into_val(&env)]);
+        env.invoke_contract::<()>(&board_contract, &Symbol::new(&env, "clear_rules"), args);
+    }
+
+    // ========================================================================
+    // Voting Operations
+    // ========================================================================
+
+    /// Set voting configuration for a board (admin+)
+    /// Checkbox fields arrive as "true"/"false" strings; the multiplier and vote weights
+    /// as number strings
+    pub fn set_voting_config(
+        env: Env,
+        board_id: u64,
+        enabled: String,
+        allow_downvotes: String,
+        karma_enabled: String,
+        karma_multiplier: String,
+        vote_weight_member: String,
+        vote_weight_moderator: String,
+        caller: Address,
+    ) {
+        caller.require_auth();
+
+        let permissions: Address = env
+            .storage()
+            .instance()
+            .get(&AdminKey::Permissions)
+            .expect("Not initialized");
+
+        // Verify caller has admin permissions
+        let caller_perms: PermissionSet = env.invoke_contract(
+            &permissions,
+            &Symbol::new(&env, "get_permissions"),
+            Vec::from_array(
+                &env,
+                [board_id.into_val(&env), caller.clone().into_val(&env)],
+            ),
+        );
+
+        if !caller_perms.can_admin {
+            panic!("Caller must be admin or owner");
+        }
+
+        let config = Self::parse_voting_config(
+            &enabled,
+            &allow_downvotes,
+            &karma_enabled,
+            &karma_multiplier,
+        );
+        let member_weight = Self::parse_vote_weight(&vote_weight_member);
+        let moderator_weight = Self::parse_vote_weight(&vote_weight_moderator);
+
+        let voting_contract =
+            Self::get_voting_contract_address(&env).expect("Voting contract not configured");
+
+        let args: Vec<Val> = Vec::from_array(
+            &env,
+            [
+                board_id.into_val(&env),
+                config.into_val(&env),
+                caller.into_val(&env),
+            ],
+        );
+        env.invoke_contract::<()>(
+            &voting_contract,
+            &Symbol::new(&env, "set_voting_config"),
+            args,
+        );
+
+        // Weights live under their own key so stored configs keep decoding
+        let args: Vec<Val> = Vec::from_array(
+            &env,
+            [
+                board_id.into_val(&env),
+                member_weight.into_val(&env),
+                moderator_weight.into_val(&env),
+                caller.into_val(&env),
+            ],
+        );
+        env.invoke_contract::<()>(
+            &voting_contract,
+            &Symbol::new(&env, "set_vote_weights"),
+            args,
+        );
+    }
+
+    /// Set the reactions enabled on a board (admin+).
+    /// Accepts a comma-separated list of reaction names since HTML forms submit strings.
+    pub fn set_reaction_allowlist(env: Env, board_id: u64, reactions: String, caller: Address) {
+        caller.require_auth();
+
+        let permissions: Address = env
+            .storage()
+            .instance()
+            .get(&AdminKey::Permissions)
+            .expect("Not initialized");
+
+        // Verify caller has admin permissions
+        let caller_perms: PermissionSet = env.invoke_contract(
+            &permissions,
+            &Symbol::new(&env, "get_permissions"),
+            Vec::from_array(
+                &env,
+                [board_id.into_val(&env), caller.clone().into_val(&env)],
+            ),
+        );
+
+        if !caller_perms.can_admin {
+            panic!("Caller must be admin or owner");
+        }
+
+        let emojis = Self::parse_reaction_list(&env, &reactions);
+
+        let voting_contract =
+            Self::get_voting_contract_address(&env).expect("Voting contract not configured");
+
+        let args: Vec<Val> = Vec::from_array(
+            &env,
+            [
+                board_id.into_val(&env),
+                emojis.into_val(&env),
+                caller.into_val(&env),
+            ],
+        );
+        env.invoke_contract::<()>(
+            &voting_contract,
+            &Symbol::new(&env, "set_reaction_allowlist"),
+            args,
+        );
+    }
+
+    /// Parse a comma-separated reaction list from a form value.
+    /// Names must come from REACTION_NAMES; repeats are dropped.
+    fn parse_reaction_list(env: &Env, reactions: &String) -> Vec<Symbol> {
+        let len = reactions.len() as usize;
+        if len > MAX_REACTION_LIST_LEN {
+            panic!("Reaction list is too long");
+        }
+        let mut buf = [0u8; MAX_REACTION_LIST_LEN];
+        reactions.copy_into_slice(&mut buf[..len]);
+
+        let mut emojis: Vec<Symbol> = Vec::new(env);
+        for token in buf[..len].split(|b| *b == b',') {
+            let token = token.trim_ascii();
+            if token.is_empty() {
+                continue;
+            }
+            let Some(name) = REACTION_NAMES.iter().find(|name| name.as_bytes() == token) else {
+                panic!("Unknown reaction name");
+            };
+            let emoji = Symbol::new(env, name);
+            if !emojis.contains(&emoji) {
+                emojis.push_back(emoji);
+            }
+        }
+        emojis
+    }
+
+    /// Parse a comma- or space-separated image host list from a form value.
+    /// Hosts are lowercased and repeats dropped; the board contract validates them.
+    fn parse_image_hosts(env: &Env, hosts: &String) -> Vec<String> {
+        let len = hosts.len() as usize;
+        if len > MAX_IMAGE_HOST_LIST_LEN {
+            panic!("Image host list is too long");
+        }
+        let mut buf = [0u8; MAX_IMAGE_HOST_LIST_LEN];
+        hosts.copy_into_slice(&mut buf[..len]);
+        buf[..len].make_ascii_lowercase();
+
+        let mut parsed: Vec<String> = Vec::new(env);
+        for token in buf[..len].split(|b| *b == b',' || b.is_ascii_whitespace()) {
+            if token.is_empty() {
+                continue;
+            }
+            let host = String::from_bytes(env, token);
+            if !parsed.contains(&host) {
+                parsed.push_back(host);
+            }
+        }
+        parsed
+    }
+
+    /// Build a VotingConfig from form strings.
+    /// Empty checkbox values are treated as unchecked; an empty multiplier defaults to 1.
+    fn parse_voting_config(
+        enabled: &String,
+        allow_downvotes: &String,
+        karma_enabled: &String,
+        karma_multiplier: &String,
+    ) -> VotingConfig {
+        let multiplier = if karma_multiplier.is_empty() {
+            1
+        } else {
+            Self::parse_string_to_u32(karma_multiplier)
+        };
+        if !(1..=10).contains(&multiplier) {
+            panic!("Karma multiplier must be between 1 and 10");
+        }
+
+        VotingConfig {
+            enabled: Self::parse_checkbox(&Some(enabled.clone()), false),
+            allow_downvotes: Self::parse_checkbox(&Some(allow_downvotes.clone()), false),
+            karma_enabled: Self::parse_checkbox(&Some(karma_enabled.clone()), false),
+            karma_multiplier: multiplier,
+        }
+    }
+
+    /// Parse a vote weight form field (1-10; empty defaults to 1)
+    fn parse_vote_weight(value: &String) -> u32 {
+        let weight = if value.is_empty() {
+            1
+        } else {
+            Self::parse_string_to_u32(value)
+        };
+        if !(1..=10).contains(&weight) {
+            panic!("Vote weights must be between 1 and 10");
+        }
+        weight
+    }
+
+    // ========================================================================
+    // Config Operations (site-wide settings)
+    // ========================================================================
+
+    /// Helper to verify caller is registry admin
+    fn require_registry_admin(env: &Env, caller: &Address) {
+        caller.require_auth();
+        let registry: Address = env
+            .storage()
+            .instance()
+            .get(&AdminKey::Registry)
+            .expect("Not initialized");
+        let is_admin: bool = env.invoke_contract(
+            &registry,
+            &Symbol::new(env, "is_admin"),
+            Vec::from_array(env, [caller.into_val(env)]),
+        );
+        if !is_admin {
+            panic!("Caller must be registry admin");
+        }
+    }
+
+    /// Helper to get and update branding
+    fn update_branding_field<F>(env: &Env, caller: &Address, updater: F)
+    where
+        F: FnOnce(&mut Branding),
+    {
+        Self::require_registry_admin(env, caller);
+
+        let config: Address = env
+            .storage()
+            .instance()
+            .get(&AdminKey::Config)
+            .expect("Config not set");
+
+        // Get current branding
+        let mut branding: Branding =
+            env.invoke_contract(&config, &Symbol::new(env, "get_branding"), Vec::new(env));
+
+        // Apply the update
+        updater(&mut branding);
+
+        // Save updated branding
+        let args: Vec<Val> = Vec::from_array(env, [branding.into_val(env), caller.into_val(env)]);
+        env.invoke_contract::<()>(&config, &Symbol::new(env, "set_branding"), args);
+    }
+
+    /// Set site name (registry admin only)
+    pub fn set_site_name(env: Env, site_name: String, caller: Address) {
+        Self::update_branding_field(&env, &caller, |b| {
+            if !site_name.is_empty() {
+                b.site_name = site_name;
+            }
+        });
+    }
+
+    /// Set tagline (registry admin only)
+    pub fn set_tagline(env: Env, tagline: String, caller: Address) {
+        Self::update_branding_field(&env, &caller, |b| {
+            if !tagline.is_empty() {
+                b.tagline = tagline;
+            }
+        });
+    }
+
+    /// Set primary color (registry admin only)
+    pub fn set_primary_color(env: Env, primary_color: String, caller: Address) {
+        Self::update_branding_field(&env, &caller, |b| {
+            if !primary_color.is_empty() {
+                b.primary_color = primary_color;
+            }
+        });
+    }
+
+    /// Set logo URL (registry admin only)
+    pub fn set_logo_url(env: Env, logo_url: String, caller: Address) {
+        Self::update_branding_field(&env, &caller, |b| {
+            b.logo_url = logo_url;
+        });
+    }
+
+    /// Set favicon URL (registry admin only)
+    pub fn set_favicon_url(env: Env, favicon_url: String, caller: Address) {
+        Self::update_branding_field(&env, &caller, |b| {
+            b.favicon_url = favicon_url;
+        });
+    }
+
+    /// Set footer text (registry admin only)
+    pub fn set_footer_text(env: Env, footer_text: String, caller: Address) {
+        Self::update_branding_field(&env, &caller, |b| {
+            b.footer_text = footer_text;
+        });
+    }
+
+    /// Update creation thresholds (registry admin only)
+    pub fn update_thresholds(
+        env: Env,
+        threshold_type: String,
+        min_karma: Option<String>,
+        min_account_age_secs: Option<String>,
+        min_post_count: Option<String>,
+        require_profile: Option<String>,
+        per_user_limit: Option<String>,
+        xlm_lock_stroops: Option<String>,
+        caller: Address,
+    ) {
+        Self::require_registry_admin(&env, &caller);
+
+        let config: Address = env
+            .storage()
+            .instance()
+            .get(&AdminKey::Config)
+            .expect("Config not set");
+
+        // Determine type
+        let is_board = {
+            let len = threshold_type.len() as usize;
+            let mut buf = [0u8; 16];
+            let copy_len = core::cmp::min(len, 16);
+            threshold_type.copy_into_slice(&mut buf[..copy_len]);
+            &buf[..5] == b"board"
+        };
+
+        // Get current thresholds
+        let mut thresholds: CreationThresholds = if is_board {
+            env.invoke_contract(
+                &config,
+                &Symbol::new(&env, "get_board_thresholds"),
+                Vec::new(&env),
+            )
+        } else {
+            env.invoke_contract(
+                &config,
+                &Symbol::new(&env, "get_community_thresholds"),
+                Vec::new(&env),
+            )
+        };
+
+        // Update fields
+        if let Some(s) = min_karma {
+            if !s.is_empty() {
+                thresholds.min_karma = Self::parse_string_to_u32(&s) as i64;
+            }
+        }
+        if let Some(s) = min_account_age_secs {
+            if !s.is_empty() {
+                thresholds.min_account_age_secs = Self::parse_string_to_u64(&s);
+            }
+        }
+        if let Some(s) = min_post_count {
+            if !s.is_empty() {
+                thresholds.min_post_count = Self::parse_string_to_u32(&s);
+            }
+        }
+        if let Some(s) = require_profile {
+            thresholds.require_profile = Self::parse_checkbox(&Some(s), false);
+        }
+        if let Some(s) = per_user_limit {
+            if !s.is_empty() {
+                thresholds.per_user_limit = Self::parse_string_to_u32(&s);
+            }
+        }
+        if let Some(s) = xlm_lock_stroops {
+            if !s.is_empty() {
+                thresholds.xlm_lock_stroops = Self::parse_string_to_u64(&s) as i128;
+            }
+        }
+
+        // Save updated thresholds
+        let func_name = if is_board {
+            "set_board_thresholds"
+        } else {
+            "set_community_thresholds"
+        };
+        let args: Vec<Val> =
+            Vec::from_array(&env, [thresholds.into_val(&env), caller.into_val(&env)]);
+        env.invoke_contract::<()>(&config, &Symbol::new(&env, func_name), args);
+    }
+
+    /// Set site-wide max reply depth (registry admin only)
+    pub fn config_set_max_reply_depth(env: Env, max_reply_depth: String, caller: Address) {
+        Self::require_registry_admin(&env, &caller);
+        let config: Address = env
+            .storage()
+            .instance()
+            .get(&AdminKey::Config)
+            .expect("Config not set");
+        let depth = Self::parse_string_to_u32(&max_reply_depth);
+        let args: Vec<Val> = Vec::from_array(&env, [depth.into_val(&env), caller.into_val(&env)]);
+        env.invoke_contract::<()>(&config, &Symbol::new(&env, "set_max_reply_depth"), args);
+    }
+
+    /// Set site-wide reply chunk size (registry admin only)
+    pub fn config_set_reply_chunk_size(env: Env, reply_chunk_size: String, caller: Address) {
+        Self::require_registry_admin(&env, &caller);
+        let config: Address = env
+            .storage()
+            .instance()
+            .get(&AdminKey::Config)
+            .expect("Config not set");
+        let size = Self::parse_string_to_u32(&reply_chunk_size);
+        let args: Vec<Val> = Vec::from_array(&env, [size.into_val(&env), caller.into_val(&env)]);
+        env.invoke_contract::<()>(&config, &Symbol::new(&env, "set_reply_chunk_size"), args);
+    }
+
+    /// Set default edit window (registry admin only)
+    pub fn config_set_default_edit_window(env: Env, default_edit_window: String, caller: Address) {
+        Self::require_registry_admin(&env, &caller);
+        let config: Address = env
+            .storage()
+            .instance()
+            .get(&AdminKey::Config)
+            .expect("Config not set");
+        let seconds = Self::parse_string_to_u64(&default_edit_window);
+        let args: Vec<Val> = Vec::from_array(&env, [seconds.into_val(&env), caller.into_val(&env)]);
+        env.invoke_contract::<()>(&config, &Symbol::new(&env, "set_default_edit_window"), args);
+    }
+
+    /// Set thread body max bytes (registry admin only)
+    pub fn config_set_thread_body_max_bytes(
+        env: Env,
+        thread_body_max_bytes: String,
+        caller: Address,
+    ) {
+        Self::require_registry_admin(&env, &caller);
+        let config: Address = env
+            .storage()
+            .instance()
+            .get(&AdminKey::Config)
+            .expect("Config not set");
+        let max_bytes = Self::parse_string_to_u32(&thread_body_max_bytes);
+        let args: Vec<Val> =
+            Vec::from_array(&env, [max_bytes.into_val(&env), caller.into_val(&env)]);
+        env.invoke_contract::<()>(
+            &config,
+            &Symbol::new(&env, "set_thread_body_max_bytes"),
+            args,
+        );
+    }
+
+    /// Set name limits (registry admin only)
+    pub fn config_set_name_limits(
+        env: Env,
+        limit_type: String,
+        min_length: String,
+        max_length: String,
+        caller: Address,
+    ) {
+        Self::require_registry_admin(&env, &caller);
+        let config: Address = env
+            .storage()
+            .instance()
+            .get(&AdminKey::Config)
+            .expect("Config not set");
+
+        let is_board = {
+            let len = limit_type.len() as usize;
+            let mut buf = [0u8; 16];
+            let copy_len = core::cmp::min(len, 16);
+            limit_type.copy_into_slice(&mut buf[..copy_len]);
+            &buf[..5] == b"board"
+        };
+
+        let limits = NameLimits {
+            min_length: Self::parse_string_to_u32(&min_length),
+            max_length: Self::parse_string_to_u32(&max_length),
+        };
+
+        let func_name = if is_board {
+            "set_board_name_limits"
+        } else {
+            "set_community_name_limits"
+        };
+        let args: Vec<Val> = Vec::from_array(&env, [limits.into_val(&env), caller.into_val(&env)]);
+        env.invoke_contract::<()>(&config, &Symbol::new(&env, func_name), args);
+    }
+
+    /// Upgrade the contract WASM
+    pub fn upgrade(env: Env, new_wasm_hash: BytesN<32>) {
+        let registry: Address = env
+            .storage()
+            .instance()
+            .get(&AdminKey::Registry)
+            .expect("Not initialized");
+        registry.require_auth();
+
+        env.deployer().update_current_contract_wasm(new_wasm_hash);
+    }
+}
+
+#[cfg(test)]
+mod test {
+    extern crate std;
+
+    use super::*;
+    use soroban_sdk::testutils::Address as _;
+    use soroban_sdk::Env;
+
+    /// Helper to setup a boards-admin contract with all dependencies
+    fn setup_admin(
+        env: &Env,
+    ) -> (
+        BoardsAdminClient,
+        Address,
+        Address,
+        Address,
+        Address,
+        Address,
+    ) {
+        env.mock_all_auths();
+
+        let contract_id = env.register(BoardsAdmin, ());
+        let client = BoardsAdminClient::new(env, &contract_id);
+
+        let registry = Address::generate(env);
+        let permissions = Address::generate(env);
+        let content = Address::generate(env);
+        let theme = Address::generate(env);
+        let config = Address::generate(env);
+
+        client.init(&registry, &permissions, &content, &theme, &config);
+
+        (client, registry, permissions, content, theme, config)
+    }
+
+    #[test]
+    fn test_init() {
+        let env = Env::default();
+        env.mock_all_auths();
+
+        let contract_id = env.register(BoardsAdmin, ());
+        let client = BoardsAdminClient::new(&env, &contract_id);
+
+        let registry = Address::generate(&env);
+        let permissions = Address::generate(&env);
+        let content = Address::generate(&env);
+        let theme = Address::generate(&env);
+        let config = Address::generate(&env);
+
+        client.init(&registry, &permissions, &content, &theme, &config);
+
+        assert_eq!(client.get_registry(), registry);
+        assert_eq!(client.get_permissions(), permissions);
+        assert_eq!(client.get_content(), content);
+        assert_eq!(client.get_theme(), theme);
+        assert_eq!(client.get_config(), Some(config));
+    }
+
+    #[test]
+    #[should_panic(expected = "Already initialized")]
+    fn test_double_init() {
+        let env = Env::default();
+        env.mock_all_auths();
+
+        let contract_id = env.register(BoardsAdmin, ());
+        let client = BoardsAdminClient::new(&env, &contract_id);
+
+        let registry = Address::generate(&env);
+        let permissions = Address::generate(&env);
+        let content = Address::generate(&env);
+        let theme = Address::generate(&env);
+        let config = Address::generate(&env);
+
+        client.init(&registry, &permissions, &content, &theme, &config);
+        // Should panic on second init
+        client.init(&registry, &permissions, &content, &theme, &config);
+    }
+
+    #[test]
+    fn test_get_registry() {
+        let env = Env::default();
+        let (client, registry, _, _, _, _) = setup_admin(&env);
+        assert_eq!(client.get_registry(), registry);
+    }
+
+    #[test]
+    fn test_get_permissions() {
+        let env = Env::default();
+        let (client, _, permissions, _, _, _) = setup_admin(&env);
+        assert_eq!(client.get_permissions(), permissions);
+    }
+
+    #[test]
+    fn test_get_content() {
+        let env = Env::default();
+        let (client, _, _, content, _, _) = setup_admin(&env);
+        assert_eq!(client.get_content(), content);
+    }
+
+    #[test]
+    fn test_get_theme() {
+        let env = Env::default();
+        let (client, _, _, _, theme, _) = setup_admin(&env);
+        assert_eq!(client.get_theme(), theme);
+    }
+
+    #[test]
+    fn test_get_config() {
+        let env = Env::default();
+        let (client, _, _, _, _, config) = setup_admin(&env);
+        assert_eq!(client.get_config(), Some(config));
+    }
+
+    #[test]
+    fn test_render_not_found() {
+        let env = Env::default();
+        let (client, _, _, _, _, _) = setup_admin(&env);
+
+        // Render unknown path should return "not found" content
+        let path = String::from_str(&env, "/unknown");
+        let html = client.render(&Some(path), &None);
+        assert!(html.len() > 0);
+    }
+
+    #[test]
+    fn test_render_no_path() {
+        let env = Env::default();
+        let (client, _, _, _, _, _) = setup_admin(&env);
+
+        // Render with no path (None)
+        let html = client.render(&None, &None);
+        assert!(html.len() > 0);
+    }
+
+    #[test]
+    fn test_render_empty_path() {
+        let env = Env::default();
+        let (client, _, _, _, _, _) = setup_admin(&env);
+
+        // Render with empty path
+        let path = String::from_str(&env, "");
+        let html = client.render(&Some(path), &None);
+        assert!(html.len() > 0);
+    }
+
+    #[test]
+    fn test_render_root_path() {
+        let env = Env::default();
+        let (client, _, _, _, _, _) = setup_admin(&env);
+
+        // Render with root path "/"
+        let path = String::from_str(&env, "/");
+        let html = client.render(&Some(path), &None);
+        assert!(html.len() > 0);
+    }
+
+    // Note: Admin actions like set_role, ban_user, rename_board, etc.
+    // require cross-contract calls to permissions, registry, and board contracts.
+    // These are integration-level tests that would require setting up
+    // the entire contract ecosystem with real initialized contracts.
+    // Role removal is covered below against a minimal permissions mock.
+
+    /// Minimal permissions contract that stores board roles
+    #[contract]
+    pub struct MockPermissions;
+
+    #[contractimpl]
+    impl MockPermissions {
+        pub fn set_role(env: Env, board_id: u64, user: Address, role: Role, _caller: Address) {
+            env.storage()
+                .persistent()
+                .set(&(board_id, user.clone()), &role);
+            let key = (Symbol::new(&env, "users"), board_id);
+            let mut users: Vec<Address> = env
+                .storage()
+                .persistent()
+                .get(&key)
+                .unwrap_or(Vec::new(&env));
+            if !users.contains(&user) {
+                users.push_back(user);
+                env.storage().persistent().set(&key, &users);
+            }
+        }
+
+        pub fn request_invite(env: Env, board_id: u64, message: String, user: Address) {
+            let mut requests = Self::list_invite_requests(env.clone(), board_id);
+            requests.push_back(InviteRequest {
+                user: user.clone(),
+                board_id,
+                created_at: env.ledger().timestamp(),
+            });
+            env.storage()
+                .persistent()
+                .set(&(Symbol::new(&env, "invites"), board_id), &requests);
+            env.storage()
+                .persistent()
+                .set(&(Symbol::new(&env, "invite_msg"), board_id, user), &message);
+        }
+
+        pub fn get_invite_messages(env: Env, board_id: u64, users: Vec<Address>) -> Vec<String> {
+            let mut messages = Vec::new(&env);
+            for user in users.iter() {
+                messages.push_back(
+                    env.storage()
+                        .persistent()
+                        .get(&(Symbol::new(&env, "invite_msg"), board_id, user))
+                        .unwrap_or(String::from_str(&env, "")),
+                );
+            }
+            messages
+        }
+
+        pub fn list_invite_requests(env: Env, board_id: u64) -> Vec<InviteRequest> {
+            env.storage()
+                .persistent()
+                .get(&(Symbol::new(&env, "invites"), board_id))
+                .unwrap_or(Vec::new(&env))
+        }
+
+        pub fn leave_board(env: Env, board_id: u64, caller: Address) {
+            env.storage().persistent().remove(&(board_id, caller));
+        }
+
+        pub fn get_invite_request_ttl(_env: Env, _board_id: u64) -> u64 {
+            0
+        }
 
-    /// Set a user's role on a board (admin+)
-    pub fn set_role(env: Env, board_id: u64, user: Address, role: Role, caller: Address) {
-        caller.require_auth();
+        pub fn get_flag_threshold(env: Env, board_id: u64) -> u32 {
+            env.storage()
+                .persistent()
+                .get(&(Symbol::new(&env, "threshold"), board_id))
+                .unwrap_or(3)
+        }
 
-        let permissions: Address = env
-            .storage()
-            .instance()
-            .get(&AdminKey::Permissions)
-            .expect("Not initialized");
+        pub fn set_flag_threshold(env: Env, board_id: u64, threshold: u32, _caller: Address) {
+            env.storage()
+                .persistent()
+                .set(&(Symbol::new(&env, "threshold"), board_id), &threshold);
+        }
 
-        // Verify caller has admin permissions
-        let caller_perms: PermissionSet = env.invoke_contract(
-            &permissions,
-            &Symbol::new(&env, "get_permissions"),
-            Vec::from_array(&env, [board_id.into_val(&env), caller.into_val(&env)]),
-        );
+        pub fn accept_invite_with_role(
+            env: Env,
+            board_id: u64,
+            user: Address,
+            role: Role,
+            caller: Address,
+        ) {
+            Self::set_role(env, board_id, user, role, caller);
+        }
 
-        if !caller_perms.can_admin {
-            panic!("Caller must be admin or owner");
+        fn users_with_role(env: &Env, board_id: u64, role: Role) -> Vec<Address> {
+            let users: Vec<Address> = env
+                .storage()
+                .persistent()
+                .get(&(Symbol::new(env, "users"), board_id))
+                .unwrap_or(Vec::new(env));
+            let mut matching = Vec::new(env);
+            for user in users.iter() {
+                if Self::get_role(env.clone(), board_id, user.clone()) == role {
+                    matching.push_back(user);
+                }
+            }
+            matching
         }
 
-        // Set the role
-        let args: Vec<Val> = Vec::from_array(
-            &env,
-            [
-                board_id.into_val(&env),
-                user.into_val(&env),
-                role.into_val(&env),
-                caller.into_val(&env),
-            ],
-        );
-        env.invoke_contract::<()>(&permissions, &Symbol::new(&env, "set_role"), args);
-    }
+        pub fn get_board_owner(env: Env, board_id: u64) -> Option<Address> {
+            Self::users_with_role(&env, board_id, Role::Owner).first()
+        }
 
-    /// Add a user as Member (convenience function for forms)
-    pub fn add_member(env: Env, board_id: u64, user_address: Address, caller: Address) {
-        Self::set_role(env, board_id, user_address, Role::Member, caller);
-    }
+        pub fn list_admins(env: Env, board_id: u64) -> Vec<Address> {
+            Self::users_with_role(&env, board_id, Role::Admin)
+        }
 
-    /// Add a user as Moderator (convenience function for forms)
-    pub fn add_moderator(env: Env, board_id: u64, user_address: Address, caller: Address) {
-        Self::set_role(env, board_id, user_address, Role::Moderator, caller);
-    }
+        pub fn list_moderators(env: Env, board_id: u64) -> Vec<Address> {
+            Self::users_with_role(&env, board_id, Role::Moderator)
+        }
 
-    /// Add a user as Admin (convenience function for forms)
-    pub fn add_admin(env: Env, board_id: u64, user_address: Address, caller: Address) {
-        Self::set_role(env, board_id, user_address, Role::Admin, caller);
-    }
+        pub fn list_members(env: Env, board_id: u64) -> Vec<Address> {
+            Self::users_with_role(&env, board_id, Role::Member)
+        }
 
-    // ========================================================================
-    // Invite Operations
-    // ========================================================================
+        pub fn list_members_page(env: Env, board_id: u64, start: u32, limit: u32) -> Vec<Address> {
+            let members = Self::list_members(env.clone(), board_id);
+            let end = start.saturating_add(limit).min(members.len());
+            if start >= end {
+                return Vec::new(&env);
+            }
+            members.slice(start..end)
+        }
 
-    /// Accept a pending invite request (moderator+)
-    pub fn accept_invite(env: Env, board_id: u64, user: Address, caller: Address) {
-        caller.require_auth();
+        pub fn role_count(env: Env, board_id: u64, role: Role) -> u32 {
+            Self::users_with_role(&env, board_id, role).len()
+        }
 
-        let permissions: Address = env
-            .storage()
-            .instance()
-            .get(&AdminKey::Permissions)
-            .expect("Not initialized");
+        pub fn get_role(env: Env, board_id: u64, user: Address) -> Role {
+            env.storage()
+                .persistent()
+                .get(&(board_id, user))
+                .unwrap_or(Role::Guest)
+        }
 
-        // Verify caller has moderator permissions
-        let caller_perms: PermissionSet = env.invoke_contract(
-            &permissions,
-            &Symbol::new(&env, "get_permissions"),
-            Vec::from_array(&env, [board_id.into_val(&env), caller.into_val(&env)]),
-        );
+        pub fn get_permissions(env: Env, board_id: u64, user: Address) -> PermissionSet {
+            let role = Self::get_role(env, board_id, user);
+            PermissionSet {
+                role,
+                can_view: true,
+                can_post: (role as u32) >= (Role::Member as u32),
+                can_moderate: (role as u32) >= (Role::Moderator as u32),
+                can_admin: (role as u32) >= (Role::Admin as u32),
+                is_banned: false,
+            }
+        }
 
-        if !caller_perms.can_moderate {
-            panic!("Caller must be moderator or higher");
+        pub fn ban_user(
+            env: Env,
+            board_id: u64,
+            user: Address,
+            reason: String,
+            duration_hours: Option<u64>,
+            caller: Address,
+        ) {
+            let ban = Ban {
+                user,
+                board_id,
+                issuer: caller,
+                reason,
+                created_at: env.ledger().timestamp(),
+                expires_at: duration_hours.map(|h| env.ledger().timestamp() + h * 3600),
+            };
+            let mut bans = Self::list_bans(env.clone(), board_id);
+            bans.push_back(ban);
+            env.storage()
+                .persistent()
+                .set(&(Symbol::new(&env, "bans"), board_id), &bans);
         }
 
-        // Accept the invite
-        let args: Vec<Val> = Vec::from_array(
-            &env,
-            [
-                board_id.into_val(&env),
-                user.into_val(&env),
-                caller.into_val(&env),
-            ],
-        );
-        env.invoke_contract::<()>(&permissions, &Symbol::new(&env, "accept_invite"), args);
+        pub fn list_bans(env: Env, board_id: u64) -> Vec<Ban> {
+            env.storage()
+                .persistent()
+                .get(&(Symbol::new(&env, "bans"), board_id))
+                .unwrap_or(Vec::new(&env))
+        }
     }
 
-    /// Revoke/reject a pending invite request (moderator+)
-    pub fn revoke_invite(env: Env, board_id: u64, user: Address, caller: Address) {
-        caller.require_auth();
-
-        let permissions: Address = env
-            .storage()
-            .instance()
-            .get(&AdminKey::Permissions)
-            .expect("Not initialized");
+    /// Minimal content contract that records which replies were hidden or cleared.
+    /// Like the real contract, missing replies are ignored rather than rejected.
+    #[contract]
+    pub struct MockContent;
 
-        // Verify caller has moderator permissions
-        let caller_perms: PermissionSet = env.invoke_contract(
-            &permissions,
-            &Symbol::new(&env, "get_permissions"),
-            Vec::from_array(&env, [board_id.into_val(&env), caller.into_val(&env)]),
-        );
+    #[contractimpl]
+    impl MockContent {
+        pub fn add_reply(env: Env, thread_id: u64, reply_id: u64) {
+            env.storage()
+                .instance()
+                .set(&(Symbol::new(&env, "reply"), thread_id, reply_id), &true);
+        }
 
-        if !caller_perms.can_moderate {
-            panic!("Caller must be moderator or higher");
+        pub fn get_reply_author(
+            env: Env,
+            _board_id: u64,
+            thread_id: u64,
+            reply_id: u64,
+        ) -> Option<Address> {
+            env.storage()
+                .instance()
+                .has(&(Symbol::new(&env, "reply"), thread_id, reply_id))
+                .then(|| env.current_contract_address())
         }
 
-        // Revoke the invite
-        let args: Vec<Val> = Vec::from_array(
-            &env,
-            [
-                board_id.into_val(&env),
-                user.into_val(&env),
-                caller.into_val(&env),
-            ],
-        );
-        env.invoke_contract::<()>(&permissions, &Symbol::new(&env, "revoke_invite"), args);
-    }
+        pub fn set_reply_hidden(
+            env: Env,
+            board_id: u64,
+            thread_id: u64,
+            reply_id: u64,
+            hidden: bool,
+        ) {
+            if Self::get_reply_author(env.clone(), board_id, thread_id, reply_id).is_some() {
+                env.storage()
+                    .instance()
+                    .set(&(thread_id, reply_id), &hidden);
+            }
+        }
 
-    /// Directly invite a user as Member (moderator+)
-    pub fn invite_member(env: Env, board_id: u64, user: Address, caller: Address) {
-        Self::invite_with_role(env, board_id, user, Role::Member, caller);
-    }
+        pub fn set_reply_log(env: Env, user: Address, replies: Vec<(u64, u64)>) {
+            env.storage()
+                .instance()
+                .set(&(Symbol::new(&env, "log"), user), &replies);
+        }
 
-    /// Directly invite a user as Moderator (admin+)
-    pub fn invite_moderator(env: Env, board_id: u64, user: Address, caller: Address) {
-        Self::invite_with_role(env, board_id, user, Role::Moderator, caller);
-    }
+        pub fn user_reply_log_len(env: Env, _board_id: u64, user: Address) -> u64 {
+            env.storage()
+                .instance()
+                .get::<_, Vec<(u64, u64)>>(&(Symbol::new(&env, "log"), user))
+                .map_or(0, |replies| replies.len() as u64)
+        }
 
-    /// Directly invite a user as Admin (owner only)
-    pub fn invite_admin(env: Env, board_id: u64, user: Address, caller: Address) {
-        Self::invite_with_role(env, board_id, user, Role::Admin, caller);
-    }
+        /// Known replies from the log window; missing ones are skipped
+        pub fn list_user_reply_log(
+            env: Env,
+            board_id: u64,
+            user: Address,
+            start: u64,
+            limit: u64,
+        ) -> Vec<ReplyMeta> {
+            let entries: Vec<(u64, u64)> = env
+                .storage()
+                .instance()
+                .get(&(Symbol::new(&env, "log"), user.clone()))
+                .unwrap_or(Vec::new(&env));
+            let mut replies = Vec::new(&env);
+            for (thread_id, id) in entries.iter().skip(start as usize).take(limit as usize) {
+                if Self::get_reply_author(env.clone(), board_id, thread_id, id).is_some() {
+                    replies.push_back(ReplyMeta {
+                        id,
+                        board_id,
+                        thread_id,
+                        parent_id: 0,
+                        depth: 0,
+                        creator: user.clone(),
+                        created_at: 0,
+                        updated_at: 0,
+                        is_hidden: Self::is_reply_hidden(env.clone(), thread_id, id),
+                        is_deleted: false,
+                        flag_count: 0,
+                    });
+                }
+            }
+            replies
+        }
 
-    /// Helper function to invite with a specific role
-    fn invite_with_role(env: Env, board_id: u64, user: Address, role: Role, caller: Address) {
-        caller.require_auth();
+        pub fn clear_flags(env: Env, _board_id: u64, thread_id: u64, reply_id: Option<u64>) {
+            env.storage()
+                .instance()
+                .set(&(Symbol::new(&env, "cleared"), thread_id, reply_id), &true);
+        }
 
-        let permissions: Address = env
-            .storage()
-            .instance()
-            .get(&AdminKey::Permissions)
-            .expect("Not initialized");
+        pub fn is_cleared(env: Env, thread_id: u64, reply_id: Option<u64>) -> bool {
+            env.storage()
+                .instance()
+                .has(&(Symbol::new(&env, "cleared"), thread_id, reply_id))
+        }
 
-        // The permissions contract handles authorization checks
-        let args: Vec<Val> = Vec::from_array(
-            &env,
-            [
-                board_id.into_val(&env),
-                user.into_val(&env),
-                role.into_val(&env),
-                caller.into_val(&env),
-            ],
-        );
-        env.invoke_contract::<()>(&permissions, &Symbol::new(&env, "invite_member"), args);
+        pub fn is_reply_hidden(env: Env, thread_id: u64, reply_id: u64) -> bool {
+            env.storage()
+                .instance()
+                .get(&(thread_id, reply_id))
+                .unwrap_or(false)
+        }
     }
 
-    /// Ban a user from a board (moderator+)
-    pub fn ban_user(
-        env: Env,
-        board_id: u64,
-        user: Address,
-        reason: String,
-        duration_hours: u64,
-        caller: Address,
-    ) {
-        caller.require_auth();
+    /// Minimal registry that resolves contract aliases and tracks site admins
+    #[contract]
+    pub struct MockRegistry;
 
-        let permissions: Address = env
-            .storage()
-            .instance()
-            .get(&AdminKey::Permissions)
-            .expect("Not initialized");
+    #[contractimpl]
+    impl MockRegistry {
+        pub fn set_contract(env: Env, alias: Symbol, address: Address) {
+            env.storage().instance().set(&alias, &address);
+        }
 
-        // Verify caller has moderator permissions
-        let caller_perms: PermissionSet = env.invoke_contract(
-            &permissions,
-            &Symbol::new(&env, "get_permissions"),
-            Vec::from_array(&env, [board_id.into_val(&env), caller.into_val(&env)]),
-        );
+        pub fn get_contract_by_alias(env: Env, alias: Symbol) -> Option<Address> {
+            env.storage().instance().get(&alias)
+        }
 
-        if !caller_perms.can_moderate {
-            panic!("Caller must be moderator or higher");
+        pub fn get_admins(env: Env) -> Vec<Address> {
+            env.storage()
+                .instance()
+                .get(&Symbol::new(&env, "admins"))
+                .unwrap_or(Vec::new(&env))
         }
 
-        // Calculate expiry (0 = permanent)
-        let expires_at: Option<u64> = if duration_hours > 0 {
-            Some(env.ledger().timestamp() + (duration_hours * 3600))
-        } else {
-            None
-        };
+        pub fn is_admin(env: Env, address: Address) -> bool {
+            Self::get_admins(env).contains(&address)
+        }
 
-        // Ban the user
-        // Permissions expects: (board_id, user, reason, duration_hours, caller)
-        let args: Vec<Val> = Vec::from_array(
-            &env,
-            [
-                board_id.into_val(&env),
-                user.into_val(&env),
-                reason.into_val(&env),
-                expires_at.into_val(&env),
-                caller.into_val(&env),
-            ],
-        );
-        env.invoke_contract::<()>(&permissions, &Symbol::new(&env, "ban_user"), args);
-    }
+        pub fn add_admin(env: Env, new_admin: Address, _caller: Address) {
+            let mut admins = Self::get_admins(env.clone());
+            admins.push_back(new_admin);
+            env.storage()
+                .instance()
+                .set(&Symbol::new(&env, "admins"), &admins);
+        }
 
-    /// Unban a user from a board (moderator+)
-    pub fn unban_user(env: Env, board_id: u64, user: Address, caller: Address) {
-        caller.require_auth();
+        pub fn upgrade_contract(
+            env: Env,
+            contract_id: Address,
+            new_wasm_hash: BytesN<32>,
+            _caller: Address,
+        ) {
+            env.storage().instance().set(&contract_id, &new_wasm_hash);
+        }
 
-        let permissions: Address = env
-            .storage()
-            .instance()
-            .get(&AdminKey::Permissions)
-            .expect("Not initialized");
+        pub fn get_contract_wasm_hash(env: Env, contract_id: Address) -> Option<BytesN<32>> {
+            env.storage().instance().get(&contract_id)
+        }
 
-        // Verify caller has moderator permissions
-        let caller_perms: PermissionSet = env.invoke_contract(
-            &permissions,
-            &Symbol::new(&env, "get_permissions"),
-            Vec::from_array(&env, [board_id.into_val(&env), caller.into_val(&env)]),
-        );
+        pub fn remove_admin(env: Env, admin_to_remove: Address, _caller: Address) {
+            let mut admins = Self::get_admins(env.clone());
+            if let Some(i) = admins.first_index_of(&admin_to_remove) {
+                admins.remove(i);
+            }
+            env.storage()
+                .instance()
+                .set(&Symbol::new(&env, "admins"), &admins);
+        }
+    }
 
-        if !caller_perms.can_moderate {
-            panic!("Caller must be moderator or higher");
+    /// Minimal board contract that stores flairs and known threads for board 0
+    #[contract]
+    pub struct MockBoard;
+
+    #[contractimpl]
+    impl MockBoard {
+        pub fn create_flair(
+            env: Env,
+            _board_id: u64,
+            name: String,
+            color: String,
+            bg_color: String,
+            required: bool,
+            mod_only: bool,
+            _caller: Address,
+        ) -> u32 {
+            let id: u32 = env.storage().instance().get(&0u32).unwrap_or(0);
+            let flair = FlairDef {
+                id,
+                name,
+                color,
+                bg_color,
+                required,
+                mod_only,
+                enabled: true,
+            };
+            env.storage().persistent().set(&id, &flair);
+            env.storage().instance().set(&0u32, &(id + 1));
+            id
         }
 
-        // Unban the user
-        let args: Vec<Val> = Vec::from_array(
-            &env,
-            [
-                board_id.into_val(&env),
-                user.into_val(&env),
-                caller.into_val(&env),
-            ],
-        );
-        env.invoke_contract::<()>(&permissions, &Symbol::new(&env, "unban_user"), args);
-    }
+        pub fn update_flair(
+            env: Env,
+            _board_id: u64,
+            flair_id: u32,
+            flair: FlairDef,
+            _caller: Address,
+        ) {
+            env.storage().persistent().set(&flair_id, &flair);
+        }
 
-    /// Hide a thread (moderator+)
-    pub fn hide_thread(env: Env, board_id: u64, thread_id: u64, caller: Address) {
-        caller.require_auth();
+        pub fn disable_flair(env: Env, _board_id: u64, flair_id: u32, _caller: Address) {
+            let mut flair: FlairDef = env.storage().persistent().get(&flair_id).unwrap();
+            flair.enabled = false;
+            env.storage().persistent().set(&flair_id, &flair);
+        }
 
-        let permissions: Address = env
-            .storage()
-            .instance()
-            .get(&AdminKey::Permissions)
-            .expect("Not initialized");
+        pub fn get_flair(env: Env, _board_id: u64, flair_id: u32) -> Option<FlairDef> {
+            env.storage().persistent().get(&flair_id)
+        }
+
+        pub fn add_thread(env: Env, thread_id: u64) {
+            env.storage()
+                .instance()
+                .set(&(Symbol::new(&env, "thread"), thread_id), &true);
+        }
 
-        // Verify caller has moderator permissions
-        let caller_perms: PermissionSet = env.invoke_contract(
-            &permissions,
-            &Symbol::new(&env, "get_permissions"),
-            Vec::from_array(&env, [board_id.into_val(&env), caller.into_val(&env)]),
-        );
+        pub fn get_thread_title_and_author(
+            env: Env,
+            _board_id: u64,
+            thread_id: u64,
+        ) -> Option<(String, Address)> {
+            env.storage()
+                .instance()
+                .has(&(Symbol::new(&env, "thread"), thread_id))
+                .then(|| (String::from_str(&env, ""), env.current_contract_address()))
+        }
 
-        if !caller_perms.can_moderate {
-            panic!("Caller must be moderator or higher");
+        /// Missing threads are ignored, as the real board contract does
+        pub fn set_thread_hidden(env: Env, board_id: u64, thread_id: u64, hidden: bool) {
+            if Self::get_thread_title_and_author(env.clone(), board_id, thread_id).is_some() {
+                env.storage()
+                    .instance()
+                    .set(&(Symbol::new(&env, "hidden"), thread_id), &hidden);
+            }
         }
 
-        // Get board contract (single contract for all boards)
-        let board_contract = Self::get_board_contract_address(&env);
+        pub fn is_thread_hidden(env: Env, thread_id: u64) -> bool {
+            env.storage()
+                .instance()
+                .get(&(Symbol::new(&env, "hidden"), thread_id))
+                .unwrap_or(false)
+        }
 
-        // Hide the thread (now requires board_id)
-        let args: Vec<Val> = Vec::from_array(
-            &env,
-            [
-                board_id.into_val(&env),
-                thread_id.into_val(&env),
-                true.into_val(&env),
-            ],
-        );
-        env.invoke_contract::<()>(
-            &board_contract,
-            &Symbol::new(&env, "set_thread_hidden"),
-            args,
-        );
-    }
+        pub fn set_thread_log(env: Env, user: Address, thread_ids: Vec<u64>) {
+            env.storage()
+                .instance()
+                .set(&(Symbol::new(&env, "log"), user), &thread_ids);
+        }
 
-    /// Unhide a thread (moderator+)
-    pub fn unhide_thread(env: Env, board_id: u64, thread_id: u64, caller: Address) {
-        caller.require_auth();
+        pub fn user_thread_log_len(env: Env, _board_id: u64, user: Address) -> u64 {
+            env.storage()
+                .instance()
+                .get::<_, Vec<u64>>(&(Symbol::new(&env, "log"), user))
+                .map_or(0, |ids| ids.len() as u64)
+        }
 
-        let permissions: Address = env
-            .storage()
-            .instance()
-            .get(&AdminKey::Permissions)
-            .expect("Not initialized");
+        /// Known threads from the log window; missing ones are skipped
+        pub fn list_user_thread_log(
+            env: Env,
+            board_id: u64,
+            user: Address,
+            start: u64,
+            limit: u64,
+        ) -> Vec<ThreadMeta> {
+            let ids: Vec<u64> = env
+                .storage()
+                .instance()
+                .get(&(Symbol::new(&env, "log"), user.clone()))
+                .unwrap_or(Vec::new(&env));
+            let mut threads = Vec::new(&env);
+            for id in ids.iter().skip(start as usize).take(limit as usize) {
+                if Self::get_thread_title_and_author(env.clone(), board_id, id).is_some() {
+                    threads.push_back(ThreadMeta {
+                        id,
+                        board_id,
+                        title: String::from_str(&env, ""),
+                        creator: user.clone(),
+                        created_at: 0,
+                        updated_at: 0,
+                        reply_count: 0,
+                        is_locked: false,
+                        is_pinned: false,
+                        is_hidden: Self::is_thread_hidden(env.clone(), id),
+                        is_deleted: false,
+                        flair_id: None,
+                    });
+                }
+            }
+            threads
+        }
 
-        // Verify caller has moderator permissions
-        let caller_perms: PermissionSet = env.invoke_contract(
-            &permissions,
-            &Symbol::new(&env, "get_permissions"),
-            Vec::from_array(&env, [board_id.into_val(&env), caller.into_val(&env)]),
-        );
+        pub fn archive_board(env: Env, board_id: u64, _caller: Address) {
+            env.storage()
+                .instance()
+                .set(&(Symbol::new(&env, "archived"), board_id), &true);
+        }
 
-        if !caller_perms.can_moderate {
-            panic!("Caller must be moderator or higher");
+        pub fn unarchive_board(env: Env, board_id: u64, _caller: Address) {
+            env.storage()
+                .instance()
+                .remove(&(Symbol::new(&env, "archived"), board_id));
         }
 
-        // Get board contract (single contract for all boards)
-        let board_contract = Self::get_board_contract_address(&env);
+        pub fn is_archived(env: Env, board_id: u64) -> bool {
+            env.storage()
+                .instance()
+                .has(&(Symbol::new(&env, "archived"), board_id))
+        }
 
-        // Unhide the thread (now requires board_id)
-        let args: Vec<Val> = Vec::from_array(
-            &env,
-            [
-                board_id.into_val(&env),
-                thread_id.into_val(&env),
-                false.into_val(&env),
-            ],
-        );
-        env.invoke_contract::<()>(
-            &board_contract,
-            &Symbol::new(&env, "set_thread_hidden"),
-            args,
-        );
-    }
+        pub fn get_config(env: Env, _board_id: u64) -> BoardConfig {
+            BoardConfig {
+                name: String::from_str(&env, "general"),
+                description: String::from_str(&env, ""),
+                is_private: Self::get_setting(&env, "private", 0) == Some(1),
+                is_readonly: Self::get_setting(&env, "readonly", 0) == Some(1),
+                max_reply_depth: 10,
+                reply_chunk_size: 6,
+            }
+        }
 
-    /// Hide a reply (moderator+)
-    pub fn hide_reply(env: Env, board_id: u64, thread_id: u64, reply_id: u64, caller: Address) {
-        caller.require_auth();
+        pub fn set_private(env: Env, board_id: u64, is_private: bool, _caller: Address) {
+            Self::put_setting(&env, "private", board_id, is_private as u64);
+        }
 
-        let permissions: Address = env
-            .storage()
-            .instance()
-            .get(&AdminKey::Permissions)
-            .expect("Not initialized");
-        let content: Address = env
-            .storage()
-            .instance()
-            .get(&AdminKey::Content)
-            .expect("Not initialized");
+        pub fn set_readonly(env: Env, board_id: u64, is_readonly: bool, _caller: Address) {
+            Self::put_setting(&env, "readonly", board_id, is_readonly as u64);
+        }
 
-        // Verify caller has moderator permissions
-        let caller_perms: PermissionSet = env.invoke_contract(
-            &permissions,
-            &Symbol::new(&env, "get_permissions"),
-            Vec::from_array(&env, [board_id.into_val(&env), caller.into_val(&env)]),
-        );
+        pub fn get_default_sort(env: Env, board_id: u64) -> ThreadSort {
+            env.storage()
+                .instance()
+                .get(&(Symbol::new(&env, "sort"), board_id))
+                .unwrap_or(ThreadSort::Hot)
+        }
 
-        if !caller_perms.can_moderate {
-            panic!("Caller must be moderator or higher");
+        pub fn set_default_sort(env: Env, board_id: u64, sort: ThreadSort, _caller: Address) {
+            env.storage()
+                .instance()
+                .set(&(Symbol::new(&env, "sort"), board_id), &sort);
         }
 
-        // Hide the reply
-        let args: Vec<Val> = Vec::from_array(
-            &env,
-            [
-                board_id.into_val(&env),
-                thread_id.into_val(&env),
-                reply_id.into_val(&env),
-                true.into_val(&env),
-            ],
-        );
-        env.invoke_contract::<()>(&content, &Symbol::new(&env, "set_reply_hidden"), args);
-    }
+        pub fn get_controversial_min_votes(env: Env, board_id: u64) -> u32 {
+            Self::get_setting(&env, "min_votes", board_id).unwrap_or(5) as u32
+        }
 
-    /// Unhide a reply (moderator+)
-    pub fn unhide_reply(env: Env, board_id: u64, thread_id: u64, reply_id: u64, caller: Address) {
-        caller.require_auth();
+        pub fn set_controversial_min_votes(
+            env: Env,
+            board_id: u64,
+            min_votes: u32,
+            _caller: Address,
+        ) {
+            Self::put_setting(&env, "min_votes", board_id, min_votes as u64);
+        }
 
-        let permissions: Address = env
-            .storage()
-            .instance()
-            .get(&AdminKey::Permissions)
-            .expect("Not initialized");
-        let content: Address = env
-            .storage()
-            .instance()
-            .get(&AdminKey::Content)
-            .expect("Not initialized");
+        pub fn get_max_reply_bytes(env: Env, board_id: u64) -> u32 {
+            Self::get_setting(&env, "max_reply", board_id).unwrap_or(8_192) as u32
+        }
 
-        // Verify caller has moderator permissions
-        let caller_perms: PermissionSet = env.invoke_contract(
-            &permissions,
-            &Symbol::new(&env, "get_permissions"),
-            Vec::from_array(&env, [board_id.into_val(&env), caller.into_val(&env)]),
-        );
+        pub fn set_post_size_limits(
+            env: Env,
+            board_id: u64,
+            max_thread_body_bytes: u32,
+            max_reply_bytes: u32,
+            _caller: Address,
+        ) {
+            Self::put_setting(&env, "max_body", board_id, max_thread_body_bytes as u64);
+            Self::put_setting(&env, "max_reply", board_id, max_reply_bytes as u64);
+        }
 
-        if !caller_perms.can_moderate {
-            panic!("Caller must be moderator or higher");
+        pub fn get_render_budget(env: Env, board_id: u64) -> u32 {
+            Self::get_setting(&env, "budget", board_id).unwrap_or(12_288) as u32
         }
 
-        // Unhide the reply
-        let args: Vec<Val> = Vec::from_array(
-            &env,
-            [
-                board_id.into_val(&env),
-                thread_id.into_val(&env),
-                reply_id.into_val(&env),
-                false.into_val(&env),
-            ],
-        );
-        env.invoke_contract::<()>(&content, &Symbol::new(&env, "set_reply_hidden"), args);
-    }
+        pub fn set_render_budget(env: Env, board_id: u64, budget: u32, _caller: Address) {
+            Self::put_setting(&env, "budget", board_id, budget as u64);
+        }
 
-    /// Clear flags on content (moderator+)
-    pub fn clear_flags(
-        env: Env,
-        board_id: u64,
-        thread_id: u64,
-        reply_id: Option<u64>,
-        caller: Address,
-    ) {
-        caller.require_auth();
+        pub fn get_collapse_score(env: Env, board_id: u64) -> i32 {
+            Self::get_setting(&env, "collapse_score", board_id).map_or(-3, |v| v as i64 as i32)
+        }
 
-        let permissions: Address = env
-            .storage()
-            .instance()
-            .get(&AdminKey::Permissions)
-            .expect("Not initialized");
-        let content: Address = env
-            .storage()
-            .instance()
-            .get(&AdminKey::Content)
-            .expect("Not initialized");
+        pub fn set_collapse_score(env: Env, board_id: u64, score: i32, _caller: Address) {
+            Self::put_setting(&env, "collapse_score", board_id, score as i64 as u64);
+        }
 
-        // Verify caller has moderator permissions
-        let caller_perms: PermissionSet = env.invoke_contract(
-            &permissions,
-            &Symbol::new(&env, "get_permissions"),
-            Vec::from_array(&env, [board_id.into_val(&env), caller.into_val(&env)]),
-        );
+        pub fn get_collapse_depth(env: Env, board_id: u64) -> u32 {
+            Self::get_setting(&env, "collapse", board_id).unwrap_or(4) as u32
+        }
 
-        if !caller_perms.can_moderate {
-            panic!("Caller must be moderator or higher");
+        pub fn set_collapse_depth(env: Env, board_id: u64, depth: u32, _caller: Address) {
+            Self::put_setting(&env, "collapse", board_id, depth as u64);
         }
 
-        // Clear flags
-        let args: Vec<Val> = Vec::from_array(
-            &env,
-            [
-                board_id.into_val(&env),
-                thread_id.into_val(&env),
-                reply_id.into_val(&env),
-            ],
-        );
-        env.invoke_contract::<()>(&content, &Symbol::new(&env, "clear_flags"), args);
-    }
+        pub fn get_collapse_children_over(env: Env, board_id: u64) -> u32 {
+            Self::get_setting(&env, "children_over", board_id).unwrap_or(0) as u32
+        }
 
-    /// Update flag threshold for a board (admin+)
-    /// Accepts threshold as String since HTML forms submit strings
-    pub fn set_flag_threshold(env: Env, board_id: u64, threshold: String, caller: Address) {
-        caller.require_auth();
+        pub fn set_collapse_children_over(env: Env, board_id: u64, count: u32, _caller: Address) {
+            Self::put_setting(&env, "children_over", board_id, count as u64);
+        }
 
-        // Parse string to u32
-        let threshold_u32 = string_to_u32(&env, &threshold).expect("Invalid number");
+        pub fn get_wiki_edit_role(env: Env, board_id: u64) -> Role {
+            env.storage()
+                .instance()
+                .get(&(Symbol::new(&env, "wiki_role"), board_id))
+                .unwrap_or(Role::Member)
+        }
 
-        let permissions: Address = env
-            .storage()
-            .instance()
-            .get(&AdminKey::Permissions)
-            .expect("Not initialized");
+        pub fn set_wiki_edit_role(env: Env, board_id: u64, role: Role, _caller: Address) {
+            env.storage()
+                .instance()
+                .set(&(Symbol::new(&env, "wiki_role"), board_id), &role);
+        }
 
-        // Verify caller has admin permissions
-        let caller_perms: PermissionSet = env.invoke_contract(
-            &permissions,
-            &Symbol::new(&env, "get_permissions"),
-            Vec::from_array(&env, [board_id.into_val(&env), caller.into_val(&env)]),
-        );
+        pub fn get_locale(env: Env, board_id: u64) -> Option<Symbol> {
+            env.storage()
+                .instance()
+                .get(&(Symbol::new(&env, "locale"), board_id))
+        }
 
-        if !caller_perms.can_admin {
-            panic!("Caller must be admin or owner");
+        pub fn set_locale(env: Env, board_id: u64, locale: Option<Symbol>, _caller: Address) {
+            let key = (Symbol::new(&env, "locale"), board_id);
+            match locale {
+                Some(locale) => env.storage().instance().set(&key, &locale),
+                None => env.storage().instance().remove(&key),
+            }
         }
 
-        // Set the threshold
-        let args: Vec<Val> = Vec::from_array(
-            &env,
-            [
-                board_id.into_val(&env),
-                threshold_u32.into_val(&env),
-                caller.into_val(&env),
-            ],
-        );
-        env.invoke_contract::<()>(&permissions, &Symbol::new(&env, "set_flag_threshold"), args);
-    }
+        pub fn get_image_policy(env: Env, board_id: u64) -> ImagePolicy {
+            env.storage()
+                .instance()
+                .get(&(Symbol::new(&env, "images"), board_id))
+                .unwrap_or(ImagePolicy {
+                    allow_images: true,
+                    allowed_hosts: Vec::new(&env),
+                })
+        }
 
-    /// Update reply chunk size for waterfall loading (admin+)
-    /// Accepts chunk_size as String since HTML forms submit strings
-    pub fn set_chunk_size(env: Env, board_id: u64, chunk_size: String, caller: Address) {
-        caller.require_auth();
+        pub fn set_image_policy(
+            env: Env,
+            board_id: u64,
+            allow_images: bool,
+            allowed_hosts: Vec<String>,
+            _caller: Address,
+        ) {
+            env.storage().instance().set(
+                &(Symbol::new(&env, "images"), board_id),
+                &ImagePolicy {
+                    allow_images,
+                    allowed_hosts,
+                },
+            );
+        }
 
-        // Parse string to u32
-        let chunk_size_u32 = string_to_u32(&env, &chunk_size).expect("Invalid number");
+        pub fn list_blocked_terms(env: Env, board_id: u64) -> Vec<String> {
+            env.storage()
+                .instance()
+                .get(&(Symbol::new(&env, "blocked"), board_id))
+                .unwrap_or(Vec::new(&env))
+        }
 
-        let permissions: Address = env
-            .storage()
-            .instance()
-            .get(&AdminKey::Permissions)
-            .expect("Not initialized");
+        pub fn add_blocked_term(env: Env, board_id: u64, term: String, _caller: Address) {
+            let mut terms = Self::list_blocked_terms(env.clone(), board_id);
+            terms.push_back(term);
+            env.storage()
+                .instance()
+                .set(&(Symbol::new(&env, "blocked"), board_id), &terms);
+        }
+
+        pub fn remove_blocked_term(env: Env, board_id: u64, term: String, _caller: Address) {
+            let mut terms = Self::list_blocked_terms(env.clone(), board_id);
+            if let Some(index) = terms.first_index_of(&term) {
+                terms.remove(index);
+            }
+            env.storage()
+                .instance()
+                .set(&(Symbol::new(&env, "blocked"), board_id), &terms);
+        }
 
-        // Verify caller has admin permissions
-        let caller_perms: PermissionSet = env.invoke_contract(
-            &permissions,
-            &Symbol::new(&env, "get_permissions"),
-            Vec::from_array(&env, [board_id.into_val(&env), caller.into_val(&env)]),
-        );
+        pub fn get_reply_cooldown(env: Env, board_id: u64) -> u64 {
+            Self::get_setting(&env, "cooldown", board_id).unwrap_or(0)
+        }
 
-        if !caller_perms.can_admin {
-            panic!("Caller must be admin or owner");
+        pub fn set_reply_cooldown(env: Env, board_id: u64, seconds: u64, _caller: Address) {
+            Self::put_setting(&env, "cooldown", board_id, seconds);
         }
 
-        // Validate chunk size (1-20 is reasonable range)
-        if !(1..=20).contains(&chunk_size_u32) {
-            panic!("Chunk size must be between 1 and 20");
+        pub fn is_modlog_public(env: Env, board_id: u64) -> bool {
+            Self::get_setting(&env, "modlog", board_id) == Some(1)
         }
 
-        // Get board contract (single contract for all boards)
-        let board_contract = Self::get_board_contract_address(&env);
+        pub fn set_public_modlog(env: Env, board_id: u64, public_modlog: bool, _caller: Address) {
+            Self::put_setting(&env, "modlog", board_id, public_modlog as u64);
+        }
 
-        // Set the chunk size (now requires board_id)
-        let args: Vec<Val> = Vec::from_array(
-            &env,
-            [
-                board_id.into_val(&env),
-                chunk_size_u32.into_val(&env),
-                caller.into_val(&env),
-            ],
-        );
-        env.invoke_contract::<()>(&board_contract, &Symbol::new(&env, "set_chunk_size"), args);
-    }
+        pub fn get_min_karma_to_post(env: Env, board_id: u64) -> i64 {
+            Self::get_setting(&env, "karma_post", board_id).unwrap_or(0) as i64
+        }
 
-    /// Update maximum reply depth for a board (admin+)
-    /// Accepts max_depth as String since HTML forms submit strings
-    pub fn set_max_reply_depth(env: Env, board_id: u64, max_depth: String, caller: Address) {
-        caller.require_auth();
+        pub fn set_karma_thresholds(
+            env: Env,
+            board_id: u64,
+            min_karma_to_post: i64,
+            min_karma_to_reply: i64,
+            _caller: Address,
+        ) {
+            Self::put_setting(&env, "karma_post", board_id, min_karma_to_post as u64);
+            Self::put_setting(&env, "karma_reply", board_id, min_karma_to_reply as u64);
+        }
 
-        // Parse string to u32
-        let max_depth_u32 = string_to_u32(&env, &max_depth).expect("Invalid number");
+        pub fn is_karma_exempt(env: Env, board_id: u64, user: Address) -> bool {
+            env.storage()
+                .instance()
+                .get(&(Symbol::new(&env, "exempt"), board_id, user))
+                .unwrap_or(false)
+        }
 
-        let permissions: Address = env
-            .storage()
-            .instance()
-            .get(&AdminKey::Permissions)
-            .expect("Not initialized");
+        pub fn set_karma_exempt(
+            env: Env,
+            board_id: u64,
+            user: Address,
+            exempt: bool,
+            _caller: Address,
+        ) {
+            env.storage()
+                .instance()
+                .set(&(Symbol::new(&env, "exempt"), board_id, user), &exempt);
+        }
 
-        // Verify caller has admin permissions
-        let caller_perms: PermissionSet = env.invoke_contract(
-            &permissions,
-            &Symbol::new(&env, "get_permissions"),
-            Vec::from_array(
-                &env,
-                [board_id.into_val(&env), caller.clone().into_val(&env)],
-            ),
-        );
+        pub fn get_chunk_size(env: Env, board_id: u64) -> u32 {
+            Self::get_setting(&env, "chunk", board_id).unwrap_or(6) as u32
+        }
 
-        if !caller_perms.can_admin {
-            panic!("Caller must be admin or owner");
+        pub fn set_chunk_size(env: Env, board_id: u64, chunk_size: u32, _caller: Address) {
+            Self::put_setting(&env, "chunk", board_id, chunk_size as u64);
         }
 
-        // Validate max depth (1-20 is reasonable range)
-        if !(1..=20).contains(&max_depth_u32) {
-            panic!("Max reply depth must be between 1 and 20");
+        pub fn get_max_reply_depth(env: Env, board_id: u64) -> u32 {
+            Self::get_setting(&env, "depth", board_id).unwrap_or(10) as u32
         }
 
-        // Get board contract (single contract for all boards)
-        let board_contract = Self::get_board_contract_address(&env);
+        pub fn set_max_reply_depth(env: Env, board_id: u64, max_depth: u32, _caller: Address) {
+            Self::put_setting(&env, "depth", board_id, max_depth as u64);
+        }
 
-        // Set the max reply depth (now requires board_id)
-        let args: Vec<Val> = Vec::from_array(
-            &env,
-            [
-                board_id.into_val(&env),
-                max_depth_u32.into_val(&env),
-                caller.into_val(&env),
-            ],
-        );
-        env.invoke_contract::<()>(
-            &board_contract,
-            &Symbol::new(&env, "set_max_reply_depth"),
-            args,
-        );
-    }
+        pub fn get_edit_window(env: Env, board_id: u64) -> u64 {
+            Self::get_setting(&env, "edit", board_id).unwrap_or(0)
+        }
 
-    /// Update edit window for a board (admin+)
-    /// Accepts edit_hours as String since HTML forms submit strings
-    /// Input is in hours, stored as seconds
-    pub fn set_edit_window(env: Env, board_id: u64, edit_hours: String, caller: Address) {
-        caller.require_auth();
+        pub fn set_edit_window(env: Env, board_id: u64, seconds: u64, _caller: Address) {
+            Self::put_setting(&env, "edit", board_id, seconds);
+        }
 
-        // Parse string to u32 (hours)
-        let hours_u32 = string_to_u32(&env, &edit_hours).expect("Invalid number");
+        /// Number of settings writes, to check which setters were called
+        pub fn setting_writes(env: Env) -> u32 {
+            env.storage()
+                .instance()
+                .get(&Symbol::new(&env, "writes"))
+                .unwrap_or(0)
+        }
 
-        let permissions: Address = env
-            .storage()
-            .instance()
-            .get(&AdminKey::Permissions)
-            .expect("Not initialized");
+        pub fn set_theme_by_name(env: Env, board_id: u64, name: String, _caller: Address) {
+            let key = (Symbol::new(&env, "theme"), board_id);
+            if name.is_empty() {
+                env.storage().instance().remove(&key);
+            } else {
+                env.storage().instance().set(&key, &name);
+            }
+        }
 
-        // Verify caller has admin permissions
-        let caller_perms: PermissionSet = env.invoke_contract(
-            &permissions,
-            &Symbol::new(&env, "get_permissions"),
-            Vec::from_array(
-                &env,
-                [board_id.into_val(&env), caller.clone().into_val(&env)],
-            ),
-        );
+        pub fn get_theme_name(env: Env, board_id: u64) -> Option<String> {
+            env.storage()
+                .instance()
+                .get(&(Symbol::new(&env, "theme"), board_id))
+        }
 
-        if !caller_perms.can_admin {
-            panic!("Caller must be admin or owner");
+        pub fn set_custom_css(env: Env, board_id: u64, css: String, _caller: Address) {
+            let key = (Symbol::new(&env, "css"), board_id);
+            if css.is_empty() {
+                env.storage().instance().remove(&key);
+            } else {
+                env.storage().instance().set(&key, &css);
+            }
         }
 
-        // Convert hours to seconds (0 stays 0 for "no limit")
-        let seconds: u64 = (hours_u32 as u64) * 3600;
+        pub fn get_custom_css(env: Env, board_id: u64) -> Option<String> {
+            env.storage()
+                .instance()
+                .get(&(Symbol::new(&env, "css"), board_id))
+        }
 
-        // Get board contract (single contract for all boards)
-        let board_contract = Self::get_board_contract_address(&env);
+        fn get_setting(env: &Env, name: &str, board_id: u64) -> Option<u64> {
+            env.storage()
+                .instance()
+                .get(&(Symbol::new(env, name), board_id))
+        }
 
-        // Set the edit window (now requires board_id)
-        let args: Vec<Val> = Vec::from_array(
-            &env,
-            [
-                board_id.into_val(&env),
-                seconds.into_val(&env),
-                caller.into_val(&env),
-            ],
-        );
-        env.invoke_contract::<()>(&board_contract, &Symbol::new(&env, "set_edit_window"), args);
+        fn put_setting(env: &Env, name: &str, board_id: u64, value: u64) {
+            env.storage()
+                .instance()
+                .set(&(Symbol::new(env, name), board_id), &value);
+            let writes = Self::setting_writes(env.clone());
+            env.storage()
+                .instance()
+                .set(&Symbol::new(env, "writes"), &(writes + 1));
+        }
     }
 
-    /// Rename a board (admin+)
-    /// The old name becomes an alias that continues to resolve
-    pub fn rename_board(env: Env, board_id: u64, new_name: String, caller: Address) {
-        caller.require_auth();
+    /// Minimal voting contract that stores a board's voting config
+    #[contract]
+    pub struct MockVoting;
 
-        let permissions: Address = env
-            .storage()
-            .instance()
-            .get(&AdminKey::Permissions)
-            .expect("Not initialized");
+    #[contractimpl]
+    impl MockVoting {
+        pub fn set_voting_config(env: Env, board_id: u64, config: VotingConfig, _caller: Address) {
+            env.storage().persistent().set(&board_id, &config);
+        }
 
-        // Verify caller has admin permissions
-        let caller_perms: PermissionSet = env.invoke_contract(
-            &permissions,
-            &Symbol::new(&env, "get_permissions"),
-            Vec::from_array(
-                &env,
-                [board_id.into_val(&env), caller.clone().into_val(&env)],
-            ),
-        );
+        pub fn get_voting_config(env: Env, board_id: u64) -> Option<VotingConfig> {
+            env.storage().persistent().get(&board_id)
+        }
 
-        if !caller_perms.can_admin {
-            panic!("Caller must be admin or owner");
+        pub fn set_vote_weights(
+            env: Env,
+            board_id: u64,
+            member_weight: u32,
+            moderator_weight: u32,
+            _caller: Address,
+        ) {
+            env.storage().persistent().set(
+                &(Symbol::new(&env, "weights"), board_id),
+                &(member_weight, moderator_weight),
+            );
         }
 
-        // Call registry's rename_board function
-        let registry: Address = env
-            .storage()
-            .instance()
-            .get(&AdminKey::Registry)
-            .expect("Not initialized");
+        pub fn get_vote_weights(env: Env, board_id: u64) -> (u32, u32) {
+            env.storage()
+                .persistent()
+                .get(&(Symbol::new(&env, "weights"), board_id))
+                .unwrap_or((1, 1))
+        }
 
-        let args: Vec<Val> = Vec::from_array(
-            &env,
-            [
-                board_id.into_val(&env),
-                new_name.into_val(&env),
-                caller.into_val(&env),
-            ],
-        );
-        env.invoke_contract::<()>(&registry, &Symbol::new(&env, "rename_board"), args);
-    }
+        pub fn set_reaction_allowlist(
+            env: Env,
+            board_id: u64,
+            emojis: Vec<Symbol>,
+            _caller: Address,
+        ) {
+            env.storage()
+                .persistent()
+                .set(&(Symbol::new(&env, "reactions"), board_id), &emojis);
+        }
 
-    /// List a board publicly (admin+)
-    /// Makes the board appear on the home page
-    pub fn list_board(env: Env, board_id: u64, caller: Address) {
-        caller.require_auth();
-        Self::set_board_listed(env, board_id, true, caller);
+        pub fn get_reaction_allowlist(env: Env, board_id: u64) -> Vec<Symbol> {
+            env.storage()
+                .persistent()
+                .get(&(Symbol::new(&env, "reactions"), board_id))
+                .unwrap_or(Vec::new(&env))
+        }
     }
 
-    /// Unlist a board (admin+)
-    /// Hides the board from the home page but keeps it accessible via direct link
-    pub fn unlist_board(env: Env, board_id: u64, caller: Address) {
-        caller.require_auth();
-        Self::set_board_listed(env, board_id, false, caller);
+    /// Register a voting mock under the "voting" alias of the admin's registry
+    fn register_mock_voting<'a>(env: &'a Env, client: &BoardsAdminClient) -> MockVotingClient<'a> {
+        let voting_id = env.register(MockVoting, ());
+        MockRegistryClient::new(env, &client.get_registry())
+            .set_contract(&Symbol::new(env, "voting"), &voting_id);
+        MockVotingClient::new(env, &voting_id)
     }
 
-    /// Helper to set board listed status
-    fn set_board_listed(env: Env, board_id: u64, is_listed: bool, caller: Address) {
-        // Get board contract (single contract for all boards)
-        let board_contract = Self::get_board_contract_address(&env);
+    /// Set up admin contract backed by permissions, registry, and board mocks,
+    /// with one user per role on board 0
+    fn setup_roles(
+        env: &Env,
+    ) -> (
+        BoardsAdminClient,
+        MockPermissionsClient,
+        MockBoardClient,
+        Address,
+        Address,
+        Address,
+        Address,
+    ) {
+        env.mock_all_auths();
 
-        // Call board contract's set_board_listed function (now requires board_id)
-        // (The board contract handles permission checking internally)
-        let args: Vec<Val> = Vec::from_array(
-            &env,
-            [
-                board_id.into_val(&env),
-                is_listed.into_val(&env),
-                caller.into_val(&env),
-            ],
-        );
-        env.invoke_contract::<()>(
-            &board_contract,
-            &Symbol::new(&env, "set_board_listed"),
-            args,
-        );
-    }
+        let perms_id = env.register(MockPermissions, ());
+        let perms = MockPermissionsClient::new(env, &perms_id);
 
-    /// Make a board public (admin+)
-    pub fn make_public(env: Env, board_id: u64, caller: Address) {
-        caller.require_auth();
-        Self::set_board_private(env, board_id, false, caller);
-    }
+        let board_id = env.register(MockBoard, ());
+        let board = MockBoardClient::new(env, &board_id);
 
-    /// Make a board private (admin+)
-    pub fn make_private(env: Env, board_id: u64, caller: Address) {
-        caller.require_auth();
-        Self::set_board_private(env, board_id, true, caller);
+        let registry = env.register(MockRegistry, ());
+        MockRegistryClient::new(env, &registry).set_contract(&Symbol::new(env, "board"), &board_id);
+
+        let contract_id = env.register(BoardsAdmin, ());
+        let client = BoardsAdminClient::new(env, &contract_id);
+        let content = Address::generate(env);
+        let theme = Address::generate(env);
+        let config = Address::generate(env);
+        client.init(&registry, &perms_id, &content, &theme, &config);
+
+        let owner = Address::generate(env);
+        let admin = Address::generate(env);
+        let moderator = Address::generate(env);
+        let member = Address::generate(env);
+        perms.set_role(&0, &owner, &Role::Owner, &owner);
+        perms.set_role(&0, &admin, &Role::Admin, &owner);
+        perms.set_role(&0, &moderator, &Role::Moderator, &owner);
+        perms.set_role(&0, &member, &Role::Member, &owner);
+
+        (client, perms, board, owner, admin, moderator, member)
     }
 
-    /// Helper to set board private status
-    fn set_board_private(env: Env, board_id: u64, is_private: bool, caller: Address) {
-        // Get board contract (single contract for all boards)
-        let board_contract = Self::get_board_contract_address(&env);
+    #[test]
+    fn test_remove_roles_with_sufficient_permission() {
+        let env = Env::default();
+        let (client, perms, _, owner, admin, moderator, member) = setup_roles(&env);
 
-        // Call board contract's set_private function (now requires board_id)
-        // (The board contract handles permission checking internally)
-        let args: Vec<Val> = Vec::from_array(
-            &env,
-            [
-                board_id.into_val(&env),
-                is_private.into_val(&env),
-                caller.into_val(&env),
-            ],
-        );
-        env.invoke_contract::<()>(&board_contract, &Symbol::new(&env, "set_private"), args);
+        // Moderator can remove a member
+        client.remove_member(&0, &member, &moderator);
+        assert!(perms.get_role(&0, &member) == Role::Guest);
+
+        // Admin can demote a moderator
+        client.remove_moderator(&0, &moderator, &admin);
+        assert!(perms.get_role(&0, &moderator) == Role::Member);
+
+        // Owner can demote an admin
+        client.remove_admin(&0, &admin, &owner);
+        assert!(perms.get_role(&0, &admin) == Role::Member);
     }
 
-    /// Enable posting on a board (admin+)
-    pub fn enable_posting(env: Env, board_id: u64, caller: Address) {
-        caller.require_auth();
-        Self::set_board_readonly(env, board_id, false, caller);
+    #[test]
+    #[should_panic(expected = "Only owner can remove admins")]
+    fn test_admin_cannot_remove_admin() {
+        let env = Env::default();
+        let (client, perms, _, owner, admin, _, _) = setup_roles(&env);
+        let other_admin = Address::generate(&env);
+        perms.set_role(&0, &other_admin, &Role::Admin, &owner);
+
+        client.remove_admin(&0, &other_admin, &admin);
     }
 
-    /// Make a board read-only (admin+)
-    pub fn make_readonly(env: Env, board_id: u64, caller: Address) {
-        caller.require_auth();
-        Self::set_board_readonly(env, board_id, true, caller);
+    #[test]
+    #[should_panic(expected = "Caller must be admin or owner")]
+    fn test_moderator_cannot_remove_moderator() {
+        let env = Env::default();
+        let (client, perms, _, owner, _, moderator, _) = setup_roles(&env);
+        let other_mod = Address::generate(&env);
+        perms.set_role(&0, &other_mod, &Role::Moderator, &owner);
+
+        client.remove_moderator(&0, &other_mod, &moderator);
     }
 
-    /// Helper to set board readonly status
-    fn set_board_readonly(env: Env, board_id: u64, is_readonly: bool, caller: Address) {
-        let permissions: Address = env
-            .storage()
-            .instance()
-            .get(&AdminKey::Permissions)
-            .expect("Not initialized");
+    #[test]
+    #[should_panic(expected = "Caller must be moderator or higher")]
+    fn test_member_cannot_remove_member() {
+        let env = Env::default();
+        let (client, perms, _, owner, _, _, member) = setup_roles(&env);
+        let other_member = Address::generate(&env);
+        perms.set_role(&0, &other_member, &Role::Member, &owner);
 
-        // Verify caller has admin permissions
-        let caller_perms: PermissionSet = env.invoke_contract(
-            &permissions,
-            &Symbol::new(&env, "get_permissions"),
-            Vec::from_array(
-                &env,
-                [board_id.into_val(&env), caller.clone().into_val(&env)],
-            ),
-        );
+        client.remove_member(&0, &other_member, &member);
+    }
 
-        if !caller_perms.can_admin {
-            panic!("Caller must be admin or owner");
-        }
+    #[test]
+    #[should_panic(expected = "User does not have the role being removed")]
+    fn test_remove_member_cannot_target_admin() {
+        let env = Env::default();
+        let (client, _, _, _, admin, moderator, _) = setup_roles(&env);
 
-        // Get board contract (single contract for all boards)
-        let board_contract = Self::get_board_contract_address(&env);
+        // A moderator must not be able to strip an admin via remove_member
+        client.remove_member(&0, &admin, &moderator);
+    }
 
-        // Call board contract's set_readonly function (now requires board_id)
-        let args: Vec<Val> = Vec::from_array(
-            &env,
-            [
-                board_id.into_val(&env),
-                is_readonly.into_val(&env),
-                caller.into_val(&env),
-            ],
+    #[test]
+    fn test_flair_round_trip_from_form_strings() {
+        let env = Env::default();
+        let (client, _, board, _, admin, _, _) = setup_roles(&env);
+        let s = |v: &str| String::from_str(&env, v);
+
+        // Create: unchecked checkbox arrives as "false", checked as "true"
+        client.create_flair(
+            &0,
+            &s("Question"),
+            &Some(s("#000000")),
+            &None,
+            &Some(s("true")),
+            &Some(s("false")),
+            &admin,
         );
-        env.invoke_contract::<()>(&board_contract, &Symbol::new(&env, "set_readonly"), args);
+        let flair = board.get_flair(&0, &0).unwrap();
+        assert_eq!(flair.name, s("Question"));
+        assert_eq!(flair.color, s("#000000"));
+        assert_eq!(flair.bg_color, s("#808080"));
+        assert!(flair.required);
+        assert!(!flair.mod_only);
+        assert!(flair.enabled);
+
+        // Update: flair id arrives as a string, missing enabled defaults to true
+        client.update_flair(
+            &0,
+            &s("0"),
+            &s("Answered"),
+            &Some(s("#ffffff")),
+            &Some(s("#00aa00")),
+            &Some(s("false")),
+            &Some(s("true")),
+            &None,
+            &admin,
+        );
+        let flair = board.get_flair(&0, &0).unwrap();
+        assert_eq!(flair.id, 0);
+        assert_eq!(flair.name, s("Answered"));
+        assert_eq!(flair.bg_color, s("#00aa00"));
+        assert!(!flair.required);
+        assert!(flair.mod_only);
+        assert!(flair.enabled);
+
+        // Disable
+        client.disable_flair(&0, &s("0"), &admin);
+        assert!(!board.get_flair(&0, &0).unwrap().enabled);
     }
 
-    /// Pin a thread (moderator+)
-    pub fn pin_thread(env: Env, board_id: u64, thread_id: u64, caller: Address) {
-        caller.require_auth();
-
-        let permissions: Address = env
-            .storage()
-            .instance()
-            .get(&AdminKey::Permissions)
-            .expect("Not initialized");
-
-        // Verify caller has moderator permissions
-        let caller_perms: PermissionSet = env.invoke_contract(
-            &permissions,
-            &Symbol::new(&env, "get_permissions"),
-            Vec::from_array(&env, [board_id.into_val(&env), caller.into_val(&env)]),
+    #[test]
+    #[should_panic(expected = "Flair name must be 1-32 characters")]
+    fn test_create_flair_rejects_empty_name() {
+        let env = Env::default();
+        let (client, _, _, _, admin, _, _) = setup_roles(&env);
+
+        client.create_flair(
+            &0,
+            &String::from_str(&env, ""),
+            &None,
+            &None,
+            &None,
+            &None,
+            &admin,
         );
+    }
 
-        if !caller_perms.can_moderate {
-            panic!("Caller must be moderator or higher");
-        }
+    #[test]
+    #[should_panic(expected = "Flair colors must be hex (#rgb or #rrggbb)")]
+    fn test_create_flair_rejects_style_injection() {
+        let env = Env::default();
+        let (client, _, _, _, admin, _, _) = setup_roles(&env);
+        let s = |v: &str| String::from_str(&env, v);
 
-        // Get board contract (single contract for all boards)
-        let board_contract = Self::get_board_contract_address(&env);
+        let color = s("#fff;background-image:url(https://evil)");
+        client.create_flair(&0, &s("Spoiler"), &Some(color), &None, &None, &None, &admin);
+    }
 
-        // Pin the thread (now requires board_id)
-        let args: Vec<Val> = Vec::from_array(
-            &env,
-            [
-                board_id.into_val(&env),
-                thread_id.into_val(&env),
-                true.into_val(&env),
-            ],
-        );
-        env.invoke_contract::<()>(
-            &board_contract,
-            &Symbol::new(&env, "set_thread_pinned"),
-            args,
+    #[test]
+    fn test_flair_edit_form_escapes_stored_values() {
+        let env = Env::default();
+        let (client, _, board, _, admin, _, _) = setup_roles(&env);
+        let s = |v: &str| String::from_str(&env, v);
+
+        // Flairs stored before validation may hold arbitrary strings
+        board.create_flair(
+            &0,
+            &s("Q\"><script>"),
+            &s("red;background-image:url(x)"),
+            &s("#000"),
+            &false,
+            &false,
+            &admin,
         );
+
+        let path = String::from_str(&env, "/b/0/flairs/0/edit");
+        let html = client.render(&Some(path), &Some(admin));
+        let count =
+            |needle: &str| count_in_render(&html, &Bytes::from_slice(&env, needle.as_bytes()));
+        assert_eq!(count("background: #000; color: inherit;"), 1);
+        assert_eq!(count("value=\"Q&quot;>&lt;script>\""), 1);
+        assert_eq!(count("value=\"red;background-image:url(x)\""), 1);
+        assert_eq!(count("<script>"), 0);
     }
 
-    /// Unpin a thread (moderator+)
-    pub fn unpin_thread(env: Env, board_id: u64, thread_id: u64, caller: Address) {
-        caller.require_auth();
+    #[test]
+    #[should_panic(expected = "Caller must be admin or owner")]
+    fn test_create_flair_requires_admin() {
+        let env = Env::default();
+        let (client, _, _, _, _, moderator, _) = setup_roles(&env);
 
-        let permissions: Address = env
-            .storage()
-            .instance()
-            .get(&AdminKey::Permissions)
-            .expect("Not initialized");
+        let name = String::from_str(&env, "Question");
+        client.create_flair(&0, &name, &None, &None, &None, &None, &moderator);
+    }
 
-        // Verify caller has moderator permissions
-        let caller_perms: PermissionSet = env.invoke_contract(
-            &permissions,
-            &Symbol::new(&env, "get_permissions"),
-            Vec::from_array(&env, [board_id.into_val(&env), caller.into_val(&env)]),
-        );
+    #[test]
+    fn test_set_voting_config_parses_form_strings() {
+        let env = Env::default();
+        let (client, _, _, _, admin, _, _) = setup_roles(&env);
+        let voting = register_mock_voting(&env, &client);
+        let s = |v: &str| String::from_str(&env, v);
+
+        let (yes, no) = (s("true"), s("false"));
+        client.set_voting_config(&0, &yes, &no, &yes, &s("3"), &s("2"), &s("5"), &admin);
+        let cfg = voting.get_voting_config(&0).unwrap();
+        assert!(cfg.enabled);
+        assert!(!cfg.allow_downvotes);
+        assert!(cfg.karma_enabled);
+        assert_eq!(cfg.karma_multiplier, 3);
+        assert_eq!(voting.get_vote_weights(&0), (2, 5));
+
+        // Empty values do not panic: checkboxes are off, multiplier and weights default to 1
+        let empty = s("");
+        client.set_voting_config(&0, &empty, &empty, &empty, &empty, &empty, &empty, &admin);
+        let cfg = voting.get_voting_config(&0).unwrap();
+        assert!(!cfg.enabled);
+        assert!(!cfg.allow_downvotes);
+        assert!(!cfg.karma_enabled);
+        assert_eq!(cfg.karma_multiplier, 1);
+        assert_eq!(voting.get_vote_weights(&0), (1, 1));
+    }
 
-        if !caller_perms.can_moderate {
-            panic!("Caller must be moderator or higher");
-        }
+    #[test]
+    fn test_voting_form_prefills_current_numbers() {
+        let env = Env::default();
+        let (client, _, _, _, admin, _, _) = setup_roles(&env);
+        register_mock_voting(&env, &client);
+        let s = |v: &str| String::from_str(&env, v);
+
+        let yes = s("true");
+        client.set_voting_config(&0, &yes, &yes, &yes, &s("7"), &s("4"), &s("9"), &admin);
+
+        // Saving the form untouched must not reset the multiplier or weights to 1
+        let path = String::from_str(&env, "/b/0/voting");
+        let html = client.render(&Some(path), &Some(admin));
+        let count =
+            |needle: &str| count_in_render(&html, &Bytes::from_slice(&env, needle.as_bytes()));
+        assert_eq!(count("value=\"7\""), 1);
+        assert_eq!(count("value=\"4\""), 1);
+        assert_eq!(count("value=\"9\""), 1);
+    }
 
-        // Get board contract (single contract for all boards)
-        let board_contract = Self::get_board_contract_address(&env);
+    #[test]
+    #[should_panic(expected = "Karma multiplier must be between 1 and 10")]
+    fn test_set_voting_config_rejects_out_of_range_multiplier() {
+        let env = Env::default();
+        let (client, _, _, _, admin, _, _) = setup_roles(&env);
+        register_mock_voting(&env, &client);
+        let s = |v: &str| String::from_str(&env, v);
 
-        // Unpin the thread (now requires board_id)
-        let args: Vec<Val> = Vec::from_array(
-            &env,
-            [
-                board_id.into_val(&env),
-                thread_id.into_val(&env),
-                false.into_val(&env),
-            ],
-        );
-        env.invoke_contract::<()>(
-            &board_contract,
-            &Symbol::new(&env, "set_thread_pinned"),
-            args,
-        );
+        let yes = s("true");
+        client.set_voting_config(&0, &yes, &yes, &yes, &s("11"), &s("1"), &s("1"), &admin);
     }
 
-    /// Lock a thread (moderator+)
-    pub fn lock_thread(env: Env, board_id: u64, thread_id: u64, caller: Address) {
-        caller.require_auth();
+    #[test]
+    #[should_panic(expected = "Vote weights must be between 1 and 10")]
+    fn test_set_voting_config_rejects_zero_vote_weight() {
+        let env = Env::default();
+        let (client, _, _, _, admin, _, _) = setup_roles(&env);
+        register_mock_voting(&env, &client);
+        let s = |v: &str| String::from_str(&env, v);
 
-        let permissions: Address = env
-            .storage()
-            .instance()
-            .get(&AdminKey::Permissions)
-            .expect("Not initialized");
+        let yes = s("true");
+        client.set_voting_config(&0, &yes, &yes, &yes, &s("1"), &s("0"), &s("1"), &admin);
+    }
 
-        // Verify caller has moderator permissions
-        let caller_perms: PermissionSet = env.invoke_contract(
-            &permissions,
-            &Symbol::new(&env, "get_permissions"),
-            Vec::from_array(&env, [board_id.into_val(&env), caller.into_val(&env)]),
-        );
+    #[test]
+    #[should_panic(expected = "Caller must be admin or owner")]
+    fn test_set_voting_config_requires_admin() {
+        let env = Env::default();
+        let (client, _, _, _, _, moderator, _) = setup_roles(&env);
+        register_mock_voting(&env, &client);
+        let s = |v: &str| String::from_str(&env, v);
 
-        if !caller_perms.can_moderate {
-            panic!("Caller must be moderator or higher");
-        }
+        let (yes, one) = (s("true"), s("1"));
+        client.set_voting_config(&0, &yes, &yes, &yes, &one, &one, &one, &moderator);
+    }
 
-        // Get board contract (single contract for all boards)
-        let board_contract = Self::get_board_contract_address(&env);
+    #[test]
+    fn test_set_reaction_allowlist_parses_list() {
+        let env = Env::default();
+        let (client, _, _, _, admin, _, _) = setup_roles(&env);
+        let voting = register_mock_voting(&env, &client);
 
-        // Lock the thread (now requires board_id)
-        let args: Vec<Val> = Vec::from_array(
+        let list = String::from_str(&env, " rocket,heart , rocket,, ");
+        client.set_reaction_allowlist(&0, &list, &admin);
+        let expected = Vec::from_array(
             &env,
-            [
-                board_id.into_val(&env),
-                thread_id.into_val(&env),
-                true.into_val(&env),
-            ],
-        );
-        env.invoke_contract::<()>(
-            &board_contract,
-            &Symbol::new(&env, "set_thread_locked"),
-            args,
+            [Symbol::new(&env, "rocket"), Symbol::new(&env, "heart")],
         );
+        assert_eq!(voting.get_reaction_allowlist(&0), expected);
+
+        // An empty list turns reactions off
+        client.set_reaction_allowlist(&0, &String::from_str(&env, ""), &admin);
+        assert_eq!(voting.get_reaction_allowlist(&0).len(), 0);
     }
 
-    /// Unlock a thread (moderator+)
-    pub fn unlock_thread(env: Env, board_id: u64, thread_id: u64, caller: Address) {
-        caller.require_auth();
+    #[test]
+    #[should_panic(expected = "Unknown reaction")]
+    fn test_set_reaction_allowlist_rejects_unknown_name() {
+        let env = Env::default();
+        let (client, _, _, _, admin, _, _) = setup_roles(&env);
+        register_mock_voting(&env, &client);
 
-        let permissions: Address = env
-            .storage()
-            .instance()
-            .get(&AdminKey::Permissions)
-            .expect("Not initialized");
+        client.set_reaction_allowlist(&0, &String::from_str(&env, "heart, skull"), &admin);
+    }
 
-        // Verify caller has moderator permissions
-        let caller_perms: PermissionSet = env.invoke_contract(
-            &permissions,
-            &Symbol::new(&env, "get_permissions"),
-            Vec::from_array(&env, [board_id.into_val(&env), caller.into_val(&env)]),
+    #[test]
+    fn test_mod_log_records_hide_and_unhide_in_order() {
+        let env = Env::default();
+        let (client, _, board, _, _, moderator, _) = setup_roles(&env);
+        board.add_thread(&7);
+
+        client.hide_thread(&0, &7, &moderator);
+        client.unhide_thread(&0, &7, &moderator);
+
+        assert_eq!(client.get_mod_action_count(&0), 2);
+
+        // Newest first
+        let actions = client.list_mod_actions(&0, &0, &10);
+        assert_eq!(actions.len(), 2);
+        let latest = actions.get(0).unwrap();
+        let earliest = actions.get(1).unwrap();
+        assert_eq!(latest.action, Symbol::new(&env, "unhide_thread"));
+        assert_eq!(earliest.action, Symbol::new(&env, "hide_thread"));
+        assert_eq!(earliest.actor, moderator);
+        assert_eq!(earliest.target_thread, Some(7));
+        assert_eq!(earliest.target_reply, None);
+
+        // Paging past the first entry returns only the older one
+        let older = client.list_mod_actions(&0, &1, &10);
+        assert_eq!(older.len(), 1);
+        assert_eq!(
+            older.get(0).unwrap().action,
+            Symbol::new(&env, "hide_thread")
         );
 
-        if !caller_perms.can_moderate {
-            panic!("Caller must be moderator or higher");
-        }
+        // Other boards have an empty log
+        assert_eq!(client.list_mod_actions(&1, &0, &10).len(), 0);
+    }
 
-        // Get board contract (single contract for all boards)
-        let board_contract = Self::get_board_contract_address(&env);
+    /// The last moderation event the admin contract published
+    fn last_mod_event(
+        env: &Env,
+        client: &BoardsAdminClient,
+    ) -> ((Symbol, u64), (Symbol, ModTarget, Address, Option<String>)) {
+        use soroban_sdk::testutils::Events as _;
+        let (_, topics, data) = env
+            .events()
+            .all()
+            .iter()
+            .rev()
+            .find(|(contract, _, _)| *contract == client.address)
+            .unwrap();
+        let topic: Symbol = topics.get(0).unwrap().into_val(env);
+        let board_id: u64 = topics.get(1).unwrap().into_val(env);
+        ((topic, board_id), data.into_val(env))
+    }
 
-        // Unlock the thread (now requires board_id)
-        let args: Vec<Val> = Vec::from_array(
-            &env,
-            [
-                board_id.into_val(&env),
-                thread_id.into_val(&env),
-                false.into_val(&env),
-            ],
-        );
-        env.invoke_contract::<()>(
-            &board_contract,
-            &Symbol::new(&env, "set_thread_locked"),
-            args,
-        );
+    #[test]
+    fn test_hide_and_ban_emit_mod_events() {
+        let env = Env::default();
+        let (client, _, board, _, _, moderator, member) = setup_roles(&env);
+        board.add_thread(&7);
+
+        client.hide_thread(&0, &7, &moderator);
+        let (topics, data) = last_mod_event(&env, &client);
+        assert_eq!(topics, (symbol_short!("mod"), 0));
+        assert_eq!(data.0, Symbol::new(&env, "hide_thread"));
+        assert_eq!(data.1, ModTarget::Thread(7));
+        assert_eq!(data.2, moderator);
+        assert_eq!(data.3, None);
+
+        // Only the first 64 bytes of the ban reason are carried
+        let reason = "Repeated spam links in every thread, see the flag queue for the full list";
+        client.ban_user(&0, &member, &String::from_str(&env, reason), &0, &moderator);
+        let (topics, data) = last_mod_event(&env, &client);
+        assert_eq!(topics, (symbol_short!("mod"), 0));
+        assert_eq!(data.0, Symbol::new(&env, "ban_user"));
+        assert_eq!(data.1, ModTarget::User(member));
+        assert_eq!(data.2, moderator);
+        assert_eq!(data.3, Some(String::from_str(&env, &reason[..64])));
     }
 
-    /// Delete a thread (moderator+)
-    pub fn delete_thread(env: Env, board_id: u64, thread_id: u64, caller: Address) {
-        caller.require_auth();
+    #[test]
+    fn test_hide_threads_bulk_skips_missing() {
+        let env = Env::default();
+        let (client, _, board, _, _, moderator, _) = setup_roles(&env);
+        board.add_thread(&1);
+        board.add_thread(&2);
 
-        let permissions: Address = env
-            .storage()
-            .instance()
-            .get(&AdminKey::Permissions)
-            .expect("Not initialized");
+        let ids = Vec::from_array(&env, [1u64, 99, 2]);
+        assert_eq!(client.hide_threads_bulk(&0, &ids, &moderator), 2);
+        assert_eq!(client.get_mod_action_count(&0), 2);
 
-        // Verify caller has moderator permissions
-        let caller_perms: PermissionSet = env.invoke_contract(
-            &permissions,
-            &Symbol::new(&env, "get_permissions"),
-            Vec::from_array(
-                &env,
-                [board_id.into_val(&env), caller.clone().into_val(&env)],
-            ),
-        );
+        // The form handler parses "thread" / "thread:reply" selections
+        let selected = String::from_str(&env, "1, 99,2");
+        assert_eq!(client.hide_selected(&0, &selected, &moderator), 2);
+    }
 
-        if !caller_perms.can_moderate {
-            panic!("Caller must be moderator or higher");
+    #[test]
+    fn test_hide_all_by_user_resumes_and_spares_others() {
+        let env = Env::default();
+        let (client, perms, board, _, _, moderator, member) = setup_roles(&env);
+        let content = MockContentClient::new(&env, &env.register(MockContent, ()));
+        client.set_contracts(
+            &client.get_registry(),
+            &perms.address,
+            &content.address,
+            &Address::generate(&env),
+        );
+        for thread_id in 1..=4u64 {
+            board.add_thread(&thread_id);
+        }
+        for reply_id in 0..27u64 {
+            content.add_reply(&7, &reply_id);
         }
 
-        // Get board contract (single contract for all boards)
-        let board_contract = Self::get_board_contract_address(&env);
-
-        // Delete the thread (soft delete) - now requires board_id
-        let args: Vec<Val> = Vec::from_array(
-            &env,
-            [
-                board_id.into_val(&env),
-                thread_id.into_val(&env),
-                caller.into_val(&env),
-            ],
+        // Three threads (one already hidden), then 27 replies and one since deleted
+        let spammer = Address::generate(&env);
+        board.set_thread_hidden(&0, &2, &true);
+        board.set_thread_log(&spammer, &Vec::from_array(&env, [1u64, 2, 4]));
+        board.set_thread_log(&member, &Vec::from_array(&env, [3u64]));
+        let mut replies = Vec::new(&env);
+        for reply_id in 0..27u64 {
+            replies.push_back((7u64, reply_id));
+        }
+        replies.push_back((7, 99));
+        content.set_reply_log(&spammer, &replies);
+        assert_eq!(client.user_post_count(&0, &spammer), 31);
+
+        // Each ban entry offers the cleanup from where the last run stopped
+        let reason = String::from_str(&env, "Spam");
+        perms.ban_user(&0, &spammer, &reason, &None, &moderator);
+        let render_bans = || {
+            let path = String::from_str(&env, "/b/0/banned");
+            client.render(&Some(path), &Some(moderator.clone()))
+        };
+        let count = |html: &Bytes, needle: &str| {
+            count_in_render(html, &Bytes::from_slice(&env, needle.as_bytes()))
+        };
+        let html = render_bans();
+        assert_eq!(count(&html, "name=\"start\" value=\"0\""), 1);
+        assert_eq!(count(&html, "31 posts left to check"), 1);
+
+        assert!(client
+            .try_hide_all_by_user(&0, &spammer, &0, &25, &member)
+            .is_err());
+        assert!(client
+            .try_hide_all_by_user(&0, &spammer, &0, &26, &moderator)
+            .is_err());
+
+        // Only posts actually hidden are counted
+        assert_eq!(
+            client.hide_all_by_user(&0, &spammer, &0, &25, &moderator),
+            24
         );
-        env.invoke_contract::<()>(&board_contract, &Symbol::new(&env, "delete_thread"), args);
-    }
-
-    /// Delete a reply (moderator+)
-    pub fn delete_reply(env: Env, board_id: u64, thread_id: u64, reply_id: u64, caller: Address) {
-        caller.require_auth();
+        assert!(board.is_thread_hidden(&1));
+        assert!(board.is_thread_hidden(&4));
+        assert!(content.is_reply_hidden(&7, &21));
+        assert!(!content.is_reply_hidden(&7, &22));
+        let html = render_bans();
+        assert_eq!(count(&html, "name=\"start\" value=\"25\""), 1);
+        assert_eq!(count(&html, "6 posts left to check"), 1);
+
+        assert_eq!(
+            client.hide_all_by_user(&0, &spammer, &25, &25, &moderator),
+            5
+        );
+        assert!(content.is_reply_hidden(&7, &26));
+        assert_eq!(
+            client.hide_all_by_user(&0, &spammer, &31, &25, &moderator),
+            0
+        );
+        assert_eq!(count(&render_bans(), "Hide All Posts"), 0);
 
-        let permissions: Address = env
-            .storage()
-            .instance()
-            .get(&AdminKey::Permissions)
-            .expect("Not initialized");
-        let content: Address = env
-            .storage()
-            .instance()
-            .get(&AdminKey::Content)
-            .expect("Not initialized");
+        // Already-hidden posts are skipped and other users' posts are untouched
+        assert!(!board.is_thread_hidden(&3));
+        assert_eq!(client.get_mod_action_count(&0), 29);
+    }
 
-        // Verify caller has moderator permissions
-        let caller_perms: PermissionSet = env.invoke_contract(
-            &permissions,
-            &Symbol::new(&env, "get_permissions"),
-            Vec::from_array(
-                &env,
-                [board_id.into_val(&env), caller.clone().into_val(&env)],
-            ),
+    #[test]
+    fn test_bulk_moderation_skips_missing_items() {
+        let env = Env::default();
+        let (client, perms, board, _, _, moderator, _) = setup_roles(&env);
+        let content = MockContentClient::new(&env, &env.register(MockContent, ()));
+        client.set_contracts(
+            &client.get_registry(),
+            &perms.address,
+            &content.address,
+            &Address::generate(&env),
         );
+        board.add_thread(&1);
+        content.add_reply(&1, &5);
+
+        // Thread 99 and reply 1:6 don't exist, so they are neither counted nor logged
+        let selected = String::from_str(&env, "1,99,1:5,1:6");
+        assert_eq!(client.hide_selected(&0, &selected, &moderator), 2);
+        assert!(board.is_thread_hidden(&1));
+        assert!(content.is_reply_hidden(&1, &5));
+        assert_eq!(client.get_mod_action_count(&0), 2);
+
+        assert_eq!(client.clear_selected(&0, &selected, &moderator), 2);
+        assert!(content.is_cleared(&1, &None));
+        assert!(content.is_cleared(&1, &Some(5)));
+        assert!(!content.is_cleared(&99, &None));
+        assert!(!content.is_cleared(&1, &Some(6)));
+        assert_eq!(client.get_mod_action_count(&0), 4);
+    }
 
-        if !caller_perms.can_moderate {
-            panic!("Caller must be moderator or higher");
+    #[test]
+    #[should_panic(expected = "Too many items in batch (max 25)")]
+    fn test_hide_threads_bulk_enforces_cap() {
+        let env = Env::default();
+        let (client, _, _, _, _, moderator, _) = setup_roles(&env);
+
+        let mut ids = Vec::new(&env);
+        for i in 0..26u64 {
+            ids.push_back(i);
         }
+        client.hide_threads_bulk(&0, &ids, &moderator);
+    }
 
-        // Delete the reply (soft delete via content contract)
-        let args: Vec<Val> = Vec::from_array(
-            &env,
-            [
-                board_id.into_val(&env),
-                thread_id.into_val(&env),
-                reply_id.into_val(&env),
-                caller.into_val(&env),
-            ],
-        );
-        env.invoke_contract::<()>(&content, &Symbol::new(&env, "delete_reply"), args);
+    #[test]
+    #[should_panic(expected = "Caller must be moderator or higher")]
+    fn test_clear_flags_bulk_requires_moderator() {
+        let env = Env::default();
+        let (client, _, _, _, _, _, member) = setup_roles(&env);
+
+        let items = Vec::from_array(&env, [(1u64, None), (2u64, Some(3u64))]);
+        client.clear_flags_bulk(&0, &items, &member);
     }
 
-    // ========================================================================
-    // Flair Operations
-    // ========================================================================
+    #[test]
+    #[should_panic(expected = "Caller must be moderator or higher")]
+    fn test_remove_crosspost_requires_moderator() {
+        let env = Env::default();
+        let (client, _, _, _, _, _, member) = setup_roles(&env);
+        client.remove_crosspost(&0, &7, &member);
+    }
 
-    /// Create a new flair (admin+)
-    /// Parameters are optional to handle form submissions where fields may be empty
-    pub fn create_flair(
-        env: Env,
-        board_id: u64,
-        name: String,
-        color: Option<String>,
-        bg_color: Option<String>,
-        required: Option<String>,
-        mod_only: Option<String>,
-        caller: Address,
-    ) {
-        caller.require_auth();
+    #[test]
+    fn test_format_timestamp() {
+        let env = Env::default();
+        assert_eq!(
+            BoardsAdmin::format_timestamp(&env, 1_740_837_600),
+            Bytes::from_slice(&env, b"2025-03-01 14:00 UTC")
+        );
+        assert_eq!(
+            BoardsAdmin::format_timestamp(&env, 42),
+            Bytes::from_slice(&env, b"Ledger 42")
+        );
+    }
 
-        let permissions: Address = env
-            .storage()
-            .instance()
-            .get(&AdminKey::Permissions)
-            .expect("Not initialized");
+    #[test]
+    fn test_content_preview() {
+        let env = Env::default();
 
-        // Verify caller has admin permissions
-        let caller_perms: PermissionSet = env.invoke_contract(
-            &permissions,
-            &Symbol::new(&env, "get_permissions"),
-            Vec::from_array(
-                &env,
-                [board_id.into_val(&env), caller.clone().into_val(&env)],
-            ),
+        // Short bodies are kept whole with line breaks folded and markup escaped
+        assert_eq!(
+            BoardsAdmin::content_preview(&env, &Bytes::from_slice(&env, b"Hi\n<b>there</b>")),
+            Bytes::from_slice(&env, b"Hi &lt;b>there&lt;/b>")
         );
 
-        if !caller_perms.can_admin {
-            panic!("Caller must be admin or owner");
+        // Long bodies are cut, backing off so a multi-byte character isn't split
+        let mut long = Bytes::new(&env);
+        for _ in 0..199 {
+            long.push_back(b'a');
         }
+        long.append(&Bytes::from_slice(&env, "é and more".as_bytes()));
+        let preview = BoardsAdmin::content_preview(&env, &long);
+        assert_eq!(preview.len(), 199 + 3);
+        assert_eq!(preview.slice(199..), Bytes::from_slice(&env, b"..."));
+    }
 
-        // Get board contract (single contract for all boards)
-        let board_contract = Self::get_board_contract_address(&env);
+    /// Count occurrences of `needle` in rendered output
+    fn count_in_render(html: &Bytes, needle: &Bytes) -> usize {
+        let mut haystack = std::vec![0u8; html.len() as usize];
+        let mut pattern = std::vec![0u8; needle.len() as usize];
+        html.copy_into_slice(&mut haystack);
+        needle.copy_into_slice(&mut pattern);
+        haystack
+            .windows(pattern.len())
+            .filter(|w| *w == pattern.as_slice())
+            .count()
+    }
 
-        // Provide defaults for optional color fields
-        let final_color = color.unwrap_or_else(|| String::from_str(&env, "#ffffff"));
-        let final_bg_color = bg_color.unwrap_or_else(|| String::from_str(&env, "#808080"));
+    /// The rendered line that starts with `start` (up to the next newline)
+    fn render_line<'a>(page: &'a [u8], start: &[u8]) -> &'a [u8] {
+        let begin = page
+            .windows(start.len())
+            .position(|w| w == start)
+            .expect("Line not found in render");
+        let len = page[begin..]
+            .iter()
+            .position(|b| *b == b'\n')
+            .unwrap_or(page.len() - begin);
+        &page[begin..begin + len]
+    }
 
-        // Parse checkbox values (check for "true" string value)
-        let is_required = required
-            .map(|s| s == String::from_str(&env, "true"))
-            .unwrap_or(false);
-        let is_mod_only = mod_only
-            .map(|s| s == String::from_str(&env, "true"))
-            .unwrap_or(false);
+    #[test]
+    fn test_members_page_rows_carry_own_address() {
+        let env = Env::default();
+        let (client, _, _, owner, admin, moderator, member) = setup_roles(&env);
+
+        let path = String::from_str(&env, "/b/0/members");
+        let html = client.render(&Some(path), &Some(owner));
+        let mut page = std::vec![0u8; html.len() as usize];
+        html.copy_into_slice(&mut page);
+        let page = page.as_slice();
+
+        let rows = [
+            (admin, &["remove_admin"][..]),
+            (moderator, &["remove_moderator"][..]),
+            (member, &["add_moderator", "remove_member"][..]),
+        ];
+        let mut addrs = [[0u8; 56]; 3];
+        for (i, (user, _)) in rows.iter().enumerate() {
+            user.to_string().copy_into_slice(&mut addrs[i]);
+        }
 
-        // Create the flair (now requires board_id)
-        let args: Vec<Val> = Vec::from_array(
-            &env,
-            [
-                board_id.into_val(&env),
-                name.into_val(&env),
-                final_color.into_val(&env),
-                final_bg_color.into_val(&env),
-                is_required.into_val(&env),
-                is_mod_only.into_val(&env),
-                caller.into_val(&env),
-            ],
-        );
-        env.invoke_contract::<u32>(&board_contract, &Symbol::new(&env, "create_flair"), args);
+        let field = b"<input type=\"hidden\" name=\"user_address\" value=\"";
+        for (i, (_, funcs)) in rows.iter().enumerate() {
+            let mut start = [0u8; 59];
+            start[..3].copy_from_slice(b"- `");
+            start[3..].copy_from_slice(&addrs[i]);
+            let line = render_line(page, &start);
+
+            // One form per row: the address field precedes every action link in the row
+            let form_at = line
+                .windows(field.len())
+                .position(|w| w == field)
+                .expect("Row has no address field");
+            assert_eq!(line.windows(field.len()).filter(|w| *w == field).count(), 1);
+            assert!(line[form_at + field.len()..].starts_with(&addrs[i]));
+            for func in funcs.iter() {
+                let link_at = line
+                    .windows(func.len())
+                    .position(|w| w == func.as_bytes())
+                    .expect("Row is missing an action");
+                assert!(link_at > form_at);
+            }
+
+            // No other row's address leaks into this row
+            for (j, other) in addrs.iter().enumerate() {
+                if j != i {
+                    assert!(!line.windows(56).any(|w| w == other));
+                }
+            }
+        }
     }
 
-    /// Disable a flair (admin+)
-    pub fn disable_flair(env: Env, board_id: u64, flair_id: String, caller: Address) {
-        caller.require_auth();
+    #[test]
+    fn test_banned_page_scopes_user_per_ban() {
+        let env = Env::default();
+        let (client, perms, _, owner, _, moderator, member) = setup_roles(&env);
+        let other = Address::generate(&env);
+
+        perms.ban_user(
+            &0,
+            &member,
+            &String::from_str(&env, "Spam"),
+            &Some(24),
+            &owner,
+        );
+        perms.ban_user(&0, &other, &String::from_str(&env, "Abuse"), &None, &owner);
 
-        let flair_id_u32 = string_to_u32(&env, &flair_id).expect("Invalid number");
+        let path = String::from_str(&env, "/b/0/banned");
+        let html = client.render(&Some(path), &Some(moderator));
 
-        let permissions: Address = env
-            .storage()
-            .instance()
-            .get(&AdminKey::Permissions)
-            .expect("Not initialized");
+        // Both the unban and extend forms of each ban carry that ban's own address
+        for user in [member, other] {
+            let mut addr = [0u8; 56];
+            user.to_string().copy_into_slice(&mut addr);
 
-        // Verify caller has admin permissions
-        let caller_perms: PermissionSet = env.invoke_contract(
-            &permissions,
-            &Symbol::new(&env, "get_permissions"),
-            Vec::from_array(
+            let mut form = Bytes::from_slice(
                 &env,
-                [board_id.into_val(&env), caller.clone().into_val(&env)],
-            ),
-        );
-
-        if !caller_perms.can_admin {
-            panic!("Caller must be admin or owner");
+                b"<div data-form>\n<input type=\"hidden\" name=\"board_id\" value=\"0\" />\n\
+                  <input type=\"hidden\" name=\"user\" value=\"",
+            );
+            form.append(&Bytes::from_slice(&env, &addr));
+            form.append(&Bytes::from_slice(&env, b"\" />\n"));
+            assert_eq!(count_in_render(&html, &form), 2);
         }
+    }
 
-        // Get board contract (single contract for all boards)
-        let board_contract = Self::get_board_contract_address(&env);
+    #[test]
+    fn test_subpage_breadcrumbs() {
+        let env = Env::default();
+        let (client, _, _, owner, _, _, _) = setup_roles(&env);
 
-        // Disable the flair (now requires board_id)
-        let args: Vec<Val> = Vec::from_array(
+        let path = String::from_str(&env, "/b/0/members");
+        let html = client.render(&Some(path), &Some(owner));
+        let trail = Bytes::from_slice(
             &env,
-            [
-                board_id.into_val(&env),
-                flair_id_u32.into_val(&env),
-                caller.into_val(&env),
-            ],
+            b"<a href=\"render:/\">Home</a><span class=\"crumb-sep\">\xE2\x80\xBA</span>\
+              <a href=\"render:/b/0\">general</a><span class=\"crumb-sep\">\xE2\x80\xBA</span>\
+              <a href=\"render:/admin/b/0/settings\">Settings</a>\
+              <span class=\"crumb-sep\">\xE2\x80\xBA</span>\
+              <span class=\"crumb-current\">Board Members</span>",
+        );
+        assert_eq!(count_in_render(&html, &trail), 1);
+        assert_eq!(
+            count_in_render(&html, &Bytes::from_slice(&env, b"crumb-sep")),
+            3
+        );
+        assert_eq!(
+            count_in_render(&html, &Bytes::from_slice(&env, b"Back to Settings")),
+            0
         );
-        env.invoke_contract::<()>(&board_contract, &Symbol::new(&env, "disable_flair"), args);
     }
 
-    /// Update an existing flair (admin+)
-    pub fn update_flair(
-        env: Env,
-        board_id: u64,
-        flair_id: u64,
-        name: String,
-        color: String,
-        bg_color: String,
-        required: Option<String>,
-        mod_only: Option<String>,
-        enabled: Option<String>,
-        caller: Address,
-    ) {
-        caller.require_auth();
+    #[test]
+    fn test_accept_invite_as_role() {
+        let env = Env::default();
+        let (client, perms, _, _, admin, moderator, _) = setup_roles(&env);
+        let newcomer = Address::generate(&env);
+        let trusted = Address::generate(&env);
+
+        // Moderators can accept as Member
+        perms.request_invite(&0, &String::from_str(&env, ""), &newcomer);
+        client.accept_invite_as(&0, &newcomer, &Role::Member, &moderator);
+        assert!(perms.get_role(&0, &newcomer) == Role::Member);
+
+        // Admins can fast-track to Moderator
+        perms.request_invite(&0, &String::from_str(&env, ""), &trusted);
+        client.accept_invite_moderator(&0, &trusted, &admin);
+        assert!(perms.get_role(&0, &trusted) == Role::Moderator);
+    }
 
-        let flair_id_u32 = flair_id as u32;
+    #[test]
+    #[should_panic(expected = "Caller must be admin or owner to grant Moderator or above")]
+    fn test_moderator_cannot_accept_invite_as_moderator() {
+        let env = Env::default();
+        let (client, perms, _, _, _, moderator, _) = setup_roles(&env);
+        let user = Address::generate(&env);
 
-        let permissions: Address = env
-            .storage()
-            .instance()
-            .get(&AdminKey::Permissions)
-            .expect("Not initialized");
+        perms.request_invite(&0, &String::from_str(&env, ""), &user);
+        client.accept_invite_as(&0, &user, &Role::Moderator, &moderator);
+    }
 
-        // Verify caller has admin permissions
-        let caller_perms: PermissionSet = env.invoke_contract(
-            &permissions,
-            &Symbol::new(&env, "get_permissions"),
-            Vec::from_array(
-                &env,
-                [board_id.into_val(&env), caller.clone().into_val(&env)],
-            ),
+    #[test]
+    fn test_invites_page_role_buttons() {
+        let env = Env::default();
+        let (client, perms, _, _, admin, moderator, _) = setup_roles(&env);
+        let user = Address::generate(&env);
+        perms.request_invite(&0, &String::from_str(&env, ""), &user);
+
+        let mod_label = Bytes::from_slice(&env, b"Accept as Moderator");
+        let path = String::from_str(&env, "/b/0/invites");
+
+        // Moderators only get the Member button
+        let html = client.render(&Some(path.clone()), &Some(moderator));
+        assert_eq!(
+            count_in_render(&html, &Bytes::from_slice(&env, b"Accept as Member")),
+            1
         );
+        assert_eq!(count_in_render(&html, &mod_label), 0);
 
-        if !caller_perms.can_admin {
-            panic!("Caller must be admin or owner");
-        }
+        let html = client.render(&Some(path), &Some(admin));
+        assert_eq!(count_in_render(&html, &mod_label), 1);
+    }
 
-        // Get board contract (single contract for all boards)
-        let board_contract = Self::get_board_contract_address(&env);
+    #[test]
+    fn test_invites_page_shows_request_message() {
+        let env = Env::default();
+        let (client, perms, _, _, _, moderator, _) = setup_roles(&env);
+        let user = Address::generate(&env);
+        perms.request_invite(
+            &0,
+            &String::from_str(&env, "Met <you> at\nthe meetup"),
+            &user,
+        );
 
-        // Parse checkbox values (check for "true" string value)
-        let is_required = required
-            .map(|s| s == String::from_str(&env, "true"))
-            .unwrap_or(false);
-        let is_mod_only = mod_only
-            .map(|s| s == String::from_str(&env, "true"))
-            .unwrap_or(false);
-        let is_enabled = enabled
-            .map(|s| s == String::from_str(&env, "true"))
-            .unwrap_or(true); // Default to enabled
+        let path = String::from_str(&env, "/b/0/invites");
+        let html = client.render(&Some(path), &Some(moderator));
+        let quoted = Bytes::from_slice(&env, b"> Met &lt;you> at the meetup");
+        assert_eq!(count_in_render(&html, &quoted), 1);
+    }
 
-        // Build FlairDef
-        let flair = FlairDef {
-            id: flair_id_u32,
-            name,
-            color,
-            bg_color,
-            required: is_required,
-            mod_only: is_mod_only,
-            enabled: is_enabled,
-        };
+    #[test]
+    fn test_update_board_settings_applies_only_changed_fields() {
+        let env = Env::default();
+        let (client, perms, board, _, admin, _, _) = setup_roles(&env);
+        let s = |v: &str| String::from_str(&env, v);
+
+        // Chunk size and threshold match the current values, edit window is blank
+        let changed =
+            client.update_board_settings(&0, &s("6"), &s(" 12 "), &s(""), &s("3"), &admin);
+        assert_eq!(changed, 1);
+        assert_eq!(board.get_max_reply_depth(&0), 12);
+        assert_eq!(board.get_chunk_size(&0), 6);
+        assert_eq!(board.setting_writes(), 1);
+        assert_eq!(perms.get_flag_threshold(&0), 3);
+
+        // Resubmitting the same form changes nothing
+        let changed = client.update_board_settings(&0, &s("6"), &s("12"), &s(""), &s("3"), &admin);
+        assert_eq!(changed, 0);
+        assert_eq!(board.setting_writes(), 1);
+
+        let changed = client.update_board_settings(&0, &s(""), &s(""), &s("48"), &s("5"), &admin);
+        assert_eq!(changed, 2);
+        assert_eq!(board.get_edit_window(&0), 48 * 3600);
+        assert_eq!(perms.get_flag_threshold(&0), 5);
+    }
 
-        // Update the flair (board_id is required by boards-board.update_flair)
-        let args: Vec<Val> = Vec::from_array(
-            &env,
-            [
-                board_id.into_val(&env),
-                flair_id_u32.into_val(&env),
-                flair.into_val(&env),
-                caller.into_val(&env),
-            ],
-        );
-        env.invoke_contract::<()>(&board_contract, &Symbol::new(&env, "update_flair"), args);
+    #[test]
+    fn test_update_board_settings_keeps_partial_hour_edit_window() {
+        let env = Env::default();
+        let (client, _, board, _, admin, _, _) = setup_roles(&env);
+        let s = |v: &str| String::from_str(&env, v);
+        board.set_edit_window(&0, &5400, &admin);
+        let writes = board.setting_writes();
+
+        // The form shows a 90-minute window as "1"; saving it unchanged keeps the window
+        let changed = client.update_board_settings(&0, &s(""), &s(""), &s("1"), &s(""), &admin);
+        assert_eq!(changed, 0);
+        assert_eq!(board.get_edit_window(&0), 5400);
+        assert_eq!(board.setting_writes(), writes);
+
+        let changed = client.update_board_settings(&0, &s(""), &s(""), &s("2"), &s(""), &admin);
+        assert_eq!(changed, 1);
+        assert_eq!(board.get_edit_window(&0), 2 * 3600);
     }
 
-    // ========================================================================
-    // Rules Operations
-    // ========================================================================
+    #[test]
+    #[should_panic(expected = "chunk_size must be between 1 and 20")]
+    fn test_update_board_settings_names_invalid_field() {
+        let env = Env::default();
+        let (client, _, _, _, admin, _, _) = setup_roles(&env);
+        let s = |v: &str| String::from_str(&env, v);
 
-    /// Set board rules (admin+)
-    pub fn set_rules(env: Env, board_id: u64, rules: String, caller: Address) {
-        caller.require_auth();
+        client.update_board_settings(&0, &s("25"), &s("12"), &s(""), &s(""), &admin);
+    }
 
-        let permissions: Address = env
-            .storage()
-            .instance()
-            .get(&AdminKey::Permissions)
-            .expect("Not initialized");
+    #[test]
+    #[should_panic(expected = "max_depth must be a number")]
+    fn test_update_board_settings_rejects_non_numeric_field() {
+        let env = Env::default();
+        let (client, _, _, _, admin, _, _) = setup_roles(&env);
+        let s = |v: &str| String::from_str(&env, v);
 
-        // Verify caller has admin permissions
-        let caller_perms: PermissionSet = env.invoke_contract(
-            &permissions,
-            &Symbol::new(&env, "get_permissions"),
-            Vec::from_array(
-                &env,
-                [board_id.into_val(&env), caller.clone().into_val(&env)],
-            ),
-        );
+        client.update_board_settings(&0, &s(""), &s("deep"), &s(""), &s(""), &admin);
+    }
+
+    #[test]
+    #[should_panic(expected = "Caller must be admin or owner")]
+    fn test_update_board_settings_requires_admin() {
+        let env = Env::default();
+        let (client, _, _, _, _, moderator, _) = setup_roles(&env);
+        let s = |v: &str| String::from_str(&env, v);
+
+        client.update_board_settings(&0, &s("5"), &s(""), &s(""), &s(""), &moderator);
+    }
+
+    #[test]
+    fn test_destructive_actions_accept_board_name_or_confirm() {
+        let env = Env::default();
+        let (client, _, board, _, admin, _, _) = setup_roles(&env);
 
-        if !caller_perms.can_admin {
-            panic!("Caller must be admin or owner");
-        }
+        client.make_private(&0, &String::from_str(&env, "general"), &admin);
+        client.make_readonly(&0, &String::from_str(&env, " CONFIRM "), &admin);
 
-        // Get board contract (single contract for all boards)
-        let board_contract = Self::get_board_contract_address(&env);
+        let config = board.get_config(&0);
+        assert!(config.is_private);
+        assert!(config.is_readonly);
+    }
 
-        // Set the rules (now requires board_id)
-        let args: Vec<Val> = Vec::from_array(
-            &env,
-            [
-                board_id.into_val(&env),
-                rules.into_val(&env),
-                caller.into_val(&env),
-            ],
-        );
-        env.invoke_contract::<()>(&board_contract, &Symbol::new(&env, "set_rules"), args);
+    #[test]
+    #[should_panic(expected = "Confirmation required: type the board name or CONFIRM")]
+    fn test_destructive_action_requires_confirmation() {
+        let env = Env::default();
+        let (client, _, _, _, admin, _, _) = setup_roles(&env);
+
+        client.make_private(&0, &String::from_str(&env, ""), &admin);
     }
 
-    /// Clear board rules (admin+)
-    pub fn clear_rules(env: Env, board_id: u64, caller: Address) {
-        caller.require_auth();
+    #[test]
+    #[should_panic(expected = "Confirmation does not match: type the board name or CONFIRM")]
+    fn test_destructive_action_rejects_wrong_confirmation() {
+        let env = Env::default();
+        let (client, _, _, _, admin, _, _) = setup_roles(&env);
 
-        let permissions: Address = env
-            .storage()
-            .instance()
-            .get(&AdminKey::Permissions)
-            .expect("Not initialized");
+        client.make_readonly(&0, &String::from_str(&env, "confirm"), &admin);
+    }
 
-        // Verify caller has admin permissions
-        let caller_perms: PermissionSet = env.invoke_contract(
-            &permissions,
-            &Symbol::new(&env, "get_permissions"),
-            Vec::from_array(
-                &env,
-                [board_id.into_val(&env), caller.clone().into_val(&env)],
-            ),
-        );
+    #[test]
+    fn test_member_can_leave_board() {
+        let env = Env::default();
+        let (client, perms, _, _, _, _, member) = setup_roles(&env);
 
-        if !caller_perms.can_admin {
-            panic!("Caller must be admin or owner");
-        }
+        client.leave_board(&0, &String::from_str(&env, "general"), &member);
+        assert!(perms.get_role(&0, &member) == Role::Guest);
+    }
 
-        // Get board contract (single contract for all boards)
-        let board_contract = Self::get_board_contract_address(&env);
+    #[test]
+    #[should_panic(expected = "Owner must transfer ownership before leaving the board")]
+    fn test_owner_cannot_leave_board() {
+        let env = Env::default();
+        let (client, _, _, owner, _, _, _) = setup_roles(&env);
 
-        // Clear the rules (now requires board_id)
-        let args: Vec<Val> =
-            Vec::from_array(&env, [board_id.into_val(&env), caller.into_val(&env)]);
-        env.invoke_contract::<()>(&board_contract, &Symbol::new(&env, "clear_rules"), args);
+        client.leave_board(&0, &String::from_str(&env, "CONFIRM"), &owner);
     }
 
-    // ========================================================================
-    // Config Operations (site-wide settings)
-    // ========================================================================
+    #[test]
+    fn test_set_default_sort() {
+        let env = Env::default();
+        let (client, _, board, _, admin, _, _) = setup_roles(&env);
 
-    /// Helper to verify caller is registry admin
-    fn require_registry_admin(env: &Env, caller: &Address) {
-        caller.require_auth();
-        let registry: Address = env
-            .storage()
-            .instance()
-            .get(&AdminKey::Registry)
-            .expect("Not initialized");
-        let is_admin: bool = env.invoke_contract(
-            &registry,
-            &Symbol::new(env, "is_admin"),
-            Vec::from_array(env, [caller.into_val(env)]),
-        );
-        if !is_admin {
-            panic!("Caller must be registry admin");
-        }
+        assert!(board.get_default_sort(&0) == ThreadSort::Hot);
+        client.set_default_sort(&0, &String::from_str(&env, " active "), &admin);
+        assert!(board.get_default_sort(&0) == ThreadSort::Active);
+        client.set_default_sort(&0, &String::from_str(&env, "controversial"), &admin);
+        assert!(board.get_default_sort(&0) == ThreadSort::Controversial);
+
+        assert_eq!(board.get_controversial_min_votes(&0), 5);
+        client.set_controversial_min_votes(&0, &String::from_str(&env, "12"), &admin);
+        assert_eq!(board.get_controversial_min_votes(&0), 12);
     }
 
-    /// Helper to get and update branding
-    fn update_branding_field<F>(env: &Env, caller: &Address, updater: F)
-    where
-        F: FnOnce(&mut Branding),
-    {
-        Self::require_registry_admin(env, caller);
+    #[test]
+    fn test_set_post_size_limits_boundary() {
+        let env = Env::default();
+        let (client, _, board, _, admin, _, _) = setup_roles(&env);
+        let s = |v: &str| String::from_str(&env, v);
 
-        let config: Address = env
-            .storage()
-            .instance()
-            .get(&AdminKey::Config)
-            .expect("Config not set");
+        assert_eq!(board.get_max_reply_bytes(&0), 8_192);
+        client.set_post_size_limits(&0, &s("16384"), &s("1"), &admin);
+        assert_eq!(board.get_max_reply_bytes(&0), 1);
+    }
 
-        // Get current branding
-        let mut branding: Branding =
-            env.invoke_contract(&config, &Symbol::new(env, "get_branding"), Vec::new(env));
+    #[test]
+    fn test_set_render_budget() {
+        let env = Env::default();
+        let (client, _, board, _, admin, moderator, _) = setup_roles(&env);
+        let s = |v: &str| String::from_str(&env, v);
+
+        assert_eq!(board.get_render_budget(&0), 12_288);
+        client.set_render_budget(&0, &s("32768"), &admin);
+        assert_eq!(board.get_render_budget(&0), 32_768);
+        assert!(client
+            .try_set_render_budget(&0, &s("4096"), &moderator)
+            .is_err());
+    }
 
-        // Apply the update
-        updater(&mut branding);
+    #[test]
+    fn test_set_collapse_depth() {
+        let env = Env::default();
+        let (client, _, board, _, admin, moderator, _) = setup_roles(&env);
+        let s = |v: &str| String::from_str(&env, v);
+
+        assert_eq!(board.get_collapse_depth(&0), 4);
+        client.set_collapse_depth(&0, &s("6"), &admin);
+        assert_eq!(board.get_collapse_depth(&0), 6);
+        assert!(client
+            .try_set_collapse_depth(&0, &s("2"), &moderator)
+            .is_err());
+    }
 
-        // Save updated branding
-        let args: Vec<Val> = Vec::from_array(env, [branding.into_val(env), caller.into_val(env)]);
-        env.invoke_contract::<()>(&config, &Symbol::new(env, "set_branding"), args);
+    #[test]
+    fn test_set_collapse_score_accepts_negative_values() {
+        let env = Env::default();
+        let (client, _, board, _, admin, moderator, _) = setup_roles(&env);
+        let s = |v: &str| String::from_str(&env, v);
+
+        assert_eq!(board.get_collapse_score(&0), -3);
+        client.set_collapse_score(&0, &s(" -8 "), &admin);
+        assert_eq!(board.get_collapse_score(&0), -8);
+        client.set_collapse_score(&0, &s("2"), &admin);
+        assert_eq!(board.get_collapse_score(&0), 2);
+        assert!(client.try_set_collapse_score(&0, &s("-"), &admin).is_err());
+        assert!(client
+            .try_set_collapse_score(&0, &s("-1"), &moderator)
+            .is_err());
     }
 
-    /// Set site name (registry admin only)
-    pub fn set_site_name(env: Env, site_name: String, caller: Address) {
-        Self::update_branding_field(&env, &caller, |b| {
-            if !site_name.is_empty() {
-                b.site_name = site_name;
-            }
-        });
+    #[test]
+    fn test_set_collapse_children_over() {
+        let env = Env::default();
+        let (client, _, board, _, admin, moderator, _) = setup_roles(&env);
+        let s = |v: &str| String::from_str(&env, v);
+
+        assert_eq!(board.get_collapse_children_over(&0), 0);
+        client.set_collapse_children_over(&0, &s("10"), &admin);
+        assert_eq!(board.get_collapse_children_over(&0), 10);
+        client.set_collapse_children_over(&0, &s("0"), &admin);
+        assert_eq!(board.get_collapse_children_over(&0), 0);
+        assert!(client
+            .try_set_collapse_children_over(&0, &s("5"), &moderator)
+            .is_err());
     }
 
-    /// Set tagline (registry admin only)
-    pub fn set_tagline(env: Env, tagline: String, caller: Address) {
-        Self::update_branding_field(&env, &caller, |b| {
-            if !tagline.is_empty() {
-                b.tagline = tagline;
-            }
-        });
+    #[test]
+    fn test_set_wiki_edit_role() {
+        let env = Env::default();
+        let (client, _, board, _, admin, moderator, _) = setup_roles(&env);
+        let s = |v: &str| String::from_str(&env, v);
+
+        assert!(board.get_wiki_edit_role(&0) == Role::Member);
+        client.set_wiki_edit_role(&0, &s(" moderator "), &admin);
+        assert!(board.get_wiki_edit_role(&0) == Role::Moderator);
+        assert!(client
+            .try_set_wiki_edit_role(&0, &s("admin"), &admin)
+            .is_err());
+        assert!(client
+            .try_set_wiki_edit_role(&0, &s("member"), &moderator)
+            .is_err());
     }
 
-    /// Set primary color (registry admin only)
-    pub fn set_primary_color(env: Env, primary_color: String, caller: Address) {
-        Self::update_branding_field(&env, &caller, |b| {
-            if !primary_color.is_empty() {
-                b.primary_color = primary_color;
-            }
-        });
+    #[test]
+    fn test_set_locale() {
+        let env = Env::default();
+        let (client, _, board, _, admin, moderator, _) = setup_roles(&env);
+        let s = |v: &str| String::from_str(&env, v);
+
+        assert_eq!(board.get_locale(&0), None);
+        client.set_locale(&0, &s(" de "), &admin);
+        assert_eq!(board.get_locale(&0), Some(Symbol::new(&env, "de")));
+        assert!(client.try_set_locale(&0, &s("klingon"), &admin).is_err());
+        assert!(client.try_set_locale(&0, &s("ja"), &moderator).is_err());
+
+        client.set_locale(&0, &s(""), &admin);
+        assert_eq!(board.get_locale(&0), None);
     }
 
-    /// Set logo URL (registry admin only)
-    pub fn set_logo_url(env: Env, logo_url: String, caller: Address) {
-        Self::update_branding_field(&env, &caller, |b| {
-            b.logo_url = logo_url;
-        });
+    #[test]
+    fn test_set_image_policy() {
+        let env = Env::default();
+        let (client, _, board, _, admin, moderator, _) = setup_roles(&env);
+        let s = |v: &str| String::from_str(&env, v);
+
+        assert!(board.get_image_policy(&0).allow_images);
+        client.set_image_policy(
+            &0,
+            &s("true"),
+            &s(" I.imgur.com, example.com example.com,"),
+            &admin,
+        );
+        let policy = board.get_image_policy(&0);
+        assert!(policy.allow_images);
+        assert_eq!(
+            policy.allowed_hosts,
+            Vec::from_array(&env, [s("i.imgur.com"), s("example.com")])
+        );
+
+        client.set_image_policy(&0, &s("false"), &s(""), &admin);
+        let policy = board.get_image_policy(&0);
+        assert!(!policy.allow_images);
+        assert!(policy.allowed_hosts.is_empty());
+
+        assert!(client
+            .try_set_image_policy(&0, &s("true"), &s(""), &moderator)
+            .is_err());
     }
 
-    /// Set favicon URL (registry admin only)
-    pub fn set_favicon_url(env: Env, favicon_url: String, caller: Address) {
-        Self::update_branding_field(&env, &caller, |b| {
-            b.favicon_url = favicon_url;
-        });
+    #[test]
+    fn test_blocked_terms_are_admin_only() {
+        let env = Env::default();
+        let (client, _, board, _, admin, moderator, _) = setup_roles(&env);
+        let s = |v: &str| String::from_str(&env, v);
+
+        client.add_blocked_term(&0, &s("scam.example"), &admin);
+        client.add_blocked_term(&0, &s("spam"), &admin);
+        assert!(client
+            .try_add_blocked_term(&0, &s("other"), &moderator)
+            .is_err());
+        assert!(client
+            .try_remove_blocked_term(&0, &s("spam"), &moderator)
+            .is_err());
+        client.remove_blocked_term(&0, &s("spam"), &admin);
+        assert_eq!(
+            board.list_blocked_terms(&0),
+            Vec::from_array(&env, [s("scam.example")])
+        );
     }
 
-    /// Set footer text (registry admin only)
-    pub fn set_footer_text(env: Env, footer_text: String, caller: Address) {
-        Self::update_branding_field(&env, &caller, |b| {
-            b.footer_text = footer_text;
-        });
+    #[test]
+    fn test_set_reply_cooldown_is_admin_only() {
+        let env = Env::default();
+        let (client, _, board, _, admin, moderator, _) = setup_roles(&env);
+        let s = |v: &str| String::from_str(&env, v);
+
+        assert_eq!(board.get_reply_cooldown(&0), 0);
+        client.set_reply_cooldown(&0, &s(" 45 "), &admin);
+        assert_eq!(board.get_reply_cooldown(&0), 45);
+        assert!(client
+            .try_set_reply_cooldown(&0, &s("soon"), &admin)
+            .is_err());
+        assert!(client
+            .try_set_reply_cooldown(&0, &s("0"), &moderator)
+            .is_err());
+        assert_eq!(board.get_reply_cooldown(&0), 45);
     }
 
-    /// Update creation thresholds (registry admin only)
-    pub fn update_thresholds(
-        env: Env,
-        threshold_type: String,
-        min_karma: Option<String>,
-        min_account_age_secs: Option<String>,
-        min_post_count: Option<String>,
-        require_profile: Option<String>,
-        per_user_limit: Option<String>,
-        xlm_lock_stroops: Option<String>,
-        caller: Address,
-    ) {
-        Self::require_registry_admin(&env, &caller);
+    #[test]
+    fn test_set_public_modlog_parses_checkbox() {
+        let env = Env::default();
+        let (client, _, board, _, admin, moderator, _) = setup_roles(&env);
+        let s = |v: &str| String::from_str(&env, v);
+
+        assert!(!board.is_modlog_public(&0));
+        client.set_public_modlog(&0, &s("true"), &admin);
+        assert!(board.is_modlog_public(&0));
+        assert!(client
+            .try_set_public_modlog(&0, &s("false"), &moderator)
+            .is_err());
+        client.set_public_modlog(&0, &s(""), &admin);
+        assert!(!board.is_modlog_public(&0));
+    }
 
-        let config: Address = env
-            .storage()
-            .instance()
-            .get(&AdminKey::Config)
-            .expect("Config not set");
+    #[test]
+    fn test_set_karma_thresholds_and_exemptions() {
+        let env = Env::default();
+        let (client, _, board, _, admin, moderator, _) = setup_roles(&env);
+        let s = |v: &str| String::from_str(&env, v);
+
+        assert_eq!(board.get_min_karma_to_post(&0), 0);
+        client.set_karma_thresholds(&0, &s(" 10 "), &s("0"), &admin);
+        assert_eq!(board.get_min_karma_to_post(&0), 10);
+
+        let user = Address::generate(&env);
+        client.exempt_from_karma(&0, &user, &moderator);
+        assert!(board.is_karma_exempt(&0, &user));
+        client.remove_karma_exemption(&0, &user, &moderator);
+        assert!(!board.is_karma_exempt(&0, &user));
+
+        // Moderators can exempt users but not change the thresholds
+        assert!(client
+            .try_set_karma_thresholds(&0, &s("5"), &s("5"), &moderator)
+            .is_err());
+    }
 
-        // Determine type
-        let is_board = {
-            let len = threshold_type.len() as usize;
-            let mut buf = [0u8; 16];
-            let copy_len = core::cmp::min(len, 16);
-            threshold_type.copy_into_slice(&mut buf[..copy_len]);
-            &buf[..5] == b"board"
-        };
+    #[test]
+    fn test_set_and_reset_board_theme() {
+        let env = Env::default();
+        let (client, _, board, _, admin, moderator, _) = setup_roles(&env);
+        let dark = String::from_str(&env, "dark");
 
-        // Get current thresholds
-        let mut thresholds: CreationThresholds = if is_board {
-            env.invoke_contract(
-                &config,
-                &Symbol::new(&env, "get_board_thresholds"),
-                Vec::new(&env),
-            )
-        } else {
-            env.invoke_contract(
-                &config,
-                &Symbol::new(&env, "get_community_thresholds"),
-                Vec::new(&env),
-            )
-        };
+        client.set_board_theme(&0, &dark, &admin);
+        assert_eq!(board.get_theme_name(&0), Some(dark.clone()));
+        assert!(client.try_reset_board_theme(&0, &moderator).is_err());
 
-        // Update fields
-        if let Some(s) = min_karma {
-            if !s.is_empty() {
-                thresholds.min_karma = string_to_u32(&env, &s).expect("Invalid number") as i64;
-            }
-        }
-        if let Some(s) = min_account_age_secs {
-            if !s.is_empty() {
-                thresholds.min_account_age_secs =
-                    string_to_u32(&env, &s).expect("Invalid number") as u64;
-            }
-        }
-        if let Some(s) = min_post_count {
-            if !s.is_empty() {
-                thresholds.min_post_count = string_to_u32(&env, &s).expect("Invalid number");
-            }
-        }
-        if let Some(s) = require_profile {
-            thresholds.require_profile = s == String::from_str(&env, "true");
-        }
-        if let Some(s) = per_user_limit {
-            if !s.is_empty() {
-                thresholds.per_user_limit = string_to_u32(&env, &s).expect("Invalid number");
-            }
-        }
-        if let Some(s) = xlm_lock_stroops {
-            if !s.is_empty() {
-                thresholds.xlm_lock_stroops =
-                    string_to_u32(&env, &s).expect("Invalid number") as i128;
-            }
-        }
+        client.reset_board_theme(&0, &admin);
+        assert_eq!(board.get_theme_name(&0), None);
+        assert!(client.try_set_board_theme(&0, &dark, &moderator).is_err());
+    }
 
-        // Save updated thresholds
-        let func_name = if is_board {
-            "set_board_thresholds"
-        } else {
-            "set_community_thresholds"
-        };
-        let args: Vec<Val> =
-            Vec::from_array(&env, [thresholds.into_val(&env), caller.into_val(&env)]);
-        env.invoke_contract::<()>(&config, &Symbol::new(&env, func_name), args);
+    #[test]
+    fn test_set_and_reset_custom_css() {
+        let env = Env::default();
+        let (client, _, board, _, admin, moderator, _) = setup_roles(&env);
+        let css = String::from_str(&env, ".board-header { color: red; }");
+
+        client.set_custom_css(&0, &css, &admin);
+        assert_eq!(board.get_custom_css(&0), Some(css.clone()));
+        assert!(client.try_reset_custom_css(&0, &moderator).is_err());
+
+        client.reset_custom_css(&0, &admin);
+        assert_eq!(board.get_custom_css(&0), None);
+        assert!(client.try_set_custom_css(&0, &css, &moderator).is_err());
     }
 
-    /// Set site-wide max reply depth (registry admin only)
-    pub fn config_set_max_reply_depth(env: Env, max_reply_depth: String, caller: Address) {
-        Self::require_registry_admin(&env, &caller);
-        let config: Address = env
-            .storage()
-            .instance()
-            .get(&AdminKey::Config)
-            .expect("Config not set");
-        let depth = string_to_u32(&env, &max_reply_depth).expect("Invalid number");
-        let args: Vec<Val> = Vec::from_array(&env, [depth.into_val(&env), caller.into_val(&env)]);
-        env.invoke_contract::<()>(&config, &Symbol::new(&env, "set_max_reply_depth"), args);
+    #[test]
+    #[should_panic(expected = "Post size limits must be between 1 and 16384 bytes")]
+    fn test_set_post_size_limits_rejects_one_byte_over() {
+        let env = Env::default();
+        let (client, _, _, _, admin, _, _) = setup_roles(&env);
+        let s = |v: &str| String::from_str(&env, v);
+        client.set_post_size_limits(&0, &s("16385"), &s("8192"), &admin);
     }
 
-    /// Set site-wide reply chunk size (registry admin only)
-    pub fn config_set_reply_chunk_size(env: Env, reply_chunk_size: String, caller: Address) {
-        Self::require_registry_admin(&env, &caller);
-        let config: Address = env
-            .storage()
-            .instance()
-            .get(&AdminKey::Config)
-            .expect("Config not set");
-        let size = string_to_u32(&env, &reply_chunk_size).expect("Invalid number");
-        let args: Vec<Val> = Vec::from_array(&env, [size.into_val(&env), caller.into_val(&env)]);
-        env.invoke_contract::<()>(&config, &Symbol::new(&env, "set_reply_chunk_size"), args);
+    #[test]
+    #[should_panic(expected = "Unknown sort: use hot, new, top, active or controversial")]
+    fn test_set_default_sort_rejects_unknown() {
+        let env = Env::default();
+        let (client, _, _, _, admin, _, _) = setup_roles(&env);
+
+        client.set_default_sort(&0, &String::from_str(&env, "controversial"), &admin);
     }
 
-    /// Set default edit window (registry admin only)
-    pub fn config_set_default_edit_window(env: Env, default_edit_window: String, caller: Address) {
-        Self::require_registry_admin(&env, &caller);
-        let config: Address = env
-            .storage()
-            .instance()
-            .get(&AdminKey::Config)
-            .expect("Config not set");
-        let seconds = string_to_u32(&env, &default_edit_window).expect("Invalid number") as u64;
-        let args: Vec<Val> = Vec::from_array(&env, [seconds.into_val(&env), caller.into_val(&env)]);
-        env.invoke_contract::<()>(&config, &Symbol::new(&env, "set_default_edit_window"), args);
+    #[test]
+    fn test_page_bounds() {
+        // An empty list still renders one page
+        assert_eq!(BoardsAdmin::page_bounds(0, 1, 50), (1, 0, 1));
+        assert_eq!(BoardsAdmin::page_bounds(50, 1, 50), (1, 0, 1));
+        assert_eq!(BoardsAdmin::page_bounds(51, 2, 50), (2, 50, 2));
+        assert_eq!(BoardsAdmin::page_bounds(120, 3, 50), (3, 100, 3));
+
+        // Out of range pages clamp to the first/last page
+        assert_eq!(BoardsAdmin::page_bounds(120, 0, 50), (1, 0, 3));
+        assert_eq!(BoardsAdmin::page_bounds(120, 9, 50), (3, 100, 3));
+        assert_eq!(
+            BoardsAdmin::page_bounds(u32::MAX, u32::MAX, 50).0,
+            u32::MAX / 50 + 1
+        );
     }
 
-    /// Set thread body max bytes (registry admin only)
-    pub fn config_set_thread_body_max_bytes(
-        env: Env,
-        thread_body_max_bytes: String,
-        caller: Address,
-    ) {
-        Self::require_registry_admin(&env, &caller);
-        let config: Address = env
-            .storage()
-            .instance()
-            .get(&AdminKey::Config)
-            .expect("Config not set");
-        let max_bytes = string_to_u32(&env, &thread_body_max_bytes).expect("Invalid number");
-        let args: Vec<Val> =
-            Vec::from_array(&env, [max_bytes.into_val(&env), caller.into_val(&env)]);
-        env.invoke_contract::<()>(
-            &config,
-            &Symbol::new(&env, "set_thread_body_max_bytes"),
-            args,
+    #[test]
+    fn test_format_count() {
+        let env = Env::default();
+        assert_eq!(
+            BoardsAdmin::format_count(&env, 0),
+            Bytes::from_slice(&env, b"0")
+        );
+        assert_eq!(
+            BoardsAdmin::format_count(&env, 999),
+            Bytes::from_slice(&env, b"999")
+        );
+        assert_eq!(
+            BoardsAdmin::format_count(&env, 1_204),
+            Bytes::from_slice(&env, b"1,204")
+        );
+        assert_eq!(
+            BoardsAdmin::format_count(&env, 4_294_967_295),
+            Bytes::from_slice(&env, b"4,294,967,295")
         );
     }
 
-    /// Set name limits (registry admin only)
-    pub fn config_set_name_limits(
-        env: Env,
-        limit_type: String,
-        min_length: String,
-        max_length: String,
-        caller: Address,
-    ) {
-        Self::require_registry_admin(&env, &caller);
-        let config: Address = env
-            .storage()
-            .instance()
-            .get(&AdminKey::Config)
-            .expect("Config not set");
+    #[test]
+    fn test_members_page_paginates_members() {
+        let env = Env::default();
+        let (client, perms, _, owner, _, _, _) = setup_roles(&env);
+        for _ in 0..MEMBERS_PER_PAGE {
+            perms.set_role(&0, &Address::generate(&env), &Role::Member, &owner);
+        }
 
-        let is_board = {
-            let len = limit_type.len() as usize;
-            let mut buf = [0u8; 16];
-            let copy_len = core::cmp::min(len, 16);
-            limit_type.copy_into_slice(&mut buf[..copy_len]);
-            &buf[..5] == b"board"
-        };
+        // 51 members: page 2 holds only the last one
+        let path = String::from_str(&env, "/b/0/members/2");
+        let last_page = client.render(&Some(path), &Some(owner));
+        let mut page = std::vec![0u8; last_page.len() as usize];
+        last_page.copy_into_slice(&mut page);
+        let page = page.as_slice();
+        let has = |needle: &[u8]| page.windows(needle.len()).any(|w| w == needle);
+        assert!(has(b"Members (51)"));
+        assert!(has(b"Page 2 of 2"));
+        assert!(has(b"[< Prev](render:/admin/b/0/members/1)"));
+        assert!(!has(b"[Next >]"));
+
+        // Owner, admin and moderator rows plus the single member on this page
+        assert_eq!(page.windows(3).filter(|w| *w == b"- `").count(), 4);
+    }
 
-        let limits = NameLimits {
-            min_length: string_to_u32(&env, &min_length).expect("Invalid number"),
-            max_length: string_to_u32(&env, &max_length).expect("Invalid number"),
-        };
+    #[test]
+    fn test_archive_board_round_trip() {
+        let env = Env::default();
+        let (client, _, board, owner, _, _, _) = setup_roles(&env);
 
-        let func_name = if is_board {
-            "set_board_name_limits"
-        } else {
-            "set_community_name_limits"
-        };
-        let args: Vec<Val> = Vec::from_array(&env, [limits.into_val(&env), caller.into_val(&env)]);
-        env.invoke_contract::<()>(&config, &Symbol::new(&env, func_name), args);
+        client.archive_board(&0, &String::from_str(&env, "CONFIRM"), &owner);
+        assert!(board.is_archived(&0));
+        let log = client.list_mod_actions(&0, &0, &1);
+        assert!(log.get(0).unwrap().action == Symbol::new(&env, "archive_board"));
+
+        client.unarchive_board(&0, &owner);
+        assert!(!board.is_archived(&0));
     }
 
-    /// Upgrade the contract WASM
-    pub fn upgrade(env: Env, new_wasm_hash: BytesN<32>) {
-        let registry: Address = env
-            .storage()
-            .instance()
-            .get(&AdminKey::Registry)
-            .expect("Not initialized");
-        registry.require_auth();
+    #[test]
+    #[should_panic(expected = "Only the board owner can archive or unarchive it")]
+    fn test_archive_board_requires_owner() {
+        let env = Env::default();
+        let (client, _, _, _, admin, _, _) = setup_roles(&env);
+        client.archive_board(&0, &String::from_str(&env, "CONFIRM"), &admin);
+    }
 
-        env.deployer().update_current_contract_wasm(new_wasm_hash);
+    /// Register `admin` as a registry admin on the mock registry
+    fn make_registry_admin(env: &Env, client: &BoardsAdminClient, admin: &Address) {
+        MockRegistryClient::new(env, &client.get_registry()).add_admin(admin, admin);
     }
-}
 
-#[cfg(test)]
-mod test {
-    use super::*;
-    use soroban_sdk::testutils::Address as _;
-    use soroban_sdk::Env;
+    #[test]
+    fn test_upgrade_board_goes_through_registry() {
+        let env = Env::default();
+        let (client, _, board, owner, _, _, _) = setup_roles(&env);
+        make_registry_admin(&env, &client, &owner);
 
-    /// Helper to setup a boards-admin contract with all dependencies
-    fn setup_admin(
-        env: &Env,
-    ) -> (
-        BoardsAdminClient,
-        Address,
-        Address,
-        Address,
-        Address,
-        Address,
-    ) {
-        env.mock_all_auths();
+        let hash = BytesN::from_array(&env, &[0xabu8; 32]);
+        client.upgrade_board(&hash, &owner);
 
-        let contract_id = env.register(BoardsAdmin, ());
-        let client = BoardsAdminClient::new(env, &contract_id);
+        let registry = MockRegistryClient::new(&env, &client.get_registry());
+        assert_eq!(
+            registry.get_contract_wasm_hash(&board.address),
+            Some(hash.clone())
+        );
 
-        let registry = Address::generate(env);
-        let permissions = Address::generate(env);
-        let content = Address::generate(env);
-        let theme = Address::generate(env);
-        let config = Address::generate(env);
+        // The form variant takes the alias and a hex hash
+        let hex = String::from_str(
+            &env,
+            "cdcdcdcdcdcdcdcdcdcdcdcdcdcdcdcdcdcdcdcdcdcdcdcdcdcdcdcdcdcdcdcd",
+        );
+        client.upgrade_contract_by_alias(&String::from_str(&env, "board"), &hex, &owner);
+        assert_eq!(
+            registry.get_contract_wasm_hash(&board.address),
+            Some(BytesN::from_array(&env, &[0xcdu8; 32]))
+        );
+    }
 
-        client.init(&registry, &permissions, &content, &theme, &config);
+    #[test]
+    #[should_panic(expected = "Caller must be registry admin")]
+    fn test_upgrade_board_requires_registry_admin() {
+        let env = Env::default();
+        let (client, _, _, owner, _, _, _) = setup_roles(&env);
 
-        (client, registry, permissions, content, theme, config)
+        // Board owners are not registry admins
+        client.upgrade_board(&BytesN::from_array(&env, &[1u8; 32]), &owner);
     }
 
     #[test]
-    fn test_init() {
+    fn test_wasm_hash_hex_round_trip() {
         let env = Env::default();
-        env.mock_all_auths();
+        let hash = BytesN::from_array(&env, &[0x0fu8; 32]);
+        let hex = BoardsAdmin::wasm_hash_to_hex(&env, &hash);
+        assert_eq!(hex.len(), 64);
+        assert_eq!(hex.get(0), Some(b'0'));
+        assert_eq!(hex.get(1), Some(b'f'));
+
+        let mut buf = [0u8; 64];
+        hex.copy_into_slice(&mut buf);
+        let parsed = BoardsAdmin::parse_wasm_hash(
+            &env,
+            &String::from_str(&env, core::str::from_utf8(&buf).unwrap()),
+        );
+        assert_eq!(parsed, hash);
+    }
 
-        let contract_id = env.register(BoardsAdmin, ());
-        let client = BoardsAdminClient::new(&env, &contract_id);
+    #[test]
+    #[should_panic(expected = "Invalid WASM hash")]
+    fn test_parse_wasm_hash_rejects_non_hex() {
+        let env = Env::default();
+        let hex = String::from_str(
+            &env,
+            "zzzzzzzzzzzzzzzzzzzzzzzzzzzzzzzzzzzzzzzzzzzzzzzzzzzzzzzzzzzzzzzz",
+        );
+        BoardsAdmin::parse_wasm_hash(&env, &hex);
+    }
 
-        let registry = Address::generate(&env);
-        let permissions = Address::generate(&env);
-        let content = Address::generate(&env);
-        let theme = Address::generate(&env);
-        let config = Address::generate(&env);
+    #[test]
+    fn test_user_notes_add_list_delete() {
+        let env = Env::default();
+        let (client, _, _, _, admin, moderator, member) = setup_roles(&env);
+
+        let first =
+            client.add_user_note(&0, &member, &String::from_str(&env, "Warned"), &moderator);
+        let second =
+            client.add_user_note(&0, &member, &String::from_str(&env, "Warned again"), &admin);
+
+        let notes = client.list_user_notes(&0, &member);
+        assert_eq!(notes.len(), 2);
+        assert_eq!(notes.get(0).unwrap().author, moderator);
+        assert_eq!(
+            notes.get(1).unwrap().text,
+            String::from_str(&env, "Warned again")
+        );
 
-        client.init(&registry, &permissions, &content, &theme, &config);
+        // Author can delete their own note, admin can delete anyone's
+        client.delete_user_note(&0, &member, &first, &moderator);
+        assert_eq!(client.list_user_notes(&0, &member).len(), 1);
+        client.delete_user_note(&0, &member, &second, &admin);
+        assert_eq!(client.list_user_notes(&0, &member).len(), 0);
+    }
 
-        assert_eq!(client.get_registry(), registry);
-        assert_eq!(client.get_permissions(), permissions);
-        assert_eq!(client.get_content(), content);
-        assert_eq!(client.get_theme(), theme);
-        assert_eq!(client.get_config(), Some(config));
+    #[test]
+    #[should_panic(expected = "Only the note author or an admin can delete it")]
+    fn test_user_note_delete_requires_author_or_admin() {
+        let env = Env::default();
+        let (client, perms, _, owner, admin, _, member) = setup_roles(&env);
+        let other_mod = Address::generate(&env);
+        perms.set_role(&0, &other_mod, &Role::Moderator, &owner);
+
+        let id = client.add_user_note(&0, &member, &String::from_str(&env, "Spam"), &admin);
+        client.delete_user_note(&0, &member, &id, &other_mod);
     }
 
     #[test]
-    #[should_panic(expected = "Already initialized")]
-    fn test_double_init() {
+    #[should_panic(expected = "Caller must be moderator or higher")]
+    fn test_user_note_requires_moderator() {
         let env = Env::default();
-        env.mock_all_auths();
+        let (client, _, _, _, _, _, member) = setup_roles(&env);
 
-        let contract_id = env.register(BoardsAdmin, ());
-        let client = BoardsAdminClient::new(&env, &contract_id);
+        client.add_user_note(&0, &member, &String::from_str(&env, "Hi"), &member);
+    }
 
-        let registry = Address::generate(&env);
-        let permissions = Address::generate(&env);
-        let content = Address::generate(&env);
-        let theme = Address::generate(&env);
-        let config = Address::generate(&env);
+    #[test]
+    fn test_parse_string_to_u32_edge_cases() {
+        let env = Env::default();
+        let s = |v: &str| String::from_str(&env, v);
+
+        assert_eq!(BoardsAdmin::parse_string_to_u32(&s("0")), 0);
+        assert_eq!(BoardsAdmin::parse_string_to_u32(&s("007")), 7);
+        assert_eq!(BoardsAdmin::parse_string_to_u32(&s("4294967295")), u32::MAX);
+        assert_eq!(BoardsAdmin::parse_string_to_u32(&s(" 10")), 10);
+        assert_eq!(BoardsAdmin::parse_string_to_u32(&s("10 ")), 10);
+        assert_eq!(BoardsAdmin::parse_string_to_u32(&s("\t42\n")), 42);
+    }
 
-        client.init(&registry, &permissions, &content, &theme, &config);
-        // Should panic on second init
-        client.init(&registry, &permissions, &content, &theme, &config);
+    #[test]
+    #[should_panic(expected = "Number too large")]
+    fn test_parse_string_to_u32_overflow() {
+        let env = Env::default();
+        BoardsAdmin::parse_string_to_u32(&String::from_str(&env, "4294967296"));
     }
 
     #[test]
-    fn test_get_registry() {
+    #[should_panic(expected = "Invalid number")]
+    fn test_parse_string_to_u32_junk() {
         let env = Env::default();
-        let (client, registry, _, _, _, _) = setup_admin(&env);
-        assert_eq!(client.get_registry(), registry);
+        BoardsAdmin::parse_string_to_u32(&String::from_str(&env, "1 2"));
     }
 
     #[test]
-    fn test_get_permissions() {
+    #[should_panic(expected = "Number is required")]
+    fn test_parse_string_to_u32_whitespace_only() {
         let env = Env::default();
-        let (client, _, permissions, _, _, _) = setup_admin(&env);
-        assert_eq!(client.get_permissions(), permissions);
+        BoardsAdmin::parse_string_to_u32(&String::from_str(&env, "   "));
     }
 
     #[test]
-    fn test_get_content() {
+    fn test_parse_string_to_u64() {
         let env = Env::default();
-        let (client, _, _, content, _, _) = setup_admin(&env);
-        assert_eq!(client.get_content(), content);
+        let s = |v: &str| String::from_str(&env, v);
+
+        assert_eq!(
+            BoardsAdmin::parse_string_to_u64(&s(" 1740837600 ")),
+            1_740_837_600
+        );
+        assert_eq!(
+            BoardsAdmin::parse_string_to_u64(&s("18446744073709551615")),
+            u64::MAX
+        );
     }
 
     #[test]
-    fn test_get_theme() {
+    #[should_panic(expected = "Number too large")]
+    fn test_parse_string_to_u64_overflow() {
         let env = Env::default();
-        let (client, _, _, _, theme, _) = setup_admin(&env);
-        assert_eq!(client.get_theme(), theme);
+        BoardsAdmin::parse_string_to_u64(&String::from_str(&env, "18446744073709551616"));
     }
 
     #[test]
-    fn test_get_config() {
+    fn test_parse_string_to_bool() {
         let env = Env::default();
-        let (client, _, _, _, _, config) = setup_admin(&env);
-        assert_eq!(client.get_config(), Some(config));
+        let s = |v: &str| String::from_str(&env, v);
+
+        assert!(BoardsAdmin::parse_string_to_bool(&s("true")));
+        assert!(BoardsAdmin::parse_string_to_bool(&s("1")));
+        assert!(BoardsAdmin::parse_string_to_bool(&s(" true ")));
+        assert!(!BoardsAdmin::parse_string_to_bool(&s("false")));
+        assert!(!BoardsAdmin::parse_string_to_bool(&s("0")));
     }
 
     #[test]
-    fn test_render_not_found() {
+    #[should_panic(expected = "Invalid boolean")]
+    fn test_parse_string_to_bool_junk() {
         let env = Env::default();
-        let (client, _, _, _, _, _) = setup_admin(&env);
+        BoardsAdmin::parse_string_to_bool(&String::from_str(&env, "yes"));
+    }
 
-        // Render unknown path should return "not found" content
-        let path = String::from_str(&env, "/unknown");
-        let html = client.render(&Some(path), &None);
-        assert!(html.len() > 0);
+    #[test]
+    fn test_set_contracts_repoints_siblings() {
+        let env = Env::default();
+        let (client, _, _, _, _, _, _) = setup_roles(&env);
+
+        let registry = Address::generate(&env);
+        let permissions = Address::generate(&env);
+        let content = Address::generate(&env);
+        let theme = Address::generate(&env);
+        client.set_contracts(&registry, &permissions, &content, &theme);
+
+        assert_eq!(client.get_registry(), registry);
+        assert_eq!(client.get_permissions(), permissions);
     }
 
     #[test]
-    fn test_render_no_path() {
+    fn test_non_registry_cannot_upgrade_or_repoint() {
         let env = Env::default();
-        let (client, _, _, _, _, _) = setup_admin(&env);
+        let contract_id = env.register(BoardsAdmin, ());
+        let client = BoardsAdminClient::new(&env, &contract_id);
+        let registry = Address::generate(&env);
+        let other = Address::generate(&env);
+        client.init(
+            &registry,
+            &Address::generate(&env),
+            &Address::generate(&env),
+            &Address::generate(&env),
+            &Address::generate(&env),
+        );
 
-        // Render with no path (None)
-        let html = client.render(&None, &None);
-        assert!(html.len() > 0);
+        // Without the registry's signature both calls are rejected
+        let hash = BytesN::from_array(&env, &[0u8; 32]);
+        assert!(client.try_upgrade(&hash).is_err());
+        assert!(client
+            .try_set_contracts(&other, &other, &other, &other)
+            .is_err());
+        assert_eq!(client.get_registry(), registry);
     }
 
     #[test]
-    fn test_render_empty_path() {
+    fn test_registry_admin_add_and_remove() {
         let env = Env::default();
-        let (client, _, _, _, _, _) = setup_admin(&env);
+        let (client, _, _, owner, _, _, _) = setup_roles(&env);
+        let registry = MockRegistryClient::new(&env, &client.get_registry());
+        registry.add_admin(&owner, &owner);
 
-        // Render with empty path
-        let path = String::from_str(&env, "");
-        let html = client.render(&Some(path), &None);
-        assert!(html.len() > 0);
+        let second = Address::generate(&env);
+        client.add_registry_admin(&second, &owner);
+        assert!(registry.is_admin(&second));
+
+        client.remove_registry_admin(&second, &owner);
+        assert!(!registry.is_admin(&second));
+        assert_eq!(registry.get_admins().len(), 1);
     }
 
     #[test]
-    fn test_render_root_path() {
+    #[should_panic(expected = "Caller must be registry admin")]
+    fn test_registry_admin_requires_admin() {
         let env = Env::default();
-        let (client, _, _, _, _, _) = setup_admin(&env);
+        let (client, _, _, owner, admin, _, _) = setup_roles(&env);
+        MockRegistryClient::new(&env, &client.get_registry()).add_admin(&owner, &owner);
 
-        // Render with root path "/"
-        let path = String::from_str(&env, "/");
-        let html = client.render(&Some(path), &None);
-        assert!(html.len() > 0);
+        client.add_registry_admin(&admin, &admin);
     }
 
-    // Note: Admin actions like set_role, ban_user, rename_board, etc.
-    // require cross-contract calls to permissions, registry, and board contracts.
-    // These are integration-level tests that would require setting up
-    // the entire contract ecosystem with real initialized contracts.
+    #[test]
+    #[should_panic(expected = "Cannot remove the last admin")]
+    fn test_registry_admin_cannot_remove_last() {
+        let env = Env::default();
+        let (client, _, _, owner, _, _, _) = setup_roles(&env);
+        MockRegistryClient::new(&env, &client.get_registry()).add_admin(&owner, &owner);
+
+        client.remove_registry_admin(&owner, &owner);
+    }
+
+    #[test]
+    #[should_panic(expected = "Ban duration is too long")]
+    fn test_global_ban_rejects_overflowing_duration() {
+        let env = Env::default();
+        let (client, _, _, owner, _, _, _) = setup_roles(&env);
+        MockRegistryClient::new(&env, &client.get_registry()).add_admin(&owner, &owner);
+
+        client.global_ban_user(
+            &Address::generate(&env),
+            &String::from_str(&env, "spam"),
+            &String::from_str(&env, "18446744073709551615"),
+            &owner,
+        );
+    }
 }
@@ -1,10 +1,14 @@
 #![no_std]
 #![allow(clippy::too_many_arguments)]
 
+use boards_shared::{
+    char_floor, copy_string_prefix, escape_attr, escape_html, is_hex_color, is_safe_css,
+    is_valid_address, next_mention, sanitize_color, MAX_MENTIONS_PER_POST, MENTION_SCAN_LEN,
+};
 use soroban_render_sdk::prelude::*;
 use soroban_sdk::{
-    contract, contractimpl, contracttype, Address, Bytes, BytesN, Env, IntoVal, String, Symbol,
-    Val, Vec,
+    contract, contracterror, contractimpl, contracttype, panic_with_error, Address, Bytes, BytesN,
+    Env, IntoVal, Map, String, Symbol, Val, Vec,
 };
 
 // Declare render capabilities
@@ -29,6 +33,8 @@ pub enum BoardKey {
     Community,
     /// Temporary: community slug for current render (avoids re-entrant calls)
     RenderCommunitySlug,
+    /// Temporary: (name, theme contract) previewed by the current render
+    RenderPreviewTheme,
     /// Total number of boards (auto-increment counter)
     BoardCount,
 
@@ -63,6 +69,153 @@ pub enum BoardKey {
     BoardBySlug(String),
     /// Community slug for boards that belong to a community (stored at creation time)
     BoardCommunitySlug(u64),
+    /// Archive record for an archived board (board_id) -> BoardArchive
+    BoardArchive(u64),
+    /// Community a board belongs to, set by the community contract (board_id) -> u64
+    BoardCommunityId(u64),
+    /// Sorted IDs of the boards matching a listing filter within one chunk of
+    /// board IDs (filter, chunk) -> Vec<u64>
+    BoardIndex(BoardFilter, u64),
+    /// Non-empty chunks of a listing index and their sizes, ascending
+    /// (filter) -> Vec<(u64, u32)>
+    BoardIndexChunks(BoardFilter),
+    /// Boards below this ID predate the listing indexes and are not in them yet -> u64
+    BoardsIndexedFrom,
+    /// Board IDs featured on the home page, in display order -> Vec<u64>
+    FeaturedBoards,
+    /// New thread/reply counts for a board on one day (board_id, day number) -> DayStats
+    DailyStats(u64, u64),
+    /// Thread template offered on the create form (board_id, template_id) -> ThreadTemplate
+    Template(u64, u32),
+    /// IDs of a board's templates, oldest first (board_id) -> Vec<u32>
+    BoardTemplateIds(u64),
+    /// Next template ID for a board (board_id) -> u32
+    BoardNextTemplateId(u64),
+    /// Wiki page by slug (board_id, slug) -> WikiPage
+    WikiPage(u64, String),
+    /// Version a wiki page's last edit replaced (board_id, slug) -> WikiPage
+    WikiPagePrevious(u64, String),
+    /// A board's wiki pages, oldest first (board_id) -> Vec<WikiPageInfo>
+    BoardWikiPages(u64),
+    /// Lowercase terms rejected in titles and posts (board_id) -> Vec<String>
+    BlockedTerms(u64),
+    /// Tag definitions per board, in creation order (board_id) -> Vec<TagDef>
+    BoardTagDefs(u64),
+    /// First thread logged under its author when it was created; older threads are
+    /// added by `log_user_threads` (board_id) -> u64
+    ThreadLogFrom(u64),
+    /// Threads below this ID have been added to the author logs by the backfill (board_id) -> u64
+    ThreadLogBackfilled(u64),
+}
+
+/// Storage keys for per-board settings. Kept apart from BoardKey because a
+/// contract type may have at most 50 variants; a key is stored by its variant
+/// name, so moving one between these enums leaves its data where it was.
+#[contracttype]
+#[derive(Clone)]
+pub enum SettingKey {
+    /// Default thread ordering for the board view (board_id) -> ThreadSort
+    BoardDefaultSort(u64),
+    /// Maximum bytes of quoted content prefilled into a reply (board_id) -> u32
+    BoardQuoteLimit(u64),
+    /// Approximate output budget for the initial thread page (board_id) -> u32
+    BoardRenderBudget(u64),
+    /// Maximum thread body size in bytes (board_id) -> u32
+    BoardMaxThreadBodyBytes(u64),
+    /// Maximum reply size in bytes (board_id) -> u32
+    BoardMaxReplyBytes(u64),
+    /// Minimum total votes for a thread to appear under Controversial (board_id) -> u32
+    BoardControversialMinVotes(u64),
+    /// Reply depth past which the thread view links to a subtree page (board_id) -> u32
+    BoardCollapseDepth(u64),
+    /// Reply score below which replies start collapsed (board_id) -> i32
+    BoardCollapseScore(u64),
+    /// Children count above which a reply shows a "Show N replies" link (board_id) -> u32, 0 = off
+    BoardCollapseChildrenOver(u64),
+    /// Minimum total karma needed to create threads (board_id) -> i64, 0 = off
+    BoardMinKarmaToPost(u64),
+    /// Minimum total karma needed to reply (board_id) -> i64, 0 = off
+    BoardMinKarmaToReply(u64),
+    /// CSS appended after the theme stylesheet (board_id) -> String
+    BoardCustomCss(u64),
+    /// Theme picked from the registry's catalog (board_id) -> String
+    BoardThemeName(u64),
+    /// Lowest role that can edit a board's wiki (board_id) -> Role
+    BoardWikiEditRole(u64),
+    /// Inline image rules enforced by the content contract on write (board_id) -> ImagePolicy
+    BoardImagePolicy(u64),
+    /// Language the board is written in, one of BOARD_LOCALES (board_id) -> Symbol
+    BoardLocale(u64),
+    /// Seconds a user must wait between replies in one thread (board_id) -> u64, 0 = off
+    BoardReplyCooldown(u64),
+    /// Set while the moderation log is public (board_id) -> bool
+    BoardPublicModlog(u64),
+}
+
+/// Storage keys for per-thread data (see SettingKey for why these are separate)
+#[contracttype]
+#[derive(Clone)]
+pub enum ThreadKey {
+    /// Subscribers recorded before the chunked index: (board_id, thread_id) -> Vec<Address>.
+    /// Moved into SubscriberChunk the next time someone subscribes or unsubscribes.
+    Subscribers(u64, u64),
+    /// Number of users subscribed to a thread (board_id, thread_id) -> u32
+    SubscriberCount(u64, u64),
+    /// One chunk of a thread's subscribers (board_id, thread_id, chunk) -> Vec<Address>
+    SubscriberChunk(u64, u64, u32),
+    /// Position of a user in a thread's subscriber chunks (board_id, thread_id, user) -> u32
+    SubscriberSlot(u64, u64, Address),
+    /// Thread hidden by the flag threshold rather than a moderator: (board_id, thread_id)
+    ThreadAutoHidden(u64, u64),
+    /// A moderator unhid an auto-hidden thread; no auto-hide until its flags are cleared
+    ThreadAutoHideSuppressed(u64, u64),
+    /// Reply window for a time-boxed thread (board_id, thread_id) -> ThreadSchedule
+    ThreadSchedule(u64, u64),
+    /// Reply marked as the answer to a Q&A thread (board_id, thread_id) -> u64
+    AcceptedAnswer(u64, u64),
+    /// Tags on a thread (board_id, thread_id) -> Vec<u32>
+    ThreadTags(u64, u64),
+    /// Threads carrying a tag, newest tagging first (board_id, tag_id) -> Vec<u64>
+    TagThreads(u64, u32),
+}
+
+/// Storage keys for per-user data (see SettingKey for why these are separate)
+#[contracttype]
+#[derive(Clone)]
+pub enum UserKey {
+    /// Thread IDs a user is subscribed to: (board_id, user) -> Vec<u64>
+    Subscriptions(u64, Address),
+    /// User exempted from the karma thresholds by a moderator: (board_id, user)
+    BoardKarmaExempt(u64, Address),
+    /// Display preferences for a viewer, across all boards (user) -> DisplayPref
+    UserPref(Address),
+    /// Board-local display name used when no profile contract is set (board_id, user)
+    DisplayName(u64, Address),
+    /// Owner of a lowercased display name on a board (board_id, name) -> Address
+    DisplayNameOwner(u64, String),
+    /// Authors a viewer has blocked, across all boards (user) -> Vec<Address>
+    BlockedUsers(Address),
+    /// Threads a user saved for later, newest first (user) -> Vec<(board_id, thread_id)>
+    SavedThreads(Address),
+    /// Recent threads started by a user, oldest first (board_id, user) -> Vec<u64>
+    UserThreads(u64, Address),
+    /// One chunk of every thread a user started, in logging order
+    /// (board_id, user, chunk) -> Vec<u64>
+    UserThreadLog(u64, Address, u64),
+    /// Number of threads in a user's thread log (board_id, user) -> u64
+    UserThreadLogLen(u64, Address),
+    /// What a user has read on a board (board_id, user) -> ReadMarks
+    ReadMarks(u64, Address),
+}
+
+/// Errors that can occur in the board contract. Codes start at 100 so they
+/// can't be mistaken for registry errors passed through from board creation.
+#[contracterror]
+#[derive(Copy, Clone, Debug, Eq, PartialEq, PartialOrd, Ord)]
+#[repr(u32)]
+pub enum BoardError {
+    /// The title contains a term on the board's blocklist
+    BlockedTerm = 100,
 }
 
 /// Board metadata (stored per-board)
@@ -81,6 +234,38 @@ pub struct BoardMeta {
     pub is_listed: bool,
 }
 
+/// New threads and replies on a board during one day (days since the Unix epoch)
+#[contracttype]
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct DayStats {
+    pub threads: u32,
+    pub replies: u32,
+}
+
+/// Archive record for a retired board. Archiving is reversible; the
+/// listed/read-only flags from before archiving are restored on unarchive.
+#[contracttype]
+#[derive(Clone)]
+pub struct BoardArchive {
+    pub archived_at: u64,
+    pub archived_by: Address,
+    pub was_listed: bool,
+    pub was_readonly: bool,
+}
+
+/// Listing filter for `list_boards_by_filter` / `count_boards_by_filter`.
+/// Each filter has its own chunked index, updated whenever a board changes.
+#[contracttype]
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum BoardFilter {
+    Listed,
+    Unlisted,
+    Private,
+    InCommunity(u64),
+    Standalone,
+    Archived,
+}
+
 /// Thread metadata
 #[contracttype]
 #[derive(Clone)]
@@ -112,6 +297,28 @@ pub struct BoardConfig {
     pub reply_chunk_size: u32,
 }
 
+/// Thread list ordering for the board view
+#[contracttype]
+#[derive(Clone, Copy, PartialEq, Eq)]
+#[repr(u32)]
+pub enum ThreadSort {
+    Hot = 0,
+    New = 1,
+    Top = 2,
+    Active = 3,
+    Controversial = 4,
+}
+
+/// Top-level reply ordering on the thread view (mirror of the content contract's)
+#[contracttype]
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+#[repr(u32)]
+pub enum ReplySort {
+    Oldest = 0,
+    Newest = 1,
+    Top = 2,
+}
+
 /// Reply metadata from content contract
 #[contracttype]
 #[derive(Clone)]
@@ -141,6 +348,18 @@ pub enum Role {
     Owner = 4,
 }
 
+/// Viewer permissions from permissions contract
+#[contracttype]
+#[derive(Clone)]
+pub struct PermissionSet {
+    pub role: Role,
+    pub can_view: bool,
+    pub can_post: bool,
+    pub can_moderate: bool,
+    pub can_admin: bool,
+    pub is_banned: bool,
+}
+
 /// Vote direction from voting contract
 #[contracttype]
 #[derive(Clone, Copy, PartialEq, Eq)]
@@ -151,6 +370,317 @@ pub enum VoteDirection {
     Down = 2,
 }
 
+/// Voting configuration from voting contract
+#[contracttype]
+#[derive(Clone)]
+pub struct VotingConfig {
+    pub enabled: bool,
+    pub allow_downvotes: bool,
+    pub karma_enabled: bool,
+    pub karma_multiplier: u32,
+}
+
+/// Thread status filter for moderation views
+#[contracttype]
+#[derive(Clone, Copy, PartialEq, Eq)]
+#[repr(u32)]
+pub enum ThreadFilter {
+    All = 0,
+    Hidden = 1,
+    Locked = 2,
+    Deleted = 3,
+    Pinned = 4,
+    Flagged = 5,
+}
+
+/// Maximum number of thread IDs scanned by a single filtered listing
+const MAX_FILTER_SCAN: u64 = 100;
+
+/// Board IDs covered by one chunk of a listing index
+const BOARD_INDEX_CHUNK: u64 = 64;
+
+/// Boards from before the listing indexes that one listing call checks directly
+const MAX_LEGACY_BOARD_SCAN: u64 = 50;
+
+/// Most boards the home page features at once
+const MAX_FEATURED_BOARDS: u32 = 10;
+
+/// Fixed-point scale of hot scores (1000 = one order of magnitude of votes)
+const HOT_SCALE: u64 = 1000;
+
+/// Thread age, in seconds, that offsets a tenfold difference in score (12.5 hours)
+const HOT_DECAY_SECONDS: u64 = 45_000;
+
+/// Total votes a thread needs to be ranked under Controversial, unless the board sets its own
+const DEFAULT_CONTROVERSIAL_MIN_VOTES: u32 = 5;
+
+/// Nesting shown inline before deeper replies move behind "Continue this thread"
+const DEFAULT_COLLAPSE_DEPTH: u32 = 4;
+
+/// Replies scoring below this start collapsed, unless the board sets its own
+const DEFAULT_COLLAPSE_SCORE: i32 = -3;
+
+/// Title search only looks at this many of the most recent threads
+const SEARCH_SCAN_WINDOW: u64 = 500;
+
+/// Longest search query accepted, in bytes
+const MAX_SEARCH_QUERY_LEN: usize = 64;
+
+/// Title bytes compared against a search query
+const MAX_SEARCH_TITLE_LEN: usize = 256;
+
+/// Search results shown per page
+const SEARCH_PAGE_SIZE: u32 = 20;
+
+/// Default number of bytes quoted into a reply form
+const DEFAULT_QUOTE_LIMIT: u32 = 1000;
+
+/// Upper bound for a board's quote limit
+const MAX_QUOTE_LIMIT: u32 = 10_000;
+
+/// Default byte budget for the initial thread page before its body is cut short
+const DEFAULT_RENDER_BUDGET: u32 = 12_288;
+
+/// Bounds for a board's thread render budget. The minimum leaves room for a
+/// body excerpt next to the page chrome.
+const MIN_RENDER_BUDGET: u32 = 4096;
+const MAX_RENDER_BUDGET: u32 = 65_536;
+
+/// Rough size of the thread page after the body (actions, votes, mod controls)
+const THREAD_TAIL_BYTES: u32 = 2048;
+
+/// Default maximum thread body size in bytes
+const DEFAULT_MAX_THREAD_BODY_BYTES: u32 = 16_384;
+
+/// Default maximum reply size in bytes
+const DEFAULT_MAX_REPLY_BYTES: u32 = 8_192;
+
+/// Upper bound for post size limits (the content contract stores at most 16 KB per post)
+const MAX_POST_BYTES: u32 = 16_384;
+
+/// Longest per-thread reply cooldown a board can set (1 hour)
+const MAX_REPLY_COOLDOWN: u64 = 3_600;
+
+/// Moderation log entries per page (further pages load as continuations)
+const MODLOG_PAGE_SIZE: u64 = 25;
+
+/// Display glyphs for the reactions the voting contract allows
+const REACTION_GLYPHS: [(&str, &str); 8] = [
+    ("thumbsup", "👍"),
+    ("tada", "🎉"),
+    ("heart", "❤️"),
+    ("eyes", "👀"),
+    ("laugh", "😄"),
+    ("rocket", "🚀"),
+    ("thinking", "🤔"),
+    ("sad", "😢"),
+];
+
+/// Mentions listed on the /mentions page
+const MENTIONS_PAGE_SIZE: u32 = 20;
+
+/// Notifications shown on the notifications page
+const NOTIFICATIONS_PAGE_SIZE: u32 = 50;
+
+/// Recent threads indexed per user and board; older entries are dropped
+const MAX_USER_THREADS: u32 = 50;
+
+/// Thread IDs per chunk of a user's full thread log
+const USER_LOG_CHUNK: u64 = 50;
+
+/// Entries shown on a user's activity page
+const ACTIVITY_PAGE_SIZE: u32 = 30;
+
+/// Bytes of a reply shown as its excerpt on the activity page
+const ACTIVITY_EXCERPT_LEN: u32 = 140;
+
+/// Days shown on a board's stats page
+const STATS_PAGE_DAYS: u64 = 30;
+
+/// Longest range `get_stats_range` returns, in days
+const MAX_STATS_RANGE_DAYS: u64 = 90;
+
+/// Width of the longest bar on the stats page, in characters
+const STATS_BAR_WIDTH: u32 = 20;
+
+/// Ledgers closed per day (about one every five seconds)
+const LEDGERS_PER_DAY: u32 = 17_280;
+
+/// Entries are only bumped once their remaining TTL drops below this many ledgers
+const TTL_THRESHOLD: u32 = 30 * LEDGERS_PER_DAY;
+
+/// Bumped entries live for this many more ledgers
+const TTL_EXTEND_TO: u32 = 120 * LEDGERS_PER_DAY;
+
+/// Most threads `extend_board_ttl` bumps per call
+const MAX_TTL_BATCH: u64 = 50;
+
+/// Users listed on the /leaderboard page
+const LEADERBOARD_SIZE: u32 = 25;
+
+/// Threads listed by the `/export/board` JSON route
+const EXPORT_THREAD_LIMIT: u64 = 50;
+
+/// Most replies included in a `/export/t/{tid}/md` document. Each one reads its
+/// metadata, body and author's display name, so this keeps the export inside a
+/// single call's ledger read limit. Also the page size for reply listings.
+const MAX_EXPORT_REPLIES: u32 = 30;
+
+/// Longest window a `/digest/{days}` page covers, in days
+const MAX_DIGEST_DAYS: u64 = 30;
+
+/// Window used by `/digest` when no day count is given
+const DEFAULT_DIGEST_DAYS: u64 = 7;
+
+/// Threads listed in a digest
+const DIGEST_THREADS: u32 = 20;
+
+/// Largest per-board CSS override, in bytes
+const MAX_CUSTOM_CSS_BYTES: u32 = 8192;
+
+/// Most tags a board can define
+const MAX_TAGS: u32 = 50;
+
+/// Longest tag name, in bytes
+const MAX_TAG_NAME_LEN: usize = 24;
+
+/// Most tags on a single thread
+const MAX_THREAD_TAGS: u32 = 5;
+
+/// Threads kept in each tag's index; the oldest taggings drop off beyond this
+const MAX_TAG_INDEX: u32 = 200;
+
+/// Threads listed on a /tag/{id} page
+const TAG_PAGE_THREADS: u32 = 50;
+
+/// Most thread templates a board can hold
+const MAX_TEMPLATES: u32 = 10;
+
+/// Longest template name, in bytes
+const MAX_TEMPLATE_NAME_LEN: u32 = 32;
+
+/// Largest template body, in bytes
+const MAX_TEMPLATE_BYTES: u32 = 4096;
+
+/// Most wiki pages a board can hold
+const MAX_WIKI_PAGES: u32 = 50;
+
+/// Longest wiki page slug, in bytes
+const MAX_WIKI_SLUG_LEN: usize = 32;
+
+/// Longest wiki page title, in bytes
+const MAX_WIKI_TITLE_LEN: u32 = 100;
+
+/// Largest wiki page body, in bytes
+const MAX_WIKI_PAGE_BYTES: u32 = 16_384;
+
+/// Most hosts in a board's image allowlist
+const MAX_IMAGE_HOSTS: u32 = 20;
+
+/// Longest allowlisted image host, in bytes
+const MAX_IMAGE_HOST_LEN: usize = 64;
+
+/// Most terms in a board's blocklist
+const MAX_BLOCKED_TERMS: u32 = 100;
+
+/// Longest blocked term, in bytes
+const MAX_BLOCKED_TERM_LEN: usize = 64;
+
+/// Bytes of text lowercased and searched at a time when checking the blocklist
+const BLOCKLIST_SCAN_WINDOW: usize = 4096;
+
+/// Locales a board can declare, with the date order each one uses
+const BOARD_LOCALES: [(&str, DateStyle); 16] = [
+    ("en", DateStyle::Iso),
+    ("cs", DateStyle::DayMonthYear),
+    ("de", DateStyle::DayMonthYear),
+    ("es", DateStyle::Iso),
+    ("fi", DateStyle::DayMonthYear),
+    ("fr", DateStyle::Iso),
+    ("it", DateStyle::Iso),
+    ("ja", DateStyle::Japanese),
+    ("ko", DateStyle::Iso),
+    ("nb", DateStyle::DayMonthYear),
+    ("nl", DateStyle::Iso),
+    ("pl", DateStyle::DayMonthYear),
+    ("pt", DateStyle::Iso),
+    ("ru", DateStyle::DayMonthYear),
+    ("tr", DateStyle::DayMonthYear),
+    ("zh", DateStyle::Iso),
+];
+
+/// Board-local display names: 3-20 ASCII letters, digits or underscores
+const MIN_DISPLAY_NAME_LEN: usize = 3;
+const MAX_DISPLAY_NAME_LEN: usize = 20;
+
+/// Seconds before a display name can be changed again (7 days)
+const DISPLAY_NAME_COOLDOWN: u64 = 7 * 24 * 60 * 60;
+
+/// Most threads one user can subscribe to on a board
+const MAX_SUBSCRIPTIONS: u32 = 100;
+
+/// Addresses per chunk of a thread's subscriber index
+const SUBSCRIBER_CHUNK: u32 = 50;
+
+/// Most addresses one user can block
+const MAX_BLOCKED_USERS: u32 = 200;
+
+/// Most threads one user can save, across all boards
+const MAX_SAVED_THREADS: u32 = 200;
+
+/// Threads a user's read marks remember per board, beyond the board-wide mark
+const MAX_READ_THREADS: u32 = 100;
+
+/// Saved threads listed per page of the /saved route
+const SAVED_PAGE_SIZE: u32 = 20;
+
+/// Opens the collapsed wrapper around a blocked author's content (closed with `</details>`)
+const BLOCKED_CONTENT_SUMMARY: &str = concat!(
+    "<details class=\"blocked-content\">",
+    "<summary>Content from a blocked user — show anyway</summary>\n"
+);
+
+/// Opens the collapsed block around a low-scoring reply; the score and `) — show` follow
+const LOW_SCORE_SUMMARY_START: &str =
+    "<details class=\"low-score\"><summary>comment score below threshold (";
+
+/// Opens the collapsed block around a reply flagged past half the auto-hide threshold
+const FLAGGED_CONTENT_SUMMARY: &str = concat!(
+    "<details class=\"heavily-flagged\">",
+    "<summary>reply flagged for review — show</summary>\n"
+);
+
+/// Stylesheet served when no theme contract is configured
+const FALLBACK_CSS: &str = concat!(
+    "body{font-family:system-ui,sans-serif;max-width:960px;margin:0 auto;padding:1rem;",
+    "line-height:1.5;color:#222;background:#fff}",
+    "a{color:#0645ad}",
+    ".thread-body,.reply{padding:0.5rem 0;border-bottom:1px solid #ddd}",
+    ".badge{font-size:0.8em;padding:0 0.4em;border-radius:3px;background:#eee}",
+    ".content-unavailable{color:#888;font-style:italic}\n"
+);
+
+/// Placeholder for post bodies when the board has no content contract
+const CONTENT_UNAVAILABLE: &str = "<span class=\"content-unavailable\">Content unavailable</span>";
+
+/// Longest theme name the registry accepts
+const MAX_THEME_NAME_LEN: usize = 32;
+
+/// Draft key used by the create-thread form
+const THREAD_DRAFT_KEY: &str = "thread";
+
+/// Longest draft title the content contract accepts
+const MAX_DRAFT_TITLE_LEN: usize = 256;
+
+/// Longest stored string echoed back into an HTML attribute (e.g. the edit-title field)
+const MAX_ATTR_LEN: usize = 512;
+
+/// Thread titles longer than this many characters are shortened in breadcrumbs
+const MAX_BREADCRUMB_TITLE_CHARS: usize = 40;
+
+/// Markup between breadcrumb segments
+const BREADCRUMB_SEPARATOR: &str = "<span class=\"crumb-sep\">›</span>";
+
 /// Vote tally from voting contract
 #[contracttype]
 #[derive(Clone)]
@@ -161,6 +691,21 @@ pub struct VoteTally {
     pub first_vote_at: u64,
 }
 
+/// Thread poll from voting contract
+#[contracttype]
+#[derive(Clone)]
+pub struct Poll {
+    pub id: u64,
+    pub board_id: u64,
+    pub thread_id: u64,
+    pub question: String,
+    pub options: Vec<String>,
+    pub counts: Vec<u32>,
+    pub multi: bool,
+    pub closes_at: Option<u64>,
+    pub voters: u32,
+}
+
 /// Flair definition for categorizing threads
 #[contracttype]
 #[derive(Clone)]
@@ -174,6 +719,95 @@ pub struct FlairDef {
     pub enabled: bool,    // Whether flair is active
 }
 
+/// A lightweight label a thread can carry alongside its flair
+#[contracttype]
+#[derive(Clone, Debug, PartialEq)]
+pub struct TagDef {
+    pub id: u32,
+    pub name: String, // lowercase letters, digits and dashes, max MAX_TAG_NAME_LEN
+}
+
+/// Starting text for new threads, e.g. a bug report outline
+#[contracttype]
+#[derive(Clone)]
+pub struct ThreadTemplate {
+    pub id: u32,
+    pub name: String, // max 32 chars
+    pub body: String, // max MAX_TEMPLATE_BYTES
+}
+
+/// A board wiki page (e.g. an FAQ), edited by trusted members
+#[contracttype]
+#[derive(Clone)]
+pub struct WikiPage {
+    pub slug: String,  // lowercase letters, digits and dashes
+    pub title: String, // max MAX_WIKI_TITLE_LEN
+    pub body: String,  // markdown, max MAX_WIKI_PAGE_BYTES
+    pub editor: Address,
+    pub updated_at: u64,
+}
+
+/// Wiki index entry, kept apart from the page so listing doesn't load bodies
+#[contracttype]
+#[derive(Clone)]
+pub struct WikiPageInfo {
+    pub slug: String,
+    pub title: String,
+    pub editor: Address,
+    pub updated_at: u64,
+}
+
+/// Inline image rules for a board. With images allowed and no hosts listed,
+/// posts are stored as written; otherwise the content contract turns images
+/// that aren't allowed into plain links when a post is created or edited.
+#[contracttype]
+#[derive(Clone, Debug, PartialEq)]
+pub struct ImagePolicy {
+    pub allow_images: bool,
+    pub allowed_hosts: Vec<String>, // lowercase host names, exact match
+}
+
+/// What a user has read on a board: everything updated before `board_read_at`,
+/// plus threads they marked read since. Thread cards show a "new" dot for
+/// threads updated after both.
+#[contracttype]
+#[derive(Clone)]
+pub struct ReadMarks {
+    pub board_read_at: u64,
+    /// (thread_id, read_at), most recently read first, max MAX_READ_THREADS
+    pub threads: Vec<(u64, u64)>,
+}
+
+/// How dates are written on a board, picked by its locale
+#[derive(Clone, Copy, Debug, PartialEq)]
+enum DateStyle {
+    /// `2025-03-01 14:00 UTC`
+    Iso,
+    /// `01.03.2025 14:00 UTC`
+    DayMonthYear,
+    /// `2025年03月01日 14:00 UTC`
+    Japanese,
+}
+
+/// Reply window for a time-boxed thread (e.g. an AMA). Outside the window the
+/// thread behaves as locked; nothing runs at the boundaries, the ledger time is
+/// checked whenever the thread is read.
+#[contracttype]
+#[derive(Clone, Debug, PartialEq)]
+pub struct ThreadSchedule {
+    pub open_at: Option<u64>,
+    pub lock_at: Option<u64>,
+}
+
+/// Where the current ledger time falls in a thread's schedule
+#[derive(Clone, Copy, PartialEq)]
+enum ReplyWindow {
+    Open,
+    /// Replies open at this timestamp
+    NotYetOpen(u64),
+    Closed,
+}
+
 /// Crosspost reference from content contract
 #[contracttype]
 #[derive(Clone)]
@@ -186,6 +820,94 @@ pub struct CrosspostRef {
     pub crossposted_at: u64,
 }
 
+/// Tombstone of a removed crosspost from content contract
+#[contracttype]
+#[derive(Clone)]
+pub struct RemovedCrosspost {
+    pub original_board_id: u64,
+    pub original_thread_id: u64,
+    pub removed_by: Address,
+    pub removed_at: u64,
+}
+
+/// Crosspost destination from content contract (stored on the original thread)
+#[contracttype]
+#[derive(Clone)]
+pub struct CrosspostLocation {
+    pub board_id: u64,
+    pub thread_id: u64,
+    pub created_at: u64,
+}
+
+/// Mention record from content contract
+#[contracttype]
+#[derive(Clone)]
+pub struct Mention {
+    pub board_id: u64,
+    pub thread_id: u64,
+    pub reply_id: Option<u64>,
+    pub author: Address,
+    pub created_at: u64,
+}
+
+/// Moderation log entry from the admin contract (matches boards-admin ModAction)
+#[contracttype]
+#[derive(Clone)]
+pub struct ModAction {
+    pub actor: Address,
+    pub board_id: u64,
+    pub action: Symbol,
+    pub target_thread: Option<u64>,
+    pub target_reply: Option<u64>,
+    pub target_user: Option<Address>,
+    pub timestamp: u64,
+    pub note: Option<String>,
+}
+
+/// One entry in a user's post history on a board
+#[contracttype]
+#[derive(Clone)]
+pub struct ActivityItem {
+    pub thread_id: u64,
+    /// None for the thread itself
+    pub reply_id: Option<u64>,
+    pub created_at: u64,
+    /// Hidden or deleted, or posted in a thread that is
+    pub is_hidden: bool,
+}
+
+/// Notification record from content contract
+#[contracttype]
+#[derive(Clone)]
+pub struct Notification {
+    pub id: u64,
+    pub kind: Symbol,
+    pub board_id: u64,
+    pub thread_id: u64,
+    pub reply_id: Option<u64>,
+    pub actor: Address,
+    pub created_at: u64,
+}
+
+/// Previous post body from content contract
+#[contracttype]
+#[derive(Clone)]
+pub struct Revision {
+    pub body: Bytes,
+    pub editor: Address,
+    pub replaced_at: u64,
+}
+
+/// Saved draft from content contract
+#[contracttype]
+#[derive(Clone)]
+pub struct Draft {
+    pub key: Symbol,
+    pub title: String,
+    pub body: Bytes,
+    pub saved_at: u64,
+}
+
 /// Community info for navigation (minimal struct for cross-contract calls)
 #[contracttype]
 #[derive(Clone)]
@@ -195,6 +917,34 @@ pub struct CommunityInfo {
     pub display_name: String,
 }
 
+/// A display name claimed on one board, with when it was claimed (for the rename cooldown)
+#[contracttype]
+#[derive(Clone, Debug, PartialEq)]
+pub struct DisplayNameClaim {
+    pub name: String,
+    pub claimed_at: u64,
+}
+
+/// Color scheme a viewer picked
+#[contracttype]
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+#[repr(u32)]
+pub enum ColorScheme {
+    /// Follow the theme default (and the viewer's system setting)
+    Auto = 0,
+    /// Always use the light palette
+    Light = 1,
+    /// Always use the dark palette
+    Dark = 2,
+}
+
+/// A viewer's display preferences, shared by every board
+#[contracttype]
+#[derive(Clone, Debug, PartialEq)]
+pub struct DisplayPref {
+    pub scheme: ColorScheme,
+}
+
 #[contract]
 pub struct BoardsBoard;
 
@@ -474,6 +1224,30 @@ impl BoardsBoard {
             .get(&BoardKey::BoardCount)
             .unwrap_or(0);
 
+        // Registry-side name rules and spam limits (per-account cap and cooldown). Their
+        // error codes pass through; a registry that can't be reached or predates them
+        // doesn't block.
+        let name_args: Vec<Val> =
+            Vec::from_array(&env, [board_id.into_val(&env), name.clone().into_val(&env)]);
+        let record_args: Vec<Val> = Vec::from_array(
+            &env,
+            [board_id.into_val(&env), caller.clone().into_val(&env)],
+        );
+        for (func, args) in [
+            ("register_board_name", name_args),
+            ("record_board_creation", record_args),
+        ] {
+            if let Err(Ok(err)) = env.try_invoke_contract::<(), soroban_sdk::Error>(
+                &registry,
+                &Symbol::new(&env, func),
+                args,
+            ) {
+                if err.is_type(soroban_sdk::xdr::ScErrorType::Contract) {
+                    panic_with_error!(&env, err);
+                }
+            }
+        }
+
         // Generate or validate slug
         let final_slug = if let Some(explicit_slug) = slug {
             // Validate explicit slug
@@ -542,6 +1316,13 @@ impl BoardsBoard {
             &BoardKey::BoardCreatedAt(board_id),
             &env.ledger().timestamp(),
         );
+        // Older boards join the listing indexes through migrate_index_boards
+        if !env.storage().instance().has(&BoardKey::BoardsIndexedFrom) {
+            env.storage()
+                .instance()
+                .set(&BoardKey::BoardsIndexedFrom, &board_id);
+        }
+        Self::reindex_board(&env, board_id, &Vec::new(&env));
 
         // Increment board count
         env.storage()
@@ -663,20 +1444,52 @@ impl BoardsBoard {
             .set(&BoardKey::BoardBySlug(new_slug), &board_id);
     }
 
-    /// Remove board from standalone slug index (called when board joins a community).
-    pub fn remove_standalone_slug_index(env: Env, board_id: u64, caller: Address) {
+    /// Set a board's display name. Only callable by the registry, which validates the
+    /// name and keeps the name index (see the registry's `rename_board`).
+    pub fn set_board_name(env: Env, board_id: u64, name: String, caller: Address) {
         caller.require_auth();
 
-        // Verify caller is community contract (via registry lookup)
-        let registry: Address = env
+        let registry: Option<Address> = env.storage().instance().get(&BoardKey::Registry);
+        if registry != Some(caller) {
+            panic!("Only registry can set board name");
+        }
+
+        let mut board: BoardMeta = env
             .storage()
-            .instance()
-            .get(&BoardKey::Registry)
-            .expect("Contract not initialized");
+            .persistent()
+            .get(&BoardKey::Board(board_id))
+            .expect("Board not found");
+        board.name = name.clone();
+        env.storage()
+            .persistent()
+            .set(&BoardKey::Board(board_id), &board);
 
-        let community_alias = Symbol::new(&env, "community");
-        let args: Vec<Val> = Vec::from_array(&env, [community_alias.into_val(&env)]);
-        let community: Option<Address> = env
+        if let Some(mut config) = env
+            .storage()
+            .persistent()
+            .get::<_, BoardConfig>(&BoardKey::BoardConfig(board_id))
+        {
+            config.name = name;
+            env.storage()
+                .persistent()
+                .set(&BoardKey::BoardConfig(board_id), &config);
+        }
+    }
+
+    /// Remove board from standalone slug index (called when board joins a community).
+    pub fn remove_standalone_slug_index(env: Env, board_id: u64, caller: Address) {
+        caller.require_auth();
+
+        // Verify caller is community contract (via registry lookup)
+        let registry: Address = env
+            .storage()
+            .instance()
+            .get(&BoardKey::Registry)
+            .expect("Contract not initialized");
+
+        let community_alias = Symbol::new(&env, "community");
+        let args: Vec<Val> = Vec::from_array(&env, [community_alias.into_val(&env)]);
+        let community: Option<Address> = env
             .try_invoke_contract::<Option<Address>, soroban_sdk::Error>(
                 &registry,
                 &Symbol::new(&env, "get_contract_by_alias"),
@@ -820,6 +1633,49 @@ impl BoardsBoard {
         env.storage()
             .persistent()
             .remove(&BoardKey::BoardCommunitySlug(board_id));
+
+        // The board is standalone again
+        let before = Self::board_filters(&env, board_id);
+        env.storage()
+            .persistent()
+            .remove(&BoardKey::BoardCommunityId(board_id));
+        Self::reindex_board(&env, board_id, &before);
+    }
+
+    /// Record the community a board belongs to (called when the board is added
+    /// to a community, alongside `set_board_community_slug`). Keeps the
+    /// InCommunity/Standalone listing indexes current. Only callable by the
+    /// community contract.
+    pub fn set_board_community_id(env: Env, board_id: u64, community_id: u64, caller: Address) {
+        caller.require_auth();
+
+        let registry: Address = env
+            .storage()
+            .instance()
+            .get(&BoardKey::Registry)
+            .expect("Contract not initialized");
+
+        let community_alias = Symbol::new(&env, "community");
+        let args: Vec<Val> = Vec::from_array(&env, [community_alias.into_val(&env)]);
+        let community: Option<Address> = env
+            .try_invoke_contract::<Option<Address>, soroban_sdk::Error>(
+                &registry,
+                &Symbol::new(&env, "get_contract_by_alias"),
+                args,
+            )
+            .ok()
+            .and_then(|r| r.ok())
+            .flatten();
+
+        if community.map(|c| c != caller).unwrap_or(true) {
+            panic!("Only community contract can set board community");
+        }
+
+        let before = Self::board_filters(&env, board_id);
+        env.storage()
+            .persistent()
+            .set(&BoardKey::BoardCommunityId(board_id), &community_id);
+        Self::reindex_board(&env, board_id, &before);
     }
 
     /// Get the community slug for a board (if it belongs to a community).
@@ -922,6 +1778,15 @@ impl BoardsBoard {
         boards
     }
 
+    /// Ids and names of boards [start, start + limit), for the registry's name index
+    pub fn list_board_names(env: Env, start: u64, limit: u64) -> Vec<(u64, String)> {
+        let mut names = Vec::new(&env);
+        for board in Self::list_boards(env.clone(), start, limit).iter() {
+            names.push_back((board.id, board.name));
+        }
+        names
+    }
+
     /// Get total board count
     pub fn board_count(env: Env) -> u64 {
         env.storage()
@@ -930,6 +1795,358 @@ impl BoardsBoard {
             .unwrap_or(0)
     }
 
+    /// List boards matching a filter, newest first. Reads one page of the
+    /// filter's chunked index; boards that predate the indexes are checked
+    /// directly, a bounded number per call, until `migrate_index_boards` has run.
+    pub fn list_boards_by_filter(
+        env: Env,
+        start: u64,
+        limit: u64,
+        filter: BoardFilter,
+    ) -> Vec<BoardMeta> {
+        let mut boards = Vec::new(&env);
+        let mut skip = start;
+
+        let chunks = Self::board_index_chunks(&env, &filter);
+        for (chunk, size) in chunks.iter().rev() {
+            if boards.len() as u64 >= limit {
+                return boards;
+            }
+            if skip >= size as u64 {
+                skip -= size as u64;
+                continue;
+            }
+            let ids = Self::board_index_chunk(&env, &filter, chunk);
+            for board_id in ids.iter().rev().skip(skip as usize) {
+                if boards.len() as u64 >= limit {
+                    break;
+                }
+                if let Some(board) = env.storage().persistent().get(&BoardKey::Board(board_id)) {
+                    boards.push_back(board);
+                }
+            }
+            skip = 0;
+        }
+
+        let indexed_from = Self::boards_indexed_from(&env);
+        let oldest = indexed_from.saturating_sub(MAX_LEGACY_BOARD_SCAN);
+        for board_id in (oldest..indexed_from).rev() {
+            if boards.len() as u64 >= limit {
+                break;
+            }
+            let community_id = Self::lookup_board_community_id(&env, board_id);
+            if !Self::filters_for(&env, board_id, community_id).contains(&filter) {
+                continue;
+            }
+            if skip > 0 {
+                skip -= 1;
+                continue;
+            }
+            if let Some(board) = env.storage().persistent().get(&BoardKey::Board(board_id)) {
+                boards.push_back(board);
+            }
+        }
+
+        boards
+    }
+
+    /// Count boards matching a filter. Boards that predate the listing indexes
+    /// are counted once `migrate_index_boards` has run.
+    pub fn count_boards_by_filter(env: Env, filter: BoardFilter) -> u64 {
+        Self::board_index_chunks(&env, &filter)
+            .iter()
+            .map(|(_, size)| size as u64)
+            .sum()
+    }
+
+    /// Migration function: add the newest `limit` boards that predate the
+    /// listing indexes to them. Community membership is read from the community
+    /// contract. Returns how many older boards are left. Only callable by
+    /// registry admins.
+    pub fn migrate_index_boards(env: Env, limit: u64, caller: Address) -> u64 {
+        caller.require_auth();
+        Self::require_registry_admin(&env, &caller, "Only registry admin can index boards");
+
+        let indexed_from = Self::boards_indexed_from(&env);
+        let remaining = indexed_from.saturating_sub(limit);
+        env.storage()
+            .instance()
+            .set(&BoardKey::BoardsIndexedFrom, &remaining);
+
+        for board_id in (remaining..indexed_from).rev() {
+            if !env.storage().persistent().has(&BoardKey::Board(board_id)) {
+                continue;
+            }
+            let key = BoardKey::BoardCommunityId(board_id);
+            if !env.storage().persistent().has(&key) {
+                if let Some(community_id) = Self::lookup_board_community_id(&env, board_id) {
+                    env.storage().persistent().set(&key, &community_id);
+                }
+            }
+            // Indexing is idempotent, so an empty "before" is safe to re-run
+            Self::reindex_board(&env, board_id, &Vec::new(&env));
+        }
+        remaining
+    }
+
+    /// First board ID in the listing indexes. Until a board is created with
+    /// the indexes in place, every existing board predates them.
+    fn boards_indexed_from(env: &Env) -> u64 {
+        env.storage()
+            .instance()
+            .get(&BoardKey::BoardsIndexedFrom)
+            .unwrap_or_else(|| Self::board_count(env.clone()))
+    }
+
+    /// Community a board belongs to, asking the community contract when the
+    /// board predates `set_board_community_id`
+    fn lookup_board_community_id(env: &Env, board_id: u64) -> Option<u64> {
+        if let Some(community_id) = env
+            .storage()
+            .persistent()
+            .get(&BoardKey::BoardCommunityId(board_id))
+        {
+            return Some(community_id);
+        }
+        let community: Address = env.storage().instance().get(&BoardKey::Community)?;
+        env.try_invoke_contract::<Option<u64>, soroban_sdk::Error>(
+            &community,
+            &Symbol::new(env, "get_board_community"),
+            Vec::from_array(env, [board_id.into_val(env)]),
+        )
+        .ok()
+        .and_then(|r| r.ok())
+        .flatten()
+    }
+
+    /// Non-empty chunks of a filter's listing index
+    fn board_index_chunks(env: &Env, filter: &BoardFilter) -> Vec<(u64, u32)> {
+        env.storage()
+            .persistent()
+            .get(&BoardKey::BoardIndexChunks(filter.clone()))
+            .unwrap_or_else(|| Vec::new(env))
+    }
+
+    /// Board IDs in one chunk of a filter's listing index
+    fn board_index_chunk(env: &Env, filter: &BoardFilter, chunk: u64) -> Vec<u64> {
+        env.storage()
+            .persistent()
+            .get(&BoardKey::BoardIndex(filter.clone(), chunk))
+            .unwrap_or_else(|| Vec::new(env))
+    }
+
+    /// Add a board to or drop it from a filter's listing index
+    fn update_board_index(env: &Env, filter: &BoardFilter, board_id: u64, add: bool) {
+        let chunk = board_id / BOARD_INDEX_CHUNK;
+        let mut ids = Self::board_index_chunk(env, filter, chunk);
+        match (ids.binary_search(board_id), add) {
+            (Err(pos), true) => ids.insert(pos, board_id),
+            (Ok(pos), false) => {
+                ids.remove(pos);
+            }
+            _ => return,
+        }
+        let key = BoardKey::BoardIndex(filter.clone(), chunk);
+        if ids.is_empty() {
+            env.storage().persistent().remove(&key);
+        } else {
+            env.storage().persistent().set(&key, &ids);
+        }
+
+        // Keep the chunk list in step; new boards land in the last chunk
+        let mut chunks = Self::board_index_chunks(env, filter);
+        let mut pos = chunks.len();
+        while pos > 0 && chunks.get_unchecked(pos - 1).0 >= chunk {
+            pos -= 1;
+        }
+        let found = pos < chunks.len() && chunks.get_unchecked(pos).0 == chunk;
+        match (found, ids.len()) {
+            (true, 0) => {
+                chunks.remove(pos);
+            }
+            (true, size) => chunks.set(pos, (chunk, size)),
+            (false, 0) => return,
+            (false, size) => chunks.insert(pos, (chunk, size)),
+        }
+        env.storage()
+            .persistent()
+            .set(&BoardKey::BoardIndexChunks(filter.clone()), &chunks);
+    }
+
+    /// Filters a board currently matches
+    fn board_filters(env: &Env, board_id: u64) -> Vec<BoardFilter> {
+        let community_id = env
+            .storage()
+            .persistent()
+            .get(&BoardKey::BoardCommunityId(board_id));
+        Self::filters_for(env, board_id, community_id)
+    }
+
+    /// Filters a board matches, given the community it belongs to
+    fn filters_for(env: &Env, board_id: u64, community_id: Option<u64>) -> Vec<BoardFilter> {
+        let mut filters = Vec::new(env);
+        let Some(meta) = env
+            .storage()
+            .persistent()
+            .get::<_, BoardMeta>(&BoardKey::Board(board_id))
+        else {
+            return filters;
+        };
+
+        filters.push_back(if meta.is_listed {
+            BoardFilter::Listed
+        } else {
+            BoardFilter::Unlisted
+        });
+        if meta.is_private {
+            filters.push_back(BoardFilter::Private);
+        }
+        match community_id {
+            Some(community_id) => filters.push_back(BoardFilter::InCommunity(community_id)),
+            None => filters.push_back(BoardFilter::Standalone),
+        }
+        if Self::is_archived(env.clone(), board_id) {
+            filters.push_back(BoardFilter::Archived);
+        }
+        filters
+    }
+
+    /// Move a board between listing indexes after a change: drop it from the
+    /// filters it matched `before` but no longer does, add it to the new ones
+    fn reindex_board(env: &Env, board_id: u64, before: &Vec<BoardFilter>) {
+        let after = Self::board_filters(env, board_id);
+        if !Self::is_featurable(&after) {
+            Self::drop_featured(env, board_id);
+        }
+        // Older boards are listed by a direct check until they are migrated
+        if board_id < Self::boards_indexed_from(env) {
+            return;
+        }
+        for filter in before.iter() {
+            if !after.contains(&filter) {
+                Self::update_board_index(env, &filter, board_id, false);
+            }
+        }
+        for filter in after.iter() {
+            Self::update_board_index(env, &filter, board_id, true);
+        }
+    }
+
+    // =========================================================================
+    // Featured Boards
+    // =========================================================================
+
+    /// Feature a board at the end of the home page's featured list, or unfeature it
+    /// (registry admin only). Only public, listed, unarchived boards can be featured.
+    pub fn set_featured(env: Env, board_id: u64, featured: bool, caller: Address) {
+        caller.require_auth();
+        Self::require_registry_admin(&env, &caller, "Only registry admin can feature boards");
+
+        if !featured {
+            Self::drop_featured(&env, board_id);
+            return;
+        }
+
+        if !env.storage().persistent().has(&BoardKey::Board(board_id)) {
+            panic!("Board not found");
+        }
+        if !Self::is_featurable(&Self::board_filters(&env, board_id)) {
+            panic!("Only public, listed boards can be featured");
+        }
+
+        let mut featured_ids = Self::featured_ids(&env);
+        if featured_ids.contains(board_id) {
+            return;
+        }
+        if featured_ids.len() >= MAX_FEATURED_BOARDS {
+            panic!("Featured list is full");
+        }
+        featured_ids.push_back(board_id);
+        env.storage()
+            .instance()
+            .set(&BoardKey::FeaturedBoards, &featured_ids);
+    }
+
+    /// Move a featured board to a new position in the list (registry admin only).
+    /// Positions past the end move it last.
+    pub fn move_featured(env: Env, board_id: u64, position: u32, caller: Address) {
+        caller.require_auth();
+        Self::require_registry_admin(&env, &caller, "Only registry admin can feature boards");
+
+        let mut featured_ids = Self::featured_ids(&env);
+        let Some(current) = featured_ids.first_index_of(board_id) else {
+            panic!("Board is not featured");
+        };
+        featured_ids.remove(current);
+        featured_ids.insert(position.min(featured_ids.len()), board_id);
+        env.storage()
+            .instance()
+            .set(&BoardKey::FeaturedBoards, &featured_ids);
+    }
+
+    /// Featured boards, in display order
+    pub fn list_featured(env: Env) -> Vec<BoardMeta> {
+        let mut boards = Vec::new(&env);
+        for board_id in Self::featured_ids(&env).iter() {
+            if let Some(meta) = Self::get_board(env.clone(), board_id) {
+                boards.push_back(meta);
+            }
+        }
+        boards
+    }
+
+    /// Check if a board is featured
+    pub fn is_featured(env: Env, board_id: u64) -> bool {
+        Self::featured_ids(&env).contains(board_id)
+    }
+
+    fn featured_ids(env: &Env) -> Vec<u64> {
+        env.storage()
+            .instance()
+            .get(&BoardKey::FeaturedBoards)
+            .unwrap_or_else(|| Vec::new(env))
+    }
+
+    /// Whether a board with these listing filters may stay featured
+    fn is_featurable(filters: &Vec<BoardFilter>) -> bool {
+        filters.contains(&BoardFilter::Listed)
+            && !filters.contains(&BoardFilter::Private)
+            && !filters.contains(&BoardFilter::Archived)
+    }
+
+    fn drop_featured(env: &Env, board_id: u64) {
+        let mut featured_ids = Self::featured_ids(env);
+        if let Some(pos) = featured_ids.first_index_of(board_id) {
+            featured_ids.remove(pos);
+            env.storage()
+                .instance()
+                .set(&BoardKey::FeaturedBoards, &featured_ids);
+        }
+    }
+
+    fn require_registry_admin(env: &Env, caller: &Address, message: &str) {
+        let registry: Address = env
+            .storage()
+            .instance()
+            .get(&BoardKey::Registry)
+            .expect("Contract not initialized");
+
+        let admin_args: Vec<Val> = Vec::from_array(env, [caller.clone().into_val(env)]);
+        let is_admin: bool = env
+            .try_invoke_contract::<bool, soroban_sdk::Error>(
+                &registry,
+                &Symbol::new(env, "is_admin"),
+                admin_args,
+            )
+            .ok()
+            .and_then(|r| r.ok())
+            .unwrap_or(false);
+
+        if !is_admin {
+            panic!("{}", message);
+        }
+    }
+
     /// Count boards created by a user
     pub fn count_user_boards(env: Env, user: Address) -> u32 {
         env.storage()
@@ -971,6 +2188,10 @@ impl BoardsBoard {
             }
         }
 
+        if !is_hex_color(&color) || !is_hex_color(&bg_color) {
+            panic!("Flair colors must be hex (#rgb or #rrggbb)");
+        }
+
         // Get next flair ID
         let flair_id: u32 = env
             .storage()
@@ -1026,6 +2247,10 @@ impl BoardsBoard {
             }
         }
 
+        if !is_hex_color(&flair.color) || !is_hex_color(&flair.bg_color) {
+            panic!("Flair colors must be hex (#rgb or #rrggbb)");
+        }
+
         let flairs: Vec<FlairDef> = env
             .storage()
             .persistent()
@@ -1227,202 +2452,191 @@ impl BoardsBoard {
 
         thread.flair_id = flair_id;
         thread.updated_at = env.ledger().timestamp();
-        env.storage()
-            .persistent()
-            .set(&BoardKey::BoardThread(board_id, thread_id), &thread);
+        Self::store_thread_meta(&env, board_id, thread_id, &thread);
     }
 
-    // Permission check helpers
+    // =========================================================================
+    // Tag Management Functions
+    // =========================================================================
 
-    /// Check if user can create threads on this board
-    fn check_can_create_thread(env: &Env, board_id: u64, user: &Address) {
-        let permissions: Address = env
+    /// Create a tag (Moderator+ only). Names are 1-24 lowercase letters, digits or
+    /// dashes and unique on the board.
+    pub fn create_tag(env: Env, board_id: u64, name: String, caller: Address) -> u32 {
+        caller.require_auth();
+
+        // Check moderator permissions (only if permissions contract is set)
+        if let Some(permissions) = env
             .storage()
             .instance()
-            .get(&BoardKey::Permissions)
-            .expect("Not initialized");
-
-        let args: Vec<Val> = Vec::from_array(env, [board_id.into_val(env), user.into_val(env)]);
-        let fn_name = Symbol::new(env, "can_create_thread");
-        let can_create: bool = env.invoke_contract(&permissions, &fn_name, args);
-
-        if !can_create {
-            panic!("Not authorized to create threads");
+            .get::<_, Address>(&BoardKey::Permissions)
+        {
+            let args: Vec<Val> =
+                Vec::from_array(&env, [board_id.into_val(&env), caller.into_val(&env)]);
+            let can_moderate: bool =
+                env.invoke_contract(&permissions, &Symbol::new(&env, "can_moderate"), args);
+            if !can_moderate {
+                panic!("Only moderators can create tags");
+            }
         }
-    }
 
-    /// Check if user has moderator permissions
-    fn check_can_moderate(env: &Env, board_id: u64, user: &Address) {
-        let permissions: Address = env
+        if !Self::is_valid_tag_name(&name) {
+            panic!("Tag names must be 1-24 lowercase letters, digits or dashes");
+        }
+        if !env
             .storage()
-            .instance()
-            .get(&BoardKey::Permissions)
-            .expect("Not initialized");
-
-        let args: Vec<Val> = Vec::from_array(env, [board_id.into_val(env), user.into_val(env)]);
-        let fn_name = Symbol::new(env, "can_moderate");
-        let can_moderate: bool = env.invoke_contract(&permissions, &fn_name, args);
-
-        if !can_moderate {
-            panic!("Not authorized to moderate");
+            .persistent()
+            .has(&BoardKey::BoardConfig(board_id))
+        {
+            panic!("Board not found");
         }
-    }
 
-    /// Get board configuration
-    pub fn get_config(env: Env, board_id: u64) -> BoardConfig {
-        env.storage()
-            .persistent()
-            .get(&BoardKey::BoardConfig(board_id))
-            .expect("Board not found")
-    }
-
-    /// Get whether board is listed publicly (for home page)
-    pub fn get_board_listed(env: Env, board_id: u64) -> bool {
-        env.storage()
-            .persistent()
-            .get(&BoardKey::BoardListed(board_id))
-            .unwrap_or(true) // Default to listed for backward compatibility
-    }
-
-    /// Set whether board is listed publicly (owner/admin only)
-    pub fn set_board_listed(env: Env, board_id: u64, is_listed: bool, caller: Address) {
-        caller.require_auth();
-
-        // Check admin permissions (only if permissions contract is set)
-        if env.storage().instance().has(&BoardKey::Permissions) {
-            let permissions: Address = env
-                .storage()
-                .instance()
-                .get(&BoardKey::Permissions)
-                .unwrap();
-            let args: Vec<Val> =
-                Vec::from_array(&env, [board_id.into_val(&env), caller.into_val(&env)]);
-            let fn_name = Symbol::new(&env, "can_admin");
-            let can_admin: bool = env.invoke_contract(&permissions, &fn_name, args);
-            if !can_admin {
-                panic!("Only owner or admin can change listed status");
-            }
+        let mut tags = Self::list_tags(env.clone(), board_id);
+        if tags.len() >= MAX_TAGS {
+            panic!("Board already has the maximum number of tags");
+        }
+        if tags.iter().any(|tag| tag.name == name) {
+            panic!("Tag already exists");
         }
 
+        // Tags are never deleted, so the next ID is the count
+        let tag_id = tags.len();
+        tags.push_back(TagDef { id: tag_id, name });
         env.storage()
             .persistent()
-            .set(&BoardKey::BoardListed(board_id), &is_listed);
-
-        // Update BoardMeta too
-        if let Some(mut meta) = env
-            .storage()
-            .persistent()
-            .get::<_, BoardMeta>(&BoardKey::Board(board_id))
-        {
-            meta.is_listed = is_listed;
-            env.storage()
-                .persistent()
-                .set(&BoardKey::Board(board_id), &meta);
-        }
+            .set(&BoardKey::BoardTagDefs(board_id), &tags);
+        tag_id
     }
 
-    /// Get board creator address
-    pub fn get_creator(env: Env, board_id: u64) -> Option<Address> {
+    /// List all tags for a board
+    pub fn list_tags(env: Env, board_id: u64) -> Vec<TagDef> {
         env.storage()
             .persistent()
-            .get(&BoardKey::BoardCreator(board_id))
+            .get(&BoardKey::BoardTagDefs(board_id))
+            .unwrap_or(Vec::new(&env))
     }
 
-    /// Get board creation timestamp
-    pub fn get_created_at(env: Env, board_id: u64) -> u64 {
+    /// Get the IDs of the tags on a thread
+    pub fn get_thread_tags(env: Env, board_id: u64, thread_id: u64) -> Vec<u32> {
         env.storage()
             .persistent()
-            .get(&BoardKey::BoardCreatedAt(board_id))
-            .unwrap_or(0) // Default to 0 for legacy boards
+            .get(&ThreadKey::ThreadTags(board_id, thread_id))
+            .unwrap_or(Vec::new(&env))
     }
 
-    /// Increment thread count (called by content contract when creating threads)
-    /// This is separate from create_thread to allow external contracts to track thread counts
-    pub fn increment_thread_count(env: Env, board_id: u64) -> u64 {
-        let count: u64 = env
-            .storage()
-            .persistent()
-            .get(&BoardKey::BoardThreadCount(board_id))
-            .unwrap_or(0);
-        let new_count = count + 1;
+    /// Get the threads carrying a tag, most recently tagged first. Only the newest
+    /// MAX_TAG_INDEX taggings are kept.
+    pub fn list_tag_threads(env: Env, board_id: u64, tag_id: u32) -> Vec<u64> {
         env.storage()
             .persistent()
-            .set(&BoardKey::BoardThreadCount(board_id), &new_count);
-
-        // Update BoardMeta too
-        if let Some(mut meta) = env
-            .storage()
-            .persistent()
-            .get::<_, BoardMeta>(&BoardKey::Board(board_id))
-        {
-            meta.thread_count = new_count;
-            env.storage()
-                .persistent()
-                .set(&BoardKey::Board(board_id), &meta);
-        }
-
-        new_count
+            .get(&ThreadKey::TagThreads(board_id, tag_id))
+            .unwrap_or(Vec::new(&env))
     }
 
-    /// Set thread count (admin function for correcting counts)
-    pub fn set_thread_count(env: Env, board_id: u64, count: u64, caller: Address) {
+    /// Replace a thread's tags (thread creator or Moderator+). At most
+    /// MAX_THREAD_TAGS tags; repeated IDs count once. Updates each tag's thread index.
+    pub fn set_thread_tags(
+        env: Env,
+        board_id: u64,
+        thread_id: u64,
+        tag_ids: Vec<u32>,
+        caller: Address,
+    ) {
         caller.require_auth();
 
-        let meta: BoardMeta = env
+        let thread: ThreadMeta = env
             .storage()
             .persistent()
-            .get(&BoardKey::Board(board_id))
-            .expect("Board not found");
-
-        // Allow board creator or registry admin to set thread count
-        let is_registry_admin = if let Some(registry) = env
-            .storage()
-            .instance()
-            .get::<_, Address>(&BoardKey::Registry)
-        {
-            let args: Vec<Val> = Vec::from_array(&env, [caller.clone().into_val(&env)]);
-            env.invoke_contract::<bool>(&registry, &Symbol::new(&env, "is_admin"), args)
-        } else {
-            false
-        };
+            .get(&BoardKey::BoardThread(board_id, thread_id))
+            .expect("Thread not found");
+        if thread.creator != caller {
+            if let Some(permissions) = env
+                .storage()
+                .instance()
+                .get::<_, Address>(&BoardKey::Permissions)
+            {
+                let args: Vec<Val> =
+                    Vec::from_array(&env, [board_id.into_val(&env), caller.into_val(&env)]);
+                let can_moderate: bool =
+                    env.invoke_contract(&permissions, &Symbol::new(&env, "can_moderate"), args);
+                if !can_moderate {
+                    panic!("Only thread creator or moderators can set tags");
+                }
+            }
+        }
 
-        if meta.creator != caller && !is_registry_admin {
-            panic!("Only board creator or registry admin can set thread count");
+        let tag_count = Self::list_tags(env.clone(), board_id).len();
+        let mut new_tags: Vec<u32> = Vec::new(&env);
+        for tag_id in tag_ids.iter() {
+            if tag_id >= tag_count {
+                panic!("Tag not found");
+            }
+            if !new_tags.contains(tag_id) {
+                new_tags.push_back(tag_id);
+            }
+        }
+        if new_tags.len() > MAX_THREAD_TAGS {
+            panic!("Threads can have at most 5 tags");
         }
 
-        env.storage()
-            .persistent()
-            .set(&BoardKey::BoardThreadCount(board_id), &count);
+        // Drop the thread from the indexes of tags it lost, add it to new ones
+        let old_tags = Self::get_thread_tags(env.clone(), board_id, thread_id);
+        for tag_id in old_tags.iter() {
+            if !new_tags.contains(tag_id) {
+                let key = ThreadKey::TagThreads(board_id, tag_id);
+                let mut threads = Self::list_tag_threads(env.clone(), board_id, tag_id);
+                if let Some(index) = threads.first_index_of(thread_id) {
+                    threads.remove(index);
+                    env.storage().persistent().set(&key, &threads);
+                }
+            }
+        }
+        for tag_id in new_tags.iter() {
+            if !old_tags.contains(tag_id) {
+                let mut threads = Self::list_tag_threads(env.clone(), board_id, tag_id);
+                threads.push_front(thread_id);
+                while threads.len() > MAX_TAG_INDEX {
+                    threads.pop_back();
+                }
+                env.storage()
+                    .persistent()
+                    .set(&ThreadKey::TagThreads(board_id, tag_id), &threads);
+            }
+        }
 
-        let mut updated_meta = meta;
-        updated_meta.thread_count = count;
-        env.storage()
-            .persistent()
-            .set(&BoardKey::Board(board_id), &updated_meta);
+        let key = ThreadKey::ThreadTags(board_id, thread_id);
+        if new_tags.is_empty() {
+            env.storage().persistent().remove(&key);
+        } else {
+            env.storage().persistent().set(&key, &new_tags);
+        }
     }
 
-    /// Get reply chunk size for waterfall loading
-    pub fn get_chunk_size(env: Env, board_id: u64) -> u32 {
-        let config: BoardConfig = env
-            .storage()
-            .persistent()
-            .get(&BoardKey::BoardConfig(board_id))
-            .expect("Board not found");
-        // Handle old configs that don't have this field (default to 6)
-        if config.reply_chunk_size == 0 {
-            6
-        } else {
-            config.reply_chunk_size
+    /// Tag names are 1-24 lowercase ASCII letters, digits or dashes
+    fn is_valid_tag_name(name: &String) -> bool {
+        let len = name.len() as usize;
+        if !(1..=MAX_TAG_NAME_LEN).contains(&len) {
+            return false;
         }
+        let mut buf = [0u8; MAX_TAG_NAME_LEN];
+        name.copy_into_slice(&mut buf[..len]);
+        buf[..len]
+            .iter()
+            .all(|b| b.is_ascii_lowercase() || b.is_ascii_digit() || *b == b'-')
     }
 
-    /// Set reply chunk size (must be >= 1, owner/admin only)
-    pub fn set_chunk_size(env: Env, board_id: u64, size: u32, caller: Address) {
-        caller.require_auth();
+    // =========================================================================
+    // Thread Templates
+    // =========================================================================
 
-        if size < 1 {
-            panic!("Chunk size must be at least 1");
-        }
+    /// Create a thread template (Admin+ only)
+    pub fn create_template(
+        env: Env,
+        board_id: u64,
+        name: String,
+        body: String,
+        caller: Address,
+    ) -> u32 {
+        caller.require_auth();
 
         // Check admin permissions (only if permissions contract is set)
         if env.storage().instance().has(&BoardKey::Permissions) {
@@ -1436,39 +2650,56 @@ impl BoardsBoard {
             let fn_name = Symbol::new(&env, "can_admin");
             let can_admin: bool = env.invoke_contract(&permissions, &fn_name, args);
             if !can_admin {
-                panic!("Only owner or admin can change chunk size");
+                panic!("Only owner or admin can create templates");
             }
         }
 
-        let mut config: BoardConfig = env
+        if name.is_empty() || name.len() > MAX_TEMPLATE_NAME_LEN {
+            panic!("Template name must be 1-32 characters");
+        }
+        if body.is_empty() || body.len() > MAX_TEMPLATE_BYTES {
+            panic!("Template body must be 1-4096 bytes");
+        }
+
+        let mut ids: Vec<u32> = env
             .storage()
             .persistent()
-            .get(&BoardKey::BoardConfig(board_id))
-            .expect("Board not found");
-        config.reply_chunk_size = size;
-        env.storage()
-            .persistent()
-            .set(&BoardKey::BoardConfig(board_id), &config);
-    }
+            .get(&BoardKey::BoardTemplateIds(board_id))
+            .unwrap_or(Vec::new(&env));
+        if ids.len() >= MAX_TEMPLATES {
+            panic!("Board already has the maximum number of templates");
+        }
 
-    /// Get maximum reply depth for nested replies
-    pub fn get_max_reply_depth(env: Env, board_id: u64) -> u32 {
-        let config: BoardConfig = env
+        let template_id: u32 = env
             .storage()
             .persistent()
-            .get(&BoardKey::BoardConfig(board_id))
-            .expect("Board not found");
-        config.max_reply_depth
+            .get(&BoardKey::BoardNextTemplateId(board_id))
+            .unwrap_or(0);
+
+        let template = ThreadTemplate {
+            id: template_id,
+            name,
+            body,
+        };
+        env.storage()
+            .persistent()
+            .set(&BoardKey::Template(board_id, template_id), &template);
+        ids.push_back(template_id);
+        env.storage()
+            .persistent()
+            .set(&BoardKey::BoardTemplateIds(board_id), &ids);
+        env.storage()
+            .persistent()
+            .set(&BoardKey::BoardNextTemplateId(board_id), &(template_id + 1));
+
+        template_id
     }
 
-    /// Set maximum reply depth (must be >= 1, owner/admin only)
-    pub fn set_max_reply_depth(env: Env, board_id: u64, depth: u32, caller: Address) {
+    /// Delete a thread template (Admin+ only). Threads started from it are
+    /// unaffected; its `/new/{template_id}` link falls back to a blank form.
+    pub fn delete_template(env: Env, board_id: u64, template_id: u32, caller: Address) {
         caller.require_auth();
 
-        if depth < 1 {
-            panic!("Max reply depth must be at least 1");
-        }
-
         // Check admin permissions (only if permissions contract is set)
         if env.storage().instance().has(&BoardKey::Permissions) {
             let permissions: Address = env
@@ -1481,194 +2712,305 @@ impl BoardsBoard {
             let fn_name = Symbol::new(&env, "can_admin");
             let can_admin: bool = env.invoke_contract(&permissions, &fn_name, args);
             if !can_admin {
-                panic!("Only owner or admin can change max reply depth");
+                panic!("Only owner or admin can delete templates");
             }
         }
 
-        let mut config: BoardConfig = env
+        let mut ids: Vec<u32> = env
             .storage()
             .persistent()
-            .get(&BoardKey::BoardConfig(board_id))
-            .expect("Board not found");
-        config.max_reply_depth = depth;
+            .get(&BoardKey::BoardTemplateIds(board_id))
+            .unwrap_or(Vec::new(&env));
+        let Some(index) = ids.first_index_of(template_id) else {
+            panic!("Template not found");
+        };
+
+        ids.remove(index);
         env.storage()
             .persistent()
-            .set(&BoardKey::BoardConfig(board_id), &config);
-    }
-
-    /// Get edit window in seconds (0 = no limit)
-    pub fn get_edit_window(env: Env, board_id: u64) -> u64 {
-        // Default to 86400 (24 hours) if not set
+            .set(&BoardKey::BoardTemplateIds(board_id), &ids);
         env.storage()
             .persistent()
-            .get(&BoardKey::BoardEditWindow(board_id))
-            .unwrap_or(86400u64)
+            .remove(&BoardKey::Template(board_id, template_id));
     }
 
-    /// Set edit window in seconds (0 = no limit, owner/admin only)
-    pub fn set_edit_window(env: Env, board_id: u64, seconds: u64, caller: Address) {
-        caller.require_auth();
+    /// List a board's thread templates, oldest first
+    pub fn list_templates(env: Env, board_id: u64) -> Vec<ThreadTemplate> {
+        let ids: Vec<u32> = env
+            .storage()
+            .persistent()
+            .get(&BoardKey::BoardTemplateIds(board_id))
+            .unwrap_or(Vec::new(&env));
 
-        // Check admin permissions (only if permissions contract is set)
-        if env.storage().instance().has(&BoardKey::Permissions) {
-            let permissions: Address = env
-                .storage()
-                .instance()
-                .get(&BoardKey::Permissions)
-                .unwrap();
-            let args: Vec<Val> =
-                Vec::from_array(&env, [board_id.into_val(&env), caller.into_val(&env)]);
-            let fn_name = Symbol::new(&env, "can_admin");
-            let can_admin: bool = env.invoke_contract(&permissions, &fn_name, args);
-            if !can_admin {
-                panic!("Only owner or admin can change edit window");
+        let mut templates = Vec::new(&env);
+        for id in ids.iter() {
+            if let Some(template) = Self::get_template(env.clone(), board_id, id) {
+                templates.push_back(template);
             }
         }
-
-        env.storage()
-            .persistent()
-            .set(&BoardKey::BoardEditWindow(board_id), &seconds);
+        templates
     }
 
-    /// Check if board is read-only
-    pub fn is_readonly(env: Env, board_id: u64) -> bool {
+    /// Get a thread template by ID
+    pub fn get_template(env: Env, board_id: u64, template_id: u32) -> Option<ThreadTemplate> {
         env.storage()
             .persistent()
-            .get::<_, BoardConfig>(&BoardKey::BoardConfig(board_id))
-            .map(|c| c.is_readonly)
-            .unwrap_or(false)
+            .get(&BoardKey::Template(board_id, template_id))
     }
 
-    /// Set board read-only status (owner/admin only)
-    pub fn set_readonly(env: Env, board_id: u64, is_readonly: bool, caller: Address) {
+    // =========================================================================
+    // Wiki
+    // =========================================================================
+
+    /// Create or replace a wiki page. Callers need the board's wiki edit role
+    /// (Member+ unless raised to Moderator+); the version being replaced is kept
+    /// as the page's previous revision.
+    pub fn set_wiki_page(
+        env: Env,
+        board_id: u64,
+        slug: String,
+        title: String,
+        body: String,
+        caller: Address,
+    ) {
         caller.require_auth();
 
-        // Check admin permissions (only if permissions contract is set)
-        if env.storage().instance().has(&BoardKey::Permissions) {
-            let permissions: Address = env
-                .storage()
-                .instance()
-                .get(&BoardKey::Permissions)
-                .unwrap();
-            let args: Vec<Val> =
-                Vec::from_array(&env, [board_id.into_val(&env), caller.into_val(&env)]);
-            let fn_name = Symbol::new(&env, "can_admin");
-            let can_admin: bool = env.invoke_contract(&permissions, &fn_name, args);
-            if !can_admin {
-                panic!("Only owner or admin can change read-only status");
-            }
+        if !env
+            .storage()
+            .persistent()
+            .has(&BoardKey::BoardConfig(board_id))
+        {
+            panic!("Board not found");
+        }
+        if !Self::can_edit_wiki(env.clone(), board_id, caller.clone()) {
+            panic!("Not authorized to edit the wiki");
+        }
+        if !Self::is_valid_wiki_slug(&slug) {
+            panic!("Wiki slug must be 1-32 lowercase letters, digits or dashes");
+        }
+        if title.is_empty() || title.len() > MAX_WIKI_TITLE_LEN {
+            panic!("Wiki title must be 1-100 characters");
+        }
+        if body.is_empty() || body.len() > MAX_WIKI_PAGE_BYTES {
+            panic!("Wiki page body must be 1-16384 bytes");
         }
 
-        let mut config: BoardConfig = env
+        let now = env.ledger().timestamp();
+        let mut pages: Vec<WikiPageInfo> = env
             .storage()
             .persistent()
-            .get(&BoardKey::BoardConfig(board_id))
-            .expect("Board not found");
-        config.is_readonly = is_readonly;
+            .get(&BoardKey::BoardWikiPages(board_id))
+            .unwrap_or(Vec::new(&env));
+        let info = WikiPageInfo {
+            slug: slug.clone(),
+            title: title.clone(),
+            editor: caller.clone(),
+            updated_at: now,
+        };
+        match pages.iter().position(|page| page.slug == slug) {
+            Some(index) => pages.set(index as u32, info),
+            None => {
+                if pages.len() >= MAX_WIKI_PAGES {
+                    panic!("Board already has the maximum number of wiki pages");
+                }
+                pages.push_back(info);
+            }
+        }
+
+        let key = BoardKey::WikiPage(board_id, slug.clone());
+        if let Some(previous) = env.storage().persistent().get::<_, WikiPage>(&key) {
+            env.storage().persistent().set(
+                &BoardKey::WikiPagePrevious(board_id, slug.clone()),
+                &previous,
+            );
+        }
+        let page = WikiPage {
+            slug,
+            title,
+            body,
+            editor: caller,
+            updated_at: now,
+        };
+        env.storage().persistent().set(&key, &page);
         env.storage()
             .persistent()
-            .set(&BoardKey::BoardConfig(board_id), &config);
+            .set(&BoardKey::BoardWikiPages(board_id), &pages);
+    }
 
-        // Update BoardMeta too
-        if let Some(mut meta) = env
-            .storage()
+    /// Get a wiki page by slug
+    pub fn get_wiki_page(env: Env, board_id: u64, slug: String) -> Option<WikiPage> {
+        env.storage()
             .persistent()
-            .get::<_, BoardMeta>(&BoardKey::Board(board_id))
-        {
-            meta.is_readonly = is_readonly;
-            env.storage()
-                .persistent()
-                .set(&BoardKey::Board(board_id), &meta);
-        }
+            .get(&BoardKey::WikiPage(board_id, slug))
     }
 
-    /// Check if board is private (members only)
-    pub fn is_private(env: Env, board_id: u64) -> bool {
+    /// Get the version of a wiki page that its last edit replaced
+    pub fn get_previous_wiki_page(env: Env, board_id: u64, slug: String) -> Option<WikiPage> {
         env.storage()
             .persistent()
-            .get::<_, BoardConfig>(&BoardKey::BoardConfig(board_id))
-            .map(|c| c.is_private)
-            .unwrap_or(false)
+            .get(&BoardKey::WikiPagePrevious(board_id, slug))
     }
 
-    /// Set board private status (owner/admin only)
-    pub fn set_private(env: Env, board_id: u64, is_private: bool, caller: Address) {
+    /// List a board's wiki pages, oldest first
+    pub fn list_wiki_pages(env: Env, board_id: u64) -> Vec<WikiPageInfo> {
+        env.storage()
+            .persistent()
+            .get(&BoardKey::BoardWikiPages(board_id))
+            .unwrap_or(Vec::new(&env))
+    }
+
+    /// Get the lowest role that can edit a board's wiki (Member unless changed)
+    pub fn get_wiki_edit_role(env: Env, board_id: u64) -> Role {
+        env.storage()
+            .persistent()
+            .get(&SettingKey::BoardWikiEditRole(board_id))
+            .unwrap_or(Role::Member)
+    }
+
+    /// Set the lowest role that can edit a board's wiki: Member or Moderator (Admin+ only)
+    pub fn set_wiki_edit_role(env: Env, board_id: u64, role: Role, caller: Address) {
         caller.require_auth();
 
         // Check admin permissions (only if permissions contract is set)
-        if env.storage().instance().has(&BoardKey::Permissions) {
-            let permissions: Address = env
-                .storage()
-                .instance()
-                .get(&BoardKey::Permissions)
-                .unwrap();
+        if let Some(permissions) = env
+            .storage()
+            .instance()
+            .get::<_, Address>(&BoardKey::Permissions)
+        {
             let args: Vec<Val> =
                 Vec::from_array(&env, [board_id.into_val(&env), caller.into_val(&env)]);
-            let fn_name = Symbol::new(&env, "can_admin");
-            let can_admin: bool = env.invoke_contract(&permissions, &fn_name, args);
+            let can_admin: bool =
+                env.invoke_contract(&permissions, &Symbol::new(&env, "can_admin"), args);
             if !can_admin {
-                panic!("Only owner or admin can change private status");
+                panic!("Only owner or admin can change who edits the wiki");
             }
         }
 
-        let mut config: BoardConfig = env
+        if role != Role::Member && role != Role::Moderator {
+            panic!("Wiki edit role must be Member or Moderator");
+        }
+        if !env
             .storage()
             .persistent()
-            .get(&BoardKey::BoardConfig(board_id))
-            .expect("Board not found");
-        config.is_private = is_private;
+            .has(&BoardKey::BoardConfig(board_id))
+        {
+            panic!("Board not found");
+        }
         env.storage()
             .persistent()
-            .set(&BoardKey::BoardConfig(board_id), &config);
+            .set(&SettingKey::BoardWikiEditRole(board_id), &role);
+    }
 
-        // Update BoardMeta too
-        if let Some(mut meta) = env
+    /// Check if a user can edit a board's wiki. Anyone can while no permissions
+    /// contract is set; otherwise banned users can't, and others need the wiki edit role.
+    pub fn can_edit_wiki(env: Env, board_id: u64, user: Address) -> bool {
+        let Some(permissions) = env
             .storage()
-            .persistent()
-            .get::<_, BoardMeta>(&BoardKey::Board(board_id))
-        {
-            meta.is_private = is_private;
-            env.storage()
-                .persistent()
-                .set(&BoardKey::Board(board_id), &meta);
+            .instance()
+            .get::<_, Address>(&BoardKey::Permissions)
+        else {
+            return true;
+        };
+
+        let args: Vec<Val> = Vec::from_array(&env, [board_id.into_val(&env), user.into_val(&env)]);
+        let set: PermissionSet =
+            env.invoke_contract(&permissions, &Symbol::new(&env, "get_permissions"), args);
+        Self::wiki_role_allows(&env, board_id, &set)
+    }
+
+    /// Whether a permission set meets the board's wiki edit role
+    fn wiki_role_allows(env: &Env, board_id: u64, permissions: &PermissionSet) -> bool {
+        let required = Self::get_wiki_edit_role(env.clone(), board_id);
+        !permissions.is_banned && (permissions.role as u32) >= (required as u32)
+    }
+
+    /// Wiki slugs are 1-32 lowercase ASCII letters, digits or dashes
+    fn is_valid_wiki_slug(slug: &String) -> bool {
+        let len = slug.len() as usize;
+        if !(1..=MAX_WIKI_SLUG_LEN).contains(&len) {
+            return false;
         }
+        let mut buf = [0u8; MAX_WIKI_SLUG_LEN];
+        slug.copy_into_slice(&mut buf[..len]);
+        buf[..len]
+            .iter()
+            .all(|b| b.is_ascii_lowercase() || b.is_ascii_digit() || *b == b'-')
     }
 
-    /// Set board rules (markdown text, Admin+ only)
-    /// Rules are displayed on the board page and when creating new posts
-    pub fn set_rules(env: Env, board_id: u64, rules: String, caller: Address) {
-        caller.require_auth();
+    // Permission check helpers
 
-        // Check admin permissions (only if permissions contract is set)
-        if env.storage().instance().has(&BoardKey::Permissions) {
-            let permissions: Address = env
-                .storage()
-                .instance()
-                .get(&BoardKey::Permissions)
-                .unwrap();
-            let args: Vec<Val> =
-                Vec::from_array(&env, [board_id.into_val(&env), caller.into_val(&env)]);
-            let fn_name = Symbol::new(&env, "can_admin");
-            let can_admin: bool = env.invoke_contract(&permissions, &fn_name, args);
-            if !can_admin {
-                panic!("Only owner or admin can set board rules");
-            }
+    /// Check if user can create threads on this board
+    fn check_can_create_thread(env: &Env, board_id: u64, user: &Address) {
+        let permissions: Address = env
+            .storage()
+            .instance()
+            .get(&BoardKey::Permissions)
+            .expect("Not initialized");
+
+        let args: Vec<Val> = Vec::from_array(env, [board_id.into_val(env), user.into_val(env)]);
+        let fn_name = Symbol::new(env, "can_create_thread");
+        let can_create: bool = env.invoke_contract(&permissions, &fn_name, args);
+
+        if !can_create {
+            panic!("Not authorized to create threads");
+        }
+    }
+
+    /// Check if a user is quarantined on a board (false if permissions is unavailable)
+    fn is_quarantined(env: &Env, board_id: u64, user: &Address) -> bool {
+        let Some(permissions) = env
+            .storage()
+            .instance()
+            .get::<_, Address>(&BoardKey::Permissions)
+        else {
+            return false;
+        };
+
+        let args: Vec<Val> = Vec::from_array(env, [board_id.into_val(env), user.into_val(env)]);
+        env.try_invoke_contract::<bool, soroban_sdk::Error>(
+            &permissions,
+            &Symbol::new(env, "is_quarantined"),
+            args,
+        )
+        .unwrap_or(Ok(false))
+        .unwrap_or(false)
+    }
+
+    /// Check if user has moderator permissions
+    fn check_can_moderate(env: &Env, board_id: u64, user: &Address) {
+        let permissions: Address = env
+            .storage()
+            .instance()
+            .get(&BoardKey::Permissions)
+            .expect("Not initialized");
+
+        let args: Vec<Val> = Vec::from_array(env, [board_id.into_val(env), user.into_val(env)]);
+        let fn_name = Symbol::new(env, "can_moderate");
+        let can_moderate: bool = env.invoke_contract(&permissions, &fn_name, args);
+
+        if !can_moderate {
+            panic!("Not authorized to moderate");
         }
+    }
 
+    /// Get board configuration
+    pub fn get_config(env: Env, board_id: u64) -> BoardConfig {
         env.storage()
             .persistent()
-            .set(&BoardKey::BoardRules(board_id), &rules);
+            .get(&BoardKey::BoardConfig(board_id))
+            .expect("Board not found")
     }
 
-    /// Get board rules (returns None if no rules are set)
-    pub fn get_rules(env: Env, board_id: u64) -> Option<String> {
+    /// Get whether board is listed publicly (for home page)
+    pub fn get_board_listed(env: Env, board_id: u64) -> bool {
         env.storage()
             .persistent()
-            .get(&BoardKey::BoardRules(board_id))
+            .get(&BoardKey::BoardListed(board_id))
+            .unwrap_or(true) // Default to listed for backward compatibility
     }
 
-    /// Clear board rules (Admin+ only)
-    pub fn clear_rules(env: Env, board_id: u64, caller: Address) {
+    /// Set whether board is listed publicly (owner/admin only)
+    pub fn set_board_listed(env: Env, board_id: u64, is_listed: bool, caller: Address) {
         caller.require_auth();
 
         // Check admin permissions (only if permissions contract is set)
@@ -1683,3965 +3025,17306 @@ impl BoardsBoard {
             let fn_name = Symbol::new(&env, "can_admin");
             let can_admin: bool = env.invoke_contract(&permissions, &fn_name, args);
             if !can_admin {
-                panic!("Only owner or admin can clear board rules");
+                panic!("Only owner or admin can change listed status");
             }
         }
 
+        if is_listed && Self::is_archived(env.clone(), board_id) {
+            panic!("Board is archived");
+        }
+
+        let before = Self::board_filters(&env, board_id);
         env.storage()
             .persistent()
-            .remove(&BoardKey::BoardRules(board_id));
-    }
+            .set(&BoardKey::BoardListed(board_id), &is_listed);
 
-    /// Check if content is within the edit window
-    /// Returns true if content can be edited (within window or no limit)
-    fn is_within_edit_window(env: &Env, board_id: u64, created_at: u64) -> bool {
-        let edit_window_seconds: u64 = env
+        // Update BoardMeta too
+        if let Some(mut meta) = env
             .storage()
             .persistent()
-            .get(&BoardKey::BoardEditWindow(board_id))
-            .unwrap_or(86400u64);
-
-        // 0 = no time limit
-        if edit_window_seconds == 0 {
-            return true;
+            .get::<_, BoardMeta>(&BoardKey::Board(board_id))
+        {
+            meta.is_listed = is_listed;
+            env.storage()
+                .persistent()
+                .set(&BoardKey::Board(board_id), &meta);
         }
-
-        let current_time = env.ledger().timestamp();
-        current_time <= created_at + edit_window_seconds
+        Self::reindex_board(&env, board_id, &before);
     }
 
-    /// Create a new thread (returns thread ID)
-    /// Note: Auth is handled by the calling contract (theme). When called directly
-    /// (e.g., via CLI), callers should ensure proper authorization.
-    pub fn create_thread(
-        env: Env,
-        board_id: u64,
-        title: String,
-        flair_id: Option<String>,
-        creator: Address,
-    ) -> u64 {
-        // Note: require_auth() removed because this is called by the theme contract,
-        // which already handles authentication. Cross-contract auth doesn't propagate
-        // automatically in Soroban.
+    /// Get board creator address
+    pub fn get_creator(env: Env, board_id: u64) -> Option<Address> {
+        env.storage()
+            .persistent()
+            .get(&BoardKey::BoardCreator(board_id))
+    }
 
-        // Check permissions (only if permissions contract is set)
-        if env.storage().instance().has(&BoardKey::Permissions) {
-            Self::check_can_create_thread(&env, board_id, &creator);
-        }
+    /// Get board creation timestamp
+    pub fn get_created_at(env: Env, board_id: u64) -> u64 {
+        env.storage()
+            .persistent()
+            .get(&BoardKey::BoardCreatedAt(board_id))
+            .unwrap_or(0) // Default to 0 for legacy boards
+    }
 
-        // Check if board is readonly
-        let config: BoardConfig = env
+    /// Increment thread count (called by content contract when creating threads)
+    /// This is separate from create_thread to allow external contracts to track thread counts
+    pub fn increment_thread_count(env: Env, board_id: u64) -> u64 {
+        let count: u64 = env
             .storage()
             .persistent()
-            .get(&BoardKey::BoardConfig(board_id))
-            .expect("Board not found");
-        if config.is_readonly {
-            panic!("Board is read-only");
-        }
+            .get(&BoardKey::BoardThreadCount(board_id))
+            .unwrap_or(0);
+        let new_count = count + 1;
+        env.storage()
+            .persistent()
+            .set(&BoardKey::BoardThreadCount(board_id), &new_count);
 
-        // Parse flair_id from string: "none" or empty → None, "flair_N" → Some(N)
-        let parsed_flair_id: Option<u32> = if let Some(ref flair_str) = flair_id {
-            let none_str = String::from_str(&env, "none");
-            if flair_str.is_empty() || flair_str == &none_str {
-                None
-            } else {
-                // Expect format "flair_N" - parse N as u32
-                let mut buf = [0u8; 32];
-                let len = core::cmp::min(flair_str.len() as usize, 32);
-                flair_str.copy_into_slice(&mut buf[..len]);
-
-                // Check for "flair_" prefix (6 chars)
-                if len > 6
-                    && buf[0] == b'f'
-                    && buf[1] == b'l'
-                    && buf[2] == b'a'
-                    && buf[3] == b'i'
-                    && buf[4] == b'r'
-                    && buf[5] == b'_'
-                {
-                    // Parse the number after "flair_"
-                    let mut result: u32 = 0;
-                    for &b in buf.iter().take(len).skip(6) {
-                        if b.is_ascii_digit() {
-                            result = result * 10 + (b - b'0') as u32;
-                        } else {
-                            panic!("Invalid flair_id format");
-                        }
-                    }
-                    Some(result)
-                } else {
-                    panic!("Invalid flair_id: expected 'none' or 'flair_N' format");
-                }
-            }
-        } else {
-            None
-        };
-
-        // Get flairs for this board
-        let flairs: Vec<FlairDef> = env
-            .storage()
-            .persistent()
-            .get(&BoardKey::BoardFlairDefs(board_id))
-            .unwrap_or(Vec::new(&env));
-
-        // Check if user is moderator (needed for mod_only flair access)
-        let is_moderator = if env.storage().instance().has(&BoardKey::Permissions) {
-            let permissions: Address = env
-                .storage()
-                .instance()
-                .get(&BoardKey::Permissions)
-                .expect("Not initialized");
-            let args: Vec<Val> = Vec::from_array(
-                &env,
-                [board_id.into_val(&env), creator.clone().into_val(&env)],
-            );
-            env.try_invoke_contract::<bool, soroban_sdk::Error>(
-                &permissions,
-                &Symbol::new(&env, "can_moderate"),
-                args,
-            )
-            .unwrap_or(Ok(false))
-            .unwrap_or(false)
-        } else {
-            false
-        };
-
-        // Check if any flair is required (only consider flairs the user can actually select)
-        let mut has_required_flair = false;
-        for i in 0..flairs.len() {
-            let flair = flairs.get(i).unwrap();
-            if flair.required && flair.enabled && (!flair.mod_only || is_moderator) {
-                has_required_flair = true;
-                break;
-            }
-        }
-
-        // Validate flair selection
-        let validated_flair_id = if let Some(fid) = parsed_flair_id {
-            // User selected a flair - validate it exists and is usable
-            let mut flair_valid = false;
-            let mut flair_mod_only = false;
-            for i in 0..flairs.len() {
-                let flair = flairs.get(i).unwrap();
-                if flair.id == fid {
-                    if !flair.enabled {
-                        panic!("Selected flair is disabled");
-                    }
-                    flair_valid = true;
-                    flair_mod_only = flair.mod_only;
-                    break;
-                }
-            }
-            if !flair_valid {
-                panic!("Selected flair does not exist");
-            }
-            if flair_mod_only && !is_moderator {
-                panic!("Selected flair is moderator-only");
-            }
-            Some(fid)
-        } else if has_required_flair {
-            // No flair selected but one is required
-            panic!("A flair is required for new posts on this board");
-        } else {
-            None
-        };
-
-        let thread_id: u64 = env
-            .storage()
-            .persistent()
-            .get(&BoardKey::BoardThreadCount(board_id))
-            .unwrap_or(0);
-
-        let thread = ThreadMeta {
-            id: thread_id,
-            board_id,
-            title,
-            creator,
-            created_at: env.ledger().timestamp(),
-            updated_at: env.ledger().timestamp(),
-            reply_count: 0,
-            is_locked: false,
-            is_pinned: false,
-            is_hidden: false,
-            is_deleted: false,
-            flair_id: validated_flair_id,
-        };
-
-        env.storage()
-            .persistent()
-            .set(&BoardKey::BoardThread(board_id, thread_id), &thread);
-        env.storage()
-            .persistent()
-            .set(&BoardKey::BoardThreadCount(board_id), &(thread_id + 1));
-
-        // Update BoardMeta thread count
+        // Update BoardMeta too
         if let Some(mut meta) = env
             .storage()
             .persistent()
             .get::<_, BoardMeta>(&BoardKey::Board(board_id))
         {
-            meta.thread_count = thread_id + 1;
+            meta.thread_count = new_count;
             env.storage()
                 .persistent()
                 .set(&BoardKey::Board(board_id), &meta);
         }
 
-        thread_id
+        new_count
     }
 
-    /// Get thread metadata
-    pub fn get_thread(env: Env, board_id: u64, thread_id: u64) -> Option<ThreadMeta> {
-        env.storage()
-            .persistent()
-            .get(&BoardKey::BoardThread(board_id, thread_id))
-    }
+    /// Set thread count (admin function for correcting counts)
+    pub fn set_thread_count(env: Env, board_id: u64, count: u64, caller: Address) {
+        caller.require_auth();
 
-    /// Get thread title and author (for crossposting)
-    pub fn get_thread_title_and_author(
-        env: Env,
-        board_id: u64,
-        thread_id: u64,
-    ) -> Option<(String, Address)> {
-        let thread: Option<ThreadMeta> = env
+        let meta: BoardMeta = env
             .storage()
             .persistent()
-            .get(&BoardKey::BoardThread(board_id, thread_id));
-        thread.map(|t| (t.title, t.creator))
-    }
+            .get(&BoardKey::Board(board_id))
+            .expect("Board not found");
 
-    /// List threads with pagination
-    pub fn list_threads(env: Env, board_id: u64, start: u64, limit: u64) -> Vec<ThreadMeta> {
-        let count: u64 = env
+        // Allow board creator or registry admin to set thread count
+        let is_registry_admin = if let Some(registry) = env
             .storage()
-            .persistent()
-            .get(&BoardKey::BoardThreadCount(board_id))
-            .unwrap_or(0);
-
-        let mut threads = Vec::new(&env);
-
-        // Return newest first (reverse order)
-        let actual_start = if count > start { count - start - 1 } else { 0 };
+            .instance()
+            .get::<_, Address>(&BoardKey::Registry)
+        {
+            let args: Vec<Val> = Vec::from_array(&env, [caller.clone().into_val(&env)]);
+            env.invoke_contract::<bool>(&registry, &Symbol::new(&env, "is_admin"), args)
+        } else {
+            false
+        };
 
-        for i in 0..limit {
-            if actual_start < i {
-                break;
-            }
-            let idx = actual_start - i;
-            if let Some(thread) = env
-                .storage()
-                .persistent()
-                .get(&BoardKey::BoardThread(board_id, idx))
-            {
-                threads.push_back(thread);
-            }
+        if meta.creator != caller && !is_registry_admin {
+            panic!("Only board creator or registry admin can set thread count");
         }
 
-        threads
-    }
+        env.storage()
+            .persistent()
+            .set(&BoardKey::BoardThreadCount(board_id), &count);
 
-    /// Get thread count for a board
-    pub fn thread_count(env: Env, board_id: u64) -> u64 {
+        let mut updated_meta = meta;
+        updated_meta.thread_count = count;
         env.storage()
             .persistent()
-            .get(&BoardKey::BoardThreadCount(board_id))
-            .unwrap_or(0)
+            .set(&BoardKey::Board(board_id), &updated_meta);
     }
 
-    /// Lock a thread (no more replies)
-    pub fn lock_thread(env: Env, board_id: u64, thread_id: u64, caller: Address) {
-        caller.require_auth();
-
-        // Check moderator permissions (only if permissions contract is set)
-        if env.storage().instance().has(&BoardKey::Permissions) {
-            Self::check_can_moderate(&env, board_id, &caller);
-        }
-
-        if let Some(mut thread) = env
+    /// Get reply chunk size for waterfall loading
+    pub fn get_chunk_size(env: Env, board_id: u64) -> u32 {
+        let config: BoardConfig = env
             .storage()
             .persistent()
-            .get::<_, ThreadMeta>(&BoardKey::BoardThread(board_id, thread_id))
-        {
-            thread.is_locked = true;
-            env.storage()
-                .persistent()
-                .set(&BoardKey::BoardThread(board_id, thread_id), &thread);
+            .get(&BoardKey::BoardConfig(board_id))
+            .expect("Board not found");
+        // Handle old configs that don't have this field (default to 6)
+        if config.reply_chunk_size == 0 {
+            6
+        } else {
+            config.reply_chunk_size
         }
     }
 
-    /// Unlock a thread
-    pub fn unlock_thread(env: Env, board_id: u64, thread_id: u64, caller: Address) {
+    /// Set reply chunk size (must be >= 1, owner/admin only)
+    pub fn set_chunk_size(env: Env, board_id: u64, size: u32, caller: Address) {
         caller.require_auth();
 
-        // Check moderator permissions (only if permissions contract is set)
+        if size < 1 {
+            panic!("Chunk size must be at least 1");
+        }
+
+        // Check admin permissions (only if permissions contract is set)
         if env.storage().instance().has(&BoardKey::Permissions) {
-            Self::check_can_moderate(&env, board_id, &caller);
+            let permissions: Address = env
+                .storage()
+                .instance()
+                .get(&BoardKey::Permissions)
+                .unwrap();
+            let args: Vec<Val> =
+                Vec::from_array(&env, [board_id.into_val(&env), caller.into_val(&env)]);
+            let fn_name = Symbol::new(&env, "can_admin");
+            let can_admin: bool = env.invoke_contract(&permissions, &fn_name, args);
+            if !can_admin {
+                panic!("Only owner or admin can change chunk size");
+            }
         }
 
-        if let Some(mut thread) = env
+        let mut config: BoardConfig = env
             .storage()
             .persistent()
-            .get::<_, ThreadMeta>(&BoardKey::BoardThread(board_id, thread_id))
+            .get(&BoardKey::BoardConfig(board_id))
+            .expect("Board not found");
+        config.reply_chunk_size = size;
+        env.storage()
+            .persistent()
+            .set(&BoardKey::BoardConfig(board_id), &config);
+    }
+
+    /// Get the default thread ordering for the board view.
+    /// Boards that never chose one keep the original default: Hot when voting
+    /// is enabled, New otherwise.
+    pub fn get_default_sort(env: Env, board_id: u64) -> ThreadSort {
+        if let Some(sort) = env
+            .storage()
+            .persistent()
+            .get(&SettingKey::BoardDefaultSort(board_id))
         {
-            thread.is_locked = false;
-            env.storage()
-                .persistent()
-                .set(&BoardKey::BoardThread(board_id, thread_id), &thread);
+            return sort;
+        }
+        if env.storage().instance().has(&BoardKey::Voting) {
+            ThreadSort::Hot
+        } else {
+            ThreadSort::New
         }
     }
 
-    /// Pin a thread
-    pub fn pin_thread(env: Env, board_id: u64, thread_id: u64, caller: Address) {
+    /// Set the default thread ordering for the board view (owner/admin only)
+    pub fn set_default_sort(env: Env, board_id: u64, sort: ThreadSort, caller: Address) {
         caller.require_auth();
 
-        // Check moderator permissions (only if permissions contract is set)
-        if env.storage().instance().has(&BoardKey::Permissions) {
-            Self::check_can_moderate(&env, board_id, &caller);
+        // Check admin permissions (only if permissions contract is set)
+        if let Some(permissions) = env
+            .storage()
+            .instance()
+            .get::<_, Address>(&BoardKey::Permissions)
+        {
+            let args: Vec<Val> =
+                Vec::from_array(&env, [board_id.into_val(&env), caller.into_val(&env)]);
+            let can_admin: bool =
+                env.invoke_contract(&permissions, &Symbol::new(&env, "can_admin"), args);
+            if !can_admin {
+                panic!("Only owner or admin can change default sort");
+            }
         }
 
-        if let Some(mut thread) = env
+        if !env
             .storage()
             .persistent()
-            .get::<_, ThreadMeta>(&BoardKey::BoardThread(board_id, thread_id))
+            .has(&BoardKey::BoardConfig(board_id))
         {
-            thread.is_pinned = true;
-            env.storage()
-                .persistent()
-                .set(&BoardKey::BoardThread(board_id, thread_id), &thread);
-
-            let mut pinned: Vec<u64> = env
-                .storage()
-                .persistent()
-                .get(&BoardKey::BoardPinnedThreads(board_id))
-                .unwrap_or(Vec::new(&env));
-            pinned.push_back(thread_id);
-            env.storage()
-                .persistent()
-                .set(&BoardKey::BoardPinnedThreads(board_id), &pinned);
+            panic!("Board not found");
         }
+        env.storage()
+            .persistent()
+            .set(&SettingKey::BoardDefaultSort(board_id), &sort);
     }
 
-    /// Unpin a thread
-    pub fn unpin_thread(env: Env, board_id: u64, thread_id: u64, caller: Address) {
+    /// Get the number of bytes quoted into the reply form when quoting a post
+    pub fn get_quote_limit(env: Env, board_id: u64) -> u32 {
+        env.storage()
+            .persistent()
+            .get(&SettingKey::BoardQuoteLimit(board_id))
+            .unwrap_or(DEFAULT_QUOTE_LIMIT)
+    }
+
+    /// Set the quote limit in bytes (owner/admin only, 0 disables quoting)
+    pub fn set_quote_limit(env: Env, board_id: u64, limit: u32, caller: Address) {
         caller.require_auth();
 
-        // Check moderator permissions (only if permissions contract is set)
-        if env.storage().instance().has(&BoardKey::Permissions) {
-            Self::check_can_moderate(&env, board_id, &caller);
+        // Check admin permissions (only if permissions contract is set)
+        if let Some(permissions) = env
+            .storage()
+            .instance()
+            .get::<_, Address>(&BoardKey::Permissions)
+        {
+            let args: Vec<Val> =
+                Vec::from_array(&env, [board_id.into_val(&env), caller.into_val(&env)]);
+            let can_admin: bool =
+                env.invoke_contract(&permissions, &Symbol::new(&env, "can_admin"), args);
+            if !can_admin {
+                panic!("Only owner or admin can change quote limit");
+            }
         }
 
-        if let Some(mut thread) = env
+        if limit > MAX_QUOTE_LIMIT {
+            panic!("Quote limit must be at most 10000 bytes");
+        }
+        if !env
             .storage()
             .persistent()
-            .get::<_, ThreadMeta>(&BoardKey::BoardThread(board_id, thread_id))
+            .has(&BoardKey::BoardConfig(board_id))
         {
-            thread.is_pinned = false;
-            env.storage()
-                .persistent()
-                .set(&BoardKey::BoardThread(board_id, thread_id), &thread);
-
-            // Remove from pinned list
-            let pinned: Vec<u64> = env
-                .storage()
-                .persistent()
-                .get(&BoardKey::BoardPinnedThreads(board_id))
-                .unwrap_or(Vec::new(&env));
-            let mut new_pinned = Vec::new(&env);
-            for id in pinned.iter() {
-                if id != thread_id {
-                    new_pinned.push_back(id);
-                }
-            }
-            env.storage()
-                .persistent()
-                .set(&BoardKey::BoardPinnedThreads(board_id), &new_pinned);
+            panic!("Board not found");
         }
+        env.storage()
+            .persistent()
+            .set(&SettingKey::BoardQuoteLimit(board_id), &limit);
     }
 
-    /// Get pinned threads for a board
-    pub fn get_pinned_threads(env: Env, board_id: u64) -> Vec<ThreadMeta> {
-        let pinned_ids: Vec<u64> = env
-            .storage()
+    /// Get the approximate byte budget for the initial thread page. Longer thread
+    /// bodies are cut short with a link to the full post.
+    pub fn get_render_budget(env: Env, board_id: u64) -> u32 {
+        env.storage()
             .persistent()
-            .get(&BoardKey::BoardPinnedThreads(board_id))
-            .unwrap_or(Vec::new(&env));
-
-        let mut threads = Vec::new(&env);
-        for id in pinned_ids.iter() {
-            if let Some(thread) = env
-                .storage()
-                .persistent()
-                .get(&BoardKey::BoardThread(board_id, id))
-            {
-                threads.push_back(thread);
-            }
-        }
-        threads
+            .get(&SettingKey::BoardRenderBudget(board_id))
+            .unwrap_or(DEFAULT_RENDER_BUDGET)
     }
 
-    /// Hide a thread (moderator action)
-    pub fn hide_thread(env: Env, board_id: u64, thread_id: u64, caller: Address) {
+    /// Set the thread render budget in bytes (owner/admin only, 4096-65536)
+    pub fn set_render_budget(env: Env, board_id: u64, budget: u32, caller: Address) {
         caller.require_auth();
 
-        // Check moderator permissions (only if permissions contract is set)
-        if env.storage().instance().has(&BoardKey::Permissions) {
-            Self::check_can_moderate(&env, board_id, &caller);
+        // Check admin permissions (only if permissions contract is set)
+        if let Some(permissions) = env
+            .storage()
+            .instance()
+            .get::<_, Address>(&BoardKey::Permissions)
+        {
+            let args: Vec<Val> =
+                Vec::from_array(&env, [board_id.into_val(&env), caller.into_val(&env)]);
+            let can_admin: bool =
+                env.invoke_contract(&permissions, &Symbol::new(&env, "can_admin"), args);
+            if !can_admin {
+                panic!("Only owner or admin can change render budget");
+            }
         }
 
-        if let Some(mut thread) = env
+        if !(MIN_RENDER_BUDGET..=MAX_RENDER_BUDGET).contains(&budget) {
+            panic!("Render budget must be between 4096 and 65536 bytes");
+        }
+        if !env
             .storage()
             .persistent()
-            .get::<_, ThreadMeta>(&BoardKey::BoardThread(board_id, thread_id))
+            .has(&BoardKey::BoardConfig(board_id))
         {
-            thread.is_hidden = true;
-            env.storage()
-                .persistent()
-                .set(&BoardKey::BoardThread(board_id, thread_id), &thread);
+            panic!("Board not found");
         }
+        env.storage()
+            .persistent()
+            .set(&SettingKey::BoardRenderBudget(board_id), &budget);
     }
 
-    /// Unhide a thread (moderator action)
-    pub fn unhide_thread(env: Env, board_id: u64, thread_id: u64, caller: Address) {
+    /// Get the maximum thread body size in bytes (checked by the content contract)
+    pub fn get_max_thread_body_bytes(env: Env, board_id: u64) -> u32 {
+        env.storage()
+            .persistent()
+            .get(&SettingKey::BoardMaxThreadBodyBytes(board_id))
+            .unwrap_or(DEFAULT_MAX_THREAD_BODY_BYTES)
+    }
+
+    /// Get the maximum reply size in bytes (checked by the content contract)
+    pub fn get_max_reply_bytes(env: Env, board_id: u64) -> u32 {
+        env.storage()
+            .persistent()
+            .get(&SettingKey::BoardMaxReplyBytes(board_id))
+            .unwrap_or(DEFAULT_MAX_REPLY_BYTES)
+    }
+
+    /// Set the post size limits in bytes (owner/admin only, each 1-16384)
+    pub fn set_post_size_limits(
+        env: Env,
+        board_id: u64,
+        max_thread_body_bytes: u32,
+        max_reply_bytes: u32,
+        caller: Address,
+    ) {
         caller.require_auth();
 
-        // Check moderator permissions (only if permissions contract is set)
-        if env.storage().instance().has(&BoardKey::Permissions) {
-            Self::check_can_moderate(&env, board_id, &caller);
+        // Check admin permissions (only if permissions contract is set)
+        if let Some(permissions) = env
+            .storage()
+            .instance()
+            .get::<_, Address>(&BoardKey::Permissions)
+        {
+            let args: Vec<Val> =
+                Vec::from_array(&env, [board_id.into_val(&env), caller.into_val(&env)]);
+            let can_admin: bool =
+                env.invoke_contract(&permissions, &Symbol::new(&env, "can_admin"), args);
+            if !can_admin {
+                panic!("Only owner or admin can change post size limits");
+            }
         }
 
-        if let Some(mut thread) = env
+        let valid = 1..=MAX_POST_BYTES;
+        if !valid.contains(&max_thread_body_bytes) || !valid.contains(&max_reply_bytes) {
+            panic!("Post size limits must be between 1 and 16384 bytes");
+        }
+        if !env
             .storage()
             .persistent()
-            .get::<_, ThreadMeta>(&BoardKey::BoardThread(board_id, thread_id))
+            .has(&BoardKey::BoardConfig(board_id))
         {
-            thread.is_hidden = false;
-            env.storage()
-                .persistent()
-                .set(&BoardKey::BoardThread(board_id, thread_id), &thread);
+            panic!("Board not found");
         }
+        env.storage().persistent().set(
+            &SettingKey::BoardMaxThreadBodyBytes(board_id),
+            &max_thread_body_bytes,
+        );
+        env.storage()
+            .persistent()
+            .set(&SettingKey::BoardMaxReplyBytes(board_id), &max_reply_bytes);
     }
 
-    /// Set thread hidden state (called by admin contract)
-    pub fn set_thread_hidden(env: Env, board_id: u64, thread_id: u64, hidden: bool) {
-        // Note: Auth is handled by the calling admin contract
-        if let Some(mut thread) = env
-            .storage()
+    /// Get the board's inline image rules (images allowed from any host unless changed)
+    pub fn get_image_policy(env: Env, board_id: u64) -> ImagePolicy {
+        env.storage()
             .persistent()
-            .get::<_, ThreadMeta>(&BoardKey::BoardThread(board_id, thread_id))
-        {
-            thread.is_hidden = hidden;
-            thread.updated_at = env.ledger().timestamp();
-            env.storage()
-                .persistent()
-                .set(&BoardKey::BoardThread(board_id, thread_id), &thread);
-        }
+            .get(&SettingKey::BoardImagePolicy(board_id))
+            .unwrap_or(ImagePolicy {
+                allow_images: true,
+                allowed_hosts: Vec::new(&env),
+            })
     }
 
-    /// Set thread locked state (called by admin contract)
-    pub fn set_thread_locked(env: Env, board_id: u64, thread_id: u64, locked: bool) {
-        // Note: Auth is handled by the calling admin contract
-        if let Some(mut thread) = env
+    /// Set the board's inline image rules (owner/admin only). An empty host list
+    /// allows images from any host; applies to posts written from now on.
+    pub fn set_image_policy(
+        env: Env,
+        board_id: u64,
+        allow_images: bool,
+        allowed_hosts: Vec<String>,
+        caller: Address,
+    ) {
+        caller.require_auth();
+
+        // Check admin permissions (only if permissions contract is set)
+        if let Some(permissions) = env
             .storage()
-            .persistent()
-            .get::<_, ThreadMeta>(&BoardKey::BoardThread(board_id, thread_id))
+            .instance()
+            .get::<_, Address>(&BoardKey::Permissions)
         {
-            thread.is_locked = locked;
-            thread.updated_at = env.ledger().timestamp();
-            env.storage()
-                .persistent()
-                .set(&BoardKey::BoardThread(board_id, thread_id), &thread);
+            let args: Vec<Val> =
+                Vec::from_array(&env, [board_id.into_val(&env), caller.into_val(&env)]);
+            let can_admin: bool =
+                env.invoke_contract(&permissions, &Symbol::new(&env, "can_admin"), args);
+            if !can_admin {
+                panic!("Only owner or admin can change the image policy");
+            }
         }
-    }
 
-    /// Set thread pinned state (called by admin contract)
-    pub fn set_thread_pinned(env: Env, board_id: u64, thread_id: u64, pinned: bool) {
-        // Note: Auth is handled by the calling admin contract
-        if let Some(mut thread) = env
+        if allowed_hosts.len() > MAX_IMAGE_HOSTS {
+            panic!("Too many image hosts");
+        }
+        for host in allowed_hosts.iter() {
+            if !Self::is_valid_image_host(&host) {
+                panic!("Image hosts must be lowercase domain names");
+            }
+        }
+        if !env
             .storage()
             .persistent()
-            .get::<_, ThreadMeta>(&BoardKey::BoardThread(board_id, thread_id))
+            .has(&BoardKey::BoardConfig(board_id))
         {
-            thread.is_pinned = pinned;
-            thread.updated_at = env.ledger().timestamp();
+            panic!("Board not found");
+        }
+        env.storage().persistent().set(
+            &SettingKey::BoardImagePolicy(board_id),
+            &ImagePolicy {
+                allow_images,
+                allowed_hosts,
+            },
+        );
+    }
 
-            // Update pinned list
-            let mut pinned_list: Vec<u64> = env
-                .storage()
-                .persistent()
-                .get(&BoardKey::BoardPinnedThreads(board_id))
-                .unwrap_or(Vec::new(&env));
+    /// Image hosts are 1-64 lowercase ASCII letters, digits, dots or dashes
+    fn is_valid_image_host(host: &String) -> bool {
+        let len = host.len() as usize;
+        if !(1..=MAX_IMAGE_HOST_LEN).contains(&len) {
+            return false;
+        }
+        let mut buf = [0u8; MAX_IMAGE_HOST_LEN];
+        host.copy_into_slice(&mut buf[..len]);
+        buf[..len]
+            .iter()
+            .all(|b| b.is_ascii_lowercase() || b.is_ascii_digit() || *b == b'.' || *b == b'-')
+    }
 
-            if pinned {
-                // Add to list if not already present
-                let mut found = false;
-                for i in 0..pinned_list.len() {
-                    if pinned_list.get(i).unwrap() == thread_id {
-                        found = true;
-                        break;
-                    }
-                }
-                if !found {
-                    pinned_list.push_back(thread_id);
-                }
-            } else {
-                // Remove from list
-                let mut new_list = Vec::new(&env);
-                for i in 0..pinned_list.len() {
-                    let id = pinned_list.get(i).unwrap();
-                    if id != thread_id {
-                        new_list.push_back(id);
-                    }
-                }
-                pinned_list = new_list;
-            }
+    /// Get the board's blocked terms, in the order they were added
+    pub fn list_blocked_terms(env: Env, board_id: u64) -> Vec<String> {
+        env.storage()
+            .persistent()
+            .get(&BoardKey::BlockedTerms(board_id))
+            .unwrap_or(Vec::new(&env))
+    }
 
-            env.storage()
-                .persistent()
-                .set(&BoardKey::BoardPinnedThreads(board_id), &pinned_list);
-            env.storage()
-                .persistent()
-                .set(&BoardKey::BoardThread(board_id, thread_id), &thread);
+    /// Add a term to the board's blocklist (owner/admin only). Terms are stored
+    /// lowercase; adding one that is already listed does nothing.
+    pub fn add_blocked_term(env: Env, board_id: u64, term: String, caller: Address) {
+        Self::require_blocklist_admin(&env, board_id, &caller);
+        let term = Self::normalize_blocked_term(&env, &term);
+        let mut terms = Self::list_blocked_terms(env.clone(), board_id);
+        if terms.contains(&term) {
+            return;
+        }
+        if terms.len() >= MAX_BLOCKED_TERMS {
+            panic!("Blocklist is full");
         }
+        terms.push_back(term);
+        env.storage()
+            .persistent()
+            .set(&BoardKey::BlockedTerms(board_id), &terms);
     }
 
-    /// Delete a thread (soft delete - sets is_deleted flag)
-    /// Only author or moderator+ can delete
-    pub fn delete_thread(env: Env, board_id: u64, thread_id: u64, caller: Address) {
-        caller.require_auth();
-
-        if let Some(mut thread) = env
-            .storage()
+    /// Remove a term from the board's blocklist (owner/admin only), ignoring case
+    pub fn remove_blocked_term(env: Env, board_id: u64, term: String, caller: Address) {
+        Self::require_blocklist_admin(&env, board_id, &caller);
+        let term = Self::normalize_blocked_term(&env, &term);
+        let mut terms = Self::list_blocked_terms(env.clone(), board_id);
+        let Some(index) = terms.first_index_of(&term) else {
+            return;
+        };
+        terms.remove(index);
+        env.storage()
             .persistent()
-            .get::<_, ThreadMeta>(&BoardKey::BoardThread(board_id, thread_id))
-        {
-            // Verify caller is author or moderator
-            let is_author = thread.creator == caller;
-            let is_moderator = if env.storage().instance().has(&BoardKey::Permissions) {
-                let permissions: Address = env
-                    .storage()
-                    .instance()
-                    .get(&BoardKey::Permissions)
-                    .unwrap();
-                let args: Vec<Val> =
-                    Vec::from_array(&env, [board_id.into_val(&env), caller.into_val(&env)]);
-                let fn_name = Symbol::new(&env, "can_moderate");
-                env.invoke_contract(&permissions, &fn_name, args)
-            } else {
-                false
-            };
+            .set(&BoardKey::BlockedTerms(board_id), &terms);
+    }
 
-            if !is_author && !is_moderator {
-                panic!("Only author or moderator can delete thread");
+    /// Whether `text` contains any of the board's blocked terms, ignoring ASCII case.
+    /// Called by the content contract before it stores a post.
+    pub fn contains_blocked_term(env: Env, board_id: u64, text: Bytes) -> bool {
+        let terms = Self::list_blocked_terms(env.clone(), board_id);
+        if terms.is_empty() || text.is_empty() {
+            return false;
+        }
+
+        // Windows overlap by one byte less than the longest term, so a match
+        // straddling a window boundary is still found whole in the next window
+        let len = text.len();
+        let mut window = [0u8; BLOCKLIST_SCAN_WINDOW];
+        let mut term_buf = [0u8; MAX_BLOCKED_TERM_LEN];
+        let mut start = 0u32;
+        loop {
+            let end = len.min(start + BLOCKLIST_SCAN_WINDOW as u32);
+            let window_len = (end - start) as usize;
+            text.slice(start..end)
+                .copy_into_slice(&mut window[..window_len]);
+            window[..window_len].make_ascii_lowercase();
+            let haystack = &window[..window_len];
+
+            for term in terms.iter() {
+                let term_len = term.len() as usize;
+                term.copy_into_slice(&mut term_buf[..term_len]);
+                if haystack
+                    .windows(term_len)
+                    .any(|candidate| candidate == &term_buf[..term_len])
+                {
+                    return true;
+                }
             }
 
-            thread.is_deleted = true;
-            thread.updated_at = env.ledger().timestamp();
-            env.storage()
-                .persistent()
-                .set(&BoardKey::BoardThread(board_id, thread_id), &thread);
+            if end == len {
+                return false;
+            }
+            start = end - (MAX_BLOCKED_TERM_LEN as u32 - 1);
         }
     }
 
-    /// Edit thread title (author or moderator)
-    pub fn edit_thread_title(
-        env: Env,
-        board_id: u64,
-        thread_id: u64,
-        new_title: String,
-        caller: Address,
-    ) {
+    /// Panic with `BoardError::BlockedTerm` if the title contains a blocked term
+    fn check_blocked_title(env: &Env, board_id: u64, title: &String) {
+        let text = soroban_render_sdk::bytes::string_to_bytes(env, title);
+        if Self::contains_blocked_term(env.clone(), board_id, text) {
+            panic_with_error!(env, BoardError::BlockedTerm);
+        }
+    }
+
+    /// Blocklist changes need the board owner or an admin
+    fn require_blocklist_admin(env: &Env, board_id: u64, caller: &Address) {
         caller.require_auth();
 
-        if let Some(mut thread) = env
+        // Check admin permissions (only if permissions contract is set)
+        if let Some(permissions) = env
             .storage()
-            .persistent()
-            .get::<_, ThreadMeta>(&BoardKey::BoardThread(board_id, thread_id))
+            .instance()
+            .get::<_, Address>(&BoardKey::Permissions)
         {
-            // Verify caller is author or moderator
-            let is_author = thread.creator == caller;
-            let is_moderator = if env.storage().instance().has(&BoardKey::Permissions) {
-                let permissions: Address = env
-                    .storage()
-                    .instance()
-                    .get(&BoardKey::Permissions)
-                    .unwrap();
-                let args: Vec<Val> =
-                    Vec::from_array(&env, [board_id.into_val(&env), caller.into_val(&env)]);
-                let fn_name = Symbol::new(&env, "can_moderate");
-                env.invoke_contract(&permissions, &fn_name, args)
-            } else {
-                false
-            };
-
-            if !is_author && !is_moderator {
-                panic!("Only author or moderator can edit title");
+            let args: Vec<Val> =
+                Vec::from_array(env, [board_id.into_val(env), caller.into_val(env)]);
+            let can_admin: bool =
+                env.invoke_contract(&permissions, &Symbol::new(env, "can_admin"), args);
+            if !can_admin {
+                panic!("Only owner or admin can change the blocklist");
             }
+        }
+        if !env
+            .storage()
+            .persistent()
+            .has(&BoardKey::BoardConfig(board_id))
+        {
+            panic!("Board not found");
+        }
+    }
 
-            thread.title = new_title;
-            thread.updated_at = env.ledger().timestamp();
-            env.storage()
-                .persistent()
-                .set(&BoardKey::BoardThread(board_id, thread_id), &thread);
+    /// Trim and ASCII-lowercase a blocked term, which must end up 1-64 bytes long
+    fn normalize_blocked_term(env: &Env, term: &String) -> String {
+        let len = term.len() as usize;
+        if len > MAX_BLOCKED_TERM_LEN {
+            panic!("Blocked terms must be 1-64 bytes");
         }
+        let mut buf = [0u8; MAX_BLOCKED_TERM_LEN];
+        term.copy_into_slice(&mut buf[..len]);
+        let trimmed = buf[..len].trim_ascii();
+        if trimmed.is_empty() {
+            panic!("Blocked terms must be 1-64 bytes");
+        }
+        let mut lower = [0u8; MAX_BLOCKED_TERM_LEN];
+        lower[..trimmed.len()].copy_from_slice(trimmed);
+        lower[..trimmed.len()].make_ascii_lowercase();
+        String::from_bytes(env, &lower[..trimmed.len()])
     }
 
-    /// Increment reply count for a thread (called by content contract)
-    pub fn increment_reply_count(env: Env, board_id: u64, thread_id: u64) {
-        if let Some(mut thread) = env
+    /// Get the board's locale (None if the board hasn't declared one)
+    pub fn get_locale(env: Env, board_id: u64) -> Option<Symbol> {
+        env.storage()
+            .persistent()
+            .get(&SettingKey::BoardLocale(board_id))
+    }
+
+    /// Set the board's locale, one of BOARD_LOCALES, or None to clear it (owner/admin only).
+    /// The locale picks how dates are written and is shared with the registry's board list.
+    pub fn set_locale(env: Env, board_id: u64, locale: Option<Symbol>, caller: Address) {
+        caller.require_auth();
+
+        // Check admin permissions (only if permissions contract is set)
+        if let Some(permissions) = env
+            .storage()
+            .instance()
+            .get::<_, Address>(&BoardKey::Permissions)
+        {
+            let args: Vec<Val> =
+                Vec::from_array(&env, [board_id.into_val(&env), caller.into_val(&env)]);
+            let can_admin: bool =
+                env.invoke_contract(&permissions, &Symbol::new(&env, "can_admin"), args);
+            if !can_admin {
+                panic!("Only owner or admin can change the board locale");
+            }
+        }
+
+        if let Some(locale) = &locale {
+            if Self::locale_entry(&env, locale).is_none() {
+                panic!("Unsupported locale");
+            }
+        }
+        if !env
             .storage()
             .persistent()
-            .get::<_, ThreadMeta>(&BoardKey::BoardThread(board_id, thread_id))
+            .has(&BoardKey::BoardConfig(board_id))
         {
-            thread.reply_count += 1;
-            thread.updated_at = env.ledger().timestamp();
-            env.storage()
+            panic!("Board not found");
+        }
+        match &locale {
+            Some(locale) => env
+                .storage()
                 .persistent()
-                .set(&BoardKey::BoardThread(board_id, thread_id), &thread);
+                .set(&SettingKey::BoardLocale(board_id), locale),
+            None => env
+                .storage()
+                .persistent()
+                .remove(&SettingKey::BoardLocale(board_id)),
+        }
+
+        // Let the registry list boards by language. Failures are ignored.
+        if let Some(registry) = env
+            .storage()
+            .instance()
+            .get::<_, Address>(&BoardKey::Registry)
+        {
+            let _ = env.try_invoke_contract::<(), soroban_sdk::Error>(
+                &registry,
+                &Symbol::new(&env, "report_board_locale"),
+                Vec::from_array(&env, [board_id.into_val(&env), locale.into_val(&env)]),
+            );
         }
     }
 
-    // ========================================================================
-    // Rendering - Board, thread, and reply views
-    // ========================================================================
+    /// The BOARD_LOCALES entry for a locale, if it's supported
+    fn locale_entry(env: &Env, locale: &Symbol) -> Option<(&'static str, DateStyle)> {
+        BOARD_LOCALES
+            .iter()
+            .find(|(code, _)| Symbol::new(env, code) == *locale)
+            .copied()
+    }
 
-    /// Main render entry point for board routes
-    /// Routes are relative to the board (e.g., "/" = board view, "/t/0" = thread 0)
-    /// board_id is now passed as a parameter
-    /// community_slug: If board is in a community, pass the community's URL slug here
-    ///                 to enable proper path building without re-entrant calls
-    pub fn render(
+    /// How dates are written on a board (ISO unless its locale says otherwise)
+    fn date_style(env: &Env, board_id: u64) -> DateStyle {
+        Self::get_locale(env.clone(), board_id)
+            .and_then(|locale| Self::locale_entry(env, &locale))
+            .map_or(DateStyle::Iso, |(_, style)| style)
+    }
+
+    /// Get the minimum total karma needed to create threads (0 = no threshold)
+    pub fn get_min_karma_to_post(env: Env, board_id: u64) -> i64 {
+        env.storage()
+            .persistent()
+            .get(&SettingKey::BoardMinKarmaToPost(board_id))
+            .unwrap_or(0)
+    }
+
+    /// Get the minimum total karma needed to reply (0 = no threshold)
+    pub fn get_min_karma_to_reply(env: Env, board_id: u64) -> i64 {
+        env.storage()
+            .persistent()
+            .get(&SettingKey::BoardMinKarmaToReply(board_id))
+            .unwrap_or(0)
+    }
+
+    /// Set the karma thresholds for posting and replying (owner/admin only, 0 = off)
+    pub fn set_karma_thresholds(
         env: Env,
         board_id: u64,
-        path: Option<String>,
-        viewer: Option<Address>,
-        community_slug: Option<String>,
-    ) -> Bytes {
-        // Store community_slug in temp storage for use during this render
-        // This avoids re-entrant calls back to community contract
-        if let Some(ref slug) = community_slug {
-            env.storage()
-                .temporary()
-                .set(&BoardKey::RenderCommunitySlug, slug);
-        } else {
-            // Clear any stale value
-            env.storage()
-                .temporary()
-                .remove(&BoardKey::RenderCommunitySlug);
+        min_karma_to_post: i64,
+        min_karma_to_reply: i64,
+        caller: Address,
+    ) {
+        caller.require_auth();
+
+        // Check admin permissions (only if permissions contract is set)
+        if let Some(permissions) = env
+            .storage()
+            .instance()
+            .get::<_, Address>(&BoardKey::Permissions)
+        {
+            let args: Vec<Val> =
+                Vec::from_array(&env, [board_id.into_val(&env), caller.into_val(&env)]);
+            let can_admin: bool =
+                env.invoke_contract(&permissions, &Symbol::new(&env, "can_admin"), args);
+            if !can_admin {
+                panic!("Only owner or admin can change karma thresholds");
+            }
         }
 
-        Router::new(&env, path.clone())
-            // Board view (thread list)
-            .handle(b"/", |_| Self::render_board(&env, board_id, &viewer))
-            // Create thread form
-            .or_handle(b"/new", |_| {
-                Self::render_create_thread(&env, board_id, &viewer)
-            })
-            // Thread reply form (must be before thread view)
-            .or_handle(b"/t/{tid}/reply", |req| {
-                let thread_id = req.get_var_u32(b"tid").unwrap_or(0) as u64;
-                Self::render_reply_form(&env, board_id, thread_id, None, &viewer)
-            })
-            // Load top-level replies batch (waterfall loading)
-            .or_handle(b"/t/{tid}/replies/{start}", |req| {
-                let thread_id = req.get_var_u32(b"tid").unwrap_or(0) as u64;
-                let start = req.get_var_u32(b"start").unwrap_or(0);
-                Self::render_replies_batch(&env, board_id, thread_id, start, &viewer)
-            })
-            // Load children of a reply batch
-            .or_handle(b"/t/{tid}/r/{rid}/children/{start}", |req| {
-                let thread_id = req.get_var_u32(b"tid").unwrap_or(0) as u64;
-                let reply_id = req.get_var_u32(b"rid").unwrap_or(0) as u64;
-                let start = req.get_var_u32(b"start").unwrap_or(0);
-                Self::render_children_batch(&env, board_id, thread_id, reply_id, start, &viewer)
-            })
-            // Nested reply form
-            .or_handle(b"/t/{tid}/r/{rid}/reply", |req| {
-                let thread_id = req.get_var_u32(b"tid").unwrap_or(0) as u64;
-                let reply_id = req.get_var_u32(b"rid").unwrap_or(0) as u64;
-                Self::render_reply_form(&env, board_id, thread_id, Some(reply_id), &viewer)
-            })
-            // Edit thread form
-            .or_handle(b"/t/{tid}/edit", |req| {
-                let thread_id = req.get_var_u32(b"tid").unwrap_or(0) as u64;
-                Self::render_edit_thread(&env, board_id, thread_id, &viewer)
-            })
-            // Edit reply form
-            .or_handle(b"/t/{tid}/r/{rid}/edit", |req| {
-                let thread_id = req.get_var_u32(b"tid").unwrap_or(0) as u64;
-                let reply_id = req.get_var_u32(b"rid").unwrap_or(0) as u64;
-                Self::render_edit_reply(&env, board_id, thread_id, reply_id, &viewer)
-            })
-            // Thread view
-            .or_handle(b"/t/{tid}", |req| {
-                let thread_id = req.get_var_u32(b"tid").unwrap_or(0) as u64;
-                Self::render_thread(&env, board_id, thread_id, &viewer)
-            })
-            // Default - board view
-            .or_default(|_| Self::render_board(&env, board_id, &viewer))
+        if min_karma_to_post < 0 || min_karma_to_reply < 0 {
+            panic!("Karma thresholds cannot be negative");
+        }
+        if !env
+            .storage()
+            .persistent()
+            .has(&BoardKey::BoardConfig(board_id))
+        {
+            panic!("Board not found");
+        }
+        env.storage().persistent().set(
+            &SettingKey::BoardMinKarmaToPost(board_id),
+            &min_karma_to_post,
+        );
+        env.storage().persistent().set(
+            &SettingKey::BoardMinKarmaToReply(board_id),
+            &min_karma_to_reply,
+        );
     }
 
-    /// Render navigation bar via include from main contract.
-    /// Uses {{include}} tag for deferred loading - no cross-contract call overhead.
-    fn render_nav<'a>(
-        env: &'a Env,
-        board_id: u64,
-        _viewer: &Option<Address>,
-    ) -> MarkdownBuilder<'a> {
-        let aliases = Self::fetch_aliases(env);
+    /// Check if a user has been exempted from the board's karma thresholds
+    pub fn is_karma_exempt(env: Env, board_id: u64, user: Address) -> bool {
+        env.storage()
+            .persistent()
+            .has(&UserKey::BoardKarmaExempt(board_id, user))
+    }
 
-        // Get board metadata for slug-based return path
-        let board_meta: Option<BoardMeta> =
-            env.storage().persistent().get(&BoardKey::Board(board_id));
+    /// Exempt a user from (or subject them to) the karma thresholds (moderator+ only)
+    pub fn set_karma_exempt(env: Env, board_id: u64, user: Address, exempt: bool, caller: Address) {
+        caller.require_auth();
 
-        // Build include tag with return path for this board
-        // Format: {{include contract=@main func="render_nav_include" viewer return_path="@main:{base_path}"}}
-        let mut include_tag = Bytes::from_slice(
-            env,
-            b"{{include contract=@main func=\"render_nav_include\" viewer return_path=\"@main:",
-        );
+        if env.storage().instance().has(&BoardKey::Permissions) {
+            Self::check_can_moderate(&env, board_id, &caller);
+        }
 
-        if let Some(meta) = board_meta {
-            include_tag.append(&Self::build_board_base_path(env, board_id, &meta.slug));
+        let key = UserKey::BoardKarmaExempt(board_id, user);
+        if exempt {
+            env.storage().persistent().set(&key, &true);
         } else {
-            // Fallback to numeric ID if board not found
-            include_tag.append(&Bytes::from_slice(env, b"/b/"));
-            include_tag.append(&u64_to_bytes(env, board_id));
+            env.storage().persistent().remove(&key);
         }
-        include_tag.append(&Bytes::from_slice(env, b"\"}}"));
+    }
 
-        MarkdownBuilder::new(env)
-            .raw(aliases) // Emit aliases for include resolution
-            .raw(include_tag)
+    /// Get the seconds a user must wait between replies in the same thread (0 = off)
+    pub fn get_reply_cooldown(env: Env, board_id: u64) -> u64 {
+        env.storage()
+            .persistent()
+            .get(&SettingKey::BoardReplyCooldown(board_id))
+            .unwrap_or(0)
     }
 
-    /// Render back navigation with optional community link
-    /// Shows "← Community Name" if board is in a community, plus "← Home"
-    fn render_back_nav<'a>(
-        env: &'a Env,
-        mut md: MarkdownBuilder<'a>,
-        board_id: u64,
-    ) -> MarkdownBuilder<'a> {
-        md = md.div_start("back-nav");
-
-        // Check if board is in a community
-        if let Some(community) = Self::get_board_community(env, board_id) {
-            // Build community display name
-            let mut display_buf = [0u8; 64];
-            let display_len = community.display_name.len() as usize;
-            let display_copy_len = core::cmp::min(display_len, 60);
-            community
-                .display_name
-                .copy_into_slice(&mut display_buf[0..display_copy_len]);
-            let display =
-                core::str::from_utf8(&display_buf[0..display_copy_len]).unwrap_or("Community");
-
-            // Build community name for URL
-            let mut name_buf = [0u8; 32];
-            let name_len = community.name.len() as usize;
-            let name_copy_len = core::cmp::min(name_len, 32);
-            community
-                .name
-                .copy_into_slice(&mut name_buf[0..name_copy_len]);
-            let name = core::str::from_utf8(&name_buf[0..name_copy_len]).unwrap_or("");
+    /// Set the per-thread reply cooldown (owner/admin only, 0 = off). The content contract
+    /// enforces it; moderators are exempt.
+    pub fn set_reply_cooldown(env: Env, board_id: u64, seconds: u64, caller: Address) {
+        caller.require_auth();
 
-            // Community link
-            md = md
-                .raw_str("<a href=\"render:/c/")
-                .raw_str(name)
-                .raw_str("\" class=\"back-link back-community\">← ")
-                .raw_str(display)
-                .raw_str("</a>");
+        // Check admin permissions (only if permissions contract is set)
+        if let Some(permissions) = env
+            .storage()
+            .instance()
+            .get::<_, Address>(&BoardKey::Permissions)
+        {
+            let args: Vec<Val> =
+                Vec::from_array(&env, [board_id.into_val(&env), caller.into_val(&env)]);
+            let can_admin: bool =
+                env.invoke_contract(&permissions, &Symbol::new(&env, "can_admin"), args);
+            if !can_admin {
+                panic!("Only owner or admin can change the reply cooldown");
+            }
         }
 
-        // Home link (always shown)
-        md = md.raw_str("<a href=\"render:/\" class=\"back-link back-home\">← Home</a>");
+        if seconds > MAX_REPLY_COOLDOWN {
+            panic!("Reply cooldown cannot exceed 3600 seconds");
+        }
+        if !env
+            .storage()
+            .persistent()
+            .has(&BoardKey::BoardConfig(board_id))
+        {
+            panic!("Board not found");
+        }
+        env.storage()
+            .persistent()
+            .set(&SettingKey::BoardReplyCooldown(board_id), &seconds);
+    }
 
-        md.div_end()
+    /// Check whether non-moderators can read the board's moderation log
+    pub fn is_modlog_public(env: Env, board_id: u64) -> bool {
+        env.storage()
+            .persistent()
+            .has(&SettingKey::BoardPublicModlog(board_id))
     }
 
-    /// Append footer to builder via include from main contract.
-    /// Uses {{include}} tag for deferred loading - no cross-contract call overhead.
-    fn render_footer_into<'a>(_env: &'a Env, md: MarkdownBuilder<'a>) -> MarkdownBuilder<'a> {
-        md.raw_str("{{include contract=@main func=\"render_footer_include\"}}")
+    /// Open the moderation log to everyone who can view the board, or close it to
+    /// moderators only (owner/admin only). Actors and reasons stay moderator-only.
+    pub fn set_public_modlog(env: Env, board_id: u64, public_modlog: bool, caller: Address) {
+        caller.require_auth();
+
+        // Check admin permissions (only if permissions contract is set)
+        if let Some(permissions) = env
+            .storage()
+            .instance()
+            .get::<_, Address>(&BoardKey::Permissions)
+        {
+            let args: Vec<Val> =
+                Vec::from_array(&env, [board_id.into_val(&env), caller.into_val(&env)]);
+            let can_admin: bool =
+                env.invoke_contract(&permissions, &Symbol::new(&env, "can_admin"), args);
+            if !can_admin {
+                panic!("Only owner or admin can change the moderation log visibility");
+            }
+        }
+
+        if !env
+            .storage()
+            .persistent()
+            .has(&BoardKey::BoardConfig(board_id))
+        {
+            panic!("Board not found");
+        }
+        let key = SettingKey::BoardPublicModlog(board_id);
+        if public_modlog {
+            env.storage().persistent().set(&key, &true);
+        } else {
+            env.storage().persistent().remove(&key);
+        }
     }
 
-    /// Render a thread card for the board list
-    fn render_thread_card<'a>(
-        env: &'a Env,
-        md: MarkdownBuilder<'a>,
+    /// Karma a user is missing to post on a board, as `(required, current)`.
+    ///
+    /// Returns `None` when no threshold is set, the user is exempt or can moderate,
+    /// or the voting contract is unavailable.
+    pub fn karma_shortfall(
+        env: Env,
         board_id: u64,
-        base_path: &Bytes,
-        thread: &ThreadMeta,
-        voting_contract: &Option<Address>,
-        flairs: &Vec<FlairDef>,
-    ) -> MarkdownBuilder<'a> {
-        // Get vote tally if voting contract is available
-        let score = if let Some(voting) = voting_contract {
-            let args: Vec<Val> =
-                Vec::from_array(env, [board_id.into_val(env), thread.id.into_val(env)]);
-            let tally: VoteTally =
-                env.invoke_contract(voting, &Symbol::new(env, "get_thread_tally"), args);
-            Some(tally.score)
+        user: Address,
+        is_reply: bool,
+    ) -> Option<(i64, i64)> {
+        let required = if is_reply {
+            Self::get_min_karma_to_reply(env.clone(), board_id)
         } else {
-            None
+            Self::get_min_karma_to_post(env.clone(), board_id)
         };
-
-        // Thread card with optional vote score
-        let mut md = md.raw_str("<div class=\"thread-card-wrapper\">");
-
-        // Vote score display (if voting enabled)
-        if let Some(s) = score {
-            md = md
-                .raw_str("<span class=\"vote-score-compact\">")
-                .number(s as u32) // Display score (handle negatives in CSS)
-                .raw_str("</span>");
+        if required <= 0 || Self::is_karma_exempt(env.clone(), board_id, user.clone()) {
+            return None;
         }
 
-        md = md
-            .raw_str("<a href=\"render:")
-            .raw(base_path.clone())
-            .raw_str("/t/")
-            .number(thread.id as u32)
-            .raw_str("\" class=\"thread-card\">");
-
-        // Display flair if thread has one
-        if let Some(flair_id) = thread.flair_id {
-            for i in 0..flairs.len() {
-                let flair = flairs.get(i).unwrap();
-                if flair.id == flair_id && flair.enabled {
-                    md = md
-                        .raw_str("<span class=\"flair\" style=\"color:")
-                        .text_string(&flair.color)
-                        .raw_str(";background:")
-                        .text_string(&flair.bg_color)
-                        .raw_str("\">")
-                        .text_string(&flair.name)
-                        .raw_str("</span> ");
-                    break;
-                }
+        if let Some(permissions) = env
+            .storage()
+            .instance()
+            .get::<_, Address>(&BoardKey::Permissions)
+        {
+            let args: Vec<Val> =
+                Vec::from_array(&env, [board_id.into_val(&env), user.into_val(&env)]);
+            let can_moderate = env
+                .try_invoke_contract::<bool, soroban_sdk::Error>(
+                    &permissions,
+                    &Symbol::new(&env, "can_moderate"),
+                    args,
+                )
+                .ok()
+                .and_then(|r| r.ok())
+                .unwrap_or(false);
+            if can_moderate {
+                return None;
             }
         }
 
-        md = md
-            .raw_str("<span class=\"thread-card-title\">")
-            .text_string(&thread.title)
-            .raw_str("</span><span class=\"thread-card-meta\">");
-        if thread.is_hidden {
-            md = md.raw_str("<span class=\"badge badge-hidden\">hidden</span> ");
-        }
-        if thread.is_pinned {
-            md = md.raw_str("<span class=\"badge badge-pinned\">pinned</span> ");
-        }
-        if thread.is_locked {
-            md = md.raw_str("<span class=\"badge badge-locked\">locked</span> ");
+        let voting: Address = env.storage().instance().get(&BoardKey::Voting)?;
+        let karma = env
+            .try_invoke_contract::<i64, soroban_sdk::Error>(
+                &voting,
+                &Symbol::new(&env, "get_total_karma"),
+                Vec::from_array(&env, [user.into_val(&env)]),
+            )
+            .ok()
+            .and_then(|r| r.ok())?;
+        if karma < required {
+            Some((required, karma))
+        } else {
+            None
         }
-        md.number(thread.reply_count)
-            .text(" replies · ")
-            .raw(Self::format_timestamp(env, thread.created_at))
-            .raw_str("</span></a></div>\n")
     }
 
-    /// Render board view with thread list
-    fn render_board(env: &Env, board_id: u64, viewer: &Option<Address>) -> Bytes {
-        // Get board metadata for slug-based URLs
-        let board_meta: BoardMeta = env
-            .storage()
+    /// Get the minimum total votes a thread needs to appear under the Controversial sort
+    pub fn get_controversial_min_votes(env: Env, board_id: u64) -> u32 {
+        env.storage()
             .persistent()
-            .get(&BoardKey::Board(board_id))
-            .expect("Board not found");
+            .get(&SettingKey::BoardControversialMinVotes(board_id))
+            .unwrap_or(DEFAULT_CONTROVERSIAL_MIN_VOTES)
+    }
 
-        // Build base path for all links
-        let base_path = Self::build_board_base_path(env, board_id, &board_meta.slug);
+    /// Set the minimum total votes for the Controversial sort (owner/admin only, at least 1)
+    pub fn set_controversial_min_votes(env: Env, board_id: u64, min_votes: u32, caller: Address) {
+        caller.require_auth();
 
-        let config: BoardConfig = env
+        // Check admin permissions (only if permissions contract is set)
+        if let Some(permissions) = env
             .storage()
+            .instance()
+            .get::<_, Address>(&BoardKey::Permissions)
+        {
+            let args: Vec<Val> =
+                Vec::from_array(&env, [board_id.into_val(&env), caller.into_val(&env)]);
+            let can_admin: bool =
+                env.invoke_contract(&permissions, &Symbol::new(&env, "can_admin"), args);
+            if !can_admin {
+                panic!("Only owner or admin can change the controversial vote minimum");
+            }
+        }
+
+        if min_votes == 0 {
+            panic!("Controversial vote minimum must be at least 1");
+        }
+        env.storage().persistent().set(
+            &SettingKey::BoardControversialMinVotes(board_id),
+            &min_votes,
+        );
+    }
+
+    /// Get the reply depth at which the thread view stops nesting inline
+    pub fn get_collapse_depth(env: Env, board_id: u64) -> u32 {
+        env.storage()
             .persistent()
-            .get(&BoardKey::BoardConfig(board_id))
-            .expect("Board not found");
+            .get(&SettingKey::BoardCollapseDepth(board_id))
+            .unwrap_or(DEFAULT_COLLAPSE_DEPTH)
+    }
 
-        // Get viewer role (needed for private board check, admin button, and hidden thread filtering)
-        let perms_addr_opt = env
+    /// Set the inline nesting depth for replies (owner/admin only, at least 1)
+    pub fn set_collapse_depth(env: Env, board_id: u64, depth: u32, caller: Address) {
+        caller.require_auth();
+
+        // Check admin permissions (only if permissions contract is set)
+        if let Some(permissions) = env
             .storage()
             .instance()
-            .get::<_, Address>(&BoardKey::Permissions);
-        let viewer_role = if let Some(ref perms_addr) = perms_addr_opt {
-            if let Some(user) = viewer {
-                let args: Vec<Val> =
-                    Vec::from_array(env, [board_id.into_val(env), user.into_val(env)]);
-                env.invoke_contract(perms_addr, &Symbol::new(env, "get_role"), args)
-            } else {
-                Role::Guest
+            .get::<_, Address>(&BoardKey::Permissions)
+        {
+            let args: Vec<Val> =
+                Vec::from_array(&env, [board_id.into_val(&env), caller.into_val(&env)]);
+            let can_admin: bool =
+                env.invoke_contract(&permissions, &Symbol::new(&env, "can_admin"), args);
+            if !can_admin {
+                panic!("Only owner or admin can change the collapse depth");
             }
-        } else {
-            Role::Guest
-        };
-        let viewer_can_moderate = (viewer_role as u32) >= (Role::Moderator as u32);
-
-        // Get voting contract for displaying vote scores
-        let voting_contract: Option<Address> = env.storage().instance().get(&BoardKey::Voting);
+        }
 
-        // Get flairs for displaying on thread cards
-        let flairs: Vec<FlairDef> = env
+        if depth == 0 {
+            panic!("Collapse depth must be at least 1");
+        }
+        if !env
             .storage()
             .persistent()
-            .get(&BoardKey::BoardFlairDefs(board_id))
-            .unwrap_or(Vec::new(env));
-
-        // Check permissions for private boards
-        if config.is_private {
-            if let Some(ref perms_addr) = perms_addr_opt {
-                // If not a member, show access denied
-                if (viewer_role as u32) < (Role::Member as u32) {
-                    return Self::render_private_board_message(
-                        env, board_id, &config, viewer, perms_addr,
-                    );
-                }
-            }
+            .has(&BoardKey::BoardConfig(board_id))
+        {
+            panic!("Board not found");
         }
+        env.storage()
+            .persistent()
+            .set(&SettingKey::BoardCollapseDepth(board_id), &depth);
+    }
 
-        let mut md = Self::render_nav(env, board_id, viewer);
-        md = Self::render_back_nav(env, md, board_id);
-        md = md
-            .div_start("page-header")
-            .raw_str("<h1>")
-            .text_string(&config.name)
-            .raw_str("</h1>")
-            .raw_str("<p>")
-            .text_string(&config.description)
-            .raw_str("</p>")
-            .div_end()
-            .newline();
+    /// Get the children count above which replies link to their children instead of
+    /// embedding them (0 = always embed)
+    pub fn get_collapse_children_over(env: Env, board_id: u64) -> u32 {
+        env.storage()
+            .persistent()
+            .get(&SettingKey::BoardCollapseChildrenOver(board_id))
+            .unwrap_or(0)
+    }
 
-        if config.is_private {
-            md = md.raw_str("<span class=\"badge badge-private\">private</span> ");
-        }
+    /// Set the children count above which replies show a "Show N replies" link
+    /// (owner/admin only, 0 = always embed)
+    pub fn set_collapse_children_over(env: Env, board_id: u64, count: u32, caller: Address) {
+        caller.require_auth();
 
-        if config.is_readonly {
-            md = md.note("This board is read-only.");
+        // Check admin permissions (only if permissions contract is set)
+        if let Some(permissions) = env
+            .storage()
+            .instance()
+            .get::<_, Address>(&BoardKey::Permissions)
+        {
+            let args: Vec<Val> =
+                Vec::from_array(&env, [board_id.into_val(&env), caller.into_val(&env)]);
+            let can_admin: bool =
+                env.invoke_contract(&permissions, &Symbol::new(&env, "can_admin"), args);
+            if !can_admin {
+                panic!("Only owner or admin can change when replies collapse");
+            }
         }
 
-        // Show board rules if set
-        if let Some(rules) = env
+        if !env
             .storage()
             .persistent()
-            .get::<_, String>(&BoardKey::BoardRules(board_id))
+            .has(&BoardKey::BoardConfig(board_id))
         {
-            if !rules.is_empty() {
-                md = md
-                    .div_start("board-rules")
-                    .raw_str("<details><summary><strong>Board Rules</strong></summary>")
-                    .div_start("rules-content")
-                    .text_string(&rules)
-                    .div_end()
-                    .raw_str("</details>")
-                    .div_end();
-            }
-        }
-
-        // Show create thread button if user has Member+ role and board is not read-only
-        let can_create = viewer.is_some()
-            && !config.is_readonly
-            && (viewer_role as u32) >= (Role::Member as u32);
-        if can_create {
-            md = md
-                .raw_str("<a href=\"render:")
-                .raw(base_path.clone())
-                .raw_str("/new\" class=\"action-btn\">+ New Thread</a>")
-                .newline();
+            panic!("Board not found");
         }
+        env.storage()
+            .persistent()
+            .set(&SettingKey::BoardCollapseChildrenOver(board_id), &count);
+    }
 
-        // Show settings button for Admin+ users (uses numeric ID for admin routes)
-        if (viewer_role as u32) >= (Role::Admin as u32) {
-            md = md
-                .raw_str("<a href=\"render:/admin/b/")
-                .number(board_id as u32)
-                .raw_str("/settings\" class=\"action-btn action-btn-secondary\">⚙ Settings</a>")
-                .newline();
-        }
+    /// Get the reply score below which replies render collapsed
+    pub fn get_collapse_score(env: Env, board_id: u64) -> i32 {
+        env.storage()
+            .persistent()
+            .get(&SettingKey::BoardCollapseScore(board_id))
+            .unwrap_or(DEFAULT_COLLAPSE_SCORE)
+    }
 
-        md = md.raw_str("<h2>Threads</h2>\n");
+    /// Set the low-score collapse threshold for replies (owner/admin only)
+    pub fn set_collapse_score(env: Env, board_id: u64, score: i32, caller: Address) {
+        caller.require_auth();
 
-        // Sort order selector (if voting contract is configured)
-        if voting_contract.is_some() {
-            md = md
-                .div_start("sort-selector")
-                .raw_str("<span class=\"sort-label\">Sort:</span>")
-                // Hot is the default when voting is available
-                .raw_str("<a href=\"render:")
-                .raw(base_path.clone())
-                .raw_str("\" class=\"sort-option sort-active\">Hot</a>")
-                .raw_str("<a href=\"render:")
-                .raw(base_path.clone())
-                .raw_str("?sort=new\" class=\"sort-option\">New</a>")
-                .raw_str("<a href=\"render:")
-                .raw(base_path.clone())
-                .raw_str("?sort=top\" class=\"sort-option\">Top</a>")
-                .raw_str("<a href=\"render:")
-                .raw(base_path.clone())
-                .raw_str("?sort=controversial\" class=\"sort-option\">Controversial</a>")
-                .div_end();
+        // Check admin permissions (only if permissions contract is set)
+        if let Some(permissions) = env
+            .storage()
+            .instance()
+            .get::<_, Address>(&BoardKey::Permissions)
+        {
+            let args: Vec<Val> =
+                Vec::from_array(&env, [board_id.into_val(&env), caller.into_val(&env)]);
+            let can_admin: bool =
+                env.invoke_contract(&permissions, &Symbol::new(&env, "can_admin"), args);
+            if !can_admin {
+                panic!("Only owner or admin can change the collapse score");
+            }
         }
 
-        md = md.div_start("thread-list");
-
-        // Fetch threads
-        let thread_count: u64 = env
+        if !env
             .storage()
             .persistent()
-            .get(&BoardKey::BoardThreadCount(board_id))
-            .unwrap_or(0);
+            .has(&BoardKey::BoardConfig(board_id))
+        {
+            panic!("Board not found");
+        }
+        env.storage()
+            .persistent()
+            .set(&SettingKey::BoardCollapseScore(board_id), &score);
+    }
 
-        if thread_count == 0 {
-            md = md
-                .div_end()
-                .paragraph("No threads yet. Be the first to post!");
-        } else {
-            // Get pinned threads list
-            let pinned_threads: Vec<u64> = env
-                .storage()
-                .persistent()
-                .get(&BoardKey::BoardPinnedThreads(board_id))
-                .unwrap_or(Vec::new(env));
+    /// Get maximum reply depth for nested replies
+    pub fn get_max_reply_depth(env: Env, board_id: u64) -> u32 {
+        let config: BoardConfig = env
+            .storage()
+            .persistent()
+            .get(&BoardKey::BoardConfig(board_id))
+            .expect("Board not found");
+        config.max_reply_depth
+    }
 
-            let limit = 20u64;
-            let mut shown = 0u64;
+    /// Set maximum reply depth (must be >= 1, owner/admin only)
+    pub fn set_max_reply_depth(env: Env, board_id: u64, depth: u32, caller: Address) {
+        caller.require_auth();
 
-            // First, render pinned threads
-            for i in 0..pinned_threads.len() {
-                if shown >= limit {
-                    break;
-                }
-                let thread_id = pinned_threads.get(i).unwrap();
-                if let Some(thread) = env
-                    .storage()
-                    .persistent()
-                    .get::<_, ThreadMeta>(&BoardKey::BoardThread(board_id, thread_id))
-                {
-                    // Skip hidden threads for non-moderators
-                    if thread.is_hidden && !viewer_can_moderate {
-                        continue;
-                    }
-                    md = Self::render_thread_card(
-                        env,
-                        md,
-                        board_id,
-                        &base_path,
-                        &thread,
-                        &voting_contract,
-                        &flairs,
-                    );
-                    shown += 1;
-                }
-            }
+        if depth < 1 {
+            panic!("Max reply depth must be at least 1");
+        }
 
-            // Then render remaining threads (newest first), skipping pinned ones
-            let start_idx = thread_count - 1;
-            let mut idx = start_idx;
-            while shown < limit && idx < thread_count {
-                if let Some(thread) = env
-                    .storage()
-                    .persistent()
-                    .get::<_, ThreadMeta>(&BoardKey::BoardThread(board_id, idx))
-                {
-                    // Skip pinned threads (already shown above)
-                    if thread.is_pinned {
-                        if idx > 0 {
-                            idx -= 1;
-                        } else {
-                            break;
-                        }
-                        continue;
-                    }
-                    // Skip hidden threads for non-moderators
-                    if thread.is_hidden && !viewer_can_moderate {
-                        if idx > 0 {
-                            idx -= 1;
-                        } else {
-                            break;
-                        }
-                        continue;
-                    }
-                    md = Self::render_thread_card(
-                        env,
-                        md,
-                        board_id,
-                        &base_path,
-                        &thread,
-                        &voting_contract,
-                        &flairs,
-                    );
-                    shown += 1;
-                }
-                if idx > 0 {
-                    idx -= 1;
-                } else {
-                    break;
-                }
+        // Check admin permissions (only if permissions contract is set)
+        if env.storage().instance().has(&BoardKey::Permissions) {
+            let permissions: Address = env
+                .storage()
+                .instance()
+                .get(&BoardKey::Permissions)
+                .unwrap();
+            let args: Vec<Val> =
+                Vec::from_array(&env, [board_id.into_val(&env), caller.into_val(&env)]);
+            let fn_name = Symbol::new(&env, "can_admin");
+            let can_admin: bool = env.invoke_contract(&permissions, &fn_name, args);
+            if !can_admin {
+                panic!("Only owner or admin can change max reply depth");
             }
-            md = md.div_end();
         }
 
-        Self::render_footer_into(env, md).build()
+        let mut config: BoardConfig = env
+            .storage()
+            .persistent()
+            .get(&BoardKey::BoardConfig(board_id))
+            .expect("Board not found");
+        config.max_reply_depth = depth;
+        env.storage()
+            .persistent()
+            .set(&BoardKey::BoardConfig(board_id), &config);
     }
 
-    /// Render private board access denied message
-    fn render_private_board_message(
-        env: &Env,
-        board_id: u64,
-        config: &BoardConfig,
-        viewer: &Option<Address>,
-        perms_addr: &Address,
-    ) -> Bytes {
-        let mut md = Self::render_nav(env, board_id, viewer);
-        md = Self::render_back_nav(env, md, board_id);
-        md = md
-            .div_start("page-header")
-            .raw_str("<h1>")
-            .text_string(&config.name)
-            .raw_str("</h1>")
-            .raw_str("<p>")
-            .text_string(&config.description)
-            .raw_str("</p>")
-            .div_end()
-            .newline()
-            .raw_str("<span class=\"badge badge-private\">private</span>")
-            .newline()
-            .newline();
+    /// Get edit window in seconds (0 = no limit)
+    pub fn get_edit_window(env: Env, board_id: u64) -> u64 {
+        // Default to 86400 (24 hours) if not set
+        env.storage()
+            .persistent()
+            .get(&BoardKey::BoardEditWindow(board_id))
+            .unwrap_or(86400u64)
+    }
 
-        if viewer.is_none() {
-            md = md
-                .warning("This is a private board. Please connect your wallet to request access.");
-        } else {
-            // Check if user has a pending invite request
-            let has_request_args: Vec<Val> = Vec::from_array(
-                env,
-                [
-                    board_id.into_val(env),
-                    viewer.as_ref().unwrap().into_val(env),
-                ],
-            );
-            let has_request: bool = env.invoke_contract(
-                perms_addr,
-                &Symbol::new(env, "has_invite_request"),
-                has_request_args,
-            );
+    /// Set edit window in seconds (0 = no limit, owner/admin only)
+    pub fn set_edit_window(env: Env, board_id: u64, seconds: u64, caller: Address) {
+        caller.require_auth();
 
-            if has_request {
-                md = md.tip("Your request to join this board is pending approval.");
-            } else {
-                md = md.note("This is a private board. You can request access from the board administrators.")
-                    .newline()
-                    .raw_str("<input type=\"hidden\" name=\"board_id\" value=\"")
-                    .number(board_id as u32)
-                    .raw_str("\" />\n")
-                    .raw_str("<input type=\"hidden\" name=\"caller\" value=\"")
-                    .text_string(&viewer.as_ref().unwrap().to_string())
-                    .raw_str("\" />\n")
-                    .form_link_to("Request to Join", "perms", "request_invite");
+        // Check admin permissions (only if permissions contract is set)
+        if env.storage().instance().has(&BoardKey::Permissions) {
+            let permissions: Address = env
+                .storage()
+                .instance()
+                .get(&BoardKey::Permissions)
+                .unwrap();
+            let args: Vec<Val> =
+                Vec::from_array(&env, [board_id.into_val(&env), caller.into_val(&env)]);
+            let fn_name = Symbol::new(&env, "can_admin");
+            let can_admin: bool = env.invoke_contract(&permissions, &fn_name, args);
+            if !can_admin {
+                panic!("Only owner or admin can change edit window");
             }
         }
 
-        Self::render_footer_into(env, md).build()
+        env.storage()
+            .persistent()
+            .set(&BoardKey::BoardEditWindow(board_id), &seconds);
     }
 
-    /// Render hidden thread access denied message
-    fn render_hidden_thread_message(env: &Env, board_id: u64, viewer: &Option<Address>) -> Bytes {
-        // Get board metadata for slug-based URLs
-        let board_meta: BoardMeta = env
-            .storage()
+    /// Check if board is read-only
+    pub fn is_readonly(env: Env, board_id: u64) -> bool {
+        env.storage()
             .persistent()
-            .get(&BoardKey::Board(board_id))
-            .expect("Board not found");
+            .get::<_, BoardConfig>(&BoardKey::BoardConfig(board_id))
+            .map(|c| c.is_readonly)
+            .unwrap_or(false)
+    }
 
-        // Build base path for links
-        let base_path = Self::build_board_base_path(env, board_id, &board_meta.slug);
+    /// Set board read-only status (owner/admin only)
+    pub fn set_readonly(env: Env, board_id: u64, is_readonly: bool, caller: Address) {
+        caller.require_auth();
 
-        let md = Self::render_nav(env, board_id, viewer)
-            .newline()
-            .raw_str("<div class=\"back-nav\"><a href=\"render:")
-            .raw(base_path)
-            .raw_str("\" class=\"back-link\">← Back to Board</a></div>\n")
-            .newline()
-            .warning("This thread has been hidden by a moderator.");
-        Self::render_footer_into(env, md).build()
-    }
+        // Check admin permissions (only if permissions contract is set)
+        if env.storage().instance().has(&BoardKey::Permissions) {
+            let permissions: Address = env
+                .storage()
+                .instance()
+                .get(&BoardKey::Permissions)
+                .unwrap();
+            let args: Vec<Val> =
+                Vec::from_array(&env, [board_id.into_val(&env), caller.into_val(&env)]);
+            let fn_name = Symbol::new(&env, "can_admin");
+            let can_admin: bool = env.invoke_contract(&permissions, &fn_name, args);
+            if !can_admin {
+                panic!("Only owner or admin can change read-only status");
+            }
+        }
 
-    /// Render create thread form
-    fn render_create_thread(env: &Env, board_id: u64, viewer: &Option<Address>) -> Bytes {
-        // Get board metadata for slug-based URLs
-        let board_meta: BoardMeta = env
+        if !is_readonly && Self::is_archived(env.clone(), board_id) {
+            panic!("Board is archived");
+        }
+
+        let mut config: BoardConfig = env
             .storage()
             .persistent()
-            .get(&BoardKey::Board(board_id))
+            .get(&BoardKey::BoardConfig(board_id))
             .expect("Board not found");
+        config.is_readonly = is_readonly;
+        env.storage()
+            .persistent()
+            .set(&BoardKey::BoardConfig(board_id), &config);
 
-        // Build base path for all links
-        let base_path = Self::build_board_base_path(env, board_id, &board_meta.slug);
+        // Update BoardMeta too
+        if let Some(mut meta) = env
+            .storage()
+            .persistent()
+            .get::<_, BoardMeta>(&BoardKey::Board(board_id))
+        {
+            meta.is_readonly = is_readonly;
+            env.storage()
+                .persistent()
+                .set(&BoardKey::Board(board_id), &meta);
+        }
+    }
+
+    /// Archive a board (owner only): makes it read-only, unlists it and
+    /// records when it was archived. Reversed by `unarchive_board`.
+    pub fn archive_board(env: Env, board_id: u64, caller: Address) {
+        caller.require_auth();
+        Self::require_board_owner(&env, board_id, &caller);
+
+        if Self::is_archived(env.clone(), board_id) {
+            panic!("Board is already archived");
+        }
 
-        // Get board config for private board check
         let config: BoardConfig = env
             .storage()
             .persistent()
             .get(&BoardKey::BoardConfig(board_id))
             .expect("Board not found");
 
-        // Get permissions contract and viewer role early for private board check
-        let perms_addr_opt = env
-            .storage()
-            .instance()
-            .get::<_, Address>(&BoardKey::Permissions);
-        let (viewer_role, is_moderator) = if let Some(ref perms_addr) = perms_addr_opt {
-            if let Some(user) = viewer {
-                let role_args: Vec<Val> =
-                    Vec::from_array(env, [board_id.into_val(env), user.into_val(env)]);
-                let role: Role =
-                    env.invoke_contract(perms_addr, &Symbol::new(env, "get_role"), role_args);
-                let can_mod = (role as u32) >= (Role::Moderator as u32);
-                (role, can_mod)
-            } else {
-                (Role::Guest, false)
-            }
-        } else {
-            (Role::Guest, false)
+        let before = Self::board_filters(&env, board_id);
+        let archive = BoardArchive {
+            archived_at: env.ledger().timestamp(),
+            archived_by: caller,
+            was_listed: Self::get_board_listed(env.clone(), board_id),
+            was_readonly: config.is_readonly,
         };
+        env.storage()
+            .persistent()
+            .set(&BoardKey::BoardArchive(board_id), &archive);
 
-        // Check permissions for private boards - must be Member+ to access
-        if config.is_private {
-            if let Some(ref perms_addr) = perms_addr_opt {
-                if (viewer_role as u32) < (Role::Member as u32) {
-                    return Self::render_private_board_message(
-                        env, board_id, &config, viewer, perms_addr,
-                    );
-                }
-            }
-        }
+        Self::apply_listed_readonly(&env, board_id, false, true);
+        Self::reindex_board(&env, board_id, &before);
+    }
 
-        let mut md = Self::render_nav(env, board_id, viewer)
-            .newline()  // Blank line after nav-bar div for markdown parsing
-            // Error mappings for user-friendly error messages
-            .raw_str("{{errors {\"1\": \"This board is read-only and does not accept new content.\", \"7\": \"Please select a flair for your post.\"}}}\n")
-            .raw_str("<div class=\"back-nav\"><a href=\"render:")
-            .raw(base_path.clone())
-            .raw_str("\" class=\"back-link\">← Back to Board</a></div>\n")
-            .newline()  // Blank line before h1 for markdown parsing
-            .h1("New Thread");
+    /// Unarchive a board (owner only), restoring its previous listed and
+    /// read-only settings
+    pub fn unarchive_board(env: Env, board_id: u64, caller: Address) {
+        caller.require_auth();
+        Self::require_board_owner(&env, board_id, &caller);
 
-        // Show rules reminder if rules are set
-        if let Some(rules) = env
+        let archive: BoardArchive = env
             .storage()
             .persistent()
-            .get::<_, String>(&BoardKey::BoardRules(board_id))
-        {
-            if !rules.is_empty() {
-                md = md.div_start("rules-reminder")
-                    .raw_str("<details open><summary><strong>Before posting, please read the board rules:</strong></summary>")
-                    .div_start("rules-content")
-                    .text_string(&rules)
-                    .div_end()
-                    .raw_str("</details>")
-                    .div_end();
-            }
-        }
-
-        // Check if board is read-only
-        if config.is_readonly {
-            md = md.warning("This board is read-only. New threads cannot be created.");
-            return Self::render_footer_into(env, md).build();
-        }
-
-        if viewer.is_none() {
-            md = md.warning("Please connect your wallet to create a thread.");
-            return Self::render_footer_into(env, md).build();
-        }
+            .get(&BoardKey::BoardArchive(board_id))
+            .expect("Board is not archived");
+        let before = Self::board_filters(&env, board_id);
+        env.storage()
+            .persistent()
+            .remove(&BoardKey::BoardArchive(board_id));
 
-        // Check if user has permission to create threads (requires Member+ role)
-        if (viewer_role as u32) < (Role::Member as u32) {
-            md = md.warning("You don't have permission to create threads on this board.");
-            return Self::render_footer_into(env, md).build();
-        }
+        Self::apply_listed_readonly(&env, board_id, archive.was_listed, archive.was_readonly);
+        Self::reindex_board(&env, board_id, &before);
+    }
 
-        // Get flairs for the selector
-        let flairs: Vec<FlairDef> = env
-            .storage()
+    /// Get the archive record for a board, if it is archived
+    pub fn get_board_archive(env: Env, board_id: u64) -> Option<BoardArchive> {
+        env.storage()
             .persistent()
-            .get(&BoardKey::BoardFlairDefs(board_id))
-            .unwrap_or(Vec::new(env));
-
-        // Check if any flair is required
-        let mut flair_required = false;
-        for i in 0..flairs.len() {
-            let flair = flairs.get(i).unwrap();
-            if flair.required && flair.enabled && (!flair.mod_only || is_moderator) {
-                flair_required = true;
-                break;
-            }
-        }
+            .get(&BoardKey::BoardArchive(board_id))
+    }
 
-        md = md
-            .raw_str("<input type=\"hidden\" name=\"_redirect\" value=\"")
-            .raw(base_path.clone())
-            .raw_str("\" />\n")
-            .raw_str("<input type=\"hidden\" name=\"board_id\" value=\"")
-            .number(board_id as u32)
-            .raw_str("\" />\n")
-            .input("title", "Thread title")
-            .newline();
+    /// Check if a board is archived
+    pub fn is_archived(env: Env, board_id: u64) -> bool {
+        env.storage()
+            .persistent()
+            .has(&BoardKey::BoardArchive(board_id))
+    }
 
-        // Add flair selector if flairs exist
-        let mut has_visible_flairs = false;
-        for i in 0..flairs.len() {
-            let flair = flairs.get(i).unwrap();
-            if flair.enabled && (!flair.mod_only || is_moderator) {
-                has_visible_flairs = true;
-                break;
+    /// Which of these boards are archived, in the order given
+    pub fn get_archived_boards(env: Env, board_ids: Vec<u64>) -> Vec<u64> {
+        let mut archived = Vec::new(&env);
+        for board_id in board_ids.iter() {
+            if Self::is_archived(env.clone(), board_id) {
+                archived.push_back(board_id);
             }
         }
+        archived
+    }
 
-        if has_visible_flairs {
-            md = md
-                .raw_str("<div class=\"flair-selector\">\n")
-                .raw_str("<label>Flair");
-            if flair_required {
-                md = md.raw_str(" <span class=\"required\">*</span>");
-            }
-            md = md
-                .raw_str(":</label>\n")
-                .raw_str("<select name=\"flair_id\">\n")
-                .raw_str("<option value=\"none\">-- Select flair --</option>\n");
-
-            for i in 0..flairs.len() {
-                let flair = flairs.get(i).unwrap();
-                if flair.enabled && (!flair.mod_only || is_moderator) {
-                    // Use "flair_N" format to prevent viewer from converting to integer
-                    md = md
-                        .raw_str("<option value=\"flair_")
-                        .number(flair.id)
-                        .raw_str("\" style=\"color:")
-                        .text_string(&flair.color)
-                        .raw_str(";background:")
-                        .text_string(&flair.bg_color)
-                        .raw_str("\">")
-                        .text_string(&flair.name)
-                        .raw_str("</option>\n");
-                }
-            }
-
-            md = md.raw_str("</select>\n");
-            if flair_required {
-                md = md.raw_str("<small class=\"flair-required-notice\" style=\"color:#c00;display:block;margin-top:4px;\">* A flair is required for new posts on this board</small>\n");
+    /// Which of these (board_id, thread_id) threads are hidden, deleted or gone, in the
+    /// order given. Lets feeds built elsewhere drop threads removed after they were listed.
+    pub fn get_removed_threads(env: Env, threads: Vec<(u64, u64)>) -> Vec<(u64, u64)> {
+        let mut removed = Vec::new(&env);
+        for (board_id, thread_id) in threads.iter() {
+            let visible = env
+                .storage()
+                .persistent()
+                .get::<_, ThreadMeta>(&BoardKey::BoardThread(board_id, thread_id))
+                .is_some_and(|t| !t.is_hidden && !t.is_deleted);
+            if !visible {
+                removed.push_back((board_id, thread_id));
             }
-            md = md.raw_str("</div>\n");
-        } else {
-            // Always include flair_id field - the content contract expects it
-            md = md.raw_str("<input type=\"hidden\" name=\"flair_id\" value=\"none\" />\n");
         }
-
-        md = md
-            .textarea_markdown("body", 10, "Write your post content here...")
-            .newline()
-            .raw_str("<input type=\"hidden\" name=\"caller\" value=\"")
-            .text_string(&viewer.as_ref().unwrap().to_string())
-            .raw_str("\" />\n")
-            .newline()
-            .form_link_to("Create Thread", "content", "create_thread")
-            .newline()
-            .newline()
-            .raw_str("[Cancel](render:")
-            .raw(base_path)
-            .raw_str(")");
-
-        Self::render_footer_into(env, md).build()
+        removed
     }
 
-    /// Render thread view
-    fn render_thread(env: &Env, board_id: u64, thread_id: u64, viewer: &Option<Address>) -> Bytes {
-        let content: Address = env
+    /// Helper: verify caller is the board owner (only if permissions contract is set)
+    fn require_board_owner(env: &Env, board_id: u64, caller: &Address) {
+        if let Some(permissions) = env
             .storage()
             .instance()
-            .get(&BoardKey::Content)
-            .expect("Content contract not configured");
-
-        // Get profile contract for author display
-        let profile_contract = Self::get_profile_contract(env);
-
-        // Get board metadata for slug-based URLs
-        let board_meta: BoardMeta = env
-            .storage()
-            .persistent()
-            .get(&BoardKey::Board(board_id))
-            .expect("Board not found");
+            .get::<_, Address>(&BoardKey::Permissions)
+        {
+            let owner: Option<Address> = env.invoke_contract(
+                &permissions,
+                &Symbol::new(env, "get_board_owner"),
+                Vec::from_array(env, [board_id.into_val(env)]),
+            );
+            if owner.as_ref() != Some(caller) {
+                panic!("Only the board owner can archive or unarchive it");
+            }
+        }
+    }
 
-        // Get board config for readonly check
-        let config: BoardConfig = env
+    /// Helper: write listed and read-only flags to storage, config and metadata
+    fn apply_listed_readonly(env: &Env, board_id: u64, is_listed: bool, is_readonly: bool) {
+        let mut config: BoardConfig = env
             .storage()
             .persistent()
             .get(&BoardKey::BoardConfig(board_id))
             .expect("Board not found");
+        config.is_readonly = is_readonly;
+        env.storage()
+            .persistent()
+            .set(&BoardKey::BoardConfig(board_id), &config);
 
-        // Get thread metadata
-        let thread = env
-            .storage()
+        env.storage()
             .persistent()
-            .get::<_, ThreadMeta>(&BoardKey::BoardThread(board_id, thread_id));
+            .set(&BoardKey::BoardListed(board_id), &is_listed);
 
-        // Get viewer role for hidden thread check and moderator controls
-        let perms_addr_opt = env
+        if let Some(mut meta) = env
             .storage()
-            .instance()
-            .get::<_, Address>(&BoardKey::Permissions);
-        let viewer_role = if let Some(ref perms_addr) = perms_addr_opt {
-            if let Some(user) = viewer {
-                let args: Vec<Val> =
-                    Vec::from_array(env, [board_id.into_val(env), user.into_val(env)]);
-                env.invoke_contract(perms_addr, &Symbol::new(env, "get_role"), args)
-            } else {
-                Role::Guest
-            }
-        } else {
-            Role::Guest
-        };
-        let viewer_can_moderate = (viewer_role as u32) >= (Role::Moderator as u32);
+            .persistent()
+            .get::<_, BoardMeta>(&BoardKey::Board(board_id))
+        {
+            meta.is_listed = is_listed;
+            meta.is_readonly = is_readonly;
+            env.storage()
+                .persistent()
+                .set(&BoardKey::Board(board_id), &meta);
+        }
+    }
 
-        // Check permissions for private boards - must be Member+ to view threads
-        if config.is_private {
-            if let Some(ref perms_addr) = perms_addr_opt {
-                if (viewer_role as u32) < (Role::Member as u32) {
-                    return Self::render_private_board_message(
-                        env, board_id, &config, viewer, perms_addr,
-                    );
-                }
+    /// Check if board is private (members only)
+    pub fn is_private(env: Env, board_id: u64) -> bool {
+        env.storage()
+            .persistent()
+            .get::<_, BoardConfig>(&BoardKey::BoardConfig(board_id))
+            .map(|c| c.is_private)
+            .unwrap_or(false)
+    }
+
+    /// Set board private status (owner/admin only)
+    pub fn set_private(env: Env, board_id: u64, is_private: bool, caller: Address) {
+        caller.require_auth();
+
+        // Check admin permissions (only if permissions contract is set)
+        if env.storage().instance().has(&BoardKey::Permissions) {
+            let permissions: Address = env
+                .storage()
+                .instance()
+                .get(&BoardKey::Permissions)
+                .unwrap();
+            let args: Vec<Val> =
+                Vec::from_array(&env, [board_id.into_val(&env), caller.into_val(&env)]);
+            let fn_name = Symbol::new(&env, "can_admin");
+            let can_admin: bool = env.invoke_contract(&permissions, &fn_name, args);
+            if !can_admin {
+                panic!("Only owner or admin can change private status");
             }
         }
 
-        // Check if thread is hidden - only moderators can view hidden threads
-        let is_hidden = thread.as_ref().map(|t| t.is_hidden).unwrap_or(false);
-        if is_hidden && !viewer_can_moderate {
-            return Self::render_hidden_thread_message(env, board_id, viewer);
+        let mut config: BoardConfig = env
+            .storage()
+            .persistent()
+            .get(&BoardKey::BoardConfig(board_id))
+            .expect("Board not found");
+        let before = Self::board_filters(&env, board_id);
+        config.is_private = is_private;
+        env.storage()
+            .persistent()
+            .set(&BoardKey::BoardConfig(board_id), &config);
+
+        // Update BoardMeta too
+        if let Some(mut meta) = env
+            .storage()
+            .persistent()
+            .get::<_, BoardMeta>(&BoardKey::Board(board_id))
+        {
+            meta.is_private = is_private;
+            env.storage()
+                .persistent()
+                .set(&BoardKey::Board(board_id), &meta);
         }
+        Self::reindex_board(&env, board_id, &before);
+    }
 
-        // Determine if posting is allowed (requires Member+ role, not readonly, not locked)
-        let is_readonly = config.is_readonly;
-        let is_locked = thread.as_ref().map(|t| t.is_locked).unwrap_or(false);
-        let viewer_can_post = (viewer_role as u32) >= (Role::Member as u32);
-        let can_post = !is_readonly && !is_locked && viewer_can_post;
+    /// Set board rules (markdown text, Admin+ only)
+    /// Rules are displayed on the board page and when creating new posts
+    pub fn set_rules(env: Env, board_id: u64, rules: String, caller: Address) {
+        caller.require_auth();
 
-        // Build base path for all links in this thread
-        let base_path = Self::build_board_base_path(env, board_id, &board_meta.slug);
+        // Check admin permissions (only if permissions contract is set)
+        if env.storage().instance().has(&BoardKey::Permissions) {
+            let permissions: Address = env
+                .storage()
+                .instance()
+                .get(&BoardKey::Permissions)
+                .unwrap();
+            let args: Vec<Val> =
+                Vec::from_array(&env, [board_id.into_val(&env), caller.into_val(&env)]);
+            let fn_name = Symbol::new(&env, "can_admin");
+            let can_admin: bool = env.invoke_contract(&permissions, &fn_name, args);
+            if !can_admin {
+                panic!("Only owner or admin can set board rules");
+            }
+        }
 
-        let mut md = Self::render_nav(env, board_id, viewer)
-            .newline()
-            .raw_str("<div class=\"back-nav\"><a href=\"render:")
-            .raw(base_path.clone())
-            .raw_str("\" class=\"back-link\">← Back to Board</a></div>\n");
+        env.storage()
+            .persistent()
+            .set(&BoardKey::BoardRules(board_id), &rules);
+    }
 
-        // Get flairs for display
-        let flairs: Vec<FlairDef> = env
-            .storage()
+    /// Get board rules (returns None if no rules are set)
+    pub fn get_rules(env: Env, board_id: u64) -> Option<String> {
+        env.storage()
             .persistent()
-            .get(&BoardKey::BoardFlairDefs(board_id))
-            .unwrap_or(Vec::new(env));
+            .get(&BoardKey::BoardRules(board_id))
+    }
 
-        // Show thread title if available
-        if let Some(ref t) = thread {
-            md = md.raw_str("<h1>");
+    /// Clear board rules (Admin+ only)
+    pub fn clear_rules(env: Env, board_id: u64, caller: Address) {
+        caller.require_auth();
 
-            // Display flair before title if thread has one
-            if let Some(flair_id) = t.flair_id {
-                for i in 0..flairs.len() {
-                    let flair = flairs.get(i).unwrap();
-                    if flair.id == flair_id && flair.enabled {
-                        md = md
-                            .raw_str("<span class=\"flair\" style=\"color:")
-                            .text_string(&flair.color)
-                            .raw_str(";background:")
-                            .text_string(&flair.bg_color)
-                            .raw_str("\">")
-                            .text_string(&flair.name)
-                            .raw_str("</span> ");
-                        break;
-                    }
-                }
+        // Check admin permissions (only if permissions contract is set)
+        if env.storage().instance().has(&BoardKey::Permissions) {
+            let permissions: Address = env
+                .storage()
+                .instance()
+                .get(&BoardKey::Permissions)
+                .unwrap();
+            let args: Vec<Val> =
+                Vec::from_array(&env, [board_id.into_val(&env), caller.into_val(&env)]);
+            let fn_name = Symbol::new(&env, "can_admin");
+            let can_admin: bool = env.invoke_contract(&permissions, &fn_name, args);
+            if !can_admin {
+                panic!("Only owner or admin can clear board rules");
             }
+        }
 
-            md = md.text_string(&t.title).raw_str("</h1>\n");
+        env.storage()
+            .persistent()
+            .remove(&BoardKey::BoardRules(board_id));
+    }
 
-            // Show author (with return path so "Go Back" returns here)
-            let return_path =
-                Self::build_thread_return_path(env, board_id, &board_meta.slug, thread_id);
-            md = md.raw_str("<div class=\"thread-meta\">by ");
-            md = Self::render_author(env, md, &t.creator, &profile_contract, Some(return_path));
-            md = md
-                .raw_str(" · ")
-                .raw(Self::format_timestamp(env, t.created_at))
-                .raw_str("</div>\n");
-        } else {
-            md = md.raw_str("<h1>Thread</h1>\n");
+    /// Set CSS overrides appended after the theme stylesheet (Admin+ only).
+    /// An empty string resets the board to the theme defaults.
+    pub fn set_custom_css(env: Env, board_id: u64, css: String, caller: Address) {
+        caller.require_auth();
+
+        // Check admin permissions (only if permissions contract is set)
+        if let Some(permissions) = env
+            .storage()
+            .instance()
+            .get::<_, Address>(&BoardKey::Permissions)
+        {
+            let args: Vec<Val> =
+                Vec::from_array(&env, [board_id.into_val(&env), caller.into_val(&env)]);
+            let can_admin: bool =
+                env.invoke_contract(&permissions, &Symbol::new(&env, "can_admin"), args);
+            if !can_admin {
+                panic!("Only owner or admin can change board CSS");
+            }
         }
 
-        // Show status badges
-        let is_pinned = thread.as_ref().map(|t| t.is_pinned).unwrap_or(false);
-        if is_hidden && viewer_can_moderate {
-            md = md.raw_str("<span class=\"badge badge-hidden\">hidden</span> ");
+        let key = SettingKey::BoardCustomCss(board_id);
+        if css.is_empty() {
+            env.storage().persistent().remove(&key);
+            return;
         }
-        if is_pinned {
-            md = md.raw_str("<span class=\"badge badge-pinned\">pinned</span> ");
+        if css.len() > MAX_CUSTOM_CSS_BYTES {
+            panic!("Custom CSS must be 8 KB or less");
         }
-        if is_locked {
-            md = md.raw_str("<span class=\"badge badge-locked\">locked</span> ");
+        if !Self::is_safe_css(&css) {
+            panic!("Custom CSS cannot contain @import, url(, < or backslashes");
         }
-        if is_readonly {
-            md = md.raw_str("<span class=\"badge badge-readonly\">read-only board</span> ");
+        env.storage().persistent().set(&key, &css);
+    }
+
+    /// Get a board's CSS overrides (None if the board uses the theme as-is)
+    pub fn get_custom_css(env: Env, board_id: u64) -> Option<String> {
+        env.storage()
+            .persistent()
+            .get(&SettingKey::BoardCustomCss(board_id))
+    }
+
+    /// Pick a theme from the registry's catalog by name (Admin+ only).
+    /// An empty name returns the board to the registry's default theme.
+    pub fn set_theme_by_name(env: Env, board_id: u64, name: String, caller: Address) {
+        caller.require_auth();
+
+        // Check admin permissions (only if permissions contract is set)
+        if let Some(permissions) = env
+            .storage()
+            .instance()
+            .get::<_, Address>(&BoardKey::Permissions)
+        {
+            let args: Vec<Val> =
+                Vec::from_array(&env, [board_id.into_val(&env), caller.into_val(&env)]);
+            let can_admin: bool =
+                env.invoke_contract(&permissions, &Symbol::new(&env, "can_admin"), args);
+            if !can_admin {
+                panic!("Only owner or admin can change the board theme");
+            }
         }
-        if is_hidden || is_pinned || is_locked || is_readonly {
-            md = md.newline();
+
+        let key = SettingKey::BoardThemeName(board_id);
+        if name.is_empty() {
+            env.storage().persistent().remove(&key);
+            return;
         }
+        if Self::lookup_theme(&env, &name).is_none() {
+            panic!("Theme not registered");
+        }
+        env.storage().persistent().set(&key, &name);
+    }
 
-        // Check if this is a crosspost and show header
-        let crosspost_args: Vec<Val> =
-            Vec::from_array(env, [board_id.into_val(env), thread_id.into_val(env)]);
-        let crosspost_ref: Option<CrosspostRef> = env
-            .try_invoke_contract::<Option<CrosspostRef>, soroban_sdk::Error>(
-                &content,
-                &Symbol::new(env, "get_crosspost_ref"),
-                crosspost_args,
-            )
-            .ok()
-            .and_then(|r| r.ok())
-            .flatten();
+    /// Get the name of the board's theme (None when using the registry default)
+    pub fn get_theme_name(env: Env, board_id: u64) -> Option<String> {
+        env.storage()
+            .persistent()
+            .get(&SettingKey::BoardThemeName(board_id))
+    }
 
-        if let Some(ref xpost) = crosspost_ref {
-            // Look up original board's slug for the crosspost link
-            let original_board_meta: Option<BoardMeta> = env
-                .storage()
-                .persistent()
-                .get(&BoardKey::Board(xpost.original_board_id));
-            let original_base_path = if let Some(ref orig_meta) = original_board_meta {
-                Self::build_board_base_path(env, xpost.original_board_id, &orig_meta.slug)
-            } else {
-                // Fallback to numeric ID if board not found (shouldn't happen)
-                let mut path = Bytes::from_slice(env, b"/b/");
-                path.append(&soroban_render_sdk::bytes::u32_to_bytes(
-                    env,
-                    xpost.original_board_id as u32,
-                ));
-                path
-            };
+    /// Resolve a theme name through the registry's catalog
+    fn lookup_theme(env: &Env, name: &String) -> Option<Address> {
+        let registry: Address = env.storage().instance().get(&BoardKey::Registry)?;
+        env.try_invoke_contract::<Option<Address>, soroban_sdk::Error>(
+            &registry,
+            &Symbol::new(env, "get_theme"),
+            Vec::from_array(env, [name.into_val(env)]),
+        )
+        .ok()
+        .and_then(|r| r.ok())
+        .flatten()
+    }
 
-            md = md
-                .div_start("crosspost-header")
-                .raw_str("<span class=\"crosspost-badge\">⤴ Crosspost</span> ")
-                .raw_str("Originally posted in [")
-                .raw(original_base_path.clone())
-                .raw_str("](render:")
-                .raw(original_base_path)
-                .raw_str("/t/")
-                .number(xpost.original_thread_id as u32)
-                .raw_str(") by ");
-            // Show original author (return path goes back to current thread)
-            let return_path =
-                Self::build_thread_return_path(env, board_id, &board_meta.slug, thread_id);
-            md = Self::render_author(
-                env,
-                md,
-                &xpost.original_author,
-                &profile_contract,
-                Some(return_path.clone()),
-            );
-            md = md.div_end().newline();
-        }
-
-        // Thread body in a container
-        md = md.div_start("thread-body");
-
-        // Get thread body from content contract
-        let args: Vec<Val> =
-            Vec::from_array(env, [board_id.into_val(env), thread_id.into_val(env)]);
-        let body: Bytes =
-            env.invoke_contract(&content, &Symbol::new(env, "get_thread_body"), args.clone());
+    /// The board's picked theme contract, or None for the default stylesheet.
+    /// A theme unregistered after being picked falls back to the default.
+    fn chosen_theme(env: &Env, board_id: u64) -> Option<Address> {
+        let name = Self::get_theme_name(env.clone(), board_id)?;
+        Self::lookup_theme(env, &name)
+    }
 
-        if !body.is_empty() {
-            md = md.raw(body);
+    /// Set the caller's color scheme; Auto clears the preference
+    pub fn set_display_pref(env: Env, scheme: ColorScheme, user: Address) {
+        user.require_auth();
+        let key = UserKey::UserPref(user);
+        if scheme == ColorScheme::Auto {
+            env.storage().persistent().remove(&key);
         } else {
-            md = md.italic("No content");
+            env.storage()
+                .persistent()
+                .set(&key, &DisplayPref { scheme });
         }
+    }
 
-        md = md.div_end().newline();
-
-        // Vote buttons (if voting contract is configured and user is logged in)
-        let voting_contract: Option<Address> = env.storage().instance().get(&BoardKey::Voting);
-        if let Some(ref voting) = voting_contract {
-            // Get thread vote tally
-            let tally_args: Vec<Val> =
-                Vec::from_array(env, [board_id.into_val(env), thread_id.into_val(env)]);
-            let tally: VoteTally =
-                env.invoke_contract(voting, &Symbol::new(env, "get_thread_tally"), tally_args);
-
-            // Get viewer's current vote (if logged in)
-            let viewer_vote = if let Some(ref user) = viewer {
-                let vote_args: Vec<Val> = Vec::from_array(
-                    env,
-                    [
-                        board_id.into_val(env),
-                        thread_id.into_val(env),
-                        user.into_val(env),
-                    ],
-                );
-                env.invoke_contract::<VoteDirection>(
-                    voting,
-                    &Symbol::new(env, "get_user_thread_vote"),
-                    vote_args,
-                )
-            } else {
-                VoteDirection::None
-            };
+    /// Get a viewer's display preferences (None if they never chose)
+    pub fn get_display_pref(env: Env, user: Address) -> Option<DisplayPref> {
+        env.storage().persistent().get(&UserKey::UserPref(user))
+    }
 
-            md = md.div_start("vote-buttons");
+    /// Block an author: their threads and replies collapse for the caller only
+    pub fn block_user(env: Env, target: Address, caller: Address) {
+        caller.require_auth();
+        if target == caller {
+            panic!("You can't block yourself");
+        }
+        let mut blocked = Self::list_blocked(env.clone(), caller.clone());
+        if blocked.contains(&target) {
+            return;
+        }
+        if blocked.len() >= MAX_BLOCKED_USERS {
+            panic!("Block list is full");
+        }
+        blocked.push_back(target);
+        env.storage()
+            .persistent()
+            .set(&UserKey::BlockedUsers(caller), &blocked);
+    }
 
-            // Upvote button
-            if viewer.is_some() {
-                let up_class = if viewer_vote == VoteDirection::Up {
-                    "vote-up vote-active"
-                } else {
-                    "vote-up"
-                };
-                md = md
-                    .raw_str("<a href=\"tx:@voting:vote_thread {&quot;board_id&quot;:")
-                    .number(board_id as u32)
-                    .raw_str(",&quot;thread_id&quot;:")
-                    .number(thread_id as u32)
-                    .raw_str(",&quot;direction&quot;:");
-                // Toggle: if already up, set to none; otherwise set to up
-                if viewer_vote == VoteDirection::Up {
-                    md = md.raw_str("0"); // None
-                } else {
-                    md = md.raw_str("1"); // Up
-                }
-                md = md
-                    .raw_str("}\" class=\"")
-                    .raw_str(up_class)
-                    .raw_str("\">▲</a>");
+    /// Remove an author from the caller's block list
+    pub fn unblock_user(env: Env, target: Address, caller: Address) {
+        caller.require_auth();
+        let mut blocked = Self::list_blocked(env.clone(), caller.clone());
+        if let Some(index) = blocked.first_index_of(&target) {
+            blocked.remove(index);
+            if blocked.is_empty() {
+                env.storage()
+                    .persistent()
+                    .remove(&UserKey::BlockedUsers(caller));
             } else {
-                md = md.raw_str("<span class=\"vote-up vote-disabled\">▲</span>");
+                env.storage()
+                    .persistent()
+                    .set(&UserKey::BlockedUsers(caller), &blocked);
             }
+        }
+    }
 
-            // Score display
-            md = md
-                .raw_str("<span class=\"vote-score\">")
-                .number(tally.score as u32)
-                .raw_str("</span>");
-
-            // Downvote button
-            if viewer.is_some() {
-                let down_class = if viewer_vote == VoteDirection::Down {
-                    "vote-down vote-active"
-                } else {
-                    "vote-down"
-                };
-                md = md
-                    .raw_str("<a href=\"tx:@voting:vote_thread {&quot;board_id&quot;:")
-                    .number(board_id as u32)
-                    .raw_str(",&quot;thread_id&quot;:")
-                    .number(thread_id as u32)
-                    .raw_str(",&quot;direction&quot;:");
-                // Toggle: if already down, set to none; otherwise set to down
-                if viewer_vote == VoteDirection::Down {
-                    md = md.raw_str("0"); // None
-                } else {
-                    md = md.raw_str("2"); // Down
-                }
-                md = md
-                    .raw_str("}\" class=\"")
-                    .raw_str(down_class)
-                    .raw_str("\">▼</a>");
-            } else {
-                md = md.raw_str("<span class=\"vote-down vote-disabled\">▼</span>");
-            }
+    /// Authors a user has blocked
+    pub fn list_blocked(env: Env, user: Address) -> Vec<Address> {
+        env.storage()
+            .persistent()
+            .get(&UserKey::BlockedUsers(user))
+            .unwrap_or(Vec::new(&env))
+    }
 
-            md = md.div_end().newline();
+    /// The viewer's block list, read once per render (empty for guests)
+    fn blocked_for(env: &Env, viewer: &Option<Address>) -> Vec<Address> {
+        match viewer {
+            Some(user) => Self::list_blocked(env.clone(), user.clone()),
+            None => Vec::new(env),
         }
+    }
 
-        // Thread actions (only show if viewer is logged in and posting is allowed)
-        if viewer.is_some() && can_post {
-            md = md
-                .div_start("thread-actions")
-                .raw_str("[Reply to Thread](render:")
-                .raw(base_path.clone())
-                .raw_str("/t/")
-                .number(thread_id as u32)
-                .raw_str("/reply)");
-
-            // Show edit button if user can edit
-            if let Some(ref t) = thread {
-                let (is_author, is_moderator) = Self::can_edit(env, board_id, &t.creator, viewer);
-                let can_edit_time =
-                    is_moderator || Self::is_within_edit_window(env, board_id, t.created_at);
+    /// Claim a display name on a board. Authors render with it while no profile
+    /// contract is configured. Names are unique per board (ignoring case) and can
+    /// be changed once every 7 days.
+    pub fn claim_display_name(env: Env, board_id: u64, name: String, caller: Address) {
+        caller.require_auth();
+        if !env.storage().persistent().has(&BoardKey::Board(board_id)) {
+            panic!("Board not found");
+        }
+        let Some(key) = Self::display_name_key(&env, &name) else {
+            panic!("Display name must be 3-20 letters, numbers or underscores");
+        };
 
-                if (is_author || is_moderator) && can_edit_time {
-                    md = md
-                        .text(" ")
-                        .raw_str("[Edit](render:")
-                        .raw(base_path.clone())
-                        .raw_str("/t/")
-                        .number(thread_id as u32)
-                        .raw_str("/edit)");
-                }
+        let owner_key = UserKey::DisplayNameOwner(board_id, key);
+        if let Some(owner) = env.storage().persistent().get::<_, Address>(&owner_key) {
+            if owner != caller {
+                panic!("Display name already taken");
             }
+        }
 
-            // Crosspost button (only if not already a crosspost)
-            if crosspost_ref.is_none() {
-                md = md
-                    .text(" ")
-                    .raw_str("[Crosspost](render:/crosspost?from_board=")
-                    .text_string(&board_meta.slug)
-                    .raw_str("&from_thread=")
-                    .number(thread_id as u32)
-                    .raw_str(")");
-
-                // Show crosspost count if any
-                let xpost_count_args: Vec<Val> =
-                    Vec::from_array(env, [board_id.into_val(env), thread_id.into_val(env)]);
-                let xpost_count: u32 = env
-                    .try_invoke_contract::<u32, soroban_sdk::Error>(
-                        &content,
-                        &Symbol::new(env, "get_crosspost_count"),
-                        xpost_count_args,
-                    )
-                    .ok()
-                    .and_then(|r| r.ok())
-                    .unwrap_or(0);
-
-                if xpost_count > 0 {
-                    md = md
-                        .raw_str(" <span class=\"crosspost-count\">")
-                        .number(xpost_count)
-                        .raw_str(" crosspost");
-                    if xpost_count > 1 {
-                        md = md.raw_str("s");
-                    }
-                    md = md.raw_str("</span>");
-                }
+        let claim_key = UserKey::DisplayName(board_id, caller.clone());
+        let now = env.ledger().timestamp();
+        if let Some(previous) = env
+            .storage()
+            .persistent()
+            .get::<_, DisplayNameClaim>(&claim_key)
+        {
+            if now < previous.claimed_at + DISPLAY_NAME_COOLDOWN {
+                panic!("Display name can only be changed once every 7 days");
+            }
+            if let Some(old_key) = Self::display_name_key(&env, &previous.name) {
+                env.storage()
+                    .persistent()
+                    .remove(&UserKey::DisplayNameOwner(board_id, old_key));
             }
-
-            md = md.div_end().newline();
         }
 
-        // Moderator controls (only show for moderator+ users)
-        // Uses raw HTML links since we're inside a div (markdown not processed in HTML blocks)
-        if viewer_can_moderate {
-            if let Some(ref user) = viewer {
-                md = md
-                    .div_start("mod-actions")
-                    .raw_str("<strong>Mod Actions:</strong> ")
-                    // Hidden fields for all actions
-                    .raw_str("<input type=\"hidden\" name=\"board_id\" value=\"")
-                    .number(board_id as u32)
-                    .raw_str("\" />")
-                    .raw_str("<input type=\"hidden\" name=\"thread_id\" value=\"")
-                    .number(thread_id as u32)
-                    .raw_str("\" />")
-                    .raw_str("<input type=\"hidden\" name=\"caller\" value=\"")
-                    .text_string(&user.to_string())
-                    .raw_str("\" />");
+        env.storage().persistent().set(&owner_key, &caller);
+        env.storage().persistent().set(
+            &claim_key,
+            &DisplayNameClaim {
+                name,
+                claimed_at: now,
+            },
+        );
+    }
 
-                // Lock/Unlock
-                if is_locked {
-                    md = md.raw_str("<a href=\"form:@admin:unlock_thread\">[Unlock]</a>");
-                } else {
-                    md = md.raw_str("<a href=\"form:@admin:lock_thread\">[Lock]</a>");
-                }
+    /// Get a user's display name on a board (None if they haven't claimed one)
+    pub fn get_display_name(env: Env, board_id: u64, user: Address) -> Option<String> {
+        env.storage()
+            .persistent()
+            .get::<_, DisplayNameClaim>(&UserKey::DisplayName(board_id, user))
+            .map(|claim| claim.name)
+    }
 
-                // Hide/Unhide
-                if is_hidden {
-                    md = md.raw_str(" <a href=\"form:@admin:unhide_thread\">[Unhide]</a>");
-                } else {
-                    md = md.raw_str(" <a href=\"form:@admin:hide_thread\">[Hide]</a>");
-                }
+    /// Lowercased form of a valid display name, used for uniqueness (None if invalid)
+    fn display_name_key(env: &Env, name: &String) -> Option<String> {
+        let len = name.len() as usize;
+        if !(MIN_DISPLAY_NAME_LEN..=MAX_DISPLAY_NAME_LEN).contains(&len) {
+            return None;
+        }
+        let mut buf = [0u8; MAX_DISPLAY_NAME_LEN];
+        name.copy_into_slice(&mut buf[..len]);
+        if !buf[..len]
+            .iter()
+            .all(|b| b.is_ascii_alphanumeric() || *b == b'_')
+        {
+            return None;
+        }
+        buf[..len].make_ascii_lowercase();
+        Some(String::from_bytes(env, &buf[..len]))
+    }
 
-                // Pin/Unpin
-                if is_pinned {
-                    md = md.raw_str(" <a href=\"form:@admin:unpin_thread\">[Unpin]</a>");
+    /// Get the default stylesheet, plus the palette for the viewer's chosen color scheme
+    pub fn styles_for(env: Env, viewer: Option<Address>) -> Bytes {
+        let mut css = Self::styles(env.clone());
+        let scheme = viewer
+            .map(|user| Self::color_scheme(&env, &user))
+            .unwrap_or(ColorScheme::Auto);
+        if let Some(palette) = Self::scheme_css(&env, None, scheme) {
+            css.append(&Bytes::from_slice(
+                &env,
+                if scheme == ColorScheme::Dark {
+                    b"\n/* Dark mode */\n"
                 } else {
-                    md = md.raw_str(" <a href=\"form:@admin:pin_thread\">[Pin]</a>");
-                }
-
-                md = md.div_end().newline();
-            }
+                    b"\n/* Light mode */\n"
+                },
+            ));
+            css.append(&palette);
         }
+        css
+    }
 
-        md = md.raw_str("<h2>Replies</h2>\n");
+    fn color_scheme(env: &Env, user: &Address) -> ColorScheme {
+        Self::get_display_pref(env.clone(), user.clone())
+            .map(|pref| pref.scheme)
+            .unwrap_or(ColorScheme::Auto)
+    }
 
-        // Fetch reply count
-        let reply_count: u64 =
-            env.invoke_contract(&content, &Symbol::new(env, "get_reply_count"), args);
+    /// Palette forcing a color scheme, from a theme (the default theme if None).
+    /// None for Auto, or if the theme has no such palette.
+    fn scheme_css(env: &Env, theme: Option<Address>, scheme: ColorScheme) -> Option<Bytes> {
+        let func = match scheme {
+            ColorScheme::Auto => return None,
+            ColorScheme::Light => "light_styles",
+            ColorScheme::Dark => "dark_styles",
+        };
+        let theme = theme.or_else(|| env.storage().instance().get(&BoardKey::Theme))?;
+        env.try_invoke_contract::<Bytes, soroban_sdk::Error>(
+            &theme,
+            &Symbol::new(env, func),
+            Vec::new(env),
+        )
+        .ok()
+        .and_then(|r| r.ok())
+    }
 
-        if reply_count == 0 {
-            md = md.paragraph("No replies yet. Be the first to respond!");
-        } else {
-            // Use waterfall loading with slug-based path
-            md = md
-                .raw_str("{{render path=\"")
-                .raw(base_path.clone())
-                .raw_str("/t/")
-                .number(thread_id as u32)
-                .raw_str("/replies/0\"}}");
-        }
+    /// Stylesheet of a theme contract (None if the call fails)
+    fn theme_styles(env: &Env, theme: &Address) -> Option<Bytes> {
+        env.try_invoke_contract::<Bytes, soroban_sdk::Error>(
+            theme,
+            &Symbol::new(env, "styles"),
+            Vec::new(env),
+        )
+        .ok()
+        .and_then(|r| r.ok())
+    }
 
-        Self::render_footer_into(env, md).build()
+    /// Check stored custom CSS against the shared reject list
+    fn is_safe_css(css: &String) -> bool {
+        let len = css.len() as usize;
+        let mut buf = [0u8; MAX_CUSTOM_CSS_BYTES as usize];
+        css.copy_into_slice(&mut buf[..len]);
+        is_safe_css(&buf[..len])
     }
 
-    /// Render a batch of top-level replies
-    fn render_replies_batch(
-        env: &Env,
-        board_id: u64,
-        thread_id: u64,
-        start: u32,
-        viewer: &Option<Address>,
-    ) -> Bytes {
-        let content: Address = env
+    /// Check if content is within the edit window
+    /// Returns true if content can be edited (within window or no limit)
+    fn is_within_edit_window(env: &Env, board_id: u64, created_at: u64) -> bool {
+        let edit_window_seconds: u64 = env
             .storage()
-            .instance()
-            .get(&BoardKey::Content)
-            .expect("Content contract not configured");
+            .persistent()
+            .get(&BoardKey::BoardEditWindow(board_id))
+            .unwrap_or(86400u64);
 
-        // Get profile contract for author display
-        let profile_contract = Self::get_profile_contract(env);
+        // 0 = no time limit
+        if edit_window_seconds == 0 {
+            return true;
+        }
 
-        // Get voting contract for vote buttons
-        let voting_contract: Option<Address> = env.storage().instance().get(&BoardKey::Voting);
+        let current_time = env.ledger().timestamp();
+        current_time <= created_at + edit_window_seconds
+    }
 
-        // Get board metadata for slug-based URLs
-        let board_meta: BoardMeta = env
-            .storage()
-            .persistent()
-            .get(&BoardKey::Board(board_id))
-            .expect("Board not found");
+    /// Create a new thread (returns thread ID)
+    /// Note: Auth is handled by the calling contract (theme). When called directly
+    /// (e.g., via CLI), callers should ensure proper authorization.
+    pub fn create_thread(
+        env: Env,
+        board_id: u64,
+        title: String,
+        flair_id: Option<String>,
+        creator: Address,
+    ) -> u64 {
+        // Note: require_auth() removed because this is called by the theme contract,
+        // which already handles authentication. Cross-contract auth doesn't propagate
+        // automatically in Soroban.
 
-        // Build base path for all links
-        let base_path = Self::build_board_base_path(env, board_id, &board_meta.slug);
+        // Check permissions (only if permissions contract is set)
+        if env.storage().instance().has(&BoardKey::Permissions) {
+            Self::check_can_create_thread(&env, board_id, &creator);
+        }
+        if Self::karma_shortfall(env.clone(), board_id, creator.clone(), false).is_some() {
+            panic!("Not enough karma to post on this board");
+        }
+        Self::check_blocked_title(&env, board_id, &title);
 
+        // Check if board is readonly
         let config: BoardConfig = env
             .storage()
             .persistent()
             .get(&BoardKey::BoardConfig(board_id))
             .expect("Board not found");
-        let chunk_size = if config.reply_chunk_size == 0 {
-            6
+        if config.is_readonly {
+            panic!("Board is read-only");
+        }
+
+        // Parse flair_id from string: "none" or empty → None, "flair_N" → Some(N)
+        let parsed_flair_id: Option<u32> = if let Some(ref flair_str) = flair_id {
+            let none_str = String::from_str(&env, "none");
+            if flair_str.is_empty() || flair_str == &none_str {
+                None
+            } else {
+                // Expect format "flair_N" - parse N as u32
+                let mut buf = [0u8; 32];
+                let len = core::cmp::min(flair_str.len() as usize, 32);
+                flair_str.copy_into_slice(&mut buf[..len]);
+
+                // Check for "flair_" prefix (6 chars)
+                if len > 6
+                    && buf[0] == b'f'
+                    && buf[1] == b'l'
+                    && buf[2] == b'a'
+                    && buf[3] == b'i'
+                    && buf[4] == b'r'
+                    && buf[5] == b'_'
+                {
+                    // Parse the number after "flair_"
+                    let mut result: u32 = 0;
+                    for &b in buf.iter().take(len).skip(6) {
+                        if b.is_ascii_digit() {
+                            result = result * 10 + (b - b'0') as u32;
+                        } else {
+                            panic!("Invalid flair_id format");
+                        }
+                    }
+                    Some(result)
+                } else {
+                    panic!("Invalid flair_id: expected 'none' or 'flair_N' format");
+                }
+            }
         } else {
-            config.reply_chunk_size
+            None
         };
 
-        // Get viewer role for permission check
-        let perms_addr_opt = env
+        // Get flairs for this board
+        let flairs: Vec<FlairDef> = env
             .storage()
-            .instance()
-            .get::<_, Address>(&BoardKey::Permissions);
-        let viewer_role = if let Some(ref perms_addr) = perms_addr_opt {
-            if let Some(user) = viewer {
-                let args: Vec<Val> =
-                    Vec::from_array(env, [board_id.into_val(env), user.into_val(env)]);
-                env.invoke_contract(perms_addr, &Symbol::new(env, "get_role"), args)
-            } else {
-                Role::Guest
-            }
+            .persistent()
+            .get(&BoardKey::BoardFlairDefs(board_id))
+            .unwrap_or(Vec::new(&env));
+
+        // Check if user is moderator (needed for mod_only flair access)
+        let is_moderator = if env.storage().instance().has(&BoardKey::Permissions) {
+            let permissions: Address = env
+                .storage()
+                .instance()
+                .get(&BoardKey::Permissions)
+                .expect("Not initialized");
+            let args: Vec<Val> = Vec::from_array(
+                &env,
+                [board_id.into_val(&env), creator.clone().into_val(&env)],
+            );
+            env.try_invoke_contract::<bool, soroban_sdk::Error>(
+                &permissions,
+                &Symbol::new(&env, "can_moderate"),
+                args,
+            )
+            .unwrap_or(Ok(false))
+            .unwrap_or(false)
         } else {
-            Role::Guest
+            false
         };
 
-        // Check permissions for private boards - must be Member+ to access
-        if config.is_private {
-            if let Some(ref perms_addr) = perms_addr_opt {
-                if (viewer_role as u32) < (Role::Member as u32) {
-                    return Self::render_private_board_message(
-                        env, board_id, &config, viewer, perms_addr,
-                    );
-                }
+        // Check if any flair is required (only consider flairs the user can actually select)
+        let mut has_required_flair = false;
+        for i in 0..flairs.len() {
+            let flair = flairs.get(i).unwrap();
+            if flair.required && flair.enabled && (!flair.mod_only || is_moderator) {
+                has_required_flair = true;
+                break;
             }
         }
 
-        // Determine if posting is allowed (requires Member+ role, not readonly, not locked)
-        let thread = env
+        // Validate flair selection
+        let validated_flair_id = if let Some(fid) = parsed_flair_id {
+            // User selected a flair - validate it exists and is usable
+            let mut flair_valid = false;
+            let mut flair_mod_only = false;
+            for i in 0..flairs.len() {
+                let flair = flairs.get(i).unwrap();
+                if flair.id == fid {
+                    if !flair.enabled {
+                        panic!("Selected flair is disabled");
+                    }
+                    flair_valid = true;
+                    flair_mod_only = flair.mod_only;
+                    break;
+                }
+            }
+            if !flair_valid {
+                panic!("Selected flair does not exist");
+            }
+            if flair_mod_only && !is_moderator {
+                panic!("Selected flair is moderator-only");
+            }
+            Some(fid)
+        } else if has_required_flair {
+            // No flair selected but one is required
+            panic!("A flair is required for new posts on this board");
+        } else {
+            None
+        };
+
+        let thread_id: u64 = env
             .storage()
             .persistent()
-            .get::<_, ThreadMeta>(&BoardKey::BoardThread(board_id, thread_id));
-        let is_locked = thread.as_ref().map(|t| t.is_locked).unwrap_or(false);
-        let viewer_can_post = (viewer_role as u32) >= (Role::Member as u32);
-        let can_post = !config.is_readonly && !is_locked && viewer_can_post;
+            .get(&BoardKey::BoardThreadCount(board_id))
+            .unwrap_or(0);
 
-        // Get total reply count
-        let count_args: Vec<Val> =
-            Vec::from_array(env, [board_id.into_val(env), thread_id.into_val(env)]);
-        let total_count: u64 =
-            env.invoke_contract(&content, &Symbol::new(env, "get_reply_count"), count_args);
+        // Quarantined users' threads start hidden until a moderator unhides them
+        let is_hidden = Self::is_quarantined(&env, board_id, &creator);
 
-        // Fetch this batch of replies
-        let list_args: Vec<Val> = Vec::from_array(
-            env,
-            [
-                board_id.into_val(env),
-                thread_id.into_val(env),
-                start.into_val(env),
-                chunk_size.into_val(env),
-            ],
-        );
-        let replies: Vec<ReplyMeta> = env.invoke_contract(
-            &content,
-            &Symbol::new(env, "list_top_level_replies"),
-            list_args,
-        );
+        let thread = ThreadMeta {
+            id: thread_id,
+            board_id,
+            title,
+            creator,
+            created_at: env.ledger().timestamp(),
+            updated_at: env.ledger().timestamp(),
+            reply_count: 0,
+            is_locked: false,
+            is_pinned: false,
+            is_hidden,
+            is_deleted: false,
+            flair_id: validated_flair_id,
+        };
 
-        let mut md = MarkdownBuilder::new(env);
+        Self::store_thread_meta(&env, board_id, thread_id, &thread);
+        env.storage()
+            .persistent()
+            .set(&BoardKey::BoardThreadCount(board_id), &(thread_id + 1));
 
-        for i in 0..replies.len() {
-            if let Some(reply) = replies.get(i) {
-                md = Self::render_reply_item_waterfall(
-                    env,
-                    md,
-                    &content,
-                    &reply,
-                    board_id,
-                    thread_id,
-                    &base_path,
-                    &board_meta.slug,
-                    viewer,
-                    can_post,
-                    &profile_contract,
-                    &voting_contract,
-                );
-            }
-        }
+        // Update BoardMeta thread count
+        if let Some(mut meta) = env
+            .storage()
+            .persistent()
+            .get::<_, BoardMeta>(&BoardKey::Board(board_id))
+        {
+            meta.thread_count = thread_id + 1;
+            env.storage()
+                .persistent()
+                .set(&BoardKey::Board(board_id), &meta);
+        }
 
-        // If more replies exist, add continuation with slug-based path
-        let next_start = start + chunk_size;
-        if (next_start as u64) < total_count {
-            md = md
-                .raw_str("{{render path=\"")
-                .raw(base_path)
-                .raw_str("/t/")
-                .number(thread_id as u32)
-                .raw_str("/replies/")
-                .number(next_start)
-                .raw_str("\"}}");
+        // Index the thread under its author for their post history, and log it
+        // for moderation. Threads before the first logged one are backfilled.
+        Self::index_user_thread(&env, board_id, thread_id, &thread.creator);
+        if !env
+            .storage()
+            .persistent()
+            .has(&BoardKey::ThreadLogFrom(board_id))
+        {
+            env.storage()
+                .persistent()
+                .set(&BoardKey::ThreadLogFrom(board_id), &thread_id);
+        }
+        Self::log_user_thread(&env, board_id, thread_id, &thread.creator);
+        Self::report_activity(&env, board_id);
+        // Public threads also go into the registry's cross-board feed
+        if !is_hidden && !config.is_private && Self::get_board_listed(env.clone(), board_id) {
+            Self::report_thread_activity(&env, &thread);
         }
+        Self::record_daily_stat(&env, board_id, true);
 
-        md.build()
+        thread_id
     }
 
-    /// Render a batch of children for a reply
-    fn render_children_batch(
-        env: &Env,
+    /// Get thread metadata
+    pub fn get_thread(env: Env, board_id: u64, thread_id: u64) -> Option<ThreadMeta> {
+        env.storage()
+            .persistent()
+            .get(&BoardKey::BoardThread(board_id, thread_id))
+    }
+
+    /// Get thread title and author (for crossposting)
+    pub fn get_thread_title_and_author(
+        env: Env,
         board_id: u64,
         thread_id: u64,
-        parent_id: u64,
-        start: u32,
-        viewer: &Option<Address>,
-    ) -> Bytes {
-        let content: Address = env
+    ) -> Option<(String, Address)> {
+        let thread: Option<ThreadMeta> = env
+            .storage()
+            .persistent()
+            .get(&BoardKey::BoardThread(board_id, thread_id));
+        thread.map(|t| (t.title, t.creator))
+    }
+
+    /// A user's recent threads and replies on a board, newest first.
+    /// Hidden and deleted posts are included but marked; views decide whether to show them.
+    pub fn list_recent_activity(
+        env: Env,
+        board_id: u64,
+        user: Address,
+        limit: u32,
+    ) -> Vec<ActivityItem> {
+        let thread_ids: Vec<u64> = env
+            .storage()
+            .persistent()
+            .get(&UserKey::UserThreads(board_id, user.clone()))
+            .unwrap_or(Vec::new(&env));
+        let replies: Vec<ReplyMeta> = match env
             .storage()
             .instance()
-            .get(&BoardKey::Content)
-            .expect("Content contract not configured");
+            .get::<_, Address>(&BoardKey::Content)
+        {
+            Some(content) => env
+                .try_invoke_contract::<Vec<ReplyMeta>, soroban_sdk::Error>(
+                    &content,
+                    &Symbol::new(&env, "list_user_replies"),
+                    Vec::from_array(
+                        &env,
+                        [
+                            board_id.into_val(&env),
+                            user.into_val(&env),
+                            limit.into_val(&env),
+                        ],
+                    ),
+                )
+                .ok()
+                .and_then(|r| r.ok())
+                .unwrap_or(Vec::new(&env)),
+            None => Vec::new(&env),
+        };
+        let load_thread = |thread_id: u64| {
+            env.storage()
+                .persistent()
+                .get::<_, ThreadMeta>(&BoardKey::BoardThread(board_id, thread_id))
+        };
 
-        // Get profile contract for author display
-        let profile_contract = Self::get_profile_contract(env);
+        // Both sources are newest first: merge them by timestamp
+        let mut items = Vec::new(&env);
+        let mut threads_left = thread_ids.len();
+        let mut next_thread = thread_ids.last().and_then(load_thread);
+        let mut reply_index = 0;
+        while items.len() < limit {
+            let reply = replies.get(reply_index);
+            let take_thread = match (&next_thread, &reply) {
+                (Some(thread), Some(reply)) => thread.created_at >= reply.created_at,
+                (Some(_), None) => true,
+                (None, Some(_)) => false,
+                (None, None) => break,
+            };
+            if take_thread {
+                let thread = next_thread.unwrap();
+                items.push_back(ActivityItem {
+                    thread_id: thread.id,
+                    reply_id: None,
+                    created_at: thread.created_at,
+                    is_hidden: thread.is_hidden || thread.is_deleted,
+                });
+                threads_left -= 1;
+                next_thread = match threads_left {
+                    0 => None,
+                    n => thread_ids.get(n - 1).and_then(load_thread),
+                };
+            } else {
+                let reply = reply.unwrap();
+                let thread_gone = load_thread(reply.thread_id)
+                    .map(|t| t.is_hidden || t.is_deleted)
+                    .unwrap_or(true);
+                items.push_back(ActivityItem {
+                    thread_id: reply.thread_id,
+                    reply_id: Some(reply.id),
+                    created_at: reply.created_at,
+                    is_hidden: reply.is_hidden || reply.is_deleted || thread_gone,
+                });
+                reply_index += 1;
+            }
+        }
+        items
+    }
 
-        // Get voting contract for vote buttons
-        let voting_contract: Option<Address> = env.storage().instance().get(&BoardKey::Voting);
+    /// Add threads started before the per-user index existed to it (owner/admin),
+    /// `limit` thread IDs at a time from `start`. Safe to re-run.
+    pub fn index_user_threads(env: Env, board_id: u64, start: u64, limit: u64, caller: Address) {
+        caller.require_auth();
 
-        // Get board metadata for slug-based URLs
-        let board_meta: BoardMeta = env
+        if let Some(permissions) = env
             .storage()
-            .persistent()
-            .get(&BoardKey::Board(board_id))
-            .expect("Board not found");
+            .instance()
+            .get::<_, Address>(&BoardKey::Permissions)
+        {
+            let args: Vec<Val> =
+                Vec::from_array(&env, [board_id.into_val(&env), caller.into_val(&env)]);
+            let can_admin: bool =
+                env.invoke_contract(&permissions, &Symbol::new(&env, "can_admin"), args);
+            if !can_admin {
+                panic!("Only owner or admin can index threads");
+            }
+        }
 
-        // Build base path for all links
-        let base_path = Self::build_board_base_path(env, board_id, &board_meta.slug);
+        let count: u64 = env
+            .storage()
+            .persistent()
+            .get(&BoardKey::BoardThreadCount(board_id))
+            .unwrap_or(0);
+        for thread_id in start..start.saturating_add(limit).min(count) {
+            if let Some(thread) = Self::get_thread(env.clone(), board_id, thread_id) {
+                Self::index_user_thread(&env, board_id, thread_id, &thread.creator);
+            }
+        }
+    }
 
-        let config: BoardConfig = env
+    /// Insert a thread into its author's index (IDs ascending), keeping the newest
+    /// MAX_USER_THREADS. Already-indexed threads are left alone.
+    fn index_user_thread(env: &Env, board_id: u64, thread_id: u64, creator: &Address) {
+        let user_key = UserKey::UserThreads(board_id, creator.clone());
+        let mut user_threads: Vec<u64> = env
             .storage()
             .persistent()
-            .get(&BoardKey::BoardConfig(board_id))
-            .expect("Board not found");
-        let chunk_size = if config.reply_chunk_size == 0 {
-            6
-        } else {
-            config.reply_chunk_size
+            .get(&user_key)
+            .unwrap_or(Vec::new(env));
+        let Err(pos) = user_threads.binary_search(thread_id) else {
+            return;
         };
+        if pos == 0 && user_threads.len() >= MAX_USER_THREADS {
+            return;
+        }
+        user_threads.insert(pos, thread_id);
+        while user_threads.len() > MAX_USER_THREADS {
+            user_threads.pop_front();
+        }
+        env.storage().persistent().set(&user_key, &user_threads);
+    }
 
-        // Get viewer role for permission check
-        let perms_addr_opt = env
+    /// Number of threads in a user's full thread log on a board
+    pub fn user_thread_log_len(env: Env, board_id: u64, user: Address) -> u64 {
+        env.storage()
+            .persistent()
+            .get(&UserKey::UserThreadLogLen(board_id, user))
+            .unwrap_or(0)
+    }
+
+    /// Threads from a user's full thread log, `limit` entries from position `start`.
+    /// Unlike the recent-thread index the log is never trimmed, so moderation tools
+    /// can page through everything a user posted. Missing threads are skipped.
+    pub fn list_user_thread_log(
+        env: Env,
+        board_id: u64,
+        user: Address,
+        start: u64,
+        limit: u64,
+    ) -> Vec<ThreadMeta> {
+        let end = start.saturating_add(limit).min(Self::user_thread_log_len(
+            env.clone(),
+            board_id,
+            user.clone(),
+        ));
+        let mut threads = Vec::new(&env);
+        let mut pos = start;
+        while pos < end {
+            let chunk: Vec<u64> = env
+                .storage()
+                .persistent()
+                .get(&UserKey::UserThreadLog(
+                    board_id,
+                    user.clone(),
+                    pos / USER_LOG_CHUNK,
+                ))
+                .unwrap_or(Vec::new(&env));
+            let chunk_end = (pos / USER_LOG_CHUNK + 1) * USER_LOG_CHUNK;
+            for thread_id in chunk
+                .iter()
+                .skip((pos % USER_LOG_CHUNK) as usize)
+                .take((chunk_end.min(end) - pos) as usize)
+            {
+                if let Some(thread) = Self::get_thread(env.clone(), board_id, thread_id) {
+                    threads.push_back(thread);
+                }
+            }
+            pos = chunk_end;
+        }
+        threads
+    }
+
+    /// Add threads started before the author logs existed to them (owner/admin),
+    /// up to `limit` per call, continuing where the previous call stopped.
+    /// Returns how many older threads are still to be added.
+    pub fn log_user_threads(env: Env, board_id: u64, limit: u64, caller: Address) -> u64 {
+        caller.require_auth();
+
+        if let Some(permissions) = env
             .storage()
             .instance()
-            .get::<_, Address>(&BoardKey::Permissions);
-        let viewer_role = if let Some(ref perms_addr) = perms_addr_opt {
-            if let Some(user) = viewer {
-                let args: Vec<Val> =
-                    Vec::from_array(env, [board_id.into_val(env), user.into_val(env)]);
-                env.invoke_contract(perms_addr, &Symbol::new(env, "get_role"), args)
-            } else {
-                Role::Guest
+            .get::<_, Address>(&BoardKey::Permissions)
+        {
+            let args: Vec<Val> =
+                Vec::from_array(&env, [board_id.into_val(&env), caller.into_val(&env)]);
+            let can_admin: bool =
+                env.invoke_contract(&permissions, &Symbol::new(&env, "can_admin"), args);
+            if !can_admin {
+                panic!("Only owner or admin can index threads");
             }
-        } else {
-            Role::Guest
-        };
+        }
 
-        // Check permissions for private boards - must be Member+ to access
-        if config.is_private {
-            if let Some(ref perms_addr) = perms_addr_opt {
-                if (viewer_role as u32) < (Role::Member as u32) {
-                    return Self::render_private_board_message(
-                        env, board_id, &config, viewer, perms_addr,
-                    );
-                }
+        // Threads from the first logged one on were logged when they were created
+        let logged_from: u64 = env
+            .storage()
+            .persistent()
+            .get(&BoardKey::ThreadLogFrom(board_id))
+            .unwrap_or_else(|| {
+                env.storage()
+                    .persistent()
+                    .get(&BoardKey::BoardThreadCount(board_id))
+                    .unwrap_or(0)
+            });
+        let cursor_key = BoardKey::ThreadLogBackfilled(board_id);
+        let start: u64 = env.storage().persistent().get(&cursor_key).unwrap_or(0);
+        let end = start.saturating_add(limit).min(logged_from).max(start);
+        for thread_id in start..end {
+            if let Some(thread) = Self::get_thread(env.clone(), board_id, thread_id) {
+                Self::log_user_thread(&env, board_id, thread_id, &thread.creator);
             }
         }
+        env.storage().persistent().set(&cursor_key, &end);
+        logged_from.saturating_sub(end)
+    }
 
-        // Determine if posting is allowed (requires Member+ role, not readonly, not locked)
-        let thread = env
+    /// Append a thread to its author's full thread log
+    fn log_user_thread(env: &Env, board_id: u64, thread_id: u64, creator: &Address) {
+        let len_key = UserKey::UserThreadLogLen(board_id, creator.clone());
+        let len: u64 = env.storage().persistent().get(&len_key).unwrap_or(0);
+        let chunk_key = UserKey::UserThreadLog(board_id, creator.clone(), len / USER_LOG_CHUNK);
+        let mut chunk: Vec<u64> = env
             .storage()
             .persistent()
-            .get::<_, ThreadMeta>(&BoardKey::BoardThread(board_id, thread_id));
-        let is_locked = thread.as_ref().map(|t| t.is_locked).unwrap_or(false);
-        let viewer_can_post = (viewer_role as u32) >= (Role::Member as u32);
-        let can_post = !config.is_readonly && !is_locked && viewer_can_post;
+            .get(&chunk_key)
+            .unwrap_or(Vec::new(env));
+        chunk.push_back(thread_id);
+        env.storage().persistent().set(&chunk_key, &chunk);
+        env.storage().persistent().set(&len_key, &(len + 1));
+    }
 
-        // Get total children count
-        let count_args: Vec<Val> = Vec::from_array(
-            env,
-            [
-                board_id.into_val(env),
-                thread_id.into_val(env),
-                parent_id.into_val(env),
-            ],
-        );
-        let total_count: u32 = env.invoke_contract(
-            &content,
-            &Symbol::new(env, "get_children_count"),
-            count_args,
-        );
+    /// List threads with pagination
+    pub fn list_threads(env: Env, board_id: u64, start: u64, limit: u64) -> Vec<ThreadMeta> {
+        let count: u64 = env
+            .storage()
+            .persistent()
+            .get(&BoardKey::BoardThreadCount(board_id))
+            .unwrap_or(0);
 
-        // Fetch this batch of children
-        let list_args: Vec<Val> = Vec::from_array(
-            env,
-            [
-                board_id.into_val(env),
-                thread_id.into_val(env),
-                parent_id.into_val(env),
-                start.into_val(env),
-                chunk_size.into_val(env),
-            ],
-        );
-        let children: Vec<ReplyMeta> = env.invoke_contract(
-            &content,
-            &Symbol::new(env, "list_children_replies"),
-            list_args,
-        );
+        let mut threads = Vec::new(&env);
 
-        let mut md = MarkdownBuilder::new(env);
+        // Return newest first (reverse order)
+        let actual_start = if count > start { count - start - 1 } else { 0 };
 
-        for i in 0..children.len() {
-            if let Some(child) = children.get(i) {
-                md = Self::render_reply_item_waterfall(
-                    env,
-                    md,
-                    &content,
-                    &child,
-                    board_id,
-                    thread_id,
-                    &base_path,
-                    &board_meta.slug,
-                    viewer,
-                    can_post,
-                    &profile_contract,
-                    &voting_contract,
-                );
+        for i in 0..limit {
+            if actual_start < i {
+                break;
+            }
+            let idx = actual_start - i;
+            if let Some(thread) = env
+                .storage()
+                .persistent()
+                .get(&BoardKey::BoardThread(board_id, idx))
+            {
+                threads.push_back(thread);
             }
         }
 
-        // If more children exist, add continuation with slug-based path
-        let next_start = start + chunk_size;
-        if next_start < total_count {
-            md = md
-                .raw_str("{{render path=\"")
-                .raw(base_path)
-                .raw_str("/t/")
-                .number(thread_id as u32)
-                .raw_str("/r/")
-                .number(parent_id as u32)
-                .raw_str("/children/")
-                .number(next_start)
-                .raw_str("\"}}");
-        }
-
-        md.build()
+        threads
     }
 
-    /// Render a single reply with waterfall loading for children
-    fn render_reply_item_waterfall<'a>(
-        env: &Env,
-        mut md: MarkdownBuilder<'a>,
-        content: &Address,
-        reply: &ReplyMeta,
+    /// List threads matching a status filter (newest first).
+    /// Scans the thread IDs in the pagination window [start, start + limit)
+    /// and returns those that match, so results may be fewer than `limit`.
+    /// The scan window is capped at MAX_FILTER_SCAN to keep gas bounded.
+    pub fn list_threads_filtered(
+        env: Env,
         board_id: u64,
-        thread_id: u64,
-        base_path: &Bytes,
-        board_slug: &String,
-        viewer: &Option<Address>,
-        can_post: bool,
-        profile_contract: &Option<Address>,
-        voting_contract: &Option<Address>,
-    ) -> MarkdownBuilder<'a> {
-        md = md.div_start("reply");
+        filter: ThreadFilter,
+        start: u64,
+        limit: u64,
+    ) -> Vec<ThreadMeta> {
+        let count: u64 = env
+            .storage()
+            .persistent()
+            .get(&BoardKey::BoardThreadCount(board_id))
+            .unwrap_or(0);
 
-        // Reply header with author (with return path so "Go Back" returns to thread)
-        let return_path = Self::build_thread_return_path(env, board_id, board_slug, thread_id);
-        md = md.div_start("reply-header");
-        md = Self::render_author(env, md, &reply.creator, profile_contract, Some(return_path));
-        md = md
-            .raw_str(" · Reply #")
-            .number(reply.id as u32)
-            .raw_str(" · ")
-            .raw(Self::format_timestamp(env, reply.created_at))
-            .div_end();
+        let mut threads = Vec::new(&env);
+        if start >= count {
+            return threads;
+        }
 
-        // Reply content
-        if reply.is_hidden {
-            md = md
-                .div_start("reply-content reply-hidden")
-                .text("[This reply has been hidden by a moderator]")
-                .div_end();
-        } else if reply.is_deleted {
-            md = md
-                .div_start("reply-content reply-deleted")
-                .text("[This reply has been deleted]")
-                .div_end();
+        let limit = if limit > MAX_FILTER_SCAN {
+            MAX_FILTER_SCAN
         } else {
-            let args: Vec<Val> = Vec::from_array(
-                env,
-                [
-                    board_id.into_val(env),
-                    thread_id.into_val(env),
-                    reply.id.into_val(env),
-                ],
-            );
-            let content_bytes: Bytes =
-                env.invoke_contract(content, &Symbol::new(env, "get_reply_content"), args);
+            limit
+        };
 
-            md = md.div_start("reply-content").raw(content_bytes).div_end();
+        let actual_start = count - start - 1;
+        let mut window = Vec::new(&env);
+        for i in 0..limit {
+            if actual_start < i {
+                break;
+            }
+            let idx = actual_start - i;
+            if let Some(thread) = env
+                .storage()
+                .persistent()
+                .get::<_, ThreadMeta>(&BoardKey::BoardThread(board_id, idx))
+            {
+                window.push_back(thread);
+            }
         }
 
-        // Vote buttons for reply (if voting contract is configured)
-        if let Some(ref voting) = voting_contract {
-            // Get reply vote tally
-            let tally_args: Vec<Val> = Vec::from_array(
-                env,
-                [
-                    board_id.into_val(env),
-                    thread_id.into_val(env),
-                    reply.id.into_val(env),
-                ],
-            );
-            let tally: VoteTally =
-                env.invoke_contract(voting, &Symbol::new(env, "get_reply_tally"), tally_args);
-
-            // Get viewer's current vote (if logged in)
-            let viewer_vote = if let Some(ref user) = viewer {
-                let vote_args: Vec<Val> = Vec::from_array(
-                    env,
-                    [
-                        board_id.into_val(env),
-                        thread_id.into_val(env),
-                        reply.id.into_val(env),
-                        user.into_val(env),
-                    ],
-                );
-                env.invoke_contract::<VoteDirection>(
-                    voting,
-                    &Symbol::new(env, "get_user_reply_vote"),
-                    vote_args,
-                )
-            } else {
-                VoteDirection::None
-            };
-
-            md = md.div_start("reply-votes");
-
-            // Upvote button
-            if viewer.is_some() {
-                let up_class = if viewer_vote == VoteDirection::Up {
-                    "vote-up vote-active"
-                } else {
-                    "vote-up"
-                };
-                md = md
-                    .raw_str("<a href=\"tx:@voting:vote_reply {&quot;board_id&quot;:")
-                    .number(board_id as u32)
-                    .raw_str(",&quot;thread_id&quot;:")
-                    .number(thread_id as u32)
-                    .raw_str(",&quot;reply_id&quot;:")
-                    .number(reply.id as u32)
-                    .raw_str(",&quot;direction&quot;:");
-                if viewer_vote == VoteDirection::Up {
-                    md = md.raw_str("0");
-                } else {
-                    md = md.raw_str("1");
+        if filter == ThreadFilter::Flagged {
+            // Flag counts live in the content contract; one batched call covers the window
+            let flag_counts = Self::thread_flag_counts(&env, board_id, &window);
+            for (thread, flags) in window.iter().zip(flag_counts.iter()) {
+                if flags > 0 {
+                    threads.push_back(thread);
                 }
-                md = md
-                    .raw_str("}\" class=\"")
-                    .raw_str(up_class)
-                    .raw_str("\">▲</a>");
-            } else {
-                md = md.raw_str("<span class=\"vote-up vote-disabled\">▲</span>");
             }
-
-            // Score
-            md = md
-                .raw_str("<span class=\"vote-score-inline\">")
-                .number(tally.score as u32)
-                .raw_str("</span>");
-
-            // Downvote button
-            if viewer.is_some() {
-                let down_class = if viewer_vote == VoteDirection::Down {
-                    "vote-down vote-active"
-                } else {
-                    "vote-down"
-                };
-                md = md
-                    .raw_str("<a href=\"tx:@voting:vote_reply {&quot;board_id&quot;:")
-                    .number(board_id as u32)
-                    .raw_str(",&quot;thread_id&quot;:")
-                    .number(thread_id as u32)
-                    .raw_str(",&quot;reply_id&quot;:")
-                    .number(reply.id as u32)
-                    .raw_str(",&quot;direction&quot;:");
-                if viewer_vote == VoteDirection::Down {
-                    md = md.raw_str("0");
-                } else {
-                    md = md.raw_str("2");
+        } else {
+            for thread in window.iter() {
+                if Self::thread_matches_filter(&thread, filter) {
+                    threads.push_back(thread);
                 }
-                md = md
-                    .raw_str("}\" class=\"")
-                    .raw_str(down_class)
-                    .raw_str("\">▼</a>");
-            } else {
-                md = md.raw_str("<span class=\"vote-down vote-disabled\">▼</span>");
             }
-
-            md = md.div_end();
         }
 
-        // Reply actions
-        md = md.div_start("reply-meta");
-
-        // Only show Reply button if posting is allowed
-        if viewer.is_some() && can_post {
-            md = md
-                .raw_str("[Reply](render:")
-                .raw(base_path.clone())
-                .raw_str("/t/")
-                .number(thread_id as u32)
-                .raw_str("/r/")
-                .number(reply.id as u32)
-                .raw_str("/reply)");
+        threads
+    }
 
-            // Show edit button if user can edit (and reply is not deleted)
-            if !reply.is_deleted {
-                let (is_author, is_moderator) =
-                    Self::can_edit(env, board_id, &reply.creator, viewer);
-                let can_edit_time =
-                    is_moderator || Self::is_within_edit_window(env, board_id, reply.created_at);
+    /// Check whether a thread matches a status filter. Flagged needs the content
+    /// contract and is handled in batch by `list_threads_filtered`.
+    fn thread_matches_filter(thread: &ThreadMeta, filter: ThreadFilter) -> bool {
+        match filter {
+            ThreadFilter::All => true,
+            ThreadFilter::Hidden => thread.is_hidden,
+            ThreadFilter::Locked => thread.is_locked,
+            ThreadFilter::Deleted => thread.is_deleted,
+            ThreadFilter::Pinned => thread.is_pinned,
+            ThreadFilter::Flagged => false,
+        }
+    }
 
-                if (is_author || is_moderator) && can_edit_time {
-                    md = md
-                        .text(" ")
-                        .raw_str("[Edit](render:")
-                        .raw(base_path.clone())
-                        .raw_str("/t/")
-                        .number(thread_id as u32)
-                        .raw_str("/r/")
-                        .number(reply.id as u32)
-                        .raw_str("/edit)");
-                }
-            }
+    /// Flag counts for a batch of threads, in order (all zero without a content contract)
+    fn thread_flag_counts(env: &Env, board_id: u64, threads: &Vec<ThreadMeta>) -> Vec<u32> {
+        let mut ids = Vec::new(env);
+        for thread in threads.iter() {
+            ids.push_back(thread.id);
         }
+        env.storage()
+            .instance()
+            .get::<_, Address>(&BoardKey::Content)
+            .and_then(|content| {
+                let args: Vec<Val> =
+                    Vec::from_array(env, [board_id.into_val(env), ids.into_val(env)]);
+                env.try_invoke_contract::<Vec<u32>, soroban_sdk::Error>(
+                    &content,
+                    &Symbol::new(env, "get_thread_flag_counts"),
+                    args,
+                )
+                .ok()
+                .and_then(|r| r.ok())
+            })
+            .unwrap_or_else(|| {
+                let mut zeros = Vec::new(env);
+                for _ in threads.iter() {
+                    zeros.push_back(0u32);
+                }
+                zeros
+            })
+    }
 
-        // Flag button is always available to logged in users
-        if viewer.is_some() {
-            md = md
-                .text(" ")
-                .raw_str("[Flag](tx:@content:flag_reply {\"board_id\":")
-                .number(board_id as u32)
-                .raw_str(",\"thread_id\":")
-                .number(thread_id as u32)
-                .raw_str(",\"reply_id\":")
-                .number(reply.id as u32)
-                .raw_str(",\"reason\":\"\"})");
+    /// Case-insensitive substring search over thread titles (newest first).
+    /// Only the most recent SEARCH_SCAN_WINDOW threads are scanned; `start` skips
+    /// that many matches. Hidden and deleted threads are left out unless the
+    /// viewer is a moderator.
+    pub fn search_threads(
+        env: Env,
+        board_id: u64,
+        query: String,
+        start: u32,
+        limit: u32,
+        viewer: Option<Address>,
+    ) -> Vec<ThreadMeta> {
+        let mut results = Vec::new(&env);
+        let query_len = query.len() as usize;
+        if query_len == 0 || query_len > MAX_SEARCH_QUERY_LEN || limit == 0 {
+            return results;
         }
+        let mut needle = [0u8; MAX_SEARCH_QUERY_LEN];
+        query.copy_into_slice(&mut needle[..query_len]);
+        needle[..query_len].make_ascii_lowercase();
+        let needle = &needle[..query_len];
 
-        md = md.div_end();
+        let permissions: Option<Address> = env.storage().instance().get(&BoardKey::Permissions);
+        let can_moderate = match (&viewer, permissions) {
+            (Some(user), Some(permissions)) => {
+                let args: Vec<Val> =
+                    Vec::from_array(&env, [board_id.into_val(&env), user.into_val(&env)]);
+                env.invoke_contract::<bool>(&permissions, &Symbol::new(&env, "can_moderate"), args)
+            }
+            _ => false,
+        };
 
-        // Get children count
-        let count_args: Vec<Val> = Vec::from_array(
-            env,
-            [
-                board_id.into_val(env),
-                thread_id.into_val(env),
-                reply.id.into_val(env),
-            ],
-        );
-        let children_count: u32 =
-            env.invoke_contract(content, &Symbol::new(env, "get_children_count"), count_args);
+        let count: u64 = env
+            .storage()
+            .persistent()
+            .get(&BoardKey::BoardThreadCount(board_id))
+            .unwrap_or(0);
+        let oldest = count.saturating_sub(SEARCH_SCAN_WINDOW);
 
-        // If has children, embed continuation for waterfall loading with slug-based path
-        if children_count > 0 {
-            md = md
-                .raw_str("{{render path=\"")
-                .raw(base_path.clone())
-                .raw_str("/t/")
-                .number(thread_id as u32)
-                .raw_str("/r/")
-                .number(reply.id as u32)
-                .raw_str("/children/0\"}}");
+        let mut skipped = 0u32;
+        let mut idx = count;
+        while idx > oldest && results.len() < limit {
+            idx -= 1;
+            let Some(thread) = env
+                .storage()
+                .persistent()
+                .get::<_, ThreadMeta>(&BoardKey::BoardThread(board_id, idx))
+            else {
+                continue;
+            };
+            if (thread.is_hidden || thread.is_deleted) && !can_moderate {
+                continue;
+            }
+            if !Self::title_contains(&thread.title, needle) {
+                continue;
+            }
+            if skipped < start {
+                skipped += 1;
+                continue;
+            }
+            results.push_back(thread);
         }
 
-        md = md.div_end();
-        md
+        results
     }
 
-    /// Render reply form
-    fn render_reply_form(
-        env: &Env,
-        board_id: u64,
-        thread_id: u64,
-        parent_reply_id: Option<u64>,
-        viewer: &Option<Address>,
-    ) -> Bytes {
-        // Get board metadata for slug-based URLs
-        let board_meta: BoardMeta = env
-            .storage()
-            .persistent()
-            .get(&BoardKey::Board(board_id))
-            .expect("Board not found");
+    /// Check whether a title contains an already lower-cased needle (ASCII case folding)
+    fn title_contains(title: &String, needle: &[u8]) -> bool {
+        if (title.len() as usize) < needle.len() {
+            return false;
+        }
+        let mut buf = [0u8; MAX_SEARCH_TITLE_LEN];
+        let len = copy_string_prefix(title, &mut buf);
+        buf[..len].make_ascii_lowercase();
+        buf[..len].windows(needle.len()).any(|w| w == needle)
+    }
 
-        // Build base path for all links
-        let base_path = Self::build_board_base_path(env, board_id, &board_meta.slug);
-
-        // Get board config for private board check
-        let config: BoardConfig = env
-            .storage()
+    /// Get thread count for a board
+    pub fn thread_count(env: Env, board_id: u64) -> u64 {
+        env.storage()
             .persistent()
-            .get(&BoardKey::BoardConfig(board_id))
-            .expect("Board not found");
+            .get(&BoardKey::BoardThreadCount(board_id))
+            .unwrap_or(0)
+    }
 
-        // Get permissions contract and viewer role early for private board check
-        let perms_addr_opt = env
-            .storage()
-            .instance()
-            .get::<_, Address>(&BoardKey::Permissions);
-        let viewer_role = if let Some(ref perms_addr) = perms_addr_opt {
-            if let Some(user) = viewer {
-                let role_args: Vec<Val> =
-                    Vec::from_array(env, [board_id.into_val(env), user.into_val(env)]);
-                env.invoke_contract(perms_addr, &Symbol::new(env, "get_role"), role_args)
-            } else {
-                Role::Guest
-            }
-        } else {
-            Role::Guest
-        };
+    /// Lock a thread (no more replies)
+    pub fn lock_thread(env: Env, board_id: u64, thread_id: u64, caller: Address) {
+        caller.require_auth();
 
-        // Check permissions for private boards - must be Member+ to access
-        if config.is_private {
-            if let Some(ref perms_addr) = perms_addr_opt {
-                if (viewer_role as u32) < (Role::Member as u32) {
-                    return Self::render_private_board_message(
-                        env, board_id, &config, viewer, perms_addr,
-                    );
-                }
-            }
+        // Check moderator permissions (only if permissions contract is set)
+        if env.storage().instance().has(&BoardKey::Permissions) {
+            Self::check_can_moderate(&env, board_id, &caller);
         }
 
-        let mut md = Self::render_nav(env, board_id, viewer)
-            .newline()
-            // Error mappings for user-friendly error messages
-            .raw_str("{{errors {\"1\": \"This board is read-only.\", \"2\": \"This thread is locked and does not accept new replies.\", \"3\": \"You don't have permission to perform this action.\"}}}\n")
-            .raw_str("[< Back to Thread](render:")
-            .raw(base_path.clone())
-            .raw_str("/t/")
-            .number(thread_id as u32)
-            .raw_str(")")
-            .newline()
-            .newline();
-
-        if parent_reply_id.is_some() {
-            md = md.raw_str("<h1>Reply to Comment</h1>\n");
-        } else {
-            md = md.raw_str("<h1>Reply to Thread</h1>\n");
+        if let Some(mut thread) = env
+            .storage()
+            .persistent()
+            .get::<_, ThreadMeta>(&BoardKey::BoardThread(board_id, thread_id))
+        {
+            thread.is_locked = true;
+            Self::store_thread_meta(&env, board_id, thread_id, &thread);
         }
+    }
 
-        // Check if board is read-only
-        if config.is_readonly {
-            md = md.warning("This board is read-only. Replies cannot be posted.");
-            return Self::render_footer_into(env, md).build();
+    /// Unlock a thread
+    pub fn unlock_thread(env: Env, board_id: u64, thread_id: u64, caller: Address) {
+        caller.require_auth();
+
+        // Check moderator permissions (only if permissions contract is set)
+        if env.storage().instance().has(&BoardKey::Permissions) {
+            Self::check_can_moderate(&env, board_id, &caller);
         }
 
-        // Check if thread is locked
-        if let Some(thread) = env
+        if let Some(mut thread) = env
             .storage()
             .persistent()
             .get::<_, ThreadMeta>(&BoardKey::BoardThread(board_id, thread_id))
         {
-            if thread.is_locked {
-                md = md.warning("This thread is locked. Replies cannot be posted.");
-                return Self::render_footer_into(env, md).build();
-            }
+            thread.is_locked = false;
+            Self::store_thread_meta(&env, board_id, thread_id, &thread);
         }
+    }
 
-        if viewer.is_none() {
-            md = md.warning("Please connect your wallet to reply.");
-            return Self::render_footer_into(env, md).build();
-        }
+    /// Open a thread for replies at `open_at` and lock it at `lock_at` (Moderator+).
+    /// Either bound may be None; clearing both removes the schedule.
+    pub fn set_thread_schedule(
+        env: Env,
+        board_id: u64,
+        thread_id: u64,
+        open_at: Option<u64>,
+        lock_at: Option<u64>,
+        caller: Address,
+    ) {
+        caller.require_auth();
 
-        // Check if user has permission to reply (requires Member+ role)
-        if (viewer_role as u32) < (Role::Member as u32) {
-            md = md.warning("You don't have permission to reply on this board.");
-            return Self::render_footer_into(env, md).build();
+        // Check moderator permissions (only if permissions contract is set)
+        if env.storage().instance().has(&BoardKey::Permissions) {
+            Self::check_can_moderate(&env, board_id, &caller);
         }
 
-        // Calculate parent_id and depth
-        let (parent_id, depth): (u64, u32) = if let Some(pid) = parent_reply_id {
-            if let Some(content_addr) = env
-                .storage()
-                .instance()
-                .get::<_, Address>(&BoardKey::Content)
-            {
-                let args: Vec<Val> = Vec::from_array(
-                    env,
-                    [
-                        board_id.into_val(env),
-                        thread_id.into_val(env),
-                        pid.into_val(env),
-                    ],
-                );
-                let parent_reply: Option<ReplyMeta> =
-                    env.invoke_contract(&content_addr, &Symbol::new(env, "get_reply"), args);
-                match parent_reply {
-                    Some(reply) => (pid, reply.depth + 1),
-                    None => (pid, 1),
-                }
-            } else {
-                (pid, 1)
+        if !env
+            .storage()
+            .persistent()
+            .has(&BoardKey::BoardThread(board_id, thread_id))
+        {
+            panic!("Thread not found");
+        }
+        if let (Some(open), Some(lock)) = (open_at, lock_at) {
+            if lock <= open {
+                panic!("Lock time must be after open time");
             }
-        } else {
-            (0, 0)
-        };
-
-        md = md
-            .raw_str("<input type=\"hidden\" name=\"_redirect\" value=\"")
-            .raw(base_path.clone())
-            .raw_str("/t/")
-            .number(thread_id as u32)
-            .raw_str("\" />\n")
-            .raw_str("<input type=\"hidden\" name=\"board_id\" value=\"")
-            .number(board_id as u32)
-            .raw_str("\" />\n")
-            .raw_str("<input type=\"hidden\" name=\"thread_id\" value=\"")
-            .number(thread_id as u32)
-            .raw_str("\" />\n")
-            .raw_str("<input type=\"hidden\" name=\"parent_id\" value=\"")
-            .number(parent_id as u32)
-            .raw_str("\" />\n")
-            .raw_str("<input type=\"hidden\" name=\"depth\" value=\"")
-            .number(depth)
-            .raw_str("\" />\n")
-            .textarea_markdown("content_str", 6, "Write your reply...")
-            .newline()
-            .raw_str("<input type=\"hidden\" name=\"caller\" value=\"")
-            .text_string(&viewer.as_ref().unwrap().to_string())
-            .raw_str("\" />\n")
-            .newline()
-            .form_link_to("Post Reply", "content", "create_reply")
-            .newline()
-            .newline()
-            .raw_str("[Cancel](render:")
-            .raw(base_path)
-            .raw_str("/t/")
-            .number(thread_id as u32)
-            .raw_str(")");
+        }
 
-        Self::render_footer_into(env, md).build()
+        let key = ThreadKey::ThreadSchedule(board_id, thread_id);
+        if open_at.is_none() && lock_at.is_none() {
+            env.storage().persistent().remove(&key);
+        } else {
+            let schedule = ThreadSchedule { open_at, lock_at };
+            env.storage().persistent().set(&key, &schedule);
+        }
     }
 
-    /// Check if user can edit content (author or moderator)
-    fn can_edit(
-        env: &Env,
-        board_id: u64,
-        creator: &Address,
-        viewer: &Option<Address>,
-    ) -> (bool, bool) {
-        let viewer = match viewer {
-            Some(v) => v,
-            None => return (false, false),
-        };
-
-        let is_author = creator == viewer;
+    /// Get a thread's reply schedule, if one is set
+    pub fn get_thread_schedule(env: Env, board_id: u64, thread_id: u64) -> Option<ThreadSchedule> {
+        env.storage()
+            .persistent()
+            .get(&ThreadKey::ThreadSchedule(board_id, thread_id))
+    }
 
-        // Check if viewer is moderator
-        let is_moderator = if env.storage().instance().has(&BoardKey::Permissions) {
-            let permissions: Address = env
-                .storage()
-                .instance()
-                .get(&BoardKey::Permissions)
-                .unwrap();
-            let args: Vec<Val> =
-                Vec::from_array(env, [board_id.into_val(env), viewer.into_val(env)]);
-            let can_moderate: bool =
-                env.invoke_contract(&permissions, &Symbol::new(env, "can_moderate"), args);
-            can_moderate
-        } else {
-            false
+    /// Check a thread's schedule against the current ledger time
+    fn reply_window(env: &Env, board_id: u64, thread_id: u64) -> ReplyWindow {
+        let Some(schedule) = Self::get_thread_schedule(env.clone(), board_id, thread_id) else {
+            return ReplyWindow::Open;
         };
+        let now = env.ledger().timestamp();
+        match schedule {
+            ThreadSchedule {
+                open_at: Some(open_at),
+                ..
+            } if now < open_at => ReplyWindow::NotYetOpen(open_at),
+            ThreadSchedule {
+                lock_at: Some(lock_at),
+                ..
+            } if now > lock_at => ReplyWindow::Closed,
+            _ => ReplyWindow::Open,
+        }
+    }
 
-        (is_author, is_moderator)
+    /// Whether a thread is locked, by a moderator or by its schedule
+    fn replies_closed(env: &Env, board_id: u64, thread: &ThreadMeta) -> bool {
+        thread.is_locked || Self::reply_window(env, board_id, thread.id) != ReplyWindow::Open
     }
 
-    /// Render edit thread form
-    fn render_edit_thread(
-        env: &Env,
+    /// Mark a reply as the thread's accepted answer (thread author or Moderator+).
+    /// Passing None clears it; marking another reply replaces it.
+    pub fn set_accepted_answer(
+        env: Env,
         board_id: u64,
         thread_id: u64,
-        viewer: &Option<Address>,
-    ) -> Bytes {
-        // Get board metadata for slug-based URLs
-        let board_meta: BoardMeta = env
-            .storage()
-            .persistent()
-            .get(&BoardKey::Board(board_id))
-            .expect("Board not found");
-
-        // Build base path for all links
-        let base_path = Self::build_board_base_path(env, board_id, &board_meta.slug);
+        reply_id: Option<u64>,
+        caller: Address,
+    ) {
+        caller.require_auth();
 
-        // Get board config for private board check
-        let config: BoardConfig = env
+        let thread: ThreadMeta = env
             .storage()
             .persistent()
-            .get(&BoardKey::BoardConfig(board_id))
-            .expect("Board not found");
-
-        // Get permissions contract and viewer role early for private board check
-        let perms_addr_opt = env
-            .storage()
-            .instance()
-            .get::<_, Address>(&BoardKey::Permissions);
-        let viewer_role = if let Some(ref perms_addr) = perms_addr_opt {
-            if let Some(user) = viewer {
-                let role_args: Vec<Val> =
-                    Vec::from_array(env, [board_id.into_val(env), user.into_val(env)]);
-                env.invoke_contract(perms_addr, &Symbol::new(env, "get_role"), role_args)
-            } else {
-                Role::Guest
-            }
-        } else {
-            Role::Guest
-        };
+            .get(&BoardKey::BoardThread(board_id, thread_id))
+            .expect("Thread not found");
 
-        // Check permissions for private boards - must be Member+ to access
-        if config.is_private {
-            if let Some(ref perms_addr) = perms_addr_opt {
-                if (viewer_role as u32) < (Role::Member as u32) {
-                    return Self::render_private_board_message(
-                        env, board_id, &config, viewer, perms_addr,
-                    );
-                }
-            }
+        // The author decides for their own question; moderators can always step in
+        if caller != thread.creator && env.storage().instance().has(&BoardKey::Permissions) {
+            Self::check_can_moderate(&env, board_id, &caller);
         }
 
+        let key = ThreadKey::AcceptedAnswer(board_id, thread_id);
+        let Some(reply_id) = reply_id else {
+            env.storage().persistent().remove(&key);
+            return;
+        };
+
         let content: Address = env
             .storage()
             .instance()
             .get(&BoardKey::Content)
-            .expect("Content contract not configured");
+            .expect("Content contract not set");
+        let args: Vec<Val> = Vec::from_array(
+            &env,
+            [
+                board_id.into_val(&env),
+                thread_id.into_val(&env),
+                reply_id.into_val(&env),
+            ],
+        );
+        let reply: Option<ReplyMeta> =
+            env.invoke_contract(&content, &Symbol::new(&env, "get_reply"), args);
+        match reply {
+            Some(r) if r.thread_id == thread_id && !r.is_deleted => {}
+            _ => panic!("Reply not found in this thread"),
+        }
 
-        let mut md = Self::render_nav(env, board_id, viewer)
-            .newline()
-            .raw_str("[< Back to Thread](render:")
-            .raw(base_path.clone())
-            .raw_str("/t/")
-            .number(thread_id as u32)
-            .raw_str(")")
-            .newline()
-            .newline()
-            .h1("Edit Thread");
+        env.storage().persistent().set(&key, &reply_id);
+    }
 
-        // Check if board is read-only
-        if config.is_readonly {
-            md = md.warning("This board is read-only. Threads cannot be edited.");
-            return Self::render_footer_into(env, md).build();
-        }
+    /// Get the reply marked as a thread's accepted answer, if any
+    pub fn get_accepted_answer(env: Env, board_id: u64, thread_id: u64) -> Option<u64> {
+        env.storage()
+            .persistent()
+            .get(&ThreadKey::AcceptedAnswer(board_id, thread_id))
+    }
 
-        if viewer.is_none() {
-            md = md.warning("Please connect your wallet to edit.");
-            return Self::render_footer_into(env, md).build();
+    /// Pin a thread
+    pub fn pin_thread(env: Env, board_id: u64, thread_id: u64, caller: Address) {
+        caller.require_auth();
+
+        // Check moderator permissions (only if permissions contract is set)
+        if env.storage().instance().has(&BoardKey::Permissions) {
+            Self::check_can_moderate(&env, board_id, &caller);
         }
 
-        // Get thread metadata
-        let thread = match env
+        if let Some(mut thread) = env
             .storage()
             .persistent()
             .get::<_, ThreadMeta>(&BoardKey::BoardThread(board_id, thread_id))
         {
-            Some(t) => t,
-            None => {
-                md = md.warning("Thread not found.");
-                return Self::render_footer_into(env, md).build();
-            }
-        };
+            thread.is_pinned = true;
+            Self::store_thread_meta(&env, board_id, thread_id, &thread);
 
-        // Check if locked
-        if thread.is_locked {
-            md = md.warning("This thread is locked and cannot be edited.");
-            return Self::render_footer_into(env, md).build();
+            let mut pinned: Vec<u64> = env
+                .storage()
+                .persistent()
+                .get(&BoardKey::BoardPinnedThreads(board_id))
+                .unwrap_or(Vec::new(&env));
+            pinned.push_back(thread_id);
+            env.storage()
+                .persistent()
+                .set(&BoardKey::BoardPinnedThreads(board_id), &pinned);
         }
+    }
 
-        // Check edit permission
-        let (is_author, is_moderator) = Self::can_edit(env, board_id, &thread.creator, viewer);
+    /// Unpin a thread
+    pub fn unpin_thread(env: Env, board_id: u64, thread_id: u64, caller: Address) {
+        caller.require_auth();
 
-        if !is_author && !is_moderator {
-            md = md.warning("You don't have permission to edit this thread.");
-            return Self::render_footer_into(env, md).build();
+        // Check moderator permissions (only if permissions contract is set)
+        if env.storage().instance().has(&BoardKey::Permissions) {
+            Self::check_can_moderate(&env, board_id, &caller);
         }
 
-        // Check edit window (only applies to non-moderators)
-        if is_author
-            && !is_moderator
-            && !Self::is_within_edit_window(env, board_id, thread.created_at)
+        if let Some(mut thread) = env
+            .storage()
+            .persistent()
+            .get::<_, ThreadMeta>(&BoardKey::BoardThread(board_id, thread_id))
         {
-            md = md.warning("The edit window has expired for this thread.");
-            return Self::render_footer_into(env, md).build();
-        }
-
-        // Get current thread body
-        let args: Vec<Val> =
-            Vec::from_array(env, [board_id.into_val(env), thread_id.into_val(env)]);
-        let body: Bytes = env.invoke_contract(&content, &Symbol::new(env, "get_thread_body"), args);
+            thread.is_pinned = false;
+            Self::store_thread_meta(&env, board_id, thread_id, &thread);
 
-        // Convert Bytes to escaped string for textarea
-        // Note: We need to include the body content in a way the form can use
-        // For now, we'll use a hidden field with base64 or just render the textarea
+            // Remove from pinned list
+            let pinned: Vec<u64> = env
+                .storage()
+                .persistent()
+                .get(&BoardKey::BoardPinnedThreads(board_id))
+                .unwrap_or(Vec::new(&env));
+            let mut new_pinned = Vec::new(&env);
+            for id in pinned.iter() {
+                if id != thread_id {
+                    new_pinned.push_back(id);
+                }
+            }
+            env.storage()
+                .persistent()
+                .set(&BoardKey::BoardPinnedThreads(board_id), &new_pinned);
+        }
+    }
 
-        md = md
-            .raw_str("<input type=\"hidden\" name=\"_redirect\" value=\"")
-            .raw(base_path.clone())
-            .raw_str("/t/")
-            .number(thread_id as u32)
-            .raw_str("\" />\n")
-            .raw_str("<input type=\"hidden\" name=\"board_id\" value=\"")
-            .number(board_id as u32)
-            .raw_str("\" />\n")
-            .raw_str("<input type=\"hidden\" name=\"thread_id\" value=\"")
-            .number(thread_id as u32)
-            .raw_str("\" />\n")
-            .raw_str("<label>Title</label>\n")
-            .raw_str("<input type=\"text\" name=\"new_title\" value=\"")
-            .text_string(&thread.title)
-            .raw_str("\" />\n")
-            .newline()
-            .raw_str("<label>Content</label>\n")
-            .raw_str("<textarea name=\"new_body\" data-editor=\"markdown\" rows=\"10\">");
+    /// Get pinned threads for a board
+    pub fn get_pinned_threads(env: Env, board_id: u64) -> Vec<ThreadMeta> {
+        let pinned_ids: Vec<u64> = env
+            .storage()
+            .persistent()
+            .get(&BoardKey::BoardPinnedThreads(board_id))
+            .unwrap_or(Vec::new(&env));
 
-        // Include the current body content in the textarea
-        if !body.is_empty() {
-            md = md.raw(body);
+        let mut threads = Vec::new(&env);
+        for id in pinned_ids.iter() {
+            if let Some(thread) = env
+                .storage()
+                .persistent()
+                .get(&BoardKey::BoardThread(board_id, id))
+            {
+                threads.push_back(thread);
+            }
         }
+        threads
+    }
 
-        md = md
-            .raw_str("</textarea>\n")
-            .newline()
-            .raw_str("<input type=\"hidden\" name=\"caller\" value=\"")
-            .text_string(&viewer.as_ref().unwrap().to_string())
-            .raw_str("\" />\n")
-            .newline()
-            .form_link_to("Save Changes", "content", "edit_thread")
-            .newline()
-            .newline()
-            .raw_str("[Cancel](render:")
-            .raw(base_path)
-            .raw_str("/t/")
-            .number(thread_id as u32)
-            .raw_str(")");
+    /// Hide a thread (moderator action)
+    pub fn hide_thread(env: Env, board_id: u64, thread_id: u64, caller: Address) {
+        caller.require_auth();
 
-        Self::render_footer_into(env, md).build()
-    }
+        // Check moderator permissions (only if permissions contract is set)
+        if env.storage().instance().has(&BoardKey::Permissions) {
+            Self::check_can_moderate(&env, board_id, &caller);
+        }
 
-    /// Render edit reply form
-    fn render_edit_reply(
-        env: &Env,
-        board_id: u64,
-        thread_id: u64,
-        reply_id: u64,
-        viewer: &Option<Address>,
-    ) -> Bytes {
-        // Get board metadata for slug-based URLs
-        let board_meta: BoardMeta = env
+        if let Some(mut thread) = env
             .storage()
             .persistent()
-            .get(&BoardKey::Board(board_id))
-            .expect("Board not found");
+            .get::<_, ThreadMeta>(&BoardKey::BoardThread(board_id, thread_id))
+        {
+            thread.is_hidden = true;
+            Self::store_thread_meta(&env, board_id, thread_id, &thread);
+        }
+    }
 
-        // Build base path for all links
-        let base_path = Self::build_board_base_path(env, board_id, &board_meta.slug);
+    /// Unhide a thread (moderator action)
+    pub fn unhide_thread(env: Env, board_id: u64, thread_id: u64, caller: Address) {
+        caller.require_auth();
 
-        // Get board config for private board check
-        let config: BoardConfig = env
+        // Check moderator permissions (only if permissions contract is set)
+        if env.storage().instance().has(&BoardKey::Permissions) {
+            Self::check_can_moderate(&env, board_id, &caller);
+        }
+
+        if let Some(mut thread) = env
             .storage()
             .persistent()
-            .get(&BoardKey::BoardConfig(board_id))
-            .expect("Board not found");
+            .get::<_, ThreadMeta>(&BoardKey::BoardThread(board_id, thread_id))
+        {
+            thread.is_hidden = false;
+            Self::store_thread_meta(&env, board_id, thread_id, &thread);
+        }
+    }
 
-        // Get permissions contract and viewer role early for private board check
-        let perms_addr_opt = env
+    /// Set thread hidden state (called by admin contract)
+    pub fn set_thread_hidden(env: Env, board_id: u64, thread_id: u64, hidden: bool) {
+        // Note: Auth is handled by the calling admin contract
+        if let Some(mut thread) = env
             .storage()
-            .instance()
-            .get::<_, Address>(&BoardKey::Permissions);
-        let viewer_role = if let Some(ref perms_addr) = perms_addr_opt {
-            if let Some(user) = viewer {
-                let role_args: Vec<Val> =
-                    Vec::from_array(env, [board_id.into_val(env), user.into_val(env)]);
-                env.invoke_contract(perms_addr, &Symbol::new(env, "get_role"), role_args)
-            } else {
-                Role::Guest
-            }
-        } else {
-            Role::Guest
-        };
-
-        // Check permissions for private boards - must be Member+ to access
-        if config.is_private {
-            if let Some(ref perms_addr) = perms_addr_opt {
-                if (viewer_role as u32) < (Role::Member as u32) {
-                    return Self::render_private_board_message(
-                        env, board_id, &config, viewer, perms_addr,
+            .persistent()
+            .get::<_, ThreadMeta>(&BoardKey::BoardThread(board_id, thread_id))
+        {
+            // A moderator decision replaces any auto-hide; unhiding an auto-hidden
+            // thread keeps it visible until its flags are cleared
+            let auto_key = ThreadKey::ThreadAutoHidden(board_id, thread_id);
+            if env.storage().persistent().has(&auto_key) {
+                env.storage().persistent().remove(&auto_key);
+                if !hidden {
+                    env.storage().persistent().set(
+                        &ThreadKey::ThreadAutoHideSuppressed(board_id, thread_id),
+                        &true,
                     );
                 }
             }
+
+            thread.is_hidden = hidden;
+            thread.updated_at = env.ledger().timestamp();
+            Self::store_thread_meta(&env, board_id, thread_id, &thread);
         }
+    }
 
+    /// Called by the content contract when a thread is flagged.
+    /// Hides the thread once `flag_count` reaches the board's flag threshold,
+    /// unless a moderator has unhidden it since its flags were last cleared.
+    pub fn notify_thread_flagged(env: Env, board_id: u64, thread_id: u64, flag_count: u32) {
         let content: Address = env
             .storage()
             .instance()
             .get(&BoardKey::Content)
-            .expect("Content contract not configured");
-
-        let mut md = Self::render_nav(env, board_id, viewer)
-            .newline()
-            .raw_str("[< Back to Thread](render:")
-            .raw(base_path.clone())
-            .raw_str("/t/")
-            .number(thread_id as u32)
-            .raw_str(")")
-            .newline()
-            .newline()
-            .h1("Edit Reply");
-
-        // Check if board is read-only
-        if config.is_readonly {
-            md = md.warning("This board is read-only. Replies cannot be edited.");
-            return Self::render_footer_into(env, md).build();
-        }
+            .expect("Content contract not set");
+        content.require_auth();
 
-        // Check if thread is locked
-        if let Some(thread) = env
+        if env
             .storage()
             .persistent()
-            .get::<_, ThreadMeta>(&BoardKey::BoardThread(board_id, thread_id))
+            .has(&ThreadKey::ThreadAutoHideSuppressed(board_id, thread_id))
         {
-            if thread.is_locked {
-                md = md.warning("This thread is locked. Replies cannot be edited.");
-                return Self::render_footer_into(env, md).build();
-            }
+            return;
+        }
+        if flag_count < Self::get_flag_threshold(&env, board_id) {
+            return;
         }
 
-        if viewer.is_none() {
-            md = md.warning("Please connect your wallet to edit.");
-            return Self::render_footer_into(env, md).build();
+        let key = BoardKey::BoardThread(board_id, thread_id);
+        let Some(mut thread) = env.storage().persistent().get::<_, ThreadMeta>(&key) else {
+            return;
+        };
+        if thread.is_hidden || thread.is_deleted {
+            return;
         }
 
-        // Get reply metadata from content contract
-        let args: Vec<Val> = Vec::from_array(
-            env,
-            [
-                board_id.into_val(env),
-                thread_id.into_val(env),
-                reply_id.into_val(env),
-            ],
+        thread.is_hidden = true;
+        thread.updated_at = env.ledger().timestamp();
+        env.storage().persistent().set(&key, &thread);
+        env.storage()
+            .persistent()
+            .set(&ThreadKey::ThreadAutoHidden(board_id, thread_id), &true);
+
+        env.events().publish(
+            (Symbol::new(&env, "thread_auto_hidden"), board_id),
+            (thread_id, flag_count),
         );
-        let reply: Option<ReplyMeta> =
-            env.invoke_contract(&content, &Symbol::new(env, "get_reply"), args.clone());
+    }
 
-        let reply = match reply {
-            Some(r) => r,
-            None => {
-                md = md.warning("Reply not found.");
-                return Self::render_footer_into(env, md).build();
-            }
-        };
+    /// Called by the content contract when a thread's flags are cleared.
+    /// Re-enables auto-hide for a thread a moderator had unhidden.
+    pub fn reset_thread_auto_hide(env: Env, board_id: u64, thread_id: u64) {
+        let content: Address = env
+            .storage()
+            .instance()
+            .get(&BoardKey::Content)
+            .expect("Content contract not set");
+        content.require_auth();
 
-        // Check if deleted
-        if reply.is_deleted {
-            md = md.warning("This reply has been deleted and cannot be edited.");
-            return Self::render_footer_into(env, md).build();
-        }
+        env.storage()
+            .persistent()
+            .remove(&ThreadKey::ThreadAutoHideSuppressed(board_id, thread_id));
+    }
 
-        // Check edit permission
-        let (is_author, is_moderator) = Self::can_edit(env, board_id, &reply.creator, viewer);
+    /// Check whether a thread was hidden by the flag threshold rather than a moderator
+    pub fn is_thread_auto_hidden(env: Env, board_id: u64, thread_id: u64) -> bool {
+        env.storage()
+            .persistent()
+            .has(&ThreadKey::ThreadAutoHidden(board_id, thread_id))
+    }
 
-        if !is_author && !is_moderator {
-            md = md.warning("You don't have permission to edit this reply.");
-            return Self::render_footer_into(env, md).build();
-        }
+    /// Get the flag threshold from the permissions contract (default: 3)
+    fn get_flag_threshold(env: &Env, board_id: u64) -> u32 {
+        let Some(permissions) = env
+            .storage()
+            .instance()
+            .get::<_, Address>(&BoardKey::Permissions)
+        else {
+            return 3;
+        };
+        env.invoke_contract(
+            &permissions,
+            &Symbol::new(env, "get_flag_threshold"),
+            Vec::from_array(env, [board_id.into_val(env)]),
+        )
+    }
 
-        // Check edit window (only applies to non-moderators)
-        if is_author
-            && !is_moderator
-            && !Self::is_within_edit_window(env, board_id, reply.created_at)
+    /// Set thread locked state (called by admin contract)
+    pub fn set_thread_locked(env: Env, board_id: u64, thread_id: u64, locked: bool) {
+        // Note: Auth is handled by the calling admin contract
+        if let Some(mut thread) = env
+            .storage()
+            .persistent()
+            .get::<_, ThreadMeta>(&BoardKey::BoardThread(board_id, thread_id))
         {
-            md = md.warning("The edit window has expired for this reply.");
-            return Self::render_footer_into(env, md).build();
+            thread.is_locked = locked;
+            thread.updated_at = env.ledger().timestamp();
+            Self::store_thread_meta(&env, board_id, thread_id, &thread);
         }
+    }
 
-        // Get current reply content
-        let reply_content: Bytes =
-            env.invoke_contract(&content, &Symbol::new(env, "get_reply_content"), args);
+    /// Set thread pinned state (called by admin contract)
+    pub fn set_thread_pinned(env: Env, board_id: u64, thread_id: u64, pinned: bool) {
+        // Note: Auth is handled by the calling admin contract
+        if let Some(mut thread) = env
+            .storage()
+            .persistent()
+            .get::<_, ThreadMeta>(&BoardKey::BoardThread(board_id, thread_id))
+        {
+            thread.is_pinned = pinned;
+            thread.updated_at = env.ledger().timestamp();
 
-        md = md
-            .raw_str("<input type=\"hidden\" name=\"_redirect\" value=\"")
-            .raw(base_path.clone())
-            .raw_str("/t/")
-            .number(thread_id as u32)
-            .raw_str("\" />\n")
-            .raw_str("<input type=\"hidden\" name=\"board_id\" value=\"")
-            .number(board_id as u32)
-            .raw_str("\" />\n")
-            .raw_str("<input type=\"hidden\" name=\"thread_id\" value=\"")
-            .number(thread_id as u32)
-            .raw_str("\" />\n")
-            .raw_str("<input type=\"hidden\" name=\"reply_id\" value=\"")
-            .number(reply_id as u32)
-            .raw_str("\" />\n")
-            .raw_str("<label>Content</label>\n")
-            .raw_str("<textarea name=\"content\" data-editor=\"markdown\" rows=\"6\">");
+            // Update pinned list
+            let mut pinned_list: Vec<u64> = env
+                .storage()
+                .persistent()
+                .get(&BoardKey::BoardPinnedThreads(board_id))
+                .unwrap_or(Vec::new(&env));
 
-        // Include the current content in the textarea
-        if !reply_content.is_empty() {
-            md = md.raw(reply_content);
+            if pinned {
+                // Add to list if not already present
+                let mut found = false;
+                for i in 0..pinned_list.len() {
+                    if pinned_list.get(i).unwrap() == thread_id {
+                        found = true;
+                        break;
+                    }
+                }
+                if !found {
+                    pinned_list.push_back(thread_id);
+                }
+            } else {
+                // Remove from list
+                let mut new_list = Vec::new(&env);
+                for i in 0..pinned_list.len() {
+                    let id = pinned_list.get(i).unwrap();
+                    if id != thread_id {
+                        new_list.push_back(id);
+                    }
+                }
+                pinned_list = new_list;
+            }
+
+            env.storage()
+                .persistent()
+                .set(&BoardKey::BoardPinnedThreads(board_id), &pinned_list);
+            Self::store_thread_meta(&env, board_id, thread_id, &thread);
         }
+    }
 
-        md = md
-            .raw_str("</textarea>\n")
-            .newline()
-            .raw_str("<input type=\"hidden\" name=\"caller\" value=\"")
-            .text_string(&viewer.as_ref().unwrap().to_string())
-            .raw_str("\" />\n")
-            .newline()
-            .form_link_to("Save Changes", "content", "edit_reply_content")
-            .newline()
-            .newline()
-            .raw_str("[Cancel](render:")
-            .raw(base_path)
-            .raw_str("/t/")
-            .number(thread_id as u32)
-            .raw_str(")");
+    /// Delete a thread (soft delete - sets is_deleted flag)
+    /// Only author or moderator+ can delete
+    pub fn delete_thread(env: Env, board_id: u64, thread_id: u64, caller: Address) {
+        caller.require_auth();
 
-        Self::render_footer_into(env, md).build()
+        if let Some(mut thread) = env
+            .storage()
+            .persistent()
+            .get::<_, ThreadMeta>(&BoardKey::BoardThread(board_id, thread_id))
+        {
+            // Verify caller is author or moderator
+            let is_author = thread.creator == caller;
+            let is_moderator = if env.storage().instance().has(&BoardKey::Permissions) {
+                let permissions: Address = env
+                    .storage()
+                    .instance()
+                    .get(&BoardKey::Permissions)
+                    .unwrap();
+                let args: Vec<Val> =
+                    Vec::from_array(&env, [board_id.into_val(&env), caller.into_val(&env)]);
+                let fn_name = Symbol::new(&env, "can_moderate");
+                env.invoke_contract(&permissions, &fn_name, args)
+            } else {
+                false
+            };
+
+            if !is_author && !is_moderator {
+                panic!("Only author or moderator can delete thread");
+            }
+
+            thread.is_deleted = true;
+            thread.updated_at = env.ledger().timestamp();
+            Self::store_thread_meta(&env, board_id, thread_id, &thread);
+        }
     }
 
-    /// Get CSS from Theme contract
-    pub fn styles(env: Env) -> Bytes {
-        let theme: Address = env
+    /// Soft-delete a removed crosspost thread (content contract only; it checks
+    /// that the caller is the crossposter, original author or a moderator)
+    pub fn remove_crosspost_thread(env: Env, board_id: u64, thread_id: u64) {
+        let content: Address = env
             .storage()
             .instance()
-            .get(&BoardKey::Theme)
-            .expect("Theme contract not configured");
+            .get(&BoardKey::Content)
+            .expect("Content contract not set");
+        content.require_auth();
 
-        env.invoke_contract(&theme, &Symbol::new(&env, "styles"), Vec::new(&env))
+        let key = BoardKey::BoardThread(board_id, thread_id);
+        if let Some(mut thread) = env.storage().persistent().get::<_, ThreadMeta>(&key) {
+            thread.is_deleted = true;
+            thread.updated_at = env.ledger().timestamp();
+            env.storage().persistent().set(&key, &thread);
+        }
     }
 
-    /// Upgrade the contract WASM
-    pub fn upgrade(env: Env, new_wasm_hash: BytesN<32>) {
-        let registry: Address = env
+    /// Edit thread title (author or moderator)
+    pub fn edit_thread_title(
+        env: Env,
+        board_id: u64,
+        thread_id: u64,
+        new_title: String,
+        caller: Address,
+    ) {
+        caller.require_auth();
+
+        if let Some(mut thread) = env
             .storage()
-            .instance()
-            .get(&BoardKey::Registry)
-            .expect("Not initialized");
-        registry.require_auth();
+            .persistent()
+            .get::<_, ThreadMeta>(&BoardKey::BoardThread(board_id, thread_id))
+        {
+            // Verify caller is author or moderator
+            let is_author = thread.creator == caller;
+            let is_moderator = if env.storage().instance().has(&BoardKey::Permissions) {
+                let permissions: Address = env
+                    .storage()
+                    .instance()
+                    .get(&BoardKey::Permissions)
+                    .unwrap();
+                let args: Vec<Val> =
+                    Vec::from_array(&env, [board_id.into_val(&env), caller.into_val(&env)]);
+                let fn_name = Symbol::new(&env, "can_moderate");
+                env.invoke_contract(&permissions, &fn_name, args)
+            } else {
+                false
+            };
 
-        env.deployer().update_current_contract_wasm(new_wasm_hash);
-    }
+            if !is_author && !is_moderator {
+                panic!("Only author or moderator can edit title");
+            }
+            Self::check_blocked_title(&env, board_id, &new_title);
 
-    /// Get profile contract from registry (if available)
-    fn get_profile_contract(env: &Env) -> Option<Address> {
-        let registry: Address = env.storage().instance().get(&BoardKey::Registry)?;
+            thread.title = new_title;
+            thread.updated_at = env.ledger().timestamp();
+            Self::store_thread_meta(&env, board_id, thread_id, &thread);
+        }
+    }
 
-        // Look up profile contract from registry using get_contract
-        let args: Vec<Val> = Vec::from_array(env, [Symbol::new(env, "profile").into_val(env)]);
-        env.invoke_contract(&registry, &Symbol::new(env, "get_contract"), args)
+    /// Increment reply count for a thread (called by content contract)
+    pub fn increment_reply_count(env: Env, board_id: u64, thread_id: u64) {
+        if let Some(mut thread) = env
+            .storage()
+            .persistent()
+            .get::<_, ThreadMeta>(&BoardKey::BoardThread(board_id, thread_id))
+        {
+            thread.reply_count += 1;
+            thread.updated_at = env.ledger().timestamp();
+            Self::store_thread_meta(&env, board_id, thread_id, &thread);
+            Self::report_activity(&env, board_id);
+            Self::record_daily_stat(&env, board_id, false);
+        }
     }
 
-    /// Fetch `{{aliases ...}}` tag from registry via cross-contract call.
-    ///
-    /// This enables includes using aliases like `{{include contract=config func="..."}}`
-    /// in rendered content. Call this early in render functions and prepend to output.
-    fn fetch_aliases(env: &Env) -> Bytes {
-        let registry_opt: Option<Address> = env.storage().instance().get(&BoardKey::Registry);
+    /// Count a new thread or reply in today's stats bucket
+    fn record_daily_stat(env: &Env, board_id: u64, is_thread: bool) {
+        let key = BoardKey::DailyStats(board_id, env.ledger().timestamp() / 86_400);
+        let mut stats: DayStats = env.storage().persistent().get(&key).unwrap_or_default();
+        if is_thread {
+            stats.threads = stats.threads.saturating_add(1);
+        } else {
+            stats.replies = stats.replies.saturating_add(1);
+        }
+        env.storage().persistent().set(&key, &stats);
+    }
 
-        let Some(registry) = registry_opt else {
-            return Bytes::new(env);
-        };
+    /// Per-day thread and reply counts for a board, one entry per day from
+    /// `from_day` through `to_day` (days since the Unix epoch). The range stops at
+    /// today and is cut to its most recent 90 days; an empty range returns nothing.
+    pub fn get_stats_range(env: Env, board_id: u64, from_day: u64, to_day: u64) -> Vec<DayStats> {
+        let today = env.ledger().timestamp() / 86_400;
+        let to_day = to_day.min(today);
+        let from_day = from_day.max((to_day + 1).saturating_sub(MAX_STATS_RANGE_DAYS));
+
+        let mut days = Vec::new(&env);
+        if from_day > to_day {
+            return days;
+        }
+        for day in from_day..=to_day {
+            let stats: DayStats = env
+                .storage()
+                .persistent()
+                .get(&BoardKey::DailyStats(board_id, day))
+                .unwrap_or_default();
+            days.push_back(stats);
+        }
+        days
+    }
 
-        // Call registry's render_aliases function
-        let args: Vec<Val> = Vec::new(env);
-        env.try_invoke_contract::<Bytes, soroban_sdk::Error>(
-            &registry,
-            &Symbol::new(env, "render_aliases"),
-            args,
-        )
-        .ok()
-        .and_then(|r| r.ok())
-        .unwrap_or(Bytes::new(env))
+    /// Store a thread's metadata and keep it from being archived
+    fn store_thread_meta(env: &Env, board_id: u64, thread_id: u64, thread: &ThreadMeta) {
+        let key = BoardKey::BoardThread(board_id, thread_id);
+        env.storage().persistent().set(&key, thread);
+        env.storage()
+            .persistent()
+            .extend_ttl(&key, TTL_THRESHOLD, TTL_EXTEND_TO);
     }
 
-    /// Get the community this board belongs to (if any)
-    /// Uses try_invoke to handle potential re-entrant call issues gracefully
-    fn get_board_community(env: &Env, board_id: u64) -> Option<CommunityInfo> {
-        // Get community contract address directly from storage
-        let community_contract: Address = env.storage().instance().get(&BoardKey::Community)?;
+    /// Extend a persistent entry's TTL if it exists
+    fn bump_ttl<K: IntoVal<Env, Val>>(env: &Env, key: &K) {
+        if env.storage().persistent().has(key) {
+            env.storage()
+                .persistent()
+                .extend_ttl(key, TTL_THRESHOLD, TTL_EXTEND_TO);
+        }
+    }
 
-        // Query community contract for board's community ID
-        // Use try_invoke to gracefully handle any issues
-        let args: Vec<Val> = Vec::from_array(env, [board_id.into_val(env)]);
-        let community_id_result = env.try_invoke_contract::<Option<u64>, soroban_sdk::Error>(
-            &community_contract,
-            &Symbol::new(env, "get_board_community"),
+    /// Extend the TTL of a thread's metadata and subscriber count
+    fn bump_thread_ttl(env: &Env, board_id: u64, thread_id: u64) {
+        Self::bump_ttl(env, &BoardKey::BoardThread(board_id, thread_id));
+        Self::bump_ttl(env, &ThreadKey::Subscribers(board_id, thread_id));
+        Self::bump_ttl(env, &ThreadKey::SubscriberCount(board_id, thread_id));
+    }
+
+    /// Keep a thread from being archived. Anyone may call this (they pay the fee).
+    pub fn extend_thread_ttl(env: Env, board_id: u64, thread_id: u64) {
+        if !env
+            .storage()
+            .persistent()
+            .has(&BoardKey::BoardThread(board_id, thread_id))
+        {
+            panic!("Thread not found");
+        }
+        Self::bump_thread_ttl(&env, board_id, thread_id);
+    }
+
+    /// Maintenance pass that keeps a board from being archived: its metadata,
+    /// settings, flairs and rules, plus up to 50 threads from `start`. Anyone may
+    /// call this. Returns the thread ID to continue from.
+    pub fn extend_board_ttl(env: Env, board_id: u64, start: u64, limit: u64) -> u64 {
+        env.storage()
+            .instance()
+            .extend_ttl(TTL_THRESHOLD, TTL_EXTEND_TO);
+        for key in [
+            BoardKey::Board(board_id),
+            BoardKey::BoardConfig(board_id),
+            BoardKey::BoardThreadCount(board_id),
+            BoardKey::BoardPinnedThreads(board_id),
+            BoardKey::BoardFlairDefs(board_id),
+            BoardKey::BoardRules(board_id),
+            BoardKey::BoardListed(board_id),
+            BoardKey::BoardCreator(board_id),
+            BoardKey::BoardCreatedAt(board_id),
+            BoardKey::BoardEditWindow(board_id),
+        ] {
+            Self::bump_ttl(&env, &key);
+        }
+
+        let thread_count: u64 = env
+            .storage()
+            .persistent()
+            .get(&BoardKey::BoardThreadCount(board_id))
+            .unwrap_or(0);
+        let end = start
+            .saturating_add(limit.min(MAX_TTL_BATCH))
+            .min(thread_count);
+        for thread_id in start..end {
+            Self::bump_thread_ttl(&env, board_id, thread_id);
+        }
+        end.max(start)
+    }
+
+    /// Tell the registry this board just had a new thread or reply, so the
+    /// board index can show when it was last active. Failures are ignored.
+    fn report_activity(env: &Env, board_id: u64) {
+        let Some(registry) = env
+            .storage()
+            .instance()
+            .get::<_, Address>(&BoardKey::Registry)
+        else {
+            return;
+        };
+        let _ = env.try_invoke_contract::<(), soroban_sdk::Error>(
+            &registry,
+            &Symbol::new(env, "report_board_activity"),
+            Vec::from_array(env, [board_id.into_val(env)]),
+        );
+    }
+
+    /// Add a new thread to the registry's recent activity feed. Failures are
+    /// ignored so a registry problem never blocks posting.
+    fn report_thread_activity(env: &Env, thread: &ThreadMeta) {
+        let Some(registry) = env
+            .storage()
+            .instance()
+            .get::<_, Address>(&BoardKey::Registry)
+        else {
+            return;
+        };
+        let args: Vec<Val> = Vec::from_array(
+            env,
+            [
+                thread.board_id.into_val(env),
+                thread.id.into_val(env),
+                thread.title.into_val(env),
+                thread.created_at.into_val(env),
+            ],
+        );
+        let _ = env.try_invoke_contract::<(), soroban_sdk::Error>(
+            &registry,
+            &Symbol::new(env, "report_activity"),
             args,
         );
+    }
 
-        // Unwrap the nested results/options
-        let community_id = match community_id_result {
-            Ok(Ok(Some(id))) => id,
-            _ => return None,
+    /// Check if a thread is locked
+    pub fn is_thread_locked(env: Env, board_id: u64, thread_id: u64) -> bool {
+        env.storage()
+            .persistent()
+            .get::<_, ThreadMeta>(&BoardKey::BoardThread(board_id, thread_id))
+            .map(|t| t.is_locked)
+            .unwrap_or(false)
+    }
+
+    /// Get the reason a thread would reject a new reply, if any.
+    /// Folds together board and thread state so the content contract can make
+    /// a single call before persisting a reply. Returns one of:
+    /// `read_only` (board is read-only/archived), `locked`, `hidden`, `deleted`,
+    /// or `not_found`. Returns None if the thread accepts replies.
+    pub fn reply_rejection_reason(env: Env, board_id: u64, thread_id: u64) -> Option<Symbol> {
+        if Self::is_readonly(env.clone(), board_id) {
+            return Some(Symbol::new(&env, "read_only"));
+        }
+
+        let Some(thread) = env
+            .storage()
+            .persistent()
+            .get::<_, ThreadMeta>(&BoardKey::BoardThread(board_id, thread_id))
+        else {
+            return Some(Symbol::new(&env, "not_found"));
         };
 
-        // Fetch community metadata
-        let meta_args: Vec<Val> = Vec::from_array(env, [community_id.into_val(env)]);
-        let community_meta_result = env
-            .try_invoke_contract::<Option<CommunityInfo>, soroban_sdk::Error>(
-                &community_contract,
-                &Symbol::new(env, "get_community_info"),
-                meta_args,
-            );
+        if thread.is_deleted {
+            Some(Symbol::new(&env, "deleted"))
+        } else if Self::replies_closed(&env, board_id, &thread) {
+            Some(Symbol::new(&env, "locked"))
+        } else if thread.is_hidden {
+            Some(Symbol::new(&env, "hidden"))
+        } else {
+            None
+        }
+    }
 
-        match community_meta_result {
-            Ok(Ok(Some(meta))) => Some(meta),
-            _ => None,
+    /// Check if a thread currently accepts new replies
+    pub fn can_accept_reply(env: Env, board_id: u64, thread_id: u64) -> bool {
+        Self::reply_rejection_reason(env, board_id, thread_id).is_none()
+    }
+
+    /// Check if a thread accepts new votes and vote changes (checked by the voting contract).
+    /// Locked, hidden, deleted and missing threads, and threads on archived boards, don't.
+    pub fn is_thread_votable(env: Env, board_id: u64, thread_id: u64) -> bool {
+        if Self::is_archived(env.clone(), board_id) {
+            return false;
         }
+        env.storage()
+            .persistent()
+            .get::<_, ThreadMeta>(&BoardKey::BoardThread(board_id, thread_id))
+            .map(|thread| !thread.is_locked && !thread.is_hidden && !thread.is_deleted)
+            .unwrap_or(false)
     }
 
-    /// Render author info (username link or truncated address)
-    ///
-    /// When return_path is provided, clicking the author link and then "Go Back"
-    /// on the profile page will return to that path.
-    fn render_author<'a>(
-        env: &Env,
-        md: MarkdownBuilder<'a>,
-        creator: &Address,
-        profile_contract: &Option<Address>,
-        return_path: Option<Bytes>,
-    ) -> MarkdownBuilder<'a> {
-        match profile_contract {
-            Some(profile_addr) => {
-                // Use the return path variant if we have a return path
-                let rendered: Bytes = match return_path {
-                    Some(path) => {
-                        let args: Vec<Val> =
-                            Vec::from_array(env, [creator.into_val(env), path.into_val(env)]);
-                        env.invoke_contract(
-                            profile_addr,
-                            &Symbol::new(env, "render_profile_compact_return"),
-                            args,
-                        )
-                    }
-                    None => {
-                        let args: Vec<Val> = Vec::from_array(env, [creator.into_val(env)]);
-                        env.invoke_contract(
-                            profile_addr,
-                            &Symbol::new(env, "render_profile_card_compact"),
-                            args,
-                        )
-                    }
-                };
-                md.raw(rendered)
-            }
-            None => {
-                // Fallback: show truncated address
-                md.raw_str("<span class=\"author\">")
-                    .raw(Self::truncate_address(env, creator))
-                    .raw_str("</span>")
-            }
+    // ========================================================================
+    // Thread subscriptions
+    // ========================================================================
+
+    /// Subscribe a user to a thread
+    pub fn subscribe(env: Env, board_id: u64, thread_id: u64, user: Address) {
+        user.require_auth();
+
+        if !env
+            .storage()
+            .persistent()
+            .has(&BoardKey::BoardThread(board_id, thread_id))
+        {
+            panic!("Thread not found");
+        }
+
+        let sub_key = UserKey::Subscriptions(board_id, user.clone());
+        let mut subscriptions: Vec<u64> = env
+            .storage()
+            .persistent()
+            .get(&sub_key)
+            .unwrap_or(Vec::new(&env));
+        if subscriptions.contains(thread_id) {
+            return;
+        }
+        if subscriptions.len() >= MAX_SUBSCRIPTIONS {
+            panic!("Subscription limit reached");
         }
+        subscriptions.push_back(thread_id);
+        env.storage().persistent().set(&sub_key, &subscriptions);
+
+        // Reverse index for notification fan-out
+        Self::migrate_subscribers(&env, board_id, thread_id);
+        Self::add_subscriber(&env, board_id, thread_id, &user);
     }
 
-    /// Build the base path for a board (e.g., "/b/{slug}" or "/c/{community}/b/{slug}")
-    ///
-    /// For community boards: /c/{community_slug}/b/{board_slug}
-    /// For standalone boards: /b/{board_slug}
-    ///
-    /// First checks temp storage for community slug (passed during render to avoid re-entrant calls).
-    /// Falls back to querying community contract if not in temp storage.
-    fn build_board_base_path(env: &Env, _board_id: u64, board_slug: &String) -> Bytes {
-        // First check temp storage for community slug (set during render)
-        let community_slug_opt: Option<String> = env
+    /// Unsubscribe a user from a thread (no-op if not subscribed)
+    pub fn unsubscribe(env: Env, board_id: u64, thread_id: u64, user: Address) {
+        user.require_auth();
+
+        let sub_key = UserKey::Subscriptions(board_id, user.clone());
+        let mut subscriptions: Vec<u64> = env
             .storage()
-            .temporary()
-            .get(&BoardKey::RenderCommunitySlug);
+            .persistent()
+            .get(&sub_key)
+            .unwrap_or(Vec::new(&env));
+        let Some(idx) = subscriptions.first_index_of(thread_id) else {
+            return;
+        };
+        subscriptions.remove(idx);
+        env.storage().persistent().set(&sub_key, &subscriptions);
 
-        if let Some(community_slug) = community_slug_opt {
-            // Community board: /c/{community_slug}/b/{board_slug}
-            let mut path = Bytes::from_slice(env, b"/c/");
-            path.append(&soroban_render_sdk::bytes::string_to_bytes(
-                env,
-                &community_slug,
-            ));
-            path.append(&Bytes::from_slice(env, b"/b/"));
-            path.append(&soroban_render_sdk::bytes::string_to_bytes(env, board_slug));
-            path
+        Self::migrate_subscribers(&env, board_id, thread_id);
+        Self::remove_subscriber(&env, board_id, thread_id, &user);
+    }
+
+    /// Move a thread's pre-chunking subscriber list into the chunked index
+    fn migrate_subscribers(env: &Env, board_id: u64, thread_id: u64) {
+        let legacy_key = ThreadKey::Subscribers(board_id, thread_id);
+        let Some(subscribers) = env
+            .storage()
+            .persistent()
+            .get::<_, Vec<Address>>(&legacy_key)
+        else {
+            return;
+        };
+        env.storage().persistent().remove(&legacy_key);
+        for user in subscribers.iter() {
+            Self::add_subscriber(env, board_id, thread_id, &user);
+        }
+    }
+
+    /// Append a user to a thread's subscriber chunks (no-op if already there)
+    fn add_subscriber(env: &Env, board_id: u64, thread_id: u64, user: &Address) {
+        let slot_key = ThreadKey::SubscriberSlot(board_id, thread_id, user.clone());
+        if env.storage().persistent().has(&slot_key) {
+            return;
+        }
+        let count_key = ThreadKey::SubscriberCount(board_id, thread_id);
+        let count: u32 = env.storage().persistent().get(&count_key).unwrap_or(0);
+        let chunk_key = ThreadKey::SubscriberChunk(board_id, thread_id, count / SUBSCRIBER_CHUNK);
+        let mut chunk: Vec<Address> = env
+            .storage()
+            .persistent()
+            .get(&chunk_key)
+            .unwrap_or(Vec::new(env));
+        chunk.push_back(user.clone());
+        env.storage().persistent().set(&chunk_key, &chunk);
+        env.storage().persistent().set(&slot_key, &count);
+        env.storage().persistent().set(&count_key, &(count + 1));
+    }
+
+    /// Remove a user from a thread's subscriber chunks by moving the last
+    /// subscriber into their slot, so only two chunks are touched
+    fn remove_subscriber(env: &Env, board_id: u64, thread_id: u64, user: &Address) {
+        let slot_key = ThreadKey::SubscriberSlot(board_id, thread_id, user.clone());
+        let Some(slot) = env.storage().persistent().get::<_, u32>(&slot_key) else {
+            return;
+        };
+        env.storage().persistent().remove(&slot_key);
+
+        // Every slot is counted, so the count is at least one here
+        let count_key = ThreadKey::SubscriberCount(board_id, thread_id);
+        let count: u32 = env.storage().persistent().get(&count_key).unwrap_or(1);
+        let last = count - 1;
+        let last_key = ThreadKey::SubscriberChunk(board_id, thread_id, last / SUBSCRIBER_CHUNK);
+        let mut last_chunk: Vec<Address> = env
+            .storage()
+            .persistent()
+            .get(&last_key)
+            .unwrap_or(Vec::new(env));
+        let moved = last_chunk.pop_back();
+
+        if slot != last {
+            if let Some(moved) = moved {
+                if slot / SUBSCRIBER_CHUNK == last / SUBSCRIBER_CHUNK {
+                    last_chunk.set(slot % SUBSCRIBER_CHUNK, moved.clone());
+                } else {
+                    let chunk_key =
+                        ThreadKey::SubscriberChunk(board_id, thread_id, slot / SUBSCRIBER_CHUNK);
+                    let mut chunk: Vec<Address> = env
+                        .storage()
+                        .persistent()
+                        .get(&chunk_key)
+                        .unwrap_or(Vec::new(env));
+                    chunk.set(slot % SUBSCRIBER_CHUNK, moved.clone());
+                    env.storage().persistent().set(&chunk_key, &chunk);
+                }
+                env.storage().persistent().set(
+                    &ThreadKey::SubscriberSlot(board_id, thread_id, moved),
+                    &slot,
+                );
+            }
+        }
+
+        if last_chunk.is_empty() {
+            env.storage().persistent().remove(&last_key);
         } else {
-            // Standalone board: /b/{board_slug}
-            let mut path = Bytes::from_slice(env, b"/b/");
-            path.append(&soroban_render_sdk::bytes::string_to_bytes(env, board_slug));
-            path
+            env.storage().persistent().set(&last_key, &last_chunk);
+        }
+        if last == 0 {
+            env.storage().persistent().remove(&count_key);
+        } else {
+            env.storage().persistent().set(&count_key, &last);
+        }
+    }
+
+    /// Check if a user is subscribed to a thread
+    pub fn is_subscribed(env: Env, board_id: u64, thread_id: u64, user: Address) -> bool {
+        env.storage()
+            .persistent()
+            .get::<_, Vec<u64>>(&UserKey::Subscriptions(board_id, user))
+            .map(|subs| subs.contains(thread_id))
+            .unwrap_or(false)
+    }
+
+    /// List threads a user is subscribed to (deleted threads are skipped)
+    pub fn list_subscriptions(
+        env: Env,
+        board_id: u64,
+        user: Address,
+        start: u64,
+        limit: u64,
+    ) -> Vec<ThreadMeta> {
+        let subscriptions: Vec<u64> = env
+            .storage()
+            .persistent()
+            .get(&UserKey::Subscriptions(board_id, user))
+            .unwrap_or(Vec::new(&env));
+
+        let mut threads = Vec::new(&env);
+        let mut skipped = 0u64;
+
+        for thread_id in subscriptions.iter() {
+            if threads.len() as u64 >= limit {
+                break;
+            }
+            if let Some(thread) = env
+                .storage()
+                .persistent()
+                .get::<_, ThreadMeta>(&BoardKey::BoardThread(board_id, thread_id))
+            {
+                if thread.is_deleted {
+                    continue;
+                }
+                if skipped < start {
+                    skipped += 1;
+                    continue;
+                }
+                threads.push_back(thread);
+            }
         }
+
+        threads
+    }
+
+    /// Number of users subscribed to a thread
+    pub fn get_subscriber_count(env: Env, board_id: u64, thread_id: u64) -> u32 {
+        if let Some(legacy) = env
+            .storage()
+            .persistent()
+            .get::<_, Vec<Address>>(&ThreadKey::Subscribers(board_id, thread_id))
+        {
+            return legacy.len();
+        }
+        env.storage()
+            .persistent()
+            .get(&ThreadKey::SubscriberCount(board_id, thread_id))
+            .unwrap_or(0)
+    }
+
+    /// Users subscribed to a thread, `limit` from position `start`, in no
+    /// particular order
+    pub fn get_subscribers(
+        env: Env,
+        board_id: u64,
+        thread_id: u64,
+        start: u32,
+        limit: u32,
+    ) -> Vec<Address> {
+        if let Some(legacy) = env
+            .storage()
+            .persistent()
+            .get::<_, Vec<Address>>(&ThreadKey::Subscribers(board_id, thread_id))
+        {
+            let end = start.saturating_add(limit).min(legacy.len());
+            if start >= end {
+                return Vec::new(&env);
+            }
+            return legacy.slice(start..end);
+        }
+
+        let count = Self::get_subscriber_count(env.clone(), board_id, thread_id);
+        let end = start.saturating_add(limit).min(count);
+        let mut subscribers = Vec::new(&env);
+        let mut pos = start;
+        while pos < end {
+            let chunk: Vec<Address> = env
+                .storage()
+                .persistent()
+                .get(&ThreadKey::SubscriberChunk(
+                    board_id,
+                    thread_id,
+                    pos / SUBSCRIBER_CHUNK,
+                ))
+                .unwrap_or(Vec::new(&env));
+            let chunk_end = (pos / SUBSCRIBER_CHUNK + 1) * SUBSCRIBER_CHUNK;
+            for user in chunk
+                .iter()
+                .skip((pos % SUBSCRIBER_CHUNK) as usize)
+                .take((chunk_end.min(end) - pos) as usize)
+            {
+                subscribers.push_back(user);
+            }
+            pos = chunk_end;
+        }
+        subscribers
+    }
+
+    // ========================================================================
+    // Saved threads
+    // ========================================================================
+
+    /// Save a thread for later (no-op if already saved). Unlike subscribing,
+    /// saving never triggers notifications.
+    pub fn save_thread(env: Env, board_id: u64, thread_id: u64, user: Address) {
+        user.require_auth();
+
+        if !env
+            .storage()
+            .persistent()
+            .has(&BoardKey::BoardThread(board_id, thread_id))
+        {
+            panic!("Thread not found");
+        }
+
+        let key = UserKey::SavedThreads(user);
+        let mut saved: Vec<(u64, u64)> = env
+            .storage()
+            .persistent()
+            .get(&key)
+            .unwrap_or(Vec::new(&env));
+        if saved.contains((board_id, thread_id)) {
+            return;
+        }
+        if saved.len() >= MAX_SAVED_THREADS {
+            panic!("Saved threads limit reached");
+        }
+        saved.push_front((board_id, thread_id));
+        env.storage().persistent().set(&key, &saved);
+    }
+
+    /// Remove a thread from a user's saved list (no-op if not saved). Works for
+    /// threads that have since been deleted.
+    pub fn unsave_thread(env: Env, board_id: u64, thread_id: u64, user: Address) {
+        user.require_auth();
+
+        let key = UserKey::SavedThreads(user);
+        let mut saved: Vec<(u64, u64)> = env
+            .storage()
+            .persistent()
+            .get(&key)
+            .unwrap_or(Vec::new(&env));
+        let Some(index) = saved.first_index_of((board_id, thread_id)) else {
+            return;
+        };
+        saved.remove(index);
+        if saved.is_empty() {
+            env.storage().persistent().remove(&key);
+        } else {
+            env.storage().persistent().set(&key, &saved);
+        }
+    }
+
+    /// List a user's saved threads as (board_id, thread_id), newest first.
+    /// Only the user can list their saves.
+    pub fn list_saved(env: Env, user: Address, start: u32, limit: u32) -> Vec<(u64, u64)> {
+        user.require_auth();
+
+        let saved: Vec<(u64, u64)> = env
+            .storage()
+            .persistent()
+            .get(&UserKey::SavedThreads(user))
+            .unwrap_or(Vec::new(&env));
+        let end = start.saturating_add(limit).min(saved.len());
+        if start >= end {
+            return Vec::new(&env);
+        }
+        saved.slice(start..end)
+    }
+
+    /// The viewer's saved threads, or None for guests (who get no save toggles)
+    fn saved_for(env: &Env, viewer: &Option<Address>) -> Option<Vec<(u64, u64)>> {
+        let user = viewer.as_ref()?;
+        Some(
+            env.storage()
+                .persistent()
+                .get(&UserKey::SavedThreads(user.clone()))
+                .unwrap_or(Vec::new(env)),
+        )
+    }
+
+    // ========================================================================
+    // Read tracking
+    // ========================================================================
+
+    /// Mark everything on a board as read for the user. Clears their per-thread
+    /// marks, which the board-wide mark now covers.
+    pub fn mark_board_read(env: Env, board_id: u64, user: Address) {
+        user.require_auth();
+
+        if !env.storage().persistent().has(&BoardKey::Board(board_id)) {
+            panic!("Board not found");
+        }
+        let marks = ReadMarks {
+            board_read_at: env.ledger().timestamp(),
+            threads: Vec::new(&env),
+        };
+        env.storage()
+            .persistent()
+            .set(&UserKey::ReadMarks(board_id, user), &marks);
+    }
+
+    /// Mark one thread as read for the user. Only the MAX_READ_THREADS most
+    /// recently read threads are remembered; older ones fall back to the board-wide mark.
+    pub fn mark_thread_read(env: Env, board_id: u64, thread_id: u64, user: Address) {
+        user.require_auth();
+
+        if !env
+            .storage()
+            .persistent()
+            .has(&BoardKey::BoardThread(board_id, thread_id))
+        {
+            panic!("Thread not found");
+        }
+
+        let key = UserKey::ReadMarks(board_id, user);
+        let mut marks: ReadMarks = env.storage().persistent().get(&key).unwrap_or(ReadMarks {
+            board_read_at: 0,
+            threads: Vec::new(&env),
+        });
+        if let Some(index) = marks.threads.iter().position(|(id, _)| id == thread_id) {
+            marks.threads.remove(index as u32);
+        }
+        marks
+            .threads
+            .push_front((thread_id, env.ledger().timestamp()));
+        while marks.threads.len() > MAX_READ_THREADS {
+            marks.threads.pop_back();
+        }
+        env.storage().persistent().set(&key, &marks);
+    }
+
+    /// Get what a user has read on a board (None until they first mark something read)
+    pub fn get_read_marks(env: Env, board_id: u64, user: Address) -> Option<ReadMarks> {
+        env.storage()
+            .persistent()
+            .get(&UserKey::ReadMarks(board_id, user))
+    }
+
+    /// The viewer's read marks, or None for guests and viewers not tracking this board
+    fn read_marks_for(env: &Env, board_id: u64, viewer: &Option<Address>) -> Option<ReadMarks> {
+        let user = viewer.as_ref()?;
+        Self::get_read_marks(env.clone(), board_id, user.clone())
+    }
+
+    /// Whether a thread changed after the viewer last read it
+    fn is_unread(marks: &ReadMarks, thread: &ThreadMeta) -> bool {
+        let thread_read_at = marks
+            .threads
+            .iter()
+            .find(|(id, _)| *id == thread.id)
+            .map_or(0, |(_, read_at)| read_at);
+        thread.updated_at > marks.board_read_at.max(thread_read_at)
+    }
+
+    /// Count unread threads among the board's newest MAX_FILTER_SCAN
+    fn count_unread_threads(
+        env: &Env,
+        board_id: u64,
+        marks: &ReadMarks,
+        viewer: &Option<Address>,
+        viewer_can_moderate: bool,
+    ) -> u32 {
+        let thread_count: u64 = env
+            .storage()
+            .persistent()
+            .get(&BoardKey::BoardThreadCount(board_id))
+            .unwrap_or(0);
+        let mut unread = 0;
+        for thread_id in thread_count.saturating_sub(MAX_FILTER_SCAN)..thread_count {
+            let Some(thread) = env
+                .storage()
+                .persistent()
+                .get::<_, ThreadMeta>(&BoardKey::BoardThread(board_id, thread_id))
+            else {
+                continue;
+            };
+            if thread.is_deleted
+                || (thread.is_hidden
+                    && !viewer_can_moderate
+                    && viewer.as_ref() != Some(&thread.creator))
+            {
+                continue;
+            }
+            if Self::is_unread(marks, &thread) {
+                unread += 1;
+            }
+        }
+        unread
+    }
+
+    // ========================================================================
+    // Rendering - Board, thread, and reply views
+    // ========================================================================
+
+    /// Main render entry point for board routes
+    /// Routes are relative to the board (e.g., "/" = board view, "/t/0" = thread 0)
+    /// board_id is now passed as a parameter
+    /// community_slug: If board is in a community, pass the community's URL slug here
+    ///                 to enable proper path building without re-entrant calls
+    pub fn render(
+        env: Env,
+        board_id: u64,
+        path: Option<String>,
+        viewer: Option<Address>,
+        community_slug: Option<String>,
+    ) -> Bytes {
+        // Rendering is the most frequent entry point, so it keeps the contract alive
+        env.storage()
+            .instance()
+            .extend_ttl(TTL_THRESHOLD, TTL_EXTEND_TO);
+
+        // Store community_slug in temp storage for use during this render
+        // This avoids re-entrant calls back to community contract
+        if let Some(ref slug) = community_slug {
+            env.storage()
+                .temporary()
+                .set(&BoardKey::RenderCommunitySlug, slug);
+        } else {
+            // Clear any stale value
+            env.storage()
+                .temporary()
+                .remove(&BoardKey::RenderCommunitySlug);
+        }
+
+        Router::new(&env, path.clone())
+            // Board view (thread list)
+            .handle(b"/", |req| {
+                let sort = req.get_query_param(b"sort");
+                Self::render_board(&env, board_id, &viewer, sort)
+            })
+            // Create thread form
+            .or_handle(b"/new/{template_id}", |req| {
+                let template_id = req.get_var_u32(b"template_id");
+                Self::render_create_thread(&env, board_id, template_id, &viewer)
+            })
+            .or_handle(b"/new", |_| {
+                Self::render_create_thread(&env, board_id, None, &viewer)
+            })
+            // Thread reply form (must be before thread view)
+            .or_handle(b"/t/{tid}/reply", |req| {
+                let thread_id = req.get_var_u32(b"tid").unwrap_or(0) as u64;
+                Self::render_reply_form(&env, board_id, thread_id, None, false, &viewer)
+            })
+            // Thread reply form prefilled with the quoted thread body
+            .or_handle(b"/t/{tid}/quote", |req| {
+                let thread_id = req.get_var_u32(b"tid").unwrap_or(0) as u64;
+                Self::render_reply_form(&env, board_id, thread_id, None, true, &viewer)
+            })
+            // Load top-level replies batch (waterfall loading)
+            .or_handle(b"/t/{tid}/replies/{start}", |req| {
+                let thread_id = req.get_var_u32(b"tid").unwrap_or(0) as u64;
+                let start = req.get_var_u32(b"start").unwrap_or(0);
+                Self::render_replies_batch(
+                    &env,
+                    board_id,
+                    thread_id,
+                    start,
+                    None,
+                    ReplySort::Oldest,
+                    &viewer,
+                )
+            })
+            // The last segment is a continuation's viewer ctx or a sort name
+            .or_handle(b"/t/{tid}/replies/{start}/{ctx}", |req| {
+                let thread_id = req.get_var_u32(b"tid").unwrap_or(0) as u64;
+                let start = req.get_var_u32(b"start").unwrap_or(0);
+                let ctx = req.get_var_u32(b"ctx");
+                let sort = match ctx {
+                    Some(_) => None,
+                    None => req.get_var(b"ctx").and_then(|p| Self::parse_reply_sort(&p)),
+                };
+                Self::render_replies_batch(
+                    &env,
+                    board_id,
+                    thread_id,
+                    start,
+                    ctx,
+                    sort.unwrap_or(ReplySort::Oldest),
+                    &viewer,
+                )
+            })
+            .or_handle(b"/t/{tid}/replies/{start}/{ctx}/{sort}", |req| {
+                let thread_id = req.get_var_u32(b"tid").unwrap_or(0) as u64;
+                let start = req.get_var_u32(b"start").unwrap_or(0);
+                let ctx = req.get_var_u32(b"ctx");
+                let sort = req
+                    .get_var(b"sort")
+                    .and_then(|p| Self::parse_reply_sort(&p))
+                    .unwrap_or(ReplySort::Oldest);
+                Self::render_replies_batch(&env, board_id, thread_id, start, ctx, sort, &viewer)
+            })
+            // Load children of a reply batch
+            .or_handle(b"/t/{tid}/r/{rid}/children/{start}", |req| {
+                let thread_id = req.get_var_u32(b"tid").unwrap_or(0) as u64;
+                let reply_id = req.get_var_u32(b"rid").unwrap_or(0) as u64;
+                let start = req.get_var_u32(b"start").unwrap_or(0);
+                Self::render_children_batch(
+                    &env, board_id, thread_id, reply_id, start, None, &viewer,
+                )
+            })
+            .or_handle(b"/t/{tid}/r/{rid}/children/{start}/{ctx}", |req| {
+                let thread_id = req.get_var_u32(b"tid").unwrap_or(0) as u64;
+                let reply_id = req.get_var_u32(b"rid").unwrap_or(0) as u64;
+                let start = req.get_var_u32(b"start").unwrap_or(0);
+                let ctx = req.get_var_u32(b"ctx");
+                Self::render_children_batch(
+                    &env, board_id, thread_id, reply_id, start, ctx, &viewer,
+                )
+            })
+            // Nested reply form
+            .or_handle(b"/t/{tid}/r/{rid}/reply", |req| {
+                let thread_id = req.get_var_u32(b"tid").unwrap_or(0) as u64;
+                let reply_id = req.get_var_u32(b"rid").unwrap_or(0) as u64;
+                Self::render_reply_form(&env, board_id, thread_id, Some(reply_id), true, &viewer)
+            })
+            // Deeply nested reply as the root of its own page
+            .or_handle(b"/t/{tid}/r/{rid}/subtree", |req| {
+                let thread_id = req.get_var_u32(b"tid").unwrap_or(0) as u64;
+                let reply_id = req.get_var_u32(b"rid").unwrap_or(0) as u64;
+                Self::render_reply_permalink(&env, board_id, thread_id, reply_id, true, &viewer)
+            })
+            // Single reply permalink with its parent for context
+            .or_handle(b"/t/{tid}/r/{rid}", |req| {
+                let thread_id = req.get_var_u32(b"tid").unwrap_or(0) as u64;
+                let reply_id = req.get_var_u32(b"rid").unwrap_or(0) as u64;
+                Self::render_reply_permalink(&env, board_id, thread_id, reply_id, false, &viewer)
+            })
+            // Boards this thread has been crossposted to
+            .or_handle(b"/t/{tid}/crossposts", |req| {
+                let thread_id = req.get_var_u32(b"tid").unwrap_or(0) as u64;
+                Self::render_crossposts(&env, board_id, thread_id, &viewer)
+            })
+            // Edit history (moderators only)
+            .or_handle(b"/t/{tid}/revisions", |req| {
+                let thread_id = req.get_var_u32(b"tid").unwrap_or(0) as u64;
+                Self::render_revisions(&env, board_id, thread_id, None, &viewer)
+            })
+            .or_handle(b"/t/{tid}/r/{rid}/revisions", |req| {
+                let thread_id = req.get_var_u32(b"tid").unwrap_or(0) as u64;
+                let reply_id = req.get_var_u32(b"rid").unwrap_or(0) as u64;
+                Self::render_revisions(&env, board_id, thread_id, Some(reply_id), &viewer)
+            })
+            // Edit thread form
+            .or_handle(b"/t/{tid}/edit", |req| {
+                let thread_id = req.get_var_u32(b"tid").unwrap_or(0) as u64;
+                Self::render_edit_thread(&env, board_id, thread_id, &viewer)
+            })
+            // Edit reply form
+            .or_handle(b"/t/{tid}/r/{rid}/edit", |req| {
+                let thread_id = req.get_var_u32(b"tid").unwrap_or(0) as u64;
+                let reply_id = req.get_var_u32(b"rid").unwrap_or(0) as u64;
+                Self::render_edit_reply(&env, board_id, thread_id, reply_id, &viewer)
+            })
+            // Moderation view: threads filtered by status
+            .or_handle(b"/mod/threads/{filter}", |req| {
+                let filter = req.get_var(b"filter").unwrap_or(Bytes::new(&env));
+                Self::render_mod_threads(&env, board_id, &filter, &viewer)
+            })
+            // Viewer's subscribed threads
+            .or_handle(b"/subscriptions", |_| {
+                Self::render_subscriptions(&env, board_id, &viewer)
+            })
+            // Viewer's saved threads, across all boards
+            .or_handle(b"/saved/{page}", |req| {
+                let page = req.get_var_u32(b"page").unwrap_or(1);
+                Self::render_saved(&env, board_id, page, &viewer)
+            })
+            .or_handle(b"/saved", |_| {
+                Self::render_saved(&env, board_id, 1, &viewer)
+            })
+            // Posts on this board that mention the viewer
+            .or_handle(b"/mentions", |_| {
+                Self::render_mentions(&env, board_id, &viewer)
+            })
+            // A user's recent threads and replies on this board
+            .or_handle(b"/u/{address}/activity", |req| {
+                let address = req.get_var(b"address").unwrap_or(Bytes::new(&env));
+                Self::render_user_activity(&env, board_id, &address, &viewer)
+            })
+            // Replies to the viewer's posts and moderator actions on them
+            .or_handle(b"/notifications", |_| {
+                Self::render_notifications(&env, board_id, &viewer)
+            })
+            // Top karma earners on this board
+            .or_handle(b"/leaderboard", |_| {
+                Self::render_leaderboard(&env, board_id, &viewer)
+            })
+            // Daily thread/reply counts for the last 30 days
+            .or_handle(b"/stats", |_| Self::render_stats(&env, board_id, &viewer))
+            // Threads carrying a tag
+            .or_handle(b"/tag/{id}", |req| {
+                let tag_id = req.get_var_u32(b"id").unwrap_or(u32::MAX);
+                Self::render_tag(&env, board_id, tag_id, &viewer)
+            })
+            // Top threads of the last few days (1-30, default 7)
+            .or_handle(b"/digest/{days}", |req| {
+                let days = req.get_var_u32(b"days").unwrap_or(0) as u64;
+                let days = days.clamp(1, MAX_DIGEST_DAYS);
+                Self::render_digest(&env, board_id, days, &viewer)
+            })
+            .or_handle(b"/digest", |_| {
+                Self::render_digest(&env, board_id, DEFAULT_DIGEST_DAYS, &viewer)
+            })
+            // Moderation log, public when the board opts in; later pages are continuations
+            .or_handle(b"/modlog/{start}", |req| {
+                let start = req.get_var_u32(b"start").unwrap_or(0) as u64;
+                Self::render_modlog(&env, board_id, start, &viewer)
+            })
+            .or_handle(b"/modlog", |_| {
+                Self::render_modlog(&env, board_id, 0, &viewer)
+            })
+            // Board wiki: index and pages
+            .or_handle(b"/wiki/{slug}", |req| {
+                let slug = req.get_var(b"slug").unwrap_or(Bytes::new(&env));
+                Self::render_wiki(&env, board_id, Some(slug), &viewer)
+            })
+            .or_handle(b"/wiki", |_| {
+                Self::render_wiki(&env, board_id, None, &viewer)
+            })
+            .or_handle(b"/name", |_| {
+                Self::render_display_name(&env, board_id, &viewer)
+            })
+            // Board view styled with a catalog theme
+            .or_handle(b"/preview/theme/{name}", |req| {
+                let name = req.get_var(b"name").unwrap_or(Bytes::new(&env));
+                Self::render_theme_preview(&env, board_id, &name, &viewer)
+            })
+            // JSON exports for off-chain tooling
+            .or_handle(b"/export/board", |_| {
+                Self::render_export_board(&env, board_id, &viewer)
+            })
+            .or_handle(b"/export/t/{tid}", |req| {
+                let thread_id = req.get_var_u32(b"tid").unwrap_or(0) as u64;
+                Self::render_export_thread(&env, board_id, thread_id, &viewer)
+            })
+            .or_handle(b"/export/t/{tid}/md", |req| {
+                let thread_id = req.get_var_u32(b"tid").unwrap_or(0) as u64;
+                Self::render_export_thread_markdown(&env, board_id, thread_id, &viewer)
+            })
+            .or_handle(b"/export/digest/{days}", |req| {
+                let days = req.get_var_u32(b"days").unwrap_or(0) as u64;
+                let days = days.clamp(1, MAX_DIGEST_DAYS);
+                Self::render_export_digest_markdown(&env, board_id, days, &viewer)
+            })
+            // Thread title search (?q=, paged with ?start=)
+            .or_handle(b"/search", |req| {
+                let query = req.get_query_param(b"q").unwrap_or(Bytes::new(&env));
+                let start = req.get_query_param_u64(b"start").unwrap_or(0) as u32;
+                Self::render_search(&env, board_id, &query, start, &viewer)
+            })
+            // Thread view without the output budget
+            .or_handle(b"/t/{tid}/full", |req| {
+                let thread_id = req.get_var_u32(b"tid").unwrap_or(0) as u64;
+                let sort = req
+                    .get_query_param(b"sort")
+                    .and_then(|p| Self::parse_reply_sort(&p))
+                    .unwrap_or(ReplySort::Oldest);
+                Self::render_thread(&env, board_id, thread_id, true, sort, &viewer)
+            })
+            // Thread view
+            .or_handle(b"/t/{tid}", |req| {
+                let thread_id = req.get_var_u32(b"tid").unwrap_or(0) as u64;
+                let sort = req
+                    .get_query_param(b"sort")
+                    .and_then(|p| Self::parse_reply_sort(&p))
+                    .unwrap_or(ReplySort::Oldest);
+                Self::render_thread(&env, board_id, thread_id, false, sort, &viewer)
+            })
+            // Default - board view
+            .or_default(|req| {
+                let sort = req.get_query_param(b"sort");
+                Self::render_board(&env, board_id, &viewer, sort)
+            })
+    }
+
+    /// Render navigation bar via include from main contract.
+    /// Uses {{include}} tag for deferred loading - no cross-contract call overhead.
+    fn render_nav<'a>(
+        env: &'a Env,
+        board_id: u64,
+        viewer: &Option<Address>,
+    ) -> MarkdownBuilder<'a> {
+        let aliases = Self::fetch_aliases(env);
+
+        // Get board metadata for slug-based return path
+        let board_meta: Option<BoardMeta> =
+            env.storage().persistent().get(&BoardKey::Board(board_id));
+
+        // Build include tag with return path for this board
+        // Format: {{include contract=@main func="render_nav_include" viewer return_path="@main:{base_path}"}}
+        let mut include_tag = Bytes::from_slice(
+            env,
+            b"{{include contract=@main func=\"render_nav_include\" viewer return_path=\"@main:",
+        );
+
+        let base_path = if let Some(meta) = board_meta {
+            Self::build_board_base_path(env, board_id, &meta.slug)
+        } else {
+            // Fallback to numeric ID if board not found
+            let mut path = Bytes::from_slice(env, b"/b/");
+            path.append(&u64_to_bytes(env, board_id));
+            path
+        };
+        include_tag.append(&base_path);
+        include_tag.append(&Bytes::from_slice(env, b"\"}}"));
+
+        let mut md = MarkdownBuilder::new(env)
+            .raw(aliases) // Emit aliases for include resolution
+            .raw(include_tag);
+
+        // A picked (or previewed) theme is inlined over the default stylesheet
+        let preview: Option<(String, Address)> =
+            env.storage().temporary().get(&BoardKey::RenderPreviewTheme);
+        let theme = match &preview {
+            Some((_, theme)) => Some(theme.clone()),
+            None => Self::chosen_theme(env, board_id),
+        };
+        if let Some(css) = theme
+            .clone()
+            .and_then(|theme| Self::theme_styles(env, &theme))
+        {
+            md = md.raw_str("<style>").raw(css).raw_str("</style>\n");
+        }
+
+        // Viewers who picked a color scheme get the theme's palette for it, and
+        // everyone signed in can switch to the other two
+        if let Some(user) = viewer {
+            let scheme = Self::color_scheme(env, user);
+            if let Some(css) = Self::scheme_css(env, theme, scheme) {
+                md = md.raw_str("<style>").raw(css).raw_str("</style>\n");
+            }
+            md = md.raw_str("<div class=\"display-toggle\">");
+            for (choice, link) in [
+                (
+                    ColorScheme::Dark,
+                    "2}\" title=\"Switch to dark mode\">🌙</a>",
+                ),
+                (
+                    ColorScheme::Light,
+                    "1}\" title=\"Switch to light mode\">☀</a>",
+                ),
+                (
+                    ColorScheme::Auto,
+                    "0}\" title=\"Follow the theme default\">Auto</a>",
+                ),
+            ] {
+                if choice != scheme {
+                    md = md
+                        .raw_str("<a href=\"tx:@board:set_display_pref {&quot;scheme&quot;:")
+                        .raw_str(link);
+                }
+            }
+            md = md.raw_str("</div>\n");
+
+            let unread = Self::unread_notifications(env, user);
+            md = md
+                .raw_str("<div class=\"notification-bell\"><a href=\"render:")
+                .raw(base_path)
+                .raw_str("/notifications\" title=\"Notifications\">🔔");
+            if unread > 0 {
+                md = md.raw_str(" ").number(unread);
+            }
+            md = md.raw_str("</a></div>\n");
+        }
+        if let Some((name, _)) = preview {
+            md = md
+                .div_start("theme-preview")
+                .raw_str("Previewing the <strong>")
+                .text_string(&name)
+                .raw_str("</strong> theme.")
+                .div_end();
+        }
+
+        // Board CSS overrides, already checked by set_custom_css
+        if let Some(custom) = Self::get_custom_css(env.clone(), board_id) {
+            md = md
+                .raw_str("<style>")
+                .raw(soroban_render_sdk::bytes::string_to_bytes(env, &custom))
+                .raw_str("</style>\n");
+        }
+        md
+    }
+
+    /// Render back navigation with optional community link
+    /// Shows "← Community Name" if board is in a community, plus "← Home"
+    fn render_back_nav<'a>(
+        env: &'a Env,
+        mut md: MarkdownBuilder<'a>,
+        board_id: u64,
+    ) -> MarkdownBuilder<'a> {
+        md = md.div_start("back-nav");
+
+        // Check if board is in a community
+        if let Some(community) = Self::get_board_community(env, board_id) {
+            // Build community display name
+            let mut display_buf = [0u8; 64];
+            let display_len = community.display_name.len() as usize;
+            let display_copy_len = core::cmp::min(display_len, 60);
+            community
+                .display_name
+                .copy_into_slice(&mut display_buf[0..display_copy_len]);
+            let display =
+                core::str::from_utf8(&display_buf[0..display_copy_len]).unwrap_or("Community");
+
+            // Build community name for URL
+            let mut name_buf = [0u8; 32];
+            let name_len = community.name.len() as usize;
+            let name_copy_len = core::cmp::min(name_len, 32);
+            community
+                .name
+                .copy_into_slice(&mut name_buf[0..name_copy_len]);
+            let name = core::str::from_utf8(&name_buf[0..name_copy_len]).unwrap_or("");
+
+            // Community link
+            md = md
+                .raw_str("<a href=\"render:/c/")
+                .raw_str(name)
+                .raw_str("\" class=\"back-link back-community\">← ")
+                .raw_str(display)
+                .raw_str("</a>");
+        }
+
+        // Home link (always shown)
+        md = md.raw_str("<a href=\"render:/\" class=\"back-link back-home\">← Home</a>");
+
+        md.div_end()
+    }
+
+    /// Render breadcrumbs: Home › Community › Board › Thread › current page.
+    /// Every segment links except the last, which is plain text. Without `current`
+    /// the thread (or board) is the last segment.
+    fn render_breadcrumbs<'a>(
+        env: &'a Env,
+        mut md: MarkdownBuilder<'a>,
+        board_id: u64,
+        thread_id: Option<u64>,
+        current: Option<&str>,
+    ) -> MarkdownBuilder<'a> {
+        md = md
+            .div_start("breadcrumbs")
+            .raw_str("<a href=\"render:/\">Home</a>");
+
+        if let Some(community) = Self::get_board_community(env, board_id) {
+            md = md
+                .raw_str(BREADCRUMB_SEPARATOR)
+                .raw_str("<a href=\"render:/c/")
+                .raw(Self::escape_attr(env, &community.name))
+                .raw_str("\">")
+                .raw(Self::escape_attr(env, &community.display_name))
+                .raw_str("</a>");
+        }
+
+        let thread = thread_id.and_then(|id| {
+            env.storage()
+                .persistent()
+                .get::<_, ThreadMeta>(&BoardKey::BoardThread(board_id, id))
+        });
+        let board_meta: Option<BoardMeta> =
+            env.storage().persistent().get(&BoardKey::Board(board_id));
+        if let Some(meta) = board_meta {
+            let base_path = Self::build_board_base_path(env, board_id, &meta.slug);
+            md = md.raw_str(BREADCRUMB_SEPARATOR);
+            if thread.is_none() && current.is_none() {
+                md = md
+                    .raw_str("<span class=\"crumb-current\">")
+                    .raw(Self::escape_attr(env, &meta.name))
+                    .raw_str("</span>");
+            } else {
+                md = md
+                    .raw_str("<a href=\"render:")
+                    .raw(base_path.clone())
+                    .raw_str("\">")
+                    .raw(Self::escape_attr(env, &meta.name))
+                    .raw_str("</a>");
+            }
+
+            if let Some(t) = thread {
+                md = md.raw_str(BREADCRUMB_SEPARATOR);
+                if current.is_none() {
+                    md = md
+                        .raw_str("<span class=\"crumb-current\">")
+                        .raw(Self::breadcrumb_title(env, &t.title))
+                        .raw_str("</span>");
+                } else {
+                    md = md
+                        .raw_str("<a href=\"render:")
+                        .raw(base_path)
+                        .raw_str("/t/")
+                        .number(t.id as u32)
+                        .raw_str("\">")
+                        .raw(Self::breadcrumb_title(env, &t.title))
+                        .raw_str("</a>");
+                }
+            }
+        }
+
+        if let Some(page) = current {
+            md = md
+                .raw_str(BREADCRUMB_SEPARATOR)
+                .raw_str("<span class=\"crumb-current\">")
+                .raw_str(page)
+                .raw_str("</span>");
+        }
+
+        md.div_end()
+    }
+
+    /// Escaped thread title for breadcrumbs, cut to `MAX_BREADCRUMB_TITLE_CHARS`
+    /// characters with an ellipsis
+    fn breadcrumb_title(env: &Env, title: &String) -> Bytes {
+        // Enough bytes for the longest UTF-8 prefix plus one more character start
+        const BUF_LEN: usize = MAX_BREADCRUMB_TITLE_CHARS * 4 + 1;
+        let mut buf = [0u8; BUF_LEN];
+        let copied = copy_string_prefix(title, &mut buf);
+
+        let mut chars = 0;
+        for i in 0..copied {
+            if buf[i] & 0xC0 != 0x80 {
+                if chars == MAX_BREADCRUMB_TITLE_CHARS {
+                    let mut cut = escape_html(env, &Bytes::from_slice(env, &buf[..i]));
+                    cut.append(&Bytes::from_slice(env, "…".as_bytes()));
+                    return cut;
+                }
+                chars += 1;
+            }
+        }
+        escape_html(env, &Bytes::from_slice(env, &buf[..copied]))
+    }
+
+    /// Append footer to builder via include from main contract.
+    /// Uses {{include}} tag for deferred loading - no cross-contract call overhead.
+    fn render_footer_into<'a>(_env: &'a Env, md: MarkdownBuilder<'a>) -> MarkdownBuilder<'a> {
+        md.raw_str("{{include contract=@main func=\"render_footer_include\"}}")
+    }
+
+    /// Render a thread card for the board list
+    fn render_thread_card<'a>(
+        env: &'a Env,
+        mut md: MarkdownBuilder<'a>,
+        board_id: u64,
+        base_path: &Bytes,
+        thread: &ThreadMeta,
+        voting_contract: &Option<Address>,
+        flairs: &Vec<FlairDef>,
+        tags: &Vec<TagDef>,
+        blocked: &Vec<Address>,
+        saved: &Option<Vec<(u64, u64)>>,
+        read_marks: &Option<ReadMarks>,
+    ) -> MarkdownBuilder<'a> {
+        // Get vote tally if voting contract is available
+        let score = if let Some(voting) = voting_contract {
+            let args: Vec<Val> =
+                Vec::from_array(env, [board_id.into_val(env), thread.id.into_val(env)]);
+            let tally: VoteTally =
+                env.invoke_contract(voting, &Symbol::new(env, "get_thread_tally"), args);
+            Some(tally.score)
+        } else {
+            None
+        };
+
+        // Threads by blocked authors collapse for this viewer; pinned announcements never do
+        let collapsed = !thread.is_pinned && blocked.contains(&thread.creator);
+        if collapsed {
+            md = md.raw_str(BLOCKED_CONTENT_SUMMARY);
+        }
+
+        // Thread card with optional vote score
+        md = md.raw_str("<div class=\"thread-card-wrapper\">");
+
+        // Vote score display (if voting enabled)
+        if let Some(s) = score {
+            md = md
+                .raw_str("<span class=\"vote-score-compact\">")
+                .number(s as u32) // Display score (handle negatives in CSS)
+                .raw_str("</span>");
+        }
+
+        md = md
+            .raw_str("<a href=\"render:")
+            .raw(base_path.clone())
+            .raw_str("/t/")
+            .number(thread.id as u32)
+            .raw_str("\" class=\"thread-card\">");
+
+        // Display flair if thread has one
+        if let Some(flair_id) = thread.flair_id {
+            for i in 0..flairs.len() {
+                let flair = flairs.get(i).unwrap();
+                if flair.id == flair_id && flair.enabled {
+                    md = md
+                        .raw_str("<span class=\"flair\" style=\"color:")
+                        .raw(sanitize_color(env, &flair.color))
+                        .raw_str(";background:")
+                        .raw(sanitize_color(env, &flair.bg_color))
+                        .raw_str("\">")
+                        .raw(Self::escape_attr(env, &flair.name))
+                        .raw_str("</span> ");
+                    break;
+                }
+            }
+        }
+
+        md = Self::render_tag_chips(env, md, board_id, thread.id, tags, None);
+        if read_marks
+            .as_ref()
+            .is_some_and(|marks| Self::is_unread(marks, thread))
+        {
+            md = md.raw_str("<span class=\"new-dot\" title=\"New activity\">●</span> ");
+        }
+        md = md
+            .raw_str("<span class=\"thread-card-title\">")
+            .text_string(&thread.title)
+            .raw_str("</span><span class=\"thread-card-meta\">");
+        if thread.is_hidden {
+            md = md.raw_str("<span class=\"badge badge-hidden\">hidden</span> ");
+        }
+        if thread.is_pinned {
+            md = md.raw_str("<span class=\"badge badge-pinned\">pinned</span> ");
+        }
+        if thread.is_locked {
+            md = md.raw_str("<span class=\"badge badge-locked\">locked</span> ");
+        } else {
+            md = Self::render_schedule_badge(env, md, Self::reply_window(env, board_id, thread.id));
+        }
+        if Self::get_accepted_answer(env.clone(), board_id, thread.id).is_some() {
+            md = md.raw_str("<span class=\"badge badge-solved\">solved</span> ");
+        }
+        md = md
+            .number(thread.reply_count)
+            .text(" replies · ")
+            .raw(Self::format_board_timestamp(
+                env,
+                board_id,
+                thread.created_at,
+            ))
+            .raw_str("</span></a>");
+        if let Some(saved) = saved {
+            let is_saved = saved.contains((board_id, thread.id));
+            md = Self::render_save_toggle(md, board_id, thread.id, is_saved);
+        }
+        md = md.raw_str("</div>\n");
+        if collapsed {
+            md = md.raw_str("</details>\n");
+        }
+        md
+    }
+
+    /// Render a thread's tags as chips, linked to their /tag/{id} pages when
+    /// `link_base` is given (thread cards are links already). `tags` is the
+    /// board's tag list, loaded once by the caller.
+    fn render_tag_chips<'a>(
+        env: &Env,
+        mut md: MarkdownBuilder<'a>,
+        board_id: u64,
+        thread_id: u64,
+        tags: &Vec<TagDef>,
+        link_base: Option<&Bytes>,
+    ) -> MarkdownBuilder<'a> {
+        let tag_ids = Self::get_thread_tags(env.clone(), board_id, thread_id);
+        for tag_id in tag_ids.iter() {
+            let Some(tag) = tags.get(tag_id) else {
+                continue;
+            };
+            md = match link_base {
+                Some(base_path) => md
+                    .raw_str("<a href=\"render:")
+                    .raw(base_path.clone())
+                    .raw_str("/tag/")
+                    .number(tag.id)
+                    .raw_str("\" class=\"tag-chip\">#"),
+                None => md.raw_str("<span class=\"tag-chip\">#"),
+            };
+            md = md.text_string(&tag.name).raw_str(if link_base.is_some() {
+                "</a> "
+            } else {
+                "</span> "
+            });
+        }
+        md
+    }
+
+    /// Render `/tag/{id}`: the threads most recently given a tag
+    fn render_tag(env: &Env, board_id: u64, tag_id: u32, viewer: &Option<Address>) -> Bytes {
+        let config: BoardConfig = env
+            .storage()
+            .persistent()
+            .get(&BoardKey::BoardConfig(board_id))
+            .expect("Board not found");
+
+        let viewer_perms = Self::viewer_permissions(env, board_id, viewer);
+
+        // Private boards show their tags to members only
+        if config.is_private && (viewer_perms.role as u32) < (Role::Member as u32) {
+            if let Some(perms_addr) = env
+                .storage()
+                .instance()
+                .get::<_, Address>(&BoardKey::Permissions)
+            {
+                return Self::render_private_board_message(
+                    env,
+                    board_id,
+                    &config,
+                    viewer,
+                    &perms_addr,
+                );
+            }
+        }
+
+        let board_meta: BoardMeta = env
+            .storage()
+            .persistent()
+            .get(&BoardKey::Board(board_id))
+            .expect("Board not found");
+        let base_path = Self::build_board_base_path(env, board_id, &board_meta.slug);
+
+        let mut md = Self::render_nav(env, board_id, viewer);
+        md = Self::render_back_nav(env, md, board_id);
+        let tags = Self::list_tags(env.clone(), board_id);
+        let Some(tag) = tags.get(tag_id) else {
+            md = md.h1("Tag not found");
+            return Self::render_footer_into(env, md).build();
+        };
+        md = md
+            .raw_str("<h1>Threads tagged #")
+            .text_string(&tag.name)
+            .raw_str("</h1>\n");
+
+        let voting_contract: Option<Address> = env.storage().instance().get(&BoardKey::Voting);
+        let blocked = Self::blocked_for(env, viewer);
+        let saved = Self::saved_for(env, viewer);
+        let read_marks = Self::read_marks_for(env, board_id, viewer);
+        let flairs: Vec<FlairDef> = env
+            .storage()
+            .persistent()
+            .get(&BoardKey::BoardFlairDefs(board_id))
+            .unwrap_or(Vec::new(env));
+
+        let mut shown = 0u32;
+        md = md.div_start("thread-list");
+        for thread_id in Self::list_tag_threads(env.clone(), board_id, tag_id).iter() {
+            if shown >= TAG_PAGE_THREADS {
+                break;
+            }
+            let Some(thread) = env
+                .storage()
+                .persistent()
+                .get::<_, ThreadMeta>(&BoardKey::BoardThread(board_id, thread_id))
+            else {
+                continue;
+            };
+            if thread.is_hidden || thread.is_deleted {
+                continue;
+            }
+            md = Self::render_thread_card(
+                env,
+                md,
+                board_id,
+                &base_path,
+                &thread,
+                &voting_contract,
+                &flairs,
+                &tags,
+                &blocked,
+                &saved,
+                &read_marks,
+            );
+            shown += 1;
+        }
+        md = md.div_end();
+        if shown == 0 {
+            md = md.paragraph("No threads carry this tag yet.");
+        }
+
+        Self::render_footer_into(env, md).build()
+    }
+
+    /// Render a page of the moderation log, newest first. The first page carries the
+    /// board chrome; later pages are bare batches embedded as continuations.
+    /// Non-moderators see the action, its target and the date, but never who acted,
+    /// which user was affected or the reason given.
+    fn render_modlog(env: &Env, board_id: u64, start: u64, viewer: &Option<Address>) -> Bytes {
+        let config: BoardConfig = env
+            .storage()
+            .persistent()
+            .get(&BoardKey::BoardConfig(board_id))
+            .expect("Board not found");
+        let viewer_perms = Self::viewer_permissions(env, board_id, viewer);
+
+        // Private boards show their log to members only
+        if config.is_private && (viewer_perms.role as u32) < (Role::Member as u32) {
+            if let Some(perms_addr) = env
+                .storage()
+                .instance()
+                .get::<_, Address>(&BoardKey::Permissions)
+            {
+                return Self::render_private_board_message(
+                    env,
+                    board_id,
+                    &config,
+                    viewer,
+                    &perms_addr,
+                );
+            }
+        }
+
+        let board_meta: BoardMeta = env
+            .storage()
+            .persistent()
+            .get(&BoardKey::Board(board_id))
+            .expect("Board not found");
+        let base_path = Self::build_board_base_path(env, board_id, &board_meta.slug);
+
+        let first_page = start == 0;
+        let mut md = if first_page {
+            let md = Self::render_nav(env, board_id, viewer);
+            Self::render_back_nav(env, md, board_id).h1("Moderation Log")
+        } else {
+            MarkdownBuilder::new(env)
+        };
+        let finish = |md| {
+            if first_page {
+                Self::render_footer_into(env, md).build()
+            } else {
+                md.build()
+            }
+        };
+
+        let can_moderate = viewer_perms.can_moderate;
+        if !can_moderate && !Self::is_modlog_public(env.clone(), board_id) {
+            md = md.paragraph("This board's moderation log is visible to moderators only.");
+            return finish(md);
+        }
+
+        let Some(admin) = Self::get_admin_contract(env) else {
+            md = md.paragraph("The moderation log is unavailable.");
+            return finish(md);
+        };
+        let count = env
+            .try_invoke_contract::<u64, soroban_sdk::Error>(
+                &admin,
+                &Symbol::new(env, "get_mod_action_count"),
+                Vec::from_array(env, [board_id.into_val(env)]),
+            )
+            .ok()
+            .and_then(|r| r.ok())
+            .unwrap_or(0);
+        let actions = env
+            .try_invoke_contract::<Vec<ModAction>, soroban_sdk::Error>(
+                &admin,
+                &Symbol::new(env, "list_mod_actions"),
+                Vec::from_array(
+                    env,
+                    [
+                        board_id.into_val(env),
+                        start.into_val(env),
+                        MODLOG_PAGE_SIZE.into_val(env),
+                    ],
+                ),
+            )
+            .ok()
+            .and_then(|r| r.ok())
+            .unwrap_or(Vec::new(env));
+        if first_page && actions.is_empty() {
+            md = md.paragraph("No moderation actions have been recorded yet.");
+            return finish(md);
+        }
+
+        let profile_contract = if can_moderate {
+            Self::get_profile_contract(env)
+        } else {
+            None
+        };
+        for action in actions.iter() {
+            md = md
+                .raw_str("<div class=\"modlog-entry\"><span class=\"modlog-action\">")
+                .raw_str(Self::mod_action_label(env, &action.action))
+                .raw_str("</span>");
+
+            // Thread targets show their title while the thread is still visible to the viewer
+            if let Some(thread_id) = action.target_thread {
+                let thread = env
+                    .storage()
+                    .persistent()
+                    .get::<_, ThreadMeta>(&BoardKey::BoardThread(board_id, thread_id))
+                    .filter(|t| !t.is_deleted && (!t.is_hidden || can_moderate));
+                md = md.raw_str(" ");
+                md = match thread {
+                    Some(thread) => md
+                        .raw_str("<a href=\"render:")
+                        .raw(base_path.clone())
+                        .raw_str("/t/")
+                        .number(thread_id as u32)
+                        .raw_str("\">")
+                        .text_string(&thread.title)
+                        .raw_str("</a>"),
+                    None => md.raw_str("<span class=\"modlog-removed\">[removed]</span>"),
+                };
+                if let Some(reply_id) = action.target_reply.filter(|_| can_moderate) {
+                    md = md.raw_str(" (reply #").number(reply_id as u32).raw_str(")");
+                }
+            }
+            md = md
+                .raw_str(" <span class=\"modlog-date\">")
+                .raw(Self::format_board_timestamp(
+                    env,
+                    board_id,
+                    action.timestamp,
+                ))
+                .raw_str("</span>");
+
+            if can_moderate {
+                md = md.raw_str("<div class=\"modlog-details\">by ");
+                md = Self::render_author(env, md, board_id, &action.actor, &profile_contract, None);
+                if let Some(user) = &action.target_user {
+                    md = md.raw_str(" · user ");
+                    md = Self::render_author(env, md, board_id, user, &profile_contract, None);
+                }
+                if let Some(note) = &action.note {
+                    md = md
+                        .raw_str(" · <span class=\"modlog-note\">")
+                        .text_string(note)
+                        .raw_str("</span>");
+                }
+                md = md.raw_str("</div>");
+            }
+            md = md.raw_str("</div>\n");
+        }
+
+        let next_start = start + actions.len() as u64;
+        if !actions.is_empty() && next_start < count {
+            md = md
+                .raw_str("{{render path=\"")
+                .raw(base_path)
+                .raw_str("/modlog/")
+                .number(next_start as u32)
+                .raw_str("\"}}");
+        }
+        finish(md)
+    }
+
+    /// Human-readable label for a moderation log action (mirrors the admin contract's)
+    fn mod_action_label(env: &Env, action: &Symbol) -> &'static str {
+        let labels: [(&str, &'static str); 23] = [
+            ("hide_thread", "Hid thread"),
+            ("unhide_thread", "Unhid thread"),
+            ("hide_reply", "Hid reply"),
+            ("unhide_reply", "Unhid reply"),
+            ("lock_thread", "Locked thread"),
+            ("unlock_thread", "Unlocked thread"),
+            ("schedule_thread", "Scheduled thread"),
+            ("pin_thread", "Pinned thread"),
+            ("unpin_thread", "Unpinned thread"),
+            ("delete_thread", "Deleted thread"),
+            ("delete_reply", "Deleted reply"),
+            ("remove_crosspost", "Removed crosspost"),
+            ("clear_flags", "Cleared flags"),
+            ("ban_user", "Banned user"),
+            ("unban_user", "Unbanned user"),
+            ("extend_ban", "Extended ban"),
+            ("set_role", "Changed role"),
+            ("remove_role", "Removed role"),
+            ("transfer_owner", "Accepted ownership"),
+            ("quarantine", "Quarantined user"),
+            ("unquarantine", "Released user from quarantine"),
+            ("archive_board", "Archived board"),
+            ("unarchive_board", "Unarchived board"),
+        ];
+        for (name, label) in labels.iter() {
+            if *action == Symbol::new(env, name) {
+                return label;
+            }
+        }
+        "Moderation action"
+    }
+
+    /// Render board view with thread list
+    fn render_board(
+        env: &Env,
+        board_id: u64,
+        viewer: &Option<Address>,
+        sort_param: Option<Bytes>,
+    ) -> Bytes {
+        // Get board metadata for slug-based URLs
+        let board_meta: BoardMeta = env
+            .storage()
+            .persistent()
+            .get(&BoardKey::Board(board_id))
+            .expect("Board not found");
+
+        // Build base path for all links
+        let base_path = Self::build_board_base_path(env, board_id, &board_meta.slug);
+
+        let config: BoardConfig = env
+            .storage()
+            .persistent()
+            .get(&BoardKey::BoardConfig(board_id))
+            .expect("Board not found");
+
+        // Get viewer role (needed for private board check, admin button, and hidden thread filtering)
+        let perms_addr_opt = env
+            .storage()
+            .instance()
+            .get::<_, Address>(&BoardKey::Permissions);
+        let viewer_role = if let Some(ref perms_addr) = perms_addr_opt {
+            if let Some(user) = viewer {
+                let args: Vec<Val> =
+                    Vec::from_array(env, [board_id.into_val(env), user.into_val(env)]);
+                env.invoke_contract(perms_addr, &Symbol::new(env, "get_role"), args)
+            } else {
+                Role::Guest
+            }
+        } else {
+            Role::Guest
+        };
+        let viewer_can_moderate = (viewer_role as u32) >= (Role::Moderator as u32);
+
+        // Get voting contract for displaying vote scores
+        let voting_contract: Option<Address> = env.storage().instance().get(&BoardKey::Voting);
+        let blocked = Self::blocked_for(env, viewer);
+        let saved = Self::saved_for(env, viewer);
+        let read_marks = Self::read_marks_for(env, board_id, viewer);
+
+        // Get flairs for displaying on thread cards
+        let flairs: Vec<FlairDef> = env
+            .storage()
+            .persistent()
+            .get(&BoardKey::BoardFlairDefs(board_id))
+            .unwrap_or(Vec::new(env));
+        let tags = Self::list_tags(env.clone(), board_id);
+
+        // Check permissions for private boards
+        if config.is_private {
+            if let Some(ref perms_addr) = perms_addr_opt {
+                // If not a member, show access denied
+                if (viewer_role as u32) < (Role::Member as u32) {
+                    return Self::render_private_board_message(
+                        env, board_id, &config, viewer, perms_addr,
+                    );
+                }
+            }
+        }
+
+        let mut md = Self::render_nav(env, board_id, viewer);
+        md = Self::render_back_nav(env, md, board_id);
+        md = md
+            .div_start("page-header")
+            .raw_str("<h1>")
+            .text_string(&config.name)
+            .raw_str("</h1>")
+            .raw_str("<p>")
+            .text_string(&config.description)
+            .raw_str("</p>")
+            .div_end()
+            .newline();
+        md = Self::render_search_box(env, md, &base_path, &Bytes::new(env));
+
+        if config.is_private {
+            md = md.raw_str("<span class=\"badge badge-private\">private</span> ");
+        }
+        if let Some((code, _)) = Self::get_locale(env.clone(), board_id)
+            .and_then(|locale| Self::locale_entry(env, &locale))
+        {
+            md = md
+                .raw_str("<span class=\"badge badge-locale\">")
+                .raw_str(code)
+                .raw_str("</span> ");
+        }
+
+        if Self::is_archived(env.clone(), board_id) {
+            md = md.warning("This board has been archived. It is read-only and no longer listed.");
+        } else if config.is_readonly {
+            md = md.note("This board is read-only.");
+        }
+
+        // Show board rules if set
+        if let Some(rules) = env
+            .storage()
+            .persistent()
+            .get::<_, String>(&BoardKey::BoardRules(board_id))
+        {
+            if !rules.is_empty() {
+                md = md
+                    .div_start("board-rules")
+                    .raw_str("<details><summary><strong>Board Rules</strong></summary>")
+                    .div_start("rules-content")
+                    .text_string(&rules)
+                    .div_end()
+                    .raw_str("</details>")
+                    .div_end();
+            }
+        }
+
+        // Show create thread button if user has Member+ role and board is not read-only
+        let can_create = viewer.is_some()
+            && !config.is_readonly
+            && (viewer_role as u32) >= (Role::Member as u32);
+        if can_create {
+            md = md
+                .raw_str("<a href=\"render:")
+                .raw(base_path.clone())
+                .raw_str("/new\" class=\"action-btn\">+ New Thread</a>")
+                .newline();
+        }
+
+        // Show subscriptions link for logged-in users
+        if viewer.is_some() {
+            md = md
+                .raw_str("<a href=\"render:")
+                .raw(base_path.clone())
+                .raw_str("/subscriptions\" class=\"action-btn action-btn-secondary\">★ Subscriptions</a>")
+                .newline()
+                .raw_str("<a href=\"render:")
+                .raw(base_path.clone())
+                .raw_str("/mentions\" class=\"action-btn action-btn-secondary\">@ Mentions</a>")
+                .newline()
+                .raw_str("<a href=\"render:")
+                .raw(base_path.clone())
+                .raw_str("/saved\" class=\"action-btn action-btn-secondary\">🔖 Saved</a>")
+                .newline();
+        }
+
+        // Board-local display names stand in for profiles when there's no profile contract
+        if viewer.is_some() && !Self::has_profile_contract(env) {
+            md = md
+                .raw_str("<a href=\"render:")
+                .raw(base_path.clone())
+                .raw_str("/name\" class=\"action-btn action-btn-secondary\">✎ Display Name</a>")
+                .newline();
+        }
+
+        // Leaderboard link whenever a voting contract is tracking karma
+        if env.storage().instance().has(&BoardKey::Voting) {
+            md = md
+                .raw_str("<a href=\"render:")
+                .raw(base_path.clone())
+                .raw_str(
+                    "/leaderboard\" class=\"action-btn action-btn-secondary\">🏆 Leaderboard</a>",
+                )
+                .newline();
+        }
+
+        md = md
+            .raw_str("<a href=\"render:")
+            .raw(base_path.clone())
+            .raw_str("/stats\" class=\"action-btn action-btn-secondary\">📊 Stats</a>")
+            .newline()
+            .raw_str("<a href=\"render:")
+            .raw(base_path.clone())
+            .raw_str("/wiki\" class=\"action-btn action-btn-secondary\">📖 Wiki</a>")
+            .newline();
+
+        // Show settings button for Admin+ users (uses numeric ID for admin routes)
+        if (viewer_role as u32) >= (Role::Admin as u32) {
+            md = md
+                .raw_str("<a href=\"render:/admin/b/")
+                .number(board_id as u32)
+                .raw_str("/settings\" class=\"action-btn action-btn-secondary\">⚙ Settings</a>")
+                .newline();
+        }
+
+        // Show moderation view link for Moderator+ users
+        if viewer_can_moderate {
+            md = md
+                .raw_str("<a href=\"render:")
+                .raw(base_path.clone())
+                .raw_str("/mod/threads/flagged\" class=\"action-btn action-btn-secondary\">Moderation</a>")
+                .newline();
+        }
+
+        // Unread summary from the viewer's read marks, and the link that sets them
+        if viewer.is_some() {
+            if let Some(ref marks) = read_marks {
+                let unread =
+                    Self::count_unread_threads(env, board_id, marks, viewer, viewer_can_moderate);
+                if unread > 0 {
+                    md = md
+                        .raw_str("<p class=\"unread-summary\">")
+                        .number(unread)
+                        .raw_str(if unread == 1 { " thread" } else { " threads" })
+                        .raw_str(" updated since your last visit</p>\n");
+                }
+            }
+            md = md
+                .raw_str("<a href=\"tx:@board:mark_board_read {&quot;board_id&quot;:")
+                .number(board_id as u32)
+                .raw_str("}\" class=\"action-btn action-btn-secondary\">✓ Mark all read</a>")
+                .newline();
+        }
+
+        md = md.raw_str("<h2>Threads</h2>\n");
+
+        // An explicit ?sort= wins; otherwise use the board's default ordering
+        let sort = sort_param
+            .and_then(|p| Self::parse_thread_sort(&p))
+            .unwrap_or_else(|| Self::get_default_sort(env.clone(), board_id));
+
+        // Sort order selector (if voting contract is configured)
+        if voting_contract.is_some() {
+            md = md
+                .div_start("sort-selector")
+                .raw_str("<span class=\"sort-label\">Sort:</span>");
+            let options: [(&str, &str, ThreadSort); 5] = [
+                ("hot", "Hot", ThreadSort::Hot),
+                ("new", "New", ThreadSort::New),
+                ("top", "Top", ThreadSort::Top),
+                ("active", "Active", ThreadSort::Active),
+                ("controversial", "Controversial", ThreadSort::Controversial),
+            ];
+            for (param, label, option) in options.iter() {
+                md = md
+                    .raw_str("<a href=\"render:")
+                    .raw(base_path.clone())
+                    .raw_str("?sort=")
+                    .raw_str(param)
+                    .raw_str(if *option == sort {
+                        "\" class=\"sort-option sort-active\">"
+                    } else {
+                        "\" class=\"sort-option\">"
+                    })
+                    .raw_str(label)
+                    .raw_str("</a>");
+            }
+            md = md.div_end();
+        }
+
+        md = md.div_start("thread-list");
+
+        // Fetch threads
+        let thread_count: u64 = env
+            .storage()
+            .persistent()
+            .get(&BoardKey::BoardThreadCount(board_id))
+            .unwrap_or(0);
+
+        if thread_count == 0 {
+            md = md
+                .div_end()
+                .paragraph("No threads yet. Be the first to post!");
+        } else {
+            // Get pinned threads list
+            let pinned_threads: Vec<u64> = env
+                .storage()
+                .persistent()
+                .get(&BoardKey::BoardPinnedThreads(board_id))
+                .unwrap_or(Vec::new(env));
+
+            let limit = 20u64;
+            let mut shown = 0u64;
+
+            // First, render pinned threads
+            for i in 0..pinned_threads.len() {
+                if shown >= limit {
+                    break;
+                }
+                let thread_id = pinned_threads.get(i).unwrap();
+                if let Some(thread) = env
+                    .storage()
+                    .persistent()
+                    .get::<_, ThreadMeta>(&BoardKey::BoardThread(board_id, thread_id))
+                {
+                    // Skip hidden threads for non-moderators (authors still see their own)
+                    if thread.is_hidden
+                        && !viewer_can_moderate
+                        && viewer.as_ref() != Some(&thread.creator)
+                    {
+                        continue;
+                    }
+                    md = Self::render_thread_card(
+                        env,
+                        md,
+                        board_id,
+                        &base_path,
+                        &thread,
+                        &voting_contract,
+                        &flairs,
+                        &tags,
+                        &blocked,
+                        &saved,
+                        &read_marks,
+                    );
+                    shown += 1;
+                }
+            }
+
+            // Hot, Top, Active and Controversial rank the most recent threads; New lists
+            // newest first, as do Hot and Controversial when there are no votes to rank by
+            let ranks = match sort {
+                ThreadSort::Hot | ThreadSort::Controversial => voting_contract.is_some(),
+                ThreadSort::Top | ThreadSort::Active => true,
+                ThreadSort::New => false,
+            };
+            if ranks {
+                let ranked = Self::rank_threads(
+                    env,
+                    board_id,
+                    sort,
+                    &voting_contract,
+                    viewer,
+                    viewer_can_moderate,
+                    (limit - shown) as u32,
+                );
+                if ranked.is_empty() && sort == ThreadSort::Controversial {
+                    md = md.paragraph("No threads have enough votes to be controversial yet.");
+                }
+                for thread in ranked.iter() {
+                    md = Self::render_thread_card(
+                        env,
+                        md,
+                        board_id,
+                        &base_path,
+                        &thread,
+                        &voting_contract,
+                        &flairs,
+                        &tags,
+                        &blocked,
+                        &saved,
+                        &read_marks,
+                    );
+                }
+            } else {
+                // Then render remaining threads (newest first), skipping pinned ones
+                let start_idx = thread_count - 1;
+                let mut idx = start_idx;
+                while shown < limit && idx < thread_count {
+                    if let Some(thread) = env
+                        .storage()
+                        .persistent()
+                        .get::<_, ThreadMeta>(&BoardKey::BoardThread(board_id, idx))
+                    {
+                        // Skip pinned threads (already shown above)
+                        if thread.is_pinned {
+                            if idx > 0 {
+                                idx -= 1;
+                            } else {
+                                break;
+                            }
+                            continue;
+                        }
+                        // Skip hidden threads for non-moderators (authors still see their own)
+                        if thread.is_hidden
+                            && !viewer_can_moderate
+                            && viewer.as_ref() != Some(&thread.creator)
+                        {
+                            if idx > 0 {
+                                idx -= 1;
+                            } else {
+                                break;
+                            }
+                            continue;
+                        }
+                        md = Self::render_thread_card(
+                            env,
+                            md,
+                            board_id,
+                            &base_path,
+                            &thread,
+                            &voting_contract,
+                            &flairs,
+                            &tags,
+                            &blocked,
+                            &saved,
+                            &read_marks,
+                        );
+                        shown += 1;
+                    }
+                    if idx > 0 {
+                        idx -= 1;
+                    } else {
+                        break;
+                    }
+                }
+            }
+            md = md.div_end();
+        }
+
+        // Members of private boards can leave; owners must transfer ownership first
+        if config.is_private
+            && (viewer_role as u32) >= (Role::Member as u32)
+            && viewer_role != Role::Owner
+        {
+            md = md
+                .raw_str("<details><summary>Leave board</summary>\n")
+                .note("You will lose access to this board and need to request an invite to return.")
+                .raw_str("<div data-form>\n")
+                .raw_str("<input type=\"hidden\" name=\"board_id\" value=\"")
+                .number(board_id as u32)
+                .raw_str("\" />\n")
+                .raw_str("<input type=\"hidden\" name=\"_redirect\" value=\"")
+                .raw(base_path.clone())
+                .raw_str("\" />\n")
+                .input("confirm", "Board name or CONFIRM")
+                .newline()
+                .form_link_to("Leave Board", "admin", "leave_board")
+                .raw_str("\n</div>\n</details>\n");
+        }
+
+        Self::render_footer_into(env, md).build()
+    }
+
+    /// Parse a ?sort= query value
+    fn parse_thread_sort(value: &Bytes) -> Option<ThreadSort> {
+        let mut buf = [0u8; 16];
+        if value.len() as usize > buf.len() {
+            return None;
+        }
+        let len = value.len() as usize;
+        value.copy_into_slice(&mut buf[..len]);
+        match &buf[..len] {
+            b"hot" => Some(ThreadSort::Hot),
+            b"new" => Some(ThreadSort::New),
+            b"top" => Some(ThreadSort::Top),
+            b"active" => Some(ThreadSort::Active),
+            b"controversial" => Some(ThreadSort::Controversial),
+            _ => None,
+        }
+    }
+
+    /// Parse a reply sort from a thread's ?sort= query or batch path
+    fn parse_reply_sort(value: &Bytes) -> Option<ReplySort> {
+        let mut buf = [0u8; 8];
+        if value.len() as usize > buf.len() {
+            return None;
+        }
+        let len = value.len() as usize;
+        value.copy_into_slice(&mut buf[..len]);
+        match &buf[..len] {
+            b"oldest" => Some(ReplySort::Oldest),
+            b"newest" => Some(ReplySort::Newest),
+            b"top" => Some(ReplySort::Top),
+            _ => None,
+        }
+    }
+
+    /// Path segment for a reply sort
+    fn reply_sort_name(sort: ReplySort) -> &'static str {
+        match sort {
+            ReplySort::Oldest => "oldest",
+            ReplySort::Newest => "newest",
+            ReplySort::Top => "top",
+        }
+    }
+
+    /// Pick up to `count` visible, unpinned threads from the most recent
+    /// MAX_FILTER_SCAN, ordered by hot score (Hot), vote score (Top), last
+    /// activity (Active) or up/down split (Controversial, which leaves out
+    /// threads below the board's minimum vote count). Ties keep the newer
+    /// thread first.
+    fn rank_threads(
+        env: &Env,
+        board_id: u64,
+        sort: ThreadSort,
+        voting_contract: &Option<Address>,
+        viewer: &Option<Address>,
+        viewer_can_moderate: bool,
+        count: u32,
+    ) -> Vec<ThreadMeta> {
+        let thread_count: u64 = env
+            .storage()
+            .persistent()
+            .get(&BoardKey::BoardThreadCount(board_id))
+            .unwrap_or(0);
+        let scan_end = thread_count.saturating_sub(MAX_FILTER_SCAN);
+
+        let mut candidates: Vec<ThreadMeta> = Vec::new(env);
+        let mut keys: Vec<i64> = Vec::new(env);
+        let mut idx = thread_count;
+        while idx > scan_end {
+            idx -= 1;
+            let Some(thread) = env
+                .storage()
+                .persistent()
+                .get::<_, ThreadMeta>(&BoardKey::BoardThread(board_id, idx))
+            else {
+                continue;
+            };
+            if thread.is_pinned
+                || (thread.is_hidden
+                    && !viewer_can_moderate
+                    && viewer.as_ref() != Some(&thread.creator))
+            {
+                continue;
+            }
+            let key = match (sort, voting_contract) {
+                (ThreadSort::Top, Some(voting)) => {
+                    let args: Vec<Val> =
+                        Vec::from_array(env, [board_id.into_val(env), idx.into_val(env)]);
+                    let tally: VoteTally =
+                        env.invoke_contract(voting, &Symbol::new(env, "get_thread_tally"), args);
+                    tally.score as i64
+                }
+                (ThreadSort::Hot, Some(voting)) => {
+                    let args: Vec<Val> =
+                        Vec::from_array(env, [board_id.into_val(env), idx.into_val(env)]);
+                    let tally: VoteTally =
+                        env.invoke_contract(voting, &Symbol::new(env, "get_thread_tally"), args);
+                    Self::hot_score(tally.score, thread.created_at)
+                }
+                (ThreadSort::Top, None) | (ThreadSort::Controversial, _) => 0,
+                _ => thread.updated_at as i64,
+            };
+            candidates.push_back(thread);
+            keys.push_back(key);
+        }
+
+        // Controversial needs up and down counts, fetched for the whole window at once
+        if let (ThreadSort::Controversial, Some(voting)) = (sort, voting_contract) {
+            let mut thread_ids: Vec<u64> = Vec::new(env);
+            for thread in candidates.iter() {
+                thread_ids.push_back(thread.id);
+            }
+            let tallies: Vec<VoteTally> = env
+                .try_invoke_contract::<Vec<VoteTally>, soroban_sdk::Error>(
+                    voting,
+                    &Symbol::new(env, "get_thread_tallies"),
+                    Vec::from_array(env, [board_id.into_val(env), thread_ids.into_val(env)]),
+                )
+                .ok()
+                .and_then(|r| r.ok())
+                .unwrap_or(Vec::new(env));
+
+            let min_votes = Self::get_controversial_min_votes(env.clone(), board_id).max(1);
+            let mut kept: Vec<ThreadMeta> = Vec::new(env);
+            keys = Vec::new(env);
+            for (thread, tally) in candidates.iter().zip(tallies.iter()) {
+                if tally.upvotes.saturating_add(tally.downvotes) >= min_votes {
+                    kept.push_back(thread);
+                    keys.push_back(Self::controversy(tally.upvotes, tally.downvotes));
+                }
+            }
+            candidates = kept;
+        }
+
+        let mut ranked = Vec::new(env);
+        while ranked.len() < count && !candidates.is_empty() {
+            let mut best = 0;
+            for i in 1..keys.len() {
+                if keys.get(i).unwrap() > keys.get(best).unwrap() {
+                    best = i;
+                }
+            }
+            ranked.push_back(candidates.get(best).unwrap());
+            candidates.remove(best);
+            keys.remove(best);
+        }
+        ranked
+    }
+
+    /// How evenly a thread's votes are split, weighted by how many there are:
+    /// total * min(up, down) / max(up, down), scaled by 1000 (0 when unanimous)
+    fn controversy(upvotes: u32, downvotes: u32) -> i64 {
+        let max = upvotes.max(downvotes) as i64;
+        if max == 0 {
+            return 0;
+        }
+        let total = upvotes as i64 + downvotes as i64;
+        total * upvotes.min(downvotes) as i64 * 1000 / max
+    }
+
+    /// Hot score in HOT_SCALE fixed point: the signed log10 of the vote score
+    /// plus the creation time in units of HOT_DECAY_SECONDS. Each vote counts
+    /// for less than the one before it, and a thread needs ten times the score
+    /// to hold its place against one posted 12.5 hours later.
+    fn hot_score(score: i32, created_at: u64) -> i64 {
+        let order = Self::log10_fixed(score.unsigned_abs().max(1)) as i64;
+        order * score.signum() as i64 + (created_at * HOT_SCALE / HOT_DECAY_SECONDS) as i64
+    }
+
+    /// log10(n) in HOT_SCALE fixed point, from a binary logarithm with ten
+    /// fractional bits (no floats in no_std)
+    fn log10_fixed(n: u32) -> u64 {
+        let int_bits = 31 - n.leading_zeros();
+        let mut log2 = (int_bits as u64) << 10;
+
+        // Normalize n to [1, 2) in Q30, then square to extract each fractional bit
+        let mut x = ((n as u64) << 30) >> int_bits;
+        for bit in (0..10).rev() {
+            x = (x * x) >> 30;
+            if x >= 2 << 30 {
+                x >>= 1;
+                log2 |= 1 << bit;
+            }
+        }
+
+        // log10(n) = log2(n) * log10(2), with log10(2) ~= 0.30103
+        log2 * 30_103 * HOT_SCALE / (100_000 << 10)
+    }
+
+    /// Render the viewer's subscribed threads for a board
+    fn render_subscriptions(env: &Env, board_id: u64, viewer: &Option<Address>) -> Bytes {
+        let board_meta: BoardMeta = env
+            .storage()
+            .persistent()
+            .get(&BoardKey::Board(board_id))
+            .expect("Board not found");
+        let base_path = Self::build_board_base_path(env, board_id, &board_meta.slug);
+
+        let mut md = Self::render_nav(env, board_id, viewer);
+        md = Self::render_back_nav(env, md, board_id);
+        md = md.h1("Subscribed Threads");
+
+        let Some(user) = viewer else {
+            md = md.warning("Please connect your wallet to view your subscriptions.");
+            return Self::render_footer_into(env, md).build();
+        };
+
+        let voting_contract: Option<Address> = env.storage().instance().get(&BoardKey::Voting);
+        let blocked = Self::blocked_for(env, viewer);
+        let saved = Self::saved_for(env, viewer);
+        let read_marks = Self::read_marks_for(env, board_id, viewer);
+        let flairs: Vec<FlairDef> = env
+            .storage()
+            .persistent()
+            .get(&BoardKey::BoardFlairDefs(board_id))
+            .unwrap_or(Vec::new(env));
+        let tags = Self::list_tags(env.clone(), board_id);
+
+        let threads = Self::list_subscriptions(env.clone(), board_id, user.clone(), 0, 50);
+        if threads.is_empty() {
+            md = md.paragraph("You are not subscribed to any threads on this board.");
+        } else {
+            md = md.div_start("thread-list");
+            for thread in threads.iter() {
+                md = Self::render_thread_card(
+                    env,
+                    md,
+                    board_id,
+                    &base_path,
+                    &thread,
+                    &voting_contract,
+                    &flairs,
+                    &tags,
+                    &blocked,
+                    &saved,
+                    &read_marks,
+                );
+            }
+            md = md.div_end();
+        }
+
+        Self::render_footer_into(env, md).build()
+    }
+
+    /// Render the viewer's saved threads, grouped by board. Threads that were
+    /// deleted or hidden since render as tombstones with a remove link.
+    fn render_saved(env: &Env, board_id: u64, page: u32, viewer: &Option<Address>) -> Bytes {
+        let board_meta: BoardMeta = env
+            .storage()
+            .persistent()
+            .get(&BoardKey::Board(board_id))
+            .expect("Board not found");
+        let base_path = Self::build_board_base_path(env, board_id, &board_meta.slug);
+
+        let mut md = Self::render_nav(env, board_id, viewer);
+        md = Self::render_back_nav(env, md, board_id);
+        md = md.h1("Saved Threads");
+
+        let saved = Self::saved_for(env, viewer);
+        let Some(ref all) = saved else {
+            md = md.warning("Please connect your wallet to view your saved threads.");
+            return Self::render_footer_into(env, md).build();
+        };
+        let page = page.max(1);
+        let start = (page - 1).saturating_mul(SAVED_PAGE_SIZE).min(all.len());
+        let end = start.saturating_add(SAVED_PAGE_SIZE).min(all.len());
+        let entries = all.slice(start..end);
+        if entries.is_empty() {
+            md = md.paragraph("You haven't saved any threads yet.");
+            return Self::render_footer_into(env, md).build();
+        }
+
+        let voting_contract: Option<Address> = env.storage().instance().get(&BoardKey::Voting);
+        let blocked = Self::blocked_for(env, viewer);
+
+        // One section per board, in order of each board's newest save on this page
+        let mut boards: Vec<u64> = Vec::new(env);
+        for (saved_board, _) in entries.iter() {
+            if !boards.contains(saved_board) {
+                boards.push_back(saved_board);
+            }
+        }
+        for saved_board in boards.iter() {
+            let meta: Option<BoardMeta> = env
+                .storage()
+                .persistent()
+                .get(&BoardKey::Board(saved_board));
+            let board_path = match meta {
+                Some(ref meta) => {
+                    md = md
+                        .raw_str("<h2>")
+                        .text_string(&meta.name)
+                        .raw_str("</h2>\n");
+                    Self::build_board_base_path(env, saved_board, &meta.slug)
+                }
+                None => {
+                    md = md.raw_str("<h2>Deleted board</h2>\n");
+                    Bytes::new(env)
+                }
+            };
+            let flairs: Vec<FlairDef> = env
+                .storage()
+                .persistent()
+                .get(&BoardKey::BoardFlairDefs(saved_board))
+                .unwrap_or(Vec::new(env));
+            let tags = Self::list_tags(env.clone(), saved_board);
+            let read_marks = Self::read_marks_for(env, saved_board, viewer);
+
+            md = md.div_start("thread-list");
+            for (entry_board, thread_id) in entries.iter() {
+                if entry_board != saved_board {
+                    continue;
+                }
+                let thread = env
+                    .storage()
+                    .persistent()
+                    .get::<_, ThreadMeta>(&BoardKey::BoardThread(saved_board, thread_id))
+                    .filter(|t| !t.is_deleted && !t.is_hidden && meta.is_some());
+                match thread {
+                    Some(thread) => {
+                        md = Self::render_thread_card(
+                            env,
+                            md,
+                            saved_board,
+                            &board_path,
+                            &thread,
+                            &voting_contract,
+                            &flairs,
+                            &tags,
+                            &blocked,
+                            &saved,
+                            &read_marks,
+                        );
+                    }
+                    None => {
+                        md = md
+                            .raw_str("<div class=\"thread-card-wrapper saved-tombstone\">")
+                            .raw_str("<span class=\"thread-card\">This thread is no longer available</span>");
+                        md = Self::render_save_toggle(md, saved_board, thread_id, true)
+                            .raw_str("</div>\n");
+                    }
+                }
+            }
+            md = md.div_end();
+        }
+
+        // Pagination
+        if page > 1 || end < all.len() {
+            md = md.div_start("pagination");
+            if page > 1 {
+                md = md
+                    .raw_str("<a href=\"render:")
+                    .raw(base_path.clone())
+                    .raw_str("/saved/")
+                    .number(page - 1)
+                    .raw_str("\">← Newer</a> ");
+            }
+            if end < all.len() {
+                md = md
+                    .raw_str("<a href=\"render:")
+                    .raw(base_path)
+                    .raw_str("/saved/")
+                    .number(page + 1)
+                    .raw_str("\">Older →</a>");
+            }
+            md = md.div_end();
+        }
+
+        Self::render_footer_into(env, md).build()
+    }
+
+    /// Save/unsave toggle for a thread, shown in its header and on thread cards
+    fn render_save_toggle<'a>(
+        md: MarkdownBuilder<'a>,
+        board_id: u64,
+        thread_id: u64,
+        is_saved: bool,
+    ) -> MarkdownBuilder<'a> {
+        let md = md
+            .raw_str("<a href=\"tx:@board:")
+            .raw_str(if is_saved {
+                "unsave_thread"
+            } else {
+                "save_thread"
+            })
+            .raw_str(" {&quot;board_id&quot;:")
+            .number(board_id as u32)
+            .raw_str(",&quot;thread_id&quot;:")
+            .number(thread_id as u32)
+            .raw_str("}\" class=\"save-toggle");
+        if is_saved {
+            md.raw_str(" saved\" title=\"Remove from saved\">Saved</a>")
+        } else {
+            md.raw_str("\" title=\"Save for later\">Save</a>")
+        }
+    }
+
+    /// Render the viewer's recent mentions on this board
+    fn render_mentions(env: &Env, board_id: u64, viewer: &Option<Address>) -> Bytes {
+        let config: BoardConfig = env
+            .storage()
+            .persistent()
+            .get(&BoardKey::BoardConfig(board_id))
+            .expect("Board not found");
+
+        // Private boards show mentions to members only
+        if config.is_private
+            && (Self::viewer_permissions(env, board_id, viewer).role as u32) < (Role::Member as u32)
+        {
+            if let Some(perms_addr) = env
+                .storage()
+                .instance()
+                .get::<_, Address>(&BoardKey::Permissions)
+            {
+                return Self::render_private_board_message(
+                    env,
+                    board_id,
+                    &config,
+                    viewer,
+                    &perms_addr,
+                );
+            }
+        }
+
+        let board_meta: BoardMeta = env
+            .storage()
+            .persistent()
+            .get(&BoardKey::Board(board_id))
+            .expect("Board not found");
+        let base_path = Self::build_board_base_path(env, board_id, &board_meta.slug);
+
+        let mut md = Self::render_nav(env, board_id, viewer);
+        md = Self::render_back_nav(env, md, board_id);
+        md = md.h1("Mentions");
+
+        let Some(user) = viewer else {
+            md = md.warning("Please connect your wallet to view your mentions.");
+            return Self::render_footer_into(env, md).build();
+        };
+
+        // The index spans all boards; older content contracts don't have it
+        let mentions: Vec<Mention> = match env
+            .storage()
+            .instance()
+            .get::<_, Address>(&BoardKey::Content)
+        {
+            Some(content) => env
+                .try_invoke_contract::<Vec<Mention>, soroban_sdk::Error>(
+                    &content,
+                    &Symbol::new(env, "list_mentions"),
+                    Vec::from_array(
+                        env,
+                        [user.into_val(env), 0u32.into_val(env), 100u32.into_val(env)],
+                    ),
+                )
+                .ok()
+                .and_then(|r| r.ok())
+                .unwrap_or(Vec::new(env)),
+            None => Vec::new(env),
+        };
+
+        let profile_contract = Self::get_profile_contract(env);
+        let mut shown = 0u32;
+        for mention in mentions.iter() {
+            if shown >= MENTIONS_PAGE_SIZE {
+                break;
+            }
+            if mention.board_id != board_id {
+                continue;
+            }
+            // Skip mentions in threads that are no longer visible
+            let Some(thread) = env
+                .storage()
+                .persistent()
+                .get::<_, ThreadMeta>(&BoardKey::BoardThread(board_id, mention.thread_id))
+            else {
+                continue;
+            };
+            if thread.is_hidden || thread.is_deleted {
+                continue;
+            }
+
+            md = md
+                .div_start("mention-item")
+                .raw_str("<a href=\"render:")
+                .raw(base_path.clone())
+                .raw_str("/t/")
+                .number(mention.thread_id as u32);
+            if let Some(reply_id) = mention.reply_id {
+                md = md.raw_str("/r/").number(reply_id as u32);
+            }
+            md = md
+                .raw_str("\">")
+                .text_string(&thread.title)
+                .raw_str("</a>")
+                .raw_str(if mention.reply_id.is_some() {
+                    " (reply)"
+                } else {
+                    " (thread)"
+                })
+                .raw_str("<div class=\"mention-meta\">by ");
+            md = Self::render_author(env, md, board_id, &mention.author, &profile_contract, None);
+            md = md
+                .raw_str(" · ")
+                .raw(Self::format_board_timestamp(
+                    env,
+                    board_id,
+                    mention.created_at,
+                ))
+                .raw_str("</div>")
+                .div_end();
+            shown += 1;
+        }
+
+        if shown == 0 {
+            md = md.paragraph("Nobody has mentioned you on this board yet.");
+        }
+
+        Self::render_footer_into(env, md).build()
+    }
+
+    /// Render a user's recent threads and replies on this board as one timeline
+    fn render_user_activity(
+        env: &Env,
+        board_id: u64,
+        address: &Bytes,
+        viewer: &Option<Address>,
+    ) -> Bytes {
+        let md = Self::render_nav(env, board_id, viewer).newline();
+        let mut md =
+            Self::render_breadcrumbs(env, md, board_id, None, Some("Activity")).h1("Activity");
+
+        // Moderators also see hidden and deleted posts
+        let viewer_can_moderate = match Self::export_access(env, board_id, viewer) {
+            Ok(can_moderate) => can_moderate,
+            Err(reason) => {
+                md = md.warning(if reason == "private" {
+                    "This board is private."
+                } else {
+                    "Board not found."
+                });
+                return Self::render_footer_into(env, md).build();
+            }
+        };
+        // Malformed addresses would trap in from_string_bytes
+        if !is_valid_address(address) {
+            md = md.warning("User not found.");
+            return Self::render_footer_into(env, md).build();
+        }
+        let user = Address::from_string_bytes(address);
+        let board_meta: BoardMeta = env
+            .storage()
+            .persistent()
+            .get(&BoardKey::Board(board_id))
+            .expect("Board not found");
+        let base_path = Self::build_board_base_path(env, board_id, &board_meta.slug);
+        let content: Option<Address> = env.storage().instance().get(&BoardKey::Content);
+
+        let profile_contract = Self::get_profile_contract(env);
+        md = md.raw_str("<p>Recent posts by ");
+        md = Self::render_author(env, md, board_id, &user, &profile_contract, None)
+            .raw_str("</p>\n");
+
+        let items = Self::list_recent_activity(env.clone(), board_id, user, ACTIVITY_PAGE_SIZE);
+        let mut shown = 0u32;
+        for item in items.iter() {
+            if item.is_hidden && !viewer_can_moderate {
+                continue;
+            }
+            let title = env
+                .storage()
+                .persistent()
+                .get::<_, ThreadMeta>(&BoardKey::BoardThread(board_id, item.thread_id))
+                .map(|thread| thread.title)
+                .unwrap_or(String::from_str(env, "a deleted thread"));
+
+            md = md
+                .div_start("activity-item")
+                .raw_str(if item.reply_id.is_some() {
+                    "<span class=\"badge badge-reply\">reply</span> "
+                } else {
+                    "<span class=\"badge badge-thread\">thread</span> "
+                });
+            if item.is_hidden {
+                md = md.raw_str("<span class=\"badge badge-hidden\">hidden</span> ");
+            }
+            md = md
+                .raw_str("<a href=\"render:")
+                .raw(base_path.clone())
+                .raw_str("/t/")
+                .number(item.thread_id as u32);
+            if let Some(reply_id) = item.reply_id {
+                md = md.raw_str("/r/").number(reply_id as u32);
+            }
+            md = md.raw_str("\">").text_string(&title).raw_str("</a>");
+
+            if let (Some(reply_id), Some(content)) = (item.reply_id, &content) {
+                let body: Bytes = env.invoke_contract(
+                    content,
+                    &Symbol::new(env, "get_reply_content"),
+                    Vec::from_array(
+                        env,
+                        [
+                            board_id.into_val(env),
+                            item.thread_id.into_val(env),
+                            reply_id.into_val(env),
+                        ],
+                    ),
+                );
+                md = md
+                    .raw_str("<div class=\"activity-excerpt\">")
+                    .raw(escape_html(env, &Self::activity_excerpt(env, &body)))
+                    .raw_str("</div>");
+            }
+            md = md
+                .raw_str("<div class=\"activity-meta\">")
+                .raw(Self::format_board_timestamp(env, board_id, item.created_at))
+                .raw_str("</div>")
+                .div_end();
+            shown += 1;
+        }
+
+        if shown == 0 {
+            md = md.paragraph("No posts on this board yet.");
+        }
+
+        Self::render_footer_into(env, md).build()
+    }
+
+    /// First line of a reply, cut to ACTIVITY_EXCERPT_LEN bytes on a character boundary
+    fn activity_excerpt(env: &Env, body: &Bytes) -> Bytes {
+        let line_end = body
+            .iter()
+            .position(|b| b == b'\n')
+            .unwrap_or(body.len() as usize);
+        let end = char_floor(body, (line_end as u32).min(ACTIVITY_EXCERPT_LEN));
+        let mut excerpt = body.slice(0..end);
+        if end < body.len() {
+            excerpt.append(&Bytes::from_slice(env, "…".as_bytes()));
+        }
+        excerpt
+    }
+
+    /// Unread notification count for the nav bell (0 if the content contract has no inbox)
+    fn unread_notifications(env: &Env, user: &Address) -> u32 {
+        let Some(content) = env
+            .storage()
+            .instance()
+            .get::<_, Address>(&BoardKey::Content)
+        else {
+            return 0;
+        };
+        env.try_invoke_contract::<u32, soroban_sdk::Error>(
+            &content,
+            &Symbol::new(env, "get_unread_count"),
+            Vec::from_array(env, [user.into_val(env)]),
+        )
+        .ok()
+        .and_then(|r| r.ok())
+        .unwrap_or(0)
+    }
+
+    /// Render the viewer's notifications from every board, newest first
+    fn render_notifications(env: &Env, board_id: u64, viewer: &Option<Address>) -> Bytes {
+        let mut md = Self::render_nav(env, board_id, viewer);
+        md = Self::render_back_nav(env, md, board_id);
+        md = md.h1("Notifications");
+
+        let Some(user) = viewer else {
+            md = md.warning("Please connect your wallet to view your notifications.");
+            return Self::render_footer_into(env, md).build();
+        };
+
+        let notifications: Vec<Notification> = match env
+            .storage()
+            .instance()
+            .get::<_, Address>(&BoardKey::Content)
+        {
+            Some(content) => env
+                .try_invoke_contract::<Vec<Notification>, soroban_sdk::Error>(
+                    &content,
+                    &Symbol::new(env, "list_notifications"),
+                    Vec::from_array(
+                        env,
+                        [
+                            user.into_val(env),
+                            0u32.into_val(env),
+                            NOTIFICATIONS_PAGE_SIZE.into_val(env),
+                        ],
+                    ),
+                )
+                .ok()
+                .and_then(|r| r.ok())
+                .unwrap_or(Vec::new(env)),
+            None => Vec::new(env),
+        };
+        let Some(newest) = notifications.first() else {
+            md = md.paragraph("You have no notifications yet.");
+            return Self::render_footer_into(env, md).build();
+        };
+
+        // The list is newest first, so the unread ones lead it
+        let unread = Self::unread_notifications(env, user);
+        if unread > 0 {
+            md = md
+                .raw_str("<a href=\"tx:@content:mark_read {&quot;up_to_id&quot;:")
+                .raw(u64_to_bytes(env, newest.id))
+                .raw_str("}\" class=\"action-btn action-btn-secondary\">Mark all read</a>")
+                .newline();
+        }
+
+        let profile_contract = Self::get_profile_contract(env);
+        for (index, item) in notifications.iter().enumerate() {
+            let Some(meta) = env
+                .storage()
+                .persistent()
+                .get::<_, BoardMeta>(&BoardKey::Board(item.board_id))
+            else {
+                continue;
+            };
+            let base_path = Self::build_board_base_path(env, item.board_id, &meta.slug);
+
+            md = md.raw_str(if (index as u32) < unread {
+                "<div class=\"notification-item unread\">"
+            } else {
+                "<div class=\"notification-item\">"
+            });
+            let kind = item.kind.clone();
+            if kind == Symbol::new(env, "banned") {
+                md = md
+                    .raw_str("You were banned from <a href=\"render:")
+                    .raw(base_path)
+                    .raw_str("\">")
+                    .text_string(&meta.name)
+                    .raw_str("</a>");
+            } else {
+                let title = env
+                    .storage()
+                    .persistent()
+                    .get::<_, ThreadMeta>(&BoardKey::BoardThread(item.board_id, item.thread_id))
+                    .map(|thread| thread.title)
+                    .unwrap_or(String::from_str(env, "a deleted thread"));
+                if kind == Symbol::new(env, "hidden") {
+                    md = md.raw_str(if item.reply_id.is_some() {
+                        "A moderator hid your reply in "
+                    } else {
+                        "A moderator hid your thread "
+                    });
+                } else {
+                    md = Self::render_author(
+                        env,
+                        md,
+                        item.board_id,
+                        &item.actor,
+                        &profile_contract,
+                        None,
+                    );
+                    md = md.raw_str(if kind == Symbol::new(env, "thread_reply") {
+                        " replied to your thread "
+                    } else {
+                        " replied to your reply in "
+                    });
+                }
+                md = md
+                    .raw_str("<a href=\"render:")
+                    .raw(base_path)
+                    .raw_str("/t/")
+                    .number(item.thread_id as u32);
+                if let Some(reply_id) = item.reply_id {
+                    md = md.raw_str("/r/").number(reply_id as u32);
+                }
+                md = md.raw_str("\">").text_string(&title).raw_str("</a>");
+            }
+            md = md
+                .raw_str("<div class=\"notification-meta\">")
+                .raw(Self::format_board_timestamp(env, board_id, item.created_at))
+                .raw_str("</div></div>\n");
+        }
+
+        Self::render_footer_into(env, md).build()
+    }
+
+    /// Render the board view styled with a catalog theme, leaving the board's theme unchanged
+    fn render_theme_preview(
+        env: &Env,
+        board_id: u64,
+        raw_name: &Bytes,
+        viewer: &Option<Address>,
+    ) -> Bytes {
+        let len = raw_name.len() as usize;
+        let mut buf = [0u8; MAX_THEME_NAME_LEN];
+        let preview = if len <= MAX_THEME_NAME_LEN {
+            raw_name.copy_into_slice(&mut buf[..len]);
+            let name = String::from_bytes(env, &buf[..len]);
+            Self::lookup_theme(env, &name).map(|theme| (name, theme))
+        } else {
+            None
+        };
+        let Some(preview) = preview else {
+            let mut md = Self::render_nav(env, board_id, viewer);
+            md = Self::render_back_nav(env, md, board_id);
+            md = md.warning("Theme not found.");
+            return Self::render_footer_into(env, md).build();
+        };
+
+        env.storage()
+            .temporary()
+            .set(&BoardKey::RenderPreviewTheme, &preview);
+        let page = Self::render_board(env, board_id, viewer, None);
+        env.storage()
+            .temporary()
+            .remove(&BoardKey::RenderPreviewTheme);
+        page
+    }
+
+    /// Render the form for claiming a board-local display name
+    fn render_display_name(env: &Env, board_id: u64, viewer: &Option<Address>) -> Bytes {
+        let md = Self::render_nav(env, board_id, viewer).newline();
+        let mut md = Self::render_breadcrumbs(env, md, board_id, None, Some("Display Name"))
+            .h1("Display Name");
+
+        if Self::has_profile_contract(env) {
+            md = md.paragraph("Names come from user profiles on this site.");
+            return Self::render_footer_into(env, md).build();
+        }
+        let Some(user) = viewer else {
+            md = md.warning("Please connect your wallet to choose a display name.");
+            return Self::render_footer_into(env, md).build();
+        };
+
+        md = match Self::get_display_name(env.clone(), board_id, user.clone()) {
+            Some(name) => md
+                .raw_str("<p>Your name on this board: <strong>")
+                .text_string(&name)
+                .raw_str("</strong></p>\n"),
+            None => md.paragraph("You haven't chosen a name on this board yet."),
+        };
+        md = md
+            .note("3-20 letters, numbers or underscores. Names can be changed once every 7 days.")
+            .raw_str("<div data-form>\n")
+            .raw_str("<input type=\"hidden\" name=\"board_id\" value=\"")
+            .number(board_id as u32)
+            .raw_str("\" />\n")
+            .input("name", "Display name")
+            .newline()
+            .form_link_to("Save Name", "board", "claim_display_name")
+            .raw_str("\n</div>\n");
+
+        Self::render_footer_into(env, md).build()
+    }
+
+    /// Render the last 30 days of new threads and replies, with a bar per day
+    fn render_stats(env: &Env, board_id: u64, viewer: &Option<Address>) -> Bytes {
+        let config: BoardConfig = env
+            .storage()
+            .persistent()
+            .get(&BoardKey::BoardConfig(board_id))
+            .expect("Board not found");
+
+        // Private boards show their stats to members only
+        if config.is_private {
+            if let Some(perms_addr) = env
+                .storage()
+                .instance()
+                .get::<_, Address>(&BoardKey::Permissions)
+            {
+                let role = match viewer {
+                    Some(user) => env.invoke_contract(
+                        &perms_addr,
+                        &Symbol::new(env, "get_role"),
+                        Vec::from_array(env, [board_id.into_val(env), user.into_val(env)]),
+                    ),
+                    None => Role::Guest,
+                };
+                if (role as u32) < (Role::Member as u32) {
+                    return Self::render_private_board_message(
+                        env,
+                        board_id,
+                        &config,
+                        viewer,
+                        &perms_addr,
+                    );
+                }
+            }
+        }
+
+        let mut md = Self::render_nav(env, board_id, viewer);
+        md = Self::render_back_nav(env, md, board_id);
+        md = md.h1("Board Stats");
+
+        let today = env.ledger().timestamp() / 86_400;
+        let from_day = (today + 1).saturating_sub(STATS_PAGE_DAYS);
+        let days = Self::get_stats_range(env.clone(), board_id, from_day, today);
+
+        let mut total_threads = 0u32;
+        let mut total_replies = 0u32;
+        let mut busiest = 0u32;
+        for stats in days.iter() {
+            total_threads = total_threads.saturating_add(stats.threads);
+            total_replies = total_replies.saturating_add(stats.replies);
+            busiest = busiest.max(stats.threads.saturating_add(stats.replies));
+        }
+
+        md = md
+            .paragraph("New threads and replies per day over the last 30 days (UTC).")
+            .raw_str("**Totals:** ")
+            .number(total_threads)
+            .text(" threads · ")
+            .number(total_replies)
+            .text(" replies")
+            .newline()
+            .newline()
+            .raw_str("| Day | Threads | Replies | |\n")
+            .raw_str("|---|---:|---:|---|\n");
+
+        // Most recent day first
+        for (i, stats) in days.iter().enumerate().rev() {
+            let posts = stats.threads.saturating_add(stats.replies);
+            let bar_len = if busiest == 0 {
+                0
+            } else {
+                // Any activity gets at least one block
+                (posts * STATS_BAR_WIDTH).div_ceil(busiest)
+            };
+            md = md
+                .raw_str("| ")
+                .raw(Self::format_day(env, from_day + i as u64))
+                .raw_str(" | ")
+                .number(stats.threads)
+                .raw_str(" | ")
+                .number(stats.replies)
+                .raw_str(" | <span class=\"stats-bar\">");
+            for _ in 0..bar_len {
+                md = md.raw_str("\u{2588}");
+            }
+            md = md.raw_str("</span> |\n");
+        }
+
+        Self::render_footer_into(env, md).build()
+    }
+
+    /// The top `DIGEST_THREADS` visible threads created in the last `days` days, with
+    /// their scores. Ranked by score when a voting contract answers, otherwise by reply
+    /// count (the first flag says which). Only the newest `MAX_FILTER_SCAN` threads
+    /// are considered; the second flag is set when that cut the period short.
+    fn digest_threads(env: &Env, board_id: u64, days: u64) -> (Vec<(ThreadMeta, i32)>, bool, bool) {
+        let thread_count: u64 = env
+            .storage()
+            .persistent()
+            .get(&BoardKey::BoardThreadCount(board_id))
+            .unwrap_or(0);
+        let scan_end = thread_count.saturating_sub(MAX_FILTER_SCAN);
+        let cutoff = env.ledger().timestamp().saturating_sub(days * 86_400);
+
+        let mut candidates: Vec<ThreadMeta> = Vec::new(env);
+        let mut thread_ids: Vec<u64> = Vec::new(env);
+        // Older threads exist past the window unless the cutoff is reached first
+        let mut truncated = scan_end > 0;
+        let mut idx = thread_count;
+        while idx > scan_end {
+            idx -= 1;
+            let Some(thread) = env
+                .storage()
+                .persistent()
+                .get::<_, ThreadMeta>(&BoardKey::BoardThread(board_id, idx))
+            else {
+                continue;
+            };
+            // Thread ids follow creation order, so everything older is outside the window too
+            if thread.created_at < cutoff {
+                truncated = false;
+                break;
+            }
+            if thread.is_hidden || thread.is_deleted {
+                continue;
+            }
+            thread_ids.push_back(thread.id);
+            candidates.push_back(thread);
+        }
+
+        let tallies: Option<Vec<VoteTally>> = env
+            .storage()
+            .instance()
+            .get::<_, Address>(&BoardKey::Voting)
+            .and_then(|voting| {
+                env.try_invoke_contract::<Vec<VoteTally>, soroban_sdk::Error>(
+                    &voting,
+                    &Symbol::new(env, "get_thread_tallies"),
+                    Vec::from_array(env, [board_id.into_val(env), thread_ids.into_val(env)]),
+                )
+                .ok()
+                .and_then(|r| r.ok())
+            })
+            .filter(|tallies| tallies.len() == candidates.len());
+        let by_score = tallies.is_some();
+
+        let mut keys: Vec<i64> = Vec::new(env);
+        let mut scores: Vec<i32> = Vec::new(env);
+        for (i, thread) in candidates.iter().enumerate() {
+            let score = tallies
+                .as_ref()
+                .map(|t| t.get_unchecked(i as u32).score)
+                .unwrap_or(0);
+            scores.push_back(score);
+            keys.push_back(if by_score {
+                score as i64
+            } else {
+                thread.reply_count as i64
+            });
+        }
+
+        // Candidates run newest first, so ties go to the newer thread
+        let mut ranked = Vec::new(env);
+        while ranked.len() < DIGEST_THREADS && !candidates.is_empty() {
+            let mut best = 0;
+            for i in 1..keys.len() {
+                if keys.get_unchecked(i) > keys.get_unchecked(best) {
+                    best = i;
+                }
+            }
+            ranked.push_back((candidates.get_unchecked(best), scores.get_unchecked(best)));
+            candidates.remove(best);
+            keys.remove(best);
+            scores.remove(best);
+        }
+        (ranked, by_score, truncated)
+    }
+
+    /// Render `/digest/{days}`: the board's top threads from the last `days` days
+    fn render_digest(env: &Env, board_id: u64, days: u64, viewer: &Option<Address>) -> Bytes {
+        let config: BoardConfig = env
+            .storage()
+            .persistent()
+            .get(&BoardKey::BoardConfig(board_id))
+            .expect("Board not found");
+
+        let viewer_perms = Self::viewer_permissions(env, board_id, viewer);
+
+        // Private boards show their digest to members only
+        if config.is_private && (viewer_perms.role as u32) < (Role::Member as u32) {
+            if let Some(perms_addr) = env
+                .storage()
+                .instance()
+                .get::<_, Address>(&BoardKey::Permissions)
+            {
+                return Self::render_private_board_message(
+                    env,
+                    board_id,
+                    &config,
+                    viewer,
+                    &perms_addr,
+                );
+            }
+        }
+
+        let meta: BoardMeta = env
+            .storage()
+            .persistent()
+            .get(&BoardKey::Board(board_id))
+            .expect("Board not found");
+        let base_path = Self::build_board_base_path(env, board_id, &meta.slug);
+        let profile_contract = Self::get_profile_contract(env);
+        let (threads, by_score, truncated) = Self::digest_threads(env, board_id, days);
+
+        let mut md = Self::render_nav(env, board_id, viewer);
+        md = Self::render_back_nav(env, md, board_id);
+        md = md
+            .raw_str("<h1>Top threads of the last ")
+            .number(days as u32)
+            .raw_str(if days == 1 { " day" } else { " days" })
+            .raw_str("</h1>\n");
+        if !by_score {
+            md = md.note("Voting isn't enabled on this board, so threads are ranked by replies.");
+        }
+        if truncated {
+            md = md.note(
+                "Only the newest 100 threads were checked, so older threads from this period may be missing.",
+            );
+        }
+
+        if threads.is_empty() {
+            md = md.paragraph("No new threads in this period.");
+        } else {
+            md = md.raw_str("<ol class=\"digest\">\n");
+            for (thread, score) in threads.iter() {
+                md = md
+                    .raw_str("<li><a href=\"render:")
+                    .raw(base_path.clone())
+                    .raw_str("/t/")
+                    .number(thread.id as u32)
+                    .raw_str("\">")
+                    .text_string(&thread.title)
+                    .raw_str("</a> <span class=\"digest-meta\">by ");
+                md = Self::render_author(
+                    env,
+                    md,
+                    board_id,
+                    &thread.creator,
+                    &profile_contract,
+                    None,
+                );
+                if by_score {
+                    md = md
+                        .raw_str(" · ")
+                        .raw_str(if score < 0 { "−" } else { "" })
+                        .number(score.unsigned_abs())
+                        .raw_str(" points");
+                }
+                md = md
+                    .raw_str(" · ")
+                    .number(thread.reply_count)
+                    .raw_str(" replies</span></li>\n");
+            }
+            md = md.raw_str("</ol>\n");
+        }
+
+        md = md
+            .raw_str("<p><a href=\"render:")
+            .raw(base_path)
+            .raw_str("/export/digest/")
+            .number(days as u32)
+            .raw_str("\">Plain markdown</a> for copying into a newsletter or chat.</p>\n");
+
+        Self::render_footer_into(env, md).build()
+    }
+
+    /// Render the wiki index, or the page at `raw_slug`. Viewers who can edit the
+    /// wiki get a new-page form on the index and an edit (or create) form on pages.
+    fn render_wiki(
+        env: &Env,
+        board_id: u64,
+        raw_slug: Option<Bytes>,
+        viewer: &Option<Address>,
+    ) -> Bytes {
+        let config: BoardConfig = env
+            .storage()
+            .persistent()
+            .get(&BoardKey::BoardConfig(board_id))
+            .expect("Board not found");
+        let perms_addr: Option<Address> = env.storage().instance().get(&BoardKey::Permissions);
+        let permissions = Self::viewer_permissions(env, board_id, viewer);
+
+        // Private boards show their wiki to members only
+        if let Some(ref perms_addr) = perms_addr {
+            if config.is_private && (permissions.role as u32) < (Role::Member as u32) {
+                return Self::render_private_board_message(
+                    env, board_id, &config, viewer, perms_addr,
+                );
+            }
+        }
+        let can_edit = viewer.is_some()
+            && (perms_addr.is_none() || Self::wiki_role_allows(env, board_id, &permissions));
+
+        let board_meta: BoardMeta = env
+            .storage()
+            .persistent()
+            .get(&BoardKey::Board(board_id))
+            .expect("Board not found");
+        let base_path = Self::build_board_base_path(env, board_id, &board_meta.slug);
+
+        let md = Self::render_nav(env, board_id, viewer).newline();
+        let mut md = Self::render_breadcrumbs(env, md, board_id, None, Some("Wiki"));
+
+        let Some(raw_slug) = raw_slug else {
+            md = md.h1("Wiki");
+            let pages = Self::list_wiki_pages(env.clone(), board_id);
+            if pages.is_empty() {
+                md = md.paragraph("This board's wiki has no pages yet.");
+            } else {
+                md = md.div_start("wiki-index");
+                for page in pages.iter() {
+                    md = md
+                        .raw_str("<div class=\"wiki-index-item\"><a href=\"render:")
+                        .raw(base_path.clone())
+                        .raw_str("/wiki/")
+                        .text_string(&page.slug)
+                        .raw_str("\">")
+                        .raw(Self::escape_attr(env, &page.title))
+                        .raw_str("</a> <span class=\"wiki-updated\">updated ")
+                        .raw(Self::format_board_timestamp(env, board_id, page.updated_at))
+                        .raw_str("</span></div>\n");
+                }
+                md = md.div_end();
+            }
+            if can_edit && pages.len() < MAX_WIKI_PAGES {
+                md = md
+                    .h2("New Page")
+                    .note("Slugs are up to 32 lowercase letters, digits or dashes.");
+                md = Self::render_wiki_form(env, md, board_id, &base_path, None, None);
+            }
+            return Self::render_footer_into(env, md).build();
+        };
+
+        // Anything that isn't a valid slug can't name a page
+        let len = raw_slug.len() as usize;
+        let mut buf = [0u8; MAX_WIKI_SLUG_LEN];
+        let slug = if len <= MAX_WIKI_SLUG_LEN {
+            raw_slug.copy_into_slice(&mut buf[..len]);
+            Some(String::from_bytes(env, &buf[..len])).filter(|s| Self::is_valid_wiki_slug(s))
+        } else {
+            None
+        };
+        let page = slug
+            .clone()
+            .and_then(|slug| Self::get_wiki_page(env.clone(), board_id, slug));
+
+        match page {
+            Some(ref page) => {
+                let profile_contract = Self::get_profile_contract(env);
+                md = md
+                    .raw_str("<h1>")
+                    .raw(Self::escape_attr(env, &page.title))
+                    .raw_str("</h1>\n")
+                    .div_start("wiki-meta")
+                    .raw_str("Last edited by ");
+                md = Self::render_author(env, md, board_id, &page.editor, &profile_contract, None);
+                md = md
+                    .raw_str(" · ")
+                    .raw(Self::format_board_timestamp(env, board_id, page.updated_at))
+                    .div_end()
+                    .div_start("wiki-body")
+                    .text_string(&page.body)
+                    .div_end()
+                    .newline();
+            }
+            None => {
+                md = md.h1("Wiki").paragraph("This wiki page doesn't exist yet.");
+            }
+        }
+
+        if let Some(ref slug) = slug.filter(|_| can_edit) {
+            md = md.h2(if page.is_some() {
+                "Edit Page"
+            } else {
+                "Create Page"
+            });
+            md = Self::render_wiki_form(env, md, board_id, &base_path, Some(slug), page.as_ref());
+        }
+
+        md = md
+            .raw_str("<a href=\"render:")
+            .raw(base_path)
+            .raw_str("/wiki\">← All wiki pages</a>\n");
+        Self::render_footer_into(env, md).build()
+    }
+
+    /// Render the wiki page form: a fixed slug when editing or creating a named
+    /// page, or a slug field when creating from the index
+    fn render_wiki_form<'a>(
+        env: &Env,
+        mut md: MarkdownBuilder<'a>,
+        board_id: u64,
+        base_path: &Bytes,
+        slug: Option<&String>,
+        page: Option<&WikiPage>,
+    ) -> MarkdownBuilder<'a> {
+        md = md
+            .raw_str("<div data-form>\n")
+            .raw_str("<input type=\"hidden\" name=\"_redirect\" value=\"")
+            .raw(base_path.clone())
+            .raw_str("/wiki");
+        md = match slug {
+            Some(slug) => md
+                .raw_str("/")
+                .text_string(slug)
+                .raw_str("\" />\n")
+                .raw_str("<input type=\"hidden\" name=\"slug\" value=\"")
+                .text_string(slug)
+                .raw_str("\" />\n"),
+            None => md
+                .raw_str("\" />\n")
+                .raw_str("<input type=\"text\" name=\"slug\" placeholder=\"faq\" pattern=\"[a-z0-9\\-]+\" />\n"),
+        };
+        md = md
+            .raw_str("<input type=\"hidden\" name=\"board_id\" value=\"")
+            .number(board_id as u32)
+            .raw_str("\" />\n")
+            .raw_str("<label>Title</label>\n")
+            .raw_str("<input type=\"text\" name=\"title\" value=\"");
+        if let Some(page) = page {
+            md = md.raw(Self::escape_attr(env, &page.title));
+        }
+        md = md
+            .raw_str("\" />\n")
+            .raw_str("<label>Content</label>\n")
+            .raw_str("<textarea name=\"body\" data-editor=\"markdown\" rows=\"12\">");
+        // Escaped so the page can't close the textarea early
+        if let Some(page) = page {
+            let body = soroban_render_sdk::bytes::string_to_bytes(env, &page.body);
+            md = md.raw(escape_html(env, &body));
+        }
+        md.raw_str("</textarea>\n")
+            .newline()
+            .form_link_to("Save Page", "board", "set_wiki_page")
+            .raw_str("\n</div>\n")
+    }
+
+    /// Format a day number (days since the Unix epoch) as "YYYY-MM-DD"
+    fn format_day(env: &Env, day: u64) -> Bytes {
+        let (year, month, day) = Self::days_to_date(day as i64);
+        let buffer = [
+            b'0' + ((year / 1000) % 10) as u8,
+            b'0' + ((year / 100) % 10) as u8,
+            b'0' + ((year / 10) % 10) as u8,
+            b'0' + (year % 10) as u8,
+            b'-',
+            b'0' + (month / 10),
+            b'0' + (month % 10),
+            b'-',
+            b'0' + (day / 10),
+            b'0' + (day % 10),
+        ];
+        Bytes::from_slice(env, &buffer)
+    }
+
+    /// Render the board's top karma earners and the viewer's own standing
+    fn render_leaderboard(env: &Env, board_id: u64, viewer: &Option<Address>) -> Bytes {
+        let mut md = Self::render_nav(env, board_id, viewer);
+        md = Self::render_back_nav(env, md, board_id);
+        md = md.h1("Leaderboard");
+
+        let voting: Option<Address> = env.storage().instance().get(&BoardKey::Voting);
+        let karma_enabled = voting.as_ref().is_some_and(|voting| {
+            env.try_invoke_contract::<VotingConfig, soroban_sdk::Error>(
+                voting,
+                &Symbol::new(env, "get_voting_config"),
+                Vec::from_array(env, [board_id.into_val(env)]),
+            )
+            .ok()
+            .and_then(|r| r.ok())
+            .is_some_and(|c| c.karma_enabled)
+        });
+        let Some(voting) = voting.filter(|_| karma_enabled) else {
+            md = md.paragraph("Karma isn't tracked on this board.");
+            return Self::render_footer_into(env, md).build();
+        };
+
+        // Fetch every tracked entry so the viewer's rank can be found past the shown rows
+        let top: Vec<(Address, i64)> = env
+            .try_invoke_contract::<Vec<(Address, i64)>, soroban_sdk::Error>(
+                &voting,
+                &Symbol::new(env, "top_karma"),
+                Vec::from_array(env, [board_id.into_val(env), u32::MAX.into_val(env)]),
+            )
+            .ok()
+            .and_then(|r| r.ok())
+            .unwrap_or(Vec::new(env));
+
+        if top.is_empty() {
+            md = md.paragraph("Nobody has earned karma on this board yet.");
+        } else {
+            let profile_contract = Self::get_profile_contract(env);
+            md = md.div_start("leaderboard");
+            for (index, (user, karma)) in top.iter().take(LEADERBOARD_SIZE as usize).enumerate() {
+                md = md
+                    .div_start("leaderboard-row")
+                    .raw_str("<span class=\"leaderboard-rank\">#")
+                    .number(index as u32 + 1)
+                    .raw_str("</span> ");
+                md = Self::render_author(env, md, board_id, &user, &profile_contract, None);
+                md = md
+                    .raw_str(" <span class=\"leaderboard-karma\">")
+                    .raw(Self::format_karma(env, karma))
+                    .raw_str(" karma</span>")
+                    .div_end();
+            }
+            md = md.div_end();
+        }
+
+        if let Some(user) = viewer {
+            let karma: i64 = env
+                .try_invoke_contract::<i64, soroban_sdk::Error>(
+                    &voting,
+                    &Symbol::new(env, "get_board_karma"),
+                    Vec::from_array(env, [board_id.into_val(env), user.into_val(env)]),
+                )
+                .ok()
+                .and_then(|r| r.ok())
+                .unwrap_or(0);
+            md = md
+                .div_start("leaderboard-viewer")
+                .raw_str("Your karma: <strong>")
+                .raw(Self::format_karma(env, karma))
+                .raw_str("</strong> · ");
+            md = match top.iter().position(|(ranked, _)| ranked == *user) {
+                Some(index) => md.raw_str("Rank #").number(index as u32 + 1),
+                None => md.raw_str("Not ranked yet"),
+            };
+            md = md.div_end();
+        }
+
+        Self::render_footer_into(env, md).build()
+    }
+
+    /// Render thread title search results for `?q=` (`start` skips earlier matches)
+    fn render_search(
+        env: &Env,
+        board_id: u64,
+        raw_query: &Bytes,
+        start: u32,
+        viewer: &Option<Address>,
+    ) -> Bytes {
+        let board_meta: BoardMeta = env
+            .storage()
+            .persistent()
+            .get(&BoardKey::Board(board_id))
+            .expect("Board not found");
+        let base_path = Self::build_board_base_path(env, board_id, &board_meta.slug);
+
+        let config: BoardConfig = env
+            .storage()
+            .persistent()
+            .get(&BoardKey::BoardConfig(board_id))
+            .expect("Board not found");
+        if config.is_private {
+            if let Some(perms_addr) = env
+                .storage()
+                .instance()
+                .get::<_, Address>(&BoardKey::Permissions)
+            {
+                let viewer_role = match viewer {
+                    Some(user) => {
+                        let args: Vec<Val> =
+                            Vec::from_array(env, [board_id.into_val(env), user.into_val(env)]);
+                        env.invoke_contract(&perms_addr, &Symbol::new(env, "get_role"), args)
+                    }
+                    None => Role::Guest,
+                };
+                if (viewer_role as u32) < (Role::Member as u32) {
+                    return Self::render_private_board_message(
+                        env,
+                        board_id,
+                        &config,
+                        viewer,
+                        &perms_addr,
+                    );
+                }
+            }
+        }
+
+        let query = Self::decode_query(env, raw_query);
+        let mut md = Self::render_nav(env, board_id, viewer);
+        md = Self::render_back_nav(env, md, board_id);
+        md = md.h1("Search Threads");
+        md = Self::render_search_box(env, md, &base_path, &query);
+
+        if query.is_empty() {
+            md = md.paragraph("Enter part of a thread title to search this board.");
+            return Self::render_footer_into(env, md).build();
+        }
+
+        // Ask for one extra match to know whether there is a next page
+        let mut buf = [0u8; MAX_SEARCH_QUERY_LEN];
+        let query_len = query.len() as usize;
+        query.copy_into_slice(&mut buf[..query_len]);
+        let results = Self::search_threads(
+            env.clone(),
+            board_id,
+            String::from_bytes(env, &buf[..query_len]),
+            start,
+            SEARCH_PAGE_SIZE + 1,
+            viewer.clone(),
+        );
+
+        if results.is_empty() {
+            md = md.paragraph(if start == 0 {
+                "No thread titles match your search."
+            } else {
+                "No more matching threads."
+            });
+        } else {
+            let voting_contract: Option<Address> = env.storage().instance().get(&BoardKey::Voting);
+            let blocked = Self::blocked_for(env, viewer);
+            let saved = Self::saved_for(env, viewer);
+            let read_marks = Self::read_marks_for(env, board_id, viewer);
+            let flairs: Vec<FlairDef> = env
+                .storage()
+                .persistent()
+                .get(&BoardKey::BoardFlairDefs(board_id))
+                .unwrap_or(Vec::new(env));
+            let tags = Self::list_tags(env.clone(), board_id);
+
+            md = md.div_start("thread-list");
+            for thread in results.iter().take(SEARCH_PAGE_SIZE as usize) {
+                md = Self::render_thread_card(
+                    env,
+                    md,
+                    board_id,
+                    &base_path,
+                    &thread,
+                    &voting_contract,
+                    &flairs,
+                    &tags,
+                    &blocked,
+                    &saved,
+                    &read_marks,
+                );
+            }
+            md = md.div_end();
+        }
+
+        // Pagination links carry the query back in encoded form
+        let encoded = Self::encode_query(env, &query);
+        let has_more = results.len() > SEARCH_PAGE_SIZE;
+        if start > 0 || has_more {
+            md = md.div_start("pagination");
+            if start > 0 {
+                md = md
+                    .raw_str("<a href=\"render:")
+                    .raw(base_path.clone())
+                    .raw_str("/search?q=")
+                    .raw(encoded.clone())
+                    .raw_str("&amp;start=")
+                    .number(start.saturating_sub(SEARCH_PAGE_SIZE))
+                    .raw_str("\">← Previous</a> ");
+            }
+            if has_more {
+                md = md
+                    .raw_str("<a href=\"render:")
+                    .raw(base_path.clone())
+                    .raw_str("/search?q=")
+                    .raw(encoded)
+                    .raw_str("&amp;start=")
+                    .number(start + SEARCH_PAGE_SIZE)
+                    .raw_str("\">More results →</a>");
+            }
+            md = md.div_end();
+        }
+
+        md = md
+            .raw_str("<p class=\"search-note\">Only the ")
+            .number(SEARCH_SCAN_WINDOW as u32)
+            .raw_str(" most recent threads are searched.</p>\n");
+
+        Self::render_footer_into(env, md).build()
+    }
+
+    /// Render the thread title search box (a GET form onto the /search route)
+    fn render_search_box<'a>(
+        env: &'a Env,
+        md: MarkdownBuilder<'a>,
+        base_path: &Bytes,
+        query: &Bytes,
+    ) -> MarkdownBuilder<'a> {
+        md.raw_str("<form class=\"board-search\" method=\"get\" action=\"render:")
+            .raw(base_path.clone())
+            .raw_str("/search\">")
+            .raw_str("<input type=\"search\" name=\"q\" maxlength=\"64\" ")
+            .raw_str("placeholder=\"Search thread titles\" value=\"")
+            .raw(escape_html(env, query))
+            .raw_str("\" /> <button type=\"submit\">Search</button></form>\n")
+    }
+
+    /// Decode a `+`/`%XX` encoded query value, trimming spaces and keeping at most
+    /// MAX_SEARCH_QUERY_LEN bytes
+    fn decode_query(env: &Env, raw: &Bytes) -> Bytes {
+        let hex = |c: u8| (c as char).to_digit(16).map(|d| d as u8);
+        let mut decoded = Bytes::new(env);
+        let mut i = 0;
+        while i < raw.len() && (decoded.len() as usize) < MAX_SEARCH_QUERY_LEN {
+            let byte = raw.get(i).unwrap();
+            i += 1;
+            let byte = match byte {
+                b'+' => b' ',
+                b'%' if i + 1 < raw.len() => {
+                    match (hex(raw.get(i).unwrap()), hex(raw.get(i + 1).unwrap())) {
+                        (Some(hi), Some(lo)) => {
+                            i += 2;
+                            (hi << 4) | lo
+                        }
+                        _ => b'%',
+                    }
+                }
+                _ => byte,
+            };
+            if byte == b' ' && decoded.is_empty() {
+                continue;
+            }
+            decoded.push_back(byte);
+        }
+        while decoded.last() == Some(b' ') {
+            decoded.pop_back();
+        }
+        decoded
+    }
+
+    /// Percent-encode a query value for use in a render: link
+    fn encode_query(env: &Env, query: &Bytes) -> Bytes {
+        const HEX: &[u8; 16] = b"0123456789ABCDEF";
+        let mut encoded = Bytes::new(env);
+        for byte in query.iter() {
+            if byte.is_ascii_alphanumeric() || matches!(byte, b'-' | b'_' | b'.' | b'~') {
+                encoded.push_back(byte);
+            } else {
+                encoded.push_back(b'%');
+                encoded.push_back(HEX[(byte >> 4) as usize]);
+                encoded.push_back(HEX[(byte & 0x0F) as usize]);
+            }
+        }
+        encoded
+    }
+
+    /// Parse a thread filter from a URL path segment
+    fn parse_thread_filter(filter: &Bytes) -> Option<ThreadFilter> {
+        let len = filter.len() as usize;
+        if len > 16 {
+            return None;
+        }
+        let mut buf = [0u8; 16];
+        filter.copy_into_slice(&mut buf[..len]);
+        match &buf[..len] {
+            b"all" => Some(ThreadFilter::All),
+            b"hidden" => Some(ThreadFilter::Hidden),
+            b"locked" => Some(ThreadFilter::Locked),
+            b"deleted" => Some(ThreadFilter::Deleted),
+            b"pinned" => Some(ThreadFilter::Pinned),
+            b"flagged" => Some(ThreadFilter::Flagged),
+            _ => None,
+        }
+    }
+
+    /// Render moderator view of threads filtered by status
+    fn render_mod_threads(
+        env: &Env,
+        board_id: u64,
+        filter_bytes: &Bytes,
+        viewer: &Option<Address>,
+    ) -> Bytes {
+        let board_meta: BoardMeta = env
+            .storage()
+            .persistent()
+            .get(&BoardKey::Board(board_id))
+            .expect("Board not found");
+        let base_path = Self::build_board_base_path(env, board_id, &board_meta.slug);
+
+        let mut md = Self::render_nav(env, board_id, viewer);
+        md = Self::render_back_nav(env, md, board_id);
+        md = md.h1("Moderation: Threads");
+
+        // Only moderators can see this view
+        let viewer_role = match (
+            env.storage()
+                .instance()
+                .get::<_, Address>(&BoardKey::Permissions),
+            viewer,
+        ) {
+            (Some(perms_addr), Some(user)) => {
+                let args: Vec<Val> =
+                    Vec::from_array(env, [board_id.into_val(env), user.into_val(env)]);
+                env.invoke_contract(&perms_addr, &Symbol::new(env, "get_role"), args)
+            }
+            _ => Role::Guest,
+        };
+        if (viewer_role as u32) < (Role::Moderator as u32) {
+            md = md.warning("You must be a moderator to view this page.");
+            return Self::render_footer_into(env, md).build();
+        }
+        let user = viewer.as_ref().unwrap();
+
+        let Some(filter) = Self::parse_thread_filter(filter_bytes) else {
+            md = md.warning("Unknown filter.");
+            return Self::render_footer_into(env, md).build();
+        };
+
+        // Filter selector
+        md = md
+            .div_start("sort-selector")
+            .raw_str("<span class=\"sort-label\">Show:</span>");
+        let options: [(&str, ThreadFilter); 6] = [
+            ("all", ThreadFilter::All),
+            ("hidden", ThreadFilter::Hidden),
+            ("locked", ThreadFilter::Locked),
+            ("deleted", ThreadFilter::Deleted),
+            ("pinned", ThreadFilter::Pinned),
+            ("flagged", ThreadFilter::Flagged),
+        ];
+        for (name, f) in options.iter() {
+            md = md
+                .raw_str("<a href=\"render:")
+                .raw(base_path.clone())
+                .raw_str("/mod/threads/")
+                .raw_str(name)
+                .raw_str(if *f == filter {
+                    "\" class=\"sort-option sort-active\">"
+                } else {
+                    "\" class=\"sort-option\">"
+                })
+                .raw_str(name)
+                .raw_str("</a>");
+        }
+        md = md.div_end();
+
+        let threads =
+            Self::list_threads_filtered(env.clone(), board_id, filter, 0, MAX_FILTER_SCAN);
+
+        if threads.is_empty() {
+            md = md.paragraph("No matching threads in the most recent threads.");
+            return Self::render_footer_into(env, md).build();
+        }
+
+        for thread in threads.iter() {
+            md = md
+                .raw_str("<div class=\"thread-card-wrapper\" data-form>")
+                .raw_str("<a href=\"render:")
+                .raw(base_path.clone())
+                .raw_str("/t/")
+                .number(thread.id as u32)
+                .raw_str("\" class=\"thread-card\"><span class=\"thread-card-title\">")
+                .text_string(&thread.title)
+                .raw_str("</span></a>")
+                .raw_str("<input type=\"hidden\" name=\"board_id\" value=\"")
+                .number(board_id as u32)
+                .raw_str("\" />")
+                .raw_str("<input type=\"hidden\" name=\"thread_id\" value=\"")
+                .number(thread.id as u32)
+                .raw_str("\" />")
+                .raw_str("<input type=\"hidden\" name=\"caller\" value=\"")
+                .text_string(&user.to_string())
+                .raw_str("\" />");
+            if thread.is_hidden {
+                md = md.raw_str(" <a href=\"form:@admin:unhide_thread\">[Unhide]</a>");
+            }
+            if thread.is_locked {
+                md = md.raw_str(" <a href=\"form:@admin:unlock_thread\">[Unlock]</a>");
+            }
+            if thread.is_pinned {
+                md = md.raw_str(" <a href=\"form:@admin:unpin_thread\">[Unpin]</a>");
+            }
+            md = md.raw_str("</div>\n");
+        }
+
+        Self::render_footer_into(env, md).build()
+    }
+
+    /// Render private board access denied message
+    fn render_private_board_message(
+        env: &Env,
+        board_id: u64,
+        config: &BoardConfig,
+        viewer: &Option<Address>,
+        perms_addr: &Address,
+    ) -> Bytes {
+        let mut md = Self::render_nav(env, board_id, viewer);
+        md = Self::render_back_nav(env, md, board_id);
+        md = md
+            .div_start("page-header")
+            .raw_str("<h1>")
+            .text_string(&config.name)
+            .raw_str("</h1>")
+            .raw_str("<p>")
+            .text_string(&config.description)
+            .raw_str("</p>")
+            .div_end()
+            .newline()
+            .raw_str("<span class=\"badge badge-private\">private</span>")
+            .newline()
+            .newline();
+
+        if viewer.is_none() {
+            md = md
+                .warning("This is a private board. Please connect your wallet to request access.");
+        } else {
+            // Check if user has a pending invite request
+            let has_request_args: Vec<Val> = Vec::from_array(
+                env,
+                [
+                    board_id.into_val(env),
+                    viewer.as_ref().unwrap().into_val(env),
+                ],
+            );
+            let has_request: bool = env.invoke_contract(
+                perms_addr,
+                &Symbol::new(env, "has_invite_request"),
+                has_request_args,
+            );
+
+            if has_request {
+                md = md.tip("Your request to join this board is pending approval.");
+            } else {
+                md = md.note("This is a private board. You can request access from the board administrators.")
+                    .newline()
+                    .raw_str("<input type=\"hidden\" name=\"board_id\" value=\"")
+                    .number(board_id as u32)
+                    .raw_str("\" />\n")
+                    .raw_str("<input type=\"hidden\" name=\"caller\" value=\"")
+                    .text_string(&viewer.as_ref().unwrap().to_string())
+                    .raw_str("\" />\n")
+                    .textarea(
+                        "message",
+                        3,
+                        "Tell the moderators why you want to join (optional, max 500 characters)",
+                    )
+                    .newline()
+                    .form_link_to("Request to Join", "perms", "request_invite");
+            }
+        }
+
+        Self::render_footer_into(env, md).build()
+    }
+
+    /// Render hidden thread access denied message
+    fn render_hidden_thread_message(env: &Env, board_id: u64, viewer: &Option<Address>) -> Bytes {
+        // Get board metadata for slug-based URLs
+        let board_meta: BoardMeta = env
+            .storage()
+            .persistent()
+            .get(&BoardKey::Board(board_id))
+            .expect("Board not found");
+
+        // Build base path for links
+        let base_path = Self::build_board_base_path(env, board_id, &board_meta.slug);
+
+        let md = Self::render_nav(env, board_id, viewer)
+            .newline()
+            .raw_str("<div class=\"back-nav\"><a href=\"render:")
+            .raw(base_path)
+            .raw_str("\" class=\"back-link\">← Back to Board</a></div>\n")
+            .newline()
+            .warning("This thread has been hidden by a moderator.");
+        Self::render_footer_into(env, md).build()
+    }
+
+    /// Render the page shown in place of a post when the board has no content contract
+    fn render_content_unavailable(
+        env: &Env,
+        board_id: u64,
+        thread_id: u64,
+        viewer: &Option<Address>,
+    ) -> Bytes {
+        let md = Self::render_nav(env, board_id, viewer).newline();
+        let md = Self::render_breadcrumbs(env, md, board_id, Some(thread_id), None)
+            .paragraph("Content is unavailable on this board.");
+        Self::render_footer_into(env, md).build()
+    }
+
+    /// Highlighted copy of a thread's accepted answer, shown under the thread body.
+    /// Skipped if the reply has since been deleted or hidden from this viewer.
+    fn render_accepted_answer<'a>(
+        env: &'a Env,
+        mut md: MarkdownBuilder<'a>,
+        content: &Address,
+        board_id: u64,
+        thread_id: u64,
+        base_path: &Bytes,
+        board_slug: &String,
+        profile_contract: &Option<Address>,
+        viewer: &Option<Address>,
+        with_body: bool,
+    ) -> MarkdownBuilder<'a> {
+        let Some(reply_id) = Self::get_accepted_answer(env.clone(), board_id, thread_id) else {
+            return md;
+        };
+        let args: Vec<Val> = Vec::from_array(
+            env,
+            [
+                board_id.into_val(env),
+                thread_id.into_val(env),
+                reply_id.into_val(env),
+            ],
+        );
+        let reply: Option<ReplyMeta> = env
+            .try_invoke_contract::<Option<ReplyMeta>, soroban_sdk::Error>(
+                content,
+                &Symbol::new(env, "get_reply"),
+                args.clone(),
+            )
+            .ok()
+            .and_then(|r| r.ok())
+            .flatten();
+        let Some(reply) = reply else {
+            return md;
+        };
+        if reply.is_deleted || (reply.is_hidden && viewer.as_ref() != Some(&reply.creator)) {
+            return md;
+        }
+
+        md = md
+            .div_start("accepted-answer")
+            .raw_str("<div class=\"accepted-answer-header\">✓ Accepted answer · ");
+        let return_path = Self::build_thread_return_path(env, board_id, board_slug, thread_id);
+        md = Self::render_author(
+            env,
+            md,
+            board_id,
+            &reply.creator,
+            profile_contract,
+            Some(return_path),
+        );
+        md = md
+            .raw_str(" · <a href=\"render:")
+            .raw(base_path.clone())
+            .raw_str("/t/")
+            .number(thread_id as u32)
+            .raw_str("/r/")
+            .number(reply_id as u32)
+            .raw_str("\">Reply #")
+            .number(reply_id as u32)
+            .raw_str("</a></div>\n");
+
+        if with_body {
+            let body: Bytes =
+                env.invoke_contract(content, &Symbol::new(env, "get_reply_content"), args);
+            let collapsed = Self::blocked_for(env, viewer).contains(&reply.creator);
+            if collapsed {
+                md = md.raw_str(BLOCKED_CONTENT_SUMMARY);
+            }
+            md = md
+                .div_start("reply-content")
+                .raw(Self::link_mentions(env, &body, profile_contract))
+                .div_end();
+            if collapsed {
+                md = md.raw_str("</details>\n");
+            }
+        }
+        md.div_end().newline()
+    }
+
+    /// Badge for a thread its schedule currently keeps closed to replies
+    fn render_schedule_badge<'a>(
+        env: &'a Env,
+        md: MarkdownBuilder<'a>,
+        window: ReplyWindow,
+    ) -> MarkdownBuilder<'a> {
+        match window {
+            ReplyWindow::Open => md,
+            ReplyWindow::NotYetOpen(open_at) => md
+                .raw_str("<span class=\"badge badge-scheduled\">opens in ")
+                .raw(Self::format_duration(
+                    env,
+                    open_at - env.ledger().timestamp(),
+                ))
+                .raw_str("</span> "),
+            ReplyWindow::Closed => md.raw_str("<span class=\"badge badge-closed\">closed</span> "),
+        }
+    }
+
+    /// Moderator form for a thread's reply schedule, shown below the mod actions.
+    /// Times are entered as hours from now; leaving both blank clears the schedule.
+    fn render_schedule_form<'a>(
+        env: &'a Env,
+        mut md: MarkdownBuilder<'a>,
+        board_id: u64,
+        thread_id: u64,
+        user: &Address,
+    ) -> MarkdownBuilder<'a> {
+        md = md.raw_str("<details class=\"thread-schedule\"><summary>Schedule replies</summary>\n");
+        if let Some(schedule) = Self::get_thread_schedule(env.clone(), board_id, thread_id) {
+            if let Some(open_at) = schedule.open_at {
+                md = md
+                    .raw_str("<p>Opens: ")
+                    .raw(Self::format_board_timestamp(env, board_id, open_at))
+                    .raw_str("</p>\n");
+            }
+            if let Some(lock_at) = schedule.lock_at {
+                md = md
+                    .raw_str("<p>Locks: ")
+                    .raw(Self::format_board_timestamp(env, board_id, lock_at))
+                    .raw_str("</p>\n");
+            }
+        }
+        md.raw_str("<div data-form>\n")
+            .raw_str("<input type=\"hidden\" name=\"board_id\" value=\"")
+            .number(board_id as u32)
+            .raw_str("\" />\n")
+            .raw_str("<input type=\"hidden\" name=\"thread_id\" value=\"")
+            .number(thread_id as u32)
+            .raw_str("\" />\n")
+            .raw_str("<input type=\"hidden\" name=\"caller\" value=\"")
+            .text_string(&user.to_string())
+            .raw_str("\" />\n")
+            .raw_str("<input type=\"text\" name=\"open_in_hours\" placeholder=\"Open in (hours, blank = now)\" />\n")
+            .raw_str("<input type=\"text\" name=\"lock_in_hours\" placeholder=\"Lock in (hours, blank = never)\" />\n")
+            .raw_str("<a href=\"form:@admin:set_thread_schedule\">[Set Schedule]</a>\n")
+            .raw_str("</div>\n</details>\n")
+    }
+
+    /// Render create thread form, with the body pre-filled from `template_id` if it
+    /// names one of the board's templates
+    fn render_create_thread(
+        env: &Env,
+        board_id: u64,
+        template_id: Option<u32>,
+        viewer: &Option<Address>,
+    ) -> Bytes {
+        // Get board metadata for slug-based URLs
+        let board_meta: BoardMeta = env
+            .storage()
+            .persistent()
+            .get(&BoardKey::Board(board_id))
+            .expect("Board not found");
+
+        // Build base path for all links
+        let base_path = Self::build_board_base_path(env, board_id, &board_meta.slug);
+
+        // Get board config for private board check
+        let config: BoardConfig = env
+            .storage()
+            .persistent()
+            .get(&BoardKey::BoardConfig(board_id))
+            .expect("Board not found");
+
+        // Get permissions contract and viewer role early for private board check
+        let perms_addr_opt = env
+            .storage()
+            .instance()
+            .get::<_, Address>(&BoardKey::Permissions);
+        let (viewer_role, is_moderator) = if let Some(ref perms_addr) = perms_addr_opt {
+            if let Some(user) = viewer {
+                let role_args: Vec<Val> =
+                    Vec::from_array(env, [board_id.into_val(env), user.into_val(env)]);
+                let role: Role =
+                    env.invoke_contract(perms_addr, &Symbol::new(env, "get_role"), role_args);
+                let can_mod = (role as u32) >= (Role::Moderator as u32);
+                (role, can_mod)
+            } else {
+                (Role::Guest, false)
+            }
+        } else {
+            (Role::Guest, false)
+        };
+
+        // Check permissions for private boards - must be Member+ to access
+        if config.is_private {
+            if let Some(ref perms_addr) = perms_addr_opt {
+                if (viewer_role as u32) < (Role::Member as u32) {
+                    return Self::render_private_board_message(
+                        env, board_id, &config, viewer, perms_addr,
+                    );
+                }
+            }
+        }
+
+        let mut md = Self::render_nav(env, board_id, viewer)
+            .newline()  // Blank line after nav-bar div for markdown parsing
+            // Error mappings for user-friendly error messages
+            .raw_str("{{errors {\"1\": \"This board is read-only and does not accept new content.\", \"7\": \"Please select a flair for your post.\", ")
+            .raw(Self::posting_errors(env, board_id))
+            .raw_str("}}}\n")
+            .raw_str("<div class=\"back-nav\"><a href=\"render:")
+            .raw(base_path.clone())
+            .raw_str("\" class=\"back-link\">← Back to Board</a></div>\n")
+            .newline()  // Blank line before h1 for markdown parsing
+            .h1("New Thread");
+
+        // Show rules reminder if rules are set
+        if let Some(rules) = env
+            .storage()
+            .persistent()
+            .get::<_, String>(&BoardKey::BoardRules(board_id))
+        {
+            if !rules.is_empty() {
+                md = md.div_start("rules-reminder")
+                    .raw_str("<details open><summary><strong>Before posting, please read the board rules:</strong></summary>")
+                    .div_start("rules-content")
+                    .text_string(&rules)
+                    .div_end()
+                    .raw_str("</details>")
+                    .div_end();
+            }
+        }
+
+        // Check if board is read-only
+        if config.is_readonly {
+            md = md.warning("This board is read-only. New threads cannot be created.");
+            return Self::render_footer_into(env, md).build();
+        }
+
+        if viewer.is_none() {
+            md = md.warning("Please connect your wallet to create a thread.");
+            return Self::render_footer_into(env, md).build();
+        }
+
+        // Check if user has permission to create threads (requires Member+ role)
+        if (viewer_role as u32) < (Role::Member as u32) {
+            md = md.warning("You don't have permission to create threads on this board.");
+            return Self::render_footer_into(env, md).build();
+        }
+
+        // Pre-empt the karma threshold rather than letting the submit fail
+        let user = viewer.as_ref().unwrap();
+        if let Some((required, karma)) =
+            Self::karma_shortfall(env.clone(), board_id, user.clone(), false)
+        {
+            md = md
+                .raw_str("<div class=\"karma-notice\">You need ")
+                .raw(Self::format_karma(env, required))
+                .raw_str(" karma to post here (you have ")
+                .raw(Self::format_karma(env, karma))
+                .raw_str(").</div>\n");
+            return Self::render_footer_into(env, md).build();
+        }
+
+        // Get flairs for the selector
+        let flairs: Vec<FlairDef> = env
+            .storage()
+            .persistent()
+            .get(&BoardKey::BoardFlairDefs(board_id))
+            .unwrap_or(Vec::new(env));
+
+        // Check if any flair is required
+        let mut flair_required = false;
+        for i in 0..flairs.len() {
+            let flair = flairs.get(i).unwrap();
+            if flair.required && flair.enabled && (!flair.mod_only || is_moderator) {
+                flair_required = true;
+                break;
+            }
+        }
+
+        // Restore the viewer's saved draft, if any
+        let draft = Self::fetch_draft(
+            env,
+            "get_draft",
+            Vec::from_array(
+                env,
+                [
+                    board_id.into_val(env),
+                    Symbol::new(env, THREAD_DRAFT_KEY).into_val(env),
+                    user.into_val(env),
+                ],
+            ),
+        );
+        if let Some(ref draft) = draft {
+            md = Self::render_draft_restore(env, md, board_id, draft, true);
+        }
+
+        // Template picker; a chosen template replaces the draft body
+        let templates = Self::list_templates(env.clone(), board_id);
+        let template = template_id.and_then(|id| Self::get_template(env.clone(), board_id, id));
+        if !templates.is_empty() {
+            md = md
+                .raw_str("<details class=\"template-picker\"><summary>")
+                .raw_str("Start from a template</summary>\n<ul>\n");
+            for t in templates.iter() {
+                md = md
+                    .raw_str("<li><a href=\"render:")
+                    .raw(base_path.clone())
+                    .raw_str("/new/")
+                    .number(t.id)
+                    .raw_str("\">")
+                    .raw(Self::escape_attr(env, &t.name))
+                    .raw_str("</a></li>\n");
+            }
+            md = md.raw_str("</ul>\n</details>\n");
+        }
+
+        md = md
+            .raw_str("<input type=\"hidden\" name=\"_redirect\" value=\"")
+            .raw(base_path.clone())
+            .raw_str("\" />\n")
+            .raw_str("<input type=\"hidden\" name=\"board_id\" value=\"")
+            .number(board_id as u32)
+            .raw_str("\" />\n");
+        md = match draft {
+            Some(ref draft) if !draft.title.is_empty() => md
+                .raw_str("<input type=\"text\" name=\"title\" placeholder=\"Thread title\"")
+                .raw_str(" value=\"")
+                .raw(escape_html(
+                    env,
+                    &Self::draft_title_bytes(env, &draft.title),
+                ))
+                .raw_str("\" />\n"),
+            _ => md.input("title", "Thread title").newline(),
+        };
+
+        // Add flair selector if flairs exist
+        let mut has_visible_flairs = false;
+        for i in 0..flairs.len() {
+            let flair = flairs.get(i).unwrap();
+            if flair.enabled && (!flair.mod_only || is_moderator) {
+                has_visible_flairs = true;
+                break;
+            }
+        }
+
+        if has_visible_flairs {
+            md = md
+                .raw_str("<div class=\"flair-selector\">\n")
+                .raw_str("<label>Flair");
+            if flair_required {
+                md = md.raw_str(" <span class=\"required\">*</span>");
+            }
+            md = md
+                .raw_str(":</label>\n")
+                .raw_str("<select name=\"flair_id\">\n")
+                .raw_str("<option value=\"none\">-- Select flair --</option>\n");
+
+            for i in 0..flairs.len() {
+                let flair = flairs.get(i).unwrap();
+                if flair.enabled && (!flair.mod_only || is_moderator) {
+                    // Use "flair_N" format to prevent viewer from converting to integer
+                    md = md
+                        .raw_str("<option value=\"flair_")
+                        .number(flair.id)
+                        .raw_str("\" style=\"color:")
+                        .raw(sanitize_color(env, &flair.color))
+                        .raw_str(";background:")
+                        .raw(sanitize_color(env, &flair.bg_color))
+                        .raw_str("\">")
+                        .raw(Self::escape_attr(env, &flair.name))
+                        .raw_str("</option>\n");
+                }
+            }
+
+            md = md.raw_str("</select>\n");
+            if flair_required {
+                md = md.raw_str("<small class=\"flair-required-notice\" style=\"color:#c00;display:block;margin-top:4px;\">* A flair is required for new posts on this board</small>\n");
+            }
+            md = md.raw_str("</div>\n");
+        } else {
+            // Always include flair_id field - the content contract expects it
+            md = md.raw_str("<input type=\"hidden\" name=\"flair_id\" value=\"none\" />\n");
+        }
+
+        let prefill = match (template, &draft) {
+            (Some(template), _) => Some(soroban_render_sdk::bytes::string_to_bytes(
+                env,
+                &template.body,
+            )),
+            (None, Some(draft)) => Some(draft.body.clone()),
+            (None, None) => None,
+        };
+        md = match prefill {
+            // Escaped so a draft or template can't close the textarea early
+            Some(ref body) => md
+                .raw_str("<textarea name=\"body\" data-editor=\"markdown\" rows=\"10\"")
+                .raw_str(" placeholder=\"Write your post content here...\">")
+                .raw(escape_html(env, body))
+                .raw_str("</textarea>\n"),
+            None => md.textarea_markdown("body", 10, "Write your post content here..."),
+        };
+
+        md = md
+            .newline()
+            .raw_str("<input type=\"hidden\" name=\"draft_key\" value=\"")
+            .raw_str(THREAD_DRAFT_KEY)
+            .raw_str("\" />\n")
+            .raw_str("<input type=\"hidden\" name=\"caller\" value=\"")
+            .text_string(&user.to_string())
+            .raw_str("\" />\n")
+            .newline()
+            .form_link_to("Create Thread", "content", "create_thread")
+            .raw_str(" ")
+            .form_link_to("Save Draft", "content", "save_thread_draft")
+            .newline()
+            .newline()
+            .raw_str("[Cancel](render:")
+            .raw(base_path)
+            .raw_str(")");
+
+        Self::render_footer_into(env, md).build()
+    }
+
+    /// Render thread view
+    /// Render a thread page. Unless `full` is set, the body is cut to fit the
+    /// board's render budget and the optional sections are replaced by placeholders.
+    fn render_thread(
+        env: &Env,
+        board_id: u64,
+        thread_id: u64,
+        full: bool,
+        sort: ReplySort,
+        viewer: &Option<Address>,
+    ) -> Bytes {
+        // Without a content contract the page still renders, with bodies marked unavailable
+        let content: Option<Address> = env.storage().instance().get(&BoardKey::Content);
+
+        // Get profile contract for author display
+        let profile_contract = Self::get_profile_contract(env);
+
+        // Get board metadata for slug-based URLs
+        let board_meta: BoardMeta = env
+            .storage()
+            .persistent()
+            .get(&BoardKey::Board(board_id))
+            .expect("Board not found");
+
+        // Get board config for readonly check
+        let config: BoardConfig = env
+            .storage()
+            .persistent()
+            .get(&BoardKey::BoardConfig(board_id))
+            .expect("Board not found");
+
+        // Get thread metadata
+        let thread = env
+            .storage()
+            .persistent()
+            .get::<_, ThreadMeta>(&BoardKey::BoardThread(board_id, thread_id));
+        if thread.is_some() {
+            Self::bump_thread_ttl(env, board_id, thread_id);
+        }
+
+        // Get viewer role for hidden thread check and moderator controls
+        let perms_addr_opt = env
+            .storage()
+            .instance()
+            .get::<_, Address>(&BoardKey::Permissions);
+        let viewer_perms = Self::viewer_permissions(env, board_id, viewer);
+        let viewer_role = viewer_perms.role;
+        let viewer_can_moderate = (viewer_role as u32) >= (Role::Moderator as u32);
+
+        // Check permissions for private boards - must be Member+ to view threads
+        if config.is_private {
+            if let Some(ref perms_addr) = perms_addr_opt {
+                if (viewer_role as u32) < (Role::Member as u32) {
+                    return Self::render_private_board_message(
+                        env, board_id, &config, viewer, perms_addr,
+                    );
+                }
+            }
+        }
+
+        // Check if thread is hidden - only moderators and the author can view hidden threads
+        // (quarantined users keep seeing their own posts)
+        let is_hidden = thread.as_ref().map(|t| t.is_hidden).unwrap_or(false);
+        let viewer_is_author = match (viewer, thread.as_ref()) {
+            (Some(v), Some(t)) => *v == t.creator,
+            _ => false,
+        };
+        if is_hidden && !viewer_can_moderate && !viewer_is_author {
+            return Self::render_hidden_thread_message(env, board_id, viewer);
+        }
+
+        // Determine if posting is allowed (requires Member+ role, not readonly, not locked)
+        let is_readonly = config.is_readonly;
+        let is_locked = thread.as_ref().map(|t| t.is_locked).unwrap_or(false);
+        let window = Self::reply_window(env, board_id, thread_id);
+        let viewer_can_post = (viewer_role as u32) >= (Role::Member as u32);
+        let can_post = !is_readonly && !is_locked && window == ReplyWindow::Open && viewer_can_post;
+
+        // Build base path for all links in this thread
+        let base_path = Self::build_board_base_path(env, board_id, &board_meta.slug);
+
+        // Posts written in a moderator capacity get a MOD badge and their own class
+        let thread_distinguished = thread.as_ref().is_some_and(|t| !t.is_deleted)
+            && content
+                .as_ref()
+                .is_some_and(|c| Self::is_post_distinguished(env, c, board_id, thread_id, None));
+
+        let md = Self::render_nav(env, board_id, viewer).newline();
+        let mut md = Self::render_breadcrumbs(env, md, board_id, Some(thread_id), None);
+
+        // Get flairs for display
+        let flairs: Vec<FlairDef> = env
+            .storage()
+            .persistent()
+            .get(&BoardKey::BoardFlairDefs(board_id))
+            .unwrap_or(Vec::new(env));
+        let tags = Self::list_tags(env.clone(), board_id);
+
+        // Show thread title if available
+        if let Some(ref t) = thread {
+            md = md.raw_str("<h1>");
+
+            // Display flair before title if thread has one
+            if let Some(flair_id) = t.flair_id {
+                for i in 0..flairs.len() {
+                    let flair = flairs.get(i).unwrap();
+                    if flair.id == flair_id && flair.enabled {
+                        md = md
+                            .raw_str("<span class=\"flair\" style=\"color:")
+                            .raw(sanitize_color(env, &flair.color))
+                            .raw_str(";background:")
+                            .raw(sanitize_color(env, &flair.bg_color))
+                            .raw_str("\">")
+                            .raw(Self::escape_attr(env, &flair.name))
+                            .raw_str("</span> ");
+                        break;
+                    }
+                }
+            }
+
+            md = md.text_string(&t.title).raw_str("</h1>\n");
+            md = Self::render_tag_chips(env, md, board_id, thread_id, &tags, Some(&base_path));
+
+            // Show author (with return path so "Go Back" returns here)
+            let return_path =
+                Self::build_thread_return_path(env, board_id, &board_meta.slug, thread_id);
+            md = md.raw_str("<div class=\"thread-meta\">by ");
+            md = Self::render_author(
+                env,
+                md,
+                board_id,
+                &t.creator,
+                &profile_contract,
+                Some(return_path),
+            );
+            if thread_distinguished {
+                md = md.raw_str(
+                    " <span class=\"badge badge-mod\" title=\"Posted as a moderator\">MOD</span>",
+                );
+            }
+            md = md
+                .raw_str(" · ")
+                .raw(Self::format_board_timestamp(env, board_id, t.created_at));
+            if content
+                .as_ref()
+                .is_some_and(|c| Self::is_post_edited(env, c, board_id, thread_id, None))
+            {
+                md = Self::render_edited_marker(
+                    env,
+                    md,
+                    &base_path,
+                    thread_id,
+                    None,
+                    viewer_can_moderate,
+                );
+            }
+
+            // Subscribe/unsubscribe toggle for logged-in viewers
+            if let Some(ref user) = viewer {
+                let subscribed =
+                    Self::is_subscribed(env.clone(), board_id, thread_id, user.clone());
+                md = md
+                    .raw_str(" · <a href=\"tx:@board:")
+                    .raw_str(if subscribed {
+                        "unsubscribe"
+                    } else {
+                        "subscribe"
+                    })
+                    .raw_str(" {&quot;board_id&quot;:")
+                    .number(board_id as u32)
+                    .raw_str(",&quot;thread_id&quot;:")
+                    .number(thread_id as u32)
+                    .raw_str("}\" class=\"subscribe-toggle");
+                if subscribed {
+                    md = md.raw_str(" subscribed\" title=\"Unsubscribe\">★</a>");
+                } else {
+                    md = md.raw_str("\" title=\"Subscribe\">☆</a>");
+                }
+                let is_saved = Self::saved_for(env, viewer)
+                    .is_some_and(|saved| saved.contains((board_id, thread_id)));
+                md = Self::render_save_toggle(md.raw_str(" · "), board_id, thread_id, is_saved);
+                // Viewers not tracking the board yet can start with this thread
+                let unread = match Self::read_marks_for(env, board_id, viewer) {
+                    Some(marks) => Self::is_unread(&marks, t),
+                    None => true,
+                };
+                if unread {
+                    md = md
+                        .raw_str(" · <a href=\"tx:@board:mark_thread_read {&quot;board_id&quot;:")
+                        .number(board_id as u32)
+                        .raw_str(",&quot;thread_id&quot;:")
+                        .number(thread_id as u32)
+                        .raw_str("}\" class=\"mark-read\" title=\"Mark as read\">Mark read</a>");
+                }
+                if !t.is_deleted && content.is_some() {
+                    md = Self::render_distinguish_toggle(
+                        md,
+                        " · ",
+                        board_id,
+                        thread_id,
+                        None,
+                        thread_distinguished,
+                        viewer_is_author,
+                        viewer_can_moderate,
+                    );
+                }
+            }
+
+            md = md.raw_str("</div>\n");
+        } else {
+            md = md.raw_str("<h1>Thread</h1>\n");
+        }
+
+        // Show status badges
+        let is_pinned = thread.as_ref().map(|t| t.is_pinned).unwrap_or(false);
+        if is_hidden && viewer_can_moderate {
+            md = md.raw_str("<span class=\"badge badge-hidden\">hidden</span> ");
+        }
+        if is_pinned {
+            md = md.raw_str("<span class=\"badge badge-pinned\">pinned</span> ");
+        }
+        if is_locked {
+            md = md.raw_str("<span class=\"badge badge-locked\">locked</span> ");
+        } else {
+            md = Self::render_schedule_badge(env, md, window);
+        }
+        if is_readonly {
+            md = md.raw_str("<span class=\"badge badge-readonly\">read-only board</span> ");
+        }
+        if is_hidden || is_pinned || is_locked || window != ReplyWindow::Open || is_readonly {
+            md = md.newline();
+        }
+
+        // Check if this is a crosspost and show header
+        let crosspost_args: Vec<Val> =
+            Vec::from_array(env, [board_id.into_val(env), thread_id.into_val(env)]);
+        let crosspost_ref: Option<CrosspostRef> = content.as_ref().and_then(|content| {
+            env.try_invoke_contract::<Option<CrosspostRef>, soroban_sdk::Error>(
+                content,
+                &Symbol::new(env, "get_crosspost_ref"),
+                crosspost_args,
+            )
+            .ok()
+            .and_then(|r| r.ok())
+            .flatten()
+        });
+
+        if let Some(ref xpost) = crosspost_ref {
+            // Look up original board's slug for the crosspost link
+            let original_board_meta: Option<BoardMeta> = env
+                .storage()
+                .persistent()
+                .get(&BoardKey::Board(xpost.original_board_id));
+            let original_base_path = if let Some(ref orig_meta) = original_board_meta {
+                Self::build_board_base_path(env, xpost.original_board_id, &orig_meta.slug)
+            } else {
+                // Fallback to numeric ID if board not found (shouldn't happen)
+                let mut path = Bytes::from_slice(env, b"/b/");
+                path.append(&soroban_render_sdk::bytes::u32_to_bytes(
+                    env,
+                    xpost.original_board_id as u32,
+                ));
+                path
+            };
+
+            md = md
+                .div_start("crosspost-header")
+                .raw_str("<span class=\"crosspost-badge\">⤴ Crosspost</span> ")
+                .raw_str("Originally posted in [")
+                .raw(original_base_path.clone())
+                .raw_str("](render:")
+                .raw(original_base_path)
+                .raw_str("/t/")
+                .number(xpost.original_thread_id as u32)
+                .raw_str(") by ");
+            // Show original author (return path goes back to current thread)
+            let return_path =
+                Self::build_thread_return_path(env, board_id, &board_meta.slug, thread_id);
+            md = Self::render_author(
+                env,
+                md,
+                board_id,
+                &xpost.original_author,
+                &profile_contract,
+                Some(return_path.clone()),
+            );
+
+            // Crossposter, original author and this board's moderators can retract it
+            let can_remove = viewer_can_moderate
+                || viewer
+                    .as_ref()
+                    .is_some_and(|v| *v == xpost.crossposted_by || *v == xpost.original_author);
+            if can_remove {
+                md = md
+                    .raw_str(" <a href=\"tx:@content:remove_crosspost {&quot;board_id&quot;:")
+                    .number(board_id as u32)
+                    .raw_str(",&quot;thread_id&quot;:")
+                    .number(thread_id as u32)
+                    .raw_str("}\" class=\"crosspost-remove\">Remove crosspost</a>");
+            }
+            md = md.div_end().newline();
+        }
+
+        // A removed crosspost keeps a tombstone in place of the header
+        let removed_crosspost: Option<RemovedCrosspost> = match content {
+            Some(ref content) if crosspost_ref.is_none() => env
+                .try_invoke_contract::<Option<RemovedCrosspost>, soroban_sdk::Error>(
+                    content,
+                    &Symbol::new(env, "get_removed_crosspost"),
+                    Vec::from_array(env, [board_id.into_val(env), thread_id.into_val(env)]),
+                )
+                .ok()
+                .and_then(|r| r.ok())
+                .flatten(),
+            _ => None,
+        };
+        if let Some(ref removed) = removed_crosspost {
+            md = md
+                .div_start("crosspost-header crosspost-removed")
+                .raw_str("<span class=\"crosspost-badge\">⤴ Crosspost</span> ")
+                .raw_str("This crosspost was removed on ")
+                .raw(Self::format_board_timestamp(
+                    env,
+                    board_id,
+                    removed.removed_at,
+                ))
+                .raw_str(".")
+                .div_end()
+                .newline();
+        }
+
+        // Thread body in a container, collapsed if the viewer blocked the author
+        // (pinned announcements are always shown)
+        let collapsed = thread
+            .as_ref()
+            .is_some_and(|t| !t.is_pinned && Self::blocked_for(env, viewer).contains(&t.creator));
+        if collapsed {
+            md = md.raw_str(BLOCKED_CONTENT_SUMMARY);
+        }
+        md = md.div_start(if thread_distinguished {
+            "thread-body distinguished"
+        } else {
+            "thread-body"
+        });
+
+        // Get thread body from content contract
+        let args: Vec<Val> =
+            Vec::from_array(env, [board_id.into_val(env), thread_id.into_val(env)]);
+        let mut body: Bytes = match content {
+            Some(ref content) => {
+                env.invoke_contract(content, &Symbol::new(env, "get_thread_body"), args.clone())
+            }
+            None => Bytes::new(env),
+        };
+
+        // The body is the only unbounded section, so it is what gets cut to fit the budget.
+        // Everything above it has already been emitted and is counted exactly.
+        let head = md.build();
+        let used = head.len() + THREAD_TAIL_BYTES;
+        md = MarkdownBuilder::new(env).raw(head);
+        let budget = Self::get_render_budget(env.clone(), board_id);
+        let over_budget = !full && used + body.len() > budget;
+        if over_budget {
+            body = Self::cut_body(env, &body, budget.saturating_sub(used));
+        }
+
+        if content.is_none() {
+            md = md.raw_str(CONTENT_UNAVAILABLE);
+        } else if !body.is_empty() {
+            md = md.raw(Self::link_mentions(env, &body, &profile_contract));
+        } else {
+            md = md.italic("No content");
+        }
+
+        md = md.div_end().newline();
+        if collapsed {
+            md = md.raw_str("</details>\n");
+        }
+        if over_budget {
+            md = md
+                .div_start("show-full-post")
+                .raw_str("[Show full post](render:")
+                .raw(base_path.clone())
+                .raw_str("/t/")
+                .number(thread_id as u32)
+                .raw_str("/full)")
+                .div_end()
+                .newline();
+        }
+
+        // Accepted answer is repeated under the question (just a link when over budget)
+        if let Some(ref content) = content {
+            md = Self::render_accepted_answer(
+                env,
+                md,
+                content,
+                board_id,
+                thread_id,
+                &base_path,
+                &board_meta.slug,
+                &profile_contract,
+                viewer,
+                !over_budget,
+            );
+        }
+
+        // Vote buttons (if voting contract is configured and user is logged in)
+        let voting_contract: Option<Address> = env.storage().instance().get(&BoardKey::Voting);
+        if let (false, Some(voting)) = (over_budget, &voting_contract) {
+            md = Self::render_poll(env, md, voting, board_id, thread_id, viewer);
+        }
+        if over_budget && voting_contract.is_some() {
+            // Tallies and reactions are left to the full post
+            md = md
+                .div_start("vote-buttons budget-skipped")
+                .raw_str("Votes and reactions are on the [full post](render:")
+                .raw(base_path.clone())
+                .raw_str("/t/")
+                .number(thread_id as u32)
+                .raw_str("/full).")
+                .div_end()
+                .newline();
+        } else if let Some(ref voting) = voting_contract {
+            // Get thread vote tally
+            let tally_args: Vec<Val> =
+                Vec::from_array(env, [board_id.into_val(env), thread_id.into_val(env)]);
+            let tally: VoteTally =
+                env.invoke_contract(voting, &Symbol::new(env, "get_thread_tally"), tally_args);
+
+            // Get viewer's current vote (if logged in)
+            let viewer_vote = if let Some(ref user) = viewer {
+                let vote_args: Vec<Val> = Vec::from_array(
+                    env,
+                    [
+                        board_id.into_val(env),
+                        thread_id.into_val(env),
+                        user.into_val(env),
+                    ],
+                );
+                env.invoke_contract::<VoteDirection>(
+                    voting,
+                    &Symbol::new(env, "get_user_thread_vote"),
+                    vote_args,
+                )
+            } else {
+                VoteDirection::None
+            };
+
+            let allow_downvotes = Self::downvotes_allowed(env, voting, board_id);
+
+            // Votes are frozen on locked, hidden, deleted and archived threads
+            let votes_open = Self::is_thread_votable(env.clone(), board_id, thread_id);
+            // Authors can't vote on their own thread, but can still withdraw an earlier vote
+            let can_upvote = votes_open
+                && viewer.is_some()
+                && (!viewer_is_author || viewer_vote == VoteDirection::Up);
+            let can_downvote = votes_open
+                && viewer.is_some()
+                && (!viewer_is_author || viewer_vote == VoteDirection::Down);
+
+            md = md.div_start("vote-buttons");
+
+            // Upvote button
+            if can_upvote {
+                let up_class = if viewer_vote == VoteDirection::Up {
+                    "vote-up vote-active"
+                } else {
+                    "vote-up"
+                };
+                md = md
+                    .raw_str("<a href=\"tx:@voting:vote_thread {&quot;board_id&quot;:")
+                    .number(board_id as u32)
+                    .raw_str(",&quot;thread_id&quot;:")
+                    .number(thread_id as u32)
+                    .raw_str(",&quot;direction&quot;:");
+                // Toggle: if already up, set to none; otherwise set to up
+                if viewer_vote == VoteDirection::Up {
+                    md = md.raw_str("0"); // None
+                } else {
+                    md = md.raw_str("1"); // Up
+                }
+                md = md
+                    .raw_str("}\" class=\"")
+                    .raw_str(up_class)
+                    .raw_str("\">▲</a>");
+            } else if votes_open && viewer_is_author {
+                md = Self::render_own_vote_arrow(md, "vote-up", "▲");
+            } else {
+                md = md.raw_str("<span class=\"vote-up vote-disabled\">▲</span>");
+            }
+
+            // Score display
+            md = md
+                .raw_str("<span class=\"vote-score\">")
+                .number(tally.score as u32)
+                .raw_str("</span>");
+
+            // Downvote button (omitted when the board disallows downvotes)
+            if allow_downvotes && can_downvote {
+                let down_class = if viewer_vote == VoteDirection::Down {
+                    "vote-down vote-active"
+                } else {
+                    "vote-down"
+                };
+                md = md
+                    .raw_str("<a href=\"tx:@voting:vote_thread {&quot;board_id&quot;:")
+                    .number(board_id as u32)
+                    .raw_str(",&quot;thread_id&quot;:")
+                    .number(thread_id as u32)
+                    .raw_str(",&quot;direction&quot;:");
+                // Toggle: if already down, set to none; otherwise set to down
+                if viewer_vote == VoteDirection::Down {
+                    md = md.raw_str("0"); // None
+                } else {
+                    md = md.raw_str("2"); // Down
+                }
+                md = md
+                    .raw_str("}\" class=\"")
+                    .raw_str(down_class)
+                    .raw_str("\">▼</a>");
+            } else if allow_downvotes && votes_open && viewer_is_author {
+                md = Self::render_own_vote_arrow(md, "vote-down", "▼");
+            } else if allow_downvotes {
+                md = md.raw_str("<span class=\"vote-down vote-disabled\">▼</span>");
+            }
+
+            md = md.div_end().newline();
+
+            md = Self::render_reactions(env, md, voting, board_id, thread_id, viewer);
+        }
+
+        // Thread actions (only show if viewer is logged in and posting is allowed)
+        if viewer.is_some() && can_post {
+            md = md
+                .div_start("thread-actions")
+                .raw_str("[Reply to Thread](render:")
+                .raw(base_path.clone())
+                .raw_str("/t/")
+                .number(thread_id as u32)
+                .raw_str("/reply) [Quote](render:")
+                .raw(base_path.clone())
+                .raw_str("/t/")
+                .number(thread_id as u32)
+                .raw_str("/quote)");
+
+            // Show edit button if user can edit
+            if let Some(ref t) = thread {
+                let (is_author, is_moderator) = Self::can_edit(&t.creator, viewer, &viewer_perms);
+                let can_edit_time =
+                    is_moderator || Self::is_within_edit_window(env, board_id, t.created_at);
+
+                if (is_author || is_moderator) && can_edit_time {
+                    md = md
+                        .text(" ")
+                        .raw_str("[Edit](render:")
+                        .raw(base_path.clone())
+                        .raw_str("/t/")
+                        .number(thread_id as u32)
+                        .raw_str("/edit)");
+                }
+            }
+
+            // Crosspost button (only if not already a crosspost)
+            if crosspost_ref.is_none() && removed_crosspost.is_none() {
+                md = md
+                    .text(" ")
+                    .raw_str("[Crosspost](render:/crosspost?from_board=")
+                    .text_string(&board_meta.slug)
+                    .raw_str("&from_thread=")
+                    .number(thread_id as u32)
+                    .raw_str(")");
+
+                // Show crosspost count if any (skipped when over the render budget)
+                let xpost_count_args: Vec<Val> =
+                    Vec::from_array(env, [board_id.into_val(env), thread_id.into_val(env)]);
+                let xpost_count: u32 = match content {
+                    Some(ref content) if !over_budget => env
+                        .try_invoke_contract::<u32, soroban_sdk::Error>(
+                            content,
+                            &Symbol::new(env, "get_crosspost_count"),
+                            xpost_count_args,
+                        )
+                        .ok()
+                        .and_then(|r| r.ok())
+                        .unwrap_or(0),
+                    _ => 0,
+                };
+
+                if xpost_count > 0 {
+                    md = md
+                        .raw_str(" <a href=\"render:")
+                        .raw(base_path.clone())
+                        .raw_str("/t/")
+                        .number(thread_id as u32)
+                        .raw_str("/crossposts\" class=\"crosspost-count\">")
+                        .number(xpost_count)
+                        .raw_str(" crosspost");
+                    if xpost_count > 1 {
+                        md = md.raw_str("s");
+                    }
+                    md = md.raw_str("</a>");
+                }
+            }
+
+            md = md.div_end().newline();
+        }
+
+        // Moderator controls (only show for moderator+ users)
+        // Uses raw HTML links since we're inside a div (markdown not processed in HTML blocks)
+        if viewer_can_moderate {
+            if let Some(ref user) = viewer {
+                md = md
+                    .div_start("mod-actions")
+                    .raw_str("<strong>Mod Actions:</strong> ")
+                    // Hidden fields for all actions
+                    .raw_str("<input type=\"hidden\" name=\"board_id\" value=\"")
+                    .number(board_id as u32)
+                    .raw_str("\" />")
+                    .raw_str("<input type=\"hidden\" name=\"thread_id\" value=\"")
+                    .number(thread_id as u32)
+                    .raw_str("\" />")
+                    .raw_str("<input type=\"hidden\" name=\"caller\" value=\"")
+                    .text_string(&user.to_string())
+                    .raw_str("\" />");
+
+                // Lock/Unlock
+                if is_locked {
+                    md = md.raw_str("<a href=\"form:@admin:unlock_thread\">[Unlock]</a>");
+                } else {
+                    md = md.raw_str("<a href=\"form:@admin:lock_thread\">[Lock]</a>");
+                }
+
+                // Hide/Unhide
+                if is_hidden {
+                    md = md.raw_str(" <a href=\"form:@admin:unhide_thread\">[Unhide]</a>");
+                } else {
+                    md = md.raw_str(" <a href=\"form:@admin:hide_thread\">[Hide]</a>");
+                }
+
+                // Pin/Unpin
+                if is_pinned {
+                    md = md.raw_str(" <a href=\"form:@admin:unpin_thread\">[Unpin]</a>");
+                } else {
+                    md = md.raw_str(" <a href=\"form:@admin:pin_thread\">[Pin]</a>");
+                }
+
+                md = md.div_end().newline();
+                md = Self::render_schedule_form(env, md, board_id, thread_id, user);
+            }
+        }
+
+        // Fetch reply count
+        let reply_count: Option<u64> = content.as_ref().map(|content| {
+            env.invoke_contract(content, &Symbol::new(env, "get_reply_count"), args)
+        });
+
+        // Reply order selector (Top only when there are votes to rank by)
+        if reply_count.is_some_and(|count| count > 1) {
+            md = md
+                .div_start("sort-selector reply-sort")
+                .raw_str("<span class=\"sort-label\">Sort replies:</span>");
+            for option in [ReplySort::Oldest, ReplySort::Newest, ReplySort::Top] {
+                if option == ReplySort::Top && voting_contract.is_none() {
+                    continue;
+                }
+                let name = Self::reply_sort_name(option);
+                md = md
+                    .raw_str("<a href=\"render:")
+                    .raw(base_path.clone())
+                    .raw_str("/t/")
+                    .number(thread_id as u32)
+                    .raw_str("?sort=")
+                    .raw_str(name)
+                    .raw_str(if option == sort {
+                        "\" class=\"sort-option sort-active\">"
+                    } else {
+                        "\" class=\"sort-option\">"
+                    })
+                    .raw_str(name)
+                    .raw_str("</a>");
+            }
+            md = md.div_end();
+        }
+
+        md = md.raw_str("<h2>Replies</h2>\n");
+
+        if reply_count.is_none() {
+            md = md.paragraph("Replies are unavailable.");
+        } else if reply_count == Some(0) {
+            md = md.paragraph("No replies yet. Be the first to respond!");
+        } else {
+            // Use waterfall loading with slug-based path
+            md = md
+                .raw_str("{{render path=\"")
+                .raw(base_path.clone())
+                .raw_str("/t/")
+                .number(thread_id as u32)
+                .raw_str("/replies/0/")
+                .number(Self::encode_batch_ctx(can_post, &viewer_perms));
+            if sort != ReplySort::Oldest {
+                md = md.raw_str("/").raw_str(Self::reply_sort_name(sort));
+            }
+            md = md.raw_str("\"}}");
+        }
+
+        Self::render_footer_into(env, md).build()
+    }
+
+    /// Render a batch of top-level replies
+    fn render_replies_batch(
+        env: &Env,
+        board_id: u64,
+        thread_id: u64,
+        start: u32,
+        ctx: Option<u32>,
+        sort: ReplySort,
+        viewer: &Option<Address>,
+    ) -> Bytes {
+        let Some(content) = env
+            .storage()
+            .instance()
+            .get::<_, Address>(&BoardKey::Content)
+        else {
+            return Bytes::from_slice(env, CONTENT_UNAVAILABLE.as_bytes());
+        };
+
+        // Get profile contract for author display
+        let profile_contract = Self::get_profile_contract(env);
+
+        // Get voting contract for vote buttons
+        let voting_contract: Option<Address> = env.storage().instance().get(&BoardKey::Voting);
+        let blocked = Self::blocked_for(env, viewer);
+
+        // Get board metadata for slug-based URLs
+        let board_meta: BoardMeta = env
+            .storage()
+            .persistent()
+            .get(&BoardKey::Board(board_id))
+            .expect("Board not found");
+
+        // Build base path for all links
+        let base_path = Self::build_board_base_path(env, board_id, &board_meta.slug);
+
+        let config: BoardConfig = env
+            .storage()
+            .persistent()
+            .get(&BoardKey::BoardConfig(board_id))
+            .expect("Board not found");
+        let chunk_size = if config.reply_chunk_size == 0 {
+            6
+        } else {
+            config.reply_chunk_size
+        };
+
+        // The continuation ctx from the page that embedded this batch stands in for the
+        // permission and thread lookups; private boards always re-check membership
+        let (viewer_perms, can_post) = match ctx {
+            Some(ctx) if !config.is_private => Self::decode_batch_ctx(ctx),
+            _ => {
+                // Get viewer role for permission check
+                let perms_addr_opt = env
+                    .storage()
+                    .instance()
+                    .get::<_, Address>(&BoardKey::Permissions);
+                let viewer_perms = Self::viewer_permissions(env, board_id, viewer);
+                let viewer_role = viewer_perms.role;
+
+                // Check permissions for private boards - must be Member+ to access
+                if config.is_private {
+                    if let Some(ref perms_addr) = perms_addr_opt {
+                        if (viewer_role as u32) < (Role::Member as u32) {
+                            return Self::render_private_board_message(
+                                env, board_id, &config, viewer, perms_addr,
+                            );
+                        }
+                    }
+                }
+
+                // Determine if posting is allowed (Member+ role, not readonly, not locked)
+                let thread = env
+                    .storage()
+                    .persistent()
+                    .get::<_, ThreadMeta>(&BoardKey::BoardThread(board_id, thread_id));
+                let is_locked = thread
+                    .as_ref()
+                    .map(|t| Self::replies_closed(env, board_id, t))
+                    .unwrap_or(false);
+                let viewer_can_post = (viewer_role as u32) >= (Role::Member as u32);
+                (
+                    viewer_perms,
+                    !config.is_readonly && !is_locked && viewer_can_post,
+                )
+            }
+        };
+        let ctx = Self::encode_batch_ctx(can_post, &viewer_perms);
+
+        // Get total reply count
+        let count_args: Vec<Val> =
+            Vec::from_array(env, [board_id.into_val(env), thread_id.into_val(env)]);
+        let total_count: u64 =
+            env.invoke_contract(&content, &Symbol::new(env, "get_reply_count"), count_args);
+
+        // Fetch this batch of replies (oldest-first is the plain listing)
+        let mut list_args: Vec<Val> = Vec::from_array(
+            env,
+            [
+                board_id.into_val(env),
+                thread_id.into_val(env),
+                start.into_val(env),
+                chunk_size.into_val(env),
+            ],
+        );
+        let list_fn = if sort == ReplySort::Oldest {
+            "list_top_level_replies"
+        } else {
+            list_args.push_back(sort.into_val(env));
+            "list_top_level_replies_sorted"
+        };
+        let replies: Vec<ReplyMeta> =
+            env.invoke_contract(&content, &Symbol::new(env, list_fn), list_args);
+
+        // Render each distinct author once for the whole batch
+        let return_path =
+            Self::build_thread_return_path(env, board_id, &board_meta.slug, thread_id);
+        let author_cards = Self::render_author_cards(env, &replies, &profile_contract, return_path);
+        let ids = Self::reply_ids(env, &replies);
+        let edited = Self::edited_reply_ids(env, &content, board_id, thread_id, ids.clone());
+        let distinguished =
+            Self::distinguished_reply_ids(env, &content, board_id, thread_id, ids.clone());
+        // Downvotes are allowed or not for the whole batch, so ask the voting contract once
+        let allow_downvotes = voting_contract
+            .as_ref()
+            .map(|voting| Self::downvotes_allowed(env, voting, board_id))
+            .unwrap_or(true);
+        let reactions =
+            Self::reply_reactions(env, &voting_contract, board_id, thread_id, &ids, viewer);
+
+        let mut md = MarkdownBuilder::new(env);
+
+        for i in 0..replies.len() {
+            if let Some(reply) = replies.get(i) {
+                md = Self::render_reply_item_waterfall(
+                    env,
+                    md,
+                    &content,
+                    &reply,
+                    board_id,
+                    thread_id,
+                    &base_path,
+                    &board_meta.slug,
+                    viewer,
+                    &viewer_perms,
+                    &blocked,
+                    can_post,
+                    &profile_contract,
+                    &author_cards,
+                    &edited,
+                    &distinguished,
+                    &voting_contract,
+                    allow_downvotes,
+                    &reactions,
+                    true,
+                    0,
+                );
+            }
+        }
+
+        // If more replies exist, add continuation with slug-based path
+        let next_start = start + chunk_size;
+        if (next_start as u64) < total_count {
+            md = md
+                .raw_str("{{render path=\"")
+                .raw(base_path)
+                .raw_str("/t/")
+                .number(thread_id as u32)
+                .raw_str("/replies/")
+                .number(next_start)
+                .raw_str("/")
+                .number(ctx);
+            if sort != ReplySort::Oldest {
+                md = md.raw_str("/").raw_str(Self::reply_sort_name(sort));
+            }
+            md = md.raw_str("\"}}");
+        }
+
+        md.build()
+    }
+
+    /// Render a batch of children for a reply
+    fn render_children_batch(
+        env: &Env,
+        board_id: u64,
+        thread_id: u64,
+        parent_id: u64,
+        start: u32,
+        ctx: Option<u32>,
+        viewer: &Option<Address>,
+    ) -> Bytes {
+        let Some(content) = env
+            .storage()
+            .instance()
+            .get::<_, Address>(&BoardKey::Content)
+        else {
+            return Bytes::from_slice(env, CONTENT_UNAVAILABLE.as_bytes());
+        };
+
+        // Get profile contract for author display
+        let profile_contract = Self::get_profile_contract(env);
+
+        // Get voting contract for vote buttons
+        let voting_contract: Option<Address> = env.storage().instance().get(&BoardKey::Voting);
+        let blocked = Self::blocked_for(env, viewer);
+
+        // Get board metadata for slug-based URLs
+        let board_meta: BoardMeta = env
+            .storage()
+            .persistent()
+            .get(&BoardKey::Board(board_id))
+            .expect("Board not found");
+
+        // Build base path for all links
+        let base_path = Self::build_board_base_path(env, board_id, &board_meta.slug);
+
+        let config: BoardConfig = env
+            .storage()
+            .persistent()
+            .get(&BoardKey::BoardConfig(board_id))
+            .expect("Board not found");
+        let chunk_size = if config.reply_chunk_size == 0 {
+            6
+        } else {
+            config.reply_chunk_size
+        };
+
+        // The continuation ctx from the page that embedded this batch stands in for the
+        // permission and thread lookups; private boards always re-check membership
+        let (viewer_perms, can_post) = match ctx {
+            Some(ctx) if !config.is_private => Self::decode_batch_ctx(ctx),
+            _ => {
+                // Get viewer role for permission check
+                let perms_addr_opt = env
+                    .storage()
+                    .instance()
+                    .get::<_, Address>(&BoardKey::Permissions);
+                let viewer_perms = Self::viewer_permissions(env, board_id, viewer);
+                let viewer_role = viewer_perms.role;
+
+                // Check permissions for private boards - must be Member+ to access
+                if config.is_private {
+                    if let Some(ref perms_addr) = perms_addr_opt {
+                        if (viewer_role as u32) < (Role::Member as u32) {
+                            return Self::render_private_board_message(
+                                env, board_id, &config, viewer, perms_addr,
+                            );
+                        }
+                    }
+                }
+
+                // Determine if posting is allowed (Member+ role, not readonly, not locked)
+                let thread = env
+                    .storage()
+                    .persistent()
+                    .get::<_, ThreadMeta>(&BoardKey::BoardThread(board_id, thread_id));
+                let is_locked = thread
+                    .as_ref()
+                    .map(|t| Self::replies_closed(env, board_id, t))
+                    .unwrap_or(false);
+                let viewer_can_post = (viewer_role as u32) >= (Role::Member as u32);
+                (
+                    viewer_perms,
+                    !config.is_readonly && !is_locked && viewer_can_post,
+                )
+            }
+        };
+        // Subtree pages pass their root's depth along above the permission bits
+        let depth_offset = ctx.map_or(0, |ctx| ctx >> 2);
+        let ctx = Self::encode_batch_ctx(can_post, &viewer_perms) | (depth_offset << 2);
+
+        // Get total children count
+        let count_args: Vec<Val> = Vec::from_array(
+            env,
+            [
+                board_id.into_val(env),
+                thread_id.into_val(env),
+                parent_id.into_val(env),
+            ],
+        );
+        let total_count: u32 = env.invoke_contract(
+            &content,
+            &Symbol::new(env, "get_children_count"),
+            count_args,
+        );
+
+        // Fetch this batch of children
+        let list_args: Vec<Val> = Vec::from_array(
+            env,
+            [
+                board_id.into_val(env),
+                thread_id.into_val(env),
+                parent_id.into_val(env),
+                start.into_val(env),
+                chunk_size.into_val(env),
+            ],
+        );
+        let children: Vec<ReplyMeta> = env.invoke_contract(
+            &content,
+            &Symbol::new(env, "list_children_replies"),
+            list_args,
+        );
+
+        // Render each distinct author once for the whole batch
+        let return_path =
+            Self::build_thread_return_path(env, board_id, &board_meta.slug, thread_id);
+        let author_cards =
+            Self::render_author_cards(env, &children, &profile_contract, return_path);
+        let ids = Self::reply_ids(env, &children);
+        let edited = Self::edited_reply_ids(env, &content, board_id, thread_id, ids.clone());
+        let distinguished =
+            Self::distinguished_reply_ids(env, &content, board_id, thread_id, ids.clone());
+        // Downvotes are allowed or not for the whole batch, so ask the voting contract once
+        let allow_downvotes = voting_contract
+            .as_ref()
+            .map(|voting| Self::downvotes_allowed(env, voting, board_id))
+            .unwrap_or(true);
+        let reactions =
+            Self::reply_reactions(env, &voting_contract, board_id, thread_id, &ids, viewer);
+
+        let mut md = MarkdownBuilder::new(env);
+
+        for i in 0..children.len() {
+            if let Some(child) = children.get(i) {
+                md = Self::render_reply_item_waterfall(
+                    env,
+                    md,
+                    &content,
+                    &child,
+                    board_id,
+                    thread_id,
+                    &base_path,
+                    &board_meta.slug,
+                    viewer,
+                    &viewer_perms,
+                    &blocked,
+                    can_post,
+                    &profile_contract,
+                    &author_cards,
+                    &edited,
+                    &distinguished,
+                    &voting_contract,
+                    allow_downvotes,
+                    &reactions,
+                    true,
+                    depth_offset,
+                );
+            }
+        }
+
+        // If more children exist, add continuation with slug-based path
+        let next_start = start + chunk_size;
+        if next_start < total_count {
+            md = md
+                .raw_str("{{render path=\"")
+                .raw(base_path)
+                .raw_str("/t/")
+                .number(thread_id as u32)
+                .raw_str("/r/")
+                .number(parent_id as u32)
+                .raw_str("/children/")
+                .number(next_start)
+                .raw_str("/")
+                .number(ctx)
+                .raw_str("\"}}");
+        }
+
+        md.build()
+    }
+
+    /// Render a single reply permalink, with its direct parent shown for context.
+    /// As a `subtree` page (reached from "Continue this thread") the reply is the
+    /// root under thread breadcrumbs and the parent is only linked.
+    fn render_reply_permalink(
+        env: &Env,
+        board_id: u64,
+        thread_id: u64,
+        reply_id: u64,
+        subtree: bool,
+        viewer: &Option<Address>,
+    ) -> Bytes {
+        let Some(content) = env
+            .storage()
+            .instance()
+            .get::<_, Address>(&BoardKey::Content)
+        else {
+            return Self::render_content_unavailable(env, board_id, thread_id, viewer);
+        };
+
+        // Get profile contract for author display
+        let profile_contract = Self::get_profile_contract(env);
+
+        // Get voting contract for vote buttons
+        let voting_contract: Option<Address> = env.storage().instance().get(&BoardKey::Voting);
+        let blocked = Self::blocked_for(env, viewer);
+
+        // Get board metadata for slug-based URLs
+        let board_meta: BoardMeta = env
+            .storage()
+            .persistent()
+            .get(&BoardKey::Board(board_id))
+            .expect("Board not found");
+
+        // Build base path for all links
+        let base_path = Self::build_board_base_path(env, board_id, &board_meta.slug);
+
+        let config: BoardConfig = env
+            .storage()
+            .persistent()
+            .get(&BoardKey::BoardConfig(board_id))
+            .expect("Board not found");
+
+        // Get viewer role for permission check and moderator reveal
+        let perms_addr_opt = env
+            .storage()
+            .instance()
+            .get::<_, Address>(&BoardKey::Permissions);
+        let viewer_perms = Self::viewer_permissions(env, board_id, viewer);
+        let viewer_role = viewer_perms.role;
+        let viewer_can_moderate = (viewer_role as u32) >= (Role::Moderator as u32);
+
+        // Check permissions for private boards - must be Member+ to access
+        if config.is_private {
+            if let Some(ref perms_addr) = perms_addr_opt {
+                if (viewer_role as u32) < (Role::Member as u32) {
+                    return Self::render_private_board_message(
+                        env, board_id, &config, viewer, perms_addr,
+                    );
+                }
+            }
+        }
+
+        // Replies in hidden threads stay hidden along with the thread
+        let thread = env
+            .storage()
+            .persistent()
+            .get::<_, ThreadMeta>(&BoardKey::BoardThread(board_id, thread_id));
+        let is_hidden = thread.as_ref().map(|t| t.is_hidden).unwrap_or(false);
+        let viewer_is_author = match (viewer, thread.as_ref()) {
+            (Some(v), Some(t)) => *v == t.creator,
+            _ => false,
+        };
+        if is_hidden && !viewer_can_moderate && !viewer_is_author {
+            return Self::render_hidden_thread_message(env, board_id, viewer);
+        }
+
+        // Determine if posting is allowed (requires Member+ role, not readonly, not locked)
+        let is_locked = thread
+            .as_ref()
+            .map(|t| Self::replies_closed(env, board_id, t))
+            .unwrap_or(false);
+        let viewer_can_post = (viewer_role as u32) >= (Role::Member as u32);
+        let can_post = !config.is_readonly && !is_locked && viewer_can_post;
+
+        let mut md = Self::render_nav(env, board_id, viewer).newline();
+        if subtree {
+            md = Self::render_breadcrumbs(env, md, board_id, Some(thread_id), Some("Continued"));
+        } else {
+            md = md
+                .raw_str("<div class=\"back-nav\"><a href=\"render:")
+                .raw(base_path.clone())
+                .raw_str("/t/")
+                .number(thread_id as u32)
+                .raw_str("\" class=\"back-link\">← View full thread</a></div>\n");
+        }
+
+        if let Some(ref t) = thread {
+            md = md.raw_str("<h1>").text_string(&t.title).raw_str("</h1>\n");
+        }
+
+        let reply_args: Vec<Val> = Vec::from_array(
+            env,
+            [
+                board_id.into_val(env),
+                thread_id.into_val(env),
+                reply_id.into_val(env),
+            ],
+        );
+        let reply: Option<ReplyMeta> =
+            env.invoke_contract(&content, &Symbol::new(env, "get_reply"), reply_args);
+
+        let Some(reply) = reply else {
+            md = md.warning("Reply not found.");
+            return Self::render_footer_into(env, md).build();
+        };
+
+        // One lookup each covers edit and moderator markers for the reply and its parent
+        let mut ids = Vec::from_array(env, [reply.id]);
+        if !subtree && reply.depth > 0 {
+            ids.push_back(reply.parent_id);
+        }
+        let edited = Self::edited_reply_ids(env, &content, board_id, thread_id, ids.clone());
+        let distinguished =
+            Self::distinguished_reply_ids(env, &content, board_id, thread_id, ids.clone());
+        // Downvotes are allowed or not for the whole batch, so ask the voting contract once
+        let allow_downvotes = voting_contract
+            .as_ref()
+            .map(|voting| Self::downvotes_allowed(env, voting, board_id))
+            .unwrap_or(true);
+        let reactions =
+            Self::reply_reactions(env, &voting_contract, board_id, thread_id, &ids, viewer);
+
+        // Direct parent for context (top-level replies have depth 0 and no parent)
+        if subtree && reply.depth > 0 {
+            md = md
+                .div_start("reply-context")
+                .raw_str("<a href=\"render:")
+                .raw(base_path.clone())
+                .raw_str("/t/")
+                .number(thread_id as u32)
+                .raw_str("/r/")
+                .number(reply.parent_id as u32)
+                .raw_str("\">↑ Parent reply #")
+                .number(reply.parent_id as u32)
+                .raw_str("</a>")
+                .div_end()
+                .newline();
+        } else if reply.depth > 0 {
+            let parent_args: Vec<Val> = Vec::from_array(
+                env,
+                [
+                    board_id.into_val(env),
+                    thread_id.into_val(env),
+                    reply.parent_id.into_val(env),
+                ],
+            );
+            let parent: Option<ReplyMeta> =
+                env.invoke_contract(&content, &Symbol::new(env, "get_reply"), parent_args);
+            if let Some(parent) = parent {
+                md = md
+                    .div_start("reply-context")
+                    .raw_str("<a href=\"render:")
+                    .raw(base_path.clone())
+                    .raw_str("/t/")
+                    .number(thread_id as u32)
+                    .raw_str("/r/")
+                    .number(parent.id as u32)
+                    .raw_str("\">In reply to #")
+                    .number(parent.id as u32)
+                    .raw_str("</a>\n");
+                md = Self::render_reply_item_waterfall(
+                    env,
+                    md,
+                    &content,
+                    &parent,
+                    board_id,
+                    thread_id,
+                    &base_path,
+                    &board_meta.slug,
+                    viewer,
+                    &viewer_perms,
+                    &blocked,
+                    false,
+                    &profile_contract,
+                    &Map::new(env),
+                    &edited,
+                    &distinguished,
+                    &voting_contract,
+                    allow_downvotes,
+                    &reactions,
+                    false,
+                    0,
+                );
+                md = md.div_end();
+            }
+        }
+
+        md = md.div_start("reply-permalink");
+        md = Self::render_reply_item_waterfall(
+            env,
+            md,
+            &content,
+            &reply,
+            board_id,
+            thread_id,
+            &base_path,
+            &board_meta.slug,
+            viewer,
+            &viewer_perms,
+            &blocked,
+            can_post,
+            &profile_contract,
+            &Map::new(env),
+            &edited,
+            &distinguished,
+            &voting_contract,
+            allow_downvotes,
+            &reactions,
+            true,
+            reply.depth,
+        );
+
+        // Moderators can reveal hidden content behind the tombstone
+        // (deleted replies have their content cleared, so there is nothing to reveal)
+        let viewer_is_reply_author = viewer.as_ref() == Some(&reply.creator);
+        if reply.is_hidden && !reply.is_deleted && viewer_can_moderate && !viewer_is_reply_author {
+            let content_args: Vec<Val> = Vec::from_array(
+                env,
+                [
+                    board_id.into_val(env),
+                    thread_id.into_val(env),
+                    reply.id.into_val(env),
+                ],
+            );
+            let content_bytes: Bytes = env.invoke_contract(
+                &content,
+                &Symbol::new(env, "get_reply_content"),
+                content_args,
+            );
+            md = md
+                .raw_str("<details class=\"mod-reveal\"><summary>Show hidden content")
+                .raw_str(" (moderators only)</summary>\n")
+                .div_start("reply-content")
+                .raw(content_bytes)
+                .div_end()
+                .raw_str("</details>\n");
+        }
+        md = md.div_end();
+
+        Self::render_footer_into(env, md).build()
+    }
+
+    /// Render a single reply with waterfall loading for children
+    fn render_reply_item_waterfall<'a>(
+        env: &Env,
+        mut md: MarkdownBuilder<'a>,
+        content: &Address,
+        reply: &ReplyMeta,
+        board_id: u64,
+        thread_id: u64,
+        base_path: &Bytes,
+        board_slug: &String,
+        viewer: &Option<Address>,
+        viewer_perms: &PermissionSet,
+        blocked: &Vec<Address>,
+        can_post: bool,
+        profile_contract: &Option<Address>,
+        author_cards: &Map<Address, Bytes>,
+        edited: &Vec<u64>,
+        distinguished: &Vec<u64>,
+        voting_contract: &Option<Address>,
+        allow_downvotes: bool,
+        reactions: &Map<u64, (Vec<(Symbol, u32)>, Vec<Symbol>)>,
+        load_children: bool,
+        depth_offset: u32,
+    ) -> MarkdownBuilder<'a> {
+        let is_distinguished = !reply.is_deleted && distinguished.contains(reply.id);
+        md = md.div_start(if is_distinguished {
+            "reply distinguished"
+        } else {
+            "reply"
+        });
+
+        // The vote tally also decides whether a low-scoring reply starts collapsed
+        let tally: Option<VoteTally> = voting_contract.as_ref().map(|voting| {
+            let tally_args: Vec<Val> = Vec::from_array(
+                env,
+                [
+                    board_id.into_val(env),
+                    thread_id.into_val(env),
+                    reply.id.into_val(env),
+                ],
+            );
+            env.invoke_contract(voting, &Symbol::new(env, "get_reply_tally"), tally_args)
+        });
+
+        // Reply header with author (with return path so "Go Back" returns to thread)
+        md = md.div_start("reply-header");
+        md = match author_cards.get(reply.creator.clone()) {
+            Some(card) => md.raw(card),
+            None => {
+                let return_path =
+                    Self::build_thread_return_path(env, board_id, board_slug, thread_id);
+                Self::render_author(
+                    env,
+                    md,
+                    board_id,
+                    &reply.creator,
+                    profile_contract,
+                    Some(return_path),
+                )
+            }
+        };
+        if is_distinguished {
+            md = md.raw_str(
+                " <span class=\"badge badge-mod\" title=\"Posted as a moderator\">MOD</span>",
+            );
+        }
+        md = md
+            .raw_str(" · <a href=\"render:")
+            .raw(base_path.clone())
+            .raw_str("/t/")
+            .number(thread_id as u32)
+            .raw_str("/r/")
+            .number(reply.id as u32)
+            .raw_str("\" class=\"reply-permalink\">Reply #")
+            .number(reply.id as u32)
+            .raw_str("</a> · ")
+            .raw(Self::format_board_timestamp(
+                env,
+                board_id,
+                reply.created_at,
+            ));
+        let is_accepted = !reply.is_deleted
+            && Self::get_accepted_answer(env.clone(), board_id, thread_id) == Some(reply.id);
+        if is_accepted {
+            md = md.raw_str(" <span class=\"badge badge-accepted\">✓ Accepted answer</span>");
+        }
+        let is_edited = !reply.is_deleted && edited.contains(reply.id);
+        if is_edited {
+            let (_, is_moderator) = Self::can_edit(&reply.creator, viewer, viewer_perms);
+            md = Self::render_edited_marker(
+                env,
+                md,
+                base_path,
+                thread_id,
+                Some(reply.id),
+                is_moderator,
+            );
+        }
+        md = md.div_end();
+
+        // Reply content (authors still see their own hidden replies)
+        if reply.is_hidden && viewer.as_ref() != Some(&reply.creator) {
+            md = md
+                .div_start("reply-content reply-hidden")
+                .text("[This reply has been hidden by a moderator]")
+                .div_end();
+        } else if reply.is_deleted {
+            md = md
+                .div_start("reply-content reply-deleted")
+                .text("[This reply has been deleted]")
+                .div_end();
+        } else {
+            let args: Vec<Val> = Vec::from_array(
+                env,
+                [
+                    board_id.into_val(env),
+                    thread_id.into_val(env),
+                    reply.id.into_val(env),
+                ],
+            );
+            let content_bytes: Bytes =
+                env.invoke_contract(content, &Symbol::new(env, "get_reply_content"), args);
+
+            // Blocked authors, low scores and heavy flagging start collapsed;
+            // moderators see the latter two expanded
+            let linked = Self::link_mentions(env, &content_bytes, profile_contract);
+            let blocked_author = blocked.contains(&reply.creator);
+            let low_score = tally.as_ref().map(|t| t.score).filter(|score| {
+                !viewer_perms.can_moderate
+                    && *score < Self::get_collapse_score(env.clone(), board_id)
+            });
+            let heavily_flagged = !viewer_perms.can_moderate
+                && reply.flag_count > 0
+                && reply.flag_count * 2 >= Self::get_flag_threshold(env, board_id);
+            if blocked_author {
+                md = md.raw_str(BLOCKED_CONTENT_SUMMARY);
+            } else if let Some(score) = low_score {
+                md = md
+                    .raw_str(LOW_SCORE_SUMMARY_START)
+                    .raw_str(if score < 0 { "−" } else { "" })
+                    .number(score.unsigned_abs())
+                    .raw_str(") — show</summary>\n");
+            } else if heavily_flagged {
+                md = md.raw_str(FLAGGED_CONTENT_SUMMARY);
+            }
+            md = md.div_start("reply-content").raw(linked).div_end();
+            if blocked_author || low_score.is_some() || heavily_flagged {
+                md = md.raw_str("</details>\n");
+            }
+        }
+
+        // Vote buttons for reply (if voting contract is configured)
+        if let (Some(voting), Some(tally)) = (voting_contract, tally) {
+            // Get viewer's current vote (if logged in)
+            let viewer_vote = if let Some(ref user) = viewer {
+                let vote_args: Vec<Val> = Vec::from_array(
+                    env,
+                    [
+                        board_id.into_val(env),
+                        thread_id.into_val(env),
+                        reply.id.into_val(env),
+                        user.into_val(env),
+                    ],
+                );
+                env.invoke_contract::<VoteDirection>(
+                    voting,
+                    &Symbol::new(env, "get_user_reply_vote"),
+                    vote_args,
+                )
+            } else {
+                VoteDirection::None
+            };
+
+            // Votes are frozen on locked, hidden, deleted and archived threads
+            let votes_open = Self::is_thread_votable(env.clone(), board_id, thread_id);
+            // Authors can't vote on their own reply, but can still withdraw an earlier vote
+            let viewer_is_author = viewer.as_ref() == Some(&reply.creator);
+            let can_upvote = votes_open
+                && viewer.is_some()
+                && (!viewer_is_author || viewer_vote == VoteDirection::Up);
+            let can_downvote = votes_open
+                && viewer.is_some()
+                && (!viewer_is_author || viewer_vote == VoteDirection::Down);
+
+            md = md.div_start("reply-votes");
+
+            // Upvote button
+            if can_upvote {
+                let up_class = if viewer_vote == VoteDirection::Up {
+                    "vote-up vote-active"
+                } else {
+                    "vote-up"
+                };
+                md = md
+                    .raw_str("<a href=\"tx:@voting:vote_reply {&quot;board_id&quot;:")
+                    .number(board_id as u32)
+                    .raw_str(",&quot;thread_id&quot;:")
+                    .number(thread_id as u32)
+                    .raw_str(",&quot;reply_id&quot;:")
+                    .number(reply.id as u32)
+                    .raw_str(",&quot;direction&quot;:");
+                if viewer_vote == VoteDirection::Up {
+                    md = md.raw_str("0");
+                } else {
+                    md = md.raw_str("1");
+                }
+                md = md
+                    .raw_str("}\" class=\"")
+                    .raw_str(up_class)
+                    .raw_str("\">▲</a>");
+            } else if votes_open && viewer_is_author {
+                md = Self::render_own_vote_arrow(md, "vote-up", "▲");
+            } else {
+                md = md.raw_str("<span class=\"vote-up vote-disabled\">▲</span>");
+            }
+
+            // Score
+            md = md
+                .raw_str("<span class=\"vote-score-inline\">")
+                .number(tally.score as u32)
+                .raw_str("</span>");
+
+            // Downvote button (omitted when the board disallows downvotes)
+            if allow_downvotes && can_downvote {
+                let down_class = if viewer_vote == VoteDirection::Down {
+                    "vote-down vote-active"
+                } else {
+                    "vote-down"
+                };
+                md = md
+                    .raw_str("<a href=\"tx:@voting:vote_reply {&quot;board_id&quot;:")
+                    .number(board_id as u32)
+                    .raw_str(",&quot;thread_id&quot;:")
+                    .number(thread_id as u32)
+                    .raw_str(",&quot;reply_id&quot;:")
+                    .number(reply.id as u32)
+                    .raw_str(",&quot;direction&quot;:");
+                if viewer_vote == VoteDirection::Down {
+                    md = md.raw_str("0");
+                } else {
+                    md = md.raw_str("2");
+                }
+                md = md
+                    .raw_str("}\" class=\"")
+                    .raw_str(down_class)
+                    .raw_str("\">▼</a>");
+            } else if allow_downvotes && votes_open && viewer_is_author {
+                md = Self::render_own_vote_arrow(md, "vote-down", "▼");
+            } else if allow_downvotes {
+                md = md.raw_str("<span class=\"vote-down vote-disabled\">▼</span>");
+            }
+
+            md = md.div_end();
+
+            if let Some((counts, mine)) = reactions.get(reply.id) {
+                md = Self::render_reaction_chips(
+                    env,
+                    md,
+                    board_id,
+                    thread_id,
+                    Some(reply.id),
+                    &counts,
+                    &mine,
+                    viewer,
+                );
+            }
+        }
+
+        // Reply actions
+        md = md.div_start("reply-meta");
+
+        // Only show Reply button if posting is allowed and the reply can take children
+        if viewer.is_some() && can_post {
+            if reply.depth < Self::get_max_reply_depth(env.clone(), board_id) {
+                md = md
+                    .raw_str("[Reply](render:")
+                    .raw(base_path.clone())
+                    .raw_str("/t/")
+                    .number(thread_id as u32)
+                    .raw_str("/r/")
+                    .number(reply.id as u32)
+                    .raw_str("/reply)");
+            }
+
+            // Show edit button if user can edit (and reply is not deleted)
+            if !reply.is_deleted {
+                let (is_author, is_moderator) =
+                    Self::can_edit(&reply.creator, viewer, viewer_perms);
+                let can_edit_time =
+                    is_moderator || Self::is_within_edit_window(env, board_id, reply.created_at);
+
+                if (is_author || is_moderator) && can_edit_time {
+                    md = md
+                        .text(" ")
+                        .raw_str("[Edit](render:")
+                        .raw(base_path.clone())
+                        .raw_str("/t/")
+                        .number(thread_id as u32)
+                        .raw_str("/r/")
+                        .number(reply.id as u32)
+                        .raw_str("/edit)");
+                }
+            }
+        }
+
+        if let (Some(v), false) = (viewer, reply.is_deleted) {
+            md = Self::render_distinguish_toggle(
+                md,
+                " ",
+                board_id,
+                thread_id,
+                Some(reply.id),
+                is_distinguished,
+                *v == reply.creator,
+                viewer_perms.can_moderate,
+            );
+        }
+
+        // The thread author and moderators can mark (or unmark) the accepted answer
+        if let (Some(v), false) = (viewer, reply.is_deleted) {
+            let can_accept = viewer_perms.can_moderate
+                || env
+                    .storage()
+                    .persistent()
+                    .get::<_, ThreadMeta>(&BoardKey::BoardThread(board_id, thread_id))
+                    .is_some_and(|t| t.creator == *v);
+            if can_accept {
+                md = md
+                    .raw_str(" <a href=\"tx:@board:set_accepted_answer {&quot;board_id&quot;:")
+                    .number(board_id as u32)
+                    .raw_str(",&quot;thread_id&quot;:")
+                    .number(thread_id as u32)
+                    .raw_str(",&quot;reply_id&quot;:");
+                if is_accepted {
+                    md = md.raw_str("null}\" class=\"accept-answer\">Unmark answer</a>");
+                } else {
+                    md = md
+                        .number(reply.id as u32)
+                        .raw_str("}\" class=\"accept-answer\">✓ Accept answer</a>");
+                }
+            }
+        }
+
+        // Flag form (reason + optional details) is always available to logged in users
+        if viewer.is_some() {
+            md = md
+                .raw_str("<div data-form class=\"flag-form\">\n")
+                .raw_str("<input type=\"hidden\" name=\"board_id\" value=\"")
+                .number(board_id as u32)
+                .raw_str("\" />\n")
+                .raw_str("<input type=\"hidden\" name=\"thread_id\" value=\"")
+                .number(thread_id as u32)
+                .raw_str("\" />\n")
+                .raw_str("<input type=\"hidden\" name=\"reply_id\" value=\"")
+                .number(reply.id as u32)
+                .raw_str("\" />\n")
+                .raw_str("<select name=\"reason\">")
+                .raw_str("<option value=\"spam\">Spam</option>")
+                .raw_str("<option value=\"abuse\">Abuse</option>")
+                .raw_str("<option value=\"off-topic\">Off-topic</option>")
+                .raw_str("<option value=\"other\">Other</option>")
+                .raw_str("</select>\n")
+                .raw_str("<input type=\"text\" name=\"details\" maxlength=\"280\" ")
+                .raw_str("placeholder=\"Details (optional)\" />\n")
+                .form_link_to("Flag", "content", "flag_reply")
+                .raw_str("</div>\n");
+        }
+
+        md = md.div_end();
+
+        // Get children count (skipped when the reply is shown only for context)
+        let count_args: Vec<Val> = Vec::from_array(
+            env,
+            [
+                board_id.into_val(env),
+                thread_id.into_val(env),
+                reply.id.into_val(env),
+            ],
+        );
+        let children_count: u32 = if load_children {
+            env.invoke_contract(content, &Symbol::new(env, "get_children_count"), count_args)
+        } else {
+            0
+        };
+
+        // Past the collapse depth, children move to their own page instead of nesting further
+        let collapse = children_count > 0
+            && reply.depth.saturating_sub(depth_offset)
+                >= Self::get_collapse_depth(env.clone(), board_id);
+        // Large families load on demand rather than with the page
+        let collapse_over = Self::get_collapse_children_over(env.clone(), board_id);
+        let ctx = Self::encode_batch_ctx(can_post, viewer_perms) | (depth_offset << 2);
+        if collapse {
+            md = md
+                .raw_str("<a href=\"render:")
+                .raw(base_path.clone())
+                .raw_str("/t/")
+                .number(thread_id as u32)
+                .raw_str("/r/")
+                .number(reply.id as u32)
+                .raw_str("/subtree\" class=\"continue-thread\">Continue this thread →</a>\n");
+        } else if collapse_over > 0 && children_count > collapse_over {
+            md = md
+                .raw_str("<a href=\"render:")
+                .raw(base_path.clone())
+                .raw_str("/t/")
+                .number(thread_id as u32)
+                .raw_str("/r/")
+                .number(reply.id as u32)
+                .raw_str("/children/0/")
+                .number(ctx)
+                .raw_str("\" class=\"show-replies\">Show ")
+                .number(children_count)
+                .raw_str(" replies</a>\n");
+        } else if children_count > 0 {
+            // Embed continuation for waterfall loading with slug-based path
+            md = md
+                .raw_str("{{render path=\"")
+                .raw(base_path.clone())
+                .raw_str("/t/")
+                .number(thread_id as u32)
+                .raw_str("/r/")
+                .number(reply.id as u32)
+                .raw_str("/children/0/")
+                .number(ctx)
+                .raw_str("\"}}");
+        }
+
+        md = md.div_end();
+        md
+    }
+
+    /// Render reply form
+    /// Nested replies always quote their parent; `quote_thread` quotes the thread body
+    /// for top-level replies.
+    fn render_reply_form(
+        env: &Env,
+        board_id: u64,
+        thread_id: u64,
+        parent_reply_id: Option<u64>,
+        quote_thread: bool,
+        viewer: &Option<Address>,
+    ) -> Bytes {
+        // Get board metadata for slug-based URLs
+        let board_meta: BoardMeta = env
+            .storage()
+            .persistent()
+            .get(&BoardKey::Board(board_id))
+            .expect("Board not found");
+
+        // Build base path for all links
+        let base_path = Self::build_board_base_path(env, board_id, &board_meta.slug);
+
+        // Get board config for private board check
+        let config: BoardConfig = env
+            .storage()
+            .persistent()
+            .get(&BoardKey::BoardConfig(board_id))
+            .expect("Board not found");
+
+        // Get permissions contract and viewer role early for private board check
+        let perms_addr_opt = env
+            .storage()
+            .instance()
+            .get::<_, Address>(&BoardKey::Permissions);
+        let viewer_role = if let Some(ref perms_addr) = perms_addr_opt {
+            if let Some(user) = viewer {
+                let role_args: Vec<Val> =
+                    Vec::from_array(env, [board_id.into_val(env), user.into_val(env)]);
+                env.invoke_contract(perms_addr, &Symbol::new(env, "get_role"), role_args)
+            } else {
+                Role::Guest
+            }
+        } else {
+            Role::Guest
+        };
+
+        // Check permissions for private boards - must be Member+ to access
+        if config.is_private {
+            if let Some(ref perms_addr) = perms_addr_opt {
+                if (viewer_role as u32) < (Role::Member as u32) {
+                    return Self::render_private_board_message(
+                        env, board_id, &config, viewer, perms_addr,
+                    );
+                }
+            }
+        }
+
+        let mut md = Self::render_nav(env, board_id, viewer)
+            .newline()
+            // Error mappings for user-friendly error messages
+            .raw_str("{{errors {\"1\": \"This board is read-only.\", \"2\": \"This thread is locked and does not accept new replies.\", \"3\": \"You don't have permission to perform this action.\", ")
+            .raw(Self::posting_errors(env, board_id))
+            .raw_str("}}}\n");
+        md = Self::render_breadcrumbs(env, md, board_id, Some(thread_id), Some("Reply"));
+        md = md.newline();
+
+        if parent_reply_id.is_some() {
+            md = md.raw_str("<h1>Reply to Comment</h1>\n");
+        } else {
+            md = md.raw_str("<h1>Reply to Thread</h1>\n");
+        }
+
+        // Check if board is read-only
+        if config.is_readonly {
+            md = md.warning("This board is read-only. Replies cannot be posted.");
+            return Self::render_footer_into(env, md).build();
+        }
+
+        // Check if thread is locked
+        let thread = env
+            .storage()
+            .persistent()
+            .get::<_, ThreadMeta>(&BoardKey::BoardThread(board_id, thread_id));
+        if let Some(ref thread) = thread {
+            if thread.is_locked {
+                md = md.warning("This thread is locked. Replies cannot be posted.");
+                return Self::render_footer_into(env, md).build();
+            }
+            match Self::reply_window(env, board_id, thread_id) {
+                ReplyWindow::Open => {}
+                ReplyWindow::NotYetOpen(open_at) => {
+                    md = md
+                        .warning("This thread is not open for replies yet.")
+                        .raw_str("<p class=\"schedule-notice\">Replies open in ")
+                        .raw(Self::format_duration(
+                            env,
+                            open_at - env.ledger().timestamp(),
+                        ))
+                        .raw_str(".</p>\n");
+                    return Self::render_footer_into(env, md).build();
+                }
+                ReplyWindow::Closed => {
+                    md = md.warning("This thread is closed. Replies cannot be posted.");
+                    return Self::render_footer_into(env, md).build();
+                }
+            }
+        }
+
+        if viewer.is_none() {
+            md = md.warning("Please connect your wallet to reply.");
+            return Self::render_footer_into(env, md).build();
+        }
+
+        // Check if user has permission to reply (requires Member+ role)
+        if (viewer_role as u32) < (Role::Member as u32) {
+            md = md.warning("You don't have permission to reply on this board.");
+            return Self::render_footer_into(env, md).build();
+        }
+
+        let content_opt = env
+            .storage()
+            .instance()
+            .get::<_, Address>(&BoardKey::Content);
+        let quote_limit = Self::get_quote_limit(env.clone(), board_id);
+
+        // Calculate parent_id and depth, and collect the text to quote
+        let mut quoted: Option<Bytes> = None;
+        // Where to send the viewer if the parent is already at the maximum depth
+        let mut grandparent_id: Option<u64> = None;
+        let (parent_id, depth): (u64, u32) = if let Some(pid) = parent_reply_id {
+            if let Some(ref content_addr) = content_opt {
+                let args: Vec<Val> = Vec::from_array(
+                    env,
+                    [
+                        board_id.into_val(env),
+                        thread_id.into_val(env),
+                        pid.into_val(env),
+                    ],
+                );
+                let parent_reply: Option<ReplyMeta> =
+                    env.invoke_contract(content_addr, &Symbol::new(env, "get_reply"), args.clone());
+                match parent_reply {
+                    Some(reply) => {
+                        // Hidden and deleted replies are never quoted
+                        if !reply.is_hidden && !reply.is_deleted && quote_limit > 0 {
+                            quoted = Some(env.invoke_contract(
+                                content_addr,
+                                &Symbol::new(env, "get_reply_content"),
+                                args,
+                            ));
+                        }
+                        if reply.depth > 0 {
+                            grandparent_id = Some(reply.parent_id);
+                        }
+                        (pid, reply.depth + 1)
+                    }
+                    None => (pid, 1),
+                }
+            } else {
+                (pid, 1)
+            }
+        } else {
+            let thread_hidden = thread.as_ref().map(|t| t.is_hidden).unwrap_or(true);
+            if quote_thread && !thread_hidden && quote_limit > 0 {
+                if let Some(ref content_addr) = content_opt {
+                    let args: Vec<Val> =
+                        Vec::from_array(env, [board_id.into_val(env), thread_id.into_val(env)]);
+                    quoted = Some(env.invoke_contract(
+                        content_addr,
+                        &Symbol::new(env, "get_thread_body"),
+                        args,
+                    ));
+                }
+            }
+            (0, 0)
+        };
+
+        // The reply would nest past the board's limit, so offer the level above instead
+        if depth > config.max_reply_depth {
+            md = md
+                .warning("Maximum nesting reached — reply to the parent comment instead.")
+                .raw_str("[Reply to the parent comment](render:")
+                .raw(base_path.clone())
+                .raw_str("/t/")
+                .number(thread_id as u32);
+            if let Some(grandparent_id) = grandparent_id {
+                md = md.raw_str("/r/").number(grandparent_id as u32);
+            }
+            md = md.raw_str("/reply)\n");
+            return Self::render_footer_into(env, md).build();
+        }
+
+        let user = viewer.as_ref().unwrap();
+
+        // Tell the viewer how long until the content contract accepts their next reply
+        if (viewer_role as u32) < (Role::Moderator as u32) {
+            if let Some(wait) = Self::reply_cooldown_remaining(env, board_id, thread_id, user) {
+                md = md
+                    .raw_str("<p class=\"cooldown-notice\">You can reply again in ")
+                    .raw(Self::format_seconds(env, wait))
+                    .raw_str(".</p>\n");
+            }
+        }
+
+        // A saved draft takes the place of the quote
+        let draft = Self::fetch_draft(
+            env,
+            "get_reply_draft",
+            Vec::from_array(
+                env,
+                [
+                    board_id.into_val(env),
+                    thread_id.into_val(env),
+                    parent_id.into_val(env),
+                    user.into_val(env),
+                ],
+            ),
+        );
+        if let Some(ref draft) = draft {
+            md = Self::render_draft_restore(env, md, board_id, draft, false);
+        }
+
+        md = md
+            .raw_str("<input type=\"hidden\" name=\"_redirect\" value=\"")
+            .raw(base_path.clone())
+            .raw_str("/t/")
+            .number(thread_id as u32)
+            .raw_str("\" />\n")
+            .raw_str("<input type=\"hidden\" name=\"board_id\" value=\"")
+            .number(board_id as u32)
+            .raw_str("\" />\n")
+            .raw_str("<input type=\"hidden\" name=\"thread_id\" value=\"")
+            .number(thread_id as u32)
+            .raw_str("\" />\n")
+            .raw_str("<input type=\"hidden\" name=\"parent_id\" value=\"")
+            .number(parent_id as u32)
+            .raw_str("\" />\n")
+            .raw_str("<input type=\"hidden\" name=\"depth\" value=\"")
+            .number(depth)
+            .raw_str("\" />\n")
+            .raw_str("<textarea name=\"content_str\" data-editor=\"markdown\" rows=\"6\"")
+            .raw_str(" placeholder=\"Write your reply...\">");
+
+        // Prefill the draft or quote; both are escaped so they can't close the textarea early
+        if let Some(draft) = draft {
+            md = md.raw(escape_html(env, &draft.body));
+        } else if let Some(body) = quoted {
+            if !body.is_empty() {
+                md = md.raw(Self::build_quote(env, &body, quote_limit));
+            }
+        }
+
+        md = md
+            .raw_str("</textarea>\n")
+            .newline()
+            .raw_str("<input type=\"hidden\" name=\"caller\" value=\"")
+            .text_string(&user.to_string())
+            .raw_str("\" />\n")
+            .newline()
+            .form_link_to("Post Reply", "content", "create_reply")
+            .raw_str(" ")
+            .form_link_to("Save Draft", "content", "save_reply_draft")
+            .newline()
+            .newline()
+            .raw_str("[Cancel](render:")
+            .raw(base_path)
+            .raw_str("/t/")
+            .number(thread_id as u32)
+            .raw_str(")");
+
+        Self::render_footer_into(env, md).build()
+    }
+
+    /// Check whether a post has edit history (false if the content contract predates it)
+    fn is_post_edited(
+        env: &Env,
+        content: &Address,
+        board_id: u64,
+        thread_id: u64,
+        reply_id: Option<u64>,
+    ) -> bool {
+        let args: Vec<Val> = Vec::from_array(
+            env,
+            [
+                board_id.into_val(env),
+                thread_id.into_val(env),
+                reply_id.into_val(env),
+            ],
+        );
+        env.try_invoke_contract::<bool, soroban_sdk::Error>(
+            content,
+            &Symbol::new(env, "is_edited"),
+            args,
+        )
+        .ok()
+        .and_then(|r| r.ok())
+        .unwrap_or(false)
+    }
+
+    /// Ids of a batch of replies
+    fn reply_ids(env: &Env, replies: &Vec<ReplyMeta>) -> Vec<u64> {
+        let mut ids: Vec<u64> = Vec::new(env);
+        for reply in replies.iter() {
+            ids.push_back(reply.id);
+        }
+        ids
+    }
+
+    /// Which of the given replies have edit history, fetched in one call
+    /// (empty if the content contract predates it)
+    fn edited_reply_ids(
+        env: &Env,
+        content: &Address,
+        board_id: u64,
+        thread_id: u64,
+        reply_ids: Vec<u64>,
+    ) -> Vec<u64> {
+        let args: Vec<Val> = Vec::from_array(
+            env,
+            [
+                board_id.into_val(env),
+                thread_id.into_val(env),
+                reply_ids.into_val(env),
+            ],
+        );
+        env.try_invoke_contract::<Vec<u64>, soroban_sdk::Error>(
+            content,
+            &Symbol::new(env, "get_edited_replies"),
+            args,
+        )
+        .ok()
+        .and_then(|r| r.ok())
+        .unwrap_or(Vec::new(env))
+    }
+
+    /// Which of the given replies were posted in a moderator capacity, fetched in one
+    /// call (empty if the content contract predates it)
+    fn distinguished_reply_ids(
+        env: &Env,
+        content: &Address,
+        board_id: u64,
+        thread_id: u64,
+        reply_ids: Vec<u64>,
+    ) -> Vec<u64> {
+        let args: Vec<Val> = Vec::from_array(
+            env,
+            [
+                board_id.into_val(env),
+                thread_id.into_val(env),
+                reply_ids.into_val(env),
+            ],
+        );
+        env.try_invoke_contract::<Vec<u64>, soroban_sdk::Error>(
+            content,
+            &Symbol::new(env, "get_distinguished"),
+            args,
+        )
+        .ok()
+        .and_then(|r| r.ok())
+        .unwrap_or(Vec::new(env))
+    }
+
+    /// Reaction counts and the viewer's own reactions for a batch of replies, keyed by
+    /// reply id and fetched in one call each (empty if the voting contract predates them)
+    fn reply_reactions(
+        env: &Env,
+        voting_contract: &Option<Address>,
+        board_id: u64,
+        thread_id: u64,
+        reply_ids: &Vec<u64>,
+        viewer: &Option<Address>,
+    ) -> Map<u64, (Vec<(Symbol, u32)>, Vec<Symbol>)> {
+        let mut reactions = Map::new(env);
+        let Some(voting) = voting_contract else {
+            return reactions;
+        };
+        let args: Vec<Val> = Vec::from_array(
+            env,
+            [
+                board_id.into_val(env),
+                thread_id.into_val(env),
+                reply_ids.into_val(env),
+            ],
+        );
+        let Some(counts) = env
+            .try_invoke_contract::<Vec<Vec<(Symbol, u32)>>, soroban_sdk::Error>(
+                voting,
+                &Symbol::new(env, "get_reply_reactions"),
+                args,
+            )
+            .ok()
+            .and_then(|r| r.ok())
+        else {
+            return reactions;
+        };
+
+        let mine: Vec<Vec<Symbol>> = match viewer {
+            Some(user) => {
+                let user_args: Vec<Val> = Vec::from_array(
+                    env,
+                    [
+                        board_id.into_val(env),
+                        thread_id.into_val(env),
+                        reply_ids.into_val(env),
+                        user.into_val(env),
+                    ],
+                );
+                env.invoke_contract(
+                    voting,
+                    &Symbol::new(env, "get_user_reply_reactions"),
+                    user_args,
+                )
+            }
+            None => Vec::new(env),
+        };
+
+        for (i, reply_id) in reply_ids.iter().enumerate() {
+            if let Some(reply_counts) = counts.get(i as u32) {
+                let reply_mine = mine.get(i as u32).unwrap_or(Vec::new(env));
+                reactions.set(reply_id, (reply_counts, reply_mine));
+            }
+        }
+        reactions
+    }
+
+    /// Whether a thread (reply_id None) or reply was posted in a moderator capacity.
+    /// False when the content contract predates the mark.
+    fn is_post_distinguished(
+        env: &Env,
+        content: &Address,
+        board_id: u64,
+        thread_id: u64,
+        reply_id: Option<u64>,
+    ) -> bool {
+        let args: Vec<Val> = Vec::from_array(
+            env,
+            [
+                board_id.into_val(env),
+                thread_id.into_val(env),
+                reply_id.into_val(env),
+            ],
+        );
+        env.try_invoke_contract::<bool, soroban_sdk::Error>(
+            content,
+            &Symbol::new(env, "is_distinguished"),
+            args,
+        )
+        .ok()
+        .and_then(|r| r.ok())
+        .unwrap_or(false)
+    }
+
+    /// Render the link that adds or removes the moderator mark on a post, after
+    /// `separator`. Authors who moderate can add it; the author or any moderator can
+    /// remove it.
+    fn render_distinguish_toggle<'a>(
+        md: MarkdownBuilder<'a>,
+        separator: &str,
+        board_id: u64,
+        thread_id: u64,
+        reply_id: Option<u64>,
+        distinguished: bool,
+        viewer_is_author: bool,
+        viewer_can_moderate: bool,
+    ) -> MarkdownBuilder<'a> {
+        let allowed = if distinguished {
+            viewer_is_author || viewer_can_moderate
+        } else {
+            viewer_is_author && viewer_can_moderate
+        };
+        if !allowed {
+            return md;
+        }
+        let mut md = md
+            .raw_str(separator)
+            .raw_str("<a href=\"tx:@content:distinguish {&quot;board_id&quot;:")
+            .number(board_id as u32)
+            .raw_str(",&quot;thread_id&quot;:")
+            .number(thread_id as u32)
+            .raw_str(",&quot;reply_id&quot;:");
+        md = match reply_id {
+            Some(id) => md.number(id as u32),
+            None => md.raw_str("null"),
+        };
+        md.raw_str(if distinguished {
+            "}\" class=\"distinguish-toggle\">Undistinguish</a>"
+        } else {
+            "}\" class=\"distinguish-toggle\" title=\"Mark as posted in your moderator role\">Distinguish</a>"
+        })
+    }
+
+    /// Render the "(edited)" marker; moderators get a link to the edit history
+    fn render_edited_marker<'a>(
+        _env: &Env,
+        md: MarkdownBuilder<'a>,
+        base_path: &Bytes,
+        thread_id: u64,
+        reply_id: Option<u64>,
+        link_history: bool,
+    ) -> MarkdownBuilder<'a> {
+        if !link_history {
+            return md.raw_str(" · <span class=\"edited-marker\">(edited)</span>");
+        }
+        let mut md = md
+            .raw_str(" · <a href=\"render:")
+            .raw(base_path.clone())
+            .raw_str("/t/")
+            .number(thread_id as u32);
+        if let Some(id) = reply_id {
+            md = md.raw_str("/r/").number(id as u32);
+        }
+        md.raw_str("/revisions\" class=\"edited-marker\">(edited)</a>")
+    }
+
+    /// Render a post's edit history: the current body followed by each
+    /// replaced body, newest first (moderators only)
+    fn render_revisions(
+        env: &Env,
+        board_id: u64,
+        thread_id: u64,
+        reply_id: Option<u64>,
+        viewer: &Option<Address>,
+    ) -> Bytes {
+        let md = Self::render_nav(env, board_id, viewer).newline();
+        let mut md =
+            Self::render_breadcrumbs(env, md, board_id, Some(thread_id), Some("Edit History"))
+                .h1("Edit History");
+
+        // Only moderators can see edit history
+        let viewer_role = match (
+            env.storage()
+                .instance()
+                .get::<_, Address>(&BoardKey::Permissions),
+            viewer,
+        ) {
+            (Some(perms_addr), Some(user)) => {
+                let args: Vec<Val> =
+                    Vec::from_array(env, [board_id.into_val(env), user.into_val(env)]);
+                env.invoke_contract(&perms_addr, &Symbol::new(env, "get_role"), args)
+            }
+            _ => Role::Guest,
+        };
+        if (viewer_role as u32) < (Role::Moderator as u32) {
+            md = md.warning("You must be a moderator to view edit history.");
+            return Self::render_footer_into(env, md).build();
+        }
+
+        let Some(content) = env
+            .storage()
+            .instance()
+            .get::<_, Address>(&BoardKey::Content)
+        else {
+            md = md.paragraph("Content is unavailable on this board.");
+            return Self::render_footer_into(env, md).build();
+        };
+        let post_args: Vec<Val> = match reply_id {
+            Some(id) => Vec::from_array(
+                env,
+                [
+                    board_id.into_val(env),
+                    thread_id.into_val(env),
+                    id.into_val(env),
+                ],
+            ),
+            None => Vec::from_array(env, [board_id.into_val(env), thread_id.into_val(env)]),
+        };
+        let current: Bytes = env.invoke_contract(
+            &content,
+            &Symbol::new(
+                env,
+                if reply_id.is_some() {
+                    "get_reply_content"
+                } else {
+                    "get_thread_body"
+                },
+            ),
+            post_args,
+        );
+
+        let history_args: Vec<Val> = Vec::from_array(
+            env,
+            [
+                board_id.into_val(env),
+                thread_id.into_val(env),
+                reply_id.into_val(env),
+            ],
+        );
+        let revisions: Vec<Revision> = env
+            .try_invoke_contract::<Vec<Revision>, soroban_sdk::Error>(
+                &content,
+                &Symbol::new(env, "list_revisions"),
+                history_args,
+            )
+            .ok()
+            .and_then(|r| r.ok())
+            .unwrap_or(Vec::new(env));
+
+        md = md
+            .h2("Current")
+            .raw_str("<pre class=\"revision-body\">")
+            .raw(escape_html(env, &current))
+            .raw_str("</pre>\n");
+
+        if revisions.is_empty() {
+            md = md.paragraph("This post has not been edited.");
+            return Self::render_footer_into(env, md).build();
+        }
+
+        let profile_contract = Self::get_profile_contract(env);
+        for revision in revisions.iter() {
+            md = md
+                .raw_str("<div class=\"revision\">\n<div class=\"revision-meta\">Replaced ")
+                .raw(Self::format_board_timestamp(
+                    env,
+                    board_id,
+                    revision.replaced_at,
+                ))
+                .raw_str(" by ");
+            md = Self::render_author(env, md, board_id, &revision.editor, &profile_contract, None);
+            md = md
+                .raw_str("</div>\n<pre class=\"revision-body\">")
+                .raw(escape_html(env, &revision.body))
+                .raw_str("</pre>\n</div>\n");
+        }
+        md = md.note("Only the most recent revisions are kept.");
+
+        Self::render_footer_into(env, md).build()
+    }
+
+    /// Render the boards a thread has been crossposted to, with who crossposted it and when.
+    /// Destinations on private boards the viewer can't access are not named.
+    fn render_crossposts(
+        env: &Env,
+        board_id: u64,
+        thread_id: u64,
+        viewer: &Option<Address>,
+    ) -> Bytes {
+        let perms_addr_opt = env
+            .storage()
+            .instance()
+            .get::<_, Address>(&BoardKey::Permissions);
+        let role_on = |id: u64| -> Role {
+            match (&perms_addr_opt, viewer) {
+                (Some(perms_addr), Some(user)) => {
+                    let args: Vec<Val> =
+                        Vec::from_array(env, [id.into_val(env), user.into_val(env)]);
+                    env.invoke_contract(perms_addr, &Symbol::new(env, "get_role"), args)
+                }
+                _ => Role::Guest,
+            }
+        };
+
+        let config: BoardConfig = env
+            .storage()
+            .persistent()
+            .get(&BoardKey::BoardConfig(board_id))
+            .expect("Board not found");
+        if config.is_private {
+            if let Some(ref perms_addr) = perms_addr_opt {
+                if (role_on(board_id) as u32) < (Role::Member as u32) {
+                    return Self::render_private_board_message(
+                        env, board_id, &config, viewer, perms_addr,
+                    );
+                }
+            }
+        }
+
+        let md = Self::render_nav(env, board_id, viewer).newline();
+        let mut md =
+            Self::render_breadcrumbs(env, md, board_id, Some(thread_id), Some("Crossposts"))
+                .h1("Crossposts");
+
+        let Some(content) = env
+            .storage()
+            .instance()
+            .get::<_, Address>(&BoardKey::Content)
+        else {
+            md = md.paragraph("Content is unavailable on this board.");
+            return Self::render_footer_into(env, md).build();
+        };
+        let list_args: Vec<Val> =
+            Vec::from_array(env, [board_id.into_val(env), thread_id.into_val(env)]);
+        let locations: Vec<CrosspostLocation> = env
+            .try_invoke_contract::<Vec<CrosspostLocation>, soroban_sdk::Error>(
+                &content,
+                &Symbol::new(env, "list_crossposts"),
+                list_args,
+            )
+            .ok()
+            .and_then(|r| r.ok())
+            .unwrap_or(Vec::new(env));
+
+        if locations.is_empty() {
+            md = md.paragraph("This thread has not been crossposted.");
+            return Self::render_footer_into(env, md).build();
+        }
+
+        let profile_contract = Self::get_profile_contract(env);
+        md = md.raw_str("<ul class=\"crosspost-list\">\n");
+        for loc in locations.iter() {
+            md = md.raw_str("<li>");
+
+            let dest_meta = Self::get_board(env.clone(), loc.board_id);
+            let dest_private = env
+                .storage()
+                .persistent()
+                .get::<_, BoardConfig>(&BoardKey::BoardConfig(loc.board_id))
+                .map(|c| c.is_private)
+                .unwrap_or(false);
+            let Some(dest_meta) = dest_meta else {
+                md = md.raw_str("a deleted board</li>\n");
+                continue;
+            };
+            if dest_private && (role_on(loc.board_id) as u32) < (Role::Member as u32) {
+                md = md
+                    .raw_str("a private board <span class=\"crosspost-meta\">")
+                    .raw(Self::format_board_timestamp(env, board_id, loc.created_at))
+                    .raw_str("</span></li>\n");
+                continue;
+            }
+
+            md = md
+                .raw_str("<a href=\"render:")
+                .raw(Self::build_board_base_path(
+                    env,
+                    loc.board_id,
+                    &dest_meta.slug,
+                ))
+                .raw_str("/t/")
+                .number(loc.thread_id as u32)
+                .raw_str("\">")
+                .text_string(&dest_meta.name)
+                .raw_str("</a> <span class=\"crosspost-meta\">");
+
+            // The crossposter is recorded on the destination thread
+            let ref_args: Vec<Val> = Vec::from_array(
+                env,
+                [loc.board_id.into_val(env), loc.thread_id.into_val(env)],
+            );
+            let xpost_ref: Option<CrosspostRef> = env
+                .try_invoke_contract::<Option<CrosspostRef>, soroban_sdk::Error>(
+                    &content,
+                    &Symbol::new(env, "get_crosspost_ref"),
+                    ref_args,
+                )
+                .ok()
+                .and_then(|r| r.ok())
+                .flatten();
+            if let Some(xpost) = xpost_ref {
+                md = md.raw_str("by ");
+                md = Self::render_author(
+                    env,
+                    md,
+                    board_id,
+                    &xpost.crossposted_by,
+                    &profile_contract,
+                    None,
+                );
+                md = md.raw_str(" ");
+            }
+            md = md
+                .raw(Self::format_board_timestamp(env, board_id, loc.created_at))
+                .raw_str("</span></li>\n");
+        }
+        md = md.raw_str("</ul>\n");
+
+        Self::render_footer_into(env, md).build()
+    }
+
+    /// Error messages for the content contract's post size, karma, blocked term and reply
+    /// cooldown errors (codes 9-13), as entries for an `{{errors ...}}` block
+    fn posting_errors(env: &Env, board_id: u64) -> Bytes {
+        let mut errors = Bytes::from_slice(env, b"\"9\": \"Thread body is too long (limit ");
+        errors.append(&Self::format_count(
+            env,
+            Self::get_max_thread_body_bytes(env.clone(), board_id),
+        ));
+        errors.append(&Bytes::from_slice(
+            env,
+            b" bytes).\", \"10\": \"Reply is too long (limit ",
+        ));
+        errors.append(&Self::format_count(
+            env,
+            Self::get_max_reply_bytes(env.clone(), board_id),
+        ));
+        errors.append(&Bytes::from_slice(env, b" bytes).\""));
+        errors.append(&Bytes::from_slice(
+            env,
+            b", \"11\": \"You don't have enough karma to post on this board yet.\"",
+        ));
+        errors.append(&Bytes::from_slice(
+            env,
+            b", \"12\": \"Your post contains a term this board doesn't allow.\"",
+        ));
+        errors.append(&Bytes::from_slice(
+            env,
+            b", \"13\": \"You're replying too quickly in this thread. Please wait a moment.\"",
+        ));
+        errors
+    }
+
+    /// Seconds until the viewer may reply in a thread again, or `None` when no cooldown
+    /// applies (no cooldown set, no earlier reply, or the window has passed)
+    fn reply_cooldown_remaining(
+        env: &Env,
+        board_id: u64,
+        thread_id: u64,
+        user: &Address,
+    ) -> Option<u64> {
+        let cooldown = Self::get_reply_cooldown(env.clone(), board_id);
+        if cooldown == 0 {
+            return None;
+        }
+        let content: Address = env.storage().instance().get(&BoardKey::Content)?;
+        let args: Vec<Val> = Vec::from_array(
+            env,
+            [
+                board_id.into_val(env),
+                thread_id.into_val(env),
+                user.into_val(env),
+            ],
+        );
+        let last_reply_at = env
+            .try_invoke_contract::<Option<u64>, soroban_sdk::Error>(
+                &content,
+                &Symbol::new(env, "get_last_reply_at"),
+                args,
+            )
+            .ok()
+            .and_then(|r| r.ok())
+            .flatten()?;
+        let now = env.ledger().timestamp();
+        let ready_at = last_reply_at.saturating_add(cooldown);
+        if now < ready_at {
+            Some(ready_at - now)
+        } else {
+            None
+        }
+    }
+
+    /// Format a count with thousands separators (e.g. 1,204)
+    fn format_count(env: &Env, n: u32) -> Bytes {
+        let mut digits = [0u8; 10];
+        let mut len = 0;
+        let mut rest = n;
+        loop {
+            digits[len] = b'0' + (rest % 10) as u8;
+            len += 1;
+            rest /= 10;
+            if rest == 0 {
+                break;
+            }
+        }
+
+        let mut result = Bytes::new(env);
+        for i in (0..len).rev() {
+            result.push_back(digits[i]);
+            if i > 0 && i % 3 == 0 {
+                result.push_back(b',');
+            }
+        }
+        result
+    }
+
+    /// Disabled vote arrow shown to authors on their own threads and replies
+    fn render_own_vote_arrow<'a>(
+        md: MarkdownBuilder<'a>,
+        class: &str,
+        arrow: &str,
+    ) -> MarkdownBuilder<'a> {
+        md.raw_str("<span class=\"")
+            .raw_str(class)
+            .raw_str(" vote-disabled vote-own\" title=\"You can't vote on your own post\">")
+            .raw_str(arrow)
+            .raw_str("</span>")
+    }
+
+    /// Format a signed karma value with thousands separators (e.g. -1,204)
+    fn format_karma(env: &Env, karma: i64) -> Bytes {
+        let magnitude = Self::format_count(env, karma.unsigned_abs().min(u32::MAX as u64) as u32);
+        if karma < 0 {
+            let mut signed = Bytes::from_slice(env, b"-");
+            signed.append(&magnitude);
+            signed
+        } else {
+            magnitude
+        }
+    }
+
+    /// Escape a stored string for use inside a double-quoted HTML attribute,
+    /// cut to `MAX_ATTR_LEN` bytes
+    fn escape_attr(env: &Env, value: &String) -> Bytes {
+        escape_attr(env, value, MAX_ATTR_LEN)
+    }
+
+    /// Fetch a draft from the content contract (None if it predates drafts)
+    fn fetch_draft(env: &Env, func: &str, args: Vec<Val>) -> Option<Draft> {
+        let content: Address = env.storage().instance().get(&BoardKey::Content)?;
+        env.try_invoke_contract::<Option<Draft>, soroban_sdk::Error>(
+            &content,
+            &Symbol::new(env, func),
+            args,
+        )
+        .ok()
+        .and_then(|r| r.ok())
+        .flatten()
+    }
+
+    /// Render the "Restore draft" notice shown above a form prefilled from a draft
+    fn render_draft_restore<'a>(
+        env: &'a Env,
+        md: MarkdownBuilder<'a>,
+        board_id: u64,
+        draft: &Draft,
+        discardable: bool,
+    ) -> MarkdownBuilder<'a> {
+        let mut md = md
+            .raw_str("<div class=\"draft-restore\"><strong>Restore draft</strong> ")
+            .raw_str("Your draft saved ")
+            .raw(Self::format_board_timestamp(env, board_id, draft.saved_at))
+            .raw_str(" has been restored below.");
+        if discardable {
+            md = md
+                .raw_str(" <a href=\"tx:@content:delete_draft {&quot;board_id&quot;:")
+                .number(board_id as u32)
+                .raw_str(",&quot;key&quot;:&quot;")
+                .raw_str(THREAD_DRAFT_KEY)
+                .raw_str("&quot;}\" class=\"draft-discard\">Discard draft</a>");
+        }
+        md.raw_str("</div>\n")
+    }
+
+    /// Convert a draft title to bytes for escaping
+    fn draft_title_bytes(env: &Env, title: &String) -> Bytes {
+        let len = (title.len() as usize).min(MAX_DRAFT_TITLE_LEN);
+        let mut buf = [0u8; MAX_DRAFT_TITLE_LEN];
+        title.copy_into_slice(&mut buf[..len]);
+        Bytes::from_slice(env, &buf[..len])
+    }
+
+    /// Check if user can edit content (author or moderator)
+    fn can_edit(
+        creator: &Address,
+        viewer: &Option<Address>,
+        viewer_perms: &PermissionSet,
+    ) -> (bool, bool) {
+        match viewer {
+            Some(v) => (creator == v, viewer_perms.can_moderate),
+            None => (false, false),
+        }
+    }
+
+    /// Pack the viewer context a thread page computed into the `{ctx}` segment of
+    /// its waterfall continuation paths (bit 0: can post, bit 1: can moderate), so
+    /// reply batches don't look it up again. Children paths on subtree pages also
+    /// carry the root's depth in the bits above these.
+    fn encode_batch_ctx(can_post: bool, viewer_perms: &PermissionSet) -> u32 {
+        (can_post as u32) | ((viewer_perms.can_moderate as u32) << 1)
+    }
+
+    /// Unpack a continuation ctx into the viewer's permissions and posting ability.
+    ///
+    /// The ctx arrives in a path the viewer controls, so it is trusted for rendering
+    /// only: it decides which Reply/Edit links appear, never what gets written. Every
+    /// action behind those links re-checks permissions when submitted, so a forged
+    /// ctx at worst shows buttons that fail. Read access is not taken from the ctx:
+    /// batches on private boards always re-check membership.
+    fn decode_batch_ctx(ctx: u32) -> (PermissionSet, bool) {
+        let can_post = ctx & 1 != 0;
+        let can_moderate = ctx & 2 != 0;
+        let role = if can_moderate {
+            Role::Moderator
+        } else if can_post {
+            Role::Member
+        } else {
+            Role::Guest
+        };
+        let perms = PermissionSet {
+            role,
+            can_view: true,
+            can_post,
+            can_moderate,
+            can_admin: false,
+            is_banned: false,
+        };
+        (perms, can_post)
+    }
+
+    /// Fetch the viewer's permission set with a single cross-contract call.
+    /// Guests, and boards without a permissions contract, get no permissions.
+    fn viewer_permissions(env: &Env, board_id: u64, viewer: &Option<Address>) -> PermissionSet {
+        let perms_addr: Option<Address> = env.storage().instance().get(&BoardKey::Permissions);
+        match (perms_addr, viewer) {
+            (Some(perms_addr), Some(user)) => {
+                let args: Vec<Val> =
+                    Vec::from_array(env, [board_id.into_val(env), user.into_val(env)]);
+                env.invoke_contract(&perms_addr, &Symbol::new(env, "get_permissions"), args)
+            }
+            _ => PermissionSet {
+                role: Role::Guest,
+                can_view: true,
+                can_post: false,
+                can_moderate: false,
+                can_admin: false,
+                is_banned: false,
+            },
+        }
+    }
+
+    /// Render edit thread form
+    fn render_edit_thread(
+        env: &Env,
+        board_id: u64,
+        thread_id: u64,
+        viewer: &Option<Address>,
+    ) -> Bytes {
+        // Get board metadata for slug-based URLs
+        let board_meta: BoardMeta = env
+            .storage()
+            .persistent()
+            .get(&BoardKey::Board(board_id))
+            .expect("Board not found");
+
+        // Build base path for all links
+        let base_path = Self::build_board_base_path(env, board_id, &board_meta.slug);
+
+        // Get board config for private board check
+        let config: BoardConfig = env
+            .storage()
+            .persistent()
+            .get(&BoardKey::BoardConfig(board_id))
+            .expect("Board not found");
+
+        // Get permissions contract and viewer role early for private board check
+        let perms_addr_opt = env
+            .storage()
+            .instance()
+            .get::<_, Address>(&BoardKey::Permissions);
+        let viewer_perms = Self::viewer_permissions(env, board_id, viewer);
+        let viewer_role = viewer_perms.role;
+
+        // Check permissions for private boards - must be Member+ to access
+        if config.is_private {
+            if let Some(ref perms_addr) = perms_addr_opt {
+                if (viewer_role as u32) < (Role::Member as u32) {
+                    return Self::render_private_board_message(
+                        env, board_id, &config, viewer, perms_addr,
+                    );
+                }
+            }
+        }
+
+        let mut md = Self::render_nav(env, board_id, viewer)
+            .newline()
+            .raw_str("{{errors {")
+            .raw(Self::posting_errors(env, board_id))
+            .raw_str("}}}\n");
+        md = Self::render_breadcrumbs(env, md, board_id, Some(thread_id), Some("Edit"))
+            .h1("Edit Thread");
+
+        let Some(content) = env
+            .storage()
+            .instance()
+            .get::<_, Address>(&BoardKey::Content)
+        else {
+            md = md.paragraph("Content is unavailable on this board.");
+            return Self::render_footer_into(env, md).build();
+        };
+
+        // Check if board is read-only
+        if config.is_readonly {
+            md = md.warning("This board is read-only. Threads cannot be edited.");
+            return Self::render_footer_into(env, md).build();
+        }
+
+        if viewer.is_none() {
+            md = md.warning("Please connect your wallet to edit.");
+            return Self::render_footer_into(env, md).build();
+        }
+
+        // Get thread metadata
+        let thread = match env
+            .storage()
+            .persistent()
+            .get::<_, ThreadMeta>(&BoardKey::BoardThread(board_id, thread_id))
+        {
+            Some(t) => t,
+            None => {
+                md = md.warning("Thread not found.");
+                return Self::render_footer_into(env, md).build();
+            }
+        };
+
+        // Check if locked
+        if thread.is_locked {
+            md = md.warning("This thread is locked and cannot be edited.");
+            return Self::render_footer_into(env, md).build();
+        }
+
+        // Check edit permission
+        let (is_author, is_moderator) = Self::can_edit(&thread.creator, viewer, &viewer_perms);
+
+        if !is_author && !is_moderator {
+            md = md.warning("You don't have permission to edit this thread.");
+            return Self::render_footer_into(env, md).build();
+        }
+
+        // Check edit window (only applies to non-moderators)
+        if is_author
+            && !is_moderator
+            && !Self::is_within_edit_window(env, board_id, thread.created_at)
+        {
+            md = md.warning("The edit window has expired for this thread.");
+            return Self::render_footer_into(env, md).build();
+        }
+
+        // Get current thread body
+        let args: Vec<Val> =
+            Vec::from_array(env, [board_id.into_val(env), thread_id.into_val(env)]);
+        let body: Bytes = env.invoke_contract(&content, &Symbol::new(env, "get_thread_body"), args);
+
+        // Convert Bytes to escaped string for textarea
+        // Note: We need to include the body content in a way the form can use
+        // For now, we'll use a hidden field with base64 or just render the textarea
+
+        md = md
+            .raw_str("<input type=\"hidden\" name=\"_redirect\" value=\"")
+            .raw(base_path.clone())
+            .raw_str("/t/")
+            .number(thread_id as u32)
+            .raw_str("\" />\n")
+            .raw_str("<input type=\"hidden\" name=\"board_id\" value=\"")
+            .number(board_id as u32)
+            .raw_str("\" />\n")
+            .raw_str("<input type=\"hidden\" name=\"thread_id\" value=\"")
+            .number(thread_id as u32)
+            .raw_str("\" />\n")
+            .raw_str("<label>Title</label>\n")
+            .raw_str("<input type=\"text\" name=\"new_title\" value=\"")
+            .raw(Self::escape_attr(env, &thread.title))
+            .raw_str("\" />\n")
+            .newline()
+            .raw_str("<label>Content</label>\n")
+            .raw_str("<textarea name=\"new_body\" data-editor=\"markdown\" rows=\"10\">");
+
+        // Include the current body content in the textarea
+        if !body.is_empty() {
+            md = md.raw(body);
+        }
+
+        md = md
+            .raw_str("</textarea>\n")
+            .newline()
+            .raw_str("<input type=\"hidden\" name=\"caller\" value=\"")
+            .text_string(&viewer.as_ref().unwrap().to_string())
+            .raw_str("\" />\n")
+            .newline()
+            .form_link_to("Save Changes", "content", "edit_thread")
+            .newline()
+            .newline()
+            .raw_str("[Cancel](render:")
+            .raw(base_path)
+            .raw_str("/t/")
+            .number(thread_id as u32)
+            .raw_str(")");
+
+        Self::render_footer_into(env, md).build()
+    }
+
+    /// Render edit reply form
+    fn render_edit_reply(
+        env: &Env,
+        board_id: u64,
+        thread_id: u64,
+        reply_id: u64,
+        viewer: &Option<Address>,
+    ) -> Bytes {
+        // Get board metadata for slug-based URLs
+        let board_meta: BoardMeta = env
+            .storage()
+            .persistent()
+            .get(&BoardKey::Board(board_id))
+            .expect("Board not found");
+
+        // Build base path for all links
+        let base_path = Self::build_board_base_path(env, board_id, &board_meta.slug);
+
+        // Get board config for private board check
+        let config: BoardConfig = env
+            .storage()
+            .persistent()
+            .get(&BoardKey::BoardConfig(board_id))
+            .expect("Board not found");
+
+        // Get permissions contract and viewer role early for private board check
+        let perms_addr_opt = env
+            .storage()
+            .instance()
+            .get::<_, Address>(&BoardKey::Permissions);
+        let viewer_perms = Self::viewer_permissions(env, board_id, viewer);
+        let viewer_role = viewer_perms.role;
+
+        // Check permissions for private boards - must be Member+ to access
+        if config.is_private {
+            if let Some(ref perms_addr) = perms_addr_opt {
+                if (viewer_role as u32) < (Role::Member as u32) {
+                    return Self::render_private_board_message(
+                        env, board_id, &config, viewer, perms_addr,
+                    );
+                }
+            }
+        }
+
+        let mut md = Self::render_nav(env, board_id, viewer)
+            .newline()
+            .raw_str("{{errors {")
+            .raw(Self::posting_errors(env, board_id))
+            .raw_str("}}}\n");
+        md = Self::render_breadcrumbs(env, md, board_id, Some(thread_id), Some("Edit"))
+            .h1("Edit Reply");
+
+        let Some(content) = env
+            .storage()
+            .instance()
+            .get::<_, Address>(&BoardKey::Content)
+        else {
+            md = md.paragraph("Content is unavailable on this board.");
+            return Self::render_footer_into(env, md).build();
+        };
+
+        // Check if board is read-only
+        if config.is_readonly {
+            md = md.warning("This board is read-only. Replies cannot be edited.");
+            return Self::render_footer_into(env, md).build();
+        }
+
+        // Check if thread is locked
+        if let Some(thread) = env
+            .storage()
+            .persistent()
+            .get::<_, ThreadMeta>(&BoardKey::BoardThread(board_id, thread_id))
+        {
+            if thread.is_locked {
+                md = md.warning("This thread is locked. Replies cannot be edited.");
+                return Self::render_footer_into(env, md).build();
+            }
+        }
+
+        if viewer.is_none() {
+            md = md.warning("Please connect your wallet to edit.");
+            return Self::render_footer_into(env, md).build();
+        }
+
+        // Get reply metadata from content contract
+        let args: Vec<Val> = Vec::from_array(
+            env,
+            [
+                board_id.into_val(env),
+                thread_id.into_val(env),
+                reply_id.into_val(env),
+            ],
+        );
+        let reply: Option<ReplyMeta> =
+            env.invoke_contract(&content, &Symbol::new(env, "get_reply"), args.clone());
+
+        let reply = match reply {
+            Some(r) => r,
+            None => {
+                md = md.warning("Reply not found.");
+                return Self::render_footer_into(env, md).build();
+            }
+        };
+
+        // Check if deleted
+        if reply.is_deleted {
+            md = md.warning("This reply has been deleted and cannot be edited.");
+            return Self::render_footer_into(env, md).build();
+        }
+
+        // Check edit permission
+        let (is_author, is_moderator) = Self::can_edit(&reply.creator, viewer, &viewer_perms);
+
+        if !is_author && !is_moderator {
+            md = md.warning("You don't have permission to edit this reply.");
+            return Self::render_footer_into(env, md).build();
+        }
+
+        // Check edit window (only applies to non-moderators)
+        if is_author
+            && !is_moderator
+            && !Self::is_within_edit_window(env, board_id, reply.created_at)
+        {
+            md = md.warning("The edit window has expired for this reply.");
+            return Self::render_footer_into(env, md).build();
+        }
+
+        // Get current reply content
+        let reply_content: Bytes =
+            env.invoke_contract(&content, &Symbol::new(env, "get_reply_content"), args);
+
+        md = md
+            .raw_str("<input type=\"hidden\" name=\"_redirect\" value=\"")
+            .raw(base_path.clone())
+            .raw_str("/t/")
+            .number(thread_id as u32)
+            .raw_str("\" />\n")
+            .raw_str("<input type=\"hidden\" name=\"board_id\" value=\"")
+            .number(board_id as u32)
+            .raw_str("\" />\n")
+            .raw_str("<input type=\"hidden\" name=\"thread_id\" value=\"")
+            .number(thread_id as u32)
+            .raw_str("\" />\n")
+            .raw_str("<input type=\"hidden\" name=\"reply_id\" value=\"")
+            .number(reply_id as u32)
+            .raw_str("\" />\n")
+            .raw_str("<label>Content</label>\n")
+            .raw_str("<textarea name=\"content\" data-editor=\"markdown\" rows=\"6\">");
+
+        // Include the current content in the textarea
+        if !reply_content.is_empty() {
+            md = md.raw(reply_content);
+        }
+
+        md = md
+            .raw_str("</textarea>\n")
+            .newline()
+            .raw_str("<input type=\"hidden\" name=\"caller\" value=\"")
+            .text_string(&viewer.as_ref().unwrap().to_string())
+            .raw_str("\" />\n")
+            .newline()
+            .form_link_to("Save Changes", "content", "edit_reply_content")
+            .newline()
+            .newline()
+            .raw_str("[Cancel](render:")
+            .raw(base_path)
+            .raw_str("/t/")
+            .number(thread_id as u32)
+            .raw_str(")");
+
+        Self::render_footer_into(env, md).build()
+    }
+
+    /// Get CSS from Theme contract (a small built-in stylesheet if none is configured)
+    pub fn styles(env: Env) -> Bytes {
+        env.storage()
+            .instance()
+            .get::<_, Address>(&BoardKey::Theme)
+            .and_then(|theme| Self::theme_styles(&env, &theme))
+            .unwrap_or_else(|| Bytes::from_slice(&env, FALLBACK_CSS.as_bytes()))
+    }
+
+    /// Get the board's theme CSS followed by its own overrides, so they take precedence
+    pub fn board_styles(env: Env, board_id: u64) -> Bytes {
+        let mut css = Self::chosen_theme(&env, board_id)
+            .and_then(|theme| Self::theme_styles(&env, &theme))
+            .unwrap_or_else(|| Self::styles(env.clone()));
+        if let Some(custom) = Self::get_custom_css(env.clone(), board_id) {
+            css.append(&Bytes::from_slice(&env, b"\n/* Board overrides */\n"));
+            css.append(&soroban_render_sdk::bytes::string_to_bytes(&env, &custom));
+        }
+        css
+    }
+
+    /// Upgrade the contract WASM
+    pub fn upgrade(env: Env, new_wasm_hash: BytesN<32>) {
+        let registry: Address = env
+            .storage()
+            .instance()
+            .get(&BoardKey::Registry)
+            .expect("Not initialized");
+        registry.require_auth();
+
+        env.deployer().update_current_contract_wasm(new_wasm_hash);
+    }
+
+    /// Render a thread's poll, if it has one. Logged-in viewers who haven't voted
+    /// get one link per option; everyone else sees the results as bars, and voters
+    /// can still change their pick until the poll closes.
+    fn render_poll<'a>(
+        env: &Env,
+        mut md: MarkdownBuilder<'a>,
+        voting: &Address,
+        board_id: u64,
+        thread_id: u64,
+        viewer: &Option<Address>,
+    ) -> MarkdownBuilder<'a> {
+        let args: Vec<Val> =
+            Vec::from_array(env, [board_id.into_val(env), thread_id.into_val(env)]);
+        // Older voting contracts have no polls
+        let Some(poll) = env
+            .try_invoke_contract::<Option<Poll>, soroban_sdk::Error>(
+                voting,
+                &Symbol::new(env, "get_poll"),
+                args,
+            )
+            .ok()
+            .and_then(|r| r.ok())
+            .flatten()
+        else {
+            return md;
+        };
+
+        let picked: Vec<u32> = match viewer {
+            Some(user) => {
+                let vote_args: Vec<Val> =
+                    Vec::from_array(env, [poll.id.into_val(env), user.into_val(env)]);
+                env.invoke_contract(voting, &Symbol::new(env, "get_poll_vote"), vote_args)
+            }
+            None => Vec::new(env),
+        };
+        let now = env.ledger().timestamp();
+        let closed = poll.closes_at.is_some_and(|at| now >= at);
+        let can_vote = viewer.is_some() && !closed;
+
+        md = md
+            .div_start("poll")
+            .raw_str("<p class=\"poll-question\"><strong>")
+            .raw(Self::escape_attr(env, &poll.question))
+            .raw_str("</strong>");
+        if poll.multi {
+            md = md.raw_str(" <small>(pick any)</small>");
+        }
+        md = md.raw_str("</p>\n");
+
+        let show_results = !can_vote || !picked.is_empty();
+        let total: u32 = poll.counts.iter().sum();
+        for (idx, option) in poll.options.iter().enumerate() {
+            let idx = idx as u32;
+            let count = poll.counts.get(idx).unwrap_or(0);
+            md = md.raw_str("<div class=\"poll-option\">");
+            if show_results {
+                let percent = if total == 0 {
+                    0
+                } else {
+                    (count as u64 * 100 / total as u64) as u32
+                };
+                md = md.raw_str("<span class=\"poll-bar\">");
+                for tenth in 0..10 {
+                    md = md.raw_str(if tenth * 10 < percent { "█" } else { "░" });
+                }
+                md = md.raw_str("</span> ").number(percent).raw_str("% ");
+            }
+            if picked.contains(idx) {
+                md = md.raw_str("✓ ");
+            }
+            if can_vote {
+                md = md
+                    .raw_str("<a href=\"tx:@voting:vote_poll {&quot;poll_id&quot;:")
+                    .number(poll.id as u32)
+                    .raw_str(",&quot;option_idx&quot;:")
+                    .number(idx)
+                    .raw_str("}\">")
+                    .raw(Self::escape_attr(env, &option))
+                    .raw_str("</a>");
+            } else {
+                md = md.raw(Self::escape_attr(env, &option));
+            }
+            if show_results {
+                md = md.raw_str(" (").number(count).raw_str(")");
+            }
+            md = md.raw_str("</div>\n");
+        }
+
+        md = md
+            .raw_str("<p class=\"poll-meta\">")
+            .number(poll.voters)
+            .raw_str(if poll.voters == 1 {
+                " voter"
+            } else {
+                " voters"
+            });
+        if closed {
+            md = md.raw_str(" · closed");
+        } else if let Some(at) = poll.closes_at {
+            md = md
+                .raw_str(" · closes in ")
+                .raw(Self::format_duration(env, at - now));
+        }
+        md.raw_str("</p>\n").div_end().newline()
+    }
+
+    /// Render reaction chips for a thread (replies get theirs per batch,
+    /// see `reply_reactions`)
+    fn render_reactions<'a>(
+        env: &Env,
+        md: MarkdownBuilder<'a>,
+        voting: &Address,
+        board_id: u64,
+        thread_id: u64,
+        viewer: &Option<Address>,
+    ) -> MarkdownBuilder<'a> {
+        let reply_id: Option<u64> = None;
+        let args: Vec<Val> = Vec::from_array(
+            env,
+            [
+                board_id.into_val(env),
+                thread_id.into_val(env),
+                reply_id.into_val(env),
+            ],
+        );
+        // Older voting contracts have no reactions
+        let Some(reactions) = env
+            .try_invoke_contract::<Vec<(Symbol, u32)>, soroban_sdk::Error>(
+                voting,
+                &Symbol::new(env, "get_reactions"),
+                args,
+            )
+            .ok()
+            .and_then(|r| r.ok())
+        else {
+            return md;
+        };
+        if reactions.is_empty() {
+            return md;
+        }
+
+        let mine: Vec<Symbol> = match viewer {
+            Some(user) => {
+                let user_args: Vec<Val> = Vec::from_array(
+                    env,
+                    [
+                        board_id.into_val(env),
+                        thread_id.into_val(env),
+                        reply_id.into_val(env),
+                        user.into_val(env),
+                    ],
+                );
+                env.invoke_contract(voting, &Symbol::new(env, "get_user_reactions"), user_args)
+            }
+            None => Vec::new(env),
+        };
+
+        Self::render_reaction_chips(
+            env, md, board_id, thread_id, reply_id, &reactions, &mine, viewer,
+        )
+    }
+
+    /// Render reaction chips for a thread (reply_id None) or reply.
+    /// Logged-in viewers get toggle links; guests only see reactions that have been used.
+    fn render_reaction_chips<'a>(
+        env: &Env,
+        mut md: MarkdownBuilder<'a>,
+        board_id: u64,
+        thread_id: u64,
+        reply_id: Option<u64>,
+        reactions: &Vec<(Symbol, u32)>,
+        mine: &Vec<Symbol>,
+        viewer: &Option<Address>,
+    ) -> MarkdownBuilder<'a> {
+        if reactions.is_empty() {
+            return md;
+        }
+
+        md = md.div_start("reactions");
+        for (emoji, count) in reactions.iter() {
+            let Some((name, glyph)) = REACTION_GLYPHS
+                .iter()
+                .find(|(name, _)| Symbol::new(env, name) == emoji)
+            else {
+                continue;
+            };
+
+            if viewer.is_some() {
+                md = md
+                    .raw_str(if reply_id.is_some() {
+                        "<a href=\"tx:@voting:react_reply {&quot;board_id&quot;:"
+                    } else {
+                        "<a href=\"tx:@voting:react_thread {&quot;board_id&quot;:"
+                    })
+                    .number(board_id as u32)
+                    .raw_str(",&quot;thread_id&quot;:")
+                    .number(thread_id as u32);
+                if let Some(id) = reply_id {
+                    md = md.raw_str(",&quot;reply_id&quot;:").number(id as u32);
+                }
+                md = md
+                    .raw_str(",&quot;emoji&quot;:&quot;")
+                    .raw_str(name)
+                    .raw_str("&quot;}\" class=\"reaction-chip")
+                    .raw_str(if mine.contains(&emoji) {
+                        " reaction-active"
+                    } else {
+                        ""
+                    })
+                    .raw_str("\" title=\"")
+                    .raw_str(name)
+                    .raw_str("\">")
+                    .raw_str(glyph)
+                    .raw_str(" ")
+                    .number(count)
+                    .raw_str("</a>");
+            } else if count > 0 {
+                md = md
+                    .raw_str("<span class=\"reaction-chip\" title=\"")
+                    .raw_str(name)
+                    .raw_str("\">")
+                    .raw_str(glyph)
+                    .raw_str(" ")
+                    .number(count)
+                    .raw_str("</span>");
+            }
+        }
+        md.div_end()
+    }
+
+    /// Check whether a board allows downvotes (defaults to true if voting config is unavailable)
+    fn downvotes_allowed(env: &Env, voting: &Address, board_id: u64) -> bool {
+        env.try_invoke_contract::<VotingConfig, soroban_sdk::Error>(
+            voting,
+            &Symbol::new(env, "get_voting_config"),
+            Vec::from_array(env, [board_id.into_val(env)]),
+        )
+        .ok()
+        .and_then(|r| r.ok())
+        .map(|c| c.allow_downvotes)
+        .unwrap_or(true)
+    }
+
+    /// Whether a profile contract is registered (tolerates a missing registry)
+    fn has_profile_contract(env: &Env) -> bool {
+        let Some(registry) = env
+            .storage()
+            .instance()
+            .get::<_, Address>(&BoardKey::Registry)
+        else {
+            return false;
+        };
+        env.try_invoke_contract::<Option<Address>, soroban_sdk::Error>(
+            &registry,
+            &Symbol::new(env, "get_contract"),
+            Vec::from_array(env, [Symbol::new(env, "profile").into_val(env)]),
+        )
+        .ok()
+        .and_then(|r| r.ok())
+        .flatten()
+        .is_some()
+    }
+
+    /// Get admin contract from registry (if available)
+    fn get_admin_contract(env: &Env) -> Option<Address> {
+        let registry: Address = env.storage().instance().get(&BoardKey::Registry)?;
+        let args: Vec<Val> = Vec::from_array(env, [Symbol::new(env, "admin").into_val(env)]);
+        env.try_invoke_contract::<Option<Address>, soroban_sdk::Error>(
+            &registry,
+            &Symbol::new(env, "get_contract"),
+            args,
+        )
+        .ok()
+        .and_then(|r| r.ok())
+        .flatten()
+    }
+
+    /// Get profile contract from registry (if available)
+    fn get_profile_contract(env: &Env) -> Option<Address> {
+        let registry: Address = env.storage().instance().get(&BoardKey::Registry)?;
+
+        // Look up profile contract from registry using get_contract
+        let args: Vec<Val> = Vec::from_array(env, [Symbol::new(env, "profile").into_val(env)]);
+        env.invoke_contract(&registry, &Symbol::new(env, "get_contract"), args)
+    }
+
+    /// Fetch `{{aliases ...}}` tag from registry via cross-contract call.
+    ///
+    /// This enables includes using aliases like `{{include contract=config func="..."}}`
+    /// in rendered content. Call this early in render functions and prepend to output.
+    fn fetch_aliases(env: &Env) -> Bytes {
+        let registry_opt: Option<Address> = env.storage().instance().get(&BoardKey::Registry);
+
+        let Some(registry) = registry_opt else {
+            return Bytes::new(env);
+        };
+
+        // Call registry's render_aliases function
+        let args: Vec<Val> = Vec::new(env);
+        env.try_invoke_contract::<Bytes, soroban_sdk::Error>(
+            &registry,
+            &Symbol::new(env, "render_aliases"),
+            args,
+        )
+        .ok()
+        .and_then(|r| r.ok())
+        .unwrap_or(Bytes::new(env))
+    }
+
+    /// Get the community this board belongs to (if any)
+    /// Uses try_invoke to handle potential re-entrant call issues gracefully
+    fn get_board_community(env: &Env, board_id: u64) -> Option<CommunityInfo> {
+        // Get community contract address directly from storage
+        let community_contract: Address = env.storage().instance().get(&BoardKey::Community)?;
+
+        // Query community contract for board's community ID
+        // Use try_invoke to gracefully handle any issues
+        let args: Vec<Val> = Vec::from_array(env, [board_id.into_val(env)]);
+        let community_id_result = env.try_invoke_contract::<Option<u64>, soroban_sdk::Error>(
+            &community_contract,
+            &Symbol::new(env, "get_board_community"),
+            args,
+        );
+
+        // Unwrap the nested results/options
+        let community_id = match community_id_result {
+            Ok(Ok(Some(id))) => id,
+            _ => return None,
+        };
+
+        // Fetch community metadata
+        let meta_args: Vec<Val> = Vec::from_array(env, [community_id.into_val(env)]);
+        let community_meta_result = env
+            .try_invoke_contract::<Option<CommunityInfo>, soroban_sdk::Error>(
+                &community_contract,
+                &Symbol::new(env, "get_community_info"),
+                meta_args,
+            );
+
+        match community_meta_result {
+            Ok(Ok(Some(meta))) => Some(meta),
+            _ => None,
+        }
+    }
+
+    /// Pre-render the author cards for a batch of replies with one
+    /// `render_authors_batch` call covering each distinct author. Returns an empty
+    /// map when there is no profile contract or it predates the batch function,
+    /// in which case replies fall back to `render_author`.
+    fn render_author_cards(
+        env: &Env,
+        replies: &Vec<ReplyMeta>,
+        profile_contract: &Option<Address>,
+        return_path: Bytes,
+    ) -> Map<Address, Bytes> {
+        let mut cards = Map::new(env);
+        let Some(profile_addr) = profile_contract else {
+            return cards;
+        };
+
+        let mut authors: Vec<Address> = Vec::new(env);
+        for reply in replies.iter() {
+            if !authors.contains(&reply.creator) {
+                authors.push_back(reply.creator);
+            }
+        }
+        if authors.is_empty() {
+            return cards;
+        }
+
+        let args: Vec<Val> = Vec::from_array(
+            env,
+            [authors.clone().into_val(env), return_path.into_val(env)],
+        );
+        let rendered = env
+            .try_invoke_contract::<Vec<Bytes>, soroban_sdk::Error>(
+                profile_addr,
+                &Symbol::new(env, "render_authors_batch"),
+                args,
+            )
+            .ok()
+            .and_then(|r| r.ok());
+        if let Some(rendered) = rendered {
+            for (author, card) in authors.iter().zip(rendered.iter()) {
+                cards.set(author, card);
+            }
+        }
+        cards
+    }
+
+    /// Render author info (username link or truncated address)
+    ///
+    /// When return_path is provided, clicking the author link and then "Go Back"
+    /// on the profile page will return to that path.
+    fn render_author<'a>(
+        env: &Env,
+        md: MarkdownBuilder<'a>,
+        board_id: u64,
+        creator: &Address,
+        profile_contract: &Option<Address>,
+        return_path: Option<Bytes>,
+    ) -> MarkdownBuilder<'a> {
+        match profile_contract {
+            Some(profile_addr) => {
+                // Use the return path variant if we have a return path
+                let rendered: Bytes = match return_path {
+                    Some(path) => {
+                        let args: Vec<Val> =
+                            Vec::from_array(env, [creator.into_val(env), path.into_val(env)]);
+                        env.invoke_contract(
+                            profile_addr,
+                            &Symbol::new(env, "render_profile_compact_return"),
+                            args,
+                        )
+                    }
+                    None => {
+                        let args: Vec<Val> = Vec::from_array(env, [creator.into_val(env)]);
+                        env.invoke_contract(
+                            profile_addr,
+                            &Symbol::new(env, "render_profile_card_compact"),
+                            args,
+                        )
+                    }
+                };
+                md.raw(rendered)
+            }
+            None => {
+                // Fallback: the board-local display name, else a truncated address
+                let label = match Self::get_display_name(env.clone(), board_id, creator.clone()) {
+                    Some(name) => soroban_render_sdk::bytes::string_to_bytes(env, &name),
+                    None => Self::truncate_address(env, creator),
+                };
+                md.raw_str("<span class=\"author\">")
+                    .raw(label)
+                    .raw_str("</span>")
+            }
+        }
+    }
+
+    /// Build the base path for a board (e.g., "/b/{slug}" or "/c/{community}/b/{slug}")
+    ///
+    /// For community boards: /c/{community_slug}/b/{board_slug}
+    /// For standalone boards: /b/{board_slug}
+    ///
+    /// First checks temp storage for community slug (passed during render to avoid re-entrant calls).
+    /// Falls back to querying community contract if not in temp storage.
+    fn build_board_base_path(env: &Env, _board_id: u64, board_slug: &String) -> Bytes {
+        // First check temp storage for community slug (set during render)
+        let community_slug_opt: Option<String> = env
+            .storage()
+            .temporary()
+            .get(&BoardKey::RenderCommunitySlug);
+
+        if let Some(community_slug) = community_slug_opt {
+            // Community board: /c/{community_slug}/b/{board_slug}
+            let mut path = Bytes::from_slice(env, b"/c/");
+            path.append(&soroban_render_sdk::bytes::string_to_bytes(
+                env,
+                &community_slug,
+            ));
+            path.append(&Bytes::from_slice(env, b"/b/"));
+            path.append(&soroban_render_sdk::bytes::string_to_bytes(env, board_slug));
+            path
+        } else {
+            // Standalone board: /b/{board_slug}
+            let mut path = Bytes::from_slice(env, b"/b/");
+            path.append(&soroban_render_sdk::bytes::string_to_bytes(env, board_slug));
+            path
+        }
+    }
+
+    /// Build a return path for the current thread view (using slugs)
+    fn build_thread_return_path(
+        env: &Env,
+        board_id: u64,
+        board_slug: &String,
+        thread_id: u64,
+    ) -> Bytes {
+        // Build cross-contract return path: @main:{base_path}/t/{thread_id}
+        let mut path = Bytes::from_slice(env, b"@main:");
+        path.append(&Self::build_board_base_path(env, board_id, board_slug));
+        path.append(&Bytes::from_slice(env, b"/t/"));
+        path.append(&soroban_render_sdk::bytes::u32_to_bytes(
+            env,
+            thread_id as u32,
+        ));
+        path
+    }
+
+    /// Truncate an address for display
+    fn truncate_address(env: &Env, address: &Address) -> Bytes {
+        let addr_string = address.to_string();
+        let full_bytes = soroban_render_sdk::bytes::string_to_bytes(env, &addr_string);
+        let len = full_bytes.len();
+
+        if len <= 12 {
+            return full_bytes;
+        }
+
+        let mut result = Bytes::new(env);
+
+        // First 4 characters
+        for i in 0..4 {
+            if let Some(c) = full_bytes.get(i) {
+                result.push_back(c);
+            }
+        }
+
+        // Ellipsis
+        result.push_back(b'.');
+        result.push_back(b'.');
+        result.push_back(b'.');
+
+        // Last 4 characters
+        for i in (len - 4)..len {
+            if let Some(c) = full_bytes.get(i) {
+                result.push_back(c);
+            }
+        }
+
+        result
+    }
+
+    /// Wrap `@username` mentions of registered profiles in profile links.
+    /// Uses the same bounded scan as the content contract's mention index.
+    fn link_mentions(env: &Env, body: &Bytes, profile_contract: &Option<Address>) -> Bytes {
+        let Some(profile) = profile_contract else {
+            return body.clone();
+        };
+        let len = (body.len() as usize).min(MENTION_SCAN_LEN);
+        let mut buf = [0u8; MENTION_SCAN_LEN];
+        body.slice(0..len as u32).copy_into_slice(&mut buf[..len]);
+
+        let mut linked = Bytes::new(env);
+        let mut copied = 0usize;
+        let mut found = 0u32;
+        let mut from = 0;
+        while found < MAX_MENTIONS_PER_POST {
+            let Some((start, end)) = next_mention(&buf[..len], from) else {
+                break;
+            };
+            found += 1;
+            let name = &buf[start..end];
+            let lookup_args: Vec<Val> =
+                Vec::from_array(env, [String::from_bytes(env, name).into_val(env)]);
+            let registered = env
+                .try_invoke_contract::<Option<Address>, soroban_sdk::Error>(
+                    profile,
+                    &Symbol::new(env, "get_address_by_username"),
+                    lookup_args,
+                )
+                .ok()
+                .and_then(|r| r.ok())
+                .flatten()
+                .is_some();
+            if registered {
+                // Copy up to the `@`
+                linked.append(&body.slice(copied as u32..(start - 1) as u32));
+                linked.append(&Bytes::from_slice(env, b"[@"));
+                linked.append(&Bytes::from_slice(env, name));
+                linked.append(&Bytes::from_slice(env, b"](render:@profile:/u/"));
+                linked.append(&Bytes::from_slice(env, name));
+                linked.append(&Bytes::from_slice(env, b")"));
+                copied = end;
+            }
+            from = end;
+        }
+        linked.append(&body.slice(copied as u32..body.len()));
+        linked
+    }
+
+    /// Cut a post body to at most `limit` bytes for an excerpt. Ends on the last line
+    /// break that fits (or a character boundary if the first line is longer), and
+    /// closes a code fence left open by the cut.
+    fn cut_body(env: &Env, body: &Bytes, limit: u32) -> Bytes {
+        let end = char_floor(body, limit);
+
+        // Fence state at the end of each complete line
+        let mut open: Option<u8> = None;
+        let mut last_break: Option<(u32, Option<u8>)> = None;
+        let mut in_prefix = true;
+        let mut indent = 0u32;
+        let mut run = 0u32;
+        let mut run_char = 0u8;
+        for (i, b) in body.slice(0..end).iter().enumerate() {
+            if b == b'\n' {
+                last_break = Some((i as u32 + 1, open));
+                in_prefix = true;
+                indent = 0;
+                run = 0;
+                continue;
+            }
+            if !in_prefix {
+                continue;
+            }
+            if b == b' ' && run == 0 && indent < 3 {
+                indent += 1;
+            } else if (b == b'`' || b == b'~') && (run == 0 || b == run_char) {
+                run_char = b;
+                run += 1;
+                if run == 3 {
+                    // A fence only closes with the character that opened it
+                    match open {
+                        None => open = Some(b),
+                        Some(c) if c == b => open = None,
+                        _ => {}
+                    }
+                    in_prefix = false;
+                }
+            } else {
+                in_prefix = false;
+            }
+        }
+        let (end, open) = match last_break {
+            Some((line_end, open)) if end < body.len() => (line_end, open),
+            _ => (end, open),
+        };
+
+        let mut cut = body.slice(0..end);
+        if let Some(c) = open {
+            if !cut.is_empty() && cut.get(cut.len() - 1) != Some(b'\n') {
+                cut.push_back(b'\n');
+            }
+            cut.append(&Bytes::from_slice(env, &[c, c, c, b'\n']));
+        }
+        cut
+    }
+
+    /// Turn post content into a markdown quote for the reply form.
+    /// Cuts at `limit` bytes without splitting a UTF-8 character, prefixes each line
+    /// with "> " and escapes `&` and `<` so the text can't close the textarea.
+    fn build_quote(env: &Env, body: &Bytes, limit: u32) -> Bytes {
+        let mut end = char_floor(body, limit);
+        let truncated = end < body.len();
+
+        // Trailing line breaks would only add empty quote lines
+        while end > 0 && matches!(body.get(end - 1).unwrap(), b'\n' | b'\r') {
+            end -= 1;
+        }
+
+        let mut quote = Bytes::from_slice(env, b"> ");
+        for i in 0..end {
+            match body.get(i).unwrap() {
+                b'\r' => {}
+                b'\n' => quote.append(&Bytes::from_slice(env, b"\n> ")),
+                b'&' => quote.append(&Bytes::from_slice(env, b"&amp;")),
+                b'<' => quote.append(&Bytes::from_slice(env, b"&lt;")),
+                byte => quote.push_back(byte),
+            }
+        }
+        if truncated {
+            quote.append(&Bytes::from_slice(env, b"..."));
+        }
+        quote.append(&Bytes::from_slice(env, b"\n\n"));
+        quote
+    }
+
+    /// Describe a span of seconds in its largest whole unit ("2 hours", "1 day")
+    fn format_duration(env: &Env, seconds: u64) -> Bytes {
+        let (count, unit) = if seconds >= 86_400 {
+            (seconds / 86_400, " day")
+        } else if seconds >= 3_600 {
+            (seconds / 3_600, " hour")
+        } else {
+            ((seconds / 60).max(1), " minute")
+        };
+        let mut result = u64_to_bytes(env, count);
+        result.append(&Bytes::from_slice(env, unit.as_bytes()));
+        if count != 1 {
+            result.push_back(b's');
+        }
+        result
+    }
+
+    /// Describe a short wait compactly ("42s", "3m 5s")
+    fn format_seconds(env: &Env, seconds: u64) -> Bytes {
+        let mut result = Bytes::new(env);
+        if seconds >= 60 {
+            result.append(&u64_to_bytes(env, seconds / 60));
+            result.push_back(b'm');
+            if seconds % 60 == 0 {
+                return result;
+            }
+            result.push_back(b' ');
+        }
+        result.append(&u64_to_bytes(env, seconds % 60));
+        result.push_back(b's');
+        result
+    }
+
+    /// Format a Unix timestamp as a human-readable date string in the given style.
+    /// Returns "YYYY-MM-DD HH:MM UTC" for DateStyle::Iso.
+    fn format_timestamp(env: &Env, timestamp: u64, style: DateStyle) -> Bytes {
+        // Handle legacy ledger sequence numbers (small values)
+        // Unix timestamps for 2024+ are ~1700000000+
+        if timestamp < 1_000_000_000 {
+            // This is likely a ledger sequence, not a timestamp
+            let mut result = Bytes::from_slice(env, b"Ledger ");
+            result.append(&u64_to_bytes(env, timestamp));
+            return result;
+        }
+
+        // Convert Unix timestamp to date components
+        let total_seconds = timestamp;
+        let total_minutes = total_seconds / 60;
+        let total_hours = total_minutes / 60;
+        let total_days = total_hours / 24;
+
+        let minutes = (total_minutes % 60) as u8;
+        let hours = (total_hours % 24) as u8;
+
+        // Calculate year, month, day from days since epoch (Jan 1, 1970)
+        let (year, month, day) = Self::days_to_date(total_days as i64);
+
+        let two_digits = |n: u8| [b'0' + ((n / 10) % 10), b'0' + (n % 10)];
+        let year_digits = [
+            b'0' + ((year / 1000) % 10) as u8,
+            b'0' + ((year / 100) % 10) as u8,
+            b'0' + ((year / 10) % 10) as u8,
+            b'0' + (year % 10) as u8,
+        ];
+
+        // Date in the board's order, then " HH:MM UTC"
+        let mut result = Bytes::new(env);
+        match style {
+            DateStyle::Iso => {
+                result.extend_from_slice(&year_digits);
+                result.push_back(b'-');
+                result.extend_from_slice(&two_digits(month));
+                result.push_back(b'-');
+                result.extend_from_slice(&two_digits(day));
+            }
+            DateStyle::DayMonthYear => {
+                result.extend_from_slice(&two_digits(day));
+                result.push_back(b'.');
+                result.extend_from_slice(&two_digits(month));
+                result.push_back(b'.');
+                result.extend_from_slice(&year_digits);
+            }
+            DateStyle::Japanese => {
+                result.extend_from_slice(&year_digits);
+                result.extend_from_slice("年".as_bytes());
+                result.extend_from_slice(&two_digits(month));
+                result.extend_from_slice("月".as_bytes());
+                result.extend_from_slice(&two_digits(day));
+                result.extend_from_slice("日".as_bytes());
+            }
+        }
+        result.push_back(b' ');
+        result.extend_from_slice(&two_digits(hours));
+        result.push_back(b':');
+        result.extend_from_slice(&two_digits(minutes));
+        result.extend_from_slice(b" UTC");
+        result
+    }
+
+    /// Format a timestamp in the date order of the board's locale
+    fn format_board_timestamp(env: &Env, board_id: u64, timestamp: u64) -> Bytes {
+        Self::format_timestamp(env, timestamp, Self::date_style(env, board_id))
+    }
+
+    /// Convert days since Unix epoch to (year, month, day).
+    /// Algorithm based on Howard Hinnant's date algorithms.
+    fn days_to_date(days: i64) -> (i32, u8, u8) {
+        let z = days + 719468;
+        let era = if z >= 0 { z } else { z - 146096 } / 146097;
+        let doe = (z - era * 146097) as u32; // day of era
+        let yoe = (doe - doe / 1460 + doe / 36524 - doe / 146096) / 365; // year of era
+        let y = yoe as i64 + era * 400;
+        let doy = doe - (365 * yoe + yoe / 4 - yoe / 100); // day of year
+        let mp = (5 * doy + 2) / 153; // month index
+        let d = doy - (153 * mp + 2) / 5 + 1;
+        let m = if mp < 10 { mp + 3 } else { mp - 9 };
+        let year = if m <= 2 { y + 1 } else { y };
+
+        (year as i32, m as u8, d as u8)
+    }
+
+    // =========================================================================
+    // Rendering - JSON export
+    // =========================================================================
+
+    /// Render `/export/board`: board config and the newest threads as JSON
+    fn render_export_board(env: &Env, board_id: u64, viewer: &Option<Address>) -> Bytes {
+        let viewer_can_moderate = match Self::export_access(env, board_id, viewer) {
+            Ok(can_moderate) => can_moderate,
+            Err(error) => return Self::json_error(env, error),
+        };
+
+        let mut out = Bytes::from_slice(env, b"{\"board\":");
+        Self::json_board(env, &mut out, board_id);
+        Self::json_raw(env, &mut out, ",\"threads\":[");
+        let mut first = true;
+        for thread in Self::list_threads(env.clone(), board_id, 0, EXPORT_THREAD_LIMIT).iter() {
+            if (thread.is_hidden || thread.is_deleted) && !viewer_can_moderate {
+                continue;
+            }
+            if !first {
+                out.push_back(b',');
+            }
+            first = false;
+            out.push_back(b'{');
+            Self::json_thread_fields(env, &mut out, &thread);
+            out.push_back(b'}');
+        }
+        Self::json_raw(env, &mut out, "]}");
+        out
+    }
+
+    /// Render `/export/t/{tid}`: thread metadata, body and the first page of replies as JSON
+    fn render_export_thread(
+        env: &Env,
+        board_id: u64,
+        thread_id: u64,
+        viewer: &Option<Address>,
+    ) -> Bytes {
+        let viewer_can_moderate = match Self::export_access(env, board_id, viewer) {
+            Ok(can_moderate) => can_moderate,
+            Err(error) => return Self::json_error(env, error),
+        };
+        let thread = match env
+            .storage()
+            .persistent()
+            .get::<_, ThreadMeta>(&BoardKey::BoardThread(board_id, thread_id))
+        {
+            Some(t) if viewer_can_moderate || !(t.is_hidden || t.is_deleted) => t,
+            _ => return Self::json_error(env, "not_found"),
+        };
+
+        let Some(content) = env
+            .storage()
+            .instance()
+            .get::<_, Address>(&BoardKey::Content)
+        else {
+            return Self::json_error(env, "content_unavailable");
+        };
+        let voting: Option<Address> = env.storage().instance().get(&BoardKey::Voting);
+        let config: BoardConfig = env
+            .storage()
+            .persistent()
+            .get(&BoardKey::BoardConfig(board_id))
+            .expect("Board not found");
+        let chunk_size = if config.reply_chunk_size == 0 {
+            6
+        } else {
+            config.reply_chunk_size
+        };
+
+        let mut out = Bytes::from_slice(env, b"{\"board\":");
+        Self::json_board(env, &mut out, board_id);
+        Self::json_raw(env, &mut out, ",\"thread\":{");
+        Self::json_thread_fields(env, &mut out, &thread);
+        Self::json_raw(env, &mut out, ",\"score\":");
+        let tally = voting.as_ref().and_then(|voting| {
+            env.try_invoke_contract::<VoteTally, soroban_sdk::Error>(
+                voting,
+                &Symbol::new(env, "get_thread_tally"),
+                Vec::from_array(env, [board_id.into_val(env), thread_id.into_val(env)]),
+            )
+            .ok()
+            .and_then(|r| r.ok())
+        });
+        Self::json_score(env, &mut out, tally);
+        Self::json_raw(env, &mut out, ",\"body\":");
+        let args: Vec<Val> =
+            Vec::from_array(env, [board_id.into_val(env), thread_id.into_val(env)]);
+        let body: Bytes = env.invoke_contract(&content, &Symbol::new(env, "get_thread_body"), args);
+        Self::json_bytes(env, &mut out, &body);
+
+        Self::json_raw(env, &mut out, "},\"replies\":[");
+        let list_args: Vec<Val> = Vec::from_array(
+            env,
+            [
+                board_id.into_val(env),
+                thread_id.into_val(env),
+                0u32.into_val(env),
+                chunk_size.into_val(env),
+            ],
+        );
+        let replies: Vec<ReplyMeta> = env.invoke_contract(
+            &content,
+            &Symbol::new(env, "list_top_level_replies"),
+            list_args,
+        );
+        let mut first = true;
+        for reply in replies.iter() {
+            if (reply.is_hidden || reply.is_deleted) && !viewer_can_moderate {
+                continue;
+            }
+            if !first {
+                out.push_back(b',');
+            }
+            first = false;
+
+            let reply_args: Vec<Val> = Vec::from_array(
+                env,
+                [
+                    board_id.into_val(env),
+                    thread_id.into_val(env),
+                    reply.id.into_val(env),
+                ],
+            );
+            Self::json_raw(env, &mut out, "{\"id\":");
+            out.append(&u64_to_bytes(env, reply.id));
+            Self::json_raw(env, &mut out, ",\"parent_id\":");
+            out.append(&u64_to_bytes(env, reply.parent_id));
+            Self::json_raw(env, &mut out, ",\"depth\":");
+            out.append(&u64_to_bytes(env, reply.depth as u64));
+            Self::json_raw(env, &mut out, ",\"creator\":");
+            Self::json_string(env, &mut out, &reply.creator.to_string());
+            Self::json_raw(env, &mut out, ",\"created_at\":");
+            out.append(&u64_to_bytes(env, reply.created_at));
+            Self::json_raw(env, &mut out, ",\"updated_at\":");
+            out.append(&u64_to_bytes(env, reply.updated_at));
+            Self::json_raw(env, &mut out, ",\"is_hidden\":");
+            Self::json_bool(env, &mut out, reply.is_hidden);
+            Self::json_raw(env, &mut out, ",\"is_deleted\":");
+            Self::json_bool(env, &mut out, reply.is_deleted);
+            Self::json_raw(env, &mut out, ",\"score\":");
+            let tally = voting.as_ref().and_then(|voting| {
+                env.try_invoke_contract::<VoteTally, soroban_sdk::Error>(
+                    voting,
+                    &Symbol::new(env, "get_reply_tally"),
+                    reply_args.clone(),
+                )
+                .ok()
+                .and_then(|r| r.ok())
+            });
+            Self::json_score(env, &mut out, tally);
+            Self::json_raw(env, &mut out, ",\"body\":");
+            let body: Bytes =
+                env.invoke_contract(&content, &Symbol::new(env, "get_reply_content"), reply_args);
+            Self::json_bytes(env, &mut out, &body);
+            out.push_back(b'}');
+        }
+        Self::json_raw(env, &mut out, "]}");
+        out
+    }
+
+    /// Check that the viewer may export this board. Returns whether they can moderate,
+    /// or the error code to report.
+    fn export_access(
+        env: &Env,
+        board_id: u64,
+        viewer: &Option<Address>,
+    ) -> Result<bool, &'static str> {
+        let Some(config) = env
+            .storage()
+            .persistent()
+            .get::<_, BoardConfig>(&BoardKey::BoardConfig(board_id))
+        else {
+            return Err("not_found");
+        };
+
+        let perms_addr_opt = env
+            .storage()
+            .instance()
+            .get::<_, Address>(&BoardKey::Permissions);
+        let viewer_role = match (&perms_addr_opt, viewer) {
+            (Some(perms_addr), Some(user)) => {
+                let args: Vec<Val> =
+                    Vec::from_array(env, [board_id.into_val(env), user.into_val(env)]);
+                env.invoke_contract(perms_addr, &Symbol::new(env, "get_role"), args)
+            }
+            _ => Role::Guest,
+        };
+
+        // Same rule as the rendered views: private boards need Member+ when permissions are set
+        if config.is_private
+            && perms_addr_opt.is_some()
+            && (viewer_role as u32) < (Role::Member as u32)
+        {
+            return Err("private");
+        }
+        Ok((viewer_role as u32) >= (Role::Moderator as u32))
+    }
+
+    /// Append the board object (metadata, config and thread count)
+    fn json_board(env: &Env, out: &mut Bytes, board_id: u64) {
+        let meta: BoardMeta = env
+            .storage()
+            .persistent()
+            .get(&BoardKey::Board(board_id))
+            .expect("Board not found");
+        let config: BoardConfig = env
+            .storage()
+            .persistent()
+            .get(&BoardKey::BoardConfig(board_id))
+            .expect("Board not found");
+
+        Self::json_raw(env, out, "{\"id\":");
+        out.append(&u64_to_bytes(env, board_id));
+        Self::json_raw(env, out, ",\"slug\":");
+        Self::json_string(env, out, &meta.slug);
+        Self::json_raw(env, out, ",\"name\":");
+        Self::json_string(env, out, &config.name);
+        Self::json_raw(env, out, ",\"description\":");
+        Self::json_string(env, out, &config.description);
+        Self::json_raw(env, out, ",\"creator\":");
+        Self::json_string(env, out, &meta.creator.to_string());
+        Self::json_raw(env, out, ",\"created_at\":");
+        out.append(&u64_to_bytes(env, meta.created_at));
+        Self::json_raw(env, out, ",\"is_private\":");
+        Self::json_bool(env, out, config.is_private);
+        Self::json_raw(env, out, ",\"is_readonly\":");
+        Self::json_bool(env, out, config.is_readonly);
+        Self::json_raw(env, out, ",\"max_reply_depth\":");
+        out.append(&u64_to_bytes(env, config.max_reply_depth as u64));
+        Self::json_raw(env, out, ",\"reply_chunk_size\":");
+        out.append(&u64_to_bytes(env, config.reply_chunk_size as u64));
+        Self::json_raw(env, out, ",\"thread_count\":");
+        out.append(&u64_to_bytes(
+            env,
+            Self::thread_count(env.clone(), board_id),
+        ));
+        out.push_back(b'}');
+    }
+
+    /// Append the fields of a thread object (without the surrounding braces)
+    fn json_thread_fields(env: &Env, out: &mut Bytes, thread: &ThreadMeta) {
+        Self::json_raw(env, out, "\"id\":");
+        out.append(&u64_to_bytes(env, thread.id));
+        Self::json_raw(env, out, ",\"title\":");
+        Self::json_string(env, out, &thread.title);
+        Self::json_raw(env, out, ",\"creator\":");
+        Self::json_string(env, out, &thread.creator.to_string());
+        Self::json_raw(env, out, ",\"created_at\":");
+        out.append(&u64_to_bytes(env, thread.created_at));
+        Self::json_raw(env, out, ",\"updated_at\":");
+        out.append(&u64_to_bytes(env, thread.updated_at));
+        Self::json_raw(env, out, ",\"reply_count\":");
+        out.append(&u64_to_bytes(env, thread.reply_count as u64));
+        Self::json_raw(env, out, ",\"is_locked\":");
+        Self::json_bool(env, out, thread.is_locked);
+        Self::json_raw(env, out, ",\"is_pinned\":");
+        Self::json_bool(env, out, thread.is_pinned);
+        Self::json_raw(env, out, ",\"is_hidden\":");
+        Self::json_bool(env, out, thread.is_hidden);
+        Self::json_raw(env, out, ",\"is_deleted\":");
+        Self::json_bool(env, out, thread.is_deleted);
+        Self::json_raw(env, out, ",\"flair_id\":");
+        match thread.flair_id {
+            Some(id) => out.append(&u64_to_bytes(env, id as u64)),
+            None => Self::json_raw(env, out, "null"),
+        }
+    }
+
+    /// `{"error":"<code>"}` for exports the viewer can't see
+    fn json_error(env: &Env, code: &str) -> Bytes {
+        let mut out = Bytes::from_slice(env, b"{\"error\":\"");
+        Self::json_raw(env, &mut out, code);
+        Self::json_raw(env, &mut out, "\"}");
+        out
+    }
+
+    /// Append a fragment that is already valid JSON
+    fn json_raw(env: &Env, out: &mut Bytes, fragment: &str) {
+        out.append(&Bytes::from_slice(env, fragment.as_bytes()));
+    }
+
+    /// Append `true` or `false`
+    fn json_bool(env: &Env, out: &mut Bytes, value: bool) {
+        Self::json_raw(env, out, if value { "true" } else { "false" });
+    }
+
+    /// Append a vote score, or `null` when there is no voting contract
+    fn json_score(env: &Env, out: &mut Bytes, tally: Option<VoteTally>) {
+        match tally {
+            Some(tally) => {
+                if tally.score < 0 {
+                    out.push_back(b'-');
+                }
+                out.append(&u64_to_bytes(env, tally.score.unsigned_abs() as u64));
+            }
+            None => Self::json_raw(env, out, "null"),
+        }
+    }
+
+    /// Append a stored string as a JSON string literal
+    fn json_string(env: &Env, out: &mut Bytes, value: &String) {
+        Self::json_bytes(
+            env,
+            out,
+            &soroban_render_sdk::bytes::string_to_bytes(env, value),
+        );
+    }
+
+    /// Append a JSON string literal, escaping quotes, backslashes and control characters
+    fn json_bytes(env: &Env, out: &mut Bytes, value: &Bytes) {
+        const HEX: &[u8; 16] = b"0123456789abcdef";
+        out.push_back(b'"');
+        for byte in value.iter() {
+            match byte {
+                b'"' => Self::json_raw(env, out, "\\\""),
+                b'\\' => Self::json_raw(env, out, "\\\\"),
+                b'\n' => Self::json_raw(env, out, "\\n"),
+                b'\r' => Self::json_raw(env, out, "\\r"),
+                b'\t' => Self::json_raw(env, out, "\\t"),
+                0x00..=0x1F => {
+                    Self::json_raw(env, out, "\\u00");
+                    out.push_back(HEX[(byte >> 4) as usize]);
+                    out.push_back(HEX[(byte & 0x0F) as usize]);
+                }
+                _ => out.push_back(byte),
+            }
+        }
+        out.push_back(b'"');
+    }
+
+    // =========================================================================
+    // Rendering - Markdown export
+    // =========================================================================
+
+    /// Render `/export/t/{tid}/md`: the whole thread as one markdown document, with
+    /// replies flattened depth-first under `>` markers. Replies past
+    /// `MAX_EXPORT_REPLIES` are left out and a note says so.
+    fn render_export_thread_markdown(
+        env: &Env,
+        board_id: u64,
+        thread_id: u64,
+        viewer: &Option<Address>,
+    ) -> Bytes {
+        let viewer_can_moderate = match Self::export_access(env, board_id, viewer) {
+            Ok(can_moderate) => can_moderate,
+            Err("private") => return Bytes::from_slice(env, b"This board is private.\n"),
+            Err(_) => return Bytes::from_slice(env, b"Thread not found.\n"),
+        };
+        let thread = match env
+            .storage()
+            .persistent()
+            .get::<_, ThreadMeta>(&BoardKey::BoardThread(board_id, thread_id))
+        {
+            Some(t) if viewer_can_moderate || !(t.is_hidden || t.is_deleted) => t,
+            _ => return Bytes::from_slice(env, b"Thread not found.\n"),
+        };
+        let Some(content) = env
+            .storage()
+            .instance()
+            .get::<_, Address>(&BoardKey::Content)
+        else {
+            return Bytes::from_slice(env, b"Content is temporarily unavailable.\n");
+        };
+
+        let raw = |fragment: &str| Bytes::from_slice(env, fragment.as_bytes());
+        let mut out = raw("# ");
+        out.append(&soroban_render_sdk::bytes::string_to_bytes(
+            env,
+            &thread.title,
+        ));
+        out.append(&raw("\n\n**"));
+        out.append(&Self::export_author(env, board_id, &thread.creator));
+        out.append(&raw("** · "));
+        out.append(&Self::format_timestamp(
+            env,
+            thread.created_at,
+            DateStyle::Iso,
+        ));
+        out.append(&raw("\n\n"));
+        if thread.is_deleted {
+            out.append(&raw("[This thread has been deleted]"));
+        } else {
+            let args: Vec<Val> =
+                Vec::from_array(env, [board_id.into_val(env), thread_id.into_val(env)]);
+            let body: Bytes =
+                env.invoke_contract(&content, &Symbol::new(env, "get_thread_body"), args);
+            out.append(&body);
+        }
+        out.append(&raw("\n\n---\n\n## Replies\n\n"));
+
+        let mut exported = 0u32;
+        let mut complete = true;
+        let mut start = 0u32;
+        loop {
+            let args: Vec<Val> = Vec::from_array(
+                env,
+                [
+                    board_id.into_val(env),
+                    thread_id.into_val(env),
+                    start.into_val(env),
+                    MAX_EXPORT_REPLIES.into_val(env),
+                ],
+            );
+            let page: Vec<ReplyMeta> =
+                env.invoke_contract(&content, &Symbol::new(env, "list_top_level_replies"), args);
+            complete = Self::export_replies_markdown(
+                env,
+                &mut out,
+                &content,
+                &page,
+                0,
+                viewer_can_moderate,
+                &mut exported,
+            );
+            if !complete || page.len() < MAX_EXPORT_REPLIES {
+                break;
+            }
+            start += MAX_EXPORT_REPLIES;
+        }
+
+        if exported == 0 {
+            out.append(&raw("*No replies.*\n"));
+        }
+        if !complete {
+            out.append(&raw("*Export stopped after "));
+            out.append(&u64_to_bytes(env, MAX_EXPORT_REPLIES as u64));
+            out.append(&raw(" replies; later replies are not included.*\n"));
+        }
+        out
+    }
+
+    /// Append a page of sibling replies, each followed by its children, depth-first
+    /// and quoted `level` deep. Bodies and child counts are fetched once per page.
+    /// Returns false once `MAX_EXPORT_REPLIES` have been written and more remain.
+    fn export_replies_markdown(
+        env: &Env,
+        out: &mut Bytes,
+        content: &Address,
+        replies: &Vec<ReplyMeta>,
+        level: u32,
+        viewer_can_moderate: bool,
+        exported: &mut u32,
+    ) -> bool {
+        let Some(first) = replies.first() else {
+            return true;
+        };
+        if *exported >= MAX_EXPORT_REPLIES {
+            return false;
+        }
+        let (board_id, thread_id) = (first.board_id, first.thread_id);
+        // Replies past the cap are never written, so don't fetch for them
+        let take = replies.len().min(MAX_EXPORT_REPLIES - *exported);
+        let shows_body =
+            |reply: &ReplyMeta| !reply.is_deleted && (viewer_can_moderate || !reply.is_hidden);
+
+        let mut ids: Vec<u64> = Vec::new(env);
+        let mut body_ids: Vec<u64> = Vec::new(env);
+        for reply in replies.slice(0..take).iter() {
+            ids.push_back(reply.id);
+            if shows_body(&reply) {
+                body_ids.push_back(reply.id);
+            }
+        }
+        let batch_args = |ids: &Vec<u64>| -> Vec<Val> {
+            Vec::from_array(
+                env,
+                [
+                    board_id.into_val(env),
+                    thread_id.into_val(env),
+                    ids.into_val(env),
+                ],
+            )
+        };
+        let bodies: Vec<Bytes> = if body_ids.is_empty() {
+            Vec::new(env)
+        } else {
+            env.invoke_contract(
+                content,
+                &Symbol::new(env, "get_reply_contents"),
+                batch_args(&body_ids),
+            )
+        };
+        let child_counts: Vec<u32> = env.invoke_contract(
+            content,
+            &Symbol::new(env, "get_children_counts"),
+            batch_args(&ids),
+        );
+
+        let mut next_body = 0u32;
+        for (i, reply) in replies.iter().enumerate() {
+            if *exported >= MAX_EXPORT_REPLIES {
+                return false;
+            }
+            *exported += 1;
+
+            let body = if shows_body(&reply) {
+                next_body += 1;
+                bodies.get(next_body - 1)
+            } else {
+                None
+            };
+            Self::export_reply_block(env, out, &reply, body, level);
+
+            if child_counts.get(i as u32).unwrap_or(0) == 0 {
+                continue;
+            }
+            let mut start = 0u32;
+            loop {
+                let args: Vec<Val> = Vec::from_array(
+                    env,
+                    [
+                        board_id.into_val(env),
+                        thread_id.into_val(env),
+                        reply.id.into_val(env),
+                        start.into_val(env),
+                        MAX_EXPORT_REPLIES.into_val(env),
+                    ],
+                );
+                let children: Vec<ReplyMeta> =
+                    env.invoke_contract(content, &Symbol::new(env, "list_children_replies"), args);
+                if !Self::export_replies_markdown(
+                    env,
+                    out,
+                    content,
+                    &children,
+                    level + 1,
+                    viewer_can_moderate,
+                    exported,
+                ) {
+                    return false;
+                }
+                if children.len() < MAX_EXPORT_REPLIES {
+                    break;
+                }
+                start += MAX_EXPORT_REPLIES;
+            }
+        }
+        true
+    }
+
+    /// Append one reply's header and body (or tombstone), quoted `level` deep
+    fn export_reply_block(
+        env: &Env,
+        out: &mut Bytes,
+        reply: &ReplyMeta,
+        body: Option<Bytes>,
+        level: u32,
+    ) {
+        let mut block = Bytes::from_slice(env, b"**");
+        block.append(&Self::export_author(env, reply.board_id, &reply.creator));
+        block.append(&Bytes::from_slice(env, "** · ".as_bytes()));
+        block.append(&Self::format_timestamp(
+            env,
+            reply.created_at,
+            DateStyle::Iso,
+        ));
+        block.append(&Bytes::from_slice(env, " · #".as_bytes()));
+        block.append(&u64_to_bytes(env, reply.id));
+        block.push_back(b'\n');
+        // Tombstones match the thread view's
+        match body {
+            Some(body) => block.append(&body),
+            None if reply.is_deleted => {
+                block.append(&Bytes::from_slice(env, b"[This reply has been deleted]"))
+            }
+            None => block.append(&Bytes::from_slice(
+                env,
+                b"[This reply has been hidden by a moderator]",
+            )),
+        }
+        Self::append_quoted(env, out, &block, level);
+        out.append(&Bytes::from_slice(env, b"\n\n"));
+    }
+
+    /// Append `text` with `level` quote markers (`> `) at the start of every line,
+    /// copying it a line at a time
+    fn append_quoted(env: &Env, out: &mut Bytes, text: &Bytes, level: u32) {
+        if level == 0 {
+            out.append(text);
+            return;
+        }
+        let mut prefix = Bytes::new(env);
+        for _ in 0..level {
+            prefix.append(&Bytes::from_slice(env, b"> "));
+        }
+        let mut line_start = 0u32;
+        for (i, byte) in text.iter().enumerate() {
+            if byte == b'\n' {
+                out.append(&prefix);
+                out.append(&text.slice(line_start..i as u32 + 1));
+                line_start = i as u32 + 1;
+            }
+        }
+        if line_start < text.len() {
+            out.append(&prefix);
+            out.append(&text.slice(line_start..text.len()));
+        }
+    }
+
+    /// Render `/export/digest/{days}`: the `/digest/{days}` list as plain markdown,
+    /// one numbered line per thread with a link, author, score and reply count
+    fn render_export_digest_markdown(
+        env: &Env,
+        board_id: u64,
+        days: u64,
+        viewer: &Option<Address>,
+    ) -> Bytes {
+        match Self::export_access(env, board_id, viewer) {
+            Ok(_) => {}
+            Err("private") => return Bytes::from_slice(env, b"This board is private.\n"),
+            Err(_) => return Bytes::from_slice(env, b"Board not found.\n"),
+        }
+        let meta: BoardMeta = env
+            .storage()
+            .persistent()
+            .get(&BoardKey::Board(board_id))
+            .expect("Board not found");
+        let base_path = Self::build_board_base_path(env, board_id, &meta.slug);
+        let (threads, by_score, truncated) = Self::digest_threads(env, board_id, days);
+
+        let raw = |fragment: &str| Bytes::from_slice(env, fragment.as_bytes());
+        let mut out = raw("# ");
+        out.append(&soroban_render_sdk::bytes::string_to_bytes(env, &meta.name));
+        out.append(&raw(": top threads of the last "));
+        out.append(&u64_to_bytes(env, days));
+        out.append(&raw(if days == 1 { " day\n\n" } else { " days\n\n" }));
+        if truncated {
+            out.append(&raw(
+                "*Only the newest 100 threads were checked, so older threads from this period may be missing.*\n\n",
+            ));
+        }
+        if threads.is_empty() {
+            out.append(&raw("*No new threads in this period.*\n"));
+        }
+        let mut rank = 0u64;
+        for (thread, score) in threads.iter() {
+            rank += 1;
+            out.append(&u64_to_bytes(env, rank));
+            out.append(&raw(". ["));
+            out.append(&soroban_render_sdk::bytes::string_to_bytes(
+                env,
+                &thread.title,
+            ));
+            out.append(&raw("]("));
+            out.append(&base_path);
+            out.append(&raw("/t/"));
+            out.append(&u64_to_bytes(env, thread.id));
+            out.append(&raw(") by "));
+            out.append(&Self::export_author(env, board_id, &thread.creator));
+            if by_score {
+                out.append(&raw(if score < 0 { " · -" } else { " · " }));
+                out.append(&u64_to_bytes(env, score.unsigned_abs() as u64));
+                out.append(&raw(" points"));
+            }
+            out.append(&raw(" · "));
+            out.append(&u64_to_bytes(env, thread.reply_count as u64));
+            out.append(&raw(" replies\n"));
+        }
+        out
+    }
+
+    /// An author as plain text: the board display name and address, or just the address
+    fn export_author(env: &Env, board_id: u64, user: &Address) -> Bytes {
+        let address = soroban_render_sdk::bytes::string_to_bytes(env, &user.to_string());
+        match Self::get_display_name(env.clone(), board_id, user.clone()) {
+            Some(name) => {
+                let mut label = soroban_render_sdk::bytes::string_to_bytes(env, &name);
+                label.append(&Bytes::from_slice(env, b" ("));
+                label.append(&address);
+                label.push_back(b')');
+                label
+            }
+            None => address,
+        }
+    }
+
+    // =========================================================================
+    // Slug Helper Functions
+    // =========================================================================
+
+    /// Validate slug format: 3-30 chars, starts with lowercase letter,
+    /// contains only lowercase letters, numbers, and hyphens, cannot end with hyphen.
+    fn validate_slug(env: &Env, slug: &String) {
+        let len = slug.len() as usize;
+        if !(3..=30).contains(&len) {
+            panic!("Board slug must be 3-30 characters");
+        }
+
+        let mut buf = [0u8; 30];
+        let copy_len = core::cmp::min(len, 30);
+        slug.copy_into_slice(&mut buf[..copy_len]);
+
+        // First character must be lowercase letter
+        let first = buf[0];
+        if !first.is_ascii_lowercase() {
+            panic!("Board slug must start with lowercase letter");
+        }
+
+        // All characters must be lowercase alphanumeric or hyphen
+        for &c in buf.iter().take(copy_len) {
+            let valid = c.is_ascii_lowercase() || c.is_ascii_digit() || c == b'-';
+            if !valid {
+                panic!("Board slug can only contain lowercase letters, numbers, and hyphens");
+            }
+        }
+
+        // Cannot end with hyphen
+        if buf[copy_len - 1] == b'-' {
+            panic!("Board slug cannot end with hyphen");
+        }
+
+        let _ = env;
+    }
+
+    /// Generate a slug from a board name.
+    /// Converts to lowercase, replaces spaces and special chars with hyphens,
+    /// collapses multiple hyphens, trims leading/trailing hyphens.
+    fn generate_slug_from_name(env: &Env, name: &String) -> String {
+        let len = name.len() as usize;
+        if len == 0 {
+            return String::from_str(env, "board");
+        }
+
+        let mut name_buf = [0u8; 64];
+        let copy_len = core::cmp::min(len, 64);
+        name.copy_into_slice(&mut name_buf[..copy_len]);
+
+        let mut slug_buf = [0u8; 30];
+        let mut slug_len = 0usize;
+        let mut last_was_hyphen = true; // Start true to skip leading hyphens
+
+        for &c in name_buf.iter().take(copy_len) {
+            if slug_len >= 30 {
+                break;
+            }
+
+            // Convert to lowercase letter
+            if c.is_ascii_uppercase() {
+                slug_buf[slug_len] = c + 32; // to lowercase
+                slug_len += 1;
+                last_was_hyphen = false;
+            } else if c.is_ascii_lowercase() {
+                slug_buf[slug_len] = c;
+                slug_len += 1;
+                last_was_hyphen = false;
+            } else if c.is_ascii_digit() {
+                // Numbers are OK but not as first char
+                if slug_len > 0 {
+                    slug_buf[slug_len] = c;
+                    slug_len += 1;
+                    last_was_hyphen = false;
+                }
+            } else if !last_was_hyphen && slug_len > 0 {
+                // Replace spaces, underscores, etc with hyphen (collapse multiple)
+                slug_buf[slug_len] = b'-';
+                slug_len += 1;
+                last_was_hyphen = true;
+            }
+        }
+
+        // Trim trailing hyphen
+        while slug_len > 0 && slug_buf[slug_len - 1] == b'-' {
+            slug_len -= 1;
+        }
+
+        // Ensure minimum length
+        if slug_len < 3 {
+            return String::from_str(env, "board");
+        }
+
+        // Build String from slice
+        String::from_str(
+            env,
+            core::str::from_utf8(&slug_buf[..slug_len]).unwrap_or("board"),
+        )
+    }
+
+    /// Generate a random 4-character suffix for slug conflict resolution.
+    fn generate_random_suffix(env: &Env) -> String {
+        // Use ledger timestamp and sequence for pseudo-randomness
+        let timestamp = env.ledger().timestamp();
+        let sequence = env.ledger().sequence();
+        let combined = timestamp.wrapping_mul(31).wrapping_add(sequence as u64);
+
+        let chars = b"abcdefghijklmnopqrstuvwxyz0123456789";
+        let mut suffix = [0u8; 4];
+        let mut val = combined;
+        for s in &mut suffix {
+            *s = chars[(val % 36) as usize];
+            val /= 36;
+        }
+
+        String::from_str(env, core::str::from_utf8(&suffix).unwrap_or("0000"))
+    }
+
+    /// Check if a slug is available for standalone boards.
+    fn is_slug_available(env: &Env, slug: &String) -> bool {
+        !env.storage()
+            .persistent()
+            .has(&BoardKey::BoardBySlug(slug.clone()))
+    }
+
+    /// Generate a unique slug, appending random suffix if needed.
+    /// For standalone boards only - community boards are handled by boards-community.
+    fn generate_unique_slug(env: &Env, base_slug: &String) -> String {
+        // Check if base slug is available
+        if Self::is_slug_available(env, base_slug) {
+            return base_slug.clone();
+        }
+
+        // Try with random suffixes (up to 10 attempts)
+        for _ in 0..10 {
+            let suffix = Self::generate_random_suffix(env);
+
+            // Build slug with suffix: base-suffix
+            let base_len = base_slug.len() as usize;
+            let mut buf = [0u8; 30];
+            let copy_len = core::cmp::min(base_len, 25); // Leave room for -xxxx
+            base_slug.copy_into_slice(&mut buf[..copy_len]);
+
+            buf[copy_len] = b'-';
+            let mut suffix_buf = [0u8; 4];
+            suffix.copy_into_slice(&mut suffix_buf);
+            for (i, &c) in suffix_buf.iter().enumerate() {
+                buf[copy_len + 1 + i] = c;
+            }
+
+            let new_slug = String::from_str(
+                env,
+                core::str::from_utf8(&buf[..copy_len + 5]).unwrap_or("board"),
+            );
+
+            if Self::is_slug_available(env, &new_slug) {
+                return new_slug;
+            }
+        }
+
+        // Fallback: use timestamp as suffix
+        let ts = env.ledger().timestamp();
+        let ts_str = Self::u64_to_string(env, ts);
+
+        let base_len = base_slug.len() as usize;
+        let mut buf = [0u8; 30];
+        let copy_len = core::cmp::min(base_len, 15);
+        base_slug.copy_into_slice(&mut buf[..copy_len]);
+        buf[copy_len] = b'-';
+
+        let ts_len = ts_str.len() as usize;
+        let ts_copy = core::cmp::min(ts_len, 14);
+        ts_str.copy_into_slice(&mut buf[copy_len + 1..copy_len + 1 + ts_copy]);
+
+        String::from_str(
+            env,
+            core::str::from_utf8(&buf[..copy_len + 1 + ts_copy]).unwrap_or("board"),
+        )
+    }
+
+    /// Convert u64 to String (for timestamp suffix).
+    fn u64_to_string(env: &Env, mut n: u64) -> String {
+        if n == 0 {
+            return String::from_str(env, "0");
+        }
+
+        let mut buf = [0u8; 20];
+        let mut pos = 20;
+        while n > 0 && pos > 0 {
+            pos -= 1;
+            buf[pos] = b'0' + (n % 10) as u8;
+            n /= 10;
+        }
+
+        String::from_str(env, core::str::from_utf8(&buf[pos..]).unwrap_or("0"))
+    }
+}
+
+#[cfg(test)]
+mod test {
+    extern crate std;
+
+    use super::*;
+    use soroban_sdk::testutils::{Address as _, Ledger as _};
+    use soroban_sdk::Env;
+
+    /// Helper to set up a contract with a board for testing
+    fn setup_with_board(env: &Env) -> (BoardsBoardClient, u64, Address) {
+        env.mock_all_auths();
+
+        let contract_id = env.register(BoardsBoard, ());
+        let client = BoardsBoardClient::new(env, &contract_id);
+
+        let registry = Address::generate(env);
+
+        // Initialize contract (no permissions for simpler testing)
+        client.init(&registry, &None, &None, &None);
+
+        // Create a board
+        let name = String::from_str(env, "General");
+        let desc = String::from_str(env, "General discussion");
+        let is_private = String::from_str(env, "false");
+        let is_listed = String::from_str(env, "true");
+        let caller = Address::generate(env);
+
+        let board_id = client.create_board(&name, &desc, &is_private, &is_listed, &caller);
+
+        (client, board_id, caller)
+    }
+
+    #[test]
+    fn test_init_and_create_board() {
+        let env = Env::default();
+        env.mock_all_auths();
+
+        let contract_id = env.register(BoardsBoard, ());
+        let client = BoardsBoardClient::new(&env, &contract_id);
+
+        let registry = Address::generate(&env);
+        client.init(&registry, &None, &None, &None);
+
+        assert_eq!(client.board_count(), 0);
+
+        let name = String::from_str(&env, "General");
+        let desc = String::from_str(&env, "General discussion");
+        let is_private = String::from_str(&env, "false");
+        let is_listed = String::from_str(&env, "true");
+        let caller = Address::generate(&env);
+
+        let board_id = client.create_board(&name, &desc, &is_private, &is_listed, &caller);
+        assert_eq!(board_id, 0);
+        assert_eq!(client.board_count(), 1);
+
+        let board = client.get_board(&board_id).unwrap();
+        assert_eq!(board.name, name.clone());
+        assert_eq!(
+            client.list_board_names(&0, &10),
+            Vec::from_array(&env, [(board_id, name)])
+        );
+    }
+
+    #[test]
+    fn test_archive_and_unarchive_board() {
+        let env = Env::default();
+        let (client, board_id, owner) = setup_with_board(&env);
+
+        client.archive_board(&board_id, &owner);
+        assert!(client.is_archived(&board_id));
+        assert_eq!(
+            client.get_archived_boards(&Vec::from_array(&env, [board_id, board_id + 1])),
+            Vec::from_array(&env, [board_id])
+        );
+        assert!(client.is_readonly(&board_id));
+        assert!(!client.get_board_listed(&board_id));
+        assert_eq!(client.list_listed_boards(&0, &10).len(), 0);
+        assert_eq!(
+            client.get_board_archive(&board_id).unwrap().archived_by,
+            owner
+        );
+
+        // Unarchiving restores the board's previous settings
+        client.unarchive_board(&board_id, &owner);
+        assert!(!client.is_archived(&board_id));
+        assert!(!client.is_readonly(&board_id));
+        assert!(client.get_board_listed(&board_id));
+        assert_eq!(client.list_listed_boards(&0, &10).len(), 1);
+    }
+
+    fn setup_featured(env: &Env) -> (BoardsBoardClient<'_>, Address, Address) {
+        env.mock_all_auths();
+        let registry = MockRegistryClient::new(env, &env.register(MockRegistry, ()));
+        let admin = Address::generate(env);
+        registry.set_admin(&admin);
+        let client = BoardsBoardClient::new(env, &env.register(BoardsBoard, ()));
+        client.init(&registry.address, &None, &None, &None);
+
+        let owner = Address::generate(env);
+        let name = String::from_str(env, "Board");
+        let empty = String::from_str(env, "");
+        for _ in 0..(MAX_FEATURED_BOARDS + 1) {
+            client.create_board(&name, &empty, &empty, &empty, &owner);
+        }
+        (client, admin, owner)
+    }
+
+    #[test]
+    fn test_featured_boards_cap_and_order() {
+        let env = Env::default();
+        let (client, admin, owner) = setup_featured(&env);
+
+        for board_id in 0..MAX_FEATURED_BOARDS as u64 {
+            client.set_featured(&board_id, &true, &admin);
+        }
+        let result = client.try_set_featured(&(MAX_FEATURED_BOARDS as u64), &true, &admin);
+        assert!(result.is_err());
+        assert_eq!(client.list_featured().len(), MAX_FEATURED_BOARDS);
+
+        // Reordering and unfeaturing free up the list
+        client.move_featured(&3, &0, &admin);
+        assert_eq!(client.list_featured().get_unchecked(0).id, 3);
+        assert_eq!(client.list_featured().get_unchecked(1).id, 0);
+        client.set_featured(&3, &false, &admin);
+        assert!(!client.is_featured(&3));
+        client.set_featured(&(MAX_FEATURED_BOARDS as u64), &true, &admin);
+
+        // Board owners aren't registry admins
+        assert!(client.try_set_featured(&3, &true, &owner).is_err());
+    }
+
+    #[test]
+    fn test_unlisted_or_archived_boards_leave_featured_list() {
+        let env = Env::default();
+        let (client, admin, owner) = setup_featured(&env);
+        for board_id in 0..3u64 {
+            client.set_featured(&board_id, &true, &admin);
+        }
+
+        client.set_board_listed(&0, &false, &owner);
+        client.archive_board(&1, &owner);
+        assert!(!client.is_featured(&0));
+        assert!(!client.is_featured(&1));
+        assert_eq!(client.list_featured().len(), 1);
+
+        // They don't come back on their own, and can't be featured while hidden
+        client.set_board_listed(&0, &true, &owner);
+        assert!(!client.is_featured(&0));
+        assert!(client.try_set_featured(&1, &true, &admin).is_err());
+    }
+
+    #[test]
+    fn test_daily_stats_buckets_and_range() {
+        let env = Env::default();
+        let (client, board_id, caller) = setup_with_board(&env);
+        let title = String::from_str(&env, "Hello");
+        let day = 19_700u64;
+
+        // Two threads and a reply at different times on the same day share a bucket
+        env.ledger().set_timestamp(day * 86_400 + 10);
+        let thread_id = client.create_thread(&board_id, &title, &None, &caller);
+        env.ledger().set_timestamp(day * 86_400 + 86_399);
+        client.create_thread(&board_id, &title, &None, &caller);
+        client.increment_reply_count(&board_id, &thread_id);
+
+        // The next day starts a fresh bucket
+        env.ledger().set_timestamp((day + 1) * 86_400);
+        client.increment_reply_count(&board_id, &thread_id);
+
+        let stats = client.get_stats_range(&board_id, &(day - 1), &(day + 1));
+        assert_eq!(stats.len(), 3);
+        assert_eq!(stats.get_unchecked(0), DayStats::default());
+        assert_eq!(
+            stats.get_unchecked(1),
+            DayStats {
+                threads: 2,
+                replies: 1
+            }
+        );
+        assert_eq!(
+            stats.get_unchecked(2),
+            DayStats {
+                threads: 0,
+                replies: 1
+            }
+        );
+
+        // Future days are cut off, long ranges keep their most recent days
+        assert_eq!(
+            client.get_stats_range(&board_id, &day, &(day + 50)).len(),
+            2
+        );
+        let long = client.get_stats_range(&board_id, &0, &(day + 1));
+        assert_eq!(long.len(), MAX_STATS_RANGE_DAYS as u32);
+        assert_eq!(long.get_unchecked(long.len() - 1).replies, 1);
+        assert_eq!(client.get_stats_range(&board_id, &(day + 1), &day).len(), 0);
+    }
+
+    #[test]
+    fn test_registry_creation_limits_block_board_creation() {
+        let env = Env::default();
+        env.mock_all_auths();
+
+        let registry = MockRegistryClient::new(&env, &env.register(MockRegistry, ()));
+        let client = BoardsBoardClient::new(&env, &env.register(BoardsBoard, ()));
+        client.init(&registry.address, &None, &None, &None);
+        let name = String::from_str(&env, "Spam");
+        let empty = String::from_str(&env, "");
+        let caller = Address::generate(&env);
+
+        client.create_board(&name, &empty, &empty, &empty, &caller);
+
+        // The registry's error code (here: cap reached) reaches the caller unchanged
+        registry.set_creation_error(&1);
+        let result = client.try_create_board(&name, &empty, &empty, &empty, &caller);
+        assert_eq!(
+            result.err(),
+            Some(Ok(soroban_sdk::Error::from_contract_error(1)))
+        );
+        assert_eq!(client.board_count(), 1);
+    }
+
+    #[test]
+    fn test_registry_name_rules_and_rename() {
+        let env = Env::default();
+        env.mock_all_auths();
+
+        let registry = MockRegistryClient::new(&env, &env.register(MockRegistry, ()));
+        let client = BoardsBoardClient::new(&env, &env.register(BoardsBoard, ()));
+        client.init(&registry.address, &None, &None, &None);
+        let empty = String::from_str(&env, "");
+        let caller = Address::generate(&env);
+
+        // A rejected name (here: reserved) keeps its registry error code
+        registry.set_name_error(&4);
+        let result = client.try_create_board(
+            &String::from_str(&env, "admin"),
+            &empty,
+            &empty,
+            &empty,
+            &caller,
+        );
+        assert_eq!(
+            result.err(),
+            Some(Ok(soroban_sdk::Error::from_contract_error(4)))
+        );
+        assert_eq!(client.board_count(), 0);
+
+        registry.set_name_error(&0);
+        let board_id = client.create_board(
+            &String::from_str(&env, "General"),
+            &empty,
+            &empty,
+            &empty,
+            &caller,
+        );
+
+        // Only the registry can change the name, in both metadata and config
+        let lobby = String::from_str(&env, "Lobby");
+        assert!(client
+            .try_set_board_name(&board_id, &lobby, &caller)
+            .is_err());
+        client.set_board_name(&board_id, &lobby, &registry.address);
+        assert_eq!(client.get_board(&board_id).unwrap().name, lobby);
+        assert_eq!(client.get_config(&board_id).name, lobby);
+    }
+
+    #[test]
+    fn test_list_boards_by_filter() {
+        let env = Env::default();
+        env.mock_all_auths();
+
+        let registry = MockRegistryClient::new(&env, &env.register(MockRegistry, ()));
+        let community = Address::generate(&env);
+        registry.set_community(&community);
+        let client = BoardsBoardClient::new(&env, &env.register(BoardsBoard, ()));
+        client.init(&registry.address, &None, &None, &None);
+
+        let owner = Address::generate(&env);
+        for (is_private, is_listed) in [
+            ("false", "true"),
+            ("false", "false"),
+            ("true", "true"),
+            ("false", "true"),
+            ("false", "true"),
+        ] {
+            client.create_board(
+                &String::from_str(&env, "Board"),
+                &String::from_str(&env, ""),
+                &String::from_str(&env, is_private),
+                &String::from_str(&env, is_listed),
+                &owner,
+            );
+        }
+        client.set_board_community_id(&3, &7, &community);
+        client.archive_board(&4, &owner);
+
+        let ids = |start: u64, limit: u64, filter: BoardFilter| {
+            let mut ids = std::vec::Vec::new();
+            for board in client.list_boards_by_filter(&start, &limit, &filter).iter() {
+                ids.push(board.id);
+            }
+            ids
+        };
+        assert_eq!(ids(0, 10, BoardFilter::Listed), [3, 2, 0]);
+        assert_eq!(ids(0, 10, BoardFilter::Unlisted), [4, 1]);
+        assert_eq!(ids(0, 10, BoardFilter::Private), [2]);
+        assert_eq!(ids(0, 10, BoardFilter::InCommunity(7)), [3]);
+        assert_eq!(ids(0, 10, BoardFilter::Archived), [4]);
+        assert_eq!(client.count_boards_by_filter(&BoardFilter::Standalone), 4);
+
+        // Page boundaries
+        assert_eq!(ids(0, 2, BoardFilter::Standalone), [4, 2]);
+        assert_eq!(ids(2, 2, BoardFilter::Standalone), [1, 0]);
+        assert!(ids(4, 2, BoardFilter::Standalone).is_empty());
+        assert!(ids(10, 2, BoardFilter::Standalone).is_empty());
+
+        // Leaving the community and unarchiving move the boards back
+        client.clear_board_community_slug(&3, &community);
+        client.unarchive_board(&4, &owner);
+        assert_eq!(
+            client.count_boards_by_filter(&BoardFilter::InCommunity(7)),
+            0
+        );
+        assert_eq!(client.count_boards_by_filter(&BoardFilter::Archived), 0);
+        assert_eq!(ids(0, 10, BoardFilter::Standalone), [4, 3, 2, 1, 0]);
+        assert_eq!(ids(0, 10, BoardFilter::Listed), [4, 3, 2, 0]);
+    }
+
+    #[test]
+    fn test_board_index_backfills_older_boards_and_spans_chunks() {
+        let env = Env::default();
+        env.mock_all_auths();
+
+        let registry = MockRegistryClient::new(&env, &env.register(MockRegistry, ()));
+        let admin = Address::generate(&env);
+        registry.set_admin(&admin);
+        let contract_id = env.register(BoardsBoard, ());
+        let client = BoardsBoardClient::new(&env, &contract_id);
+        client.init(&registry.address, &None, &None, &None);
+
+        let owner = Address::generate(&env);
+        let create = |is_listed: &str| {
+            client.create_board(
+                &String::from_str(&env, "Board"),
+                &String::from_str(&env, ""),
+                &String::from_str(&env, "false"),
+                &String::from_str(&env, is_listed),
+                &owner,
+            )
+        };
+        for is_listed in ["true", "false", "true"] {
+            create(is_listed);
+        }
+
+        // Roll the indexes back to how a deployment from before them looks
+        env.as_contract(&contract_id, || {
+            let storage = env.storage();
+            storage.instance().remove(&BoardKey::BoardsIndexedFrom);
+            for filter in [
+                BoardFilter::Listed,
+                BoardFilter::Unlisted,
+                BoardFilter::Standalone,
+            ] {
+                storage
+                    .persistent()
+                    .remove(&BoardKey::BoardIndexChunks(filter.clone()));
+                storage
+                    .persistent()
+                    .remove(&BoardKey::BoardIndex(filter, 0));
+            }
+        });
+        create("true");
+
+        let ids = |start: u64, limit: u64, filter: BoardFilter| {
+            let mut ids = std::vec::Vec::new();
+            for board in client.list_boards_by_filter(&start, &limit, &filter).iter() {
+                ids.push(board.id);
+            }
+            ids
+        };
+
+        // Older boards are listed before the backfill, but not yet counted
+        assert_eq!(ids(0, 10, BoardFilter::Listed), [3, 2, 0]);
+        assert_eq!(ids(1, 1, BoardFilter::Listed), [2]);
+        assert_eq!(client.count_boards_by_filter(&BoardFilter::Listed), 1);
+
+        let result = client.try_migrate_index_boards(&2, &owner);
+        assert!(result.is_err());
+        assert_eq!(client.migrate_index_boards(&2, &admin), 1);
+        assert_eq!(client.count_boards_by_filter(&BoardFilter::Listed), 2);
+        assert_eq!(ids(0, 10, BoardFilter::Listed), [3, 2, 0]);
+        assert_eq!(client.migrate_index_boards(&2, &admin), 0);
+        assert_eq!(client.count_boards_by_filter(&BoardFilter::Listed), 3);
+        assert_eq!(client.count_boards_by_filter(&BoardFilter::Unlisted), 1);
+        assert_eq!(ids(0, 10, BoardFilter::Listed), [3, 2, 0]);
+
+        // Pages read from the newest chunk down into older ones
+        while client.board_count() < 70 {
+            create("true");
+        }
+        assert_eq!(client.count_boards_by_filter(&BoardFilter::Standalone), 70);
+        assert_eq!(ids(0, 3, BoardFilter::Standalone), [69, 68, 67]);
+        assert_eq!(ids(5, 3, BoardFilter::Standalone), [64, 63, 62]);
+        assert_eq!(ids(68, 5, BoardFilter::Standalone), [1, 0]);
+    }
+
+    #[test]
+    #[should_panic(expected = "Board is archived")]
+    fn test_archived_board_cannot_be_relisted() {
+        let env = Env::default();
+        let (client, board_id, owner) = setup_with_board(&env);
+
+        client.archive_board(&board_id, &owner);
+        client.set_board_listed(&board_id, &true, &owner);
+    }
+
+    /// Stands in for the content contract when reporting thread flags
+    #[contract]
+    pub struct MockContent;
+
+    #[contractimpl]
+    impl MockContent {
+        pub fn report_flags(env: Env, board: Address, board_id: u64, thread_id: u64, count: u32) {
+            BoardsBoardClient::new(&env, &board)
+                .notify_thread_flagged(&board_id, &thread_id, &count);
+        }
+
+        pub fn clear_flags(env: Env, board: Address, board_id: u64, thread_id: u64) {
+            BoardsBoardClient::new(&env, &board).reset_thread_auto_hide(&board_id, &thread_id);
+        }
+
+        pub fn retract_crosspost(env: Env, board: Address, board_id: u64, thread_id: u64) {
+            BoardsBoardClient::new(&env, &board).remove_crosspost_thread(&board_id, &thread_id);
+        }
+
+        pub fn add_reply(env: Env, reply: ReplyMeta, body: String) {
+            let key = (reply.board_id, reply.thread_id, reply.id);
+            env.storage()
+                .instance()
+                .set(&(Symbol::new(&env, "body"), key), &body);
+            env.storage()
+                .instance()
+                .set(&(Symbol::new(&env, "reply"), key), &reply);
+
+            if reply.depth == 0 {
+                let count_key = (Symbol::new(&env, "count"), reply.board_id, reply.thread_id);
+                let count: u64 = env.storage().instance().get(&count_key).unwrap_or(0);
+                env.storage().instance().set(&count_key, &(count + 1));
+            } else {
+                let kids_key = (Symbol::new(&env, "kids"), key.0, key.1, reply.parent_id);
+                let mut kids: Vec<u64> = env
+                    .storage()
+                    .instance()
+                    .get(&kids_key)
+                    .unwrap_or(Vec::new(&env));
+                kids.push_back(reply.id);
+                env.storage().instance().set(&kids_key, &kids);
+            }
+
+            let by_key = (
+                Symbol::new(&env, "by"),
+                reply.board_id,
+                reply.creator.clone(),
+            );
+            let mut by_user: Vec<ReplyMeta> = env
+                .storage()
+                .instance()
+                .get(&by_key)
+                .unwrap_or(Vec::new(&env));
+            by_user.push_back(reply);
+            env.storage().instance().set(&by_key, &by_user);
+        }
+
+        pub fn list_user_replies(
+            env: Env,
+            board_id: u64,
+            user: Address,
+            limit: u32,
+        ) -> Vec<ReplyMeta> {
+            let by_user: Vec<ReplyMeta> = env
+                .storage()
+                .instance()
+                .get(&(Symbol::new(&env, "by"), board_id, user))
+                .unwrap_or(Vec::new(&env));
+            let mut replies = Vec::new(&env);
+            for reply in by_user.iter().rev().take(limit as usize) {
+                replies.push_back(reply);
+            }
+            replies
+        }
+
+        pub fn get_reply(
+            env: Env,
+            board_id: u64,
+            thread_id: u64,
+            reply_id: u64,
+        ) -> Option<ReplyMeta> {
+            let key = (board_id, thread_id, reply_id);
+            env.storage()
+                .instance()
+                .get(&(Symbol::new(&env, "reply"), key))
+        }
+
+        pub fn get_reply_content(env: Env, board_id: u64, thread_id: u64, reply_id: u64) -> Bytes {
+            let key = (board_id, thread_id, reply_id);
+            let body: Option<String> = env
+                .storage()
+                .instance()
+                .get(&(Symbol::new(&env, "body"), key));
+            match body {
+                Some(body) => soroban_render_sdk::bytes::string_to_bytes(&env, &body),
+                None => Bytes::new(&env),
+            }
+        }
+
+        pub fn set_thread_flag_count(env: Env, thread_id: u64, count: u32) {
+            env.storage()
+                .instance()
+                .set(&(Symbol::new(&env, "flags"), thread_id), &count);
+        }
+
+        pub fn get_thread_flag_counts(env: Env, _board_id: u64, thread_ids: Vec<u64>) -> Vec<u32> {
+            let calls: u32 = env
+                .storage()
+                .instance()
+                .get(&Symbol::new(&env, "flag_calls"))
+                .unwrap_or(0);
+            env.storage()
+                .instance()
+                .set(&Symbol::new(&env, "flag_calls"), &(calls + 1));
+            let mut counts = Vec::new(&env);
+            for thread_id in thread_ids.iter() {
+                counts.push_back(
+                    env.storage()
+                        .instance()
+                        .get(&(Symbol::new(&env, "flags"), thread_id))
+                        .unwrap_or(0),
+                );
+            }
+            counts
+        }
+
+        pub fn flag_count_calls(env: Env) -> u32 {
+            env.storage()
+                .instance()
+                .get(&Symbol::new(&env, "flag_calls"))
+                .unwrap_or(0)
+        }
+
+        pub fn set_edited(env: Env, thread_id: u64, reply_id: u64) {
+            env.storage()
+                .instance()
+                .set(&(Symbol::new(&env, "edited"), thread_id, reply_id), &true);
+        }
+
+        pub fn get_edited_replies(
+            env: Env,
+            _board_id: u64,
+            thread_id: u64,
+            reply_ids: Vec<u64>,
+        ) -> Vec<u64> {
+            let calls: u32 = env
+                .storage()
+                .instance()
+                .get(&Symbol::new(&env, "edit_calls"))
+                .unwrap_or(0);
+            env.storage()
+                .instance()
+                .set(&Symbol::new(&env, "edit_calls"), &(calls + 1));
+            let mut edited = Vec::new(&env);
+            for reply_id in reply_ids.iter() {
+                if env
+                    .storage()
+                    .instance()
+                    .has(&(Symbol::new(&env, "edited"), thread_id, reply_id))
+                {
+                    edited.push_back(reply_id);
+                }
+            }
+            edited
+        }
+
+        pub fn edited_calls(env: Env) -> u32 {
+            env.storage()
+                .instance()
+                .get(&Symbol::new(&env, "edit_calls"))
+                .unwrap_or(0)
+        }
+
+        pub fn set_distinguished(env: Env, thread_id: u64, reply_id: Option<u64>) {
+            env.storage()
+                .instance()
+                .set(&(Symbol::new(&env, "mod_mark"), thread_id, reply_id), &true);
+        }
+
+        pub fn is_distinguished(
+            env: Env,
+            _board_id: u64,
+            thread_id: u64,
+            reply_id: Option<u64>,
+        ) -> bool {
+            env.storage()
+                .instance()
+                .has(&(Symbol::new(&env, "mod_mark"), thread_id, reply_id))
+        }
+
+        pub fn get_distinguished(
+            env: Env,
+            _board_id: u64,
+            thread_id: u64,
+            reply_ids: Vec<u64>,
+        ) -> Vec<u64> {
+            let calls: u32 = env
+                .storage()
+                .instance()
+                .get(&Symbol::new(&env, "mark_calls"))
+                .unwrap_or(0);
+            env.storage()
+                .instance()
+                .set(&Symbol::new(&env, "mark_calls"), &(calls + 1));
+            let mut distinguished = Vec::new(&env);
+            for reply_id in reply_ids.iter() {
+                if env.storage().instance().has(&(
+                    Symbol::new(&env, "mod_mark"),
+                    thread_id,
+                    Some(reply_id),
+                )) {
+                    distinguished.push_back(reply_id);
+                }
+            }
+            distinguished
+        }
+
+        pub fn distinguished_calls(env: Env) -> u32 {
+            env.storage()
+                .instance()
+                .get(&Symbol::new(&env, "mark_calls"))
+                .unwrap_or(0)
+        }
+
+        pub fn set_last_reply_at(env: Env, thread_id: u64, user: Address, at: u64) {
+            env.storage()
+                .instance()
+                .set(&(Symbol::new(&env, "last_reply"), thread_id, user), &at);
+        }
+
+        pub fn get_last_reply_at(
+            env: Env,
+            _board_id: u64,
+            thread_id: u64,
+            user: Address,
+        ) -> Option<u64> {
+            env.storage()
+                .instance()
+                .get(&(Symbol::new(&env, "last_reply"), thread_id, user))
+        }
+
+        pub fn set_thread_body(env: Env, body: String) {
+            env.storage()
+                .instance()
+                .set(&Symbol::new(&env, "thread_body"), &body);
+        }
+
+        pub fn get_thread_body(env: Env, _board_id: u64, _thread_id: u64) -> Bytes {
+            let body: Option<String> = env
+                .storage()
+                .instance()
+                .get(&Symbol::new(&env, "thread_body"));
+            match body {
+                Some(body) => soroban_render_sdk::bytes::string_to_bytes(&env, &body),
+                None => Bytes::new(&env),
+            }
+        }
+
+        pub fn list_top_level_replies(
+            env: Env,
+            board_id: u64,
+            thread_id: u64,
+            start: u32,
+            limit: u32,
+        ) -> Vec<ReplyMeta> {
+            let mut replies = Vec::new(&env);
+            for id in start..start + limit {
+                let key = (board_id, thread_id, id as u64);
+                let reply: Option<ReplyMeta> = env
+                    .storage()
+                    .instance()
+                    .get(&(Symbol::new(&env, "reply"), key));
+                if let Some(reply) = reply.filter(|r| r.depth == 0) {
+                    replies.push_back(reply);
+                }
+            }
+            replies
+        }
+
+        pub fn list_top_level_replies_sorted(
+            env: Env,
+            board_id: u64,
+            thread_id: u64,
+            start: u32,
+            limit: u32,
+            sort: ReplySort,
+        ) -> Vec<ReplyMeta> {
+            let count = Self::get_reply_count(env.clone(), board_id, thread_id) as u32;
+            let all = Self::list_top_level_replies(env.clone(), board_id, thread_id, 0, count);
+            let mut replies = Vec::new(&env);
+            for i in start..(start + limit).min(all.len()) {
+                let idx = match sort {
+                    ReplySort::Newest => all.len() - 1 - i,
+                    _ => i,
+                };
+                replies.push_back(all.get(idx).unwrap());
+            }
+            replies
+        }
+
+        pub fn get_reply_count(env: Env, board_id: u64, thread_id: u64) -> u64 {
+            env.storage()
+                .instance()
+                .get(&(Symbol::new(&env, "count"), board_id, thread_id))
+                .unwrap_or(0)
+        }
+
+        pub fn get_reply_contents(
+            env: Env,
+            board_id: u64,
+            thread_id: u64,
+            reply_ids: Vec<u64>,
+        ) -> Vec<Bytes> {
+            let mut bodies = Vec::new(&env);
+            for id in reply_ids.iter() {
+                bodies.push_back(Self::get_reply_content(
+                    env.clone(),
+                    board_id,
+                    thread_id,
+                    id,
+                ));
+            }
+            bodies
+        }
+
+        pub fn get_children_counts(
+            env: Env,
+            board_id: u64,
+            thread_id: u64,
+            ids: Vec<u64>,
+        ) -> Vec<u32> {
+            let mut counts = Vec::new(&env);
+            for id in ids.iter() {
+                counts.push_back(Self::get_children_count(
+                    env.clone(),
+                    board_id,
+                    thread_id,
+                    id,
+                ));
+            }
+            counts
+        }
+
+        pub fn get_children_count(env: Env, board_id: u64, thread_id: u64, id: u64) -> u32 {
+            let kids_key = (Symbol::new(&env, "kids"), board_id, thread_id, id);
+            let kids: Vec<u64> = env
+                .storage()
+                .instance()
+                .get(&kids_key)
+                .unwrap_or(Vec::new(&env));
+            kids.len()
+        }
+
+        pub fn list_children_replies(
+            env: Env,
+            board_id: u64,
+            thread_id: u64,
+            parent_id: u64,
+            start: u32,
+            limit: u32,
+        ) -> Vec<ReplyMeta> {
+            let kids_key = (Symbol::new(&env, "kids"), board_id, thread_id, parent_id);
+            let kids: Vec<u64> = env
+                .storage()
+                .instance()
+                .get(&kids_key)
+                .unwrap_or(Vec::new(&env));
+            let mut replies = Vec::new(&env);
+            for i in start..(start + limit).min(kids.len()) {
+                let key = (board_id, thread_id, kids.get(i).unwrap());
+                let reply: Option<ReplyMeta> = env
+                    .storage()
+                    .instance()
+                    .get(&(Symbol::new(&env, "reply"), key));
+                if let Some(reply) = reply {
+                    replies.push_back(reply);
+                }
+            }
+            replies
+        }
+
+        pub fn add_notification(env: Env, user: Address, notification: Notification) {
+            let key = (Symbol::new(&env, "inbox"), user);
+            let mut inbox: Vec<Notification> =
+                env.storage().instance().get(&key).unwrap_or(Vec::new(&env));
+            inbox.push_front(notification);
+            env.storage().instance().set(&key, &inbox);
+        }
+
+        pub fn list_notifications(
+            env: Env,
+            user: Address,
+            _start: u32,
+            _limit: u32,
+        ) -> Vec<Notification> {
+            env.storage()
+                .instance()
+                .get(&(Symbol::new(&env, "inbox"), user))
+                .unwrap_or(Vec::new(&env))
+        }
+
+        /// Everything in the mock inbox counts as unread
+        pub fn get_unread_count(env: Env, user: Address) -> u32 {
+            Self::list_notifications(env, user, 0, 0).len()
+        }
+
+        pub fn save_draft(env: Env, board_id: u64, draft: Draft, user: Address) {
+            let key = (
+                Symbol::new(&env, "draft"),
+                board_id,
+                draft.key.clone(),
+                user,
+            );
+            env.storage().instance().set(&key, &draft);
+        }
+
+        pub fn get_draft(env: Env, board_id: u64, key: Symbol, user: Address) -> Option<Draft> {
+            env.storage()
+                .instance()
+                .get(&(Symbol::new(&env, "draft"), board_id, key, user))
+        }
+
+        pub fn add_crosspost(env: Env, location: CrosspostLocation, xpost: CrosspostRef) {
+            let list_key = (
+                Symbol::new(&env, "xposts"),
+                xpost.original_board_id,
+                xpost.original_thread_id,
+            );
+            let mut list: Vec<CrosspostLocation> = env
+                .storage()
+                .instance()
+                .get(&list_key)
+                .unwrap_or(Vec::new(&env));
+            list.push_back(location.clone());
+            env.storage().instance().set(&list_key, &list);
+            env.storage().instance().set(
+                &(
+                    Symbol::new(&env, "xref"),
+                    location.board_id,
+                    location.thread_id,
+                ),
+                &xpost,
+            );
+        }
+
+        pub fn list_crossposts(env: Env, board_id: u64, thread_id: u64) -> Vec<CrosspostLocation> {
+            env.storage()
+                .instance()
+                .get(&(Symbol::new(&env, "xposts"), board_id, thread_id))
+                .unwrap_or(Vec::new(&env))
+        }
+
+        pub fn get_crosspost_ref(env: Env, board_id: u64, thread_id: u64) -> Option<CrosspostRef> {
+            env.storage()
+                .instance()
+                .get(&(Symbol::new(&env, "xref"), board_id, thread_id))
+        }
+    }
+
+    /// Registry stub; named contracts (such as profile) resolve to None unless set
+    #[contract]
+    pub struct MockRegistry;
+
+    #[contractimpl]
+    impl MockRegistry {
+        pub fn set_contract(env: Env, name: Symbol, address: Address) {
+            env.storage()
+                .instance()
+                .set(&(Symbol::new(&env, "contract"), name), &address);
+        }
+
+        pub fn get_contract(env: Env, name: Symbol) -> Option<Address> {
+            env.storage()
+                .instance()
+                .get(&(Symbol::new(&env, "contract"), name))
+        }
+
+        pub fn set_community(env: Env, community: Address) {
+            env.storage()
+                .instance()
+                .set(&Symbol::new(&env, "community"), &community);
+        }
+
+        pub fn get_contract_by_alias(env: Env, alias: Symbol) -> Option<Address> {
+            env.storage().instance().get(&alias)
+        }
+
+        pub fn register_theme(env: Env, name: String, address: Address) {
+            env.storage().instance().set(&name, &address);
+        }
+
+        pub fn unregister_theme(env: Env, name: String) {
+            env.storage().instance().remove(&name);
+        }
+
+        pub fn get_theme(env: Env, name: String) -> Option<Address> {
+            env.storage().instance().get(&name)
+        }
+
+        pub fn set_admin(env: Env, admin: Address) {
+            env.storage()
+                .instance()
+                .set(&Symbol::new(&env, "admin"), &admin);
+        }
+
+        pub fn is_admin(env: Env, address: Address) -> bool {
+            env.storage()
+                .instance()
+                .get::<_, Address>(&Symbol::new(&env, "admin"))
+                == Some(address)
+        }
+
+        pub fn set_creation_error(env: Env, code: u32) {
+            env.storage()
+                .instance()
+                .set(&Symbol::new(&env, "creation_error"), &code);
+        }
+
+        pub fn record_board_creation(env: Env, _board_id: u64, _creator: Address) {
+            let code: u32 = env
+                .storage()
+                .instance()
+                .get(&Symbol::new(&env, "creation_error"))
+                .unwrap_or(0);
+            if code > 0 {
+                panic_with_error!(&env, soroban_sdk::Error::from_contract_error(code));
+            }
+        }
+
+        pub fn set_name_error(env: Env, code: u32) {
+            env.storage()
+                .instance()
+                .set(&Symbol::new(&env, "name_error"), &code);
+        }
+
+        pub fn register_board_name(env: Env, _board_id: u64, _name: String) {
+            let code: u32 = env
+                .storage()
+                .instance()
+                .get(&Symbol::new(&env, "name_error"))
+                .unwrap_or(0);
+            if code > 0 {
+                panic_with_error!(&env, soroban_sdk::Error::from_contract_error(code));
+            }
+        }
+    }
+
+    /// Role stub: every viewer is a plain member unless made a guest or moderator.
+    /// Counts lookups so tests can check how often a render queries it.
+    #[contract]
+    pub struct MockPermissions;
+
+    #[contractimpl]
+    impl MockPermissions {
+        pub fn get_role(env: Env, _board_id: u64, user: Address) -> Role {
+            Self::count_call(&env);
+            let guest_key = (Symbol::new(&env, "guest"), user.clone());
+            if env.storage().instance().get(&guest_key).unwrap_or(false) {
+                Role::Guest
+            } else if env.storage().instance().get(&user).unwrap_or(false) {
+                Role::Moderator
+            } else {
+                Role::Member
+            }
+        }
+
+        pub fn make_moderator(env: Env, user: Address) {
+            env.storage().instance().set(&user, &true);
+        }
+
+        pub fn make_guest(env: Env, user: Address) {
+            let key = (Symbol::new(&env, "guest"), user);
+            env.storage().instance().set(&key, &true);
+        }
+
+        pub fn can_moderate(env: Env, _board_id: u64, user: Address) -> bool {
+            Self::count_call(&env);
+            env.storage().instance().get(&user).unwrap_or(false)
+        }
+
+        pub fn make_admin(env: Env, user: Address) {
+            let key = (Symbol::new(&env, "admin"), user);
+            env.storage().instance().set(&key, &true);
+        }
+
+        pub fn can_admin(env: Env, _board_id: u64, user: Address) -> bool {
+            let key = (Symbol::new(&env, "admin"), user);
+            env.storage().instance().get(&key).unwrap_or(false)
+        }
+
+        pub fn get_permissions(env: Env, board_id: u64, user: Address) -> PermissionSet {
+            let role = Self::get_role(env, board_id, user);
+            PermissionSet {
+                role,
+                can_view: true,
+                can_post: true,
+                can_moderate: role == Role::Moderator,
+                can_admin: false,
+                is_banned: false,
+            }
+        }
+
+        pub fn call_count(env: Env) -> u32 {
+            env.storage()
+                .instance()
+                .get(&Symbol::new(&env, "calls"))
+                .unwrap_or(0)
+        }
+
+        fn count_call(env: &Env) {
+            let calls = Self::call_count(env.clone());
+            env.storage()
+                .instance()
+                .set(&Symbol::new(env, "calls"), &(calls + 1));
+        }
+    }
+
+    /// Admin stub serving a settable moderation log, newest first
+    #[contract]
+    pub struct MockAdmin;
+
+    #[contractimpl]
+    impl MockAdmin {
+        pub fn set_mod_actions(env: Env, actions: Vec<ModAction>) {
+            env.storage()
+                .instance()
+                .set(&Symbol::new(&env, "modlog"), &actions);
+        }
+
+        pub fn get_mod_action_count(env: Env, _board_id: u64) -> u64 {
+            Self::actions(&env).len() as u64
+        }
+
+        pub fn list_mod_actions(
+            env: Env,
+            _board_id: u64,
+            start: u64,
+            limit: u64,
+        ) -> Vec<ModAction> {
+            let actions = Self::actions(&env);
+            let start = (start as u32).min(actions.len());
+            let end = (start + limit as u32).min(actions.len());
+            actions.slice(start..end)
+        }
+
+        fn actions(env: &Env) -> Vec<ModAction> {
+            env.storage()
+                .instance()
+                .get(&Symbol::new(env, "modlog"))
+                .unwrap_or(Vec::new(env))
+        }
+    }
+
+    /// Voting stub with settable total karma and thread scores
+    #[contract]
+    pub struct MockVoting;
+
+    #[contractimpl]
+    impl MockVoting {
+        pub fn set_karma(env: Env, user: Address, karma: i64) {
+            env.storage().instance().set(&user, &karma);
+        }
+
+        pub fn get_total_karma(env: Env, user: Address) -> i64 {
+            env.storage().instance().get(&user).unwrap_or(0)
+        }
+
+        pub fn get_board_karma(env: Env, _board_id: u64, user: Address) -> i64 {
+            env.storage().instance().get(&user).unwrap_or(0)
+        }
+
+        pub fn set_karma_enabled(env: Env, enabled: bool) {
+            env.storage()
+                .instance()
+                .set(&Symbol::new(&env, "karma"), &enabled);
+        }
+
+        pub fn get_voting_config(env: Env, _board_id: u64) -> VotingConfig {
+            VotingConfig {
+                enabled: true,
+                allow_downvotes: true,
+                karma_enabled: env
+                    .storage()
+                    .instance()
+                    .get(&Symbol::new(&env, "karma"))
+                    .unwrap_or(true),
+                karma_multiplier: 1,
+            }
+        }
+
+        pub fn set_top_karma(env: Env, top: Vec<(Address, i64)>) {
+            env.storage()
+                .instance()
+                .set(&Symbol::new(&env, "top"), &top);
+        }
+
+        pub fn top_karma(env: Env, _board_id: u64, _limit: u32) -> Vec<(Address, i64)> {
+            env.storage()
+                .instance()
+                .get(&Symbol::new(&env, "top"))
+                .unwrap_or(Vec::new(&env))
+        }
+
+        pub fn set_score(env: Env, thread_id: u64, score: i32) {
+            env.storage().instance().set(&thread_id, &score);
+        }
+
+        pub fn get_thread_tally(env: Env, _board_id: u64, thread_id: u64) -> VoteTally {
+            VoteTally {
+                upvotes: 0,
+                downvotes: 0,
+                score: env.storage().instance().get(&thread_id).unwrap_or(0),
+                first_vote_at: 0,
+            }
+        }
+
+        pub fn set_votes(env: Env, thread_id: u64, upvotes: u32, downvotes: u32) {
+            env.storage().instance().set(
+                &(thread_id, Symbol::new(&env, "votes")),
+                &(upvotes, downvotes),
+            );
+        }
+
+        pub fn get_thread_tallies(
+            env: Env,
+            _board_id: u64,
+            thread_ids: Vec<u64>,
+        ) -> Vec<VoteTally> {
+            let mut tallies = Vec::new(&env);
+            for thread_id in thread_ids.iter() {
+                let (upvotes, downvotes): (u32, u32) = env
+                    .storage()
+                    .instance()
+                    .get(&(thread_id, Symbol::new(&env, "votes")))
+                    .unwrap_or((0, 0));
+                tallies.push_back(VoteTally {
+                    upvotes,
+                    downvotes,
+                    score: upvotes as i32 - downvotes as i32,
+                    first_vote_at: 0,
+                });
+            }
+            tallies
+        }
+
+        pub fn set_reply_score(env: Env, id: u64, score: i32) {
+            env.storage()
+                .instance()
+                .set(&(Symbol::new(&env, "reply_score"), id), &score);
+        }
+
+        pub fn get_reply_tally(env: Env, _board_id: u64, _thread_id: u64, id: u64) -> VoteTally {
+            VoteTally {
+                upvotes: 0,
+                downvotes: 0,
+                score: env
+                    .storage()
+                    .instance()
+                    .get(&(Symbol::new(&env, "reply_score"), id))
+                    .unwrap_or(0),
+                first_vote_at: 0,
+            }
+        }
+
+        pub fn get_user_reply_vote(
+            _env: Env,
+            _board_id: u64,
+            _thread_id: u64,
+            _reply_id: u64,
+            _user: Address,
+        ) -> VoteDirection {
+            VoteDirection::None
+        }
+
+        /// Reactions on a reply, and whether the viewer set the first of them
+        pub fn set_reply_reactions(env: Env, id: u64, counts: Vec<(Symbol, u32)>, mine: bool) {
+            env.storage()
+                .instance()
+                .set(&(Symbol::new(&env, "reactions"), id), &(counts, mine));
+        }
+
+        pub fn get_reply_reactions(
+            env: Env,
+            _board_id: u64,
+            _thread_id: u64,
+            reply_ids: Vec<u64>,
+        ) -> Vec<Vec<(Symbol, u32)>> {
+            let calls_key = Symbol::new(&env, "reaction_calls");
+            let calls: u32 = env.storage().instance().get(&calls_key).unwrap_or(0);
+            env.storage().instance().set(&calls_key, &(calls + 1));
+
+            let mut result = Vec::new(&env);
+            for id in reply_ids.iter() {
+                let (counts, _): (Vec<(Symbol, u32)>, bool) = env
+                    .storage()
+                    .instance()
+                    .get(&(Symbol::new(&env, "reactions"), id))
+                    .unwrap_or((Vec::new(&env), false));
+                result.push_back(counts);
+            }
+            result
+        }
+
+        pub fn get_user_reply_reactions(
+            env: Env,
+            _board_id: u64,
+            _thread_id: u64,
+            reply_ids: Vec<u64>,
+            _user: Address,
+        ) -> Vec<Vec<Symbol>> {
+            let mut result = Vec::new(&env);
+            for id in reply_ids.iter() {
+                let mut mine = Vec::new(&env);
+                if let Some((counts, true)) = env
+                    .storage()
+                    .instance()
+                    .get::<_, (Vec<(Symbol, u32)>, bool)>(&(Symbol::new(&env, "reactions"), id))
+                {
+                    if let Some((emoji, _)) = counts.get(0) {
+                        mine.push_back(emoji);
+                    }
+                }
+                result.push_back(mine);
+            }
+            result
+        }
+
+        /// Number of batch reaction lookups, to check replies don't fetch their own
+        pub fn reaction_calls(env: Env) -> u32 {
+            env.storage()
+                .instance()
+                .get(&Symbol::new(&env, "reaction_calls"))
+                .unwrap_or(0)
+        }
+
+        pub fn set_poll(env: Env, poll: Poll) {
+            env.storage()
+                .instance()
+                .set(&Symbol::new(&env, "poll"), &poll);
+        }
+
+        pub fn get_poll(env: Env, _board_id: u64, _thread_id: u64) -> Option<Poll> {
+            env.storage().instance().get(&Symbol::new(&env, "poll"))
+        }
+    }
+
+    /// Profile stub that resolves registered usernames and counts the author
+    /// cards it renders
+    #[contract]
+    pub struct MockProfile;
+
+    #[contractimpl]
+    impl MockProfile {
+        pub fn register(env: Env, username: String, user: Address) {
+            env.storage().instance().set(&username, &user);
+        }
+
+        pub fn get_address_by_username(env: Env, username: String) -> Option<Address> {
+            env.storage().instance().get(&username)
+        }
+
+        /// Make the stub behave like a deployment without render_authors_batch
+        pub fn disable_batch(env: Env) {
+            env.storage()
+                .instance()
+                .set(&Symbol::new(&env, "no_batch"), &true);
+        }
+
+        pub fn render_profile_compact_return(env: Env, _user: Address, _path: Bytes) -> Bytes {
+            Self::add_cards(&env, 1);
+            Bytes::from_slice(&env, b"<span class=\"author\">card</span>")
+        }
+
+        pub fn render_authors_batch(env: Env, authors: Vec<Address>, path: Bytes) -> Vec<Bytes> {
+            let no_batch: bool = env
+                .storage()
+                .instance()
+                .get(&Symbol::new(&env, "no_batch"))
+                .unwrap_or(false);
+            if no_batch {
+                panic!("render_authors_batch not supported");
+            }
+            let mut cards = Vec::new(&env);
+            for author in authors.iter() {
+                cards.push_back(Self::render_profile_compact_return(
+                    env.clone(),
+                    author,
+                    path.clone(),
+                ));
+            }
+            cards
+        }
+
+        pub fn cards_rendered(env: Env) -> u32 {
+            env.storage()
+                .instance()
+                .get(&Symbol::new(&env, "cards"))
+                .unwrap_or(0)
+        }
+
+        fn add_cards(env: &Env, count: u32) {
+            let total = Self::cards_rendered(env.clone()) + count;
+            env.storage()
+                .instance()
+                .set(&Symbol::new(env, "cards"), &total);
+        }
+    }
+
+    fn render_count(html: &Bytes, needle: &str) -> usize {
+        let needle = needle.as_bytes();
+        let len = html.len() as usize;
+        if needle.len() > len {
+            return 0;
+        }
+        (0..=len - needle.len())
+            .filter(|start| {
+                needle
+                    .iter()
+                    .enumerate()
+                    .all(|(i, b)| html.get((start + i) as u32) == Some(*b))
+            })
+            .count()
+    }
+
+    fn render_contains(html: &Bytes, needle: &str) -> bool {
+        render_count(html, needle) > 0
+    }
+
+    fn mock_reply(env: &Env, board_id: u64, thread_id: u64, id: u64, parent: u64) -> ReplyMeta {
+        ReplyMeta {
+            id,
+            board_id,
+            thread_id,
+            parent_id: parent,
+            depth: if id == parent { 0 } else { 1 },
+            creator: Address::generate(env),
+            created_at: 0,
+            updated_at: 0,
+            is_hidden: false,
+            is_deleted: false,
+            flag_count: 0,
+        }
+    }
+
+    /// Set up a board contract wired to a mock content contract, with one thread
+    fn setup_with_content(env: &Env) -> (BoardsBoardClient, MockContentClient, u64, u64) {
+        env.mock_all_auths();
+
+        let contract_id = env.register(BoardsBoard, ());
+        let client = BoardsBoardClient::new(env, &contract_id);
+        let content = MockContentClient::new(env, &env.register(MockContent, ()));
+        let registry = env.register(MockRegistry, ());
+        client.init(&registry, &None, &Some(content.address.clone()), &None);
+
+        let caller = Address::generate(env);
+        let board_id = client.create_board(
+            &String::from_str(env, "General"),
+            &String::from_str(env, "General discussion"),
+            &String::from_str(env, "false"),
+            &String::from_str(env, "true"),
+            &caller,
+        );
+        let thread_id =
+            client.create_thread(&board_id, &String::from_str(env, "Hello"), &None, &caller);
+
+        // From here on only the content contract's own calls are authorized
+        env.set_auths(&[]);
+        (client, content, board_id, thread_id)
+    }
+
+    #[test]
+    fn test_flagged_filter_batches_flag_counts() {
+        let env = Env::default();
+        let (client, content, board_id, _) = setup_with_content(&env);
+        env.mock_all_auths();
+        let creator = Address::generate(&env);
+        for _ in 0..4 {
+            client.create_thread(&board_id, &String::from_str(&env, "Topic"), &None, &creator);
+        }
+        content.set_thread_flag_count(&1, &2);
+        content.set_thread_flag_count(&3, &1);
+
+        let flagged = client.list_threads_filtered(&board_id, &ThreadFilter::Flagged, &0, &10);
+        assert_eq!(flagged.len(), 2);
+        assert_eq!(flagged.get_unchecked(0).id, 3);
+        assert_eq!(flagged.get_unchecked(1).id, 1);
+        // All five threads were checked with a single content call
+        assert_eq!(content.flag_count_calls(), 1);
+    }
+
+    #[test]
+    fn test_reply_batch_fetches_edit_markers_once() {
+        let env = Env::default();
+        let (client, content, board_id, thread_id) = setup_with_content(&env);
+        for id in 0..3 {
+            let reply = mock_reply(&env, board_id, thread_id, id, id);
+            content.add_reply(&reply, &String::from_str(&env, "Body"));
+        }
+        content.set_edited(&thread_id, &1);
+
+        let path = String::from_str(&env, "/t/0/replies/0");
+        let html = client.render(&board_id, &Some(path), &None, &None);
+        assert_eq!(render_count(&html, "(edited)"), 1);
+        assert_eq!(content.edited_calls(), 1);
+    }
+
+    #[test]
+    fn test_thread_view_fetches_viewer_permissions_once() {
+        let env = Env::default();
+        let (client, content, board_id, thread_id) = setup_with_content(&env);
+        env.mock_all_auths();
+        let perms = MockPermissionsClient::new(&env, &env.register(MockPermissions, ()));
+        client.set_permissions(&perms.address);
+        for id in 0..3 {
+            let reply = mock_reply(&env, board_id, thread_id, id, id);
+            content.add_reply(&reply, &String::from_str(&env, "Body"));
+        }
+        let moderator = Address::generate(&env);
+        perms.make_moderator(&moderator);
+        let viewer = Some(moderator);
+
+        // One lookup covers the moderator controls and the thread edit link
+        let path = String::from_str(&env, "/t/0");
+        let html = client.render(&board_id, &Some(path), &viewer, &None);
+        assert_eq!(perms.call_count(), 1);
+        assert!(render_contains(&html, "form:@admin:lock_thread"));
+        assert!(render_contains(&html, "/t/0/edit)"));
+        assert!(render_contains(&html, "/t/0/replies/0"));
+
+        // ...and one more covers every reply in a batch
+        let path = String::from_str(&env, "/t/0/replies/0");
+        let html = client.render(&board_id, &Some(path), &viewer, &None);
+        assert_eq!(perms.call_count(), 2);
+        assert_eq!(render_count(&html, "/edit)"), 3);
+        assert_eq!(render_count(&html, "flag_reply"), 3);
+
+        // Members only get the edit link on their own replies
+        let member = Some(Address::generate(&env));
+        let path = String::from_str(&env, "/t/0/replies/0");
+        let html = client.render(&board_id, &Some(path), &member, &None);
+        assert_eq!(perms.call_count(), 3);
+        assert_eq!(render_count(&html, "/edit)"), 0);
+        assert_eq!(render_count(&html, "/reply)"), 3);
+    }
+
+    #[test]
+    fn test_distinguished_posts_get_mod_badge_and_toggle() {
+        let env = Env::default();
+        let (client, content, board_id, thread_id) = setup_with_content(&env);
+        env.mock_all_auths();
+        let perms = MockPermissionsClient::new(&env, &env.register(MockPermissions, ()));
+        client.set_permissions(&perms.address);
+        let mod_reply = mock_reply(&env, board_id, thread_id, 0, 0);
+        let mod_author = Some(mod_reply.creator.clone());
+        perms.make_moderator(&mod_reply.creator);
+        content.add_reply(&mod_reply, &String::from_str(&env, "Official"));
+        let member_reply = mock_reply(&env, board_id, thread_id, 1, 1);
+        let member_author = Some(member_reply.creator.clone());
+        content.add_reply(&member_reply, &String::from_str(&env, "Opinion"));
+        content.set_distinguished(&thread_id, &None);
+        content.set_distinguished(&thread_id, &Some(0));
+
+        let thread_path = Some(String::from_str(&env, "/t/0"));
+        let html = client.render(&board_id, &thread_path, &None, &None);
+        assert_eq!(render_count(&html, "badge-mod"), 1);
+        assert!(render_contains(
+            &html,
+            "class=\"thread-body distinguished\""
+        ));
+        assert!(!render_contains(&html, "distinguish-toggle"));
+
+        // Reply marks come from one lookup per batch
+        let replies_path = Some(String::from_str(&env, "/t/0/replies/0"));
+        let calls = content.distinguished_calls();
+        let html = client.render(&board_id, &replies_path, &None, &None);
+        assert_eq!(content.distinguished_calls(), calls + 1);
+        assert_eq!(render_count(&html, "badge-mod"), 1);
+        assert_eq!(render_count(&html, "class=\"reply distinguished\""), 1);
+
+        // The moderator can unmark their reply; other moderators can only unmark
+        let html = client.render(&board_id, &replies_path, &mod_author, &None);
+        assert_eq!(render_count(&html, ">Undistinguish</a>"), 1);
+        assert_eq!(render_count(&html, ">Distinguish</a>"), 0);
+        assert!(render_contains(
+            &html,
+            "tx:@content:distinguish {&quot;board_id&quot;:0,&quot;thread_id&quot;:0,&quot;reply_id&quot;:0}"
+        ));
+        let html = client.render(&board_id, &thread_path, &mod_author, &None);
+        assert!(render_contains(
+            &html,
+            "&quot;reply_id&quot;:null}\" class=\"distinguish-toggle\">Undistinguish"
+        ));
+
+        // Members never get the link, not even on their own reply
+        let html = client.render(&board_id, &replies_path, &member_author, &None);
+        assert!(!render_contains(&html, "distinguish-toggle"));
+    }
+
+    #[test]
+    fn test_reply_form_shows_cooldown_countdown() {
+        let env = Env::default();
+        let (client, content, board_id, thread_id) = setup_with_content(&env);
+        env.mock_all_auths();
+        let perms = MockPermissionsClient::new(&env, &env.register(MockPermissions, ()));
+        client.set_permissions(&perms.address);
+        let admin = Address::generate(&env);
+        perms.make_admin(&admin);
+        let member = Address::generate(&env);
+        let moderator = Address::generate(&env);
+        perms.make_moderator(&moderator);
+
+        // Only admins set the cooldown, and it is capped at an hour
+        assert_eq!(client.get_reply_cooldown(&board_id), 0);
+        assert!(client
+            .try_set_reply_cooldown(&board_id, &60, &member)
+            .is_err());
+        assert!(client
+            .try_set_reply_cooldown(&board_id, &3_601, &admin)
+            .is_err());
+        client.set_reply_cooldown(&board_id, &60, &admin);
+        assert_eq!(client.get_reply_cooldown(&board_id), 60);
+
+        env.ledger().set_timestamp(10_018);
+        content.set_last_reply_at(&thread_id, &member, &10_000);
+        content.set_last_reply_at(&thread_id, &moderator, &10_000);
+        let render = |viewer: &Address| {
+            let path = String::from_str(&env, "/t/0/reply");
+            client.render(&board_id, &Some(path), &Some(viewer.clone()), &None)
+        };
+        let html = render(&member);
+        assert!(render_contains(
+            &html,
+            "<p class=\"cooldown-notice\">You can reply again in 42s.</p>"
+        ));
+        assert!(render_contains(
+            &html,
+            "\"13\": \"You're replying too quickly"
+        ));
+
+        // Moderators are exempt, so they never see the notice
+        assert!(!render_contains(&render(&moderator), "cooldown-notice"));
+
+        // Longer waits show minutes; the notice goes once the window passes
+        client.set_reply_cooldown(&board_id, &150, &admin);
+        assert!(render_contains(
+            &render(&member),
+            "You can reply again in 2m 12s."
+        ));
+        env.ledger().set_timestamp(10_150);
+        assert!(!render_contains(&render(&member), "cooldown-notice"));
+    }
+
+    #[test]
+    fn test_reply_batch_trusts_continuation_ctx() {
+        let env = Env::default();
+        let (client, content, board_id, thread_id) = setup_with_content(&env);
+        env.mock_all_auths();
+        let perms = MockPermissionsClient::new(&env, &env.register(MockPermissions, ()));
+        client.set_permissions(&perms.address);
+        for id in 0..8 {
+            let reply = mock_reply(&env, board_id, thread_id, id, id);
+            content.add_reply(&reply, &String::from_str(&env, "Body"));
+        }
+        let moderator = Address::generate(&env);
+        perms.make_moderator(&moderator);
+
+        // The thread page hands its viewer context to the first batch
+        let render = |path: &str, viewer: &Address| {
+            let path = String::from_str(&env, path);
+            client.render(&board_id, &Some(path), &Some(viewer.clone()), &None)
+        };
+        let html = render("/t/0", &moderator);
+        assert!(render_contains(&html, "/t/0/replies/0/3\"}}"));
+
+        // With the ctx a batch renders the same output without a permissions lookup
+        let derived = render("/t/0/replies/0", &moderator);
+        let calls = perms.call_count();
+        let trusted = render("/t/0/replies/0/3", &moderator);
+        assert_eq!(perms.call_count(), calls);
+        assert_eq!(trusted, derived);
+        assert!(render_contains(&trusted, "/t/0/replies/6/3\"}}"));
+
+        let member = Address::generate(&env);
+        let derived = render("/t/0/replies/6", &member);
+        let trusted = render("/t/0/replies/6/1", &member);
+        assert_eq!(trusted, derived);
+        assert_eq!(render_count(&trusted, "/edit)"), 0);
+    }
+
+    #[test]
+    fn test_reply_batch_renders_each_author_once() {
+        let env = Env::default();
+        let (client, content, board_id, thread_id) = setup_with_content(&env);
+        env.mock_all_auths();
+        let profile = MockProfileClient::new(&env, &env.register(MockProfile, ()));
+        let registry: Address = env.as_contract(&client.address, || {
+            env.storage().instance().get(&BoardKey::Registry).unwrap()
+        });
+        let registry = MockRegistryClient::new(&env, &registry);
+        registry.set_contract(&Symbol::new(&env, "profile"), &profile.address);
+
+        // Four replies from two authors
+        let alice = Address::generate(&env);
+        let bob = Address::generate(&env);
+        for (id, author) in [&alice, &bob, &alice, &alice].into_iter().enumerate() {
+            let mut reply = mock_reply(&env, board_id, thread_id, id as u64, id as u64);
+            reply.creator = author.clone();
+            content.add_reply(&reply, &String::from_str(&env, "Body"));
+        }
+
+        let path = String::from_str(&env, "/t/0/replies/0");
+        let html = client.render(&board_id, &Some(path.clone()), &None, &None);
+        assert_eq!(profile.cards_rendered(), 2);
+        assert_eq!(render_count(&html, "<span class=\"author\">card</span>"), 4);
+
+        // Older profile contracts without the batch call get one call per reply
+        profile.disable_batch();
+        let html = client.render(&board_id, &Some(path), &None, &None);
+        assert_eq!(profile.cards_rendered(), 6);
+        assert_eq!(render_count(&html, "<span class=\"author\">card</span>"), 4);
+    }
+
+    #[test]
+    fn test_oversized_thread_body_is_cut_to_the_render_budget() {
+        let env = Env::default();
+        let (client, content, board_id, _) = setup_with_content(&env);
+        let body = std::format!("{}END-OF-POST", "lorem ipsum ".repeat(1280));
+        content.set_thread_body(&String::from_str(&env, &body));
+
+        let html = client.render(
+            &board_id,
+            &Some(String::from_str(&env, "/t/0")),
+            &None,
+            &None,
+        );
+        assert!(render_contains(&html, "[Show full post](render:"));
+        assert!(render_contains(&html, "/t/0/full)"));
+        assert!(!render_contains(&html, "END-OF-POST"));
+        assert!(html.len() < DEFAULT_RENDER_BUDGET + 4096);
+
+        // The full route renders everything
+        let path = String::from_str(&env, "/t/0/full");
+        let html = client.render(&board_id, &Some(path), &None, &None);
+        assert!(render_contains(&html, "END-OF-POST"));
+        assert!(!render_contains(&html, "Show full post"));
+
+        // A larger board budget fits the body on the thread page
+        env.mock_all_auths();
+        assert!(client
+            .try_set_render_budget(&board_id, &1024, &Address::generate(&env))
+            .is_err());
+        assert!(client
+            .try_set_render_budget(
+                &board_id,
+                &(THREAD_TAIL_BYTES + 1),
+                &Address::generate(&env)
+            )
+            .is_err());
+        client.set_render_budget(&board_id, &MAX_RENDER_BUDGET, &Address::generate(&env));
+        let html = client.render(
+            &board_id,
+            &Some(String::from_str(&env, "/t/0")),
+            &None,
+            &None,
+        );
+        assert!(render_contains(&html, "END-OF-POST"));
+    }
+
+    #[test]
+    fn test_render_budget_cuts_whole_lines_and_closes_fences() {
+        let env = Env::default();
+        let (client, content, board_id, _) = setup_with_content(&env);
+        let body = std::format!(
+            "Intro\n```rust\n{}```\nEND-OF-POST",
+            "let answer = 42; // the same line again\n".repeat(600)
+        );
+        content.set_thread_body(&String::from_str(&env, &body));
+
+        let path = Some(String::from_str(&env, "/t/0"));
+        let html = client.render(&board_id, &path, &None, &None);
+        assert!(render_contains(&html, "[Show full post](render:"));
+        assert!(!render_contains(&html, "END-OF-POST"));
+        // The excerpt ends on a whole line and its open fence is closed
+        assert!(render_contains(&html, "// the same line again\n```\n"));
+        assert_eq!(render_count(&html, "```"), 2);
+        // Only the tail of the page is estimated, so the page stays near the budget
+        assert!(html.len() <= DEFAULT_RENDER_BUDGET + THREAD_TAIL_BYTES);
+
+        // The smallest budget still leaves room for part of the body
+        env.mock_all_auths();
+        let admin = Address::generate(&env);
+        client.set_render_budget(&board_id, &MIN_RENDER_BUDGET, &admin);
+        let html = client.render(&board_id, &path, &None, &None);
+        assert!(render_contains(&html, "Intro\n"));
+        assert!(render_contains(&html, "[Show full post](render:"));
+    }
+
+    #[test]
+    fn test_thread_ttl_bumped_on_access() {
+        use soroban_sdk::testutils::storage::Persistent as _;
+
+        let env = Env::default();
+        // Everything starts with the full TTL so the contracts outlive the jump below
+        env.ledger().with_mut(|li| {
+            li.min_persistent_entry_ttl = TTL_EXTEND_TO;
+            li.max_entry_ttl = TTL_EXTEND_TO * 2;
+        });
+        let (client, _, board_id, thread_id) = setup_with_content(&env);
+        let thread_ttl = || {
+            env.as_contract(&client.address, || {
+                env.storage()
+                    .persistent()
+                    .get_ttl(&BoardKey::BoardThread(board_id, thread_id))
+            })
+        };
+
+        // Once the remaining TTL falls under the threshold, a read bumps it back up
+        env.ledger()
+            .with_mut(|li| li.sequence_number += TTL_EXTEND_TO - TTL_THRESHOLD + 10);
+        assert_eq!(thread_ttl(), TTL_THRESHOLD - 10);
+        let path = String::from_str(&env, "/t/0");
+        client.render(&board_id, &Some(path), &None, &None);
+        assert_eq!(thread_ttl(), TTL_EXTEND_TO);
+
+        // The maintenance pass does the same without rendering
+        env.ledger()
+            .with_mut(|li| li.sequence_number += TTL_EXTEND_TO - TTL_THRESHOLD + 10);
+        assert_eq!(client.extend_board_ttl(&board_id, &0, &100), 1);
+        assert_eq!(thread_ttl(), TTL_EXTEND_TO);
+
+        // Above the threshold a bump is a no-op
+        env.ledger().with_mut(|li| li.sequence_number += 100);
+        client.extend_thread_ttl(&board_id, &thread_id);
+        assert_eq!(thread_ttl(), TTL_EXTEND_TO - 100);
+        assert!(client.try_extend_thread_ttl(&board_id, &5).is_err());
+    }
+
+    #[test]
+    fn test_thread_auto_hidden_at_flag_threshold() {
+        let env = Env::default();
+        let (client, content, board_id, thread_id) = setup_with_content(&env);
+
+        // Below the default threshold of 3 nothing happens
+        content.report_flags(&client.address, &board_id, &thread_id, &2);
+        assert!(!client.get_thread(&board_id, &thread_id).unwrap().is_hidden);
+
+        content.report_flags(&client.address, &board_id, &thread_id, &3);
+        assert!(client.get_thread(&board_id, &thread_id).unwrap().is_hidden);
+        assert!(client.is_thread_auto_hidden(&board_id, &thread_id));
+    }
+
+    #[test]
+    fn test_unhidden_thread_not_rehidden_until_flags_cleared() {
+        let env = Env::default();
+        let (client, content, board_id, thread_id) = setup_with_content(&env);
+
+        content.report_flags(&client.address, &board_id, &thread_id, &3);
+
+        // Moderator unhides: the marker goes and further flags don't re-hide
+        client.set_thread_hidden(&board_id, &thread_id, &false);
+        assert!(!client.is_thread_auto_hidden(&board_id, &thread_id));
+        content.report_flags(&client.address, &board_id, &thread_id, &4);
+        assert!(!client.get_thread(&board_id, &thread_id).unwrap().is_hidden);
+
+        // Once flags are cleared, auto-hide applies again
+        content.clear_flags(&client.address, &board_id, &thread_id);
+        content.report_flags(&client.address, &board_id, &thread_id, &3);
+        assert!(client.get_thread(&board_id, &thread_id).unwrap().is_hidden);
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_notify_thread_flagged_requires_content_contract() {
+        let env = Env::default();
+        let (client, _, board_id, thread_id) = setup_with_content(&env);
+
+        client.notify_thread_flagged(&board_id, &thread_id, &10);
+    }
+
+    #[test]
+    fn test_reply_permalink_shows_parent_context() {
+        let env = Env::default();
+        let (client, content, board_id, thread_id) = setup_with_content(&env);
+
+        content.add_reply(
+            &mock_reply(&env, board_id, thread_id, 0, 0),
+            &String::from_str(&env, "Parent body"),
+        );
+        content.add_reply(
+            &mock_reply(&env, board_id, thread_id, 1, 0),
+            &String::from_str(&env, "Child body"),
+        );
+        let mut hidden = mock_reply(&env, board_id, thread_id, 2, 2);
+        hidden.is_hidden = true;
+        content.add_reply(&hidden, &String::from_str(&env, "Secret body"));
+
+        let path = String::from_str(&env, "/t/0/r/1");
+        let html = client.render(&board_id, &Some(path), &None, &None);
+        assert!(render_contains(&html, "Child body"));
+        assert!(render_contains(&html, "In reply to #0"));
+        assert!(render_contains(&html, "Parent body"));
+        assert!(render_contains(&html, "View full thread"));
+
+        // Guests see the tombstone, not the hidden content
+        let path = String::from_str(&env, "/t/0/r/2");
+        let html = client.render(&board_id, &Some(path), &None, &None);
+        assert!(render_contains(&html, "hidden by a moderator"));
+        assert!(!render_contains(&html, "Secret body"));
+        assert!(!render_contains(&html, "In reply to"));
+
+        let path = String::from_str(&env, "/t/0/r/9");
+        let html = client.render(&board_id, &Some(path), &None, &None);
+        assert!(render_contains(&html, "Reply not found."));
+    }
+
+    #[test]
+    fn test_breadcrumbs_on_thread_pages() {
+        let env = Env::default();
+        let (client, _, board_id, thread_id) = setup_with_content(&env);
+
+        // Thread view: the thread title is the current (unlinked) segment
+        let path = String::from_str(&env, "/t/0");
+        let html = client.render(&board_id, &Some(path), &None, &None);
+        assert!(render_contains(
+            &html,
+            "<a href=\"render:/\">Home</a><span class=\"crumb-sep\">›</span><a href=\"render:/b/"
+        ));
+        assert!(render_contains(
+            &html,
+            "General</a><span class=\"crumb-sep\">›</span><span class=\"crumb-current\">Hello"
+        ));
+        assert!(!render_contains(&html, "Back to Board"));
+
+        // Sub-pages link the thread and end at the page name
+        let path = String::from_str(&env, "/t/0/reply");
+        let html = client.render(&board_id, &Some(path), &None, &None);
+        assert!(render_contains(
+            &html,
+            "/t/0\">Hello</a><span class=\"crumb-sep\">›</span>"
+        ));
+        assert!(render_contains(
+            &html,
+            "<span class=\"crumb-current\">Reply</span>"
+        ));
+        assert_eq!(render_count(&html, "crumb-sep"), 3);
+
+        // Long titles are cut to 40 characters with an ellipsis
+        env.mock_all_auths();
+        let title = String::from_str(&env, "Éclairs, croissants and other pastries worth baking");
+        let long_id = client.create_thread(&board_id, &title, &None, &Address::generate(&env));
+        assert_eq!(long_id, thread_id + 1);
+        let path = String::from_str(&env, "/t/1");
+        let html = client.render(&board_id, &Some(path), &None, &None);
+        assert!(render_contains(
+            &html,
+            "<span class=\"crumb-current\">Éclairs, croissants and other pastries w…</span>"
+        ));
+    }
+
+    /// Parse a JSON export, failing the test if it isn't valid JSON
+    fn parse_json(out: &Bytes) -> serde_json::Value {
+        let mut buf = std::vec![0u8; out.len() as usize];
+        out.copy_into_slice(&mut buf);
+        serde_json::from_slice(&buf).expect("export is not valid JSON")
+    }
+
+    #[test]
+    fn test_export_thread_json() {
+        let env = Env::default();
+        let (client, content, board_id, thread_id) = setup_with_content(&env);
+        env.mock_all_auths();
+        let voting = MockVotingClient::new(&env, &env.register(MockVoting, ()));
+        client.set_voting(&voting.address);
+        voting.set_score(&thread_id, &-3);
+
+        let body = "He said \"hi\"\n\tthen left \\ \u{1} ☕";
+        content.set_thread_body(&String::from_str(&env, body));
+        content.add_reply(
+            &mock_reply(&env, board_id, thread_id, 0, 0),
+            &String::from_str(&env, "First <b>reply</b>"),
+        );
+        let mut hidden = mock_reply(&env, board_id, thread_id, 1, 1);
+        hidden.is_hidden = true;
+        content.add_reply(&hidden, &String::from_str(&env, "Secret body"));
+
+        let path = String::from_str(&env, "/export/t/0");
+        let doc = parse_json(&client.render(&board_id, &Some(path.clone()), &None, &None));
+        assert_eq!(doc["board"]["name"], "General");
+        assert_eq!(doc["board"]["thread_count"], 1);
+        assert_eq!(doc["thread"]["id"], 0);
+        assert_eq!(doc["thread"]["title"], "Hello");
+        assert_eq!(doc["thread"]["score"], -3);
+        assert_eq!(doc["thread"]["body"], body);
+        let replies = doc["replies"].as_array().unwrap();
+        assert_eq!(replies.len(), 1);
+        assert_eq!(replies[0]["id"], 0);
+        assert_eq!(replies[0]["score"], 0);
+        assert_eq!(replies[0]["body"], "First <b>reply</b>");
+
+        // Moderators also get hidden replies
+        let perms = MockPermissionsClient::new(&env, &env.register(MockPermissions, ()));
+        client.set_permissions(&perms.address);
+        let moderator = Address::generate(&env);
+        perms.make_moderator(&moderator);
+        let doc = parse_json(&client.render(&board_id, &Some(path), &Some(moderator), &None));
+        let replies = doc["replies"].as_array().unwrap();
+        assert_eq!(replies.len(), 2);
+        assert_eq!(replies[1]["is_hidden"], true);
+    }
+
+    #[test]
+    fn test_export_board_json_skips_hidden_threads() {
+        let env = Env::default();
+        let (client, _, board_id, thread_id) = setup_with_content(&env);
+        env.mock_all_auths();
+        let title = String::from_str(&env, "Say \"hi\" \\ wave");
+        client.create_thread(&board_id, &title, &None, &Address::generate(&env));
+        client.set_thread_hidden(&board_id, &thread_id, &true);
+
+        let path = String::from_str(&env, "/export/board");
+        let doc = parse_json(&client.render(&board_id, &Some(path), &None, &None));
+        assert_eq!(doc["board"]["description"], "General discussion");
+        assert_eq!(doc["board"]["is_private"], false);
+        assert_eq!(doc["board"]["thread_count"], 2);
+        let threads = doc["threads"].as_array().unwrap();
+        assert_eq!(threads.len(), 1);
+        assert_eq!(threads[0]["title"], "Say \"hi\" \\ wave");
+        assert!(threads[0]["flair_id"].is_null());
+
+        // Hidden and missing threads export as not_found
+        let path = String::from_str(&env, "/export/t/0");
+        let doc = parse_json(&client.render(&board_id, &Some(path), &None, &None));
+        assert_eq!(doc["error"], "not_found");
+        let path = String::from_str(&env, "/export/t/9");
+        let doc = parse_json(&client.render(&board_id, &Some(path), &None, &None));
+        assert_eq!(doc["error"], "not_found");
+    }
+
+    #[test]
+    fn test_export_thread_markdown_nests_replies_depth_first() {
+        let env = Env::default();
+        let (client, content, board_id, thread_id) = setup_with_content(&env);
+        env.mock_all_auths();
+        content.set_thread_body(&String::from_str(&env, "Opening post"));
+
+        // 0 ─ 1 ─ 2, then 3 with a hidden child 4 and a deleted child 5
+        let mut replies = std::vec::Vec::new();
+        for (id, parent, depth) in [
+            (0, 0, 0),
+            (1, 0, 1),
+            (2, 1, 2),
+            (3, 3, 0),
+            (4, 3, 1),
+            (5, 3, 1),
+        ] {
+            let mut reply = mock_reply(&env, board_id, thread_id, id, parent);
+            reply.depth = depth;
+            reply.is_hidden = id == 4;
+            reply.is_deleted = id == 5;
+            replies.push(reply);
+        }
+        let bodies = [
+            "Top one",
+            "Child line one\nChild line two",
+            "Grandchild",
+            "Top two",
+            "Hidden body",
+            "",
+        ];
+        for (reply, body) in replies.iter().zip(bodies) {
+            content.add_reply(reply, &String::from_str(&env, body));
+        }
+
+        let path = Some(String::from_str(&env, "/export/t/0/md"));
+        let doc = client.render(&board_id, &path, &None, &None);
+        let mut buf = std::vec![0u8; doc.len() as usize];
+        doc.copy_into_slice(&mut buf);
+        let text = std::string::String::from_utf8(buf).unwrap();
+
+        assert!(text.starts_with("# Hello\n\n**"));
+        assert!(text.contains("Opening post\n\n---\n\n## Replies\n\n"));
+        assert!(text.contains(" · #0\nTop one\n\n"));
+        assert!(text.contains(" · #1\n> Child line one\n> Child line two\n\n"));
+        assert!(text.contains("\n> > **"));
+        assert!(text.contains(" · #2\n> > Grandchild\n\n"));
+        assert!(text.contains(" · #4\n> [This reply has been hidden by a moderator]"));
+        assert!(text.contains(" · #5\n> [This reply has been deleted]"));
+        assert!(!text.contains("Hidden body"));
+        assert!(!text.contains("Export stopped"));
+
+        // Depth-first: each subtree comes before the next top-level reply
+        let order: std::vec::Vec<usize> = (0..6)
+            .map(|id| text.find(&std::format!(" · #{}\n", id)).unwrap())
+            .collect();
+        assert!(order.windows(2).all(|pair| pair[0] < pair[1]));
+    }
+
+    #[test]
+    fn test_export_thread_markdown_notes_replies_past_the_cap() {
+        let env = Env::default();
+        let (client, content, board_id, thread_id) = setup_with_content(&env);
+        env.mock_all_auths();
+        let path = Some(String::from_str(&env, "/export/t/0/md"));
+        let export = || {
+            let doc = client.render(&board_id, &path, &None, &None);
+            let mut buf = std::vec![0u8; doc.len() as usize];
+            doc.copy_into_slice(&mut buf);
+            std::string::String::from_utf8(buf).unwrap()
+        };
+
+        // A full export has no note; the cap is shared across nesting levels
+        let last = MAX_EXPORT_REPLIES as u64 - 1;
+        for id in 0..=last {
+            let mut reply = mock_reply(&env, board_id, thread_id, id, id.min(1));
+            reply.depth = if id > 1 { 1 } else { 0 };
+            content.add_reply(&reply, &String::from_str(&env, "Body"));
+        }
+        let text = export();
+        assert!(text.contains(&std::format!(" · #{}\n> Body\n\n", last)));
+        assert!(!text.contains("Export stopped"));
+
+        // One more reply is left out, and the document says so
+        let reply = mock_reply(&env, board_id, thread_id, last + 1, 1);
+        content.add_reply(&reply, &String::from_str(&env, "Body"));
+        let text = export();
+        assert!(!text.contains(&std::format!(" · #{}\n", last + 1)));
+        assert!(text.ends_with(&std::format!(
+            "*Export stopped after {} replies; later replies are not included.*\n",
+            MAX_EXPORT_REPLIES
+        )));
+    }
+
+    #[test]
+    fn test_digest_window_and_reply_count_fallback() {
+        let env = Env::default();
+        let (client, board_id, caller) = setup_with_board(&env);
+        let now = 19_700u64 * 86_400;
+        let post = |title: &str, days_ago: u64, replies: u32| {
+            env.ledger().set_timestamp(now - days_ago * 86_400);
+            let id =
+                client.create_thread(&board_id, &String::from_str(&env, title), &None, &caller);
+            for _ in 0..replies {
+                client.increment_reply_count(&board_id, &id);
+            }
+        };
+        post("Ancient history", 12, 9);
+        post("Quiet thread", 5, 1);
+        post("Busy thread", 3, 4);
+        post("Fresh thread", 1, 0);
+        env.ledger().set_timestamp(now);
+
+        // Without voting, the last week ranks by replies and leaves out older threads
+        let path = Some(String::from_str(&env, "/digest/7"));
+        let html = client.render(&board_id, &path, &None, &None);
+        let mut buf = std::vec![0u8; html.len() as usize];
+        html.copy_into_slice(&mut buf);
+        let text = std::string::String::from_utf8(buf).unwrap();
+        assert!(text.contains("Top threads of the last 7 days"));
+        assert!(text.contains("ranked by replies"));
+        assert!(!text.contains("Ancient history"));
+        assert!(!text.contains(" points"));
+        let busy = text.find("Busy thread").unwrap();
+        let quiet = text.find("Quiet thread").unwrap();
+        let fresh = text.find("Fresh thread").unwrap();
+        assert!(busy < quiet && quiet < fresh);
+        assert!(text.contains("/export/digest/7\""));
+
+        // A shorter window cuts further, and day counts are held to 1-30
+        let path = Some(String::from_str(&env, "/digest/2"));
+        let html = client.render(&board_id, &path, &None, &None);
+        assert!(render_contains(&html, "Fresh thread"));
+        assert!(!render_contains(&html, "Busy thread"));
+        let path = Some(String::from_str(&env, "/digest/90"));
+        let html = client.render(&board_id, &path, &None, &None);
+        assert!(render_contains(&html, "last 30 days"));
+        assert!(render_contains(&html, "Ancient history"));
+        let path = Some(String::from_str(&env, "/digest/0"));
+        let html = client.render(&board_id, &path, &None, &None);
+        assert!(render_contains(&html, "last 1 day<"));
+        assert!(render_contains(&html, "No new threads in this period."));
+        assert!(!render_contains(&html, "Only the newest 100 threads"));
+    }
+
+    #[test]
+    fn test_digest_notes_when_the_scan_window_cuts_the_period_short() {
+        let env = Env::default();
+        let (client, board_id, caller) = setup_with_board(&env);
+        let now = 19_700u64 * 86_400;
+        env.ledger().set_timestamp(now - 2 * 86_400);
+        let title = String::from_str(&env, "Busy day");
+        for _ in 0..=MAX_FILTER_SCAN {
+            client.create_thread(&board_id, &title, &None, &caller);
+        }
+        env.ledger().set_timestamp(now);
+
+        // The oldest thread of the week sits past the newest 100
+        let path = Some(String::from_str(&env, "/digest/7"));
+        let html = client.render(&board_id, &path, &None, &None);
+        assert!(render_contains(
+            &html,
+            "Only the newest 100 threads were checked"
+        ));
+        let path = Some(String::from_str(&env, "/export/digest/7"));
+        let doc = client.render(&board_id, &path, &None, &None);
+        assert!(render_contains(
+            &doc,
+            "*Only the newest 100 threads were checked, so older threads from this period may be missing.*"
+        ));
+
+        // A window the cutoff closes first is complete
+        let path = Some(String::from_str(&env, "/digest/1"));
+        let html = client.render(&board_id, &path, &None, &None);
+        assert!(!render_contains(&html, "Only the newest 100 threads"));
+    }
+
+    #[test]
+    fn test_digest_ranks_by_score_with_markdown_export() {
+        let env = Env::default();
+        let (client, board_id, caller) = setup_with_board(&env);
+        let voting = MockVotingClient::new(&env, &env.register(MockVoting, ()));
+        client.set_voting(&voting.address);
+        let now = 19_700u64 * 86_400;
+        env.ledger().set_timestamp(now - 3 * 86_400);
+        let popular = client.create_thread(
+            &board_id,
+            &String::from_str(&env, "Popular thread"),
+            &None,
+            &caller,
+        );
+        client.increment_reply_count(&board_id, &popular);
+        env.ledger().set_timestamp(now - 86_400);
+        let chatty = client.create_thread(
+            &board_id,
+            &String::from_str(&env, "Chatty thread"),
+            &None,
+            &caller,
+        );
+        for _ in 0..5 {
+            client.increment_reply_count(&board_id, &chatty);
+        }
+        voting.set_votes(&popular, &8, &1);
+        voting.set_votes(&chatty, &1, &3);
+        env.ledger().set_timestamp(now);
+
+        let path = Some(String::from_str(&env, "/export/digest/7"));
+        let doc = client.render(&board_id, &path, &None, &None);
+        let mut buf = std::vec![0u8; doc.len() as usize];
+        doc.copy_into_slice(&mut buf);
+        let text = std::string::String::from_utf8(buf).unwrap();
+        assert!(text.starts_with("# General: top threads of the last 7 days\n\n"));
+        assert!(text.contains("1. [Popular thread](/b/general/t/0) by "));
+        assert!(text.contains(" · 7 points · 1 replies\n"));
+        assert!(text.contains("2. [Chatty thread](/b/general/t/1) by "));
+        assert!(text.contains(" · -2 points · 5 replies\n"));
+        assert!(!text.contains("<"));
+    }
+
+    #[test]
+    fn test_display_name_claims() {
+        let env = Env::default();
+        let (client, board_id, _) = setup_with_board(&env);
+        let alice = Address::generate(&env);
+        let bob = Address::generate(&env);
+        let s = |v: &str| String::from_str(&env, v);
+
+        env.ledger().set_timestamp(1_700_000_000);
+        client.claim_display_name(&board_id, &s("Alice"), &alice);
+        assert_eq!(client.get_display_name(&board_id, &alice), Some(s("Alice")));
+
+        // Names are unique per board, ignoring case, and must be 3-20 word characters
+        assert!(client
+            .try_claim_display_name(&board_id, &s("alice"), &bob)
+            .is_err());
+        assert!(client
+            .try_claim_display_name(&board_id, &s("ab"), &bob)
+            .is_err());
+        assert!(client
+            .try_claim_display_name(&board_id, &s("bad name"), &bob)
+            .is_err());
+        assert_eq!(client.get_display_name(&board_id, &bob), None);
+
+        // Renames wait out the cooldown and free the old name
+        assert!(client
+            .try_claim_display_name(&board_id, &s("Alicia"), &alice)
+            .is_err());
+        env.ledger()
+            .set_timestamp(1_700_000_000 + DISPLAY_NAME_COOLDOWN);
+        client.claim_display_name(&board_id, &s("Alicia"), &alice);
+        client.claim_display_name(&board_id, &s("alice"), &bob);
+        assert_eq!(client.get_display_name(&board_id, &bob), Some(s("alice")));
+    }
+
+    #[test]
+    #[should_panic(expected = "Display name already taken")]
+    fn test_display_name_duplicate_rejected() {
+        let env = Env::default();
+        let (client, board_id, _) = setup_with_board(&env);
+        let name = String::from_str(&env, "carol_99");
+
+        client.claim_display_name(&board_id, &name, &Address::generate(&env));
+        client.claim_display_name(&board_id, &name, &Address::generate(&env));
+    }
+
+    #[test]
+    fn test_author_uses_display_name_without_profile_contract() {
+        let env = Env::default();
+        let (client, _, board_id, _) = setup_with_content(&env);
+        env.mock_all_auths();
+        let author = Address::generate(&env);
+        client.create_thread(&board_id, &String::from_str(&env, "Named"), &None, &author);
+
+        let path = String::from_str(&env, "/t/1");
+        let html = client.render(&board_id, &Some(path.clone()), &None, &None);
+        assert!(!render_contains(
+            &html,
+            "<span class=\"author\">Alice</span>"
+        ));
+
+        client.claim_display_name(&board_id, &String::from_str(&env, "Alice"), &author);
+        let html = client.render(&board_id, &Some(path), &None, &None);
+        assert!(render_contains(
+            &html,
+            "<span class=\"author\">Alice</span>"
+        ));
+    }
+
+    #[test]
+    fn test_block_list() {
+        let env = Env::default();
+        env.mock_all_auths();
+        let (client, _, _) = setup_with_board(&env);
+        let viewer = Address::generate(&env);
+        let spammer = Address::generate(&env);
+
+        client.block_user(&spammer, &viewer);
+        client.block_user(&spammer, &viewer);
+        assert_eq!(
+            client.list_blocked(&viewer),
+            Vec::from_array(&env, [spammer.clone()])
+        );
+        assert!(client.try_block_user(&viewer, &viewer).is_err());
+
+        client.unblock_user(&spammer, &viewer);
+        assert!(client.list_blocked(&viewer).is_empty());
+    }
+
+    #[test]
+    fn test_blocked_author_collapsed_unless_pinned() {
+        let env = Env::default();
+        let (client, _, board_id, thread_id) = setup_with_content(&env);
+        env.mock_all_auths();
+        let creator = client.get_thread(&board_id, &thread_id).unwrap().creator;
+        let viewer = Address::generate(&env);
+        client.block_user(&creator, &viewer);
+
+        // Only the blocking viewer sees the collapsed card
+        let html = client.render(&board_id, &None, &Some(viewer.clone()), &None);
+        assert!(render_contains(&html, "class=\"blocked-content\""));
+        let html = client.render(&board_id, &None, &None, &None);
+        assert!(!render_contains(&html, "class=\"blocked-content\""));
+
+        let path = Some(String::from_str(&env, "/t/0"));
+        let html = client.render(&board_id, &path, &Some(viewer.clone()), &None);
+        assert!(render_contains(&html, "class=\"blocked-content\""));
+
+        // Pinned threads stay visible
+        client.pin_thread(&board_id, &thread_id, &creator);
+        let html = client.render(&board_id, &None, &Some(viewer.clone()), &None);
+        assert!(!render_contains(&html, "class=\"blocked-content\""));
+        let html = client.render(&board_id, &path, &Some(viewer), &None);
+        assert!(!render_contains(&html, "class=\"blocked-content\""));
+    }
+
+    #[test]
+    fn test_notification_bell_and_inbox() {
+        let env = Env::default();
+        let (client, content, board_id, thread_id) = setup_with_content(&env);
+        let viewer = Address::generate(&env);
+        let html = client.render(&board_id, &None, &Some(viewer.clone()), &None);
+        assert!(render_contains(&html, "title=\"Notifications\">🔔</a>"));
+
+        content.add_notification(
+            &viewer,
+            &Notification {
+                id: 1,
+                kind: Symbol::new(&env, "thread_reply"),
+                board_id,
+                thread_id,
+                reply_id: Some(0),
+                actor: Address::generate(&env),
+                created_at: 0,
+            },
+        );
+        let html = client.render(&board_id, &None, &Some(viewer.clone()), &None);
+        assert!(render_contains(&html, "title=\"Notifications\">🔔 1</a>"));
+
+        let path = Some(String::from_str(&env, "/notifications"));
+        let html = client.render(&board_id, &path, &Some(viewer), &None);
+        assert!(render_contains(
+            &html,
+            "<div class=\"notification-item unread\">"
+        ));
+        assert!(render_contains(&html, " replied to your thread "));
+        assert!(render_contains(&html, "/t/0/r/0\">Hello</a>"));
+        assert!(render_contains(
+            &html,
+            "tx:@content:mark_read {&quot;up_to_id&quot;:1}"
+        ));
+    }
+
+    #[test]
+    fn test_recent_activity_interleaves_threads_and_replies() {
+        let env = Env::default();
+        let (client, content, board_id, thread_id) = setup_with_content(&env);
+        env.mock_all_auths();
+        let user = Address::generate(&env);
+        let reply_by = |thread_id: u64, created_at: u64, hidden: bool| {
+            let mut reply = mock_reply(&env, board_id, thread_id, 0, 0);
+            reply.creator = user.clone();
+            reply.created_at = created_at;
+            reply.is_hidden = hidden;
+            reply
+        };
+
+        env.ledger().set_timestamp(100);
+        let first = client.create_thread(&board_id, &String::from_str(&env, "First"), &None, &user);
+        let body = String::from_str(&env, "Nice point\nand more");
+        content.add_reply(&reply_by(thread_id, 150, false), &body);
+        env.ledger().set_timestamp(200);
+        let second =
+            client.create_thread(&board_id, &String::from_str(&env, "Second"), &None, &user);
+        content.add_reply(&reply_by(first, 250, true), &body);
+
+        let activity = client.list_recent_activity(&board_id, &user, &10);
+        let order: std::vec::Vec<(u64, Option<u64>)> = activity
+            .iter()
+            .map(|item| (item.thread_id, item.reply_id))
+            .collect();
+        assert_eq!(
+            order,
+            std::vec![
+                (first, Some(0)),
+                (second, None),
+                (thread_id, Some(0)),
+                (first, None)
+            ]
+        );
+        assert!(activity.get(0).unwrap().is_hidden);
+        assert_eq!(client.list_recent_activity(&board_id, &user, &2).len(), 2);
+
+        // Guests don't see the hidden reply
+        let mut address = [0u8; 56];
+        user.to_string().copy_into_slice(&mut address);
+        let address = core::str::from_utf8(&address).unwrap();
+        let path = String::from_str(&env, &std::format!("/u/{}/activity", address));
+        let html = client.render(&board_id, &Some(path), &None, &None);
+        assert_eq!(render_count(&html, "class=\"activity-item\""), 3);
+        assert!(render_contains(
+            &html,
+            "<span class=\"badge badge-reply\">reply</span>"
+        ));
+        assert!(render_contains(
+            &html,
+            "<div class=\"activity-excerpt\">Nice point…</div>"
+        ));
+        assert!(!render_contains(&html, "/t/1/r/0"));
+
+        // A malformed address renders "not found" rather than trapping
+        let mut bad = std::string::String::from(address);
+        bad.replace_range(10..11, if &address[10..11] == "A" { "B" } else { "A" });
+        let path = String::from_str(&env, &std::format!("/u/{}/activity", bad));
+        let html = client.render(&board_id, &Some(path), &None, &None);
+        assert!(render_contains(&html, "User not found."));
+    }
+
+    #[test]
+    fn test_index_user_threads_backfills_older_threads() {
+        let env = Env::default();
+        let (client, board_id, owner) = setup_with_board(&env);
+        let user = Address::generate(&env);
+        let mut ids = Vec::new(&env);
+        for title in ["One", "Two", "Three"] {
+            ids.push_back(client.create_thread(
+                &board_id,
+                &String::from_str(&env, title),
+                &None,
+                &user,
+            ));
+        }
+
+        // Threads started before the index existed
+        env.as_contract(&client.address, || {
+            env.storage()
+                .persistent()
+                .remove(&UserKey::UserThreads(board_id, user.clone()));
+        });
+        assert_eq!(client.list_recent_activity(&board_id, &user, &10).len(), 0);
+
+        client.index_user_threads(&board_id, &1, &5, &owner);
+        client.index_user_threads(&board_id, &0, &2, &owner);
+        let activity = client.list_recent_activity(&board_id, &user, &10);
+        assert_eq!(activity.len(), 3);
+        for i in 0..3 {
+            assert_eq!(activity.get(i).unwrap().thread_id, ids.get(2 - i).unwrap());
+        }
+    }
+
+    #[test]
+    fn test_user_thread_log_backfills_older_threads() {
+        let env = Env::default();
+        let (client, board_id, owner) = setup_with_board(&env);
+        let user = Address::generate(&env);
+        let other = Address::generate(&env);
+        let create = |title: &str, author: &Address| {
+            client.create_thread(&board_id, &String::from_str(&env, title), &None, author)
+        };
+        let first = create("One", &user);
+        let second = create("Two", &user);
+
+        // Threads started before the log existed
+        env.as_contract(&client.address, || {
+            let storage = env.storage().persistent();
+            storage.remove(&UserKey::UserThreadLog(board_id, user.clone(), 0));
+            storage.remove(&UserKey::UserThreadLogLen(board_id, user.clone()));
+            storage.remove(&BoardKey::ThreadLogFrom(board_id));
+        });
+        let third = create("Three", &user);
+        create("Elsewhere", &other);
+        assert_eq!(client.user_thread_log_len(&board_id, &user), 1);
+
+        // The backfill resumes where it stopped and adds nothing once done
+        assert_eq!(client.log_user_threads(&board_id, &1, &owner), 1);
+        assert_eq!(client.log_user_threads(&board_id, &10, &owner), 0);
+        assert_eq!(client.log_user_threads(&board_id, &10, &owner), 0);
+        let threads = client.list_user_thread_log(&board_id, &user, &0, &10);
+        assert_eq!(threads.len(), 3);
+        for (i, thread_id) in [third, first, second].into_iter().enumerate() {
+            assert_eq!(threads.get(i as u32).unwrap().id, thread_id);
+        }
+        let window = client.list_user_thread_log(&board_id, &user, &1, &1);
+        assert_eq!(window.len(), 1);
+        assert_eq!(window.get(0).unwrap().id, first);
+        assert_eq!(client.user_thread_log_len(&board_id, &other), 1);
+    }
+
+    #[test]
+    fn test_build_quote_escapes_textarea_close() {
+        let env = Env::default();
+        let body = Bytes::from_slice(&env, b"Hi\r\n</textarea><b>x</b> & more\n\n");
+        let quote = BoardsBoard::build_quote(&env, &body, 1000);
+        let expected: &[u8] = b"> Hi\n> &lt;/textarea>&lt;b>x&lt;/b> &amp; more\n\n";
+        assert_eq!(quote, Bytes::from_slice(&env, expected));
+    }
+
+    #[test]
+    fn test_build_quote_truncates_on_char_boundary() {
+        let env = Env::default();
+        let body = Bytes::from_slice(&env, "h\u{e9}llo".as_bytes());
+        let quote = BoardsBoard::build_quote(&env, &body, 2);
+        assert_eq!(quote, Bytes::from_slice(&env, b"> h...\n\n"));
+    }
+
+    #[test]
+    fn test_reply_flag_form_captures_reason() {
+        let env = Env::default();
+        let (client, content, board_id, thread_id) = setup_with_content(&env);
+        env.mock_all_auths();
+        client.set_permissions(&env.register(MockPermissions, ()));
+
+        content.add_reply(
+            &mock_reply(&env, board_id, thread_id, 0, 0),
+            &String::from_str(&env, "Buy cheap watches"),
+        );
+
+        let path = String::from_str(&env, "/t/0/r/0");
+        let viewer = Some(Address::generate(&env));
+        let html = client.render(&board_id, &Some(path), &viewer, &None);
+        assert!(render_contains(
+            &html,
+            "<select name=\"reason\"><option value=\"spam\">"
+        ));
+        assert!(render_contains(&html, "name=\"reply_id\" value=\"0\""));
+        assert!(render_contains(&html, "@content:flag_reply"));
+        assert!(!render_contains(&html, "\"reason\":\"\""));
+
+        // Guests get no flag form
+        let path = String::from_str(&env, "/t/0/r/0");
+        let html = client.render(&board_id, &Some(path), &None, &None);
+        assert!(!render_contains(&html, "flag-form"));
+    }
+
+    #[test]
+    fn test_quote_reply_form_keeps_content_inside_textarea() {
+        let env = Env::default();
+        let (client, content, board_id, thread_id) = setup_with_content(&env);
+        env.mock_all_auths();
+        client.set_permissions(&env.register(MockPermissions, ()));
+
+        content.add_reply(
+            &mock_reply(&env, board_id, thread_id, 0, 0),
+            &String::from_str(&env, "nice</textarea><img src=x>"),
+        );
+
+        let path = String::from_str(&env, "/t/0/r/0/reply");
+        let viewer = Some(Address::generate(&env));
+        let html = client.render(&board_id, &Some(path), &viewer, &None);
+        assert_eq!(render_count(&html, "</textarea>"), 1);
+        assert!(render_contains(&html, "> nice&lt;/textarea>&lt;img src=x>"));
+        assert!(!render_contains(&html, "<img"));
+    }
+
+    #[test]
+    fn test_create_thread_form_restores_escaped_draft() {
+        let env = Env::default();
+        let (client, content, board_id, _) = setup_with_content(&env);
+        env.mock_all_auths();
+        client.set_permissions(&env.register(MockPermissions, ()));
+
+        let user = Address::generate(&env);
+        let draft = Draft {
+            key: Symbol::new(&env, THREAD_DRAFT_KEY),
+            title: String::from_str(&env, "Say \"hi\"><b>"),
+            body: Bytes::from_slice(&env, b"long post</textarea><img src=x>"),
+            saved_at: 0,
+        };
+        content.save_draft(&board_id, &draft, &user);
+
+        let path = String::from_str(&env, "/new");
+        let html = client.render(&board_id, &Some(path), &Some(user), &None);
+        assert!(render_contains(&html, "Restore draft"));
+        assert!(render_contains(
+            &html,
+            "value=\"Say &quot;hi&quot;>&lt;b>\""
+        ));
+        assert!(render_contains(
+            &html,
+            ">long post&lt;/textarea>&lt;img src=x></textarea>"
+        ));
+        assert!(!render_contains(&html, "<img"));
+        assert!(render_contains(
+            &html,
+            "name=\"draft_key\" value=\"thread\""
+        ));
+
+        // Without a draft the form starts empty
+        let path = String::from_str(&env, "/new");
+        let html = client.render(
+            &board_id,
+            &Some(path),
+            &Some(Address::generate(&env)),
+            &None,
+        );
+        assert!(!render_contains(&html, "Restore draft"));
+    }
+
+    #[test]
+    fn test_edit_thread_form_escapes_title_attribute() {
+        let env = Env::default();
+        let (client, _, board_id, _) = setup_with_content(&env);
+
+        let author = Address::generate(&env);
+        let title = String::from_str(&env, "Hi\" autofocus onfocus=\"alert(1)");
+        let thread_id = client.create_thread(&board_id, &title, &None, &author);
+        assert_eq!(thread_id, 1);
+
+        let path = String::from_str(&env, "/t/1/edit");
+        let html = client.render(&board_id, &Some(path), &Some(author), &None);
+        assert!(render_contains(
+            &html,
+            "value=\"Hi&quot; autofocus onfocus=&quot;alert(1)\""
+        ));
+        assert!(!render_contains(&html, "onfocus=\"alert"));
+    }
+
+    #[test]
+    fn test_sanitize_color_rejects_style_injection() {
+        let env = Env::default();
+        let s = |v: &str| String::from_str(&env, v);
+        let sanitize = |v: &str| sanitize_color(&env, &s(v));
+
+        assert_eq!(sanitize("#ff4500"), Bytes::from_slice(&env, b"#ff4500"));
+        assert_eq!(sanitize("#FFF"), Bytes::from_slice(&env, b"#FFF"));
+        assert_eq!(sanitize("purple"), Bytes::from_slice(&env, b"purple"));
+        let inherit = Bytes::from_slice(&env, b"inherit");
+        assert_eq!(sanitize("red;background-image:url(x)"), inherit);
+        assert_eq!(sanitize("#fff\"><script>"), inherit);
+        assert_eq!(sanitize("#ggg"), inherit);
+
+        let name = BoardsBoard::escape_attr(&env, &s("<b onclick=\"x\">"));
+        assert_eq!(
+            name,
+            Bytes::from_slice(&env, b"&lt;b onclick=&quot;x&quot;>")
+        );
+    }
+
+    #[test]
+    #[should_panic(expected = "Flair colors must be hex (#rgb or #rrggbb)")]
+    fn test_create_flair_rejects_non_hex_color() {
+        let env = Env::default();
+        let (client, board_id, owner) = setup_with_board(&env);
+
+        client.create_flair(
+            &board_id,
+            &String::from_str(&env, "Spoiler"),
+            &String::from_str(&env, "red;background-image:url(https://evil)"),
+            &String::from_str(&env, "#000000"),
+            &false,
+            &false,
+            &owner,
+        );
+    }
+
+    #[test]
+    fn test_search_threads_matches_titles_case_insensitively() {
+        let env = Env::default();
+        let (client, board_id, owner) = setup_with_board(&env);
+        let s = |v: &str| String::from_str(&env, v);
+
+        for title in [
+            "Rust Async Tips",
+            "Cooking with rust",
+            "Gardening",
+            "Rusty tools",
+        ] {
+            client.create_thread(&board_id, &s(title), &None, &owner);
+        }
+        client.hide_thread(&board_id, &3, &owner);
+
+        // Newest first; the hidden "Rusty tools" thread is left out
+        let results = client.search_threads(&board_id, &s("RUST"), &0, &10, &None);
+        assert_eq!(results.len(), 2);
+        assert_eq!(results.get(0).unwrap().title, s("Cooking with rust"));
+        assert_eq!(results.get(1).unwrap().title, s("Rust Async Tips"));
+
+        let results = client.search_threads(&board_id, &s("sync t"), &0, &10, &None);
+        assert_eq!(results.len(), 1);
+        assert_eq!(results.get(0).unwrap().id, 0);
+
+        assert_eq!(
+            client
+                .search_threads(&board_id, &s("rake"), &0, &10, &None)
+                .len(),
+            0
+        );
+        assert_eq!(
+            client
+                .search_threads(&board_id, &s(""), &0, &10, &None)
+                .len(),
+            0
+        );
+    }
+
+    #[test]
+    fn test_search_threads_paginates_matches() {
+        let env = Env::default();
+        let (client, board_id, owner) = setup_with_board(&env);
+        let s = |v: &str| String::from_str(&env, v);
+
+        for title in ["Post A", "Other", "Post B", "Post C", "Post D", "Post E"] {
+            client.create_thread(&board_id, &s(title), &None, &owner);
+        }
+
+        let ids = |start: u32| {
+            let results = client.search_threads(&board_id, &s("post"), &start, &2, &None);
+            let mut ids = [u64::MAX; 2];
+            for (i, thread) in results.iter().enumerate() {
+                ids[i] = thread.id;
+            }
+            ids
+        };
+        assert_eq!(ids(0), [5, 4]);
+        assert_eq!(ids(2), [3, 2]);
+        assert_eq!(ids(4), [0, u64::MAX]);
+        assert_eq!(ids(5), [u64::MAX, u64::MAX]);
+    }
+
+    #[test]
+    fn test_search_page_decodes_and_escapes_query() {
+        let env = Env::default();
+        let (client, _, board_id, _) = setup_with_content(&env);
+        let author = Address::generate(&env);
+        client.create_thread(
+            &board_id,
+            &String::from_str(&env, "Async tips"),
+            &None,
+            &author,
+        );
+
+        let path = String::from_str(&env, "/search?q=+ASYNC+%22%3E");
+        let html = client.render(&board_id, &Some(path), &None, &None);
+        assert!(render_contains(&html, "value=\"ASYNC &quot;>\""));
+        assert!(render_contains(
+            &html,
+            "No thread titles match your search."
+        ));
+
+        let path = String::from_str(&env, "/search?q=async+TIPS");
+        let html = client.render(&board_id, &Some(path), &None, &None);
+        assert!(render_contains(&html, "Async tips"));
+        assert!(!render_contains(&html, "Hello"));
+    }
+
+    #[test]
+    fn test_crossposts_page_names_public_boards_only() {
+        let env = Env::default();
+        let (client, content, board_id, thread_id) = setup_with_content(&env);
+        env.mock_all_auths();
+
+        let creator = Address::generate(&env);
+        let s = |v: &str| String::from_str(&env, v);
+        let public_id =
+            client.create_board(&s("Rust Fans"), &s(""), &s("false"), &s("true"), &creator);
+        let secret_id =
+            client.create_board(&s("Hidden Lair"), &s(""), &s("true"), &s("false"), &creator);
+
+        for (dest, dest_thread) in [(public_id, 4u64), (secret_id, 9u64)] {
+            content.add_crosspost(
+                &CrosspostLocation {
+                    board_id: dest,
+                    thread_id: dest_thread,
+                    created_at: 0,
+                },
+                &CrosspostRef {
+                    original_board_id: board_id,
+                    original_thread_id: thread_id,
+                    original_title: s("Hello"),
+                    original_author: creator.clone(),
+                    crossposted_by: creator.clone(),
+                    crossposted_at: 0,
+                },
+            );
+        }
+
+        let path = String::from_str(&env, "/t/0/crossposts");
+        let html = client.render(&board_id, &Some(path), &None, &None);
+        assert!(render_contains(&html, ">Rust Fans</a>"));
+        assert!(render_contains(&html, "/t/4\">"));
+        assert!(render_contains(&html, "a private board"));
+        assert!(!render_contains(&html, "Hidden Lair"));
+        assert!(!render_contains(&html, "/t/9"));
+    }
+
+    #[test]
+    fn test_remove_crosspost_thread_soft_deletes() {
+        let env = Env::default();
+        let (client, content, board_id, thread_id) = setup_with_content(&env);
+
+        content.retract_crosspost(&client.address, &board_id, &thread_id);
+        assert!(client.get_thread(&board_id, &thread_id).unwrap().is_deleted);
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_remove_crosspost_thread_requires_content_contract() {
+        let env = Env::default();
+        let (client, _, board_id, thread_id) = setup_with_content(&env);
+        client.remove_crosspost_thread(&board_id, &thread_id);
+    }
+
+    #[test]
+    fn test_link_mentions_wraps_registered_usernames() {
+        let env = Env::default();
+        let profile = MockProfileClient::new(&env, &env.register(MockProfile, ()));
+        profile.register(&String::from_str(&env, "alice"), &Address::generate(&env));
+
+        let body = Bytes::from_slice(&env, b"hi @alice and @bob, mail a@alice");
+        let linked = BoardsBoard::link_mentions(&env, &body, &Some(profile.address.clone()));
+        let expected: &[u8] = b"hi [@alice](render:@profile:/u/alice) and @bob, mail a@alice";
+        assert_eq!(linked, Bytes::from_slice(&env, expected));
+
+        // Without a profile contract the content is left untouched
+        assert_eq!(BoardsBoard::link_mentions(&env, &body, &None), body);
+    }
+
+    #[test]
+    fn test_revisions_page_is_moderator_only() {
+        let env = Env::default();
+        let (client, _, board_id, _) = setup_with_content(&env);
+
+        let path = String::from_str(&env, "/t/0/revisions");
+        let html = client.render(
+            &board_id,
+            &Some(path),
+            &Some(Address::generate(&env)),
+            &None,
+        );
+        assert!(render_contains(
+            &html,
+            "You must be a moderator to view edit history."
+        ));
+
+        let escaped = escape_html(&env, &Bytes::from_slice(&env, b"a</pre>&b"));
+        assert_eq!(escaped, Bytes::from_slice(&env, b"a&lt;/pre>&amp;b"));
+    }
+
+    #[test]
+    fn test_default_sort_fallback_and_override() {
+        let env = Env::default();
+        let (client, board_id, owner) = setup_with_board(&env);
+
+        // No stored choice and no voting contract: newest first
+        assert!(client.get_default_sort(&board_id) == ThreadSort::New);
+
+        client.set_default_sort(&board_id, &ThreadSort::Top, &owner);
+        assert!(client.get_default_sort(&board_id) == ThreadSort::Top);
+    }
+
+    #[test]
+    fn test_parse_thread_sort() {
+        let env = Env::default();
+        let parse = |s: &[u8]| BoardsBoard::parse_thread_sort(&Bytes::from_slice(&env, s));
+
+        assert!(parse(b"active") == Some(ThreadSort::Active));
+        assert!(parse(b"hot") == Some(ThreadSort::Hot));
+        assert!(parse(b"controversial") == Some(ThreadSort::Controversial));
+        assert!(parse(b"oldest").is_none());
+        assert!(parse(b"").is_none());
+    }
+
+    #[test]
+    fn test_create_thread_without_flair() {
+        let env = Env::default();
+        let (client, board_id, _) = setup_with_board(&env);
+
+        let creator = Address::generate(&env);
+        let title = String::from_str(&env, "Hello World");
+
+        // Create thread without flair (None)
+        let thread_id = client.create_thread(&board_id, &title, &None, &creator);
+        assert_eq!(thread_id, 0);
+        assert_eq!(client.thread_count(&board_id), 1);
+
+        let thread = client.get_thread(&board_id, &thread_id).unwrap();
+        assert_eq!(thread.title, title);
+        assert_eq!(thread.creator, creator);
+        assert!(thread.flair_id.is_none());
+    }
+
+    #[test]
+    fn test_create_thread_with_optional_flair() {
+        let env = Env::default();
+        let (client, board_id, owner) = setup_with_board(&env);
+
+        // Create a flair first
+        let flair_name = String::from_str(&env, "Discussion");
+        let flair_color = String::from_str(&env, "#ffffff");
+        let flair_bg = String::from_str(&env, "#0000ff");
+        let flair_id = client.create_flair(
+            &board_id,
+            &flair_name,
+            &flair_color,
+            &flair_bg,
+            &false, // not required
+            &false, // not mod_only
+            &owner,
+        );
+
+        let creator = Address::generate(&env);
+        let title = String::from_str(&env, "Thread with flair");
+
+        // Create thread with flair (use flair_N format for form input)
+        let flair_id_str = String::from_str(&env, "flair_0"); // First flair ID is 0
+        let thread_id = client.create_thread(&board_id, &title, &Some(flair_id_str), &creator);
+
+        let thread = client.get_thread(&board_id, &thread_id).unwrap();
+        assert_eq!(thread.flair_id, Some(flair_id));
+    }
+
+    #[test]
+    #[should_panic(expected = "A flair is required for new posts on this board")]
+    fn test_required_flair_enforcement() {
+        let env = Env::default();
+        let (client, board_id, owner) = setup_with_board(&env);
+
+        // Create a required flair
+        let flair_name = String::from_str(&env, "Required Flair");
+        let flair_color = String::from_str(&env, "#ffffff");
+        let flair_bg = String::from_str(&env, "#ff0000");
+        client.create_flair(
+            &board_id,
+            &flair_name,
+            &flair_color,
+            &flair_bg,
+            &true,  // required!
+            &false, // not mod_only
+            &owner,
+        );
+
+        let creator = Address::generate(&env);
+        let title = String::from_str(&env, "Thread without flair");
+
+        // This should panic because flair is required but not provided
+        client.create_thread(&board_id, &title, &None, &creator);
+    }
+
+    #[test]
+    fn test_required_flair_with_selection() {
+        let env = Env::default();
+        let (client, board_id, owner) = setup_with_board(&env);
+
+        // Create a required flair
+        let flair_name = String::from_str(&env, "Required Flair");
+        let flair_color = String::from_str(&env, "#ffffff");
+        let flair_bg = String::from_str(&env, "#ff0000");
+        let flair_id = client.create_flair(
+            &board_id,
+            &flair_name,
+            &flair_color,
+            &flair_bg,
+            &true,  // required
+            &false, // not mod_only
+            &owner,
+        );
+
+        let creator = Address::generate(&env);
+        let title = String::from_str(&env, "Thread with required flair");
+
+        // This should succeed because we provide the required flair (use flair_N format)
+        let flair_id_str = String::from_str(&env, "flair_0"); // First flair ID is 0
+        let thread_id = client.create_thread(&board_id, &title, &Some(flair_id_str), &creator);
+
+        let thread = client.get_thread(&board_id, &thread_id).unwrap();
+        assert_eq!(thread.flair_id, Some(flair_id));
+    }
+
+    #[test]
+    #[should_panic(expected = "Selected flair does not exist")]
+    fn test_invalid_flair_id() {
+        let env = Env::default();
+        let (client, board_id, _) = setup_with_board(&env);
+
+        let creator = Address::generate(&env);
+        let title = String::from_str(&env, "Thread with invalid flair");
+
+        // Try to use a flair ID that doesn't exist (use flair_N format)
+        let invalid_flair = String::from_str(&env, "flair_999");
+        client.create_thread(&board_id, &title, &Some(invalid_flair), &creator);
+    }
+
+    #[test]
+    fn test_pin_and_lock() {
+        let env = Env::default();
+        let (client, board_id, _) = setup_with_board(&env);
+
+        let creator = Address::generate(&env);
+        let moderator = Address::generate(&env);
+        let title = String::from_str(&env, "Important Announcement");
+
+        let thread_id = client.create_thread(&board_id, &title, &None, &creator);
+
+        // Pin thread
+        client.pin_thread(&board_id, &thread_id, &moderator);
+        let thread = client.get_thread(&board_id, &thread_id).unwrap();
+        assert!(thread.is_pinned);
+
+        let pinned = client.get_pinned_threads(&board_id);
+        assert_eq!(pinned.len(), 1);
+
+        // Lock thread
+        client.lock_thread(&board_id, &thread_id, &moderator);
+        let thread = client.get_thread(&board_id, &thread_id).unwrap();
+        assert!(thread.is_locked);
+    }
+
+    #[test]
+    fn test_hide_thread() {
+        let env = Env::default();
+        let (client, board_id, _) = setup_with_board(&env);
+
+        let creator = Address::generate(&env);
+        let moderator = Address::generate(&env);
+        let title = String::from_str(&env, "Test Thread");
+
+        let thread_id = client.create_thread(&board_id, &title, &None, &creator);
+
+        // Hide thread
+        client.hide_thread(&board_id, &thread_id, &moderator);
+        let thread = client.get_thread(&board_id, &thread_id).unwrap();
+        assert!(thread.is_hidden);
+
+        // Unhide thread
+        client.unhide_thread(&board_id, &thread_id, &moderator);
+        let thread = client.get_thread(&board_id, &thread_id).unwrap();
+        assert!(!thread.is_hidden);
+    }
+
+    #[test]
+    fn test_get_removed_threads() {
+        let env = Env::default();
+        let (client, board_id, _) = setup_with_board(&env);
+
+        let creator = Address::generate(&env);
+        let moderator = Address::generate(&env);
+        let title = String::from_str(&env, "Test Thread");
+        let kept = client.create_thread(&board_id, &title, &None, &creator);
+        let hidden = client.create_thread(&board_id, &title, &None, &creator);
+        let deleted = client.create_thread(&board_id, &title, &None, &creator);
+        client.hide_thread(&board_id, &hidden, &moderator);
+        client.delete_thread(&board_id, &deleted, &creator);
+
+        let threads = Vec::from_array(
+            &env,
+            [
+                (board_id, deleted),
+                (board_id, kept),
+                (board_id, hidden),
+                (board_id, 99),
+            ],
+        );
+        assert_eq!(
+            client.get_removed_threads(&threads),
+            Vec::from_array(
+                &env,
+                [(board_id, deleted), (board_id, hidden), (board_id, 99)]
+            )
+        );
+    }
+
+    #[test]
+    fn test_list_threads() {
+        let env = Env::default();
+        let (client, board_id, _) = setup_with_board(&env);
+
+        let creator = Address::generate(&env);
+
+        // Create multiple threads
+        let titles = ["Thread 0", "Thread 1", "Thread 2", "Thread 3", "Thread 4"];
+        for title_str in titles.iter() {
+            let title = String::from_str(&env, title_str);
+            client.create_thread(&board_id, &title, &None, &creator);
+        }
+
+        // List threads (should return newest first)
+        let threads = client.list_threads(&board_id, &0, &3);
+        assert_eq!(threads.len(), 3);
+        // First thread should be the newest (id 4)
+        assert_eq!(threads.get(0).unwrap().id, 4);
+
+        // Get remaining threads
+        let more_threads = client.list_threads(&board_id, &3, &10);
+        assert_eq!(more_threads.len(), 2);
+    }
+
+    #[test]
+    fn test_get_config() {
+        let env = Env::default();
+        env.mock_all_auths();
+
+        let contract_id = env.register(BoardsBoard, ());
+        let client = BoardsBoardClient::new(&env, &contract_id);
+
+        let registry = Address::generate(&env);
+        client.init(&registry, &None, &None, &None);
+
+        let name = String::from_str(&env, "Private Board");
+        let desc = String::from_str(&env, "Secret discussions");
+        let is_private = String::from_str(&env, "true");
+        let is_listed = String::from_str(&env, "false");
+        let caller = Address::generate(&env);
+
+        let board_id = client.create_board(&name, &desc, &is_private, &is_listed, &caller);
+
+        let config = client.get_config(&board_id);
+        assert_eq!(config.name, name);
+        assert_eq!(config.description, desc);
+        assert!(config.is_private);
+        assert!(!config.is_readonly);
+    }
+
+    #[test]
+    fn test_get_nonexistent_thread() {
+        let env = Env::default();
+        let (client, board_id, _) = setup_with_board(&env);
+
+        // Get thread that doesn't exist
+        let thread = client.get_thread(&board_id, &999);
+        assert!(thread.is_none());
+    }
+
+    #[test]
+    fn test_increment_reply_count() {
+        let env = Env::default();
+        let (client, board_id, _) = setup_with_board(&env);
+
+        let creator = Address::generate(&env);
+        let title = String::from_str(&env, "Test Thread");
+        let thread_id = client.create_thread(&board_id, &title, &None, &creator);
+
+        // Initially 0 replies
+        assert_eq!(
+            client
+                .get_thread(&board_id, &thread_id)
+                .unwrap()
+                .reply_count,
+            0
+        );
+
+        // Increment reply count
+        client.increment_reply_count(&board_id, &thread_id);
+        assert_eq!(
+            client
+                .get_thread(&board_id, &thread_id)
+                .unwrap()
+                .reply_count,
+            1
+        );
+
+        client.increment_reply_count(&board_id, &thread_id);
+        assert_eq!(
+            client
+                .get_thread(&board_id, &thread_id)
+                .unwrap()
+                .reply_count,
+            2
+        );
+    }
+
+    #[test]
+    fn test_thread_defaults() {
+        let env = Env::default();
+        let (client, board_id, _) = setup_with_board(&env);
+
+        let creator = Address::generate(&env);
+        let title = String::from_str(&env, "New Thread");
+        let thread_id = client.create_thread(&board_id, &title, &None, &creator);
+
+        let thread = client.get_thread(&board_id, &thread_id).unwrap();
+
+        // Default values
+        assert_eq!(thread.reply_count, 0);
+        assert!(!thread.is_locked);
+        assert!(!thread.is_pinned);
+        assert!(!thread.is_hidden);
+        assert!(!thread.is_deleted);
+        assert!(thread.flair_id.is_none());
+    }
+
+    #[test]
+    fn test_delete_thread() {
+        let env = Env::default();
+        let (client, board_id, _) = setup_with_board(&env);
+
+        let creator = Address::generate(&env);
+        let title = String::from_str(&env, "Thread to Delete");
+        let thread_id = client.create_thread(&board_id, &title, &None, &creator);
+
+        // Initially not deleted
+        let thread = client.get_thread(&board_id, &thread_id).unwrap();
+        assert!(!thread.is_deleted);
+
+        // Delete thread (author can delete)
+        client.delete_thread(&board_id, &thread_id, &creator);
+        let deleted_thread = client.get_thread(&board_id, &thread_id).unwrap();
+        assert!(deleted_thread.is_deleted);
+    }
+
+    // ========================================================================
+    // Slug Tests
+    // ========================================================================
+
+    #[test]
+    fn test_create_board_with_explicit_slug() {
+        let env = Env::default();
+        env.mock_all_auths();
+
+        let contract_id = env.register(BoardsBoard, ());
+        let client = BoardsBoardClient::new(&env, &contract_id);
+
+        let registry = Address::generate(&env);
+        client.init(&registry, &None, &None, &None);
+
+        let name = String::from_str(&env, "Test Board");
+        let desc = String::from_str(&env, "A test board");
+        let is_private = String::from_str(&env, "false");
+        let is_listed = String::from_str(&env, "true");
+        let slug = String::from_str(&env, "test-board");
+        let caller = Address::generate(&env);
+
+        let board_id = client.create_board_with_slug(
+            &name,
+            &desc,
+            &is_private,
+            &is_listed,
+            &Some(slug.clone()),
+            &caller,
+        );
+        assert_eq!(board_id, 0);
+
+        let board = client.get_board(&board_id).unwrap();
+        assert_eq!(board.name, name);
+        assert_eq!(board.slug, slug);
+    }
+
+    #[test]
+    fn test_create_board_auto_generates_slug() {
+        let env = Env::default();
+        env.mock_all_auths();
+
+        let contract_id = env.register(BoardsBoard, ());
+        let client = BoardsBoardClient::new(&env, &contract_id);
+
+        let registry = Address::generate(&env);
+        client.init(&registry, &None, &None, &None);
+
+        let name = String::from_str(&env, "My Awesome Board");
+        let desc = String::from_str(&env, "Auto-generated slug test");
+        let is_private = String::from_str(&env, "false");
+        let is_listed = String::from_str(&env, "true");
+        let caller = Address::generate(&env);
+
+        // Pass None for slug to auto-generate
+        let board_id =
+            client.create_board_with_slug(&name, &desc, &is_private, &is_listed, &None, &caller);
+
+        let board = client.get_board(&board_id).unwrap();
+        // Slug should be derived from name: "my-awesome-board"
+        let expected_slug = String::from_str(&env, "my-awesome-board");
+        assert_eq!(board.slug, expected_slug);
+    }
+
+    #[test]
+    fn test_get_board_by_slug() {
+        let env = Env::default();
+        env.mock_all_auths();
+
+        let contract_id = env.register(BoardsBoard, ());
+        let client = BoardsBoardClient::new(&env, &contract_id);
+
+        let registry = Address::generate(&env);
+        client.init(&registry, &None, &None, &None);
+
+        let name = String::from_str(&env, "Lookup Test");
+        let desc = String::from_str(&env, "Test slug lookup");
+        let is_private = String::from_str(&env, "false");
+        let is_listed = String::from_str(&env, "true");
+        let slug = String::from_str(&env, "lookup-test");
+        let caller = Address::generate(&env);
+
+        let board_id = client.create_board_with_slug(
+            &name,
+            &desc,
+            &is_private,
+            &is_listed,
+            &Some(slug.clone()),
+            &caller,
+        );
+
+        // Look up by slug
+        let found_board = client.get_board_by_slug(&slug);
+        assert!(found_board.is_some());
+        assert_eq!(found_board.unwrap().id, board_id);
+
+        // Look up by ID should also work
+        let found_id = client.get_board_id_by_slug(&slug);
+        assert_eq!(found_id, Some(board_id));
+    }
+
+    #[test]
+    fn test_slug_uniqueness_adds_suffix() {
+        let env = Env::default();
+        env.mock_all_auths();
+
+        let contract_id = env.register(BoardsBoard, ());
+        let client = BoardsBoardClient::new(&env, &contract_id);
+
+        let registry = Address::generate(&env);
+        client.init(&registry, &None, &None, &None);
+
+        let desc = String::from_str(&env, "Description");
+        let is_private = String::from_str(&env, "false");
+        let is_listed = String::from_str(&env, "true");
+        let caller = Address::generate(&env);
+
+        // Create first board with slug "general"
+        let name1 = String::from_str(&env, "General");
+        let slug1 = String::from_str(&env, "general");
+        let board1_id = client.create_board_with_slug(
+            &name1,
+            &desc,
+            &is_private,
+            &is_listed,
+            &Some(slug1.clone()),
+            &caller,
+        );
+        let board1 = client.get_board(&board1_id).unwrap();
+        assert_eq!(board1.slug, slug1);
+
+        // Create second board with same slug - should get suffix
+        let name2 = String::from_str(&env, "General 2");
+        let board2_id = client.create_board_with_slug(
+            &name2,
+            &desc,
+            &is_private,
+            &is_listed,
+            &Some(slug1.clone()),
+            &caller,
+        );
+        let board2 = client.get_board(&board2_id).unwrap();
+        // Slug should NOT equal "general" - it should have a suffix
+        assert!(board2.slug != slug1);
+        // But should start with "general-"
+        // We can't easily check prefix in Soroban, so just verify they're different boards
+        assert_ne!(board1_id, board2_id);
     }
 
-    /// Build a return path for the current thread view (using slugs)
-    fn build_thread_return_path(
-        env: &Env,
-        board_id: u64,
-        board_slug: &String,
-        thread_id: u64,
-    ) -> Bytes {
-        // Build cross-contract return path: @main:{base_path}/t/{thread_id}
-        let mut path = Bytes::from_slice(env, b"@main:");
-        path.append(&Self::build_board_base_path(env, board_id, board_slug));
-        path.append(&Bytes::from_slice(env, b"/t/"));
-        path.append(&soroban_render_sdk::bytes::u32_to_bytes(
-            env,
-            thread_id as u32,
-        ));
-        path
-    }
+    #[test]
+    #[should_panic(expected = "Board slug must be 3-30 characters")]
+    fn test_slug_validation_too_short() {
+        let env = Env::default();
+        env.mock_all_auths();
 
-    /// Truncate an address for display
-    fn truncate_address(env: &Env, address: &Address) -> Bytes {
-        let addr_string = address.to_string();
-        let full_bytes = soroban_render_sdk::bytes::string_to_bytes(env, &addr_string);
-        let len = full_bytes.len();
+        let contract_id = env.register(BoardsBoard, ());
+        let client = BoardsBoardClient::new(&env, &contract_id);
 
-        if len <= 12 {
-            return full_bytes;
-        }
+        let registry = Address::generate(&env);
+        client.init(&registry, &None, &None, &None);
 
-        let mut result = Bytes::new(env);
+        let name = String::from_str(&env, "Short");
+        let desc = String::from_str(&env, "Description");
+        let is_private = String::from_str(&env, "false");
+        let is_listed = String::from_str(&env, "true");
+        let slug = String::from_str(&env, "ab"); // Too short - 2 chars
+        let caller = Address::generate(&env);
 
-        // First 4 characters
-        for i in 0..4 {
-            if let Some(c) = full_bytes.get(i) {
-                result.push_back(c);
-            }
-        }
+        // This should panic
+        client.create_board_with_slug(&name, &desc, &is_private, &is_listed, &Some(slug), &caller);
+    }
 
-        // Ellipsis
-        result.push_back(b'.');
-        result.push_back(b'.');
-        result.push_back(b'.');
+    #[test]
+    #[should_panic(expected = "Board slug must start with lowercase letter")]
+    fn test_slug_validation_must_start_with_letter() {
+        let env = Env::default();
+        env.mock_all_auths();
 
-        // Last 4 characters
-        for i in (len - 4)..len {
-            if let Some(c) = full_bytes.get(i) {
-                result.push_back(c);
-            }
-        }
+        let contract_id = env.register(BoardsBoard, ());
+        let client = BoardsBoardClient::new(&env, &contract_id);
 
-        result
-    }
+        let registry = Address::generate(&env);
+        client.init(&registry, &None, &None, &None);
 
-    /// Format a Unix timestamp as a human-readable date string.
-    /// Returns "YYYY-MM-DD HH:MM UTC" format.
-    fn format_timestamp(env: &Env, timestamp: u64) -> Bytes {
-        // Handle legacy ledger sequence numbers (small values)
-        // Unix timestamps for 2024+ are ~1700000000+
-        if timestamp < 1_000_000_000 {
-            // This is likely a ledger sequence, not a timestamp
-            let mut result = Bytes::from_slice(env, b"Ledger ");
-            result.append(&u64_to_bytes(env, timestamp));
-            return result;
-        }
+        let name = String::from_str(&env, "Numbers");
+        let desc = String::from_str(&env, "Description");
+        let is_private = String::from_str(&env, "false");
+        let is_listed = String::from_str(&env, "true");
+        let slug = String::from_str(&env, "123-board"); // Starts with number
+        let caller = Address::generate(&env);
 
-        // Convert Unix timestamp to date components
-        let total_seconds = timestamp;
-        let total_minutes = total_seconds / 60;
-        let total_hours = total_minutes / 60;
-        let total_days = total_hours / 24;
+        // This should panic
+        client.create_board_with_slug(&name, &desc, &is_private, &is_listed, &Some(slug), &caller);
+    }
 
-        let minutes = (total_minutes % 60) as u8;
-        let hours = (total_hours % 24) as u8;
+    #[test]
+    fn test_board_metadata_includes_slug() {
+        let env = Env::default();
+        env.mock_all_auths();
 
-        // Calculate year, month, day from days since epoch (Jan 1, 1970)
-        let (year, month, day) = Self::days_to_date(total_days as i64);
+        let contract_id = env.register(BoardsBoard, ());
+        let client = BoardsBoardClient::new(&env, &contract_id);
 
-        // Format: "YYYY-MM-DD HH:MM UTC"
-        let mut buffer = [0u8; 20];
-
-        // Year (4 digits)
-        buffer[0] = b'0' + ((year / 1000) % 10) as u8;
-        buffer[1] = b'0' + ((year / 100) % 10) as u8;
-        buffer[2] = b'0' + ((year / 10) % 10) as u8;
-        buffer[3] = b'0' + (year % 10) as u8;
-        buffer[4] = b'-';
-
-        // Month (2 digits)
-        buffer[5] = b'0' + ((month / 10) % 10);
-        buffer[6] = b'0' + (month % 10);
-        buffer[7] = b'-';
-
-        // Day (2 digits)
-        buffer[8] = b'0' + ((day / 10) % 10);
-        buffer[9] = b'0' + (day % 10);
-        buffer[10] = b' ';
-
-        // Hour (2 digits)
-        buffer[11] = b'0' + ((hours / 10) % 10);
-        buffer[12] = b'0' + (hours % 10);
-        buffer[13] = b':';
-
-        // Minute (2 digits)
-        buffer[14] = b'0' + ((minutes / 10) % 10);
-        buffer[15] = b'0' + (minutes % 10);
-        buffer[16] = b' ';
-
-        // UTC
-        buffer[17] = b'U';
-        buffer[18] = b'T';
-        buffer[19] = b'C';
+        let registry = Address::generate(&env);
+        client.init(&registry, &None, &None, &None);
 
-        Bytes::from_slice(env, &buffer)
-    }
+        let name = String::from_str(&env, "Meta Test");
+        let desc = String::from_str(&env, "Testing metadata");
+        let is_private = String::from_str(&env, "false");
+        let is_listed = String::from_str(&env, "true");
+        let slug = String::from_str(&env, "meta-test");
+        let caller = Address::generate(&env);
 
-    /// Convert days since Unix epoch to (year, month, day).
-    /// Algorithm based on Howard Hinnant's date algorithms.
-    fn days_to_date(days: i64) -> (i32, u8, u8) {
-        let z = days + 719468;
-        let era = if z >= 0 { z } else { z - 146096 } / 146097;
-        let doe = (z - era * 146097) as u32; // day of era
-        let yoe = (doe - doe / 1460 + doe / 36524 - doe / 146096) / 365; // year of era
-        let y = yoe as i64 + era * 400;
-        let doy = doe - (365 * yoe + yoe / 4 - yoe / 100); // day of year
-        let mp = (5 * doy + 2) / 153; // month index
-        let d = doy - (153 * mp + 2) / 5 + 1;
-        let m = if mp < 10 { mp + 3 } else { mp - 9 };
-        let year = if m <= 2 { y + 1 } else { y };
+        let board_id = client.create_board_with_slug(
+            &name,
+            &desc,
+            &is_private,
+            &is_listed,
+            &Some(slug.clone()),
+            &caller,
+        );
 
-        (year as i32, m as u8, d as u8)
+        // Get board and verify slug is in metadata
+        let board = client.get_board(&board_id).unwrap();
+        assert_eq!(board.id, board_id);
+        assert_eq!(board.slug, slug);
+        assert_eq!(board.name, name);
+        assert_eq!(board.creator, caller);
     }
 
-    // =========================================================================
-    // Slug Helper Functions
-    // =========================================================================
+    #[test]
+    fn test_subscribe_and_list_subscriptions() {
+        let env = Env::default();
+        let (client, board_id, _) = setup_with_board(&env);
 
-    /// Validate slug format: 3-30 chars, starts with lowercase letter,
-    /// contains only lowercase letters, numbers, and hyphens, cannot end with hyphen.
-    fn validate_slug(env: &Env, slug: &String) {
-        let len = slug.len() as usize;
-        if !(3..=30).contains(&len) {
-            panic!("Board slug must be 3-30 characters");
-        }
+        let creator = Address::generate(&env);
+        let t0 = client.create_thread(&board_id, &String::from_str(&env, "First"), &None, &creator);
+        let t1 = client.create_thread(
+            &board_id,
+            &String::from_str(&env, "Second"),
+            &None,
+            &creator,
+        );
 
-        let mut buf = [0u8; 30];
-        let copy_len = core::cmp::min(len, 30);
-        slug.copy_into_slice(&mut buf[..copy_len]);
+        let user = Address::generate(&env);
+        client.subscribe(&board_id, &t0, &user);
+        client.subscribe(&board_id, &t1, &user);
+        // Subscribing twice is idempotent
+        client.subscribe(&board_id, &t0, &user);
+
+        assert!(client.is_subscribed(&board_id, &t0, &user));
+        assert_eq!(client.get_subscriber_count(&board_id, &t0), 1);
+        assert_eq!(client.get_subscribers(&board_id, &t0, &0, &10).len(), 1);
+
+        let subs = client.list_subscriptions(&board_id, &user, &0, &10);
+        assert_eq!(subs.len(), 2);
+        assert_eq!(subs.get(0).unwrap().id, t0);
+        assert_eq!(subs.get(1).unwrap().id, t1);
+
+        // Deleted threads are skipped
+        client.delete_thread(&board_id, &t0, &creator);
+        let subs = client.list_subscriptions(&board_id, &user, &0, &10);
+        assert_eq!(subs.len(), 1);
+        assert_eq!(subs.get(0).unwrap().id, t1);
+    }
 
-        // First character must be lowercase letter
-        let first = buf[0];
-        if !first.is_ascii_lowercase() {
-            panic!("Board slug must start with lowercase letter");
-        }
+    #[test]
+    fn test_subscriptions_are_capped() {
+        let env = Env::default();
+        let (client, board_id, _) = setup_with_board(&env);
 
-        // All characters must be lowercase alphanumeric or hyphen
-        for &c in buf.iter().take(copy_len) {
-            let valid = c.is_ascii_lowercase() || c.is_ascii_digit() || c == b'-';
-            if !valid {
-                panic!("Board slug can only contain lowercase letters, numbers, and hyphens");
-            }
+        let creator = Address::generate(&env);
+        let title = String::from_str(&env, "Topic");
+        let user = Address::generate(&env);
+        for _ in 0..MAX_SUBSCRIPTIONS {
+            let thread_id = client.create_thread(&board_id, &title, &None, &creator);
+            client.subscribe(&board_id, &thread_id, &user);
         }
 
-        // Cannot end with hyphen
-        if buf[copy_len - 1] == b'-' {
-            panic!("Board slug cannot end with hyphen");
-        }
+        let extra = client.create_thread(&board_id, &title, &None, &creator);
+        assert!(client.try_subscribe(&board_id, &extra, &user).is_err());
+        assert!(!client.is_subscribed(&board_id, &extra, &user));
 
-        let _ = env;
+        // Unsubscribing frees a slot
+        client.unsubscribe(&board_id, &0, &user);
+        client.subscribe(&board_id, &extra, &user);
+        assert!(client.is_subscribed(&board_id, &extra, &user));
     }
 
-    /// Generate a slug from a board name.
-    /// Converts to lowercase, replaces spaces and special chars with hyphens,
-    /// collapses multiple hyphens, trims leading/trailing hyphens.
-    fn generate_slug_from_name(env: &Env, name: &String) -> String {
-        let len = name.len() as usize;
-        if len == 0 {
-            return String::from_str(env, "board");
+    #[test]
+    fn test_subscribers_are_chunked() {
+        let env = Env::default();
+        let (client, board_id, _) = setup_with_board(&env);
+
+        let creator = Address::generate(&env);
+        let thread_id =
+            client.create_thread(&board_id, &String::from_str(&env, "Topic"), &None, &creator);
+
+        // A list from before the chunked index is still read, and moved on the next subscribe
+        let legacy = Vec::from_array(&env, [Address::generate(&env), Address::generate(&env)]);
+        env.as_contract(&client.address, || {
+            env.storage()
+                .persistent()
+                .set(&ThreadKey::Subscribers(board_id, thread_id), &legacy);
+        });
+        assert_eq!(client.get_subscriber_count(&board_id, &thread_id), 2);
+
+        let total = SUBSCRIBER_CHUNK + 10;
+        let mut users = std::vec::Vec::new();
+        for _ in 2..total {
+            let user = Address::generate(&env);
+            client.subscribe(&board_id, &thread_id, &user);
+            users.push(user);
+        }
+        assert_eq!(client.get_subscriber_count(&board_id, &thread_id), total);
+        assert_eq!(
+            client
+                .get_subscribers(&board_id, &thread_id, &0, &SUBSCRIBER_CHUNK)
+                .len(),
+            SUBSCRIBER_CHUNK
+        );
+        let rest = client.get_subscribers(&board_id, &thread_id, &SUBSCRIBER_CHUNK, &100);
+        assert_eq!(rest.len(), 10);
+        assert_eq!(rest.get(9).unwrap(), users[users.len() - 1]);
+        let first = client.get_subscribers(&board_id, &thread_id, &0, &2);
+        assert_eq!(first.get(0).unwrap(), legacy.get(0).unwrap());
+
+        // Removing from the first chunk moves the last subscriber into the gap
+        client.unsubscribe(&board_id, &thread_id, &users[1]);
+        let all = client.get_subscribers(&board_id, &thread_id, &0, &100);
+        assert_eq!(all.len(), total - 1);
+        assert!(!all.contains(&users[1]));
+        assert_eq!(all.get(3).unwrap(), users[users.len() - 1]);
+
+        // The moved subscriber can still be removed
+        client.unsubscribe(&board_id, &thread_id, &users[users.len() - 1]);
+        for user in users.iter() {
+            client.unsubscribe(&board_id, &thread_id, user);
         }
+        let remaining = client.get_subscribers(&board_id, &thread_id, &0, &100);
+        assert_eq!(remaining, legacy);
+    }
 
-        let mut name_buf = [0u8; 64];
-        let copy_len = core::cmp::min(len, 64);
-        name.copy_into_slice(&mut name_buf[..copy_len]);
+    #[test]
+    fn test_unsubscribe() {
+        let env = Env::default();
+        let (client, board_id, _) = setup_with_board(&env);
 
-        let mut slug_buf = [0u8; 30];
-        let mut slug_len = 0usize;
-        let mut last_was_hyphen = true; // Start true to skip leading hyphens
+        let creator = Address::generate(&env);
+        let thread_id =
+            client.create_thread(&board_id, &String::from_str(&env, "Topic"), &None, &creator);
 
-        for &c in name_buf.iter().take(copy_len) {
-            if slug_len >= 30 {
-                break;
-            }
+        let user = Address::generate(&env);
+        // Unsubscribing without a subscription is a no-op
+        client.unsubscribe(&board_id, &thread_id, &user);
 
-            // Convert to lowercase letter
-            if c.is_ascii_uppercase() {
-                slug_buf[slug_len] = c + 32; // to lowercase
-                slug_len += 1;
-                last_was_hyphen = false;
-            } else if c.is_ascii_lowercase() {
-                slug_buf[slug_len] = c;
-                slug_len += 1;
-                last_was_hyphen = false;
-            } else if c.is_ascii_digit() {
-                // Numbers are OK but not as first char
-                if slug_len > 0 {
-                    slug_buf[slug_len] = c;
-                    slug_len += 1;
-                    last_was_hyphen = false;
-                }
-            } else if !last_was_hyphen && slug_len > 0 {
-                // Replace spaces, underscores, etc with hyphen (collapse multiple)
-                slug_buf[slug_len] = b'-';
-                slug_len += 1;
-                last_was_hyphen = true;
-            }
-        }
+        client.subscribe(&board_id, &thread_id, &user);
+        client.unsubscribe(&board_id, &thread_id, &user);
 
-        // Trim trailing hyphen
-        while slug_len > 0 && slug_buf[slug_len - 1] == b'-' {
-            slug_len -= 1;
-        }
+        assert!(!client.is_subscribed(&board_id, &thread_id, &user));
+        assert_eq!(client.get_subscriber_count(&board_id, &thread_id), 0);
+        assert_eq!(
+            client.get_subscribers(&board_id, &thread_id, &0, &10).len(),
+            0
+        );
+        assert_eq!(
+            client.list_subscriptions(&board_id, &user, &0, &10).len(),
+            0
+        );
+    }
 
-        // Ensure minimum length
-        if slug_len < 3 {
-            return String::from_str(env, "board");
-        }
+    #[test]
+    #[should_panic(expected = "Thread not found")]
+    fn test_subscribe_missing_thread() {
+        let env = Env::default();
+        let (client, board_id, _) = setup_with_board(&env);
 
-        // Build String from slice
-        String::from_str(
-            env,
-            core::str::from_utf8(&slug_buf[..slug_len]).unwrap_or("board"),
-        )
+        let user = Address::generate(&env);
+        client.subscribe(&board_id, &42, &user);
     }
 
-    /// Generate a random 4-character suffix for slug conflict resolution.
-    fn generate_random_suffix(env: &Env) -> String {
-        // Use ledger timestamp and sequence for pseudo-randomness
-        let timestamp = env.ledger().timestamp();
-        let sequence = env.ledger().sequence();
-        let combined = timestamp.wrapping_mul(31).wrapping_add(sequence as u64);
+    #[test]
+    fn test_can_accept_reply_state_transitions() {
+        let env = Env::default();
+        let (client, board_id, owner) = setup_with_board(&env);
 
-        let chars = b"abcdefghijklmnopqrstuvwxyz0123456789";
-        let mut suffix = [0u8; 4];
-        let mut val = combined;
-        for s in &mut suffix {
-            *s = chars[(val % 36) as usize];
-            val /= 36;
-        }
+        let creator = Address::generate(&env);
+        let thread_id =
+            client.create_thread(&board_id, &String::from_str(&env, "Topic"), &None, &creator);
 
-        String::from_str(env, core::str::from_utf8(&suffix).unwrap_or("0000"))
-    }
+        // Missing thread
+        assert!(!client.can_accept_reply(&board_id, &99));
+        assert_eq!(
+            client.reply_rejection_reason(&board_id, &99),
+            Some(Symbol::new(&env, "not_found"))
+        );
 
-    /// Check if a slug is available for standalone boards.
-    fn is_slug_available(env: &Env, slug: &String) -> bool {
-        !env.storage()
-            .persistent()
-            .has(&BoardKey::BoardBySlug(slug.clone()))
-    }
+        // Open thread
+        assert!(client.can_accept_reply(&board_id, &thread_id));
+        assert_eq!(client.reply_rejection_reason(&board_id, &thread_id), None);
 
-    /// Generate a unique slug, appending random suffix if needed.
-    /// For standalone boards only - community boards are handled by boards-community.
-    fn generate_unique_slug(env: &Env, base_slug: &String) -> String {
-        // Check if base slug is available
-        if Self::is_slug_available(env, base_slug) {
-            return base_slug.clone();
-        }
+        // Locked
+        client.set_thread_locked(&board_id, &thread_id, &true);
+        assert!(client.is_thread_locked(&board_id, &thread_id));
+        assert_eq!(
+            client.reply_rejection_reason(&board_id, &thread_id),
+            Some(Symbol::new(&env, "locked"))
+        );
+        client.set_thread_locked(&board_id, &thread_id, &false);
+        assert!(client.can_accept_reply(&board_id, &thread_id));
 
-        // Try with random suffixes (up to 10 attempts)
-        for _ in 0..10 {
-            let suffix = Self::generate_random_suffix(env);
+        // Hidden
+        client.set_thread_hidden(&board_id, &thread_id, &true);
+        assert_eq!(
+            client.reply_rejection_reason(&board_id, &thread_id),
+            Some(Symbol::new(&env, "hidden"))
+        );
+        client.set_thread_hidden(&board_id, &thread_id, &false);
+        assert!(client.can_accept_reply(&board_id, &thread_id));
 
-            // Build slug with suffix: base-suffix
-            let base_len = base_slug.len() as usize;
-            let mut buf = [0u8; 30];
-            let copy_len = core::cmp::min(base_len, 25); // Leave room for -xxxx
-            base_slug.copy_into_slice(&mut buf[..copy_len]);
+        // Read-only board takes precedence over thread state
+        client.set_readonly(&board_id, &true, &owner);
+        assert_eq!(
+            client.reply_rejection_reason(&board_id, &thread_id),
+            Some(Symbol::new(&env, "read_only"))
+        );
+        client.set_readonly(&board_id, &false, &owner);
+        assert!(client.can_accept_reply(&board_id, &thread_id));
 
-            buf[copy_len] = b'-';
-            let mut suffix_buf = [0u8; 4];
-            suffix.copy_into_slice(&mut suffix_buf);
-            for (i, &c) in suffix_buf.iter().enumerate() {
-                buf[copy_len + 1 + i] = c;
-            }
+        // Deleted
+        client.delete_thread(&board_id, &thread_id, &creator);
+        assert_eq!(
+            client.reply_rejection_reason(&board_id, &thread_id),
+            Some(Symbol::new(&env, "deleted"))
+        );
+        assert!(!client.can_accept_reply(&board_id, &thread_id));
+    }
 
-            let new_slug = String::from_str(
-                env,
-                core::str::from_utf8(&buf[..copy_len + 5]).unwrap_or("board"),
-            );
+    #[test]
+    fn test_list_threads_filtered() {
+        let env = Env::default();
+        let (client, board_id, _) = setup_with_board(&env);
 
-            if Self::is_slug_available(env, &new_slug) {
-                return new_slug;
-            }
+        let creator = Address::generate(&env);
+        for _ in 0..5 {
+            client.create_thread(
+                &board_id,
+                &String::from_str(&env, "Thread"),
+                &None,
+                &creator,
+            );
         }
+        client.set_thread_hidden(&board_id, &0, &true);
+        client.set_thread_locked(&board_id, &1, &true);
+        client.set_thread_locked(&board_id, &2, &true);
+        client.set_thread_pinned(&board_id, &3, &true);
+        client.delete_thread(&board_id, &4, &creator);
 
-        // Fallback: use timestamp as suffix
-        let ts = env.ledger().timestamp();
-        let ts_str = Self::u64_to_string(env, ts);
+        let all = client.list_threads_filtered(&board_id, &ThreadFilter::All, &0, &10);
+        assert_eq!(all.len(), 5);
+        // Newest first
+        assert_eq!(all.get(0).unwrap().id, 4);
 
-        let base_len = base_slug.len() as usize;
-        let mut buf = [0u8; 30];
-        let copy_len = core::cmp::min(base_len, 15);
-        base_slug.copy_into_slice(&mut buf[..copy_len]);
-        buf[copy_len] = b'-';
+        let hidden = client.list_threads_filtered(&board_id, &ThreadFilter::Hidden, &0, &10);
+        assert_eq!(hidden.len(), 1);
+        assert_eq!(hidden.get(0).unwrap().id, 0);
 
-        let ts_len = ts_str.len() as usize;
-        let ts_copy = core::cmp::min(ts_len, 14);
-        ts_str.copy_into_slice(&mut buf[copy_len + 1..copy_len + 1 + ts_copy]);
+        let locked = client.list_threads_filtered(&board_id, &ThreadFilter::Locked, &0, &10);
+        assert_eq!(locked.len(), 2);
 
-        String::from_str(
-            env,
-            core::str::from_utf8(&buf[..copy_len + 1 + ts_copy]).unwrap_or("board"),
-        )
-    }
+        let pinned = client.list_threads_filtered(&board_id, &ThreadFilter::Pinned, &0, &10);
+        assert_eq!(pinned.len(), 1);
+        assert_eq!(pinned.get(0).unwrap().id, 3);
 
-    /// Convert u64 to String (for timestamp suffix).
-    fn u64_to_string(env: &Env, mut n: u64) -> String {
-        if n == 0 {
-            return String::from_str(env, "0");
-        }
+        let deleted = client.list_threads_filtered(&board_id, &ThreadFilter::Deleted, &0, &10);
+        assert_eq!(deleted.len(), 1);
+        assert_eq!(deleted.get(0).unwrap().id, 4);
 
-        let mut buf = [0u8; 20];
-        let mut pos = 20;
-        while n > 0 && pos > 0 {
-            pos -= 1;
-            buf[pos] = b'0' + (n % 10) as u8;
-            n /= 10;
-        }
+        // No content contract configured, so nothing can be flagged
+        let flagged = client.list_threads_filtered(&board_id, &ThreadFilter::Flagged, &0, &10);
+        assert_eq!(flagged.len(), 0);
 
-        String::from_str(env, core::str::from_utf8(&buf[pos..]).unwrap_or("0"))
+        // Only the pagination window is scanned: threads 4 and 3
+        let window = client.list_threads_filtered(&board_id, &ThreadFilter::Locked, &0, &2);
+        assert_eq!(window.len(), 0);
+        let window = client.list_threads_filtered(&board_id, &ThreadFilter::Locked, &2, &2);
+        assert_eq!(window.len(), 2);
+
+        // Start past the end returns nothing
+        let empty = client.list_threads_filtered(&board_id, &ThreadFilter::All, &10, &10);
+        assert_eq!(empty.len(), 0);
     }
-}
 
-#[cfg(test)]
-mod test {
-    use super::*;
-    use soroban_sdk::testutils::Address as _;
-    use soroban_sdk::Env;
+    #[test]
+    fn test_karma_thresholds_gate_posting() {
+        let env = Env::default();
+        let (client, board_id, owner) = setup_with_board(&env);
+        let voting = MockVotingClient::new(&env, &env.register(MockVoting, ()));
+        client.set_voting(&voting.address);
 
-    /// Helper to set up a contract with a board for testing
-    fn setup_with_board(env: &Env) -> (BoardsBoardClient, u64, Address) {
-        env.mock_all_auths();
+        let newcomer = Address::generate(&env);
+        let title = String::from_str(&env, "Hello");
+        voting.set_karma(&newcomer, &3);
 
-        let contract_id = env.register(BoardsBoard, ());
-        let client = BoardsBoardClient::new(env, &contract_id);
+        // Thresholds are off by default
+        assert_eq!(client.get_min_karma_to_post(&board_id), 0);
+        assert_eq!(client.karma_shortfall(&board_id, &newcomer, &false), None);
 
-        let registry = Address::generate(env);
+        client.set_karma_thresholds(&board_id, &10, &5, &owner);
+        assert_eq!(
+            client.karma_shortfall(&board_id, &newcomer, &false),
+            Some((10, 3))
+        );
+        assert_eq!(
+            client.karma_shortfall(&board_id, &newcomer, &true),
+            Some((5, 3))
+        );
+        assert!(client
+            .try_create_thread(&board_id, &title, &None, &newcomer)
+            .is_err());
 
-        // Initialize contract (no permissions for simpler testing)
-        client.init(&registry, &None, &None, &None);
+        voting.set_karma(&newcomer, &-2);
+        assert_eq!(
+            client.karma_shortfall(&board_id, &newcomer, &true),
+            Some((5, -2))
+        );
 
-        // Create a board
-        let name = String::from_str(env, "General");
-        let desc = String::from_str(env, "General discussion");
-        let is_private = String::from_str(env, "false");
-        let is_listed = String::from_str(env, "true");
-        let caller = Address::generate(env);
+        voting.set_karma(&newcomer, &10);
+        assert_eq!(client.karma_shortfall(&board_id, &newcomer, &true), None);
+        client.create_thread(&board_id, &title, &None, &newcomer);
 
-        let board_id = client.create_board(&name, &desc, &is_private, &is_listed, &caller);
+        // Exempted users bypass the threshold regardless of karma
+        let vouched = Address::generate(&env);
+        client.set_karma_exempt(&board_id, &vouched, &true, &owner);
+        assert!(client.is_karma_exempt(&board_id, &vouched));
+        client.create_thread(&board_id, &title, &None, &vouched);
 
-        (client, board_id, caller)
+        client.set_karma_exempt(&board_id, &vouched, &false, &owner);
+        assert_eq!(
+            client.karma_shortfall(&board_id, &vouched, &false),
+            Some((10, 0))
+        );
     }
 
     #[test]
-    fn test_init_and_create_board() {
+    #[should_panic(expected = "Karma thresholds cannot be negative")]
+    fn test_set_karma_thresholds_rejects_negative() {
+        let env = Env::default();
+        let (client, board_id, owner) = setup_with_board(&env);
+        client.set_karma_thresholds(&board_id, &-1, &0, &owner);
+    }
+
+    #[test]
+    fn test_create_thread_form_shows_karma_requirement() {
         let env = Env::default();
+        let (client, _, board_id, _) = setup_with_content(&env);
         env.mock_all_auths();
+        let perms = MockPermissionsClient::new(&env, &env.register(MockPermissions, ()));
+        let voting = MockVotingClient::new(&env, &env.register(MockVoting, ()));
+        client.set_voting(&voting.address);
+        client.set_karma_thresholds(&board_id, &10, &0, &Address::generate(&env));
+        client.set_permissions(&perms.address);
+
+        let user = Address::generate(&env);
+        voting.set_karma(&user, &3);
+        let path = String::from_str(&env, "/new");
+        let html = client.render(&board_id, &Some(path.clone()), &Some(user.clone()), &None);
+        assert!(render_contains(
+            &html,
+            "You need 10 karma to post here (you have 3)."
+        ));
+        assert!(!render_contains(&html, "Thread title"));
 
-        let contract_id = env.register(BoardsBoard, ());
-        let client = BoardsBoardClient::new(&env, &contract_id);
+        // Moderators bypass the threshold
+        perms.make_moderator(&user);
+        let html = client.render(&board_id, &Some(path), &Some(user), &None);
+        assert!(!render_contains(&html, "karma to post here"));
+        assert!(render_contains(&html, "Thread title"));
+    }
 
-        let registry = Address::generate(&env);
-        client.init(&registry, &None, &None, &None);
+    #[test]
+    fn test_author_sees_disabled_vote_arrows_on_own_reply() {
+        let env = Env::default();
+        let (client, content, board_id, thread_id) = setup_with_content(&env);
+        env.mock_all_auths();
+        client.set_voting(&env.register(MockVoting, ()));
 
-        assert_eq!(client.board_count(), 0);
+        let reply = mock_reply(&env, board_id, thread_id, 0, 0);
+        content.add_reply(&reply, &String::from_str(&env, "Body"));
 
-        let name = String::from_str(&env, "General");
-        let desc = String::from_str(&env, "General discussion");
-        let is_private = String::from_str(&env, "false");
-        let is_listed = String::from_str(&env, "true");
-        let caller = Address::generate(&env);
+        let path = String::from_str(&env, "/t/0/r/0");
+        let html = client.render(&board_id, &Some(path.clone()), &Some(reply.creator), &None);
+        assert_eq!(render_count(&html, "vote-disabled vote-own"), 2);
+        assert!(!render_contains(&html, "tx:@voting:vote_reply"));
 
-        let board_id = client.create_board(&name, &desc, &is_private, &is_listed, &caller);
-        assert_eq!(board_id, 0);
-        assert_eq!(client.board_count(), 1);
+        let other = Address::generate(&env);
+        let html = client.render(&board_id, &Some(path), &Some(other), &None);
+        assert!(!render_contains(&html, "vote-own"));
+        assert_eq!(render_count(&html, "tx:@voting:vote_reply"), 2);
+    }
 
-        let board = client.get_board(&board_id).unwrap();
-        assert_eq!(board.name, name);
+    #[test]
+    fn test_thread_votable_follows_lock_and_archive() {
+        let env = Env::default();
+        let (client, board_id, owner) = setup_with_board(&env);
+        let thread_id =
+            client.create_thread(&board_id, &String::from_str(&env, "Hello"), &None, &owner);
+        assert!(client.is_thread_votable(&board_id, &thread_id));
+        assert!(!client.is_thread_votable(&board_id, &(thread_id + 1)));
+
+        client.lock_thread(&board_id, &thread_id, &owner);
+        assert!(!client.is_thread_votable(&board_id, &thread_id));
+        client.unlock_thread(&board_id, &thread_id, &owner);
+        assert!(client.is_thread_votable(&board_id, &thread_id));
+
+        client.archive_board(&board_id, &owner);
+        assert!(!client.is_thread_votable(&board_id, &thread_id));
     }
 
     #[test]
-    fn test_create_thread_without_flair() {
+    fn test_locked_thread_renders_disabled_vote_arrows() {
         let env = Env::default();
-        let (client, board_id, _) = setup_with_board(&env);
+        let (client, content, board_id, thread_id) = setup_with_content(&env);
+        env.mock_all_auths();
+        client.set_voting(&env.register(MockVoting, ()));
+        content.add_reply(
+            &mock_reply(&env, board_id, thread_id, 0, 0),
+            &String::from_str(&env, "Body"),
+        );
+        client.lock_thread(&board_id, &thread_id, &Address::generate(&env));
 
-        let creator = Address::generate(&env);
-        let title = String::from_str(&env, "Hello World");
+        let path = String::from_str(&env, "/t/0/r/0");
+        let html = client.render(
+            &board_id,
+            &Some(path),
+            &Some(Address::generate(&env)),
+            &None,
+        );
+        assert!(!render_contains(&html, "tx:@voting:vote_reply"));
+        assert!(render_contains(
+            &html,
+            "<span class=\"vote-up vote-disabled\">"
+        ));
+        assert!(render_contains(&html, "vote-score-inline"));
+    }
 
-        // Create thread without flair (None)
-        let thread_id = client.create_thread(&board_id, &title, &None, &creator);
-        assert_eq!(thread_id, 0);
-        assert_eq!(client.thread_count(&board_id), 1);
+    #[test]
+    fn test_hot_score_decays_against_fresh_threads() {
+        let now = 1_700_000_000u64;
+        let hour = 3600;
 
-        let thread = client.get_thread(&board_id, &thread_id).unwrap();
-        assert_eq!(thread.title, title);
-        assert_eq!(thread.creator, creator);
-        assert!(thread.flair_id.is_none());
+        // Votes count logarithmically: tenfold the score is worth 12.5 hours
+        assert!(BoardsBoard::hot_score(100, now - hour) > BoardsBoard::hot_score(10, now));
+        assert_eq!(
+            BoardsBoard::hot_score(100, now),
+            BoardsBoard::hot_score(10, now + HOT_DECAY_SECONDS)
+        );
+
+        // A heavily upvoted week-old thread ends up below brand new content
+        let week_old = BoardsBoard::hot_score(1000, now - 7 * 24 * hour);
+        assert!(week_old < BoardsBoard::hot_score(1, now));
+        assert!(week_old < BoardsBoard::hot_score(0, now));
+
+        // Downvoted threads sink below unvoted ones of the same age
+        assert!(BoardsBoard::hot_score(-10, now) < BoardsBoard::hot_score(0, now));
     }
 
     #[test]
-    fn test_create_thread_with_optional_flair() {
+    fn test_hot_sort_ranks_by_hot_score_with_newer_winning_ties() {
         let env = Env::default();
         let (client, board_id, owner) = setup_with_board(&env);
+        let voting = MockVotingClient::new(&env, &env.register(MockVoting, ()));
+        let title = String::from_str(&env, "Thread");
+
+        env.ledger().set_timestamp(1_700_000_000);
+        let popular_old = client.create_thread(&board_id, &title, &None, &owner);
+        env.ledger().set_timestamp(1_700_000_000 + 7 * 24 * 3600);
+        let tied_older = client.create_thread(&board_id, &title, &None, &owner);
+        let tied_newer = client.create_thread(&board_id, &title, &None, &owner);
+        let rising = client.create_thread(&board_id, &title, &None, &owner);
+        voting.set_score(&popular_old, &1000);
+        voting.set_score(&tied_older, &3);
+        voting.set_score(&tied_newer, &3);
+        voting.set_score(&rising, &50);
+
+        let ranked = env.as_contract(&client.address, || {
+            BoardsBoard::rank_threads(
+                &env,
+                board_id,
+                ThreadSort::Hot,
+                &Some(voting.address.clone()),
+                &None,
+                false,
+                10,
+            )
+        });
+        let mut ids = [0u64; 4];
+        for (i, thread) in ranked.iter().enumerate() {
+            ids[i] = thread.id;
+        }
+        assert_eq!(ids, [rising, tied_newer, tied_older, popular_old]);
+    }
 
-        // Create a flair first
-        let flair_name = String::from_str(&env, "Discussion");
-        let flair_color = String::from_str(&env, "#ffffff");
-        let flair_bg = String::from_str(&env, "#0000ff");
-        let flair_id = client.create_flair(
-            &board_id,
-            &flair_name,
-            &flair_color,
-            &flair_bg,
-            &false, // not required
-            &false, // not mod_only
-            &owner,
-        );
+    #[test]
+    fn test_controversial_sort_ranks_split_votes_first() {
+        let env = Env::default();
+        let (client, board_id, owner) = setup_with_board(&env);
+        let voting = MockVotingClient::new(&env, &env.register(MockVoting, ()));
+        let title = String::from_str(&env, "Thread");
+
+        let even = client.create_thread(&board_id, &title, &None, &owner);
+        let lopsided = client.create_thread(&board_id, &title, &None, &owner);
+        let small = client.create_thread(&board_id, &title, &None, &owner);
+        let unvoted = client.create_thread(&board_id, &title, &None, &owner);
+        voting.set_votes(&even, &10, &10);
+        voting.set_votes(&lopsided, &20, &1);
+        voting.set_votes(&small, &2, &2);
+
+        let rank = || {
+            let ranked = env.as_contract(&client.address, || {
+                BoardsBoard::rank_threads(
+                    &env,
+                    board_id,
+                    ThreadSort::Controversial,
+                    &Some(voting.address.clone()),
+                    &None,
+                    false,
+                    10,
+                )
+            });
+            let mut ids = Vec::new(&env);
+            for thread in ranked.iter() {
+                ids.push_back(thread.id);
+            }
+            ids
+        };
 
-        let creator = Address::generate(&env);
-        let title = String::from_str(&env, "Thread with flair");
+        // 2/2 falls under the default minimum of 5 votes; unvoted threads never appear
+        assert_eq!(rank(), Vec::from_array(&env, [even, lopsided]));
 
-        // Create thread with flair (use flair_N format for form input)
-        let flair_id_str = String::from_str(&env, "flair_0"); // First flair ID is 0
-        let thread_id = client.create_thread(&board_id, &title, &Some(flair_id_str), &creator);
+        client.set_controversial_min_votes(&board_id, &1, &owner);
+        assert_eq!(rank(), Vec::from_array(&env, [even, small, lopsided]));
+        assert!(!rank().contains(unvoted));
+    }
 
-        let thread = client.get_thread(&board_id, &thread_id).unwrap();
-        assert_eq!(thread.flair_id, Some(flair_id));
+    #[test]
+    fn test_leaderboard_lists_top_karma_and_viewer_rank() {
+        let env = Env::default();
+        let (client, board_id, _) = setup_with_board(&env);
+        let path = Some(String::from_str(&env, "/leaderboard"));
+
+        // Without a voting contract there is no karma to rank
+        let html = client.render(&board_id, &path, &None, &None);
+        assert!(render_contains(&html, "Karma isn't tracked on this board."));
+
+        let voting = MockVotingClient::new(&env, &env.register(MockVoting, ()));
+        client.set_voting(&voting.address);
+        let html = client.render(&board_id, &path, &None, &None);
+        assert!(render_contains(
+            &html,
+            "Nobody has earned karma on this board yet."
+        ));
+
+        let leader = Address::generate(&env);
+        let runner_up = Address::generate(&env);
+        voting.set_top_karma(&Vec::from_array(
+            &env,
+            [(leader.clone(), 1_500i64), (runner_up.clone(), 12i64)],
+        ));
+        voting.set_karma(&runner_up, &12);
+        let html = client.render(&board_id, &path, &Some(runner_up), &None);
+        assert!(render_contains(&html, "#1</span>"));
+        assert!(render_contains(&html, "1,500 karma"));
+        assert!(render_contains(
+            &html,
+            "Your karma: <strong>12</strong> · Rank #2"
+        ));
+
+        let newcomer = Address::generate(&env);
+        let html = client.render(&board_id, &path, &Some(newcomer), &None);
+        assert!(render_contains(
+            &html,
+            "Your karma: <strong>0</strong> · Not ranked yet"
+        ));
+
+        voting.set_karma_enabled(&false);
+        let html = client.render(&board_id, &path, &Some(leader), &None);
+        assert!(render_contains(&html, "Karma isn't tracked on this board."));
+    }
+
+    /// Theme stub with a settable stylesheet
+    #[contract]
+    pub struct MockTheme;
+
+    #[contractimpl]
+    impl MockTheme {
+        pub fn set_styles(env: Env, css: Bytes) {
+            env.storage()
+                .instance()
+                .set(&Symbol::new(&env, "css"), &css);
+        }
+
+        pub fn styles(env: Env) -> Bytes {
+            env.storage()
+                .instance()
+                .get(&Symbol::new(&env, "css"))
+                .unwrap_or(Bytes::from_slice(&env, b".theme{}"))
+        }
+
+        pub fn dark_styles(env: Env) -> Bytes {
+            Bytes::from_slice(&env, b".dark-mode{}")
+        }
+
+        pub fn light_styles(env: Env) -> Bytes {
+            Bytes::from_slice(&env, b".light-mode{}")
+        }
     }
 
     #[test]
-    #[should_panic(expected = "A flair is required for new posts on this board")]
-    fn test_required_flair_enforcement() {
+    fn test_custom_css_appended_after_theme_styles() {
         let env = Env::default();
         let (client, board_id, owner) = setup_with_board(&env);
+        client.set_theme(&env.register(MockTheme, ()));
+        let theme = Bytes::from_slice(&env, b".theme{}");
+        assert_eq!(client.board_styles(&board_id), theme);
 
-        // Create a required flair
-        let flair_name = String::from_str(&env, "Required Flair");
-        let flair_color = String::from_str(&env, "#ffffff");
-        let flair_bg = String::from_str(&env, "#ff0000");
-        client.create_flair(
+        client.set_custom_css(
             &board_id,
-            &flair_name,
-            &flair_color,
-            &flair_bg,
-            &true,  // required!
-            &false, // not mod_only
+            &String::from_str(&env, ".board{color:red}"),
             &owner,
         );
+        let expected: &[u8] = b".theme{}\n/* Board overrides */\n.board{color:red}";
+        assert_eq!(
+            client.board_styles(&board_id),
+            Bytes::from_slice(&env, expected)
+        );
+        let html = client.render(&board_id, &None, &None, &None);
+        assert!(render_contains(&html, "<style>.board{color:red}</style>"));
 
-        let creator = Address::generate(&env);
-        let title = String::from_str(&env, "Thread without flair");
+        // The shared stylesheet is untouched
+        assert_eq!(client.styles(), theme);
 
-        // This should panic because flair is required but not provided
-        client.create_thread(&board_id, &title, &None, &creator);
+        // An empty string resets the board to the theme defaults
+        client.set_custom_css(&board_id, &String::from_str(&env, ""), &owner);
+        assert_eq!(client.get_custom_css(&board_id), None);
+        assert_eq!(client.board_styles(&board_id), theme);
     }
 
     #[test]
-    fn test_required_flair_with_selection() {
+    fn test_custom_css_size_cap_and_reject_list() {
         let env = Env::default();
         let (client, board_id, owner) = setup_with_board(&env);
+        let rejected = |css: &String| client.try_set_custom_css(&board_id, css, &owner).is_err();
+        let css = |text: &str| String::from_str(&env, text);
+
+        assert!(rejected(&css("@IMPORT 'https://example.com/x.css';")));
+        assert!(rejected(&css(
+            ".a{background:URL(https://example.com/x.png)}"
+        )));
+        assert!(rejected(&css(".a{}</style><script>")));
+        assert!(rejected(&css(".a{background:u\\72l(x)}")));
+        assert!(!rejected(&css(".a{color:#7857e1}")));
+
+        let oversized = [b'a'; MAX_CUSTOM_CSS_BYTES as usize + 1];
+        let limit = MAX_CUSTOM_CSS_BYTES as usize;
+        assert!(!rejected(&String::from_bytes(&env, &oversized[..limit])));
+        assert!(rejected(&String::from_bytes(&env, &oversized)));
+    }
 
-        // Create a required flair
-        let flair_name = String::from_str(&env, "Required Flair");
-        let flair_color = String::from_str(&env, "#ffffff");
-        let flair_bg = String::from_str(&env, "#ff0000");
-        let flair_id = client.create_flair(
-            &board_id,
-            &flair_name,
-            &flair_color,
-            &flair_bg,
-            &true,  // required
-            &false, // not mod_only
-            &owner,
-        );
+    /// Set up a board backed by the mock registry, with MockTheme as the default theme
+    /// and a "dark" theme contract that is not registered yet
+    fn setup_with_themes(
+        env: &Env,
+    ) -> (BoardsBoardClient, MockRegistryClient, u64, Address, Address) {
+        env.mock_all_auths();
 
-        let creator = Address::generate(&env);
-        let title = String::from_str(&env, "Thread with required flair");
+        let client = BoardsBoardClient::new(env, &env.register(BoardsBoard, ()));
+        let registry = MockRegistryClient::new(env, &env.register(MockRegistry, ()));
+        client.init(&registry.address, &None, &None, &None);
+        client.set_theme(&env.register(MockTheme, ()));
 
-        // This should succeed because we provide the required flair (use flair_N format)
-        let flair_id_str = String::from_str(&env, "flair_0"); // First flair ID is 0
-        let thread_id = client.create_thread(&board_id, &title, &Some(flair_id_str), &creator);
+        let dark = MockThemeClient::new(env, &env.register(MockTheme, ()));
+        dark.set_styles(&Bytes::from_slice(env, b".dark{}"));
 
-        let thread = client.get_thread(&board_id, &thread_id).unwrap();
-        assert_eq!(thread.flair_id, Some(flair_id));
+        let owner = Address::generate(env);
+        let board_id = client.create_board(
+            &String::from_str(env, "General"),
+            &String::from_str(env, "General discussion"),
+            &String::from_str(env, "false"),
+            &String::from_str(env, "true"),
+            &owner,
+        );
+        (client, registry, board_id, owner, dark.address)
     }
 
     #[test]
-    #[should_panic(expected = "Selected flair does not exist")]
-    fn test_invalid_flair_id() {
+    fn test_theme_by_name_falls_back_when_unregistered() {
         let env = Env::default();
-        let (client, board_id, _) = setup_with_board(&env);
+        let (client, registry, board_id, owner, dark_theme) = setup_with_themes(&env);
+        let dark = String::from_str(&env, "dark");
+
+        // Only catalog themes can be picked
+        assert!(client
+            .try_set_theme_by_name(&board_id, &dark, &owner)
+            .is_err());
+        registry.register_theme(&dark, &dark_theme);
+        client.set_theme_by_name(&board_id, &dark, &owner);
+        assert_eq!(client.get_theme_name(&board_id), Some(dark.clone()));
+        assert_eq!(
+            client.board_styles(&board_id),
+            Bytes::from_slice(&env, b".dark{}")
+        );
+        let html = client.render(&board_id, &None, &None, &None);
+        assert!(render_contains(&html, "<style>.dark{}</style>"));
 
-        let creator = Address::generate(&env);
-        let title = String::from_str(&env, "Thread with invalid flair");
+        // Unregistering the theme returns the board to the default stylesheet
+        registry.unregister_theme(&dark);
+        assert_eq!(
+            client.board_styles(&board_id),
+            Bytes::from_slice(&env, b".theme{}")
+        );
+        let html = client.render(&board_id, &None, &None, &None);
+        assert!(!render_contains(&html, ".dark{}"));
 
-        // Try to use a flair ID that doesn't exist (use flair_N format)
-        let invalid_flair = String::from_str(&env, "flair_999");
-        client.create_thread(&board_id, &title, &Some(invalid_flair), &creator);
+        // An empty name clears the choice
+        client.set_theme_by_name(&board_id, &String::from_str(&env, ""), &owner);
+        assert_eq!(client.get_theme_name(&board_id), None);
     }
 
     #[test]
-    fn test_pin_and_lock() {
+    fn test_theme_preview_route() {
         let env = Env::default();
-        let (client, board_id, _) = setup_with_board(&env);
-
-        let creator = Address::generate(&env);
-        let moderator = Address::generate(&env);
-        let title = String::from_str(&env, "Important Announcement");
-
-        let thread_id = client.create_thread(&board_id, &title, &None, &creator);
-
-        // Pin thread
-        client.pin_thread(&board_id, &thread_id, &moderator);
-        let thread = client.get_thread(&board_id, &thread_id).unwrap();
-        assert!(thread.is_pinned);
+        let (client, registry, board_id, _, dark_theme) = setup_with_themes(&env);
+        registry.register_theme(&String::from_str(&env, "dark"), &dark_theme);
+
+        let path = Some(String::from_str(&env, "/preview/theme/dark"));
+        let html = client.render(&board_id, &path, &None, &None);
+        assert!(render_contains(&html, "<style>.dark{}</style>"));
+        assert!(render_contains(
+            &html,
+            "Previewing the <strong>dark</strong> theme."
+        ));
 
-        let pinned = client.get_pinned_threads(&board_id);
-        assert_eq!(pinned.len(), 1);
+        // Previewing doesn't pick the theme
+        assert_eq!(client.get_theme_name(&board_id), None);
+        let html = client.render(&board_id, &None, &None, &None);
+        assert!(!render_contains(&html, ".dark{}"));
 
-        // Lock thread
-        client.lock_thread(&board_id, &thread_id, &moderator);
-        let thread = client.get_thread(&board_id, &thread_id).unwrap();
-        assert!(thread.is_locked);
+        let path = Some(String::from_str(&env, "/preview/theme/missing"));
+        let html = client.render(&board_id, &path, &None, &None);
+        assert!(render_contains(&html, "Theme not found."));
     }
 
     #[test]
-    fn test_hide_thread() {
+    fn test_color_scheme_only_for_opted_in_viewers() {
         let env = Env::default();
         let (client, board_id, _) = setup_with_board(&env);
+        client.set_theme(&env.register(MockTheme, ()));
+        let night_owl = Address::generate(&env);
+        let other = Address::generate(&env);
+        let theme = Bytes::from_slice(&env, b".theme{}");
 
-        let creator = Address::generate(&env);
-        let moderator = Address::generate(&env);
-        let title = String::from_str(&env, "Test Thread");
+        // No preference follows the theme default
+        assert_eq!(client.get_display_pref(&night_owl), None);
+        assert_eq!(client.styles_for(&Some(night_owl.clone())), theme);
 
-        let thread_id = client.create_thread(&board_id, &title, &None, &creator);
+        client.set_display_pref(&ColorScheme::Dark, &night_owl);
+        let expected: &[u8] = b".theme{}\n/* Dark mode */\n.dark-mode{}";
+        assert_eq!(
+            client.styles_for(&Some(night_owl.clone())),
+            Bytes::from_slice(&env, expected)
+        );
+        assert_eq!(client.styles_for(&Some(other.clone())), theme);
+        assert_eq!(client.styles_for(&None), theme);
+
+        let html = client.render(&board_id, &None, &Some(night_owl.clone()), &None);
+        assert!(render_contains(&html, "<style>.dark-mode{}</style>"));
+        assert!(render_contains(
+            &html,
+            "set_display_pref {&quot;scheme&quot;:1}"
+        ));
+        assert!(render_contains(
+            &html,
+            "set_display_pref {&quot;scheme&quot;:0}"
+        ));
+        assert!(!render_contains(
+            &html,
+            "set_display_pref {&quot;scheme&quot;:2}"
+        ));
+        let html = client.render(&board_id, &None, &Some(other), &None);
+        assert!(!render_contains(&html, ".dark-mode{}"));
+        assert!(render_contains(
+            &html,
+            "set_display_pref {&quot;scheme&quot;:2}"
+        ));
+        assert!(!render_contains(
+            &html,
+            "set_display_pref {&quot;scheme&quot;:0}"
+        ));
 
-        // Hide thread
-        client.hide_thread(&board_id, &thread_id, &moderator);
-        let thread = client.get_thread(&board_id, &thread_id).unwrap();
-        assert!(thread.is_hidden);
+        // Light forces the light palette even where the system prefers dark
+        client.set_display_pref(&ColorScheme::Light, &night_owl);
+        let expected: &[u8] = b".theme{}\n/* Light mode */\n.light-mode{}";
+        assert_eq!(
+            client.styles_for(&Some(night_owl.clone())),
+            Bytes::from_slice(&env, expected)
+        );
 
-        // Unhide thread
-        client.unhide_thread(&board_id, &thread_id, &moderator);
-        let thread = client.get_thread(&board_id, &thread_id).unwrap();
-        assert!(!thread.is_hidden);
+        // Auto clears the preference and follows the theme again
+        client.set_display_pref(&ColorScheme::Auto, &night_owl);
+        assert_eq!(client.get_display_pref(&night_owl), None);
+        assert_eq!(client.styles_for(&Some(night_owl)), theme);
     }
 
     #[test]
-    fn test_list_threads() {
+    fn test_render_without_optional_contracts() {
         let env = Env::default();
-        let (client, board_id, _) = setup_with_board(&env);
-
-        let creator = Address::generate(&env);
-
-        // Create multiple threads
-        let titles = ["Thread 0", "Thread 1", "Thread 2", "Thread 3", "Thread 4"];
-        for title_str in titles.iter() {
-            let title = String::from_str(&env, title_str);
-            client.create_thread(&board_id, &title, &None, &creator);
-        }
-
-        // List threads (should return newest first)
-        let threads = client.list_threads(&board_id, &0, &3);
-        assert_eq!(threads.len(), 3);
-        // First thread should be the newest (id 4)
-        assert_eq!(threads.get(0).unwrap().id, 4);
+        let (client, board_id, caller) = setup_with_board(&env);
+        let title = String::from_str(&env, "Hello");
+        let thread_id = client.create_thread(&board_id, &title, &None, &caller);
+
+        let html = client.render(&board_id, &None, &None, &None);
+        assert!(render_contains(&html, "Hello"));
+
+        let path = Some(String::from_str(&env, "/t/0"));
+        let html = client.render(&board_id, &path, &Some(caller.clone()), &None);
+        assert!(render_contains(&html, "Hello"));
+        assert!(render_contains(&html, "Content unavailable"));
+        assert!(render_contains(&html, "Replies are unavailable."));
+
+        let path = Some(String::from_str(&env, "/t/0/replies/0"));
+        let html = client.render(&board_id, &path, &None, &None);
+        assert!(render_contains(&html, "Content unavailable"));
+        let path = Some(String::from_str(
+            &env,
+            &std::format!("/t/{}/edit", thread_id),
+        ));
+        let html = client.render(&board_id, &path, &Some(caller), &None);
+        assert!(render_contains(
+            &html,
+            "Content is unavailable on this board."
+        ));
 
-        // Get remaining threads
-        let more_threads = client.list_threads(&board_id, &3, &10);
-        assert_eq!(more_threads.len(), 2);
+        assert_eq!(
+            client.styles(),
+            Bytes::from_slice(&env, FALLBACK_CSS.as_bytes())
+        );
     }
 
     #[test]
-    fn test_get_config() {
+    fn test_create_thread_form_prefills_escaped_template() {
         let env = Env::default();
+        let (client, _, board_id, _) = setup_with_content(&env);
         env.mock_all_auths();
+        let perms = MockPermissionsClient::new(&env, &env.register(MockPermissions, ()));
+        client.set_permissions(&perms.address);
+        let admin = Address::generate(&env);
+        perms.make_admin(&admin);
 
-        let contract_id = env.register(BoardsBoard, ());
-        let client = BoardsBoardClient::new(&env, &contract_id);
-
-        let registry = Address::generate(&env);
-        client.init(&registry, &None, &None, &None);
-
-        let name = String::from_str(&env, "Private Board");
-        let desc = String::from_str(&env, "Secret discussions");
-        let is_private = String::from_str(&env, "true");
-        let is_listed = String::from_str(&env, "false");
-        let caller = Address::generate(&env);
-
-        let board_id = client.create_board(&name, &desc, &is_private, &is_listed, &caller);
-
-        let config = client.get_config(&board_id);
-        assert_eq!(config.name, name);
-        assert_eq!(config.description, desc);
-        assert!(config.is_private);
-        assert!(!config.is_readonly);
+        let bug = client.create_template(
+            &board_id,
+            &String::from_str(&env, "Bug <report>"),
+            &String::from_str(&env, "Steps:</textarea><img src=x>"),
+            &admin,
+        );
+        let question = client.create_template(
+            &board_id,
+            &String::from_str(&env, "Question"),
+            &String::from_str(&env, "What I tried:"),
+            &admin,
+        );
+        assert_eq!(client.list_templates(&board_id).len(), 2);
+
+        let user = Some(Address::generate(&env));
+        let path = String::from_str(&env, "/new");
+        let html = client.render(&board_id, &Some(path), &user, &None);
+        assert!(render_contains(&html, "/new/0\">Bug &lt;report></a>"));
+        assert!(render_contains(&html, "/new/1\">Question</a>"));
+        assert!(!render_contains(&html, "Steps:"));
+
+        let path = Some(String::from_str(&env, &std::format!("/new/{}", bug)));
+        let html = client.render(&board_id, &path, &user, &None);
+        assert!(render_contains(
+            &html,
+            ">Steps:&lt;/textarea>&lt;img src=x></textarea>"
+        ));
+        assert!(!render_contains(&html, "<img"));
+
+        // A deleted template leaves a blank form behind
+        client.delete_template(&board_id, &question, &admin);
+        assert_eq!(client.list_templates(&board_id).len(), 1);
+        let path = Some(String::from_str(&env, &std::format!("/new/{}", question)));
+        let html = client.render(&board_id, &path, &user, &None);
+        assert!(render_contains(&html, "Thread title"));
+        assert!(!render_contains(&html, "What I tried:"));
     }
 
     #[test]
-    fn test_get_nonexistent_thread() {
+    #[should_panic(expected = "Only owner or admin can create templates")]
+    fn test_create_template_requires_admin() {
         let env = Env::default();
         let (client, board_id, _) = setup_with_board(&env);
+        client.set_permissions(&env.register(MockPermissions, ()));
 
-        // Get thread that doesn't exist
-        let thread = client.get_thread(&board_id, &999);
-        assert!(thread.is_none());
+        client.create_template(
+            &board_id,
+            &String::from_str(&env, "Question"),
+            &String::from_str(&env, "What I tried:"),
+            &Address::generate(&env),
+        );
     }
 
     #[test]
-    fn test_increment_reply_count() {
+    fn test_thread_schedule_opens_and_closes_replies() {
         let env = Env::default();
-        let (client, board_id, _) = setup_with_board(&env);
-
-        let creator = Address::generate(&env);
-        let title = String::from_str(&env, "Test Thread");
-        let thread_id = client.create_thread(&board_id, &title, &None, &creator);
-
-        // Initially 0 replies
-        assert_eq!(
-            client
-                .get_thread(&board_id, &thread_id)
-                .unwrap()
-                .reply_count,
-            0
+        let (client, board_id, caller) = setup_with_board(&env);
+        let start = 1_700_000_000;
+        env.ledger().set_timestamp(start);
+        let title = String::from_str(&env, "AMA");
+        let thread_id = client.create_thread(&board_id, &title, &None, &caller);
+
+        let open_at = start + 2 * 3600;
+        let lock_at = open_at + 3600;
+        client.set_thread_schedule(
+            &board_id,
+            &thread_id,
+            &Some(open_at),
+            &Some(lock_at),
+            &caller,
         );
-
-        // Increment reply count
-        client.increment_reply_count(&board_id, &thread_id);
+        assert!(!client.can_accept_reply(&board_id, &thread_id));
+        let html = client.render(&board_id, &None, &None, &None);
+        assert!(render_contains(&html, "badge-scheduled\">opens in 2 hours"));
+
+        env.ledger().set_timestamp(open_at);
+        assert!(client.can_accept_reply(&board_id, &thread_id));
+        let html = client.render(&board_id, &None, &None, &None);
+        assert!(!render_contains(&html, "opens in"));
+
+        env.ledger().set_timestamp(lock_at);
+        assert!(client.can_accept_reply(&board_id, &thread_id));
+        env.ledger().set_timestamp(lock_at + 1);
         assert_eq!(
-            client
-                .get_thread(&board_id, &thread_id)
-                .unwrap()
-                .reply_count,
-            1
+            client.reply_rejection_reason(&board_id, &thread_id),
+            Some(Symbol::new(&env, "locked"))
         );
+        let html = client.render(&board_id, &None, &None, &None);
+        assert!(render_contains(&html, "badge-closed\">closed"));
 
-        client.increment_reply_count(&board_id, &thread_id);
-        assert_eq!(
-            client
-                .get_thread(&board_id, &thread_id)
-                .unwrap()
-                .reply_count,
-            2
-        );
+        // Clearing the schedule reopens the thread
+        client.set_thread_schedule(&board_id, &thread_id, &None, &None, &caller);
+        assert!(client.can_accept_reply(&board_id, &thread_id));
+        assert_eq!(client.get_thread_schedule(&board_id, &thread_id), None);
     }
 
     #[test]
-    fn test_thread_defaults() {
+    fn test_thread_renders_poll_results_for_guests() {
         let env = Env::default();
-        let (client, board_id, _) = setup_with_board(&env);
-
-        let creator = Address::generate(&env);
-        let title = String::from_str(&env, "New Thread");
-        let thread_id = client.create_thread(&board_id, &title, &None, &creator);
-
-        let thread = client.get_thread(&board_id, &thread_id).unwrap();
-
-        // Default values
-        assert_eq!(thread.reply_count, 0);
-        assert!(!thread.is_locked);
-        assert!(!thread.is_pinned);
-        assert!(!thread.is_hidden);
-        assert!(!thread.is_deleted);
-        assert!(thread.flair_id.is_none());
+        let (client, _, board_id, thread_id) = setup_with_content(&env);
+        env.mock_all_auths();
+        let voting = MockVotingClient::new(&env, &env.register(MockVoting, ()));
+        client.set_voting(&voting.address);
+        voting.set_poll(&Poll {
+            id: 0,
+            board_id,
+            thread_id,
+            question: String::from_str(&env, "Ship <it>?"),
+            options: Vec::from_array(
+                &env,
+                [String::from_str(&env, "Yes"), String::from_str(&env, "No")],
+            ),
+            counts: Vec::from_array(&env, [3, 1]),
+            multi: false,
+            closes_at: None,
+            voters: 4,
+        });
+
+        let path = Some(String::from_str(&env, "/t/0"));
+        let html = client.render(&board_id, &path, &None, &None);
+        assert!(render_contains(&html, "<strong>Ship &lt;it>?</strong>"));
+        assert!(render_contains(&html, "████████░░</span> 75% Yes (3)"));
+        assert!(render_contains(&html, "4 voters"));
+        assert!(!render_contains(&html, "vote_poll"));
     }
 
     #[test]
-    fn test_delete_thread() {
+    fn test_saved_threads_toggle_and_paginate() {
         let env = Env::default();
-        let (client, board_id, _) = setup_with_board(&env);
-
-        let creator = Address::generate(&env);
-        let title = String::from_str(&env, "Thread to Delete");
-        let thread_id = client.create_thread(&board_id, &title, &None, &creator);
-
-        // Initially not deleted
-        let thread = client.get_thread(&board_id, &thread_id).unwrap();
-        assert!(!thread.is_deleted);
+        let (client, board_id, caller) = setup_with_board(&env);
+        let title = String::from_str(&env, "Saved");
+        for _ in 0..(SAVED_PAGE_SIZE + 2) {
+            let thread_id = client.create_thread(&board_id, &title, &None, &caller);
+            client.save_thread(&board_id, &thread_id, &caller);
+        }
 
-        // Delete thread (author can delete)
-        client.delete_thread(&board_id, &thread_id, &creator);
-        let deleted_thread = client.get_thread(&board_id, &thread_id).unwrap();
-        assert!(deleted_thread.is_deleted);
+        // Saving twice and unsaving twice are both no-ops the second time
+        client.save_thread(&board_id, &0, &caller);
+        assert_eq!(
+            client.list_saved(&caller, &0, &100).len(),
+            SAVED_PAGE_SIZE + 2
+        );
+        client.unsave_thread(&board_id, &1, &caller);
+        client.unsave_thread(&board_id, &1, &caller);
+        let saved = client.list_saved(&caller, &0, &100);
+        assert_eq!(saved.len(), SAVED_PAGE_SIZE + 1);
+        assert!(!saved.contains((board_id, 1)));
+
+        // Newest first, sliced by start/limit
+        let page = client.list_saved(&caller, &SAVED_PAGE_SIZE, &10);
+        assert_eq!(page, Vec::from_array(&env, [(board_id, 0)]));
+        assert_eq!(client.list_saved(&caller, &50, &10).len(), 0);
+
+        let viewer = Some(caller.clone());
+        let path = Some(String::from_str(&env, "/saved"));
+        let html = client.render(&board_id, &path, &viewer, &None);
+        assert!(render_contains(&html, "Older →"));
+        assert!(!render_contains(&html, "← Newer"));
+        let path = Some(String::from_str(&env, "/saved/2"));
+        let html = client.render(&board_id, &path, &viewer, &None);
+        assert!(render_contains(&html, "/t/0\""));
+        assert!(render_contains(&html, "← Newer"));
+        assert!(!render_contains(&html, "Older →"));
+
+        // Guests can't see anyone's saves
+        let path = Some(String::from_str(&env, "/saved"));
+        let html = client.render(&board_id, &path, &None, &None);
+        assert!(render_contains(&html, "Please connect your wallet"));
     }
 
-    // ========================================================================
-    // Slug Tests
-    // ========================================================================
-
     #[test]
-    fn test_create_board_with_explicit_slug() {
+    fn test_accepted_answer_author_or_moderator() {
         let env = Env::default();
+        let (client, content, board_id, thread_id) = setup_with_content(&env);
         env.mock_all_auths();
+        let perms = MockPermissionsClient::new(&env, &env.register(MockPermissions, ()));
+        client.set_permissions(&perms.address);
+        for id in 0..2 {
+            let reply = mock_reply(&env, board_id, thread_id, id, id);
+            content.add_reply(&reply, &String::from_str(&env, "Try turning it off"));
+        }
+        let author = client.get_thread(&board_id, &thread_id).unwrap().creator;
+        let moderator = Address::generate(&env);
+        perms.make_moderator(&moderator);
+
+        let stranger = Address::generate(&env);
+        let result = client.try_set_accepted_answer(&board_id, &thread_id, &Some(0), &stranger);
+        assert!(result.is_err());
+
+        client.set_accepted_answer(&board_id, &thread_id, &Some(0), &author);
+        assert_eq!(client.get_accepted_answer(&board_id, &thread_id), Some(0));
+        let path = Some(String::from_str(&env, "/t/0"));
+        let html = client.render(&board_id, &path, &None, &None);
+        assert!(render_contains(&html, "✓ Accepted answer · "));
+        assert!(render_contains(&html, "Try turning it off"));
+        let html = client.render(&board_id, &None, &None, &None);
+        assert!(render_contains(&html, "badge-solved\">solved"));
+
+        // Moderators can reassign it, and the author can clear it
+        client.set_accepted_answer(&board_id, &thread_id, &Some(1), &moderator);
+        assert_eq!(client.get_accepted_answer(&board_id, &thread_id), Some(1));
+        let path = Some(String::from_str(&env, "/t/0/replies/0"));
+        let html = client.render(&board_id, &path, &Some(author.clone()), &None);
+        assert_eq!(render_count(&html, "badge-accepted"), 1);
+        assert!(render_contains(&html, "&quot;reply_id&quot;:null"));
+
+        client.set_accepted_answer(&board_id, &thread_id, &None, &author);
+        assert_eq!(client.get_accepted_answer(&board_id, &thread_id), None);
+        let html = client.render(&board_id, &None, &None, &None);
+        assert!(!render_contains(&html, "badge-solved"));
+    }
 
-        let contract_id = env.register(BoardsBoard, ());
-        let client = BoardsBoardClient::new(&env, &contract_id);
-
-        let registry = Address::generate(&env);
-        client.init(&registry, &None, &None, &None);
-
-        let name = String::from_str(&env, "Test Board");
-        let desc = String::from_str(&env, "A test board");
-        let is_private = String::from_str(&env, "false");
-        let is_listed = String::from_str(&env, "true");
-        let slug = String::from_str(&env, "test-board");
-        let caller = Address::generate(&env);
+    #[test]
+    fn test_accepted_answer_rejects_reply_from_other_thread() {
+        let env = Env::default();
+        let (client, content, board_id, thread_id) = setup_with_content(&env);
+        env.mock_all_auths();
+        let author = client.get_thread(&board_id, &thread_id).unwrap().creator;
+        let title = String::from_str(&env, "Other");
+        let other_id = client.create_thread(&board_id, &title, &None, &author);
+        let reply = mock_reply(&env, board_id, other_id, 0, 0);
+        content.add_reply(&reply, &String::from_str(&env, "Elsewhere"));
+
+        let result = client.try_set_accepted_answer(&board_id, &thread_id, &Some(0), &author);
+        assert!(result.is_err());
+        assert_eq!(client.get_accepted_answer(&board_id, &thread_id), None);
+
+        // Deleted replies can't be accepted either
+        let mut deleted = mock_reply(&env, board_id, thread_id, 0, 0);
+        deleted.is_deleted = true;
+        content.add_reply(&deleted, &String::from_str(&env, ""));
+        let result = client.try_set_accepted_answer(&board_id, &thread_id, &Some(0), &author);
+        assert!(result.is_err());
+
+        client.set_accepted_answer(&board_id, &other_id, &Some(0), &author);
+        assert_eq!(client.get_accepted_answer(&board_id, &other_id), Some(0));
+    }
 
-        let board_id = client.create_board_with_slug(
-            &name,
-            &desc,
-            &is_private,
-            &is_listed,
-            &Some(slug.clone()),
-            &caller,
-        );
-        assert_eq!(board_id, 0);
+    #[test]
+    fn test_reply_sort_carries_through_continuations() {
+        let env = Env::default();
+        let (client, content, board_id, thread_id) = setup_with_content(&env);
+        env.mock_all_auths();
+        for id in 0..8 {
+            let reply = mock_reply(&env, board_id, thread_id, id, id);
+            content.add_reply(&reply, &String::from_str(&env, "Body"));
+        }
+        let render = |path: &str| {
+            let path = Some(String::from_str(&env, path));
+            client.render(&board_id, &path, &None, &None)
+        };
 
-        let board = client.get_board(&board_id).unwrap();
-        assert_eq!(board.name, name);
-        assert_eq!(board.slug, slug);
+        // Oldest stays the default and keeps the plain continuation paths
+        let html = render("/t/0");
+        assert!(render_contains(&html, "sort-option sort-active\">oldest"));
+        assert!(render_contains(&html, "/t/0/replies/0/0\"}}"));
+        let html = render("/t/0/replies/0/0");
+        assert!(render_contains(&html, "Reply #0"));
+        assert!(render_contains(&html, "/t/0/replies/6/0\"}}"));
+
+        let html = render("/t/0?sort=newest");
+        assert!(render_contains(&html, "sort-option sort-active\">newest"));
+        assert!(render_contains(&html, "/t/0/replies/0/0/newest\"}}"));
+        let html = render("/t/0/replies/0/0/newest");
+        assert!(render_contains(&html, "Reply #7"));
+        assert!(!render_contains(&html, "Reply #1<"));
+        assert!(render_contains(&html, "/t/0/replies/6/0/newest\"}}"));
+
+        // A bare sort segment works without a ctx
+        let html = render("/t/0/replies/6/newest");
+        assert!(render_contains(&html, "Reply #1<"));
+        assert!(!render_contains(&html, "/replies/12/"));
     }
 
     #[test]
-    fn test_create_board_auto_generates_slug() {
+    fn test_deep_replies_continue_on_subtree_page() {
         let env = Env::default();
+        let (client, content, board_id, thread_id) = setup_with_content(&env);
         env.mock_all_auths();
+        client.set_collapse_depth(&board_id, &2, &Address::generate(&env));
+        // A single chain 0 → 1 → 2 → 3 → 4, each reply one level deeper
+        for id in 0..5u64 {
+            let mut reply = mock_reply(&env, board_id, thread_id, id, id.saturating_sub(1));
+            reply.depth = id as u32;
+            content.add_reply(&reply, &String::from_str(&env, "Body"));
+        }
+        let render = |path: &str| {
+            let path = Some(String::from_str(&env, path));
+            client.render(&board_id, &path, &None, &None)
+        };
 
-        let contract_id = env.register(BoardsBoard, ());
-        let client = BoardsBoardClient::new(&env, &contract_id);
+        // Below the collapse depth children keep nesting inline
+        let html = render("/t/0/r/0/children/0");
+        assert!(render_contains(&html, "Reply #1<"));
+        assert!(render_contains(&html, "/t/0/r/1/children/0/0\"}}"));
+
+        // At the collapse depth they move behind a link instead
+        let html = render("/t/0/r/1/children/0");
+        assert!(render_contains(&html, "Reply #2<"));
+        assert!(render_contains(
+            &html,
+            "/t/0/r/2/subtree\" class=\"continue-thread\""
+        ));
+        assert!(!render_contains(&html, "/t/0/r/2/children/"));
+
+        // The subtree page counts depth from its root (offset 2 above the ctx bits)
+        let html = render("/t/0/r/2/subtree");
+        assert!(render_contains(&html, "crumb-current\">Continued"));
+        assert!(render_contains(&html, "↑ Parent reply #1"));
+        assert!(render_contains(&html, "/t/0/r/2/children/0/8\"}}"));
+        let html = render("/t/0/r/2/children/0/8");
+        assert!(render_contains(&html, "Reply #3<"));
+        assert!(render_contains(&html, "/t/0/r/3/children/0/8\"}}"));
+        let html = render("/t/0/r/3/children/0/8");
+        assert!(render_contains(&html, "Reply #4<"));
+        assert!(!render_contains(&html, "continue-thread"));
+    }
 
-        let registry = Address::generate(&env);
-        client.init(&registry, &None, &None, &None);
+    #[test]
+    fn test_reply_form_blocks_replies_past_max_depth() {
+        let env = Env::default();
+        let (client, content, board_id, thread_id) = setup_with_content(&env);
+        env.mock_all_auths();
+        client.set_max_reply_depth(&board_id, &2, &Address::generate(&env));
+        client.set_permissions(&env.register(MockPermissions, ()));
+        // A chain 0 → 1 → 2, ending at the maximum depth
+        for id in 0..3u64 {
+            let mut reply = mock_reply(&env, board_id, thread_id, id, id.saturating_sub(1));
+            reply.depth = id as u32;
+            content.add_reply(&reply, &String::from_str(&env, "Body"));
+        }
+        let member = Some(Address::generate(&env));
+        let render = |path: &str| {
+            let path = Some(String::from_str(&env, path));
+            client.render(&board_id, &path, &member, &None)
+        };
 
-        let name = String::from_str(&env, "My Awesome Board");
-        let desc = String::from_str(&env, "Auto-generated slug test");
-        let is_private = String::from_str(&env, "false");
-        let is_listed = String::from_str(&env, "true");
-        let caller = Address::generate(&env);
+        // One below the maximum: the form is offered at depth == max
+        let html = render("/t/0/r/1/reply");
+        assert!(render_contains(&html, "name=\"depth\" value=\"2\""));
+        assert!(render_contains(&html, "<textarea name=\"content_str\""));
+        assert!(!render_contains(&html, "Maximum nesting reached"));
+
+        // At the maximum: a warning and a link one level up, no textarea
+        let html = render("/t/0/r/2/reply");
+        assert!(render_contains(&html, "Maximum nesting reached"));
+        assert!(render_contains(&html, "/t/0/r/1/reply)"));
+        assert!(!render_contains(&html, "<textarea"));
+
+        // The thread view drops [Reply] on replies at the maximum depth only
+        let html = render("/t/0/r/0/children/0/1");
+        assert!(render_contains(&html, "/t/0/r/1/reply)"));
+        let html = render("/t/0/r/1/children/0/1");
+        assert!(render_contains(&html, "Reply #2<"));
+        assert!(!render_contains(&html, "/t/0/r/2/reply)"));
+    }
 
-        // Pass None for slug to auto-generate
-        let board_id =
-            client.create_board_with_slug(&name, &desc, &is_private, &is_listed, &None, &caller);
+    #[test]
+    fn test_large_reply_families_show_expander_past_threshold() {
+        let env = Env::default();
+        let (client, content, board_id, thread_id) = setup_with_content(&env);
+        env.mock_all_auths();
+        // Reply 0 has two children, reply 3 has three
+        for (id, parent) in [(0, 0), (1, 0), (2, 0), (3, 3), (4, 3), (5, 3), (6, 3)] {
+            let reply = mock_reply(&env, board_id, thread_id, id, parent);
+            content.add_reply(&reply, &String::from_str(&env, "Body"));
+        }
+        let path = Some(String::from_str(&env, "/t/0/replies/0"));
+
+        // Off by default: every family is embedded
+        assert_eq!(client.get_collapse_children_over(&board_id), 0);
+        let html = client.render(&board_id, &path, &None, &None);
+        assert!(render_contains(&html, "/t/0/r/0/children/0/0\"}}"));
+        assert!(render_contains(&html, "/t/0/r/3/children/0/0\"}}"));
+        assert!(!render_contains(&html, "show-replies"));
+
+        // At the threshold children still embed; one past it they wait behind a link
+        client.set_collapse_children_over(&board_id, &2, &Address::generate(&env));
+        let html = client.render(&board_id, &path, &None, &None);
+        assert!(render_contains(&html, "/t/0/r/0/children/0/0\"}}"));
+        assert!(!render_contains(&html, "/r/3/children/0/0\"}}"));
+        assert!(render_contains(
+            &html,
+            "/r/3/children/0/0\" class=\"show-replies\">Show 3 replies</a>"
+        ));
+        assert_eq!(render_count(&html, "show-replies"), 1);
+    }
 
-        let board = client.get_board(&board_id).unwrap();
-        // Slug should be derived from name: "my-awesome-board"
-        let expected_slug = String::from_str(&env, "my-awesome-board");
-        assert_eq!(board.slug, expected_slug);
+    #[test]
+    fn test_low_score_and_flagged_replies_start_collapsed() {
+        let env = Env::default();
+        let (client, content, board_id, thread_id) = setup_with_content(&env);
+        env.mock_all_auths();
+        let voting = MockVotingClient::new(&env, &env.register(MockVoting, ()));
+        client.set_voting(&voting.address);
+        for id in 0..4 {
+            let mut reply = mock_reply(&env, board_id, thread_id, id, id);
+            // Two flags reach half the default auto-hide threshold of 3
+            reply.flag_count = if id == 3 { 2 } else { 0 };
+            content.add_reply(&reply, &String::from_str(&env, "Body"));
+        }
+        voting.set_reply_score(&0, &-3);
+        voting.set_reply_score(&1, &-5);
+        voting.set_reply_score(&2, &4);
+
+        let path = Some(String::from_str(&env, "/t/0/replies/0"));
+        let html = client.render(&board_id, &path, &None, &None);
+        assert_eq!(render_count(&html, "<details class=\"low-score\">"), 1);
+        assert!(render_contains(
+            &html,
+            "comment score below threshold (−5) — show"
+        ));
+        assert_eq!(
+            render_count(&html, "<details class=\"heavily-flagged\">"),
+            1
+        );
+
+        // A stricter board threshold also collapses the reply sitting at -3
+        client.set_collapse_score(&board_id, &0, &Address::generate(&env));
+        let html = client.render(&board_id, &path, &None, &None);
+        assert_eq!(render_count(&html, "<details class=\"low-score\">"), 2);
+        assert!(render_contains(
+            &html,
+            "comment score below threshold (−3) — show"
+        ));
     }
 
     #[test]
-    fn test_get_board_by_slug() {
+    fn test_reply_reactions_fetched_per_batch() {
         let env = Env::default();
+        let (client, content, board_id, thread_id) = setup_with_content(&env);
         env.mock_all_auths();
+        let voting = MockVotingClient::new(&env, &env.register(MockVoting, ()));
+        client.set_voting(&voting.address);
+        for id in 0..4 {
+            let reply = mock_reply(&env, board_id, thread_id, id, id);
+            content.add_reply(&reply, &String::from_str(&env, "Body"));
+        }
+        let thumbsup = Symbol::new(&env, "thumbsup");
+        let tada = Symbol::new(&env, "tada");
+        voting.set_reply_reactions(&1, &Vec::from_array(&env, [(thumbsup, 2)]), &true);
+        voting.set_reply_reactions(&3, &Vec::from_array(&env, [(tada, 1)]), &false);
+
+        let path = Some(String::from_str(&env, "/t/0/replies/0"));
+        let viewer = Address::generate(&env);
+        let html = client.render(&board_id, &path, &Some(viewer), &None);
+        assert_eq!(voting.reaction_calls(), 1);
+        assert_eq!(render_count(&html, "class=\"reaction-chip"), 2);
+        assert_eq!(render_count(&html, "reaction-chip reaction-active"), 1);
+
+        // Guests see the used reactions without toggle links
+        let html = client.render(&board_id, &path, &None, &None);
+        assert_eq!(voting.reaction_calls(), 2);
+        assert_eq!(render_count(&html, "<span class=\"reaction-chip\""), 2);
+    }
 
-        let contract_id = env.register(BoardsBoard, ());
-        let client = BoardsBoardClient::new(&env, &contract_id);
+    #[test]
+    fn test_wiki_edit_permission_matrix() {
+        let env = Env::default();
+        let (client, _, board_id, _) = setup_with_content(&env);
+        env.mock_all_auths();
 
-        let registry = Address::generate(&env);
-        client.init(&registry, &None, &None, &None);
+        // Without a permissions contract anyone can edit
+        assert!(client.can_edit_wiki(&board_id, &Address::generate(&env)));
 
-        let name = String::from_str(&env, "Lookup Test");
-        let desc = String::from_str(&env, "Test slug lookup");
-        let is_private = String::from_str(&env, "false");
-        let is_listed = String::from_str(&env, "true");
-        let slug = String::from_str(&env, "lookup-test");
-        let caller = Address::generate(&env);
+        let perms = MockPermissionsClient::new(&env, &env.register(MockPermissions, ()));
+        client.set_permissions(&perms.address);
+        let guest = Address::generate(&env);
+        perms.make_guest(&guest);
+        let member = Address::generate(&env);
+        let moderator = Address::generate(&env);
+        perms.make_moderator(&moderator);
+        let admin = Address::generate(&env);
+        perms.make_admin(&admin);
+
+        let slug = String::from_str(&env, "faq");
+        let title = String::from_str(&env, "FAQ");
+        let body = String::from_str(&env, "Ask away.");
+
+        // Member+ by default
+        assert!(!client.can_edit_wiki(&board_id, &guest));
+        assert!(client.can_edit_wiki(&board_id, &member));
+        assert!(client.can_edit_wiki(&board_id, &moderator));
+        assert!(client
+            .try_set_wiki_page(&board_id, &slug, &title, &body, &guest)
+            .is_err());
+        client.set_wiki_page(&board_id, &slug, &title, &body, &member);
+
+        let path = Some(String::from_str(&env, "/wiki/faq"));
+        let html = client.render(&board_id, &path, &Some(member.clone()), &None);
+        assert!(render_contains(&html, "<h1>FAQ</h1>"));
+        assert!(render_contains(&html, "Ask away."));
+        assert!(render_contains(&html, "Edit Page"));
+        let html = client.render(&board_id, &path, &Some(guest.clone()), &None);
+        assert!(render_contains(&html, "Ask away."));
+        assert!(!render_contains(&html, "Edit Page"));
+
+        // Raised to Moderator+, members can still read but not edit
+        assert!(client
+            .try_set_wiki_edit_role(&board_id, &Role::Moderator, &member)
+            .is_err());
+        assert!(client
+            .try_set_wiki_edit_role(&board_id, &Role::Admin, &admin)
+            .is_err());
+        client.set_wiki_edit_role(&board_id, &Role::Moderator, &admin);
+        assert!(!client.can_edit_wiki(&board_id, &member));
+        assert!(client.can_edit_wiki(&board_id, &moderator));
+        assert!(client
+            .try_set_wiki_page(&board_id, &slug, &title, &body, &member)
+            .is_err());
+        let html = client.render(&board_id, &path, &Some(member), &None);
+        assert!(!render_contains(&html, "Edit Page"));
+
+        let html = client.render(&board_id, &path, &Some(moderator.clone()), &None);
+        assert!(render_contains(&html, "Edit Page"));
+        client.set_wiki_page(
+            &board_id,
+            &slug,
+            &String::from_str(&env, "Questions"),
+            &body,
+            &moderator,
+        );
+        assert!(client.get_wiki_page(&board_id, &slug).unwrap().editor == moderator);
+    }
 
-        let board_id = client.create_board_with_slug(
-            &name,
-            &desc,
-            &is_private,
-            &is_listed,
-            &Some(slug.clone()),
-            &caller,
+    #[test]
+    fn test_read_marks_drive_new_dots_and_unread_summary() {
+        let env = Env::default();
+        let (client, _, board_id, thread_id) = setup_with_content(&env);
+        env.mock_all_auths();
+        let viewer = Address::generate(&env);
+        let as_viewer = Some(viewer.clone());
+        let thread_path = Some(String::from_str(&env, "/t/0"));
+
+        // Nothing is tracked until the viewer marks something read
+        let html = client.render(&board_id, &None, &as_viewer, &None);
+        assert!(!render_contains(&html, "new-dot"));
+        assert!(render_contains(&html, "tx:@board:mark_board_read"));
+        let html = client.render(&board_id, &thread_path, &as_viewer, &None);
+        assert!(render_contains(&html, "tx:@board:mark_thread_read"));
+
+        env.ledger().set_timestamp(1_000);
+        client.mark_board_read(&board_id, &viewer);
+        let html = client.render(&board_id, &None, &as_viewer, &None);
+        assert!(!render_contains(&html, "new-dot"));
+        assert!(!render_contains(&html, "unread-summary"));
+
+        // A new reply makes the thread unread again, for this viewer only
+        env.ledger().set_timestamp(2_000);
+        client.increment_reply_count(&board_id, &thread_id);
+        let html = client.render(&board_id, &None, &as_viewer, &None);
+        assert_eq!(render_count(&html, "class=\"new-dot\""), 1);
+        assert!(render_contains(
+            &html,
+            "1 thread updated since your last visit"
+        ));
+        let html = client.render(&board_id, &None, &None, &None);
+        assert!(!render_contains(&html, "new-dot"));
+
+        // Marking a thread read twice keeps one entry
+        env.ledger().set_timestamp(3_000);
+        client.mark_thread_read(&board_id, &thread_id, &viewer);
+        client.mark_thread_read(&board_id, &thread_id, &viewer);
+        assert_eq!(
+            client.get_read_marks(&board_id, &viewer).unwrap().threads,
+            Vec::from_array(&env, [(thread_id, 3_000)])
         );
+        let html = client.render(&board_id, &None, &as_viewer, &None);
+        assert!(!render_contains(&html, "new-dot"));
+        let html = client.render(&board_id, &thread_path, &as_viewer, &None);
+        assert!(!render_contains(&html, "tx:@board:mark_thread_read"));
+
+        // The board-wide mark replaces the per-thread ones
+        client.mark_board_read(&board_id, &viewer);
+        let marks = client.get_read_marks(&board_id, &viewer).unwrap();
+        assert_eq!(marks.board_read_at, 3_000);
+        assert!(marks.threads.is_empty());
+    }
 
-        // Look up by slug
-        let found_board = client.get_board_by_slug(&slug);
-        assert!(found_board.is_some());
-        assert_eq!(found_board.unwrap().id, board_id);
+    #[test]
+    fn test_format_timestamp_per_locale() {
+        let env = Env::default();
+        let format = |style| BoardsBoard::format_timestamp(&env, 1_740_837_600, style);
 
-        // Look up by ID should also work
-        let found_id = client.get_board_id_by_slug(&slug);
-        assert_eq!(found_id, Some(board_id));
+        assert_eq!(
+            format(DateStyle::Iso),
+            Bytes::from_slice(&env, b"2025-03-01 14:00 UTC")
+        );
+        assert_eq!(
+            format(DateStyle::DayMonthYear),
+            Bytes::from_slice(&env, b"01.03.2025 14:00 UTC")
+        );
+        assert_eq!(
+            format(DateStyle::Japanese),
+            Bytes::from_slice(&env, "2025年03月01日 14:00 UTC".as_bytes())
+        );
+        assert_eq!(
+            BoardsBoard::format_timestamp(&env, 42, DateStyle::Japanese),
+            Bytes::from_slice(&env, b"Ledger 42")
+        );
     }
 
     #[test]
-    fn test_slug_uniqueness_adds_suffix() {
+    fn test_board_locale_picks_date_style_and_badge() {
         let env = Env::default();
-        env.mock_all_auths();
+        let (client, board_id, caller) = setup_with_board(&env);
+        let style = || env.as_contract(&client.address, || BoardsBoard::date_style(&env, board_id));
+
+        assert_eq!(client.get_locale(&board_id), None);
+        assert_eq!(style(), DateStyle::Iso);
+
+        let expected = [
+            ("en", DateStyle::Iso),
+            ("fr", DateStyle::Iso),
+            ("de", DateStyle::DayMonthYear),
+            ("ru", DateStyle::DayMonthYear),
+            ("ja", DateStyle::Japanese),
+        ];
+        for (code, date_style) in expected {
+            let locale = Symbol::new(&env, code);
+            client.set_locale(&board_id, &Some(locale.clone()), &caller);
+            assert_eq!(client.get_locale(&board_id), Some(locale));
+            assert_eq!(style(), date_style);
+        }
 
-        let contract_id = env.register(BoardsBoard, ());
-        let client = BoardsBoardClient::new(&env, &contract_id);
+        let html = client.render(&board_id, &None, &None, &None);
+        assert!(render_contains(
+            &html,
+            "<span class=\"badge badge-locale\">ja</span>"
+        ));
 
-        let registry = Address::generate(&env);
-        client.init(&registry, &None, &None, &None);
+        assert!(client
+            .try_set_locale(&board_id, &Some(Symbol::new(&env, "xx")), &caller)
+            .is_err());
+        client.set_locale(&board_id, &None, &caller);
+        assert_eq!(client.get_locale(&board_id), None);
+        let html = client.render(&board_id, &None, &None, &None);
+        assert!(!render_contains(&html, "badge-locale"));
+    }
 
-        let desc = String::from_str(&env, "Description");
-        let is_private = String::from_str(&env, "false");
-        let is_listed = String::from_str(&env, "true");
-        let caller = Address::generate(&env);
+    #[test]
+    fn test_image_policy_host_validation() {
+        let env = Env::default();
+        let (client, board_id, caller) = setup_with_board(&env);
+        let hosts = |list: &[&str]| {
+            let mut hosts = Vec::new(&env);
+            for host in list {
+                hosts.push_back(String::from_str(&env, host));
+            }
+            hosts
+        };
 
-        // Create first board with slug "general"
-        let name1 = String::from_str(&env, "General");
-        let slug1 = String::from_str(&env, "general");
-        let board1_id = client.create_board_with_slug(
-            &name1,
-            &desc,
-            &is_private,
-            &is_listed,
-            &Some(slug1.clone()),
-            &caller,
-        );
-        let board1 = client.get_board(&board1_id).unwrap();
-        assert_eq!(board1.slug, slug1);
+        let policy = client.get_image_policy(&board_id);
+        assert!(policy.allow_images);
+        assert!(policy.allowed_hosts.is_empty());
+
+        let too_long = "a".repeat(65);
+        for host in [
+            "",
+            "I.imgur.com",
+            "imgur.com/a",
+            "imgur.com:443",
+            too_long.as_str(),
+        ] {
+            assert!(client
+                .try_set_image_policy(&board_id, &true, &hosts(&[host]), &caller)
+                .is_err());
+        }
+        let too_many = ["a.com"; 21];
+        assert!(client
+            .try_set_image_policy(&board_id, &true, &hosts(&too_many), &caller)
+            .is_err());
 
-        // Create second board with same slug - should get suffix
-        let name2 = String::from_str(&env, "General 2");
-        let board2_id = client.create_board_with_slug(
-            &name2,
-            &desc,
-            &is_private,
-            &is_listed,
-            &Some(slug1.clone()),
+        client.set_image_policy(
+            &board_id,
+            &false,
+            &hosts(&["i.imgur.com", "cdn-1.example.org"]),
             &caller,
         );
-        let board2 = client.get_board(&board2_id).unwrap();
-        // Slug should NOT equal "general" - it should have a suffix
-        assert!(board2.slug != slug1);
-        // But should start with "general-"
-        // We can't easily check prefix in Soroban, so just verify they're different boards
-        assert_ne!(board1_id, board2_id);
+        assert_eq!(
+            client.get_image_policy(&board_id),
+            ImagePolicy {
+                allow_images: false,
+                allowed_hosts: hosts(&["i.imgur.com", "cdn-1.example.org"]),
+            }
+        );
     }
 
     #[test]
-    #[should_panic(expected = "Board slug must be 3-30 characters")]
-    fn test_slug_validation_too_short() {
+    fn test_public_modlog_redacts_details_for_guests() {
         let env = Env::default();
+        let (client, _, board_id, thread_id) = setup_with_content(&env);
         env.mock_all_auths();
+        let perms = MockPermissionsClient::new(&env, &env.register(MockPermissions, ()));
+        client.set_permissions(&perms.address);
+        let owner = Address::generate(&env);
+        perms.make_admin(&owner);
+        let moderator = Address::generate(&env);
+        perms.make_moderator(&moderator);
+        let admin = MockAdminClient::new(&env, &env.register(MockAdmin, ()));
+        let registry: Address = env.as_contract(&client.address, || {
+            env.storage().instance().get(&BoardKey::Registry).unwrap()
+        });
+        MockRegistryClient::new(&env, &registry)
+            .set_contract(&Symbol::new(&env, "admin"), &admin.address);
+
+        let gone = client.create_thread(&board_id, &String::from_str(&env, "Gone"), &None, &owner);
+        client.set_thread_hidden(&board_id, &gone, &true);
+
+        // Newest first: a ban with a reason, a hidden thread, then 28 locks on a live thread
+        let spammer = Address::generate(&env);
+        let action =
+            |name: &str, thread: Option<u64>, user: Option<Address>, note: Option<&str>| {
+                ModAction {
+                    actor: moderator.clone(),
+                    board_id,
+                    action: Symbol::new(&env, name),
+                    target_thread: thread,
+                    target_reply: None,
+                    target_user: user,
+                    timestamp: 1_740_837_600,
+                    note: note.map(|n| String::from_str(&env, n)),
+                }
+            };
+        let mut actions = Vec::from_array(
+            &env,
+            [
+                action("ban_user", None, Some(spammer.clone()), Some("Spam links")),
+                action("hide_thread", Some(gone), None, None),
+            ],
+        );
+        for _ in 0..28 {
+            actions.push_back(action("lock_thread", Some(thread_id), None, None));
+        }
+        admin.set_mod_actions(&actions);
 
-        let contract_id = env.register(BoardsBoard, ());
-        let client = BoardsBoardClient::new(&env, &contract_id);
-
-        let registry = Address::generate(&env);
-        client.init(&registry, &None, &None, &None);
+        let render = |path: &str, viewer: Option<Address>| {
+            let path = String::from_str(&env, path);
+            client.render(&board_id, &Some(path), &viewer, &None)
+        };
 
-        let name = String::from_str(&env, "Short");
-        let desc = String::from_str(&env, "Description");
-        let is_private = String::from_str(&env, "false");
-        let is_listed = String::from_str(&env, "true");
-        let slug = String::from_str(&env, "ab"); // Too short - 2 chars
-        let caller = Address::generate(&env);
+        // Off by default: only moderators can read it
+        let html = render("/modlog", None);
+        assert!(render_contains(&html, "visible to moderators only"));
+        assert_eq!(render_count(&html, "modlog-entry"), 0);
+        assert_eq!(
+            render_count(&render("/modlog", Some(moderator.clone())), "modlog-entry"),
+            25
+        );
 
-        // This should panic
-        client.create_board_with_slug(&name, &desc, &is_private, &is_listed, &Some(slug), &caller);
+        assert!(client
+            .try_set_public_modlog(&board_id, &true, &moderator)
+            .is_err());
+        client.set_public_modlog(&board_id, &true, &owner);
+        assert!(client.is_modlog_public(&board_id));
+
+        // Guests see actions, titles and dates, but not who acted, on whom, or why
+        let html = render("/modlog", None);
+        assert_eq!(render_count(&html, "modlog-entry"), 25);
+        assert!(render_contains(&html, "Banned user"));
+        assert!(render_contains(
+            &html,
+            "<span class=\"modlog-removed\">[removed]</span>"
+        ));
+        assert!(render_contains(&html, "/t/0\">Hello</a>"));
+        assert!(render_contains(&html, "2025-03-01"));
+        assert!(!render_contains(&html, "modlog-details"));
+        assert!(!render_contains(&html, "Spam links"));
+        assert!(render_contains(&html, "/modlog/25\"}}"));
+
+        // Moderators also get the actor, the target user, the reason and hidden titles
+        let html = render("/modlog", Some(moderator.clone()));
+        assert_eq!(render_count(&html, "modlog-details"), 25);
+        assert!(render_contains(&html, "Spam links"));
+        assert!(render_contains(&html, "/t/1\">Gone</a>"));
+        assert!(!render_contains(&html, "[removed]"));
+
+        // The continuation is a bare batch with the rest of the log
+        let html = render("/modlog/25", None);
+        assert_eq!(render_count(&html, "modlog-entry"), 5);
+        assert!(!render_contains(&html, "Moderation Log"));
+        assert!(!render_contains(&html, "{{render"));
     }
 
     #[test]
-    #[should_panic(expected = "Board slug must start with lowercase letter")]
-    fn test_slug_validation_must_start_with_letter() {
+    fn test_thread_tags_cap_and_index() {
         let env = Env::default();
-        env.mock_all_auths();
+        let (client, board_id, caller) = setup_with_board(&env);
+        let s = |v: &str| String::from_str(&env, v);
+        let ids = |list: &[u32]| Vec::from_slice(&env, list);
 
-        let contract_id = env.register(BoardsBoard, ());
-        let client = BoardsBoardClient::new(&env, &contract_id);
+        for name in ["tutorial", "rust", "2024", "help", "meta", "news"] {
+            client.create_tag(&board_id, &s(name), &caller);
+        }
+        assert!(client
+            .try_create_tag(&board_id, &s("rust"), &caller)
+            .is_err());
+        assert!(client
+            .try_create_tag(&board_id, &s("Rust Lang"), &caller)
+            .is_err());
+        assert_eq!(
+            client.list_tags(&board_id).get_unchecked(1),
+            TagDef {
+                id: 1,
+                name: s("rust")
+            }
+        );
 
-        let registry = Address::generate(&env);
-        client.init(&registry, &None, &None, &None);
+        let first = client.create_thread(&board_id, &s("Intro to Soroban"), &None, &caller);
+        let second = client.create_thread(&board_id, &s("Borrowing"), &None, &caller);
+
+        // Five tags at most, and only tags that exist; repeats count once
+        assert!(client
+            .try_set_thread_tags(&board_id, &first, &ids(&[0, 1, 2, 3, 4, 5]), &caller)
+            .is_err());
+        assert!(client
+            .try_set_thread_tags(&board_id, &first, &ids(&[0, 9]), &caller)
+            .is_err());
+        client.set_thread_tags(&board_id, &first, &ids(&[0, 1, 1, 2, 3, 4]), &caller);
+        assert_eq!(
+            client.get_thread_tags(&board_id, &first),
+            ids(&[0, 1, 2, 3, 4])
+        );
 
-        let name = String::from_str(&env, "Numbers");
-        let desc = String::from_str(&env, "Description");
-        let is_private = String::from_str(&env, "false");
-        let is_listed = String::from_str(&env, "true");
-        let slug = String::from_str(&env, "123-board"); // Starts with number
-        let caller = Address::generate(&env);
+        // Newer taggings go to the front of a tag's index
+        client.set_thread_tags(&board_id, &second, &ids(&[1]), &caller);
+        assert_eq!(
+            client.list_tag_threads(&board_id, &1),
+            Vec::from_array(&env, [second, first])
+        );
 
-        // This should panic
-        client.create_board_with_slug(&name, &desc, &is_private, &is_listed, &Some(slug), &caller);
+        // Changing tags moves the thread between indexes
+        client.set_thread_tags(&board_id, &first, &ids(&[0, 5]), &caller);
+        assert_eq!(
+            client.list_tag_threads(&board_id, &1),
+            Vec::from_array(&env, [second])
+        );
+        assert_eq!(
+            client.list_tag_threads(&board_id, &5),
+            Vec::from_array(&env, [first])
+        );
+        assert_eq!(
+            client.list_tag_threads(&board_id, &0),
+            Vec::from_array(&env, [first])
+        );
+        client.set_thread_tags(&board_id, &first, &ids(&[]), &caller);
+        assert!(client.get_thread_tags(&board_id, &first).is_empty());
+        assert!(client.list_tag_threads(&board_id, &0).is_empty());
+
+        // Tag pages list the indexed threads, and cards show their chips
+        let path = Some(s("/tag/1"));
+        let html = client.render(&board_id, &path, &None, &None);
+        assert!(render_contains(&html, "Threads tagged #rust"));
+        assert!(render_contains(&html, "Borrowing"));
+        assert!(!render_contains(&html, "Intro to Soroban"));
+        let html = client.render(&board_id, &None, &None, &None);
+        assert_eq!(
+            render_count(&html, "<span class=\"tag-chip\">#rust</span>"),
+            1
+        );
+        let path = Some(s("/t/1"));
+        let html = client.render(&board_id, &path, &None, &None);
+        assert!(render_contains(
+            &html,
+            "/tag/1\" class=\"tag-chip\">#rust</a>"
+        ));
     }
 
     #[test]
-    fn test_board_metadata_includes_slug() {
+    fn test_blocked_terms_fold_case_and_removal_takes_effect() {
         let env = Env::default();
-        env.mock_all_auths();
+        let (client, board_id, caller) = setup_with_board(&env);
+        let s = |v: &str| String::from_str(&env, v);
+        let b = |v: &str| Bytes::from_slice(&env, v.as_bytes());
+
+        for bad in ["", "   ", "x".repeat(65).as_str()] {
+            assert!(client
+                .try_add_blocked_term(&board_id, &s(bad), &caller)
+                .is_err());
+        }
 
-        let contract_id = env.register(BoardsBoard, ());
-        let client = BoardsBoardClient::new(&env, &contract_id);
+        // Terms are trimmed and lowercased, and listed once
+        client.add_blocked_term(&board_id, &s(" Free-Crypto.example "), &caller);
+        client.add_blocked_term(&board_id, &s("FREE-CRYPTO.EXAMPLE"), &caller);
+        assert_eq!(
+            client.list_blocked_terms(&board_id),
+            Vec::from_array(&env, [s("free-crypto.example")])
+        );
+        assert!(client.contains_blocked_term(&board_id, &b("Visit FREE-crypto.Example now")));
+        assert!(!client.contains_blocked_term(&board_id, &b("free crypto example")));
 
-        let registry = Address::generate(&env);
-        client.init(&registry, &None, &None, &None);
+        // Matches that straddle a scan window boundary are still found
+        let mut long = "x".repeat(BLOCKLIST_SCAN_WINDOW - 5);
+        long.push_str("Free-Crypto.example");
+        assert!(client.contains_blocked_term(&board_id, &b(&long)));
 
-        let name = String::from_str(&env, "Meta Test");
-        let desc = String::from_str(&env, "Testing metadata");
-        let is_private = String::from_str(&env, "false");
-        let is_listed = String::from_str(&env, "true");
-        let slug = String::from_str(&env, "meta-test");
-        let caller = Address::generate(&env);
+        // Titles are checked on create and on edit
+        assert_eq!(
+            client.try_create_thread(&board_id, &s("Win at free-crypto.EXAMPLE"), &None, &caller),
+            Err(Ok(BoardError::BlockedTerm.into()))
+        );
+        let thread_id = client.create_thread(&board_id, &s("Hello"), &None, &caller);
+        assert!(client
+            .try_edit_thread_title(&board_id, &thread_id, &s("FREE-CRYPTO.EXAMPLE"), &caller)
+            .is_err());
+
+        // Removing a term ignores case and allows it again
+        client.remove_blocked_term(&board_id, &s("Free-Crypto.Example"), &caller);
+        assert!(client.list_blocked_terms(&board_id).is_empty());
+        client.edit_thread_title(&board_id, &thread_id, &s("FREE-CRYPTO.EXAMPLE"), &caller);
+        assert!(!client.contains_blocked_term(&board_id, &b("free-crypto.example")));
+    }
 
-        let board_id = client.create_board_with_slug(
-            &name,
-            &desc,
-            &is_private,
-            &is_listed,
-            &Some(slug.clone()),
+    #[test]
+    fn test_wiki_slug_validation_and_previous_revision() {
+        let env = Env::default();
+        let (client, board_id, caller) = setup_with_board(&env);
+        let title = String::from_str(&env, "Page");
+        let body = String::from_str(&env, "Body");
+
+        let too_long = "a".repeat(33);
+        for slug in [
+            "",
+            "FAQ",
+            "faq page",
+            "faq_1",
+            "faq/rules",
+            too_long.as_str(),
+        ] {
+            let slug = String::from_str(&env, slug);
+            assert!(client
+                .try_set_wiki_page(&board_id, &slug, &title, &body, &caller)
+                .is_err());
+        }
+        assert!(client.list_wiki_pages(&board_id).is_empty());
+
+        let slug = String::from_str(&env, "getting-started-2");
+        client.set_wiki_page(&board_id, &slug, &title, &body, &caller);
+        assert!(client.get_previous_wiki_page(&board_id, &slug).is_none());
+
+        // Editing keeps one index entry and the replaced version only
+        client.set_wiki_page(
+            &board_id,
+            &slug,
+            &String::from_str(&env, "Getting Started"),
+            &body,
             &caller,
         );
-
-        // Get board and verify slug is in metadata
-        let board = client.get_board(&board_id).unwrap();
-        assert_eq!(board.id, board_id);
-        assert_eq!(board.slug, slug);
-        assert_eq!(board.name, name);
-        assert_eq!(board.creator, caller);
+        client.set_wiki_page(
+            &board_id,
+            &slug,
+            &String::from_str(&env, "Getting started"),
+            &body,
+            &caller,
+        );
+        let pages = client.list_wiki_pages(&board_id);
+        assert_eq!(pages.len(), 1);
+        assert_eq!(
+            pages.get(0).unwrap().title,
+            String::from_str(&env, "Getting started")
+        );
+        assert_eq!(
+            client
+                .get_previous_wiki_page(&board_id, &slug)
+                .unwrap()
+                .title,
+            String::from_str(&env, "Getting Started")
+        );
     }
 }
@@ -8,6 +8,25 @@ use soroban_sdk::{
 // Declare render capabilities
 soroban_render!(markdown);
 
+/// Boards whose newest threads are merged together in one feed batch
+const FEED_BOARDS_PER_BATCH: u32 = 4;
+
+/// Boards covered by one feed page; batches chain by waterfall up to this,
+/// then a "Load more" link starts the next page
+const FEED_BOARDS_PER_PAGE: u32 = 12;
+
+/// Newest threads fetched from each board for the feed
+const FEED_THREADS_PER_BOARD: u64 = 5;
+
+/// Thread cards shown per feed batch after merging
+const FEED_THREADS_PER_BATCH: u32 = 10;
+
+/// Maximum length of a board group name
+const MAX_BOARD_GROUP_LEN: u32 = 40;
+
+/// Communities kept in a user's joined list; the oldest entry drops off first
+const MAX_JOINED_COMMUNITIES: u32 = 100;
+
 /// Storage keys for the community contract
 #[contracttype]
 #[derive(Clone)]
@@ -46,6 +65,14 @@ pub enum CommunityKey {
     PendingOwnershipTransfer(u64),
     /// Board slug -> board_id index within a community (community_id, slug) -> board_id
     CommunityBoardBySlug(u64, String),
+    /// Explicit board display order (community_id) -> Vec<u64>
+    CommunityBoardOrder(u64),
+    /// Named group a board is shown under (community_id, board_id) -> String
+    CommunityBoardGroup(u64, u64),
+    /// Communities a user has joined, oldest first (user) -> Vec<u64>
+    UserCommunities(Address),
+    /// Communities a user owns, in ID order (user) -> Vec<u64>
+    UserOwnedCommunities(Address),
 }
 
 /// Community metadata
@@ -112,6 +139,24 @@ pub struct BoardMeta {
     pub is_listed: bool,
 }
 
+/// Thread metadata (matches boards-board ThreadMeta for cross-contract calls)
+#[contracttype]
+#[derive(Clone)]
+pub struct ThreadMeta {
+    pub id: u64,
+    pub board_id: u64,
+    pub title: String,
+    pub creator: Address,
+    pub created_at: u64,
+    pub updated_at: u64,
+    pub reply_count: u32,
+    pub is_locked: bool,
+    pub is_pinned: bool,
+    pub is_hidden: bool,
+    pub is_deleted: bool,
+    pub flair_id: Option<u32>,
+}
+
 /// Minimal community info for navigation (used by board contract)
 #[contracttype]
 #[derive(Clone)]
@@ -406,6 +451,8 @@ impl BoardsCommunity {
         env.storage()
             .persistent()
             .set(&CommunityKey::CommunityMembers(community_id), &members);
+        Self::index_user_community(&env, &caller, community_id);
+        Self::index_owned_community(&env, &caller, community_id, true);
 
         // Initialize default permission settings
         let perm_defaults = CommunityPermissionDefaults {
@@ -443,6 +490,7 @@ impl BoardsCommunity {
             &Symbol::new(&env, "record_first_seen"),
             record_args,
         );
+        Self::sync_community_owner(&env, &permissions, &community);
 
         community_id
     }
@@ -640,6 +688,7 @@ impl BoardsCommunity {
         env.storage()
             .persistent()
             .set(&CommunityKey::BoardCommunity(board_id), &community_id);
+        Self::request_set_board_community(&env, board_id, Some(community_id));
 
         // Handle slug management: get board's slug and check for conflicts in community scope
         let mut board_slug = Self::get_board_slug_from_contract(&env, board_id);
@@ -664,7 +713,7 @@ impl BoardsCommunity {
         Self::request_remove_standalone_slug(&env, board_id);
 
         // Store the community slug in the board contract for efficient URL generation
-        Self::request_set_board_community_slug(&env, board_id, &community.name);
+        Self::request_set_board_community_slug(&env, board_id, community_id, &community.name);
 
         // Update board count
         community.board_count += 1;
@@ -713,6 +762,7 @@ impl BoardsCommunity {
         env.storage()
             .persistent()
             .remove(&CommunityKey::BoardCommunity(board_id));
+        Self::request_set_board_community(&env, board_id, None);
 
         // Remove from community slug index
         if let Some(slug) = board_slug {
@@ -721,6 +771,11 @@ impl BoardsCommunity {
                 .remove(&CommunityKey::CommunityBoardBySlug(community_id, slug));
         }
 
+        // Drop the board's group; the stored order is filtered on read
+        env.storage()
+            .persistent()
+            .remove(&CommunityKey::CommunityBoardGroup(community_id, board_id));
+
         // Clear the community slug from the board contract
         Self::request_clear_board_community_slug(&env, board_id);
 
@@ -759,6 +814,108 @@ impl BoardsCommunity {
             .get(&CommunityKey::CommunityBoardBySlug(community_id, slug))
     }
 
+    /// Set the display order of a community's boards (owner or admin).
+    /// Boards left out of the order are shown after it, in id order.
+    pub fn set_board_order(
+        env: Env,
+        community_id: u64,
+        ordered_board_ids: Vec<u64>,
+        caller: Address,
+    ) {
+        caller.require_auth();
+
+        let community =
+            Self::get_community(env.clone(), community_id).expect("Community not found");
+        if !Self::is_owner_or_admin(&env, &community, &caller) {
+            panic!("Only community owner or admin can order boards");
+        }
+
+        for (i, board_id) in ordered_board_ids.iter().enumerate() {
+            if Self::get_board_community(env.clone(), board_id) != Some(community_id) {
+                panic!("Board {} is not in this community", board_id);
+            }
+            if ordered_board_ids.first_index_of(board_id) != Some(i as u32) {
+                panic!("Board {} is listed more than once", board_id);
+            }
+        }
+
+        env.storage().persistent().set(
+            &CommunityKey::CommunityBoardOrder(community_id),
+            &ordered_board_ids,
+        );
+    }
+
+    /// Get a community's boards in display order: the explicit order first,
+    /// then any remaining boards in id order
+    pub fn get_board_order(env: Env, community_id: u64) -> Vec<u64> {
+        let boards = Self::get_community_boards(env.clone(), community_id);
+        let explicit: Vec<u64> = env
+            .storage()
+            .persistent()
+            .get(&CommunityKey::CommunityBoardOrder(community_id))
+            .unwrap_or_else(|| Vec::new(&env));
+
+        let mut ordered: Vec<u64> = Vec::new(&env);
+        for board_id in explicit.iter() {
+            // Boards removed since the order was set are dropped
+            if boards.contains(board_id) {
+                ordered.push_back(board_id);
+            }
+        }
+
+        let mut rest: Vec<u64> = Vec::new(&env);
+        for board_id in boards.iter() {
+            if ordered.contains(board_id) {
+                continue;
+            }
+            let mut pos = rest.len();
+            while pos > 0 && rest.get(pos - 1).unwrap() > board_id {
+                pos -= 1;
+            }
+            rest.insert(pos, board_id);
+        }
+        ordered.append(&rest);
+        ordered
+    }
+
+    /// Put a board under a named group header on the community page (owner or
+    /// admin). An empty group name removes the board from its group.
+    pub fn set_board_group(
+        env: Env,
+        community_id: u64,
+        board_id: u64,
+        group: String,
+        caller: Address,
+    ) {
+        caller.require_auth();
+
+        let community =
+            Self::get_community(env.clone(), community_id).expect("Community not found");
+        if !Self::is_owner_or_admin(&env, &community, &caller) {
+            panic!("Only community owner or admin can group boards");
+        }
+        if Self::get_board_community(env.clone(), board_id) != Some(community_id) {
+            panic!("Board {} is not in this community", board_id);
+        }
+        if group.len() > MAX_BOARD_GROUP_LEN {
+            panic!("Group name too long");
+        }
+
+        let key = CommunityKey::CommunityBoardGroup(community_id, board_id);
+        if group.is_empty() {
+            env.storage().persistent().remove(&key);
+        } else {
+            env.storage().persistent().set(&key, &group);
+        }
+    }
+
+    /// Get the group a board is shown under, if any
+    pub fn get_board_group(env: Env, community_id: u64, board_id: u64) -> Option<String> {
+        env.storage()
+            .persistent()
+            .get(&CommunityKey::CommunityBoardGroup(community_id, board_id))
+    }
+
     /// Get board slug from the board contract
     fn get_board_slug_from_contract(env: &Env, board_id: u64) -> Option<String> {
         let registry: Address = env.storage().instance().get(&CommunityKey::Registry)?;
@@ -941,8 +1098,14 @@ impl BoardsCommunity {
         None
     }
 
-    /// Tell board contract to store community slug for efficient URL generation
-    fn request_set_board_community_slug(env: &Env, board_id: u64, community_slug: &String) {
+    /// Tell board contract to store community slug for efficient URL generation,
+    /// and the community ID for its board listing indexes
+    fn request_set_board_community_slug(
+        env: &Env,
+        board_id: u64,
+        community_id: u64,
+        community_slug: &String,
+    ) {
         let registry_opt: Option<Address> = env.storage().instance().get(&CommunityKey::Registry);
         let registry = match registry_opt {
             Some(r) => r,
@@ -975,6 +1138,20 @@ impl BoardsCommunity {
                 &Symbol::new(env, "set_board_community_slug"),
                 args,
             );
+
+            let id_args: Vec<Val> = Vec::from_array(
+                env,
+                [
+                    board_id.into_val(env),
+                    community_id.into_val(env),
+                    community_contract.into_val(env),
+                ],
+            );
+            let _ = env.try_invoke_contract::<(), soroban_sdk::Error>(
+                &board_contract,
+                &Symbol::new(env, "set_board_community_id"),
+                id_args,
+            );
         }
     }
 
@@ -1133,6 +1310,10 @@ impl BoardsCommunity {
             }
         }
 
+        if Self::has_join_request(&env, community_id, &caller) {
+            panic!("Join request already pending");
+        }
+
         // Add join request
         let mut requests: Vec<JoinRequest> = env
             .storage()
@@ -1192,38 +1373,239 @@ impl BoardsCommunity {
             &new_requests,
         );
 
-        // Add to members
-        let mut members: Vec<Address> = env
+        // Add to members and update member count
+        if Self::add_member(&env, &mut community, &user) {
+            env.storage()
+                .persistent()
+                .set(&CommunityKey::Community(community_id), &community);
+        }
+    }
+
+    /// Check if user is a member of community
+    pub fn is_member(env: Env, community_id: u64, user: Address) -> bool {
+        let members: Vec<Address> = env
             .storage()
             .persistent()
             .get(&CommunityKey::CommunityMembers(community_id))
             .unwrap_or_else(|| Vec::new(&env));
-        members.push_back(user);
-        env.storage()
+
+        for member in members.iter() {
+            if member == user {
+                return true;
+            }
+        }
+        false
+    }
+
+    /// Join a public community. Joining twice is a no-op; private communities
+    /// go through request_join instead.
+    pub fn join_community(env: Env, community_id: u64, caller: Address) {
+        caller.require_auth();
+
+        let mut community: CommunityMeta = env
+            .storage()
             .persistent()
-            .set(&CommunityKey::CommunityMembers(community_id), &members);
+            .get(&CommunityKey::Community(community_id))
+            .expect("Community does not exist");
 
-        // Update member count
-        community.member_count += 1;
+        if community.is_private {
+            panic!("Community is private, request to join instead");
+        }
+
+        if Self::add_member(&env, &mut community, &caller) {
+            env.storage()
+                .persistent()
+                .set(&CommunityKey::Community(community_id), &community);
+        }
+    }
+
+    /// Leave a community. Leaving without being a member is a no-op.
+    pub fn leave_community(env: Env, community_id: u64, caller: Address) {
+        caller.require_auth();
+
+        let mut community: CommunityMeta = env
+            .storage()
+            .persistent()
+            .get(&CommunityKey::Community(community_id))
+            .expect("Community does not exist");
+
+        if caller == community.owner {
+            panic!("Community owner cannot leave, transfer ownership first");
+        }
+
+        let key = CommunityKey::CommunityMembers(community_id);
+        let mut members: Vec<Address> = env
+            .storage()
+            .persistent()
+            .get(&key)
+            .unwrap_or_else(|| Vec::new(&env));
+        let Some(index) = members.first_index_of(&caller) else {
+            return;
+        };
+        members.remove(index);
+        env.storage().persistent().set(&key, &members);
+
+        community.member_count = community.member_count.saturating_sub(1);
         env.storage()
             .persistent()
             .set(&CommunityKey::Community(community_id), &community);
+
+        let user_key = CommunityKey::UserCommunities(caller);
+        let mut joined: Vec<u64> = env
+            .storage()
+            .persistent()
+            .get(&user_key)
+            .unwrap_or_else(|| Vec::new(&env));
+        if let Some(index) = joined.first_index_of(community_id) {
+            joined.remove(index);
+            env.storage().persistent().set(&user_key, &joined);
+        }
     }
 
-    /// Check if user is a member of community
-    pub fn is_member(env: Env, community_id: u64, user: Address) -> bool {
-        let members: Vec<Address> = env
+    /// Get the communities a user has joined, most recently joined first
+    pub fn get_user_communities(env: Env, user: Address) -> Vec<CommunityMeta> {
+        let joined: Vec<u64> = env
             .storage()
             .persistent()
-            .get(&CommunityKey::CommunityMembers(community_id))
+            .get(&CommunityKey::UserCommunities(user))
             .unwrap_or_else(|| Vec::new(&env));
 
-        for member in members.iter() {
-            if member == user {
-                return true;
+        let mut result = Vec::new(&env);
+        for i in (0..joined.len()).rev() {
+            // Deleted communities are skipped
+            if let Some(community) = Self::get_community(env.clone(), joined.get_unchecked(i)) {
+                result.push_back(community);
             }
         }
-        false
+        result
+    }
+
+    /// Get the communities a user owns, oldest first
+    pub fn get_owned_communities(env: Env, user: Address) -> Vec<CommunityMeta> {
+        let owned: Vec<u64> = env
+            .storage()
+            .persistent()
+            .get(&CommunityKey::UserOwnedCommunities(user))
+            .unwrap_or_else(|| Vec::new(&env));
+
+        let mut result = Vec::new(&env);
+        for community_id in owned.iter() {
+            if let Some(community) = Self::get_community(env.clone(), community_id) {
+                result.push_back(community);
+            }
+        }
+        result
+    }
+
+    /// Add communities created before the owner index existed to it, `limit`
+    /// communities at a time from `start` (registry admin only). Returns where
+    /// to continue, or None once every community is indexed.
+    pub fn index_community_owners(
+        env: Env,
+        start: u64,
+        limit: u64,
+        caller: Address,
+    ) -> Option<u64> {
+        caller.require_auth();
+
+        let registry: Address = env
+            .storage()
+            .instance()
+            .get(&CommunityKey::Registry)
+            .expect("Not initialized");
+        let admin_args: Vec<Val> = Vec::from_array(&env, [caller.into_val(&env)]);
+        let is_registry_admin: bool = env
+            .try_invoke_contract::<bool, soroban_sdk::Error>(
+                &registry,
+                &Symbol::new(&env, "is_admin"),
+                admin_args,
+            )
+            .ok()
+            .and_then(|r| r.ok())
+            .unwrap_or(false);
+        if !is_registry_admin {
+            panic!("Only registry admin can index communities");
+        }
+
+        let count = Self::community_count(env.clone());
+        let end = start.saturating_add(limit).min(count);
+        for community_id in start..end {
+            if let Some(community) = Self::get_community(env.clone(), community_id) {
+                Self::index_owned_community(&env, &community.owner, community_id, true);
+            }
+        }
+        if end < count {
+            Some(end)
+        } else {
+            None
+        }
+    }
+
+    /// Add a community to or drop it from a user's owned list. Owners are
+    /// limited by the creation thresholds, so the list stays short.
+    fn index_owned_community(env: &Env, user: &Address, community_id: u64, owned: bool) {
+        let key = CommunityKey::UserOwnedCommunities(user.clone());
+        let mut list: Vec<u64> = env
+            .storage()
+            .persistent()
+            .get(&key)
+            .unwrap_or_else(|| Vec::new(env));
+        match (list.binary_search(community_id), owned) {
+            (Err(pos), true) => list.insert(pos, community_id),
+            (Ok(pos), false) => {
+                list.remove(pos);
+            }
+            _ => return,
+        }
+        env.storage().persistent().set(&key, &list);
+    }
+
+    /// Add a user to a community's member list and count.
+    /// Returns false (and changes nothing) if they were already a member.
+    fn add_member(env: &Env, community: &mut CommunityMeta, user: &Address) -> bool {
+        let key = CommunityKey::CommunityMembers(community.id);
+        let mut members: Vec<Address> = env
+            .storage()
+            .persistent()
+            .get(&key)
+            .unwrap_or_else(|| Vec::new(env));
+        if members.contains(user) {
+            return false;
+        }
+        members.push_back(user.clone());
+        env.storage().persistent().set(&key, &members);
+
+        community.member_count += 1;
+        Self::index_user_community(env, user, community.id);
+        true
+    }
+
+    /// Record a community in a user's joined list
+    fn index_user_community(env: &Env, user: &Address, community_id: u64) {
+        let key = CommunityKey::UserCommunities(user.clone());
+        let mut joined: Vec<u64> = env
+            .storage()
+            .persistent()
+            .get(&key)
+            .unwrap_or_else(|| Vec::new(env));
+        if joined.contains(community_id) {
+            return;
+        }
+        if joined.len() >= MAX_JOINED_COMMUNITIES {
+            joined.remove(0);
+        }
+        joined.push_back(community_id);
+        env.storage().persistent().set(&key, &joined);
+    }
+
+    /// Check whether a user has a pending join request
+    fn has_join_request(env: &Env, community_id: u64, user: &Address) -> bool {
+        let requests: Vec<JoinRequest> = env
+            .storage()
+            .persistent()
+            .get(&CommunityKey::CommunityJoinRequests(community_id))
+            .unwrap_or_else(|| Vec::new(env));
+        requests.iter().any(|req| req.user == *user)
     }
 
     // === Community Admin Management ===
@@ -1308,44 +1690,201 @@ impl BoardsCommunity {
         }
     }
 
-    /// Get communities where user is owner, admin, or registry admin
-    pub fn get_manageable_communities(env: Env, user: Address) -> Vec<CommunityInfo> {
-        let mut result: Vec<CommunityInfo> = Vec::new(&env);
-        let count = Self::community_count(env.clone());
+    // === Community Moderator Team ===
 
-        // Check if user is a registry admin (they can manage all communities)
-        let is_registry_admin = if let Some(registry) = env
-            .storage()
-            .instance()
-            .get::<CommunityKey, Address>(&CommunityKey::Registry)
-        {
-            let admin_args: Vec<Val> = Vec::from_array(&env, [user.clone().into_val(&env)]);
-            env.try_invoke_contract::<bool, soroban_sdk::Error>(
-                &registry,
-                &Symbol::new(&env, "is_admin"),
-                admin_args,
-            )
-            .ok()
-            .and_then(|r| r.ok())
-            .unwrap_or(false)
-        } else {
-            false
-        };
+    /// Add a user to the community moderator team (owner only).
+    /// Team members are treated as at least Moderator on every board in the
+    /// community by the permissions contract.
+    pub fn set_community_moderator(env: Env, community_id: u64, user: Address, caller: Address) {
+        caller.require_auth();
 
-        for id in 0..count {
-            if let Some(community) = Self::get_community(env.clone(), id) {
-                // Registry admins can manage all communities
-                if is_registry_admin || Self::is_owner_or_admin(&env, &community, &user) {
-                    result.push_back(CommunityInfo {
-                        id: community.id,
-                        name: community.name.clone(),
-                        display_name: community.display_name.clone(),
-                    });
-                }
-            }
+        let community =
+            Self::get_community(env.clone(), community_id).expect("Community not found");
+        if caller != community.owner {
+            panic!("Only community owner can set moderators");
         }
 
-        result
+        let perms: Address = env
+            .storage()
+            .instance()
+            .get(&CommunityKey::Permissions)
+            .expect("Permissions not initialized");
+        Self::sync_community_owner(&env, &perms, &community);
+        if Self::community_role(&env, &perms, community_id, &user) != Role::Guest {
+            return;
+        }
+
+        let args: Vec<Val> = Vec::from_array(
+            &env,
+            [
+                community_id.into_val(&env),
+                user.into_val(&env),
+                (Role::Moderator as u32).into_val(&env),
+                caller.into_val(&env),
+            ],
+        );
+        env.invoke_contract::<()>(&perms, &Symbol::new(&env, "set_community_role"), args);
+    }
+
+    /// Remove a user from the community moderator team (owner only)
+    pub fn remove_community_moderator(env: Env, community_id: u64, user: Address, caller: Address) {
+        caller.require_auth();
+
+        let community =
+            Self::get_community(env.clone(), community_id).expect("Community not found");
+        if caller != community.owner {
+            panic!("Only community owner can remove moderators");
+        }
+
+        let perms: Address = env
+            .storage()
+            .instance()
+            .get(&CommunityKey::Permissions)
+            .expect("Permissions not initialized");
+        Self::sync_community_owner(&env, &perms, &community);
+        if Self::community_role(&env, &perms, community_id, &user) != Role::Moderator {
+            return;
+        }
+
+        let args: Vec<Val> = Vec::from_array(
+            &env,
+            [
+                community_id.into_val(&env),
+                user.into_val(&env),
+                (Role::Guest as u32).into_val(&env),
+                caller.into_val(&env),
+            ],
+        );
+        env.invoke_contract::<()>(&perms, &Symbol::new(&env, "set_community_role"), args);
+    }
+
+    /// Get the community moderator team
+    pub fn get_community_moderators(env: Env, community_id: u64) -> Vec<Address> {
+        let perms_opt: Option<Address> = env.storage().instance().get(&CommunityKey::Permissions);
+        if let Some(perms) = perms_opt {
+            let args: Vec<Val> = Vec::from_array(&env, [community_id.into_val(&env)]);
+            env.try_invoke_contract::<Vec<Address>, soroban_sdk::Error>(
+                &perms,
+                &Symbol::new(&env, "list_community_moderators"),
+                args,
+            )
+            .ok()
+            .and_then(|r| r.ok())
+            .unwrap_or(Vec::new(&env))
+        } else {
+            Vec::new(&env)
+        }
+    }
+
+    /// Make sure the permissions contract knows the current owner, which it
+    /// checks before letting them set community roles
+    fn sync_community_owner(env: &Env, perms: &Address, community: &CommunityMeta) {
+        let args: Vec<Val> = Vec::from_array(
+            env,
+            [
+                community.id.into_val(env),
+                community.owner.clone().into_val(env),
+            ],
+        );
+        let _ = env.try_invoke_contract::<(), soroban_sdk::Error>(
+            perms,
+            &Symbol::new(env, "set_community_owner"),
+            args,
+        );
+    }
+
+    /// A user's role in a community as stored by the permissions contract
+    fn community_role(env: &Env, perms: &Address, community_id: u64, user: &Address) -> Role {
+        let args: Vec<Val> = Vec::from_array(
+            env,
+            [community_id.into_val(env), user.clone().into_val(env)],
+        );
+        env.invoke_contract(perms, &Symbol::new(env, "get_community_role"), args)
+    }
+
+    /// Tell the permissions contract which community a board belongs to, so
+    /// community moderators cascade to it without a lookup per role check
+    fn request_set_board_community(env: &Env, board_id: u64, community_id: Option<u64>) {
+        let perms_opt: Option<Address> = env.storage().instance().get(&CommunityKey::Permissions);
+        if let Some(perms) = perms_opt {
+            let args: Vec<Val> =
+                Vec::from_array(env, [board_id.into_val(env), community_id.into_val(env)]);
+            let _ = env.try_invoke_contract::<(), soroban_sdk::Error>(
+                &perms,
+                &Symbol::new(env, "set_board_community"),
+                args,
+            );
+        }
+    }
+
+    /// Record a page of the community's boards with the permissions contract
+    /// (owner or admin). Backfills boards added before moderators cascaded.
+    /// Returns where to continue, or None once every board is recorded.
+    pub fn sync_board_communities(
+        env: Env,
+        community_id: u64,
+        start: u32,
+        limit: u32,
+        caller: Address,
+    ) -> Option<u32> {
+        caller.require_auth();
+
+        let community =
+            Self::get_community(env.clone(), community_id).expect("Community not found");
+        if !Self::is_owner_or_admin(&env, &community, &caller) {
+            panic!("Only community owner or admin can sync boards");
+        }
+
+        let boards = Self::get_community_boards(env.clone(), community_id);
+        let end = start.saturating_add(limit).min(boards.len());
+        for i in start..end {
+            Self::request_set_board_community(&env, boards.get(i).unwrap(), Some(community_id));
+        }
+        if end < boards.len() {
+            Some(end)
+        } else {
+            None
+        }
+    }
+
+    /// Get communities where user is owner, admin, or registry admin
+    pub fn get_manageable_communities(env: Env, user: Address) -> Vec<CommunityInfo> {
+        let mut result: Vec<CommunityInfo> = Vec::new(&env);
+        let count = Self::community_count(env.clone());
+
+        // Check if user is a registry admin (they can manage all communities)
+        let is_registry_admin = if let Some(registry) = env
+            .storage()
+            .instance()
+            .get::<CommunityKey, Address>(&CommunityKey::Registry)
+        {
+            let admin_args: Vec<Val> = Vec::from_array(&env, [user.clone().into_val(&env)]);
+            env.try_invoke_contract::<bool, soroban_sdk::Error>(
+                &registry,
+                &Symbol::new(&env, "is_admin"),
+                admin_args,
+            )
+            .ok()
+            .and_then(|r| r.ok())
+            .unwrap_or(false)
+        } else {
+            false
+        };
+
+        for id in 0..count {
+            if let Some(community) = Self::get_community(env.clone(), id) {
+                // Registry admins can manage all communities
+                if is_registry_admin || Self::is_owner_or_admin(&env, &community, &user) {
+                    result.push_back(CommunityInfo {
+                        id: community.id,
+                        name: community.name.clone(),
+                        display_name: community.display_name.clone(),
+                    });
+                }
+            }
+        }
+
+        result
     }
 
     /// Get pending join requests (owner/admin only)
@@ -1492,32 +2031,22 @@ impl BoardsCommunity {
             .expect("Community does not exist");
 
         // Add new owner to members if not already
-        let mut members: Vec<Address> = env
-            .storage()
-            .persistent()
-            .get(&CommunityKey::CommunityMembers(community_id))
-            .unwrap_or_else(|| Vec::new(&env));
-
-        let mut is_member = false;
-        for member in members.iter() {
-            if member == caller {
-                is_member = true;
-                break;
-            }
-        }
-        if !is_member {
-            members.push_back(caller.clone());
-            community.member_count += 1;
-            env.storage()
-                .persistent()
-                .set(&CommunityKey::CommunityMembers(community_id), &members);
-        }
+        Self::add_member(&env, &mut community, &caller);
 
         // Transfer ownership
+        Self::index_owned_community(&env, &community.owner, community_id, false);
+        Self::index_owned_community(&env, &caller, community_id, true);
         community.owner = caller;
         env.storage()
             .persistent()
             .set(&CommunityKey::Community(community_id), &community);
+        if let Some(perms) = env
+            .storage()
+            .instance()
+            .get::<_, Address>(&CommunityKey::Permissions)
+        {
+            Self::sync_community_owner(&env, &perms, &community);
+        }
 
         // Remove pending transfer
         env.storage()
@@ -1580,6 +2109,10 @@ impl BoardsCommunity {
         env.storage()
             .persistent()
             .remove(&CommunityKey::PendingOwnershipTransfer(community_id));
+        env.storage()
+            .persistent()
+            .remove(&CommunityKey::CommunityBoardOrder(community_id));
+        Self::index_owned_community(&env, &community.owner, community_id, false);
 
         // Note: We don't decrement CommunityCount as IDs are not reused
     }
@@ -1590,7 +2123,9 @@ impl BoardsCommunity {
         // "/" or "" -> community listing
         // "/c/{name}" -> community home page
         // "/c/{name}/boards" -> list boards in community
+        // "/c/{name}/feed[/{start}]" -> recent threads across the community's boards
         // "/new" -> create community form
+        // "/my/communities" -> communities the viewer has joined
 
         let path_bytes = string_to_bytes(&env, &path);
         let path_len = path_bytes.len() as usize;
@@ -1603,6 +2138,11 @@ impl BoardsCommunity {
             return Self::render_create_form(&env, viewer);
         }
 
+        // Check for /my/communities route
+        if &buf[..copy_len] == b"/my/communities" {
+            return Self::render_my_communities(&env, viewer);
+        }
+
         // Check for /c/{name} route
         if copy_len >= 3 && &buf[0..3] == b"/c/" {
             // Extract community name from path
@@ -1617,6 +2157,23 @@ impl BoardsCommunity {
                     if sub_path.starts_with(b"/boards") {
                         return Self::render_community_boards(&env, &community_name, viewer);
                     }
+                    if sub_path == b"/feed" || sub_path.starts_with(b"/feed/") {
+                        // "/feed/{start}" starts a new page; "/feed/more/{start}" is a
+                        // waterfall batch within the current one
+                        let rest = sub_path.get(6..).unwrap_or(&[]);
+                        let (is_batch, digits) = match rest.strip_prefix(b"more/") {
+                            Some(digits) => (true, digits),
+                            None => (false, rest),
+                        };
+                        let start = Self::parse_u32(digits).unwrap_or(0);
+                        return Self::render_community_feed(
+                            &env,
+                            &community_name,
+                            start,
+                            is_batch,
+                            viewer,
+                        );
+                    }
                     if sub_path.starts_with(b"/settings") {
                         return Self::render_settings(&env, &community_name, viewer);
                     }
@@ -1782,7 +2339,7 @@ impl BoardsCommunity {
             .unwrap_or(end)
     }
 
-    fn render_community_list(env: &Env, _viewer: Option<Address>) -> Bytes {
+    fn render_community_list(env: &Env, viewer: Option<Address>) -> Bytes {
         let communities = Self::list_listed_communities(env.clone(), 0, 20);
 
         let mut builder = MarkdownBuilder::new(env);
@@ -1793,6 +2350,11 @@ impl BoardsCommunity {
         builder = builder.raw_str(
             "<p><a class=\"soroban-action\" href=\"render:/new\">Create Community</a></p>\n",
         );
+        if viewer.is_some() {
+            builder = builder.raw_str(
+                "<p><a href=\"render:/my/communities\">Your joined communities →</a></p>\n",
+            );
+        }
 
         if communities.is_empty() {
             builder = builder.paragraph("No communities yet. Be the first to create one!");
@@ -1808,6 +2370,61 @@ impl BoardsCommunity {
         builder.build()
     }
 
+    /// Render the communities the viewer has joined
+    fn render_my_communities(env: &Env, viewer: Option<Address>) -> Bytes {
+        let mut builder = MarkdownBuilder::new(env);
+        builder = builder.h1("Joined Communities");
+
+        let Some(user) = viewer else {
+            return builder
+                .warning("Connect your wallet to see the communities you have joined.")
+                .build();
+        };
+
+        let communities = Self::get_user_communities(env.clone(), user);
+        if communities.is_empty() {
+            builder = builder.paragraph("You haven't joined any communities yet.");
+            builder = builder
+                .raw_str("<p><a href=\"render:/communities\">Browse communities →</a></p>\n");
+        } else {
+            builder = builder.raw_str("<div class=\"community-list\">\n");
+            for community in communities.iter() {
+                builder = Self::append_community_card(env, builder, &community);
+            }
+            builder = builder.raw_str("</div>\n");
+        }
+
+        builder.build()
+    }
+
+    /// Render the gate of a private community for a signed-in non-member:
+    /// a join request form, or a note that their request is pending
+    fn render_join_request(env: &Env, community: &CommunityMeta, viewer: &Address) -> Bytes {
+        let mut builder = MarkdownBuilder::new(env);
+        builder = builder.paragraph("This is a private community. Join to view content.");
+
+        if Self::has_join_request(env, community.id, viewer) {
+            return builder
+                .note("Your join request is waiting for a community admin.")
+                .build();
+        }
+
+        builder = builder.raw_str("<input type=\"hidden\" name=\"community_id\" value=\"");
+        builder = builder.number(community.id as u32);
+        builder = builder.raw_str("\" />\n");
+        builder = builder.raw_str("<label>Message (optional):</label>\n");
+        builder = builder.raw_str("<textarea name=\"message\" rows=\"3\"></textarea>\n");
+        builder = builder.raw_str("<input type=\"hidden\" name=\"caller\" value=\"");
+        builder = builder.text_string(&viewer.to_string());
+        builder = builder.raw_str("\" />\n");
+        builder = builder.raw_str("<input type=\"hidden\" name=\"_redirect\" value=\"/c/");
+        builder = builder.text_string(&community.name);
+        builder = builder.raw_str("\" />\n");
+        builder = builder.newline();
+        builder = builder.form_link_to("Request to Join", "community", "request_join");
+        builder.build()
+    }
+
     fn append_community_card<'a>(
         _env: &'a Env,
         mut builder: MarkdownBuilder<'a>,
@@ -1855,7 +2472,8 @@ impl BoardsCommunity {
     }
 
     /// Render a list of boards as cards (fetches metadata from board contracts)
-    /// Admins and board owners can see hidden boards with a "hidden" badge
+    /// Admins and board owners can see hidden boards with a "hidden" badge.
+    /// Archived boards are left out for everyone.
     fn render_board_cards<'a>(
         env: &'a Env,
         mut builder: MarkdownBuilder<'a>,
@@ -1915,59 +2533,116 @@ impl BoardsCommunity {
             }
         };
 
-        builder = builder.raw_str("<div class=\"board-list\">\n");
-
+        // Boards render in sections, one per group in order of first appearance.
+        // Ungrouped boards form a section whose header is only shown after others.
+        let empty = String::from_str(env, "");
+        let mut groups: Vec<String> = Vec::new(env);
+        let mut sections: Vec<String> = Vec::new(env);
         for board_id in board_ids.iter() {
-            // Get board metadata (includes is_listed and thread_count)
-            let board_opt: Option<BoardMeta> = env
-                .try_invoke_contract::<BoardMeta, soroban_sdk::Error>(
-                    &board_contract,
-                    &Symbol::new(env, "get_board"),
-                    Vec::from_array(env, [board_id.into_val(env)]),
-                )
-                .ok()
-                .and_then(|r| r.ok());
-
-            if let Some(board) = board_opt {
-                // Check if viewer can see hidden boards
-                let viewer_is_board_owner = if let Some(ref v) = viewer {
-                    *v == board.creator
-                } else {
-                    false
-                };
-
-                let can_see_hidden =
-                    viewer_is_community_admin || viewer_is_registry_admin || viewer_is_board_owner;
+            let group =
+                Self::get_board_group(env.clone(), community.id, board_id).unwrap_or(empty.clone());
+            if !sections.contains(&group) {
+                sections.push_back(group.clone());
+            }
+            groups.push_back(group);
+        }
 
-                // Skip hidden boards unless viewer has permission
-                if !board.is_listed && !can_see_hidden {
+        let mut list_open = false;
+        for section in sections.iter() {
+            let mut header_pending = !section.is_empty() || list_open;
+            for (i, board_id) in board_ids.iter().enumerate() {
+                if groups.get(i as u32).unwrap() != section {
                     continue;
                 }
 
-                // Board card with link wrapper - use slug-based URL: /c/{community}/b/{slug}
-                builder = builder
-                    .raw_str("<a href=\"render:/c/")
-                    .text_string(&community.name)
-                    .raw_str("/b/")
-                    .text_string(&board.slug)
-                    .raw_str("\" class=\"board-card\"><span class=\"board-card-title\">")
-                    .text_string(&board.name)
-                    .raw_str("</span><span class=\"board-card-desc\">")
-                    .text_string(&board.description)
-                    .raw_str("</span><span class=\"board-card-meta\">")
-                    .number(board.thread_count as u32)
-                    .text(" threads");
-                if board.is_private {
-                    builder = builder.raw_str(" <span class=\"badge\">private</span>");
-                }
-                if !board.is_listed {
-                    builder = builder.raw_str(" <span class=\"badge\">hidden</span>");
+                // Get board metadata (includes is_listed and thread_count)
+                let board_opt: Option<BoardMeta> = env
+                    .try_invoke_contract::<BoardMeta, soroban_sdk::Error>(
+                        &board_contract,
+                        &Symbol::new(env, "get_board"),
+                        Vec::from_array(env, [board_id.into_val(env)]),
+                    )
+                    .ok()
+                    .and_then(|r| r.ok());
+
+                if let Some(board) = board_opt {
+                    // Check if viewer can see hidden boards
+                    let viewer_is_board_owner = if let Some(ref v) = viewer {
+                        *v == board.creator
+                    } else {
+                        false
+                    };
+
+                    let can_see_hidden = viewer_is_community_admin
+                        || viewer_is_registry_admin
+                        || viewer_is_board_owner;
+
+                    // Skip hidden boards unless viewer has permission
+                    if !board.is_listed && !can_see_hidden {
+                        continue;
+                    }
+
+                    // Archived boards are unlisted too, but are left out for everyone
+                    if !board.is_listed {
+                        let is_archived = env
+                            .try_invoke_contract::<bool, soroban_sdk::Error>(
+                                &board_contract,
+                                &Symbol::new(env, "is_archived"),
+                                Vec::from_array(env, [board_id.into_val(env)]),
+                            )
+                            .ok()
+                            .and_then(|r| r.ok())
+                            .unwrap_or(false);
+                        if is_archived {
+                            continue;
+                        }
+                    }
+
+                    if header_pending {
+                        if list_open {
+                            builder = builder.raw_str("</div>\n");
+                        }
+                        builder = builder.raw_str("<h3 class=\"board-group\">");
+                        builder = if section.is_empty() {
+                            builder.text("Other Boards")
+                        } else {
+                            builder.text_string(&section)
+                        };
+                        builder = builder.raw_str("</h3>\n<div class=\"board-list\">\n");
+                        list_open = true;
+                        header_pending = false;
+                    } else if !list_open {
+                        builder = builder.raw_str("<div class=\"board-list\">\n");
+                        list_open = true;
+                    }
+
+                    // Board card with link wrapper - use slug-based URL: /c/{community}/b/{slug}
+                    builder = builder
+                        .raw_str("<a href=\"render:/c/")
+                        .text_string(&community.name)
+                        .raw_str("/b/")
+                        .text_string(&board.slug)
+                        .raw_str("\" class=\"board-card\"><span class=\"board-card-title\">")
+                        .text_string(&board.name)
+                        .raw_str("</span><span class=\"board-card-desc\">")
+                        .text_string(&board.description)
+                        .raw_str("</span><span class=\"board-card-meta\">")
+                        .number(board.thread_count as u32)
+                        .text(" threads");
+                    if board.is_private {
+                        builder = builder.raw_str(" <span class=\"badge\">private</span>");
+                    }
+                    if !board.is_listed {
+                        builder = builder.raw_str(" <span class=\"badge\">hidden</span>");
+                    }
+                    builder = builder.raw_str("</span></a>\n");
                 }
-                builder = builder.raw_str("</span></a>\n");
             }
         }
 
-        builder = builder.raw_str("</div>\n");
+        if list_open {
+            builder = builder.raw_str("</div>\n");
+        }
         builder
     }
 
@@ -1985,9 +2660,7 @@ impl BoardsCommunity {
         if community.is_private {
             if let Some(ref v) = viewer {
                 if !Self::is_member(env.clone(), community.id, v.clone()) && *v != community.owner {
-                    return MarkdownBuilder::new(env)
-                        .paragraph("This is a private community. Join to view content.")
-                        .build();
+                    return Self::render_join_request(env, &community, v);
                 }
             } else {
                 return MarkdownBuilder::new(env)
@@ -2034,6 +2707,29 @@ impl BoardsCommunity {
         builder = builder.number(community.member_count as u32);
         builder = builder.raw_str(" members</span></div>\n");
 
+        // Join/Leave for signed-in viewers other than the owner
+        if let Some(ref v) = viewer {
+            if *v != community.owner {
+                let (label, func) = if Self::is_member(env.clone(), community.id, v.clone()) {
+                    ("Leave Community", "leave_community")
+                } else {
+                    ("Join Community", "join_community")
+                };
+                builder = builder.raw_str("<div class=\"community-membership\">\n");
+                builder = builder.raw_str("<input type=\"hidden\" name=\"community_id\" value=\"");
+                builder = builder.number(community.id as u32);
+                builder = builder.raw_str("\" />\n");
+                builder = builder.raw_str("<input type=\"hidden\" name=\"caller\" value=\"");
+                builder = builder.text_string(&v.to_string());
+                builder = builder.raw_str("\" />\n");
+                builder = builder.raw_str("<input type=\"hidden\" name=\"_redirect\" value=\"/c/");
+                builder = builder.text_string(&community.name);
+                builder = builder.raw_str("\" />\n");
+                builder = builder.form_link_to(label, "community", func);
+                builder = builder.raw_str("</div>\n");
+            }
+        }
+
         // Actions based on viewer (owner, community admin, or registry admin)
         // Check if viewer is a registry admin
         let viewer_is_registry_admin = if let Some(ref v) = viewer {
@@ -2090,10 +2786,15 @@ impl BoardsCommunity {
             }
         }
 
+        // Recent threads from every board in one list
+        builder = builder.raw_str("<p><a href=\"render:/c/");
+        builder = builder.text_string(&community.name);
+        builder = builder.raw_str("/feed\">View the community feed →</a></p>\n");
+
         // List boards in community
         builder = builder.newline();
         builder = builder.h2("Boards");
-        let board_ids = Self::get_community_boards(env.clone(), community.id);
+        let board_ids = Self::get_board_order(env.clone(), community.id);
         builder = Self::render_board_cards(env, builder, &board_ids, &viewer, &community);
 
         // Rules if set
@@ -2112,34 +2813,237 @@ impl BoardsCommunity {
         builder.build()
     }
 
-    fn render_community_boards(env: &Env, name: &String, viewer: Option<Address>) -> Bytes {
-        let community = match Self::get_community_by_name(env.clone(), name.clone()) {
-            Some(c) => c,
-            None => {
-                return MarkdownBuilder::new(env)
-                    .paragraph("Community not found")
-                    .build();
-            }
-        };
-
-        // Display name for title
-        let mut display_buf = [0u8; 128];
-        let display_len = community.display_name.len() as usize;
-        let display_copy_len = core::cmp::min(display_len, 128);
-        community
-            .display_name
-            .copy_into_slice(&mut display_buf[0..display_copy_len]);
-        let display =
-            core::str::from_utf8(&display_buf[0..display_copy_len]).unwrap_or("Community");
-
-        let mut builder = MarkdownBuilder::new(env);
-        builder = builder.h1(display);
-        builder = builder.h2("Boards");
-
-        let board_ids = Self::get_community_boards(env.clone(), community.id);
-        builder = Self::render_board_cards(env, builder, &board_ids, &viewer, &community);
+    fn render_community_boards(env: &Env, name: &String, viewer: Option<Address>) -> Bytes {
+        let community = match Self::get_community_by_name(env.clone(), name.clone()) {
+            Some(c) => c,
+            None => {
+                return MarkdownBuilder::new(env)
+                    .paragraph("Community not found")
+                    .build();
+            }
+        };
+
+        // Display name for title
+        let mut display_buf = [0u8; 128];
+        let display_len = community.display_name.len() as usize;
+        let display_copy_len = core::cmp::min(display_len, 128);
+        community
+            .display_name
+            .copy_into_slice(&mut display_buf[0..display_copy_len]);
+        let display =
+            core::str::from_utf8(&display_buf[0..display_copy_len]).unwrap_or("Community");
+
+        let mut builder = MarkdownBuilder::new(env);
+        builder = builder.h1(display);
+        builder = builder.h2("Boards");
+
+        let board_ids = Self::get_board_order(env.clone(), community.id);
+        builder = Self::render_board_cards(env, builder, &board_ids, &viewer, &community);
+
+        builder.build()
+    }
+
+    /// Render the community feed: the newest threads of a batch of boards merged
+    /// by creation time. Later batches chain by waterfall until the page's board
+    /// budget is spent, then a "Load more" link continues on a new page.
+    /// Unlisted boards, and private boards the viewer can't read, are skipped.
+    fn render_community_feed(
+        env: &Env,
+        name: &String,
+        start: u32,
+        is_batch: bool,
+        viewer: Option<Address>,
+    ) -> Bytes {
+        let mut builder = MarkdownBuilder::new(env);
+        let Some(community) = Self::get_community_by_name(env.clone(), name.clone()) else {
+            return builder.paragraph("Community not found").build();
+        };
+        if community.is_private {
+            let can_view = viewer.as_ref().is_some_and(|v| {
+                *v == community.owner || Self::is_member(env.clone(), community.id, v.clone())
+            });
+            if !can_view {
+                return builder
+                    .paragraph("This is a private community. Join to view content.")
+                    .build();
+            }
+        }
+
+        if !is_batch {
+            builder = builder
+                .div_start("back-nav")
+                .raw_str("<a href=\"render:/c/")
+                .text_string(&community.name)
+                .raw_str("\" class=\"back-link\">← ")
+                .text_string(&community.display_name)
+                .raw_str("</a>")
+                .div_end()
+                .newline()
+                .h1("Feed");
+        }
+
+        let board_ids = Self::get_community_boards(env.clone(), community.id);
+        let board_contract = env
+            .storage()
+            .instance()
+            .get::<_, Address>(&CommunityKey::Registry)
+            .and_then(|registry| {
+                env.try_invoke_contract::<Option<Address>, soroban_sdk::Error>(
+                    &registry,
+                    &Symbol::new(env, "get_contract_by_alias"),
+                    Vec::from_array(env, [Symbol::new(env, "board").into_val(env)]),
+                )
+                .ok()
+                .and_then(|r| r.ok())
+                .flatten()
+            });
+        let Some(board_contract) = board_contract else {
+            return builder.paragraph("Board contract not configured.").build();
+        };
+
+        // Each board's list is newest first; merge the batch by repeatedly taking the
+        // newest remaining head
+        let end = (start + FEED_BOARDS_PER_BATCH).min(board_ids.len());
+        let mut boards: Vec<BoardMeta> = Vec::new(env);
+        let mut lists: Vec<Vec<ThreadMeta>> = Vec::new(env);
+        for index in start..end {
+            let board_id = board_ids.get(index).unwrap();
+            let Some(board) = env
+                .try_invoke_contract::<BoardMeta, soroban_sdk::Error>(
+                    &board_contract,
+                    &Symbol::new(env, "get_board"),
+                    Vec::from_array(env, [board_id.into_val(env)]),
+                )
+                .ok()
+                .and_then(|r| r.ok())
+            else {
+                continue;
+            };
+            let readable = !board.is_private || Self::can_read_board(env, &board, &viewer);
+            if !board.is_listed || !readable {
+                continue;
+            }
+            let threads: Vec<ThreadMeta> = env
+                .try_invoke_contract::<Vec<ThreadMeta>, soroban_sdk::Error>(
+                    &board_contract,
+                    &Symbol::new(env, "list_threads"),
+                    Vec::from_array(
+                        env,
+                        [
+                            board_id.into_val(env),
+                            0u64.into_val(env),
+                            FEED_THREADS_PER_BOARD.into_val(env),
+                        ],
+                    ),
+                )
+                .ok()
+                .and_then(|r| r.ok())
+                .unwrap_or(Vec::new(env));
+            let mut visible = Vec::new(env);
+            for thread in threads.iter() {
+                if !thread.is_hidden && !thread.is_deleted {
+                    visible.push_back(thread);
+                }
+            }
+            boards.push_back(board);
+            lists.push_back(visible);
+        }
+
+        let mut heads = [0u32; FEED_BOARDS_PER_BATCH as usize];
+        let mut shown = 0u32;
+        while shown < FEED_THREADS_PER_BATCH {
+            let mut newest: Option<(u32, ThreadMeta)> = None;
+            for i in 0..lists.len() {
+                let Some(thread) = lists.get(i).unwrap().get(heads[i as usize]) else {
+                    continue;
+                };
+                let is_newer = match &newest {
+                    Some((_, current)) => thread.created_at > current.created_at,
+                    None => true,
+                };
+                if is_newer {
+                    newest = Some((i, thread));
+                }
+            }
+            let Some((i, thread)) = newest else {
+                break;
+            };
+            heads[i as usize] += 1;
+            builder = Self::append_feed_card(builder, &community, &boards.get(i).unwrap(), &thread);
+            shown += 1;
+        }
+        if shown == 0 && !is_batch && end >= board_ids.len() {
+            builder = builder.paragraph("No threads in this community yet.");
+        }
+
+        if end < board_ids.len() {
+            if end % FEED_BOARDS_PER_PAGE != 0 {
+                builder = builder
+                    .raw_str("{{render path=\"/c/")
+                    .text_string(&community.name)
+                    .raw_str("/feed/more/")
+                    .number(end)
+                    .raw_str("\"}}");
+            } else {
+                builder = builder
+                    .raw_str("<div class=\"feed-more\"><a href=\"render:/c/")
+                    .text_string(&community.name)
+                    .raw_str("/feed/")
+                    .number(end)
+                    .raw_str("\">Load more</a></div>\n");
+            }
+        }
+
+        builder.build()
+    }
+
+    /// Thread card for the feed, prefixed with the board it came from
+    fn append_feed_card<'a>(
+        builder: MarkdownBuilder<'a>,
+        community: &CommunityMeta,
+        board: &BoardMeta,
+        thread: &ThreadMeta,
+    ) -> MarkdownBuilder<'a> {
+        builder
+            .raw_str("<div class=\"feed-item\"><a href=\"render:/c/")
+            .text_string(&community.name)
+            .raw_str("/b/")
+            .text_string(&board.slug)
+            .raw_str("\" class=\"feed-board\">")
+            .text_string(&board.name)
+            .raw_str("</a> <a href=\"render:/c/")
+            .text_string(&community.name)
+            .raw_str("/b/")
+            .text_string(&board.slug)
+            .raw_str("/t/")
+            .number(thread.id as u32)
+            .raw_str("\" class=\"thread-card\"><span class=\"thread-card-title\">")
+            .text_string(&thread.title)
+            .raw_str("</span><span class=\"thread-card-meta\">")
+            .number(thread.reply_count)
+            .raw_str(" replies</span></a></div>\n")
+    }
 
-        builder.build()
+    /// Whether the viewer is at least a member of a (private) board
+    fn can_read_board(env: &Env, board: &BoardMeta, viewer: &Option<Address>) -> bool {
+        let Some(viewer) = viewer else {
+            return false;
+        };
+        let Some(permissions) = env
+            .storage()
+            .instance()
+            .get::<_, Address>(&CommunityKey::Permissions)
+        else {
+            return false;
+        };
+        env.try_invoke_contract::<Role, soroban_sdk::Error>(
+            &permissions,
+            &Symbol::new(env, "get_role"),
+            Vec::from_array(env, [board.id.into_val(env), viewer.into_val(env)]),
+        )
+        .ok()
+        .and_then(|r| r.ok())
+        .is_some_and(|role| (role as u32) >= (Role::Member as u32))
     }
 
     fn render_settings(env: &Env, name: &String, viewer: Option<Address>) -> Bytes {
@@ -2399,6 +3303,75 @@ impl BoardsCommunity {
             builder = builder.form_link_to("Remove Admin", "community", "remove_admin");
         }
 
+        // Board Order Section
+        builder = builder.newline();
+        builder = builder.h2("Board Order");
+        builder = builder.paragraph(
+            "Boards are shown in this order. Boards left out of a new order are listed last.",
+        );
+        let board_order = Self::get_board_order(env.clone(), community.id);
+        if board_order.is_empty() {
+            builder = builder.paragraph("No boards in this community yet.");
+        } else {
+            builder = builder.raw_str("<ol class=\"board-order\">\n");
+            for board_id in board_order.iter() {
+                builder = builder.raw_str("<li><code>");
+                builder = builder.number(board_id as u32);
+                builder = builder.raw_str("</code> ");
+                if let Some(slug) = Self::get_board_slug_from_contract(env, board_id) {
+                    builder = builder.text_string(&slug);
+                }
+                if let Some(group) = Self::get_board_group(env.clone(), community.id, board_id) {
+                    builder = builder.raw_str(" <span class=\"badge\">");
+                    builder = builder.text_string(&group);
+                    builder = builder.raw_str("</span>");
+                }
+                builder = builder.raw_str("</li>\n");
+            }
+            builder = builder.raw_str("</ol>\n");
+
+            // Order form, prefilled with the current order
+            builder = builder.raw_str("<input type=\"hidden\" name=\"community_id\" value=\"");
+            builder = builder.number(community.id as u32);
+            builder = builder.raw_str("\" />\n");
+            builder = builder.raw_str("<label>Board IDs in display order:</label>\n");
+            builder = builder.raw_str("<input type=\"text\" name=\"ordered_board_ids\" value=\"[");
+            for (i, board_id) in board_order.iter().enumerate() {
+                if i > 0 {
+                    builder = builder.raw_str(", ");
+                }
+                builder = builder.number(board_id as u32);
+            }
+            builder = builder.raw_str("]\" />\n");
+            builder = builder.raw_str("<input type=\"hidden\" name=\"caller\" value=\"");
+            builder = builder.text_string(&viewer.as_ref().unwrap().to_string());
+            builder = builder.raw_str("\" />\n");
+            builder = builder.raw_str("<input type=\"hidden\" name=\"_redirect\" value=\"/c/");
+            builder = builder.text_string(&community.name);
+            builder = builder.raw_str("/settings\" />\n");
+            builder = builder.newline();
+            builder = builder.form_link_to("Save Order", "community", "set_board_order");
+
+            // Group form (an empty group name removes the board from its group)
+            builder = builder.newline();
+            builder = builder.h3("Board Group");
+            builder = builder.raw_str("<input type=\"hidden\" name=\"community_id\" value=\"");
+            builder = builder.number(community.id as u32);
+            builder = builder.raw_str("\" />\n");
+            builder = builder.raw_str("<label>Board ID:</label>\n");
+            builder = builder.raw_str("<input type=\"number\" name=\"board_id\" />\n");
+            builder = builder.raw_str("<label>Group (leave empty to ungroup):</label>\n");
+            builder = builder.raw_str("<input type=\"text\" name=\"group\" />\n");
+            builder = builder.raw_str("<input type=\"hidden\" name=\"caller\" value=\"");
+            builder = builder.text_string(&viewer.as_ref().unwrap().to_string());
+            builder = builder.raw_str("\" />\n");
+            builder = builder.raw_str("<input type=\"hidden\" name=\"_redirect\" value=\"/c/");
+            builder = builder.text_string(&community.name);
+            builder = builder.raw_str("/settings\" />\n");
+            builder = builder.newline();
+            builder = builder.form_link_to("Set Group", "community", "set_board_group");
+        }
+
         // Danger Zone
         builder = builder.newline();
         builder = builder.h2("Danger Zone");
@@ -2703,6 +3676,21 @@ impl BoardsCommunity {
         String::from_str(env, core::str::from_utf8(bytes).unwrap_or(""))
     }
 
+    /// Parse an unsigned decimal path segment
+    fn parse_u32(digits: &[u8]) -> Option<u32> {
+        if digits.is_empty() {
+            return None;
+        }
+        let mut value: u32 = 0;
+        for &b in digits {
+            if !b.is_ascii_digit() {
+                return None;
+            }
+            value = value.checked_mul(10)?.checked_add((b - b'0') as u32)?;
+        }
+        Some(value)
+    }
+
     fn find_next_slash(buf: &[u8], start: usize, end: usize) -> usize {
         buf[start..end]
             .iter()
@@ -2727,6 +3715,8 @@ impl BoardsCommunity {
 
 #[cfg(test)]
 mod test {
+    extern crate std;
+
     use super::*;
     use soroban_sdk::testutils::Address as _;
     use soroban_sdk::Env;
@@ -3175,6 +4165,517 @@ mod test {
         assert_eq!(admins.len(), 0);
     }
 
+    /// Minimal registry that resolves the "board" alias
+    #[contract]
+    pub struct MockRegistry;
+
+    #[contractimpl]
+    impl MockRegistry {
+        pub fn set_board(env: Env, board: Address) {
+            env.storage()
+                .instance()
+                .set(&Symbol::new(&env, "board"), &board);
+        }
+
+        pub fn get_contract_by_alias(env: Env, alias: Symbol) -> Option<Address> {
+            env.storage().instance().get(&alias)
+        }
+
+        pub fn set_admin(env: Env, admin: Address) {
+            env.storage()
+                .instance()
+                .set(&Symbol::new(&env, "admin"), &admin);
+        }
+
+        pub fn is_admin(env: Env, address: Address) -> bool {
+            env.storage()
+                .instance()
+                .get::<_, Address>(&Symbol::new(&env, "admin"))
+                == Some(address)
+        }
+    }
+
+    /// Minimal board contract holding boards and their threads (oldest first)
+    #[contract]
+    pub struct MockBoard;
+
+    #[contractimpl]
+    impl MockBoard {
+        pub fn add_board(env: Env, board: BoardMeta) {
+            env.storage().instance().set(&board.id, &board);
+        }
+
+        pub fn get_board(env: Env, board_id: u64) -> BoardMeta {
+            env.storage().instance().get(&board_id).unwrap()
+        }
+
+        pub fn set_archived(env: Env, board_id: u64) {
+            env.storage()
+                .instance()
+                .set(&(Symbol::new(&env, "archived"), board_id), &true);
+        }
+
+        pub fn is_archived(env: Env, board_id: u64) -> bool {
+            env.storage()
+                .instance()
+                .has(&(Symbol::new(&env, "archived"), board_id))
+        }
+
+        pub fn add_thread(env: Env, thread: ThreadMeta) {
+            let key = (Symbol::new(&env, "threads"), thread.board_id);
+            let mut threads: Vec<ThreadMeta> =
+                env.storage().instance().get(&key).unwrap_or(Vec::new(&env));
+            threads.push_back(thread);
+            env.storage().instance().set(&key, &threads);
+        }
+
+        pub fn list_threads(env: Env, board_id: u64, start: u64, limit: u64) -> Vec<ThreadMeta> {
+            let threads: Vec<ThreadMeta> = env
+                .storage()
+                .instance()
+                .get(&(Symbol::new(&env, "threads"), board_id))
+                .unwrap_or(Vec::new(&env));
+            let mut newest_first = Vec::new(&env);
+            for thread in threads
+                .iter()
+                .rev()
+                .skip(start as usize)
+                .take(limit as usize)
+            {
+                newest_first.push_back(thread);
+            }
+            newest_first
+        }
+    }
+
+    /// Minimal permissions contract with per-board roles
+    #[contract]
+    pub struct MockPermissions;
+
+    #[contractimpl]
+    impl MockPermissions {
+        pub fn set_role(env: Env, board_id: u64, user: Address, role: Role) {
+            env.storage().instance().set(&(board_id, user), &role);
+        }
+
+        pub fn get_role(env: Env, board_id: u64, user: Address) -> Role {
+            env.storage()
+                .instance()
+                .get(&(board_id, user))
+                .unwrap_or(Role::Guest)
+        }
+
+        pub fn set_community_owner(env: Env, community_id: u64, owner: Address) {
+            let key = (Symbol::new(&env, "owner"), community_id);
+            env.storage().instance().set(&key, &owner);
+        }
+
+        pub fn set_community_role(
+            env: Env,
+            community_id: u64,
+            user: Address,
+            role: Role,
+            caller: Address,
+        ) {
+            let owner: Address = env
+                .storage()
+                .instance()
+                .get(&(Symbol::new(&env, "owner"), community_id))
+                .unwrap();
+            assert!(caller == owner);
+            let key = (Symbol::new(&env, "community"), community_id, user);
+            env.storage().instance().set(&key, &role);
+        }
+
+        pub fn get_community_role(env: Env, community_id: u64, user: Address) -> Role {
+            env.storage()
+                .instance()
+                .get(&(Symbol::new(&env, "community"), community_id, user))
+                .unwrap_or(Role::Guest)
+        }
+
+        pub fn set_board_community(env: Env, board_id: u64, community_id: Option<u64>) {
+            let key = (Symbol::new(&env, "board"), board_id);
+            match community_id {
+                Some(community_id) => env.storage().instance().set(&key, &community_id),
+                None => env.storage().instance().remove(&key),
+            }
+        }
+
+        pub fn get_board_community(env: Env, board_id: u64) -> Option<u64> {
+            env.storage()
+                .instance()
+                .get(&(Symbol::new(&env, "board"), board_id))
+        }
+    }
+
+    fn feed_board(env: &Env, id: u64, name: &str, is_private: bool) -> BoardMeta {
+        BoardMeta {
+            id,
+            slug: String::from_str(env, name),
+            name: String::from_str(env, name),
+            description: String::from_str(env, ""),
+            creator: Address::generate(env),
+            created_at: 0,
+            thread_count: 0,
+            is_readonly: false,
+            is_private,
+            is_listed: true,
+        }
+    }
+
+    fn feed_thread(env: &Env, board_id: u64, id: u64, title: &str, created_at: u64) -> ThreadMeta {
+        ThreadMeta {
+            id,
+            board_id,
+            title: String::from_str(env, title),
+            creator: Address::generate(env),
+            created_at,
+            updated_at: created_at,
+            reply_count: 0,
+            is_locked: false,
+            is_pinned: false,
+            is_hidden: false,
+            is_deleted: false,
+            flair_id: None,
+        }
+    }
+
+    fn find(html: &Bytes, needle: &str) -> Option<u32> {
+        let needle = Bytes::from_slice(html.env(), needle.as_bytes());
+        (0..=html.len().saturating_sub(needle.len()))
+            .find(|&start| html.slice(start..start + needle.len()) == needle)
+    }
+
+    #[test]
+    fn test_feed_merges_boards_and_skips_private() {
+        let env = Env::default();
+        env.mock_all_auths();
+
+        let registry = MockRegistryClient::new(&env, &env.register(MockRegistry, ()));
+        let board = MockBoardClient::new(&env, &env.register(MockBoard, ()));
+        let perms = MockPermissionsClient::new(&env, &env.register(MockPermissions, ()));
+        registry.set_board(&board.address);
+        let client = BoardsCommunityClient::new(&env, &env.register(BoardsCommunity, ()));
+        client.init(&registry.address, &perms.address, &Address::generate(&env));
+
+        let owner = Address::generate(&env);
+        let community_id = create_test_community(&env, &client, &owner, "tech");
+        for (id, name, is_private) in [(1, "rust", false), (2, "go", false), (3, "secret", true)] {
+            board.add_board(&feed_board(&env, id, name, is_private));
+            client.add_board(&community_id, &id, &owner);
+        }
+        board.add_thread(&feed_thread(&env, 1, 0, "Rust at 100", 100));
+        board.add_thread(&feed_thread(&env, 2, 0, "Go at 200", 200));
+        board.add_thread(&feed_thread(&env, 1, 1, "Rust at 300", 300));
+        board.add_thread(&feed_thread(&env, 2, 1, "Go at 400", 400));
+        board.add_thread(&feed_thread(&env, 3, 0, "Secret at 500", 500));
+
+        let path = String::from_str(&env, "/c/tech/feed");
+        let html = client.render(&path, &None);
+        let positions: std::vec::Vec<u32> =
+            ["Go at 400", "Rust at 300", "Go at 200", "Rust at 100"]
+                .iter()
+                .map(|title| find(&html, title).unwrap())
+                .collect();
+        assert!(positions.windows(2).all(|pair| pair[0] < pair[1]));
+        assert!(find(&html, "render:/c/tech/b/go/t/1").is_some());
+        assert!(find(&html, "Secret").is_none());
+        assert!(find(&html, "{{render").is_none());
+
+        // Members of the private board see its threads too
+        let member = Address::generate(&env);
+        perms.set_role(&3, &member, &Role::Member);
+        let html = client.render(&path, &Some(member));
+        assert!(find(&html, "Secret at 500").unwrap() < find(&html, "Go at 400").unwrap());
+    }
+
+    #[test]
+    fn test_community_moderators_cover_member_boards() {
+        let env = Env::default();
+        env.mock_all_auths();
+
+        let registry = MockRegistryClient::new(&env, &env.register(MockRegistry, ()));
+        let board = MockBoardClient::new(&env, &env.register(MockBoard, ()));
+        let perms = MockPermissionsClient::new(&env, &env.register(MockPermissions, ()));
+        registry.set_board(&board.address);
+        let client = BoardsCommunityClient::new(&env, &env.register(BoardsCommunity, ()));
+        client.init(&registry.address, &perms.address, &Address::generate(&env));
+
+        let owner = Address::generate(&env);
+        let community_id = create_test_community(&env, &client, &owner, "tech");
+        board.add_board(&feed_board(&env, 1, "rust", false));
+        client.add_board(&community_id, &1, &owner);
+        assert_eq!(perms.get_board_community(&1), Some(community_id));
+
+        // The team lives in the permissions contract's community roles
+        let team_mod = Address::generate(&env);
+        client.set_community_moderator(&community_id, &team_mod, &owner);
+        client.set_community_moderator(&community_id, &team_mod, &owner);
+        assert_eq!(
+            perms.get_community_role(&community_id, &team_mod),
+            Role::Moderator
+        );
+
+        client.remove_community_moderator(&community_id, &team_mod, &owner);
+        assert_eq!(
+            perms.get_community_role(&community_id, &team_mod),
+            Role::Guest
+        );
+
+        // Leaving the community stops the cascade
+        client.remove_board(&community_id, &1, &owner);
+        assert_eq!(perms.get_board_community(&1), None);
+
+        // Boards added before the cascade existed are backfilled page by page
+        for (id, name) in [(2, "go"), (3, "zig")] {
+            board.add_board(&feed_board(&env, id, name, false));
+            client.add_board(&community_id, &id, &owner);
+            perms.set_board_community(&id, &None);
+        }
+        assert_eq!(
+            client.sync_board_communities(&community_id, &0, &1, &owner),
+            Some(1)
+        );
+        assert_eq!(perms.get_board_community(&2), Some(community_id));
+        assert_eq!(perms.get_board_community(&3), None);
+        assert_eq!(
+            client.sync_board_communities(&community_id, &1, &1, &owner),
+            None
+        );
+        assert_eq!(perms.get_board_community(&3), Some(community_id));
+    }
+
+    #[test]
+    fn test_owned_communities_follow_transfers_and_backfill() {
+        let env = Env::default();
+        env.mock_all_auths();
+
+        let registry = MockRegistryClient::new(&env, &env.register(MockRegistry, ()));
+        let admin = Address::generate(&env);
+        registry.set_admin(&admin);
+        let contract_id = env.register(BoardsCommunity, ());
+        let client = BoardsCommunityClient::new(&env, &contract_id);
+        client.init(
+            &registry.address,
+            &Address::generate(&env),
+            &Address::generate(&env),
+        );
+
+        let owner = Address::generate(&env);
+        let other = Address::generate(&env);
+        let first = create_test_community(&env, &client, &owner, "first");
+        let second = create_test_community(&env, &client, &owner, "second");
+        assert_eq!(client.get_owned_communities(&owner).len(), 2);
+
+        client.initiate_transfer(&first, &other, &owner);
+        client.accept_transfer(&first, &other);
+        let owned = client.get_owned_communities(&owner);
+        assert_eq!(owned.len(), 1);
+        assert_eq!(owned.get(0).unwrap().id, second);
+        assert_eq!(
+            client.get_owned_communities(&other).get(0).unwrap().id,
+            first
+        );
+
+        client.delete_community(&second, &owner);
+        assert!(client.get_owned_communities(&owner).is_empty());
+
+        // Communities from before the owner index are backfilled page by page
+        env.as_contract(&contract_id, || {
+            env.storage()
+                .persistent()
+                .remove(&CommunityKey::UserOwnedCommunities(other.clone()));
+        });
+        assert!(client.try_index_community_owners(&0, &1, &other).is_err());
+        assert_eq!(client.index_community_owners(&0, &1, &admin), Some(1));
+        assert_eq!(client.get_owned_communities(&other).len(), 1);
+        assert_eq!(client.index_community_owners(&1, &1, &admin), None);
+        assert_eq!(client.get_owned_communities(&other).len(), 1);
+    }
+
+    #[test]
+    #[should_panic(expected = "Only community owner can set moderators")]
+    fn test_only_owner_sets_community_moderators() {
+        let env = Env::default();
+        let (client, _, _, _) = setup_community(&env);
+
+        let owner = Address::generate(&env);
+        let id = create_test_community(&env, &client, &owner, "mods-test");
+        let other = Address::generate(&env);
+        client.set_community_moderator(&id, &other, &other);
+    }
+
+    #[test]
+    fn test_board_order_and_groups_on_community_page() {
+        let env = Env::default();
+        env.mock_all_auths();
+
+        let registry = MockRegistryClient::new(&env, &env.register(MockRegistry, ()));
+        let board = MockBoardClient::new(&env, &env.register(MockBoard, ()));
+        registry.set_board(&board.address);
+        let client = BoardsCommunityClient::new(&env, &env.register(BoardsCommunity, ()));
+        client.init(
+            &registry.address,
+            &Address::generate(&env),
+            &Address::generate(&env),
+        );
+
+        let owner = Address::generate(&env);
+        let community_id = create_test_community(&env, &client, &owner, "tech");
+        for (id, name) in [(1, "alpha"), (2, "beta"), (3, "gamma"), (4, "delta")] {
+            board.add_board(&feed_board(&env, id, name, false));
+            client.add_board(&community_id, &id, &owner);
+        }
+
+        // Boards left out of the explicit order follow it in id order
+        client.set_board_order(&community_id, &Vec::from_array(&env, [3, 1]), &owner);
+        assert_eq!(
+            client.get_board_order(&community_id),
+            Vec::from_array(&env, [3, 1, 2, 4])
+        );
+
+        let group = String::from_str(&env, "Featured");
+        client.set_board_group(&community_id, &2, &group, &owner);
+        client.set_board_group(&community_id, &4, &group, &owner);
+
+        let html = client.render(&String::from_str(&env, "/c/tech"), &None);
+        let positions: std::vec::Vec<u32> = ["gamma", "alpha", "Featured", "beta", "delta"]
+            .iter()
+            .map(|label| find(&html, label).unwrap())
+            .collect();
+        assert!(positions.windows(2).all(|pair| pair[0] < pair[1]));
+        assert!(find(&html, "Other Boards").is_none());
+
+        // Ungrouped boards after a group get their own header
+        client.set_board_group(&community_id, &3, &group, &owner);
+        client.set_board_group(&community_id, &2, &String::from_str(&env, ""), &owner);
+        let html = client.render(&String::from_str(&env, "/c/tech"), &None);
+        assert!(find(&html, "delta").unwrap() < find(&html, "Other Boards").unwrap());
+        assert!(find(&html, "Other Boards").unwrap() < find(&html, "alpha").unwrap());
+    }
+
+    #[test]
+    fn test_archived_boards_left_out_of_community_page() {
+        let env = Env::default();
+        env.mock_all_auths();
+
+        let registry = MockRegistryClient::new(&env, &env.register(MockRegistry, ()));
+        let board = MockBoardClient::new(&env, &env.register(MockBoard, ()));
+        let perms = MockPermissionsClient::new(&env, &env.register(MockPermissions, ()));
+        registry.set_board(&board.address);
+        let client = BoardsCommunityClient::new(&env, &env.register(BoardsCommunity, ()));
+        client.init(&registry.address, &perms.address, &Address::generate(&env));
+
+        let owner = Address::generate(&env);
+        let community_id = create_test_community(&env, &client, &owner, "tech");
+        for (id, name) in [(1, "alpha"), (2, "beta"), (3, "gamma")] {
+            let mut meta = feed_board(&env, id, name, false);
+            meta.is_listed = id == 1;
+            board.add_board(&meta);
+            client.add_board(&community_id, &id, &owner);
+        }
+        board.set_archived(&3);
+
+        // The owner sees the hidden board, but not the archived one
+        let html = client.render(&String::from_str(&env, "/c/tech"), &Some(owner));
+        assert!(find(&html, "alpha").is_some());
+        assert!(find(&html, "beta").is_some());
+        assert!(find(&html, "badge\">hidden").is_some());
+        assert!(find(&html, "gamma").is_none());
+    }
+
+    #[test]
+    #[should_panic(expected = "is not in this community")]
+    fn test_board_order_rejects_foreign_boards() {
+        let env = Env::default();
+        let (client, _, _, _) = setup_community(&env);
+
+        let owner = Address::generate(&env);
+        let id = create_test_community(&env, &client, &owner, "order-test");
+        client.set_board_order(&id, &Vec::from_array(&env, [7]), &owner);
+    }
+
+    #[test]
+    fn test_join_and_leave_community() {
+        let env = Env::default();
+        let (client, _, _, _) = setup_community(&env);
+
+        let owner = Address::generate(&env);
+        let id = create_test_community(&env, &client, &owner, "joiners");
+        let user = Address::generate(&env);
+        let path = String::from_str(&env, "/c/joiners");
+        let my_path = String::from_str(&env, "/my/communities");
+
+        let html = client.render(&path, &Some(user.clone()));
+        assert!(find(&html, "join_community").is_some());
+
+        // Joining twice counts once
+        client.join_community(&id, &user);
+        client.join_community(&id, &user);
+        assert!(client.is_member(&id, &user));
+        assert_eq!(client.get_community(&id).unwrap().member_count, 2);
+        assert_eq!(client.get_user_communities(&user).len(), 1);
+        assert_eq!(client.get_user_communities(&owner).len(), 1);
+
+        let html = client.render(&path, &Some(user.clone()));
+        assert!(find(&html, "leave_community").is_some());
+        let html = client.render(&my_path, &Some(user.clone()));
+        assert!(find(&html, "render:/c/joiners").is_some());
+
+        // Leaving twice never drops the count below the remaining members
+        client.leave_community(&id, &user);
+        client.leave_community(&id, &user);
+        assert!(!client.is_member(&id, &user));
+        assert_eq!(client.get_community(&id).unwrap().member_count, 1);
+        assert_eq!(client.get_user_communities(&user).len(), 0);
+        let html = client.render(&my_path, &Some(user));
+        assert!(find(&html, "haven't joined any communities").is_some());
+    }
+
+    #[test]
+    #[should_panic(expected = "Community owner cannot leave")]
+    fn test_owner_cannot_leave_community() {
+        let env = Env::default();
+        let (client, _, _, _) = setup_community(&env);
+
+        let owner = Address::generate(&env);
+        let id = create_test_community(&env, &client, &owner, "owned");
+        client.leave_community(&id, &owner);
+    }
+
+    #[test]
+    fn test_private_community_join_goes_through_request() {
+        let env = Env::default();
+        let (client, _, _, _) = setup_community(&env);
+
+        let owner = Address::generate(&env);
+        let id = client.create_community(
+            &String::from_str(&env, "club"),
+            &String::from_str(&env, "Club"),
+            &String::from_str(&env, "Members only"),
+            &String::from_str(&env, "true"),
+            &String::from_str(&env, "false"),
+            &owner,
+        );
+        let user = Address::generate(&env);
+        assert!(client.try_join_community(&id, &user).is_err());
+
+        let path = String::from_str(&env, "/c/club");
+        let html = client.render(&path, &Some(user.clone()));
+        assert!(find(&html, "request_join").is_some());
+
+        client.request_join(&id, &String::from_str(&env, "Hi"), &user);
+        let html = client.render(&path, &Some(user.clone()));
+        assert!(find(&html, "request_join").is_none());
+        assert!(find(&html, "waiting for a community admin").is_some());
+
+        client.accept_join(&id, &user, &owner);
+        assert!(client.is_member(&id, &user));
+        assert_eq!(client.get_community(&id).unwrap().member_count, 2);
+        assert_eq!(client.get_user_communities(&user).len(), 1);
+    }
+
     // Note: add_board, remove_board, add_admin, remove_admin, request_join, accept_join,
     // initiate_transfer, accept_transfer, delete_community, etc.
     // require cross-contract calls to permissions and registry contracts.
@@ -1,9 +1,10 @@
 #![no_std]
 
+use boards_shared::{copy_string_prefix, next_mention, MAX_MENTIONS_PER_POST, MENTION_SCAN_LEN};
 use soroban_chonk::prelude::*;
 use soroban_sdk::{
-    contract, contracterror, contractimpl, contracttype, Address, Bytes, BytesN, Env, IntoVal,
-    String, Symbol, Val, Vec,
+    contract, contracterror, contractimpl, contracttype, panic_with_error, Address, Bytes, BytesN,
+    Env, IntoVal, String, Symbol, Val, Vec,
 };
 
 /// Errors that can occur in the content contract
@@ -27,8 +28,67 @@ pub enum ContentError {
     FlairRequired = 7,
     /// Board contract not available (registry not configured)
     BoardContractNotAvailable = 8,
+    /// Thread body exceeds the board's size limit
+    ThreadBodyTooLong = 9,
+    /// Reply exceeds the board's size limit
+    ReplyTooLong = 10,
+    /// User's karma is below the board's posting threshold
+    InsufficientKarma = 11,
+    /// Title or body contains a term on the board's blocklist
+    BlockedTerm = 12,
+    /// User replied to this thread too recently for the board's reply cooldown
+    ReplyCooldown = 13,
 }
 
+/// Thread body size limit used when the board contract doesn't provide one
+const DEFAULT_MAX_THREAD_BODY_BYTES: u32 = 16_384;
+
+/// Reply size limit used when the board contract doesn't provide one
+const DEFAULT_MAX_REPLY_BYTES: u32 = 8_192;
+
+/// Largest post scanned for images; posts are capped at this size by the board
+const IMAGE_SCAN_LEN: usize = 16_384;
+
+/// Longest title checked against a board's blocklist, in bytes
+const MAX_TITLE_SCAN_LEN: usize = 512;
+
+/// Mentions kept per user; older entries are dropped
+const MAX_MENTIONS_PER_USER: u32 = 100;
+
+/// Notifications stored per storage entry
+const NOTIFICATION_CHUNK_SIZE: u64 = 25;
+
+/// Notifications kept per user; the oldest chunk is dropped beyond this
+const MAX_NOTIFICATIONS: u64 = 200;
+
+/// Recent replies indexed per user and board; older entries are dropped
+const MAX_USER_REPLIES: u32 = 50;
+
+/// Replies per chunk of a user's full reply log
+const USER_LOG_CHUNK: u64 = 50;
+
+/// Previous bodies kept per post; older revisions are dropped
+const MAX_REVISIONS: u32 = 3;
+
+/// Drafts kept per user and board; saving another evicts the oldest
+const MAX_DRAFTS: u32 = 3;
+
+/// Maximum draft body size in bytes (matches the post body limit)
+const MAX_DRAFT_BODY_LEN: u32 = 16384;
+
+/// Maximum draft title length in bytes
+const MAX_DRAFT_TITLE_LEN: u32 = 256;
+
+/// Maximum draft key length (symbols hold at most 32 characters)
+const MAX_DRAFT_KEY_LEN: usize = 32;
+
+/// Maximum length of the free-text details attached to a flag
+const MAX_FLAG_DETAILS_LEN: u32 = 280;
+
+/// Replies (of any depth) whose scores the Top reply sort ranks; top-level replies
+/// past this window follow oldest-first
+const MAX_REPLY_SORT_SCAN: u32 = 50;
+
 /// Storage keys for the content contract
 #[contracttype]
 #[derive(Clone)]
@@ -68,6 +128,42 @@ pub enum ContentKey {
     CrosspostCount(u64, u64),
     /// List of locations where thread was crossposted (original_board_id, original_thread_id) -> Vec<CrosspostLocation>
     CrosspostList(u64, u64),
+    /// Mentions of a user, newest first (mentioned_user) -> Vec<Mention>
+    Mentions(Address),
+    /// Previous bodies of a post, newest first (board_id, thread_id, reply_id) -> Vec<Revision>
+    Revisions(u64, u64, Option<u64>),
+    /// Saved drafts of a user on a board, oldest first (board_id, user) -> Vec<Draft>
+    Drafts(u64, Address),
+    /// Tombstone left on a removed crosspost (board_id, thread_id) -> RemovedCrosspost
+    RemovedCrosspost(u64, u64),
+    /// Free-text details of an open flag (board_id, thread_id, reply_id, flagger) -> String
+    FlagDetails(u64, u64, Option<u64>, Address),
+    /// Number of notifications ever sent to a user (user) -> u64
+    NotificationCount(Address),
+    /// A chunk of a user's notifications, oldest first (user, chunk_index) -> Vec<Notification>
+    NotificationChunk(Address, u64),
+    /// Highest notification ID a user has read (user) -> u64
+    NotificationsRead(Address),
+    /// Recent replies by a user on a board, oldest first
+    /// (board_id, user) -> Vec<(created_at, thread_id, reply_id)>
+    UserReplies(u64, Address),
+    /// One chunk of every reply a user posted, in logging order
+    /// (board_id, user, chunk) -> Vec<(thread_id, reply_id)>
+    UserReplyLog(u64, Address, u64),
+    /// Number of replies in a user's reply log (board_id, user) -> u64
+    UserReplyLogLen(u64, Address),
+    /// First reply in a thread logged under its author when it was posted; older
+    /// replies are added by `log_user_replies` (board_id, thread_id) -> u64
+    ReplyLogFrom(u64, u64),
+    /// Replies below this ID have been added to the author logs by the backfill
+    /// (board_id, thread_id) -> u64
+    ReplyLogBackfilled(u64, u64),
+    /// Set while a post is marked as written in a moderator capacity
+    /// (board_id, thread_id, reply_id) -> bool
+    Distinguished(u64, u64, Option<u64>),
+    /// When a user last replied to a thread, kept while the board has a reply cooldown
+    /// (board_id, thread_id, user) -> u64
+    LastReplyAt(u64, u64, Address),
 }
 
 /// Reply metadata
@@ -92,6 +188,7 @@ pub struct ReplyMeta {
 #[derive(Clone)]
 pub struct Flag {
     pub flagger: Address,
+    /// Reason name (see `FlagReason`); details are kept under `ContentKey::FlagDetails`
     pub reason: String,
     pub created_at: u64,
     pub resolved: bool,
@@ -99,6 +196,29 @@ pub struct Flag {
     pub rule_violated: Option<u32>,
 }
 
+/// Reason a post was flagged. Stored on `Flag` as its lowercase name
+/// ("spam", "abuse", "off-topic" or "other").
+#[contracttype]
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+#[repr(u32)]
+pub enum FlagReason {
+    Spam = 0,
+    Abuse = 1,
+    OffTopic = 2,
+    Other = 3,
+}
+
+/// Summary of a single open flag, as shown in the moderation queue
+#[contracttype]
+#[derive(Clone)]
+pub struct FlagRecord {
+    pub flagger: Address,
+    pub reason: FlagReason,
+    /// Optional free text from the flagger (legacy free-form reasons land here)
+    pub details: String,
+    pub created_at: u64,
+}
+
 /// Type of flagged content
 #[contracttype]
 #[derive(Clone, Copy, PartialEq, Eq)]
@@ -108,6 +228,26 @@ pub enum FlaggedType {
     Reply = 1,
 }
 
+/// Ordering for a thread's top-level replies (children stay chronological)
+#[contracttype]
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+#[repr(u32)]
+pub enum ReplySort {
+    Oldest = 0,
+    Newest = 1,
+    Top = 2,
+}
+
+/// Vote tally from the voting contract (used to rank replies)
+#[contracttype]
+#[derive(Clone)]
+pub struct VoteTally {
+    pub upvotes: u32,
+    pub downvotes: u32,
+    pub score: i32,
+    pub first_vote_at: u64,
+}
+
 /// Reference to flagged content
 #[contracttype]
 #[derive(Clone)]
@@ -141,6 +281,75 @@ pub struct CrosspostLocation {
     pub created_at: u64,
 }
 
+/// Tombstone for a crosspost that was removed (stored on the crosspost thread)
+#[contracttype]
+#[derive(Clone)]
+pub struct RemovedCrosspost {
+    pub original_board_id: u64,
+    pub original_thread_id: u64,
+    pub removed_by: Address,
+    pub removed_at: u64,
+}
+
+/// A post that mentioned a user with `@username`
+#[contracttype]
+#[derive(Clone)]
+pub struct Mention {
+    pub board_id: u64,
+    pub thread_id: u64,
+    /// None when the mention is in the thread body
+    pub reply_id: Option<u64>,
+    pub author: Address,
+    pub created_at: u64,
+}
+
+/// Something that happened to a user's content: a reply, or a moderator action
+#[contracttype]
+#[derive(Clone)]
+pub struct Notification {
+    /// Per-user sequence number, starting at 1
+    pub id: u64,
+    /// "thread_reply", "reply_reply", "hidden" or "banned"
+    pub kind: Symbol,
+    pub board_id: u64,
+    /// 0 for board-wide notifications such as bans
+    pub thread_id: u64,
+    pub reply_id: Option<u64>,
+    pub actor: Address,
+    pub created_at: u64,
+}
+
+/// A post body as it was before an edit replaced it
+#[contracttype]
+#[derive(Clone)]
+pub struct Revision {
+    pub body: Bytes,
+    /// Who made the edit that replaced this body
+    pub editor: Address,
+    /// When this body was replaced
+    pub replaced_at: u64,
+}
+
+/// An unsent thread or reply saved by its author
+#[contracttype]
+#[derive(Clone)]
+pub struct Draft {
+    pub key: Symbol,
+    /// Thread title (empty for reply drafts)
+    pub title: String,
+    pub body: Bytes,
+    pub saved_at: u64,
+}
+
+/// Inline image rules from the board contract (matches boards-board ImagePolicy)
+#[contracttype]
+#[derive(Clone)]
+pub struct ImagePolicy {
+    pub allow_images: bool,
+    /// Lowercase host names images may load from; empty allows any host
+    pub allowed_hosts: Vec<String>,
+}
+
 #[contract]
 pub struct BoardsContent;
 
@@ -186,18 +395,243 @@ impl BoardsContent {
         .flatten()
     }
 
+    /// Helper: Get profile contract address from registry (None if unavailable)
+    fn get_profile_contract_address(env: &Env) -> Option<Address> {
+        let registry: Address = env.storage().instance().get(&ContentKey::Registry)?;
+        let alias_args: Vec<Val> =
+            Vec::from_array(env, [Symbol::new(env, "profile").into_val(env)]);
+        env.try_invoke_contract::<Option<Address>, soroban_sdk::Error>(
+            &registry,
+            &Symbol::new(env, "get_contract_by_alias"),
+            alias_args,
+        )
+        .ok()
+        .and_then(|r| r.ok())
+        .flatten()
+    }
+
+    /// Helper: Get admin contract address from registry (None if unavailable)
+    fn get_admin_contract_address(env: &Env) -> Option<Address> {
+        let registry: Address = env.storage().instance().get(&ContentKey::Registry)?;
+        let alias_args: Vec<Val> = Vec::from_array(env, [Symbol::new(env, "admin").into_val(env)]);
+        env.try_invoke_contract::<Option<Address>, soroban_sdk::Error>(
+            &registry,
+            &Symbol::new(env, "get_contract_by_alias"),
+            alias_args,
+        )
+        .ok()
+        .and_then(|r| r.ok())
+        .flatten()
+    }
+
+    /// Helper: Get voting contract address from registry (None if unavailable)
+    fn get_voting_contract_address(env: &Env) -> Option<Address> {
+        let registry: Address = env.storage().instance().get(&ContentKey::Registry)?;
+        let alias_args: Vec<Val> = Vec::from_array(env, [Symbol::new(env, "voting").into_val(env)]);
+        env.try_invoke_contract::<Option<Address>, soroban_sdk::Error>(
+            &registry,
+            &Symbol::new(env, "get_contract_by_alias"),
+            alias_args,
+        )
+        .ok()
+        .and_then(|r| r.ok())
+        .flatten()
+    }
+
+    /// Find distinct `@username` tokens in a post.
+    /// Only the first MENTION_SCAN_LEN bytes are scanned and at most
+    /// MAX_MENTIONS_PER_POST names are returned, so the cost per post is bounded.
+    fn find_mentions(env: &Env, body: &Bytes) -> Vec<String> {
+        let mut names: Vec<String> = Vec::new(env);
+        let len = (body.len() as usize).min(MENTION_SCAN_LEN);
+        let mut buf = [0u8; MENTION_SCAN_LEN];
+        body.slice(0..len as u32).copy_into_slice(&mut buf[..len]);
+
+        let mut from = 0;
+        while names.len() < MAX_MENTIONS_PER_POST {
+            let Some((start, end)) = next_mention(&buf[..len], from) else {
+                break;
+            };
+            let name = String::from_bytes(env, &buf[start..end]);
+            if !names.contains(&name) {
+                names.push_back(name);
+            }
+            from = end;
+        }
+        names
+    }
+
+    /// Record mentions of registered usernames in a new post.
+    /// Names that don't resolve through the profile contract are ignored,
+    /// as are self-mentions.
+    fn record_mentions(
+        env: &Env,
+        board_id: u64,
+        thread_id: u64,
+        reply_id: Option<u64>,
+        body: &Bytes,
+        author: &Address,
+    ) {
+        let Some(profile) = Self::get_profile_contract_address(env) else {
+            return;
+        };
+
+        for name in Self::find_mentions(env, body).iter() {
+            let lookup_args: Vec<Val> = Vec::from_array(env, [name.into_val(env)]);
+            let user: Option<Address> = env
+                .try_invoke_contract::<Option<Address>, soroban_sdk::Error>(
+                    &profile,
+                    &Symbol::new(env, "get_address_by_username"),
+                    lookup_args,
+                )
+                .ok()
+                .and_then(|r| r.ok())
+                .flatten();
+            let Some(user) = user else {
+                continue;
+            };
+            if user == *author {
+                continue;
+            }
+
+            let key = ContentKey::Mentions(user);
+            let mut mentions: Vec<Mention> = env
+                .storage()
+                .persistent()
+                .get(&key)
+                .unwrap_or(Vec::new(env));
+            mentions.push_front(Mention {
+                board_id,
+                thread_id,
+                reply_id,
+                author: author.clone(),
+                created_at: env.ledger().timestamp(),
+            });
+            while mentions.len() > MAX_MENTIONS_PER_USER {
+                mentions.pop_back();
+            }
+            env.storage().persistent().set(&key, &mentions);
+        }
+    }
+
+    /// Append a notification to a user's inbox. Nobody is notified about their
+    /// own actions. Once a user has more than MAX_NOTIFICATIONS, starting a new
+    /// chunk drops the oldest one.
+    fn notify(
+        env: &Env,
+        recipient: &Address,
+        kind: Symbol,
+        board_id: u64,
+        thread_id: u64,
+        reply_id: Option<u64>,
+        actor: &Address,
+    ) {
+        if recipient == actor {
+            return;
+        }
+        let count_key = ContentKey::NotificationCount(recipient.clone());
+        let count: u64 = env.storage().persistent().get(&count_key).unwrap_or(0);
+        let chunk_index = count / NOTIFICATION_CHUNK_SIZE;
+        let chunk_key = ContentKey::NotificationChunk(recipient.clone(), chunk_index);
+        let mut chunk: Vec<Notification> = env
+            .storage()
+            .persistent()
+            .get(&chunk_key)
+            .unwrap_or(Vec::new(env));
+
+        chunk.push_back(Notification {
+            id: count + 1,
+            kind,
+            board_id,
+            thread_id,
+            reply_id,
+            actor: actor.clone(),
+            created_at: env.ledger().timestamp(),
+        });
+        env.storage().persistent().set(&chunk_key, &chunk);
+        env.storage().persistent().set(&count_key, &(count + 1));
+
+        let kept_chunks = MAX_NOTIFICATIONS / NOTIFICATION_CHUNK_SIZE;
+        if chunk.len() == 1 && chunk_index >= kept_chunks {
+            env.storage()
+                .persistent()
+                .remove(&ContentKey::NotificationChunk(
+                    recipient.clone(),
+                    chunk_index - kept_chunks,
+                ));
+        }
+    }
+
+    /// Notify the thread author, and the parent reply's author for nested
+    /// replies, that a new reply was posted
+    fn notify_reply(env: &Env, reply: &ReplyMeta) {
+        let mut parent_author: Option<Address> = None;
+        if reply.parent_id > 0 || reply.depth > 0 {
+            parent_author = Self::get_reply_author(
+                env.clone(),
+                reply.board_id,
+                reply.thread_id,
+                reply.parent_id,
+            );
+            if let Some(author) = &parent_author {
+                Self::notify(
+                    env,
+                    author,
+                    Symbol::new(env, "reply_reply"),
+                    reply.board_id,
+                    reply.thread_id,
+                    Some(reply.id),
+                    &reply.creator,
+                );
+            }
+        }
+
+        let Some(board_contract) = Self::get_board_contract_address(env) else {
+            return;
+        };
+        let thread_args: Vec<Val> = Vec::from_array(
+            env,
+            [reply.board_id.into_val(env), reply.thread_id.into_val(env)],
+        );
+        let thread_author = env
+            .try_invoke_contract::<Option<(String, Address)>, soroban_sdk::Error>(
+                &board_contract,
+                &Symbol::new(env, "get_thread_title_and_author"),
+                thread_args,
+            )
+            .ok()
+            .and_then(|r| r.ok())
+            .flatten()
+            .map(|(_, author)| author);
+        // A reply to the thread author's own reply was already announced above
+        if let Some(author) = thread_author.filter(|a| parent_author.as_ref() != Some(a)) {
+            Self::notify(
+                env,
+                &author,
+                Symbol::new(env, "thread_reply"),
+                reply.board_id,
+                reply.thread_id,
+                Some(reply.id),
+                &reply.creator,
+            );
+        }
+    }
+
     /// Create a thread (entry point for thread creation)
     /// This function:
     /// 1. Calls the Board contract to create thread metadata
     /// 2. Stores the thread body content
     /// Returns the thread ID, or an error if the board is read-only
-    /// Note: Parameter order matches form field order (board_id, title, flair_id, body, caller)
+    /// Note: Parameter order matches form field order
+    /// (board_id, title, flair_id, body, draft_key, caller).
+    /// The draft named by draft_key, if any, is deleted once the thread is created.
     pub fn create_thread(
         env: Env,
         board_id: u64,
         title: String,
         flair_id: Option<String>,
         body: String,
+        draft_key: Option<String>,
         caller: Address,
     ) -> Result<u64, ContentError> {
         caller.require_auth();
@@ -211,6 +645,9 @@ impl BoardsContent {
 
         // Check board is not readonly
         Self::check_board_not_readonly(&env, &registry, board_id)?;
+        Self::check_post_size(&env, board_id, body.len(), false)?;
+        Self::check_karma(&env, board_id, &caller, false)?;
+        Self::check_blocked_terms(&env, board_id, &Self::title_bytes(&env, &title))?;
 
         // Get the board contract (single contract for all boards)
         let board_contract = Self::get_board_contract_address(&env)
@@ -270,9 +707,17 @@ impl BoardsContent {
         } else {
             Bytes::new(&env)
         };
+        Self::check_blocked_terms(&env, board_id, &body_bytes)?;
+        let body_bytes = Self::apply_image_policy(&env, board_id, body_bytes);
         let key = Self::get_or_create_thread_body_chonk(&env, board_id, thread_id);
         let chonk = Chonk::open(&env, key);
-        chonk.write_chunked(body_bytes, 4096);
+        chonk.write_chunked(body_bytes.clone(), 4096);
+
+        Self::record_mentions(&env, board_id, thread_id, None, &body_bytes, &caller);
+
+        if let Some(key) = draft_key.and_then(|k| Self::draft_key_from_string(&env, &k)) {
+            Self::remove_draft(&env, board_id, &key, &caller);
+        }
 
         // Record first-seen timestamp for the user (for account age tracking)
         // and increment post count
@@ -338,81 +783,581 @@ impl BoardsContent {
         Ok(())
     }
 
-    /// Check if thread is locked - returns error if so
-    /// Gracefully handles missing function for backwards compatibility
-    fn check_thread_not_locked(
+    /// Check a post against the board's size limit for thread bodies or replies.
+    /// Falls back to the default limits if the board contract doesn't provide them.
+    fn check_post_size(
         env: &Env,
-        _registry: &Address,
         board_id: u64,
-        thread_id: u64,
+        len: u32,
+        is_reply: bool,
     ) -> Result<(), ContentError> {
-        // Get board contract (single contract for all boards)
-        // If board contract is not available (e.g., in tests), skip the check
-        let board_contract = match Self::get_board_contract_address(env) {
-            Some(addr) => addr,
-            None => return Ok(()), // Skip check if board contract not available
+        let (getter, default_limit, error) = if is_reply {
+            (
+                "get_max_reply_bytes",
+                DEFAULT_MAX_REPLY_BYTES,
+                ContentError::ReplyTooLong,
+            )
+        } else {
+            (
+                "get_max_thread_body_bytes",
+                DEFAULT_MAX_THREAD_BODY_BYTES,
+                ContentError::ThreadBodyTooLong,
+            )
         };
+        let limit = Self::get_board_contract_address(env)
+            .and_then(|board_contract| {
+                env.try_invoke_contract::<u32, soroban_sdk::Error>(
+                    &board_contract,
+                    &Symbol::new(env, getter),
+                    Vec::from_array(env, [board_id.into_val(env)]),
+                )
+                .ok()
+                .and_then(|r| r.ok())
+            })
+            .unwrap_or(default_limit);
+
+        if len > limit {
+            return Err(error);
+        }
+        Ok(())
+    }
 
-        // Query the board contract's is_thread_locked function (now requires board_id)
-        let thread_args: Vec<Val> =
-            Vec::from_array(env, [board_id.into_val(env), thread_id.into_val(env)]);
-        let is_locked: bool = env
-            .try_invoke_contract::<bool, soroban_sdk::Error>(
+    /// Check the user against the board's karma threshold for threads or replies.
+    /// Skipped if the board contract is unavailable or doesn't support thresholds.
+    fn check_karma(
+        env: &Env,
+        board_id: u64,
+        user: &Address,
+        is_reply: bool,
+    ) -> Result<(), ContentError> {
+        let Some(board_contract) = Self::get_board_contract_address(env) else {
+            return Ok(());
+        };
+
+        let args: Vec<Val> = Vec::from_array(
+            env,
+            [
+                board_id.into_val(env),
+                user.into_val(env),
+                is_reply.into_val(env),
+            ],
+        );
+        let shortfall = env
+            .try_invoke_contract::<Option<(i64, i64)>, soroban_sdk::Error>(
                 &board_contract,
-                &Symbol::new(env, "is_thread_locked"),
-                thread_args,
+                &Symbol::new(env, "karma_shortfall"),
+                args,
             )
-            .unwrap_or(Ok(false))
-            .unwrap_or(false);
+            .ok()
+            .and_then(|r| r.ok())
+            .flatten();
 
-        if is_locked {
-            return Err(ContentError::ThreadLocked);
+        if shortfall.is_some() {
+            return Err(ContentError::InsufficientKarma);
         }
         Ok(())
     }
 
-    /// Check if user can reply on this board
-    #[allow(dead_code)]
-    fn check_can_reply(env: &Env, board_id: u64, user: &Address) {
-        if !env.storage().instance().has(&ContentKey::Permissions) {
-            return; // Skip if permissions not configured
+    /// Reject a reply posted within the board's cooldown of the user's last reply to the
+    /// same thread; moderators are exempt. Returns the cooldown in seconds (0 if the board
+    /// contract is unavailable or doesn't set one) so the caller knows whether to record
+    /// the reply's timestamp.
+    fn check_reply_cooldown(
+        env: &Env,
+        board_id: u64,
+        thread_id: u64,
+        user: &Address,
+    ) -> Result<u64, ContentError> {
+        let cooldown = Self::get_board_contract_address(env)
+            .and_then(|board_contract| {
+                env.try_invoke_contract::<u64, soroban_sdk::Error>(
+                    &board_contract,
+                    &Symbol::new(env, "get_reply_cooldown"),
+                    Vec::from_array(env, [board_id.into_val(env)]),
+                )
+                .ok()
+                .and_then(|r| r.ok())
+            })
+            .unwrap_or(0);
+        if cooldown == 0 {
+            return Ok(0);
         }
 
-        let permissions: Address = env
-            .storage()
-            .instance()
-            .get(&ContentKey::Permissions)
-            .unwrap();
-
-        let args: Vec<Val> = Vec::from_array(env, [board_id.into_val(env), user.into_val(env)]);
-        let fn_name = Symbol::new(env, "can_reply");
-        let can_reply: bool = env.invoke_contract(&permissions, &fn_name, args);
-
-        if !can_reply {
-            panic!("Not authorized to reply");
+        let last_reply_at: Option<u64> = env.storage().persistent().get(&ContentKey::LastReplyAt(
+            board_id,
+            thread_id,
+            user.clone(),
+        ));
+        if let Some(last_reply_at) = last_reply_at {
+            if env.ledger().timestamp() < last_reply_at.saturating_add(cooldown)
+                && !Self::check_is_moderator(env, board_id, user)
+            {
+                return Err(ContentError::ReplyCooldown);
+            }
         }
+        Ok(cooldown)
     }
 
-    /// Check if user can moderate on this board
-    fn check_can_moderate(env: &Env, board_id: u64, user: &Address) {
-        if !env.storage().instance().has(&ContentKey::Permissions) {
-            return; // Skip if permissions not configured
-        }
+    /// Reject text containing one of the board's blocked terms. Skipped if the board
+    /// contract is unavailable or doesn't keep a blocklist.
+    fn check_blocked_terms(env: &Env, board_id: u64, text: &Bytes) -> Result<(), ContentError> {
+        let blocked = Self::get_board_contract_address(env)
+            .and_then(|board_contract| {
+                env.try_invoke_contract::<bool, soroban_sdk::Error>(
+                    &board_contract,
+                    &Symbol::new(env, "contains_blocked_term"),
+                    Vec::from_array(env, [board_id.into_val(env), text.into_val(env)]),
+                )
+                .ok()
+                .and_then(|r| r.ok())
+            })
+            .unwrap_or(false);
 
-        let permissions: Address = env
-            .storage()
-            .instance()
-            .get(&ContentKey::Permissions)
-            .unwrap();
+        if blocked {
+            return Err(ContentError::BlockedTerm);
+        }
+        Ok(())
+    }
 
-        let args: Vec<Val> = Vec::from_array(env, [board_id.into_val(env), user.into_val(env)]);
-        let fn_name = Symbol::new(env, "can_moderate");
-        let can_moderate: bool = env.invoke_contract(&permissions, &fn_name, args);
+    /// A title as bytes, for the blocklist check
+    fn title_bytes(env: &Env, title: &String) -> Bytes {
+        let mut buf = [0u8; MAX_TITLE_SCAN_LEN];
+        let len = copy_string_prefix(title, &mut buf);
+        Bytes::from_slice(env, &buf[..len])
+    }
 
-        if !can_moderate {
-            panic!("Not authorized to moderate");
+    /// Turn images the board doesn't allow into plain links before a post is stored.
+    /// `![alt](url)` and reference images (`![alt][label]`) lose their `!` and
+    /// `<img src="url">` becomes `[image](url)`.
+    /// Posts are stored as written if the board contract is unavailable, doesn't
+    /// provide a policy, or allows images from any host.
+    fn apply_image_policy(env: &Env, board_id: u64, body: Bytes) -> Bytes {
+        let policy = Self::get_board_contract_address(env).and_then(|board_contract| {
+            env.try_invoke_contract::<ImagePolicy, soroban_sdk::Error>(
+                &board_contract,
+                &Symbol::new(env, "get_image_policy"),
+                Vec::from_array(env, [board_id.into_val(env)]),
+            )
+            .ok()
+            .and_then(|r| r.ok())
+        });
+        let Some(policy) = policy else {
+            return body;
+        };
+        if policy.allow_images && policy.allowed_hosts.is_empty() {
+            return body;
         }
-    }
+
+        let len = body.len() as usize;
+        if len > IMAGE_SCAN_LEN {
+            return body;
+        }
+        let mut buf = [0u8; IMAGE_SCAN_LEN];
+        body.copy_into_slice(&mut buf[..len]);
+        let text = &buf[..len];
+
+        let mut out = Bytes::new(env);
+        let mut changed = false;
+        let mut copied = 0;
+        let mut i = 0;
+        while i < len {
+            if text[i] == b'!' && text.get(i + 1) == Some(&b'[') {
+                let Some((alt_end, target_start, target_end, end)) =
+                    Self::markdown_image_bounds(text, i)
+                else {
+                    i += 1;
+                    continue;
+                };
+                let target = &text[target_start..target_end];
+                let allowed = if text.get(alt_end + 1) == Some(&b'(') {
+                    Self::image_allowed(&policy, target)
+                } else {
+                    Self::reference_image_allowed(&policy, text, target)
+                };
+                if !allowed {
+                    out.extend_from_slice(&text[copied..i]);
+                    if alt_end == i + 2 {
+                        // `![](url)` would become an empty link
+                        out.extend_from_slice(b"[image");
+                        copied = i + 2;
+                    } else {
+                        copied = i + 1;
+                    }
+                    changed = true;
+                }
+                i = end;
+            } else if text[i] == b'<' && Self::is_img_tag(&text[i..]) {
+                let tag_end = text[i..]
+                    .iter()
+                    .position(|b| *b == b'>')
+                    .map_or(len, |p| i + p + 1);
+                let src = Self::img_src_bounds(&text[i..tag_end]);
+                let allowed =
+                    src.is_some_and(|(s, e)| Self::image_allowed(&policy, &text[i + s..i + e]));
+                if !allowed {
+                    out.extend_from_slice(&text[copied..i]);
+                    if let Some((s, e)) = src.filter(|(s, e)| s < e) {
+                        out.extend_from_slice(b"[image](");
+                        out.extend_from_slice(&text[i + s..i + e]);
+                        out.extend_from_slice(b")");
+                    }
+                    copied = tag_end;
+                    changed = true;
+                }
+                i = tag_end;
+            } else {
+                i += 1;
+            }
+        }
+
+        if !changed {
+            return body;
+        }
+        out.extend_from_slice(&text[copied..]);
+        out
+    }
+
+    /// For a `![` at `start`: the end of the alt text, the bounds of the image's
+    /// target and where the image ends. The target is the URL of an inline image
+    /// (`![alt](url)`) and the label of a reference image (`![alt][label]`, or the
+    /// alt text for `![alt][]` and `![alt]`). Alt text may hold balanced brackets.
+    fn markdown_image_bounds(text: &[u8], start: usize) -> Option<(usize, usize, usize, usize)> {
+        let alt_end = Self::closing_bracket(text, start + 1)?;
+        match text.get(alt_end + 1) {
+            Some(b'(') => {
+                let url_start = alt_end + 2;
+                let url_len = text[url_start..]
+                    .iter()
+                    .position(|b| *b == b')' || b.is_ascii_whitespace())
+                    .unwrap_or(text.len() - url_start);
+                let url_end = url_start + url_len;
+                Some((alt_end, url_start, url_end, url_end))
+            }
+            Some(b'[') => match Self::closing_bracket(text, alt_end + 1) {
+                Some(label_end) if label_end > alt_end + 2 => {
+                    Some((alt_end, alt_end + 2, label_end, label_end + 1))
+                }
+                Some(label_end) => Some((alt_end, start + 2, alt_end, label_end + 1)),
+                None => Some((alt_end, start + 2, alt_end, alt_end + 1)),
+            },
+            _ => Some((alt_end, start + 2, alt_end, alt_end + 1)),
+        }
+    }
+
+    /// The `]` matching the `[` at `open`, skipping nested pairs and escapes.
+    /// None if a blank line comes first.
+    fn closing_bracket(text: &[u8], open: usize) -> Option<usize> {
+        let mut depth = 0u32;
+        let mut i = open + 1;
+        while i < text.len() {
+            match text[i] {
+                b'\\' => i += 1,
+                b'[' => depth += 1,
+                b']' if depth == 0 => return Some(i),
+                b']' => depth -= 1,
+                b'\n' => {
+                    let next_line = &text[i + 1..];
+                    let blank_len = next_line
+                        .iter()
+                        .position(|b| !matches!(b, b' ' | b'\t' | b'\r'))
+                        .unwrap_or(next_line.len());
+                    if next_line.get(blank_len).is_none_or(|b| *b == b'\n') {
+                        return None;
+                    }
+                }
+                _ => {}
+            }
+            i += 1;
+        }
+        None
+    }
+
+    /// Whether a reference image with this label passes the policy. Every `[label]:`
+    /// definition in the post is checked, wherever it sits, so one the scan misreads
+    /// can't hide another; a label without a definition, or any definition label
+    /// outside ASCII (where case folding could match differently), is refused.
+    fn reference_image_allowed(policy: &ImagePolicy, text: &[u8], label: &[u8]) -> bool {
+        let mut found = false;
+        let mut i = 0;
+        while i < text.len() {
+            if text[i] != b'[' {
+                i += 1;
+                continue;
+            }
+            let Some(close) = text[i + 1..]
+                .iter()
+                .position(|b| matches!(b, b']' | b'[' | b'\n'))
+                .map(|p| i + 1 + p)
+                .filter(|close| text[*close] == b']' && text.get(close + 1) == Some(&b':'))
+            else {
+                i += 1;
+                continue;
+            };
+            let def_label = &text[i + 1..close];
+            if !def_label.is_ascii() {
+                return false;
+            }
+            if Self::labels_match(def_label, label) {
+                // The URL may start on the next line
+                let rest = &text[close + 2..];
+                let url_start = rest
+                    .iter()
+                    .position(|b| !b.is_ascii_whitespace())
+                    .unwrap_or(rest.len());
+                let url = &rest[url_start..];
+                let url = match url.first() {
+                    Some(b'<') => {
+                        let len = url.iter().position(|b| *b == b'>').unwrap_or(url.len());
+                        &url[1..len]
+                    }
+                    _ => {
+                        let len = url
+                            .iter()
+                            .position(|b| b.is_ascii_whitespace())
+                            .unwrap_or(url.len());
+                        &url[..len]
+                    }
+                };
+                if !Self::image_allowed(policy, url) {
+                    return false;
+                }
+                found = true;
+            }
+            i = close + 1;
+        }
+        found
+    }
+
+    /// Whether two reference labels match, ignoring ASCII case and runs of whitespace
+    fn labels_match(a: &[u8], b: &[u8]) -> bool {
+        let mut a = a
+            .split(|c| c.is_ascii_whitespace())
+            .filter(|w| !w.is_empty());
+        let mut b = b
+            .split(|c| c.is_ascii_whitespace())
+            .filter(|w| !w.is_empty());
+        loop {
+            match (a.next(), b.next()) {
+                (None, None) => return true,
+                (Some(x), Some(y)) if x.eq_ignore_ascii_case(y) => {}
+                _ => return false,
+            }
+        }
+    }
+
+    /// Whether `text` opens an `<img` tag (any case)
+    fn is_img_tag(text: &[u8]) -> bool {
+        text.len() > 4
+            && text[1..4].eq_ignore_ascii_case(b"img")
+            && (text[4].is_ascii_whitespace() || text[4] == b'/' || text[4] == b'>')
+    }
+
+    /// Bounds of an `<img>` tag's src value, relative to the tag
+    fn img_src_bounds(tag: &[u8]) -> Option<(usize, usize)> {
+        let mut i = 4;
+        while i + 3 <= tag.len() {
+            if tag[i - 1].is_ascii_whitespace() && tag[i..i + 3].eq_ignore_ascii_case(b"src") {
+                let mut j = i + 3;
+                while j < tag.len() && tag[j].is_ascii_whitespace() {
+                    j += 1;
+                }
+                if tag.get(j) == Some(&b'=') {
+                    j += 1;
+                    while j < tag.len() && tag[j].is_ascii_whitespace() {
+                        j += 1;
+                    }
+                    let quote = tag.get(j).copied().filter(|q| *q == b'"' || *q == b'\'');
+                    let start = j + quote.is_some() as usize;
+                    let len = tag[start..]
+                        .iter()
+                        .position(|b| match quote {
+                            Some(q) => *b == q,
+                            None => b.is_ascii_whitespace() || *b == b'>',
+                        })
+                        .unwrap_or(tag.len() - start);
+                    return Some((start, start + len));
+                }
+            }
+            i += 1;
+        }
+        None
+    }
+
+    /// Whether an image URL passes the policy. Only absolute http(s) URLs have a
+    /// host to check, so relative and data: URLs are refused once hosts are listed.
+    fn image_allowed(policy: &ImagePolicy, url: &[u8]) -> bool {
+        if !policy.allow_images {
+            return false;
+        }
+        if policy.allowed_hosts.is_empty() {
+            return true;
+        }
+        let Some(host) = Self::url_host(url) else {
+            return false;
+        };
+        policy.allowed_hosts.iter().any(|allowed| {
+            let len = allowed.len() as usize;
+            let mut buf = [0u8; 64];
+            len == host.len() && len <= buf.len() && {
+                allowed.copy_into_slice(&mut buf[..len]);
+                buf[..len].eq_ignore_ascii_case(host)
+            }
+        })
+    }
+
+    /// Host of an `http://`, `https://` or `//` URL, without user info or port
+    fn url_host(url: &[u8]) -> Option<&[u8]> {
+        let prefix_len = |prefix: &[u8]| {
+            (url.len() >= prefix.len() && url[..prefix.len()].eq_ignore_ascii_case(prefix))
+                .then_some(prefix.len())
+        };
+        let rest_start = prefix_len(b"https://")
+            .or_else(|| prefix_len(b"http://"))
+            .or_else(|| prefix_len(b"//"))?;
+        let rest = &url[rest_start..];
+        let authority_len = rest
+            .iter()
+            .position(|b| *b == b'/' || *b == b'?' || *b == b'#')
+            .unwrap_or(rest.len());
+        let authority = &rest[..authority_len];
+        let host = match authority.iter().rposition(|b| *b == b'@') {
+            Some(at) => &authority[at + 1..],
+            None => authority,
+        };
+        let host_len = host.iter().position(|b| *b == b':').unwrap_or(host.len());
+        (host_len > 0).then(|| &host[..host_len])
+    }
+
+    /// Check if thread is locked - returns error if so
+    /// Gracefully handles missing function for backwards compatibility
+    fn check_thread_not_locked(
+        env: &Env,
+        _registry: &Address,
+        board_id: u64,
+        thread_id: u64,
+    ) -> Result<(), ContentError> {
+        // Get board contract (single contract for all boards)
+        // If board contract is not available (e.g., in tests), skip the check
+        let board_contract = match Self::get_board_contract_address(env) {
+            Some(addr) => addr,
+            None => return Ok(()), // Skip check if board contract not available
+        };
+
+        // Query the board contract's is_thread_locked function (now requires board_id)
+        let thread_args: Vec<Val> =
+            Vec::from_array(env, [board_id.into_val(env), thread_id.into_val(env)]);
+        let is_locked: bool = env
+            .try_invoke_contract::<bool, soroban_sdk::Error>(
+                &board_contract,
+                &Symbol::new(env, "is_thread_locked"),
+                thread_args,
+            )
+            .unwrap_or(Ok(false))
+            .unwrap_or(false);
+
+        if is_locked {
+            return Err(ContentError::ThreadLocked);
+        }
+        Ok(())
+    }
+
+    /// Helper: Check if a user is quarantined on a board (false if permissions is unavailable)
+    fn is_quarantined(env: &Env, board_id: u64, user: &Address) -> bool {
+        let Some(permissions) = env
+            .storage()
+            .instance()
+            .get::<_, Address>(&ContentKey::Permissions)
+        else {
+            return false;
+        };
+
+        let args: Vec<Val> = Vec::from_array(env, [board_id.into_val(env), user.into_val(env)]);
+        env.try_invoke_contract::<bool, soroban_sdk::Error>(
+            &permissions,
+            &Symbol::new(env, "is_quarantined"),
+            args,
+        )
+        .unwrap_or(Ok(false))
+        .unwrap_or(false)
+    }
+
+    /// Check if thread accepts new replies - returns error if not
+    /// Asks the board contract for a single rejection reason covering
+    /// read-only boards and locked, hidden, deleted, or missing threads
+    fn check_thread_accepts_reply(
+        env: &Env,
+        _registry: &Address,
+        board_id: u64,
+        thread_id: u64,
+    ) -> Result<(), ContentError> {
+        // Get board contract (single contract for all boards)
+        // If board contract is not available (e.g., in tests), skip the check
+        let board_contract = match Self::get_board_contract_address(env) {
+            Some(addr) => addr,
+            None => return Ok(()), // Skip check if board contract not available
+        };
+
+        let thread_args: Vec<Val> =
+            Vec::from_array(env, [board_id.into_val(env), thread_id.into_val(env)]);
+        let reason: Option<Symbol> = env
+            .try_invoke_contract::<Option<Symbol>, soroban_sdk::Error>(
+                &board_contract,
+                &Symbol::new(env, "reply_rejection_reason"),
+                thread_args,
+            )
+            .unwrap_or(Ok(None))
+            .unwrap_or(None);
+
+        match reason {
+            None => Ok(()),
+            Some(r) if r == Symbol::new(env, "read_only") => Err(ContentError::BoardReadOnly),
+            Some(r) if r == Symbol::new(env, "locked") => Err(ContentError::ThreadLocked),
+            Some(_) => Err(ContentError::NotFound),
+        }
+    }
+
+    /// Check if user can reply on this board
+    #[allow(dead_code)]
+    fn check_can_reply(env: &Env, board_id: u64, user: &Address) {
+        if !env.storage().instance().has(&ContentKey::Permissions) {
+            return; // Skip if permissions not configured
+        }
+
+        let permissions: Address = env
+            .storage()
+            .instance()
+            .get(&ContentKey::Permissions)
+            .unwrap();
+
+        let args: Vec<Val> = Vec::from_array(env, [board_id.into_val(env), user.into_val(env)]);
+        let fn_name = Symbol::new(env, "can_reply");
+        let can_reply: bool = env.invoke_contract(&permissions, &fn_name, args);
+
+        if !can_reply {
+            panic!("Not authorized to reply");
+        }
+    }
+
+    /// Check if user can moderate on this board
+    fn check_can_moderate(env: &Env, board_id: u64, user: &Address) {
+        if !env.storage().instance().has(&ContentKey::Permissions) {
+            return; // Skip if permissions not configured
+        }
+
+        let permissions: Address = env
+            .storage()
+            .instance()
+            .get(&ContentKey::Permissions)
+            .unwrap();
+
+        let args: Vec<Val> = Vec::from_array(env, [board_id.into_val(env), user.into_val(env)]);
+        let fn_name = Symbol::new(env, "can_moderate");
+        let can_moderate: bool = env.invoke_contract(&permissions, &fn_name, args);
+
+        if !can_moderate {
+            panic!("Not authorized to moderate");
+        }
+    }
 
     /// Store thread body content
     /// Note: Auth is handled by the calling contract (theme).
@@ -424,6 +1369,13 @@ impl BoardsContent {
         _author: Address,
     ) {
         // Note: require_auth() removed - called by theme which handles auth
+        if let Err(err) = Self::check_post_size(&env, board_id, content.len(), false) {
+            panic_with_error!(&env, err);
+        }
+        if let Err(err) = Self::check_blocked_terms(&env, board_id, &content) {
+            panic_with_error!(&env, err);
+        }
+        let content = Self::apply_image_policy(&env, board_id, content);
 
         let key = Self::get_or_create_thread_body_chonk(&env, board_id, thread_id);
         let chonk = Chonk::open(&env, key);
@@ -476,11 +1428,16 @@ impl BoardsContent {
     ) {
         caller.require_auth();
         // TODO: Verify caller is author or moderator via permissions contract
+        if let Err(err) = Self::check_post_size(&env, board_id, content.len(), false) {
+            panic_with_error!(&env, err);
+        }
+        if let Err(err) = Self::check_blocked_terms(&env, board_id, &content) {
+            panic_with_error!(&env, err);
+        }
+        let content = Self::apply_image_policy(&env, board_id, content);
 
         let key = Self::get_or_create_thread_body_chonk(&env, board_id, thread_id);
-        let chonk = Chonk::open(&env, key);
-        chonk.clear();
-        chonk.write_chunked(content, 4096);
+        Self::write_revised_body(&env, key, board_id, thread_id, None, content, &caller);
     }
 
     /// Edit thread title and body (entry point for form submissions)
@@ -511,6 +1468,8 @@ impl BoardsContent {
 
         // Check thread is not locked
         Self::check_thread_not_locked(&env, &registry, board_id, thread_id)?;
+        Self::check_post_size(&env, board_id, new_body.len(), false)?;
+        Self::check_blocked_terms(&env, board_id, &Self::title_bytes(&env, &new_title))?;
 
         // Get board contract (single contract for all boards)
         let board_contract = Self::get_board_contract_address(&env)
@@ -545,11 +1504,11 @@ impl BoardsContent {
         } else {
             Bytes::new(&env)
         };
+        Self::check_blocked_terms(&env, board_id, &body_bytes)?;
+        let body_bytes = Self::apply_image_policy(&env, board_id, body_bytes);
 
         let key = Self::get_or_create_thread_body_chonk(&env, board_id, thread_id);
-        let chonk = Chonk::open(&env, key);
-        chonk.clear();
-        chonk.write_chunked(body_bytes, 4096);
+        Self::write_revised_body(&env, key, board_id, thread_id, None, body_bytes, &caller);
 
         Ok(())
     }
@@ -575,14 +1534,17 @@ impl BoardsContent {
             .get(&ContentKey::Registry)
             .ok_or(ContentError::NotInitialized)?;
 
-        // Check board is not readonly
-        Self::check_board_not_readonly(&env, &registry, board_id)?;
-
-        // Check thread is not locked
-        Self::check_thread_not_locked(&env, &registry, board_id, thread_id)?;
+        // Check board and thread state (read-only, locked, hidden, deleted)
+        Self::check_thread_accepts_reply(&env, &registry, board_id, thread_id)?;
+        Self::check_post_size(&env, board_id, content.len(), true)?;
+        Self::check_karma(&env, board_id, &creator, true)?;
+        let cooldown = Self::check_reply_cooldown(&env, board_id, thread_id, &creator)?;
 
         let reply_id = Self::next_reply_id(&env, board_id, thread_id);
 
+        // Quarantined users' replies start hidden until a moderator unhides them
+        let is_hidden = Self::is_quarantined(&env, board_id, &creator);
+
         // Store reply metadata
         let reply = ReplyMeta {
             id: reply_id,
@@ -593,7 +1555,7 @@ impl BoardsContent {
             creator: creator.clone(),
             created_at: env.ledger().timestamp(),
             updated_at: env.ledger().timestamp(),
-            is_hidden: false,
+            is_hidden,
             is_deleted: false,
             flag_count: 0,
         };
@@ -615,11 +1577,47 @@ impl BoardsContent {
         } else {
             Bytes::new(&env)
         };
+        Self::check_blocked_terms(&env, board_id, &content_bytes)?;
+        let content_bytes = Self::apply_image_policy(&env, board_id, content_bytes);
 
         // Store reply content in chonk
         let key = Self::get_or_create_reply_chonk(&env, board_id, thread_id, reply_id);
         let chonk = Chonk::open(&env, key);
-        chonk.write_chunked(content_bytes, 4096);
+        chonk.write_chunked(content_bytes.clone(), 4096);
+
+        // The reply form's draft has been posted
+        let draft_key = Self::reply_draft_key(&env, thread_id, parent_id);
+        Self::remove_draft(&env, board_id, &draft_key, &creator);
+
+        // Hidden (quarantined) replies don't notify anyone until a moderator lets them through
+        if !is_hidden {
+            Self::record_mentions(
+                &env,
+                board_id,
+                thread_id,
+                Some(reply_id),
+                &content_bytes,
+                &creator,
+            );
+            Self::notify_reply(&env, &reply);
+        }
+
+        // Index the reply under its author for their post history, and log it for
+        // moderation. Replies before the thread's first logged one are backfilled.
+        Self::index_user_reply(&env, &reply);
+        let log_from_key = ContentKey::ReplyLogFrom(board_id, thread_id);
+        if !env.storage().persistent().has(&log_from_key) {
+            env.storage().persistent().set(&log_from_key, &reply_id);
+        }
+        Self::log_user_reply(&env, &reply);
+
+        // Start the author's cooldown for this thread
+        if cooldown > 0 {
+            env.storage().persistent().set(
+                &ContentKey::LastReplyAt(board_id, thread_id, creator.clone()),
+                &reply.created_at,
+            );
+        }
 
         // Add to thread replies list
         let mut thread_replies: Vec<u64> = env
@@ -708,6 +1706,18 @@ impl BoardsContent {
         Ok(reply_id)
     }
 
+    /// When a user last replied to a thread, if the board had a reply cooldown at the time
+    pub fn get_last_reply_at(
+        env: Env,
+        board_id: u64,
+        thread_id: u64,
+        user: Address,
+    ) -> Option<u64> {
+        env.storage()
+            .persistent()
+            .get(&ContentKey::LastReplyAt(board_id, thread_id, user))
+    }
+
     /// Get reply metadata
     pub fn get_reply(env: Env, board_id: u64, thread_id: u64, reply_id: u64) -> Option<ReplyMeta> {
         env.storage()
@@ -715,6 +1725,35 @@ impl BoardsContent {
             .get(&ContentKey::Reply(board_id, thread_id, reply_id))
     }
 
+    /// Get the author of a reply (used by the voting contract to reject self-votes)
+    pub fn get_reply_author(
+        env: Env,
+        board_id: u64,
+        thread_id: u64,
+        reply_id: u64,
+    ) -> Option<Address> {
+        Self::get_reply(env, board_id, thread_id, reply_id).map(|reply| reply.creator)
+    }
+
+    /// List a user's most recent replies on a board, newest first
+    pub fn list_user_replies(env: Env, board_id: u64, user: Address, limit: u32) -> Vec<ReplyMeta> {
+        let index: Vec<(u64, u64, u64)> = env
+            .storage()
+            .persistent()
+            .get(&ContentKey::UserReplies(board_id, user))
+            .unwrap_or(Vec::new(&env));
+        let mut result = Vec::new(&env);
+        for (_, thread_id, reply_id) in index.iter().rev() {
+            if result.len() >= limit {
+                break;
+            }
+            if let Some(reply) = Self::get_reply(env.clone(), board_id, thread_id, reply_id) {
+                result.push_back(reply);
+            }
+        }
+        result
+    }
+
     /// Get reply content
     pub fn get_reply_content(env: Env, board_id: u64, thread_id: u64, reply_id: u64) -> Bytes {
         if let Some(key) = Self::get_reply_chonk(&env, board_id, thread_id, reply_id) {
@@ -725,6 +1764,25 @@ impl BoardsContent {
         }
     }
 
+    /// Content of several replies in a thread, in the order given (for exports)
+    pub fn get_reply_contents(
+        env: Env,
+        board_id: u64,
+        thread_id: u64,
+        reply_ids: Vec<u64>,
+    ) -> Vec<Bytes> {
+        let mut bodies = Vec::new(&env);
+        for reply_id in reply_ids.iter() {
+            bodies.push_back(Self::get_reply_content(
+                env.clone(),
+                board_id,
+                thread_id,
+                reply_id,
+            ));
+        }
+        bodies
+    }
+
     /// Get reply content chunk (for progressive loading)
     pub fn get_reply_content_chunk(
         env: Env,
@@ -824,39 +1882,142 @@ impl BoardsContent {
         replies
     }
 
-    /// List child replies of a specific reply with pagination
-    pub fn list_children_replies(
+    /// List top-level replies in the given order. Top ranks the top-level replies
+    /// among the thread's first MAX_REPLY_SORT_SCAN replies by vote score, with ties
+    /// (and every reply when voting is unavailable) kept in id order.
+    pub fn list_top_level_replies_sorted(
         env: Env,
         board_id: u64,
         thread_id: u64,
-        parent_id: u64,
         start: u32,
         limit: u32,
+        sort: ReplySort,
     ) -> Vec<ReplyMeta> {
-        let child_ids: Vec<u64> = env
+        if sort == ReplySort::Oldest {
+            return Self::list_top_level_replies(env, board_id, thread_id, start, limit);
+        }
+
+        let reply_ids: Vec<u64> = env
             .storage()
             .persistent()
-            .get(&ContentKey::ChildReplies(board_id, thread_id, parent_id))
+            .get(&ContentKey::ThreadReplies(board_id, thread_id))
             .unwrap_or(Vec::new(&env));
+        let top_level = |i: u32| -> Option<ReplyMeta> {
+            env.storage()
+                .persistent()
+                .get::<_, ReplyMeta>(&ContentKey::Reply(board_id, thread_id, reply_ids.get(i)?))
+                .filter(|reply| reply.depth == 0)
+        };
 
         let mut replies = Vec::new(&env);
-        let total = child_ids.len();
-        let end = core::cmp::min(start + limit, total);
-
-        for i in start..end {
-            let reply_id = child_ids.get(i).unwrap();
-            if let Some(reply) = env
-                .storage()
-                .persistent()
-                .get(&ContentKey::Reply(board_id, thread_id, reply_id))
-            {
-                replies.push_back(reply);
+        if sort == ReplySort::Newest {
+            let mut skipped = 0u32;
+            let mut i = reply_ids.len();
+            while i > 0 && replies.len() < limit {
+                i -= 1;
+                if let Some(reply) = top_level(i) {
+                    if skipped >= start {
+                        replies.push_back(reply);
+                    } else {
+                        skipped += 1;
+                    }
+                }
             }
+            return replies;
         }
 
-        replies
-    }
-
+        // Top: score the window by id alone, then load replies in rank order until
+        // the page is full
+        let window = reply_ids.len().min(MAX_REPLY_SORT_SCAN);
+        let ids = reply_ids.slice(0..window);
+        let tallies: Vec<VoteTally> = Self::get_voting_contract_address(&env)
+            .and_then(|voting| {
+                env.try_invoke_contract::<Vec<VoteTally>, soroban_sdk::Error>(
+                    &voting,
+                    &Symbol::new(&env, "get_reply_tallies"),
+                    Vec::from_array(
+                        &env,
+                        [
+                            board_id.into_val(&env),
+                            thread_id.into_val(&env),
+                            ids.into_val(&env),
+                        ],
+                    ),
+                )
+                .ok()
+                .and_then(|r| r.ok())
+            })
+            .unwrap_or(Vec::new(&env));
+        let mut ranked = [(0i32, 0u32); MAX_REPLY_SORT_SCAN as usize];
+        for i in 0..window {
+            ranked[i as usize] = (tallies.get(i).map(|t| t.score).unwrap_or(0), i);
+        }
+        let ranked = &mut ranked[..window as usize];
+        // Highest score first; ties keep the lower id first
+        ranked.sort_unstable_by(|a, b| b.0.cmp(&a.0).then(a.1.cmp(&b.1)));
+
+        let end = start.saturating_add(limit);
+        let mut picked = 0u32;
+        for &(_, i) in ranked.iter() {
+            if picked >= end {
+                break;
+            }
+            if let Some(reply) = top_level(i) {
+                if picked >= start {
+                    replies.push_back(reply);
+                }
+                picked += 1;
+            }
+        }
+
+        // Anything past the ranked window follows in id order
+        let mut i = window;
+        while i < reply_ids.len() && picked < end {
+            if let Some(reply) = top_level(i) {
+                if picked >= start {
+                    replies.push_back(reply);
+                }
+                picked += 1;
+            }
+            i += 1;
+        }
+
+        replies
+    }
+
+    /// List child replies of a specific reply with pagination
+    pub fn list_children_replies(
+        env: Env,
+        board_id: u64,
+        thread_id: u64,
+        parent_id: u64,
+        start: u32,
+        limit: u32,
+    ) -> Vec<ReplyMeta> {
+        let child_ids: Vec<u64> = env
+            .storage()
+            .persistent()
+            .get(&ContentKey::ChildReplies(board_id, thread_id, parent_id))
+            .unwrap_or(Vec::new(&env));
+
+        let mut replies = Vec::new(&env);
+        let total = child_ids.len();
+        let end = core::cmp::min(start + limit, total);
+
+        for i in start..end {
+            let reply_id = child_ids.get(i).unwrap();
+            if let Some(reply) = env
+                .storage()
+                .persistent()
+                .get(&ContentKey::Reply(board_id, thread_id, reply_id))
+            {
+                replies.push_back(reply);
+            }
+        }
+
+        replies
+    }
+
     /// Get child reply count for a reply
     pub fn get_children_count(env: Env, board_id: u64, thread_id: u64, parent_id: u64) -> u32 {
         let child_ids: Vec<u64> = env
@@ -867,6 +2028,25 @@ impl BoardsContent {
         child_ids.len()
     }
 
+    /// Child reply counts for several replies, in the order given
+    pub fn get_children_counts(
+        env: Env,
+        board_id: u64,
+        thread_id: u64,
+        parent_ids: Vec<u64>,
+    ) -> Vec<u32> {
+        let mut counts = Vec::new(&env);
+        for parent_id in parent_ids.iter() {
+            counts.push_back(Self::get_children_count(
+                env.clone(),
+                board_id,
+                thread_id,
+                parent_id,
+            ));
+        }
+        counts
+    }
+
     /// Edit reply content (takes Bytes, for internal use)
     /// Returns an error if not authorized or if the board is read-only/thread is locked
     pub fn edit_reply(
@@ -904,6 +2084,7 @@ impl BoardsContent {
             Self::check_board_not_readonly(&env, &registry, board_id)?;
             Self::check_thread_not_locked(&env, &registry, board_id, thread_id)?;
         }
+        Self::check_post_size(&env, board_id, content.len(), true)?;
 
         reply.updated_at = env.ledger().timestamp();
         env.storage()
@@ -911,10 +2092,18 @@ impl BoardsContent {
             .set(&ContentKey::Reply(board_id, thread_id, reply_id), &reply);
 
         // Update content
+        Self::check_blocked_terms(&env, board_id, &content)?;
+        let content = Self::apply_image_policy(&env, board_id, content);
         let key = Self::get_or_create_reply_chonk(&env, board_id, thread_id, reply_id);
-        let chonk = Chonk::open(&env, key);
-        chonk.clear();
-        chonk.write_chunked(content, 4096);
+        Self::write_revised_body(
+            &env,
+            key,
+            board_id,
+            thread_id,
+            Some(reply_id),
+            content,
+            &caller,
+        );
 
         Ok(())
     }
@@ -956,6 +2145,7 @@ impl BoardsContent {
             Self::check_board_not_readonly(&env, &registry, board_id)?;
             Self::check_thread_not_locked(&env, &registry, board_id, thread_id)?;
         }
+        Self::check_post_size(&env, board_id, content.len(), true)?;
 
         reply.updated_at = env.ledger().timestamp();
         env.storage()
@@ -975,16 +2165,185 @@ impl BoardsContent {
         } else {
             Bytes::new(&env)
         };
+        Self::check_blocked_terms(&env, board_id, &content_bytes)?;
+        let content_bytes = Self::apply_image_policy(&env, board_id, content_bytes);
 
         // Update content
         let key = Self::get_or_create_reply_chonk(&env, board_id, thread_id, reply_id);
-        let chonk = Chonk::open(&env, key);
-        chonk.clear();
-        chonk.write_chunked(content_bytes, 4096);
+        Self::write_revised_body(
+            &env,
+            key,
+            board_id,
+            thread_id,
+            Some(reply_id),
+            content_bytes,
+            &caller,
+        );
 
         Ok(())
     }
 
+    /// Overwrite a post body, keeping the body it replaces as the newest revision.
+    /// Unchanged bodies don't create a revision.
+    fn write_revised_body(
+        env: &Env,
+        key: Symbol,
+        board_id: u64,
+        thread_id: u64,
+        reply_id: Option<u64>,
+        body: Bytes,
+        editor: &Address,
+    ) {
+        let chonk = Chonk::open(env, key);
+        let previous = chonk.assemble();
+        if previous == body {
+            return;
+        }
+
+        let revisions_key = ContentKey::Revisions(board_id, thread_id, reply_id);
+        let mut revisions: Vec<Revision> = env
+            .storage()
+            .persistent()
+            .get(&revisions_key)
+            .unwrap_or(Vec::new(env));
+        revisions.push_front(Revision {
+            body: previous,
+            editor: editor.clone(),
+            replaced_at: env.ledger().timestamp(),
+        });
+        while revisions.len() > MAX_REVISIONS {
+            revisions.pop_back();
+        }
+        env.storage().persistent().set(&revisions_key, &revisions);
+
+        chonk.clear();
+        chonk.write_chunked(body, 4096);
+    }
+
+    /// List previous bodies of a thread (reply_id None) or reply, newest first.
+    /// At most MAX_REVISIONS are kept.
+    pub fn list_revisions(
+        env: Env,
+        board_id: u64,
+        thread_id: u64,
+        reply_id: Option<u64>,
+    ) -> Vec<Revision> {
+        env.storage()
+            .persistent()
+            .get(&ContentKey::Revisions(board_id, thread_id, reply_id))
+            .unwrap_or(Vec::new(&env))
+    }
+
+    /// Check whether a thread (reply_id None) or reply has been edited
+    pub fn is_edited(env: Env, board_id: u64, thread_id: u64, reply_id: Option<u64>) -> bool {
+        env.storage()
+            .persistent()
+            .has(&ContentKey::Revisions(board_id, thread_id, reply_id))
+    }
+
+    /// Of the given replies, return the ids of those that have been edited
+    pub fn get_edited_replies(
+        env: Env,
+        board_id: u64,
+        thread_id: u64,
+        reply_ids: Vec<u64>,
+    ) -> Vec<u64> {
+        let mut edited = Vec::new(&env);
+        for reply_id in reply_ids.iter() {
+            if env.storage().persistent().has(&ContentKey::Revisions(
+                board_id,
+                thread_id,
+                Some(reply_id),
+            )) {
+                edited.push_back(reply_id);
+            }
+        }
+        edited
+    }
+
+    /// Check whether a thread (reply_id None) or reply is marked as posted by a
+    /// moderator in that capacity
+    pub fn is_distinguished(
+        env: Env,
+        board_id: u64,
+        thread_id: u64,
+        reply_id: Option<u64>,
+    ) -> bool {
+        env.storage()
+            .persistent()
+            .has(&ContentKey::Distinguished(board_id, thread_id, reply_id))
+    }
+
+    /// Of the given replies, return the ids of those marked as posted by a moderator
+    pub fn get_distinguished(
+        env: Env,
+        board_id: u64,
+        thread_id: u64,
+        reply_ids: Vec<u64>,
+    ) -> Vec<u64> {
+        let mut distinguished = Vec::new(&env);
+        for reply_id in reply_ids.iter() {
+            if env.storage().persistent().has(&ContentKey::Distinguished(
+                board_id,
+                thread_id,
+                Some(reply_id),
+            )) {
+                distinguished.push_back(reply_id);
+            }
+        }
+        distinguished
+    }
+
+    /// Toggle the moderator mark on a thread (reply_id None) or reply and return the
+    /// new state. Only the post's author can add the mark, and only while they are a
+    /// moderator on the board; the author or any moderator can remove it.
+    pub fn distinguish(
+        env: Env,
+        board_id: u64,
+        thread_id: u64,
+        reply_id: Option<u64>,
+        caller: Address,
+    ) -> Result<bool, ContentError> {
+        caller.require_auth();
+
+        let author = match reply_id {
+            Some(reply_id) => env
+                .storage()
+                .persistent()
+                .get::<_, ReplyMeta>(&ContentKey::Reply(board_id, thread_id, reply_id))
+                .filter(|reply| !reply.is_deleted)
+                .map(|reply| reply.creator),
+            None => Self::get_board_contract_address(&env).and_then(|board_contract| {
+                env.try_invoke_contract::<Option<(String, Address)>, soroban_sdk::Error>(
+                    &board_contract,
+                    &Symbol::new(&env, "get_thread_title_and_author"),
+                    Vec::from_array(&env, [board_id.into_val(&env), thread_id.into_val(&env)]),
+                )
+                .ok()
+                .and_then(|r| r.ok())
+                .flatten()
+                .map(|(_, author)| author)
+            }),
+        }
+        .ok_or(ContentError::NotFound)?;
+
+        let key = ContentKey::Distinguished(board_id, thread_id, reply_id);
+        let is_moderator = Self::check_is_moderator(&env, board_id, &caller);
+        if env.storage().persistent().has(&key) {
+            if author != caller && !is_moderator {
+                return Err(ContentError::NotAuthorized);
+            }
+            env.storage().persistent().remove(&key);
+            Ok(false)
+        } else {
+            if author != caller || !is_moderator {
+                return Err(ContentError::NotAuthorized);
+            }
+            env.storage().persistent().set(&key, &true);
+            Ok(true)
+        }
+    }
+
     /// Helper: Check if user is moderator
     fn check_is_moderator(env: &Env, board_id: u64, user: &Address) -> bool {
         if !env.storage().instance().has(&ContentKey::Permissions) {
@@ -1033,46 +2392,26 @@ impl BoardsContent {
         }
     }
 
-    /// Flag a reply
+    /// Flag a reply. `reason` is one of "spam", "abuse", "off-topic" or "other";
+    /// a second flag from the same user while the first is still open is ignored.
     pub fn flag_reply(
         env: Env,
         board_id: u64,
         thread_id: u64,
         reply_id: u64,
         reason: String,
+        details: Option<String>,
         flagger: Address,
     ) {
         flagger.require_auth();
+        Self::check_flag_input(&reason, &details);
 
-        let flag = Flag {
-            flagger: flagger.clone(),
-            reason,
-            created_at: env.ledger().timestamp(),
-            resolved: false,
-            rule_violated: None,
+        let key = ContentKey::Flags(board_id, thread_id, reply_id);
+        let Some((flag_count, is_first_flag)) = Self::record_flag(&env, &key, reason, &flagger)
+        else {
+            return;
         };
-
-        // Get existing flags
-        let mut flags: Vec<Flag> = env
-            .storage()
-            .persistent()
-            .get(&ContentKey::Flags(board_id, thread_id, reply_id))
-            .unwrap_or(Vec::new(&env));
-
-        // Check if this user already flagged
-        for i in 0..flags.len() {
-            if flags.get(i).unwrap().flagger == flagger {
-                panic!("Already flagged by this user");
-            }
-        }
-
-        let is_first_flag = flags.is_empty();
-        flags.push_back(flag);
-        let flag_count = flags.len();
-
-        env.storage()
-            .persistent()
-            .set(&ContentKey::Flags(board_id, thread_id, reply_id), &flags);
+        Self::set_flag_details(&env, board_id, thread_id, Some(reply_id), &flagger, details);
 
         // Update flag count on reply and check for auto-hide
         if let Some(mut reply) = env
@@ -1108,47 +2447,117 @@ impl BoardsContent {
         }
     }
 
-    /// Flag a thread
-    pub fn flag_thread(env: Env, board_id: u64, thread_id: u64, reason: String, flagger: Address) {
+    /// Flag a thread (same reasons and duplicate handling as `flag_reply`)
+    pub fn flag_thread(
+        env: Env,
+        board_id: u64,
+        thread_id: u64,
+        reason: String,
+        details: Option<String>,
+        flagger: Address,
+    ) {
         flagger.require_auth();
+        Self::check_flag_input(&reason, &details);
 
-        let flag = Flag {
-            flagger: flagger.clone(),
-            reason,
-            created_at: env.ledger().timestamp(),
-            resolved: false,
-            rule_violated: None,
+        let key = ContentKey::ThreadFlags(board_id, thread_id);
+        let Some((flag_count, is_first_flag)) = Self::record_flag(&env, &key, reason, &flagger)
+        else {
+            return;
         };
+        Self::set_flag_details(&env, board_id, thread_id, None, &flagger, details);
+
+        env.storage().persistent().set(
+            &ContentKey::ThreadFlagCount(board_id, thread_id),
+            &flag_count,
+        );
+
+        // Add to flagged content list
+        if is_first_flag {
+            Self::add_to_flagged_content(&env, board_id, thread_id, 0, FlaggedType::Thread);
+        } else {
+            Self::update_flagged_content_count(
+                &env,
+                board_id,
+                thread_id,
+                0,
+                FlaggedType::Thread,
+                flag_count,
+            );
+        }
+
+        // Auto-hide for threads is handled by the board contract, which owns ThreadMeta
+        if let Some(board_contract) = Self::get_board_contract_address(&env) {
+            let notify_args: Vec<Val> = Vec::from_array(
+                &env,
+                [
+                    board_id.into_val(&env),
+                    thread_id.into_val(&env),
+                    flag_count.into_val(&env),
+                ],
+            );
+            let _ = env.try_invoke_contract::<(), soroban_sdk::Error>(
+                &board_contract,
+                &Symbol::new(&env, "notify_thread_flagged"),
+                notify_args,
+            );
+        }
+    }
+
+    /// Retract the caller's open flag on a reply. The reply stays hidden if it was
+    /// auto-hidden; un-hiding is left to moderators.
+    pub fn unflag_reply(env: Env, board_id: u64, thread_id: u64, reply_id: u64, flagger: Address) {
+        flagger.require_auth();
+
+        let key = ContentKey::Flags(board_id, thread_id, reply_id);
+        let flag_count = Self::retract_flag(&env, &key, &flagger);
+        Self::set_flag_details(&env, board_id, thread_id, Some(reply_id), &flagger, None);
 
-        // Get existing flags
-        let mut flags: Vec<Flag> = env
+        if let Some(mut reply) = env
             .storage()
             .persistent()
-            .get(&ContentKey::ThreadFlags(board_id, thread_id))
-            .unwrap_or(Vec::new(&env));
+            .get::<_, ReplyMeta>(&ContentKey::Reply(board_id, thread_id, reply_id))
+        {
+            reply.flag_count = flag_count;
+            env.storage()
+                .persistent()
+                .set(&ContentKey::Reply(board_id, thread_id, reply_id), &reply);
+        }
 
-        // Check if this user already flagged
-        for i in 0..flags.len() {
-            if flags.get(i).unwrap().flagger == flagger {
-                panic!("Already flagged by this user");
-            }
+        if flag_count == 0 {
+            Self::remove_from_flagged_content(
+                &env,
+                board_id,
+                thread_id,
+                reply_id,
+                FlaggedType::Reply,
+            );
+        } else {
+            Self::update_flagged_content_count(
+                &env,
+                board_id,
+                thread_id,
+                reply_id,
+                FlaggedType::Reply,
+                flag_count,
+            );
         }
+    }
+
+    /// Retract the caller's open flag on a thread
+    pub fn unflag_thread(env: Env, board_id: u64, thread_id: u64, flagger: Address) {
+        flagger.require_auth();
 
-        let is_first_flag = flags.is_empty();
-        flags.push_back(flag);
-        let flag_count = flags.len();
+        let key = ContentKey::ThreadFlags(board_id, thread_id);
+        let flag_count = Self::retract_flag(&env, &key, &flagger);
+        Self::set_flag_details(&env, board_id, thread_id, None, &flagger, None);
 
-        env.storage()
-            .persistent()
-            .set(&ContentKey::ThreadFlags(board_id, thread_id), &flags);
         env.storage().persistent().set(
             &ContentKey::ThreadFlagCount(board_id, thread_id),
             &flag_count,
         );
 
-        // Add to flagged content list
-        if is_first_flag {
-            Self::add_to_flagged_content(&env, board_id, thread_id, 0, FlaggedType::Thread);
+        if flag_count == 0 {
+            Self::remove_from_flagged_content(&env, board_id, thread_id, 0, FlaggedType::Thread);
         } else {
             Self::update_flagged_content_count(
                 &env,
@@ -1159,8 +2568,6 @@ impl BoardsContent {
                 flag_count,
             );
         }
-
-        // Note: Auto-hide for threads is handled by board contract
     }
 
     /// Get flags for a reply
@@ -1179,6 +2586,46 @@ impl BoardsContent {
             .unwrap_or(Vec::new(&env))
     }
 
+    /// List unresolved flags on a thread (reply_id None) or reply
+    pub fn list_flags(
+        env: Env,
+        board_id: u64,
+        thread_id: u64,
+        reply_id: Option<u64>,
+    ) -> Vec<FlagRecord> {
+        let key = match reply_id {
+            Some(reply_id) => ContentKey::Flags(board_id, thread_id, reply_id),
+            None => ContentKey::ThreadFlags(board_id, thread_id),
+        };
+        let flags: Vec<Flag> = env
+            .storage()
+            .persistent()
+            .get(&key)
+            .unwrap_or(Vec::new(&env));
+
+        let mut records = Vec::new(&env);
+        for flag in flags.iter() {
+            if flag.resolved {
+                continue;
+            }
+            let details_key =
+                ContentKey::FlagDetails(board_id, thread_id, reply_id, flag.flagger.clone());
+            let details: Option<String> = env.storage().persistent().get(&details_key);
+            // Flags from before structured reasons keep their free-form text as details
+            let (reason, details) = match Self::parse_flag_reason(&flag.reason) {
+                Some(reason) => (reason, details.unwrap_or(String::from_str(&env, ""))),
+                None => (FlagReason::Other, flag.reason),
+            };
+            records.push_back(FlagRecord {
+                flagger: flag.flagger,
+                reason,
+                details,
+                created_at: flag.created_at,
+            });
+        }
+        records
+    }
+
     /// Get thread flag count
     pub fn get_thread_flag_count(env: Env, board_id: u64, thread_id: u64) -> u32 {
         env.storage()
@@ -1187,6 +2634,19 @@ impl BoardsContent {
             .unwrap_or(0)
     }
 
+    /// Flag counts for several threads, in the order given (for filtered listings)
+    pub fn get_thread_flag_counts(env: Env, board_id: u64, thread_ids: Vec<u64>) -> Vec<u32> {
+        let mut counts = Vec::new(&env);
+        for thread_id in thread_ids.iter() {
+            counts.push_back(Self::get_thread_flag_count(
+                env.clone(),
+                board_id,
+                thread_id,
+            ));
+        }
+        counts
+    }
+
     /// Clear/resolve flags on a reply (moderator action)
     pub fn clear_reply_flags(
         env: Env,
@@ -1206,6 +2666,8 @@ impl BoardsContent {
         {
             for i in 0..flags.len() {
                 let mut flag = flags.get(i).unwrap();
+                let reply_key = Some(reply_id);
+                Self::set_flag_details(&env, board_id, thread_id, reply_key, &flag.flagger, None);
                 flag.resolved = true;
                 flags.set(i, flag);
             }
@@ -1243,6 +2705,7 @@ impl BoardsContent {
         {
             for i in 0..flags.len() {
                 let mut flag = flags.get(i).unwrap();
+                Self::set_flag_details(&env, board_id, thread_id, None, &flag.flagger, None);
                 flag.resolved = true;
                 flags.set(i, flag);
             }
@@ -1284,8 +2747,110 @@ impl BoardsContent {
         env.invoke_contract(&permissions, &fn_name, args)
     }
 
-    /// Helper: Add item to flagged content list
-    fn add_to_flagged_content(
+    /// Helper: Parse a flag reason name
+    fn parse_flag_reason(reason: &String) -> Option<FlagReason> {
+        let len = reason.len() as usize;
+        if len > 16 {
+            return None;
+        }
+        let mut buf = [0u8; 16];
+        reason.copy_into_slice(&mut buf[..len]);
+        match &buf[..len] {
+            b"spam" => Some(FlagReason::Spam),
+            b"abuse" => Some(FlagReason::Abuse),
+            b"off-topic" => Some(FlagReason::OffTopic),
+            b"other" => Some(FlagReason::Other),
+            _ => None,
+        }
+    }
+
+    /// Helper: Validate a flag's reason and optional details
+    fn check_flag_input(reason: &String, details: &Option<String>) {
+        if Self::parse_flag_reason(reason).is_none() {
+            panic!("Invalid flag reason");
+        }
+        if let Some(details) = details {
+            if details.len() > MAX_FLAG_DETAILS_LEN {
+                panic!("Flag details are too long");
+            }
+        }
+    }
+
+    /// Helper: Append a flag unless the flagger already has an open one on the item.
+    /// Returns the open flag count and whether this is the first open flag.
+    fn record_flag(
+        env: &Env,
+        key: &ContentKey,
+        reason: String,
+        flagger: &Address,
+    ) -> Option<(u32, bool)> {
+        let mut flags: Vec<Flag> = env.storage().persistent().get(key).unwrap_or(Vec::new(env));
+
+        // Resolved flags stay for the record but no longer count
+        let mut open_count = 0;
+        for flag in flags.iter() {
+            if !flag.resolved {
+                if flag.flagger == *flagger {
+                    return None;
+                }
+                open_count += 1;
+            }
+        }
+
+        flags.push_back(Flag {
+            flagger: flagger.clone(),
+            reason,
+            created_at: env.ledger().timestamp(),
+            resolved: false,
+            rule_violated: None,
+        });
+        env.storage().persistent().set(key, &flags);
+        Some((open_count + 1, open_count == 0))
+    }
+
+    /// Helper: Remove the flagger's open flag from an item, returning the open count left
+    fn retract_flag(env: &Env, key: &ContentKey, flagger: &Address) -> u32 {
+        let flags: Vec<Flag> = env.storage().persistent().get(key).unwrap_or(Vec::new(env));
+
+        let mut kept = Vec::new(env);
+        let mut found = false;
+        let mut open_count = 0;
+        for flag in flags.iter() {
+            if !flag.resolved {
+                if !found && flag.flagger == *flagger {
+                    found = true;
+                    continue;
+                }
+                open_count += 1;
+            }
+            kept.push_back(flag);
+        }
+        if !found {
+            panic!("No open flag from this user");
+        }
+
+        env.storage().persistent().set(key, &kept);
+        open_count
+    }
+
+    /// Helper: Store (or with None/empty, remove) the details of a user's flag
+    fn set_flag_details(
+        env: &Env,
+        board_id: u64,
+        thread_id: u64,
+        reply_id: Option<u64>,
+        flagger: &Address,
+        details: Option<String>,
+    ) {
+        let key = ContentKey::FlagDetails(board_id, thread_id, reply_id, flagger.clone());
+        match details {
+            Some(details) if !details.is_empty() => env.storage().persistent().set(&key, &details),
+            _ => env.storage().persistent().remove(&key),
+        }
+    }
+
+    /// Helper: Add item to flagged content list
+    fn add_to_flagged_content(
         env: &Env,
         board_id: u64,
         thread_id: u64,
@@ -1484,6 +3049,17 @@ impl BoardsContent {
 
             // Remove from flagged content list
             Self::remove_from_flagged_content(&env, board_id, thread_id, 0, FlaggedType::Thread);
+
+            // Let the board contract re-arm auto-hide for this thread
+            if let Some(board_contract) = Self::get_board_contract_address(&env) {
+                let reset_args: Vec<Val> =
+                    Vec::from_array(&env, [board_id.into_val(&env), thread_id.into_val(&env)]);
+                let _ = env.try_invoke_contract::<(), soroban_sdk::Error>(
+                    &board_contract,
+                    &Symbol::new(&env, "reset_thread_auto_hide"),
+                    reset_args,
+                );
+            }
         }
     }
 
@@ -1524,6 +3100,8 @@ impl BoardsContent {
 
         // Check target board is not readonly
         Self::check_board_not_readonly(&env, &registry, target_board_id)?;
+        Self::check_post_size(&env, target_board_id, comment.len(), false)?;
+        Self::check_karma(&env, target_board_id, &caller, false)?;
 
         // Get board contract (single contract for all boards)
         let board_contract = Self::get_board_contract_address(&env)
@@ -1601,6 +3179,8 @@ impl BoardsContent {
                 comment.copy_into_slice(&mut temp[..16384]);
                 Bytes::from_slice(&env, &temp)
             };
+            Self::check_blocked_terms(&env, target_board_id, &comment_bytes)?;
+            let comment_bytes = Self::apply_image_policy(&env, target_board_id, comment_bytes);
             let key = Self::get_or_create_thread_body_chonk(&env, target_board_id, new_thread_id);
             let chonk = Chonk::open(&env, key);
             chonk.write_chunked(comment_bytes, 4096);
@@ -1664,6 +3244,421 @@ impl BoardsContent {
             .unwrap_or(Vec::new(&env))
     }
 
+    /// Remove a crosspost (board_id/thread_id is the crosspost thread, not the original).
+    /// Allowed for the crossposter, the original author, or a moderator of the
+    /// destination board. The crosspost thread is soft-deleted, its CrosspostRef is
+    /// replaced by a tombstone, and it is dropped from the original's crosspost list.
+    pub fn remove_crosspost(
+        env: Env,
+        board_id: u64,
+        thread_id: u64,
+        caller: Address,
+    ) -> Result<(), ContentError> {
+        caller.require_auth();
+
+        let xpost: CrosspostRef = env
+            .storage()
+            .persistent()
+            .get(&ContentKey::CrosspostRef(board_id, thread_id))
+            .ok_or(ContentError::NotFound)?;
+
+        let allowed = caller == xpost.crossposted_by
+            || caller == xpost.original_author
+            || Self::check_is_moderator(&env, board_id, &caller);
+        if !allowed {
+            return Err(ContentError::NotAuthorized);
+        }
+
+        let board_contract = Self::get_board_contract_address(&env)
+            .ok_or(ContentError::BoardContractNotAvailable)?;
+        let delete_args: Vec<Val> =
+            Vec::from_array(&env, [board_id.into_val(&env), thread_id.into_val(&env)]);
+        env.invoke_contract::<()>(
+            &board_contract,
+            &Symbol::new(&env, "remove_crosspost_thread"),
+            delete_args,
+        );
+
+        env.storage()
+            .persistent()
+            .remove(&ContentKey::CrosspostRef(board_id, thread_id));
+        env.storage().persistent().set(
+            &ContentKey::RemovedCrosspost(board_id, thread_id),
+            &RemovedCrosspost {
+                original_board_id: xpost.original_board_id,
+                original_thread_id: xpost.original_thread_id,
+                removed_by: caller,
+                removed_at: env.ledger().timestamp(),
+            },
+        );
+
+        // Drop it from the original thread's count and list
+        let count_key =
+            ContentKey::CrosspostCount(xpost.original_board_id, xpost.original_thread_id);
+        let count: u32 = env.storage().persistent().get(&count_key).unwrap_or(0);
+        env.storage()
+            .persistent()
+            .set(&count_key, &count.saturating_sub(1));
+
+        let list_key = ContentKey::CrosspostList(xpost.original_board_id, xpost.original_thread_id);
+        let mut list: Vec<CrosspostLocation> = env
+            .storage()
+            .persistent()
+            .get(&list_key)
+            .unwrap_or(Vec::new(&env));
+        if let Some(i) = list
+            .iter()
+            .position(|loc| loc.board_id == board_id && loc.thread_id == thread_id)
+        {
+            list.remove(i as u32);
+            env.storage().persistent().set(&list_key, &list);
+        }
+
+        Ok(())
+    }
+
+    /// Get the tombstone of a removed crosspost (None if it was never removed)
+    pub fn get_removed_crosspost(
+        env: Env,
+        board_id: u64,
+        thread_id: u64,
+    ) -> Option<RemovedCrosspost> {
+        env.storage()
+            .persistent()
+            .get(&ContentKey::RemovedCrosspost(board_id, thread_id))
+    }
+
+    /// List posts that mentioned a user, newest first.
+    /// Mentions in replies that have since been hidden or deleted are left out.
+    pub fn list_mentions(env: Env, user: Address, start: u32, limit: u32) -> Vec<Mention> {
+        let mentions: Vec<Mention> = env
+            .storage()
+            .persistent()
+            .get(&ContentKey::Mentions(user))
+            .unwrap_or(Vec::new(&env));
+
+        let mut result = Vec::new(&env);
+        let end = start.saturating_add(limit).min(mentions.len());
+        for i in start..end {
+            let mention = mentions.get(i).unwrap();
+            if let Some(reply_id) = mention.reply_id {
+                let visible = env
+                    .storage()
+                    .persistent()
+                    .get::<_, ReplyMeta>(&ContentKey::Reply(
+                        mention.board_id,
+                        mention.thread_id,
+                        reply_id,
+                    ))
+                    .is_some_and(|reply| !reply.is_hidden && !reply.is_deleted);
+                if !visible {
+                    continue;
+                }
+            }
+            result.push_back(mention);
+        }
+        result
+    }
+
+    /// Add a notification to a user's inbox (called by the admin contract for
+    /// moderator actions; reply notifications are recorded by create_reply)
+    pub fn push_notification(
+        env: Env,
+        recipient: Address,
+        kind: Symbol,
+        board_id: u64,
+        thread_id: u64,
+        reply_id: Option<u64>,
+        actor: Address,
+    ) {
+        let admin = Self::get_admin_contract_address(&env).expect("Admin contract not registered");
+        admin.require_auth();
+        Self::notify(
+            &env, &recipient, kind, board_id, thread_id, reply_id, &actor,
+        );
+    }
+
+    /// List a user's notifications, newest first
+    pub fn list_notifications(
+        env: Env,
+        user: Address,
+        start: u32,
+        limit: u32,
+    ) -> Vec<Notification> {
+        let mut result = Vec::new(&env);
+        let count = Self::get_notification_count(env.clone(), user.clone());
+        let oldest = Self::oldest_notification_id(count);
+        let Some(mut id) = count.checked_sub(start as u64).filter(|id| *id >= oldest) else {
+            return result;
+        };
+
+        // Walk backwards, loading each chunk once
+        let mut chunk: Vec<Notification> = Vec::new(&env);
+        let mut loaded: Option<u64> = None;
+        while id >= oldest && result.len() < limit {
+            let chunk_index = (id - 1) / NOTIFICATION_CHUNK_SIZE;
+            if loaded != Some(chunk_index) {
+                chunk = env
+                    .storage()
+                    .persistent()
+                    .get(&ContentKey::NotificationChunk(user.clone(), chunk_index))
+                    .unwrap_or(Vec::new(&env));
+                loaded = Some(chunk_index);
+            }
+            if let Some(notification) = chunk.get(((id - 1) % NOTIFICATION_CHUNK_SIZE) as u32) {
+                result.push_back(notification);
+            }
+            id -= 1;
+        }
+        result
+    }
+
+    /// Number of notifications ever sent to a user (the newest notification's ID)
+    pub fn get_notification_count(env: Env, user: Address) -> u64 {
+        env.storage()
+            .persistent()
+            .get(&ContentKey::NotificationCount(user))
+            .unwrap_or(0)
+    }
+
+    /// Number of kept notifications the user hasn't read yet
+    pub fn get_unread_count(env: Env, user: Address) -> u32 {
+        let count = Self::get_notification_count(env.clone(), user.clone());
+        let read: u64 = env
+            .storage()
+            .persistent()
+            .get(&ContentKey::NotificationsRead(user))
+            .unwrap_or(0);
+        let first_unread = (read + 1).max(Self::oldest_notification_id(count));
+        (count + 1).saturating_sub(first_unread) as u32
+    }
+
+    /// Mark every notification up to and including `up_to_id` as read.
+    /// Parameter order matches the "mark all read" link (up_to_id, user).
+    pub fn mark_read(env: Env, up_to_id: u64, user: Address) {
+        user.require_auth();
+        let count = Self::get_notification_count(env.clone(), user.clone());
+        let key = ContentKey::NotificationsRead(user);
+        let read: u64 = env.storage().persistent().get(&key).unwrap_or(0);
+        let up_to = up_to_id.min(count);
+        if up_to > read {
+            env.storage().persistent().set(&key, &up_to);
+        }
+    }
+
+    /// ID of the oldest notification still kept when `count` have been sent
+    fn oldest_notification_id(count: u64) -> u64 {
+        if count == 0 {
+            return 1;
+        }
+        let kept_chunks = MAX_NOTIFICATIONS / NOTIFICATION_CHUNK_SIZE;
+        let newest_chunk = (count - 1) / NOTIFICATION_CHUNK_SIZE;
+        newest_chunk.saturating_sub(kept_chunks - 1) * NOTIFICATION_CHUNK_SIZE + 1
+    }
+
+    /// Save a draft for a user on a board, replacing any draft with the same key.
+    /// At most MAX_DRAFTS are kept per user and board; saving another evicts the oldest.
+    pub fn save_draft(
+        env: Env,
+        board_id: u64,
+        key: Symbol,
+        title: String,
+        body: Bytes,
+        user: Address,
+    ) {
+        user.require_auth();
+        Self::store_draft(&env, board_id, key, title, body, &user);
+    }
+
+    /// Save the create-thread form as a draft
+    /// Note: Parameter order matches form field order
+    /// (board_id, title, flair_id, body, draft_key, caller)
+    pub fn save_thread_draft(
+        env: Env,
+        board_id: u64,
+        title: String,
+        _flair_id: Option<String>,
+        body: String,
+        draft_key: String,
+        caller: Address,
+    ) {
+        caller.require_auth();
+        let key = Self::draft_key_from_string(&env, &draft_key).expect("Invalid draft key");
+        let body = Self::draft_body_bytes(&env, &body);
+        Self::store_draft(&env, board_id, key, title, body, &caller);
+    }
+
+    /// Save the reply form as a draft, keyed by the thread and the post being replied to
+    /// Note: Parameter order matches form field order
+    /// (board_id, thread_id, parent_id, depth, content, caller)
+    pub fn save_reply_draft(
+        env: Env,
+        board_id: u64,
+        thread_id: u64,
+        parent_id: u64,
+        _depth: u32,
+        content: String,
+        caller: Address,
+    ) {
+        caller.require_auth();
+        let key = Self::reply_draft_key(&env, thread_id, parent_id);
+        let body = Self::draft_body_bytes(&env, &content);
+        Self::store_draft(
+            &env,
+            board_id,
+            key,
+            String::from_str(&env, ""),
+            body,
+            &caller,
+        );
+    }
+
+    /// Get a user's draft by key
+    pub fn get_draft(env: Env, board_id: u64, key: Symbol, user: Address) -> Option<Draft> {
+        let drafts: Vec<Draft> = env
+            .storage()
+            .persistent()
+            .get(&ContentKey::Drafts(board_id, user))
+            .unwrap_or(Vec::new(&env));
+        drafts.iter().find(|d| d.key == key)
+    }
+
+    /// Get a user's reply draft for a thread (parent_id 0 for top-level replies)
+    pub fn get_reply_draft(
+        env: Env,
+        board_id: u64,
+        thread_id: u64,
+        parent_id: u64,
+        user: Address,
+    ) -> Option<Draft> {
+        let key = Self::reply_draft_key(&env, thread_id, parent_id);
+        Self::get_draft(env, board_id, key, user)
+    }
+
+    /// Delete a user's draft (no-op if there is none)
+    pub fn delete_draft(env: Env, board_id: u64, key: Symbol, user: Address) {
+        user.require_auth();
+        Self::remove_draft(&env, board_id, &key, &user);
+    }
+
+    /// Helper: store a draft, moving a re-saved key to the newest slot
+    fn store_draft(
+        env: &Env,
+        board_id: u64,
+        key: Symbol,
+        title: String,
+        body: Bytes,
+        user: &Address,
+    ) {
+        if title.len() > MAX_DRAFT_TITLE_LEN {
+            panic!("Draft title is too long");
+        }
+        if body.len() > MAX_DRAFT_BODY_LEN {
+            panic!("Draft is too large");
+        }
+
+        let drafts_key = ContentKey::Drafts(board_id, user.clone());
+        let mut drafts: Vec<Draft> = env
+            .storage()
+            .persistent()
+            .get(&drafts_key)
+            .unwrap_or(Vec::new(env));
+        if let Some(i) = drafts.iter().position(|d| d.key == key) {
+            drafts.remove(i as u32);
+        }
+        while drafts.len() >= MAX_DRAFTS {
+            drafts.pop_front();
+        }
+        drafts.push_back(Draft {
+            key,
+            title,
+            body,
+            saved_at: env.ledger().timestamp(),
+        });
+        env.storage().persistent().set(&drafts_key, &drafts);
+    }
+
+    /// Helper: remove a draft without an auth check (callers have already authenticated)
+    fn remove_draft(env: &Env, board_id: u64, key: &Symbol, user: &Address) {
+        let drafts_key = ContentKey::Drafts(board_id, user.clone());
+        let mut drafts: Vec<Draft> = match env.storage().persistent().get(&drafts_key) {
+            Some(drafts) => drafts,
+            None => return,
+        };
+        let i = match drafts.iter().position(|d| &d.key == key) {
+            Some(i) => i as u32,
+            None => return,
+        };
+        drafts.remove(i);
+        if drafts.is_empty() {
+            env.storage().persistent().remove(&drafts_key);
+        } else {
+            env.storage().persistent().set(&drafts_key, &drafts);
+        }
+    }
+
+    /// Helper: convert a draft body submitted by a form to bytes
+    fn draft_body_bytes(env: &Env, body: &String) -> Bytes {
+        let len = body.len();
+        if len > MAX_DRAFT_BODY_LEN {
+            panic!("Draft is too large");
+        }
+        let mut temp = [0u8; MAX_DRAFT_BODY_LEN as usize];
+        body.copy_into_slice(&mut temp[..len as usize]);
+        Bytes::from_slice(env, &temp[..len as usize])
+    }
+
+    /// Helper: parse a draft key submitted by a form (1-32 of a-z, A-Z, 0-9, _)
+    fn draft_key_from_string(env: &Env, key: &String) -> Option<Symbol> {
+        let len = key.len() as usize;
+        if len == 0 || len > MAX_DRAFT_KEY_LEN {
+            return None;
+        }
+        let mut buf = [0u8; MAX_DRAFT_KEY_LEN];
+        key.copy_into_slice(&mut buf[..len]);
+        if !buf[..len]
+            .iter()
+            .all(|b| b.is_ascii_alphanumeric() || *b == b'_')
+        {
+            return None;
+        }
+        Some(Symbol::new(env, core::str::from_utf8(&buf[..len]).ok()?))
+    }
+
+    /// Helper: draft key for a reply form, "r" + thread_id + "_" + parent_id in base36
+    fn reply_draft_key(env: &Env, thread_id: u64, parent_id: u64) -> Symbol {
+        let mut buf = [0u8; MAX_DRAFT_KEY_LEN];
+        buf[0] = b'r';
+        let mut len = Self::write_base36(&mut buf, 1, thread_id);
+        buf[len] = b'_';
+        len = Self::write_base36(&mut buf, len + 1, parent_id);
+        Symbol::new(env, core::str::from_utf8(&buf[..len]).unwrap())
+    }
+
+    /// Helper: write n in base36 at buf[pos..], returning the end position (at most 13 digits)
+    fn write_base36(buf: &mut [u8], pos: usize, n: u64) -> usize {
+        let mut digits = [0u8; 13];
+        let mut count = 0;
+        let mut n = n;
+        loop {
+            let digit = (n % 36) as u8;
+            digits[count] = if digit < 10 {
+                b'0' + digit
+            } else {
+                b'a' + digit - 10
+            };
+            count += 1;
+            n /= 36;
+            if n == 0 {
+                break;
+            }
+        }
+        for i in 0..count {
+            buf[pos + i] = digits[count - 1 - i];
+        }
+        pos + count
+    }
+
     /// Upgrade the contract WASM
     pub fn upgrade(env: Env, new_wasm_hash: BytesN<32>) {
         let registry: Address = env
@@ -1740,102 +3735,1206 @@ impl BoardsContent {
         Self::counter_to_symbol(env, counter)
     }
 
-    /// Convert a counter to a short symbol (max 9 chars for short symbol)
-    /// Format: "c" + base36 encoded number
-    fn counter_to_symbol(env: &Env, counter: u64) -> Symbol {
-        // Use a fixed-size buffer for the symbol
-        // "c" prefix + up to 8 chars for base36 number = max 9 chars
-        let mut buf = [0u8; 9];
-        buf[0] = b'c';
+    /// Convert a counter to a short symbol (max 9 chars for short symbol)
+    /// Format: "c" + base36 encoded number
+    fn counter_to_symbol(env: &Env, counter: u64) -> Symbol {
+        // Use a fixed-size buffer for the symbol
+        // "c" prefix + up to 8 chars for base36 number = max 9 chars
+        let mut buf = [0u8; 9];
+        buf[0] = b'c';
+
+        // Encode counter in base36 (0-9, a-z)
+        let mut n = counter;
+        let mut pos = 8usize;
+
+        if n == 0 {
+            buf[1] = b'0';
+            return Symbol::new(env, core::str::from_utf8(&buf[0..2]).unwrap());
+        }
+
+        while n > 0 && pos > 0 {
+            let digit = (n % 36) as u8;
+            buf[pos] = if digit < 10 {
+                b'0' + digit
+            } else {
+                b'a' + digit - 10
+            };
+            n /= 36;
+            pos -= 1;
+        }
+
+        // Create symbol from the non-zero portion
+        let start = pos + 1;
+        buf[start - 1] = b'c';
+        Symbol::new(env, core::str::from_utf8(&buf[start - 1..9]).unwrap())
+    }
+
+    /// Add replies posted before the per-user index existed to it (moderator+),
+    /// `limit` reply IDs of a thread at a time from `start`. Safe to re-run.
+    pub fn index_user_replies(
+        env: Env,
+        board_id: u64,
+        thread_id: u64,
+        start: u64,
+        limit: u64,
+        caller: Address,
+    ) {
+        caller.require_auth();
+        Self::check_can_moderate(&env, board_id, &caller);
+
+        let end = start
+            .saturating_add(limit)
+            .min(Self::next_reply_id(&env, board_id, thread_id));
+        for reply_id in start..end {
+            if let Some(reply) = Self::get_reply(env.clone(), board_id, thread_id, reply_id) {
+                Self::index_user_reply(&env, &reply);
+            }
+        }
+    }
+
+    /// Insert a reply into its author's index by time, keeping the newest
+    /// MAX_USER_REPLIES. Already-indexed replies are left alone.
+    fn index_user_reply(env: &Env, reply: &ReplyMeta) {
+        let user_key = ContentKey::UserReplies(reply.board_id, reply.creator.clone());
+        let mut user_replies: Vec<(u64, u64, u64)> = env
+            .storage()
+            .persistent()
+            .get(&user_key)
+            .unwrap_or(Vec::new(env));
+        let entry = (reply.created_at, reply.thread_id, reply.id);
+        if user_replies.contains(&entry) {
+            return;
+        }
+        // New replies land at the end; backfilled ones walk back to their place
+        let mut pos = user_replies.len();
+        while pos > 0 && user_replies.get_unchecked(pos - 1).0 > reply.created_at {
+            pos -= 1;
+        }
+        if pos == 0 && user_replies.len() >= MAX_USER_REPLIES {
+            return;
+        }
+        user_replies.insert(pos, entry);
+        while user_replies.len() > MAX_USER_REPLIES {
+            user_replies.pop_front();
+        }
+        env.storage().persistent().set(&user_key, &user_replies);
+    }
+
+    /// Number of replies in a user's full reply log on a board
+    pub fn user_reply_log_len(env: Env, board_id: u64, user: Address) -> u64 {
+        env.storage()
+            .persistent()
+            .get(&ContentKey::UserReplyLogLen(board_id, user))
+            .unwrap_or(0)
+    }
+
+    /// Replies from a user's full reply log, `limit` entries from position `start`.
+    /// Unlike the recent-reply index the log is never trimmed, so moderation tools
+    /// can page through everything a user posted. Missing replies are skipped.
+    pub fn list_user_reply_log(
+        env: Env,
+        board_id: u64,
+        user: Address,
+        start: u64,
+        limit: u64,
+    ) -> Vec<ReplyMeta> {
+        let end = start.saturating_add(limit).min(Self::user_reply_log_len(
+            env.clone(),
+            board_id,
+            user.clone(),
+        ));
+        let mut replies = Vec::new(&env);
+        let mut pos = start;
+        while pos < end {
+            let chunk: Vec<(u64, u64)> = env
+                .storage()
+                .persistent()
+                .get(&ContentKey::UserReplyLog(
+                    board_id,
+                    user.clone(),
+                    pos / USER_LOG_CHUNK,
+                ))
+                .unwrap_or(Vec::new(&env));
+            let chunk_end = (pos / USER_LOG_CHUNK + 1) * USER_LOG_CHUNK;
+            for (thread_id, reply_id) in chunk
+                .iter()
+                .skip((pos % USER_LOG_CHUNK) as usize)
+                .take((chunk_end.min(end) - pos) as usize)
+            {
+                if let Some(reply) = Self::get_reply(env.clone(), board_id, thread_id, reply_id) {
+                    replies.push_back(reply);
+                }
+            }
+            pos = chunk_end;
+        }
+        replies
+    }
+
+    /// Add replies posted to a thread before the author logs existed to them
+    /// (moderator+), up to `limit` per call, continuing where the previous call
+    /// stopped. Returns how many older replies in the thread are still to be added.
+    pub fn log_user_replies(
+        env: Env,
+        board_id: u64,
+        thread_id: u64,
+        limit: u64,
+        caller: Address,
+    ) -> u64 {
+        caller.require_auth();
+        Self::check_can_moderate(&env, board_id, &caller);
+
+        // Replies from the first logged one on were logged when they were posted
+        let logged_from: u64 = env
+            .storage()
+            .persistent()
+            .get(&ContentKey::ReplyLogFrom(board_id, thread_id))
+            .unwrap_or_else(|| Self::next_reply_id(&env, board_id, thread_id));
+        let cursor_key = ContentKey::ReplyLogBackfilled(board_id, thread_id);
+        let start: u64 = env.storage().persistent().get(&cursor_key).unwrap_or(0);
+        let end = start.saturating_add(limit).min(logged_from).max(start);
+        for reply_id in start..end {
+            if let Some(reply) = Self::get_reply(env.clone(), board_id, thread_id, reply_id) {
+                Self::log_user_reply(&env, &reply);
+            }
+        }
+        env.storage().persistent().set(&cursor_key, &end);
+        logged_from.saturating_sub(end)
+    }
+
+    /// Append a reply to its author's full reply log
+    fn log_user_reply(env: &Env, reply: &ReplyMeta) {
+        let len_key = ContentKey::UserReplyLogLen(reply.board_id, reply.creator.clone());
+        let len: u64 = env.storage().persistent().get(&len_key).unwrap_or(0);
+        let chunk_key =
+            ContentKey::UserReplyLog(reply.board_id, reply.creator.clone(), len / USER_LOG_CHUNK);
+        let mut chunk: Vec<(u64, u64)> = env
+            .storage()
+            .persistent()
+            .get(&chunk_key)
+            .unwrap_or(Vec::new(env));
+        chunk.push_back((reply.thread_id, reply.id));
+        env.storage().persistent().set(&chunk_key, &chunk);
+        env.storage().persistent().set(&len_key, &(len + 1));
+    }
+
+    fn next_reply_id(env: &Env, board_id: u64, thread_id: u64) -> u64 {
+        env.storage()
+            .persistent()
+            .get(&ContentKey::NextReplyId(board_id, thread_id))
+            .unwrap_or(0)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use soroban_sdk::testutils::{Address as _, Ledger as _};
+    use soroban_sdk::Env;
+
+    /// Minimal registry that resolves the "board" alias
+    #[contract]
+    pub struct MockRegistry;
+
+    #[contractimpl]
+    impl MockRegistry {
+        pub fn set_board(env: Env, board: Address) {
+            env.storage()
+                .instance()
+                .set(&Symbol::new(&env, "board"), &board);
+        }
+
+        pub fn set_profile(env: Env, profile: Address) {
+            env.storage()
+                .instance()
+                .set(&Symbol::new(&env, "profile"), &profile);
+        }
+
+        pub fn set_admin(env: Env, admin: Address) {
+            env.storage()
+                .instance()
+                .set(&Symbol::new(&env, "admin"), &admin);
+        }
+
+        pub fn set_voting(env: Env, voting: Address) {
+            env.storage()
+                .instance()
+                .set(&Symbol::new(&env, "voting"), &voting);
+        }
+
+        pub fn get_contract_by_alias(env: Env, alias: Symbol) -> Option<Address> {
+            env.storage().instance().get(&alias)
+        }
+    }
+
+    /// Minimal profile contract that resolves registered usernames
+    #[contract]
+    pub struct MockProfile;
+
+    #[contractimpl]
+    impl MockProfile {
+        pub fn register(env: Env, username: String, user: Address) {
+            env.storage().instance().set(&username, &user);
+        }
+
+        pub fn get_address_by_username(env: Env, username: String) -> Option<Address> {
+            env.storage().instance().get(&username)
+        }
+    }
+
+    /// Minimal voting contract with fixed reply scores
+    #[contract]
+    pub struct MockVoting;
+
+    #[contractimpl]
+    impl MockVoting {
+        pub fn set_score(env: Env, reply_id: u64, score: i32) {
+            env.storage().instance().set(&reply_id, &score);
+        }
+
+        pub fn get_reply_tallies(
+            env: Env,
+            _board_id: u64,
+            _thread_id: u64,
+            reply_ids: Vec<u64>,
+        ) -> Vec<VoteTally> {
+            let mut tallies = Vec::new(&env);
+            for reply_id in reply_ids.iter() {
+                let score: i32 = env.storage().instance().get(&reply_id).unwrap_or(0);
+                tallies.push_back(VoteTally {
+                    upvotes: score.max(0) as u32,
+                    downvotes: 0,
+                    score,
+                    first_vote_at: 0,
+                });
+            }
+            tallies
+        }
+    }
+
+    /// Minimal board contract that reports a fixed reply rejection reason
+    #[contract]
+    pub struct MockBoard;
+
+    #[contractimpl]
+    impl MockBoard {
+        pub fn set_reason(env: Env, reason: Option<Symbol>) {
+            env.storage()
+                .instance()
+                .set(&Symbol::new(&env, "reason"), &reason);
+        }
+
+        pub fn reply_rejection_reason(env: Env, _board_id: u64, _thread_id: u64) -> Option<Symbol> {
+            env.storage()
+                .instance()
+                .get(&Symbol::new(&env, "reason"))
+                .unwrap_or(None)
+        }
+
+        pub fn get_thread_title_and_author(
+            env: Env,
+            _board_id: u64,
+            _thread_id: u64,
+        ) -> Option<(String, Address)> {
+            env.storage().instance().get(&Symbol::new(&env, "original"))
+        }
+
+        pub fn set_original(env: Env, title: String, author: Address) {
+            env.storage()
+                .instance()
+                .set(&Symbol::new(&env, "original"), &(title, author));
+        }
+
+        pub fn create_thread(env: Env, _board_id: u64, _title: String, _creator: Address) -> u64 {
+            let key = Symbol::new(&env, "next_id");
+            let id: u64 = env.storage().instance().get(&key).unwrap_or(0);
+            env.storage().instance().set(&key, &(id + 1));
+            id
+        }
+
+        pub fn remove_crosspost_thread(env: Env, board_id: u64, thread_id: u64) {
+            env.storage().instance().set(&(board_id, thread_id), &true);
+        }
+
+        pub fn is_deleted(env: Env, board_id: u64, thread_id: u64) -> bool {
+            env.storage()
+                .instance()
+                .get(&(board_id, thread_id))
+                .unwrap_or(false)
+        }
+
+        pub fn set_max_reply_bytes(env: Env, limit: u32) {
+            env.storage()
+                .instance()
+                .set(&Symbol::new(&env, "max_reply"), &limit);
+        }
+
+        pub fn get_max_reply_bytes(env: Env, _board_id: u64) -> u32 {
+            env.storage()
+                .instance()
+                .get(&Symbol::new(&env, "max_reply"))
+                .unwrap_or(DEFAULT_MAX_REPLY_BYTES)
+        }
+
+        pub fn set_image_policy(env: Env, policy: ImagePolicy) {
+            env.storage()
+                .instance()
+                .set(&Symbol::new(&env, "images"), &policy);
+        }
+
+        pub fn get_image_policy(env: Env, _board_id: u64) -> ImagePolicy {
+            env.storage()
+                .instance()
+                .get(&Symbol::new(&env, "images"))
+                .unwrap_or(ImagePolicy {
+                    allow_images: true,
+                    allowed_hosts: Vec::new(&env),
+                })
+        }
+
+        pub fn set_blocked_term(env: Env, term: Bytes) {
+            env.storage()
+                .instance()
+                .set(&Symbol::new(&env, "blocked"), &term);
+        }
+
+        /// Exact substring match on short posts; the board contract folds case
+        pub fn contains_blocked_term(env: Env, _board_id: u64, text: Bytes) -> bool {
+            let Some(term) = env
+                .storage()
+                .instance()
+                .get::<_, Bytes>(&Symbol::new(&env, "blocked"))
+            else {
+                return false;
+            };
+            let mut haystack = [0u8; 256];
+            let mut needle = [0u8; 64];
+            let (text_len, term_len) = (text.len() as usize, term.len() as usize);
+            text.copy_into_slice(&mut haystack[..text_len]);
+            term.copy_into_slice(&mut needle[..term_len]);
+            haystack[..text_len]
+                .windows(term_len)
+                .any(|candidate| candidate == &needle[..term_len])
+        }
+
+        pub fn set_reply_cooldown(env: Env, seconds: u64) {
+            env.storage()
+                .instance()
+                .set(&Symbol::new(&env, "cooldown"), &seconds);
+        }
+
+        pub fn get_reply_cooldown(env: Env, _board_id: u64) -> u64 {
+            env.storage()
+                .instance()
+                .get(&Symbol::new(&env, "cooldown"))
+                .unwrap_or(0)
+        }
+
+        pub fn set_karma_shortfall(env: Env, user: Address, shortfall: Option<(i64, i64)>) {
+            env.storage()
+                .instance()
+                .set(&(Symbol::new(&env, "karma"), user), &shortfall);
+        }
+
+        pub fn karma_shortfall(
+            env: Env,
+            _board_id: u64,
+            user: Address,
+            _is_reply: bool,
+        ) -> Option<(i64, i64)> {
+            env.storage()
+                .instance()
+                .get(&(Symbol::new(&env, "karma"), user))
+                .unwrap_or(None)
+        }
+    }
+
+    /// Minimal permissions contract that tracks quarantined users
+    #[contract]
+    pub struct MockPermissions;
+
+    #[contractimpl]
+    impl MockPermissions {
+        pub fn quarantine(env: Env, user: Address) {
+            env.storage().instance().set(&user, &true);
+        }
+
+        pub fn is_quarantined(env: Env, _board_id: u64, user: Address) -> bool {
+            env.storage().instance().get(&user).unwrap_or(false)
+        }
+
+        pub fn make_moderator(env: Env, board_id: u64, user: Address) {
+            env.storage().instance().set(&(board_id, user), &true);
+        }
+
+        pub fn can_moderate(env: Env, board_id: u64, user: Address) -> bool {
+            env.storage()
+                .instance()
+                .get(&(board_id, user))
+                .unwrap_or(false)
+        }
+    }
+
+    /// Content client wired to a mock board holding one original thread (board 0,
+    /// thread 0) by `author`, crossposted to board 1 by `crossposter`
+    fn setup_crosspost<'a>(
+        env: &'a Env,
+        author: &Address,
+        crossposter: &Address,
+    ) -> (
+        BoardsContentClient<'a>,
+        MockBoardClient<'a>,
+        MockPermissionsClient<'a>,
+        u64,
+    ) {
+        env.mock_all_auths();
+
+        let registry = MockRegistryClient::new(env, &env.register(MockRegistry, ()));
+        let board = MockBoardClient::new(env, &env.register(MockBoard, ()));
+        let perms = MockPermissionsClient::new(env, &env.register(MockPermissions, ()));
+        registry.set_board(&board.address);
+        board.set_original(&String::from_str(env, "Original"), author);
+
+        let client = BoardsContentClient::new(env, &env.register(BoardsContent, ()));
+        client.init(&registry.address, &Some(perms.address.clone()));
+
+        let comment = String::from_str(env, "");
+        let xpost_id = client.create_crosspost(&1, &0, &0, &comment, crossposter);
+        (client, board, perms, xpost_id)
+    }
+
+    #[test]
+    fn test_init_and_set_thread_body() {
+        let env = Env::default();
+        env.mock_all_auths();
+
+        let contract_id = env.register(BoardsContent, ());
+        let client = BoardsContentClient::new(&env, &contract_id);
+
+        let registry = Address::generate(&env);
+        // Pass None for permissions to skip permission checks in tests
+        client.init(&registry, &None);
+
+        let author = Address::generate(&env);
+        let content = Bytes::from_slice(&env, b"Hello, this is my first post!");
+
+        client.set_thread_body(&0, &0, &content, &author);
+
+        let retrieved = client.get_thread_body(&0, &0);
+        assert_eq!(retrieved, content);
+    }
+
+    #[test]
+    fn test_create_and_get_reply() {
+        let env = Env::default();
+        env.mock_all_auths();
+
+        let contract_id = env.register(BoardsContent, ());
+        let client = BoardsContentClient::new(&env, &contract_id);
+
+        let registry = Address::generate(&env);
+        // Pass None for permissions to skip permission checks in tests
+        client.init(&registry, &None);
+
+        let author = Address::generate(&env);
+        let content = String::from_str(&env, "This is a reply!");
+
+        let reply_id = client.create_reply(&0, &0, &0, &1, &content, &author);
+        assert_eq!(reply_id, 0);
+
+        let reply = client.get_reply(&0, &0, &reply_id).unwrap();
+        assert_eq!(reply.creator, author);
+        assert_eq!(reply.depth, 1);
+
+        let reply_content = client.get_reply_content(&0, &0, &reply_id);
+        let expected_bytes = Bytes::from_slice(&env, b"This is a reply!");
+        assert_eq!(reply_content, expected_bytes);
+
+        assert_eq!(client.get_reply_count(&0, &0), 1);
+    }
+
+    #[test]
+    fn test_reply_mentions_are_indexed() {
+        let env = Env::default();
+        env.mock_all_auths();
+
+        let client = BoardsContentClient::new(&env, &env.register(BoardsContent, ()));
+        let registry = MockRegistryClient::new(&env, &env.register(MockRegistry, ()));
+        let profile = MockProfileClient::new(&env, &env.register(MockProfile, ()));
+        registry.set_profile(&profile.address);
+        client.init(&registry.address, &None);
+
+        let alice = Address::generate(&env);
+        let bob = Address::generate(&env);
+        let carol = Address::generate(&env);
+        profile.register(&String::from_str(&env, "alice"), &alice);
+        profile.register(&String::from_str(&env, "bob"), &bob);
+        profile.register(&String::from_str(&env, "carol"), &carol);
+
+        let content = String::from_str(
+            &env,
+            "Thanks @alice (and @alice again), mail bob@example.com, @nobody, me: @carol",
+        );
+        let reply_id = client.create_reply(&2, &7, &0, &0, &content, &carol);
+
+        let mentions = client.list_mentions(&alice, &0, &10);
+        assert_eq!(mentions.len(), 1);
+        let mention = mentions.get(0).unwrap();
+        assert_eq!(mention.board_id, 2);
+        assert_eq!(mention.thread_id, 7);
+        assert_eq!(mention.reply_id, Some(reply_id));
+        assert_eq!(mention.author, carol);
+
+        // Email addresses and self-mentions are not recorded
+        assert_eq!(client.list_mentions(&bob, &0, &10).len(), 0);
+        assert_eq!(client.list_mentions(&carol, &0, &10).len(), 0);
+
+        // A deleted reply no longer lists its mentions
+        client.delete_reply(&2, &7, &reply_id, &carol);
+        assert_eq!(client.list_mentions(&alice, &0, &10).len(), 0);
+    }
+
+    #[test]
+    fn test_replies_notify_thread_and_parent_authors() {
+        let env = Env::default();
+        env.mock_all_auths();
+
+        let registry = MockRegistryClient::new(&env, &env.register(MockRegistry, ()));
+        let board = MockBoardClient::new(&env, &env.register(MockBoard, ()));
+        registry.set_board(&board.address);
+        let client = BoardsContentClient::new(&env, &env.register(BoardsContent, ()));
+        client.init(&registry.address, &None);
+
+        let op = Address::generate(&env);
+        let bob = Address::generate(&env);
+        let carol = Address::generate(&env);
+        board.set_original(&String::from_str(&env, "Topic"), &op);
+        let text = String::from_str(&env, "Reply");
+
+        let bob_reply = client.create_reply(&0, &0, &0, &0, &text, &bob);
+        // The thread author answering bob only notifies bob
+        client.create_reply(&0, &0, &bob_reply, &1, &text, &op);
+        let carol_reply = client.create_reply(&0, &0, &bob_reply, &1, &text, &carol);
+
+        let inbox = client.list_notifications(&op, &0, &10);
+        assert_eq!(inbox.len(), 2);
+        let newest = inbox.get(0).unwrap();
+        assert_eq!(newest.kind, Symbol::new(&env, "thread_reply"));
+        assert_eq!(newest.reply_id, Some(carol_reply));
+        assert_eq!(newest.actor, carol);
+
+        let inbox = client.list_notifications(&bob, &0, &10);
+        assert_eq!(inbox.len(), 2);
+        assert_eq!(inbox.get(0).unwrap().kind, Symbol::new(&env, "reply_reply"));
+        assert_eq!(inbox.get(1).unwrap().actor, op);
+
+        // Reading is tracked by ID and never goes backwards
+        assert_eq!(client.get_unread_count(&op), 2);
+        client.mark_read(&1, &op);
+        client.mark_read(&0, &op);
+        assert_eq!(client.get_unread_count(&op), 1);
+        client.mark_read(&99, &op);
+        assert_eq!(client.get_unread_count(&op), 0);
+    }
+
+    #[test]
+    fn test_notifications_are_capped() {
+        let env = Env::default();
+        env.mock_all_auths();
+
+        let registry = MockRegistryClient::new(&env, &env.register(MockRegistry, ()));
+        let client = BoardsContentClient::new(&env, &env.register(BoardsContent, ()));
+        client.init(&registry.address, &None);
+
+        let user = Address::generate(&env);
+        let moderator = Address::generate(&env);
+        let kind = Symbol::new(&env, "hidden");
+
+        // Only the registered admin contract may push notifications
+        assert!(client
+            .try_push_notification(&user, &kind, &0, &1, &None, &moderator)
+            .is_err());
+        registry.set_admin(&Address::generate(&env));
+
+        for thread_id in 1..=230u64 {
+            client.push_notification(&user, &kind, &0, &thread_id, &None, &moderator);
+        }
+
+        // Whole chunks of the oldest entries were dropped to stay within the cap
+        let inbox = client.list_notifications(&user, &0, &500);
+        assert_eq!(inbox.len(), 180);
+        assert_eq!(inbox.get(0).unwrap().id, 230);
+        assert_eq!(inbox.get(179).unwrap().thread_id, 51);
+        assert_eq!(client.get_unread_count(&user), 180);
+        assert_eq!(client.list_notifications(&user, &170, &20).len(), 10);
+    }
+
+    #[test]
+    fn test_find_mentions_is_bounded() {
+        let env = Env::default();
+        let body = Bytes::from_slice(&env, b"@a @b @c @a @d @e @f @g");
+        let names = BoardsContent::find_mentions(&env, &body);
+        assert_eq!(names.len(), MAX_MENTIONS_PER_POST);
+        assert_eq!(names.get(3).unwrap(), String::from_str(&env, "d"));
+    }
+
+    #[test]
+    fn test_edit_reply_keeps_bounded_revisions() {
+        let env = Env::default();
+        env.mock_all_auths();
+
+        let client = BoardsContentClient::new(&env, &env.register(BoardsContent, ()));
+        client.init(&Address::generate(&env), &None);
+
+        let author = Address::generate(&env);
+        let s = |v: &str| String::from_str(&env, v);
+        let reply_id = client.create_reply(&0, &0, &0, &0, &s("v1"), &author);
+        assert!(!client.is_edited(&0, &0, &Some(reply_id)));
+
+        // Saving an unchanged body does not create a revision
+        client.edit_reply_content(&0, &0, &reply_id, &s("v1"), &author);
+        assert!(!client.is_edited(&0, &0, &Some(reply_id)));
+
+        for body in ["v2", "v3", "v4", "v5"] {
+            client.edit_reply_content(&0, &0, &reply_id, &s(body), &author);
+        }
+
+        let revisions = client.list_revisions(&0, &0, &Some(reply_id));
+        assert_eq!(revisions.len(), MAX_REVISIONS);
+        assert_eq!(
+            revisions.get(0).unwrap().body,
+            Bytes::from_slice(&env, b"v4")
+        );
+        assert_eq!(
+            revisions.get(2).unwrap().body,
+            Bytes::from_slice(&env, b"v2")
+        );
+        assert_eq!(revisions.get(0).unwrap().editor, author);
+        assert_eq!(
+            client.get_reply_content(&0, &0, &reply_id),
+            Bytes::from_slice(&env, b"v5")
+        );
+
+        // Thread body history is kept separately
+        assert_eq!(client.list_revisions(&0, &0, &None).len(), 0);
+
+        let other = client.create_reply(&0, &0, &0, &0, &s("v1"), &author);
+        let edited = client.get_edited_replies(&0, &0, &Vec::from_array(&env, [other, reply_id]));
+        assert_eq!(edited, Vec::from_array(&env, [reply_id]));
+    }
+
+    #[test]
+    fn test_remove_crosspost_by_crossposter() {
+        let env = Env::default();
+        let author = Address::generate(&env);
+        let crossposter = Address::generate(&env);
+        let (client, board, _, xpost_id) = setup_crosspost(&env, &author, &crossposter);
+        assert_eq!(client.get_crosspost_count(&0, &0), 1);
+
+        // Unrelated users can't remove it
+        let stranger = Address::generate(&env);
+        assert_eq!(
+            client.try_remove_crosspost(&1, &xpost_id, &stranger),
+            Err(Ok(ContentError::NotAuthorized))
+        );
+
+        client.remove_crosspost(&1, &xpost_id, &crossposter);
+        assert!(board.is_deleted(&1, &xpost_id));
+        assert!(client.get_crosspost_ref(&1, &xpost_id).is_none());
+        assert_eq!(
+            client
+                .get_removed_crosspost(&1, &xpost_id)
+                .unwrap()
+                .removed_by,
+            crossposter
+        );
+        assert_eq!(client.get_crosspost_count(&0, &0), 0);
+        assert!(client.list_crossposts(&0, &0).is_empty());
+
+        // A removed crosspost can't be removed again
+        assert_eq!(
+            client.try_remove_crosspost(&1, &xpost_id, &crossposter),
+            Err(Ok(ContentError::NotFound))
+        );
+    }
+
+    #[test]
+    fn test_remove_crosspost_by_original_author_or_destination_mod() {
+        let env = Env::default();
+        let author = Address::generate(&env);
+        let crossposter = Address::generate(&env);
+        let (client, board, perms, first) = setup_crosspost(&env, &author, &crossposter);
+        let second = client.create_crosspost(&2, &0, &0, &String::from_str(&env, ""), &crossposter);
+        assert_eq!(client.get_crosspost_count(&0, &0), 2);
+
+        client.remove_crosspost(&1, &first, &author);
+        assert!(board.is_deleted(&1, &first));
+
+        // Moderating the origin board is not enough; it must be the destination
+        let moderator = Address::generate(&env);
+        perms.make_moderator(&0, &moderator);
+        assert_eq!(
+            client.try_remove_crosspost(&2, &second, &moderator),
+            Err(Ok(ContentError::NotAuthorized))
+        );
+        perms.make_moderator(&2, &moderator);
+        client.remove_crosspost(&2, &second, &moderator);
+
+        assert!(board.is_deleted(&2, &second));
+        assert_eq!(client.get_crosspost_count(&0, &0), 0);
+    }
+
+    #[test]
+    fn test_reply_size_limit_boundary() {
+        let env = Env::default();
+        env.mock_all_auths();
+
+        let client = BoardsContentClient::new(&env, &env.register(BoardsContent, ()));
+        client.init(&Address::generate(&env), &None);
+
+        let author = Address::generate(&env);
+        let limit = DEFAULT_MAX_REPLY_BYTES as usize;
+        let buf = [b'x'; DEFAULT_MAX_REPLY_BYTES as usize + 1];
+        let at_limit = String::from_bytes(&env, &buf[..limit]);
+        let over_limit = String::from_bytes(&env, &buf);
+
+        let reply_id = client.create_reply(&0, &0, &0, &0, &at_limit, &author);
+        assert_eq!(
+            client.try_create_reply(&0, &0, &0, &0, &over_limit, &author),
+            Err(Ok(ContentError::ReplyTooLong))
+        );
+
+        // Edits are checked too
+        client.edit_reply_content(&0, &0, &reply_id, &at_limit, &author);
+        assert_eq!(
+            client.try_edit_reply_content(&0, &0, &reply_id, &over_limit, &author),
+            Err(Ok(ContentError::ReplyTooLong))
+        );
+    }
+
+    #[test]
+    fn test_create_reply_rejects_insufficient_karma() {
+        let env = Env::default();
+        env.mock_all_auths();
+
+        let registry = MockRegistryClient::new(&env, &env.register(MockRegistry, ()));
+        let board = MockBoardClient::new(&env, &env.register(MockBoard, ()));
+        registry.set_board(&board.address);
+
+        let client = BoardsContentClient::new(&env, &env.register(BoardsContent, ()));
+        client.init(&registry.address, &None);
+
+        let newcomer = Address::generate(&env);
+        let regular = Address::generate(&env);
+        board.set_karma_shortfall(&newcomer, &Some((10, 3)));
+        let content = String::from_str(&env, "Reply");
+
+        assert_eq!(
+            client.try_create_reply(&0, &0, &0, &0, &content, &newcomer),
+            Err(Ok(ContentError::InsufficientKarma))
+        );
+        assert_eq!(
+            client.try_create_thread(
+                &0,
+                &String::from_str(&env, "Title"),
+                &None,
+                &String::from_str(&env, "Body"),
+                &None,
+                &newcomer
+            ),
+            Err(Ok(ContentError::InsufficientKarma))
+        );
+        assert_eq!(client.create_reply(&0, &0, &0, &0, &content, &regular), 0);
+        assert_eq!(client.get_reply_count(&0, &0), 1);
+    }
+
+    #[test]
+    fn test_blocked_terms_reject_posts_and_titles() {
+        let env = Env::default();
+        env.mock_all_auths();
+
+        let registry = MockRegistryClient::new(&env, &env.register(MockRegistry, ()));
+        let board = MockBoardClient::new(&env, &env.register(MockBoard, ()));
+        registry.set_board(&board.address);
+        board.set_blocked_term(&Bytes::from_slice(&env, b"scam.example"));
+
+        let client = BoardsContentClient::new(&env, &env.register(BoardsContent, ()));
+        client.init(&registry.address, &None);
+
+        let author = Address::generate(&env);
+        let s = |v: &str| String::from_str(&env, v);
+        assert_eq!(
+            client.try_create_reply(&0, &0, &0, &0, &s("Go to scam.example now"), &author),
+            Err(Ok(ContentError::BlockedTerm))
+        );
+        assert_eq!(client.get_reply_count(&0, &0), 0);
+        assert_eq!(
+            client.try_create_thread(
+                &0,
+                &s("Free coins at scam.example"),
+                &None,
+                &s("Body"),
+                &None,
+                &author
+            ),
+            Err(Ok(ContentError::BlockedTerm))
+        );
+
+        // Edits are checked too
+        client.create_reply(&0, &0, &0, &0, &s("Hello"), &author);
+        assert_eq!(
+            client.try_edit_reply_content(&0, &0, &0, &s("scam.example"), &author),
+            Err(Ok(ContentError::BlockedTerm))
+        );
+        assert!(client
+            .try_edit_thread_body(&0, &0, &Bytes::from_slice(&env, b"scam.example"), &author)
+            .is_err());
+        assert_eq!(
+            client.get_reply_content(&0, &0, &0),
+            Bytes::from_slice(&env, b"Hello")
+        );
+    }
+
+    #[test]
+    fn test_reply_cooldown_is_per_thread_and_exempts_moderators() {
+        let env = Env::default();
+        let author = Address::generate(&env);
+        let moderator = Address::generate(&env);
+        let (client, board, perms, _) = setup_crosspost(&env, &author, &moderator);
+        perms.make_moderator(&0, &moderator);
+        let s = |v: &str| String::from_str(&env, v);
+        env.ledger().set_timestamp(1_000);
+
+        // Without a cooldown nothing is tracked
+        client.create_reply(&0, &0, &0, &0, &s("First"), &author);
+        assert_eq!(client.get_last_reply_at(&0, &0, &author), None);
+
+        board.set_reply_cooldown(&60);
+        client.create_reply(&0, &0, &0, &0, &s("Second"), &author);
+        assert_eq!(client.get_last_reply_at(&0, &0, &author), Some(1_000));
+        env.ledger().set_timestamp(1_059);
+        assert_eq!(
+            client.try_create_reply(&0, &0, &0, &0, &s("Too soon"), &author),
+            Err(Ok(ContentError::ReplyCooldown))
+        );
+
+        // Other threads have their own window
+        client.create_reply(&0, &1, &0, &0, &s("Elsewhere"), &author);
+
+        // Once the window passes the reply goes through and the clock restarts
+        env.ledger().set_timestamp(1_060);
+        client.create_reply(&0, &0, &0, &0, &s("Third"), &author);
+        assert_eq!(client.get_last_reply_at(&0, &0, &author), Some(1_060));
+        env.ledger().set_timestamp(1_061);
+        assert_eq!(
+            client.try_create_reply(&0, &0, &0, &0, &s("Again"), &author),
+            Err(Ok(ContentError::ReplyCooldown))
+        );
+
+        // Moderators can reply back to back
+        client.create_reply(&0, &0, &0, &0, &s("Mod one"), &moderator);
+        client.create_reply(&0, &0, &0, &0, &s("Mod two"), &moderator);
+        assert_eq!(client.get_reply_count(&0, &0), 5);
+    }
+
+    #[test]
+    fn test_only_moderators_can_distinguish_their_posts() {
+        let env = Env::default();
+        let author = Address::generate(&env);
+        let moderator = Address::generate(&env);
+        let member = Address::generate(&env);
+        let (client, board, perms, _) = setup_crosspost(&env, &author, &moderator);
+        perms.make_moderator(&0, &moderator);
+        let s = |v: &str| String::from_str(&env, v);
+
+        let mod_reply = client.create_reply(&0, &0, &0, &0, &s("Official notice"), &moderator);
+        let member_reply = client.create_reply(&0, &0, &0, &0, &s("My opinion"), &member);
+
+        // A member can't mark their own post, nor can a moderator mark someone else's
+        assert_eq!(
+            client.try_distinguish(&0, &0, &Some(member_reply), &member),
+            Err(Ok(ContentError::NotAuthorized))
+        );
+        assert_eq!(
+            client.try_distinguish(&0, &0, &Some(member_reply), &moderator),
+            Err(Ok(ContentError::NotAuthorized))
+        );
+        assert!(!client.is_distinguished(&0, &0, &Some(member_reply)));
+
+        // Moderators mark their own posts and can take the mark off again
+        assert!(client.distinguish(&0, &0, &Some(mod_reply), &moderator));
+        assert!(client.is_distinguished(&0, &0, &Some(mod_reply)));
+        assert!(!client.is_distinguished(&0, &0, &None));
+        assert_eq!(
+            client.get_distinguished(&0, &0, &Vec::from_array(&env, [mod_reply, member_reply])),
+            Vec::from_array(&env, [mod_reply])
+        );
+        assert!(!client.distinguish(&0, &0, &Some(mod_reply), &moderator));
+        assert!(!client.is_distinguished(&0, &0, &Some(mod_reply)));
+
+        // Threads follow the same rule; the author needs the role when marking
+        assert_eq!(
+            client.try_distinguish(&0, &0, &None, &author),
+            Err(Ok(ContentError::NotAuthorized))
+        );
+        board.set_original(&s("Original"), &moderator);
+        assert!(client.distinguish(&0, &0, &None, &moderator));
+        assert!(client.is_distinguished(&0, &0, &None));
+        assert_eq!(
+            client.try_distinguish(&0, &0, &Some(99), &moderator),
+            Err(Ok(ContentError::NotFound))
+        );
+    }
+
+    #[test]
+    fn test_reply_size_limit_comes_from_board() {
+        let env = Env::default();
+        env.mock_all_auths();
+
+        let registry = MockRegistryClient::new(&env, &env.register(MockRegistry, ()));
+        let board = MockBoardClient::new(&env, &env.register(MockBoard, ()));
+        registry.set_board(&board.address);
+        board.set_max_reply_bytes(&100);
+
+        let client = BoardsContentClient::new(&env, &env.register(BoardsContent, ()));
+        client.init(&registry.address, &None);
+
+        let author = Address::generate(&env);
+        let buf = [b'x'; 101];
+        client.create_reply(
+            &0,
+            &0,
+            &0,
+            &0,
+            &String::from_bytes(&env, &buf[..100]),
+            &author,
+        );
+        assert_eq!(
+            client.try_create_reply(&0, &0, &0, &0, &String::from_bytes(&env, &buf), &author),
+            Err(Ok(ContentError::ReplyTooLong))
+        );
+    }
+
+    #[test]
+    fn test_images_outside_the_allowlist_become_links() {
+        let env = Env::default();
+        env.mock_all_auths();
+
+        let registry = MockRegistryClient::new(&env, &env.register(MockRegistry, ()));
+        let board = MockBoardClient::new(&env, &env.register(MockBoard, ()));
+        registry.set_board(&board.address);
+        board.set_image_policy(&ImagePolicy {
+            allow_images: true,
+            allowed_hosts: Vec::from_array(&env, [String::from_str(&env, "i.example.com")]),
+        });
+
+        let client = BoardsContentClient::new(&env, &env.register(BoardsContent, ()));
+        client.init(&registry.address, &None);
+
+        let author = Address::generate(&env);
+        let s = |v: &str| String::from_str(&env, v);
+        let b = |v: &str| Bytes::from_slice(&env, v.as_bytes());
+
+        // Allowlisted hosts are kept, whatever the case or port
+        let allowed =
+            "![cat](https://I.Example.com:443/cat.png) <img src=\"//i.example.com/a.gif\">";
+        client.create_reply(&0, &0, &0, &0, &s(allowed), &author);
+        assert_eq!(client.get_reply_content(&0, &0, &0), b(allowed));
+
+        // Other hosts, look-alike hosts and relative URLs become plain links
+        client.create_reply(
+            &0,
+            &0,
+            &0,
+            &0,
+            &s(
+                "a ![cat](https://evil.com/c.png) b ![](http://i.example.com.evil.com/x) \
+                <IMG alt='x' SRC='/local.png' /> c <img alt=\"none\">",
+            ),
+            &author,
+        );
+        assert_eq!(
+            client.get_reply_content(&0, &0, &1),
+            b(
+                "a [cat](https://evil.com/c.png) b [image](http://i.example.com.evil.com/x) \
+                [image](/local.png) c "
+            )
+        );
+
+        // Edits are checked against the policy again
+        client.edit_reply_content(&0, &0, &0, &s("![new](https://evil.com/n.png)"), &author);
+        assert_eq!(
+            client.get_reply_content(&0, &0, &0),
+            b("[new](https://evil.com/n.png)")
+        );
+
+        // With images turned off, allowlisted hosts become links too
+        board.set_image_policy(&ImagePolicy {
+            allow_images: false,
+            allowed_hosts: Vec::from_array(&env, [String::from_str(&env, "i.example.com")]),
+        });
+        client.edit_reply_content(&0, &0, &0, &s(allowed), &author);
+        assert_eq!(
+            client.get_reply_content(&0, &0, &0),
+            b("[cat](https://I.Example.com:443/cat.png) [image](//i.example.com/a.gif)")
+        );
+    }
+
+    #[test]
+    fn test_reference_and_nested_images_follow_the_policy() {
+        let env = Env::default();
+        env.mock_all_auths();
 
-        // Encode counter in base36 (0-9, a-z)
-        let mut n = counter;
-        let mut pos = 8usize;
+        let registry = MockRegistryClient::new(&env, &env.register(MockRegistry, ()));
+        let board = MockBoardClient::new(&env, &env.register(MockBoard, ()));
+        registry.set_board(&board.address);
+        board.set_image_policy(&ImagePolicy {
+            allow_images: true,
+            allowed_hosts: Vec::from_array(&env, [String::from_str(&env, "i.example.com")]),
+        });
 
-        if n == 0 {
-            buf[1] = b'0';
-            return Symbol::new(env, core::str::from_utf8(&buf[0..2]).unwrap());
-        }
+        let client = BoardsContentClient::new(&env, &env.register(BoardsContent, ()));
+        client.init(&registry.address, &None);
 
-        while n > 0 && pos > 0 {
-            let digit = (n % 36) as u8;
-            buf[pos] = if digit < 10 {
-                b'0' + digit
-            } else {
-                b'a' + digit - 10
-            };
-            n /= 36;
-            pos -= 1;
-        }
+        let author = Address::generate(&env);
+        let s = |v: &str| String::from_str(&env, v);
+        let b = |v: &str| Bytes::from_slice(&env, v.as_bytes());
+
+        // Brackets in the alt text don't hide the URL
+        client.create_reply(
+            &0,
+            &0,
+            &0,
+            &0,
+            &s("![a [b] c](https://evil.com/x.png) ![ok [b]](https://i.example.com/y.png)"),
+            &author,
+        );
+        assert_eq!(
+            client.get_reply_content(&0, &0, &0),
+            b("[a [b] c](https://evil.com/x.png) ![ok [b]](https://i.example.com/y.png)")
+        );
 
-        // Create symbol from the non-zero portion
-        let start = pos + 1;
-        buf[start - 1] = b'c';
-        Symbol::new(env, core::str::from_utf8(&buf[start - 1..9]).unwrap())
-    }
+        // Reference images are checked against their definitions
+        let allowed = "![x][Good  Ref] ![good ref][]\n\n[good ref]: <https://i.example.com/g.png>";
+        client.create_reply(&0, &0, &0, &0, &s(allowed), &author);
+        assert_eq!(client.get_reply_content(&0, &0, &1), b(allowed));
+        client.create_reply(
+            &0,
+            &0,
+            &0,
+            &0,
+            &s("![x][r] ![r][] ![r] ![y][missing]\n\n[r]:\n  https://evil.com/x.png"),
+            &author,
+        );
+        assert_eq!(
+            client.get_reply_content(&0, &0, &2),
+            b("[x][r] [r][] [r] [y][missing]\n\n[r]:\n  https://evil.com/x.png")
+        );
 
-    fn next_reply_id(env: &Env, board_id: u64, thread_id: u64) -> u64 {
-        env.storage()
-            .persistent()
-            .get(&ContentKey::NextReplyId(board_id, thread_id))
-            .unwrap_or(0)
+        // A second definition for the same label can't slip through, even inside a quote
+        client.create_reply(
+            &0,
+            &0,
+            &0,
+            &0,
+            &s("![q][ok]\n\n> [OK]: https://evil.com/q.png\n\n[ok]: https://i.example.com/q.png"),
+            &author,
+        );
+        assert_eq!(
+            client.get_reply_content(&0, &0, &3),
+            b("[q][ok]\n\n> [OK]: https://evil.com/q.png\n\n[ok]: https://i.example.com/q.png")
+        );
     }
-}
 
-#[cfg(test)]
-mod test {
-    use super::*;
-    use soroban_sdk::testutils::Address as _;
-    use soroban_sdk::Env;
+    #[test]
+    #[should_panic(expected = "Error(Contract, #9)")]
+    fn test_edit_thread_body_over_limit_rejected() {
+        let env = Env::default();
+        env.mock_all_auths();
+
+        let client = BoardsContentClient::new(&env, &env.register(BoardsContent, ()));
+        client.init(&Address::generate(&env), &None);
+
+        let author = Address::generate(&env);
+        let buf = [b'x'; DEFAULT_MAX_THREAD_BODY_BYTES as usize + 1];
+        let limit = DEFAULT_MAX_THREAD_BODY_BYTES as usize;
+        client.edit_thread_body(&0, &0, &Bytes::from_slice(&env, &buf[..limit]), &author);
+        client.edit_thread_body(&0, &0, &Bytes::from_slice(&env, &buf), &author);
+    }
 
     #[test]
-    fn test_init_and_set_thread_body() {
+    fn test_drafts_evict_oldest_beyond_cap() {
         let env = Env::default();
         env.mock_all_auths();
 
-        let contract_id = env.register(BoardsContent, ());
-        let client = BoardsContentClient::new(&env, &contract_id);
+        let client = BoardsContentClient::new(&env, &env.register(BoardsContent, ()));
+        client.init(&Address::generate(&env), &None);
 
-        let registry = Address::generate(&env);
-        // Pass None for permissions to skip permission checks in tests
-        client.init(&registry, &None);
+        let user = Address::generate(&env);
+        let s = |v: &str| String::from_str(&env, v);
+        let b = |v: &str| Bytes::from_slice(&env, v.as_bytes());
+        let key = |v: &str| Symbol::new(&env, v);
 
-        let author = Address::generate(&env);
-        let content = Bytes::from_slice(&env, b"Hello, this is my first post!");
+        client.save_draft(&0, &key("a"), &s("A"), &b("first"), &user);
+        client.save_draft(&0, &key("b"), &s("B"), &b("second"), &user);
+        client.save_draft(&0, &key("c"), &s("C"), &b("third"), &user);
 
-        client.set_thread_body(&0, &0, &content, &author);
+        // Re-saving "a" makes it the newest, so "b" is evicted next
+        client.save_draft(&0, &key("a"), &s("A"), &b("first, revised"), &user);
+        client.save_draft(&0, &key("d"), &s("D"), &b("fourth"), &user);
 
-        let retrieved = client.get_thread_body(&0, &0);
-        assert_eq!(retrieved, content);
+        assert!(client.get_draft(&0, &key("b"), &user).is_none());
+        assert_eq!(
+            client.get_draft(&0, &key("a"), &user).unwrap().body,
+            b("first, revised")
+        );
+        assert!(client.get_draft(&0, &key("c"), &user).is_some());
+        assert!(client.get_draft(&0, &key("d"), &user).is_some());
+
+        // Drafts are per board and per user
+        assert!(client.get_draft(&1, &key("a"), &user).is_none());
+        assert!(client
+            .get_draft(&0, &key("a"), &Address::generate(&env))
+            .is_none());
+
+        client.delete_draft(&0, &key("a"), &user);
+        assert!(client.get_draft(&0, &key("a"), &user).is_none());
     }
 
     #[test]
-    fn test_create_and_get_reply() {
+    fn test_posting_reply_deletes_its_draft() {
         let env = Env::default();
         env.mock_all_auths();
 
-        let contract_id = env.register(BoardsContent, ());
-        let client = BoardsContentClient::new(&env, &contract_id);
-
-        let registry = Address::generate(&env);
-        // Pass None for permissions to skip permission checks in tests
-        client.init(&registry, &None);
+        let client = BoardsContentClient::new(&env, &env.register(BoardsContent, ()));
+        client.init(&Address::generate(&env), &None);
 
-        let author = Address::generate(&env);
-        let content = String::from_str(&env, "This is a reply!");
+        let user = Address::generate(&env);
+        let text = String::from_str(&env, "half-written reply");
+        client.save_reply_draft(&0, &7, &0, &0, &text, &user);
+        assert_eq!(
+            client.get_reply_draft(&0, &7, &0, &user).unwrap().body,
+            Bytes::from_slice(&env, b"half-written reply")
+        );
+        // Drafts for other threads are separate
+        assert!(client.get_reply_draft(&0, &8, &0, &user).is_none());
 
-        let reply_id = client.create_reply(&0, &0, &0, &1, &content, &author);
-        assert_eq!(reply_id, 0);
+        client.create_reply(&0, &7, &0, &0, &text, &user);
+        assert!(client.get_reply_draft(&0, &7, &0, &user).is_none());
+    }
 
-        let reply = client.get_reply(&0, &0, &reply_id).unwrap();
-        assert_eq!(reply.creator, author);
-        assert_eq!(reply.depth, 1);
+    #[test]
+    #[should_panic(expected = "Draft is too large")]
+    fn test_save_draft_rejects_oversized_body() {
+        let env = Env::default();
+        env.mock_all_auths();
 
-        let reply_content = client.get_reply_content(&0, &0, &reply_id);
-        let expected_bytes = Bytes::from_slice(&env, b"This is a reply!");
-        assert_eq!(reply_content, expected_bytes);
+        let client = BoardsContentClient::new(&env, &env.register(BoardsContent, ()));
+        client.init(&Address::generate(&env), &None);
 
-        assert_eq!(client.get_reply_count(&0, &0), 1);
+        let body = Bytes::from_slice(&env, &[b'x'; MAX_DRAFT_BODY_LEN as usize + 1]);
+        let title = String::from_str(&env, "Big");
+        let user = Address::generate(&env);
+        client.save_draft(&0, &Symbol::new(&env, "thread"), &title, &body, &user);
     }
 
     #[test]
@@ -1887,8 +4986,8 @@ mod test {
         let reply_id = client.create_reply(&0, &0, &0, &1, &content, &author);
 
         // Flag
-        let reason = String::from_str(&env, "Spam");
-        client.flag_reply(&0, &0, &reply_id, &reason, &flagger);
+        let reason = String::from_str(&env, "spam");
+        client.flag_reply(&0, &0, &reply_id, &reason, &None, &flagger);
 
         let reply = client.get_reply(&0, &0, &reply_id).unwrap();
         assert_eq!(reply.flag_count, 1);
@@ -1914,13 +5013,17 @@ mod test {
         let flagger2 = Address::generate(&env);
 
         // Flag thread
-        let reason = String::from_str(&env, "Inappropriate");
-        client.flag_thread(&0, &0, &reason, &flagger1);
+        let reason = String::from_str(&env, "abuse");
+        client.flag_thread(&0, &0, &reason, &None, &flagger1);
         assert_eq!(client.get_thread_flag_count(&0, &0), 1);
 
         // Another user flags
-        client.flag_thread(&0, &0, &reason, &flagger2);
+        client.flag_thread(&0, &0, &reason, &None, &flagger2);
         assert_eq!(client.get_thread_flag_count(&0, &0), 2);
+        assert_eq!(
+            client.get_thread_flag_counts(&0, &Vec::from_array(&env, [0, 1])),
+            Vec::from_array(&env, [2, 0])
+        );
 
         // Check flagged content list
         let flagged = client.list_flagged_content(&0);
@@ -1928,6 +5031,40 @@ mod test {
         assert_eq!(flagged.get(0).unwrap().flag_count, 2);
     }
 
+    #[test]
+    fn test_list_flags_skips_resolved() {
+        let env = Env::default();
+        env.mock_all_auths();
+
+        let contract_id = env.register(BoardsContent, ());
+        let client = BoardsContentClient::new(&env, &contract_id);
+
+        let registry = Address::generate(&env);
+        client.init(&registry, &None);
+
+        let moderator = Address::generate(&env);
+        let flagger1 = Address::generate(&env);
+        let flagger2 = Address::generate(&env);
+
+        let reason = String::from_str(&env, "off-topic");
+        client.flag_thread(&0, &0, &reason, &None, &flagger1);
+
+        let flags = client.list_flags(&0, &0, &None);
+        assert_eq!(flags.len(), 1);
+        let record = flags.get(0).unwrap();
+        assert_eq!(record.flagger, flagger1);
+        assert_eq!(record.reason, FlagReason::OffTopic);
+
+        // Cleared flags drop out; a new flag shows up on its own
+        client.clear_thread_flags(&0, &0, &moderator);
+        assert_eq!(client.list_flags(&0, &0, &None).len(), 0);
+
+        client.flag_thread(&0, &0, &String::from_str(&env, "spam"), &None, &flagger2);
+        let flags = client.list_flags(&0, &0, &None);
+        assert_eq!(flags.len(), 1);
+        assert_eq!(flags.get(0).unwrap().flagger, flagger2);
+    }
+
     #[test]
     fn test_auto_hide_on_threshold() {
         let env = Env::default();
@@ -1947,8 +5084,8 @@ mod test {
         // Flag 3 times (default threshold)
         for _ in 0..3 {
             let flagger = Address::generate(&env);
-            let reason = String::from_str(&env, "Bad");
-            client.flag_reply(&0, &0, &reply_id, &reason, &flagger);
+            let reason = String::from_str(&env, "spam");
+            client.flag_reply(&0, &0, &reply_id, &reason, &None, &flagger);
         }
 
         // Should be auto-hidden
@@ -2088,8 +5225,7 @@ mod test {
     }
 
     #[test]
-    #[should_panic(expected = "Already flagged by this user")]
-    fn test_double_flag_prevented() {
+    fn test_double_flag_ignored() {
         let env = Env::default();
         env.mock_all_auths();
 
@@ -2105,11 +5241,84 @@ mod test {
         let reply_id = client.create_reply(&0, &0, &0, &1, &content, &author);
 
         // Flag once
-        let reason = String::from_str(&env, "Spam");
-        client.flag_reply(&0, &0, &reply_id, &reason, &flagger);
+        let reason = String::from_str(&env, "spam");
+        client.flag_reply(&0, &0, &reply_id, &reason, &None, &flagger);
+
+        // Flagging again is ignored and doesn't count towards auto-hide
+        client.flag_reply(&0, &0, &reply_id, &reason, &None, &flagger);
+        client.flag_reply(&0, &0, &reply_id, &reason, &None, &flagger);
+        let reply = client.get_reply(&0, &0, &reply_id).unwrap();
+        assert_eq!(reply.flag_count, 1);
+        assert!(!reply.is_hidden);
+        assert_eq!(client.get_reply_flags(&0, &0, &reply_id).len(), 1);
+    }
+
+    #[test]
+    fn test_unflag_reply_decrements_count() {
+        let env = Env::default();
+        env.mock_all_auths();
+
+        let contract_id = env.register(BoardsContent, ());
+        let client = BoardsContentClient::new(&env, &contract_id);
+        client.init(&Address::generate(&env), &None);
+
+        let author = Address::generate(&env);
+        let content = String::from_str(&env, "Content");
+        let reply_id = client.create_reply(&0, &0, &0, &1, &content, &author);
+
+        let flagger1 = Address::generate(&env);
+        let flagger2 = Address::generate(&env);
+        let details = Some(String::from_str(&env, "Links to a phishing site"));
+        client.flag_reply(
+            &0,
+            &0,
+            &reply_id,
+            &String::from_str(&env, "spam"),
+            &details,
+            &flagger1,
+        );
+        client.flag_reply(
+            &0,
+            &0,
+            &reply_id,
+            &String::from_str(&env, "abuse"),
+            &None,
+            &flagger2,
+        );
+
+        let flags = client.list_flags(&0, &0, &Some(reply_id));
+        assert_eq!(flags.get(0).unwrap().reason, FlagReason::Spam);
+        assert_eq!(flags.get(0).unwrap().details, details.unwrap());
+        assert_eq!(flags.get(1).unwrap().reason, FlagReason::Abuse);
+
+        client.unflag_reply(&0, &0, &reply_id, &flagger1);
+        assert_eq!(client.get_reply(&0, &0, &reply_id).unwrap().flag_count, 1);
+        assert_eq!(
+            client.list_flagged_content(&0).get(0).unwrap().flag_count,
+            1
+        );
+        let flags = client.list_flags(&0, &0, &Some(reply_id));
+        assert_eq!(flags.len(), 1);
+        assert_eq!(flags.get(0).unwrap().flagger, flagger2);
+
+        // The last retraction takes the reply out of the moderation queue
+        client.unflag_reply(&0, &0, &reply_id, &flagger2);
+        assert_eq!(client.get_reply(&0, &0, &reply_id).unwrap().flag_count, 0);
+        assert_eq!(client.list_flagged_content(&0).len(), 0);
+    }
+
+    #[test]
+    #[should_panic(expected = "Invalid flag reason")]
+    fn test_flag_rejects_unknown_reason() {
+        let env = Env::default();
+        env.mock_all_auths();
+
+        let contract_id = env.register(BoardsContent, ());
+        let client = BoardsContentClient::new(&env, &contract_id);
+        client.init(&Address::generate(&env), &None);
 
-        // Try to flag again - should panic
-        client.flag_reply(&0, &0, &reply_id, &reason, &flagger);
+        let reason = String::from_str(&env, "i just don't like it");
+        client.flag_thread(&0, &0, &reason, &None, &Address::generate(&env));
     }
 
     #[test]
@@ -2176,6 +5385,16 @@ mod test {
         for i in 0..children.len() {
             assert_eq!(children.get(i).unwrap().parent_id, parent_id);
         }
+
+        // Batched lookups follow the order given
+        let ids = Vec::from_array(&env, [children.get(1).unwrap().id, parent_id]);
+        assert_eq!(
+            client.get_children_counts(&0, &0, &ids),
+            Vec::from_array(&env, [0, 3])
+        );
+        let bodies = client.get_reply_contents(&0, &0, &ids);
+        assert_eq!(bodies.get(0).unwrap(), Bytes::from_slice(&env, b"Child 1"));
+        assert_eq!(bodies.get(1).unwrap(), Bytes::from_slice(&env, b"Parent"));
     }
 
     #[test]
@@ -2214,18 +5433,18 @@ mod test {
 
         // Flag reply1 once
         let flagger1 = Address::generate(&env);
-        let reason = String::from_str(&env, "Spam");
-        client.flag_reply(&0, &0, &reply1, &reason, &flagger1);
+        let reason = String::from_str(&env, "spam");
+        client.flag_reply(&0, &0, &reply1, &reason, &None, &flagger1);
 
         // Flag reply2 twice
         let flagger2 = Address::generate(&env);
         let flagger3 = Address::generate(&env);
-        client.flag_reply(&0, &0, &reply2, &reason, &flagger2);
-        client.flag_reply(&0, &0, &reply2, &reason, &flagger3);
+        client.flag_reply(&0, &0, &reply2, &reason, &None, &flagger2);
+        client.flag_reply(&0, &0, &reply2, &reason, &None, &flagger3);
 
         // Flag a thread
         let thread_flagger = Address::generate(&env);
-        client.flag_thread(&0, &0, &reason, &thread_flagger);
+        client.flag_thread(&0, &0, &reason, &None, &thread_flagger);
 
         // List all flagged content
         let flagged = client.list_flagged_content(&0);
@@ -2255,4 +5474,239 @@ mod test {
         client.edit_thread_body(&0, &0, &updated, &author);
         assert_eq!(client.get_thread_body(&0, &0), updated);
     }
+
+    #[test]
+    fn test_create_reply_respects_board_rejection_reason() {
+        let env = Env::default();
+        env.mock_all_auths();
+
+        let board_id = env.register(MockBoard, ());
+        let board = MockBoardClient::new(&env, &board_id);
+        let registry_id = env.register(MockRegistry, ());
+        MockRegistryClient::new(&env, &registry_id).set_board(&board_id);
+
+        let contract_id = env.register(BoardsContent, ());
+        let client = BoardsContentClient::new(&env, &contract_id);
+        client.init(&registry_id, &None);
+
+        let author = Address::generate(&env);
+        let content = String::from_str(&env, "Reply");
+
+        // Open thread accepts replies
+        assert_eq!(client.create_reply(&0, &0, &0, &0, &content, &author), 0);
+
+        board.set_reason(&Some(Symbol::new(&env, "locked")));
+        assert_eq!(
+            client.try_create_reply(&0, &0, &0, &0, &content, &author),
+            Err(Ok(ContentError::ThreadLocked))
+        );
+
+        board.set_reason(&Some(Symbol::new(&env, "read_only")));
+        assert_eq!(
+            client.try_create_reply(&0, &0, &0, &0, &content, &author),
+            Err(Ok(ContentError::BoardReadOnly))
+        );
+
+        board.set_reason(&Some(Symbol::new(&env, "deleted")));
+        assert_eq!(
+            client.try_create_reply(&0, &0, &0, &0, &content, &author),
+            Err(Ok(ContentError::NotFound))
+        );
+
+        // Nothing was persisted for rejected replies
+        assert_eq!(client.get_reply_count(&0, &0), 1);
+    }
+
+    #[test]
+    fn test_index_user_replies_backfills_in_time_order() {
+        let env = Env::default();
+        env.mock_all_auths();
+
+        let contract_id = env.register(BoardsContent, ());
+        let client = BoardsContentClient::new(&env, &contract_id);
+        client.init(&Address::generate(&env), &None);
+
+        let author = Address::generate(&env);
+        let content = String::from_str(&env, "Reply");
+        env.ledger().set_timestamp(100);
+        client.create_reply(&0, &0, &0, &0, &content, &author);
+        env.ledger().set_timestamp(200);
+        client.create_reply(&0, &1, &0, &0, &content, &author);
+        env.ledger().set_timestamp(300);
+        client.create_reply(&0, &0, &0, &0, &content, &author);
+
+        // Replies posted before the index existed
+        env.as_contract(&contract_id, || {
+            env.storage()
+                .persistent()
+                .remove(&ContentKey::UserReplies(0, author.clone()));
+        });
+        assert_eq!(client.list_user_replies(&0, &author, &10).len(), 0);
+
+        // Thread order doesn't matter, and re-running adds nothing
+        let moderator = Address::generate(&env);
+        client.index_user_replies(&0, &1, &0, &10, &moderator);
+        client.index_user_replies(&0, &0, &0, &1, &moderator);
+        client.index_user_replies(&0, &0, &1, &1, &moderator);
+        client.index_user_replies(&0, &0, &0, &10, &moderator);
+        let replies = client.list_user_replies(&0, &author, &10);
+        assert_eq!(replies.len(), 3);
+        for (i, (thread_id, reply_id)) in [(0, 1), (1, 0), (0, 0)].into_iter().enumerate() {
+            let reply = replies.get(i as u32).unwrap();
+            assert_eq!((reply.thread_id, reply.id), (thread_id, reply_id));
+        }
+    }
+
+    #[test]
+    fn test_user_reply_log_keeps_every_reply_and_backfills_older_ones() {
+        let env = Env::default();
+        env.mock_all_auths();
+
+        let contract_id = env.register(BoardsContent, ());
+        let client = BoardsContentClient::new(&env, &contract_id);
+        client.init(&Address::generate(&env), &None);
+
+        let author = Address::generate(&env);
+        let content = String::from_str(&env, "Reply");
+        client.create_reply(&0, &0, &0, &0, &content, &author);
+        client.create_reply(&0, &0, &0, &0, &content, &author);
+
+        // Replies posted before the log existed
+        env.as_contract(&contract_id, || {
+            let storage = env.storage().persistent();
+            storage.remove(&ContentKey::UserReplyLog(0, author.clone(), 0));
+            storage.remove(&ContentKey::UserReplyLogLen(0, author.clone()));
+            storage.remove(&ContentKey::ReplyLogFrom(0, 0));
+        });
+        client.create_reply(&0, &0, &0, &0, &content, &author);
+        assert_eq!(client.user_reply_log_len(&0, &author), 1);
+
+        // The backfill resumes where it stopped and adds nothing once done
+        let moderator = Address::generate(&env);
+        assert_eq!(client.log_user_replies(&0, &0, &1, &moderator), 1);
+        assert_eq!(client.log_user_replies(&0, &0, &10, &moderator), 0);
+        assert_eq!(client.log_user_replies(&0, &0, &10, &moderator), 0);
+        let replies = client.list_user_reply_log(&0, &author, &0, &10);
+        assert_eq!(replies.len(), 3);
+        for (i, reply_id) in [2u64, 0, 1].into_iter().enumerate() {
+            assert_eq!(replies.get(i as u32).unwrap().id, reply_id);
+        }
+
+        // The log is not trimmed, and windows can span chunks
+        for _ in 0..USER_LOG_CHUNK {
+            client.create_reply(&0, &1, &0, &0, &content, &author);
+        }
+        assert_eq!(client.user_reply_log_len(&0, &author), USER_LOG_CHUNK + 3);
+        let window = client.list_user_reply_log(&0, &author, &49, &3);
+        assert_eq!(window.len(), 3);
+        for (i, reply_id) in [46u64, 47, 48].into_iter().enumerate() {
+            let reply = window.get(i as u32).unwrap();
+            assert_eq!((reply.thread_id, reply.id), (1, reply_id));
+        }
+        assert_eq!(
+            client
+                .list_user_reply_log(&0, &author, &(USER_LOG_CHUNK + 2), &10)
+                .len(),
+            1
+        );
+    }
+
+    #[test]
+    fn test_quarantined_author_reply_starts_hidden() {
+        let env = Env::default();
+        env.mock_all_auths();
+
+        let perms_id = env.register(MockPermissions, ());
+        let perms = MockPermissionsClient::new(&env, &perms_id);
+
+        let contract_id = env.register(BoardsContent, ());
+        let client = BoardsContentClient::new(&env, &contract_id);
+        client.init(&Address::generate(&env), &Some(perms_id));
+
+        let quarantined = Address::generate(&env);
+        let regular = Address::generate(&env);
+        perms.quarantine(&quarantined);
+        let content = String::from_str(&env, "Reply");
+
+        let hidden_id = client.create_reply(&0, &0, &0, &0, &content, &quarantined);
+        let visible_id = client.create_reply(&0, &0, &0, &0, &content, &regular);
+
+        assert!(client.get_reply(&0, &0, &hidden_id).unwrap().is_hidden);
+        assert!(!client.get_reply(&0, &0, &visible_id).unwrap().is_hidden);
+    }
+
+    #[test]
+    fn test_sorted_top_level_replies_break_ties_by_id() {
+        let env = Env::default();
+        env.mock_all_auths();
+
+        let registry = MockRegistryClient::new(&env, &env.register(MockRegistry, ()));
+        let voting = MockVotingClient::new(&env, &env.register(MockVoting, ()));
+        registry.set_voting(&voting.address);
+        let client = BoardsContentClient::new(&env, &env.register(BoardsContent, ()));
+        client.init(&registry.address, &None);
+
+        let author = Address::generate(&env);
+        let text = String::from_str(&env, "reply");
+        for _ in 0..4 {
+            client.create_reply(&0, &0, &0, &0, &text, &author);
+        }
+        // Nested replies never show up in the top-level listing
+        client.create_reply(&0, &0, &1, &1, &text, &author);
+        voting.set_score(&1, &2);
+        voting.set_score(&2, &5);
+        voting.set_score(&3, &2);
+
+        let ids = |replies: Vec<ReplyMeta>| {
+            let mut ids: Vec<u64> = Vec::new(&env);
+            for reply in replies.iter() {
+                ids.push_back(reply.id);
+            }
+            ids
+        };
+        let top = client.list_top_level_replies_sorted(&0, &0, &0, &10, &ReplySort::Top);
+        assert_eq!(ids(top), Vec::from_array(&env, [2, 1, 3, 0]));
+        let page = client.list_top_level_replies_sorted(&0, &0, &1, &2, &ReplySort::Top);
+        assert_eq!(ids(page), Vec::from_array(&env, [1, 3]));
+        let newest = client.list_top_level_replies_sorted(&0, &0, &0, &3, &ReplySort::Newest);
+        assert_eq!(ids(newest), Vec::from_array(&env, [3, 2, 1]));
+        let oldest = client.list_top_level_replies_sorted(&0, &0, &0, &10, &ReplySort::Oldest);
+        assert_eq!(ids(oldest), Vec::from_array(&env, [0, 1, 2, 3]));
+    }
+
+    #[test]
+    fn test_sorted_top_level_replies_rank_only_the_window() {
+        let env = Env::default();
+        env.mock_all_auths();
+
+        let registry = MockRegistryClient::new(&env, &env.register(MockRegistry, ()));
+        let voting = MockVotingClient::new(&env, &env.register(MockVoting, ()));
+        registry.set_voting(&voting.address);
+        let client = BoardsContentClient::new(&env, &env.register(BoardsContent, ()));
+        client.init(&registry.address, &None);
+
+        let author = Address::generate(&env);
+        let text = String::from_str(&env, "reply");
+        for _ in 0..MAX_REPLY_SORT_SCAN + 2 {
+            client.create_reply(&0, &0, &0, &0, &text, &author);
+        }
+        let last = MAX_REPLY_SORT_SCAN as u64 + 1;
+        voting.set_score(&7, &3);
+        voting.set_score(&last, &9);
+
+        // The best reply in the window leads; later ones keep id order after it
+        let top = client.list_top_level_replies_sorted(&0, &0, &0, &2, &ReplySort::Top);
+        assert_eq!(top.get(0).unwrap().id, 7);
+        assert_eq!(top.get(1).unwrap().id, 0);
+        let tail = client.list_top_level_replies_sorted(
+            &0,
+            &0,
+            &(MAX_REPLY_SORT_SCAN - 1),
+            &10,
+            &ReplySort::Top,
+        );
+        assert_eq!(tail.len(), 3);
+        assert_eq!(tail.get(0).unwrap().id, MAX_REPLY_SORT_SCAN as u64 - 1);
+        assert_eq!(tail.get(2).unwrap().id, last);
+    }
 }
@@ -21,8 +21,8 @@
 use soroban_render_sdk::prelude::*;
 use soroban_render_sdk::router::Request;
 use soroban_sdk::{
-    contract, contractimpl, contracttype, Address, Bytes, BytesN, Env, IntoVal, String, Symbol,
-    Val, Vec,
+    contract, contractimpl, contracttype, Address, Bytes, BytesN, Env, IntoVal, Map, String,
+    Symbol, Val, Vec,
 };
 
 // Declare render capabilities
@@ -114,6 +114,36 @@ pub struct BoardConfig {
     pub reply_chunk_size: u32,
 }
 
+/// Board listing filter (matches boards-board BoardFilter)
+#[contracttype]
+#[derive(Clone)]
+pub enum BoardFilter {
+    Listed,
+    Unlisted,
+    Private,
+    InCommunity(u64),
+    Standalone,
+    Archived,
+}
+
+/// Board category (same structure as boards-registry Category)
+#[contracttype]
+#[derive(Clone)]
+pub struct Category {
+    pub id: u32,
+    pub name: String,
+}
+
+/// New thread in the cross-board feed (same structure as boards-registry ActivityEntry)
+#[contracttype]
+#[derive(Clone)]
+pub struct ActivityEntry {
+    pub board_id: u64,
+    pub thread_id: u64,
+    pub title: String,
+    pub created_at: u64,
+}
+
 /// Community metadata (same structure as community contract for compatibility)
 #[contracttype]
 #[derive(Clone)]
@@ -165,6 +195,18 @@ pub struct PageMeta {
     pub nav_order: u32,
 }
 
+/// Listed boards per page of the `/index` route
+const INDEX_PAGE_SIZE: u64 = 50;
+
+/// Standalone boards fetched for the home page, before unlisted ones are dropped
+const HOME_BOARD_FETCH: u64 = 50;
+
+/// Standalone boards shown on the home page
+const HOME_BOARD_LIMIT: u32 = 20;
+
+/// Entries shown in the home page's recent activity section
+const HOME_ACTIVITY_LIMIT: u32 = 10;
+
 #[contract]
 pub struct BoardsMain;
 
@@ -531,6 +573,7 @@ impl BoardsMain {
     ///
     /// Routing:
     /// - `/`, `/create`, `/help` → Rendered here (home, create board, help)
+    /// - `/index` → Rendered here (every listed, public board, for crawlers)
     /// - `/communities`, `/c/{name}/*` → Community contract
     /// - `/admin/*`, `/b/{id}/settings`, etc. → Admin contract
     /// - `/b/{id}/*` → Board contract (looked up via Registry)
@@ -550,6 +593,12 @@ impl BoardsMain {
                 // Delegate to pages contract with /help path
                 Self::delegate_to_pages(&env, &Some(String::from_str(&env, "/help")), &viewer)
             })
+            // Board index for crawlers, continued page by page
+            .or_handle(b"/index", |_| Self::render_board_index(&env, 0))
+            .or_handle(b"/index/{start}", |req| {
+                let start = req.get_var_u32(b"start").unwrap_or(0) as u64;
+                Self::render_board_index(&env, start)
+            })
             // Crosspost form
             .or_handle(b"/crosspost*", |_| {
                 Self::render_crosspost(&env, &path, &viewer)
@@ -561,6 +610,10 @@ impl BoardsMain {
             .or_handle(b"/new", |_| {
                 Self::delegate_to_community(&env, &String::from_str(&env, "/new"), &viewer)
             })
+            .or_handle(b"/my/communities", |_| {
+                let my_path = String::from_str(&env, "/my/communities");
+                Self::delegate_to_community(&env, &my_path, &viewer)
+            })
             .or_handle(b"/c/{name}/*", |req| {
                 let name = req.get_var(b"name").unwrap_or(Bytes::new(&env));
                 Self::delegate_to_community_by_name(&env, &name, &path, &viewer)
@@ -1014,6 +1067,12 @@ impl BoardsMain {
             md = md.note("Connect your wallet to participate in discussions.");
         }
 
+        // === Featured Section (only when registry admins have picked some) ===
+        md = Self::render_featured_boards(env, md, &registry);
+
+        // === Recent Activity (new threads on public, listed boards) ===
+        md = Self::render_recent_activity(env, md, &registry);
+
         // === Communities Section ===
         md = md.newline().h2("Communities");
 
@@ -1109,46 +1168,31 @@ impl BoardsMain {
         );
 
         if let Some(board_contract) = board_contract_opt {
-            // Get listed boards directly from board contract
-            let list_args: Vec<Val> =
-                Vec::from_array(env, [0u64.into_val(env), 50u64.into_val(env)]);
-            let listed_boards: Vec<BoardMeta> = env
+            // One page of the board contract's standalone index
+            let list_args: Vec<Val> = Vec::from_array(
+                env,
+                [
+                    0u64.into_val(env),
+                    HOME_BOARD_FETCH.into_val(env),
+                    BoardFilter::Standalone.into_val(env),
+                ],
+            );
+            let boards: Vec<BoardMeta> = env
                 .try_invoke_contract::<Vec<BoardMeta>, soroban_sdk::Error>(
                     &board_contract,
-                    &Symbol::new(env, "list_listed_boards"),
+                    &Symbol::new(env, "list_boards_by_filter"),
                     list_args,
                 )
                 .ok()
                 .and_then(|r| r.ok())
                 .unwrap_or_else(|| Vec::new(env));
 
-            // Filter out boards that are in communities
             let mut standalone_boards: Vec<BoardMeta> = Vec::new(env);
-            for board in listed_boards.iter() {
-                // Check if board is in a community
-                let community_id: Option<u64> = if let Some(ref community_contract) =
-                    community_contract_opt
-                {
-                    let community_args: Vec<Val> = Vec::from_array(env, [board.id.into_val(env)]);
-                    env.try_invoke_contract::<Option<u64>, soroban_sdk::Error>(
-                        community_contract,
-                        &Symbol::new(env, "get_board_community"),
-                        community_args,
-                    )
-                    .ok()
-                    .and_then(|r| r.ok())
-                    .flatten()
-                } else {
-                    None
-                };
-
-                // Only include standalone boards (not in any community)
-                if community_id.is_none() {
+            for board in boards.iter() {
+                if board.is_listed {
                     standalone_boards.push_back(board);
                 }
-
-                // Limit to 20 boards
-                if standalone_boards.len() >= 20 {
+                if standalone_boards.len() >= HOME_BOARD_LIMIT {
                     break;
                 }
             }
@@ -1159,26 +1203,76 @@ impl BoardsMain {
                 // Sort boards alphabetically by name
                 let sorted_boards = Self::sort_boards_by_name(env, standalone_boards);
 
-                md = md.raw_str("<div class=\"board-list\">\n");
+                // Registry categories, if any are set up, split the list into sections
+                let categories: Vec<Category> = env
+                    .try_invoke_contract::<Vec<Category>, soroban_sdk::Error>(
+                        &registry,
+                        &Symbol::new(env, "list_categories"),
+                        Vec::new(env),
+                    )
+                    .ok()
+                    .and_then(|r| r.ok())
+                    .unwrap_or_else(|| Vec::new(env));
+                let mut board_ids: Vec<u64> = Vec::new(env);
                 for board in sorted_boards.iter() {
-                    // Board card with link wrapper - use slug-based URL: /b/{slug}
-                    md = md
-                        .raw_str("<a href=\"render:/b/")
-                        .text_string(&board.slug)
-                        .raw_str("\" class=\"board-card\"><span class=\"board-card-title\">")
-                        .text_string(&board.name)
-                        .raw_str("</span><span class=\"board-card-desc\">")
-                        .text_string(&board.description)
-                        .raw_str("</span><span class=\"board-card-meta\">")
-                        .number(board.thread_count as u32)
-                        .text(" threads · ")
-                        .raw(Self::format_timestamp(env, board.created_at));
-                    if board.is_private {
-                        md = md.raw_str(" <span class=\"badge\">private</span>");
+                    board_ids.push_back(board.id);
+                }
+                let board_categories: Vec<u32> = if categories.is_empty() {
+                    Vec::new(env)
+                } else {
+                    env.try_invoke_contract::<Vec<u32>, soroban_sdk::Error>(
+                        &registry,
+                        &Symbol::new(env, "get_board_categories"),
+                        Vec::from_array(env, [board_ids.into_val(env)]),
+                    )
+                    .ok()
+                    .and_then(|r| r.ok())
+                    .unwrap_or_else(|| Vec::new(env))
+                };
+
+                if board_categories.is_empty() {
+                    md = md.raw_str("<div class=\"board-list\">\n");
+                    for board in sorted_boards.iter() {
+                        md = Self::render_home_board_card(env, md, &board, None);
+                    }
+                    md = md.raw_str("</div>\n");
+                } else {
+                    let mut placed: Vec<u64> = Vec::new(env);
+                    for category in categories.iter() {
+                        let mut in_category: Vec<BoardMeta> = Vec::new(env);
+                        for (i, board) in sorted_boards.iter().enumerate() {
+                            if board_categories.get(i as u32) == Some(category.id) {
+                                placed.push_back(board.id);
+                                in_category.push_back(board);
+                            }
+                        }
+                        if in_category.is_empty() {
+                            continue;
+                        }
+                        md = md
+                            .raw_str("<h3 class=\"board-group\">")
+                            .text_string(&category.name)
+                            .raw_str("</h3>\n<div class=\"board-list\">\n");
+                        for board in in_category.iter() {
+                            md =
+                                Self::render_home_board_card(env, md, &board, Some(&category.name));
+                        }
+                        md = md.raw_str("</div>\n");
+                    }
+
+                    // Boards with no category, or one since deleted
+                    if placed.len() < sorted_boards.len() {
+                        md = md
+                            .raw_str("<h3 class=\"board-group\">Uncategorized</h3>\n")
+                            .raw_str("<div class=\"board-list\">\n");
+                        for board in sorted_boards.iter() {
+                            if !placed.contains(board.id) {
+                                md = Self::render_home_board_card(env, md, &board, None);
+                            }
+                        }
+                        md = md.raw_str("</div>\n");
                     }
-                    md = md.raw_str("</span></a>\n");
                 }
-                md = md.raw_str("</div>\n");
             }
         } else {
             md = md.paragraph("Board service not configured.");
@@ -1270,6 +1364,298 @@ impl BoardsMain {
         Self::render_footer_into(env, md).build()
     }
 
+    /// Render the featured boards as large cards, in the order registry admins set
+    fn render_featured_boards<'a>(
+        env: &'a Env,
+        md: MarkdownBuilder<'a>,
+        registry: &Address,
+    ) -> MarkdownBuilder<'a> {
+        let board_contract: Option<Address> = env
+            .try_invoke_contract::<Option<Address>, soroban_sdk::Error>(
+                registry,
+                &Symbol::new(env, "get_contract_by_alias"),
+                Vec::from_array(env, [Symbol::new(env, "board").into_val(env)]),
+            )
+            .ok()
+            .and_then(|r| r.ok())
+            .flatten();
+        let Some(board_contract) = board_contract else {
+            return md;
+        };
+
+        let featured: Vec<BoardMeta> = env
+            .try_invoke_contract::<Vec<BoardMeta>, soroban_sdk::Error>(
+                &board_contract,
+                &Symbol::new(env, "list_featured"),
+                Vec::new(env),
+            )
+            .ok()
+            .and_then(|r| r.ok())
+            .unwrap_or_else(|| Vec::new(env));
+        if featured.is_empty() {
+            return md;
+        }
+
+        let mut md = md
+            .newline()
+            .h2("Featured")
+            .raw_str("<div class=\"board-list board-list-featured\">\n");
+        for board in featured.iter() {
+            md = md
+                .raw_str("<a href=\"render:/b/")
+                .text_string(&board.slug)
+                .raw_str("\" class=\"board-card board-card-featured\">")
+                .raw_str("<span class=\"board-card-title\">")
+                .text_string(&board.name)
+                .raw_str("</span><span class=\"board-card-desc\">")
+                .text_string(&board.description)
+                .raw_str("</span><span class=\"board-card-meta\">")
+                .number(board.thread_count as u32)
+                .text(" threads")
+                .raw_str("</span></a>\n");
+        }
+        md.raw_str("</div>\n")
+    }
+
+    /// Render the newest threads across all boards, skipping entries whose board is
+    /// private, unlisted or gone (checked now, so boards made private later drop out)
+    fn render_recent_activity<'a>(
+        env: &'a Env,
+        md: MarkdownBuilder<'a>,
+        registry: &Address,
+    ) -> MarkdownBuilder<'a> {
+        let board_contract: Option<Address> = env
+            .try_invoke_contract::<Option<Address>, soroban_sdk::Error>(
+                registry,
+                &Symbol::new(env, "get_contract_by_alias"),
+                Vec::from_array(env, [Symbol::new(env, "board").into_val(env)]),
+            )
+            .ok()
+            .and_then(|r| r.ok())
+            .flatten();
+        let Some(board_contract) = board_contract else {
+            return md;
+        };
+
+        // The registry keeps 50 entries; fetch them all so filtering still fills the list
+        let entries: Vec<ActivityEntry> = env
+            .try_invoke_contract::<Vec<ActivityEntry>, soroban_sdk::Error>(
+                registry,
+                &Symbol::new(env, "list_recent_activity"),
+                Vec::from_array(env, [50u32.into_val(env)]),
+            )
+            .ok()
+            .and_then(|r| r.ok())
+            .unwrap_or_else(|| Vec::new(env));
+        if entries.is_empty() {
+            return md;
+        }
+
+        // Threads hidden or deleted since they were reported are left out
+        let mut threads: Vec<(u64, u64)> = Vec::new(env);
+        for entry in entries.iter() {
+            threads.push_back((entry.board_id, entry.thread_id));
+        }
+        let removed: Vec<(u64, u64)> = env
+            .try_invoke_contract::<Vec<(u64, u64)>, soroban_sdk::Error>(
+                &board_contract,
+                &Symbol::new(env, "get_removed_threads"),
+                Vec::from_array(env, [threads.into_val(env)]),
+            )
+            .ok()
+            .and_then(|r| r.ok())
+            .unwrap_or_else(|| Vec::new(env));
+
+        // One board lookup per distinct board
+        let mut boards: Map<u64, Option<BoardMeta>> = Map::new(env);
+        let mut md = md
+            .newline()
+            .h2("Recent activity")
+            .raw_str("<ul class=\"recent-activity\">\n");
+        let mut shown = 0;
+        for entry in entries.iter() {
+            if shown >= HOME_ACTIVITY_LIMIT {
+                break;
+            }
+            if removed.contains((entry.board_id, entry.thread_id)) {
+                continue;
+            }
+            let board = match boards.get(entry.board_id) {
+                Some(board) => board,
+                None => {
+                    let board: Option<BoardMeta> = env
+                        .try_invoke_contract::<Option<BoardMeta>, soroban_sdk::Error>(
+                            &board_contract,
+                            &Symbol::new(env, "get_board"),
+                            Vec::from_array(env, [entry.board_id.into_val(env)]),
+                        )
+                        .ok()
+                        .and_then(|r| r.ok())
+                        .flatten();
+                    boards.set(entry.board_id, board.clone());
+                    board
+                }
+            };
+            let Some(board) = board.filter(|b| b.is_listed && !b.is_private) else {
+                continue;
+            };
+
+            md = md
+                .raw_str("<li><a href=\"render:/b/")
+                .text_string(&board.slug)
+                .raw_str("/t/")
+                .number(entry.thread_id as u32)
+                .raw_str("\">")
+                .text_string(&entry.title)
+                .raw_str("</a> in <a href=\"render:/b/")
+                .text_string(&board.slug)
+                .raw_str("\">")
+                .text_string(&board.name)
+                .raw_str("</a> · ")
+                .raw(Self::format_timestamp(env, entry.created_at))
+                .raw_str("</li>\n");
+            shown += 1;
+        }
+        if shown == 0 {
+            md = md.raw_str("<li>No recent threads.</li>\n");
+        }
+        md.raw_str("</ul>\n")
+    }
+
+    /// Render a board card for the home page, with its category badge if it has one
+    fn render_home_board_card<'a>(
+        env: &'a Env,
+        md: MarkdownBuilder<'a>,
+        board: &BoardMeta,
+        category: Option<&String>,
+    ) -> MarkdownBuilder<'a> {
+        // Board card with link wrapper - use slug-based URL: /b/{slug}
+        let mut md = md
+            .raw_str("<a href=\"render:/b/")
+            .text_string(&board.slug)
+            .raw_str("\" class=\"board-card\"><span class=\"board-card-title\">")
+            .text_string(&board.name)
+            .raw_str("</span><span class=\"board-card-desc\">")
+            .text_string(&board.description)
+            .raw_str("</span><span class=\"board-card-meta\">")
+            .number(board.thread_count as u32)
+            .text(" threads · ")
+            .raw(Self::format_timestamp(env, board.created_at));
+        if let Some(name) = category {
+            md = md
+                .raw_str(" <span class=\"badge badge-category\">")
+                .text_string(name)
+                .raw_str("</span>");
+        }
+        if board.is_private {
+            md = md.raw_str(" <span class=\"badge\">private</span>");
+        }
+        md.raw_str("</span></a>\n")
+    }
+
+    /// Render one page of the board index: each listed, public board with its thread
+    /// count and last activity. Further pages load through a `{{render}}` continuation.
+    fn render_board_index(env: &Env, start: u64) -> Bytes {
+        let registry: Address = env
+            .storage()
+            .instance()
+            .get(&MainKey::Registry)
+            .expect("Not initialized");
+
+        let mut md = MarkdownBuilder::new(env);
+        if start == 0 {
+            md = md.h1("Board Index");
+        }
+
+        let board_alias_args: Vec<Val> =
+            Vec::from_array(env, [Symbol::new(env, "board").into_val(env)]);
+        let board_contract_opt: Option<Address> = env.invoke_contract(
+            &registry,
+            &Symbol::new(env, "get_contract_by_alias"),
+            board_alias_args,
+        );
+        let Some(board_contract) = board_contract_opt else {
+            return md.paragraph("Board service not configured.").build();
+        };
+
+        let list_args: Vec<Val> = Vec::from_array(
+            env,
+            [
+                start.into_val(env),
+                INDEX_PAGE_SIZE.into_val(env),
+                BoardFilter::Listed.into_val(env),
+            ],
+        );
+        let boards: Vec<BoardMeta> = env
+            .try_invoke_contract::<Vec<BoardMeta>, soroban_sdk::Error>(
+                &board_contract,
+                &Symbol::new(env, "list_boards_by_filter"),
+                list_args,
+            )
+            .ok()
+            .and_then(|r| r.ok())
+            .unwrap_or_else(|| Vec::new(env));
+
+        // Last activity comes from the registry's cache (0 = never reported)
+        let mut board_ids: Vec<u64> = Vec::new(env);
+        for board in boards.iter() {
+            board_ids.push_back(board.id);
+        }
+        let activities: Vec<u64> = env
+            .try_invoke_contract::<Vec<u64>, soroban_sdk::Error>(
+                &registry,
+                &Symbol::new(env, "get_board_activities"),
+                Vec::from_array(env, [board_ids.into_val(env)]),
+            )
+            .ok()
+            .and_then(|r| r.ok())
+            .unwrap_or_else(|| Vec::new(env));
+
+        // Archived boards stay listed on the board contract but are left out here
+        let archived: Vec<u64> = env
+            .try_invoke_contract::<Vec<u64>, soroban_sdk::Error>(
+                &board_contract,
+                &Symbol::new(env, "get_archived_boards"),
+                Vec::from_array(env, [board_ids.into_val(env)]),
+            )
+            .ok()
+            .and_then(|r| r.ok())
+            .unwrap_or_else(|| Vec::new(env));
+
+        let mut shown = 0u32;
+        for (i, board) in boards.iter().enumerate() {
+            if board.is_private || archived.contains(board.id) {
+                continue;
+            }
+            shown += 1;
+            let last_active = activities
+                .get(i as u32)
+                .filter(|t| *t > 0)
+                .unwrap_or(board.created_at);
+            md = md
+                .raw_str("<div class=\"board-index-row\"><a href=\"render:/b/")
+                .text_string(&board.slug)
+                .raw_str("\">")
+                .text_string(&board.name)
+                .raw_str("</a> · ")
+                .number(board.thread_count as u32)
+                .text(" threads · last active ")
+                .raw(Self::format_timestamp(env, last_active))
+                .raw_str("</div>\n");
+        }
+
+        if boards.len() as u64 == INDEX_PAGE_SIZE {
+            md = md
+                .raw_str("{{render path=\"/index/")
+                .number((start + INDEX_PAGE_SIZE) as u32)
+                .raw_str("\"}}");
+        } else if start == 0 && shown == 0 {
+            md = md.paragraph("No public boards yet.");
+        }
+
+        md.build()
+    }
+
     /// Render create board form using Router Request (new version with query param support)
     fn render_create_board_from_request(
         env: &Env,
@@ -1297,6 +1683,12 @@ impl BoardsMain {
             .paragraph("Create a new discussion board.")
             .newline()
             .redirect("/")  // Return to board list after creating board
+            // Registry name rules, one error code each
+            .raw_str("{{errors {\"3\": \"Board names must be 3-50 characters long.\", ")
+            .raw_str("\"4\": \"That name is reserved for the site.\", ")
+            .raw_str("\"5\": \"Another board already uses that name.\", ")
+            .raw_str("\"6\": \"That name used to belong to another board and still points to it.\", ")
+            .raw_str("\"7\": \"Board names can't start or end with a dash.\"}}}\n")
             .input("name", "Board name")
             .newline()
             // Slug field - use "-" as default value (sentinel for auto-generate)
@@ -1520,6 +1912,7 @@ impl BoardsMain {
             .raw_str("<div class=\"quick-links\">\n")
             .render_link("Home", "/")
             .render_link("Communities", "/communities")
+            .render_link("Joined Communities", "/my/communities")
             .render_link("Help", "/help")
             .raw_str("</div>\n");
 
@@ -2535,6 +2928,8 @@ impl BoardsMain {
 
 #[cfg(test)]
 mod test {
+    extern crate std;
+
     use super::*;
     use soroban_sdk::testutils::Address as _;
     use soroban_sdk::Env;
@@ -2698,4 +3093,202 @@ mod test {
     // which need fully initialized dependency contracts to test.
     // These are integration-level tests that would require setting up
     // the entire contract ecosystem.
+
+    /// Registry stand-in: resolves the "board" alias and serves cached activity
+    #[contract]
+    pub struct MockRegistry;
+
+    #[contractimpl]
+    impl MockRegistry {
+        pub fn set_board(env: Env, board: Address) {
+            env.storage()
+                .instance()
+                .set(&Symbol::new(&env, "board"), &board);
+        }
+
+        pub fn get_contract_by_alias(env: Env, alias: Symbol) -> Option<Address> {
+            env.storage().instance().get(&alias)
+        }
+
+        pub fn add_activity(env: Env, board_id: u64, thread_id: u64) {
+            let key = Symbol::new(&env, "activity");
+            let mut entries: Vec<ActivityEntry> =
+                env.storage().instance().get(&key).unwrap_or(Vec::new(&env));
+            entries.push_front(ActivityEntry {
+                board_id,
+                thread_id,
+                title: String::from_str(&env, "Fresh thread"),
+                created_at: 0,
+            });
+            env.storage().instance().set(&key, &entries);
+        }
+
+        pub fn list_recent_activity(env: Env, limit: u32) -> Vec<ActivityEntry> {
+            let entries: Vec<ActivityEntry> = env
+                .storage()
+                .instance()
+                .get(&Symbol::new(&env, "activity"))
+                .unwrap_or(Vec::new(&env));
+            entries.slice(0..limit.min(entries.len()))
+        }
+
+        pub fn get_board_activities(env: Env, board_ids: Vec<u64>) -> Vec<u64> {
+            let mut activities = Vec::new(&env);
+            for board_id in board_ids.iter() {
+                activities.push_back(if board_id == 0 { 1_700_000_000 } else { 0 });
+            }
+            activities
+        }
+    }
+
+    /// Board stand-in with `count` listed boards; board 1 is private, board 3
+    /// archived, `get_board` reports board 2 as unlisted and thread 10 is removed
+    #[contract]
+    pub struct MockBoard;
+
+    #[contractimpl]
+    impl MockBoard {
+        pub fn set_count(env: Env, count: u64) {
+            env.storage()
+                .instance()
+                .set(&Symbol::new(&env, "count"), &count);
+        }
+
+        pub fn list_boards_by_filter(
+            env: Env,
+            start: u64,
+            limit: u64,
+            _filter: BoardFilter,
+        ) -> Vec<BoardMeta> {
+            let count: u64 = env
+                .storage()
+                .instance()
+                .get(&Symbol::new(&env, "count"))
+                .unwrap_or(0);
+            let mut boards = Vec::new(&env);
+            for id in start..core::cmp::min(start + limit, count) {
+                boards.push_back(BoardMeta {
+                    id,
+                    slug: String::from_str(&env, if id == 1 { "secret" } else { "board" }),
+                    name: String::from_str(&env, "Board"),
+                    description: String::from_str(&env, ""),
+                    creator: Address::generate(&env),
+                    created_at: 0,
+                    thread_count: 3,
+                    is_readonly: false,
+                    is_private: id == 1,
+                    is_listed: true,
+                });
+            }
+            boards
+        }
+
+        pub fn get_archived_boards(env: Env, board_ids: Vec<u64>) -> Vec<u64> {
+            let mut archived = Vec::new(&env);
+            if board_ids.contains(3) {
+                archived.push_back(3);
+            }
+            archived
+        }
+
+        pub fn get_removed_threads(env: Env, threads: Vec<(u64, u64)>) -> Vec<(u64, u64)> {
+            let mut removed = Vec::new(&env);
+            for (board_id, thread_id) in threads.iter() {
+                if thread_id == 10 {
+                    removed.push_back((board_id, thread_id));
+                }
+            }
+            removed
+        }
+
+        pub fn get_board(env: Env, board_id: u64) -> Option<BoardMeta> {
+            Self::list_boards_by_filter(env, board_id, 1, BoardFilter::Listed)
+                .first()
+                .map(|mut board| {
+                    board.is_listed = board_id != 2;
+                    board
+                })
+        }
+    }
+
+    fn render_count(html: &Bytes, needle: &str) -> usize {
+        let mut buf = std::vec![0u8; html.len() as usize];
+        html.copy_into_slice(&mut buf);
+        buf.windows(needle.len())
+            .filter(|w| *w == needle.as_bytes())
+            .count()
+    }
+
+    #[test]
+    fn test_board_index_pages_public_boards() {
+        let env = Env::default();
+        env.mock_all_auths();
+        let registry = MockRegistryClient::new(&env, &env.register(MockRegistry, ()));
+        let board = MockBoardClient::new(&env, &env.register(MockBoard, ()));
+        registry.set_board(&board.address);
+        board.set_count(&(INDEX_PAGE_SIZE + 1));
+
+        let client = BoardsMainClient::new(&env, &env.register(BoardsMain, ()));
+        let other = Address::generate(&env);
+        client.init(
+            &registry.address,
+            &other,
+            &other,
+            &other,
+            &other,
+            &other,
+            &other,
+        );
+
+        // A full first page: the private and archived boards are skipped and the
+        // next page chained
+        let html = client.render(&Some(String::from_str(&env, "/index")), &None);
+        assert_eq!(render_count(&html, "Board Index"), 1);
+        assert_eq!(
+            render_count(&html, "board-index-row"),
+            INDEX_PAGE_SIZE as usize - 2
+        );
+        assert_eq!(render_count(&html, "render:/b/secret"), 0);
+        assert_eq!(render_count(&html, "last active 2023-11-14 22:13 UTC"), 1);
+        assert_eq!(
+            render_count(&html, "last active Ledger 0"),
+            INDEX_PAGE_SIZE as usize - 3
+        );
+        assert_eq!(render_count(&html, "{{render path=\"/index/50\"}}"), 1);
+
+        // The last page has no heading and no continuation
+        let html = client.render(&Some(String::from_str(&env, "/index/50")), &None);
+        assert_eq!(render_count(&html, "Board Index"), 0);
+        assert_eq!(render_count(&html, "board-index-row"), 1);
+        assert_eq!(render_count(&html, "{{render"), 0);
+    }
+
+    #[test]
+    fn test_recent_activity_skips_private_unlisted_and_removed() {
+        let env = Env::default();
+        env.mock_all_auths();
+        let registry = MockRegistryClient::new(&env, &env.register(MockRegistry, ()));
+        let board = MockBoardClient::new(&env, &env.register(MockBoard, ()));
+        registry.set_board(&board.address);
+        board.set_count(&4);
+        let client = BoardsMainClient::new(&env, &env.register(BoardsMain, ()));
+
+        // Board 1 is private, board 2 unlisted, board 9 doesn't exist and thread 10
+        // was hidden after it was reported
+        for (board_id, thread_id) in [(0, 4), (1, 5), (2, 6), (9, 1), (3, 7), (0, 10), (0, 8)] {
+            registry.add_activity(&board_id, &thread_id);
+        }
+        let html = env.as_contract(&client.address, || {
+            let md = MarkdownBuilder::new(&env);
+            BoardsMain::render_recent_activity(&env, md, &registry.address).build()
+        });
+        assert_eq!(render_count(&html, "Recent activity"), 1);
+        assert_eq!(render_count(&html, "<li>"), 3);
+        assert_eq!(render_count(&html, "render:/b/board/t/8"), 1);
+        assert_eq!(render_count(&html, "render:/b/board/t/7"), 1);
+        assert_eq!(render_count(&html, "render:/b/board/t/4"), 1);
+        assert_eq!(render_count(&html, "render:/b/secret"), 0);
+        assert_eq!(render_count(&html, "/t/6"), 0);
+        assert_eq!(render_count(&html, "/t/10"), 0);
+    }
 }
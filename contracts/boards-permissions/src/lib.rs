@@ -4,6 +4,9 @@ use soroban_sdk::{
     contract, contractimpl, contracttype, Address, BytesN, Env, IntoVal, String, Symbol, Val, Vec,
 };
 
+/// Maximum length of the message attached to an invite request
+const MAX_INVITE_MESSAGE_LEN: u32 = 500;
+
 /// Role levels (hierarchical - higher includes lower permissions)
 #[contracttype]
 #[derive(Clone, Copy, Debug, PartialEq, Eq)]
@@ -82,6 +85,8 @@ pub enum PermKey {
     Registry,
     /// Board owner address
     BoardOwner(u64),
+    /// Proposed new owner awaiting acceptance (board_id) -> Address
+    PendingBoardOwner(u64),
     /// User role for a board (board_id, user) -> Role
     BoardRole(u64, Address),
     /// Ban record (board_id, user) -> Ban
@@ -96,12 +101,26 @@ pub enum PermKey {
     BoardModerators(u64),
     /// List of members for a board
     BoardMembers(u64),
+    /// Number of members in the indexed member list (board_id) -> u32
+    MemberCount(u64),
+    /// Member at a position in the indexed member list (board_id, index) -> Address
+    MemberAt(u64, u32),
+    /// Position of a member in the indexed member list (board_id, user) -> u32
+    MemberSlot(u64, Address),
+    /// Set once the indexed member list covers every member (board_id) -> bool
+    MembersIndexed(u64),
     /// List of banned users for a board
     BannedUsers(u64),
+    /// Quarantined user whose new posts start hidden (board_id, user) -> bool
+    Quarantined(u64, Address),
     /// Individual invite request (board_id, user) -> InviteRequest
     InviteRequest(u64, Address),
     /// List of users with pending invite requests for a board
     InviteRequests(u64),
+    /// Message attached to an invite request (board_id, user) -> String
+    InviteMessage(u64, Address),
+    /// Seconds before a pending invite request expires (board_id) -> u64
+    InviteRequestTtl(u64),
     /// Board role override - can only RESTRICT, not expand community role
     /// (board_id, user) -> Role
     BoardRoleOverride(u64, Address),
@@ -121,6 +140,8 @@ pub enum PermKey {
     CommunityMembers(u64),
     /// List of banned users for a community
     CommunityBannedUsers(u64),
+    /// Community a board belongs to (board_id) -> community_id
+    BoardCommunity(u64),
     /// User flair for a board (board_id, user) -> UserFlair
     UserFlair(u64, Address),
     /// First time a user was seen (for account age tracking)
@@ -129,7 +150,8 @@ pub enum PermKey {
     // Site-level keys (moved from registry for proper separation of concerns)
     /// Site-wide admin addresses
     SiteAdmins,
-    /// Count of boards created by a user (for threshold limits)
+    /// Count of boards created by a user (for threshold limits).
+    /// Persistent; older deployments stored it in instance storage.
     UserBoardCount(Address),
     /// Count of posts (threads + replies) by a user (for threshold limits)
     UserPostCount(Address),
@@ -199,6 +221,88 @@ impl BoardsPermissions {
             .get(&PermKey::BoardOwner(board_id))
     }
 
+    /// Propose a new owner for a board (current owner only).
+    /// Ownership only changes once the proposed owner accepts.
+    pub fn propose_board_owner(env: Env, board_id: u64, new_owner: Address, caller: Address) {
+        caller.require_auth();
+
+        if Self::get_board_owner(env.clone(), board_id) != Some(caller.clone()) {
+            panic!("Only owner can transfer ownership");
+        }
+        if new_owner == caller {
+            panic!("Board is already owned by this user");
+        }
+
+        env.storage()
+            .persistent()
+            .set(&PermKey::PendingBoardOwner(board_id), &new_owner);
+    }
+
+    /// Get the proposed new owner for a board, if a transfer is pending
+    pub fn get_pending_board_owner(env: Env, board_id: u64) -> Option<Address> {
+        env.storage()
+            .persistent()
+            .get(&PermKey::PendingBoardOwner(board_id))
+    }
+
+    /// Cancel a pending ownership transfer (current owner, or the proposed owner to decline)
+    pub fn cancel_board_owner_transfer(env: Env, board_id: u64, caller: Address) {
+        caller.require_auth();
+
+        if Self::get_board_owner(env.clone(), board_id) != Some(caller.clone())
+            && Self::get_pending_board_owner(env.clone(), board_id) != Some(caller)
+        {
+            panic!("Only the owner or proposed owner can cancel a transfer");
+        }
+
+        env.storage()
+            .persistent()
+            .remove(&PermKey::PendingBoardOwner(board_id));
+    }
+
+    /// Accept a pending ownership transfer (proposed owner only).
+    /// The caller becomes Owner and the previous owner is demoted to Admin.
+    pub fn accept_board_ownership(env: Env, board_id: u64, caller: Address) {
+        caller.require_auth();
+
+        let pending: Address = env
+            .storage()
+            .persistent()
+            .get(&PermKey::PendingBoardOwner(board_id))
+            .expect("No pending ownership transfer");
+        if pending != caller {
+            panic!("Only the proposed owner can accept the transfer");
+        }
+
+        let previous: Address =
+            Self::get_board_owner(env.clone(), board_id).expect("Board has no owner");
+
+        // New owner leaves whatever role list they were on
+        let old_role: Role = env
+            .storage()
+            .persistent()
+            .get(&PermKey::BoardRole(board_id, caller.clone()))
+            .unwrap_or(Role::Guest);
+        Self::remove_from_role_list(&env, board_id, &caller, old_role);
+
+        env.storage()
+            .persistent()
+            .set(&PermKey::BoardOwner(board_id), &caller);
+        env.storage()
+            .persistent()
+            .set(&PermKey::BoardRole(board_id, caller), &Role::Owner);
+
+        // Previous owner stays on as an admin
+        Self::add_to_role_list(&env, board_id, &previous, Role::Admin);
+        env.storage()
+            .persistent()
+            .set(&PermKey::BoardRole(board_id, previous), &Role::Admin);
+
+        env.storage()
+            .persistent()
+            .remove(&PermKey::PendingBoardOwner(board_id));
+    }
+
     /// Set a user's role for a board
     pub fn set_role(env: Env, board_id: u64, user: Address, role: Role, caller: Address) {
         caller.require_auth();
@@ -256,6 +360,30 @@ impl BoardsPermissions {
             .set(&PermKey::BoardRole(board_id, user), &role);
     }
 
+    /// Leave a board, dropping the caller's own role back to Guest.
+    /// Owners must transfer ownership first.
+    pub fn leave_board(env: Env, board_id: u64, caller: Address) {
+        caller.require_auth();
+
+        // Use the stored board role; site admin and community roles can't be left here
+        let role: Role = env
+            .storage()
+            .persistent()
+            .get(&PermKey::BoardRole(board_id, caller.clone()))
+            .unwrap_or(Role::Guest);
+
+        match role {
+            Role::Owner => panic!("Owner must transfer ownership before leaving the board"),
+            Role::Guest => panic!("Not a member of this board"),
+            _ => {}
+        }
+
+        Self::remove_from_role_list(&env, board_id, &caller, role);
+        env.storage()
+            .persistent()
+            .remove(&PermKey::BoardRole(board_id, caller));
+    }
+
     /// Add user to appropriate role list
     fn add_to_role_list(env: &Env, board_id: u64, user: &Address, role: Role) {
         let key = match role {
@@ -281,9 +409,70 @@ impl BoardsPermissions {
         }
 
         if !found {
+            // New boards start indexed; older ones join once index_members has run
+            let index = role == Role::Member
+                && (list.is_empty()
+                    || env
+                        .storage()
+                        .persistent()
+                        .has(&PermKey::MemberCount(board_id)));
             list.push_back(user.clone());
             env.storage().persistent().set(&key, &list);
+            if index {
+                if list.len() == 1 {
+                    env.storage()
+                        .persistent()
+                        .set(&PermKey::MembersIndexed(board_id), &true);
+                }
+                Self::index_member(env, board_id, user);
+            }
+        }
+    }
+
+    /// Append a member to the indexed member list (no-op if already there)
+    fn index_member(env: &Env, board_id: u64, user: &Address) {
+        let slot_key = PermKey::MemberSlot(board_id, user.clone());
+        if env.storage().persistent().has(&slot_key) {
+            return;
+        }
+        let count = Self::indexed_member_count(env, board_id);
+        env.storage()
+            .persistent()
+            .set(&PermKey::MemberAt(board_id, count), user);
+        env.storage().persistent().set(&slot_key, &count);
+        env.storage()
+            .persistent()
+            .set(&PermKey::MemberCount(board_id), &(count + 1));
+    }
+
+    /// Remove a member from the indexed member list by moving the last entry into its slot
+    fn unindex_member(env: &Env, board_id: u64, user: &Address) {
+        let slot_key = PermKey::MemberSlot(board_id, user.clone());
+        let slot: u32 = match env.storage().persistent().get(&slot_key) {
+            Some(slot) => slot,
+            None => return,
+        };
+        let last = Self::indexed_member_count(env, board_id) - 1;
+        if slot != last {
+            let moved: Address = env
+                .storage()
+                .persistent()
+                .get(&PermKey::MemberAt(board_id, last))
+                .unwrap();
+            env.storage()
+                .persistent()
+                .set(&PermKey::MemberAt(board_id, slot), &moved);
+            env.storage()
+                .persistent()
+                .set(&PermKey::MemberSlot(board_id, moved), &slot);
         }
+        env.storage()
+            .persistent()
+            .remove(&PermKey::MemberAt(board_id, last));
+        env.storage().persistent().remove(&slot_key);
+        env.storage()
+            .persistent()
+            .set(&PermKey::MemberCount(board_id), &last);
     }
 
     /// Remove user from role list
@@ -305,6 +494,10 @@ impl BoardsPermissions {
             }
             env.storage().persistent().set(&key, &new_list);
         }
+
+        if role == Role::Member {
+            Self::unindex_member(env, board_id, user);
+        }
     }
 
     /// Get a user's role for a board
@@ -318,10 +511,29 @@ impl BoardsPermissions {
         // re-entry issues (registry.is_admin calls back to is_site_admin).
         // Site admins are managed directly in this contract.
 
-        env.storage()
+        let role = env
+            .storage()
             .persistent()
-            .get(&PermKey::BoardRole(board_id, user))
-            .unwrap_or(Role::Guest)
+            .get(&PermKey::BoardRole(board_id, user.clone()))
+            .unwrap_or(Role::Guest);
+
+        // Community moderators (and above) are at least Moderator on every
+        // board in the community. The lookup is skipped when the board role
+        // already covers it, and get_permissions computes the role once per call.
+        if (role as u32) < (Role::Moderator as u32) {
+            if let Some(community_id) = env
+                .storage()
+                .persistent()
+                .get::<_, u64>(&PermKey::BoardCommunity(board_id))
+            {
+                let community_role = Self::get_community_role(env, community_id, user);
+                if community_role as u32 >= Role::Moderator as u32 {
+                    return Role::Moderator;
+                }
+            }
+        }
+
+        role
     }
 
     /// Check if user has at least the specified role
@@ -463,23 +675,139 @@ impl BoardsPermissions {
         }
     }
 
-    /// Check if a user is banned from a board
-    pub fn is_banned(env: Env, board_id: u64, user: Address) -> bool {
-        // Check global ban first
-        if let Some(ban) = env
+    /// Lengthen an existing ban (moderator+).
+    /// The new duration counts from now; None makes the ban permanent.
+    pub fn extend_ban(
+        env: Env,
+        board_id: u64,
+        user: Address,
+        duration_hours: Option<u64>,
+        caller: Address,
+    ) {
+        caller.require_auth();
+
+        let caller_role = Self::get_role(env.clone(), board_id, caller);
+        if caller_role != Role::Owner
+            && caller_role != Role::Admin
+            && caller_role != Role::Moderator
+        {
+            panic!("Only moderator+ can extend bans");
+        }
+
+        let user_role = Self::get_role(env.clone(), board_id, user.clone());
+        if user_role as u32 >= caller_role as u32 {
+            panic!("Cannot ban user with equal or higher role");
+        }
+
+        let mut ban: Ban = env
             .storage()
             .persistent()
-            .get::<_, Ban>(&PermKey::GlobalBan(user.clone()))
+            .get(&PermKey::BoardBan(board_id, user.clone()))
+            .expect("User is not banned");
+
+        let Some(current) = ban.expires_at else {
+            panic!("Ban is already permanent");
+        };
+
+        let expires_at = duration_hours.map(|h| env.ledger().timestamp() + h * 3600);
+        if let Some(new_expiry) = expires_at {
+            if new_expiry <= current {
+                panic!("New expiry must be later than the current one");
+            }
+        }
+
+        ban.expires_at = expires_at;
+        env.storage()
+            .persistent()
+            .set(&PermKey::BoardBan(board_id, user), &ban);
+    }
+
+    /// Remove expired bans from a board (moderator+).
+    /// Returns the number of bans removed.
+    pub fn purge_expired_bans(env: Env, board_id: u64, caller: Address) -> u32 {
+        caller.require_auth();
+
+        let caller_role = Self::get_role(env.clone(), board_id, caller);
+        if caller_role != Role::Owner
+            && caller_role != Role::Admin
+            && caller_role != Role::Moderator
         {
-            if let Some(expires_at) = ban.expires_at {
-                if env.ledger().timestamp() < expires_at {
-                    return true;
-                }
+            panic!("Only moderator+ can unban users");
+        }
+
+        let banned: Vec<Address> = env
+            .storage()
+            .persistent()
+            .get(&PermKey::BannedUsers(board_id))
+            .unwrap_or(Vec::new(&env));
+
+        let now = env.ledger().timestamp();
+        let mut remaining = Vec::new(&env);
+        let mut purged = 0;
+
+        for i in 0..banned.len() {
+            let user = banned.get(i).unwrap();
+            let key = PermKey::BoardBan(board_id, user.clone());
+            let expired = match env.storage().persistent().get::<_, Ban>(&key) {
+                Some(ban) => matches!(ban.expires_at, Some(expires) if now >= expires),
+                None => true, // Stale list entry
+            };
+            if expired {
+                env.storage().persistent().remove(&key);
+                purged += 1;
             } else {
-                return true; // Permanent ban
+                remaining.push_back(user);
             }
         }
 
+        env.storage()
+            .persistent()
+            .set(&PermKey::BannedUsers(board_id), &remaining);
+
+        purged
+    }
+
+    /// Quarantine or release a user on a board (moderator+).
+    /// New threads and replies from a quarantined user start hidden.
+    pub fn set_quarantined(env: Env, board_id: u64, user: Address, on: bool, caller: Address) {
+        caller.require_auth();
+
+        let caller_role = Self::get_role(env.clone(), board_id, caller);
+        if caller_role != Role::Owner
+            && caller_role != Role::Admin
+            && caller_role != Role::Moderator
+        {
+            panic!("Only moderator+ can quarantine users");
+        }
+
+        let user_role = Self::get_role(env.clone(), board_id, user.clone());
+        if user_role as u32 >= caller_role as u32 {
+            panic!("Cannot quarantine user with equal or higher role");
+        }
+
+        let key = PermKey::Quarantined(board_id, user);
+        if on {
+            env.storage().persistent().set(&key, &true);
+        } else {
+            env.storage().persistent().remove(&key);
+        }
+    }
+
+    /// Check if a user is quarantined on a board
+    pub fn is_quarantined(env: Env, board_id: u64, user: Address) -> bool {
+        env.storage()
+            .persistent()
+            .get(&PermKey::Quarantined(board_id, user))
+            .unwrap_or(false)
+    }
+
+    /// Check if a user is banned from a board
+    pub fn is_banned(env: Env, board_id: u64, user: Address) -> bool {
+        // Check global ban first
+        if Self::is_globally_banned(&env, &user) {
+            return true;
+        }
+
         // Check board-specific ban
         if let Some(ban) = env
             .storage()
@@ -498,6 +826,55 @@ impl BoardsPermissions {
         false
     }
 
+    /// Check for a network-wide ban. The registry copies its ban list here, so
+    /// this reads local storage only and is safe when the registry is the caller.
+    fn is_globally_banned(env: &Env, user: &Address) -> bool {
+        match env
+            .storage()
+            .persistent()
+            .get::<_, Ban>(&PermKey::GlobalBan(user.clone()))
+        {
+            Some(ban) => match ban.expires_at {
+                Some(expires_at) => env.ledger().timestamp() < expires_at,
+                None => true,
+            },
+            None => false,
+        }
+    }
+
+    /// Record a registry global ban (registry only)
+    pub fn set_global_ban(env: Env, user: Address, reason: String, expires_at: Option<u64>) {
+        let registry = Self::require_registry(&env);
+        let ban = Ban {
+            user: user.clone(),
+            board_id: 0,
+            issuer: registry,
+            reason,
+            created_at: env.ledger().timestamp(),
+            expires_at,
+        };
+        env.storage()
+            .persistent()
+            .set(&PermKey::GlobalBan(user), &ban);
+    }
+
+    /// Lift a registry global ban (registry only)
+    pub fn clear_global_ban(env: Env, user: Address) {
+        Self::require_registry(&env);
+        env.storage().persistent().remove(&PermKey::GlobalBan(user));
+    }
+
+    /// Require the registry's authorization and return its address
+    fn require_registry(env: &Env) -> Address {
+        let registry: Address = env
+            .storage()
+            .instance()
+            .get(&PermKey::Registry)
+            .expect("Not initialized");
+        registry.require_auth();
+        registry
+    }
+
     /// Get ban info for a user
     pub fn get_ban(env: Env, board_id: u64, user: Address) -> Option<Ban> {
         env.storage()
@@ -530,10 +907,15 @@ impl BoardsPermissions {
     // Invite system functions
 
     /// Request an invite to join a board (user-initiated)
-    /// Only allowed for non-members who are not banned
-    pub fn request_invite(env: Env, board_id: u64, caller: Address) {
+    /// Only allowed for non-members who are not banned. The optional message
+    /// (max 500 chars) is shown to moderators reviewing the request.
+    pub fn request_invite(env: Env, board_id: u64, message: String, caller: Address) {
         caller.require_auth();
 
+        if message.len() > MAX_INVITE_MESSAGE_LEN {
+            panic!("Invite message too long (max 500 chars)");
+        }
+
         // Check user is not already a member
         let role = Self::get_role(env.clone(), board_id, caller.clone());
         if role as u32 >= Role::Member as u32 {
@@ -545,13 +927,12 @@ impl BoardsPermissions {
             panic!("Banned users cannot request invites");
         }
 
-        // Check for existing request
-        if env
-            .storage()
-            .persistent()
-            .has(&PermKey::InviteRequest(board_id, caller.clone()))
-        {
-            panic!("Invite request already pending");
+        // Check for existing request; an expired one may be replaced
+        if let Some(existing) = Self::get_invite_request(env.clone(), board_id, caller.clone()) {
+            if !Self::is_request_expired(&env, board_id, &existing) {
+                panic!("Invite request already pending");
+            }
+            Self::remove_invite_request(&env, board_id, &caller);
         }
 
         // Create the invite request
@@ -561,10 +942,17 @@ impl BoardsPermissions {
             created_at: env.ledger().timestamp(),
         };
 
-        // Store individual request
+        // Store individual request; the message is kept apart from the request record
         env.storage()
             .persistent()
             .set(&PermKey::InviteRequest(board_id, caller.clone()), &request);
+        let message_key = PermKey::InviteMessage(board_id, caller.clone());
+        if message.is_empty() {
+            // Don't let a message from an earlier request resurface
+            env.storage().persistent().remove(&message_key);
+        } else {
+            env.storage().persistent().set(&message_key, &message);
+        }
 
         // Add to requests list
         let mut requests: Vec<Address> = env
@@ -582,31 +970,56 @@ impl BoardsPermissions {
     /// Accept an invite request (promotes user to Member)
     /// Only Moderator+ can accept invites
     pub fn accept_invite(env: Env, board_id: u64, user: Address, caller: Address) {
+        Self::accept_invite_with_role(env, board_id, user, Role::Member, caller);
+    }
+
+    /// Accept an invite request, granting a specific role.
+    /// Moderator+ can grant Member, Admin+ can grant Moderator, and only the
+    /// owner can grant Admin.
+    pub fn accept_invite_with_role(
+        env: Env,
+        board_id: u64,
+        user: Address,
+        role: Role,
+        caller: Address,
+    ) {
         caller.require_auth();
 
-        // Check caller has authority (Moderator+)
+        // Check caller has authority for the requested role
         let caller_role = Self::get_role(env.clone(), board_id, caller.clone());
         if (caller_role as u32) < (Role::Moderator as u32) {
             panic!("Only moderator+ can accept invite requests");
         }
+        match role {
+            Role::Admin => {
+                if caller_role != Role::Owner {
+                    panic!("Only owner can accept invites as admin");
+                }
+            }
+            Role::Moderator => {
+                if (caller_role as u32) < (Role::Admin as u32) {
+                    panic!("Only admin+ can accept invites as moderator");
+                }
+            }
+            Role::Member => {}
+            _ => panic!("Invalid role for invite"),
+        }
 
-        // Check invite request exists
-        if !env
-            .storage()
-            .persistent()
-            .has(&PermKey::InviteRequest(board_id, user.clone()))
-        {
-            panic!("No invite request found for this user");
+        // Check invite request exists and is still live
+        let request = Self::get_invite_request(env.clone(), board_id, user.clone())
+            .expect("No invite request found for this user");
+        if Self::is_request_expired(&env, board_id, &request) {
+            panic!("Invite request has expired");
         }
 
         // Remove the invite request
         Self::remove_invite_request(&env, board_id, &user);
 
-        // Set user role to Member
-        Self::add_to_role_list(&env, board_id, &user, Role::Member);
+        // Set user role
+        Self::add_to_role_list(&env, board_id, &user, role);
         env.storage()
             .persistent()
-            .set(&PermKey::BoardRole(board_id, user), &Role::Member);
+            .set(&PermKey::BoardRole(board_id, user), &role);
     }
 
     /// Revoke/reject an invite request
@@ -694,10 +1107,13 @@ impl BoardsPermissions {
 
     /// Helper to remove an invite request
     fn remove_invite_request(env: &Env, board_id: u64, user: &Address) {
-        // Remove individual request
+        // Remove individual request and its message
         env.storage()
             .persistent()
             .remove(&PermKey::InviteRequest(board_id, user.clone()));
+        env.storage()
+            .persistent()
+            .remove(&PermKey::InviteMessage(board_id, user.clone()));
 
         // Remove from requests list
         if let Some(requests) = env
@@ -734,17 +1150,93 @@ impl BoardsPermissions {
                 .persistent()
                 .get::<_, InviteRequest>(&PermKey::InviteRequest(board_id, addr))
             {
-                requests.push_back(request);
+                if !Self::is_request_expired(&env, board_id, &request) {
+                    requests.push_back(request);
+                }
             }
         }
         requests
     }
 
-    /// Check if a user has a pending invite request
-    pub fn has_invite_request(env: Env, board_id: u64, user: Address) -> bool {
+    /// Set how long invite requests stay pending, in seconds (admin+)
+    /// A TTL of 0 means requests never expire.
+    pub fn set_invite_request_ttl(env: Env, board_id: u64, ttl_seconds: u64, caller: Address) {
+        caller.require_auth();
+
+        let caller_role = Self::get_role(env.clone(), board_id, caller);
+        if caller_role != Role::Owner && caller_role != Role::Admin {
+            panic!("Only admin+ can set invite request TTL");
+        }
+
+        env.storage()
+            .persistent()
+            .set(&PermKey::InviteRequestTtl(board_id), &ttl_seconds);
+    }
+
+    /// Get invite request TTL for a board in seconds (default: 0, never expires)
+    pub fn get_invite_request_ttl(env: Env, board_id: u64) -> u64 {
+        env.storage()
+            .persistent()
+            .get(&PermKey::InviteRequestTtl(board_id))
+            .unwrap_or(0)
+    }
+
+    /// Remove all expired invite requests for a board (moderator+)
+    /// Returns the number of requests removed.
+    pub fn purge_expired_requests(env: Env, board_id: u64, caller: Address) -> u32 {
+        caller.require_auth();
+
+        let caller_role = Self::get_role(env.clone(), board_id, caller);
+        if (caller_role as u32) < (Role::Moderator as u32) {
+            panic!("Only moderator+ can purge invite requests");
+        }
+
+        let request_addrs: Vec<Address> = env
+            .storage()
+            .persistent()
+            .get(&PermKey::InviteRequests(board_id))
+            .unwrap_or(Vec::new(&env));
+
+        let mut remaining = Vec::new(&env);
+        let mut purged = 0;
+
+        for i in 0..request_addrs.len() {
+            let user = request_addrs.get(i).unwrap();
+            let key = PermKey::InviteRequest(board_id, user.clone());
+            let expired = match env.storage().persistent().get::<_, InviteRequest>(&key) {
+                Some(request) => Self::is_request_expired(&env, board_id, &request),
+                None => true, // Stale list entry
+            };
+            if expired {
+                env.storage().persistent().remove(&key);
+                env.storage()
+                    .persistent()
+                    .remove(&PermKey::InviteMessage(board_id, user));
+                purged += 1;
+            } else {
+                remaining.push_back(user);
+            }
+        }
+
         env.storage()
             .persistent()
-            .has(&PermKey::InviteRequest(board_id, user))
+            .set(&PermKey::InviteRequests(board_id), &remaining);
+
+        purged
+    }
+
+    /// Helper to check whether an invite request is past the board's TTL
+    fn is_request_expired(env: &Env, board_id: u64, request: &InviteRequest) -> bool {
+        let ttl = Self::get_invite_request_ttl(env.clone(), board_id);
+        ttl > 0 && env.ledger().timestamp() >= request.created_at.saturating_add(ttl)
+    }
+
+    /// Check if a user has a pending invite request
+    pub fn has_invite_request(env: Env, board_id: u64, user: Address) -> bool {
+        match Self::get_invite_request(env.clone(), board_id, user) {
+            Some(request) => !Self::is_request_expired(&env, board_id, &request),
+            None => false,
+        }
     }
 
     /// Get a specific invite request
@@ -754,6 +1246,20 @@ impl BoardsPermissions {
             .get(&PermKey::InviteRequest(board_id, user))
     }
 
+    /// Get the messages attached to invite requests, one per user (empty if none)
+    pub fn get_invite_messages(env: Env, board_id: u64, users: Vec<Address>) -> Vec<String> {
+        let mut messages = Vec::new(&env);
+        for user in users.iter() {
+            messages.push_back(
+                env.storage()
+                    .persistent()
+                    .get(&PermKey::InviteMessage(board_id, user))
+                    .unwrap_or(String::from_str(&env, "")),
+            );
+        }
+        messages
+    }
+
     // Membership list functions
 
     /// Get list of admins for a board
@@ -780,11 +1286,94 @@ impl BoardsPermissions {
             .unwrap_or(Vec::new(&env))
     }
 
+    /// Get a page of members for a board, starting at index `start`.
+    /// Reads only the requested entries once the board's members are indexed.
+    pub fn list_members_page(env: Env, board_id: u64, start: u32, limit: u32) -> Vec<Address> {
+        if !Self::members_indexed(&env, board_id) {
+            let members = Self::list_members(env.clone(), board_id);
+            let end = start.saturating_add(limit).min(members.len());
+            if start >= end {
+                return Vec::new(&env);
+            }
+            return members.slice(start..end);
+        }
+
+        let end = start
+            .saturating_add(limit)
+            .min(Self::indexed_member_count(&env, board_id));
+        let mut page = Vec::new(&env);
+        for i in start..end {
+            if let Some(addr) = env
+                .storage()
+                .persistent()
+                .get(&PermKey::MemberAt(board_id, i))
+            {
+                page.push_back(addr);
+            }
+        }
+        page
+    }
+
+    /// Index a board's existing members so pages can be read by position (admin+).
+    /// Walks `limit` entries of the member list from `start` and returns where to
+    /// continue, or None once every member is indexed. Returns Some(0) when members
+    /// left part-way through and the walk has to start over.
+    pub fn index_members(
+        env: Env,
+        board_id: u64,
+        start: u32,
+        limit: u32,
+        caller: Address,
+    ) -> Option<u32> {
+        caller.require_auth();
+
+        let caller_role = Self::get_role(env.clone(), board_id, caller);
+        if caller_role != Role::Owner && caller_role != Role::Admin {
+            panic!("Only admin+ can index members");
+        }
+        if Self::members_indexed(&env, board_id) {
+            return None;
+        }
+
+        let members = Self::list_members(env.clone(), board_id);
+        let end = start.saturating_add(limit).min(members.len());
+        for i in start..end {
+            Self::index_member(&env, board_id, &members.get(i).unwrap());
+        }
+        if end < members.len() {
+            return Some(end);
+        }
+        if Self::indexed_member_count(&env, board_id) != members.len() {
+            return Some(0);
+        }
+        env.storage()
+            .persistent()
+            .set(&PermKey::MembersIndexed(board_id), &true);
+        None
+    }
+
+    fn members_indexed(env: &Env, board_id: u64) -> bool {
+        env.storage()
+            .persistent()
+            .get(&PermKey::MembersIndexed(board_id))
+            .unwrap_or(false)
+    }
+
+    fn indexed_member_count(env: &Env, board_id: u64) -> u32 {
+        env.storage()
+            .persistent()
+            .get(&PermKey::MemberCount(board_id))
+            .unwrap_or(0)
+    }
+
     /// Get count of members with a specific role
     pub fn role_count(env: Env, board_id: u64, role: Role) -> u32 {
         match role {
             Role::Admin => Self::list_admins(env, board_id).len(),
             Role::Moderator => Self::list_moderators(env, board_id).len(),
+            Role::Member if Self::members_indexed(&env, board_id) => {
+                Self::indexed_member_count(&env, board_id)
+            }
             Role::Member => Self::list_members(env, board_id).len(),
             _ => 0,
         }
@@ -874,9 +1463,18 @@ impl BoardsPermissions {
 
     // ==================== Community Permission Functions ====================
 
-    /// Set community owner (called when community is created)
+    /// Set community owner (called when community is created or changes hands).
+    /// A previous owner loses their community role.
+    /// Only the community contract registered with the registry may call this.
     pub fn set_community_owner(env: Env, community_id: u64, owner: Address) {
-        // TODO: Verify caller is registry or community contract
+        Self::require_community_contract(&env);
+        if let Some(previous) = Self::get_community_owner(env.clone(), community_id) {
+            if previous != owner {
+                env.storage()
+                    .persistent()
+                    .remove(&PermKey::CommunityRole(community_id, previous));
+            }
+        }
         env.storage()
             .persistent()
             .set(&PermKey::CommunityOwner(community_id), &owner);
@@ -886,6 +1484,44 @@ impl BoardsPermissions {
         );
     }
 
+    /// Record which community a board belongs to (None when it leaves one).
+    /// Only the community contract registered with the registry may call this.
+    pub fn set_board_community(env: Env, board_id: u64, community_id: Option<u64>) {
+        Self::require_community_contract(&env);
+
+        let key = PermKey::BoardCommunity(board_id);
+        match community_id {
+            Some(community_id) => env.storage().persistent().set(&key, &community_id),
+            None => env.storage().persistent().remove(&key),
+        }
+    }
+
+    /// Require auth from the community contract registered with the registry
+    fn require_community_contract(env: &Env) {
+        let registry: Address = env
+            .storage()
+            .instance()
+            .get(&PermKey::Registry)
+            .expect("Not initialized");
+        let alias_args: Vec<Val> =
+            Vec::from_array(env, [Symbol::new(env, "community").into_val(env)]);
+        let community: Option<Address> = env.invoke_contract(
+            &registry,
+            &Symbol::new(env, "get_contract_by_alias"),
+            alias_args,
+        );
+        community
+            .expect("Community contract not registered")
+            .require_auth();
+    }
+
+    /// Get the community a board belongs to, if any
+    pub fn get_board_community(env: Env, board_id: u64) -> Option<u64> {
+        env.storage()
+            .persistent()
+            .get(&PermKey::BoardCommunity(board_id))
+    }
+
     /// Get community owner
     pub fn get_community_owner(env: Env, community_id: u64) -> Option<Address> {
         env.storage()
@@ -1124,18 +1760,8 @@ impl BoardsPermissions {
     /// Check if a user is banned from a community
     pub fn is_community_banned(env: Env, community_id: u64, user: Address) -> bool {
         // Check global ban first
-        if let Some(ban) = env
-            .storage()
-            .persistent()
-            .get::<_, Ban>(&PermKey::GlobalBan(user.clone()))
-        {
-            if let Some(expires_at) = ban.expires_at {
-                if env.ledger().timestamp() < expires_at {
-                    return true;
-                }
-            } else {
-                return true; // Permanent ban
-            }
+        if Self::is_globally_banned(&env, &user) {
+            return true;
         }
 
         // Check community-specific ban
@@ -1281,25 +1907,15 @@ impl BoardsPermissions {
         user: Address,
     ) -> PermissionSet {
         // Check global ban first
-        if let Some(ban) = env
-            .storage()
-            .persistent()
-            .get::<_, Ban>(&PermKey::GlobalBan(user.clone()))
-        {
-            let is_banned = match ban.expires_at {
-                Some(expires) => env.ledger().timestamp() < expires,
-                None => true,
+        if Self::is_globally_banned(&env, &user) {
+            return PermissionSet {
+                role: Role::Guest,
+                can_view: false,
+                can_post: false,
+                can_moderate: false,
+                can_admin: false,
+                is_banned: true,
             };
-            if is_banned {
-                return PermissionSet {
-                    role: Role::Guest,
-                    can_view: false,
-                    can_post: false,
-                    can_moderate: false,
-                    can_admin: false,
-                    is_banned: true,
-                };
-            }
         }
 
         // If no community, just return board permissions
@@ -1598,19 +2214,28 @@ impl BoardsPermissions {
 
     /// Get the number of boards created by a user.
     pub fn get_user_board_count(env: Env, user: Address) -> u32 {
-        env.storage()
-            .instance()
-            .get(&PermKey::UserBoardCount(user))
-            .unwrap_or(0)
+        let key = PermKey::UserBoardCount(user);
+        if let Some(count) = env.storage().persistent().get(&key) {
+            return count;
+        }
+        // Older deployments kept this per-user count in instance storage,
+        // which grows the instance entry with every board creator.
+        env.storage().instance().get(&key).unwrap_or(0)
     }
 
     /// Increment a user's board count.
     /// Called after a board is successfully created.
     pub fn increment_user_board_count(env: Env, user: Address) {
         let count = Self::get_user_board_count(env.clone(), user.clone());
-        env.storage()
-            .instance()
-            .set(&PermKey::UserBoardCount(user), &(count + 1));
+        Self::store_user_board_count(&env, user, count + 1);
+    }
+
+    /// Write a user's board count to persistent storage, dropping any
+    /// legacy instance-storage copy.
+    fn store_user_board_count(env: &Env, user: Address, count: u32) {
+        let key = PermKey::UserBoardCount(user);
+        env.storage().persistent().set(&key, &count);
+        env.storage().instance().remove(&key);
     }
 
     // ============================================================
@@ -1674,9 +2299,7 @@ impl BoardsPermissions {
             panic!("Only site admins can migrate data");
         }
 
-        env.storage()
-            .instance()
-            .set(&PermKey::UserBoardCount(user), &count);
+        Self::store_user_board_count(&env, user, count);
     }
 
     // ============================================================
@@ -2007,7 +2630,7 @@ impl BoardsPermissions {
 #[cfg(test)]
 mod test {
     use super::*;
-    use soroban_sdk::testutils::Address as _;
+    use soroban_sdk::testutils::{Address as _, Ledger as _};
     use soroban_sdk::Env;
 
     #[test]
@@ -2263,6 +2886,93 @@ mod test {
         assert_eq!(client.role_count(&0, &Role::Member), 1);
     }
 
+    #[test]
+    fn test_list_members_page() {
+        let env = Env::default();
+        env.mock_all_auths();
+
+        let contract_id = env.register(BoardsPermissions, ());
+        let client = BoardsPermissionsClient::new(&env, &contract_id);
+
+        let registry = Address::generate(&env);
+        client.init(&registry);
+
+        let owner = Address::generate(&env);
+        client.set_board_owner(&0, &owner);
+
+        let mut members = Vec::new(&env);
+        for _ in 0..5 {
+            let member = Address::generate(&env);
+            client.set_role(&0, &member, &Role::Member, &owner);
+            members.push_back(member);
+        }
+
+        // Full pages, a partial last page, and pages past the end
+        let page = client.list_members_page(&0, &0, &2);
+        assert_eq!(page.len(), 2);
+        assert_eq!(page.get(0).unwrap(), members.get(0).unwrap());
+        let page = client.list_members_page(&0, &2, &2);
+        assert_eq!(page.get(1).unwrap(), members.get(3).unwrap());
+        let page = client.list_members_page(&0, &4, &2);
+        assert_eq!(page.len(), 1);
+        assert_eq!(page.get(0).unwrap(), members.get(4).unwrap());
+        assert_eq!(client.list_members_page(&0, &5, &2).len(), 0);
+        assert_eq!(client.list_members_page(&0, &u32::MAX, &u32::MAX).len(), 0);
+        assert_eq!(client.list_members_page(&0, &0, &0).len(), 0);
+
+        // Removing a member moves the last one into its slot
+        client.set_role(&0, &members.get(1).unwrap(), &Role::Guest, &owner);
+        assert_eq!(client.role_count(&0, &Role::Member), 4);
+        let page = client.list_members_page(&0, &0, &5);
+        assert_eq!(page.len(), 4);
+        assert_eq!(page.get(1).unwrap(), members.get(4).unwrap());
+    }
+
+    #[test]
+    fn test_index_members_backfills_older_boards() {
+        let env = Env::default();
+        env.mock_all_auths();
+
+        let contract_id = env.register(BoardsPermissions, ());
+        let client = BoardsPermissionsClient::new(&env, &contract_id);
+        client.init(&Address::generate(&env));
+
+        let owner = Address::generate(&env);
+        client.set_board_owner(&0, &owner);
+
+        // A board whose member list predates the index
+        let mut members = Vec::new(&env);
+        for _ in 0..5 {
+            members.push_back(Address::generate(&env));
+        }
+        env.as_contract(&contract_id, || {
+            env.storage()
+                .persistent()
+                .set(&PermKey::BoardMembers(0), &members);
+        });
+
+        // Pages still come from the list until the backfill finishes
+        assert_eq!(client.list_members_page(&0, &3, &2).len(), 2);
+        let result = client.try_index_members(&0, &0, &2, &Address::generate(&env));
+        assert!(result.is_err());
+        assert_eq!(client.index_members(&0, &0, &2, &owner), Some(2));
+        assert_eq!(client.index_members(&0, &2, &2, &owner), Some(4));
+
+        // Members who join mid-walk are indexed as they arrive
+        let late = Address::generate(&env);
+        client.set_role(&0, &late, &Role::Member, &owner);
+        members.push_back(late);
+        assert_eq!(client.index_members(&0, &4, &2, &owner), None);
+
+        assert_eq!(client.role_count(&0, &Role::Member), 6);
+        let page = client.list_members_page(&0, &0, &10);
+        assert_eq!(page.len(), 6);
+        for i in 0..6 {
+            assert!(page.contains(members.get(i).unwrap()));
+        }
+        assert_eq!(client.index_members(&0, &0, &2, &owner), None);
+    }
+
     #[test]
     fn test_helper_functions() {
         let env = Env::default();
@@ -2487,138 +3197,393 @@ mod test {
     // Invite system tests
 
     #[test]
-    fn test_request_invite() {
+    fn test_ownership_transfer_swaps_roles() {
         let env = Env::default();
         env.mock_all_auths();
 
         let contract_id = env.register(BoardsPermissions, ());
         let client = BoardsPermissionsClient::new(&env, &contract_id);
-
-        let registry = Address::generate(&env);
-        client.init(&registry);
+        client.init(&Address::generate(&env));
 
         let owner = Address::generate(&env);
-        let user = Address::generate(&env);
-
+        let new_owner = Address::generate(&env);
         client.set_board_owner(&0, &owner);
+        client.set_role(&0, &new_owner, &Role::Moderator, &owner);
 
-        // User requests invite
-        client.request_invite(&0, &user);
+        client.propose_board_owner(&0, &new_owner, &owner);
+        assert_eq!(client.get_pending_board_owner(&0), Some(new_owner.clone()));
 
-        // Check request exists
-        assert!(client.has_invite_request(&0, &user));
-        let requests = client.list_invite_requests(&0);
-        assert_eq!(requests.len(), 1);
-        assert_eq!(requests.get(0).unwrap().user, user);
+        // Nothing changes until the transfer is accepted
+        assert_eq!(client.get_role(&0, &owner), Role::Owner);
+
+        client.accept_board_ownership(&0, &new_owner);
+
+        assert_eq!(client.get_board_owner(&0), Some(new_owner.clone()));
+        assert_eq!(client.get_role(&0, &new_owner), Role::Owner);
+        assert_eq!(client.get_role(&0, &owner), Role::Admin);
+        assert_eq!(client.get_pending_board_owner(&0), None);
+        assert!(client.list_admins(&0).contains(&owner));
+        assert!(!client.list_moderators(&0).contains(&new_owner));
     }
 
     #[test]
-    fn test_accept_invite() {
+    #[should_panic(expected = "Only the proposed owner can accept the transfer")]
+    fn test_ownership_transfer_wrong_accepter() {
         let env = Env::default();
         env.mock_all_auths();
 
         let contract_id = env.register(BoardsPermissions, ());
         let client = BoardsPermissionsClient::new(&env, &contract_id);
-
-        let registry = Address::generate(&env);
-        client.init(&registry);
+        client.init(&Address::generate(&env));
 
         let owner = Address::generate(&env);
-        let user = Address::generate(&env);
-
+        let new_owner = Address::generate(&env);
+        let other = Address::generate(&env);
         client.set_board_owner(&0, &owner);
 
-        // User requests invite
-        client.request_invite(&0, &user);
-        assert!(client.has_invite_request(&0, &user));
-
-        // Owner accepts invite
-        client.accept_invite(&0, &user, &owner);
-
-        // Check user is now member
-        assert_eq!(client.get_role(&0, &user), Role::Member);
-        assert!(!client.has_invite_request(&0, &user));
-        assert_eq!(client.list_members(&0).len(), 1);
+        client.propose_board_owner(&0, &new_owner, &owner);
+        client.accept_board_ownership(&0, &other);
     }
 
     #[test]
-    fn test_revoke_invite() {
+    fn test_ownership_transfer_cancel() {
         let env = Env::default();
         env.mock_all_auths();
 
         let contract_id = env.register(BoardsPermissions, ());
         let client = BoardsPermissionsClient::new(&env, &contract_id);
-
-        let registry = Address::generate(&env);
-        client.init(&registry);
+        client.init(&Address::generate(&env));
 
         let owner = Address::generate(&env);
-        let user = Address::generate(&env);
-
+        let new_owner = Address::generate(&env);
         client.set_board_owner(&0, &owner);
 
-        // User requests invite
-        client.request_invite(&0, &user);
-        assert!(client.has_invite_request(&0, &user));
+        client.propose_board_owner(&0, &new_owner, &owner);
+        client.cancel_board_owner_transfer(&0, &owner);
+        assert_eq!(client.get_pending_board_owner(&0), None);
 
-        // Owner revokes invite
-        client.revoke_invite(&0, &user, &owner);
+        // Accepting a cancelled transfer fails and ownership is unchanged
+        let result = client.try_accept_board_ownership(&0, &new_owner);
+        assert!(result.is_err());
+        assert_eq!(client.get_board_owner(&0), Some(owner.clone()));
+        assert_eq!(client.get_role(&0, &owner), Role::Owner);
 
-        // Check request is gone, user is still guest
-        assert!(!client.has_invite_request(&0, &user));
-        assert_eq!(client.get_role(&0, &user), Role::Guest);
+        // Moderators cannot cancel the owner's transfer; the proposed owner can decline
+        let moderator = Address::generate(&env);
+        client.set_role(&0, &moderator, &Role::Moderator, &owner);
+        client.propose_board_owner(&0, &new_owner, &owner);
+        let result = client.try_cancel_board_owner_transfer(&0, &moderator);
+        assert!(result.is_err());
+        assert_eq!(client.get_pending_board_owner(&0), Some(new_owner.clone()));
+        client.cancel_board_owner_transfer(&0, &new_owner);
+        assert_eq!(client.get_pending_board_owner(&0), None);
     }
 
     #[test]
-    fn test_invite_member_directly() {
+    fn test_expired_ban_restores_posting() {
         let env = Env::default();
         env.mock_all_auths();
+        env.ledger().set_timestamp(1_700_000_000);
 
         let contract_id = env.register(BoardsPermissions, ());
         let client = BoardsPermissionsClient::new(&env, &contract_id);
-
-        let registry = Address::generate(&env);
-        client.init(&registry);
+        client.init(&Address::generate(&env));
 
         let owner = Address::generate(&env);
-        let user = Address::generate(&env);
-
+        let member = Address::generate(&env);
         client.set_board_owner(&0, &owner);
+        client.set_role(&0, &member, &Role::Member, &owner);
 
-        // Owner directly invites as member
-        client.invite_member(&0, &user, &Role::Member, &owner);
+        // Two hour ban
+        let reason = String::from_str(&env, "Cool off");
+        client.ban_user(&0, &member, &reason, &Some(2), &owner);
+        assert!(client.is_banned(&0, &member));
+        assert!(!client.can_create_thread(&0, &member));
+        assert!(!client.can_reply(&0, &member));
 
-        // Check user is now member
-        assert_eq!(client.get_role(&0, &user), Role::Member);
-        assert_eq!(client.list_members(&0).len(), 1);
+        // One hour later the ban still applies
+        env.ledger().set_timestamp(1_700_000_000 + 3600);
+        assert!(!client.can_reply(&0, &member));
+
+        // Past expiry the member can post again
+        env.ledger().set_timestamp(1_700_000_000 + 2 * 3600);
+        assert!(!client.is_banned(&0, &member));
+        assert!(client.can_create_thread(&0, &member));
+        assert!(client.can_reply(&0, &member));
     }
 
     #[test]
-    fn test_invite_member_with_role() {
+    fn test_extend_ban() {
         let env = Env::default();
         env.mock_all_auths();
+        env.ledger().set_timestamp(1_700_000_000);
 
         let contract_id = env.register(BoardsPermissions, ());
         let client = BoardsPermissionsClient::new(&env, &contract_id);
-
-        let registry = Address::generate(&env);
-        client.init(&registry);
+        client.init(&Address::generate(&env));
 
         let owner = Address::generate(&env);
-        let new_admin = Address::generate(&env);
-        let new_mod = Address::generate(&env);
-
+        let member = Address::generate(&env);
         client.set_board_owner(&0, &owner);
+        client.set_role(&0, &member, &Role::Member, &owner);
 
-        // Owner invites as admin
-        client.invite_member(&0, &new_admin, &Role::Admin, &owner);
-        assert_eq!(client.get_role(&0, &new_admin), Role::Admin);
-        assert_eq!(client.list_admins(&0).len(), 1);
+        let reason = String::from_str(&env, "Cool off");
+        client.ban_user(&0, &member, &reason, &Some(2), &owner);
 
-        // Owner invites as moderator
-        client.invite_member(&0, &new_mod, &Role::Moderator, &owner);
-        assert_eq!(client.get_role(&0, &new_mod), Role::Moderator);
-        assert_eq!(client.list_moderators(&0).len(), 1);
+        // Extending keeps the original reason and issuer
+        client.extend_ban(&0, &member, &Some(24), &owner);
+        let ban = client.get_ban(&0, &member).unwrap();
+        assert_eq!(ban.expires_at, Some(1_700_000_000 + 24 * 3600));
+        assert_eq!(ban.reason, reason);
+        assert_eq!(ban.issuer, owner);
+
+        env.ledger().set_timestamp(1_700_000_000 + 3 * 3600);
+        assert!(client.is_banned(&0, &member));
+
+        // None makes it permanent
+        client.extend_ban(&0, &member, &None, &owner);
+        assert_eq!(client.get_ban(&0, &member).unwrap().expires_at, None);
+    }
+
+    #[test]
+    #[should_panic(expected = "New expiry must be later than the current one")]
+    fn test_extend_ban_cannot_shorten() {
+        let env = Env::default();
+        env.mock_all_auths();
+        env.ledger().set_timestamp(1_700_000_000);
+
+        let contract_id = env.register(BoardsPermissions, ());
+        let client = BoardsPermissionsClient::new(&env, &contract_id);
+        client.init(&Address::generate(&env));
+
+        let owner = Address::generate(&env);
+        let member = Address::generate(&env);
+        client.set_board_owner(&0, &owner);
+        client.set_role(&0, &member, &Role::Member, &owner);
+
+        client.ban_user(
+            &0,
+            &member,
+            &String::from_str(&env, "Spam"),
+            &Some(48),
+            &owner,
+        );
+        client.extend_ban(&0, &member, &Some(1), &owner);
+    }
+
+    #[test]
+    fn test_purge_expired_bans() {
+        let env = Env::default();
+        env.mock_all_auths();
+        env.ledger().set_timestamp(1_700_000_000);
+
+        let contract_id = env.register(BoardsPermissions, ());
+        let client = BoardsPermissionsClient::new(&env, &contract_id);
+        client.init(&Address::generate(&env));
+
+        let owner = Address::generate(&env);
+        let short_ban = Address::generate(&env);
+        let permanent_ban = Address::generate(&env);
+        client.set_board_owner(&0, &owner);
+
+        let reason = String::from_str(&env, "Spam");
+        client.ban_user(&0, &short_ban, &reason, &Some(1), &owner);
+        client.ban_user(&0, &permanent_ban, &reason, &None, &owner);
+
+        // Nothing has expired yet
+        assert_eq!(client.purge_expired_bans(&0, &owner), 0);
+        assert_eq!(client.list_bans(&0).len(), 2);
+
+        env.ledger().set_timestamp(1_700_000_000 + 3600);
+        assert_eq!(client.purge_expired_bans(&0, &owner), 1);
+
+        let bans = client.list_bans(&0);
+        assert_eq!(bans.len(), 1);
+        assert_eq!(bans.get(0).unwrap().user, permanent_ban);
+        assert!(client.get_ban(&0, &short_ban).is_none());
+    }
+
+    #[test]
+    fn test_quarantine_toggle() {
+        let env = Env::default();
+        env.mock_all_auths();
+
+        let contract_id = env.register(BoardsPermissions, ());
+        let client = BoardsPermissionsClient::new(&env, &contract_id);
+        client.init(&Address::generate(&env));
+
+        let owner = Address::generate(&env);
+        let moderator = Address::generate(&env);
+        let member = Address::generate(&env);
+        client.set_board_owner(&0, &owner);
+        client.set_role(&0, &moderator, &Role::Moderator, &owner);
+        client.set_role(&0, &member, &Role::Member, &owner);
+
+        assert!(!client.is_quarantined(&0, &member));
+        client.set_quarantined(&0, &member, &true, &moderator);
+        assert!(client.is_quarantined(&0, &member));
+        assert!(!client.is_quarantined(&1, &member));
+
+        // Quarantine does not block posting - posts are just hidden on creation
+        assert!(client.can_reply(&0, &member));
+
+        client.set_quarantined(&0, &member, &false, &moderator);
+        assert!(!client.is_quarantined(&0, &member));
+    }
+
+    #[test]
+    #[should_panic(expected = "Cannot quarantine user with equal or higher role")]
+    fn test_cannot_quarantine_equal_role() {
+        let env = Env::default();
+        env.mock_all_auths();
+
+        let contract_id = env.register(BoardsPermissions, ());
+        let client = BoardsPermissionsClient::new(&env, &contract_id);
+        client.init(&Address::generate(&env));
+
+        let owner = Address::generate(&env);
+        let mod1 = Address::generate(&env);
+        let mod2 = Address::generate(&env);
+        client.set_board_owner(&0, &owner);
+        client.set_role(&0, &mod1, &Role::Moderator, &owner);
+        client.set_role(&0, &mod2, &Role::Moderator, &owner);
+
+        client.set_quarantined(&0, &mod2, &true, &mod1);
+    }
+
+    #[test]
+    fn test_request_invite() {
+        let env = Env::default();
+        env.mock_all_auths();
+
+        let contract_id = env.register(BoardsPermissions, ());
+        let client = BoardsPermissionsClient::new(&env, &contract_id);
+
+        let registry = Address::generate(&env);
+        client.init(&registry);
+
+        let owner = Address::generate(&env);
+        let user = Address::generate(&env);
+
+        client.set_board_owner(&0, &owner);
+
+        // User requests invite
+        client.request_invite(&0, &String::from_str(&env, ""), &user);
+
+        // Check request exists
+        assert!(client.has_invite_request(&0, &user));
+        let requests = client.list_invite_requests(&0);
+        assert_eq!(requests.len(), 1);
+        assert_eq!(requests.get(0).unwrap().user, user);
+    }
+
+    #[test]
+    fn test_accept_invite() {
+        let env = Env::default();
+        env.mock_all_auths();
+
+        let contract_id = env.register(BoardsPermissions, ());
+        let client = BoardsPermissionsClient::new(&env, &contract_id);
+
+        let registry = Address::generate(&env);
+        client.init(&registry);
+
+        let owner = Address::generate(&env);
+        let user = Address::generate(&env);
+
+        client.set_board_owner(&0, &owner);
+
+        // User requests invite
+        client.request_invite(&0, &String::from_str(&env, ""), &user);
+        assert!(client.has_invite_request(&0, &user));
+
+        // Owner accepts invite
+        client.accept_invite(&0, &user, &owner);
+
+        // Check user is now member
+        assert_eq!(client.get_role(&0, &user), Role::Member);
+        assert!(!client.has_invite_request(&0, &user));
+        assert_eq!(client.list_members(&0).len(), 1);
+    }
+
+    #[test]
+    fn test_revoke_invite() {
+        let env = Env::default();
+        env.mock_all_auths();
+
+        let contract_id = env.register(BoardsPermissions, ());
+        let client = BoardsPermissionsClient::new(&env, &contract_id);
+
+        let registry = Address::generate(&env);
+        client.init(&registry);
+
+        let owner = Address::generate(&env);
+        let user = Address::generate(&env);
+
+        client.set_board_owner(&0, &owner);
+
+        // User requests invite
+        client.request_invite(&0, &String::from_str(&env, ""), &user);
+        assert!(client.has_invite_request(&0, &user));
+
+        // Owner revokes invite
+        client.revoke_invite(&0, &user, &owner);
+
+        // Check request is gone, user is still guest
+        assert!(!client.has_invite_request(&0, &user));
+        assert_eq!(client.get_role(&0, &user), Role::Guest);
+    }
+
+    #[test]
+    fn test_invite_member_directly() {
+        let env = Env::default();
+        env.mock_all_auths();
+
+        let contract_id = env.register(BoardsPermissions, ());
+        let client = BoardsPermissionsClient::new(&env, &contract_id);
+
+        let registry = Address::generate(&env);
+        client.init(&registry);
+
+        let owner = Address::generate(&env);
+        let user = Address::generate(&env);
+
+        client.set_board_owner(&0, &owner);
+
+        // Owner directly invites as member
+        client.invite_member(&0, &user, &Role::Member, &owner);
+
+        // Check user is now member
+        assert_eq!(client.get_role(&0, &user), Role::Member);
+        assert_eq!(client.list_members(&0).len(), 1);
+    }
+
+    #[test]
+    fn test_invite_member_with_role() {
+        let env = Env::default();
+        env.mock_all_auths();
+
+        let contract_id = env.register(BoardsPermissions, ());
+        let client = BoardsPermissionsClient::new(&env, &contract_id);
+
+        let registry = Address::generate(&env);
+        client.init(&registry);
+
+        let owner = Address::generate(&env);
+        let new_admin = Address::generate(&env);
+        let new_mod = Address::generate(&env);
+
+        client.set_board_owner(&0, &owner);
+
+        // Owner invites as admin
+        client.invite_member(&0, &new_admin, &Role::Admin, &owner);
+        assert_eq!(client.get_role(&0, &new_admin), Role::Admin);
+        assert_eq!(client.list_admins(&0).len(), 1);
+
+        // Owner invites as moderator
+        client.invite_member(&0, &new_mod, &Role::Moderator, &owner);
+        assert_eq!(client.get_role(&0, &new_mod), Role::Moderator);
+        assert_eq!(client.list_moderators(&0).len(), 1);
     }
 
     #[test]
@@ -2638,7 +3603,7 @@ mod test {
         client.set_board_owner(&0, &owner);
 
         // User requests invite
-        client.request_invite(&0, &user);
+        client.request_invite(&0, &String::from_str(&env, ""), &user);
         assert!(client.has_invite_request(&0, &user));
 
         // Owner directly invites (bypasses request-accept flow)
@@ -2668,7 +3633,7 @@ mod test {
         client.set_role(&0, &member, &Role::Member, &owner);
 
         // Member cannot request invite - already a member
-        client.request_invite(&0, &member);
+        client.request_invite(&0, &String::from_str(&env, ""), &member);
     }
 
     #[test]
@@ -2691,7 +3656,7 @@ mod test {
         client.ban_user(&0, &banned_user, &reason, &None, &owner);
 
         // Banned user cannot request invite
-        client.request_invite(&0, &banned_user);
+        client.request_invite(&0, &String::from_str(&env, ""), &banned_user);
     }
 
     #[test]
@@ -2712,10 +3677,10 @@ mod test {
         client.set_board_owner(&0, &owner);
 
         // First request succeeds
-        client.request_invite(&0, &user);
+        client.request_invite(&0, &String::from_str(&env, ""), &user);
 
         // Second request should fail
-        client.request_invite(&0, &user);
+        client.request_invite(&0, &String::from_str(&env, ""), &user);
     }
 
     #[test]
@@ -2737,7 +3702,7 @@ mod test {
         client.set_board_owner(&0, &owner);
         client.set_role(&0, &member, &Role::Member, &owner);
 
-        client.request_invite(&0, &user);
+        client.request_invite(&0, &String::from_str(&env, ""), &user);
 
         // Member cannot accept - should panic
         client.accept_invite(&0, &user, &member);
@@ -2784,13 +3749,202 @@ mod test {
         client.set_board_owner(&0, &owner);
         client.set_role(&0, &moderator, &Role::Moderator, &owner);
 
-        client.request_invite(&0, &user);
+        client.request_invite(&0, &String::from_str(&env, ""), &user);
 
         // Moderator can accept
         client.accept_invite(&0, &user, &moderator);
         assert_eq!(client.get_role(&0, &user), Role::Member);
     }
 
+    #[test]
+    fn test_accept_invite_with_role() {
+        let env = Env::default();
+        env.mock_all_auths();
+
+        let contract_id = env.register(BoardsPermissions, ());
+        let client = BoardsPermissionsClient::new(&env, &contract_id);
+
+        let registry = Address::generate(&env);
+        client.init(&registry);
+
+        let owner = Address::generate(&env);
+        let admin = Address::generate(&env);
+        let user = Address::generate(&env);
+
+        client.set_board_owner(&0, &owner);
+        client.set_role(&0, &admin, &Role::Admin, &owner);
+
+        client.request_invite(&0, &String::from_str(&env, ""), &user);
+
+        // Admin can accept as moderator
+        client.accept_invite_with_role(&0, &user, &Role::Moderator, &admin);
+        assert_eq!(client.get_role(&0, &user), Role::Moderator);
+        assert!(!client.has_invite_request(&0, &user));
+        assert_eq!(client.list_moderators(&0).len(), 1);
+    }
+
+    #[test]
+    #[should_panic(expected = "Only admin+ can accept invites as moderator")]
+    fn test_mod_cannot_accept_invite_as_mod() {
+        let env = Env::default();
+        env.mock_all_auths();
+
+        let contract_id = env.register(BoardsPermissions, ());
+        let client = BoardsPermissionsClient::new(&env, &contract_id);
+
+        let registry = Address::generate(&env);
+        client.init(&registry);
+
+        let owner = Address::generate(&env);
+        let moderator = Address::generate(&env);
+        let user = Address::generate(&env);
+
+        client.set_board_owner(&0, &owner);
+        client.set_role(&0, &moderator, &Role::Moderator, &owner);
+
+        client.request_invite(&0, &String::from_str(&env, ""), &user);
+
+        // Moderator cannot grant moderator - should panic
+        client.accept_invite_with_role(&0, &user, &Role::Moderator, &moderator);
+    }
+
+    #[test]
+    fn test_invite_request_message() {
+        let env = Env::default();
+        env.mock_all_auths();
+
+        let contract_id = env.register(BoardsPermissions, ());
+        let client = BoardsPermissionsClient::new(&env, &contract_id);
+        client.init(&Address::generate(&env));
+
+        let owner = Address::generate(&env);
+        let user = Address::generate(&env);
+        client.set_board_owner(&0, &owner);
+
+        let message = String::from_str(&env, "I run the local meetup");
+        client.request_invite(&0, &message, &user);
+
+        assert!(client.get_invite_request(&0, &user).is_some());
+        let users = Vec::from_array(&env, [user.clone(), Address::generate(&env)]);
+        let messages = client.get_invite_messages(&0, &users);
+        assert_eq!(messages.get(0).unwrap(), message);
+        assert!(messages.get(1).unwrap().is_empty());
+
+        // The message goes with the request
+        client.revoke_invite(&0, &user, &owner);
+        let messages = client.get_invite_messages(&0, &users);
+        assert!(messages.get(0).unwrap().is_empty());
+    }
+
+    #[test]
+    #[should_panic(expected = "Invite message too long (max 500 chars)")]
+    fn test_invite_request_message_too_long() {
+        let env = Env::default();
+        env.mock_all_auths();
+
+        let contract_id = env.register(BoardsPermissions, ());
+        let client = BoardsPermissionsClient::new(&env, &contract_id);
+        client.init(&Address::generate(&env));
+
+        let owner = Address::generate(&env);
+        client.set_board_owner(&0, &owner);
+
+        let message = String::from_bytes(&env, &[b'a'; 501]);
+        client.request_invite(&0, &message, &Address::generate(&env));
+    }
+
+    #[test]
+    fn test_invite_request_expiry() {
+        let env = Env::default();
+        env.mock_all_auths();
+        env.ledger().set_timestamp(1_700_000_000);
+
+        let contract_id = env.register(BoardsPermissions, ());
+        let client = BoardsPermissionsClient::new(&env, &contract_id);
+        client.init(&Address::generate(&env));
+
+        let owner = Address::generate(&env);
+        let stale = Address::generate(&env);
+        let fresh = Address::generate(&env);
+        client.set_board_owner(&0, &owner);
+        client.set_invite_request_ttl(&0, &3600, &owner);
+
+        let empty = String::from_str(&env, "");
+        client.request_invite(&0, &String::from_str(&env, "Please let me in"), &stale);
+        env.ledger().set_timestamp(1_700_000_000 + 1800);
+        client.request_invite(&0, &empty, &fresh);
+
+        // Stale request is past the TTL and no longer listed
+        env.ledger().set_timestamp(1_700_000_000 + 3600);
+        assert!(!client.has_invite_request(&0, &stale));
+        let requests = client.list_invite_requests(&0);
+        assert_eq!(requests.len(), 1);
+        assert_eq!(requests.get(0).unwrap().user, fresh);
+
+        assert_eq!(client.purge_expired_requests(&0, &owner), 1);
+        assert!(client.get_invite_request(&0, &stale).is_none());
+        let message_of = |user: &Address| {
+            let users = Vec::from_array(&env, [user.clone()]);
+            client.get_invite_messages(&0, &users).get(0).unwrap()
+        };
+        assert!(message_of(&stale).is_empty());
+
+        // Expired requesters may ask again, without the old message
+        client.request_invite(&0, &empty, &stale);
+        assert!(client.has_invite_request(&0, &stale));
+        assert!(message_of(&stale).is_empty());
+
+        // A message left over from before purges removed them is cleared too
+        let returning = Address::generate(&env);
+        env.as_contract(&contract_id, || {
+            env.storage().persistent().set(
+                &PermKey::InviteMessage(0, returning.clone()),
+                &String::from_str(&env, "old"),
+            );
+        });
+        client.request_invite(&0, &empty, &returning);
+        assert!(message_of(&returning).is_empty());
+    }
+
+    #[test]
+    fn test_leave_board() {
+        let env = Env::default();
+        env.mock_all_auths();
+
+        let contract_id = env.register(BoardsPermissions, ());
+        let client = BoardsPermissionsClient::new(&env, &contract_id);
+        client.init(&Address::generate(&env));
+
+        let owner = Address::generate(&env);
+        let member = Address::generate(&env);
+        client.set_board_owner(&0, &owner);
+        client.set_role(&0, &member, &Role::Member, &owner);
+
+        client.leave_board(&0, &member);
+        assert_eq!(client.get_role(&0, &member), Role::Guest);
+        assert_eq!(client.list_members(&0).len(), 0);
+
+        // A former member can ask to rejoin
+        client.request_invite(&0, &String::from_str(&env, ""), &member);
+        assert!(client.has_invite_request(&0, &member));
+    }
+
+    #[test]
+    #[should_panic(expected = "Owner must transfer ownership before leaving the board")]
+    fn test_owner_cannot_leave_board() {
+        let env = Env::default();
+        env.mock_all_auths();
+
+        let contract_id = env.register(BoardsPermissions, ());
+        let client = BoardsPermissionsClient::new(&env, &contract_id);
+        client.init(&Address::generate(&env));
+
+        let owner = Address::generate(&env);
+        client.set_board_owner(&0, &owner);
+
+        client.leave_board(&0, &owner);
+    }
+
     #[test]
     fn test_get_invite_request() {
         let env = Env::default();
@@ -2806,7 +3960,7 @@ mod test {
         let user = Address::generate(&env);
 
         client.set_board_owner(&0, &owner);
-        client.request_invite(&0, &user);
+        client.request_invite(&0, &String::from_str(&env, ""), &user);
 
         // Get the invite request
         let request = client.get_invite_request(&0, &user).unwrap();
@@ -2821,11 +3975,7 @@ mod test {
         let env = Env::default();
         env.mock_all_auths();
 
-        let contract_id = env.register(BoardsPermissions, ());
-        let client = BoardsPermissionsClient::new(&env, &contract_id);
-
-        let registry = Address::generate(&env);
-        client.init(&registry);
+        let (client, _) = setup_with_community(&env);
 
         let owner = Address::generate(&env);
 
@@ -2835,6 +3985,12 @@ mod test {
         // Owner should have Owner role
         assert_eq!(client.get_community_owner(&0), Some(owner.clone()));
         assert_eq!(client.get_community_role(&0, &owner), Role::Owner);
+
+        // Handing the community over drops the previous owner's role
+        let new_owner = Address::generate(&env);
+        client.set_community_owner(&0, &new_owner);
+        assert_eq!(client.get_community_role(&0, &new_owner), Role::Owner);
+        assert_eq!(client.get_community_role(&0, &owner), Role::Guest);
     }
 
     #[test]
@@ -2842,11 +3998,7 @@ mod test {
         let env = Env::default();
         env.mock_all_auths();
 
-        let contract_id = env.register(BoardsPermissions, ());
-        let client = BoardsPermissionsClient::new(&env, &contract_id);
-
-        let registry = Address::generate(&env);
-        client.init(&registry);
+        let (client, _) = setup_with_community(&env);
 
         let owner = Address::generate(&env);
         let admin = Address::generate(&env);
@@ -2878,11 +4030,7 @@ mod test {
         let env = Env::default();
         env.mock_all_auths();
 
-        let contract_id = env.register(BoardsPermissions, ());
-        let client = BoardsPermissionsClient::new(&env, &contract_id);
-
-        let registry = Address::generate(&env);
-        client.init(&registry);
+        let (client, _) = setup_with_community(&env);
 
         let owner = Address::generate(&env);
         let admin1 = Address::generate(&env);
@@ -2909,11 +4057,7 @@ mod test {
         let env = Env::default();
         env.mock_all_auths();
 
-        let contract_id = env.register(BoardsPermissions, ());
-        let client = BoardsPermissionsClient::new(&env, &contract_id);
-
-        let registry = Address::generate(&env);
-        client.init(&registry);
+        let (client, _) = setup_with_community(&env);
 
         let owner = Address::generate(&env);
         let user = Address::generate(&env);
@@ -2941,11 +4085,7 @@ mod test {
         let env = Env::default();
         env.mock_all_auths();
 
-        let contract_id = env.register(BoardsPermissions, ());
-        let client = BoardsPermissionsClient::new(&env, &contract_id);
-
-        let registry = Address::generate(&env);
-        client.init(&registry);
+        let (client, _) = setup_with_community(&env);
 
         let owner = Address::generate(&env);
         let user = Address::generate(&env);
@@ -2967,11 +4107,7 @@ mod test {
         let env = Env::default();
         env.mock_all_auths();
 
-        let contract_id = env.register(BoardsPermissions, ());
-        let client = BoardsPermissionsClient::new(&env, &contract_id);
-
-        let registry = Address::generate(&env);
-        client.init(&registry);
+        let (client, _) = setup_with_community(&env);
 
         let owner = Address::generate(&env);
         let admin = Address::generate(&env);
@@ -3021,11 +4157,7 @@ mod test {
         let env = Env::default();
         env.mock_all_auths();
 
-        let contract_id = env.register(BoardsPermissions, ());
-        let client = BoardsPermissionsClient::new(&env, &contract_id);
-
-        let registry = Address::generate(&env);
-        client.init(&registry);
+        let (client, _) = setup_with_community(&env);
 
         let community_owner = Address::generate(&env);
         let community_mod = Address::generate(&env);
@@ -3052,11 +4184,7 @@ mod test {
         let env = Env::default();
         env.mock_all_auths();
 
-        let contract_id = env.register(BoardsPermissions, ());
-        let client = BoardsPermissionsClient::new(&env, &contract_id);
-
-        let registry = Address::generate(&env);
-        client.init(&registry);
+        let (client, _) = setup_with_community(&env);
 
         let community_owner = Address::generate(&env);
         let community_mod = Address::generate(&env);
@@ -3086,11 +4214,7 @@ mod test {
         let env = Env::default();
         env.mock_all_auths();
 
-        let contract_id = env.register(BoardsPermissions, ());
-        let client = BoardsPermissionsClient::new(&env, &contract_id);
-
-        let registry = Address::generate(&env);
-        client.init(&registry);
+        let (client, _) = setup_with_community(&env);
 
         let community_owner = Address::generate(&env);
         let board_owner = Address::generate(&env);
@@ -3150,11 +4274,7 @@ mod test {
         let env = Env::default();
         env.mock_all_auths();
 
-        let contract_id = env.register(BoardsPermissions, ());
-        let client = BoardsPermissionsClient::new(&env, &contract_id);
-
-        let registry = Address::generate(&env);
-        client.init(&registry);
+        let (client, _) = setup_with_community(&env);
 
         let owner = Address::generate(&env);
         let moderator = Address::generate(&env);
@@ -3185,11 +4305,7 @@ mod test {
         let env = Env::default();
         env.mock_all_auths();
 
-        let contract_id = env.register(BoardsPermissions, ());
-        let client = BoardsPermissionsClient::new(&env, &contract_id);
-
-        let registry = Address::generate(&env);
-        client.init(&registry);
+        let (client, _) = setup_with_community(&env);
 
         let owner = Address::generate(&env);
         let moderator = Address::generate(&env);
@@ -3207,11 +4323,7 @@ mod test {
         let env = Env::default();
         env.mock_all_auths();
 
-        let contract_id = env.register(BoardsPermissions, ());
-        let client = BoardsPermissionsClient::new(&env, &contract_id);
-
-        let registry = Address::generate(&env);
-        client.init(&registry);
+        let (client, _) = setup_with_community(&env);
 
         let owner = Address::generate(&env);
         let user = Address::generate(&env);
@@ -3234,11 +4346,7 @@ mod test {
         let env = Env::default();
         env.mock_all_auths();
 
-        let contract_id = env.register(BoardsPermissions, ());
-        let client = BoardsPermissionsClient::new(&env, &contract_id);
-
-        let registry = Address::generate(&env);
-        client.init(&registry);
+        let (client, _) = setup_with_community(&env);
 
         let owner1 = Address::generate(&env);
         let owner2 = Address::generate(&env);
@@ -3342,4 +4450,274 @@ mod test {
         assert!(client.get_first_seen(&user1).is_some());
         assert!(client.get_first_seen(&user2).is_some());
     }
+
+    /// Minimal registry that resolves the "community" alias
+    #[contract]
+    pub struct MockRegistry;
+
+    #[contractimpl]
+    impl MockRegistry {
+        pub fn set_community(env: Env, community: Address) {
+            env.storage()
+                .instance()
+                .set(&Symbol::new(&env, "community"), &community);
+        }
+
+        pub fn get_contract_by_alias(env: Env, alias: Symbol) -> Option<Address> {
+            env.storage().instance().get(&alias)
+        }
+
+        pub fn global_ban(env: Env, permissions: Address, user: Address) {
+            let args: Vec<Val> = Vec::from_array(
+                &env,
+                [
+                    user.into_val(&env),
+                    String::from_str(&env, "spam").into_val(&env),
+                    None::<u64>.into_val(&env),
+                ],
+            );
+            env.invoke_contract::<()>(&permissions, &Symbol::new(&env, "set_global_ban"), args);
+        }
+
+        /// Ask permissions about a user while the registry is mid-call
+        pub fn can_post(env: Env, permissions: Address, board_id: u64, user: Address) -> bool {
+            env.invoke_contract(
+                &permissions,
+                &Symbol::new(&env, "can_create_thread"),
+                Vec::from_array(&env, [board_id.into_val(&env), user.into_val(&env)]),
+            )
+        }
+    }
+
+    /// Minimal board contract that gates thread locks the way the board does
+    #[contract]
+    pub struct MockBoard;
+
+    #[contractimpl]
+    impl MockBoard {
+        pub fn lock_thread(
+            env: Env,
+            permissions: Address,
+            board_id: u64,
+            thread_id: u64,
+            caller: Address,
+        ) {
+            let args: Vec<Val> =
+                Vec::from_array(&env, [board_id.into_val(&env), caller.into_val(&env)]);
+            let can_moderate: bool =
+                env.invoke_contract(&permissions, &Symbol::new(&env, "can_moderate"), args);
+            if !can_moderate {
+                panic!("Not authorized to moderate");
+            }
+            env.storage().instance().set(&(board_id, thread_id), &true);
+        }
+
+        pub fn is_locked(env: Env, board_id: u64, thread_id: u64) -> bool {
+            env.storage().instance().has(&(board_id, thread_id))
+        }
+    }
+
+    /// Permissions wired to a registry whose "community" alias is `community`
+    fn setup_with_community(env: &Env) -> (BoardsPermissionsClient<'_>, Address) {
+        let registry = MockRegistryClient::new(env, &env.register(MockRegistry, ()));
+        let community = Address::generate(env);
+        registry.set_community(&community);
+        let client = BoardsPermissionsClient::new(env, &env.register(BoardsPermissions, ()));
+        client.init(&registry.address);
+        (client, community)
+    }
+
+    #[test]
+    fn test_community_moderator_can_moderate_member_board() {
+        let env = Env::default();
+        env.mock_all_auths();
+        let (client, _) = setup_with_community(&env);
+
+        let owner = Address::generate(&env);
+        let community_owner = Address::generate(&env);
+        let team_mod = Address::generate(&env);
+        let admin = Address::generate(&env);
+        client.set_board_owner(&0, &owner);
+        client.set_board_owner(&1, &owner);
+        client.set_role(&0, &admin, &Role::Admin, &owner);
+        client.set_community_owner(&7, &community_owner);
+        client.set_community_role(&7, &team_mod, &Role::Moderator, &community_owner);
+        client.set_community_role(&7, &admin, &Role::Moderator, &community_owner);
+        client.set_board_community(&0, &Some(7));
+        assert_eq!(client.get_board_community(&0), Some(7));
+
+        // Never added to board 0 directly, yet can moderate there
+        assert_eq!(client.get_role(&0, &team_mod), Role::Moderator);
+        assert!(client.can_moderate(&0, &team_mod));
+        assert!(!client.can_admin(&0, &team_mod));
+        assert_eq!(client.get_role(&0, &community_owner), Role::Moderator);
+
+        // Board 1 is outside the community
+        assert_eq!(client.get_role(&1, &team_mod), Role::Guest);
+        assert!(!client.can_moderate(&1, &team_mod));
+
+        // A higher board role is not lowered by the community lookup
+        assert_eq!(client.get_role(&0, &admin), Role::Admin);
+
+        // Board bans still apply to community moderators
+        let reason = String::from_str(&env, "Abuse");
+        client.ban_user(&0, &team_mod, &reason, &None, &owner);
+        assert!(!client.can_moderate(&0, &team_mod));
+        client.unban_user(&0, &team_mod, &owner);
+
+        // Leaving the community drops the cascaded role
+        client.set_board_community(&0, &None);
+        assert_eq!(client.get_role(&0, &team_mod), Role::Guest);
+    }
+
+    #[test]
+    fn test_community_moderator_locks_thread_on_member_board() {
+        let env = Env::default();
+        env.mock_all_auths();
+        let (client, _) = setup_with_community(&env);
+        let board = MockBoardClient::new(&env, &env.register(MockBoard, ()));
+
+        let owner = Address::generate(&env);
+        let community_owner = Address::generate(&env);
+        let team_mod = Address::generate(&env);
+        client.set_board_owner(&0, &owner);
+        client.set_community_owner(&7, &community_owner);
+        client.set_community_role(&7, &team_mod, &Role::Moderator, &community_owner);
+        client.set_board_community(&0, &Some(7));
+
+        board.lock_thread(&client.address, &0, &3, &team_mod);
+        assert!(board.is_locked(&0, &3));
+    }
+
+    #[test]
+    #[should_panic(expected = "Not authorized to moderate")]
+    fn test_community_moderator_cannot_lock_thread_outside_community() {
+        let env = Env::default();
+        env.mock_all_auths();
+        let (client, _) = setup_with_community(&env);
+        let board = MockBoardClient::new(&env, &env.register(MockBoard, ()));
+
+        let community_owner = Address::generate(&env);
+        let team_mod = Address::generate(&env);
+        client.set_board_owner(&1, &Address::generate(&env));
+        client.set_community_owner(&7, &community_owner);
+        client.set_community_role(&7, &team_mod, &Role::Moderator, &community_owner);
+        client.set_board_community(&0, &Some(7));
+
+        board.lock_thread(&client.address, &1, &3, &team_mod);
+    }
+
+    #[test]
+    fn test_only_community_contract_sets_board_community() {
+        let env = Env::default();
+        let (client, community) = setup_with_community(&env);
+
+        // Nobody else can move a board into a community
+        assert!(client.try_set_board_community(&0, &Some(7)).is_err());
+
+        env.mock_all_auths();
+        client.set_board_community(&0, &Some(7));
+        assert_eq!(env.auths()[0].0, community);
+        assert_eq!(client.get_board_community(&0), Some(7));
+    }
+
+    #[test]
+    fn test_only_community_contract_sets_community_owner() {
+        let env = Env::default();
+        let (client, community) = setup_with_community(&env);
+        let intruder = Address::generate(&env);
+
+        // Nobody else can claim a community
+        assert!(client.try_set_community_owner(&7, &intruder).is_err());
+        assert_eq!(client.get_community_owner(&7), None);
+
+        env.mock_all_auths();
+        client.set_community_owner(&7, &intruder);
+        assert_eq!(env.auths()[0].0, community);
+        assert_eq!(client.get_community_owner(&7), Some(intruder));
+    }
+
+    #[test]
+    fn test_global_ban_blocks_posting_on_every_board() {
+        let env = Env::default();
+        env.mock_all_auths();
+
+        let registry = MockRegistryClient::new(&env, &env.register(MockRegistry, ()));
+        let client = BoardsPermissionsClient::new(&env, &env.register(BoardsPermissions, ()));
+        client.init(&registry.address);
+
+        let owner = Address::generate(&env);
+        let spammer = Address::generate(&env);
+        for board_id in [0u64, 1] {
+            client.set_board_owner(&board_id, &owner);
+            client.set_role(&board_id, &spammer, &Role::Member, &owner);
+            assert!(client.can_create_thread(&board_id, &spammer));
+        }
+
+        registry.global_ban(&client.address, &spammer);
+        for board_id in [0u64, 1] {
+            assert!(!client.can_create_thread(&board_id, &spammer));
+            assert!(!client.can_reply(&board_id, &spammer));
+            assert!(client.get_permissions(&board_id, &spammer).is_banned);
+        }
+        assert!(client.is_community_banned(&7, &spammer));
+
+        // The ban still holds when the registry itself is asking
+        assert!(!registry.can_post(&client.address, &0, &spammer));
+
+        // Lifting the registry ban restores posting everywhere
+        client.clear_global_ban(&spammer);
+        assert!(client.can_create_thread(&0, &spammer));
+        assert!(client.can_reply(&1, &spammer));
+
+        // Timed bans lapse on their own
+        client.set_global_ban(&spammer, &String::from_str(&env, "spam"), &Some(1_000));
+        assert!(!client.can_create_thread(&0, &spammer));
+        env.ledger().set_timestamp(1_000);
+        assert!(client.can_create_thread(&0, &spammer));
+    }
+
+    #[test]
+    fn test_only_registry_sets_global_bans() {
+        let env = Env::default();
+
+        let registry = Address::generate(&env);
+        let client = BoardsPermissionsClient::new(&env, &env.register(BoardsPermissions, ()));
+        client.init(&registry);
+
+        let spammer = Address::generate(&env);
+        let reason = String::from_str(&env, "spam");
+        assert!(client.try_set_global_ban(&spammer, &reason, &None).is_err());
+        assert!(client.try_clear_global_ban(&spammer).is_err());
+
+        env.mock_all_auths();
+        client.set_global_ban(&spammer, &reason, &None);
+        assert_eq!(env.auths()[0].0, registry);
+        assert!(!client.can_reply(&0, &spammer));
+    }
+
+    #[test]
+    fn test_user_board_count_moves_out_of_instance_storage() {
+        let env = Env::default();
+        env.mock_all_auths();
+
+        let contract_id = env.register(BoardsPermissions, ());
+        let client = BoardsPermissionsClient::new(&env, &contract_id);
+        client.init(&Address::generate(&env));
+
+        // Simulate a count written by an older deployment
+        let user = Address::generate(&env);
+        let key = PermKey::UserBoardCount(user.clone());
+        env.as_contract(&contract_id, || {
+            env.storage().instance().set(&key, &2u32);
+        });
+        assert_eq!(client.get_user_board_count(&user), 2);
+
+        client.increment_user_board_count(&user);
+        assert_eq!(client.get_user_board_count(&user), 3);
+        env.as_contract(&contract_id, || {
+            assert!(!env.storage().instance().has(&key));
+            assert_eq!(env.storage().persistent().get(&key), Some(3u32));
+        });
+    }
 }
@@ -9,6 +9,13 @@
 //! The registry enables contracts to find each other by alias:
 //! - Contract address registration by alias (e.g., "perms" -> Address)
 //! - Alias lookups via `get_contract_by_alias` / `get_contract`
+//! - A named catalog of theme contracts boards can choose from
+//! - A last-activity cache per board, for the board index
+//! - A ring buffer of the newest threads across all boards, for the home page
+//! - Board categories for grouping the home page
+//! - A network-wide ban list every board honors
+//! - Per-account board creation limits (cap and cooldown)
+//! - Board name rules and a case-insensitive name index (former names stay as aliases)
 //! - Board contract address discovery by ID
 //! - WASM hash storage for deploying new board contracts
 //!
@@ -21,7 +28,8 @@
 //! - Permissions/roles → boards-permissions
 
 use soroban_sdk::{
-    contract, contractimpl, contracttype, Address, Bytes, BytesN, Env, IntoVal, Symbol, Val, Vec,
+    contract, contracterror, contractimpl, contracttype, panic_with_error, Address, Bytes, BytesN,
+    Env, EnvBase, IntoVal, Map, String, Symbol, Val, Vec,
 };
 
 // Note: Board contract mapping (BoardContract, BoardContractCount, BoardWasmHash) removed.
@@ -43,6 +51,108 @@ pub enum RegistryKey {
     Contract(Symbol),
     /// Global pause flag
     Paused,
+    /// Last WASM hash installed through the registry, per contract address
+    ContractWasm(Address),
+    /// Theme catalog: name -> theme contract address (Map<String, Address>)
+    Themes,
+    /// Ledger timestamp of the last thread or reply on a board, reported by the board contract
+    BoardActivity(u64),
+    /// Newest threads across all boards, a ring buffer of up to RECENT_ACTIVITY_CAP entries
+    RecentActivity,
+    /// Slot in RecentActivity the next entry overwrites once the buffer is full (u32)
+    RecentActivityNext,
+    /// Highest category id handed out so far (ids start at 1)
+    CategoryCount,
+    /// Category name by id
+    Category(u32),
+    /// Category a board is filed under (absent = uncategorized)
+    BoardCategory(u64),
+    /// Board ids in a category, ascending (Vec<u64>)
+    CategoryBoards(u32),
+    /// Network-wide ban for a user
+    GlobalBan(Address),
+    /// Users with a global ban on record, oldest first (Vec<Address>)
+    GlobalBans,
+    /// Most boards one account may create (u32, 0 = unlimited)
+    BoardsPerAccount,
+    /// Minimum seconds between one account's board creations (u64)
+    BoardCooldown,
+    /// Boards created by an account, oldest first (Vec<u64>)
+    CreatorBoards(Address),
+    /// Ledger timestamp of an account's last board creation
+    LastBoardCreated(Address),
+    /// Language a board declared, reported by the board contract (Symbol, e.g. "de")
+    BoardLocale(u64),
+    /// Board currently holding a name, keyed by the lowercased name
+    BoardByName(String),
+    /// Board a former name still resolves to, keyed by the lowercased name
+    BoardNameAlias(String),
+    /// Lowercased current name of a board
+    BoardName(u64),
+}
+
+/// Errors that can occur in the registry contract
+#[contracterror]
+#[derive(Copy, Clone, Debug, Eq, PartialEq, PartialOrd, Ord)]
+#[repr(u32)]
+pub enum RegistryError {
+    /// The account has created as many boards as the per-account cap allows
+    BoardCapReached = 1,
+    /// The account created a board too recently
+    BoardCooldownActive = 2,
+    /// The board name is shorter than 3 or longer than 50 bytes
+    BoardNameLength = 3,
+    /// The board name is a reserved word (a route segment or service alias)
+    BoardNameReserved = 4,
+    /// Another board already has this name, ignoring case
+    BoardNameTaken = 5,
+    /// The name is a former name of another board, which still resolves to it
+    BoardNameIsAlias = 6,
+    /// The board name starts or ends with a dash
+    BoardNameEdgeDash = 7,
+}
+
+/// Maximum length of a category name
+const MAX_CATEGORY_NAME_LEN: u32 = 32;
+
+/// Shortest and longest board name, in bytes
+const MIN_BOARD_NAME_LEN: u32 = 3;
+const MAX_BOARD_NAME_LEN: u32 = 50;
+
+/// Names no board may take (compared lowercased): route segments and service aliases
+const RESERVED_BOARD_NAMES: [&[u8]; 8] = [
+    b"admin",
+    b"new",
+    b"b",
+    b"c",
+    b"t",
+    b"help",
+    b"profile",
+    b"registry",
+];
+
+/// Entries kept in the cross-board recent activity feed
+const RECENT_ACTIVITY_CAP: u32 = 50;
+
+/// Longest thread title (in bytes) stored in the recent activity feed
+const MAX_ACTIVITY_TITLE_LEN: usize = 128;
+
+/// A new thread, as listed in the home page's recent activity feed
+#[contracttype]
+#[derive(Clone, Debug, PartialEq)]
+pub struct ActivityEntry {
+    pub board_id: u64,
+    pub thread_id: u64,
+    pub title: String,
+    pub created_at: u64,
+}
+
+/// A named group of boards on the home page
+#[contracttype]
+#[derive(Clone, Debug, PartialEq)]
+pub struct Category {
+    pub id: u32,
+    pub name: String,
 }
 
 /// Addresses of shared service contracts (legacy, for backwards compatibility)
@@ -55,6 +165,18 @@ pub struct ContractAddresses {
     pub admin: Address,
 }
 
+/// A network-wide ban, honored by the permissions contract on every board
+#[contracttype]
+#[derive(Clone, Debug, PartialEq)]
+pub struct GlobalBan {
+    pub user: Address,
+    pub issuer: Address,
+    pub reason: String,
+    pub created_at: u64,
+    /// None = permanent
+    pub expires_at: Option<u64>,
+}
+
 #[contract]
 pub struct BoardsRegistry;
 
@@ -232,6 +354,789 @@ impl BoardsRegistry {
             .set(&RegistryKey::Contract(alias), &address);
     }
 
+    // =========================================================================
+    // Theme Catalog
+    // =========================================================================
+
+    /// Add or replace a named theme contract (admin only).
+    ///
+    /// Boards pick a theme by name; the "theme" alias stays the default for
+    /// boards that haven't picked one or whose theme was unregistered.
+    pub fn register_theme(env: Env, name: String, address: Address, caller: Address) {
+        Self::require_admin_auth(&env, &caller);
+
+        if name.is_empty() || name.len() > 32 {
+            panic!("Theme name must be 1-32 characters");
+        }
+
+        let mut themes = Self::list_themes(env.clone());
+        themes.set(name, address);
+        env.storage().instance().set(&RegistryKey::Themes, &themes);
+    }
+
+    /// Remove a named theme (admin only). Boards using it fall back to the default.
+    pub fn unregister_theme(env: Env, name: String, caller: Address) {
+        Self::require_admin_auth(&env, &caller);
+
+        let mut themes = Self::list_themes(env.clone());
+        themes.remove(name);
+        env.storage().instance().set(&RegistryKey::Themes, &themes);
+    }
+
+    /// Get a theme contract by name
+    pub fn get_theme(env: Env, name: String) -> Option<Address> {
+        Self::list_themes(env).get(name)
+    }
+
+    /// Get all registered themes, ordered by name
+    pub fn list_themes(env: Env) -> Map<String, Address> {
+        env.storage()
+            .instance()
+            .get(&RegistryKey::Themes)
+            .unwrap_or(Map::new(&env))
+    }
+
+    // =========================================================================
+    // Board Activity
+    // =========================================================================
+
+    /// Record that a board just had a new thread or reply.
+    /// Only the registered "board" contract may report activity.
+    pub fn report_board_activity(env: Env, board_id: u64) {
+        let board: Address = env
+            .storage()
+            .instance()
+            .get(&RegistryKey::Contract(Symbol::new(&env, "board")))
+            .expect("Board contract not registered");
+        board.require_auth();
+
+        env.storage().persistent().set(
+            &RegistryKey::BoardActivity(board_id),
+            &env.ledger().timestamp(),
+        );
+    }
+
+    /// Get when a board last had a new thread or reply (None if never reported)
+    pub fn get_board_activity(env: Env, board_id: u64) -> Option<u64> {
+        env.storage()
+            .persistent()
+            .get(&RegistryKey::BoardActivity(board_id))
+    }
+
+    /// Last-activity timestamps for several boards at once (0 if never reported)
+    pub fn get_board_activities(env: Env, board_ids: Vec<u64>) -> Vec<u64> {
+        let mut activities = Vec::new(&env);
+        for board_id in board_ids.iter() {
+            activities.push_back(Self::get_board_activity(env.clone(), board_id).unwrap_or(0));
+        }
+        activities
+    }
+
+    /// Record the locale a board declared, or None when it clears it.
+    /// Only the registered "board" contract may report locales.
+    pub fn report_board_locale(env: Env, board_id: u64, locale: Option<Symbol>) {
+        let board: Address = env
+            .storage()
+            .instance()
+            .get(&RegistryKey::Contract(Symbol::new(&env, "board")))
+            .expect("Board contract not registered");
+        board.require_auth();
+
+        match locale {
+            Some(locale) => env
+                .storage()
+                .persistent()
+                .set(&RegistryKey::BoardLocale(board_id), &locale),
+            None => env
+                .storage()
+                .persistent()
+                .remove(&RegistryKey::BoardLocale(board_id)),
+        }
+    }
+
+    /// Get the locale a board declared (None if it hasn't)
+    pub fn get_board_locale(env: Env, board_id: u64) -> Option<Symbol> {
+        env.storage()
+            .persistent()
+            .get(&RegistryKey::BoardLocale(board_id))
+    }
+
+    /// Locales for several boards at once, so board lists can group or filter by language
+    pub fn get_board_locales(env: Env, board_ids: Vec<u64>) -> Vec<Option<Symbol>> {
+        let mut locales = Vec::new(&env);
+        for board_id in board_ids.iter() {
+            locales.push_back(Self::get_board_locale(env.clone(), board_id));
+        }
+        locales
+    }
+
+    /// Add a new thread to the cross-board recent activity feed.
+    /// Only the registered "board" contract may report; once the feed holds
+    /// RECENT_ACTIVITY_CAP entries each report overwrites the oldest.
+    pub fn report_activity(
+        env: Env,
+        board_id: u64,
+        thread_id: u64,
+        title: String,
+        created_at: u64,
+    ) {
+        let board: Address = env
+            .storage()
+            .instance()
+            .get(&RegistryKey::Contract(Symbol::new(&env, "board")))
+            .expect("Board contract not registered");
+        board.require_auth();
+
+        // Keep the stored title short, cutting on a character boundary
+        let title = if title.len() as usize > MAX_ACTIVITY_TITLE_LEN {
+            let mut buf = [0u8; MAX_ACTIVITY_TITLE_LEN + 1];
+            env.string_copy_to_slice(title.to_object(), Val::U32_ZERO, &mut buf)
+                .unwrap();
+            let mut len = MAX_ACTIVITY_TITLE_LEN;
+            while len > 0 && buf[len] & 0xC0 == 0x80 {
+                len -= 1;
+            }
+            String::from_bytes(&env, &buf[..len])
+        } else {
+            title
+        };
+        let entry = ActivityEntry {
+            board_id,
+            thread_id,
+            title,
+            created_at,
+        };
+
+        let mut entries: Vec<ActivityEntry> = env
+            .storage()
+            .persistent()
+            .get(&RegistryKey::RecentActivity)
+            .unwrap_or(Vec::new(&env));
+        if entries.len() < RECENT_ACTIVITY_CAP {
+            entries.push_back(entry);
+        } else {
+            let next: u32 = env
+                .storage()
+                .persistent()
+                .get(&RegistryKey::RecentActivityNext)
+                .unwrap_or(0);
+            entries.set(next, entry);
+            env.storage().persistent().set(
+                &RegistryKey::RecentActivityNext,
+                &((next + 1) % RECENT_ACTIVITY_CAP),
+            );
+        }
+        env.storage()
+            .persistent()
+            .set(&RegistryKey::RecentActivity, &entries);
+    }
+
+    /// Newest threads across all boards, newest first (at most `limit`).
+    /// Entries are not filtered; callers drop those from private or unlisted boards.
+    pub fn list_recent_activity(env: Env, limit: u32) -> Vec<ActivityEntry> {
+        let entries: Vec<ActivityEntry> = env
+            .storage()
+            .persistent()
+            .get(&RegistryKey::RecentActivity)
+            .unwrap_or(Vec::new(&env));
+        // While filling, the newest entry is last; once full, it sits just before `next`
+        let newest = if entries.len() < RECENT_ACTIVITY_CAP {
+            entries.len()
+        } else {
+            env.storage()
+                .persistent()
+                .get(&RegistryKey::RecentActivityNext)
+                .unwrap_or(0)
+        };
+
+        let mut recent = Vec::new(&env);
+        let count = entries.len().min(limit);
+        for i in 0..count {
+            let idx = (newest + entries.len() - 1 - i) % entries.len();
+            recent.push_back(entries.get_unchecked(idx));
+        }
+        recent
+    }
+
+    // =========================================================================
+    // Board Categories
+    // =========================================================================
+
+    /// Create a category for grouping boards on the home page (admin only).
+    /// Returns the new category's id; 0 is reserved for "uncategorized".
+    pub fn create_category(env: Env, name: String, caller: Address) -> u32 {
+        Self::require_admin_auth(&env, &caller);
+        Self::validate_category_name(&name);
+
+        let id: u32 = env
+            .storage()
+            .instance()
+            .get(&RegistryKey::CategoryCount)
+            .unwrap_or(0)
+            + 1;
+        env.storage()
+            .instance()
+            .set(&RegistryKey::CategoryCount, &id);
+        env.storage()
+            .persistent()
+            .set(&RegistryKey::Category(id), &name);
+        id
+    }
+
+    /// Rename a category (admin only)
+    pub fn rename_category(env: Env, category_id: u32, name: String, caller: Address) {
+        Self::require_admin_auth(&env, &caller);
+        Self::validate_category_name(&name);
+        Self::require_category(&env, category_id);
+
+        env.storage()
+            .persistent()
+            .set(&RegistryKey::Category(category_id), &name);
+    }
+
+    /// Delete an empty category (admin only). Boards must be moved out first.
+    pub fn delete_category(env: Env, category_id: u32, caller: Address) {
+        Self::require_admin_auth(&env, &caller);
+        Self::require_category(&env, category_id);
+
+        if !Self::category_boards(&env, category_id).is_empty() {
+            panic!("Category still has boards");
+        }
+
+        env.storage()
+            .persistent()
+            .remove(&RegistryKey::Category(category_id));
+        env.storage()
+            .persistent()
+            .remove(&RegistryKey::CategoryBoards(category_id));
+    }
+
+    /// Get a category by id
+    pub fn get_category(env: Env, category_id: u32) -> Option<Category> {
+        env.storage()
+            .persistent()
+            .get(&RegistryKey::Category(category_id))
+            .map(|name| Category {
+                id: category_id,
+                name,
+            })
+    }
+
+    /// Get all categories, in creation order
+    pub fn list_categories(env: Env) -> Vec<Category> {
+        let count: u32 = env
+            .storage()
+            .instance()
+            .get(&RegistryKey::CategoryCount)
+            .unwrap_or(0);
+
+        let mut categories = Vec::new(&env);
+        for id in 1..=count {
+            if let Some(category) = Self::get_category(env.clone(), id) {
+                categories.push_back(category);
+            }
+        }
+        categories
+    }
+
+    /// File a board under a category, or pass 0 to uncategorize it.
+    /// Allowed for registry admins and the board's admins.
+    pub fn set_board_category(env: Env, board_id: u64, category_id: u32, caller: Address) {
+        caller.require_auth();
+
+        if !Self::is_admin(env.clone(), caller.clone()) {
+            let permissions: Address = env
+                .storage()
+                .instance()
+                .get(&RegistryKey::Contract(Symbol::new(&env, "perms")))
+                .expect("Not initialized");
+            let can_admin: bool = env.invoke_contract(
+                &permissions,
+                &Symbol::new(&env, "can_admin"),
+                Vec::from_array(&env, [board_id.into_val(&env), caller.into_val(&env)]),
+            );
+            if !can_admin {
+                panic!("Caller must be board admin or registry admin");
+            }
+        }
+
+        if category_id != 0 {
+            Self::require_category(&env, category_id);
+        }
+
+        let previous = Self::get_board_category(env.clone(), board_id);
+        if previous == category_id {
+            return;
+        }
+
+        if previous != 0 {
+            let mut boards = Self::category_boards(&env, previous);
+            if let Ok(pos) = boards.binary_search(board_id) {
+                boards.remove(pos);
+            }
+            env.storage()
+                .persistent()
+                .set(&RegistryKey::CategoryBoards(previous), &boards);
+        }
+
+        if category_id == 0 {
+            env.storage()
+                .persistent()
+                .remove(&RegistryKey::BoardCategory(board_id));
+            return;
+        }
+
+        let mut boards = Self::category_boards(&env, category_id);
+        if let Err(pos) = boards.binary_search(board_id) {
+            boards.insert(pos, board_id);
+        }
+        env.storage()
+            .persistent()
+            .set(&RegistryKey::CategoryBoards(category_id), &boards);
+        env.storage()
+            .persistent()
+            .set(&RegistryKey::BoardCategory(board_id), &category_id);
+    }
+
+    /// Get the category a board is filed under (0 if uncategorized)
+    pub fn get_board_category(env: Env, board_id: u64) -> u32 {
+        env.storage()
+            .persistent()
+            .get(&RegistryKey::BoardCategory(board_id))
+            .unwrap_or(0)
+    }
+
+    /// Categories for several boards at once (0 if uncategorized)
+    pub fn get_board_categories(env: Env, board_ids: Vec<u64>) -> Vec<u32> {
+        let mut categories = Vec::new(&env);
+        for board_id in board_ids.iter() {
+            categories.push_back(Self::get_board_category(env.clone(), board_id));
+        }
+        categories
+    }
+
+    /// List board ids in a category, ascending, with pagination
+    pub fn list_boards_in_category(env: Env, category_id: u32, start: u32, limit: u32) -> Vec<u64> {
+        let boards = Self::category_boards(&env, category_id);
+        let end = start.saturating_add(limit).min(boards.len());
+        if start >= end {
+            return Vec::new(&env);
+        }
+        boards.slice(start..end)
+    }
+
+    fn category_boards(env: &Env, category_id: u32) -> Vec<u64> {
+        env.storage()
+            .persistent()
+            .get(&RegistryKey::CategoryBoards(category_id))
+            .unwrap_or(Vec::new(env))
+    }
+
+    fn require_category(env: &Env, category_id: u32) {
+        if !env
+            .storage()
+            .persistent()
+            .has(&RegistryKey::Category(category_id))
+        {
+            panic!("Category not found");
+        }
+    }
+
+    fn validate_category_name(name: &String) {
+        if name.is_empty() || name.len() > MAX_CATEGORY_NAME_LEN {
+            panic!("Category name must be 1-32 characters");
+        }
+    }
+
+    // =========================================================================
+    // Global Bans
+    // =========================================================================
+
+    /// Ban a user from every board (admin only). Re-banning replaces the old ban.
+    pub fn global_ban(
+        env: Env,
+        user: Address,
+        reason: String,
+        expires_at: Option<u64>,
+        caller: Address,
+    ) {
+        Self::require_admin_auth(&env, &caller);
+
+        if Self::is_admin(env.clone(), user.clone()) {
+            panic!("Cannot ban a registry admin");
+        }
+        if reason.len() > 256 {
+            panic!("Ban reason too long");
+        }
+
+        let ban = GlobalBan {
+            user: user.clone(),
+            issuer: caller,
+            reason,
+            created_at: env.ledger().timestamp(),
+            expires_at,
+        };
+        env.storage()
+            .persistent()
+            .set(&RegistryKey::GlobalBan(user.clone()), &ban);
+
+        let mut banned = Self::global_ban_index(&env);
+        if !banned.contains(&user) {
+            banned.push_back(user.clone());
+            env.storage()
+                .persistent()
+                .set(&RegistryKey::GlobalBans, &banned);
+        }
+
+        // Permissions keeps its own copy so its checks never call back here
+        let args: Vec<Val> = Vec::from_array(
+            &env,
+            [
+                user.into_val(&env),
+                ban.reason.into_val(&env),
+                expires_at.into_val(&env),
+            ],
+        );
+        env.invoke_contract::<()>(
+            &Self::permissions(&env),
+            &Symbol::new(&env, "set_global_ban"),
+            args,
+        );
+    }
+
+    /// Lift a user's global ban (admin only)
+    pub fn global_unban(env: Env, user: Address, caller: Address) {
+        Self::require_admin_auth(&env, &caller);
+
+        env.storage()
+            .persistent()
+            .remove(&RegistryKey::GlobalBan(user.clone()));
+
+        let mut banned = Self::global_ban_index(&env);
+        if let Some(pos) = banned.first_index_of(&user) {
+            banned.remove(pos);
+            env.storage()
+                .persistent()
+                .set(&RegistryKey::GlobalBans, &banned);
+        }
+
+        env.invoke_contract::<()>(
+            &Self::permissions(&env),
+            &Symbol::new(&env, "clear_global_ban"),
+            Vec::from_array(&env, [user.into_val(&env)]),
+        );
+    }
+
+    fn permissions(env: &Env) -> Address {
+        env.storage()
+            .instance()
+            .get(&RegistryKey::Contract(Symbol::new(env, "perms")))
+            .expect("Not initialized")
+    }
+
+    /// Check if a user is under an unexpired global ban
+    pub fn is_globally_banned(env: Env, user: Address) -> bool {
+        match Self::get_global_ban(env.clone(), user) {
+            Some(ban) => match ban.expires_at {
+                Some(expires_at) => env.ledger().timestamp() < expires_at,
+                None => true,
+            },
+            None => false,
+        }
+    }
+
+    /// Get a user's global ban, expired or not
+    pub fn get_global_ban(env: Env, user: Address) -> Option<GlobalBan> {
+        env.storage()
+            .persistent()
+            .get(&RegistryKey::GlobalBan(user))
+    }
+
+    /// List global bans on record (including expired ones), oldest first, with pagination
+    pub fn list_global_bans(env: Env, start: u32, limit: u32) -> Vec<GlobalBan> {
+        let banned = Self::global_ban_index(&env);
+        let end = start.saturating_add(limit).min(banned.len());
+
+        let mut bans = Vec::new(&env);
+        for i in start..end {
+            if let Some(ban) = Self::get_global_ban(env.clone(), banned.get_unchecked(i)) {
+                bans.push_back(ban);
+            }
+        }
+        bans
+    }
+
+    /// Number of global bans on record
+    pub fn global_ban_count(env: Env) -> u32 {
+        Self::global_ban_index(&env).len()
+    }
+
+    fn global_ban_index(env: &Env) -> Vec<Address> {
+        env.storage()
+            .persistent()
+            .get(&RegistryKey::GlobalBans)
+            .unwrap_or(Vec::new(env))
+    }
+
+    // =========================================================================
+    // Board Creation Limits
+    // =========================================================================
+
+    /// Set the per-account board cap (0 = unlimited) and the cooldown in seconds
+    /// between one account's board creations (admin only)
+    pub fn set_board_creation_limits(
+        env: Env,
+        boards_per_account: u32,
+        cooldown_secs: u64,
+        caller: Address,
+    ) {
+        Self::require_admin_auth(&env, &caller);
+
+        env.storage()
+            .instance()
+            .set(&RegistryKey::BoardsPerAccount, &boards_per_account);
+        env.storage()
+            .instance()
+            .set(&RegistryKey::BoardCooldown, &cooldown_secs);
+    }
+
+    /// Get the per-account board cap and cooldown: (boards_per_account, cooldown_secs)
+    pub fn get_board_creation_limits(env: Env) -> (u32, u64) {
+        let cap: u32 = env
+            .storage()
+            .instance()
+            .get(&RegistryKey::BoardsPerAccount)
+            .unwrap_or(0);
+        let cooldown: u64 = env
+            .storage()
+            .instance()
+            .get(&RegistryKey::BoardCooldown)
+            .unwrap_or(0);
+        (cap, cooldown)
+    }
+
+    /// Record a new board against its creator, enforcing the creation limits.
+    /// Only the registered "board" contract may call this; registry admins are exempt
+    /// from the limits but their boards are still recorded.
+    pub fn record_board_creation(env: Env, board_id: u64, creator: Address) {
+        let board: Address = env
+            .storage()
+            .instance()
+            .get(&RegistryKey::Contract(Symbol::new(&env, "board")))
+            .expect("Board contract not registered");
+        board.require_auth();
+
+        let mut boards = Self::list_boards_by_creator(env.clone(), creator.clone());
+        let now = env.ledger().timestamp();
+
+        if !Self::is_admin(env.clone(), creator.clone()) {
+            let (cap, cooldown) = Self::get_board_creation_limits(env.clone());
+            if cap > 0 && boards.len() >= cap {
+                panic_with_error!(&env, RegistryError::BoardCapReached);
+            }
+            let last: Option<u64> = env
+                .storage()
+                .persistent()
+                .get(&RegistryKey::LastBoardCreated(creator.clone()));
+            if last.is_some_and(|last| now < last.saturating_add(cooldown)) {
+                panic_with_error!(&env, RegistryError::BoardCooldownActive);
+            }
+        }
+
+        boards.push_back(board_id);
+        env.storage()
+            .persistent()
+            .set(&RegistryKey::CreatorBoards(creator.clone()), &boards);
+        env.storage()
+            .persistent()
+            .set(&RegistryKey::LastBoardCreated(creator), &now);
+    }
+
+    /// Board ids created by an account, oldest first (for moderation review)
+    pub fn list_boards_by_creator(env: Env, creator: Address) -> Vec<u64> {
+        env.storage()
+            .persistent()
+            .get(&RegistryKey::CreatorBoards(creator))
+            .unwrap_or(Vec::new(&env))
+    }
+
+    // =========================================================================
+    // Board Names
+    // =========================================================================
+
+    /// Claim the name of a new board. Only the registered "board" contract may call this.
+    pub fn register_board_name(env: Env, board_id: u64, name: String) {
+        let board: Address = env
+            .storage()
+            .instance()
+            .get(&RegistryKey::Contract(Symbol::new(&env, "board")))
+            .expect("Board contract not registered");
+        board.require_auth();
+
+        let key = Self::validate_board_name(&env, board_id, &name);
+        Self::store_board_name(&env, board_id, key);
+    }
+
+    /// Rename a board and push the new name to the board contract. The old name stays
+    /// behind as an alias of the board, so it keeps resolving and no other board can
+    /// take it. Calls come through the admin contract (which checks board permissions)
+    /// unless the caller is a registry admin.
+    pub fn rename_board(env: Env, board_id: u64, new_name: String, caller: Address) {
+        if Self::is_admin(env.clone(), caller.clone()) {
+            caller.require_auth();
+        } else {
+            let admin: Address = env
+                .storage()
+                .instance()
+                .get(&RegistryKey::Contract(Symbol::new(&env, "admin")))
+                .expect("Admin contract not registered");
+            admin.require_auth();
+        }
+
+        let key = Self::validate_board_name(&env, board_id, &new_name);
+        let storage = env.storage().persistent();
+        if let Some(old) = storage.get::<_, String>(&RegistryKey::BoardName(board_id)) {
+            if old != key {
+                storage.remove(&RegistryKey::BoardByName(old.clone()));
+                storage.set(&RegistryKey::BoardNameAlias(old), &board_id);
+            }
+        }
+        // Taking back one of its own former names
+        storage.remove(&RegistryKey::BoardNameAlias(key.clone()));
+        Self::store_board_name(&env, board_id, key);
+
+        let board: Address = env
+            .storage()
+            .instance()
+            .get(&RegistryKey::Contract(Symbol::new(&env, "board")))
+            .expect("Board contract not registered");
+        let args: Vec<Val> = Vec::from_array(
+            &env,
+            [
+                board_id.into_val(&env),
+                new_name.into_val(&env),
+                env.current_contract_address().into_val(&env),
+            ],
+        );
+        env.invoke_contract::<()>(&board, &Symbol::new(&env, "set_board_name"), args);
+    }
+
+    /// Add boards created before the name index to it (admin only). Walks board ids
+    /// [start, start + limit) and returns where to continue, or None once every board
+    /// has been visited. Boards already indexed are left alone, and a name that breaks
+    /// the naming rules or clashes with an indexed one is skipped; that board joins the
+    /// index when it is renamed.
+    pub fn index_board_names(env: Env, start: u64, limit: u64, caller: Address) -> Option<u64> {
+        Self::require_admin_auth(&env, &caller);
+
+        let board: Address = env
+            .storage()
+            .instance()
+            .get(&RegistryKey::Contract(Symbol::new(&env, "board")))
+            .expect("Board contract not registered");
+        let names: Vec<(u64, String)> = env.invoke_contract(
+            &board,
+            &Symbol::new(&env, "list_board_names"),
+            Vec::from_array(&env, [start.into_val(&env), limit.into_val(&env)]),
+        );
+        for (board_id, name) in names.iter() {
+            if env
+                .storage()
+                .persistent()
+                .has(&RegistryKey::BoardName(board_id))
+            {
+                continue;
+            }
+            if let Ok(key) = Self::check_board_name(&env, board_id, &name) {
+                Self::store_board_name(&env, board_id, key);
+            }
+        }
+
+        let count: u64 =
+            env.invoke_contract(&board, &Symbol::new(&env, "board_count"), Vec::new(&env));
+        let end = start.saturating_add(limit);
+        if end < count {
+            Some(end)
+        } else {
+            None
+        }
+    }
+
+    /// Board a name belongs to, ignoring case. Former names resolve to the board that
+    /// last held them.
+    pub fn get_board_by_name(env: Env, name: String) -> Option<u64> {
+        let len = name.len();
+        if len > MAX_BOARD_NAME_LEN {
+            return None;
+        }
+        let mut buf = [0u8; MAX_BOARD_NAME_LEN as usize];
+        let lower = Self::lowercase_into(&name, &mut buf[..len as usize]);
+        let key = String::from_bytes(&env, lower);
+        let storage = env.storage().persistent();
+        storage
+            .get(&RegistryKey::BoardByName(key.clone()))
+            .or_else(|| storage.get(&RegistryKey::BoardNameAlias(key)))
+    }
+
+    /// Check a proposed name for `board_id` against the naming rules and return its
+    /// lowercased form, the index key. The board may keep its own name in a new case or
+    /// take back one of its former names. Each rule fails with its own error code.
+    fn validate_board_name(env: &Env, board_id: u64, name: &String) -> String {
+        match Self::check_board_name(env, board_id, name) {
+            Ok(key) => key,
+            Err(err) => panic_with_error!(env, err),
+        }
+    }
+
+    /// The naming rules behind `validate_board_name`, without panicking
+    fn check_board_name(env: &Env, board_id: u64, name: &String) -> Result<String, RegistryError> {
+        let len = name.len();
+        if len > MAX_BOARD_NAME_LEN {
+            return Err(RegistryError::BoardNameLength);
+        }
+        let mut buf = [0u8; MAX_BOARD_NAME_LEN as usize];
+        let lower = Self::lowercase_into(name, &mut buf[..len as usize]);
+
+        if RESERVED_BOARD_NAMES.contains(&lower) {
+            return Err(RegistryError::BoardNameReserved);
+        }
+        if len < MIN_BOARD_NAME_LEN {
+            return Err(RegistryError::BoardNameLength);
+        }
+        if lower[0] == b'-' || lower[lower.len() - 1] == b'-' {
+            return Err(RegistryError::BoardNameEdgeDash);
+        }
+
+        let key = String::from_bytes(env, lower);
+        let storage = env.storage().persistent();
+        let holder: Option<u64> = storage.get(&RegistryKey::BoardByName(key.clone()));
+        if holder.is_some_and(|id| id != board_id) {
+            return Err(RegistryError::BoardNameTaken);
+        }
+        let alias_of: Option<u64> = storage.get(&RegistryKey::BoardNameAlias(key.clone()));
+        if alias_of.is_some_and(|id| id != board_id) {
+            return Err(RegistryError::BoardNameIsAlias);
+        }
+        Ok(key)
+    }
+
+    /// Copy a name into `buf` (sized to the name) with ASCII letters lowercased
+    fn lowercase_into<'a>(name: &String, buf: &'a mut [u8]) -> &'a [u8] {
+        name.copy_into_slice(buf);
+        buf.make_ascii_lowercase();
+        buf
+    }
+
+    /// Record `key` as the current name of a board
+    fn store_board_name(env: &Env, board_id: u64, key: String) {
+        env.storage()
+            .persistent()
+            .set(&RegistryKey::BoardByName(key.clone()), &board_id);
+        env.storage()
+            .persistent()
+            .set(&RegistryKey::BoardName(board_id), &key);
+    }
+
     // =========================================================================
     // Admin Management
     // =========================================================================
@@ -403,6 +1308,10 @@ impl BoardsRegistry {
     /// Upgrade the contract WASM
     pub fn upgrade(env: Env, new_wasm_hash: BytesN<32>, caller: Address) {
         Self::require_admin_auth(&env, &caller);
+        env.storage().instance().set(
+            &RegistryKey::ContractWasm(env.current_contract_address()),
+            &new_wasm_hash,
+        );
         env.deployer().update_current_contract_wasm(new_wasm_hash);
     }
 
@@ -418,8 +1327,20 @@ impl BoardsRegistry {
 
         // Call the target contract's upgrade function
         // The target contract will verify that we (the registry) are calling it
-        let args: Vec<Val> = Vec::from_array(&env, [new_wasm_hash.into_val(&env)]);
+        let args: Vec<Val> = Vec::from_array(&env, [new_wasm_hash.clone().into_val(&env)]);
         env.invoke_contract::<()>(&contract_id, &Symbol::new(&env, "upgrade"), args);
+
+        env.storage()
+            .instance()
+            .set(&RegistryKey::ContractWasm(contract_id), &new_wasm_hash);
+    }
+
+    /// Get the last WASM hash installed on a contract through the registry.
+    /// Returns None for contracts that have not been upgraded via the registry.
+    pub fn get_contract_wasm_hash(env: Env, contract_id: Address) -> Option<BytesN<32>> {
+        env.storage()
+            .instance()
+            .get(&RegistryKey::ContractWasm(contract_id))
     }
 
     // =========================================================================
@@ -464,7 +1385,7 @@ impl BoardsRegistry {
 #[cfg(test)]
 mod test {
     use super::*;
-    use soroban_sdk::testutils::Address as _;
+    use soroban_sdk::testutils::{Address as _, Ledger as _};
     use soroban_sdk::Env;
 
     /// Helper to setup a boards-registry contract with all dependencies
@@ -611,6 +1532,526 @@ mod test {
         );
     }
 
+    #[test]
+    fn test_theme_catalog() {
+        let env = Env::default();
+        let (client, _, admin, _, _, _, _) = setup_registry(&env);
+        let dark = String::from_str(&env, "dark");
+        let light = String::from_str(&env, "light");
+
+        assert_eq!(client.list_themes().len(), 0);
+        let dark_theme = Address::generate(&env);
+        client.register_theme(&dark, &dark_theme, &admin);
+        client.register_theme(&light, &Address::generate(&env), &admin);
+        assert_eq!(client.get_theme(&dark), Some(dark_theme));
+        assert_eq!(
+            client.list_themes().keys(),
+            Vec::from_array(&env, [dark.clone(), light])
+        );
+
+        client.unregister_theme(&dark, &admin);
+        assert_eq!(client.get_theme(&dark), None);
+        assert_eq!(client.list_themes().len(), 1);
+
+        // Only registry admins manage the catalog
+        let outsider = Address::generate(&env);
+        assert!(client
+            .try_register_theme(&dark, &outsider, &outsider)
+            .is_err());
+    }
+
+    #[test]
+    fn test_board_activity_reported_by_board_contract() {
+        let env = Env::default();
+        let (client, _, admin, _, _, _, _) = setup_registry(&env);
+        let board = Address::generate(&env);
+        client.set_contract(&Symbol::new(&env, "board"), &board, &admin);
+
+        assert_eq!(client.get_board_activity(&3), None);
+        env.ledger().set_timestamp(1_700_000_000);
+        client.report_board_activity(&3);
+
+        // The board contract's authorization is what's checked
+        let auths = env.auths();
+        assert_eq!(auths.len(), 1);
+        assert_eq!(auths[0].0, board);
+
+        assert_eq!(client.get_board_activity(&3), Some(1_700_000_000));
+        assert_eq!(
+            client.get_board_activities(&Vec::from_array(&env, [3, 4])),
+            Vec::from_array(&env, [1_700_000_000, 0])
+        );
+    }
+
+    #[test]
+    fn test_board_locale_reported_by_board_contract() {
+        let env = Env::default();
+        let (client, _, admin, _, _, _, _) = setup_registry(&env);
+        let board = Address::generate(&env);
+        client.set_contract(&Symbol::new(&env, "board"), &board, &admin);
+        let de = Symbol::new(&env, "de");
+
+        assert_eq!(client.get_board_locale(&3), None);
+        client.report_board_locale(&3, &Some(de.clone()));
+        assert_eq!(client.get_board_locale(&3), Some(de.clone()));
+        assert_eq!(
+            client.get_board_locales(&Vec::from_array(&env, [3, 4])),
+            Vec::from_array(&env, [Some(de), None])
+        );
+
+        // The board contract's authorization is what's checked
+        client.report_board_locale(&3, &None);
+        let auths = env.auths();
+        assert_eq!(auths.len(), 1);
+        assert_eq!(auths[0].0, board);
+        assert_eq!(client.get_board_locale(&3), None);
+    }
+
+    #[test]
+    fn test_recent_activity_ring_buffer_wraps() {
+        let env = Env::default();
+        let (client, _, admin, _, _, _, _) = setup_registry(&env);
+        client.set_contract(
+            &Symbol::new(&env, "board"),
+            &Address::generate(&env),
+            &admin,
+        );
+        let title = String::from_str(&env, "Hello");
+
+        assert_eq!(client.list_recent_activity(&10).len(), 0);
+        for thread_id in 0..3u64 {
+            client.report_activity(&1, &thread_id, &title, &(100 + thread_id));
+        }
+        let recent = client.list_recent_activity(&10);
+        assert_eq!(recent.len(), 3);
+        assert_eq!(recent.get_unchecked(0).thread_id, 2);
+        assert_eq!(recent.get_unchecked(2).thread_id, 0);
+
+        // Past the cap, the oldest entries are overwritten
+        for thread_id in 3..(RECENT_ACTIVITY_CAP as u64 + 7) {
+            client.report_activity(&1, &thread_id, &title, &(100 + thread_id));
+        }
+        let recent = client.list_recent_activity(&100);
+        assert_eq!(recent.len(), RECENT_ACTIVITY_CAP);
+        for (i, entry) in recent.iter().enumerate() {
+            assert_eq!(entry.thread_id, RECENT_ACTIVITY_CAP as u64 + 6 - i as u64);
+        }
+        assert_eq!(
+            client.list_recent_activity(&2).get_unchecked(1).thread_id,
+            55
+        );
+
+        // Long titles are cut to the cap
+        let long_title = String::from_bytes(&env, &[b'a'; MAX_ACTIVITY_TITLE_LEN + 20]);
+        client.report_activity(&1, &999, &long_title, &2000);
+        let latest = client.list_recent_activity(&1).get_unchecked(0);
+        assert_eq!(latest.title.len() as usize, MAX_ACTIVITY_TITLE_LEN);
+    }
+
+    /// Permissions stand-in where a single address administers every board
+    #[contract]
+    pub struct MockPermissions;
+
+    #[contractimpl]
+    impl MockPermissions {
+        pub fn set_board_admin(env: Env, user: Address) {
+            env.storage().instance().set(&0u32, &user);
+        }
+
+        pub fn set_global_ban(env: Env, user: Address, _reason: String, expires_at: Option<u64>) {
+            env.storage().instance().set(&user, &expires_at);
+        }
+
+        pub fn clear_global_ban(env: Env, user: Address) {
+            env.storage().instance().remove(&user);
+        }
+
+        pub fn has_global_ban(env: Env, user: Address) -> bool {
+            env.storage().instance().has(&user)
+        }
+
+        pub fn get_global_ban_expiry(env: Env, user: Address) -> Option<u64> {
+            env.storage().instance().get(&user).flatten()
+        }
+
+        pub fn can_admin(env: Env, _board_id: u64, user: Address) -> bool {
+            env.storage().instance().get::<_, Address>(&0u32) == Some(user)
+        }
+    }
+
+    fn setup_categories(env: &Env) -> (BoardsRegistryClient<'_>, Address, Address) {
+        let (client, _, admin, _, _, _, _) = setup_registry(env);
+        let permissions = env.register(MockPermissions, ());
+        let board_admin = Address::generate(env);
+        MockPermissionsClient::new(env, &permissions).set_board_admin(&board_admin);
+        client.set_contract(&Symbol::new(env, "perms"), &permissions, &admin);
+        (client, admin, board_admin)
+    }
+
+    #[test]
+    fn test_board_category_reassignment() {
+        let env = Env::default();
+        let (client, admin, board_admin) = setup_categories(&env);
+
+        let news = client.create_category(&String::from_str(&env, "News"), &admin);
+        let games = client.create_category(&String::from_str(&env, "Games"), &admin);
+        assert_eq!((news, games), (1, 2));
+        assert_eq!(client.list_categories().len(), 2);
+
+        client.set_board_category(&5, &news, &board_admin);
+        client.set_board_category(&2, &news, &admin);
+        assert_eq!(
+            client.list_boards_in_category(&news, &0, &10),
+            Vec::from_array(&env, [2, 5])
+        );
+
+        // Moving a board takes it out of its old category
+        client.set_board_category(&5, &games, &board_admin);
+        assert_eq!(client.get_board_category(&5), games);
+        assert_eq!(
+            client.list_boards_in_category(&news, &0, &10),
+            Vec::from_array(&env, [2])
+        );
+        assert_eq!(
+            client.list_boards_in_category(&games, &0, &10),
+            Vec::from_array(&env, [5])
+        );
+
+        // 0 uncategorizes
+        client.set_board_category(&2, &0, &admin);
+        assert_eq!(
+            client.get_board_categories(&Vec::from_array(&env, [2, 5, 9])),
+            Vec::from_array(&env, [0, games, 0])
+        );
+        assert_eq!(client.list_boards_in_category(&news, &0, &10).len(), 0);
+
+        // Anyone else is turned away
+        let outsider = Address::generate(&env);
+        assert!(client.try_set_board_category(&5, &news, &outsider).is_err());
+        assert!(client.try_set_board_category(&5, &7, &admin).is_err());
+    }
+
+    #[test]
+    fn test_delete_category_with_boards() {
+        let env = Env::default();
+        let (client, admin, board_admin) = setup_categories(&env);
+
+        let news = client.create_category(&String::from_str(&env, "News"), &admin);
+        client.set_board_category(&1, &news, &board_admin);
+        let result = client.try_delete_category(&news, &admin);
+        assert!(result.is_err());
+        assert!(client.get_category(&news).is_some());
+
+        // Once emptied it can go, and its id is not reused
+        client.set_board_category(&1, &0, &board_admin);
+        client.delete_category(&news, &admin);
+        assert_eq!(client.get_category(&news), None);
+        assert_eq!(client.list_categories().len(), 0);
+        assert_eq!(
+            client.create_category(&String::from_str(&env, "Misc"), &admin),
+            2
+        );
+    }
+
+    #[test]
+    fn test_global_ban_list() {
+        let env = Env::default();
+        let (client, _, admin, _, _, _, _) = setup_registry(&env);
+        let permissions = MockPermissionsClient::new(&env, &env.register(MockPermissions, ()));
+        client.set_contract(&Symbol::new(&env, "perms"), &permissions.address, &admin);
+        let spammer = Address::generate(&env);
+        let troll = Address::generate(&env);
+        let reason = String::from_str(&env, "Spam");
+
+        env.ledger().set_timestamp(1_000);
+        client.global_ban(&spammer, &reason, &None, &admin);
+        client.global_ban(&troll, &reason, &Some(2_000), &admin);
+        assert!(client.is_globally_banned(&spammer));
+        assert!(client.is_globally_banned(&troll));
+        assert_eq!(client.global_ban_count(), 2);
+        assert_eq!(
+            client.list_global_bans(&1, &10).get_unchecked(0).user,
+            troll
+        );
+
+        // Each ban is copied to permissions
+        assert!(permissions.has_global_ban(&spammer));
+        assert_eq!(permissions.get_global_ban_expiry(&spammer), None);
+        assert!(permissions.has_global_ban(&troll));
+        assert_eq!(permissions.get_global_ban_expiry(&troll), Some(2_000));
+
+        // Timed bans lapse but stay on record until lifted
+        env.ledger().set_timestamp(2_000);
+        assert!(!client.is_globally_banned(&troll));
+        assert!(client.get_global_ban(&troll).is_some());
+
+        client.global_unban(&spammer, &admin);
+        assert!(!client.is_globally_banned(&spammer));
+        assert!(!permissions.has_global_ban(&spammer));
+        assert_eq!(client.list_global_bans(&0, &10).len(), 1);
+
+        // Only registry admins ban, and admins can't be banned
+        assert!(client
+            .try_global_ban(&spammer, &reason, &None, &troll)
+            .is_err());
+        assert!(client
+            .try_global_ban(&admin, &reason, &None, &admin)
+            .is_err());
+    }
+
+    fn setup_creation_limits(env: &Env) -> (BoardsRegistryClient<'_>, Address) {
+        let (client, _, admin, _, _, _, _) = setup_registry(env);
+        client.set_contract(&Symbol::new(env, "board"), &Address::generate(env), &admin);
+        client.set_board_creation_limits(&2, &3600, &admin);
+        (client, admin)
+    }
+
+    #[test]
+    fn test_board_cap_reached() {
+        let env = Env::default();
+        let (client, _) = setup_creation_limits(&env);
+        let creator = Address::generate(&env);
+
+        env.ledger().set_timestamp(10_000);
+        client.record_board_creation(&0, &creator);
+        env.ledger().set_timestamp(20_000);
+        client.record_board_creation(&4, &creator);
+        assert_eq!(
+            client.list_boards_by_creator(&creator),
+            Vec::from_array(&env, [0, 4])
+        );
+
+        env.ledger().set_timestamp(30_000);
+        assert_eq!(
+            client.try_record_board_creation(&5, &creator),
+            Err(Ok(RegistryError::BoardCapReached.into()))
+        );
+        assert_eq!(client.list_boards_by_creator(&creator).len(), 2);
+    }
+
+    #[test]
+    fn test_board_cooldown_active() {
+        let env = Env::default();
+        let (client, _) = setup_creation_limits(&env);
+        let creator = Address::generate(&env);
+
+        env.ledger().set_timestamp(10_000);
+        client.record_board_creation(&0, &creator);
+        env.ledger().set_timestamp(10_000 + 3599);
+        assert_eq!(
+            client.try_record_board_creation(&1, &creator),
+            Err(Ok(RegistryError::BoardCooldownActive.into()))
+        );
+
+        // The cooldown is per account
+        client.record_board_creation(&1, &Address::generate(&env));
+
+        env.ledger().set_timestamp(10_000 + 3600);
+        client.record_board_creation(&2, &creator);
+        assert_eq!(
+            client.list_boards_by_creator(&creator),
+            Vec::from_array(&env, [0, 2])
+        );
+    }
+
+    #[test]
+    fn test_registry_admin_exempt_from_creation_limits() {
+        let env = Env::default();
+        let (client, admin) = setup_creation_limits(&env);
+
+        for board_id in 0..4u64 {
+            client.record_board_creation(&board_id, &admin);
+        }
+        assert_eq!(client.list_boards_by_creator(&admin).len(), 4);
+        assert_eq!(client.get_board_creation_limits(), (2, 3600));
+    }
+
+    /// Board contract stub that keeps the display names the registry pushes to it
+    #[contract]
+    pub struct MockBoard;
+
+    #[contractimpl]
+    impl MockBoard {
+        pub fn set_board_name(env: Env, board_id: u64, name: String, _caller: Address) {
+            env.storage().instance().set(&board_id, &name);
+        }
+
+        pub fn get_name(env: Env, board_id: u64) -> Option<String> {
+            env.storage().instance().get(&board_id)
+        }
+
+        pub fn list_board_names(env: Env, start: u64, limit: u64) -> Vec<(u64, String)> {
+            let mut names = Vec::new(&env);
+            for board_id in start..start + limit {
+                if let Some(name) = env.storage().instance().get(&board_id) {
+                    names.push_back((board_id, name));
+                }
+            }
+            names
+        }
+
+        pub fn board_count(env: Env) -> u64 {
+            let mut count = 0;
+            while env.storage().instance().has(&count) {
+                count += 1;
+            }
+            count
+        }
+    }
+
+    fn setup_board_names(env: &Env) -> (BoardsRegistryClient<'_>, MockBoardClient<'_>, Address) {
+        let (client, _, admin, _, _, _, admin_contract) = setup_registry(env);
+        let board = env.register(MockBoard, ());
+        client.set_contract(&Symbol::new(env, "board"), &board, &admin);
+        (client, MockBoardClient::new(env, &board), admin_contract)
+    }
+
+    #[test]
+    fn test_board_name_length() {
+        let env = Env::default();
+        let (client, _, _) = setup_board_names(&env);
+
+        for name in ["ab", "abcdefghijklmnopqrstuvwxyzabcdefghijklmnopqrstuvwxy"] {
+            assert_eq!(
+                client.try_register_board_name(&0, &String::from_str(&env, name)),
+                Err(Ok(RegistryError::BoardNameLength.into()))
+            );
+        }
+        client.register_board_name(&0, &String::from_str(&env, "abc"));
+        client.register_board_name(
+            &1,
+            &String::from_str(&env, "abcdefghijklmnopqrstuvwxyzabcdefghijklmnopqrstuvwx"),
+        );
+    }
+
+    #[test]
+    fn test_board_name_reserved() {
+        let env = Env::default();
+        let (client, _, _) = setup_board_names(&env);
+
+        // Reserved words are matched case-insensitively, short ones included
+        for name in ["Admin", "NEW", "b", "registry"] {
+            assert_eq!(
+                client.try_register_board_name(&0, &String::from_str(&env, name)),
+                Err(Ok(RegistryError::BoardNameReserved.into()))
+            );
+        }
+        client.register_board_name(&0, &String::from_str(&env, "admins"));
+    }
+
+    #[test]
+    fn test_board_name_edge_dash() {
+        let env = Env::default();
+        let (client, _, _) = setup_board_names(&env);
+
+        for name in ["-general", "general-"] {
+            assert_eq!(
+                client.try_register_board_name(&0, &String::from_str(&env, name)),
+                Err(Ok(RegistryError::BoardNameEdgeDash.into()))
+            );
+        }
+        client.register_board_name(&0, &String::from_str(&env, "gen-eral"));
+    }
+
+    #[test]
+    fn test_board_name_taken_ignores_case() {
+        let env = Env::default();
+        let (client, board, admin_contract) = setup_board_names(&env);
+        let caller = Address::generate(&env);
+
+        client.register_board_name(&0, &String::from_str(&env, "General"));
+        assert_eq!(
+            client.try_register_board_name(&1, &String::from_str(&env, "gENERAL")),
+            Err(Ok(RegistryError::BoardNameTaken.into()))
+        );
+        assert_eq!(
+            client.get_board_by_name(&String::from_str(&env, "GENERAL")),
+            Some(0)
+        );
+
+        // The holder itself may change the case of its name
+        let renamed = String::from_str(&env, "GENERAL");
+        client.rename_board(&0, &renamed, &caller);
+
+        // A non-admin caller's rename is authorized by the admin contract
+        let auths = env.auths();
+        assert_eq!(auths.len(), 1);
+        assert_eq!(auths[0].0, admin_contract);
+        assert_eq!(board.get_name(&0), Some(renamed));
+    }
+
+    #[test]
+    fn test_index_board_names_backfills_older_boards() {
+        let env = Env::default();
+        let (client, _, admin, _, _, _, _) = setup_registry(&env);
+        let board = MockBoardClient::new(&env, &env.register(MockBoard, ()));
+        client.set_contract(&Symbol::new(&env, "board"), &board.address, &admin);
+
+        // Boards that predate the index, one of them already renamed through it
+        let s = |v: &str| String::from_str(&env, v);
+        for (board_id, name) in ["General", "GENERAL", "Admin", "Lobby", "News"]
+            .into_iter()
+            .enumerate()
+        {
+            board.set_board_name(&(board_id as u64), &s(name), &admin);
+        }
+        client.register_board_name(&3, &s("Lobby"));
+        client.rename_board(&3, &s("Help Desk"), &admin);
+
+        assert!(client
+            .try_index_board_names(&0, &2, &Address::generate(&env))
+            .is_err());
+        assert_eq!(client.index_board_names(&0, &2, &admin), Some(2));
+        assert_eq!(client.index_board_names(&2, &2, &admin), Some(4));
+        assert_eq!(client.index_board_names(&4, &2, &admin), None);
+
+        // Clashing and reserved names are skipped; indexed boards keep their entries
+        assert_eq!(client.get_board_by_name(&s("general")), Some(0));
+        assert_eq!(client.get_board_by_name(&s("admin")), None);
+        assert_eq!(client.get_board_by_name(&s("news")), Some(4));
+        assert_eq!(client.get_board_by_name(&s("help desk")), Some(3));
+        assert_eq!(client.get_board_by_name(&s("lobby")), Some(3));
+        assert_eq!(
+            client.try_register_board_name(&5, &s("News")),
+            Err(Ok(RegistryError::BoardNameTaken.into()))
+        );
+    }
+
+    #[test]
+    fn test_board_name_alias_of_another_board() {
+        let env = Env::default();
+        let (client, board, _) = setup_board_names(&env);
+        let caller = Address::generate(&env);
+        let general = String::from_str(&env, "general");
+
+        client.register_board_name(&0, &general);
+        client.rename_board(&0, &String::from_str(&env, "Lobby"), &caller);
+        assert_eq!(board.get_name(&0), Some(String::from_str(&env, "Lobby")));
+
+        // The old name still resolves and is off limits to other boards
+        assert_eq!(client.get_board_by_name(&general), Some(0));
+        assert_eq!(
+            client.try_register_board_name(&1, &general),
+            Err(Ok(RegistryError::BoardNameIsAlias.into()))
+        );
+        assert_eq!(
+            client.try_rename_board(&1, &general, &caller),
+            Err(Ok(RegistryError::BoardNameIsAlias.into()))
+        );
+
+        // The board that held it can take it back; "lobby" then becomes its alias
+        client.rename_board(&0, &general, &caller);
+        assert_eq!(
+            client.get_board_by_name(&String::from_str(&env, "lobby")),
+            Some(0)
+        );
+        client.register_board_name(&1, &String::from_str(&env, "Help Desk"));
+        assert_eq!(
+            client.get_board_by_name(&String::from_str(&env, "unknown")),
+            None
+        );
+    }
+
     #[test]
     fn test_set_contract() {
         let env = Env::default();
@@ -729,6 +2170,41 @@ mod test {
         assert!(aliases.len() > 0);
     }
 
+    /// Contract that accepts upgrades from the registry without swapping WASM
+    #[contract]
+    pub struct MockUpgradeable;
+
+    #[contractimpl]
+    impl MockUpgradeable {
+        pub fn upgrade(env: Env, new_wasm_hash: BytesN<32>) {
+            env.storage().instance().set(&0u32, &new_wasm_hash);
+        }
+    }
+
+    #[test]
+    fn test_upgrade_contract_records_wasm_hash() {
+        let env = Env::default();
+        let (client, _, admin, _, _, _, _) = setup_registry(&env);
+
+        let target = env.register(MockUpgradeable, ());
+        assert_eq!(client.get_contract_wasm_hash(&target), None);
+
+        let hash = BytesN::from_array(&env, &[7u8; 32]);
+        client.upgrade_contract(&target, &hash, &admin);
+        assert_eq!(client.get_contract_wasm_hash(&target), Some(hash));
+    }
+
+    #[test]
+    #[should_panic(expected = "Not an admin")]
+    fn test_upgrade_contract_requires_admin() {
+        let env = Env::default();
+        let (client, _, _, _, _, _, _) = setup_registry(&env);
+
+        let target = env.register(MockUpgradeable, ());
+        let hash = BytesN::from_array(&env, &[7u8; 32]);
+        client.upgrade_contract(&target, &hash, &Address::generate(&env));
+    }
+
     #[test]
     fn test_multiple_admins() {
         let env = Env::default();
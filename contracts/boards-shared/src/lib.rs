@@ -0,0 +1,303 @@
+//! Text helpers shared by the boards contracts, so that contracts which must
+//! agree on how post content is read (for example the content contract's
+//! mention index and the board contract's mention links) use the same code,
+//! and every contract escapes stored strings the same way.
+
+#![no_std]
+
+use soroban_sdk::{Bytes, Env, EnvBase, String, Val};
+
+/// Longest attribute value any contract asks `escape_attr` for, in bytes
+pub const MAX_ATTR_BUF: usize = 512;
+
+/// CSS color names accepted for colors saved before hex validation
+pub const NAMED_COLORS: [&str; 14] = [
+    "black",
+    "white",
+    "gray",
+    "grey",
+    "red",
+    "orange",
+    "yellow",
+    "green",
+    "blue",
+    "purple",
+    "pink",
+    "brown",
+    "transparent",
+    "inherit",
+];
+
+/// Maximum distinct mentions recorded for a single post
+pub const MAX_MENTIONS_PER_POST: u32 = 5;
+
+/// Only the first bytes of a post are scanned for mentions
+pub const MENTION_SCAN_LEN: usize = 4096;
+
+/// Longest username accepted after an `@`
+pub const MAX_MENTION_NAME_LEN: usize = 32;
+
+fn is_name_byte(b: u8) -> bool {
+    b.is_ascii_alphanumeric() || b == b'_'
+}
+
+/// Find the next `@username` in `buf` at or after `from` and return the byte
+/// range of the name (without the `@`).
+/// An `@` only starts a mention at a word boundary (skips emails and URLs),
+/// and names longer than MAX_MENTION_NAME_LEN are passed over.
+pub fn next_mention(buf: &[u8], from: usize) -> Option<(usize, usize)> {
+    let mut i = from;
+    while i < buf.len() {
+        let at_boundary = i == 0 || !(is_name_byte(buf[i - 1]) || b"@/.[`".contains(&buf[i - 1]));
+        if buf[i] != b'@' || !at_boundary {
+            i += 1;
+            continue;
+        }
+        let start = i + 1;
+        let mut end = start;
+        while end < buf.len() && is_name_byte(buf[end]) {
+            end += 1;
+        }
+        let name_len = end - start;
+        if name_len > 0 && name_len <= MAX_MENTION_NAME_LEN {
+            return Some((start, end));
+        }
+        i = end;
+    }
+    None
+}
+
+/// Escape `&`, `<` and `"` so stored text is shown verbatim inside HTML text or attributes
+pub fn escape_html(env: &Env, text: &Bytes) -> Bytes {
+    let mut escaped = Bytes::new(env);
+    for byte in text.iter() {
+        match byte {
+            b'&' => escaped.append(&Bytes::from_slice(env, b"&amp;")),
+            b'<' => escaped.append(&Bytes::from_slice(env, b"&lt;")),
+            b'"' => escaped.append(&Bytes::from_slice(env, b"&quot;")),
+            _ => escaped.push_back(byte),
+        }
+    }
+    escaped
+}
+
+/// Length of a Stellar account (`G...`) or contract (`C...`) address string
+pub const ADDRESS_STRKEY_LEN: usize = 56;
+
+/// Check that `text` is a well-formed account or contract address (base32 with a
+/// valid version byte and checksum), so it can be passed to
+/// `Address::from_string_bytes`, which traps on anything else
+pub fn is_valid_address(text: &Bytes) -> bool {
+    if text.len() as usize != ADDRESS_STRKEY_LEN {
+        return false;
+    }
+    let mut chars = [0u8; ADDRESS_STRKEY_LEN];
+    text.copy_into_slice(&mut chars);
+
+    // 56 base32 characters decode to 35 bytes: version, 32-byte key, 2-byte checksum
+    let mut decoded = [0u8; 35];
+    let (mut acc, mut bits, mut out) = (0u32, 0u32, 0usize);
+    for &c in chars.iter() {
+        let value = match c {
+            b'A'..=b'Z' => c - b'A',
+            b'2'..=b'7' => c - b'2' + 26,
+            _ => return false,
+        };
+        acc = (acc << 5) | value as u32;
+        bits += 5;
+        if bits >= 8 {
+            bits -= 8;
+            decoded[out] = (acc >> bits) as u8;
+            out += 1;
+        }
+    }
+    // Account (G) and contract (C) version bytes
+    if decoded[0] != 6 << 3 && decoded[0] != 2 << 3 {
+        return false;
+    }
+
+    // CRC-16/XMODEM over the version and key, stored little-endian
+    let mut crc: u16 = 0;
+    for &byte in decoded[..33].iter() {
+        crc ^= (byte as u16) << 8;
+        for _ in 0..8 {
+            crc = if crc & 0x8000 != 0 {
+                (crc << 1) ^ 0x1021
+            } else {
+                crc << 1
+            };
+        }
+    }
+    crc.to_le_bytes() == [decoded[33], decoded[34]]
+}
+
+/// Largest position at or before `at` (and within `text`) that doesn't split a
+/// UTF-8 character, for cutting previews and excerpts
+pub fn char_floor(text: &Bytes, at: u32) -> u32 {
+    let mut end = at.min(text.len());
+    // Back off while the cut lands on a UTF-8 continuation byte
+    while end > 0 && end < text.len() && (text.get(end).unwrap() & 0xC0) == 0x80 {
+        end -= 1;
+    }
+    end
+}
+
+/// Copy the start of a string into `buf`, as many bytes as fit, and return how many
+/// were copied. `String::copy_into_slice` only takes a slice of the string's full length.
+pub fn copy_string_prefix(value: &String, buf: &mut [u8]) -> usize {
+    let len = (value.len() as usize).min(buf.len());
+    value
+        .env()
+        .string_copy_to_slice(value.to_object(), Val::U32_ZERO, &mut buf[..len])
+        .unwrap();
+    len
+}
+
+/// Escape a stored string for use inside a double-quoted HTML attribute.
+/// Strings longer than `max_len` bytes (at most MAX_ATTR_BUF) are cut at a
+/// character boundary.
+pub fn escape_attr(env: &Env, value: &String, max_len: usize) -> Bytes {
+    let max_len = max_len.min(MAX_ATTR_BUF);
+    // Copy one extra byte so a cut in the middle of a character can be detected
+    let mut buf = [0u8; MAX_ATTR_BUF + 1];
+    let copied = copy_string_prefix(value, &mut buf[..max_len + 1]);
+    let mut len = copied.min(max_len);
+    if copied > max_len {
+        while len > 0 && buf[len] & 0xC0 == 0x80 {
+            len -= 1;
+        }
+    }
+    escape_html(env, &Bytes::from_slice(env, &buf[..len]))
+}
+
+/// Check for a `#rgb` or `#rrggbb` hex color
+pub fn is_hex_color(value: &String) -> bool {
+    let len = value.len() as usize;
+    if len != 4 && len != 7 {
+        return false;
+    }
+    let mut buf = [0u8; 7];
+    value.copy_into_slice(&mut buf[..len]);
+    buf[0] == b'#' && buf[1..len].iter().all(|c| c.is_ascii_hexdigit())
+}
+
+/// Reduce a stored color to a safe CSS value: a hex color or a known color name.
+/// Anything else (e.g. `red;background-image:url(...)`) renders as `inherit`.
+pub fn sanitize_color(env: &Env, value: &String) -> Bytes {
+    let len = value.len() as usize;
+    if is_hex_color(value) {
+        let mut buf = [0u8; 7];
+        value.copy_into_slice(&mut buf[..len]);
+        return Bytes::from_slice(env, &buf[..len]);
+    }
+    if len <= 16 {
+        let mut buf = [0u8; 16];
+        value.copy_into_slice(&mut buf[..len]);
+        if NAMED_COLORS
+            .iter()
+            .any(|name| name.as_bytes() == &buf[..len])
+        {
+            return Bytes::from_slice(env, &buf[..len]);
+        }
+    }
+    Bytes::from_slice(env, b"inherit")
+}
+
+/// Reject CSS that could pull in remote content (`@import`, `url(`), escape the
+/// `<style>` element it is rendered in (`<`), or hide either behind CSS escapes (`\`)
+pub fn is_safe_css(css: &[u8]) -> bool {
+    !css.iter().any(|&b| b == b'<' || b == b'\\')
+        && !css.windows(7).any(|w| w.eq_ignore_ascii_case(b"@import"))
+        && !css.windows(4).any(|w| w.eq_ignore_ascii_case(b"url("))
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use soroban_sdk::Env;
+
+    #[test]
+    fn test_next_mention_skips_emails_and_long_names() {
+        let buf = b"mail bob@example.com, @ok_1 and @@x, [@y](u) then @z";
+        let (start, end) = next_mention(buf, 0).unwrap();
+        assert_eq!(&buf[start..end], b"ok_1");
+        let (start, end) = next_mention(buf, end).unwrap();
+        assert_eq!(&buf[start..end], b"z");
+        assert_eq!(next_mention(buf, end), None);
+
+        let mut long = [b'a'; 40];
+        long[0] = b'@';
+        assert_eq!(next_mention(&long, 0), None);
+    }
+
+    #[test]
+    fn test_escape_attr_cuts_at_char_boundary() {
+        let env = Env::default();
+        let value = String::from_str(&env, "a\"<b&é");
+        assert_eq!(
+            escape_attr(&env, &value, 64),
+            Bytes::from_slice(&env, "a&quot;&lt;b&amp;é".as_bytes())
+        );
+        // A cut inside the two-byte é backs off to the start of the character
+        assert_eq!(
+            escape_attr(&env, &value, 5),
+            Bytes::from_slice(&env, b"a&quot;&lt;b&amp;")
+        );
+        // Strings far past the buffer are cut the same way
+        let long = String::from_bytes(&env, &[b'x'; MAX_ATTR_BUF * 2]);
+        assert_eq!(escape_attr(&env, &long, 3), Bytes::from_slice(&env, b"xxx"));
+        assert_eq!(
+            escape_attr(&env, &long, MAX_ATTR_BUF * 2).len() as usize,
+            MAX_ATTR_BUF
+        );
+    }
+
+    #[test]
+    fn test_is_valid_address() {
+        use soroban_sdk::testutils::Address as _;
+        use soroban_sdk::Address;
+
+        let env = Env::default();
+        let address = Address::generate(&env).to_string();
+        let mut buf = [0u8; ADDRESS_STRKEY_LEN];
+        address.copy_into_slice(&mut buf);
+        assert!(is_valid_address(&Bytes::from_slice(&env, &buf)));
+
+        // A changed character breaks the checksum; lowercase isn't base32
+        buf[10] = if buf[10] == b'A' { b'B' } else { b'A' };
+        assert!(!is_valid_address(&Bytes::from_slice(&env, &buf)));
+        assert!(!is_valid_address(&Bytes::from_slice(&env, &[b'g'; 56])));
+        assert!(!is_valid_address(&Bytes::from_slice(&env, b"GABC")));
+    }
+
+    #[test]
+    fn test_char_floor() {
+        let env = Env::default();
+        let text = Bytes::from_slice(&env, "aé".as_bytes());
+        assert_eq!(char_floor(&text, 2), 1);
+        assert_eq!(char_floor(&text, 3), 3);
+        assert_eq!(char_floor(&text, 10), 3);
+    }
+
+    #[test]
+    fn test_sanitize_color_and_css() {
+        let env = Env::default();
+        let s = |v: &str| String::from_str(&env, v);
+        assert_eq!(
+            sanitize_color(&env, &s("#0af")),
+            Bytes::from_slice(&env, b"#0af")
+        );
+        assert_eq!(
+            sanitize_color(&env, &s("red")),
+            Bytes::from_slice(&env, b"red")
+        );
+        assert_eq!(
+            sanitize_color(&env, &s("red;background:url(x)")),
+            Bytes::from_slice(&env, b"inherit")
+        );
+        assert!(is_safe_css(b".post { color: red; }"));
+        assert!(!is_safe_css(b"@IMPORT 'x.css';"));
+        assert!(!is_safe_css(b"a{background:URL(x)}"));
+        assert!(!is_safe_css(b"</style>"));
+    }
+}
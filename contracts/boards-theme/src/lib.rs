@@ -7,6 +7,8 @@
 //!
 //! Exports:
 //! - styles() / render_styles() - CSS stylesheet
+//! - dark_styles() - dark palette for viewers who opted into dark mode
+//! - light_styles() - light palette for viewers who opted out of dark mode
 //! - init() - initialization
 //! - upgrade() - contract upgrade
 //! - get_* functions - contract address getters
@@ -183,6 +185,11 @@ impl BoardsTheme {
             .rule(".back-nav a.back-community", "color: var(--primary) !important; font-weight: 500;")
             .rule(".back-nav a.back-community:hover", "color: var(--primary-hover) !important;")
             .rule(".back-nav a.back-community::after", "content: '·'; margin-left: var(--space-sm); color: var(--text-muted); font-weight: normal;")
+            .rule(".breadcrumbs", "display: flex; flex-wrap: wrap; gap: var(--space-xs); align-items: center; margin-bottom: var(--space-md); font-size: 0.875rem; color: var(--text-muted);")
+            .rule(".breadcrumbs a", "color: var(--text-muted); text-decoration: none;")
+            .rule(".breadcrumbs a:hover", "color: var(--primary);")
+            .rule(".breadcrumbs .crumb-sep", "color: var(--text-muted); opacity: 0.6;")
+            .rule(".breadcrumbs .crumb-current", "color: var(--text); font-weight: 500; overflow-wrap: anywhere;")
             // Page header
             .rule(".page-header", "margin-bottom: var(--space-lg);")
             .rule(".page-header h1", "margin-bottom: var(--space-xs);")
@@ -195,6 +202,14 @@ impl BoardsTheme {
             .rule(".board-card-desc", "display: block; color: var(--text-muted); font-size: 0.9375rem; margin-bottom: var(--space-xs); text-align: left;")
             .rule(".board-card-meta", "display: block; font-size: 0.8125rem; color: var(--text-muted); text-align: left;")
             .rule(".board-card-meta .badge", "margin-left: var(--space-xs);")
+            .rule(".board-group", "margin: var(--space-md) 0 var(--space-sm); font-size: 1rem; color: var(--text-muted); text-transform: uppercase; letter-spacing: 0.04em;")
+            .rule("a.board-card.board-card-featured", "padding: var(--space-lg) !important; border-color: var(--primary); border-width: 2px;")
+            .rule(".board-card-featured .board-card-title", "font-size: 1.25rem;")
+            .rule(".board-list-featured", "margin-bottom: var(--space-lg);")
+            .rule(".featured-board", "padding: var(--space-xs) 0; border-bottom: 1px solid var(--border);")
+            .rule(".board-order li", "margin-bottom: var(--space-xs);")
+            .rule(".board-index-row", "padding: var(--space-xs) 0; border-bottom: 1px solid var(--border); font-size: 0.875rem; color: var(--text-muted);")
+            .rule(".blocked-content > summary", "cursor: pointer; padding: var(--space-xs) 0; font-size: 0.875rem; color: var(--text-muted);")
             // Thread list - card layout similar to boards
             .rule(".thread-list", "display: flex; flex-direction: column; gap: var(--space-sm);")
             .rule("a.thread-card", "display: flex !important; flex-direction: column; align-items: flex-start !important; background: var(--bg) !important; color: var(--text) !important; border: 1px solid var(--border); border-radius: 6px; padding: var(--space-md) !important; transition: border-color 0.15s, box-shadow 0.15s; text-decoration: none !important;")
@@ -204,6 +219,8 @@ impl BoardsTheme {
             // Thread content
             .rule(".thread-body", "margin-bottom: var(--space-lg); padding: var(--space-md); background: var(--bg-muted); border-radius: 6px;")
             .rule(".thread-actions", "display: flex; gap: var(--space-sm); margin-bottom: var(--space-lg);")
+            .rule(".show-full-post", "margin-bottom: var(--space-md); font-weight: 500;")
+            .rule(".budget-skipped", "color: var(--text-muted); font-size: 0.875rem;")
             // Reply containers
             .rule(".reply", "margin-bottom: var(--space-sm); padding: var(--space-sm) var(--space-md); border-left: 3px solid var(--primary); background: var(--bg-muted); border-radius: 0 4px 4px 0;")
             .rule(".reply .reply", "margin-left: var(--space-lg);")
@@ -211,7 +228,40 @@ impl BoardsTheme {
             .rule(".reply-meta", "font-size: 0.8125rem; color: var(--text-muted); display: flex; flex-wrap: wrap; gap: var(--space-sm); align-items: center;")
             .rule(".reply-meta a", "padding: var(--space-xs) var(--space-sm); background: var(--primary); color: white; border-radius: 4px; font-size: 0.75rem;")
             .rule(".reply-meta a:hover", "background: var(--primary-hover); text-decoration: none;")
+            .rule(".flag-form", "display: inline-flex; gap: var(--space-xs); align-items: center;")
+            .rule(".flag-form select, .flag-form input[type=text]", "padding: 0.125rem var(--space-xs); font-size: 0.75rem; max-width: 10rem;")
             .rule(".reply-hidden, .reply-deleted", "font-style: italic; color: var(--text-muted);")
+            .rule(".edited-marker", "font-size: 0.8125rem; color: var(--text-muted); font-style: italic;")
+            .rule(".revision", "margin-bottom: var(--space-md);")
+            .rule(".revision-meta", "font-size: 0.8125rem; color: var(--text-muted); margin-bottom: var(--space-xs);")
+            .rule(".revision-body", "white-space: pre-wrap; padding: var(--space-sm); background: var(--bg-muted); border-radius: 4px;")
+            .rule(".reactions", "display: flex; flex-wrap: wrap; gap: var(--space-xs); margin: var(--space-xs) 0;")
+            .rule(".reaction-chip", "padding: 0 var(--space-sm); border: 1px solid var(--border); border-radius: 999px; font-size: 0.8125rem; text-decoration: none;")
+            .rule(".reaction-active", "border-color: var(--primary); background: var(--bg-muted);")
+            .rule(".mention-item", "padding: var(--space-sm) 0; border-bottom: 1px solid var(--border);")
+            .rule(".mention-meta", "font-size: 0.8125rem; color: var(--text-muted);")
+            .rule(".notification-item", "padding: var(--space-sm) 0; border-bottom: 1px solid var(--border);")
+            .rule(".notification-item.unread", "font-weight: 600;")
+            .rule(".notification-meta", "font-weight: normal; font-size: 0.8125rem; color: var(--text-muted);")
+            .rule(".activity-item", "padding: var(--space-sm) 0; border-bottom: 1px solid var(--border);")
+            .rule(".activity-excerpt", "margin-top: var(--space-xs); color: var(--text-muted); font-size: 0.9375rem;")
+            .rule(".activity-meta", "font-size: 0.8125rem; color: var(--text-muted);")
+            .rule(".feed-item", "margin-bottom: var(--space-sm);")
+            .rule(".feed-board", "display: inline-block; margin-bottom: var(--space-xs); font-size: 0.8125rem; font-weight: 600; color: var(--text-muted);")
+            .rule(".feed-more", "margin-top: var(--space-md); text-align: center;")
+            .rule(".leaderboard-row", "display: flex; align-items: center; gap: var(--space-sm); padding: var(--space-xs) 0; border-bottom: 1px solid var(--border);")
+            .rule(".stats-bar", "color: var(--primary); letter-spacing: -1px; white-space: nowrap;")
+            .rule(".leaderboard-rank", "min-width: 2.5rem; font-weight: 600; color: var(--text-muted);")
+            .rule(".leaderboard-karma", "margin-left: auto; font-weight: 600;")
+            .rule(".leaderboard-viewer", "margin-top: var(--space-md); padding: var(--space-sm) var(--space-md); background: var(--bg-muted); border-radius: 6px;")
+            .rule(".theme-preview", "margin-bottom: var(--space-md); padding: var(--space-sm) var(--space-md); border: 1px dashed var(--primary); border-radius: 6px; font-size: 0.875rem;")
+            .rule(".display-toggle", "text-align: right; margin-bottom: var(--space-xs);")
+            .rule(".display-toggle a", "text-decoration: none; font-size: 1rem; margin-left: var(--space-sm);")
+            .rule(".notification-bell", "text-align: right; margin-bottom: var(--space-xs);")
+            .rule(".notification-bell a", "text-decoration: none; font-size: 0.9375rem;")
+            .rule(".reply-context", "margin-bottom: var(--space-md); opacity: 0.8; font-size: 0.875rem;")
+            .rule(".reply-permalink", "color: inherit;")
+            .rule(".mod-reveal", "margin: var(--space-xs) 0; padding: var(--space-xs) var(--space-sm); border: 1px dashed var(--text-muted); border-radius: 4px;")
             .rule(".reply-more", "margin-top: var(--space-sm); padding: var(--space-xs) 0; font-size: 0.875rem;")
             .rule(".reply-more a", "color: var(--primary); text-decoration: none;")
             .rule(".reply-more a:hover", "text-decoration: underline;")
@@ -240,6 +290,8 @@ impl BoardsTheme {
             .rule("li", "margin-bottom: var(--space-xs);")
             // Profile integration
             .rule(".thread-meta", "font-size: 0.875rem; color: var(--text-muted); margin-bottom: var(--space-md);")
+            .rule(".subscribe-toggle", "color: var(--text-muted); text-decoration: none;")
+            .rule(".subscribe-toggle.subscribed", "color: #f5a623;")
             .rule(".reply-header", "font-size: 0.8125rem; color: var(--text-muted); margin-bottom: var(--space-xs);")
             .rule(".reply-header a", "color: var(--primary); font-weight: 500;")
             .rule(".profile-compact", "display: inline;")
@@ -255,7 +307,11 @@ impl BoardsTheme {
             .rule(".badge-private", "background: #e7d4ff; color: #5a3d7a;")
             .rule(".badge-hidden", "background: #ccc; color: #333;")
             .rule(".badge-readonly", "background: #d4edda; color: #155724;")
+            .rule(".badge-category", "background: #e0ecff; color: #1d4f91;")
+            .rule(".badge-expired", "background: #e2e3e5; color: #6c757d; text-decoration: line-through;")
+            .rule(".badge-deleted", "background: #f8d7da; color: #721c24; text-decoration: line-through;")
             .rule(".mod-actions", "margin: var(--space-sm) 0; padding: var(--space-sm); background: var(--bg-muted); border-radius: var(--radius-md); font-size: 0.875rem;")
+            .rule(".danger-zone", "margin-top: var(--space-lg); padding: var(--space-md); border: 1px solid var(--danger); border-radius: var(--radius-md);")
             // Flair styles
             .rule(".flair", "display: inline-block; padding: 0.125rem 0.5rem; border-radius: 4px; font-size: 0.75rem; font-weight: 600; margin-right: var(--space-xs); vertical-align: middle;")
             .rule(".flair-selector", "margin-bottom: var(--space-md);")
@@ -269,6 +325,10 @@ impl BoardsTheme {
             .rule(".crosspost-header", "background: var(--bg-muted); padding: var(--space-sm) var(--space-md); border-radius: 6px; margin-bottom: var(--space-md); font-size: 0.875rem; border-left: 3px solid var(--primary);")
             .rule(".crosspost-badge", "display: inline-block; padding: 0.125rem 0.5rem; background: var(--primary); color: white; border-radius: 3px; font-size: 0.75rem; font-weight: 600; margin-right: var(--space-xs);")
             .rule(".crosspost-count", "color: var(--text-muted); font-size: 0.875rem; margin-left: var(--space-xs);")
+            .rule(".crosspost-list li", "padding: var(--space-xs) 0;")
+            .rule(".crosspost-removed", "font-style: italic; color: var(--text-muted);")
+            .rule(".crosspost-remove", "margin-left: var(--space-sm); font-size: 0.8125rem; color: var(--text-muted);")
+            .rule(".crosspost-meta", "font-size: 0.8125rem; color: var(--text-muted); margin-left: var(--space-xs);")
             .rule(".crosspost-preview", "background: var(--bg-muted); padding: var(--space-md); border-radius: 6px; margin-bottom: var(--space-md);")
             // Board rules styles - green tip box
             .rule(".board-rules", "margin-bottom: var(--space-md); border-left: 4px solid #2da44e; border-radius: 6px; overflow: hidden; background: linear-gradient(to right, rgba(45, 164, 78, 0.08), rgba(45, 164, 78, 0.03)); padding: var(--space-sm);")
@@ -279,6 +339,9 @@ impl BoardsTheme {
             .rule(".rules-reminder", "margin-bottom: var(--space-md); border: 2px solid var(--warning); border-radius: 6px; overflow: hidden; background: rgba(255, 193, 7, 0.05);")
             .rule(".rules-reminder summary", "padding: var(--space-sm) var(--space-md); background: rgba(255, 193, 7, 0.1); cursor: pointer; color: var(--warning);")
             .rule(".rules-reminder summary:hover", "background: rgba(255, 193, 7, 0.15);")
+            .rule(".draft-restore", "margin-bottom: var(--space-md); padding: var(--space-sm) var(--space-md); border: 1px dashed var(--border); border-radius: 6px; font-size: 0.875rem;")
+            .rule(".draft-discard", "margin-left: var(--space-sm); color: var(--text-muted);")
+            .rule(".karma-notice", "margin-bottom: var(--space-md); padding: var(--space-sm) var(--space-md); border: 2px solid var(--warning); border-radius: 6px; background: rgba(255, 193, 7, 0.05);")
             // Vote buttons and scoring
             .rule(".vote-buttons", "display: flex; align-items: center; gap: var(--space-sm); margin-bottom: var(--space-md);")
             .rule(".vote-up, .vote-down", "display: inline-flex; align-items: center; justify-content: center; width: 2rem; height: 2rem; border-radius: 4px; background: var(--bg-muted); color: var(--text-muted); text-decoration: none; font-size: 1rem; transition: all 0.15s;")
@@ -287,6 +350,8 @@ impl BoardsTheme {
             .rule(".vote-up.vote-active", "background: #4caf50; color: white;")
             .rule(".vote-down.vote-active", "background: #f44336; color: white;")
             .rule(".vote-disabled", "cursor: not-allowed; opacity: 0.5;")
+            .rule(".vote-own", "position: relative;")
+            .rule(".vote-own:hover::after", "content: attr(title); position: absolute; left: 50%; bottom: 100%; transform: translateX(-50%); padding: 0.125rem var(--space-xs); border-radius: 4px; background: var(--text); color: var(--bg); font-size: 0.75rem; white-space: nowrap; pointer-events: none;")
             .rule(".vote-score", "font-weight: 600; font-size: 1.125rem; min-width: 2rem; text-align: center;")
             .rule(".vote-score-compact", "display: flex; align-items: center; justify-content: center; min-width: 2.5rem; padding: var(--space-xs); background: var(--primary); color: white; border-radius: 4px 0 0 4px; font-weight: 600; font-size: 0.875rem;")
             .rule(".vote-score-inline", "font-weight: 600; font-size: 0.75rem; min-width: 1.5rem; text-align: center;")
@@ -297,6 +362,9 @@ impl BoardsTheme {
             .rule(".reply-votes", "display: flex; align-items: center; gap: var(--space-xs); margin-top: var(--space-xs); font-size: 0.75rem;")
             .rule(".reply-votes .vote-up, .reply-votes .vote-down", "width: 1.25rem; height: 1.25rem; font-size: 0.625rem;")
             // Sort order selector
+            .rule(".board-search", "display: flex; gap: var(--space-xs); margin-bottom: var(--space-md);")
+            .rule(".board-search input", "flex: 1; max-width: 24rem;")
+            .rule(".search-note", "font-size: 0.8125rem; color: var(--text-muted);")
             .rule(".sort-selector", "display: flex; align-items: center; gap: var(--space-sm); margin-bottom: var(--space-md); padding: var(--space-xs) 0;")
             .rule(".sort-label", "color: var(--text-muted); font-size: 0.875rem;")
             .rule(".sort-option", "padding: var(--space-xs) var(--space-sm); background: var(--bg-muted); border-radius: 4px; color: var(--text-muted); text-decoration: none; font-size: 0.875rem; transition: all 0.15s;")
@@ -322,6 +390,7 @@ impl BoardsTheme {
             .rule(".community-header h1", "margin-bottom: var(--space-xs);")
             .rule(".community-header p", "color: var(--text-muted); margin: 0;")
             .rule(".community-actions", "display: flex; gap: var(--space-sm); margin-top: var(--space-md);")
+            .rule(".community-membership", "margin: var(--space-sm) 0;")
             // Disabled action buttons (for threshold-restricted users)
             .rule(".action-disabled", "display: inline-block; color: var(--text-muted); cursor: not-allowed; opacity: 0.6; padding: var(--space-xs) var(--space-sm); font-size: 0.875rem;")
             .rule(".action-disabled:hover", "text-decoration: none;")
@@ -351,6 +420,9 @@ impl BoardsTheme {
             .rule(".badge-private", "background: #3a2d4a; color: #c9a5ff;")
             .rule(".badge-hidden", "background: #2a2a2a; color: #888;")
             .rule(".badge-readonly", "background: #1e3a28; color: #6fdd8b;")
+            .rule(".badge-category", "background: #1c2c44; color: #8ab4f8;")
+            .rule(".badge-expired", "background: #2a2a2a; color: #888;")
+            .rule(".badge-deleted", "background: #3a1c1c; color: #ff8080;")
             // Vote buttons dark mode
             .rule(".vote-up:hover", "background: #1e3a28; color: #6fdd8b;")
             .rule(".vote-down:hover", "background: #3a1c1c; color: #ff8080;")
@@ -401,6 +473,81 @@ impl BoardsTheme {
         }
     }
 
+    /// Dark palette applied unconditionally, for viewers who chose dark mode.
+    /// Mirrors the prefers-color-scheme block in render_styles.
+    pub fn dark_styles(env: Env) -> Bytes {
+        StyleBuilder::new(&env)
+            .rule_start(":root")
+            .prop("--text", "#e0e0e0")
+            .prop("--text-muted", "#a0a0a0")
+            .prop("--bg", "#0f0f0f")
+            .prop("--bg-muted", "#1a1a1a")
+            .prop("--border", "#3e3e3e")
+            .rule_end()
+            .rule(".board-card:hover", "box-shadow: 0 2px 8px rgba(120, 87, 225, 0.2);")
+            .rule(".thread-card:hover", "box-shadow: 0 2px 8px rgba(120, 87, 225, 0.2);")
+            .rule(".alert-success", "background: #1e3a28; color: #6fdd8b;")
+            .rule(".alert-warning", "background: #3a3019; color: #ffd859;")
+            .rule(".alert-danger", "background: #3a1c1c; color: #ff8080;")
+            .rule(".alert-info", "background: #2a2644; color: #b8a8e8;")
+            // Board rules dark mode - green tip box
+            .rule(".board-rules", "background: linear-gradient(to right, rgba(111, 221, 139, 0.1), rgba(111, 221, 139, 0.03));")
+            .rule(".board-rules summary", "color: #6fdd8b;")
+            .rule(".board-rules summary:hover", "background: rgba(111, 221, 139, 0.1);")
+            .rule(".badge-pinned", "background: #3a3019; color: #ffd859;")
+            .rule(".badge-locked", "background: #3a1c1c; color: #ff8080;")
+            .rule(".badge-private", "background: #3a2d4a; color: #c9a5ff;")
+            .rule(".badge-hidden", "background: #2a2a2a; color: #888;")
+            .rule(".badge-readonly", "background: #1e3a28; color: #6fdd8b;")
+            .rule(".badge-category", "background: #1c2c44; color: #8ab4f8;")
+            .rule(".badge-expired", "background: #2a2a2a; color: #888;")
+            .rule(".badge-deleted", "background: #3a1c1c; color: #ff8080;")
+            // Vote buttons dark mode
+            .rule(".vote-up:hover", "background: #1e3a28; color: #6fdd8b;")
+            .rule(".vote-down:hover", "background: #3a1c1c; color: #ff8080;")
+            .rule(".vote-up.vote-active", "background: #2e7d32; color: white;")
+            .rule(".vote-down.vote-active", "background: #c62828; color: white;")
+            // Community cards dark mode
+            .rule("a.community-card:hover", "box-shadow: 0 2px 8px rgba(120, 87, 225, 0.2);")
+            .build()
+    }
+
+    /// Light palette applied unconditionally, for viewers who chose light mode.
+    /// Restores everything the prefers-color-scheme block in render_styles overrides.
+    pub fn light_styles(env: Env) -> Bytes {
+        StyleBuilder::new(&env)
+            .rule_start(":root")
+            .prop("--text", "#171717")
+            .prop("--text-muted", "#6f6f6f")
+            .prop("--bg", "#ffffff")
+            .prop("--bg-muted", "#f7f7f7")
+            .prop("--border", "#e2e2e2")
+            .rule_end()
+            .rule(".board-card:hover", "box-shadow: 0 2px 8px rgba(120, 87, 225, 0.1);")
+            .rule(".thread-card:hover", "box-shadow: 0 2px 8px rgba(120, 87, 225, 0.1);")
+            .rule(".alert-success", "background: #d3f9d8; color: #1e7a34;")
+            .rule(".alert-warning", "background: #fff3bf; color: #946c00;")
+            .rule(".alert-danger", "background: #ffd8d8; color: #c41d1d;")
+            .rule(".alert-info", "background: #e8e4fd; color: #5c4bad;")
+            .rule(".board-rules", "background: linear-gradient(to right, rgba(45, 164, 78, 0.08), rgba(45, 164, 78, 0.03));")
+            .rule(".board-rules summary", "color: #1a7f37;")
+            .rule(".board-rules summary:hover", "background: rgba(45, 164, 78, 0.08);")
+            .rule(".badge-pinned", "background: #ffeeba; color: #856404;")
+            .rule(".badge-locked", "background: #f8d7da; color: #721c24;")
+            .rule(".badge-private", "background: #e7d4ff; color: #5a3d7a;")
+            .rule(".badge-hidden", "background: #ccc; color: #333;")
+            .rule(".badge-readonly", "background: #d4edda; color: #155724;")
+            .rule(".badge-category", "background: #e0ecff; color: #1d4f91;")
+            .rule(".badge-expired", "background: #e2e3e5; color: #6c757d;")
+            .rule(".badge-deleted", "background: #f8d7da; color: #721c24;")
+            .rule(".vote-up:hover", "background: #e8f5e9; color: #4caf50;")
+            .rule(".vote-down:hover", "background: #ffebee; color: #f44336;")
+            .rule(".vote-up.vote-active", "background: #4caf50; color: white;")
+            .rule(".vote-down.vote-active", "background: #f44336; color: white;")
+            .rule("a.community-card:hover", "box-shadow: 0 2px 8px rgba(120, 87, 225, 0.1);")
+            .build()
+    }
+
     /// Get custom CSS from config contract, with graceful fallback
     fn get_custom_css_from_config(env: &Env) -> Bytes {
         let config_opt: Option<Address> = env.storage().instance().get(&ThemeKey::Config);
@@ -524,6 +671,21 @@ mod test {
         assert_eq!(client.get_config(), Some(config));
     }
 
+    #[test]
+    fn test_dark_styles() {
+        let env = Env::default();
+        let (client, _, _, _, _, _) = setup_theme(&env);
+
+        // A subset of the full stylesheet, applied without the media query
+        let css = client.dark_styles();
+        assert!(css.len() > 0);
+        assert!(css.len() < client.styles().len());
+
+        let css = client.light_styles();
+        assert!(css.len() > 0);
+        assert!(css.len() < client.styles().len());
+    }
+
     #[test]
     fn test_render_styles() {
         let env = Env::default();
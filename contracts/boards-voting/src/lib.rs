@@ -1,6 +1,37 @@
 #![no_std]
 
-use soroban_sdk::{contract, contractimpl, contracttype, Address, Env, Vec};
+use soroban_sdk::{
+    contract, contracterror, contractimpl, contracttype, panic_with_error, Address, Env, Map,
+    String, Symbol, Vec,
+};
+
+/// Reactions a board may enable (rendered as emoji by the board contract)
+pub const REACTION_NAMES: [&str; 8] = [
+    "thumbsup", "tada", "heart", "eyes", "laugh", "rocket", "thinking", "sad",
+];
+
+/// Reactions enabled on boards that haven't chosen their own
+const DEFAULT_REACTIONS: [&str; 4] = ["thumbsup", "tada", "heart", "eyes"];
+
+/// Number of users kept on each board's karma leaderboard
+pub const TOP_KARMA_SIZE: u32 = 50;
+
+/// Fewest and most options a poll can offer
+const MIN_POLL_OPTIONS: u32 = 2;
+const MAX_POLL_OPTIONS: u32 = 6;
+
+/// Longest poll question and option label, in bytes
+const MAX_POLL_QUESTION_LEN: u32 = 200;
+const MAX_POLL_OPTION_LEN: u32 = 80;
+
+/// Errors that can occur in the voting contract
+#[contracterror]
+#[derive(Copy, Clone, Debug, Eq, PartialEq, PartialOrd, Ord)]
+#[repr(u32)]
+pub enum VotingError {
+    /// Thread is locked, hidden, deleted, or on an archived board; its votes are frozen
+    ThreadNotVotable = 1,
+}
 
 /// Vote direction for threads and replies
 #[contracttype]
@@ -39,6 +70,25 @@ impl Default for VoteTally {
     }
 }
 
+/// A poll attached to a thread, with running per-option counts
+#[contracttype]
+#[derive(Clone, Debug)]
+pub struct Poll {
+    pub id: u64,
+    pub board_id: u64,
+    pub thread_id: u64,
+    pub question: String,
+    pub options: Vec<String>,
+    /// Votes per option, parallel to `options`
+    pub counts: Vec<u32>,
+    /// Whether voters may pick more than one option
+    pub multi: bool,
+    /// No votes are accepted from this timestamp on (None = never closes)
+    pub closes_at: Option<u64>,
+    /// Number of users with at least one option picked
+    pub voters: u32,
+}
+
 /// Configuration for voting on a board
 #[contracttype]
 #[derive(Clone, Debug)]
@@ -76,16 +126,39 @@ pub enum VoteKey {
     ThreadVote(u64, u64, Address),
     /// Vote on a reply: (board_id, thread_id, reply_id, voter) -> VoteDirection
     ReplyVote(u64, u64, u64, Address),
+    /// Weight applied with a thread vote: (board_id, thread_id, voter) -> u32 (absent = 1)
+    ThreadVoteWeight(u64, u64, Address),
+    /// Weight applied with a reply vote: (board_id, thread_id, reply_id, voter) -> u32
+    ReplyVoteWeight(u64, u64, u64, Address),
     /// Tally for a thread: (board_id, thread_id) -> VoteTally
     ThreadTally(u64, u64),
     /// Tally for a reply: (board_id, thread_id, reply_id) -> VoteTally
     ReplyTally(u64, u64, u64),
     /// Voting config for a board: board_id -> VotingConfig
     BoardVotingConfig(u64),
+    /// How much member and moderator votes count on a board:
+    /// board_id -> (member weight, moderator weight), absent = (1, 1); guests always count 1
+    BoardVoteWeights(u64),
     /// Karma for a user on a board: (board_id, user) -> i64
     BoardKarma(u64, Address),
     /// Total karma for a user across all boards: user -> i64
     TotalKarma(Address),
+    /// Enabled reactions for a board: board_id -> Vec<Symbol>
+    ReactionAllowlist(u64),
+    /// Reaction counts: (board_id, thread_id, reply_id) -> Map<Symbol, u32>
+    ReactionCounts(u64, u64, Option<u64>),
+    /// A user's reactions: (board_id, thread_id, reply_id, user) -> Vec<Symbol>
+    UserReactions(u64, u64, Option<u64>, Address),
+    /// Karma leaderboard for a board, highest first: board_id -> Vec<(Address, i64)>
+    TopKarma(u64),
+    /// Next poll ID (global across boards) -> u64
+    NextPollId,
+    /// Poll by ID: poll_id -> Poll
+    Poll(u64),
+    /// Poll attached to a thread: (board_id, thread_id) -> poll_id
+    ThreadPoll(u64, u64),
+    /// Options a user picked in a poll: (poll_id, voter) -> Vec<u32>
+    PollVote(u64, Address),
 }
 
 /// Role levels (copied from permissions contract for authorization checks)
@@ -140,6 +213,35 @@ impl BoardsVoting {
             .set(&VoteKey::BoardVotingConfig(board_id), &config);
     }
 
+    /// Set how much member and moderator (or admin) votes count on a board (admin+ only)
+    pub fn set_vote_weights(
+        env: Env,
+        board_id: u64,
+        member_weight: u32,
+        moderator_weight: u32,
+        caller: Address,
+    ) {
+        caller.require_auth();
+        Self::require_admin(&env, board_id, &caller);
+
+        if member_weight == 0 || moderator_weight == 0 {
+            panic!("Vote weights must be at least 1");
+        }
+
+        env.storage().persistent().set(
+            &VoteKey::BoardVoteWeights(board_id),
+            &(member_weight, moderator_weight),
+        );
+    }
+
+    /// Get a board's (member weight, moderator weight); both default to 1
+    pub fn get_vote_weights(env: Env, board_id: u64) -> (u32, u32) {
+        env.storage()
+            .persistent()
+            .get(&VoteKey::BoardVoteWeights(board_id))
+            .unwrap_or((1, 1))
+    }
+
     /// Get voting configuration for a board
     pub fn get_voting_config(env: Env, board_id: u64) -> VotingConfig {
         env.storage()
@@ -170,13 +272,27 @@ impl BoardsVoting {
             panic!("Downvotes are not allowed on this board");
         }
 
-        // Get previous vote
+        // Votes are frozen once the thread is locked, hidden, deleted, or archived
+        if !Self::is_thread_votable(&env, board_id, thread_id) {
+            panic_with_error!(&env, VotingError::ThreadNotVotable);
+        }
+
+        // Authors can withdraw a vote on their own thread but not cast one
+        let author = Self::content_author(&env, board_id, thread_id, None);
+        if direction != VoteDirection::None && author.as_ref() == Some(&voter) {
+            panic!("Cannot vote on your own content");
+        }
+
+        // Get previous vote and the weight it was counted with
         let vote_key = VoteKey::ThreadVote(board_id, thread_id, voter.clone());
+        let weight_key = VoteKey::ThreadVoteWeight(board_id, thread_id, voter.clone());
         let previous_vote: VoteDirection = env
             .storage()
             .persistent()
             .get(&vote_key)
             .unwrap_or(VoteDirection::None);
+        let previous_weight: u32 = env.storage().persistent().get(&weight_key).unwrap_or(1);
+        let weight = Self::vote_weight(&env, board_id, &voter, direction);
 
         // Get current tally
         let tally_key = VoteKey::ThreadTally(board_id, thread_id);
@@ -194,15 +310,16 @@ impl BoardsVoting {
         // Calculate karma delta for content author
         let karma_delta = Self::calculate_karma_delta(previous_vote, direction);
 
-        // Remove previous vote effect
+        // Remove previous vote effect with the weight it was applied at, so later
+        // config changes can't skew the tally
         match previous_vote {
             VoteDirection::Up => {
-                tally.upvotes = tally.upvotes.saturating_sub(1);
-                tally.score -= 1;
+                tally.upvotes = tally.upvotes.saturating_sub(previous_weight);
+                tally.score -= previous_weight as i32;
             }
             VoteDirection::Down => {
-                tally.downvotes = tally.downvotes.saturating_sub(1);
-                tally.score += 1;
+                tally.downvotes = tally.downvotes.saturating_sub(previous_weight);
+                tally.score += previous_weight as i32;
             }
             VoteDirection::None => {}
         }
@@ -210,33 +327,42 @@ impl BoardsVoting {
         // Apply new vote
         match direction {
             VoteDirection::Up => {
-                tally.upvotes += 1;
-                tally.score += 1;
+                tally.upvotes += weight;
+                tally.score += weight as i32;
             }
             VoteDirection::Down => {
-                tally.downvotes += 1;
-                tally.score -= 1;
+                tally.downvotes += weight;
+                tally.score -= weight as i32;
             }
             VoteDirection::None => {}
         }
 
-        // Store the new vote (or remove if None)
+        // Store the new vote and its weight (or remove if None)
         if direction == VoteDirection::None {
             env.storage().persistent().remove(&vote_key);
+            env.storage().persistent().remove(&weight_key);
         } else {
             env.storage().persistent().set(&vote_key, &direction);
+            env.storage().persistent().set(&weight_key, &weight);
         }
 
         // Store updated tally
         env.storage().persistent().set(&tally_key, &tally);
+        Self::publish_vote_change(
+            &env,
+            board_id,
+            thread_id,
+            None,
+            voter,
+            previous_vote,
+            direction,
+        );
 
-        // Update karma if enabled (we'd need the content author address here)
-        // For now, karma is updated when we have author context
-        // This would typically be done via cross-contract call to content contract
+        // Credit the thread author (skipped if the thread can't be found)
         if config.karma_enabled && karma_delta != 0 {
-            // Note: In a full implementation, we'd get the thread author from the content contract
-            // and update their karma. For now, we track the vote but karma update requires author.
-            // TODO: Add get_thread_author cross-contract call when integrating with content
+            if let Some(author) = author {
+                Self::apply_karma(&env, board_id, &author, karma_delta, &config);
+            }
         }
 
         tally
@@ -265,13 +391,27 @@ impl BoardsVoting {
             panic!("Downvotes are not allowed on this board");
         }
 
-        // Get previous vote
+        // Votes are frozen once the thread is locked, hidden, deleted, or archived
+        if !Self::is_thread_votable(&env, board_id, thread_id) {
+            panic_with_error!(&env, VotingError::ThreadNotVotable);
+        }
+
+        // Authors can withdraw a vote on their own reply but not cast one
+        let author = Self::content_author(&env, board_id, thread_id, Some(reply_id));
+        if direction != VoteDirection::None && author.as_ref() == Some(&voter) {
+            panic!("Cannot vote on your own content");
+        }
+
+        // Get previous vote and the weight it was counted with
         let vote_key = VoteKey::ReplyVote(board_id, thread_id, reply_id, voter.clone());
+        let weight_key = VoteKey::ReplyVoteWeight(board_id, thread_id, reply_id, voter.clone());
         let previous_vote: VoteDirection = env
             .storage()
             .persistent()
             .get(&vote_key)
             .unwrap_or(VoteDirection::None);
+        let previous_weight: u32 = env.storage().persistent().get(&weight_key).unwrap_or(1);
+        let weight = Self::vote_weight(&env, board_id, &voter, direction);
 
         // Get current tally
         let tally_key = VoteKey::ReplyTally(board_id, thread_id, reply_id);
@@ -286,18 +426,19 @@ impl BoardsVoting {
             tally.first_vote_at = env.ledger().timestamp();
         }
 
-        // Calculate karma delta (for future use)
-        let _karma_delta = Self::calculate_karma_delta(previous_vote, direction);
+        // Calculate karma delta for content author
+        let karma_delta = Self::calculate_karma_delta(previous_vote, direction);
 
-        // Remove previous vote effect
+        // Remove previous vote effect with the weight it was applied at, so later
+        // config changes can't skew the tally
         match previous_vote {
             VoteDirection::Up => {
-                tally.upvotes = tally.upvotes.saturating_sub(1);
-                tally.score -= 1;
+                tally.upvotes = tally.upvotes.saturating_sub(previous_weight);
+                tally.score -= previous_weight as i32;
             }
             VoteDirection::Down => {
-                tally.downvotes = tally.downvotes.saturating_sub(1);
-                tally.score += 1;
+                tally.downvotes = tally.downvotes.saturating_sub(previous_weight);
+                tally.score += previous_weight as i32;
             }
             VoteDirection::None => {}
         }
@@ -305,25 +446,43 @@ impl BoardsVoting {
         // Apply new vote
         match direction {
             VoteDirection::Up => {
-                tally.upvotes += 1;
-                tally.score += 1;
+                tally.upvotes += weight;
+                tally.score += weight as i32;
             }
             VoteDirection::Down => {
-                tally.downvotes += 1;
-                tally.score -= 1;
+                tally.downvotes += weight;
+                tally.score -= weight as i32;
             }
             VoteDirection::None => {}
         }
 
-        // Store the new vote (or remove if None)
+        // Store the new vote and its weight (or remove if None)
         if direction == VoteDirection::None {
             env.storage().persistent().remove(&vote_key);
+            env.storage().persistent().remove(&weight_key);
         } else {
             env.storage().persistent().set(&vote_key, &direction);
+            env.storage().persistent().set(&weight_key, &weight);
         }
 
         // Store updated tally
         env.storage().persistent().set(&tally_key, &tally);
+        Self::publish_vote_change(
+            &env,
+            board_id,
+            thread_id,
+            Some(reply_id),
+            voter,
+            previous_vote,
+            direction,
+        );
+
+        // Credit the reply author (skipped if the reply can't be found)
+        if config.karma_enabled && karma_delta != 0 {
+            if let Some(author) = author {
+                Self::apply_karma(&env, board_id, &author, karma_delta, &config);
+            }
+        }
 
         tally
     }
@@ -336,6 +495,15 @@ impl BoardsVoting {
             .unwrap_or_default()
     }
 
+    /// Get the tallies for several threads in one call, in the order given
+    pub fn get_thread_tallies(env: Env, board_id: u64, thread_ids: Vec<u64>) -> Vec<VoteTally> {
+        let mut tallies = Vec::new(&env);
+        for thread_id in thread_ids.iter() {
+            tallies.push_back(Self::get_thread_tally(env.clone(), board_id, thread_id));
+        }
+        tallies
+    }
+
     /// Get the tally for a reply
     pub fn get_reply_tally(env: Env, board_id: u64, thread_id: u64, reply_id: u64) -> VoteTally {
         env.storage()
@@ -344,6 +512,25 @@ impl BoardsVoting {
             .unwrap_or_default()
     }
 
+    /// Get the tallies for several replies in one thread, in the order given
+    pub fn get_reply_tallies(
+        env: Env,
+        board_id: u64,
+        thread_id: u64,
+        reply_ids: Vec<u64>,
+    ) -> Vec<VoteTally> {
+        let mut tallies = Vec::new(&env);
+        for reply_id in reply_ids.iter() {
+            tallies.push_back(Self::get_reply_tally(
+                env.clone(),
+                board_id,
+                thread_id,
+                reply_id,
+            ));
+        }
+        tallies
+    }
+
     /// Get a user's vote on a thread
     pub fn get_user_thread_vote(
         env: Env,
@@ -403,29 +590,22 @@ impl BoardsVoting {
             return;
         }
 
-        let multiplied_delta = delta * (config.karma_multiplier as i64);
-
-        // Update board-specific karma
-        let board_karma_key = VoteKey::BoardKarma(board_id, user.clone());
-        let current_board_karma: i64 = env
-            .storage()
-            .persistent()
-            .get(&board_karma_key)
-            .unwrap_or(0i64);
-        env.storage()
-            .persistent()
-            .set(&board_karma_key, &(current_board_karma + multiplied_delta));
+        Self::apply_karma(&env, board_id, &user, delta, &config);
+    }
 
-        // Update total karma
-        let total_karma_key = VoteKey::TotalKarma(user);
-        let current_total_karma: i64 = env
+    /// Get the highest-karma users on a board, best first.
+    /// Only the top TOP_KARMA_SIZE users are tracked.
+    pub fn top_karma(env: Env, board_id: u64, limit: u32) -> Vec<(Address, i64)> {
+        let top: Vec<(Address, i64)> = env
             .storage()
             .persistent()
-            .get(&total_karma_key)
-            .unwrap_or(0i64);
-        env.storage()
-            .persistent()
-            .set(&total_karma_key, &(current_total_karma + multiplied_delta));
+            .get(&VoteKey::TopKarma(board_id))
+            .unwrap_or(Vec::new(&env));
+        if top.len() <= limit {
+            top
+        } else {
+            top.slice(0..limit)
+        }
     }
 
     // === Score Algorithms ===
@@ -498,6 +678,209 @@ impl BoardsVoting {
 
     // === Internal Helpers ===
 
+    /// Look up a contract address by alias from the registry (None if unavailable)
+    fn get_contract_by_alias(env: &Env, alias: &str) -> Option<Address> {
+        use soroban_sdk::{IntoVal, Val};
+        let registry: Address = env.storage().instance().get(&VoteKey::Registry)?;
+        let args: Vec<Val> = Vec::from_array(env, [Symbol::new(env, alias).into_val(env)]);
+        env.try_invoke_contract::<Option<Address>, soroban_sdk::Error>(
+            &registry,
+            &Symbol::new(env, "get_contract_by_alias"),
+            args,
+        )
+        .ok()
+        .and_then(|r| r.ok())
+        .flatten()
+    }
+
+    /// Ask the board contract whether a thread accepts votes (true if it can't be reached)
+    fn is_thread_votable(env: &Env, board_id: u64, thread_id: u64) -> bool {
+        use soroban_sdk::{IntoVal, Val};
+        let Some(board) = Self::get_contract_by_alias(env, "board") else {
+            return true;
+        };
+        let args: Vec<Val> =
+            Vec::from_array(env, [board_id.into_val(env), thread_id.into_val(env)]);
+        env.try_invoke_contract::<bool, soroban_sdk::Error>(
+            &board,
+            &Symbol::new(env, "is_thread_votable"),
+            args,
+        )
+        .ok()
+        .and_then(|r| r.ok())
+        .unwrap_or(true)
+    }
+
+    /// Tally weight for a vote by `voter`, from their role on the board.
+    /// Removing a vote, or a role that can't be looked up, counts as 1.
+    fn vote_weight(env: &Env, board_id: u64, voter: &Address, direction: VoteDirection) -> u32 {
+        use soroban_sdk::{IntoVal, Val};
+        if direction == VoteDirection::None {
+            return 1;
+        }
+        let (member_weight, moderator_weight) = Self::get_vote_weights(env.clone(), board_id);
+        if member_weight == 1 && moderator_weight == 1 {
+            return 1;
+        }
+        let Some(permissions) = env
+            .storage()
+            .instance()
+            .get::<_, Address>(&VoteKey::Permissions)
+        else {
+            return 1;
+        };
+        let args: Vec<Val> =
+            Vec::from_array(env, [board_id.into_val(env), voter.clone().into_val(env)]);
+        let role = env
+            .try_invoke_contract::<Role, soroban_sdk::Error>(
+                &permissions,
+                &Symbol::new(env, "get_role"),
+                args,
+            )
+            .ok()
+            .and_then(|r| r.ok())
+            .unwrap_or(Role::Guest);
+        match role {
+            Role::Guest => 1,
+            Role::Member => member_weight,
+            Role::Moderator | Role::Admin | Role::Owner => moderator_weight,
+        }
+    }
+
+    /// Author of a thread (reply_id None) or reply, from the board or content contract.
+    /// None if the content or the contracts holding it can't be found.
+    fn content_author(
+        env: &Env,
+        board_id: u64,
+        thread_id: u64,
+        reply_id: Option<u64>,
+    ) -> Option<Address> {
+        use soroban_sdk::{IntoVal, String, Val};
+        match reply_id {
+            None => {
+                let board = Self::get_contract_by_alias(env, "board")?;
+                let args: Vec<Val> =
+                    Vec::from_array(env, [board_id.into_val(env), thread_id.into_val(env)]);
+                env.try_invoke_contract::<Option<(String, Address)>, soroban_sdk::Error>(
+                    &board,
+                    &Symbol::new(env, "get_thread_title_and_author"),
+                    args,
+                )
+                .ok()
+                .and_then(|r| r.ok())
+                .flatten()
+                .map(|(_, author)| author)
+            }
+            Some(reply_id) => {
+                let content = Self::get_contract_by_alias(env, "content")?;
+                let args: Vec<Val> = Vec::from_array(
+                    env,
+                    [
+                        board_id.into_val(env),
+                        thread_id.into_val(env),
+                        reply_id.into_val(env),
+                    ],
+                );
+                env.try_invoke_contract::<Option<Address>, soroban_sdk::Error>(
+                    &content,
+                    &Symbol::new(env, "get_reply_author"),
+                    args,
+                )
+                .ok()
+                .and_then(|r| r.ok())
+                .flatten()
+            }
+        }
+    }
+
+    /// Emit a `vote_changed` event so vote patterns can be analyzed off-chain
+    fn publish_vote_change(
+        env: &Env,
+        board_id: u64,
+        thread_id: u64,
+        reply_id: Option<u64>,
+        voter: Address,
+        previous: VoteDirection,
+        direction: VoteDirection,
+    ) {
+        if previous == direction {
+            return;
+        }
+        env.events().publish(
+            (Symbol::new(env, "vote_changed"), board_id),
+            (thread_id, reply_id, voter, previous, direction),
+        );
+    }
+
+    /// Add a (pre-multiplier) karma delta to a user's board and total karma
+    fn apply_karma(env: &Env, board_id: u64, user: &Address, delta: i64, config: &VotingConfig) {
+        let multiplied_delta = delta * (config.karma_multiplier as i64);
+
+        // Update board-specific karma
+        let board_karma_key = VoteKey::BoardKarma(board_id, user.clone());
+        let board_karma: i64 = env
+            .storage()
+            .persistent()
+            .get(&board_karma_key)
+            .unwrap_or(0i64)
+            + multiplied_delta;
+        env.storage()
+            .persistent()
+            .set(&board_karma_key, &board_karma);
+
+        // Update total karma
+        let total_karma_key = VoteKey::TotalKarma(user.clone());
+        let current_total_karma: i64 = env
+            .storage()
+            .persistent()
+            .get(&total_karma_key)
+            .unwrap_or(0i64);
+        env.storage()
+            .persistent()
+            .set(&total_karma_key, &(current_total_karma + multiplied_delta));
+
+        Self::update_top_karma(env, board_id, user, board_karma);
+    }
+
+    /// Move a user to their place on the board's leaderboard after their karma changed.
+    ///
+    /// Users outside a full leaderboard aren't tracked, so a listed user who falls below
+    /// the rest of the list gives up their place rather than risk outranking someone unseen.
+    fn update_top_karma(env: &Env, board_id: u64, user: &Address, karma: i64) {
+        let key = VoteKey::TopKarma(board_id);
+        let mut top: Vec<(Address, i64)> = env
+            .storage()
+            .persistent()
+            .get(&key)
+            .unwrap_or(Vec::new(env));
+        let was_full = top.len() >= TOP_KARMA_SIZE;
+
+        let mut previous: Option<i64> = None;
+        if let Some(index) = top.iter().position(|(addr, _)| addr == *user) {
+            previous = top.get(index as u32).map(|(_, k)| k);
+            top.remove(index as u32);
+        }
+
+        let lost_place = was_full
+            && previous.is_some_and(|k| karma < k)
+            && top.last().is_some_and(|(_, last)| karma < last);
+        if karma > 0 && !lost_place {
+            // Ties keep the earlier entry ahead
+            let index = top
+                .iter()
+                .position(|(_, k)| k < karma)
+                .unwrap_or(top.len() as usize);
+            if (index as u32) < TOP_KARMA_SIZE {
+                top.insert(index as u32, (user.clone(), karma));
+                if top.len() > TOP_KARMA_SIZE {
+                    top.pop_back();
+                }
+            }
+        }
+
+        env.storage().persistent().set(&key, &top);
+    }
+
     /// Calculate karma change from a vote change
     fn calculate_karma_delta(previous: VoteDirection, new: VoteDirection) -> i64 {
         let prev_val: i64 = match previous {
@@ -513,6 +896,214 @@ impl BoardsVoting {
         new_val - prev_val
     }
 
+    /// Toggle a user's reaction on a thread (reply_id None) or reply.
+    /// Reactions don't affect score or karma. Returns true if the reaction is now set.
+    pub fn react(
+        env: Env,
+        board_id: u64,
+        thread_id: u64,
+        reply_id: Option<u64>,
+        emoji: Symbol,
+        user: Address,
+    ) -> bool {
+        user.require_auth();
+
+        let allowed = Self::get_reaction_allowlist(env.clone(), board_id);
+        if !allowed.contains(&emoji) {
+            panic!("Reaction not allowed on this board");
+        }
+
+        let user_key = VoteKey::UserReactions(board_id, thread_id, reply_id, user);
+        let mut mine: Vec<Symbol> = env
+            .storage()
+            .persistent()
+            .get(&user_key)
+            .unwrap_or(Vec::new(&env));
+        let counts_key = VoteKey::ReactionCounts(board_id, thread_id, reply_id);
+        let mut counts: Map<Symbol, u32> = env
+            .storage()
+            .persistent()
+            .get(&counts_key)
+            .unwrap_or(Map::new(&env));
+        let count = counts.get(emoji.clone()).unwrap_or(0);
+
+        let reacted = match mine.first_index_of(&emoji) {
+            Some(idx) => {
+                mine.remove(idx);
+                counts.set(emoji, count.saturating_sub(1));
+                false
+            }
+            None => {
+                mine.push_back(emoji.clone());
+                counts.set(emoji, count + 1);
+                true
+            }
+        };
+
+        if mine.is_empty() {
+            env.storage().persistent().remove(&user_key);
+        } else {
+            env.storage().persistent().set(&user_key, &mine);
+        }
+        env.storage().persistent().set(&counts_key, &counts);
+        reacted
+    }
+
+    /// Toggle a reaction on a thread (form/tx-link friendly wrapper around `react`)
+    pub fn react_thread(
+        env: Env,
+        board_id: u64,
+        thread_id: u64,
+        emoji: Symbol,
+        user: Address,
+    ) -> bool {
+        Self::react(env, board_id, thread_id, None, emoji, user)
+    }
+
+    /// Toggle a reaction on a reply (form/tx-link friendly wrapper around `react`)
+    pub fn react_reply(
+        env: Env,
+        board_id: u64,
+        thread_id: u64,
+        reply_id: u64,
+        emoji: Symbol,
+        user: Address,
+    ) -> bool {
+        Self::react(env, board_id, thread_id, Some(reply_id), emoji, user)
+    }
+
+    /// Get reaction counts for a thread or reply, one entry per enabled reaction
+    /// in allowlist order (reactions no longer enabled are not reported)
+    pub fn get_reactions(
+        env: Env,
+        board_id: u64,
+        thread_id: u64,
+        reply_id: Option<u64>,
+    ) -> Vec<(Symbol, u32)> {
+        let allowlist = Self::get_reaction_allowlist(env.clone(), board_id);
+        Self::count_reactions(&env, board_id, thread_id, reply_id, &allowlist)
+    }
+
+    /// Get reaction counts for several replies in one thread, in the order given
+    pub fn get_reply_reactions(
+        env: Env,
+        board_id: u64,
+        thread_id: u64,
+        reply_ids: Vec<u64>,
+    ) -> Vec<Vec<(Symbol, u32)>> {
+        let allowlist = Self::get_reaction_allowlist(env.clone(), board_id);
+        let mut result = Vec::new(&env);
+        for reply_id in reply_ids.iter() {
+            result.push_back(Self::count_reactions(
+                &env,
+                board_id,
+                thread_id,
+                Some(reply_id),
+                &allowlist,
+            ));
+        }
+        result
+    }
+
+    /// Reaction counts for one post, in allowlist order
+    fn count_reactions(
+        env: &Env,
+        board_id: u64,
+        thread_id: u64,
+        reply_id: Option<u64>,
+        allowlist: &Vec<Symbol>,
+    ) -> Vec<(Symbol, u32)> {
+        let counts: Map<Symbol, u32> = env
+            .storage()
+            .persistent()
+            .get(&VoteKey::ReactionCounts(board_id, thread_id, reply_id))
+            .unwrap_or(Map::new(env));
+
+        let mut result = Vec::new(env);
+        for emoji in allowlist.iter() {
+            let count = counts.get(emoji.clone()).unwrap_or(0);
+            result.push_back((emoji, count));
+        }
+        result
+    }
+
+    /// Get the reactions a user has set on a thread or reply
+    pub fn get_user_reactions(
+        env: Env,
+        board_id: u64,
+        thread_id: u64,
+        reply_id: Option<u64>,
+        user: Address,
+    ) -> Vec<Symbol> {
+        env.storage()
+            .persistent()
+            .get(&VoteKey::UserReactions(board_id, thread_id, reply_id, user))
+            .unwrap_or(Vec::new(&env))
+    }
+
+    /// Get the reactions a user has set on several replies in one thread, in the order given
+    pub fn get_user_reply_reactions(
+        env: Env,
+        board_id: u64,
+        thread_id: u64,
+        reply_ids: Vec<u64>,
+        user: Address,
+    ) -> Vec<Vec<Symbol>> {
+        let mut result = Vec::new(&env);
+        for reply_id in reply_ids.iter() {
+            result.push_back(Self::get_user_reactions(
+                env.clone(),
+                board_id,
+                thread_id,
+                Some(reply_id),
+                user.clone(),
+            ));
+        }
+        result
+    }
+
+    /// Get the reactions enabled on a board
+    pub fn get_reaction_allowlist(env: Env, board_id: u64) -> Vec<Symbol> {
+        env.storage()
+            .persistent()
+            .get(&VoteKey::ReactionAllowlist(board_id))
+            .unwrap_or_else(|| {
+                let mut defaults = Vec::new(&env);
+                for name in DEFAULT_REACTIONS.iter() {
+                    defaults.push_back(Symbol::new(&env, name));
+                }
+                defaults
+            })
+    }
+
+    /// Set the reactions enabled on a board (admin+ only).
+    /// Entries must come from REACTION_NAMES; an empty list turns reactions off.
+    pub fn set_reaction_allowlist(env: Env, board_id: u64, emojis: Vec<Symbol>, caller: Address) {
+        caller.require_auth();
+
+        // Verify caller has admin permission on this board
+        Self::require_admin(&env, board_id, &caller);
+
+        let mut known = Vec::new(&env);
+        for name in REACTION_NAMES.iter() {
+            known.push_back(Symbol::new(&env, name));
+        }
+        let mut seen: Vec<Symbol> = Vec::new(&env);
+        for emoji in emojis.iter() {
+            if !known.contains(&emoji) {
+                panic!("Unknown reaction");
+            }
+            if seen.contains(&emoji) {
+                panic!("Duplicate reaction");
+            }
+            seen.push_back(emoji);
+        }
+
+        env.storage()
+            .persistent()
+            .set(&VoteKey::ReactionAllowlist(board_id), &emojis);
+    }
+
     /// Require that caller has admin+ permissions on the board
     fn require_admin(env: &Env, board_id: u64, caller: &Address) {
         // Get permissions contract
@@ -523,7 +1114,7 @@ impl BoardsVoting {
             .expect("Permissions not set");
 
         // Get caller's permissions
-        use soroban_sdk::{IntoVal, Symbol, Val};
+        use soroban_sdk::{IntoVal, Val};
         let args: Vec<Val> =
             Vec::from_array(env, [board_id.into_val(env), caller.clone().into_val(env)]);
         let perms: PermissionSet =
@@ -534,6 +1125,185 @@ impl BoardsVoting {
         }
     }
 
+    // === Polls ===
+
+    /// Attach a poll to a thread (thread author or moderator+). One poll per thread.
+    /// Returns the new poll's ID.
+    #[allow(clippy::too_many_arguments)]
+    pub fn create_poll(
+        env: Env,
+        board_id: u64,
+        thread_id: u64,
+        question: String,
+        options: Vec<String>,
+        multi: bool,
+        closes_at: Option<u64>,
+        caller: Address,
+    ) -> u64 {
+        caller.require_auth();
+
+        let author = Self::content_author(&env, board_id, thread_id, None);
+        if author.as_ref() != Some(&caller) && !Self::can_moderate(&env, board_id, &caller) {
+            panic!("Only the thread author or a moderator can add a poll");
+        }
+
+        let thread_key = VoteKey::ThreadPoll(board_id, thread_id);
+        if env.storage().persistent().has(&thread_key) {
+            panic!("Thread already has a poll");
+        }
+        if question.is_empty() || question.len() > MAX_POLL_QUESTION_LEN {
+            panic!("Poll question must be 1-200 bytes");
+        }
+        if options.len() < MIN_POLL_OPTIONS || options.len() > MAX_POLL_OPTIONS {
+            panic!("Polls need 2-6 options");
+        }
+        let mut counts = Vec::new(&env);
+        for option in options.iter() {
+            if option.is_empty() || option.len() > MAX_POLL_OPTION_LEN {
+                panic!("Poll options must be 1-80 bytes");
+            }
+            counts.push_back(0u32);
+        }
+        if closes_at.is_some_and(|at| at <= env.ledger().timestamp()) {
+            panic!("Poll must close in the future");
+        }
+
+        let poll_id: u64 = env
+            .storage()
+            .persistent()
+            .get(&VoteKey::NextPollId)
+            .unwrap_or(0);
+        let poll = Poll {
+            id: poll_id,
+            board_id,
+            thread_id,
+            question,
+            options,
+            counts,
+            multi,
+            closes_at,
+            voters: 0,
+        };
+        env.storage()
+            .persistent()
+            .set(&VoteKey::Poll(poll_id), &poll);
+        env.storage().persistent().set(&thread_key, &poll_id);
+        env.storage()
+            .persistent()
+            .set(&VoteKey::NextPollId, &(poll_id + 1));
+        poll_id
+    }
+
+    /// Vote in a poll. Single-choice polls move the voter's vote to `option_idx`
+    /// (voting for the same option again changes nothing); multi-select polls
+    /// toggle it. Votes can be changed until the poll closes. Returns the updated poll.
+    pub fn vote_poll(env: Env, poll_id: u64, option_idx: u32, voter: Address) -> Poll {
+        voter.require_auth();
+
+        let mut poll: Poll = env
+            .storage()
+            .persistent()
+            .get(&VoteKey::Poll(poll_id))
+            .expect("Poll not found");
+        if poll
+            .closes_at
+            .is_some_and(|at| env.ledger().timestamp() >= at)
+        {
+            panic!("Poll is closed");
+        }
+        if option_idx >= poll.options.len() {
+            panic!("Invalid poll option");
+        }
+        // Polls freeze along with the thread's votes
+        if !Self::is_thread_votable(&env, poll.board_id, poll.thread_id) {
+            panic_with_error!(&env, VotingError::ThreadNotVotable);
+        }
+
+        let vote_key = VoteKey::PollVote(poll_id, voter);
+        let mut picked: Vec<u32> = env
+            .storage()
+            .persistent()
+            .get(&vote_key)
+            .unwrap_or(Vec::new(&env));
+        let had_voted = !picked.is_empty();
+
+        match picked.first_index_of(option_idx) {
+            // Multi-select: picking an option again withdraws it
+            Some(idx) if poll.multi => {
+                picked.remove(idx);
+                let count = poll.counts.get(option_idx).unwrap_or(0);
+                poll.counts.set(option_idx, count.saturating_sub(1));
+            }
+            Some(_) => return poll,
+            None => {
+                if !poll.multi {
+                    // Move a single-choice vote off its previous option
+                    if let Some(previous) = picked.pop_front() {
+                        let count = poll.counts.get(previous).unwrap_or(0);
+                        poll.counts.set(previous, count.saturating_sub(1));
+                    }
+                }
+                picked.push_back(option_idx);
+                let count = poll.counts.get(option_idx).unwrap_or(0);
+                poll.counts.set(option_idx, count + 1);
+            }
+        }
+
+        match (had_voted, picked.is_empty()) {
+            (false, false) => poll.voters += 1,
+            (true, true) => poll.voters = poll.voters.saturating_sub(1),
+            _ => {}
+        }
+        if picked.is_empty() {
+            env.storage().persistent().remove(&vote_key);
+        } else {
+            env.storage().persistent().set(&vote_key, &picked);
+        }
+        env.storage()
+            .persistent()
+            .set(&VoteKey::Poll(poll_id), &poll);
+        poll
+    }
+
+    /// Get the poll attached to a thread, if any
+    pub fn get_poll(env: Env, board_id: u64, thread_id: u64) -> Option<Poll> {
+        let poll_id: u64 = env
+            .storage()
+            .persistent()
+            .get(&VoteKey::ThreadPoll(board_id, thread_id))?;
+        env.storage().persistent().get(&VoteKey::Poll(poll_id))
+    }
+
+    /// Options a user has picked in a poll (empty if they haven't voted)
+    pub fn get_poll_vote(env: Env, poll_id: u64, voter: Address) -> Vec<u32> {
+        env.storage()
+            .persistent()
+            .get(&VoteKey::PollVote(poll_id, voter))
+            .unwrap_or(Vec::new(&env))
+    }
+
+    /// Whether a user can moderate the board (false if permissions can't be reached)
+    fn can_moderate(env: &Env, board_id: u64, user: &Address) -> bool {
+        use soroban_sdk::{IntoVal, Val};
+        let Some(permissions) = env
+            .storage()
+            .instance()
+            .get::<_, Address>(&VoteKey::Permissions)
+        else {
+            return false;
+        };
+        let args: Vec<Val> =
+            Vec::from_array(env, [board_id.into_val(env), user.clone().into_val(env)]);
+        env.try_invoke_contract::<PermissionSet, soroban_sdk::Error>(
+            &permissions,
+            &Symbol::new(env, "get_permissions"),
+            args,
+        )
+        .ok()
+        .and_then(|r| r.ok())
+        .is_some_and(|perms| perms.can_moderate)
+    }
+
     /// Upgrade the contract WASM (admin only via registry)
     pub fn upgrade(env: Env, new_wasm_hash: soroban_sdk::BytesN<32>) {
         // Only allow upgrade from registry
@@ -551,8 +1321,8 @@ impl BoardsVoting {
 #[cfg(test)]
 mod test {
     use super::*;
-    use soroban_sdk::testutils::{Address as _, Ledger as _};
-    use soroban_sdk::Env;
+    use soroban_sdk::testutils::{Address as _, Events as _, Ledger as _};
+    use soroban_sdk::{Env, IntoVal, String};
 
     fn setup_contract(env: &Env) -> (Address, BoardsVotingClient<'_>) {
         let contract_id = env.register(BoardsVoting, ());
@@ -722,6 +1492,23 @@ mod test {
         assert_eq!(vote, VoteDirection::Up);
     }
 
+    #[test]
+    fn test_get_thread_tallies_batch() {
+        let env = Env::default();
+        env.mock_all_auths();
+
+        let (_contract_id, client) = setup_contract(&env);
+        let voter = Address::generate(&env);
+        client.vote_thread(&0, &1, &VoteDirection::Up, &voter);
+        client.vote_thread(&0, &3, &VoteDirection::Down, &voter);
+
+        let tallies = client.get_thread_tallies(&0, &Vec::from_array(&env, [3, 2, 1]));
+        assert_eq!(tallies.len(), 3);
+        assert_eq!(tallies.get(0).unwrap().downvotes, 1);
+        assert_eq!(tallies.get(1).unwrap().score, 0);
+        assert_eq!(tallies.get(2).unwrap().upvotes, 1);
+    }
+
     #[test]
     fn test_get_tally_no_votes() {
         let env = Env::default();
@@ -1054,4 +1841,568 @@ mod test {
         assert_eq!(tally.downvotes, 0);
         assert_eq!(tally.score, 0);
     }
+
+    /// Permissions stub that makes every caller a board admin, with settable roles
+    #[contract]
+    pub struct MockPermissions;
+
+    #[contractimpl]
+    impl MockPermissions {
+        pub fn get_permissions(_env: Env, _board_id: u64, _user: Address) -> PermissionSet {
+            PermissionSet {
+                role: Role::Admin,
+                can_view: true,
+                can_post: true,
+                can_moderate: true,
+                can_admin: true,
+                is_banned: false,
+            }
+        }
+
+        pub fn set_role(env: Env, user: Address, role: Role) {
+            env.storage().instance().set(&user, &role);
+        }
+
+        pub fn get_role(env: Env, _board_id: u64, user: Address) -> Role {
+            env.storage().instance().get(&user).unwrap_or(Role::Guest)
+        }
+    }
+
+    #[test]
+    fn test_react_toggles_and_counts() {
+        let env = Env::default();
+        env.mock_all_auths();
+
+        let (_contract_id, client) = setup_contract(&env);
+        let thumbsup = Symbol::new(&env, "thumbsup");
+        let alice = Address::generate(&env);
+        let bob = Address::generate(&env);
+
+        assert!(client.react_thread(&0, &1, &thumbsup, &alice));
+        assert!(client.react(&0, &1, &None, &thumbsup, &bob));
+        assert!(client.react_reply(&0, &1, &4, &thumbsup, &bob));
+
+        let reactions = client.get_reactions(&0, &1, &None);
+        assert_eq!(reactions.len(), 4);
+        assert_eq!(reactions.get(0).unwrap(), (thumbsup.clone(), 2));
+        assert_eq!(reactions.get(1).unwrap(), (Symbol::new(&env, "tada"), 0));
+
+        // Reacting again removes the reaction
+        assert!(!client.react_thread(&0, &1, &thumbsup, &alice));
+        assert_eq!(client.get_reactions(&0, &1, &None).get(0).unwrap().1, 1);
+        assert_eq!(client.get_user_reactions(&0, &1, &None, &alice).len(), 0);
+        assert_eq!(client.get_user_reactions(&0, &1, &Some(4), &bob).len(), 1);
+
+        // Reactions never touch the vote tally
+        assert_eq!(client.get_thread_tally(&0, &1).score, 0);
+    }
+
+    #[test]
+    fn test_reply_reactions_in_batches() {
+        let env = Env::default();
+        env.mock_all_auths();
+
+        let (_contract_id, client) = setup_contract(&env);
+        let thumbsup = Symbol::new(&env, "thumbsup");
+        let tada = Symbol::new(&env, "tada");
+        let alice = Address::generate(&env);
+        let bob = Address::generate(&env);
+
+        client.react_reply(&0, &1, &4, &thumbsup, &alice);
+        client.react_reply(&0, &1, &4, &thumbsup, &bob);
+        client.react_reply(&0, &1, &6, &tada, &alice);
+
+        // One entry per requested reply, in the order given, matching get_reactions
+        let ids = Vec::from_array(&env, [6, 5, 4]);
+        let counts = client.get_reply_reactions(&0, &1, &ids);
+        assert_eq!(counts.len(), 3);
+        assert_eq!(
+            counts.get(0).unwrap(),
+            client.get_reactions(&0, &1, &Some(6))
+        );
+        assert_eq!(
+            counts.get(1).unwrap(),
+            client.get_reactions(&0, &1, &Some(5))
+        );
+        assert_eq!(
+            counts.get(2).unwrap().get(0).unwrap(),
+            (thumbsup.clone(), 2)
+        );
+
+        let mine = client.get_user_reply_reactions(&0, &1, &ids, &alice);
+        assert_eq!(mine.get(0).unwrap(), Vec::from_array(&env, [tada]));
+        assert!(mine.get(1).unwrap().is_empty());
+        assert_eq!(mine.get(2).unwrap(), Vec::from_array(&env, [thumbsup]));
+    }
+
+    #[test]
+    #[should_panic(expected = "Reaction not allowed on this board")]
+    fn test_react_outside_allowlist() {
+        let env = Env::default();
+        env.mock_all_auths();
+
+        let (_contract_id, client) = setup_contract(&env);
+        client.react_thread(
+            &0,
+            &1,
+            &Symbol::new(&env, "rocket"),
+            &Address::generate(&env),
+        );
+    }
+
+    #[test]
+    fn test_set_reaction_allowlist() {
+        let env = Env::default();
+        env.mock_all_auths();
+
+        let client = BoardsVotingClient::new(&env, &env.register(BoardsVoting, ()));
+        client.init(&Address::generate(&env), &env.register(MockPermissions, ()));
+
+        let rocket = Symbol::new(&env, "rocket");
+        let allowlist = Vec::from_array(&env, [rocket.clone(), Symbol::new(&env, "sad")]);
+        client.set_reaction_allowlist(&0, &allowlist, &Address::generate(&env));
+        assert_eq!(client.get_reaction_allowlist(&0), allowlist);
+        // Other boards keep the defaults
+        assert_eq!(client.get_reaction_allowlist(&1).len(), 4);
+
+        assert!(client.react_thread(&0, &1, &rocket, &Address::generate(&env)));
+    }
+
+    #[test]
+    #[should_panic(expected = "Unknown reaction")]
+    fn test_set_reaction_allowlist_rejects_unknown() {
+        let env = Env::default();
+        env.mock_all_auths();
+
+        let client = BoardsVotingClient::new(&env, &env.register(BoardsVoting, ()));
+        client.init(&Address::generate(&env), &env.register(MockPermissions, ()));
+
+        let allowlist = Vec::from_array(&env, [Symbol::new(&env, "skull")]);
+        client.set_reaction_allowlist(&0, &allowlist, &Address::generate(&env));
+    }
+
+    /// Registry, board and content stub: resolves aliases and records content authors
+    #[contract]
+    pub struct MockContracts;
+
+    #[contractimpl]
+    impl MockContracts {
+        pub fn get_contract_by_alias(env: Env, _alias: Symbol) -> Option<Address> {
+            Some(env.current_contract_address())
+        }
+
+        pub fn set_author(env: Env, thread_id: u64, reply_id: Option<u64>, author: Address) {
+            env.storage()
+                .instance()
+                .set(&(thread_id, reply_id), &author);
+        }
+
+        pub fn get_thread_title_and_author(
+            env: Env,
+            _board_id: u64,
+            thread_id: u64,
+        ) -> Option<(String, Address)> {
+            let author: Option<Address> = env.storage().instance().get(&(thread_id, None::<u64>));
+            author.map(|author| (String::from_str(&env, "Title"), author))
+        }
+
+        pub fn get_reply_author(
+            env: Env,
+            _board_id: u64,
+            thread_id: u64,
+            reply_id: u64,
+        ) -> Option<Address> {
+            env.storage().instance().get(&(thread_id, Some(reply_id)))
+        }
+
+        pub fn set_locked(env: Env, thread_id: u64, locked: bool) {
+            env.storage().instance().set(&thread_id, &locked);
+        }
+
+        pub fn is_thread_votable(env: Env, _board_id: u64, thread_id: u64) -> bool {
+            !env.storage().instance().get(&thread_id).unwrap_or(false)
+        }
+    }
+
+    fn setup_with_authors(env: &Env) -> (BoardsVotingClient<'_>, MockContractsClient<'_>) {
+        let contracts = MockContractsClient::new(env, &env.register(MockContracts, ()));
+        let client = BoardsVotingClient::new(env, &env.register(BoardsVoting, ()));
+        client.init(&contracts.address, &Address::generate(env));
+        (client, contracts)
+    }
+
+    #[test]
+    #[should_panic(expected = "Cannot vote on your own content")]
+    fn test_author_cannot_vote_on_own_thread() {
+        let env = Env::default();
+        env.mock_all_auths();
+
+        let (client, contracts) = setup_with_authors(&env);
+        let author = Address::generate(&env);
+        contracts.set_author(&1, &None, &author);
+
+        client.vote_thread(&0, &1, &VoteDirection::Up, &author);
+    }
+
+    #[test]
+    #[should_panic(expected = "Cannot vote on your own content")]
+    fn test_author_cannot_vote_on_own_reply() {
+        let env = Env::default();
+        env.mock_all_auths();
+
+        let (client, contracts) = setup_with_authors(&env);
+        let author = Address::generate(&env);
+        contracts.set_author(&1, &Some(2), &author);
+
+        client.vote_reply(&0, &1, &2, &VoteDirection::Down, &author);
+    }
+
+    #[test]
+    fn test_non_author_votes_and_author_can_withdraw() {
+        let env = Env::default();
+        env.mock_all_auths();
+
+        let (client, contracts) = setup_with_authors(&env);
+        let author = Address::generate(&env);
+        let voter = Address::generate(&env);
+        contracts.set_author(&1, &None, &author);
+        contracts.set_author(&1, &Some(2), &author);
+
+        assert_eq!(
+            client.vote_thread(&0, &1, &VoteDirection::Up, &voter).score,
+            1
+        );
+        assert_eq!(
+            client
+                .vote_reply(&0, &1, &2, &VoteDirection::Up, &voter)
+                .score,
+            1
+        );
+
+        // A vote cast before authorship was known can still be toggled off
+        client.vote_thread(&0, &3, &VoteDirection::Up, &author);
+        contracts.set_author(&3, &None, &author);
+        assert_eq!(
+            client
+                .vote_thread(&0, &3, &VoteDirection::None, &author)
+                .score,
+            0
+        );
+    }
+
+    #[test]
+    fn test_vote_change_emits_event() {
+        let env = Env::default();
+        env.mock_all_auths();
+
+        let (client, _) = setup_with_authors(&env);
+        let voter = Address::generate(&env);
+
+        client.vote_reply(&0, &1, &2, &VoteDirection::Up, &voter);
+        let (contract, topics, data) = env.events().all().last().unwrap();
+        assert_eq!(contract, client.address);
+        let topic: Symbol = topics.get(0).unwrap().into_val(&env);
+        assert_eq!(topic, Symbol::new(&env, "vote_changed"));
+        let change: (u64, Option<u64>, Address, VoteDirection, VoteDirection) = data.into_val(&env);
+        assert_eq!(
+            change,
+            (
+                1,
+                Some(2),
+                voter.clone(),
+                VoteDirection::None,
+                VoteDirection::Up
+            )
+        );
+
+        client.vote_reply(&0, &1, &2, &VoteDirection::Down, &voter);
+        let (_, _, data) = env.events().all().last().unwrap();
+        let change: (u64, Option<u64>, Address, VoteDirection, VoteDirection) = data.into_val(&env);
+        assert_eq!(change.3, VoteDirection::Up);
+        assert_eq!(change.4, VoteDirection::Down);
+    }
+
+    #[test]
+    fn test_votes_frozen_while_thread_locked() {
+        let env = Env::default();
+        env.mock_all_auths();
+
+        let (client, contracts) = setup_with_authors(&env);
+        let voter = Address::generate(&env);
+        let other = Address::generate(&env);
+        assert_eq!(
+            client.vote_thread(&0, &1, &VoteDirection::Up, &voter).score,
+            1
+        );
+
+        contracts.set_locked(&1, &true);
+        let frozen = Some(Ok(soroban_sdk::Error::from(VotingError::ThreadNotVotable)));
+        let up = VoteDirection::Up;
+        assert_eq!(client.try_vote_thread(&0, &1, &up, &other).err(), frozen);
+        assert_eq!(
+            client
+                .try_vote_thread(&0, &1, &VoteDirection::None, &voter)
+                .err(),
+            frozen
+        );
+        assert_eq!(client.try_vote_reply(&0, &1, &2, &up, &other).err(), frozen);
+
+        // The tally is frozen, not cleared
+        assert_eq!(client.get_thread_tally(&0, &1).score, 1);
+        assert_eq!(
+            client.get_user_thread_vote(&0, &1, &voter),
+            VoteDirection::Up
+        );
+
+        contracts.set_locked(&1, &false);
+        assert_eq!(
+            client.vote_thread(&0, &1, &VoteDirection::Up, &other).score,
+            2
+        );
+        assert_eq!(
+            client
+                .vote_reply(&0, &1, &2, &VoteDirection::Up, &other)
+                .score,
+            1
+        );
+    }
+
+    #[test]
+    fn test_votes_credit_author_karma() {
+        let env = Env::default();
+        env.mock_all_auths();
+
+        let (client, contracts) = setup_with_authors(&env);
+        let author = Address::generate(&env);
+        let voter = Address::generate(&env);
+        contracts.set_author(&1, &None, &author);
+        contracts.set_author(&1, &Some(2), &author);
+
+        client.vote_thread(&0, &1, &VoteDirection::Up, &voter);
+        client.vote_reply(&0, &1, &2, &VoteDirection::Up, &voter);
+        assert_eq!(client.get_board_karma(&0, &author), 2);
+        assert_eq!(client.get_total_karma(&author), 2);
+
+        // Switching a vote reverses its effect before applying the new one
+        client.vote_reply(&0, &1, &2, &VoteDirection::Down, &voter);
+        assert_eq!(client.get_board_karma(&0, &author), 0);
+        assert_eq!(client.get_board_karma(&0, &voter), 0);
+    }
+
+    #[test]
+    fn test_top_karma_ranks_and_drops_users() {
+        let env = Env::default();
+        env.mock_all_auths();
+
+        let (_contract_id, client) = setup_contract(&env);
+        let updater = Address::generate(&env);
+
+        // Fill the leaderboard with karma 1..=TOP_KARMA_SIZE
+        let mut users = Vec::new(&env);
+        for karma in 1..=TOP_KARMA_SIZE {
+            let user = Address::generate(&env);
+            client.update_karma(&0, &user, &(karma as i64), &updater);
+            users.push_back(user);
+        }
+        let top = client.top_karma(&0, &3);
+        assert_eq!(top.len(), 3);
+        assert_eq!(
+            top.get(0).unwrap(),
+            (users.last().unwrap(), TOP_KARMA_SIZE as i64)
+        );
+        assert_eq!(client.top_karma(&0, &100).len(), TOP_KARMA_SIZE);
+
+        // A newcomer only gets in by beating the lowest entry, pushing it out
+        let newcomer = Address::generate(&env);
+        client.update_karma(&0, &newcomer, &1, &updater);
+        assert!(!client
+            .top_karma(&0, &100)
+            .iter()
+            .any(|(user, _)| user == newcomer));
+        client.update_karma(&0, &newcomer, &1, &updater);
+        let top = client.top_karma(&0, &100);
+        assert_eq!(top.len(), TOP_KARMA_SIZE);
+        assert!(top.iter().any(|(user, _)| user == newcomer));
+        assert!(!top.iter().any(|(user, _)| user == users.get(0).unwrap()));
+
+        // The leader moves down when downvoted and drops out below the rest of the list
+        let leader = users.last().unwrap();
+        client.update_karma(&0, &leader, &-10, &updater);
+        let top = client.top_karma(&0, &100);
+        assert_eq!(
+            top.get(10).unwrap(),
+            (leader.clone(), TOP_KARMA_SIZE as i64 - 10)
+        );
+        client.update_karma(&0, &leader, &-(TOP_KARMA_SIZE as i64), &updater);
+        let top = client.top_karma(&0, &100);
+        assert_eq!(top.len(), TOP_KARMA_SIZE - 1);
+        assert!(!top.iter().any(|(user, _)| user == leader));
+
+        // Other boards keep their own leaderboard
+        assert_eq!(client.top_karma(&1, &10).len(), 0);
+    }
+
+    #[test]
+    fn test_vote_weights_follow_role_and_survive_config_changes() {
+        let env = Env::default();
+        env.mock_all_auths();
+
+        let perms = MockPermissionsClient::new(&env, &env.register(MockPermissions, ()));
+        let client = BoardsVotingClient::new(&env, &env.register(BoardsVoting, ()));
+        client.init(&Address::generate(&env), &perms.address);
+        let admin = Address::generate(&env);
+        let guest = Address::generate(&env);
+        let member = Address::generate(&env);
+        let moderator = Address::generate(&env);
+        perms.set_role(&member, &Role::Member);
+        perms.set_role(&moderator, &Role::Moderator);
+
+        // Boards without stored weights count every vote once
+        assert_eq!(client.get_vote_weights(&0), (1, 1));
+        client.set_vote_weights(&0, &2, &5, &admin);
+
+        client.vote_thread(&0, &1, &VoteDirection::Up, &guest);
+        client.vote_thread(&0, &1, &VoteDirection::Up, &member);
+        let tally = client.vote_thread(&0, &1, &VoteDirection::Down, &moderator);
+        assert_eq!((tally.upvotes, tally.downvotes, tally.score), (3, 5, -2));
+
+        // Votes are taken back at the weight they were cast with, not the current one
+        client.set_vote_weights(&0, &1, &1, &admin);
+
+        let tally = client.vote_thread(&0, &1, &VoteDirection::None, &moderator);
+        assert_eq!((tally.upvotes, tally.downvotes, tally.score), (3, 0, 3));
+        let tally = client.vote_thread(&0, &1, &VoteDirection::Down, &member);
+        assert_eq!((tally.upvotes, tally.downvotes, tally.score), (1, 1, 0));
+        let tally = client.vote_thread(&0, &1, &VoteDirection::None, &guest);
+        assert_eq!((tally.upvotes, tally.downvotes, tally.score), (0, 1, -1));
+
+        // Reply votes carry their weight the same way
+        client.set_vote_weights(&0, &1, &3, &admin);
+        assert_eq!(
+            client
+                .vote_reply(&0, &1, &2, &VoteDirection::Up, &moderator)
+                .score,
+            3
+        );
+        client.set_vote_weights(&0, &1, &1, &admin);
+        assert_eq!(
+            client
+                .vote_reply(&0, &1, &2, &VoteDirection::None, &moderator)
+                .score,
+            0
+        );
+    }
+
+    #[test]
+    #[should_panic(expected = "Vote weights must be at least 1")]
+    fn test_vote_weights_must_be_positive() {
+        let env = Env::default();
+        env.mock_all_auths();
+
+        let client = BoardsVotingClient::new(&env, &env.register(BoardsVoting, ()));
+        client.init(&Address::generate(&env), &env.register(MockPermissions, ()));
+
+        client.set_vote_weights(&0, &0, &1, &Address::generate(&env));
+    }
+
+    fn create_test_poll(
+        env: &Env,
+        client: &BoardsVotingClient<'_>,
+        multi: bool,
+        closes_at: Option<u64>,
+        author: &Address,
+    ) -> u64 {
+        let options = Vec::from_array(
+            env,
+            [
+                String::from_str(env, "Red"),
+                String::from_str(env, "Green"),
+                String::from_str(env, "Blue"),
+            ],
+        );
+        let question = String::from_str(env, "Favourite colour?");
+        client.create_poll(&0, &1, &question, &options, &multi, &closes_at, author)
+    }
+
+    #[test]
+    fn test_poll_single_choice_counts_one_vote_per_user() {
+        let env = Env::default();
+        env.mock_all_auths();
+
+        let (client, contracts) = setup_with_authors(&env);
+        let author = Address::generate(&env);
+        contracts.set_author(&1, &None, &author);
+        let poll_id = create_test_poll(&env, &client, false, None, &author);
+        let voter = Address::generate(&env);
+
+        client.vote_poll(&poll_id, &0, &voter);
+        // Voting for the same option again is a no-op
+        let poll = client.vote_poll(&poll_id, &0, &voter);
+        assert_eq!(poll.counts, Vec::from_array(&env, [1, 0, 0]));
+        assert_eq!(poll.voters, 1);
+
+        // A different option moves the vote
+        let poll = client.vote_poll(&poll_id, &2, &voter);
+        assert_eq!(poll.counts, Vec::from_array(&env, [0, 0, 1]));
+        assert_eq!(poll.voters, 1);
+        assert_eq!(
+            client.get_poll_vote(&poll_id, &voter),
+            Vec::from_array(&env, [2])
+        );
+        assert_eq!(client.get_poll(&0, &1).unwrap().counts, poll.counts);
+    }
+
+    #[test]
+    fn test_poll_multi_select_toggles_options() {
+        let env = Env::default();
+        env.mock_all_auths();
+
+        let (client, contracts) = setup_with_authors(&env);
+        let author = Address::generate(&env);
+        contracts.set_author(&1, &None, &author);
+        let poll_id = create_test_poll(&env, &client, true, None, &author);
+        let voter = Address::generate(&env);
+
+        client.vote_poll(&poll_id, &0, &voter);
+        let poll = client.vote_poll(&poll_id, &1, &voter);
+        assert_eq!(poll.counts, Vec::from_array(&env, [1, 1, 0]));
+        assert_eq!(poll.voters, 1);
+
+        // Picking an option again withdraws it
+        client.vote_poll(&poll_id, &0, &voter);
+        let poll = client.vote_poll(&poll_id, &1, &voter);
+        assert_eq!(poll.counts, Vec::from_array(&env, [0, 0, 0]));
+        assert_eq!(poll.voters, 0);
+        assert!(client.get_poll_vote(&poll_id, &voter).is_empty());
+    }
+
+    #[test]
+    #[should_panic(expected = "Poll is closed")]
+    fn test_poll_rejects_votes_after_close() {
+        let env = Env::default();
+        env.mock_all_auths();
+
+        let (client, contracts) = setup_with_authors(&env);
+        let author = Address::generate(&env);
+        contracts.set_author(&1, &None, &author);
+        env.ledger().set_timestamp(1_000);
+        let poll_id = create_test_poll(&env, &client, false, Some(2_000), &author);
+        let voter = Address::generate(&env);
+
+        env.ledger().set_timestamp(1_999);
+        client.vote_poll(&poll_id, &1, &voter);
+        env.ledger().set_timestamp(2_000);
+        client.vote_poll(&poll_id, &0, &voter);
+    }
+
+    #[test]
+    #[should_panic(expected = "Only the thread author or a moderator can add a poll")]
+    fn test_poll_creation_limited_to_author() {
+        let env = Env::default();
+        env.mock_all_auths();
+
+        let (client, contracts) = setup_with_authors(&env);
+        contracts.set_author(&1, &None, &Address::generate(&env));
+        create_test_poll(&env, &client, false, None, &Address::generate(&env));
+    }
 }